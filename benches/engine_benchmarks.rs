@@ -434,6 +434,7 @@ fn bench_policy_evaluation(c: &mut Criterion) {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     // Create a simple policy config for benchmarking
@@ -534,6 +535,7 @@ fn bench_full_scan_pipeline(c: &mut Criterion) {
                 breakdown: None,
                 hourly: None,
                 daily: None,
+                provenance: None,
             };
 
             // Simple policy evaluation
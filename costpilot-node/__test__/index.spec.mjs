@@ -0,0 +1,43 @@
+import { test } from 'node:test'
+import assert from 'node:assert/strict'
+import { mkdtempSync, writeFileSync, existsSync } from 'node:fs'
+import { tmpdir } from 'node:os'
+import { join } from 'node:path'
+import { createRequire } from 'node:module'
+
+const { scan, report } = createRequire(import.meta.url)('../index.js')
+
+const dir = mkdtempSync(join(tmpdir(), 'costpilot-node-'))
+const plan = join(dir, 'plan.json')
+writeFileSync(
+  plan,
+  JSON.stringify({
+    format_version: '1.0',
+    resource_changes: [
+      {
+        address: 'aws_instance.web',
+        type: 'aws_instance',
+        name: 'web',
+        change: { actions: ['create'], before: null, after: { instance_type: 'm5.xlarge' } },
+      },
+    ],
+  }),
+)
+
+test('scan resolves with estimates for every change', async () => {
+  const result = await scan(plan)
+  assert.equal(result.changes.length, 1)
+  assert.equal(result.estimates.length, 1)
+  assert.ok(result.total_monthly > 0)
+  assert.equal(result.graph, null)
+})
+
+test('report writes the html bundle', async () => {
+  const written = await report(plan, { html: join(dir, 'report') })
+  assert.ok(existsSync(written.html))
+  assert.equal(written.pdf, undefined)
+})
+
+test('errors lead with the CP code', async () => {
+  await assert.rejects(scan(join(dir, 'missing.json')), /^Error: CP\d{4} /)
+})
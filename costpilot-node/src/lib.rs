@@ -0,0 +1,275 @@
+// Node.js bindings for CostPilot scans and reports
+// Each call runs the scan on the libuv thread pool and resolves a Promise
+// with plain objects shaped like the CLI's JSON output
+
+use costpilot::artifact::{parse_artifact, ArtifactNormalizer};
+use costpilot::cli::report::{trend_section, DEFAULT_SNAPSHOTS_DIR};
+use costpilot::edition::{detect_edition, EditionContext};
+use costpilot::engines::detection::DetectionEngine;
+use costpilot::engines::mapping::GraphConfig;
+use costpilot::engines::pipeline::{ResourceArena, ScanPipeline, ScanPipelineOutput};
+use costpilot::engines::policy::{PolicyEngine, PolicyLoader, PolicyResult};
+use costpilot::engines::prediction::PredictionEngine;
+use costpilot::engines::report::{HtmlReportBundle, PdfSummary, ReportData};
+use costpilot::engines::shared::models::{CostEstimate, ResourceChange};
+use costpilot::errors::CostPilotError;
+use costpilot::i18n::Locale;
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, JsUnknown, Task};
+use napi_derive::napi;
+use std::path::{Path, PathBuf};
+
+/// Analyses to run besides prediction
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Policy file evaluated against the changes
+    pub policy: Option<String>,
+    /// Explain each detection
+    pub explain: Option<bool>,
+    /// Build the dependency graph
+    pub mapping: Option<bool>,
+    /// Build the module/service/environment grouping
+    pub grouping: Option<bool>,
+}
+
+/// Where to write the report, plus the scan behind it
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    /// Directory for the HTML report pages (created if missing)
+    pub html: Option<String>,
+    /// File for the PDF executive summary
+    pub pdf: Option<String>,
+    /// Trend snapshot directory (default: .costpilot/snapshots)
+    pub snapshots: Option<String>,
+    /// Report title (default: "CostPilot report: <input>")
+    pub title: Option<String>,
+    /// Policy file evaluated against the changes
+    pub policy: Option<String>,
+}
+
+/// Files written by `report`
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct ReportOutput {
+    /// Path of the HTML report's index page
+    pub html: Option<String>,
+    /// Path of the PDF executive summary
+    pub pdf: Option<String>,
+}
+
+/// Everything one scan produced, owned so reports can borrow from it
+struct Analysis {
+    arena: ResourceArena,
+    output: ScanPipelineOutput,
+    policy_result: Option<PolicyResult>,
+    edition: EditionContext,
+}
+
+impl Analysis {
+    fn run(input: &Path, options: &ScanOptions) -> Result<Self, CostPilotError> {
+        let edition = detect_edition().unwrap_or_else(|_| EditionContext::free());
+        let changes = load_changes(input)?;
+        let estimates = PredictionEngine::new_with_edition(&edition)?.predict(&changes)?;
+        let arena = ResourceArena::new(changes, estimates);
+
+        let graph_config = GraphConfig {
+            max_depth: if edition.is_free() { Some(1) } else { None },
+            ..GraphConfig::default()
+        };
+        let output = ScanPipeline::new()
+            .with_graph_config(graph_config)
+            .with_explain(options.explain.unwrap_or(false))
+            .with_mapping(options.mapping.unwrap_or(false))
+            .with_grouping(options.grouping.unwrap_or(false))
+            .run(&arena)?;
+
+        let policy_result = match &options.policy {
+            Some(path) => {
+                let config = PolicyLoader::load_from_file(Path::new(path))?;
+                let total = total_estimate(arena.total_monthly_cost());
+                Some(PolicyEngine::new(config, &edition).evaluate(arena.changes(), &total))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            arena,
+            output,
+            policy_result,
+            edition,
+        })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "edition": if self.edition.is_premium() { "premium" } else { "free" },
+            "total_monthly": self.arena.total_monthly_cost(),
+            "changes": self.arena.changes(),
+            "estimates": self.arena.estimates(),
+            "detections": self.output.detections,
+            "explanations": self.output.explanations,
+            "graph": self.output.graph,
+            "grouping": self.output.grouping,
+            "policy_result": self.policy_result,
+        })
+    }
+}
+
+/// Resource changes from a Terraform plan JSON or a CloudFormation/CDK,
+/// SAM, Serverless or Kubernetes artifact
+fn load_changes(input: &Path) -> Result<Vec<ResourceChange>, CostPilotError> {
+    let content = std::fs::read_to_string(input).map_err(|e| {
+        CostPilotError::io_error(format!("Failed to read {}: {}", input.display(), e))
+    })?;
+    let is_plan = serde_json::from_str::<serde_json::Value>(&content)
+        .map(|value| value.get("resource_changes").is_some())
+        .unwrap_or(false);
+    if is_plan {
+        return DetectionEngine::new().detect_from_terraform_json(&content);
+    }
+    let artifact = parse_artifact(&content, &input.to_string_lossy())?;
+    Ok(ArtifactNormalizer::normalize(&artifact).to_resource_changes())
+}
+
+fn total_estimate(monthly_cost: f64) -> CostEstimate {
+    CostEstimate {
+        resource_id: "total".to_string(),
+        monthly_cost,
+        prediction_interval_low: 0.0,
+        prediction_interval_high: 0.0,
+        confidence_score: 0.0,
+        heuristic_reference: None,
+        cold_start_inference: false,
+        one_time: None,
+        breakdown: None,
+        hourly: None,
+        daily: None,
+        provenance: None,
+    }
+}
+
+/// The CP code leads the message, e.g. `CP1023 [E_FILE_NOT_FOUND] ...`
+fn to_napi_err(err: CostPilotError) -> napi::Error {
+    napi::Error::from_reason(err.to_string())
+}
+
+pub struct ScanTask {
+    input: PathBuf,
+    options: ScanOptions,
+}
+
+impl Task for ScanTask {
+    type Output = serde_json::Value;
+    type JsValue = JsUnknown;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        Analysis::run(&self.input, &self.options)
+            .map(|analysis| analysis.to_json())
+            .map_err(to_napi_err)
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        env.to_js_value(&output)
+    }
+}
+
+pub struct ReportTask {
+    input: PathBuf,
+    options: ReportOptions,
+}
+
+impl ReportTask {
+    fn write(&self) -> Result<ReportOutput, CostPilotError> {
+        if self.options.html.is_none() && self.options.pdf.is_none() {
+            return Err(CostPilotError::validation_error(
+                "report needs an `html` directory or a `pdf` file",
+            ));
+        }
+        let scan = ScanOptions {
+            policy: self.options.policy.clone(),
+            explain: Some(true),
+            mapping: Some(true),
+            grouping: Some(true),
+        };
+        let analysis = Analysis::run(&self.input, &scan)?;
+        let snapshots = self
+            .options
+            .snapshots
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SNAPSHOTS_DIR.to_string());
+        let (trend, trend_totals) = trend_section(Path::new(&snapshots), &analysis.edition);
+        let trial = analysis.edition.trial_watermark();
+
+        let data = ReportData {
+            title: self
+                .options
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("CostPilot report: {}", self.input.display())),
+            generated_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            changes: analysis.arena.changes(),
+            estimates: analysis.arena.estimates(),
+            detections: &analysis.output.detections,
+            explanations: &analysis.output.explanations,
+            graph: analysis.output.graph.as_ref(),
+            grouping: analysis.output.grouping.as_ref(),
+            policy_result: analysis.policy_result.as_ref(),
+            slo_result: None,
+            trend,
+            trend_totals,
+            total_monthly: analysis.arena.total_monthly_cost(),
+            trial: trial.as_ref(),
+            locale: Locale::current(),
+        };
+
+        let mut written = ReportOutput::default();
+        if let Some(dir) = &self.options.html {
+            let index = HtmlReportBundle::write(&data, Path::new(dir))?;
+            written.html = Some(index.display().to_string());
+        }
+        if let Some(path) = &self.options.pdf {
+            PdfSummary::write(&data, Path::new(path))?;
+            written.pdf = Some(path.clone());
+        }
+        Ok(written)
+    }
+}
+
+impl Task for ReportTask {
+    type Output = ReportOutput;
+    type JsValue = ReportOutput;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        self.write().map_err(to_napi_err)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// Scan a Terraform plan JSON or IaC artifact file
+#[napi]
+pub fn scan(input: String, options: Option<ScanOptions>) -> AsyncTask<ScanTask> {
+    AsyncTask::new(ScanTask {
+        input: PathBuf::from(input),
+        options: options.unwrap_or_default(),
+    })
+}
+
+/// Scan a file and write the HTML report and/or PDF executive summary
+#[napi]
+pub fn report(input: String, options: ReportOptions) -> AsyncTask<ReportTask> {
+    AsyncTask::new(ReportTask {
+        input: PathBuf::from(input),
+        options,
+    })
+}
+
+/// Version of the bundled CostPilot engines
+#[napi]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
@@ -0,0 +1,255 @@
+// Python bindings for the CostPilot engines
+// Thin conversion layer: arguments and results cross the boundary as plain
+// dicts and lists (via the stdlib json module), engines run natively
+
+use costpilot::artifact::{parse_artifact as parse, ArtifactNormalizer};
+use costpilot::edition::{detect_edition, EditionContext};
+use costpilot::engines::detection::DetectionEngine;
+use costpilot::engines::mapping::MappingEngine as NativeMappingEngine;
+use costpilot::engines::policy::{PolicyEngine as NativePolicyEngine, PolicyLoader};
+use costpilot::engines::prediction::PredictionEngine as NativePredictionEngine;
+use costpilot::engines::shared::models::{CostEstimate, ResourceChange};
+use costpilot::errors::CostPilotError as NativeError;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use std::path::Path;
+
+create_exception!(
+    costpilot,
+    CostPilotError,
+    PyException,
+    "Error raised by a CostPilot engine; args are (message, code, id)"
+);
+
+fn to_py_err(err: NativeError) -> PyErr {
+    CostPilotError::new_err((err.to_string(), err.code(), err.id.clone()))
+}
+
+/// Serialize `value` and rebuild it as Python objects
+fn to_py<T: serde::Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| to_py_err(NativeError::serialization_error(e.to_string())))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Rebuild Python objects (dicts, lists, scalars) as `T`
+fn from_py<T: serde::de::DeserializeOwned>(value: &Bound<'_, PyAny>, what: &str) -> PyResult<T> {
+    let json: String = value
+        .py()
+        .import("json")?
+        .call_method1("dumps", (value,))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(|e| {
+        to_py_err(NativeError::validation_error(format!(
+            "Invalid {}: {}",
+            what, e
+        )))
+    })
+}
+
+/// Licensed edition, as the CLI would detect it
+fn edition() -> EditionContext {
+    detect_edition().unwrap_or_else(|_| EditionContext::free())
+}
+
+/// Parse a CloudFormation/CDK, SAM, Serverless or Kubernetes artifact;
+/// `hint` is the file name used to detect the format
+#[pyfunction]
+#[pyo3(signature = (content, hint = ""))]
+fn parse_artifact(py: Python<'_>, content: &str, hint: &str) -> PyResult<PyObject> {
+    let artifact = parse(content, hint)
+        .map_err(NativeError::from)
+        .map_err(to_py_err)?;
+    to_py(py, &artifact)
+}
+
+/// Resource changes from a Terraform plan JSON or any artifact
+/// `parse_artifact` understands
+#[pyfunction]
+#[pyo3(signature = (content, hint = ""))]
+fn resource_changes(py: Python<'_>, content: &str, hint: &str) -> PyResult<PyObject> {
+    let is_plan = serde_json::from_str::<serde_json::Value>(content)
+        .map(|value| value.get("resource_changes").is_some())
+        .unwrap_or(false);
+    let changes = if is_plan {
+        DetectionEngine::new()
+            .detect_from_terraform_json(content)
+            .map_err(to_py_err)?
+    } else {
+        let artifact = parse(content, hint)
+            .map_err(NativeError::from)
+            .map_err(to_py_err)?;
+        ArtifactNormalizer::normalize(&artifact).to_resource_changes()
+    };
+    to_py(py, &changes)
+}
+
+/// Monthly cost prediction from the bundled or a custom heuristics file
+#[pyclass(name = "PredictionEngine", module = "costpilot", unsendable)]
+struct PredictionEngine {
+    inner: NativePredictionEngine,
+}
+
+#[pymethods]
+impl PredictionEngine {
+    #[new]
+    #[pyo3(signature = (heuristics = None))]
+    fn new(heuristics: Option<&str>) -> PyResult<Self> {
+        let inner = match heuristics {
+            Some(path) => NativePredictionEngine::from_file(Path::new(path)),
+            None => NativePredictionEngine::new_with_edition(&edition()),
+        }
+        .map_err(to_py_err)?;
+        Ok(Self { inner })
+    }
+
+    /// One cost estimate dict per resource change
+    fn predict(&mut self, py: Python<'_>, changes: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let changes: Vec<ResourceChange> = from_py(changes, "resource changes")?;
+        let estimates = self.inner.predict(&changes).map_err(to_py_err)?;
+        to_py(py, &estimates)
+    }
+
+    /// Version of the heuristics in use
+    #[getter]
+    fn heuristics_version(&self) -> String {
+        self.inner.heuristics_version().to_string()
+    }
+}
+
+/// Dependency graph of resource changes
+#[pyclass(name = "MappingEngine", module = "costpilot", unsendable)]
+struct MappingEngine {
+    inner: NativeMappingEngine,
+}
+
+#[pymethods]
+impl MappingEngine {
+    #[new]
+    fn new() -> Self {
+        Self {
+            inner: NativeMappingEngine::new(&edition()),
+        }
+    }
+
+    /// Dependency graph as a dict of nodes and edges
+    fn build_graph(&mut self, py: Python<'_>, changes: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let changes: Vec<ResourceChange> = from_py(changes, "resource changes")?;
+        let graph = self.inner.build_graph(&changes).map_err(to_py_err)?;
+        to_py(py, &graph)
+    }
+
+    /// Dependency graph as a Mermaid diagram
+    fn mermaid(&mut self, changes: &Bound<'_, PyAny>) -> PyResult<String> {
+        let changes: Vec<ResourceChange> = from_py(changes, "resource changes")?;
+        self.inner.map_dependencies(&changes).map_err(to_py_err)
+    }
+}
+
+/// Budget and resource policy evaluation
+#[pyclass(name = "PolicyEngine", module = "costpilot", unsendable)]
+struct PolicyEngine {
+    inner: NativePolicyEngine,
+}
+
+#[pymethods]
+impl PolicyEngine {
+    /// `policy` is a policy file path or a dict in the same shape
+    #[new]
+    fn new(policy: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let config = match policy.extract::<String>() {
+            Ok(path) => PolicyLoader::load_from_file(Path::new(&path)).map_err(to_py_err)?,
+            Err(_) => from_py(policy, "policy")?,
+        };
+        Ok(Self {
+            inner: NativePolicyEngine::new(config, &edition()),
+        })
+    }
+
+    /// Violations and warnings for the changes; the budget total is the
+    /// sum of `estimates` when given, otherwise of each change's
+    /// `monthly_cost`
+    #[pyo3(signature = (changes, estimates = None))]
+    fn evaluate(
+        &self,
+        py: Python<'_>,
+        changes: &Bound<'_, PyAny>,
+        estimates: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyObject> {
+        let changes: Vec<ResourceChange> = from_py(changes, "resource changes")?;
+        let total_monthly = match estimates {
+            Some(estimates) => from_py::<Vec<CostEstimate>>(estimates, "cost estimates")?
+                .iter()
+                .map(|e| e.monthly_cost)
+                .sum(),
+            None => changes.iter().filter_map(|c| c.monthly_cost).sum(),
+        };
+        let total = CostEstimate {
+            resource_id: "total".to_string(),
+            monthly_cost: total_monthly,
+            prediction_interval_low: 0.0,
+            prediction_interval_high: 0.0,
+            confidence_score: 0.0,
+            heuristic_reference: None,
+            cold_start_inference: false,
+            one_time: None,
+            breakdown: None,
+            hourly: None,
+            daily: None,
+            provenance: None,
+        };
+        to_py(py, &self.inner.evaluate(&changes, &total))
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "costpilot")]
+fn costpilot_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add("CostPilotError", m.py().get_type::<CostPilotError>())?;
+    m.add_function(wrap_pyfunction!(parse_artifact, m)?)?;
+    m.add_function(wrap_pyfunction!(resource_changes, m)?)?;
+    m.add_class::<PredictionEngine>()?;
+    m.add_class::<MappingEngine>()?;
+    m.add_class::<PolicyEngine>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"{
+        "Resources": {
+            "Queue": { "Type": "AWS::SQS::Queue", "Properties": {} }
+        }
+    }"#;
+
+    #[test]
+    fn test_resource_changes_from_template() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let changes = resource_changes(py, TEMPLATE, "stack.template.json").unwrap();
+            let changes: Vec<ResourceChange> = from_py(changes.bind(py), "changes").unwrap();
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].resource_type, "aws_sqs_queue");
+        });
+    }
+
+    #[test]
+    fn test_engine_errors_raise_costpilot_error() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let err = parse_artifact(py, "not a template", "notes.txt").unwrap_err();
+            assert!(err.is_instance_of::<CostPilotError>(py));
+            let (message, code, _id): (String, String, String) =
+                err.value(py).getattr("args").unwrap().extract().unwrap();
+            assert!(code.starts_with("CP1"), "{}", code);
+            assert!(message.contains(&code));
+
+            let policy = py.eval(pyo3::ffi::c_str!("[1, 2]"), None, None).unwrap();
+            assert!(PolicyEngine::new(&policy).is_err());
+        });
+    }
+}
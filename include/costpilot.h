@@ -0,0 +1,37 @@
+/*
+ * CostPilot C ABI
+ *
+ * Link against the costpilot cdylib (libcostpilot.so, libcostpilot.dylib
+ * or costpilot.dll) built with `cargo build --release --lib`.
+ *
+ * Requests and responses are UTF-8 JSON documents; see docs/C_ABI.md for
+ * their fields. Functions may be called from any thread.
+ */
+
+#ifndef COSTPILOT_H
+#define COSTPILOT_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Scan the input described by the JSON request `json_in`.
+ *
+ * Returns the scan result, or {"error": {...}} carrying the same CP code as
+ * the CLI. The returned string is owned by the caller and must be released
+ * with costpilot_free. Returns NULL only when `json_in` is NULL.
+ */
+char *costpilot_scan(const char *json_in);
+
+/* Release a string returned by costpilot_scan. NULL is ignored. */
+void costpilot_free(char *ptr);
+
+/* Engine version, e.g. "1.0.1". Static; do not free. */
+const char *costpilot_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* COSTPILOT_H */
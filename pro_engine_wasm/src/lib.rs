@@ -1,202 +1,185 @@
 // ProEngine WASM module - Premium feature implementations
-// Standalone WASM library with no external dependencies
-
-/// Static memory buffer for output (4KB should be sufficient)
-static mut OUTPUT_BUFFER: [u8; 4096] = [0; 4096];
+// Thin ABI layer over the native engines in the costpilot crate
+
+use costpilot::pro_engine::{NativeProEngine, ProEngineExecutor, ProEngineRequest};
+
+/// Host/engine ABI version; bump when the calling convention changes.
+/// v2 passes data through `alloc`ed memory and length-prefixed regions.
+const ABI_VERSION: i32 = 2;
+
+/// Engine functions exported by this build
+const CAPABILITIES: &[&str] = &[
+    "predict",
+    "explain",
+    "autofix",
+    "mapdeep",
+    "trend",
+    "enforce",
+    "slo_enforce",
+];
+
+/// Host side of the ABI handshake
+#[derive(serde::Deserialize)]
+struct HandshakeOffer {
+    abi_version: u32,
+    min_abi_version: u32,
+}
 
-/// Simple JSON-like structures for WASM interface
-#[derive(serde::Serialize, serde::Deserialize)]
-struct AutofixRequest {
-    detections: Vec<String>,
-    changes: Vec<String>,
-    estimates: Vec<String>,
-    mode: String,
+/// Engine side of the ABI handshake
+#[derive(serde::Serialize)]
+struct HandshakeReply {
+    abi_version: i32,
+    capabilities: Vec<String>,
 }
 
-#[derive(serde::Serialize, serde::Deserialize)]
-struct AutofixResponse {
-    fixes: Vec<FixSnippet>,
+/// Report the ABI version implemented by this engine
+#[no_mangle]
+pub extern "C" fn costpilot_abi_version() -> i32 {
+    ABI_VERSION
 }
 
-#[derive(serde::Serialize, serde::Deserialize)]
-struct FixSnippet {
-    file_path: String,
-    line_start: usize,
-    line_end: usize,
-    content: String,
-    description: String,
+/// Allocate `size` bytes of guest memory for the host
+#[no_mangle]
+pub extern "C" fn alloc(size: i32) -> i32 {
+    let mut buffer: Vec<u8> = Vec::with_capacity(size.max(1) as usize);
+    let ptr = buffer.as_mut_ptr();
+    std::mem::forget(buffer);
+    ptr as i32
 }
 
-/// Generate autofix suggestions
+/// Free memory returned by `alloc` or by an engine function
 #[no_mangle]
-pub extern "C" fn autofix(input_ptr: i32, input_len: i32) -> i32 {
-    call_engine_function(input_ptr, input_len, |req| match req {
-        AutofixRequest { detections, changes, estimates, mode } => {
-            // Generate autofix
-            let fixes = generate_autofix_wasm(&detections, &changes, &estimates, &mode);
-            let resp = AutofixResponse { fixes };
-            serde_json::to_string(&resp).map_err(|e| format!("Serialization error: {}", e))
+pub extern "C" fn dealloc(ptr: i32, size: i32) {
+    if ptr != 0 {
+        unsafe {
+            drop(Vec::from_raw_parts(ptr as *mut u8, 0, size.max(1) as usize));
         }
-    })
+    }
+}
+
+/// Negotiate capabilities with the host
+#[no_mangle]
+pub extern "C" fn costpilot_negotiate(input_ptr: i32, input_len: i32) -> i32 {
+    let input_slice = unsafe {
+        std::slice::from_raw_parts(input_ptr as *const u8, input_len as usize)
+    };
+
+    let offer: HandshakeOffer = match serde_json::from_slice(input_slice) {
+        Ok(o) => o,
+        Err(_) => return -1,
+    };
+
+    // Host cannot drive this ABI version
+    let version = ABI_VERSION as u32;
+    if version < offer.min_abi_version || version > offer.abi_version {
+        return -1;
+    }
+
+    let reply = HandshakeReply {
+        abi_version: ABI_VERSION,
+        capabilities: CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+    };
+
+    match serde_json::to_string(&reply) {
+        Ok(json) => write_region(&json),
+        Err(_) => -1,
+    }
+}
+
+/// Cost prediction
+#[no_mangle]
+pub extern "C" fn predict(input_ptr: i32, input_len: i32) -> i32 {
+    call_engine(input_ptr, input_len, "predict")
 }
 
-/// Predict cost estimates (placeholder)
+/// Explain cost predictions
 #[no_mangle]
-pub extern "C" fn predict(_input_ptr: i32, _input_len: i32) -> i32 {
-    // Placeholder - return empty result
-    let json = r#"{"estimates":[]}"#;
-    write_to_output_buffer(json)
+pub extern "C" fn explain(input_ptr: i32, input_len: i32) -> i32 {
+    call_engine(input_ptr, input_len, "explain")
 }
 
-/// Explain cost predictions (placeholder)
+/// Generate autofix suggestions
 #[no_mangle]
-pub extern "C" fn explain(_input_ptr: i32, _input_len: i32) -> i32 {
-    // Placeholder - return empty result
-    let json = r#"{"explanations":[]}"#;
-    write_to_output_buffer(json)
+pub extern "C" fn autofix(input_ptr: i32, input_len: i32) -> i32 {
+    call_engine(input_ptr, input_len, "autofix")
 }
 
-/// Map dependency graph (placeholder)
+/// Map dependency graph
 #[no_mangle]
-pub extern "C" fn mapdeep(_input_ptr: i32, _input_len: i32) -> i32 {
-    // Placeholder - return empty result
-    let json = r#"{"graph":{"nodes":[],"edges":[]}}"#;
-    write_to_output_buffer(json)
+pub extern "C" fn mapdeep(input_ptr: i32, input_len: i32) -> i32 {
+    call_engine(input_ptr, input_len, "mapdeep")
 }
 
-/// Trend analysis (placeholder)
+/// Trend snapshot
 #[no_mangle]
-pub extern "C" fn trend(_input_ptr: i32, _input_len: i32) -> i32 {
-    // Placeholder - return empty result
-    let json = r#"{"snapshot":{"timestamp":0,"costs":{}}}"#;
-    write_to_output_buffer(json)
+pub extern "C" fn trend(input_ptr: i32, input_len: i32) -> i32 {
+    call_engine(input_ptr, input_len, "trend")
 }
 
-/// Policy enforcement (placeholder)
+/// Policy enforcement
 #[no_mangle]
-pub extern "C" fn enforce(_input_ptr: i32, _input_len: i32) -> i32 {
-    // Placeholder - return empty result
-    let json = r#"{"result":{"violations":[],"actions":[]}}"#;
-    write_to_output_buffer(json)
+pub extern "C" fn enforce(input_ptr: i32, input_len: i32) -> i32 {
+    call_engine(input_ptr, input_len, "enforce")
 }
 
-/// SLO enforcement (placeholder)
+/// SLO enforcement
 #[no_mangle]
-pub extern "C" fn slo_enforce(_input_ptr: i32, _input_len: i32) -> i32 {
-    // Placeholder - return empty result
-    let json = r#"{"report":{"status":"ok","metrics":{}}}"#;
-    write_to_output_buffer(json)
-}
-
-/// Helper function to handle WASM memory and JSON serialization
-fn call_engine_function<F>(
-    input_ptr: i32,
-    input_len: i32,
-    handler: F,
-) -> i32
-where
-    F: FnOnce(AutofixRequest) -> Result<String, String>,
-{
+pub extern "C" fn slo_enforce(input_ptr: i32, input_len: i32) -> i32 {
+    call_engine(input_ptr, input_len, "slo_enforce")
+}
+
+/// Export expected to serve a request
+fn export_for(req: &ProEngineRequest) -> &'static str {
+    match req {
+        ProEngineRequest::Predict { .. } => "predict",
+        ProEngineRequest::Explain { .. } => "explain",
+        ProEngineRequest::Autofix { .. } => "autofix",
+        ProEngineRequest::MapDeep { .. } => "mapdeep",
+        ProEngineRequest::TrendSnapshot { .. } => "trend",
+        ProEngineRequest::PolicyEnforce { .. } => "enforce",
+        ProEngineRequest::SloEnforce { .. } => "slo_enforce",
+    }
+}
+
+/// Decode a request, run it on the native engines and return the response
+fn call_engine(input_ptr: i32, input_len: i32, export: &str) -> i32 {
     // Read input from WASM memory
     let input_slice = unsafe {
         std::slice::from_raw_parts(input_ptr as *const u8, input_len as usize)
     };
 
-    let input_str = match std::str::from_utf8(input_slice) {
-        Ok(s) => s,
-        Err(_) => return -1,
-    };
-
-    // Parse request
-    let req: AutofixRequest = match serde_json::from_str(input_str) {
+    let req: ProEngineRequest = match serde_json::from_slice(input_slice) {
         Ok(r) => r,
         Err(_) => return -1,
     };
 
-    // Call handler
-    let result = match handler(req) {
+    // Request sent to the wrong export
+    if export_for(&req) != export {
+        return -1;
+    }
+
+    let resp = match NativeProEngine::new().execute(req) {
         Ok(r) => r,
         Err(_) => return -1,
     };
 
-    // Write result to output buffer
-    write_to_output_buffer(&result)
+    match serde_json::to_string(&resp) {
+        Ok(json) => write_region(&json),
+        Err(_) => -1,
+    }
 }
 
-/// Write string to output buffer and return packed result
-fn write_to_output_buffer(data: &str) -> i32 {
+/// Copy a result into a freshly allocated region: a little-endian u32
+/// length followed by the bytes. The host frees it with `dealloc`.
+fn write_region(data: &str) -> i32 {
     let bytes = data.as_bytes();
     let len = bytes.len();
 
-    if len > unsafe { OUTPUT_BUFFER.len() } {
-        return -1; // Output too large
-    }
-
-    unsafe {
-        OUTPUT_BUFFER[..len].copy_from_slice(bytes);
-    }
-
-    // Return packed result: (length << 16) | offset
-    // Offset is 0 since OUTPUT_BUFFER starts at offset 4096 in WASM memory
-    ((len as i32) << 16) | 0
-}
-
-/// Core autofix logic implemented directly in WASM
-fn generate_autofix_wasm(
-    detections: &[String],
-    _changes: &[String],
-    _estimates: &[String],
-    mode: &str,
-) -> Vec<FixSnippet> {
-    let mut fixes = Vec::new();
-
-    // Simple rule-based autofix for common issues
-    for detection in detections {
-        if detection.contains("unused import") {
-            fixes.push(FixSnippet {
-                file_path: "example.rs".to_string(),
-                line_start: 1,
-                line_end: 1,
-                content: "".to_string(),
-                description: "Remove unused import".to_string(),
-            });
-        } else if detection.contains("missing semicolon") {
-            fixes.push(FixSnippet {
-                file_path: "example.rs".to_string(),
-                line_start: 5,
-                line_end: 5,
-                content: ";".to_string(),
-                description: "Add missing semicolon".to_string(),
-            });
-        } else if detection.contains("inefficient loop") {
-            fixes.push(FixSnippet {
-                file_path: "example.rs".to_string(),
-                line_start: 10,
-                line_end: 15,
-                content: "    // Optimized loop implementation\n    for item in collection.iter() {\n        // process item\n    }".to_string(),
-                description: "Optimize loop for better performance".to_string(),
-            });
-        }
-    }
-
-    // Mode-specific fixes
-    match mode {
-        "aggressive" => {
-            // Add more aggressive optimizations
-            fixes.push(FixSnippet {
-                file_path: "example.rs".to_string(),
-                line_start: 20,
-                line_end: 25,
-                content: "    // Aggressive optimization applied".to_string(),
-                description: "Apply aggressive performance optimizations".to_string(),
-            });
-        }
-        "conservative" => {
-            // Only safe fixes
-            fixes.retain(|f| !f.description.contains("aggressive"));
-        }
-        _ => {}
-    }
+    let mut region = Vec::with_capacity(4 + len);
+    region.extend_from_slice(&(len as u32).to_le_bytes());
+    region.extend_from_slice(bytes);
 
-    fixes
+    let ptr = region.as_mut_ptr();
+    std::mem::forget(region);
+    ptr as i32
 }
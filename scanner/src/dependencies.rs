@@ -0,0 +1,260 @@
+//! Direct dependencies declared in package manifests
+//!
+//! Reads `Cargo.toml`, `package.json` and `go.mod`. Only dependencies the
+//! manifest itself declares are listed; lockfiles and transitive
+//! dependencies are out of scope.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Package ecosystem of a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Go,
+}
+
+impl Ecosystem {
+    /// Package URL type (`pkg:<type>/...`)
+    pub fn purl_type(&self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::Npm => "npm",
+            Ecosystem::Go => "golang",
+        }
+    }
+}
+
+/// A dependency declared in a manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dependency {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    /// Version or version requirement as written; `None` for path and git dependencies
+    pub version: Option<String>,
+    /// Only needed for development or builds (dev-dependencies, devDependencies)
+    pub dev: bool,
+}
+
+/// Parse `path` if it is a supported manifest; `None` for any other file
+pub fn parse_manifest(path: &str, content: &str) -> Option<Vec<Dependency>> {
+    match path.rsplit('/').next().unwrap_or(path) {
+        "Cargo.toml" => Some(cargo(content)),
+        "package.json" => Some(npm(content)),
+        "go.mod" => Some(go(content)),
+        _ => None,
+    }
+}
+
+fn cargo(content: &str) -> Vec<Dependency> {
+    static INLINE_VERSION: OnceLock<Regex> = OnceLock::new();
+    let inline_version =
+        INLINE_VERSION.get_or_init(|| Regex::new(r#"\bversion\s*=\s*"([^"]*)""#).unwrap());
+
+    let mut dependencies: Vec<Dependency> = Vec::new();
+    // (dev, index of the dependency for `[dependencies.<name>]` tables)
+    let mut section: Option<(bool, Option<usize>)> = None;
+
+    for line in content.lines() {
+        let line = line.split(" #").next().unwrap_or(line).trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let header = header.trim();
+            let dev = header.contains("dev-dependencies") || header.contains("build-dependencies");
+            section = if header.ends_with("dependencies") {
+                Some((dev, None))
+            } else if let Some((table, name)) = header.rsplit_once('.') {
+                table.ends_with("dependencies").then(|| {
+                    dependencies.push(Dependency {
+                        ecosystem: Ecosystem::Cargo,
+                        name: name.trim_matches('"').to_string(),
+                        version: None,
+                        dev,
+                    });
+                    (dev, Some(dependencies.len() - 1))
+                })
+            } else {
+                None
+            };
+            continue;
+        }
+
+        let Some((dev, table)) = section else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+
+        match table {
+            Some(index) if key == "version" => {
+                dependencies[index].version = Some(value.trim_matches('"').to_string());
+            }
+            Some(_) => {}
+            None => {
+                // `name = "1.0"`, `name = { version = "1.0", ... }`, `name.workspace = true`
+                let name = key.split('.').next().unwrap_or(key).trim_matches('"');
+                let version = if value.starts_with('"') {
+                    Some(value.trim_matches('"').to_string())
+                } else {
+                    inline_version
+                        .captures(value)
+                        .map(|capture| capture[1].to_string())
+                };
+                dependencies.push(Dependency {
+                    ecosystem: Ecosystem::Cargo,
+                    name: name.to_string(),
+                    version,
+                    dev,
+                });
+            }
+        }
+    }
+    dependencies
+}
+
+fn npm(content: &str) -> Vec<Dependency> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let sections = [
+        ("dependencies", false),
+        ("optionalDependencies", false),
+        ("devDependencies", true),
+    ];
+
+    let mut dependencies = Vec::new();
+    for (section, dev) in sections {
+        let Some(entries) = manifest.get(section).and_then(|s| s.as_object()) else {
+            continue;
+        };
+        for (name, version) in entries {
+            dependencies.push(Dependency {
+                ecosystem: Ecosystem::Npm,
+                name: name.clone(),
+                version: version.as_str().map(str::to_string),
+                dev,
+            });
+        }
+    }
+    dependencies
+}
+
+fn go(content: &str) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        let requirement = if in_block {
+            if line == ")" {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if line == "require (" {
+            in_block = true;
+            continue;
+        } else if let Some(rest) = line.strip_prefix("require ") {
+            rest
+        } else {
+            continue;
+        };
+
+        // Modules marked `// indirect` are only needed by other dependencies
+        if requirement.contains("// indirect") || requirement.starts_with("//") {
+            continue;
+        }
+        let mut parts = requirement.split_whitespace();
+        if let (Some(name), Some(version)) = (parts.next(), parts.next()) {
+            dependencies.push(Dependency {
+                ecosystem: Ecosystem::Go,
+                name: name.to_string(),
+                version: Some(version.to_string()),
+                dev: false,
+            });
+        }
+    }
+    dependencies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(dependencies: &[Dependency]) -> Vec<(&str, Option<&str>, bool)> {
+        dependencies
+            .iter()
+            .map(|d| (d.name.as_str(), d.version.as_deref(), d.dev))
+            .collect()
+    }
+
+    #[test]
+    fn test_cargo_dependency_forms() {
+        let content = r#"
+[package]
+name = "app"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1.0", features = ["derive"] }
+anyhow = "1"  # errors
+local = { path = "../local" }
+shared.workspace = true
+
+[dependencies.tokio]
+version = "1.35"
+features = ["full"]
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+
+[dev-dependencies]
+tempfile = "3"
+"#;
+        let dependencies = parse_manifest("crates/app/Cargo.toml", content).unwrap();
+        assert_eq!(
+            summary(&dependencies),
+            vec![
+                ("serde", Some("1.0"), false),
+                ("anyhow", Some("1"), false),
+                ("local", None, false),
+                ("shared", None, false),
+                ("tokio", Some("1.35"), false),
+                ("libc", Some("0.2"), false),
+                ("tempfile", Some("3"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_npm_and_go_direct_dependencies() {
+        let package = r#"{"name": "web", "dependencies": {"@aws-sdk/client-s3": "^3.400.0"},
+                          "devDependencies": {"jest": "29.7.0"}}"#;
+        assert_eq!(
+            summary(&parse_manifest("web/package.json", package).unwrap()),
+            vec![
+                ("@aws-sdk/client-s3", Some("^3.400.0"), false),
+                ("jest", Some("29.7.0"), true),
+            ]
+        );
+
+        let go_mod = "module example.com/api\n\ngo 1.21\n\nrequire github.com/spf13/cobra v1.8.0\n\nrequire (\n\tgolang.org/x/sync v0.5.0\n\tgithub.com/inconshreveable/mousetrap v1.1.0 // indirect\n)\n";
+        assert_eq!(
+            summary(&parse_manifest("go.mod", go_mod).unwrap()),
+            vec![
+                ("github.com/spf13/cobra", Some("v1.8.0"), false),
+                ("golang.org/x/sync", Some("v0.5.0"), false),
+            ]
+        );
+
+        assert_eq!(parse_manifest("README.md", "# app"), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Incremental scans against a git ref
+//!
+//! A full scan stores one record per file in a cache file. `--since <ref>`
+//! then re-analyzes only the files git reports as changed since that ref
+//! (plus anything changed since the cached scan) and merges them into the
+//! cached records, so large repositories re-scan in seconds.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+
+use crate::dependencies::Dependency;
+use crate::inventory::IacFile;
+use crate::secrets::SecretFinding;
+
+/// Cache file written next to the scanned repository by default
+pub const DEFAULT_CACHE: &str = ".scanner-cache.json";
+
+/// Per-file scan result, keyed by `/`-separated relative path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileRecord {
+    pub language: Option<String>,
+    pub lines: usize,
+    pub findings: Vec<SecretFinding>,
+    pub allowlisted: usize,
+    /// Set for Terraform, CloudFormation, Kubernetes and other IaC sources
+    #[serde(default)]
+    pub iac: Option<IacFile>,
+    /// Direct dependencies, for package manifests
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<Dependency>,
+}
+
+/// Records of a previous scan
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanCache {
+    /// Scanner version that wrote the cache
+    pub version: String,
+    /// `HEAD` at the time of the scan, when the repository is a git checkout
+    pub commit: Option<String>,
+    /// `Allowlist::digest` of the allowlist used
+    pub allowlist: String,
+    pub files: BTreeMap<String, FileRecord>,
+}
+
+impl ScanCache {
+    pub fn new(
+        commit: Option<String>,
+        allowlist: String,
+        files: BTreeMap<String, FileRecord>,
+    ) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            commit,
+            allowlist,
+            files,
+        }
+    }
+
+    /// Load a cache; `None` if it is missing or unreadable
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self)?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write scan cache: {}", path.display()))
+    }
+
+    /// Whether records from this cache can be reused under the given allowlist
+    pub fn is_compatible(&self, allowlist: &str) -> bool {
+        self.version == env!("CARGO_PKG_VERSION") && self.allowlist == allowlist
+    }
+}
+
+/// Current `HEAD` commit of the repository containing `root`
+pub fn head_commit(root: &Path) -> Option<String> {
+    git(root, &["rev-parse", "--verify", "HEAD"])
+        .ok()
+        .map(|out| out.trim().to_string())
+}
+
+/// Files below `root` that differ from `since`, including deletions and
+/// untracked files, as `/`-separated paths relative to `root`
+pub fn changed_files(root: &Path, since: &str) -> Result<BTreeSet<String>> {
+    let diff = git(
+        root,
+        &[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "--relative",
+            "-z",
+            since,
+            "--",
+        ],
+    )?;
+    let untracked = git(root, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+
+    Ok(diff
+        .split('\0')
+        .chain(untracked.split('\0'))
+        .filter(|path| !path.is_empty() && !is_hidden(path))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Replace the records of `changed` files with fresh results from `analyze`;
+/// files it returns `None` for (deleted, unreadable) are dropped
+pub fn merge<F>(
+    mut files: BTreeMap<String, FileRecord>,
+    changed: &BTreeSet<String>,
+    analyze: F,
+) -> BTreeMap<String, FileRecord>
+where
+    F: Fn(&str) -> Option<FileRecord>,
+{
+    for path in changed {
+        match analyze(path) {
+            Some(record) => {
+                files.insert(path.clone(), record);
+            }
+            None => {
+                files.remove(path);
+            }
+        }
+    }
+    files
+}
+
+/// The full scan skips hidden files and directories; keep incremental scans consistent
+fn is_hidden(path: &str) -> bool {
+    path.split('/').any(|part| part.starts_with('.'))
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(lines: usize) -> FileRecord {
+        FileRecord {
+            language: Some("Rust".to_string()),
+            lines,
+            findings: Vec::new(),
+            allowlisted: 0,
+            iac: None,
+            dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge_replaces_changed_and_drops_deleted() {
+        let cached: BTreeMap<String, FileRecord> = [
+            ("src/a.rs".to_string(), record(10)),
+            ("src/b.rs".to_string(), record(20)),
+            ("src/gone.rs".to_string(), record(30)),
+        ]
+        .into_iter()
+        .collect();
+        let changed: BTreeSet<String> = ["src/b.rs", "src/gone.rs", "src/new.rs"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let merged = merge(cached, &changed, |path| match path {
+            "src/b.rs" => Some(record(25)),
+            "src/new.rs" => Some(record(5)),
+            _ => None,
+        });
+
+        let lines: Vec<(&str, usize)> = merged.iter().map(|(p, r)| (p.as_str(), r.lines)).collect();
+        assert_eq!(
+            lines,
+            vec![("src/a.rs", 10), ("src/b.rs", 25), ("src/new.rs", 5)]
+        );
+    }
+
+    #[test]
+    fn test_changed_files_since_ref() {
+        let root = std::env::temp_dir().join(format!("scanner-incremental-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        let run = |args: &[&str]| git(&root, args).unwrap();
+
+        run(&["init", "-q"]);
+        std::fs::write(root.join("src/kept.rs"), "fn kept() {}\n").unwrap();
+        std::fs::write(root.join("src/edited.rs"), "fn edited() {}\n").unwrap();
+        std::fs::write(root.join("src/removed.rs"), "fn removed() {}\n").unwrap();
+        run(&["add", "."]);
+        run(&[
+            "-c",
+            "user.name=t",
+            "-c",
+            "user.email=t@example.com",
+            "commit",
+            "-qm",
+            "base",
+        ]);
+
+        std::fs::write(root.join("src/edited.rs"), "fn edited() { 1; }\n").unwrap();
+        std::fs::remove_file(root.join("src/removed.rs")).unwrap();
+        std::fs::write(root.join("src/added.rs"), "fn added() {}\n").unwrap();
+        std::fs::write(root.join(".env"), "TOKEN=x\n").unwrap();
+
+        let changed = changed_files(&root, "HEAD").unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        let changed: Vec<&str> = changed.iter().map(String::as_str).collect();
+        assert_eq!(
+            changed,
+            vec!["src/added.rs", "src/edited.rs", "src/removed.rs"]
+        );
+    }
+
+    #[test]
+    fn test_cache_compatibility() {
+        let cache = ScanCache::new(None, "abc".to_string(), BTreeMap::new());
+        assert!(cache.is_compatible("abc"));
+        assert!(!cache.is_compatible("def"));
+    }
+}
@@ -0,0 +1,332 @@
+//! Infrastructure-as-code inventory
+//!
+//! Classifies Terraform, CDK, CloudFormation, SAM, Serverless Framework and
+//! Kubernetes sources and summarizes them per framework. The JSON written by
+//! `--inventory` is read by `costpilot init --wizard` to pick the parsers
+//! and engines for the repository.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::OnceLock;
+
+/// Inventory format version, bumped on incompatible changes
+pub const INVENTORY_SCHEMA_VERSION: u32 = 1;
+
+/// IaC framework a file belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IacKind {
+    Terraform,
+    Cdk,
+    CloudFormation,
+    Sam,
+    Serverless,
+    Kubernetes,
+}
+
+impl IacKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            IacKind::Terraform => "terraform",
+            IacKind::Cdk => "cdk",
+            IacKind::CloudFormation => "cloudformation",
+            IacKind::Sam => "sam",
+            IacKind::Serverless => "serverless",
+            IacKind::Kubernetes => "kubernetes",
+        }
+    }
+}
+
+/// What one IaC file declares
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IacFile {
+    pub kind: IacKind,
+    /// Cloud providers used (`aws`, `google`, `kubernetes`, ...)
+    pub providers: Vec<String>,
+    /// Resources, templates' `Resources` entries or manifests
+    pub resources: usize,
+    /// Terraform `module` blocks
+    pub modules: usize,
+}
+
+/// Per-framework totals
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameworkSummary {
+    pub files: usize,
+    pub resources: usize,
+    pub modules: usize,
+    /// IaC files per directory (`.` for the repository root)
+    pub directories: BTreeMap<String, usize>,
+}
+
+/// Repository-wide IaC inventory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    pub schema_version: u32,
+    pub frameworks: BTreeMap<IacKind, FrameworkSummary>,
+    /// Files referencing each provider
+    pub providers: BTreeMap<String, usize>,
+}
+
+impl Inventory {
+    /// Summarize classified files, keyed by `/`-separated relative path
+    pub fn build<'a>(files: impl IntoIterator<Item = (&'a String, &'a IacFile)>) -> Self {
+        let mut frameworks: BTreeMap<IacKind, FrameworkSummary> = BTreeMap::new();
+        let mut providers: BTreeMap<String, usize> = BTreeMap::new();
+
+        for (path, file) in files {
+            let summary = frameworks.entry(file.kind).or_default();
+            summary.files += 1;
+            summary.resources += file.resources;
+            summary.modules += file.modules;
+            let directory = match path.rsplit_once('/') {
+                Some((dir, _)) => dir.to_string(),
+                None => ".".to_string(),
+            };
+            *summary.directories.entry(directory).or_insert(0) += 1;
+
+            for provider in &file.providers {
+                *providers.entry(provider.clone()).or_insert(0) += 1;
+            }
+        }
+
+        Self {
+            schema_version: INVENTORY_SCHEMA_VERSION,
+            frameworks,
+            providers,
+        }
+    }
+}
+
+/// Classify a file by name and content; `None` for anything that is not IaC
+pub fn classify(path: &str, content: &str) -> Option<IacFile> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let extension = name.rsplit_once('.').map(|(_, ext)| ext);
+
+    if name.ends_with(".tf") || name.ends_with(".tf.json") {
+        return Some(terraform(content));
+    }
+    if name == "cdk.json" {
+        return Some(aws(IacKind::Cdk, 0));
+    }
+    if matches!(name, "serverless.yml" | "serverless.yaml") {
+        let functions = content
+            .lines()
+            .skip_while(|line| !line.starts_with("functions:"))
+            .skip(1)
+            .take_while(|line| line.is_empty() || line.starts_with(' '))
+            .filter(|line| is_yaml_key(line, 2))
+            .count();
+        return Some(aws(IacKind::Serverless, functions));
+    }
+    if !matches!(extension, Some("yaml" | "yml" | "json" | "template")) {
+        return None;
+    }
+
+    if content.contains("AWS::Serverless") {
+        return Some(aws(IacKind::Sam, cloudformation_resources(content)));
+    }
+    if content.contains("AWSTemplateFormatVersion") {
+        return Some(aws(
+            IacKind::CloudFormation,
+            cloudformation_resources(content),
+        ));
+    }
+    if name == "Chart.yaml" || is_kubernetes_manifest(content) {
+        let manifests = content
+            .lines()
+            .filter(|line| line.starts_with("kind:"))
+            .count();
+        return Some(IacFile {
+            kind: IacKind::Kubernetes,
+            providers: vec!["kubernetes".to_string()],
+            resources: manifests,
+            modules: 0,
+        });
+    }
+    None
+}
+
+fn aws(kind: IacKind, resources: usize) -> IacFile {
+    IacFile {
+        kind,
+        providers: vec!["aws".to_string()],
+        resources,
+        modules: 0,
+    }
+}
+
+fn terraform(content: &str) -> IacFile {
+    static BLOCK: OnceLock<Regex> = OnceLock::new();
+    static REQUIRED: OnceLock<Regex> = OnceLock::new();
+    let block = BLOCK.get_or_init(|| {
+        Regex::new(r#"(?m)^\s*(resource|data|provider|module)\s+"([A-Za-z0-9_-]+)""#).unwrap()
+    });
+    // `name = { source = "namespace/name" }` entries of `required_providers`
+    let required = REQUIRED.get_or_init(|| {
+        Regex::new(r#"(?m)^\s*([a-z0-9_-]+)\s*=\s*\{\s*$[^}]*source\s*=\s*"[^"]*/"#).unwrap()
+    });
+
+    let mut providers = BTreeSet::new();
+    let mut resources = 0;
+    let mut modules = 0;
+    for capture in block.captures_iter(content) {
+        let name = &capture[2];
+        match &capture[1] {
+            "resource" => {
+                resources += 1;
+                providers.insert(provider_prefix(name));
+            }
+            "data" => {
+                providers.insert(provider_prefix(name));
+            }
+            "provider" => {
+                providers.insert(name.to_string());
+            }
+            _ => modules += 1,
+        }
+    }
+    for capture in required.captures_iter(content) {
+        providers.insert(capture[1].to_string());
+    }
+
+    IacFile {
+        kind: IacKind::Terraform,
+        providers: providers.into_iter().collect(),
+        resources,
+        modules,
+    }
+}
+
+/// `aws_instance` -> `aws`
+fn provider_prefix(resource_type: &str) -> String {
+    resource_type
+        .split_once('_')
+        .map_or(resource_type, |(prefix, _)| prefix)
+        .to_string()
+}
+
+/// `Type: AWS::...` lines in YAML or JSON templates
+fn cloudformation_resources(content: &str) -> usize {
+    content
+        .lines()
+        .filter(|line| {
+            let line = line.trim().trim_start_matches('"');
+            line.starts_with("Type") && line.contains("AWS::")
+        })
+        .count()
+}
+
+/// Top-level `apiVersion:` and `kind:` lines, as in a manifest or `helm template` output
+fn is_kubernetes_manifest(content: &str) -> bool {
+    content.lines().any(|line| line.starts_with("apiVersion:"))
+        && content.lines().any(|line| line.starts_with("kind:"))
+}
+
+/// A mapping key indented by exactly `indent` spaces
+fn is_yaml_key(line: &str, indent: usize) -> bool {
+    let trimmed = line.trim_start_matches(' ');
+    line.len() - trimmed.len() == indent
+        && !trimmed.starts_with('#')
+        && !trimmed.starts_with('-')
+        && trimmed.trim_end().ends_with(':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_terraform_providers_and_modules() {
+        let content = r#"
+terraform {
+  required_providers {
+    random = {
+      source = "hashicorp/random"
+    }
+  }
+}
+
+provider "aws" {
+  region = "us-east-1"
+}
+
+resource "aws_instance" "web" {}
+resource "google_storage_bucket" "logs" {}
+data "aws_ami" "ubuntu" {}
+
+module "vpc" {
+  source = "./modules/vpc"
+}
+"#;
+        let file = classify("infra/main.tf", content).unwrap();
+        assert_eq!(file.kind, IacKind::Terraform);
+        assert_eq!(file.providers, vec!["aws", "google", "random"]);
+        assert_eq!(file.resources, 2);
+        assert_eq!(file.modules, 1);
+    }
+
+    #[test]
+    fn test_classifies_templates_and_manifests() {
+        let sam = "Transform: AWS::Serverless-2016-10-31\nResources:\n  Fn:\n    Type: AWS::Serverless::Function\n  Table:\n    Type: AWS::DynamoDB::Table\n";
+        let file = classify("app/template.yaml", sam).unwrap();
+        assert_eq!((file.kind, file.resources), (IacKind::Sam, 2));
+
+        let cfn = r#"{"AWSTemplateFormatVersion": "2010-09-09", "Resources": {"B": {"Type": "AWS::S3::Bucket"}}}"#;
+        assert_eq!(
+            classify("stack.json", cfn).unwrap().kind,
+            IacKind::CloudFormation
+        );
+
+        let manifest =
+            "apiVersion: apps/v1\nkind: Deployment\n---\napiVersion: v1\nkind: Service\n";
+        let file = classify("k8s/web.yaml", manifest).unwrap();
+        assert_eq!((file.kind, file.resources), (IacKind::Kubernetes, 2));
+
+        let serverless = "service: api\nprovider:\n  name: aws\nfunctions:\n  create:\n    handler: a.b\n  list:\n    handler: a.c\n";
+        let file = classify("serverless.yml", serverless).unwrap();
+        assert_eq!((file.kind, file.resources), (IacKind::Serverless, 2));
+
+        assert_eq!(classify(".github/workflows/ci.yml", "on: push\n"), None);
+        assert_eq!(classify("src/main.rs", "fn main() {}"), None);
+    }
+
+    #[test]
+    fn test_inventory_summarizes_per_framework() {
+        let files = [
+            (
+                "infra/main.tf".to_string(),
+                classify(
+                    "infra/main.tf",
+                    "resource \"aws_s3_bucket\" \"b\" {}\nmodule \"m\" {}\n",
+                )
+                .unwrap(),
+            ),
+            (
+                "infra/vpc.tf".to_string(),
+                classify("infra/vpc.tf", "resource \"aws_vpc\" \"v\" {}\n").unwrap(),
+            ),
+            (
+                "root.tf".to_string(),
+                classify("root.tf", "provider \"google\" {}\n").unwrap(),
+            ),
+            ("cdk.json".to_string(), classify("cdk.json", "{}").unwrap()),
+        ];
+        let inventory = Inventory::build(files.iter().map(|(p, f)| (p, f)));
+
+        let terraform = &inventory.frameworks[&IacKind::Terraform];
+        assert_eq!(
+            (terraform.files, terraform.resources, terraform.modules),
+            (3, 2, 1)
+        );
+        assert_eq!(terraform.directories["infra"], 2);
+        assert_eq!(terraform.directories["."], 1);
+        assert_eq!(inventory.frameworks[&IacKind::Cdk].files, 1);
+        assert_eq!(inventory.providers["aws"], 3);
+        assert_eq!(inventory.providers["google"], 1);
+
+        let json = serde_json::to_value(&inventory).unwrap();
+        assert!(json["frameworks"]["terraform"].is_object());
+    }
+}
@@ -1,14 +1,23 @@
 use clap::Parser;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
-use regex::Regex;
 use ignore::WalkBuilder;
 use rayon::prelude::*;
 use anyhow::{Result, Context};
 
+mod dependencies;
+mod incremental;
+mod inventory;
+mod sbom;
+mod secrets;
+
+use incremental::{FileRecord, ScanCache};
+use inventory::Inventory;
+use secrets::{Allowlist, SecretDetector, SecretFinding};
+
 #[derive(Parser)]
 #[command(name = "repo-scanner")]
 #[command(about = "Accurate repository analysis tool")]
@@ -17,13 +26,29 @@ struct Args {
     #[arg(default_value = ".")]
     path: PathBuf,
 
-    /// Output format
+    /// Output format: json, text, or cyclonedx (CycloneDX SBOM)
     #[arg(long, default_value = "json")]
     format: String,
 
     /// Enable verbose output
     #[arg(short, long)]
     verbose: bool,
+
+    /// Secret allowlist (default: .scanner-allowlist.json in the scanned path)
+    #[arg(long)]
+    allowlist: Option<PathBuf>,
+
+    /// Only re-scan files changed since this git ref, reusing the cached scan for the rest
+    #[arg(long, value_name = "REF")]
+    since: Option<String>,
+
+    /// Scan cache to read and update (default: .scanner-cache.json in the scanned path)
+    #[arg(long)]
+    cache: Option<PathBuf>,
+
+    /// Write the IaC inventory to this file (read by `costpilot init --wizard`)
+    #[arg(long)]
+    inventory: Option<PathBuf>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,6 +56,7 @@ struct ScanResult {
     summary: Summary,
     languages: HashMap<String, LanguageStats>,
     security_findings: SecurityFindings,
+    iac_inventory: Inventory,
     compliance_status: ComplianceStatus,
 }
 
@@ -40,6 +66,10 @@ struct Summary {
     total_lines: usize,
     languages_detected: usize,
     scan_duration_ms: u128,
+    /// Files analyzed in this run; below `total_files` for incremental scans
+    rescanned_files: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,7 +81,11 @@ struct LanguageStats {
 
 #[derive(Debug, Serialize, Deserialize)]
 struct SecurityFindings {
+    /// Findings per rule ID
     patterns_found: HashMap<String, usize>,
+    findings: Vec<SecretFinding>,
+    /// Matches suppressed by the allowlist or inline markers
+    allowlisted: usize,
     risk_score: f64,
     evidence_based: bool,
 }
@@ -65,7 +99,6 @@ struct ComplianceStatus {
 
 #[derive(Debug)]
 struct FileAnalysis {
-    path: PathBuf,
     language: Option<String>,
     lines: usize,
     content: String,
@@ -75,78 +108,114 @@ fn main() -> Result<()> {
     let args = Args::parse();
     let start_time = std::time::Instant::now();
 
-    println!("Scanning repository: {}", args.path.display());
-
-    // Build walker with proper ignore handling
-    let walker = WalkBuilder::new(&args.path)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true)
-        .build()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().map_or(false, |ft| ft.is_file()))
-        .collect::<Vec<_>>();
+    eprintln!("Scanning repository: {}", args.path.display());
+
+    let allowlist = match &args.allowlist {
+        Some(path) => Allowlist::load(path)?,
+        None => {
+            let default = args.path.join(secrets::DEFAULT_ALLOWLIST);
+            if default.exists() {
+                Allowlist::load(&default)?
+            } else {
+                Allowlist::default()
+            }
+        }
+    };
+    let detector = SecretDetector::new(&allowlist)?;
+    let cache_path = args.cache.clone()
+        .unwrap_or_else(|| args.path.join(incremental::DEFAULT_CACHE));
+    let commit = incremental::head_commit(&args.path);
+
+    let previous = match &args.since {
+        Some(since) => load_incremental(&args, since, &cache_path, &allowlist.digest()),
+        None => None,
+    };
 
-    if args.verbose {
-        println!("Found {} files to analyze", walker.len());
+    let (files, rescanned_files) = match previous {
+        Some((cache, changed)) => {
+            if args.verbose {
+                eprintln!("Re-scanning {} changed files", changed.len());
+            }
+            let files = incremental::merge(cache.files, &changed, |relative| {
+                scan_file(&args.path, &args.path.join(relative), &detector)
+            });
+            (files, changed.len())
+        }
+        None => {
+            let files = full_scan(&args.path, &detector, args.verbose);
+            let count = files.len();
+            (files, count)
+        }
+    };
+    let cache = ScanCache::new(commit, allowlist.digest(), files);
+    if args.since.is_some() || args.cache.is_some() {
+        cache.save(&cache_path)?;
     }
 
-    // Analyze files in parallel
-    let analyses: Vec<FileAnalysis> = walker
-        .par_iter()
-        .filter_map(|entry| analyze_file(entry.path()).ok())
-        .collect();
-
     // Aggregate results
     let mut languages: HashMap<String, LanguageStats> = HashMap::new();
     let mut total_lines = 0;
-    let mut security_patterns: HashMap<String, usize> = HashMap::new();
+    let mut allowlisted = 0;
+    let mut findings: Vec<SecretFinding> = Vec::new();
 
-    for analysis in &analyses {
-        total_lines += analysis.lines;
+    for record in cache.files.values() {
+        total_lines += record.lines;
+        allowlisted += record.allowlisted;
+        findings.extend(record.findings.iter().cloned());
 
-        if let Some(lang) = &analysis.language {
+        if let Some(lang) = &record.language {
             let stats = languages.entry(lang.clone()).or_insert(LanguageStats {
                 files: 0,
                 lines: 0,
                 percentage: 0.0,
             });
             stats.files += 1;
-            stats.lines += analysis.lines;
+            stats.lines += record.lines;
         }
-
-        // Evidence-based security pattern detection
-        detect_security_patterns(&analysis.content, &mut security_patterns);
     }
+    findings.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
 
     // Calculate percentages
-    let total_files = analyses.len();
+    let total_files = cache.files.len();
     for stats in languages.values_mut() {
         stats.percentage = (stats.files as f64 / total_files as f64) * 100.0;
     }
 
     // Calculate risk score based on evidence
-    let risk_score = calculate_risk_score(&security_patterns, total_files);
+    let risk_score = secrets::risk_score(&findings);
 
     let summary = Summary {
         total_files,
         total_lines,
         languages_detected: languages.len(),
         scan_duration_ms: start_time.elapsed().as_millis(),
+        rescanned_files,
+        since: args.since.clone(),
     };
 
     let security_findings = SecurityFindings {
-        patterns_found: security_patterns,
+        patterns_found: secrets::counts_by_rule(&findings),
+        findings,
+        allowlisted,
         risk_score,
         evidence_based: true,
     };
 
+    let iac_inventory = Inventory::build(
+        cache.files.iter().filter_map(|(path, record)| Some((path, record.iac.as_ref()?))),
+    );
+    if let Some(path) = &args.inventory {
+        fs::write(path, serde_json::to_string_pretty(&iac_inventory)?)
+            .with_context(|| format!("Failed to write inventory: {}", path.display()))?;
+    }
+
     let compliance_status = assess_compliance(&args.path)?;
 
     let result = ScanResult {
         summary,
         languages,
         security_findings,
+        iac_inventory,
         compliance_status,
     };
 
@@ -154,12 +223,100 @@ fn main() -> Result<()> {
     match args.format.as_str() {
         "json" => println!("{}", serde_json::to_string_pretty(&result)?),
         "text" => print_text_output(&result),
+        "cyclonedx" => {
+            let root = args.path.canonicalize().unwrap_or_else(|_| args.path.clone());
+            let name = root.file_name().map_or_else(
+                || root.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+            let bom = sbom::cyclonedx(&name, &cache.files);
+            println!("{}", serde_json::to_string_pretty(&bom)?)
+        }
         _ => println!("{}", serde_json::to_string_pretty(&result)?),
     }
 
     Ok(())
 }
 
+/// Analyze every file below `root` that is not ignored
+fn full_scan(root: &Path, detector: &SecretDetector, verbose: bool) -> BTreeMap<String, FileRecord> {
+    // Build walker with proper ignore handling
+    let walker = WalkBuilder::new(root)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().map_or(false, |ft| ft.is_file()))
+        .collect::<Vec<_>>();
+
+    if verbose {
+        eprintln!("Found {} files to analyze", walker.len());
+    }
+
+    // Analyze files in parallel
+    walker
+        .par_iter()
+        .filter_map(|entry| {
+            let record = scan_file(root, entry.path(), detector)?;
+            Some((relative_path(root, entry.path()), record))
+        })
+        .collect()
+}
+
+/// Reuse the cached scan for `--since`, or `None` to fall back to a full scan
+fn load_incremental(
+    args: &Args,
+    since: &str,
+    cache_path: &Path,
+    allowlist: &str,
+) -> Option<(ScanCache, BTreeSet<String>)> {
+    let cache = match ScanCache::load(cache_path) {
+        Some(cache) if cache.is_compatible(allowlist) => cache,
+        Some(_) => {
+            eprintln!("Scan cache {} is outdated; running a full scan", cache_path.display());
+            return None;
+        }
+        None => {
+            eprintln!("No scan cache at {}; running a full scan", cache_path.display());
+            return None;
+        }
+    };
+
+    // Files touched between the cached scan and `since` are stale as well
+    let refs = std::iter::once(since).chain(cache.commit.as_deref());
+    let mut changed = BTreeSet::new();
+    for reference in refs {
+        match incremental::changed_files(&args.path, reference) {
+            Ok(files) => changed.extend(files),
+            Err(e) => {
+                eprintln!("Cannot diff against {}: {:#}; running a full scan", reference, e);
+                return None;
+            }
+        }
+    }
+    Some((cache, changed))
+}
+
+fn scan_file(root: &Path, path: &Path, detector: &SecretDetector) -> Option<FileRecord> {
+    // Symlinks are skipped by the walker; skip them here too
+    if !fs::symlink_metadata(path).is_ok_and(|m| m.is_file()) {
+        return None;
+    }
+    let analysis = analyze_file(path).ok()?;
+    let relative = relative_path(root, path);
+    let secrets = detector.scan(&relative, &analysis.content);
+    Some(FileRecord {
+        language: analysis.language,
+        lines: analysis.lines,
+        findings: secrets.findings,
+        allowlisted: secrets.allowlisted,
+        iac: inventory::classify(&relative, &analysis.content),
+        dependencies: dependencies::parse_manifest(&relative, &analysis.content)
+            .unwrap_or_default(),
+    })
+}
+
 fn analyze_file(path: &Path) -> Result<FileAnalysis> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
@@ -168,7 +325,6 @@ fn analyze_file(path: &Path) -> Result<FileAnalysis> {
     let language = detect_language(path, &content);
 
     Ok(FileAnalysis {
-        path: path.to_path_buf(),
         language,
         lines,
         content,
@@ -210,55 +366,14 @@ fn detect_language(path: &Path, content: &str) -> Option<String> {
     None
 }
 
-fn detect_security_patterns(content: &str, patterns: &mut HashMap<String, usize>) {
-    let security_patterns = [
-        (r"password|Password|PASSWORD", "password_usage"),
-        (r"secret|Secret|SECRET", "secret_usage"),
-        (r"token|Token|TOKEN", "token_usage"),
-        (r"auth|Auth|AUTH", "auth_usage"),
-        (r"encrypt|Encrypt|ENCRYPT", "encryption_usage"),
-        (r"decrypt|Decrypt|DECRYPT", "decryption_usage"),
-        (r"hash|Hash|HASH", "hash_usage"),
-        (r"sql|SQL", "sql_usage"),
-        (r"xss|XSS", "xss_usage"),
-        (r"injection|Injection|INJECTION", "injection_usage"),
-    ];
-
-    for (pattern, name) in &security_patterns {
-        let re = Regex::new(pattern).unwrap();
-        let count = re.find_iter(content).count();
-        if count > 0 {
-            *patterns.entry(name.to_string()).or_insert(0) += count;
-        }
-    }
-}
-
-fn calculate_risk_score(patterns: &HashMap<String, usize>, total_files: usize) -> f64 {
-    let mut score = 0.0;
-
-    // Weight different patterns
-    let weights = [
-        ("password_usage", 2.0),
-        ("secret_usage", 3.0),
-        ("token_usage", 1.5),
-        ("auth_usage", 1.0),
-        ("encryption_usage", -1.0), // Negative because encryption is good
-        ("decryption_usage", 0.5),
-        ("hash_usage", -0.5), // Hashing can be good or bad
-        ("sql_usage", 1.0),
-        ("xss_usage", 2.0),
-        ("injection_usage", 2.5),
-    ];
-
-    for (pattern, weight) in &weights {
-        if let Some(count) = patterns.get(*pattern) {
-            score += *count as f64 * weight;
-        }
-    }
-
-    // Normalize by file count and cap at 10.0
-    let normalized_score = score / total_files as f64 * 10.0;
-    normalized_score.max(0.0).min(10.0)
+/// `/`-separated path of `path` below the scanned root
+fn relative_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 fn assess_compliance(repo_path: &Path) -> Result<ComplianceStatus> {
@@ -309,6 +424,10 @@ fn print_text_output(result: &ScanResult) {
     println!("Total Lines: {}", result.summary.total_lines);
     println!("Languages Detected: {}", result.summary.languages_detected);
     println!("Scan Duration: {}ms", result.summary.scan_duration_ms);
+    if let Some(since) = &result.summary.since {
+        println!("Incremental: {} files re-scanned since {}",
+                result.summary.rescanned_files, since);
+    }
     println!();
 
     println!("Languages:");
@@ -321,10 +440,22 @@ fn print_text_output(result: &ScanResult) {
     println!("Security Findings:");
     println!("  Risk Score: {:.2}/10.0", result.security_findings.risk_score);
     println!("  Evidence-based: {}", result.security_findings.evidence_based);
-    println!("  Patterns Found:");
-    for (pattern, count) in &result.security_findings.patterns_found {
-        println!("    {}: {}", pattern, count);
+    println!("  Allowlisted: {}", result.security_findings.allowlisted);
+    println!("  Findings:");
+    for finding in &result.security_findings.findings {
+        println!("    {}:{}:{} [{:?}] {} ({}) {}",
+                finding.file, finding.line, finding.column, finding.severity,
+                finding.rule_id, finding.description, finding.redacted);
+    }
+    println!();
+
+    println!("Infrastructure as Code:");
+    for (kind, summary) in &result.iac_inventory.frameworks {
+        println!("  {}: {} files, {} resources, {} modules",
+                kind.name(), summary.files, summary.resources, summary.modules);
     }
+    let providers: Vec<String> = result.iac_inventory.providers.keys().cloned().collect();
+    println!("  Providers: {}", providers.join(", "));
     println!();
 
     println!("Compliance Status:");
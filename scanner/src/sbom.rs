@@ -0,0 +1,240 @@
+//! CycloneDX SBOM output (`--format cyclonedx`)
+//!
+//! Emits a CycloneDX 1.5 JSON document describing the repository: direct
+//! dependencies from package manifests as `library` components, IaC sources
+//! as `file` components, and detected languages as properties of the root
+//! component. Output is deterministic so SBOMs of the same tree diff cleanly.
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+use crate::dependencies::{Dependency, Ecosystem};
+use crate::incremental::FileRecord;
+
+/// CycloneDX specification version produced
+pub const SPEC_VERSION: &str = "1.5";
+
+/// Property namespace for scanner-specific data
+const NAMESPACE: &str = "repo-scanner";
+
+/// Build the SBOM for `files` (keyed by relative path) of the repository `name`
+pub fn cyclonedx(name: &str, files: &BTreeMap<String, FileRecord>) -> Value {
+    let mut components = Vec::new();
+    let mut languages: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+
+    for (path, record) in files {
+        if let Some(language) = &record.language {
+            let entry = languages.entry(language).or_insert((0, 0));
+            entry.0 += 1;
+            entry.1 += record.lines;
+        }
+        for dependency in &record.dependencies {
+            components.push(library(path, dependency));
+        }
+        if let Some(iac) = &record.iac {
+            components.push(json!({
+                "type": "file",
+                "bom-ref": format!("file:{}", path),
+                "name": path,
+                "properties": [
+                    property("iac:framework", iac.kind.name()),
+                    property("iac:resources", &iac.resources.to_string()),
+                    property("iac:modules", &iac.modules.to_string()),
+                    property("iac:providers", &iac.providers.join(",")),
+                ],
+            }));
+        }
+    }
+
+    let language_properties: Vec<Value> = languages
+        .iter()
+        .flat_map(|(language, (files, lines))| {
+            [
+                property("language", language),
+                property(
+                    &format!("language:{}", language),
+                    &format!("files={},lines={}", files, lines),
+                ),
+            ]
+        })
+        .collect();
+    let depends_on: Vec<&Value> = components
+        .iter()
+        .filter(|c| c["type"] == "library")
+        .map(|c| &c["bom-ref"])
+        .collect();
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": SPEC_VERSION,
+        "version": 1,
+        "metadata": {
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "repo-scanner",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }],
+            },
+            "component": {
+                "type": "application",
+                "bom-ref": "root",
+                "name": name,
+                "properties": language_properties,
+            },
+        },
+        "components": components,
+        "dependencies": [{ "ref": "root", "dependsOn": depends_on }],
+    })
+}
+
+fn library(manifest: &str, dependency: &Dependency) -> Value {
+    let mut component = json!({
+        "type": "library",
+        "bom-ref": format!("{}:{}:{}", dependency.ecosystem.purl_type(), manifest, dependency.name),
+        "name": dependency.name,
+        "purl": purl(dependency),
+        "scope": if dependency.dev { "optional" } else { "required" },
+        "properties": [property("manifest", manifest)],
+    });
+    if let Some(version) = &dependency.version {
+        component["version"] = json!(version);
+    }
+    component
+}
+
+/// Package URL; the version is only included when it is exact
+fn purl(dependency: &Dependency) -> String {
+    let name = match dependency.ecosystem {
+        // npm scopes are part of the namespace, with `@` percent-encoded
+        Ecosystem::Npm => dependency.name.replace('@', "%40"),
+        _ => dependency.name.clone(),
+    };
+    let mut purl = format!("pkg:{}/{}", dependency.ecosystem.purl_type(), name);
+    if let Some(version) = dependency.version.as_deref().filter(|v| is_exact(v)) {
+        purl.push('@');
+        purl.push_str(version.trim_start_matches('='));
+    }
+    purl
+}
+
+/// `1.2.3`, `=1.2.3`, `v1.2.3`; not ranges such as `^1.2`, `~1`, `>=1, <2` or `1.2`
+fn is_exact(version: &str) -> bool {
+    let version = version.trim_start_matches('=').trim_start_matches('v');
+    let core = version.split(['-', '+']).next().unwrap_or(version);
+    core.split('.').count() == 3 && core.split('.').all(|p| p.parse::<u64>().is_ok())
+}
+
+fn property(name: &str, value: &str) -> Value {
+    json!({ "name": format!("{}:{}", NAMESPACE, name), "value": value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory;
+
+    fn record(
+        language: Option<&str>,
+        dependencies: Vec<Dependency>,
+        iac: Option<inventory::IacFile>,
+    ) -> FileRecord {
+        FileRecord {
+            language: language.map(str::to_string),
+            lines: 10,
+            findings: Vec::new(),
+            allowlisted: 0,
+            iac,
+            dependencies,
+        }
+    }
+
+    fn dependency(ecosystem: Ecosystem, name: &str, version: &str, dev: bool) -> Dependency {
+        Dependency {
+            ecosystem,
+            name: name.to_string(),
+            version: Some(version.to_string()),
+            dev,
+        }
+    }
+
+    #[test]
+    fn test_cyclonedx_document() {
+        let files: BTreeMap<String, FileRecord> = [
+            (
+                "Cargo.toml".to_string(),
+                record(
+                    Some("TOML"),
+                    vec![dependency(Ecosystem::Cargo, "serde", "1.0", false)],
+                    None,
+                ),
+            ),
+            (
+                "web/package.json".to_string(),
+                record(
+                    Some("JSON"),
+                    vec![dependency(Ecosystem::Npm, "@types/node", "20.10.0", true)],
+                    None,
+                ),
+            ),
+            (
+                "infra/main.tf".to_string(),
+                record(
+                    None,
+                    Vec::new(),
+                    inventory::classify("infra/main.tf", "resource \"aws_s3_bucket\" \"b\" {}"),
+                ),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let bom = cyclonedx("shop", &files);
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        assert_eq!(bom["specVersion"], SPEC_VERSION);
+        assert_eq!(bom["metadata"]["component"]["name"], "shop");
+
+        let components = bom["components"].as_array().unwrap();
+        assert_eq!(components.len(), 3);
+        let serde = components.iter().find(|c| c["name"] == "serde").unwrap();
+        assert_eq!(serde["purl"], "pkg:cargo/serde");
+        assert_eq!(serde["version"], "1.0");
+        assert_eq!(serde["scope"], "required");
+        let types = components
+            .iter()
+            .find(|c| c["name"] == "@types/node")
+            .unwrap();
+        assert_eq!(types["purl"], "pkg:npm/%40types/node@20.10.0");
+        assert_eq!(types["scope"], "optional");
+        let terraform = components.iter().find(|c| c["type"] == "file").unwrap();
+        assert_eq!(terraform["name"], "infra/main.tf");
+        assert_eq!(terraform["properties"][0]["value"], "terraform");
+
+        assert_eq!(
+            bom["dependencies"][0]["dependsOn"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        let languages: Vec<&Value> = bom["metadata"]["component"]["properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|p| p["name"] == "repo-scanner:language")
+            .map(|p| &p["value"])
+            .collect();
+        assert_eq!(languages, vec!["JSON", "TOML"]);
+    }
+
+    #[test]
+    fn test_exact_versions() {
+        assert!(is_exact("1.2.3"));
+        assert!(is_exact("=1.2.3"));
+        assert!(is_exact("v0.5.0"));
+        assert!(is_exact("1.0.0-beta.1"));
+        assert!(!is_exact("^1.2.3"));
+        assert!(!is_exact("1.2"));
+        assert!(!is_exact(">=1, <2"));
+    }
+}
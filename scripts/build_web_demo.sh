@@ -0,0 +1,27 @@
+#!/bin/bash
+# Build the browser package and copy it next to the static web demo
+# Usage: ./scripts/build_web_demo.sh
+#
+# Requires the wasm32-unknown-unknown target and wasm-bindgen-cli matching
+# the wasm-bindgen version in Cargo.lock. Serve examples/web-demo with any
+# static file server afterwards (e.g. `python3 -m http.server -d examples/web-demo`).
+
+set -e
+
+OUT_DIR="examples/web-demo/pkg"
+
+rustup target add wasm32-unknown-unknown
+
+# Free-edition engines only; the CLI's native-only modules are compiled out
+cargo build --lib --target wasm32-unknown-unknown --profile wasm-release \
+    --no-default-features --features wasm
+
+wasm-bindgen target/wasm32-unknown-unknown/wasm-release/costpilot.wasm \
+    --target web --out-dir "$OUT_DIR"
+
+if command -v wasm-opt &> /dev/null; then
+    wasm-opt -Oz -o "$OUT_DIR/costpilot_bg.wasm" "$OUT_DIR/costpilot_bg.wasm"
+fi
+
+echo "✅ Browser package written to $OUT_DIR"
+ls -lh "$OUT_DIR"
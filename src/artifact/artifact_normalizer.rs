@@ -61,14 +61,27 @@ impl ArtifactNormalizer {
                 // Already in correct format
                 format!("{}.{}", resource_type, id)
             }
-            ArtifactFormat::Cdk => {
-                // Convert CDK logical ID to Terraform-style
+            ArtifactFormat::Cdk | ArtifactFormat::Sam | ArtifactFormat::Serverless => {
+                // Convert CloudFormation logical ID to Terraform-style
                 // AWS::EC2::Instance -> aws_instance.MyInstance
                 format!("{}.{}", resource_type, Self::sanitize_name(id))
             }
             ArtifactFormat::Pulumi => {
                 format!("{}.{}", resource_type, id)
             }
+            ArtifactFormat::Kubernetes => {
+                // shop/deployment/web -> kubernetes_deployment.shop_web
+                let (namespace, name) = match (id.split_once('/'), id.rsplit_once('/')) {
+                    (Some((namespace, _)), Some((_, name))) => (namespace, name),
+                    _ => ("default", id),
+                };
+                format!(
+                    "{}.{}_{}",
+                    resource_type,
+                    Self::sanitize_name(namespace),
+                    Self::sanitize_name(name)
+                )
+            }
         }
     }
 
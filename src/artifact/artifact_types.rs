@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// Represents an Infrastructure as Code artifact (Terraform, CDK)
+/// Represents an Infrastructure as Code artifact (Terraform, CDK, SAM, Serverless, Kubernetes)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Artifact {
     /// Source format of the artifact
@@ -34,6 +34,15 @@ pub enum ArtifactFormat {
 
     /// Pulumi program output (future)
     Pulumi,
+
+    /// Kubernetes manifests, including rendered Helm charts
+    Kubernetes,
+
+    /// AWS SAM template
+    Sam,
+
+    /// Serverless Framework `serverless.yml`
+    Serverless,
 }
 
 impl ArtifactFormat {
@@ -43,12 +52,15 @@ impl ArtifactFormat {
             ArtifactFormat::Terraform => "Terraform",
             ArtifactFormat::Cdk => "AWS CDK",
             ArtifactFormat::Pulumi => "Pulumi",
+            ArtifactFormat::Kubernetes => "Kubernetes",
+            ArtifactFormat::Sam => "AWS SAM",
+            ArtifactFormat::Serverless => "Serverless Framework",
         }
     }
 
     /// Check if format is supported
     pub fn is_supported(&self) -> bool {
-        matches!(self, ArtifactFormat::Terraform | ArtifactFormat::Cdk)
+        !matches!(self, ArtifactFormat::Pulumi)
     }
 }
 
@@ -155,6 +167,10 @@ pub struct ArtifactOutput {
     /// Whether output is exported for cross-stack reference
     #[serde(default)]
     pub export: bool,
+
+    /// Export name (may contain `${AWS::StackName}` from `Fn::Sub`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_name: Option<String>,
 }
 
 /// Template parameter definition
@@ -319,6 +335,9 @@ mod tests {
     fn test_artifact_format_supported() {
         assert!(ArtifactFormat::Terraform.is_supported());
         assert!(ArtifactFormat::Cdk.is_supported());
+        assert!(ArtifactFormat::Kubernetes.is_supported());
+        assert!(ArtifactFormat::Sam.is_supported());
+        assert!(ArtifactFormat::Serverless.is_supported());
         assert!(!ArtifactFormat::Pulumi.is_supported());
     }
 
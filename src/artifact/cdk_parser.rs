@@ -78,6 +78,23 @@ struct CloudFormationTemplate {
     /// Resources
     #[serde(rename = "Resources", default)]
     pub resources: HashMap<String, CloudFormationResource>,
+    /// Outputs
+    #[serde(rename = "Outputs", default)]
+    pub outputs: HashMap<String, CloudFormationOutput>,
+}
+
+/// CloudFormation stack output
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+struct CloudFormationOutput {
+    /// Output value
+    #[serde(rename = "Value")]
+    pub value: serde_json::Value,
+    /// Output description
+    #[serde(rename = "Description", default)]
+    pub description: Option<String>,
+    /// Cross-stack export
+    #[serde(rename = "Export", default)]
+    pub export: Option<serde_json::Value>,
 }
 
 /// CloudFormation resource
@@ -104,9 +121,51 @@ fn parse_cloudformation_template(json_content: &str) -> ArtifactResult<CloudForm
     })
 }
 
+/// Convert template outputs, keeping the export name for cross-stack references
+fn convert_outputs(template: &CloudFormationTemplate) -> HashMap<String, ArtifactOutput> {
+    convert_output_map(&template.outputs)
+}
+
+/// Convert the `Outputs` section of an already-parsed template
+pub(super) fn template_outputs(template: &serde_json::Value) -> HashMap<String, ArtifactOutput> {
+    template
+        .get("Outputs")
+        .cloned()
+        .and_then(|outputs| serde_json::from_value(outputs).ok())
+        .map(|outputs| convert_output_map(&outputs))
+        .unwrap_or_default()
+}
+
+fn convert_output_map(
+    outputs: &HashMap<String, CloudFormationOutput>,
+) -> HashMap<String, ArtifactOutput> {
+    outputs
+        .iter()
+        .map(|(name, output)| {
+            let export_name = output
+                .export
+                .as_ref()
+                .and_then(|e| e.get("Name"))
+                .and_then(|n| {
+                    n.as_str()
+                        .or_else(|| n.get("Fn::Sub").and_then(|s| s.as_str()))
+                        .map(|s| s.to_string())
+                });
+            (
+                name.clone(),
+                ArtifactOutput {
+                    value: output.value.clone(),
+                    description: output.description.clone(),
+                    export: output.export.is_some(),
+                    export_name,
+                },
+            )
+        })
+        .collect()
+}
+
 /// Map CloudFormation resource type to Terraform-style resource type
-#[allow(dead_code)]
-fn map_cloudformation_resource_type(cf_type: &str) -> String {
+pub(super) fn map_cloudformation_resource_type(cf_type: &str) -> String {
     match cf_type {
         "AWS::EC2::Instance" => "aws_instance".to_string(),
         "AWS::EC2::LaunchTemplate" => "aws_launch_template".to_string(),
@@ -293,7 +352,7 @@ impl CdkParser {
                 tags: HashMap::new(),
             },
             resources,
-            outputs: HashMap::new(),
+            outputs: convert_outputs(&template),
             parameters: HashMap::new(),
         })
     }
@@ -524,7 +583,7 @@ impl ArtifactParser for CdkParser {
                 tags: HashMap::new(),
             },
             resources,
-            outputs: HashMap::new(),
+            outputs: convert_outputs(&template),
             parameters: HashMap::new(),
         })
     }
@@ -0,0 +1,387 @@
+use super::artifact_types::*;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Workload kinds that schedule long-running pods, with their resource type
+const WORKLOAD_KINDS: &[(&str, &str)] = &[
+    ("Deployment", "kubernetes_deployment"),
+    ("StatefulSet", "kubernetes_stateful_set"),
+    ("DaemonSet", "kubernetes_daemonset"),
+    ("ReplicaSet", "kubernetes_replica_set"),
+    ("Pod", "kubernetes_pod"),
+];
+
+/// Label Helm sets on chart resources (`<chart>-<version>`)
+const HELM_CHART_LABEL: &str = "helm.sh/chart";
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Parser for Kubernetes manifests and `helm template` output
+///
+/// Each workload becomes one resource whose properties summarize what its
+/// pods request per replica: `cpu_request` in cores and `memory_request_gib`,
+/// falling back to limits like the API server does. Containers with neither
+/// are counted in `containers_without_requests`.
+pub struct KubernetesParser;
+
+impl KubernetesParser {
+    /// Create a new Kubernetes parser
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Split multi-document YAML into objects, expanding `kind: List`
+    fn documents(content: &str) -> ArtifactResult<Vec<Value>> {
+        let mut objects = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(content) {
+            let value = Value::deserialize(document).map_err(|e| {
+                ArtifactError::ParseError(format!("Failed to parse Kubernetes YAML: {}", e))
+            })?;
+            match value.get("kind").and_then(Value::as_str) {
+                Some("List") => objects.extend(
+                    value
+                        .get("items")
+                        .and_then(Value::as_array)
+                        .cloned()
+                        .unwrap_or_default(),
+                ),
+                Some(_) => objects.push(value),
+                None if value.is_null() => {}
+                None => {
+                    return Err(ArtifactError::MissingField(
+                        "kind (not a Kubernetes manifest)".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(objects)
+    }
+
+    fn workload(object: &Value) -> Option<ArtifactResource> {
+        let kind = object.get("kind")?.as_str()?;
+        let (_, resource_type) = WORKLOAD_KINDS.iter().find(|(k, _)| *k == kind)?;
+
+        let metadata = object.get("metadata");
+        let name = metadata?.get("name")?.as_str()?;
+        let namespace = metadata
+            .and_then(|m| m.get("namespace"))
+            .and_then(Value::as_str)
+            .unwrap_or("default");
+
+        let spec = object.get("spec");
+        let pod_spec = if kind == "Pod" {
+            spec
+        } else {
+            spec.and_then(|s| s.get("template"))
+                .and_then(|t| t.get("spec"))
+        }?;
+
+        let mut properties = HashMap::new();
+        properties.insert("name".to_string(), json!(name));
+        properties.insert("namespace".to_string(), json!(namespace));
+        properties.insert("kind".to_string(), json!(kind));
+        match kind {
+            // One pod per node; the pricing model decides how many nodes
+            "DaemonSet" => {}
+            "Pod" => {
+                properties.insert("replicas".to_string(), json!(1));
+            }
+            _ => {
+                let replicas = spec
+                    .and_then(|s| s.get("replicas"))
+                    .and_then(Value::as_u64)
+                    .unwrap_or(1);
+                properties.insert("replicas".to_string(), json!(replicas));
+            }
+        }
+
+        let usage = PodResources::from_pod_spec(pod_spec);
+        properties.insert("cpu_request".to_string(), json!(usage.cpu_request));
+        properties.insert(
+            "memory_request_gib".to_string(),
+            json!(usage.memory_request_gib),
+        );
+        if let Some(cpu) = usage.cpu_limit {
+            properties.insert("cpu_limit".to_string(), json!(cpu));
+        }
+        if let Some(memory) = usage.memory_limit_gib {
+            properties.insert("memory_limit_gib".to_string(), json!(memory));
+        }
+        properties.insert(
+            "containers_without_requests".to_string(),
+            json!(usage.containers_without_requests),
+        );
+        if let Some(Value::Object(selector)) = pod_spec.get("nodeSelector") {
+            properties.insert("node_selector".to_string(), Value::Object(selector.clone()));
+        }
+
+        let mut resource_metadata = HashMap::new();
+        if let Some(chart) = metadata
+            .and_then(|m| m.get("labels"))
+            .and_then(|l| l.get(HELM_CHART_LABEL))
+            .and_then(Value::as_str)
+        {
+            resource_metadata.insert("helm_chart".to_string(), chart.to_string());
+        }
+
+        Some(ArtifactResource {
+            id: format!("{}/{}/{}", namespace, kind.to_lowercase(), name),
+            resource_type: resource_type.to_string(),
+            properties,
+            depends_on: Vec::new(),
+            metadata: resource_metadata,
+        })
+    }
+}
+
+impl Default for KubernetesParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArtifactParser for KubernetesParser {
+    fn parse(&self, content: &str) -> ArtifactResult<Artifact> {
+        let objects = Self::documents(content)?;
+
+        let mut artifact = Artifact::new(
+            ArtifactFormat::Kubernetes,
+            ArtifactMetadata {
+                source: "kubernetes".to_string(),
+                version: None,
+                stack_name: None,
+                region: None,
+                tags: HashMap::new(),
+            },
+        );
+        for object in &objects {
+            if let Some(resource) = Self::workload(object) {
+                artifact.add_resource(resource);
+            }
+        }
+        Ok(artifact)
+    }
+
+    fn format(&self) -> ArtifactFormat {
+        ArtifactFormat::Kubernetes
+    }
+}
+
+/// Per-replica requests and limits of a pod spec
+#[derive(Debug, Default, PartialEq)]
+struct PodResources {
+    cpu_request: f64,
+    memory_request_gib: f64,
+    cpu_limit: Option<f64>,
+    memory_limit_gib: Option<f64>,
+    containers_without_requests: u64,
+}
+
+impl PodResources {
+    /// Effective requests: the larger of the app containers' sum and the
+    /// largest init container, as the scheduler computes them
+    fn from_pod_spec(pod_spec: &Value) -> Self {
+        let containers = |key: &str| {
+            pod_spec
+                .get(key)
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let mut usage = Self::default();
+        for container in containers("containers") {
+            let resources = container.get("resources");
+            let requests = resources.and_then(|r| r.get("requests"));
+            let limits = resources.and_then(|r| r.get("limits"));
+            let quantity = |key: &str, parse: fn(&Value) -> Option<f64>| {
+                requests
+                    .and_then(|r| r.get(key))
+                    .or_else(|| limits.and_then(|l| l.get(key)))
+                    .and_then(parse)
+            };
+
+            let cpu = quantity("cpu", parse_cpu);
+            let memory = quantity("memory", parse_memory_gib);
+            if cpu.is_none() && memory.is_none() {
+                usage.containers_without_requests += 1;
+            }
+            usage.cpu_request += cpu.unwrap_or(0.0);
+            usage.memory_request_gib += memory.unwrap_or(0.0);
+
+            if let Some(cpu) = limits.and_then(|l| l.get("cpu")).and_then(parse_cpu) {
+                *usage.cpu_limit.get_or_insert(0.0) += cpu;
+            }
+            if let Some(memory) = limits
+                .and_then(|l| l.get("memory"))
+                .and_then(parse_memory_gib)
+            {
+                *usage.memory_limit_gib.get_or_insert(0.0) += memory;
+            }
+        }
+
+        for container in containers("initContainers") {
+            let requests = container.get("resources").and_then(|r| r.get("requests"));
+            if let Some(cpu) = requests.and_then(|r| r.get("cpu")).and_then(parse_cpu) {
+                usage.cpu_request = usage.cpu_request.max(cpu);
+            }
+            if let Some(memory) = requests
+                .and_then(|r| r.get("memory"))
+                .and_then(parse_memory_gib)
+            {
+                usage.memory_request_gib = usage.memory_request_gib.max(memory);
+            }
+        }
+
+        usage
+    }
+}
+
+/// CPU quantity in cores (`500m`, `0.5`, `2`)
+pub fn parse_cpu(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => match s.strip_suffix('m') {
+            Some(millicores) => millicores.parse::<f64>().ok().map(|m| m / 1000.0),
+            None => s.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+/// Memory quantity in GiB (`512Mi`, `1Gi`, `1G`, plain bytes)
+pub fn parse_memory_gib(value: &Value) -> Option<f64> {
+    const SUFFIXES: &[(&str, f64)] = &[
+        ("Ki", 1024.0),
+        ("Mi", 1024.0 * 1024.0),
+        ("Gi", GIB),
+        ("Ti", GIB * 1024.0),
+        ("k", 1e3),
+        ("K", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+    ];
+
+    let bytes = match value {
+        Value::Number(n) => n.as_f64()?,
+        Value::String(s) => {
+            let (number, multiplier) = SUFFIXES
+                .iter()
+                .find_map(|(suffix, multiplier)| {
+                    s.strip_suffix(suffix).map(|number| (number, *multiplier))
+                })
+                .unwrap_or((s.as_str(), 1.0));
+            number.parse::<f64>().ok()? * multiplier
+        }
+        _ => return None,
+    };
+    Some(bytes / GIB)
+}
+
+/// Whether `content` looks like Kubernetes YAML rather than another artifact
+pub fn is_kubernetes_manifest(content: &str) -> bool {
+    let top_level = |key: &str| content.lines().any(|line| line.starts_with(key));
+    top_level("apiVersion:") && top_level("kind:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+# Source: web/templates/deployment.yaml
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web
+  namespace: shop
+  labels:
+    helm.sh/chart: web-1.2.0
+spec:
+  replicas: 3
+  template:
+    spec:
+      nodeSelector:
+        eks.amazonaws.com/nodegroup: general
+      initContainers:
+        - name: migrate
+          resources:
+            requests:
+              memory: 2Gi
+      containers:
+        - name: app
+          resources:
+            requests:
+              cpu: 500m
+              memory: 512Mi
+            limits:
+              cpu: "1"
+              memory: 1Gi
+        - name: sidecar
+          resources:
+            limits:
+              cpu: 250m
+              memory: 256Mi
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: web
+---
+apiVersion: apps/v1
+kind: DaemonSet
+metadata:
+  name: log-agent
+spec:
+  template:
+    spec:
+      containers:
+        - name: agent
+"#;
+
+    #[test]
+    fn test_parse_workloads_from_rendered_chart() {
+        let artifact = KubernetesParser::new().parse(MANIFEST).unwrap();
+        assert_eq!(artifact.format, ArtifactFormat::Kubernetes);
+        assert_eq!(artifact.resources.len(), 2);
+
+        let web = artifact.get_resource("shop/deployment/web").unwrap();
+        assert_eq!(web.resource_type, "kubernetes_deployment");
+        assert_eq!(web.properties["replicas"], 3);
+        assert_eq!(web.properties["cpu_request"], 0.75);
+        // The migrate init container needs more memory than both app containers
+        assert_eq!(web.properties["memory_request_gib"], 2.0);
+        assert_eq!(web.properties["cpu_limit"], 1.25);
+        assert_eq!(web.properties["containers_without_requests"], 0);
+        assert_eq!(
+            web.properties["node_selector"]["eks.amazonaws.com/nodegroup"],
+            "general"
+        );
+        assert_eq!(web.metadata["helm_chart"], "web-1.2.0");
+
+        let agent = artifact
+            .get_resource("default/daemonset/log-agent")
+            .unwrap();
+        assert!(!agent.properties.contains_key("replicas"));
+        assert_eq!(agent.properties["containers_without_requests"], 1);
+    }
+
+    #[test]
+    fn test_quantities() {
+        assert_eq!(parse_cpu(&json!("250m")), Some(0.25));
+        assert_eq!(parse_cpu(&json!(2)), Some(2.0));
+        assert_eq!(parse_memory_gib(&json!("512Mi")), Some(0.5));
+        assert_eq!(parse_memory_gib(&json!(1073741824)), Some(1.0));
+        assert!((parse_memory_gib(&json!("1G")).unwrap() - 0.931).abs() < 0.001);
+        assert_eq!(parse_memory_gib(&json!("lots")), None);
+    }
+
+    #[test]
+    fn test_manifest_detection() {
+        assert!(is_kubernetes_manifest(MANIFEST));
+        assert!(!is_kubernetes_manifest("version: 1\nbudgets: {}\n"));
+        assert!(KubernetesParser::new().parse("budgets: {}\n").is_err());
+    }
+}
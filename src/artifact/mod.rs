@@ -3,10 +3,16 @@
 mod artifact_normalizer;
 mod artifact_types;
 mod cdk_parser;
+mod kubernetes_parser;
+mod sam_parser;
+mod serverless_parser;
 
 pub use artifact_normalizer::*;
 pub use artifact_types::*;
 pub use cdk_parser::*;
+pub use kubernetes_parser::*;
+pub use sam_parser::*;
+pub use serverless_parser::*;
 
 /// Parse an artifact from a file, auto-detecting the format
 pub fn parse_artifact_file(path: &str) -> ArtifactResult<Artifact> {
@@ -23,6 +29,14 @@ pub fn parse_artifact(content: &str, hint: &str) -> ArtifactResult<Artifact> {
         return parser.parse(content);
     }
 
+    if hint.ends_with("serverless.yml") || hint.ends_with("serverless.yaml") {
+        return ServerlessParser::new().parse(content);
+    }
+
+    if content.contains(SAM_TRANSFORM) {
+        return SamParser::new().parse(content);
+    }
+
     if hint.ends_with(".json") {
         // Try CDK first for JSON files, then fall back to others
         let parser = CdkParser::new();
@@ -31,6 +45,10 @@ pub fn parse_artifact(content: &str, hint: &str) -> ArtifactResult<Artifact> {
         }
     }
 
+    if (hint.ends_with(".yaml") || hint.ends_with(".yml")) && is_kubernetes_manifest(content) {
+        return KubernetesParser::new().parse(content);
+    }
+
     Err(ArtifactError::UnsupportedFormat(
         "Could not detect artifact format".to_string(),
     ))
@@ -0,0 +1,549 @@
+use super::artifact_types::*;
+use super::cdk_parser::{map_cloudformation_resource_type, template_outputs};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Transform that marks a CloudFormation template as AWS SAM
+pub const SAM_TRANSFORM: &str = "AWS::Serverless-2016-10-31";
+
+/// Lambda defaults applied by SAM when a function leaves them unset
+const SAM_DEFAULT_MEMORY_MB: u64 = 128;
+const SAM_DEFAULT_TIMEOUT_SECONDS: u64 = 3;
+
+/// Parser for AWS SAM templates (YAML or JSON)
+///
+/// `AWS::Serverless::*` resources are expanded the way the SAM transform
+/// would: functions become Lambda functions with `Globals` applied, and their
+/// events add the implicit APIs, schedule rules and event source mappings
+/// that carry cost. Plain CloudFormation resources are kept as they are.
+pub struct SamParser;
+
+impl SamParser {
+    /// Create a new SAM parser
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn function(
+        logical_id: &str,
+        resource: &Value,
+        globals: &Value,
+        artifact: &mut Artifact,
+    ) -> ArtifactResource {
+        let mut properties = merged_properties(resource, globals.get("Function"));
+        properties
+            .entry("MemorySize".to_string())
+            .or_insert(json!(SAM_DEFAULT_MEMORY_MB));
+        properties
+            .entry("Timeout".to_string())
+            .or_insert(json!(SAM_DEFAULT_TIMEOUT_SECONDS));
+        properties
+            .entry("Architectures".to_string())
+            .or_insert(json!(["x86_64"]));
+        if properties.contains_key("ImageUri") {
+            properties.insert("PackageType".to_string(), json!("Image"));
+        }
+        if let Some(provisioned) = properties
+            .get("ProvisionedConcurrencyConfig")
+            .and_then(|c| c.get("ProvisionedConcurrentExecutions"))
+            .cloned()
+        {
+            properties.insert("ProvisionedConcurrentExecutions".to_string(), provisioned);
+        }
+
+        let events = properties
+            .remove("Events")
+            .and_then(|e| e.as_object().cloned())
+            .unwrap_or_default();
+        let mut event_types = Vec::new();
+        for (event_name, event) in &events {
+            let event_type = event.get("Type").and_then(Value::as_str).unwrap_or("");
+            let event_properties = event.get("Properties").cloned().unwrap_or(Value::Null);
+            event_types.push(json!(event_type));
+
+            match event_type {
+                // Events without an explicit API attach to the implicit one
+                "Api" if event_properties.get("RestApiId").is_none() => {
+                    add_once(
+                        artifact,
+                        "ServerlessRestApi",
+                        "aws_api_gateway_rest_api",
+                        json!({ "EndpointConfiguration": "EDGE" }),
+                    );
+                }
+                "HttpApi" if event_properties.get("ApiId").is_none() => {
+                    add_once(
+                        artifact,
+                        "ServerlessHttpApi",
+                        "aws_apigatewayv2_api",
+                        json!({ "ProtocolType": "HTTP" }),
+                    );
+                }
+                "Schedule" | "ScheduleV2" | "EventBridgeRule" | "CloudWatchEvent" => {
+                    artifact.add_resource(event_resource(
+                        logical_id,
+                        event_name,
+                        "aws_cloudwatch_event_rule",
+                        &event_properties,
+                    ));
+                }
+                "SQS" | "Kinesis" | "DynamoDB" | "MSK" | "MQ" => {
+                    let mut mapping = event_resource(
+                        logical_id,
+                        event_name,
+                        "aws_lambda_event_source_mapping",
+                        &event_properties,
+                    );
+                    mapping
+                        .properties
+                        .insert("FunctionName".to_string(), json!({ "Ref": logical_id }));
+                    mapping.depends_on.push(logical_id.to_string());
+                    artifact.add_resource(mapping);
+                }
+                _ => {}
+            }
+        }
+        if !event_types.is_empty() {
+            properties.insert("EventSources".to_string(), Value::Array(event_types));
+        }
+
+        ArtifactResource {
+            id: logical_id.to_string(),
+            resource_type: "aws_lambda_function".to_string(),
+            metadata: tags_metadata(properties.get("Tags")),
+            properties,
+            depends_on: depends_on(resource),
+        }
+    }
+
+    fn serverless_resource(
+        logical_id: &str,
+        resource_type: &str,
+        resource: &Value,
+        globals: &Value,
+    ) -> Option<ArtifactResource> {
+        let (terraform_type, globals_key) = match resource_type {
+            "AWS::Serverless::Api" => ("aws_api_gateway_rest_api", Some("Api")),
+            "AWS::Serverless::HttpApi" => ("aws_apigatewayv2_api", Some("HttpApi")),
+            "AWS::Serverless::SimpleTable" => ("aws_dynamodb_table", Some("SimpleTable")),
+            "AWS::Serverless::StateMachine" => ("aws_sfn_state_machine", None),
+            "AWS::Serverless::LayerVersion" => ("aws_lambda_layer_version", Some("LayerVersion")),
+            // Connectors only generate IAM policies
+            "AWS::Serverless::Connector" => return None,
+            _ => return Some(cloudformation_resource(logical_id, resource)),
+        };
+
+        let mut properties =
+            merged_properties(resource, globals_key.and_then(|key| globals.get(key)));
+        match resource_type {
+            "AWS::Serverless::HttpApi" => {
+                properties.insert("ProtocolType".to_string(), json!("HTTP"));
+            }
+            "AWS::Serverless::SimpleTable" => {
+                let billing_mode = if properties.contains_key("ProvisionedThroughput") {
+                    "PROVISIONED"
+                } else {
+                    "PAY_PER_REQUEST"
+                };
+                properties.insert("BillingMode".to_string(), json!(billing_mode));
+            }
+            _ => {}
+        }
+
+        Some(ArtifactResource {
+            id: logical_id.to_string(),
+            resource_type: terraform_type.to_string(),
+            metadata: tags_metadata(properties.get("Tags")),
+            properties,
+            depends_on: depends_on(resource),
+        })
+    }
+}
+
+impl Default for SamParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArtifactParser for SamParser {
+    fn parse(&self, content: &str) -> ArtifactResult<Artifact> {
+        let template = parse_cloudformation_yaml(content)?;
+        if !is_sam_template(&template) {
+            return Err(ArtifactError::MissingField(format!(
+                "Transform: {} (not a SAM template)",
+                SAM_TRANSFORM
+            )));
+        }
+
+        let mut artifact = Artifact::new(
+            ArtifactFormat::Sam,
+            ArtifactMetadata {
+                source: "sam-template".to_string(),
+                version: template
+                    .get("AWSTemplateFormatVersion")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                stack_name: None,
+                region: None,
+                tags: HashMap::new(),
+            },
+        );
+
+        let globals = template.get("Globals").cloned().unwrap_or(Value::Null);
+        let resources = template
+            .get("Resources")
+            .and_then(Value::as_object)
+            .ok_or_else(|| ArtifactError::MissingField("Resources".to_string()))?;
+        for (logical_id, resource) in resources {
+            let resource_type = resource
+                .get("Type")
+                .and_then(Value::as_str)
+                .ok_or_else(|| ArtifactError::MissingField(format!("{}.Type", logical_id)))?;
+            let converted = if resource_type == "AWS::Serverless::Function" {
+                Some(Self::function(
+                    logical_id,
+                    resource,
+                    &globals,
+                    &mut artifact,
+                ))
+            } else {
+                Self::serverless_resource(logical_id, resource_type, resource, &globals)
+            };
+            if let Some(converted) = converted {
+                artifact.add_resource(converted);
+            }
+        }
+
+        artifact.outputs = template_outputs(&template);
+
+        Ok(artifact)
+    }
+
+    fn format(&self) -> ArtifactFormat {
+        ArtifactFormat::Sam
+    }
+}
+
+/// Whether a parsed template declares the SAM transform
+pub fn is_sam_template(template: &Value) -> bool {
+    match template.get("Transform") {
+        Some(Value::String(transform)) => transform == SAM_TRANSFORM,
+        Some(Value::Array(transforms)) => transforms.iter().any(|t| t == SAM_TRANSFORM),
+        _ => false,
+    }
+}
+
+/// Parse a CloudFormation template in YAML or JSON, expanding short-form
+/// intrinsic tags (`!Ref`, `!GetAtt`, `!Sub`, ...) to their long form
+pub fn parse_cloudformation_yaml(content: &str) -> ArtifactResult<Value> {
+    let value: serde_yaml::Value = serde_yaml::from_str(content)
+        .map_err(|e| ArtifactError::ParseError(format!("Failed to parse template: {}", e)))?;
+    Ok(yaml_to_json(value))
+}
+
+fn yaml_to_json(value: serde_yaml::Value) -> Value {
+    match value {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Bool(b),
+        serde_yaml::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                json!(i)
+            } else if let Some(u) = n.as_u64() {
+                json!(u)
+            } else {
+                json!(n.as_f64())
+            }
+        }
+        serde_yaml::Value::String(s) => Value::String(s),
+        serde_yaml::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yaml::Value::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match yaml_to_json(key) {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (key, yaml_to_json(value))
+                })
+                .collect(),
+        ),
+        serde_yaml::Value::Tagged(tagged) => {
+            let tag = tagged.tag.to_string();
+            let name = tag.trim_start_matches('!');
+            let inner = yaml_to_json(tagged.value);
+            match name {
+                "Ref" | "Condition" => json!({ name: inner }),
+                // !GetAtt Resource.Attribute
+                "GetAtt" => match inner {
+                    Value::String(s) => {
+                        let parts: Vec<&str> = s.splitn(2, '.').collect();
+                        json!({ "Fn::GetAtt": parts })
+                    }
+                    other => json!({ "Fn::GetAtt": other }),
+                },
+                _ => json!({ format!("Fn::{}", name): inner }),
+            }
+        }
+    }
+}
+
+/// Convert a plain CloudFormation resource, keeping its properties
+pub(super) fn cloudformation_resource(logical_id: &str, resource: &Value) -> ArtifactResource {
+    let resource_type = resource.get("Type").and_then(Value::as_str).unwrap_or("");
+    let properties: HashMap<String, Value> = resource
+        .get("Properties")
+        .and_then(Value::as_object)
+        .map(|p| p.clone().into_iter().collect())
+        .unwrap_or_default();
+    ArtifactResource {
+        id: logical_id.to_string(),
+        resource_type: map_cloudformation_resource_type(resource_type),
+        metadata: tags_metadata(properties.get("Tags")),
+        properties,
+        depends_on: depends_on(resource),
+    }
+}
+
+/// Resource properties with the matching `Globals` section underneath
+fn merged_properties(resource: &Value, globals: Option<&Value>) -> HashMap<String, Value> {
+    let mut properties: HashMap<String, Value> = globals
+        .and_then(Value::as_object)
+        .map(|g| g.clone().into_iter().collect())
+        .unwrap_or_default();
+    if let Some(own) = resource.get("Properties").and_then(Value::as_object) {
+        for (key, value) in own {
+            match (properties.get_mut(key), value) {
+                // Maps such as Environment.Variables and Tags merge key by key
+                (Some(Value::Object(global)), Value::Object(local)) => {
+                    merge_objects(global, local);
+                }
+                _ => {
+                    properties.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+    properties
+}
+
+fn merge_objects(base: &mut Map<String, Value>, overlay: &Map<String, Value>) {
+    for (key, value) in overlay {
+        match (base.get_mut(key), value) {
+            (Some(Value::Object(base)), Value::Object(overlay)) => merge_objects(base, overlay),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Resource generated for a function event, named as SAM names it
+fn event_resource(
+    function_id: &str,
+    event_name: &str,
+    resource_type: &str,
+    properties: &Value,
+) -> ArtifactResource {
+    let properties: HashMap<String, Value> = properties
+        .as_object()
+        .map(|p| p.clone().into_iter().collect())
+        .unwrap_or_default();
+    let depends_on = properties.values().flat_map(referenced_ids).collect();
+    ArtifactResource {
+        id: format!("{}{}", function_id, event_name),
+        resource_type: resource_type.to_string(),
+        properties,
+        depends_on,
+        metadata: HashMap::new(),
+    }
+}
+
+/// Add an implicit resource unless an earlier function already created it
+fn add_once(artifact: &mut Artifact, id: &str, resource_type: &str, properties: Value) {
+    if artifact.get_resource(id).is_some() {
+        return;
+    }
+    artifact.add_resource(ArtifactResource {
+        id: id.to_string(),
+        resource_type: resource_type.to_string(),
+        properties: properties
+            .as_object()
+            .map(|p| p.clone().into_iter().collect())
+            .unwrap_or_default(),
+        depends_on: Vec::new(),
+        metadata: HashMap::new(),
+    });
+}
+
+/// Logical IDs named by `Ref` or `Fn::GetAtt` in a value
+fn referenced_ids(value: &Value) -> Vec<String> {
+    match value {
+        Value::Object(obj) => {
+            if let Some(Value::String(id)) = obj.get("Ref") {
+                return vec![id.clone()];
+            }
+            if let Some(id) = obj
+                .get("Fn::GetAtt")
+                .and_then(|v| v.get(0))
+                .and_then(Value::as_str)
+            {
+                return vec![id.to_string()];
+            }
+            obj.values().flat_map(referenced_ids).collect()
+        }
+        Value::Array(items) => items.iter().flat_map(referenced_ids).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn depends_on(resource: &Value) -> Vec<String> {
+    match resource.get("DependsOn") {
+        Some(Value::String(id)) => vec![id.clone()],
+        Some(Value::Array(ids)) => ids
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Tags as `tag:<key>` metadata, from a SAM map or a CloudFormation list
+pub(super) fn tags_metadata(tags: Option<&Value>) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    match tags {
+        Some(Value::Object(tags)) => {
+            for (key, value) in tags {
+                if let Some(value) = value.as_str() {
+                    metadata.insert(format!("tag:{}", key), value.to_string());
+                }
+            }
+        }
+        Some(Value::Array(tags)) => {
+            for tag in tags {
+                if let (Some(key), Some(value)) = (
+                    tag.get("Key").and_then(Value::as_str),
+                    tag.get("Value").and_then(Value::as_str),
+                ) {
+                    metadata.insert(format!("tag:{}", key), value.to_string());
+                }
+            }
+        }
+        _ => {}
+    }
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"
+AWSTemplateFormatVersion: "2010-09-09"
+Transform: AWS::Serverless-2016-10-31
+Globals:
+  Function:
+    Runtime: python3.12
+    MemorySize: 512
+    Tags:
+      team: orders
+Resources:
+  OrdersQueue:
+    Type: AWS::SQS::Queue
+  CreateOrder:
+    Type: AWS::Serverless::Function
+    Properties:
+      Handler: app.create
+      Timeout: 10
+      Events:
+        Api:
+          Type: Api
+          Properties:
+            Path: /orders
+            Method: post
+        Queue:
+          Type: SQS
+          Properties:
+            Queue: !GetAtt OrdersQueue.Arn
+        Nightly:
+          Type: Schedule
+          Properties:
+            Schedule: rate(1 day)
+  ListOrders:
+    Type: AWS::Serverless::Function
+    Properties:
+      Handler: app.list
+      MemorySize: 256
+      Events:
+        Api:
+          Type: Api
+          Properties:
+            Path: /orders
+            Method: get
+  OrdersTable:
+    Type: AWS::Serverless::SimpleTable
+"#;
+
+    #[test]
+    fn test_expand_functions_and_events() {
+        let artifact = SamParser::new().parse(TEMPLATE).unwrap();
+        assert_eq!(artifact.format, ArtifactFormat::Sam);
+
+        let create = artifact.get_resource("CreateOrder").unwrap();
+        assert_eq!(create.resource_type, "aws_lambda_function");
+        assert_eq!(create.properties["MemorySize"], 512);
+        assert_eq!(create.properties["Timeout"], 10);
+        assert_eq!(create.properties["Runtime"], "python3.12");
+        assert_eq!(create.metadata["tag:team"], "orders");
+        assert_eq!(
+            artifact.get_resource("ListOrders").unwrap().properties["MemorySize"],
+            256
+        );
+
+        // Both Api events share the one implicit REST API
+        assert_eq!(
+            artifact
+                .get_resources_by_type("aws_api_gateway_rest_api")
+                .len(),
+            1
+        );
+        let mapping = artifact.get_resource("CreateOrderQueue").unwrap();
+        assert_eq!(mapping.resource_type, "aws_lambda_event_source_mapping");
+        assert!(mapping.depends_on.contains(&"OrdersQueue".to_string()));
+        assert!(artifact.get_resource("CreateOrderNightly").is_some());
+
+        assert_eq!(
+            artifact.get_resource("OrdersQueue").unwrap().resource_type,
+            "aws_sqs_queue"
+        );
+        assert_eq!(
+            artifact.get_resource("OrdersTable").unwrap().properties["BillingMode"],
+            "PAY_PER_REQUEST"
+        );
+    }
+
+    #[test]
+    fn test_normalizes_to_resource_changes() {
+        let artifact = SamParser::new().parse(TEMPLATE).unwrap();
+        let changes =
+            crate::artifact::ArtifactNormalizer::normalize(&artifact).to_resource_changes();
+        let create = changes
+            .iter()
+            .find(|c| c.resource_id == "CreateOrder")
+            .unwrap();
+        let config = create.new_config.as_ref().unwrap();
+        assert_eq!(config["memory_size"], 512);
+        assert_eq!(config["timeout"], 10);
+    }
+
+    #[test]
+    fn test_requires_sam_transform() {
+        let plain = "Resources:\n  Queue:\n    Type: AWS::SQS::Queue\n";
+        assert!(SamParser::new().parse(plain).is_err());
+        assert!(SamParser::new().parse("{ not yaml: [").is_err());
+    }
+}
@@ -0,0 +1,484 @@
+use super::artifact_types::*;
+use super::sam_parser::{cloudformation_resource, parse_cloudformation_yaml, tags_metadata};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Provider defaults the Serverless Framework applies to AWS functions
+const DEFAULT_MEMORY_MB: u64 = 1024;
+const DEFAULT_TIMEOUT_SECONDS: u64 = 6;
+const DEFAULT_STAGE: &str = "dev";
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// Variable references are resolved at most this deep (`${self:a}` -> `${self:b}` ...)
+const MAX_VARIABLE_DEPTH: usize = 8;
+
+/// Parser for Serverless Framework `serverless.yml`
+///
+/// Functions become Lambda functions with provider-level settings applied,
+/// `http`, `httpApi` and `websocket` events add the shared APIs the framework
+/// creates, and `schedule`, `sqs` and `stream` events add their rules and
+/// event source mappings. Raw CloudFormation under `resources.Resources` is
+/// included as well. `${self:...}`, `${sls:stage}` and defaults of
+/// `${opt:...}`/`${env:...}` references are resolved; other variables are left
+/// as written.
+pub struct ServerlessParser;
+
+impl ServerlessParser {
+    /// Create a new Serverless Framework parser
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn function(
+        name: &str,
+        function: &Value,
+        provider: &Value,
+        prefix: &str,
+        artifact: &mut Artifact,
+    ) -> ArtifactResource {
+        let setting = |key: &str| function.get(key).or_else(|| provider.get(key)).cloned();
+        let logical_id = format!("{}LambdaFunction", normalize_name(name));
+
+        let mut properties = HashMap::new();
+        properties.insert(
+            "FunctionName".to_string(),
+            function
+                .get("name")
+                .cloned()
+                .unwrap_or_else(|| json!(format!("{}-{}", prefix, name))),
+        );
+        properties.insert(
+            "MemorySize".to_string(),
+            setting("memorySize").unwrap_or(json!(DEFAULT_MEMORY_MB)),
+        );
+        properties.insert(
+            "Timeout".to_string(),
+            setting("timeout").unwrap_or(json!(DEFAULT_TIMEOUT_SECONDS)),
+        );
+        properties.insert(
+            "Architectures".to_string(),
+            json!([setting("architecture").unwrap_or(json!("x86_64"))]),
+        );
+        if let Some(runtime) = setting("runtime") {
+            properties.insert("Runtime".to_string(), runtime);
+        }
+        if let Some(handler) = function.get("handler") {
+            properties.insert("Handler".to_string(), handler.clone());
+        }
+        if function.get("image").is_some() {
+            properties.insert("PackageType".to_string(), json!("Image"));
+        }
+        for (key, property) in [
+            ("reservedConcurrency", "ReservedConcurrentExecutions"),
+            ("provisionedConcurrency", "ProvisionedConcurrentExecutions"),
+            ("ephemeralStorageSize", "EphemeralStorage"),
+        ] {
+            if let Some(value) = function.get(key) {
+                properties.insert(property.to_string(), value.clone());
+            }
+        }
+
+        let mut tags = provider.get("tags").cloned().unwrap_or(json!({}));
+        if let (Some(tags), Some(own)) = (
+            tags.as_object_mut(),
+            function.get("tags").and_then(Value::as_object),
+        ) {
+            tags.extend(own.clone());
+        }
+
+        let events = function
+            .get("events")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let mut event_types = Vec::new();
+        for (index, event) in events.iter().enumerate() {
+            let Some((event_type, settings)) = event.as_object().and_then(|e| e.iter().next())
+            else {
+                continue;
+            };
+            event_types.push(json!(event_type));
+            let event_id = |kind: &str| format!("{}{}{}", normalize_name(name), kind, index + 1);
+
+            match event_type.as_str() {
+                "http" => add_once(
+                    artifact,
+                    "ApiGatewayRestApi",
+                    "aws_api_gateway_rest_api",
+                    json!({}),
+                ),
+                "httpApi" => add_once(
+                    artifact,
+                    "HttpApi",
+                    "aws_apigatewayv2_api",
+                    json!({ "ProtocolType": "HTTP" }),
+                ),
+                "websocket" => add_once(
+                    artifact,
+                    "WebsocketsApi",
+                    "aws_apigatewayv2_api",
+                    json!({ "ProtocolType": "WEBSOCKET" }),
+                ),
+                "schedule" | "eventBridge" => {
+                    let properties = match settings {
+                        Value::String(rate) => json!({ "ScheduleExpression": rate }),
+                        other => other.clone(),
+                    };
+                    artifact.add_resource(event_resource(
+                        event_id("EventsRule"),
+                        "aws_cloudwatch_event_rule",
+                        properties,
+                        Vec::new(),
+                    ));
+                }
+                "sqs" | "stream" | "kafka" | "activemq" | "rabbitmq" => {
+                    let source = match settings {
+                        Value::Object(settings) => settings
+                            .get("arn")
+                            .or_else(|| settings.get("queue"))
+                            .cloned()
+                            .unwrap_or(Value::Null),
+                        other => other.clone(),
+                    };
+                    let mut depends_on = referenced_id(&source).into_iter().collect::<Vec<_>>();
+                    depends_on.push(logical_id.clone());
+                    artifact.add_resource(event_resource(
+                        event_id("EventSourceMapping"),
+                        "aws_lambda_event_source_mapping",
+                        json!({
+                            "EventSourceArn": source,
+                            "FunctionName": { "Ref": logical_id },
+                        }),
+                        depends_on,
+                    ));
+                }
+                _ => {}
+            }
+        }
+        if !event_types.is_empty() {
+            properties.insert("EventSources".to_string(), Value::Array(event_types));
+        }
+
+        ArtifactResource {
+            metadata: tags_metadata(Some(&tags)),
+            id: logical_id,
+            resource_type: "aws_lambda_function".to_string(),
+            properties,
+            depends_on: Vec::new(),
+        }
+    }
+}
+
+impl Default for ServerlessParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArtifactParser for ServerlessParser {
+    fn parse(&self, content: &str) -> ArtifactResult<Artifact> {
+        let raw = parse_cloudformation_yaml(content)?;
+        let service = match raw.get("service") {
+            Some(Value::String(name)) => name.clone(),
+            Some(Value::Object(service)) => service
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            _ => return Err(ArtifactError::MissingField("service".to_string())),
+        };
+
+        let provider = raw.get("provider").cloned().unwrap_or(json!({}));
+        let provider_name = provider.get("name").and_then(Value::as_str);
+        if provider_name.is_some_and(|name| name != "aws") {
+            return Err(ArtifactError::UnsupportedFormat(format!(
+                "Serverless provider '{}' (only aws is supported)",
+                provider_name.unwrap_or_default()
+            )));
+        }
+
+        let stage = provider
+            .get("stage")
+            .map(|stage| resolve(stage, &raw, DEFAULT_STAGE, 0))
+            .and_then(|stage| stage.as_str().map(str::to_string))
+            .unwrap_or_else(|| DEFAULT_STAGE.to_string());
+        let document = resolve(&raw, &raw, &stage, 0);
+        let provider = document.get("provider").cloned().unwrap_or(json!({}));
+        let region = provider
+            .get("region")
+            .and_then(Value::as_str)
+            .unwrap_or(DEFAULT_REGION);
+        let prefix = format!("{}-{}", service, stage);
+
+        let mut artifact = Artifact::new(
+            ArtifactFormat::Serverless,
+            ArtifactMetadata {
+                source: "serverless".to_string(),
+                version: document
+                    .get("frameworkVersion")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                // The framework deploys one CloudFormation stack per stage
+                stack_name: Some(prefix.clone()),
+                region: Some(region.to_string()),
+                tags: HashMap::new(),
+            },
+        );
+
+        if let Some(functions) = document.get("functions").and_then(Value::as_object) {
+            for (name, function) in functions {
+                let resource = Self::function(name, function, &provider, &prefix, &mut artifact);
+                artifact.add_resource(resource);
+            }
+        }
+
+        if let Some(resources) = document
+            .get("resources")
+            .and_then(|r| r.get("Resources"))
+            .and_then(Value::as_object)
+        {
+            for (logical_id, resource) in resources {
+                artifact.add_resource(cloudformation_resource(logical_id, resource));
+            }
+        }
+
+        Ok(artifact)
+    }
+
+    fn format(&self) -> ArtifactFormat {
+        ArtifactFormat::Serverless
+    }
+}
+
+/// Logical-ID form of a function name, as the framework generates it
+/// (`create-order` -> `CreateDashorder`)
+fn normalize_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let capitalized: String = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    capitalized.replace('-', "Dash").replace('_', "Underscore")
+}
+
+/// Resolve `${...}` variables throughout `value`
+fn resolve(value: &Value, root: &Value, stage: &str, depth: usize) -> Value {
+    match value {
+        Value::String(s) => resolve_string(s, root, stage, depth),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve(item, root, stage, depth))
+                .collect(),
+        ),
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .map(|(key, value)| (key.clone(), resolve(value, root, stage, depth)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Resolve a string; a string that is one reference takes the referenced
+/// value's type (`memorySize: ${self:custom.memory}` stays a number)
+fn resolve_string(s: &str, root: &Value, stage: &str, depth: usize) -> Value {
+    if depth >= MAX_VARIABLE_DEPTH || !s.contains("${") {
+        return Value::String(s.to_string());
+    }
+    if let Some(inner) = s.strip_prefix("${").and_then(|r| r.strip_suffix('}')) {
+        if !inner.contains("${") {
+            return match variable(inner, root, stage) {
+                Some(value) => resolve(&value, root, stage, depth + 1),
+                None => Value::String(s.to_string()),
+            };
+        }
+    }
+
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        match variable(&after[..end], root, stage).map(|v| resolve(&v, root, stage, depth + 1)) {
+            Some(Value::String(text)) => result.push_str(&text),
+            Some(value @ (Value::Number(_) | Value::Bool(_))) => {
+                result.push_str(&value.to_string())
+            }
+            _ => result.push_str(&rest[start..start + 3 + end]),
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    Value::String(result)
+}
+
+/// Value of one variable reference (`self:custom.memory, 512`)
+fn variable(reference: &str, root: &Value, stage: &str) -> Option<Value> {
+    let (source, fallback) = match reference.split_once(',') {
+        Some((source, fallback)) => (source.trim(), Some(literal(fallback.trim()))),
+        None => (reference.trim(), None),
+    };
+
+    let resolved = match source.split_once(':') {
+        Some(("self", path)) => path
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .try_fold(root, |value, segment| value.get(segment))
+            .cloned(),
+        Some(("sls", "stage")) => Some(json!(stage)),
+        // There are no CLI options or environment here, so `${opt:stage, 'prod'}`
+        // takes its default
+        Some(("opt", "stage")) if fallback.is_none() => Some(json!(stage)),
+        _ => None,
+    };
+    resolved.or(fallback)
+}
+
+fn literal(text: &str) -> Value {
+    let unquoted = text
+        .strip_prefix('\'')
+        .and_then(|t| t.strip_suffix('\''))
+        .or_else(|| text.strip_prefix('"').and_then(|t| t.strip_suffix('"')));
+    match unquoted {
+        Some(s) => Value::String(s.to_string()),
+        None => serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string())),
+    }
+}
+
+/// Logical ID named by `Ref` or `Fn::GetAtt`
+fn referenced_id(value: &Value) -> Option<String> {
+    value
+        .get("Ref")
+        .or_else(|| value.get("Fn::GetAtt").and_then(|v| v.get(0)))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn event_resource(
+    id: String,
+    resource_type: &str,
+    properties: Value,
+    depends_on: Vec<String>,
+) -> ArtifactResource {
+    ArtifactResource {
+        id,
+        resource_type: resource_type.to_string(),
+        properties: properties
+            .as_object()
+            .map(|p| p.clone().into_iter().collect())
+            .unwrap_or_default(),
+        depends_on,
+        metadata: HashMap::new(),
+    }
+}
+
+/// Add a resource shared by all functions unless it already exists
+fn add_once(artifact: &mut Artifact, id: &str, resource_type: &str, properties: Value) {
+    if artifact.get_resource(id).is_none() {
+        artifact.add_resource(event_resource(
+            id.to_string(),
+            resource_type,
+            properties,
+            Vec::new(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVERLESS_YML: &str = r#"
+service: orders
+frameworkVersion: "3"
+custom:
+  memory: 2048
+provider:
+  name: aws
+  runtime: nodejs20.x
+  stage: ${opt:stage, 'prod'}
+  region: eu-west-1
+  architecture: arm64
+  tags:
+    team: orders
+functions:
+  create-order:
+    handler: src/create.handler
+    memorySize: ${self:custom.memory}
+    events:
+      - http:
+          path: orders
+          method: post
+      - sqs:
+          arn: !GetAtt OrdersQueue.Arn
+  list:
+    handler: src/list.handler
+    timeout: 30
+    events:
+      - http:
+          path: orders
+          method: get
+      - schedule: rate(1 hour)
+resources:
+  Resources:
+    OrdersQueue:
+      Type: AWS::SQS::Queue
+      Properties:
+        QueueName: ${self:service}-${sls:stage}-orders
+"#;
+
+    #[test]
+    fn test_parse_functions_events_and_resources() {
+        let artifact = ServerlessParser::new().parse(SERVERLESS_YML).unwrap();
+        assert_eq!(artifact.format, ArtifactFormat::Serverless);
+        assert_eq!(artifact.metadata.stack_name.as_deref(), Some("orders-prod"));
+        assert_eq!(artifact.metadata.region.as_deref(), Some("eu-west-1"));
+
+        let create = artifact
+            .get_resource("CreateDashorderLambdaFunction")
+            .unwrap();
+        assert_eq!(create.resource_type, "aws_lambda_function");
+        assert_eq!(create.properties["MemorySize"], 2048);
+        assert_eq!(create.properties["Timeout"], 6);
+        assert_eq!(create.properties["Architectures"], json!(["arm64"]));
+        assert_eq!(
+            create.properties["FunctionName"],
+            "orders-prod-create-order"
+        );
+        assert_eq!(create.metadata["tag:team"], "orders");
+
+        let list = artifact.get_resource("ListLambdaFunction").unwrap();
+        assert_eq!(list.properties["MemorySize"], 1024);
+        assert_eq!(list.properties["Timeout"], 30);
+
+        assert_eq!(
+            artifact
+                .get_resources_by_type("aws_api_gateway_rest_api")
+                .len(),
+            1
+        );
+        let mapping = artifact
+            .get_resource("CreateDashorderEventSourceMapping2")
+            .unwrap();
+        assert!(mapping.depends_on.contains(&"OrdersQueue".to_string()));
+        assert!(artifact.get_resource("ListEventsRule2").is_some());
+
+        let queue = artifact.get_resource("OrdersQueue").unwrap();
+        assert_eq!(queue.resource_type, "aws_sqs_queue");
+        assert_eq!(queue.properties["QueueName"], "orders-prod-orders");
+    }
+
+    #[test]
+    fn test_rejects_other_providers() {
+        let yml = "service: app\nprovider:\n  name: google\nfunctions: {}\n";
+        assert!(matches!(
+            ServerlessParser::new().parse(yml),
+            Err(ArtifactError::UnsupportedFormat(_))
+        ));
+        assert!(ServerlessParser::new().parse("provider: {}\n").is_err());
+    }
+}
@@ -4,9 +4,14 @@ use clap::{Parser, Subcommand};
 use colored::*;
 use costpilot::cli::commands::autofix_patch::AutofixPatchArgs;
 use costpilot::cli::commands::autofix_snippet::AutofixSnippetArgs;
+use costpilot::cli::error_output::print_error;
+use costpilot::cli::exit_code::ExitCode;
+use costpilot::cli::options::GlobalOptions;
+use costpilot::edition::UpgradeRequired;
 use costpilot::engines::policy::ExemptionStatus;
-use std::path::PathBuf;
-use std::process;
+use costpilot::engines::shared::error_model::CostPilotError;
+use costpilot::engines::shared::pruning::Pruning;
+use std::path::{Path, PathBuf};
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const BANNER: &str = r#"
@@ -26,14 +31,46 @@ struct Cli {
     #[command(subcommand)]
     command: Commands,
 
-    #[arg(short, long, global = true)]
-    verbose: bool,
+    /// Increase log verbosity (-v info, -vv debug with span timings, -vvv trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
 
     #[arg(short = 'f', long, global = true, default_value = "text")]
     format: String,
 
+    /// Log line format on stderr
+    #[arg(long, global = true, value_enum, default_value = "text", env = costpilot::logging::LOG_FORMAT_ENV_VAR)]
+    log_format: costpilot::logging::LogFormat,
+
     #[arg(short, long, global = true)]
     debug: bool,
+
+    /// Config profile to overlay on costpilot.yaml (e.g. prod, dev)
+    #[arg(long, global = true, env = costpilot::config::PROFILE_ENV_VAR)]
+    profile: Option<String>,
+
+    /// Language of messages and report headings (en, de, ja)
+    #[arg(long, global = true, env = costpilot::i18n::LOCALE_ENV_VAR)]
+    locale: Option<String>,
+
+    /// Hash resource and module names and drop sensitive tags in exported
+    /// reports, so they can be shared outside the team
+    #[arg(
+        long,
+        global = true,
+        env = costpilot::engines::shared::redaction::REDACT_ENV_VAR,
+        value_parser = clap::builder::BoolishValueParser::new()
+    )]
+    redact: bool,
+
+    /// Show only the N costliest rows of grouping reports, maps, trend diffs
+    /// and PR comments; the rest are totalled in an "everything else" row
+    #[arg(long, global = true, value_name = "N", env = costpilot::engines::shared::pruning::TOP_ENV_VAR)]
+    top: Option<usize>,
+
+    /// Fold rows worth less than this per month into "everything else"
+    #[arg(long, global = true, value_name = "USD", env = costpilot::engines::shared::pruning::MIN_COST_ENV_VAR)]
+    min_cost: Option<f64>,
 }
 
 #[derive(Subcommand)]
@@ -41,6 +78,31 @@ enum Commands {
     #[command(about = "Scan infrastructure changes for cost issues")]
     Scan(costpilot::cli::scan::ScanCommand),
 
+    #[command(about = "Write a static HTML report combining every analysis of a plan")]
+    Report(costpilot::cli::report::ReportCommand),
+
+    #[command(about = "Re-emit a Terraform plan with the monthly cost change of each resource")]
+    Annotate(costpilot::cli::annotate::AnnotateCommand),
+
+    #[command(about = "Watch Terraform or CDK sources and show cost deltas live")]
+    Watch(costpilot::cli::watch::WatchCommand),
+
+    #[command(
+        about = "Import actual billing costs per module to recalibrate prediction intervals"
+    )]
+    Calibrate(costpilot::cli::calibrate::CalibrateCommand),
+
+    #[command(about = "Run the language server for costpilot.yaml and policy files")]
+    Lsp(costpilot::cli::lsp::LspCommand),
+
+    #[command(about = "List feature flags and whether each is on for this repository")]
+    Flags(costpilot::cli::feature_flags::FlagsCommand),
+
+    #[command(
+        about = "Encrypt, check and migrate local trend snapshots, baselines and usage logs"
+    )]
+    Storage(costpilot::cli::storage::StorageCommand),
+
     #[command(about = "Compare cost between two infrastructure plans")]
     Diff {
         #[arg(value_name = "BEFORE")]
@@ -57,11 +119,38 @@ enum Commands {
 
         #[arg(long)]
         path: Option<PathBuf>,
+
+        /// Guided setup: detect the IaC framework and scaffold config, baselines, SLOs, policies and CI
+        #[arg(long)]
+        wizard: bool,
+
+        /// Accept detected and default answers without prompting (with --wizard)
+        #[arg(long, requires = "wizard")]
+        yes: bool,
+
+        /// Monthly budget in USD (with --wizard)
+        #[arg(long, requires = "wizard")]
+        budget: Option<f64>,
+
+        /// Owning team, repeatable (with --wizard)
+        #[arg(long = "team", value_name = "TEAM", requires = "wizard")]
+        teams: Vec<String>,
+
+        /// IaC inventory from `repo-scanner --inventory` (with --wizard;
+        /// default: .costpilot/inventory.json when present)
+        #[arg(long, value_name = "FILE", requires = "wizard")]
+        inventory: Option<PathBuf>,
     },
 
     #[command(about = "Generate dependency map for infrastructure resources")]
     Map(costpilot::cli::map::MapCommand),
 
+    #[command(about = "Export JSON Schemas for machine-readable output")]
+    Schema(costpilot::cli::schema::SchemaCommand),
+
+    #[command(about = "Capture, update and bless cost baselines")]
+    Baseline(costpilot::cli::baseline::BaselineCommand),
+
     #[command(about = "Manage policy lifecycle and approvals")]
     Policy {
         #[command(subcommand)]
@@ -92,7 +181,10 @@ enum Commands {
         command: costpilot::cli::heuristics::HeuristicsCommand,
     },
 
-    #[command(about = "Explain cost predictions with stepwise reasoning")]
+    #[command(
+        about = "Explain cost predictions with stepwise reasoning",
+        args_conflicts_with_subcommands = true
+    )]
     Explain {
         #[command(subcommand)]
         command: Option<costpilot::cli::explain::ExplainCommand>,
@@ -126,8 +218,11 @@ enum Commands {
         min_snapshots: Option<usize>,
         #[arg(long)]
         min_r_squared: Option<f64>,
-        #[arg(short, long)]
-        verbose: bool,
+        /// Replay the snapshot history and show when each SLO would have fired
+        #[arg(long)]
+        replay: bool,
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
     },
 
     #[command(about = "Generate autofix snippets")]
@@ -135,8 +230,12 @@ enum Commands {
         #[arg(long, value_name = "FILE")]
         plan: Option<PathBuf>,
 
-        #[arg(short, long)]
-        verbose: bool,
+        /// Language for CDK property overrides (typescript, python)
+        #[arg(long, value_name = "LANG", default_value = "typescript")]
+        cdk_language: costpilot::engines::autofix::CdkLanguage,
+
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
     },
 
     #[command(about = "Generate autofix patches")]
@@ -148,6 +247,12 @@ enum Commands {
         command: Option<EscrowCli>,
     },
 
+    #[command(about = "Manage the Premium engine installation")]
+    Pro {
+        #[command(subcommand)]
+        command: ProCli,
+    },
+
     #[command(about = "Manage policy lifecycle")]
     PolicyLifecycle {
         #[command(subcommand)]
@@ -179,6 +284,10 @@ enum Commands {
 
         #[arg(long)]
         fail_fast: bool,
+
+        /// Apply safe fixes to costpilot.yaml before validating (keeps a .bak copy)
+        #[arg(long)]
+        fix: bool,
     },
 
     #[command(about = "Show version information")]
@@ -204,6 +313,9 @@ enum SloCommands {
 
         #[arg(long, default_value = "0.7")]
         min_r_squared: f64,
+
+        #[arg(long)]
+        replay: bool,
     },
 }
 
@@ -222,6 +334,15 @@ enum PerformanceCli {
         engine: Option<String>,
         limit: Option<usize>,
     },
+    Trace {
+        report_file: PathBuf,
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    Breakers {
+        #[arg(long)]
+        reset: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -236,8 +357,11 @@ enum SloCli {
         min_snapshots: Option<usize>,
         #[arg(long)]
         min_r_squared: Option<f64>,
-        #[arg(short, long)]
-        verbose: bool,
+        /// Replay the snapshot history and show when each SLO would have fired
+        #[arg(long)]
+        replay: bool,
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
     },
 }
 
@@ -246,11 +370,43 @@ enum EscrowCli {
     Create {
         version: String,
         output_dir: Option<PathBuf>,
+        #[arg(long)]
         include_artifacts: bool,
+        /// Earlier package directory; only files changed since it are stored
+        #[arg(long)]
+        base: Option<PathBuf>,
     },
     Verify {
         package_dir: PathBuf,
     },
+    /// Rebuild a package in a sandbox and compare its artifacts with the manifest
+    VerifyBuild {
+        package_dir: PathBuf,
+
+        /// Deposited sources (default: repository root)
+        #[arg(long)]
+        source: Option<PathBuf>,
+
+        /// Base64 Ed25519 key file used to sign the report
+        #[arg(long)]
+        key: Option<PathBuf>,
+
+        /// Report path (default: <PACKAGE_DIR>/build-verification.json)
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// Build step number to skip (repeatable), e.g. toolchain installation
+        #[arg(long = "skip-step")]
+        skip_steps: Vec<usize>,
+
+        /// Also run the package's test commands
+        #[arg(long)]
+        run_tests: bool,
+
+        /// Keep the sandbox for inspection
+        #[arg(long)]
+        keep_sandbox: bool,
+    },
     Playbook {
         package_dir: PathBuf,
         output: Option<PathBuf>,
@@ -258,6 +414,14 @@ enum EscrowCli {
     Recover {
         package_dir: PathBuf,
         working_dir: PathBuf,
+
+        /// Print the resolved recovery plan without running it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Ignore progress saved by an interrupted recovery
+        #[arg(long)]
+        restart: bool,
     },
     Configure {
         vendor_name: String,
@@ -269,20 +433,53 @@ enum EscrowCli {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum ProCli {
+    /// Install a signed offline update bundle
+    Install {
+        #[arg(value_name = "BUNDLE")]
+        bundle: PathBuf,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the installed engine version
+    Status,
+}
+
 #[derive(Subcommand, Debug)]
 enum PolicyLifecycleCli {
+    /// Submit a policy file for review by the given approvers
     Submit {
         #[arg(short, long)]
         policy: PathBuf,
-        #[arg(short, long, value_delimiter = ',')]
+        /// Approvers, each with a key in `policy_approvals` in costpilot.yaml
+        #[arg(short, long, value_delimiter = ',', required = true)]
         approvers: Vec<String>,
+        /// Approvals required to approve the policy (default: all approvers;
+        /// never below `policy_approvals.min_approvals`)
+        #[arg(long)]
+        min_approvals: Option<usize>,
+        #[arg(long, default_value = "system")]
+        author: String,
     },
-    Approve {
+    /// Sign an approval of the policy version under review
+    Sign {
         policy_id: String,
+        /// Base64 Ed25519 signing key file
+        #[arg(long)]
+        key: PathBuf,
         #[arg(short, long)]
         approver: String,
         #[arg(short, long)]
         comment: Option<String>,
+        /// Approval file (default: <POLICY_ID>.<APPROVER>.approval.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Record a signed approval; the policy is approved once enough approvers signed
+    Approve {
+        /// Approval file written by `sign`
+        approval: PathBuf,
     },
     Reject {
         policy_id: String,
@@ -303,8 +500,18 @@ enum PolicyLifecycleCli {
         #[arg(short, long)]
         reason: Option<String>,
     },
+    Archive {
+        policy_id: String,
+        #[arg(short, long, default_value = "system")]
+        actor: String,
+        #[arg(short, long)]
+        reason: Option<String>,
+    },
     Status {
         policy_id: String,
+        /// Print the lifecycle state machine as a Mermaid state diagram
+        #[arg(long)]
+        graph: bool,
     },
     History {
         policy_id: String,
@@ -348,26 +555,64 @@ enum UsageCli {
         start: String,
         end: String,
     },
+    Import {
+        dir: PathBuf,
+    },
+    /// Write a signed usage receipt to email to your CostPilot account contact
+    Receipt {
+        /// Calendar month (YYYY-MM); defaults to the previous month
+        #[arg(long)]
+        month: Option<String>,
+
+        /// Receipt file (default: costpilot-usage-<month>.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
 enum PolicyCommands {
+    /// Submit a policy file for review by the given approvers
     Submit {
         #[arg(short, long)]
         policy: PathBuf,
 
-        #[arg(short, long, value_delimiter = ',')]
+        /// Approvers, each with a key in `policy_approvals` in costpilot.yaml
+        #[arg(short, long, value_delimiter = ',', required = true)]
         approvers: Vec<String>,
+
+        /// Approvals required to approve the policy (default: all approvers;
+        /// never below `policy_approvals.min_approvals`)
+        #[arg(long)]
+        min_approvals: Option<usize>,
+
+        #[arg(long, default_value = "system")]
+        author: String,
     },
 
-    Approve {
+    /// Sign an approval of the policy version under review
+    Sign {
         policy_id: String,
 
+        /// Base64 Ed25519 signing key file
+        #[arg(long)]
+        key: PathBuf,
+
         #[arg(short, long)]
         approver: String,
 
         #[arg(short, long)]
         comment: Option<String>,
+
+        /// Approval file (default: <POLICY_ID>.<APPROVER>.approval.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Record a signed approval; the policy is approved once enough approvers signed
+    Approve {
+        /// Approval file written by `sign`
+        approval: PathBuf,
     },
 
     Reject {
@@ -397,8 +642,22 @@ enum PolicyCommands {
         reason: String,
     },
 
+    Archive {
+        policy_id: String,
+
+        #[arg(short, long, default_value = "system")]
+        actor: String,
+
+        #[arg(short, long)]
+        reason: String,
+    },
+
     Status {
         policy_id: String,
+
+        /// Print the lifecycle state machine as a Mermaid state diagram
+        #[arg(long)]
+        graph: bool,
     },
 
     History {
@@ -472,8 +731,8 @@ enum TrendCommands {
     },
 
     List {
-        #[arg(short, long)]
-        verbose: bool,
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
 
         #[arg(short = 'n', long)]
         limit: Option<usize>,
@@ -500,8 +759,39 @@ enum TrendCommands {
         #[arg(value_name = "TO")]
         to: String,
 
-        #[arg(short, long)]
-        verbose: bool,
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
+
+        /// Only show module and service rows matching this expression
+        /// (e.g. 'module=~vpc and delta>50')
+        #[arg(long, value_name = "EXPR")]
+        filter: Option<costpilot::engines::shared::filter::FilterExpr>,
+    },
+
+    /// Attach billed costs from a CUR or Cost Explorer CSV export to a snapshot
+    ImportActuals {
+        /// Cost and Usage Report or Cost Explorer CSV download
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Snapshot to attach the costs to (default: the latest)
+        #[arg(long)]
+        snapshot: Option<String>,
+
+        /// Resource tag naming the module of each line item
+        #[arg(long, default_value = costpilot::engines::trend::DEFAULT_MODULE_TAG)]
+        tag: String,
+
+        /// Billing month to import when the export covers several (YYYY-MM)
+        #[arg(long, value_name = "YYYY-MM")]
+        period: Option<String>,
+    },
+
+    /// Compare predictions with imported actuals (--format text, json or html)
+    Variance {
+        /// Write the report to a file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
     },
 
     Clean {
@@ -535,7 +825,16 @@ enum AuditCommands {
         last_n: Option<usize>,
     },
 
-    Verify,
+    /// Verify the hash chain, and against the anchor to detect truncation
+    Verify {
+        /// Audit log file (default: .costpilot/audit_log.json)
+        #[arg(long)]
+        log: Option<PathBuf>,
+
+        /// Anchor file recorded earlier (default: the anchor saved next to the log)
+        #[arg(long)]
+        anchor: Option<PathBuf>,
+    },
 
     Compliance {
         #[arg(short = 'f', long)]
@@ -545,12 +844,25 @@ enum AuditCommands {
         days: u32,
     },
 
+    /// Export entries with their hashes (ndjson/jsonl, csv or json)
     Export {
         #[arg(short = 'f', long, default_value = "ndjson")]
         format: String,
 
         #[arg(short, long)]
         output: PathBuf,
+
+        /// Only entries at or after this time (RFC 3339 or YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only entries at or before this time (RFC 3339 or YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Audit log file (default: .costpilot/audit_log.json)
+        #[arg(long)]
+        log: Option<PathBuf>,
     },
 
     Stats,
@@ -573,22 +885,39 @@ enum AuditCommands {
     },
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let edition = costpilot::edition::detect_edition()
-        .unwrap_or_else(|_| costpilot::edition::EditionContext::free());
+/// Commands refused up front in the Free edition
+const PREMIUM_COMMANDS: [&str; 3] = ["autofix", "patch", "slo"];
+
+fn premium_required(command: &str) -> ! {
+    eprintln!(
+        "{} Command '{}' requires Premium edition",
+        "⚠".bright_yellow().bold(),
+        command
+    );
+    eprintln!();
+    eprintln!("  Currently running: Free edition");
+    eprintln!("  Upgrade at: https://shieldcraft-ai.com/costpilot/upgrade");
+    eprintln!();
+    eprintln!("  Available commands: scan, diff, init, map, policy, validate, --help");
+    ExitCode::PremiumRequired.exit();
+}
 
+/// Edition for this run, falling back to Free. Detection logs its license
+/// and engine warnings, so logging must be installed first.
+fn detect_edition() -> costpilot::edition::EditionContext {
+    costpilot::edition::detect_edition()
+        .unwrap_or_else(|_| costpilot::edition::EditionContext::free())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut args: Vec<String> = std::env::args().collect();
 
     // Handle --version/-V before argument rewriting
     if args.len() >= 2 {
         let arg = &args[1];
         if arg == "--version" || arg == "-V" {
-            let edition_str = if edition.is_premium() {
-                "Premium"
-            } else {
-                "Free"
-            };
-            println!("costpilot {} ({})", VERSION, edition_str);
+            let _ = costpilot::logging::init(0, costpilot::logging::LogFormat::Text);
+            cmd_version(false, &detect_edition());
             return Ok(());
         }
     }
@@ -598,9 +927,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         let first_arg = &args[1];
         let known_commands = [
             "scan",
+            "watch",
+            "lsp",
             "diff",
             "init",
             "map",
+            "schema",
+            "baseline",
             "policy",
             "exemption",
             "trend",
@@ -614,6 +947,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             "autofix-snippet",
             "autofix-patch",
             "escrow",
+            "pro",
             "policy-lifecycle",
             "usage",
             "policy-dsl",
@@ -635,26 +969,61 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    if edition.is_free() && args.len() >= 2 {
-        let premium_commands = ["autofix", "patch", "slo"];
-        let command = args[1].to_lowercase();
-
-        if premium_commands.contains(&command.as_str()) {
-            eprintln!(
-                "{} Command '{}' requires Premium edition",
-                "⚠".bright_yellow().bold(),
-                command
-            );
-            eprintln!();
-            eprintln!("  Currently running: Free edition");
-            eprintln!("  Upgrade at: https://shieldcraft-ai.com/costpilot/upgrade");
-            eprintln!();
-            eprintln!("  Available commands: scan, diff, init, map, policy, validate, --help");
-            process::exit(1);
+    let command = args.get(1).map(|a| a.to_lowercase()).unwrap_or_default();
+
+    // clap's own exit status for usage errors (2) would read as "warnings"
+    let cli = match Cli::try_parse_from(args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            // Premium-only aliases clap does not know
+            if PREMIUM_COMMANDS.contains(&command.as_str()) {
+                let _ = costpilot::logging::init(0, costpilot::logging::LogFormat::Text);
+                if detect_edition().is_free() {
+                    premium_required(&command);
+                }
+            }
+            let _ = e.print();
+            ExitCode::for_usage_error(&e).exit();
         }
+    };
+    // --debug is shorthand for -vv
+    let verbosity = if cli.debug {
+        cli.verbose.max(2)
+    } else {
+        cli.verbose
+    };
+    if let Err(e) = costpilot::logging::init(verbosity, cli.log_format) {
+        eprintln!("⚠️  {}", e.message);
     }
-
-    let cli = Cli::parse_from(args);
+    let edition = detect_edition();
+    if edition.is_free() && PREMIUM_COMMANDS.contains(&command.as_str()) {
+        premium_required(&command);
+    }
+    // Handed to the commands that read them instead of going through the
+    // environment
+    let profile = cli
+        .profile
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(str::to_string);
+    let locale = costpilot::i18n::resolve(
+        cli.locale.as_deref(),
+        Path::new("costpilot.yaml"),
+        profile.as_deref(),
+    )
+    .unwrap_or_else(|e| {
+        tracing::warn!("{}", e.message);
+        costpilot::i18n::Locale::default()
+    });
+    // Validator and upgrade messages are looked up far from any command
+    locale.install();
+    let options = GlobalOptions {
+        profile,
+        locale,
+        redact: cli.redact,
+        pruning: Pruning::new(cli.top, cli.min_cost),
+    };
     if atty::is(atty::Stream::Stdout) {
         println!("{}", BANNER.bright_cyan());
         println!(
@@ -666,15 +1035,84 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let _start_time: Option<std::time::Instant> = None;
 
+    // Bring long-lived local state up to date before commands that read it
+    if reads_stored_state(&cli.command) {
+        match costpilot::migrations::Migrator::project().run(false) {
+            Ok(report) => {
+                if let Some(backup) = &report.backup {
+                    tracing::info!(
+                        backup = %backup.display(),
+                        "Applied {} storage migration(s); originals saved in the backup",
+                        report.applied.len()
+                    );
+                }
+            }
+            Err(e) => tracing::warn!("{}", e.message),
+        }
+    }
+
+    if edition.is_premium() {
+        if let Some(event_type) = premium_usage_event(&cli.command) {
+            costpilot::cli::usage::record_premium_usage(event_type);
+        }
+    }
+
+    let cli_format = cli.format.clone();
     let result = match cli.command {
-        Commands::Scan(scan_cmd) => scan_cmd
-            .execute_with_edition(&edition, &cli.format)
-            .map_err(|e| format!("{}", e).into()),
+        Commands::Scan(scan_cmd) => {
+            let scan_cmd = scan_cmd.with_options(options.clone());
+            match scan_cmd.execute_gated(&edition, &cli.format) {
+                Ok(ExitCode::Clean) => Ok(()),
+                Ok(code) => code.exit(),
+                Err(e) => {
+                    print_error(&e, scan_cmd.error_format(&cli.format));
+                    ExitCode::for_error(&e).exit();
+                }
+            }
+        }
+        Commands::Report(report_cmd) => match report_cmd
+            .with_options(options.clone())
+            .execute_gated(&edition)
+        {
+            Ok(ExitCode::Clean) => Ok(()),
+            Ok(code) => code.exit(),
+            Err(e) => {
+                print_error(&e, &cli.format);
+                ExitCode::for_error(&e).exit();
+            }
+        },
+        Commands::Annotate(annotate_cmd) => annotate_cmd
+            .execute(&cli.format, &edition, &options)
+            .map_err(Into::into),
+        Commands::Watch(watch_cmd) => watch_cmd.execute_with_edition(&edition).map_err(Into::into),
+        Commands::Calibrate(calibrate_cmd) => calibrate_cmd
+            .execute_with_edition(&edition, &options)
+            .map_err(Into::into),
+        Commands::Lsp(lsp_cmd) => lsp_cmd.execute().map_err(Into::into),
+        Commands::Flags(flags_cmd) => flags_cmd
+            .execute(&cli.format, &edition, &options)
+            .map_err(Into::into),
+        Commands::Storage(storage_cmd) => storage_cmd.execute(&cli.format).map_err(Into::into),
         Commands::Diff { before, after } => {
-            cmd_diff(before, after, &cli.format, cli.verbose, &edition)
+            cmd_diff(before, after, &cli.format, cli.verbose > 0, &edition)
+        }
+        Commands::Init {
+            no_ci,
+            path,
+            wizard: true,
+            yes,
+            budget,
+            teams,
+            inventory,
+        } => cmd_init_wizard(no_ci, path, yes, budget, teams, inventory),
+        Commands::Init { no_ci, path, .. } => cmd_init(no_ci, path, cli.verbose > 0),
+        Commands::Map(map_cmd) => {
+            costpilot::cli::map::execute_map_command(&map_cmd, &edition, &options)
+        }
+        Commands::Schema(schema_cmd) => {
+            costpilot::cli::schema::execute_schema_command(&schema_cmd).map_err(Into::into)
         }
-        Commands::Init { no_ci, path } => cmd_init(no_ci, path, cli.verbose),
-        Commands::Map(map_cmd) => costpilot::cli::map::execute_map_command(&map_cmd, &edition),
+        Commands::Baseline(baseline_cmd) => baseline_cmd.execute(),
         Commands::Performance { command } => {
             use costpilot::cli::performance as perf;
             let res = match command {
@@ -700,6 +1138,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         limit,
                     })
                 }
+                Some(PerformanceCli::Trace {
+                    report_file,
+                    output,
+                }) => perf::execute_performance_command(perf::PerformanceCommand::Trace {
+                    report_file,
+                    output,
+                }),
+                Some(PerformanceCli::Breakers { reset }) => {
+                    perf::execute_performance_command(perf::PerformanceCommand::Breakers { reset })
+                }
                 None => perf::execute_performance_command(perf::PerformanceCommand::Budgets),
             };
             match res {
@@ -710,20 +1158,28 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Err(e) => Err(e.into()),
             }
         }
-        Commands::Policy { command } => cmd_policy(command, &cli.format, cli.verbose, &edition),
+        Commands::Policy { command } => {
+            cmd_policy(command, &cli.format, cli.verbose > 0, &edition, &options)
+        }
         Commands::Exemption { command } => {
-            cmd_exemption(command, &cli.format, cli.verbose, &edition)
+            cmd_exemption(command, &cli.format, cli.verbose > 0, &edition)
+        }
+        Commands::Trend { command } => {
+            cmd_trend(command, &cli.format, cli.verbose > 0, &edition, &options)
         }
-        Commands::Trend { command } => cmd_trend(command, &cli.format, cli.verbose, &edition),
         Commands::Slo { command } => match command {
-            Some(SloCli::Check) => {
-                cmd_slo(Some(SloCommands::Check), &cli.format, cli.verbose, &edition)
-            }
+            Some(SloCli::Check) => cmd_slo(
+                Some(SloCommands::Check),
+                &cli.format,
+                cli.verbose > 0,
+                &edition,
+            ),
             Some(SloCli::Burn {
                 config,
                 snapshots,
                 min_snapshots,
                 min_r_squared,
+                replay,
                 verbose,
             }) => cmd_slo(
                 Some(SloCommands::Burn {
@@ -731,19 +1187,26 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     snapshots,
                     min_snapshots: min_snapshots.unwrap_or(3),
                     min_r_squared: min_r_squared.unwrap_or(0.7),
+                    replay,
                 }),
                 &cli.format,
-                verbose || cli.verbose,
+                verbose > 0 || cli.verbose > 0,
                 &edition,
             ),
-            None => cmd_slo(None, &cli.format, cli.verbose, &edition),
+            None => cmd_slo(None, &cli.format, cli.verbose > 0, &edition),
         },
-        Commands::SloCheck => cmd_slo(Some(SloCommands::Check), &cli.format, cli.verbose, &edition),
+        Commands::SloCheck => cmd_slo(
+            Some(SloCommands::Check),
+            &cli.format,
+            cli.verbose > 0,
+            &edition,
+        ),
         Commands::SloBurn {
             config,
             snapshots,
             min_snapshots,
             min_r_squared,
+            replay,
             verbose,
         } => cmd_slo(
             Some(SloCommands::Burn {
@@ -751,35 +1214,42 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 snapshots,
                 min_snapshots: min_snapshots.unwrap_or(3),
                 min_r_squared: min_r_squared.unwrap_or(0.7),
+                replay,
             }),
             &cli.format,
-            verbose || cli.verbose,
+            verbose > 0 || cli.verbose > 0,
             &edition,
         ),
-        Commands::Audit { command } => cmd_audit(command, &cli.format, cli.verbose),
-        Commands::Heuristics { command } => cmd_heuristics(command, &cli.format, cli.verbose),
-        Commands::Explain { command, args } => {
-            cmd_explain(command, args, &cli.format, cli.verbose, &edition)
+        Commands::Audit { command } => cmd_audit(command, &cli.format, cli.verbose > 0),
+        Commands::Heuristics { command } => {
+            cmd_heuristics(command, &cli.format, cli.verbose > 0, &options)
         }
-        Commands::AutofixSnippet { plan, verbose } => {
+        Commands::Explain { command, args } => cmd_explain(
+            command,
+            args,
+            &cli.format,
+            cli.verbose > 0,
+            &edition,
+            &options,
+        ),
+        Commands::AutofixSnippet {
+            plan,
+            cdk_language,
+            verbose,
+        } => {
             let plan_path = plan.ok_or("--plan is required for autofix-snippet")?;
             let args = AutofixSnippetArgs {
                 plan: plan_path,
-                verbose,
+                cdk_language,
+                verbose: verbose > 0,
             };
-            match costpilot::cli::commands::autofix_snippet::execute(&args, &edition) {
-                Ok(()) => Ok(()),
-                Err(e) => Err(format!("{}", e).into()),
-            }
+            costpilot::cli::commands::autofix_snippet::execute(&args, &edition)
         }
         Commands::AutofixPatch(args) => {
-            match costpilot::cli::commands::autofix_patch::execute(&args, &edition) {
-                Ok(()) => Ok(()),
-                Err(e) => Err(format!("{}", e).into()),
-            }
+            costpilot::cli::commands::autofix_patch::execute(&args, &edition)
         }
         Commands::PolicyDsl { command } => {
-            costpilot::cli::policy_dsl::execute_policy_dsl_command(&command)
+            costpilot::cli::policy_dsl::execute_policy_dsl_command(&command, &options)
         }
         Commands::Escrow { command } => {
             use costpilot::cli::escrow as ec;
@@ -788,14 +1258,33 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     version,
                     output_dir,
                     include_artifacts,
+                    base,
                 }) => ec::execute_escrow_command(ec::EscrowCommand::Create {
                     version,
                     output_dir,
                     include_artifacts,
+                    base,
                 }),
                 Some(EscrowCli::Verify { package_dir }) => {
                     ec::execute_escrow_command(ec::EscrowCommand::Verify { package_dir })
                 }
+                Some(EscrowCli::VerifyBuild {
+                    package_dir,
+                    source,
+                    key,
+                    output,
+                    skip_steps,
+                    run_tests,
+                    keep_sandbox,
+                }) => ec::execute_escrow_command(ec::EscrowCommand::VerifyBuild {
+                    package_dir,
+                    source_dir: source,
+                    signing_key: key,
+                    output,
+                    skip_steps,
+                    run_tests,
+                    keep_sandbox,
+                }),
                 Some(EscrowCli::Playbook {
                     package_dir,
                     output,
@@ -806,9 +1295,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Some(EscrowCli::Recover {
                     package_dir,
                     working_dir,
+                    dry_run,
+                    restart,
                 }) => ec::execute_escrow_command(ec::EscrowCommand::Recover {
                     package_dir,
                     working_dir,
+                    dry_run,
+                    restart,
                 }),
                 Some(EscrowCli::Configure {
                     vendor_name,
@@ -832,70 +1325,107 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Err(e) => Err(e.into()),
             }
         }
+        Commands::Pro { command } => {
+            use costpilot::cli::pro as pro_cmd;
+            let res = match command {
+                ProCli::Install { bundle, dry_run } => {
+                    pro_cmd::execute_pro_command(pro_cmd::ProCommand::Install { bundle, dry_run })
+                }
+                ProCli::Status => pro_cmd::execute_pro_command(pro_cmd::ProCommand::Status),
+            };
+            match res {
+                Ok(out) => {
+                    println!("{}", out);
+                    Ok(())
+                }
+                Err(e) => Err(e.into()),
+            }
+        }
         Commands::PolicyLifecycle { command } => {
             use costpilot::cli::commands::policy_lifecycle as pl;
-            match command {
-                Some(PolicyLifecycleCli::Submit { policy, approvers }) => {
-                    pl::cmd_submit(policy, approvers, &cli.format, cli.verbose, &edition)
-                        .map_err(|e| format!("{}", e).into())
+            let store = match pl::open_store(options.profile()) {
+                Ok(store) => store,
+                Err(e) => {
+                    print_error(e.as_ref(), &cli.format);
+                    ExitCode::InternalError.exit();
                 }
-                Some(PolicyLifecycleCli::Approve {
-                    policy_id,
-                    approver,
-                    comment,
-                }) => pl::cmd_approve(
+            };
+            let (format, verbose) = (cli.format.as_str(), cli.verbose > 0);
+            match command {
+                Some(PolicyLifecycleCli::Submit {
+                    policy,
+                    approvers,
+                    min_approvals,
+                    author,
+                }) => pl::cmd_submit(
+                    &store,
+                    policy,
+                    approvers,
+                    min_approvals,
+                    author,
+                    format,
+                    verbose,
+                    &edition,
+                ),
+                Some(PolicyLifecycleCli::Sign {
                     policy_id,
+                    key,
                     approver,
                     comment,
-                    &cli.format,
-                    cli.verbose,
-                    &edition,
-                )
-                .map_err(|e| format!("{}", e).into()),
+                    output,
+                }) => pl::cmd_sign(
+                    &store, policy_id, key, approver, comment, output, format, verbose, &edition,
+                ),
+                Some(PolicyLifecycleCli::Approve { approval }) => {
+                    pl::cmd_approve(&store, approval, format, verbose, &edition)
+                }
                 Some(PolicyLifecycleCli::Reject {
                     policy_id,
                     approver,
                     reason,
                 }) => pl::cmd_reject(
-                    policy_id,
-                    approver,
-                    reason,
-                    &cli.format,
-                    cli.verbose,
-                    &edition,
-                )
-                .map_err(|e| format!("{}", e).into()),
+                    &store, policy_id, approver, reason, format, verbose, &edition,
+                ),
                 Some(PolicyLifecycleCli::Activate { policy_id, actor }) => {
-                    pl::cmd_activate(policy_id, actor, &cli.format, cli.verbose, &edition)
-                        .map_err(|e| format!("{}", e).into())
+                    pl::cmd_activate(&store, policy_id, actor, format, verbose, &edition)
                 }
                 Some(PolicyLifecycleCli::Deprecate {
                     policy_id,
                     actor,
                     reason,
                 }) => pl::cmd_deprecate(
+                    &store,
                     policy_id,
                     actor,
                     reason.unwrap_or_default(),
-                    &cli.format,
-                    cli.verbose,
+                    format,
+                    verbose,
                     &edition,
-                )
-                .map_err(|e| format!("{}", e).into()),
-                Some(PolicyLifecycleCli::Status { policy_id }) => {
-                    pl::cmd_status(policy_id, &cli.format, cli.verbose, &edition)
-                        .map_err(|e| format!("{}", e).into())
+                ),
+                Some(PolicyLifecycleCli::Archive {
+                    policy_id,
+                    actor,
+                    reason,
+                }) => pl::cmd_archive(
+                    &store,
+                    policy_id,
+                    actor,
+                    reason.unwrap_or_default(),
+                    format,
+                    verbose,
+                    &edition,
+                ),
+                Some(PolicyLifecycleCli::Status { policy_id, graph }) => {
+                    pl::cmd_status(&store, policy_id, graph, format, verbose, &edition)
                 }
                 Some(PolicyLifecycleCli::History { policy_id }) => {
-                    pl::cmd_history(policy_id, &cli.format, cli.verbose, &edition)
-                        .map_err(|e| format!("{}", e).into())
+                    pl::cmd_history(&store, policy_id, format, verbose, &edition)
                 }
                 Some(PolicyLifecycleCli::Diff {
                     policy_id,
                     from,
                     to,
-                }) => pl::cmd_diff(policy_id, from, to, &cli.format, cli.verbose, &edition)
-                    .map_err(|e| format!("{}", e).into()),
+                }) => pl::cmd_diff(&store, policy_id, from, to, format, verbose, &edition),
                 None => Err("No policy-lifecycle subcommand provided".into()),
             }
         }
@@ -961,15 +1491,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         org_id,
                         start,
                         end,
-                        format: _,
-                        output,
-                    }) => usage_mod::execute_usage_command(usage_mod::UsageCommand::Chargeback {
-                        org_id,
-                        start,
-                        end,
-                        format: usage_mod::OutputFormat::Text,
+                        format,
                         output,
-                    }),
+                    }) => format
+                        .parse::<usage_mod::OutputFormat>()
+                        .and_then(|format| {
+                            usage_mod::execute_usage_command(usage_mod::UsageCommand::Chargeback {
+                                org_id,
+                                start,
+                                end,
+                                format,
+                                output,
+                            })
+                        }),
                     Some(UsageCli::Invoice {
                         team_id,
                         start,
@@ -979,6 +1513,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         start,
                         end,
                     }),
+                    Some(UsageCli::Import { dir }) => {
+                        usage_mod::execute_usage_command(usage_mod::UsageCommand::Import { dir })
+                    }
+                    Some(UsageCli::Receipt { month, output }) => match &edition.license {
+                        Some(license) => {
+                            usage_mod::execute_usage_command(usage_mod::UsageCommand::Receipt {
+                                month,
+                                license_key: license.license_key.clone(),
+                                output,
+                            })
+                        }
+                        None => Err("Usage receipts require a Premium license".to_string()),
+                    },
                     None => usage_mod::execute_usage_command(usage_mod::UsageCommand::Report {
                         team_id: "all".to_string(),
                         start: None,
@@ -997,11 +1544,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
         Commands::Group(group_cmd) => {
-            costpilot::cli::group::execute_group_command(group_cmd, &edition)
-        }
-        Commands::Validate { files, fail_fast } => {
-            cmd_validate(files, &cli.format, fail_fast, &edition)
+            costpilot::cli::group::execute_group_command(group_cmd, &edition, &options)
         }
+        Commands::Validate {
+            files,
+            fail_fast,
+            fix,
+        } => cmd_validate(files, &cli.format, fail_fast, fix, &edition),
         Commands::Version { detailed } => {
             cmd_version(detailed, &edition);
             return Ok(());
@@ -1010,13 +1559,56 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Handle errors with clean formatting (Display, not Debug)
     if let Err(e) = result {
-        eprintln!("{} {}", "Error:".bright_red().bold(), e);
-        std::process::exit(1);
+        print_error(e.as_ref(), &cli_format);
+        let code = if e.downcast_ref::<UpgradeRequired>().is_some() {
+            ExitCode::PremiumRequired
+        } else if let Some(err) = e.downcast_ref::<CostPilotError>() {
+            ExitCode::for_error(err)
+        } else {
+            ExitCode::InternalError
+        };
+        code.exit();
     }
 
     Ok(())
 }
 
+/// Commands that read snapshots, baselines, the audit log or the usage log
+fn reads_stored_state(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Trend { .. }
+            | Commands::Baseline(_)
+            | Commands::Audit { .. }
+            | Commands::Usage { .. }
+            | Commands::Slo { .. }
+            | Commands::SloBurn { .. }
+    )
+}
+
+/// Usage event recorded for commands that run premium features
+fn premium_usage_event(command: &Commands) -> Option<costpilot::engines::metering::UsageEventType> {
+    use costpilot::engines::metering::UsageEventType;
+
+    match command {
+        Commands::Scan(_)
+        | Commands::Report(_)
+        | Commands::Annotate(_)
+        | Commands::Watch(_)
+        | Commands::Diff { .. } => Some(UsageEventType::PlanAnalysis),
+        Commands::Explain { .. } => Some(UsageEventType::AdvancedPrediction),
+        Commands::Map(_) => Some(UsageEventType::DependencyMap),
+        Commands::Trend { .. } => Some(UsageEventType::TrendAnalysis),
+        Commands::Slo { .. } | Commands::SloCheck | Commands::SloBurn { .. } => {
+            Some(UsageEventType::SloCheck)
+        }
+        Commands::AutofixSnippet { .. } | Commands::AutofixPatch(_) => {
+            Some(UsageEventType::AutofixGeneration)
+        }
+        _ => None,
+    }
+}
+
 fn cmd_diff(
     before: PathBuf,
     after: PathBuf,
@@ -1056,6 +1648,7 @@ fn cmd_autofix(
             use costpilot::cli::commands::autofix_snippet;
             let args = autofix_snippet::AutofixSnippetArgs {
                 plan: plan_path,
+                cdk_language: costpilot::engines::autofix::CdkLanguage::TypeScript,
                 verbose,
             };
             autofix_snippet::execute(&args, edition)
@@ -1064,9 +1657,10 @@ fn cmd_autofix(
             use costpilot::cli::commands::autofix_patch;
             let args = autofix_patch::AutofixPatchArgs {
                 plan: plan_path,
+                cdk_language: costpilot::engines::autofix::CdkLanguage::TypeScript,
                 output: None,
                 apply: false,
-                verbose,
+                verbose: u8::from(verbose),
             };
             autofix_patch::execute(&args, edition)
         }
@@ -1104,6 +1698,39 @@ fn cmd_init(
     Ok(())
 }
 
+fn cmd_init_wizard(
+    no_ci: bool,
+    path: Option<PathBuf>,
+    yes: bool,
+    budget: Option<f64>,
+    teams: Vec<String>,
+    inventory: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use costpilot::cli::init_wizard::{run_wizard, CiProvider, WizardAnswers};
+    use costpilot::cli::inventory::{IacInventory, DEFAULT_INVENTORY};
+
+    let target_path = path.unwrap_or_else(|| PathBuf::from("."));
+    let inventory =
+        inventory.or_else(|| Some(target_path.join(DEFAULT_INVENTORY)).filter(|p| p.exists()));
+    let mut defaults = match &inventory {
+        Some(path) => WizardAnswers::from_inventory(&target_path, &IacInventory::load(path)?),
+        None => WizardAnswers::detected(&target_path),
+    };
+    if no_ci {
+        defaults.ci = CiProvider::None;
+    }
+    if let Some(budget) = budget {
+        defaults.monthly_budget = budget;
+    }
+    if !teams.is_empty() {
+        defaults.teams = teams;
+    }
+
+    let interactive = !yes && atty::is(atty::Stream::Stdin);
+    run_wizard(&target_path, interactive, defaults)?;
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn cmd_slo(
     command: Option<SloCommands>,
@@ -1126,17 +1753,31 @@ fn cmd_slo(
             snapshots,
             min_snapshots,
             min_r_squared,
+            replay,
         }) => {
-            println!("{}", "🔥 Calculating burn rate...".bright_blue().bold());
-            costpilot::cli::commands::slo_burn::execute(
-                slo,
-                snapshots,
-                format,
-                Some(min_snapshots),
-                Some(min_r_squared),
-                verbose,
-                edition,
-            )?;
+            if replay {
+                println!("{}", "📼 Replaying burn history...".bright_blue().bold());
+                costpilot::cli::commands::slo_burn::replay(
+                    slo,
+                    snapshots,
+                    format,
+                    Some(min_snapshots),
+                    Some(min_r_squared),
+                    verbose,
+                    edition,
+                )?;
+            } else {
+                println!("{}", "🔥 Calculating burn rate...".bright_blue().bold());
+                costpilot::cli::commands::slo_burn::execute(
+                    slo,
+                    snapshots,
+                    format,
+                    Some(min_snapshots),
+                    Some(min_r_squared),
+                    verbose,
+                    edition,
+                )?;
+            }
         }
         None => {
             println!("{}", "📋 Checking SLO compliance...".bright_blue().bold());
@@ -1152,42 +1793,74 @@ fn cmd_policy(
     format: &str,
     verbose: bool,
     edition: &costpilot::edition::EditionContext,
+    options: &GlobalOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use costpilot::cli::commands::policy_lifecycle;
+    let store = policy_lifecycle::open_store(options.profile())?;
 
     match command {
-        PolicyCommands::Submit { policy, approvers } => {
-            policy_lifecycle::cmd_submit(policy, approvers, format, verbose, edition)
-        }
-        PolicyCommands::Approve {
+        PolicyCommands::Submit {
+            policy,
+            approvers,
+            min_approvals,
+            author,
+        } => policy_lifecycle::cmd_submit(
+            &store,
+            policy,
+            approvers,
+            min_approvals,
+            author,
+            format,
+            verbose,
+            edition,
+        ),
+        PolicyCommands::Sign {
             policy_id,
+            key,
             approver,
             comment,
-        } => policy_lifecycle::cmd_approve(policy_id, approver, comment, format, verbose, edition),
+            output,
+        } => policy_lifecycle::cmd_sign(
+            &store, policy_id, key, approver, comment, output, format, verbose, edition,
+        ),
+        PolicyCommands::Approve { approval } => {
+            policy_lifecycle::cmd_approve(&store, approval, format, verbose, edition)
+        }
         PolicyCommands::Reject {
             policy_id,
             approver,
             reason,
-        } => policy_lifecycle::cmd_reject(policy_id, approver, reason, format, verbose, edition),
+        } => policy_lifecycle::cmd_reject(
+            &store, policy_id, approver, reason, format, verbose, edition,
+        ),
         PolicyCommands::Activate { policy_id, actor } => {
-            policy_lifecycle::cmd_activate(policy_id, actor, format, verbose, edition)
+            policy_lifecycle::cmd_activate(&store, policy_id, actor, format, verbose, edition)
         }
         PolicyCommands::Deprecate {
             policy_id,
             actor,
             reason,
-        } => policy_lifecycle::cmd_deprecate(policy_id, actor, reason, format, verbose, edition),
-        PolicyCommands::Status { policy_id } => {
-            policy_lifecycle::cmd_status(policy_id, format, verbose, edition)
+        } => policy_lifecycle::cmd_deprecate(
+            &store, policy_id, actor, reason, format, verbose, edition,
+        ),
+        PolicyCommands::Archive {
+            policy_id,
+            actor,
+            reason,
+        } => policy_lifecycle::cmd_archive(
+            &store, policy_id, actor, reason, format, verbose, edition,
+        ),
+        PolicyCommands::Status { policy_id, graph } => {
+            policy_lifecycle::cmd_status(&store, policy_id, graph, format, verbose, edition)
         }
         PolicyCommands::History { policy_id } => {
-            policy_lifecycle::cmd_history(policy_id, format, verbose, edition)
+            policy_lifecycle::cmd_history(&store, policy_id, format, verbose, edition)
         }
         PolicyCommands::Diff {
             policy_id,
             from,
             to,
-        } => policy_lifecycle::cmd_diff(policy_id, from, to, format, verbose, edition),
+        } => policy_lifecycle::cmd_diff(&store, policy_id, from, to, format, verbose, edition),
         PolicyCommands::Increment {
             policy_id,
             changelog,
@@ -1451,9 +2124,10 @@ fn cmd_exemption(
 
 fn cmd_trend(
     command: TrendCommands,
-    _format: &str,
+    format: &str,
     verbose: bool,
     edition: &costpilot::edition::EditionContext,
+    options: &GlobalOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use colored::*;
     use costpilot::engines::detection::DetectionEngine;
@@ -1568,13 +2242,16 @@ fn cmd_trend(
                     resource_count
                 );
 
-                if verbose {
+                if verbose > 0 {
                     if let Some(commit) = &snapshot.commit_hash {
                         println!("  Commit: {}", commit);
                     }
                     if let Some(branch) = &snapshot.branch {
                         println!("  Branch: {}", branch);
                     }
+                    if let Some(actuals) = &snapshot.actuals {
+                        println!("  Billed: ${:.2}/mo", actuals.total_monthly_cost);
+                    }
                 }
             }
 
@@ -1679,13 +2356,21 @@ fn cmd_trend(
             Ok(())
         }
 
-        TrendCommands::Diff { from, to, verbose } => {
-            println!(
-                "{}",
-                format!("🔍 Comparing snapshots '{}' and '{}'...", from, to)
-                    .bright_blue()
-                    .bold()
-            );
+        TrendCommands::Diff {
+            from,
+            to,
+            verbose,
+            filter,
+        } => {
+            let notification = matches!(format, "slack" | "teams");
+            if !notification {
+                println!(
+                    "{}",
+                    format!("🔍 Comparing snapshots '{}' and '{}'...", from, to)
+                        .bright_blue()
+                        .bold()
+                );
+            }
 
             let manager = SnapshotManager::new(&snapshots_dir);
             let from_snapshot = manager
@@ -1695,10 +2380,27 @@ fn cmd_trend(
                 .read_snapshot(&to)
                 .map_err(|e| format!("Failed to read snapshot '{}': {}", to, e))?;
 
-            let diff = costpilot::engines::trend::TrendDiffGenerator::generate_diff(
+            let mut diff = costpilot::engines::trend::TrendDiffGenerator::generate_diff(
                 &from_snapshot,
                 &to_snapshot,
             );
+            if let Some(filter) = &filter {
+                filter.retain(&mut diff.module_changes);
+                filter.retain(&mut diff.service_changes);
+            }
+            diff.prune(&options.pruning);
+
+            if notification {
+                use costpilot::engines::notify::{render_slack, render_teams, NotificationSummary};
+                let summary = NotificationSummary::for_trend_diff(&diff);
+                let payload = if format == "slack" {
+                    render_slack(&summary)
+                } else {
+                    render_teams(&summary)
+                };
+                println!("{}", serde_json::to_string_pretty(&payload)?);
+                return Ok(());
+            }
 
             println!();
             println!("{}", "Trend Comparison".bright_white().bold());
@@ -1717,11 +2419,14 @@ fn cmd_trend(
                 "Total Cost Change: ${:.2} ({:.1}%)",
                 diff.total_cost_delta, diff.total_cost_percent
             );
+            if let Some(filter) = &filter {
+                println!("Filter: {}", filter);
+            }
             println!();
 
             println!("Module Changes:");
             for change in &diff.module_changes {
-                if change.delta.abs() > 0.01 || verbose {
+                if change.delta.abs() > 0.01 || verbose > 0 {
                     println!(
                         "  {}: ${:.2} → ${:.2} ({}${:.2}, {:.1}%) - {}",
                         change.module,
@@ -1741,7 +2446,7 @@ fn cmd_trend(
                 }
             }
 
-            if verbose {
+            if verbose > 0 {
                 println!();
                 println!("Service Changes:");
                 for change in &diff.service_changes {
@@ -1785,6 +2490,156 @@ fn cmd_trend(
             Ok(())
         }
 
+        TrendCommands::ImportActuals {
+            file,
+            snapshot,
+            tag,
+            period,
+        } => {
+            use costpilot::engines::trend::{predicted_vs_actual, BillingImporter};
+
+            let content = std::fs::read_to_string(&file)
+                .map_err(|e| format!("Failed to read billing export: {}", e))?;
+            let mut importer = BillingImporter::new().with_module_tag(tag);
+            if let Some(period) = period {
+                importer = importer.with_period(period);
+            }
+            let actuals = importer.import(&content)?;
+
+            let trend_engine = TrendEngine::new(&snapshots_dir, edition)?;
+            let snapshot = trend_engine.import_actuals(snapshot.as_deref(), actuals.clone())?;
+
+            if format == "json" {
+                println!("{}", serde_json::to_string_pretty(&snapshot)?);
+                return Ok(());
+            }
+
+            println!(
+                "{}",
+                format!(
+                    "✅ Imported {} billed costs into snapshot '{}'",
+                    actuals.period.as_deref().unwrap_or("unlabelled"),
+                    snapshot.id
+                )
+                .bright_green()
+                .bold()
+            );
+            println!();
+            println!("{}", "Predicted vs Actual".bright_white().bold());
+            println!("{}", "━".repeat(60).bright_black());
+            let line = |name: &str, predicted: f64, actual: f64| {
+                let delta = actual - predicted;
+                let percent = if predicted > 0.0 {
+                    format!("{:+.1}%", delta / predicted * 100.0)
+                } else {
+                    "n/a".to_string()
+                };
+                println!(
+                    "  {:<28} ${:>10.2} ${:>10.2}  {}",
+                    name, predicted, actual, percent
+                );
+            };
+            println!("  {:<28} {:>11} {:>11}", "Module", "Predicted", "Billed");
+            if actuals.modules.is_empty() {
+                println!(
+                    "  {}",
+                    "(export has no module tag; totals only)".bright_black()
+                );
+            }
+            for row in predicted_vs_actual(&snapshot, &actuals) {
+                line(&row.module, row.predicted, row.actual);
+            }
+            line(
+                "Total",
+                snapshot.total_monthly_cost,
+                actuals.total_monthly_cost,
+            );
+
+            if verbose && !actuals.services.is_empty() {
+                println!();
+                println!("Billed by service:");
+                for (service, cost) in &actuals.services {
+                    println!("  {:<40} ${:>10.2}", service, cost);
+                }
+            }
+
+            Ok(())
+        }
+
+        TrendCommands::Variance { output } => {
+            let trend_engine = TrendEngine::new(&snapshots_dir, edition)?;
+            let report = trend_engine.variance_report()?;
+            if report.is_empty() {
+                return Err(
+                    "No snapshots with actuals; run 'costpilot trend import-actuals' first".into(),
+                );
+            }
+
+            let rendered = match format {
+                "json" => serde_json::to_string_pretty(&report)?,
+                "html" => report.to_html(),
+                _ => {
+                    let percent = |v: Option<f64>, sign: bool| match v {
+                        Some(v) if sign => format!("{:+.1}%", v),
+                        Some(v) => format!("{:.1}%", v),
+                        None => "-".to_string(),
+                    };
+                    let mut text = String::new();
+                    text.push_str(&format!(
+                        "{}\n{}\n",
+                        "Predicted vs Actual Variance".bright_white().bold(),
+                        "━".repeat(72).bright_black()
+                    ));
+                    let overall = &report.overall;
+                    text.push_str(&format!(
+                        "{} billed months: MAPE {}, bias {} ({} over, {} under)\n",
+                        overall.observations,
+                        percent(overall.mape, false),
+                        percent(overall.bias_percent, true),
+                        overall.over_predicted,
+                        overall.under_predicted
+                    ));
+                    if !report.modules.is_empty() {
+                        text.push_str(&format!(
+                            "\n  {:<28} {:>8} {:>8} {:>12} {:>10}\n",
+                            "Module", "MAPE", "Bias", "Over/under", "Correction"
+                        ));
+                        for module in &report.modules {
+                            text.push_str(&format!(
+                                "  {:<28} {:>8} {:>8} {:>12} {:>10}\n",
+                                module.module,
+                                percent(module.metrics.mape, false),
+                                percent(module.metrics.bias_percent, true),
+                                format!(
+                                    "{}/{}",
+                                    module.metrics.over_predicted, module.metrics.under_predicted
+                                ),
+                                module
+                                    .correction_factor
+                                    .map_or("-".to_string(), |f| format!("×{:.2}", f))
+                            ));
+                        }
+                    }
+                    text
+                }
+            };
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, &rendered)
+                        .map_err(|e| format!("Failed to write variance report: {}", e))?;
+                    println!(
+                        "{}",
+                        format!("✅ Variance report saved to '{}'", path.display())
+                            .bright_green()
+                            .bold()
+                    );
+                }
+                None => println!("{}", rendered.trim_end()),
+            }
+            Ok(())
+        }
+
         TrendCommands::Clean {
             keep,
             older_than,
@@ -1885,14 +2740,19 @@ fn cmd_audit(
         } => audit::cmd_audit_view(
             event_type, actor, resource, severity, last_n, format, verbose,
         ),
-        AuditCommands::Verify => audit::cmd_audit_verify(format, verbose),
+        AuditCommands::Verify { log, anchor } => {
+            audit::cmd_audit_verify_with_path(format, verbose, log, anchor)
+        }
         AuditCommands::Compliance { framework, days } => {
             audit::cmd_audit_compliance(framework, days, format, verbose)
         }
         AuditCommands::Export {
             format: output_format,
             output,
-        } => audit::cmd_audit_export(output_format, output, format, verbose),
+            since,
+            until,
+            log,
+        } => audit::cmd_audit_export(output_format, output, since, until, log, format, verbose),
         AuditCommands::Stats => audit::cmd_audit_stats(format, verbose),
         AuditCommands::Record {
             event_type,
@@ -1914,12 +2774,13 @@ fn cmd_audit(
 
 fn cmd_heuristics(
     command: costpilot::cli::heuristics::HeuristicsCommand,
-    _format: &str,
+    format: &str,
     _verbose: bool,
+    options: &GlobalOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use costpilot::cli::heuristics::execute_heuristics_command;
 
-    let output = execute_heuristics_command(command)?;
+    let output = execute_heuristics_command(command, format, options)?;
     println!("{}", output);
 
     Ok(())
@@ -1931,13 +2792,14 @@ fn cmd_explain(
     _format: &str,
     _verbose: bool,
     edition: &costpilot::edition::EditionContext,
+    options: &GlobalOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use costpilot::cli::explain::{execute_explain_args, execute_explain_command};
 
     let output = if let Some(a) = args {
         execute_explain_args(a, edition)?
     } else if let Some(cmd) = command {
-        execute_explain_command(cmd, edition)?
+        execute_explain_command(cmd, edition, options)?
     } else {
         return Err("No explain arguments provided".into());
     };
@@ -1951,14 +2813,15 @@ fn cmd_validate(
     files: Vec<PathBuf>,
     format: &str,
     fail_fast: bool,
+    fix: bool,
     edition: &costpilot::edition::EditionContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use costpilot::cli::commands::validate;
 
     if files.len() == 1 {
-        validate::execute(files[0].clone(), format.to_string(), edition)
+        validate::execute(files[0].clone(), format.to_string(), fix, edition)
     } else {
-        validate::execute_batch(files, format.to_string(), fail_fast, edition)
+        validate::execute_batch(files, format.to_string(), fail_fast, fix, edition)
     }
 }
 
@@ -2041,10 +2904,10 @@ fn cmd_version(detailed: bool, edition: &costpilot::edition::EditionContext) {
         "VERSION constant must match CARGO_PKG_VERSION"
     );
 
-    let edition_str = if edition.is_premium() {
-        "Premium"
-    } else {
-        "Free"
+    let edition_str = match edition.trial_days_remaining() {
+        Some(days) => format!("Trial, {} days remaining", days),
+        None if edition.is_premium() => "Premium".to_string(),
+        None => "Free".to_string(),
     };
 
     if detailed {
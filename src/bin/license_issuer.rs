@@ -1,5 +1,7 @@
 use clap::{Arg, Command};
-use costpilot::license_issuer::{generate_keypair, generate_license};
+use costpilot::license_issuer::{
+    generate_keypair, generate_license, sign_capability_policy, verify_usage_receipt,
+};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let matches = Command::new("CostPilot License Issuer")
@@ -32,8 +34,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .short('k')
                         .long("license-key")
                         .value_name("KEY")
-                        .help("License key string")
-                        .required(true),
+                        .help("License key string (generated for trials)")
+                        .required_unless_present("trial"),
                 )
                 .arg(
                     Arg::new("expires")
@@ -41,7 +43,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .long("expires")
                         .value_name("DATE")
                         .help("Expiration date in ISO 8601 format (e.g., 2025-12-31T23:59:59Z)")
-                        .required(true),
+                        .required_unless_present("trial")
+                        .conflicts_with("trial"),
+                )
+                .arg(
+                    Arg::new("trial")
+                        .long("trial")
+                        .help("Issue a 14-day trial license with every Premium feature")
+                        .action(clap::ArgAction::SetTrue),
                 )
                 .arg(
                     Arg::new("private-key")
@@ -68,6 +77,52 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .default_value("license.json"),
                 ),
         )
+        .subcommand(
+            Command::new("sign-capability-policy")
+                .about("Sign an organization capability policy for a license")
+                .arg(
+                    Arg::new("policy")
+                        .long("policy")
+                        .value_name("FILE")
+                        .help("Capability policy JSON (license_key, issuer, rules)")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("private-key")
+                        .short('p')
+                        .long("private-key")
+                        .value_name("FILE")
+                        .help("Path to Ed25519 private key file (raw 32 bytes)")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Output file path")
+                        .default_value("capability-policy.json"),
+                ),
+        )
+        .subcommand(
+            Command::new("verify-receipt")
+                .about("Verify a customer usage receipt and show its seat counts")
+                .arg(
+                    Arg::new("receipt")
+                        .long("receipt")
+                        .value_name("FILE")
+                        .help("Usage receipt JSON from `costpilot usage receipt`")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("license-key")
+                        .short('k')
+                        .long("license-key")
+                        .value_name("KEY")
+                        .help("License key the receipt should belong to")
+                        .required(true),
+                ),
+        )
         .get_matches();
 
     match matches.subcommand() {
@@ -77,6 +132,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(("generate-license", sub_matches)) => {
             generate_license(sub_matches, &std::env::current_dir().unwrap())
         }
+        Some(("sign-capability-policy", sub_matches)) => {
+            sign_capability_policy(sub_matches, &std::env::current_dir().unwrap())
+        }
+        Some(("verify-receipt", sub_matches)) => {
+            verify_usage_receipt(sub_matches, &std::env::current_dir().unwrap())
+        }
         _ => {
             println!("Use --help for usage information");
             Ok(())
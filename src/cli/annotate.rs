@@ -0,0 +1,459 @@
+// Annotate command - Terraform plan output with per-resource cost changes
+
+use crate::cli::options::GlobalOptions;
+use crate::cli::scan::ScanCommand;
+use crate::edition::EditionContext;
+use crate::engines::detection::DetectionEngine;
+use crate::engines::pipeline::predict_estimates;
+use crate::engines::prediction::{ClusterPricing, PlanPricing, UsageProfile};
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::models::{ChangeAction, ResourceChange};
+use clap::Args;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Project configuration read when `--config` is not given
+const PROJECT_CONFIG: &str = "costpilot.yaml";
+
+/// Block headers of `terraform show` output, by action symbol
+const BLOCK_PREFIXES: &[&str] = &[
+    "+ resource \"",
+    "- resource \"",
+    "~ resource \"",
+    "-/+ resource \"",
+    "+/- resource \"",
+];
+
+/// Re-emit a Terraform plan with the monthly cost change of each resource
+#[derive(Debug, Args)]
+pub struct AnnotateCommand {
+    /// Terraform plan JSON (`terraform show -json plan.tfplan`)
+    #[arg(long, value_name = "FILE")]
+    plan: PathBuf,
+
+    /// Human-readable plan to annotate (`terraform show -no-color plan.tfplan`);
+    /// rendered from the plan JSON when omitted
+    #[arg(long, value_name = "FILE")]
+    text: Option<PathBuf>,
+
+    /// Project configuration with cluster pricing
+    /// (default: costpilot.yaml when present)
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Output file path (default: stdout)
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+/// Monthly cost change of one resource block
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockAnnotation {
+    pub address: String,
+    pub action: ChangeAction,
+    pub monthly_delta: f64,
+}
+
+impl AnnotateCommand {
+    /// `format` is `terraform` (or the default `text`) for the annotated
+    /// plan, or `json` for the annotations alone
+    pub fn execute(
+        &self,
+        format: &str,
+        edition: &EditionContext,
+        options: &GlobalOptions,
+    ) -> Result<(), CostPilotError> {
+        if !matches!(format, "terraform" | "text" | "json") {
+            return Err(CostPilotError::new(
+                "E_VALIDATION",
+                ErrorCategory::ValidationError,
+                format!("Unsupported annotate format: {}", format),
+            )
+            .with_hint("Use --format terraform or --format json"));
+        }
+
+        let changes = DetectionEngine::new().detect_from_terraform_plan(&self.plan)?;
+        let config = match &self.config {
+            Some(path) => Some(path.as_path()),
+            None => Some(Path::new(PROJECT_CONFIG)).filter(|path| path.exists()),
+        };
+        let pricing = match config {
+            Some(path) => PlanPricing::new(
+                ClusterPricing::load(path, options.profile())?,
+                UsageProfile::load(path, options.profile())?,
+                &changes,
+            ),
+            None => PlanPricing::new(ClusterPricing::default(), UsageProfile::default(), &changes),
+        };
+        let annotations = annotate_changes(edition, &changes, &pricing)?;
+
+        let output = if format == "json" {
+            let total: f64 = annotations.iter().map(|a| a.monthly_delta).sum();
+            let output = serde_json::json!({
+                "monthly_delta": total,
+                "resources": annotations,
+            });
+            serde_json::to_string_pretty(&output)
+                .map_err(|e| CostPilotError::serialization_error(e.to_string()))?
+        } else {
+            let text = match &self.text {
+                Some(path) => std::fs::read_to_string(path).map_err(|e| {
+                    CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+                })?,
+                None => render_plan(&changes),
+            };
+            annotate_text(&text, &annotations)
+        };
+
+        match &self.output {
+            Some(path) => std::fs::write(path, output).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+            }),
+            None => {
+                println!("{}", output.trim_end());
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Cost change of every resource: the new configuration's estimate less the
+/// old one's. Estimates of a delete are zero, so the old configuration is
+/// priced as if it were being created.
+pub fn annotate_changes(
+    edition: &EditionContext,
+    changes: &[ResourceChange],
+    pricing: &PlanPricing,
+) -> Result<Vec<BlockAnnotation>, CostPilotError> {
+    let before: Vec<ResourceChange> = changes
+        .iter()
+        .filter(|c| c.action != ChangeAction::Create && c.old_config.is_some())
+        .map(|c| ResourceChange {
+            action: ChangeAction::Create,
+            new_config: c.old_config.clone(),
+            ..c.clone()
+        })
+        .collect();
+
+    let custom = ScanCommand::load_custom_heuristics()?;
+    let mut after_estimates = predict_estimates(edition, changes, pricing)?;
+    custom.apply(changes, &mut after_estimates);
+    let mut before_estimates = predict_estimates(edition, &before, pricing)?;
+    custom.apply(&before, &mut before_estimates);
+
+    let cost_of = |estimates: &[crate::engines::shared::models::CostEstimate]| {
+        estimates
+            .iter()
+            .map(|e| (e.resource_id.clone(), e.monthly_cost))
+            .collect::<HashMap<_, _>>()
+    };
+    let after_costs = cost_of(&after_estimates);
+    let before_costs = cost_of(&before_estimates);
+
+    Ok(changes
+        .iter()
+        .map(|change| {
+            let after = match change.action {
+                ChangeAction::Delete => 0.0,
+                _ => after_costs.get(&change.resource_id).copied().unwrap_or(0.0),
+            };
+            let before = before_costs
+                .get(&change.resource_id)
+                .copied()
+                .unwrap_or(0.0);
+            BlockAnnotation {
+                address: change.resource_id.clone(),
+                action: change.action.clone(),
+                monthly_delta: after - before,
+            }
+        })
+        .collect())
+}
+
+/// `+$123/mo`, `-$12.50/mo`
+pub fn format_delta(delta: f64) -> String {
+    let amount = format!("{:.2}", delta.abs());
+    let amount = amount.strip_suffix(".00").unwrap_or(&amount);
+    if delta >= 0.005 {
+        format!("+${}/mo", amount)
+    } else if delta <= -0.005 {
+        format!("-${}/mo", amount)
+    } else {
+        "$0/mo".to_string()
+    }
+}
+
+/// Append a `# +$123/mo` comment to every resource block header of a plan,
+/// and the total after the `Plan:` summary line
+///
+/// Blocks are matched to resources through the `# <address> will be ...`
+/// comment Terraform prints above each header; blocks without a cost (data
+/// sources, resources outside the plan JSON) are left as they are.
+pub fn annotate_text(text: &str, annotations: &[BlockAnnotation]) -> String {
+    let by_address: HashMap<&str, &BlockAnnotation> = annotations
+        .iter()
+        .map(|a| (a.address.as_str(), a))
+        .collect();
+    let total: f64 = annotations.iter().map(|a| a.monthly_delta).sum();
+
+    let mut output = String::with_capacity(text.len());
+    let mut pending: Option<&BlockAnnotation> = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        output.push_str(line);
+        if let Some(annotation) = trimmed
+            .strip_prefix("# ")
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|address| by_address.get(address))
+        {
+            pending = Some(*annotation);
+        } else if BLOCK_PREFIXES.iter().any(|p| trimmed.starts_with(p)) && trimmed.ends_with('{') {
+            if let Some(annotation) = pending.take() {
+                output.push_str("  # ");
+                output.push_str(&format_delta(annotation.monthly_delta));
+            }
+        } else if trimmed.starts_with("Plan: ") {
+            output.push_str(&format!(
+                "\n\nCostPilot: {} estimated across {} resource(s).",
+                format_delta(total),
+                annotations.len()
+            ));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Terraform-style plan text for `changes`, for when only the plan JSON is
+/// at hand: one block per resource with the attributes that change
+pub fn render_plan(changes: &[ResourceChange]) -> String {
+    if changes.is_empty() {
+        return "No changes. Your infrastructure matches the configuration.\n".to_string();
+    }
+
+    let mut text = String::from("Terraform will perform the following actions:\n");
+    let (mut add, mut change, mut destroy) = (0, 0, 0);
+    for resource in changes {
+        let (symbol, phrase) = match resource.action {
+            ChangeAction::Create => ("+", "will be created"),
+            ChangeAction::Delete => ("-", "will be destroyed"),
+            ChangeAction::Update => ("~", "will be updated in-place"),
+            ChangeAction::Replace => ("-/+", "must be replaced"),
+            ChangeAction::NoOp => continue,
+        };
+        match resource.action {
+            ChangeAction::Create => add += 1,
+            ChangeAction::Delete => destroy += 1,
+            ChangeAction::Update => change += 1,
+            _ => {
+                add += 1;
+                destroy += 1;
+            }
+        }
+
+        text.push_str(&format!(
+            "\n  # {} {}\n{:>width$} resource \"{}\" \"{}\" {{\n",
+            resource.resource_id,
+            phrase,
+            symbol,
+            resource.resource_type,
+            resource_name(&resource.resource_id, &resource.resource_type),
+            width = 2 + symbol.len(),
+        ));
+        for line in attribute_lines(resource) {
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text.push_str("    }\n");
+    }
+    text.push_str(&format!(
+        "\nPlan: {} to add, {} to change, {} to destroy.\n",
+        add, change, destroy
+    ));
+    text
+}
+
+/// `web` for `module.app.aws_instance.web[0]`
+fn resource_name(address: &str, resource_type: &str) -> String {
+    let marker = format!("{}.", resource_type);
+    let name = address
+        .rfind(&marker)
+        .map(|pos| &address[pos + marker.len()..])
+        .unwrap_or(address);
+    name.split('[').next().unwrap_or(name).to_string()
+}
+
+fn attribute_lines(resource: &ResourceChange) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let object = |config: &Option<Value>| -> serde_json::Map<String, Value> {
+        config
+            .as_ref()
+            .and_then(Value::as_object)
+            .unwrap_or(&empty)
+            .clone()
+    };
+    let (before, after) = (object(&resource.old_config), object(&resource.new_config));
+
+    let mut entries: Vec<(String, String, String)> = Vec::new();
+    match resource.action {
+        ChangeAction::Create => {
+            for (key, value) in after.iter().filter(|(_, v)| !v.is_null()) {
+                entries.push(("+".into(), key.clone(), render_value(value)));
+            }
+        }
+        ChangeAction::Delete => {
+            for (key, value) in before.iter().filter(|(_, v)| !v.is_null()) {
+                entries.push((
+                    "-".into(),
+                    key.clone(),
+                    format!("{} -> null", render_value(value)),
+                ));
+            }
+        }
+        _ => {
+            let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let old = before.get(key).unwrap_or(&Value::Null);
+                let new = after.get(key).unwrap_or(&Value::Null);
+                if old == new {
+                    continue;
+                }
+                let (symbol, rendered) = match (old.is_null(), new.is_null()) {
+                    (true, _) => ("+", render_value(new)),
+                    (_, true) => ("-", format!("{} -> null", render_value(old))),
+                    _ => (
+                        "~",
+                        format!("{} -> {}", render_value(old), render_value(new)),
+                    ),
+                };
+                entries.push((symbol.into(), key.clone(), rendered));
+            }
+        }
+    }
+
+    let width = entries
+        .iter()
+        .map(|(_, key, _)| key.len())
+        .max()
+        .unwrap_or(0);
+    entries
+        .into_iter()
+        .map(|(symbol, key, value)| format!("      {} {:<width$} = {}", symbol, key, value))
+        .collect()
+}
+
+fn render_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("{:?}", s),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn change(
+        id: &str,
+        action: ChangeAction,
+        old: Option<Value>,
+        new: Option<Value>,
+    ) -> ResourceChange {
+        ResourceChange {
+            resource_id: id.to_string(),
+            resource_type: "aws_instance".to_string(),
+            action,
+            module_path: None,
+            old_config: old,
+            new_config: new,
+            tags: HashMap::new(),
+            monthly_cost: None,
+            config: None,
+            cost_impact: None,
+        }
+    }
+
+    fn annotation(address: &str, monthly_delta: f64) -> BlockAnnotation {
+        BlockAnnotation {
+            address: address.to_string(),
+            action: ChangeAction::Create,
+            monthly_delta,
+        }
+    }
+
+    #[test]
+    fn test_format_delta() {
+        assert_eq!(format_delta(123.0), "+$123/mo");
+        assert_eq!(format_delta(-12.5), "-$12.50/mo");
+        assert_eq!(format_delta(0.001), "$0/mo");
+    }
+
+    #[test]
+    fn test_annotate_terraform_show_output() {
+        let text = r#"Terraform will perform the following actions:
+
+  # aws_instance.web will be created
+  + resource "aws_instance" "web" {
+      + instance_type = "t3.micro"
+    }
+
+  # module.vpc.aws_nat_gateway.this[0] will be destroyed
+  - resource "aws_nat_gateway" "this" {
+      - subnet_id = "subnet-1" -> null
+    }
+
+  # data.aws_ami.ubuntu will be read during apply
+ <= data "aws_ami" "ubuntu" {
+    }
+
+Plan: 1 to add, 0 to change, 1 to destroy.
+"#;
+        let annotated = annotate_text(
+            text,
+            &[
+                annotation("aws_instance.web", 150.0),
+                annotation("module.vpc.aws_nat_gateway.this[0]", -30.0),
+            ],
+        );
+
+        assert!(annotated.contains("  + resource \"aws_instance\" \"web\" {  # +$150/mo\n"));
+        assert!(annotated.contains("  - resource \"aws_nat_gateway\" \"this\" {  # -$30/mo\n"));
+        assert!(annotated.contains(" <= data \"aws_ami\" \"ubuntu\" {\n"));
+        assert!(annotated.contains("CostPilot: +$120/mo estimated across 2 resource(s)."));
+    }
+
+    #[test]
+    fn test_render_plan_from_changes() {
+        let changes = vec![
+            change(
+                "module.app.aws_instance.web[0]",
+                ChangeAction::Create,
+                None,
+                Some(json!({"ami": "ami-1", "instance_type": "t3.micro", "tags": null})),
+            ),
+            change(
+                "aws_instance.db",
+                ChangeAction::Update,
+                Some(json!({"instance_type": "t3.small", "ami": "ami-1"})),
+                Some(json!({"instance_type": "t3.large", "ami": "ami-1"})),
+            ),
+        ];
+        let text = render_plan(&changes);
+
+        assert!(text.contains("  # module.app.aws_instance.web[0] will be created\n"));
+        assert!(text.contains("  + resource \"aws_instance\" \"web\" {\n"));
+        assert!(text.contains("      + instance_type = \"t3.micro\"\n"));
+        assert!(!text.contains("tags"));
+        assert!(text.contains("  ~ resource \"aws_instance\" \"db\" {\n"));
+        assert!(text.contains("      ~ instance_type = \"t3.small\" -> \"t3.large\"\n"));
+        assert!(text.contains("Plan: 1 to add, 1 to change, 0 to destroy."));
+
+        let annotated = annotate_text(&text, &[annotation("aws_instance.db", 40.0)]);
+        assert!(annotated.contains("\"db\" {  # +$40/mo"));
+    }
+}
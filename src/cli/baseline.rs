@@ -4,12 +4,22 @@
 // - Recording expected costs from successful deployments
 // - Updating baselines with new cost expectations
 // - Validating baseline configurations
+// - Capturing baselines from a scan and blessing them with a signed approval
 
 use clap::Args;
-use std::path::PathBuf;
-
-use crate::engines::baselines::baseline_types::{Baseline, BaselinesConfig};
-use crate::engines::baselines::BaselinesManager;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::cli::commands::audit::{load_audit_log, save_audit_log, AUDIT_LOG_PATH};
+use crate::engines::baselines::approval::{
+    approval_path, decode_signing_key, decode_verifying_key, BaselineApproval,
+};
+use crate::engines::baselines::baseline_types::{Baseline, BaselineStatus, BaselinesConfig};
+use crate::engines::baselines::{
+    bless, capture_baselines, BaselinesManager, CaptureOptions, ToleranceSource,
+};
+use crate::engines::detection::DetectionEngine;
+use crate::engines::prediction::PredictionEngine;
 
 /// Manage cost baselines
 #[derive(Debug, Args)]
@@ -101,10 +111,12 @@ enum BaselineCommands {
     /// Show baseline status and violations
     ///
     /// Displays current baselines and any violations against recent costs.
+    /// With --plan, reports drift of each baseline and the tolerance band
+    /// used, widened from trend history when `tolerance.mode` is `trend`.
     ///
     /// Examples:
     ///   costpilot baseline status
-    ///   costpilot baseline status --baselines baselines.json
+    ///   costpilot baseline status --baselines baselines.json --plan tfplan.json
     Status {
         /// Path to baselines file
         #[arg(short, long, default_value = "baselines.json")]
@@ -114,6 +126,103 @@ enum BaselineCommands {
         #[arg(long)]
         plan: Option<PathBuf>,
     },
+
+    /// Capture global and per-module baselines from a scan
+    ///
+    /// Scans the plan and writes its total and per-module monthly costs as
+    /// baselines. Entries are merged into an existing file; services and
+    /// metadata are kept. Write to a candidate file to gate the update behind
+    /// `baseline bless`.
+    ///
+    /// Examples:
+    ///   costpilot baseline capture tfplan.json
+    ///   costpilot baseline capture tfplan.json --output baselines.candidate.json
+    Capture {
+        /// Path to Terraform plan JSON file
+        plan: PathBuf,
+
+        /// File to write captured baselines to
+        #[arg(short, long, default_value = "baselines.json")]
+        output: PathBuf,
+
+        /// Acceptable variance percentage for captured baselines
+        #[arg(long, default_value_t = 10.0)]
+        variance: f64,
+
+        /// Justification recorded on captured baselines
+        #[arg(long)]
+        justification: Option<String>,
+
+        /// Owner/team responsible for captured baselines
+        #[arg(long)]
+        owner: Option<String>,
+    },
+
+    /// Generate an Ed25519 key pair for approving baselines
+    ///
+    /// Writes the base64 signing key to FILE and the public key to FILE.pub.
+    /// Distribute the public key to pipelines running `baseline bless`.
+    ///
+    /// Examples:
+    ///   costpilot baseline keygen approver.key
+    Keygen {
+        /// Signing key file to create
+        key: PathBuf,
+    },
+
+    /// Sign an approval for a captured baseline candidate
+    ///
+    /// Examples:
+    ///   costpilot baseline approve baselines.candidate.json --key approver.key --approver alice
+    Approve {
+        /// Candidate baselines file written by `baseline capture`
+        candidate: PathBuf,
+
+        /// Base64 Ed25519 signing key file
+        #[arg(long)]
+        key: PathBuf,
+
+        /// Name of the approver, recorded in the audit log
+        #[arg(long)]
+        approver: String,
+
+        /// Reason for accepting the new baselines
+        #[arg(long)]
+        justification: Option<String>,
+
+        /// Approval file (default: <candidate>.approval.json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Apply a captured candidate once a signed approval is present
+    ///
+    /// The approval must sign the exact candidate content with one of the
+    /// trusted keys; otherwise baselines are left untouched. Applied updates
+    /// are recorded in the audit log.
+    ///
+    /// Examples:
+    ///   costpilot baseline bless baselines.candidate.json --trusted-key approver.key.pub
+    Bless {
+        /// Candidate baselines file written by `baseline capture`
+        candidate: PathBuf,
+
+        /// Approval file (default: <candidate>.approval.json)
+        #[arg(long)]
+        approval: Option<PathBuf>,
+
+        /// Base64 Ed25519 public key file of an allowed approver
+        #[arg(long = "trusted-key", required = true)]
+        trusted_keys: Vec<PathBuf>,
+
+        /// Path to baselines file
+        #[arg(short, long, default_value = "baselines.json")]
+        baselines: PathBuf,
+
+        /// Audit log to record the update in
+        #[arg(long, default_value = AUDIT_LOG_PATH)]
+        audit_log: PathBuf,
+    },
 }
 
 impl BaselineCommand {
@@ -142,9 +251,182 @@ impl BaselineCommand {
             BaselineCommands::Status { baselines, plan } => {
                 self.show_baseline_status(baselines, plan)
             }
+
+            BaselineCommands::Capture {
+                plan,
+                output,
+                variance,
+                justification,
+                owner,
+            } => self.capture_baselines(plan, output, *variance, justification, owner),
+
+            BaselineCommands::Keygen { key } => self.generate_key(key),
+
+            BaselineCommands::Approve {
+                candidate,
+                key,
+                approver,
+                justification,
+                output,
+            } => self.approve_candidate(candidate, key, approver, justification, output),
+
+            BaselineCommands::Bless {
+                candidate,
+                approval,
+                trusted_keys,
+                baselines,
+                audit_log,
+            } => self.bless_candidate(candidate, approval, trusted_keys, baselines, audit_log),
         }
     }
 
+    fn capture_baselines(
+        &self,
+        plan_path: &Path,
+        output_path: &Path,
+        variance: f64,
+        justification: &Option<String>,
+        owner: &Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🔄 Capturing baselines from {}...", plan_path.display());
+
+        let changes = DetectionEngine::new().detect_from_terraform_plan(plan_path)?;
+        let estimates = PredictionEngine::predict_static(&changes)?;
+
+        let defaults = CaptureOptions::default();
+        let options = CaptureOptions {
+            owner: owner.clone().unwrap_or(defaults.owner),
+            justification: justification
+                .clone()
+                .unwrap_or_else(|| format!("Captured from scan of {}", plan_path.display())),
+            variance_percent: variance,
+        };
+        let captured = capture_baselines(&changes, &estimates, &options);
+
+        let mut manager = if output_path.exists() {
+            BaselinesManager::load_from_file(output_path)?
+        } else {
+            BaselinesManager::from_config(BaselinesConfig::new())
+        };
+        manager.merge(&captured);
+        manager.validate().map_err(|errors| errors.join("; "))?;
+        manager.save_to_file(output_path)?;
+
+        if let Some(global) = &captured.global {
+            println!("   Global: ${:.2}/month", global.expected_monthly_cost);
+        }
+        let mut modules: Vec<_> = captured.modules.values().collect();
+        modules.sort_by(|a, b| a.name.cmp(&b.name));
+        for baseline in modules {
+            println!(
+                "   {}: ${:.2}/month",
+                baseline.name, baseline.expected_monthly_cost
+            );
+        }
+        println!("💾 Saved baselines to {}", output_path.display());
+
+        Ok(())
+    }
+
+    fn generate_key(&self, key_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        use base64::Engine;
+        use rand::RngCore;
+
+        if key_path.exists() {
+            return Err(format!("Key file already exists: {}", key_path.display()).into());
+        }
+
+        let mut secret = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut secret);
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&secret);
+        let engine = base64::engine::general_purpose::STANDARD;
+
+        let public_path = PathBuf::from(format!("{}.pub", key_path.display()));
+        std::fs::write(key_path, engine.encode(signing_key.to_bytes()))?;
+        std::fs::write(
+            &public_path,
+            engine.encode(signing_key.verifying_key().to_bytes()),
+        )?;
+
+        println!("🔑 Signing key: {}", key_path.display());
+        println!("   Public key:  {}", public_path.display());
+        Ok(())
+    }
+
+    fn approve_candidate(
+        &self,
+        candidate_path: &Path,
+        key_path: &Path,
+        approver: &str,
+        justification: &Option<String>,
+        output: &Option<PathBuf>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let candidate = std::fs::read(candidate_path)
+            .map_err(|e| format!("Failed to read {}: {}", candidate_path.display(), e))?;
+        let key = decode_signing_key(&std::fs::read_to_string(key_path)?)?;
+
+        let approval = BaselineApproval::sign(
+            &candidate,
+            approver,
+            justification.clone().unwrap_or_default(),
+            &key,
+        );
+        let output = output
+            .clone()
+            .unwrap_or_else(|| approval_path(candidate_path));
+        approval.save(&output)?;
+
+        println!("✍️  Approved {} as {}", candidate_path.display(), approver);
+        println!("   Approval: {}", output.display());
+        Ok(())
+    }
+
+    fn bless_candidate(
+        &self,
+        candidate_path: &Path,
+        approval: &Option<PathBuf>,
+        trusted_key_paths: &[PathBuf],
+        baselines_path: &Path,
+        audit_log_path: &Path,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let trusted = trusted_key_paths
+            .iter()
+            .map(|path| {
+                let encoded = std::fs::read_to_string(path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                decode_verifying_key(&encoded).map_err(|e| format!("{}: {}", path.display(), e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let approval_path = approval
+            .clone()
+            .unwrap_or_else(|| approval_path(candidate_path));
+
+        let mut log = load_audit_log(Some(audit_log_path.to_path_buf()))?;
+        let outcome = bless(
+            candidate_path,
+            &approval_path,
+            baselines_path,
+            &trusted,
+            &mut log,
+        )?;
+        save_audit_log(&log, Some(audit_log_path.to_path_buf()))?;
+
+        println!(
+            "✅ Blessed {} baseline(s), approved by {}",
+            outcome.updated.len(),
+            outcome.approver
+        );
+        for name in &outcome.updated {
+            println!("   • {}", name);
+        }
+        println!(
+            "📝 Recorded in {} (entry #{})",
+            audit_log_path.display(),
+            outcome.audit_sequence
+        );
+        Ok(())
+    }
+
     fn record_baselines(
         &self,
         plan_path: &PathBuf,
@@ -331,10 +613,46 @@ impl BaselineCommand {
 
         // Compare against plan if provided
         if let Some(plan) = plan_path {
-            println!("\n📈 Comparison with plan: {}", plan.display());
+            println!("\n📈 Drift against plan: {}", plan.display());
+
+            let changes = DetectionEngine::new().detect_from_terraform_plan(plan)?;
+            let estimates = PredictionEngine::predict_static(&changes)?;
+            let costs: HashMap<&str, f64> = estimates
+                .iter()
+                .map(|e| (e.resource_id.as_str(), e.monthly_cost))
+                .collect();
+            let total_cost: f64 = costs.values().sum();
+            let mut module_costs = HashMap::new();
+            for change in &changes {
+                if let (Some(module), Some(cost)) =
+                    (&change.module_path, costs.get(change.resource_id.as_str()))
+                {
+                    *module_costs.entry(module.clone()).or_insert(0.0) += cost;
+                }
+            }
 
-            // This would need plan parsing integration
-            println!("   Plan comparison not yet implemented");
+            let manager = manager.with_configured_history();
+            for entry in manager.drift_report(total_cost, &module_costs) {
+                let marker = match entry.status {
+                    BaselineStatus::Within => "✅",
+                    BaselineStatus::Exceeded { .. } => "❌",
+                    _ => "⚠️",
+                };
+                let band = match entry.tolerance.source {
+                    ToleranceSource::Fixed => format!("±{:.1}%", entry.tolerance.percent),
+                    ToleranceSource::Trend {
+                        samples,
+                        observed_percent,
+                    } => format!(
+                        "±{:.1}% (trend: {:.1}% observed over {} snapshots)",
+                        entry.tolerance.percent, observed_percent, samples
+                    ),
+                };
+                println!(
+                    "   {} {}: ${:.2} vs ${:.2} expected, band {}",
+                    marker, entry.name, entry.actual_cost, entry.expected_cost, band
+                );
+            }
         }
 
         Ok(())
@@ -0,0 +1,146 @@
+// Calibrate command - learn prediction interval widths from billing actuals
+
+use crate::cli::options::GlobalOptions;
+use crate::cli::scan::ScanCommand;
+use crate::edition::EditionContext;
+use crate::engines::detection::DetectionEngine;
+use crate::engines::pipeline::predict_estimates;
+use crate::engines::prediction::{
+    parse_actuals_csv, CalibrationUpdate, ClusterPricing, ConfidenceCalibration, PlanPricing,
+    UsageProfile,
+};
+use crate::engines::shared::error_model::CostPilotError;
+use clap::Args;
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// Project configuration read when `--config` is not given
+const PROJECT_CONFIG: &str = "costpilot.yaml";
+
+/// Import actual costs per module and recalibrate prediction intervals
+#[derive(Debug, Args)]
+pub struct CalibrateCommand {
+    /// Terraform plan JSON of the infrastructure the bill covers
+    #[arg(long, value_name = "FILE")]
+    plan: PathBuf,
+
+    /// CSV of actual monthly cost by module (columns: module, monthly_cost;
+    /// `root` for resources outside modules)
+    #[arg(long, value_name = "FILE")]
+    actuals: PathBuf,
+
+    /// Project configuration with cluster pricing
+    /// (default: costpilot.yaml when present)
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Show the comparison without saving the calibration
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl CalibrateCommand {
+    pub fn execute_with_edition(
+        &self,
+        edition: &EditionContext,
+        options: &GlobalOptions,
+    ) -> Result<(), CostPilotError> {
+        let content = std::fs::read_to_string(&self.actuals).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", self.actuals.display(), e))
+        })?;
+        let actuals = parse_actuals_csv(&content)?;
+
+        let changes = DetectionEngine::new().detect_from_terraform_plan(&self.plan)?;
+        let config = match &self.config {
+            Some(path) => Some(path.as_path()),
+            None => Some(std::path::Path::new(PROJECT_CONFIG)).filter(|path| path.exists()),
+        };
+        let pricing = match config {
+            Some(path) => PlanPricing::new(
+                ClusterPricing::load(path, options.profile())?,
+                UsageProfile::load(path, options.profile())?,
+                &changes,
+            ),
+            None => PlanPricing::new(ClusterPricing::default(), UsageProfile::default(), &changes),
+        };
+        let mut estimates = predict_estimates(edition, &changes, &pricing)?;
+        ScanCommand::load_custom_heuristics()?.apply(&changes, &mut estimates);
+
+        let path = ConfidenceCalibration::path(&edition.paths.config_dir);
+        let mut calibration = ConfidenceCalibration::load(&path)?;
+        let update = calibration.record(&changes, &estimates, &actuals);
+        print!("{}", format_update(&update));
+
+        if update.widths.is_empty() {
+            println!("\nNo module could be compared; calibration unchanged");
+        } else if self.dry_run {
+            println!("\nDry run: calibration not saved");
+        } else {
+            calibration.save(&path)?;
+            println!("\n✅ Calibration saved to {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+fn format_update(update: &CalibrationUpdate) -> String {
+    let mut output = format!("{}\n\n", "🎯 Prediction Calibration".bold().cyan());
+
+    output.push_str("Modules:\n");
+    for module in &update.modules {
+        let error = match module.relative_error() {
+            Some(error) => format!("{:.0}% off", error * 100.0),
+            None => "nothing predicted".to_string(),
+        };
+        output.push_str(&format!(
+            "  {}  predicted ${:.2}, billed ${:.2} ({})\n",
+            module.module, module.predicted, module.actual, error
+        ));
+    }
+    for module in &update.unmatched {
+        output.push_str(&format!(
+            "  {}  {}\n",
+            module,
+            "not in plan, skipped".bright_black()
+        ));
+    }
+
+    if !update.widths.is_empty() {
+        output.push_str("\nInterval width by resource family:\n");
+        for (family, (before, after)) in &update.widths {
+            let before = before.map_or("default".to_string(), |w| format!("±{:.0}%", w * 100.0));
+            output.push_str(&format!(
+                "  {}  {} → ±{:.0}%\n",
+                family,
+                before,
+                after * 100.0
+            ));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::calibration::ModuleError;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_format_update_lists_modules_and_widths() {
+        let update = CalibrationUpdate {
+            modules: vec![ModuleError {
+                module: "module.web".to_string(),
+                predicted: 100.0,
+                actual: 130.0,
+            }],
+            unmatched: vec!["module.old".to_string()],
+            widths: BTreeMap::from([("aws_instance".to_string(), (Some(0.1), 0.3))]),
+        };
+
+        let output = format_update(&update);
+        assert!(output.contains("module.web  predicted $100.00, billed $130.00 (30% off)"));
+        assert!(output.contains("module.old") && output.contains("not in plan, skipped"));
+        assert!(output.contains("aws_instance  ±10% → ±30%"));
+    }
+}
@@ -1,22 +1,25 @@
 // CLI commands for audit log and compliance reporting
 
 use crate::engines::policy::{
-    AuditEvent, AuditEventType, AuditLog, AuditQuery, AuditSeverity, ComplianceAnalyzer,
-    ComplianceFramework,
+    AuditAnchor, AuditEvent, AuditEventType, AuditLog, AuditQuery, AuditSeverity,
+    ComplianceAnalyzer, ComplianceFramework,
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use colored::Colorize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-const AUDIT_LOG_PATH: &str = ".costpilot/audit_log.json";
+pub const AUDIT_LOG_PATH: &str = ".costpilot/audit_log.json";
 
-/// Load audit log from file
-fn load_audit_log(path: Option<PathBuf>) -> Result<AuditLog, Box<dyn std::error::Error>> {
-    let log_path = path.unwrap_or_else(|| PathBuf::from(AUDIT_LOG_PATH));
+/// Anchor written next to a log on every save (`audit_log.anchor.json`)
+pub fn anchor_path(log_path: &Path) -> PathBuf {
+    log_path.with_extension("anchor.json")
+}
 
+/// Read audit log from file without checking its anchor
+fn read_audit_log(log_path: &Path) -> Result<AuditLog, Box<dyn std::error::Error>> {
     if log_path.exists() {
-        let contents = fs::read_to_string(&log_path)?;
+        let contents = fs::read_to_string(log_path)?;
         let log: AuditLog = serde_json::from_str(&contents)?;
         Ok(log)
     } else {
@@ -24,8 +27,43 @@ fn load_audit_log(path: Option<PathBuf>) -> Result<AuditLog, Box<dyn std::error:
     }
 }
 
+/// Load audit log from file, refusing a log that was deleted, truncated or
+/// rewritten since its anchor was saved so new events never restart the chain
+pub(crate) fn load_audit_log(
+    path: Option<PathBuf>,
+) -> Result<AuditLog, Box<dyn std::error::Error>> {
+    let log_path = path.unwrap_or_else(|| PathBuf::from(AUDIT_LOG_PATH));
+    let anchor_file = anchor_path(&log_path);
+    if !anchor_file.exists() {
+        return read_audit_log(&log_path);
+    }
+
+    if !log_path.exists() {
+        return Err(format!(
+            "Audit log {} is missing but {} anchors it; restore the log before recording events",
+            log_path.display(),
+            anchor_file.display()
+        )
+        .into());
+    }
+    let log = read_audit_log(&log_path)?;
+    let anchor: AuditAnchor = serde_json::from_str(&fs::read_to_string(&anchor_file)?)?;
+    log.verify_anchor(&anchor).map_err(|e| {
+        format!(
+            "Audit log {} does not match its anchor {}: {}",
+            log_path.display(),
+            anchor_file.display(),
+            e
+        )
+    })?;
+    Ok(log)
+}
+
 /// Save audit log to file
-fn save_audit_log(log: &AuditLog, path: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+pub(crate) fn save_audit_log(
+    log: &AuditLog,
+    path: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
     let log_path = path.unwrap_or_else(|| PathBuf::from(AUDIT_LOG_PATH));
 
     // Create directory if needed
@@ -35,6 +73,10 @@ fn save_audit_log(log: &AuditLog, path: Option<PathBuf>) -> Result<(), Box<dyn s
 
     let json = serde_json::to_string_pretty(log)?;
     fs::write(&log_path, json)?;
+    fs::write(
+        anchor_path(&log_path),
+        serde_json::to_string_pretty(&log.anchor())?,
+    )?;
 
     Ok(())
 }
@@ -78,6 +120,10 @@ pub fn cmd_audit_view_with_path(
             "policy_version_created" => AuditEventType::PolicyVersionCreated,
             "policy_activated" => AuditEventType::PolicyActivated,
             "slo_violation" => AuditEventType::SloViolation,
+            "rollback_executed" => AuditEventType::RollbackExecuted,
+            "baseline_blessed" => AuditEventType::BaselineBlessed,
+            "engine_verified" => AuditEventType::EngineVerified,
+            "engine_rejected" => AuditEventType::EngineRejected,
             _ => return Err(format!("Unknown event type: {}", et).into()),
         };
         query = query.with_event_type(event_type);
@@ -178,10 +224,37 @@ pub fn cmd_audit_view_with_path(
 
 /// Verify audit log integrity
 pub fn cmd_audit_verify(format: &str, _verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let log = load_audit_log(None)?;
+    cmd_audit_verify_with_path(format, _verbose, None, None)
+}
+
+/// Verify the chain of a log and, when an anchor is available, that it has
+/// not been truncated or rewritten since the anchor was taken
+pub fn cmd_audit_verify_with_path(
+    format: &str,
+    _verbose: bool,
+    log_path: Option<PathBuf>,
+    anchor: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let log_path = log_path.unwrap_or_else(|| PathBuf::from(AUDIT_LOG_PATH));
+    let log = read_audit_log(&log_path)?;
+
+    // An explicit anchor must exist; the sidecar is optional for older logs
+    let anchor_file = anchor.clone().unwrap_or_else(|| anchor_path(&log_path));
+    let anchor: Option<AuditAnchor> = if anchor.is_some() || anchor_file.exists() {
+        let contents = fs::read_to_string(&anchor_file)
+            .map_err(|e| format!("Failed to read anchor {}: {}", anchor_file.display(), e))?;
+        Some(serde_json::from_str(&contents)?)
+    } else {
+        None
+    };
+
+    let result = match &anchor {
+        Some(anchor) => log.verify_anchor(anchor),
+        None => log.verify_chain(),
+    };
 
     if format == "json" {
-        let result = match log.verify_chain() {
+        let mut output = match &result {
             Ok(_) => serde_json::json!({
                 "verified": true,
                 "entry_count": log.entry_count(),
@@ -194,7 +267,8 @@ pub fn cmd_audit_verify(format: &str, _verbose: bool) -> Result<(), Box<dyn std:
                 "error": e.to_string()
             }),
         };
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        output["anchor"] = serde_json::to_value(&anchor)?;
+        println!("{}", serde_json::to_string_pretty(&output)?);
     } else {
         println!(
             "{}",
@@ -203,7 +277,7 @@ pub fn cmd_audit_verify(format: &str, _verbose: bool) -> Result<(), Box<dyn std:
         println!("{}", "━".repeat(80).bright_black());
         println!();
 
-        match log.verify_chain() {
+        match &result {
             Ok(_) => {
                 println!("{}", "✅ Audit log verification: PASS".green().bold());
                 println!();
@@ -213,6 +287,19 @@ pub fn cmd_audit_verify(format: &str, _verbose: bool) -> Result<(), Box<dyn std:
                 if let Some(last) = log.last_entry() {
                     println!("Last entry hash: {}", &last.hash[..16].bright_black());
                 }
+                match &anchor {
+                    Some(anchor) => println!(
+                        "Anchor: {} entries, head {} ({})",
+                        anchor.entry_count,
+                        anchor.head_hash[..16].bright_black(),
+                        anchor_file.display()
+                    ),
+                    None => println!(
+                        "{} No anchor at {}; truncation of the newest entries cannot be detected.",
+                        "⚠".yellow(),
+                        anchor_file.display()
+                    ),
+                }
             }
             Err(e) => {
                 println!("{}", "❌ Audit log verification: FAIL".red().bold());
@@ -226,6 +313,9 @@ pub fn cmd_audit_verify(format: &str, _verbose: bool) -> Result<(), Box<dyn std:
         }
     }
 
+    if result.is_err() {
+        return Err("Audit log verification failed".into());
+    }
     Ok(())
 }
 
@@ -236,7 +326,18 @@ pub fn cmd_audit_compliance(
     format: &str,
     _verbose: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let log = load_audit_log(None)?;
+    cmd_audit_compliance_with_path(framework, days, format, _verbose, None)
+}
+
+/// Generate compliance report with custom path (for testing)
+pub fn cmd_audit_compliance_with_path(
+    framework: String,
+    days: u32,
+    format: &str,
+    _verbose: bool,
+    audit_log_path: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let log = load_audit_log(audit_log_path)?;
 
     let compliance_framework = match framework.to_uppercase().as_str() {
         "SOC2" => ComplianceFramework::Soc2,
@@ -350,19 +451,58 @@ pub fn cmd_audit_compliance(
     Ok(())
 }
 
+/// Parse a `--since`/`--until` bound: RFC 3339, or a date covering the whole day
+fn parse_time_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        format!(
+            "Invalid time '{}': expected RFC 3339 (2025-01-31T12:00:00Z) or YYYY-MM-DD",
+            value
+        )
+    })?;
+    let time = if end_of_day {
+        date.and_hms_nano_opt(23, 59, 59, 999_999_999)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+    Ok(time.expect("valid time of day").and_utc())
+}
+
 /// Export audit log
 pub fn cmd_audit_export(
     output_format: String,
     output_path: PathBuf,
+    since: Option<String>,
+    until: Option<String>,
+    log_path: Option<PathBuf>,
     _format: &str,
     _verbose: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let log = load_audit_log(None)?;
-
-    let export_data = match output_format.to_lowercase().as_str() {
-        "ndjson" => log.export_ndjson()?,
-        "csv" => log.export_csv()?,
-        "json" => serde_json::to_string_pretty(&log)?,
+    let log = load_audit_log(log_path)?;
+    let since = since.map(|s| parse_time_bound(&s, false)).transpose()?;
+    let until = until.map(|u| parse_time_bound(&u, true)).transpose()?;
+    let ranged = since.is_some() || until.is_some();
+
+    let (export_data, exported) = match output_format.to_lowercase().as_str() {
+        "ndjson" | "jsonl" => {
+            let data = log.export_ndjson_range(since, until)?;
+            let count = data.lines().count();
+            (data, count)
+        }
+        "csv" => {
+            let data = log.export_csv_range(since, until)?;
+            let count = data.lines().count().saturating_sub(1);
+            (data, count)
+        }
+        "json" if ranged => {
+            return Err(
+                "Time-range filters apply to ndjson/jsonl and csv exports; json exports the whole chain"
+                    .into(),
+            )
+        }
+        "json" => (serde_json::to_string_pretty(&log)?, log.entry_count()),
         _ => return Err(format!("Unknown export format: {}", output_format).into()),
     };
 
@@ -371,7 +511,18 @@ pub fn cmd_audit_export(
     println!("{}", "✅ Export complete".green().bold());
     println!();
     println!("Format: {}", output_format);
-    println!("Entries exported: {}", log.entry_count());
+    println!("Entries exported: {} of {}", exported, log.entry_count());
+    if ranged {
+        let bound = |t: Option<DateTime<Utc>>| t.map(|t| t.to_rfc3339()).unwrap_or("-".into());
+        println!("Time range: {} to {}", bound(since), bound(until));
+    }
+    match log.verify_chain() {
+        Ok(_) => println!(
+            "Chain verified: yes (head {})",
+            &log.anchor().head_hash[..16]
+        ),
+        Err(e) => println!("{} Chain verified: no ({})", "⚠".red(), e),
+    }
     println!("Output file: {}", output_path.display());
 
     Ok(())
@@ -379,7 +530,16 @@ pub fn cmd_audit_export(
 
 /// Get audit log statistics
 pub fn cmd_audit_stats(format: &str, _verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let log = load_audit_log(None)?;
+    cmd_audit_stats_with_path(format, _verbose, None)
+}
+
+/// Get audit log statistics with custom path (for testing)
+pub fn cmd_audit_stats_with_path(
+    format: &str,
+    _verbose: bool,
+    audit_log_path: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let log = load_audit_log(audit_log_path)?;
     let stats = log.get_statistics()?;
 
     if format == "json" {
@@ -445,7 +605,31 @@ pub fn cmd_audit_record(
     format: &str,
     _verbose: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut log = load_audit_log(None)?;
+    cmd_audit_record_with_path(
+        event_type,
+        actor,
+        resource_id,
+        resource_type,
+        description,
+        format,
+        _verbose,
+        None,
+    )
+}
+
+/// Record a manual audit event with custom path (for testing)
+#[allow(clippy::too_many_arguments)]
+pub fn cmd_audit_record_with_path(
+    event_type: String,
+    actor: String,
+    resource_id: String,
+    resource_type: String,
+    description: String,
+    format: &str,
+    _verbose: bool,
+    audit_log_path: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut log = load_audit_log(audit_log_path.clone())?;
 
     let audit_event_type = match event_type.to_lowercase().as_str() {
         "policy_state_change" => AuditEventType::PolicyStateChange,
@@ -465,7 +649,7 @@ pub fn cmd_audit_record(
     );
 
     let sequence = log.append(event)?;
-    save_audit_log(&log, None)?;
+    save_audit_log(&log, audit_log_path)?;
 
     if format == "json" {
         let result = serde_json::json!({
@@ -594,59 +778,149 @@ mod tests {
 
     #[test]
     fn test_cmd_audit_verify() {
-        // Clean up any existing audit log to ensure test isolation
-        let _ = std::fs::remove_file(AUDIT_LOG_PATH);
+        // Use a temporary file for test isolation; other tests write the default log
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("audit_log.json");
 
-        let result = cmd_audit_verify("text", false);
+        let result = cmd_audit_verify_with_path("text", false, Some(log_path.clone()), None);
         assert!(result.is_ok());
 
-        let _result = cmd_audit_verify("json", false);
-        let _ = std::fs::remove_file(AUDIT_LOG_PATH);
+        let _result = cmd_audit_verify_with_path("json", false, Some(log_path.clone()), None);
 
-        let result = cmd_audit_stats("text", false);
+        let result = cmd_audit_stats_with_path("text", false, Some(log_path.clone()));
         assert!(result.is_ok());
 
-        let result = cmd_audit_stats("json", false);
+        let result = cmd_audit_stats_with_path("json", false, Some(log_path));
         assert!(result.is_ok());
     }
 
     #[test]
-    fn test_cmd_audit_compliance() {
-        // Clean up any existing audit log to ensure test isolation
-        let _ = std::fs::remove_file(AUDIT_LOG_PATH);
+    fn test_verify_detects_truncation_with_anchor() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("audit_log.json");
 
-        let result = cmd_audit_compliance("SOC2".to_string(), 30, "text", false);
-        assert!(result.is_ok());
+        let mut log = AuditLog::new();
+        for actor in ["alice", "bob"] {
+            log.append(AuditEvent::new(
+                AuditEventType::PolicyApproval,
+                actor.to_string(),
+                "policy-1".to_string(),
+                "policy".to_string(),
+                "Approved".to_string(),
+            ))
+            .unwrap();
+        }
+        save_audit_log(&log, Some(log_path.clone())).unwrap();
+        assert!(anchor_path(&log_path).exists());
+        assert!(cmd_audit_verify_with_path("json", false, Some(log_path.clone()), None).is_ok());
+
+        // Drop the newest entry without touching the anchor
+        let mut contents: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&log_path).unwrap()).unwrap();
+        contents["entries"].as_array_mut().unwrap().pop();
+        fs::write(&log_path, contents.to_string()).unwrap();
+        assert!(cmd_audit_verify_with_path("json", false, Some(log_path.clone()), None).is_err());
+
+        let missing = temp_dir.path().join("missing.anchor.json");
+        assert!(cmd_audit_verify_with_path("json", false, Some(log_path), Some(missing)).is_err());
+    }
 
-        let result = cmd_audit_compliance("GDPR".to_string(), 30, "json", false);
-        assert!(result.is_ok());
+    #[test]
+    fn test_parse_time_bound() {
+        assert_eq!(
+            parse_time_bound("2025-03-01", false).unwrap().to_rfc3339(),
+            "2025-03-01T00:00:00+00:00"
+        );
+        assert!(
+            parse_time_bound("2025-03-01", true).unwrap()
+                > parse_time_bound("2025-03-01T23:59:59Z", false).unwrap()
+        );
+        assert_eq!(
+            parse_time_bound("2025-03-01T10:00:00+02:00", false)
+                .unwrap()
+                .to_rfc3339(),
+            "2025-03-01T08:00:00+00:00"
+        );
+        assert!(parse_time_bound("last week", false).is_err());
     }
 
     #[test]
-    fn test_cmd_audit_record() {
-        // Clean up any existing audit log to ensure test isolation
-        let _ = std::fs::remove_file(AUDIT_LOG_PATH);
+    fn test_cmd_audit_compliance() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("audit_log.json");
 
-        let result = cmd_audit_record(
-            "policy_state_change".to_string(),
-            "test_user".to_string(),
-            "test_policy".to_string(),
-            "policy".to_string(),
-            "Test event".to_string(),
+        let result = cmd_audit_compliance_with_path(
+            "SOC2".to_string(),
+            30,
             "text",
             false,
+            Some(log_path.clone()),
         );
         assert!(result.is_ok());
 
-        let result = cmd_audit_record(
-            "policy_approval".to_string(),
-            "test_user".to_string(),
-            "test_policy".to_string(),
-            "policy".to_string(),
-            "Test approval".to_string(),
-            "json",
-            false,
-        );
+        let result =
+            cmd_audit_compliance_with_path("GDPR".to_string(), 30, "json", false, Some(log_path));
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_cmd_audit_record() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("audit_log.json");
+        let record = |event_type: &str, format: &str| {
+            cmd_audit_record_with_path(
+                event_type.to_string(),
+                "test_user".to_string(),
+                "test_policy".to_string(),
+                "policy".to_string(),
+                "Test event".to_string(),
+                format,
+                false,
+                Some(log_path.clone()),
+            )
+        };
+
+        assert!(record("policy_state_change", "text").is_ok());
+        assert!(record("policy_approval", "json").is_ok());
+        assert_eq!(
+            load_audit_log(Some(log_path.clone()))
+                .unwrap()
+                .entry_count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_record_refuses_to_restart_a_truncated_log() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("audit_log.json");
+        let record = || {
+            cmd_audit_record_with_path(
+                "policy_approval".to_string(),
+                "alice".to_string(),
+                "policy-1".to_string(),
+                "policy".to_string(),
+                "Approved".to_string(),
+                "json",
+                false,
+                Some(log_path.clone()),
+            )
+        };
+        record().unwrap();
+        record().unwrap();
+        let anchor = fs::read_to_string(anchor_path(&log_path)).unwrap();
+
+        // Dropping the newest entry no longer matches the anchor
+        let mut contents: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&log_path).unwrap()).unwrap();
+        contents["entries"].as_array_mut().unwrap().pop();
+        fs::write(&log_path, contents.to_string()).unwrap();
+        assert!(record().is_err());
+
+        // Deleting the log does not start a fresh chain either
+        fs::remove_file(&log_path).unwrap();
+        assert!(record().is_err());
+        assert!(!log_path.exists());
+        assert_eq!(fs::read_to_string(anchor_path(&log_path)).unwrap(), anchor);
+    }
 }
@@ -1,5 +1,6 @@
 // Autofix patch command implementation - Generate full unified diff patches
 
+use crate::engines::autofix::{AutofixEngine, AutofixMode, AutofixTarget, CdkLanguage};
 use crate::engines::detection::DetectionEngine;
 use crate::engines::prediction::PredictionEngine;
 use clap::Args;
@@ -8,10 +9,14 @@ use std::path::PathBuf;
 
 #[derive(Debug, Args)]
 pub struct AutofixPatchArgs {
-    /// Path to Terraform plan JSON file
+    /// Path to Terraform plan JSON or CloudFormation/CDK template
     #[arg(long, value_name = "FILE")]
     pub plan: PathBuf,
 
+    /// Language for CDK property overrides (typescript, python)
+    #[arg(long, value_name = "LANG", default_value = "typescript")]
+    pub cdk_language: CdkLanguage,
+
     /// Output file for patches (default: stdout)
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<PathBuf>,
@@ -21,16 +26,16 @@ pub struct AutofixPatchArgs {
     pub apply: bool,
 
     /// Show detailed patch metadata
-    #[arg(short, long)]
-    pub verbose: bool,
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
 }
 
 pub fn execute(
     args: &AutofixPatchArgs,
     edition: &crate::edition::EditionContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Require Premium for autofix
-    crate::edition::require_premium(edition, "Autofix")?;
+    // Require Premium autofix, unless the organization policy disabled it
+    edition.require_capability(crate::edition::Capability::Autofix, "Autofix")?;
 
     println!(
         "{}",
@@ -38,14 +43,15 @@ pub fn execute(
     );
     println!();
 
-    // Load and parse plan
-    println!("{}", "Loading Terraform plan...".dimmed());
-    let plan_content = std::fs::read_to_string(&args.plan)?;
-    let plan: serde_json::Value = serde_json::from_str(&plan_content)?;
-
-    // Extract resource changes
-    let changes = crate::cli::utils::extract_resource_changes(&plan)?;
-    println!("   Found {} resource changes", changes.len());
+    // Load plan or template
+    println!("{}", "Loading plan...".dimmed());
+    let input = crate::cli::utils::load_autofix_input(&args.plan, args.cdk_language)?;
+    let changes = input.changes;
+    println!(
+        "   Found {} resource changes ({})",
+        changes.len(),
+        input.target.name()
+    );
     println!();
 
     // Detect cost regressions
@@ -81,12 +87,24 @@ pub fn execute(
 
     // Generate patches
     println!("{}", "Generating fix patches...".dimmed());
-    let autofix_result = edition.require_pro("Autofix")?.autofix(
-        &detections_with_estimates,
-        &changes,
-        &[], // estimates not used for patch mode
-        crate::engines::autofix::AutofixMode::Patch,
-    )?;
+    let autofix_result = if input.target == AutofixTarget::Terraform {
+        edition.require_pro("Autofix")?.autofix(
+            &detections_with_estimates,
+            &changes,
+            &[], // estimates not used for patch mode
+            AutofixMode::Patch,
+        )?
+    } else {
+        AutofixEngine::generate_fixes_for_target(
+            &detections_with_estimates,
+            &changes,
+            &[],
+            AutofixMode::Patch,
+            input.target,
+            &input.construct_paths,
+            edition,
+        )?
+    };
 
     if autofix_result.patches.is_empty() {
         println!("   {} No patches available", "ℹ".bright_blue());
@@ -111,7 +129,7 @@ pub fn execute(
         output_buffer.push_str(&format!("{}\n", header.bold().green()));
         output_buffer.push_str(&format!("{}\n", "=".repeat(header.len())));
 
-        if args.verbose {
+        if args.verbose > 0 {
             output_buffer.push_str(&format!("Resource Type: {}\n", patch.resource_type));
             output_buffer.push_str(&format!("File: {}\n", patch.filename));
             output_buffer.push_str(&format!(
@@ -1,5 +1,8 @@
 // Autofix snippet command implementation - Generate fix snippets (MVP)
 
+use crate::engines::autofix::{
+    AutofixEngine, AutofixMode, AutofixTarget, CdkLanguage, SnippetFormat,
+};
 use crate::engines::detection::DetectionEngine;
 use crate::engines::prediction::PredictionEngine;
 use colored::Colorize;
@@ -7,6 +10,7 @@ use std::path::PathBuf;
 
 pub struct AutofixSnippetArgs {
     pub plan: PathBuf,
+    pub cdk_language: CdkLanguage,
     pub verbose: bool,
 }
 
@@ -14,20 +18,21 @@ pub fn execute(
     args: &AutofixSnippetArgs,
     edition: &crate::edition::EditionContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // Require Premium for autofix
-    crate::edition::require_premium(edition, "Autofix")?;
+    // Require Premium autofix, unless the organization policy disabled it
+    edition.require_capability(crate::edition::Capability::Autofix, "Autofix")?;
 
     println!("{}", "🔧 CostPilot Autofix - Snippet Mode".bold().cyan());
     println!();
 
-    // Load and parse plan
-    println!("{}", "Loading Terraform plan...".dimmed());
-    let plan_content = std::fs::read_to_string(&args.plan)?;
-    let plan: serde_json::Value = serde_json::from_str(&plan_content)?;
-
-    // Extract resource changes
-    let changes = crate::cli::utils::extract_resource_changes(&plan)?;
-    println!("   Found {} resource changes", changes.len());
+    // Load plan or template
+    println!("{}", "Loading plan...".dimmed());
+    let input = crate::cli::utils::load_autofix_input(&args.plan, args.cdk_language)?;
+    let changes = input.changes;
+    println!(
+        "   Found {} resource changes ({})",
+        changes.len(),
+        input.target.name()
+    );
     println!();
 
     // Detect cost regressions
@@ -63,12 +68,24 @@ pub fn execute(
 
     // Generate snippets
     println!("{}", "Generating fix snippets...".dimmed());
-    let autofix_result = edition.require_pro("Autofix")?.autofix(
-        &detections_with_estimates,
-        &changes,
-        &[], // estimates not used for snippet mode
-        crate::engines::autofix::AutofixMode::Snippet,
-    )?;
+    let autofix_result = if input.target == AutofixTarget::Terraform {
+        edition.require_pro("Autofix")?.autofix(
+            &detections_with_estimates,
+            &changes,
+            &[], // estimates not used for snippet mode
+            AutofixMode::Snippet,
+        )?
+    } else {
+        AutofixEngine::generate_fixes_for_target(
+            &detections_with_estimates,
+            &changes,
+            &[],
+            AutofixMode::Snippet,
+            input.target,
+            &input.construct_paths,
+            edition,
+        )?
+    };
 
     if autofix_result.fixes.is_empty() {
         println!("   {} No fix snippets available", "ℹ".bright_blue());
@@ -105,6 +122,12 @@ pub fn execute(
             println!();
         }
 
+        if !matches!(fix.format, SnippetFormat::Terraform) {
+            println!("{}", "Fix:".cyan());
+            println!("{}", fix.snippet);
+            println!();
+        }
+
         println!("{}", "Before:".yellow());
         println!("{}", fix.before_after.before);
         println!();
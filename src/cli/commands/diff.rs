@@ -1,10 +1,11 @@
 // costpilot diff command implementation
 
 use crate::engines::detection::DetectionEngine;
+use crate::engines::diff::{PlanDiff, ResourceDiffKind};
 use colored::Colorize;
 use std::path::PathBuf;
 
-/// Execute the diff command to compare two Terraform plans
+/// Execute the diff command to compare two Terraform plans resource by resource
 pub fn execute(
     before: PathBuf,
     after: PathBuf,
@@ -53,7 +54,6 @@ pub fn execute(
         .map_err(|e| format!("Invalid UTF-8 from ProEngine: {}", e))?;
     let before_estimates: Vec<crate::engines::shared::models::CostEstimate> =
         pro_serde::deserialize(before_output_str)?;
-    let before_monthly: f64 = before_estimates.iter().map(|e| e.monthly_cost).sum();
 
     let after_input = pro_serde::serialize(&after_changes)?;
     let after_output = pro.predict(after_input.as_bytes())?;
@@ -61,27 +61,31 @@ pub fn execute(
         .map_err(|e| format!("Invalid UTF-8 from ProEngine: {}", e))?;
     let after_estimates: Vec<crate::engines::shared::models::CostEstimate> =
         pro_serde::deserialize(after_output_str)?;
-    let after_monthly: f64 = after_estimates.iter().map(|e| e.monthly_cost).sum();
 
-    // Calculate delta
-    let delta = after_monthly - before_monthly;
-
-    let percentage = if before_monthly > 0.0 {
-        (delta / before_monthly) * 100.0
-    } else {
-        0.0
-    };
+    let diff = PlanDiff::compute(
+        &before_changes,
+        &before_estimates,
+        &after_changes,
+        &after_estimates,
+    );
 
     match format {
-        "json" => print_diff_json(before_monthly, after_monthly, delta, percentage),
-        "markdown" => print_diff_markdown(before_monthly, after_monthly, delta, percentage),
-        _ => print_diff_text(before_monthly, after_monthly, delta, percentage, verbose),
+        "json" => print_diff_json(&diff),
+        "markdown" => print_diff_markdown(&diff),
+        _ => print_diff_text(&diff, verbose),
     }
 
     Ok(())
 }
 
-fn print_diff_text(before: f64, after: f64, delta: f64, percentage: f64, verbose: bool) {
+fn print_diff_text(diff: &PlanDiff, verbose: bool) {
+    let (before, after, delta, percentage) = (
+        diff.before_monthly,
+        diff.after_monthly,
+        diff.monthly_delta(),
+        diff.percentage(),
+    );
+
     println!("{}", "Cost Comparison".bold());
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!();
@@ -135,6 +139,47 @@ fn print_diff_text(before: f64, after: f64, delta: f64, percentage: f64, verbose
 
     println!("  {}  {} {}", "Severity:".bold(), severity.1, severity.0);
 
+    println!();
+    println!(
+        "{} ({} added, {} removed, {} changed, {} unchanged)",
+        "Resources".bold(),
+        diff.count(ResourceDiffKind::Added),
+        diff.count(ResourceDiffKind::Removed),
+        diff.count(ResourceDiffKind::Changed),
+        diff.unchanged
+    );
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    if diff.resources.is_empty() {
+        println!("  No cost-relevant resource changes");
+    }
+    for resource in &diff.resources {
+        let marker = match resource.kind {
+            ResourceDiffKind::Added => "+".bright_red(),
+            ResourceDiffKind::Removed => "-".bright_green(),
+            _ => "~".bright_yellow(),
+        };
+        println!(
+            "  {} {}  {} → {}  {}",
+            marker,
+            resource.address.bold(),
+            cost_or_dash(
+                resource.kind != ResourceDiffKind::Added,
+                resource.before_monthly
+            ),
+            cost_or_dash(
+                resource.kind != ResourceDiffKind::Removed,
+                resource.after_monthly
+            ),
+            signed(resource.monthly_delta)
+        );
+        for change in &resource.attribute_changes {
+            println!(
+                "      {}: {} → {}",
+                change.path, change.before, change.after
+            );
+        }
+    }
+
     if verbose {
         println!();
         println!("{}", "💡 Tip".bright_cyan());
@@ -149,10 +194,16 @@ fn print_diff_text(before: f64, after: f64, delta: f64, percentage: f64, verbose
     }
 }
 
-fn print_diff_json(before: f64, after: f64, delta: f64, percentage: f64) {
+fn print_diff_json(diff: &PlanDiff) {
     use serde_json::json;
 
-    let diff = json!({
+    let (before, after, delta, percentage) = (
+        diff.before_monthly,
+        diff.after_monthly,
+        diff.monthly_delta(),
+        diff.percentage(),
+    );
+    let diff_json = json!({
         "before": {
             "monthly_cost": before,
         },
@@ -172,12 +223,26 @@ fn print_diff_json(before: f64, after: f64, delta: f64, percentage: f64) {
         } else {
             "INFO"
         },
+        "summary": {
+            "added": diff.count(ResourceDiffKind::Added),
+            "removed": diff.count(ResourceDiffKind::Removed),
+            "changed": diff.count(ResourceDiffKind::Changed),
+            "unchanged": diff.unchanged,
+        },
+        "resources": diff.resources,
     });
 
-    println!("{}", serde_json::to_string_pretty(&diff).unwrap());
+    println!("{}", serde_json::to_string_pretty(&diff_json).unwrap());
 }
 
-fn print_diff_markdown(before: f64, after: f64, delta: f64, percentage: f64) {
+fn print_diff_markdown(diff: &PlanDiff) {
+    let (before, after, delta, percentage) = (
+        diff.before_monthly,
+        diff.after_monthly,
+        diff.monthly_delta(),
+        diff.percentage(),
+    );
+
     println!("# Cost Difference Report");
     println!();
     println!("## Summary");
@@ -213,15 +278,44 @@ fn print_diff_markdown(before: f64, after: f64, delta: f64, percentage: f64) {
     println!("| **Severity** | {} |", severity);
     println!();
 
-    println!("## Details");
+    println!("## Resources");
     println!();
-    println!("### Before Plan");
-    println!("- Monthly Cost: ${:.2}", before);
-    println!();
-
-    println!("### After Plan");
-    println!("- Monthly Cost: ${:.2}", after);
+    println!(
+        "{} added, {} removed, {} changed, {} unchanged",
+        diff.count(ResourceDiffKind::Added),
+        diff.count(ResourceDiffKind::Removed),
+        diff.count(ResourceDiffKind::Changed),
+        diff.unchanged
+    );
     println!();
+    if !diff.resources.is_empty() {
+        println!("| Resource | Change | Before | After | Delta | Cost drivers |");
+        println!("|----------|--------|--------|-------|-------|--------------|");
+        for resource in &diff.resources {
+            let drivers = resource
+                .attribute_changes
+                .iter()
+                .map(|c| format!("`{}`: {} → {}", c.path, c.before, c.after))
+                .collect::<Vec<_>>()
+                .join("<br>");
+            println!(
+                "| `{}` | {} | {} | {} | {} | {} |",
+                resource.address,
+                resource.kind.label(),
+                cost_or_dash(
+                    resource.kind != ResourceDiffKind::Added,
+                    resource.before_monthly
+                ),
+                cost_or_dash(
+                    resource.kind != ResourceDiffKind::Removed,
+                    resource.after_monthly
+                ),
+                signed(resource.monthly_delta),
+                drivers.replace('|', "\\|")
+            );
+        }
+        println!();
+    }
 
     println!("---");
     println!();
@@ -230,6 +324,23 @@ fn print_diff_markdown(before: f64, after: f64, delta: f64, percentage: f64) {
     println!("- Run `costpilot autofix patch` for cost optimization suggestions");
 }
 
+/// Monthly cost, or a dash on the side of the diff the resource is absent from
+fn cost_or_dash(present: bool, cost: f64) -> String {
+    if present {
+        format!("${:.2}", cost)
+    } else {
+        "—".to_string()
+    }
+}
+
+fn signed(value: f64) -> String {
+    if value < 0.0 {
+        format!("-${:.2}", value.abs())
+    } else {
+        format!("+${:.2}", value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +349,16 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    /// Plan diff with only the monthly totals set
+    fn totals(before: f64, after: f64) -> PlanDiff {
+        PlanDiff {
+            before_monthly: before,
+            after_monthly: after,
+            resources: Vec::new(),
+            unchanged: 0,
+        }
+    }
+
     fn create_mock_plan(temp_dir: &TempDir, filename: &str, content: &str) -> std::path::PathBuf {
         let path = temp_dir.path().join(filename);
         fs::write(&path, content).unwrap();
@@ -291,39 +412,64 @@ mod tests {
     #[test]
     fn test_print_diff_text_cost_increase() {
         // Test that the function doesn't panic
-        print_diff_text(100.0, 120.0, 20.0, 20.0, false);
-        print_diff_text(100.0, 120.0, 20.0, 20.0, true);
+        print_diff_text(&totals(100.0, 120.0), false);
+        print_diff_text(&totals(100.0, 120.0), true);
     }
 
     #[test]
     fn test_print_diff_text_cost_decrease() {
-        print_diff_text(120.0, 100.0, -20.0, -16.67, false);
-        print_diff_text(120.0, 100.0, -20.0, -16.67, true);
+        print_diff_text(&totals(120.0, 100.0), false);
+        print_diff_text(&totals(120.0, 100.0), true);
     }
 
     #[test]
     fn test_print_diff_text_no_change() {
-        print_diff_text(100.0, 100.0, 0.0, 0.0, false);
-        print_diff_text(100.0, 100.0, 0.0, 0.0, true);
+        print_diff_text(&totals(100.0, 100.0), false);
+        print_diff_text(&totals(100.0, 100.0), true);
     }
 
     #[test]
     fn test_print_diff_text_zero_before() {
-        print_diff_text(0.0, 50.0, 50.0, 0.0, false);
+        print_diff_text(&totals(0.0, 50.0), false);
     }
 
     #[test]
     fn test_print_diff_json() {
-        print_diff_json(100.0, 120.0, 20.0, 20.0);
-        print_diff_json(120.0, 100.0, -20.0, -16.67);
-        print_diff_json(100.0, 100.0, 0.0, 0.0);
+        print_diff_json(&totals(100.0, 120.0));
+        print_diff_json(&totals(120.0, 100.0));
+        print_diff_json(&totals(100.0, 100.0));
     }
 
     #[test]
     fn test_print_diff_markdown() {
-        print_diff_markdown(100.0, 120.0, 20.0, 20.0);
-        print_diff_markdown(120.0, 100.0, -20.0, -16.67);
-        print_diff_markdown(100.0, 100.0, 0.0, 0.0);
+        print_diff_markdown(&totals(100.0, 120.0));
+        print_diff_markdown(&totals(120.0, 100.0));
+        print_diff_markdown(&totals(100.0, 100.0));
+    }
+
+    #[test]
+    fn test_print_diff_with_resources() {
+        use crate::engines::diff::{AttributeChange, ResourceDiff};
+
+        let mut diff = totals(100.0, 190.0);
+        diff.resources = vec![ResourceDiff {
+            address: "aws_instance.web".to_string(),
+            resource_type: "aws_instance".to_string(),
+            kind: ResourceDiffKind::Changed,
+            before_monthly: 30.0,
+            after_monthly: 120.0,
+            monthly_delta: 90.0,
+            attribute_changes: vec![AttributeChange {
+                path: "instance_type".to_string(),
+                before: serde_json::json!("t3.medium"),
+                after: serde_json::json!("t3.xlarge"),
+            }],
+        }];
+        print_diff_text(&diff, false);
+        print_diff_json(&diff);
+        print_diff_markdown(&diff);
+        assert_eq!(cost_or_dash(false, 30.0), "—");
+        assert_eq!(signed(-5.0), "-$5.00");
     }
 
     #[test]
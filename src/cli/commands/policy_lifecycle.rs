@@ -1,15 +1,92 @@
 // Policy lifecycle CLI commands
+//
+// Lifecycle state, approvals and history are persisted in a LifecycleStore
+// (.costpilot/policy_lifecycle by default), so each command picks up where
+// the previous one left off: draft → review → approved → active →
+// deprecated → archived.
 
 use colored::*;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::engines::baselines::approval::decode_signing_key;
+use crate::engines::policy::{
+    ApprovalStatus, ApproverKeyring, LifecycleStore, PolicyApproval, PolicyContent, PolicyRecord,
+    PolicyState,
+};
+
+/// Load policy content from a YAML or JSON policy file
+///
+/// The policy id defaults to the file stem; `id`, `name` and `description`
+/// are taken from the document when present.
+fn load_policy_content(policy_file: &Path) -> Result<PolicyContent, Box<dyn std::error::Error>> {
+    let raw = std::fs::read_to_string(policy_file)
+        .map_err(|e| format!("Failed to read {}: {}", policy_file.display(), e))?;
+    let rules: serde_json::Value = serde_yaml::from_str(&raw)
+        .map_err(|e| format!("Failed to parse {}: {}", policy_file.display(), e))?;
+
+    let field = |key: &str| rules.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let id = match field("id") {
+        Some(id) => id,
+        None => policy_file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or("Invalid policy filename")?
+            .to_string(),
+    };
+
+    Ok(PolicyContent {
+        name: field("name").unwrap_or_else(|| id.clone()),
+        description: field("description").unwrap_or_default(),
+        id,
+        rules,
+        config: HashMap::new(),
+    })
+}
 
-use crate::engines::policy::lifecycle::PolicyLifecycle as PolicyLifecycleManager;
-use crate::engines::policy::{ApprovalWorkflowManager, PolicyContent, PolicyHistory, PolicyState};
+/// The default lifecycle store, checked against the approver keyring in
+/// costpilot.yaml (`policy_approvals`, with `profile` applied)
+pub fn open_store(profile: Option<&str>) -> Result<LifecycleStore, Box<dyn std::error::Error>> {
+    let config = Path::new("costpilot.yaml");
+    let keyring = if config.exists() {
+        ApproverKeyring::load(config, profile)?
+    } else {
+        ApproverKeyring::default()
+    };
+    Ok(LifecycleStore::default().with_keyring(keyring))
+}
+
+fn colored_state(state: PolicyState) -> ColoredString {
+    let name = format!("{:?}", state);
+    match state {
+        PolicyState::Draft => name.bright_black(),
+        PolicyState::Review => name.bright_yellow(),
+        PolicyState::Approved => name.bright_cyan(),
+        PolicyState::Active => name.bright_green(),
+        PolicyState::Deprecated => name.yellow(),
+        PolicyState::Archived => name.bright_black(),
+    }
+}
+
+fn print_transition_json(record: &PolicyRecord) -> Result<(), Box<dyn std::error::Error>> {
+    let output = serde_json::json!({
+        "policy_id": record.policy_id(),
+        "state": record.state,
+        "version": record.history.current_version,
+        "transition": record.history.transitions.last(),
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
 
 /// Execute policy submit command
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_submit(
+    store: &LifecycleStore,
     policy_file: PathBuf,
     approvers: Vec<String>,
+    min_approvals: Option<usize>,
+    author: String,
     format: &str,
     verbose: bool,
     _edition: &crate::edition::EditionContext,
@@ -24,32 +101,23 @@ pub fn cmd_submit(
         println!("  Approvers: {}", approvers.join(", "));
     }
 
-    // Load policy (simplified - would integrate with actual policy loader)
-    let policy_id = policy_file
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .ok_or("Invalid policy filename")?
-        .to_string();
-
-    // Create workflow manager
-    let mut manager = ApprovalWorkflowManager::new();
-
-    // Setup roles (in production, load from config)
-    manager.assign_role("policy-approver".to_string(), approvers.clone());
-
-    // Register policy
-    manager.register_policy(policy_id.clone(), None)?;
+    if approvers.is_empty() {
+        return Err("At least one approver is required (--approvers)".into());
+    }
 
-    // Submit for approval
-    let assigned_approvers =
-        manager.submit_for_approval(&policy_id, "system@costpilot".to_string())?;
+    let content = load_policy_content(&policy_file)?;
+    let min_approvals = min_approvals.unwrap_or(approvers.len());
+    let record = store.submit(content, author, approvers, min_approvals)?;
+    let policy_id = record.policy_id().to_string();
 
     match format {
         "json" => {
             let output = serde_json::json!({
                 "policy_id": policy_id,
                 "status": "submitted",
-                "approvers": assigned_approvers,
+                "version": record.history.current_version,
+                "approvers": record.approval_config.allowed_approvers,
+                "min_approvals": record.approval_config.min_approvals,
             });
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
@@ -61,16 +129,23 @@ pub fn cmd_submit(
             );
             println!();
             println!("Policy ID: {}", policy_id.bright_white());
+            println!("Version: {}", record.history.current_version);
             println!("Status: {}", "Review".bright_yellow());
             println!();
-            println!("Approval requests sent to:");
-            for approver in &assigned_approvers {
+            println!(
+                "Approval required from {} of:",
+                record.approval_config.min_approvals
+            );
+            for approver in &record.approval_config.allowed_approvers {
                 println!("  • {}", approver);
             }
             println!();
             println!("Next steps:");
-            println!("  1. Wait for approvers to review");
-            println!("  2. Check status: costpilot policy status {}", policy_id);
+            println!(
+                "  1. Approvers sign: costpilot policy sign {} --key <KEY> --approver <NAME>",
+                policy_id
+            );
+            println!("  2. Record approvals: costpilot policy approve <APPROVAL>");
             println!(
                 "  3. Activate when approved: costpilot policy activate {}",
                 policy_id
@@ -81,68 +156,117 @@ pub fn cmd_submit(
     Ok(())
 }
 
-/// Execute policy approve command
-pub fn cmd_approve(
+/// Execute policy sign command: write a signed approval of the policy
+/// version under review
+#[allow(clippy::too_many_arguments)]
+pub fn cmd_sign(
+    store: &LifecycleStore,
     policy_id: String,
+    key_path: PathBuf,
     approver: String,
     comment: Option<String>,
+    output: Option<PathBuf>,
+    format: &str,
+    _verbose: bool,
+    _edition: &crate::edition::EditionContext,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let record = store.load(&policy_id)?;
+    if record.state != PolicyState::Review {
+        return Err(format!(
+            "Policy '{}' is {:?}; only policies in review can be approved",
+            policy_id, record.state
+        )
+        .into());
+    }
+
+    let key = decode_signing_key(
+        &std::fs::read_to_string(&key_path)
+            .map_err(|e| format!("Failed to read {}: {}", key_path.display(), e))?,
+    )?;
+    let approval = PolicyApproval::sign(&record, approver, comment, &key);
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from(format!("{}.{}.approval.json", policy_id, approval.approver))
+    });
+    approval.save(&output)?;
+
+    match format {
+        "json" => {
+            let result = serde_json::json!({
+                "policy_id": policy_id,
+                "version": approval.version,
+                "approver": approval.approver,
+                "approval": output,
+            });
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+        _ => {
+            println!(
+                "✍️  Signed approval of {} v{} as {}",
+                policy_id, approval.version, approval.approver
+            );
+            println!("   Approval: {}", output.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute policy approve command: record a signed approval, checked against
+/// the key submitted for its approver
+pub fn cmd_approve(
+    store: &LifecycleStore,
+    approval_file: PathBuf,
     format: &str,
     _verbose: bool,
     _edition: &crate::edition::EditionContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let approval = PolicyApproval::load(&approval_file)?;
     println!(
         "{}",
-        format!("✅ Approving policy '{}'...", policy_id)
+        format!("✅ Approving policy '{}'...", approval.policy_id)
             .bright_blue()
             .bold()
     );
 
-    // In production, would load from persistence layer
-    let mut manager = ApprovalWorkflowManager::new();
-
-    // For demo, setup mock data
-    manager.assign_role("policy-approver".to_string(), vec![approver.clone()]);
-    manager.register_policy(policy_id.clone(), None)?;
-    manager.submit_for_approval(&policy_id, "author".to_string())?;
-
-    // Record approval
-    let result = manager.approve(
-        &policy_id,
-        approver.clone(),
-        "APPROVAL-001".to_string(),
-        comment.clone(),
-    )?;
+    let record = store.approve(&approval)?;
+    let summary = record.summary();
 
     match format {
         "json" => {
-            println!("{}", serde_json::to_string_pretty(&result)?);
+            println!("{}", serde_json::to_string_pretty(&summary)?);
         }
         _ => {
             println!();
             println!("{}", "✅ Approval recorded".bright_green().bold());
             println!();
-            println!("Policy ID: {}", policy_id.bright_white());
-            println!("Approver: {}", approver);
-            if let Some(c) = comment {
+            println!("Policy ID: {}", approval.policy_id.bright_white());
+            println!("Version: {}", approval.version);
+            println!("Approver: {}", approval.approver);
+            if let Some(c) = &approval.comment {
                 println!("Comment: {}", c);
             }
             println!();
 
-            if result.sufficient_approvals {
+            if record.state == PolicyState::Approved {
                 println!(
                     "{}",
                     "🎉 Policy has sufficient approvals!".bright_green().bold()
                 );
                 println!();
-                println!("Next step: costpilot policy activate {}", policy_id);
+                println!(
+                    "Next step: costpilot policy activate {}",
+                    approval.policy_id
+                );
             } else {
                 println!(
                     "Approvals: {}/{}",
-                    result.remaining_approvals, result.remaining_approvals
+                    summary.approvals_received, summary.approvals_required
                 );
                 println!(
                     "Waiting for {} more approval(s)",
-                    result.remaining_approvals
+                    summary
+                        .approvals_required
+                        .saturating_sub(summary.approvals_received)
                 );
             }
         }
@@ -153,6 +277,7 @@ pub fn cmd_approve(
 
 /// Execute policy reject command
 pub fn cmd_reject(
+    store: &LifecycleStore,
     policy_id: String,
     approver: String,
     reason: String,
@@ -167,17 +292,10 @@ pub fn cmd_reject(
             .bold()
     );
 
-    let mut manager = ApprovalWorkflowManager::new();
-    manager.assign_role("policy-approver".to_string(), vec![approver.clone()]);
-    manager.register_policy(policy_id.clone(), None)?;
-    manager.submit_for_approval(&policy_id, "author".to_string())?;
-
-    let result = manager.reject(&policy_id, approver.clone(), reason.clone())?;
+    let record = store.reject(&policy_id, approver.clone(), reason.clone())?;
 
     match format {
-        "json" => {
-            println!("{}", serde_json::to_string_pretty(&result)?);
-        }
+        "json" => print_transition_json(&record)?,
         _ => {
             println!();
             println!("{}", "❌ Policy rejected".bright_red().bold());
@@ -196,6 +314,7 @@ pub fn cmd_reject(
 
 /// Execute policy activate command
 pub fn cmd_activate(
+    store: &LifecycleStore,
     policy_id: String,
     actor: String,
     format: &str,
@@ -209,24 +328,10 @@ pub fn cmd_activate(
             .bold()
     );
 
-    let mut manager = ApprovalWorkflowManager::new();
-    manager.register_policy(policy_id.clone(), None)?;
-
-    // Simulate approved state (in production, load from storage)
-    let lifecycle = manager.get_lifecycle_mut(&policy_id).unwrap();
-    lifecycle.current_state = PolicyState::Approved;
-
-    manager.activate_policy(&policy_id, actor.clone())?;
+    let record = store.activate(&policy_id, actor.clone())?;
 
     match format {
-        "json" => {
-            let output = serde_json::json!({
-                "policy_id": policy_id,
-                "status": "active",
-                "activated_by": actor,
-            });
-            println!("{}", serde_json::to_string_pretty(&output)?);
-        }
+        "json" => print_transition_json(&record)?,
         _ => {
             println!();
             println!(
@@ -247,6 +352,7 @@ pub fn cmd_activate(
 
 /// Execute policy deprecate command
 pub fn cmd_deprecate(
+    store: &LifecycleStore,
     policy_id: String,
     actor: String,
     reason: String,
@@ -261,24 +367,10 @@ pub fn cmd_deprecate(
             .bold()
     );
 
-    let mut manager = ApprovalWorkflowManager::new();
-    manager.register_policy(policy_id.clone(), None)?;
-
-    let lifecycle = manager.get_lifecycle_mut(&policy_id).unwrap();
-    lifecycle.current_state = PolicyState::Active;
-
-    manager.deprecate_policy(&policy_id, actor.clone(), reason.clone())?;
+    let record = store.deprecate(&policy_id, actor, reason.clone())?;
 
     match format {
-        "json" => {
-            let output = serde_json::json!({
-                "policy_id": policy_id,
-                "status": "deprecated",
-                "deprecated_by": actor,
-                "reason": reason,
-            });
-            println!("{}", serde_json::to_string_pretty(&output)?);
-        }
+        "json" => print_transition_json(&record)?,
         _ => {
             println!();
             println!("{}", "⚠️  Policy deprecated".bright_yellow().bold());
@@ -295,13 +387,71 @@ pub fn cmd_deprecate(
     Ok(())
 }
 
+/// Execute policy archive command
+pub fn cmd_archive(
+    store: &LifecycleStore,
+    policy_id: String,
+    actor: String,
+    reason: String,
+    format: &str,
+    _verbose: bool,
+    _edition: &crate::edition::EditionContext,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!(
+        "{}",
+        format!("🗄️  Archiving policy '{}'...", policy_id)
+            .bright_blue()
+            .bold()
+    );
+
+    let record = store.archive(&policy_id, actor, reason.clone())?;
+
+    match format {
+        "json" => print_transition_json(&record)?,
+        _ => {
+            println!();
+            println!("{}", "🗄️  Policy archived".bright_white().bold());
+            println!();
+            println!("Policy ID: {}", policy_id.bright_white());
+            println!("Status: {}", "Archived".bright_black());
+            println!("Reason: {}", reason);
+            println!();
+            println!("The policy is no longer enforced and cannot change state.");
+        }
+    }
+
+    Ok(())
+}
+
 /// Execute policy status command
+///
+/// With `graph`, prints the lifecycle state machine of the policy as a
+/// Mermaid state diagram instead of the status summary.
 pub fn cmd_status(
+    store: &LifecycleStore,
     policy_id: String,
+    graph: bool,
     format: &str,
     verbose: bool,
     _edition: &crate::edition::EditionContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let record = store.load(&policy_id)?;
+
+    if graph {
+        match format {
+            "json" => {
+                let output = serde_json::json!({
+                    "policy_id": policy_id,
+                    "state": record.state,
+                    "graph": record.state_graph(),
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            }
+            _ => println!("{}", record.state_graph()),
+        }
+        return Ok(());
+    }
+
     println!(
         "{}",
         format!("📊 Policy status for '{}'...", policy_id)
@@ -309,9 +459,7 @@ pub fn cmd_status(
             .bold()
     );
 
-    // Mock lifecycle for demo
-    let lifecycle = PolicyLifecycleManager::new(policy_id.clone());
-    let summary = lifecycle.summary();
+    let summary = record.summary();
 
     match format {
         "json" => {
@@ -323,16 +471,8 @@ pub fn cmd_status(
             println!("{}", "━".repeat(60).bright_black());
             println!();
             println!("Policy ID: {}", summary.policy_id.bright_white());
-
-            let state_colored = match summary.current_state {
-                PolicyState::Draft => format!("{:?}", summary.current_state).bright_black(),
-                PolicyState::Review => format!("{:?}", summary.current_state).bright_yellow(),
-                PolicyState::Approved => format!("{:?}", summary.current_state).bright_cyan(),
-                PolicyState::Active => format!("{:?}", summary.current_state).bright_green(),
-                PolicyState::Deprecated => format!("{:?}", summary.current_state).yellow(),
-                PolicyState::Archived => format!("{:?}", summary.current_state).bright_black(),
-            };
-            println!("Status: {}", state_colored);
+            println!("Version: {}", record.history.current_version);
+            println!("Status: {}", colored_state(summary.current_state));
             println!("Description: {}", summary.state_description);
             println!();
 
@@ -361,6 +501,15 @@ pub fn cmd_status(
                     "  Received: {}/{}",
                     summary.approvals_received, summary.approvals_required
                 );
+                for request in &record.approvals {
+                    let status = match request.status {
+                        ApprovalStatus::Approved => "approved".bright_green(),
+                        ApprovalStatus::Rejected => "rejected".bright_red(),
+                        ApprovalStatus::Pending => "pending".bright_yellow(),
+                        _ => format!("{:?}", request.status).to_lowercase().normal(),
+                    };
+                    println!("  • {} ({})", request.approver, status);
+                }
                 if summary.has_rejections {
                     println!("  Rejections: {}", "⚠️  Yes".bright_yellow());
                 }
@@ -370,7 +519,18 @@ pub fn cmd_status(
                 println!();
             }
 
+            let next: Vec<String> = summary
+                .current_state
+                .valid_transitions()
+                .iter()
+                .map(|state| format!("{:?}", state))
+                .collect();
+            if !next.is_empty() {
+                println!("Next states: {}", next.join(", "));
+            }
+
             if verbose {
+                println!();
                 println!("History:");
                 println!("  Transitions: {}", summary.transition_count);
                 if let Some(last) = summary.last_transition {
@@ -385,6 +545,7 @@ pub fn cmd_status(
 
 /// Execute policy history command
 pub fn cmd_history(
+    store: &LifecycleStore,
     policy_id: String,
     format: &str,
     _verbose: bool,
@@ -397,19 +558,7 @@ pub fn cmd_history(
             .bold()
     );
 
-    // Mock history for demo
-    use serde_json::json;
-    use std::collections::HashMap;
-
-    let content = PolicyContent {
-        id: policy_id.clone(),
-        name: "Test Policy".to_string(),
-        description: "Test policy description".to_string(),
-        rules: json!({"test": "rule"}),
-        config: HashMap::new(),
-    };
-
-    let history = PolicyHistory::new(policy_id.clone(), content, "author@example.com".to_string());
+    let history = store.load(&policy_id)?.history;
 
     match format {
         "json" => {
@@ -452,6 +601,27 @@ pub fn cmd_history(
                 history.current_version.bright_green()
             );
             println!("Total versions: {}", history.version_count());
+
+            if !history.transitions.is_empty() {
+                println!();
+                println!("{}", "State Transitions".bright_white().bold());
+                println!("{}", "━".repeat(60).bright_black());
+                for transition in &history.transitions {
+                    println!(
+                        "  {} {:?} → {} by {}",
+                        transition
+                            .timestamp
+                            .get(..19)
+                            .unwrap_or(&transition.timestamp),
+                        transition.from_state,
+                        colored_state(transition.to_state),
+                        transition.actor
+                    );
+                    if let Some(reason) = &transition.reason {
+                        println!("      {}", reason.bright_black());
+                    }
+                }
+            }
         }
     }
 
@@ -460,6 +630,7 @@ pub fn cmd_history(
 
 /// Execute policy diff command
 pub fn cmd_diff(
+    store: &LifecycleStore,
     policy_id: String,
     from_version: String,
     to_version: String,
@@ -474,39 +645,8 @@ pub fn cmd_diff(
             .bold()
     );
 
-    // Mock for demo - create history with multiple versions
-    use serde_json::json;
-    use std::collections::HashMap;
-
-    let content = PolicyContent {
-        id: policy_id.clone(),
-        name: "Test Policy".to_string(),
-        description: "Test policy description".to_string(),
-        rules: json!({"test": "rule"}),
-        config: HashMap::new(),
-    };
-
-    let mut history =
-        PolicyHistory::new(policy_id, content.clone(), "author@example.com".to_string());
-
-    // Add a second version for diff testing
-    let mut updated_content = content;
-    updated_content.name = "Updated Test Policy".to_string();
-    updated_content.description = "Updated test policy description".to_string();
-    updated_content.rules = json!({"test": "updated_rule"});
-    history
-        .add_version(
-            updated_content,
-            "updater@example.com".to_string(),
-            "Updated policy content".to_string(),
-            false,
-        )
-        .unwrap();
-
-    let diff = history.diff(&from_version, &to_version).map_err(|e| {
-        eprintln!("Diff error: {:?}", e);
-        e
-    })?;
+    let history = store.load(&policy_id)?.history;
+    let diff = history.diff(&from_version, &to_version)?;
 
     match format {
         "json" => {
@@ -555,182 +695,329 @@ pub fn cmd_diff(
 mod tests {
     use super::*;
     use crate::edition::EditionContext;
-    use tempfile::NamedTempFile;
+    use base64::Engine;
+    use ed25519_dalek::SigningKey;
+    use tempfile::TempDir;
+
+    struct Fixture {
+        dir: TempDir,
+        store: LifecycleStore,
+        edition: EditionContext,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            // The keyring trusts alice's and bob's keys, seeded 1 and 2
+            let keyring = ["alice", "bob"].into_iter().zip(1u8..).fold(
+                ApproverKeyring::default(),
+                |keyring, (name, seed)| {
+                    keyring.with_key(name, SigningKey::from_bytes(&[seed; 32]).verifying_key())
+                },
+            );
+            let store = LifecycleStore::new(dir.path().join("lifecycle")).with_keyring(keyring);
+            Self {
+                dir,
+                store,
+                edition: EditionContext::default(),
+            }
+        }
+
+        fn path(&self, name: &str) -> PathBuf {
+            self.dir.path().join(name)
+        }
+
+        fn write_policy(&self, body: &str) -> PathBuf {
+            let path = self.path("nat-limits.yaml");
+            std::fs::write(&path, body).unwrap();
+            path
+        }
+
+        fn write_keys(&self, name: &str, seed: u8) -> (PathBuf, PathBuf) {
+            let key = SigningKey::from_bytes(&[seed; 32]);
+            let engine = base64::engine::general_purpose::STANDARD;
+            let private = self.path(&format!("{}.key", name));
+            let public = self.path(&format!("{}.key.pub", name));
+            std::fs::write(&private, engine.encode(key.to_bytes())).unwrap();
+            std::fs::write(&public, engine.encode(key.verifying_key().to_bytes())).unwrap();
+            (private, public)
+        }
+
+        /// Submit for review by `approvers`
+        fn submit(&self, body: &str, approvers: &[&str]) {
+            cmd_submit(
+                &self.store,
+                self.write_policy(body),
+                approvers.iter().map(|a| a.to_string()).collect(),
+                None,
+                "author@example.com".to_string(),
+                "text",
+                false,
+                &self.edition,
+            )
+            .unwrap();
+        }
+
+        fn sign_and_approve(&self, approver: &str, seed: u8, format: &str) {
+            let (private, _) = self.write_keys(approver, seed);
+            let approval = self.path(&format!("{}.approval.json", approver));
+            cmd_sign(
+                &self.store,
+                "nat-limits".to_string(),
+                private,
+                approver.to_string(),
+                Some("Looks good!".to_string()),
+                Some(approval.clone()),
+                format,
+                false,
+                &self.edition,
+            )
+            .unwrap();
+            cmd_approve(&self.store, approval, format, false, &self.edition).unwrap();
+        }
+
+        fn state(&self) -> PolicyState {
+            self.store.load("nat-limits").unwrap().state
+        }
+    }
+
+    #[test]
+    fn test_load_policy_content() {
+        let fixture = Fixture::new();
+        let path = fixture.write_policy("name: NAT limits\nrules:\n  - max: 2\n");
+        let content = load_policy_content(&path).unwrap();
+        assert_eq!(content.id, "nat-limits");
+        assert_eq!(content.name, "NAT limits");
+        assert_eq!(content.rules["rules"][0]["max"], 2);
+    }
 
     #[test]
     fn test_cmd_submit() {
-        let temp_file = NamedTempFile::new().unwrap();
-        let policy_file = temp_file.path().to_path_buf();
-        let approvers = vec![
-            "approver1@example.com".to_string(),
-            "approver2@example.com".to_string(),
-        ];
-        let edition = EditionContext::default();
-
-        let result = cmd_submit(policy_file, approvers, "text", false, &edition);
-        assert!(result.is_ok());
+        let fixture = Fixture::new();
+        fixture.submit("rules: []\n", &["alice", "bob"]);
+        let record = fixture.store.load("nat-limits").unwrap();
+        assert_eq!(record.state, PolicyState::Review);
+        assert_eq!(record.approval_config.min_approvals, 2);
 
+        // Already in review
         let result = cmd_submit(
-            temp_file.path().to_path_buf(),
-            vec!["approver@example.com".to_string()],
+            &fixture.store,
+            fixture.path("nat-limits.yaml"),
+            vec!["alice".to_string()],
+            None,
+            "author@example.com".to_string(),
             "json",
             false,
-            &edition,
+            &fixture.edition,
         );
-        assert!(result.is_ok());
+        assert!(result.is_err());
     }
 
     #[test]
     fn test_cmd_approve() {
-        let policy_id = "test-policy".to_string();
-        let approver = "approver@example.com".to_string();
-        let comment = Some("Looks good!".to_string());
-        let edition = EditionContext::default();
-
-        let result = cmd_approve(
-            policy_id.clone(),
-            approver.clone(),
-            comment.clone(),
-            "text",
-            false,
-            &edition,
-        );
-        assert!(result.is_ok());
+        let fixture = Fixture::new();
+        fixture.submit("rules: []\n", &["alice", "bob"]);
 
-        let result = cmd_approve(policy_id, approver, comment, "json", false, &edition);
-        assert!(result.is_ok());
+        fixture.sign_and_approve("alice", 1, "text");
+        assert_eq!(fixture.state(), PolicyState::Review);
+        fixture.sign_and_approve("bob", 2, "json");
+        assert_eq!(fixture.state(), PolicyState::Approved);
     }
 
     #[test]
-    fn test_cmd_reject() {
-        let policy_id = "test-policy".to_string();
-        let approver = "approver@example.com".to_string();
-        let reason = "Needs more review".to_string();
-        let edition = EditionContext::default();
+    fn test_cmd_submit_requires_keyring_approvers() {
+        let fixture = Fixture::new();
+        let submit = |approvers: &[&str], author: &str| {
+            cmd_submit(
+                &fixture.store,
+                fixture.write_policy("rules: []\n"),
+                approvers.iter().map(|a| a.to_string()).collect(),
+                None,
+                author.to_string(),
+                "text",
+                false,
+                &fixture.edition,
+            )
+        };
 
-        let result = cmd_reject(
-            policy_id.clone(),
-            approver.clone(),
-            reason.clone(),
+        // carol has no key in the keyring; alice cannot review her own policy
+        assert!(submit(&["alice", "carol"], "author@example.com").is_err());
+        assert!(submit(&["alice", "bob"], "alice").is_err());
+        assert!(!fixture.store.exists("nat-limits"));
+    }
+
+    #[test]
+    fn test_cmd_approve_untrusted_key() {
+        let fixture = Fixture::new();
+        fixture.submit("rules: []\n", &["alice"]);
+        // Signed as alice with a key other than the one submitted for her
+        let (private, _) = fixture.write_keys("mallory", 7);
+        let approval = fixture.path("alice.approval.json");
+        cmd_sign(
+            &fixture.store,
+            "nat-limits".to_string(),
+            private,
+            "alice".to_string(),
+            None,
+            Some(approval.clone()),
             "text",
             false,
-            &edition,
-        );
-        assert!(result.is_ok());
+            &fixture.edition,
+        )
+        .unwrap();
 
-        let result = cmd_reject(policy_id, approver, reason, "json", false, &edition);
-        assert!(result.is_ok());
+        let result = cmd_approve(&fixture.store, approval, "text", false, &fixture.edition);
+        assert!(result.is_err());
+        assert_eq!(fixture.state(), PolicyState::Review);
     }
 
     #[test]
-    fn test_cmd_activate() {
-        let policy_id = "test-policy".to_string();
-        let actor = "admin@example.com".to_string();
-        let edition = EditionContext::default();
+    fn test_cmd_reject() {
+        let fixture = Fixture::new();
+        fixture.submit("rules: []\n", &["alice"]);
 
-        let result = cmd_activate(policy_id.clone(), actor.clone(), "text", false, &edition);
+        let result = cmd_reject(
+            &fixture.store,
+            "nat-limits".to_string(),
+            "alice".to_string(),
+            "Needs more review".to_string(),
+            "text",
+            false,
+            &fixture.edition,
+        );
         assert!(result.is_ok());
+        assert_eq!(fixture.state(), PolicyState::Draft);
 
-        let result = cmd_activate(policy_id, actor, "json", false, &edition);
-        assert!(result.is_ok());
+        // Resubmitting changed content creates a new version
+        fixture.submit("rules:\n  - max: 3\n", &["alice"]);
+        let record = fixture.store.load("nat-limits").unwrap();
+        assert_eq!(record.history.version_count(), 2);
     }
 
     #[test]
-    fn test_cmd_deprecate() {
-        let policy_id = "test-policy".to_string();
+    fn test_cmd_activate_deprecate_archive() {
+        let fixture = Fixture::new();
+        fixture.submit("rules: []\n", &["alice"]);
         let actor = "admin@example.com".to_string();
-        let reason = "Replaced by new policy".to_string();
-        let edition = EditionContext::default();
 
-        let result = cmd_deprecate(
-            policy_id.clone(),
+        // Not approved yet
+        let result = cmd_activate(
+            &fixture.store,
+            "nat-limits".to_string(),
             actor.clone(),
-            reason.clone(),
             "text",
             false,
-            &edition,
+            &fixture.edition,
         );
-        assert!(result.is_ok());
+        assert!(result.is_err());
 
-        let result = cmd_deprecate(policy_id, actor, reason, "json", false, &edition);
-        assert!(result.is_ok());
-    }
-
-    #[test]
-    fn test_cmd_status() {
-        let policy_id = "test-policy".to_string();
-        let edition = EditionContext::default();
-
-        let result = cmd_status(policy_id.clone(), "text", false, &edition);
-        assert!(result.is_ok());
+        fixture.sign_and_approve("alice", 1, "text");
+        cmd_activate(
+            &fixture.store,
+            "nat-limits".to_string(),
+            actor.clone(),
+            "text",
+            false,
+            &fixture.edition,
+        )
+        .unwrap();
+        assert_eq!(fixture.state(), PolicyState::Active);
 
-        let result = cmd_status(policy_id.clone(), "json", false, &edition);
-        assert!(result.is_ok());
+        cmd_deprecate(
+            &fixture.store,
+            "nat-limits".to_string(),
+            actor.clone(),
+            "Replaced by new policy".to_string(),
+            "json",
+            false,
+            &fixture.edition,
+        )
+        .unwrap();
+        assert_eq!(fixture.state(), PolicyState::Deprecated);
 
-        let result = cmd_status(policy_id, "text", true, &edition);
-        assert!(result.is_ok());
+        cmd_archive(
+            &fixture.store,
+            "nat-limits".to_string(),
+            actor,
+            "Removed".to_string(),
+            "text",
+            false,
+            &fixture.edition,
+        )
+        .unwrap();
+        assert_eq!(fixture.state(), PolicyState::Archived);
     }
 
     #[test]
-    fn test_cmd_history() {
-        let policy_id = "test-policy".to_string();
-        let edition = EditionContext::default();
-
-        let result = cmd_history(policy_id.clone(), "text", false, &edition);
-        assert!(result.is_ok());
-
-        let result = cmd_history(policy_id, "json", false, &edition);
-        assert!(result.is_ok());
+    fn test_cmd_status() {
+        let fixture = Fixture::new();
+        let edition = &fixture.edition;
+        assert!(cmd_status(
+            &fixture.store,
+            "nat-limits".to_string(),
+            false,
+            "text",
+            false,
+            edition
+        )
+        .is_err());
+
+        fixture.submit("rules: []\n", &["alice"]);
+        for (graph, format, verbose) in [
+            (false, "text", false),
+            (false, "json", false),
+            (false, "text", true),
+            (true, "text", false),
+            (true, "json", false),
+        ] {
+            let result = cmd_status(
+                &fixture.store,
+                "nat-limits".to_string(),
+                graph,
+                format,
+                verbose,
+                edition,
+            );
+            assert!(result.is_ok());
+        }
     }
 
     #[test]
-    fn test_cmd_diff() {
-        let policy_id = "test-policy".to_string();
-        let from_version = "1.0.0".to_string();
-        let to_version = "1.0.1".to_string();
-        let edition = EditionContext::default();
-
-        // Create a history with multiple versions for testing
-        let mut content = PolicyContent {
-            id: policy_id.clone(),
-            name: "Test Policy".to_string(),
-            description: "Test policy description".to_string(),
-            rules: serde_json::json!({"test": "rule"}),
-            config: std::collections::HashMap::new(),
-        };
-
-        let mut history = PolicyHistory::new(
-            policy_id.clone(),
-            content.clone(),
-            "author@example.com".to_string(),
-        );
-
-        // Modify content and add a new version
-        content.name = "Updated Test Policy".to_string();
-        content.description = "Updated test policy description".to_string();
-        content.rules = serde_json::json!({"test": "updated_rule"});
-        history
-            .add_version(
-                content,
-                "updater@example.com".to_string(),
-                "Updated policy content".to_string(),
-                false,
-            )
-            .unwrap();
-
-        // Test diff with text format
-        let result = cmd_diff(
-            policy_id.clone(),
-            from_version.clone(),
-            to_version.clone(),
+    fn test_cmd_history_and_diff() {
+        let fixture = Fixture::new();
+        fixture.submit("rules: []\n", &["alice"]);
+        cmd_reject(
+            &fixture.store,
+            "nat-limits".to_string(),
+            "alice".to_string(),
+            "Too loose".to_string(),
             "text",
             false,
-            &edition,
-        );
-        assert!(result.is_ok());
+            &fixture.edition,
+        )
+        .unwrap();
+        fixture.submit("rules:\n  - max: 3\n", &["alice"]);
 
-        // Test diff with json format
-        let result = cmd_diff(policy_id, from_version, to_version, "json", false, &edition);
-        assert!(result.is_ok());
+        for format in ["text", "json"] {
+            let result = cmd_history(
+                &fixture.store,
+                "nat-limits".to_string(),
+                format,
+                false,
+                &fixture.edition,
+            );
+            assert!(result.is_ok());
+
+            let result = cmd_diff(
+                &fixture.store,
+                "nat-limits".to_string(),
+                "1.0.0".to_string(),
+                "1.0.1".to_string(),
+                format,
+                false,
+                &fixture.edition,
+            );
+            assert!(result.is_ok());
+        }
     }
 }
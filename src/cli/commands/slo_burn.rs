@@ -3,8 +3,10 @@
 use colored::*;
 use std::path::PathBuf;
 
-use crate::engines::slo::burn_rate::{BurnRateCalculator, BurnReport};
+use crate::cli::exit_code::ExitCode;
+use crate::engines::slo::burn_rate::{BurnRateCalculator, BurnReplay, BurnReport};
 use crate::engines::slo::{BurnRisk, SloManager};
+use crate::engines::trend::snapshot_types::CostSnapshot;
 use crate::engines::trend::SnapshotManager;
 
 /// Execute the SLO burn rate analysis command
@@ -17,6 +19,58 @@ pub fn execute(
     verbose: bool,
     edition: &crate::edition::EditionContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let (slo_manager, snapshots) = load_inputs(slo_path, snapshots_dir, verbose, edition)?;
+    let calculator = calculator(min_snapshots, min_r_squared);
+
+    // Run burn rate analysis
+    let report = calculator.analyze_all(&slo_manager.config().slos, &snapshots);
+
+    // Output results based on format
+    match format {
+        "json" => output_json(&report)?,
+        "markdown" => output_markdown(&report)?,
+        _ => output_text(&report)?,
+    }
+
+    // Exit with error code if action required
+    if report.requires_action() {
+        ExitCode::SloBreach.exit();
+    }
+
+    Ok(())
+}
+
+/// Replay the snapshot history against the current SLO definitions and show
+/// when each burn alert would have fired. Informational: never exits with
+/// the SLO breach code, so thresholds can be tuned before enforcement.
+pub fn replay(
+    slo_path: Option<PathBuf>,
+    snapshots_dir: Option<PathBuf>,
+    format: &str,
+    min_snapshots: Option<usize>,
+    min_r_squared: Option<f64>,
+    verbose: bool,
+    edition: &crate::edition::EditionContext,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (slo_manager, snapshots) = load_inputs(slo_path, snapshots_dir, verbose, edition)?;
+    let replay =
+        calculator(min_snapshots, min_r_squared).replay(&slo_manager.config().slos, &snapshots);
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&replay)?),
+        "markdown" => output_replay_markdown(&replay),
+        _ => output_replay_text(&replay),
+    }
+    Ok(())
+}
+
+/// Load and validate the SLO config and the snapshot history
+fn load_inputs(
+    slo_path: Option<PathBuf>,
+    snapshots_dir: Option<PathBuf>,
+    verbose: bool,
+    edition: &crate::edition::EditionContext,
+) -> Result<(SloManager, Vec<CostSnapshot>), Box<dyn std::error::Error>> {
     // Default paths
     let slo_path = slo_path.unwrap_or_else(|| PathBuf::from(".costpilot/slo.json"));
     let snapshots_dir = snapshots_dir.unwrap_or_else(|| PathBuf::from(".costpilot/snapshots"));
@@ -59,8 +113,12 @@ pub fn execute(
         println!("  Loaded {} snapshots", history.snapshots.len());
     }
 
-    // Create calculator with custom thresholds if provided
-    let calculator = if let (Some(min_snap), Some(min_r2)) = (min_snapshots, min_r_squared) {
+    Ok((slo_manager, history.snapshots))
+}
+
+/// Create calculator with custom thresholds if provided
+fn calculator(min_snapshots: Option<usize>, min_r_squared: Option<f64>) -> BurnRateCalculator {
+    if let (Some(min_snap), Some(min_r2)) = (min_snapshots, min_r_squared) {
         BurnRateCalculator::with_thresholds(min_snap, min_r2)
     } else if let Some(min_snap) = min_snapshots {
         BurnRateCalculator::with_thresholds(min_snap, 0.7)
@@ -68,24 +126,7 @@ pub fn execute(
         BurnRateCalculator::with_thresholds(3, min_r2)
     } else {
         BurnRateCalculator::new()
-    };
-
-    // Run burn rate analysis
-    let report = calculator.analyze_all(&slo_manager.config().slos, &history.snapshots);
-
-    // Output results based on format
-    match format {
-        "json" => output_json(&report)?,
-        "markdown" => output_markdown(&report)?,
-        _ => output_text(&report)?,
     }
-
-    // Exit with error code if action required
-    if report.requires_action() {
-        std::process::exit(1);
-    }
-
-    Ok(())
 }
 
 /// Output report as formatted text
@@ -300,6 +341,139 @@ fn output_markdown(report: &BurnReport) -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
+/// Date part of an RFC 3339 timestamp
+fn day(timestamp: &str) -> &str {
+    timestamp.get(..10).unwrap_or(timestamp)
+}
+
+fn firing_risk(risk: &BurnRisk) -> &'static str {
+    match risk {
+        BurnRisk::Critical => "🔥",
+        BurnRisk::High => "🔶",
+        _ => "⚠️",
+    }
+}
+
+/// Output a burn replay as formatted text
+fn output_replay_text(replay: &BurnReplay) {
+    println!();
+    println!("{}", "📼 SLO Burn Replay".bright_cyan().bold());
+    println!("{}", "━".repeat(60).bright_black());
+    if let (Some(from), Some(to)) = (&replay.from, &replay.to) {
+        println!(
+            "  {} snapshots, {} to {}",
+            replay.total_snapshots,
+            day(from),
+            day(to)
+        );
+    }
+    println!();
+
+    for slo in &replay.slos {
+        println!(
+            "{} (${:.2}/month)",
+            slo.slo_name.bright_white().bold(),
+            slo.slo_limit
+        );
+        if slo.points_evaluated == 0 {
+            println!("  {}", "Not enough history to analyze".yellow());
+            println!();
+            continue;
+        }
+
+        if slo.firings.is_empty() {
+            println!("  {}", "Would never have fired".green());
+        }
+        for firing in &slo.firings {
+            let breach = match firing.days_to_breach {
+                Some(days) => format!("{:.1} days to breach", days),
+                None => "already exceeded".to_string(),
+            };
+            let resolved = match &firing.resolved_at {
+                Some(resolved) => format!("cleared {}", day(resolved)),
+                None => "still firing".to_string(),
+            };
+            println!(
+                "  {} {} {:?} at ${:.2}/day, {} ({})",
+                firing_risk(&firing.risk),
+                day(&firing.fired_at),
+                firing.risk,
+                firing.burn_rate,
+                breach,
+                resolved
+            );
+        }
+
+        println!(
+            "  Alerting:       {} of {} snapshots",
+            slo.points_alerting, slo.points_evaluated
+        );
+        match &slo.first_breach {
+            Some(breach) => println!("  First Breach:   {}", day(breach).bright_red()),
+            None => println!("  First Breach:   {}", "never".green()),
+        }
+        if let Some(days) = slo.lead_time_days {
+            println!("  Lead Time:      {:.1} days", days);
+        }
+        let false_alarms = slo.false_alarms();
+        if false_alarms > 0 {
+            println!(
+                "  False Alarms:   {}",
+                false_alarms.to_string().bright_yellow()
+            );
+        }
+        println!();
+    }
+
+    println!("{}", "━".repeat(60).bright_black());
+    println!(
+        "{}",
+        "Replay only: SLO limits and --min-snapshots/--min-r-squared can be tuned and re-run."
+            .bright_black()
+    );
+    println!();
+}
+
+/// Output a burn replay as Markdown
+fn output_replay_markdown(replay: &BurnReplay) {
+    println!("## 📼 SLO Burn Replay");
+    println!();
+    if let (Some(from), Some(to)) = (&replay.from, &replay.to) {
+        println!(
+            "{} snapshots, {} to {}",
+            replay.total_snapshots,
+            day(from),
+            day(to)
+        );
+        println!();
+    }
+
+    println!("| SLO | Fired | Alerting | First Breach | Lead Time | False Alarms |");
+    println!("|-----|-------|----------|--------------|-----------|--------------|");
+    for slo in &replay.slos {
+        let fired = if slo.firings.is_empty() {
+            "never".to_string()
+        } else {
+            slo.firings
+                .iter()
+                .map(|f| format!("{} {}", firing_risk(&f.risk), day(&f.fired_at)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        println!(
+            "| {} | {} | {}/{} | {} | {} | {} |",
+            slo.slo_name,
+            fired,
+            slo.points_alerting,
+            slo.points_evaluated,
+            slo.first_breach.as_deref().map_or("never", day),
+            slo.lead_time_days
+                .map_or("-".to_string(), |days| format!("{:.1} days", days)),
+            slo.false_alarms()
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,4 +536,34 @@ mod tests {
         let result = output_markdown(&report);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_output_replay() {
+        let replay = BurnReplay {
+            slos: vec![crate::engines::slo::SloReplay {
+                slo_id: "slo-1".to_string(),
+                slo_name: "Production Budget".to_string(),
+                slo_limit: 5000.0,
+                points_evaluated: 3,
+                points_alerting: 1,
+                firings: vec![crate::engines::slo::BurnFiring {
+                    snapshot_id: "snap-22".to_string(),
+                    fired_at: "2024-01-22T00:00:00Z".to_string(),
+                    risk: BurnRisk::High,
+                    burn_rate: 150.0,
+                    days_to_breach: Some(10.0),
+                    resolved_at: None,
+                }],
+                first_breach: Some("2024-01-29T00:00:00Z".to_string()),
+                lead_time_days: Some(7.0),
+            }],
+            total_snapshots: 5,
+            from: Some("2024-01-01T00:00:00Z".to_string()),
+            to: Some("2024-01-29T00:00:00Z".to_string()),
+            generated_at: Utc::now().to_rfc3339(),
+        };
+        output_replay_text(&replay);
+        output_replay_markdown(&replay);
+        assert_eq!(day("2024-01-22T00:00:00Z"), "2024-01-22");
+    }
 }
@@ -1,5 +1,6 @@
 // SLO check command implementation
 
+use crate::cli::exit_code::ExitCode;
 use crate::engines::slo::{SloConfig, SloManager};
 use crate::engines::trend::TrendEngine;
 use colored::Colorize;
@@ -51,7 +52,7 @@ pub fn execute(
     }
 
     // Require Premium for trend tracking
-    crate::edition::require_premium(edition, "Trend tracking")?;
+    edition.require_capability(crate::edition::Capability::Trend, "Trend tracking")?;
 
     let trend_engine = TrendEngine::new(snapshots_dir.to_str().unwrap(), edition)?;
     let history = trend_engine.load_history()?;
@@ -193,7 +194,7 @@ pub fn execute(
         );
         let blocking = slo_manager.get_blocking_violations(&report);
         eprintln!("  {} blocking violation(s) detected", blocking.len());
-        std::process::exit(1);
+        ExitCode::SloBreach.exit();
     }
 
     if report.summary.violation_count > 0 {
@@ -201,7 +202,7 @@ pub fn execute(
             "\n{} SLO violations detected (non-blocking)",
             "⚠️".yellow().bold()
         );
-        std::process::exit(1);
+        ExitCode::Warnings.exit();
     }
 
     println!("\n{} All SLOs passed", "✅".green().bold());
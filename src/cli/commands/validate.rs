@@ -1,6 +1,6 @@
 // Validate command - validate configuration files
 
-use crate::validation::validate_file;
+use crate::validation::{fix_and_validate_file, validate_file, ValidationReport};
 use colored::Colorize;
 use std::path::PathBuf;
 
@@ -8,10 +8,11 @@ use std::path::PathBuf;
 pub fn execute(
     file: PathBuf,
     format: String,
+    fix: bool,
     _edition: &crate::edition::EditionContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Validate the file
-    let report = validate_file(&file)?;
+    let report = check_file(&file, fix)?;
 
     // Output based on format
     match format.as_str() {
@@ -39,13 +40,14 @@ pub fn execute_batch(
     files: Vec<PathBuf>,
     format: String,
     fail_fast: bool,
+    fix: bool,
     _edition: &crate::edition::EditionContext,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut all_valid = true;
     let mut reports = Vec::new();
 
     for file in &files {
-        let report = validate_file(file)?;
+        let report = check_file(file, fix)?;
 
         if !report.is_valid {
             all_valid = false;
@@ -102,6 +104,15 @@ pub fn execute_batch(
     Ok(())
 }
 
+fn check_file(file: &PathBuf, fix: bool) -> Result<ValidationReport, Box<dyn std::error::Error>> {
+    let report = if fix {
+        fix_and_validate_file(file)?
+    } else {
+        validate_file(file)?
+    };
+    Ok(report)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,81 @@
+// Error output shared by every command
+//
+// A failed command prints its stable code, error id, message and hint to
+// stderr the same way everywhere. With `--format json` an `{"error": ...}`
+// document is also written to stdout so CI tooling parses it like any other
+// result. Errors that were not raised as a `CostPilotError` (plain strings
+// from argument handling) carry the unclassified code in JSON and print as
+// a bare message in text.
+
+use crate::engines::shared::error_codes::UNCLASSIFIED_CODE;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use colored::Colorize;
+
+/// The `CostPilotError` behind `error`, or an unclassified wrapper
+pub fn classify(error: &(dyn std::error::Error + 'static)) -> CostPilotError {
+    match error.downcast_ref::<CostPilotError>() {
+        Some(err) => err.clone(),
+        None => CostPilotError::new(
+            "E_UNCLASSIFIED",
+            ErrorCategory::InternalError,
+            error.to_string(),
+        ),
+    }
+}
+
+/// Render an error for `format` (`json` or anything else for text)
+pub fn render(err: &CostPilotError, format: &str) -> String {
+    if matches!(format, "json" | "ndjson") {
+        return serde_json::to_string_pretty(&err.to_json_report())
+            .unwrap_or_else(|_| err.to_machine_format());
+    }
+    if err.code() == UNCLASSIFIED_CODE && err.id == "E_UNCLASSIFIED" {
+        return format!("{} {}", "Error:".bright_red().bold(), err.message);
+    }
+    format!("{} {}", "Error:".bright_red().bold(), err)
+}
+
+/// Print a failed command's error to stderr and, for JSON formats, the
+/// error document to stdout in place of the result
+pub fn print_error(error: &(dyn std::error::Error + 'static), format: &str) {
+    let err = classify(error);
+    eprintln!("{}", render(&err, "text"));
+    if matches!(format, "json" | "ndjson") {
+        println!("{}", render(&err, format));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_json_includes_code_and_hint() {
+        let err = CostPilotError::new(
+            "SCAN_001",
+            ErrorCategory::FileSystemError,
+            "No plan specified",
+        )
+        .with_hint("Pass --plan");
+        let value: serde_json::Value = serde_json::from_str(&render(&err, "json")).unwrap();
+        assert_eq!(value["error"]["code"], "CP1001");
+        assert_eq!(value["error"]["id"], "SCAN_001");
+        assert_eq!(value["error"]["category"], "FileSystemError");
+        assert_eq!(value["error"]["hint"], "Pass --plan");
+    }
+
+    #[test]
+    fn test_classify_wraps_plain_errors() {
+        let plain: Box<dyn std::error::Error> = "--plan is required".into();
+        let err = classify(plain.as_ref());
+        assert_eq!(err.code(), UNCLASSIFIED_CODE);
+        let text = render(&err, "text");
+        assert!(text.ends_with(" --plan is required"));
+        assert!(!text.contains(UNCLASSIFIED_CODE));
+
+        let typed: Box<dyn std::error::Error> =
+            Box::new(CostPilotError::config_error("bad profile"));
+        assert_eq!(classify(typed.as_ref()).code(), "CP2001");
+        assert!(render(&classify(typed.as_ref()), "text").contains("CP2001 [E_CONFIG] bad profile"));
+    }
+}
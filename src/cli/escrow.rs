@@ -1,8 +1,8 @@
 // CLI commands for software escrow management
 
 use crate::engines::escrow::{
-    EscrowPackage, RecoveryOrchestrator, RecoveryPlaybook, ReleaseAutomation, ReleaseConfig,
-    VendorInfo,
+    BuildVerifier, EscrowPackage, RecoveryOrchestrator, RecoveryPlaybook, ReleaseAutomation,
+    ReleaseConfig, VendorInfo,
 };
 use std::path::PathBuf;
 
@@ -14,11 +14,27 @@ pub enum EscrowCommand {
         version: String,
         output_dir: Option<PathBuf>,
         include_artifacts: bool,
+        /// Earlier package to deposit a delta against
+        base: Option<PathBuf>,
     },
 
     /// Verify escrow package integrity
     Verify { package_dir: PathBuf },
 
+    /// Rebuild the package in a sandbox and compare artifacts with the manifest
+    VerifyBuild {
+        package_dir: PathBuf,
+        /// Deposited sources (default: repository root)
+        source_dir: Option<PathBuf>,
+        /// Base64 Ed25519 key file used to sign the report
+        signing_key: Option<PathBuf>,
+        /// Report path (default: <package_dir>/build-verification.json)
+        output: Option<PathBuf>,
+        skip_steps: Vec<usize>,
+        run_tests: bool,
+        keep_sandbox: bool,
+    },
+
     /// Generate recovery playbook
     Playbook {
         package_dir: PathBuf,
@@ -29,6 +45,10 @@ pub enum EscrowCommand {
     Recover {
         package_dir: PathBuf,
         working_dir: PathBuf,
+        /// Print the resolved plan without running it
+        dry_run: bool,
+        /// Ignore progress saved by an interrupted run
+        restart: bool,
     },
 
     /// Configure escrow settings
@@ -49,8 +69,29 @@ pub fn execute_escrow_command(cmd: EscrowCommand) -> Result<String, String> {
             version,
             output_dir,
             include_artifacts,
-        } => execute_create(&version, output_dir, include_artifacts),
+            base,
+        } => execute_create(&version, output_dir, include_artifacts, base),
         EscrowCommand::Verify { package_dir } => execute_verify(&package_dir),
+        EscrowCommand::VerifyBuild {
+            package_dir,
+            source_dir,
+            signing_key,
+            output,
+            skip_steps,
+            run_tests,
+            keep_sandbox,
+        } => {
+            let source_dir = match source_dir {
+                Some(dir) => dir,
+                None => get_repository_root()?,
+            };
+            let verifier = skip_steps
+                .into_iter()
+                .fold(BuildVerifier::new(source_dir), |v, step| v.skip_step(step))
+                .run_tests(run_tests)
+                .keep_sandbox(keep_sandbox);
+            execute_verify_build(&package_dir, &verifier, signing_key, output)
+        }
         EscrowCommand::Playbook {
             package_dir,
             output,
@@ -58,7 +99,9 @@ pub fn execute_escrow_command(cmd: EscrowCommand) -> Result<String, String> {
         EscrowCommand::Recover {
             package_dir,
             working_dir,
-        } => execute_recover(&package_dir, &working_dir),
+            dry_run,
+            restart,
+        } => execute_recover(&package_dir, &working_dir, dry_run, restart),
         EscrowCommand::Configure {
             vendor_name,
             contact_email,
@@ -72,7 +115,10 @@ fn execute_create(
     version: &str,
     output_dir: Option<PathBuf>,
     include_artifacts: bool,
+    base: Option<PathBuf>,
 ) -> Result<String, String> {
+    let base = base.map(|dir| EscrowPackage::load(&dir)).transpose()?;
+
     // Load configuration
     let config = load_escrow_config()?;
 
@@ -93,14 +139,29 @@ fn execute_create(
     let automation = ReleaseAutomation::new(release_config, repo_root);
 
     // Create package
-    let package = automation.create_package(version)?;
+    let package = automation.create_package_from(version, base.as_ref())?;
 
     // Deposit package
     let receipt = automation.deposit_package(&package)?;
 
+    let delta = package
+        .delta
+        .as_ref()
+        .map(|d| {
+            format!(
+                "\nDelta of v{}: {} changed, {} removed, {} unchanged files kept in the base\n",
+                d.base_version,
+                d.changed_files.len(),
+                d.removed_files.len(),
+                package.source_files.len() - d.changed_files.len()
+            )
+        })
+        .unwrap_or_default();
+
     Ok(format!(
-        "✅ Escrow package created successfully\n\n{}",
-        receipt.format_text()
+        "✅ Escrow package created successfully\n\n{}{}",
+        receipt.format_text(),
+        delta
     ))
 }
 
@@ -114,6 +175,45 @@ fn execute_verify(package_dir: &Path) -> Result<String, String> {
     Ok(report.format_text())
 }
 
+fn execute_verify_build(
+    package_dir: &Path,
+    verifier: &BuildVerifier,
+    signing_key: Option<PathBuf>,
+    output: Option<PathBuf>,
+) -> Result<String, String> {
+    use base64::Engine;
+
+    let package = EscrowPackage::load(package_dir)?;
+    let mut report = verifier.run(&package)?;
+
+    if let Some(key_path) = signing_key {
+        let encoded = std::fs::read_to_string(&key_path)
+            .map_err(|e| format!("Failed to read key {}: {}", key_path.display(), e))?;
+        let bytes: [u8; 32] = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| format!("Invalid signing key {}", key_path.display()))?;
+        report.sign(&ed25519_dalek::SigningKey::from_bytes(&bytes));
+    }
+
+    let output = output.unwrap_or_else(|| package_dir.join("build-verification.json"));
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize report: {}", e))?;
+    std::fs::write(&output, json).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    let text = format!(
+        "{}\n📄 Report written to: {}",
+        report.format_text(),
+        output.display()
+    );
+    if report.report.verified {
+        Ok(text)
+    } else {
+        Err(format!("{}\n\nBuild could not be reproduced", text))
+    }
+}
+
 fn execute_playbook(package_dir: &Path, output: Option<PathBuf>) -> Result<String, String> {
     // Load package
     let package = EscrowPackage::load(package_dir)?;
@@ -134,16 +234,23 @@ fn execute_playbook(package_dir: &Path, output: Option<PathBuf>) -> Result<Strin
     }
 }
 
-fn execute_recover(package_dir: &Path, working_dir: &Path) -> Result<String, String> {
+fn execute_recover(
+    package_dir: &Path,
+    working_dir: &Path,
+    dry_run: bool,
+    restart: bool,
+) -> Result<String, String> {
     // Load package
     let package = EscrowPackage::load(package_dir)?;
 
-    // Create working directory
-    std::fs::create_dir_all(working_dir)
-        .map_err(|e| format!("Failed to create working directory: {}", e))?;
+    let orchestrator = RecoveryOrchestrator::new(package, working_dir.to_path_buf())
+        .with_package_dir(package_dir.to_path_buf())
+        .restart(restart);
+    if dry_run {
+        return Ok(orchestrator.plan().format_text());
+    }
 
     // Run recovery
-    let orchestrator = RecoveryOrchestrator::new(package, working_dir.to_path_buf());
     let report = orchestrator.recover()?;
 
     Ok(report.format_text())
@@ -203,6 +310,9 @@ fn execute_list(escrow_dir: Option<PathBuf>) -> Result<String, String> {
                 ));
                 output.push_str(&format!("   Date: {}\n", package.metadata.release_date));
                 output.push_str(&format!("   Commit: {}\n", package.metadata.commit_hash));
+                if let Some(delta) = &package.delta {
+                    output.push_str(&format!("   Delta of: v{}\n", delta.base_version));
+                }
                 output.push_str(&format!("   Location: {}\n\n", path.display()));
             }
         }
@@ -304,6 +414,7 @@ mod tests {
             version: "1.0.0".to_string(),
             output_dir: Some(output_dir.clone()),
             include_artifacts: false,
+            base: None,
         };
 
         // This will fail because we're not in a git repo, but we can test the error handling
@@ -366,6 +477,8 @@ mod tests {
         let cmd = EscrowCommand::Recover {
             package_dir: package_dir.clone(),
             working_dir: working_dir.clone(),
+            dry_run: false,
+            restart: false,
         };
 
         let result = execute_escrow_command(cmd);
@@ -0,0 +1,415 @@
+// Exit-code contract and CI gating thresholds
+//
+// Every command exits with one of the codes below so pipelines can gate
+// merges on the exit status alone. When several conditions apply, the most
+// severe code wins. Warnings only affect the exit status with
+// `--fail-on warnings`, so free-edition scans (where policy violations are
+// downgraded to warnings) keep exiting 0.
+
+use crate::engines::policy::PolicyResult;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::models::{Detection, Severity};
+use crate::engines::slo::{SloResult, SloStatus};
+use std::fmt;
+use std::str::FromStr;
+
+/// Process exit status, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ExitCode {
+    /// No findings that affect the gate
+    Clean,
+    /// Non-blocking findings (policy warnings, non-blocking SLO results)
+    Warnings,
+    /// Policy violations or a `--fail-on*` threshold was crossed
+    PolicyViolation,
+    /// A blocking cost SLO was breached
+    SloBreach,
+    /// The command could not complete
+    InternalError,
+    /// The command line could not be parsed
+    UsageError,
+    /// The command needs the Premium edition
+    PremiumRequired,
+}
+
+impl ExitCode {
+    pub fn code(self) -> i32 {
+        match self {
+            ExitCode::Clean => 0,
+            ExitCode::Warnings => 2,
+            ExitCode::PolicyViolation => 3,
+            ExitCode::SloBreach => 4,
+            ExitCode::InternalError => 5,
+            ExitCode::UsageError => 6,
+            ExitCode::PremiumRequired => 7,
+        }
+    }
+
+    /// Exit code for a command that failed with `err`
+    pub fn for_error(err: &CostPilotError) -> Self {
+        match err.category {
+            ErrorCategory::PolicyViolation => ExitCode::PolicyViolation,
+            _ if err.id == "E_UPGRADE_REQUIRED" => ExitCode::PremiumRequired,
+            _ => ExitCode::InternalError,
+        }
+    }
+
+    /// Exit code for a command line clap rejected; help and version output
+    /// are not errors
+    pub fn for_usage_error(err: &clap::Error) -> Self {
+        if err.use_stderr() {
+            ExitCode::UsageError
+        } else {
+            ExitCode::Clean
+        }
+    }
+
+    /// Terminate the process with this code
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+}
+
+/// Maximum allowed monthly cost increase, parsed from `10%` or `$50`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IncreaseThreshold {
+    /// Percentage of the reference cost
+    Percent(f64),
+    /// Absolute monthly amount in USD
+    Amount(f64),
+}
+
+impl IncreaseThreshold {
+    /// Whether `cost` exceeds the threshold relative to `reference`
+    ///
+    /// Percentages need a reference cost; `None` is returned without one.
+    pub fn exceeded(&self, cost: f64, reference: Option<f64>) -> Option<bool> {
+        match *self {
+            IncreaseThreshold::Amount(limit) => Some(cost - reference.unwrap_or(0.0) > limit),
+            IncreaseThreshold::Percent(limit) => {
+                let reference = reference?;
+                if reference <= 0.0 {
+                    return Some(cost > 0.0);
+                }
+                Some((cost - reference) / reference * 100.0 > limit)
+            }
+        }
+    }
+}
+
+impl FromStr for IncreaseThreshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (value, percent) = match s.strip_suffix('%') {
+            Some(value) => (value, true),
+            None => (s.strip_prefix('$').unwrap_or(s), false),
+        };
+        let value: f64 = value
+            .trim()
+            .parse()
+            .ok()
+            .filter(|v: &f64| v.is_finite() && *v >= 0.0)
+            .ok_or_else(|| {
+                format!(
+                    "invalid increase threshold '{}' (expected e.g. 10% or $50)",
+                    s
+                )
+            })?;
+        Ok(if percent {
+            IncreaseThreshold::Percent(value)
+        } else {
+            IncreaseThreshold::Amount(value)
+        })
+    }
+}
+
+impl fmt::Display for IncreaseThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IncreaseThreshold::Percent(p) => write!(f, "{}%", p),
+            IncreaseThreshold::Amount(a) => write!(f, "${:.2}", a),
+        }
+    }
+}
+
+/// Condition that fails the gate, parsed from `warnings` or `key=value`
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailOn {
+    /// Exit 2 on policy warnings or non-blocking SLO results
+    Warnings,
+    /// Any detection at or above this severity
+    Severity(Severity),
+}
+
+impl FromStr for FailOn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("warnings") {
+            return Ok(FailOn::Warnings);
+        }
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("invalid condition '{}' (expected warnings or key=value)", s))?;
+        match key.trim().to_lowercase().as_str() {
+            "severity" => {
+                let severity = match value.trim().to_lowercase().as_str() {
+                    "low" => Severity::Low,
+                    "medium" => Severity::Medium,
+                    "high" => Severity::High,
+                    "critical" => Severity::Critical,
+                    other => {
+                        return Err(format!(
+                            "unknown severity '{}' (expected low, medium, high or critical)",
+                            other
+                        ))
+                    }
+                };
+                Ok(FailOn::Severity(severity))
+            }
+            other => Err(format!(
+                "unknown condition '{}' (supported: severity)",
+                other
+            )),
+        }
+    }
+}
+
+/// Results a gate decision is based on
+#[derive(Debug, Default)]
+pub struct GateInput<'a> {
+    /// Estimated monthly cost after the change
+    pub monthly_cost: f64,
+    /// Cost the increase is measured against, if known
+    pub reference_cost: Option<f64>,
+    pub detections: &'a [Detection],
+    pub policy_result: Option<&'a PolicyResult>,
+    pub slo_result: Option<&'a SloResult>,
+}
+
+/// Exit code plus the reasons that produced it
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateVerdict {
+    pub code: ExitCode,
+    pub reasons: Vec<String>,
+}
+
+impl GateVerdict {
+    pub(crate) fn raise(&mut self, code: ExitCode, reason: String) {
+        self.code = self.code.max(code);
+        self.reasons.push(reason);
+    }
+}
+
+/// Thresholds from `--fail-on-increase` and `--fail-on`
+#[derive(Debug, Clone, Default)]
+pub struct CiGate {
+    pub fail_on_increase: Option<IncreaseThreshold>,
+    pub fail_on: Vec<FailOn>,
+}
+
+impl CiGate {
+    pub fn evaluate(&self, input: &GateInput) -> Result<GateVerdict, CostPilotError> {
+        let warnings_fail = self.fail_on.contains(&FailOn::Warnings);
+        let mut verdict = GateVerdict {
+            code: ExitCode::Clean,
+            reasons: Vec::new(),
+        };
+
+        if let Some(policy) = input.policy_result {
+            if !policy.passed || !policy.violations.is_empty() {
+                verdict.raise(
+                    ExitCode::PolicyViolation,
+                    format!("{} policy violation(s)", policy.violations.len()),
+                );
+            }
+            if warnings_fail && !policy.warnings.is_empty() {
+                verdict.raise(
+                    ExitCode::Warnings,
+                    format!("{} policy warning(s)", policy.warnings.len()),
+                );
+            }
+        }
+
+        if let Some(slo) = input.slo_result {
+            if slo.should_block {
+                verdict.raise(ExitCode::SloBreach, slo.message.clone());
+            } else if warnings_fail
+                && (!slo.passed
+                    || slo
+                        .evaluations
+                        .iter()
+                        .any(|e| e.status == SloStatus::Warning))
+            {
+                verdict.raise(ExitCode::Warnings, slo.message.clone());
+            }
+        }
+
+        if let Some(threshold) = self.fail_on_increase {
+            let exceeded = threshold
+                .exceeded(input.monthly_cost, input.reference_cost)
+                .ok_or_else(|| {
+                    CostPilotError::new(
+                        "GATE_001",
+                        ErrorCategory::InvalidInput,
+                        format!(
+                            "--fail-on-increase {} needs a reference cost to compare against",
+                            threshold
+                        ),
+                    )
+                    .with_hint(
+                        "Pass --baselines with a global baseline, or use an amount such as $50"
+                            .to_string(),
+                    )
+                })?;
+            if exceeded {
+                verdict.raise(
+                    ExitCode::PolicyViolation,
+                    format!(
+                        "Monthly cost ${:.2} exceeds allowed increase of {} over ${:.2}",
+                        input.monthly_cost,
+                        threshold,
+                        input.reference_cost.unwrap_or(0.0)
+                    ),
+                );
+            }
+        }
+
+        for condition in &self.fail_on {
+            match condition {
+                FailOn::Warnings => {}
+                FailOn::Severity(min) => {
+                    let count = input
+                        .detections
+                        .iter()
+                        .filter(|d| d.severity >= *min)
+                        .count();
+                    if count > 0 {
+                        verdict.raise(
+                            ExitCode::PolicyViolation,
+                            format!("{} detection(s) at severity {:?} or above", count, min),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_parsing() {
+        assert_eq!(
+            "10%".parse::<IncreaseThreshold>().unwrap(),
+            IncreaseThreshold::Percent(10.0)
+        );
+        assert_eq!(
+            "$50".parse::<IncreaseThreshold>().unwrap(),
+            IncreaseThreshold::Amount(50.0)
+        );
+        assert!("ten%".parse::<IncreaseThreshold>().is_err());
+        assert_eq!(
+            "severity=HIGH".parse::<FailOn>().unwrap(),
+            FailOn::Severity(Severity::High)
+        );
+        assert_eq!("warnings".parse::<FailOn>().unwrap(), FailOn::Warnings);
+        assert!("owner=team".parse::<FailOn>().is_err());
+    }
+
+    #[test]
+    fn test_most_severe_code_wins() {
+        let detections = vec![Detection::builder()
+            .rule_id("NAT_GATEWAY_COST")
+            .resource_id("aws_nat_gateway.main")
+            .severity(Severity::High)
+            .build()];
+        let policy = PolicyResult {
+            violations: Vec::new(),
+            warnings: vec!["budget nearly exhausted".to_string()],
+            applied_exemptions: Vec::new(),
+            passed: true,
+        };
+        let input = GateInput {
+            monthly_cost: 115.0,
+            reference_cost: Some(100.0),
+            detections: &detections,
+            policy_result: Some(&policy),
+            slo_result: None,
+        };
+
+        assert_eq!(
+            CiGate::default().evaluate(&input).unwrap().code,
+            ExitCode::Clean
+        );
+        let warnings = CiGate {
+            fail_on: vec![FailOn::Warnings],
+            ..Default::default()
+        };
+        assert_eq!(warnings.evaluate(&input).unwrap().code, ExitCode::Warnings);
+
+        let gate = CiGate {
+            fail_on_increase: Some(IncreaseThreshold::Percent(10.0)),
+            fail_on: vec![FailOn::Warnings, FailOn::Severity(Severity::Critical)],
+        };
+        let verdict = gate.evaluate(&input).unwrap();
+        assert_eq!(verdict.code, ExitCode::PolicyViolation);
+        assert_eq!(verdict.code.code(), 3);
+        assert_eq!(verdict.reasons.len(), 2);
+    }
+
+    #[test]
+    fn test_percent_threshold_requires_reference() {
+        let gate = CiGate {
+            fail_on_increase: Some(IncreaseThreshold::Percent(10.0)),
+            fail_on: Vec::new(),
+        };
+        let err = gate
+            .evaluate(&GateInput {
+                monthly_cost: 50.0,
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert_eq!(ExitCode::for_error(&err), ExitCode::InternalError);
+
+        let gate = CiGate {
+            fail_on_increase: Some(IncreaseThreshold::Amount(40.0)),
+            fail_on: Vec::new(),
+        };
+        let verdict = gate
+            .evaluate(&GateInput {
+                monthly_cost: 50.0,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(verdict.code, ExitCode::PolicyViolation);
+    }
+
+    #[test]
+    fn test_usage_and_upgrade_errors_have_own_codes() {
+        let cmd = || {
+            clap::Command::new("costpilot")
+                .version("1.0.0")
+                .arg(clap::Arg::new("plan").long("plan"))
+        };
+        let err = cmd()
+            .try_get_matches_from(["costpilot", "--plna"])
+            .unwrap_err();
+        assert_eq!(ExitCode::for_usage_error(&err), ExitCode::UsageError);
+        assert_eq!(ExitCode::for_usage_error(&err).code(), 6);
+        for flag in ["--help", "--version"] {
+            let err = cmd().try_get_matches_from(["costpilot", flag]).unwrap_err();
+            assert_eq!(ExitCode::for_usage_error(&err), ExitCode::Clean);
+        }
+
+        let err = CostPilotError::upgrade_required("Trend tracking requires Premium");
+        assert_eq!(ExitCode::for_error(&err), ExitCode::PremiumRequired);
+        assert_eq!(ExitCode::for_error(&err).code(), 7);
+    }
+}
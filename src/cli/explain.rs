@@ -1,10 +1,14 @@
 // CLI command for explaining cost predictions
 
+use crate::cli::options::GlobalOptions;
 use crate::engines::detection::DetectionEngine;
-use crate::engines::prediction::PredictionEngine;
-use crate::engines::shared::models::ChangeAction;
+use crate::engines::prediction::{PredictionEngine, UsageProfile};
+use crate::engines::shared::models::{ChangeAction, CostEstimate, ResourceChange};
 use clap::Subcommand;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Project configuration holding the usage profile
+const PROJECT_CONFIG: &str = "costpilot.yaml";
 
 #[derive(Debug, Subcommand)]
 pub enum ExplainCommand {
@@ -19,8 +23,17 @@ pub enum ExplainCommand {
         resource: String,
 
         /// Show verbose step-by-step reasoning
-        #[arg(short, long)]
-        verbose: bool,
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
+
+        /// Also list the single changes that would make this resource cheaper
+        #[arg(long)]
+        counterfactual: bool,
+
+        /// Show the estimate's audit trail: heuristic, pricing version,
+        /// assumptions and every calculation step
+        #[arg(long)]
+        show_math: bool,
     },
 
     /// Explain all resources in a plan
@@ -68,25 +81,48 @@ pub struct ExplainArgs {
 pub fn execute_explain_command(
     command: ExplainCommand,
     edition: &crate::edition::EditionContext,
+    options: &GlobalOptions,
 ) -> Result<String, String> {
     match command {
         ExplainCommand::Resource {
             plan,
             resource,
             verbose,
+            counterfactual,
+            show_math,
         } => {
+            let verbose = verbose > 0;
+
             // Gate verbose mode for Premium
             if verbose {
-                crate::edition::require_premium(edition, "Advanced Explain")
+                edition
+                    .require_capability(crate::edition::Capability::ExplainFull, "Advanced Explain")
                     .map_err(|e| e.to_string())?;
             }
 
-            if edition.capabilities.allow_explain_full {
-                execute_explain_resource(plan, resource, verbose, edition)
+            // Free edition: top patterns only
+            let mut output = if edition.capabilities.allow_explain_full {
+                execute_explain_resource(
+                    plan.clone(),
+                    resource.clone(),
+                    verbose,
+                    counterfactual,
+                    edition,
+                    options.profile(),
+                )?
             } else {
-                // Free edition: top patterns only
-                execute_explain_lite(plan)
+                execute_explain_lite(plan.clone())?
+            };
+            if show_math {
+                output.push('\n');
+                output.push_str(&execute_show_math(
+                    plan,
+                    &resource,
+                    edition,
+                    options.profile(),
+                )?);
             }
+            Ok(output)
         }
         ExplainCommand::All {
             plan,
@@ -94,7 +130,7 @@ pub fn execute_explain_command(
             limit,
         } => {
             if edition.capabilities.allow_explain_full {
-                execute_explain_all(plan, min_cost, limit, edition)
+                execute_explain_all(plan, min_cost, limit, edition, options.profile())
             } else {
                 execute_explain_lite(plan)
             }
@@ -106,7 +142,9 @@ fn execute_explain_resource(
     plan_path: PathBuf,
     resource_id: String,
     verbose: bool,
+    counterfactual: bool,
     edition: &crate::edition::EditionContext,
+    profile: Option<&str>,
 ) -> Result<String, String> {
     // Load plan
     let detection_engine = DetectionEngine::new();
@@ -121,8 +159,7 @@ fn execute_explain_resource(
         .ok_or_else(|| format!("Resource not found: {}", resource_id))?;
 
     // Initialize prediction engine
-    let prediction_engine = PredictionEngine::new_with_edition(edition)
-        .map_err(|e| format!("Failed to initialize prediction engine: {}", e))?;
+    let prediction_engine = plan_engine(edition, &changes, profile)?;
 
     // Generate explanation
     let chain = prediction_engine
@@ -167,14 +204,130 @@ fn execute_explain_resource(
         ));
     }
 
+    if counterfactual {
+        let explainer =
+            crate::engines::explain::PredictionExplainer::from_engine(&prediction_engine);
+        let counterfactuals = explainer.counterfactuals(change);
+        output.push_str("\nWhat would make this cheaper:\n");
+        if counterfactuals.is_empty() {
+            output.push_str("  No single configuration change lowers this estimate\n");
+        }
+        for (idx, counterfactual) in counterfactuals.iter().enumerate() {
+            output.push_str(&format!(
+                "  {}. {} (saves ${:.2}/month)\n",
+                idx + 1,
+                counterfactual.description(),
+                counterfactual.monthly_savings
+            ));
+        }
+    }
+
+    Ok(output)
+}
+
+/// Prediction engine for a plan: usage-billed resources are priced from the
+/// project's usage profile (with `profile` applied), and the autoscaling
+/// targets and task definitions in `changes`
+fn plan_engine(
+    edition: &crate::edition::EditionContext,
+    changes: &[ResourceChange],
+    profile: Option<&str>,
+) -> Result<PredictionEngine, String> {
+    let usage = if Path::new(PROJECT_CONFIG).exists() {
+        UsageProfile::load(Path::new(PROJECT_CONFIG), profile)
+            .map_err(|e| format!("Failed to load usage profile: {}", e))?
+    } else {
+        UsageProfile::default()
+    };
+    Ok(PredictionEngine::new_with_edition(edition)
+        .map_err(|e| format!("Failed to initialize prediction engine: {}", e))?
+        .with_usage_profile(usage)
+        .with_autoscaling_targets(changes)
+        .with_task_definitions(changes))
+}
+
+/// Audit trail of the estimate the edition's scan produces for a resource
+fn execute_show_math(
+    plan_path: PathBuf,
+    resource_id: &str,
+    edition: &crate::edition::EditionContext,
+    profile: Option<&str>,
+) -> Result<String, String> {
+    let changes = DetectionEngine::new()
+        .detect_from_file(&plan_path)
+        .map_err(|e| format!("Failed to load plan: {}", e))?;
+    let change = changes
+        .iter()
+        .find(|c| c.resource_id == resource_id)
+        .ok_or_else(|| format!("Resource not found: {}", resource_id))?;
+
+    let estimate = if edition.capabilities.allow_explain_full {
+        let prediction_engine = plan_engine(edition, &changes, profile)?;
+        Some(
+            prediction_engine
+                .predict_resource_cost(change)
+                .map_err(|e| format!("Failed to predict cost: {}", e))?,
+        )
+    } else {
+        PredictionEngine::predict_static(std::slice::from_ref(change))
+            .map_err(|e| format!("Failed to predict cost: {}", e))?
+            .into_iter()
+            .next()
+    };
+
+    let mut output = format!("🧮 Calculation: {}\n\n", change.resource_id);
+    let Some(estimate) = estimate else {
+        output.push_str(&format!(
+            "No ongoing cost is estimated for {:?} actions\n",
+            change.action
+        ));
+        return Ok(output);
+    };
+    output.push_str(&format_provenance(&estimate));
     Ok(output)
 }
 
+fn format_provenance(estimate: &CostEstimate) -> String {
+    let mut output = String::new();
+    match &estimate.provenance {
+        Some(provenance) => {
+            output.push_str(&format!("Heuristic: {}\n", provenance.heuristic_id));
+            if let Some(version) = &provenance.pricing_version {
+                output.push_str(&format!("Pricing Version: {}\n", version));
+            }
+            if !provenance.assumptions.is_empty() {
+                output.push_str("\nAssumptions:\n");
+                for assumption in &provenance.assumptions {
+                    output.push_str(&format!("  • {}\n", assumption));
+                }
+            }
+            output.push_str("\nSteps:\n");
+            for step in &provenance.steps {
+                output.push_str(&format!("  {}. {}\n", step.step_number, step.operation));
+                output.push_str(&format!("     {} → {}\n", step.input, step.output));
+                for line in step.reasoning.lines() {
+                    output.push_str(&format!("     {}\n", line.trim()));
+                }
+            }
+        }
+        None => output.push_str(&format!(
+            "No calculation trail was recorded for this estimate (source: {})\n",
+            estimate.heuristic_reference.as_deref().unwrap_or("unknown")
+        )),
+    }
+    output.push_str(&format!(
+        "\nResult: ${:.2}/month (range ${:.2} - ${:.2})\n",
+        estimate.monthly_cost, estimate.prediction_interval_low, estimate.prediction_interval_high
+    ));
+    output
+}
+
 fn execute_explain_all(
     plan_path: PathBuf,
     min_cost: f64,
     limit: Option<usize>,
     edition: &crate::edition::EditionContext,
+    profile: Option<&str>,
 ) -> Result<String, String> {
     // Load plan
     let detection_engine = DetectionEngine::new();
@@ -183,8 +336,7 @@ fn execute_explain_all(
         .map_err(|e| format!("Failed to load plan: {}", e))?;
 
     // Initialize prediction engine
-    let prediction_engine = PredictionEngine::new_with_edition(edition)
-        .map_err(|e| format!("Failed to initialize prediction engine: {}", e))?;
+    let prediction_engine = plan_engine(edition, &changes, profile)?;
 
     // Generate predictions and filter
     let mut explanations = Vec::new();
@@ -417,6 +569,45 @@ mod tests {
         assert!(output.contains("Reasoning"));
     }
 
+    #[test]
+    fn test_show_math_lists_calculation_steps() {
+        let plan = PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/terraform/ec2_create.json"
+        ));
+
+        let output = execute_explain_command(
+            ExplainCommand::Resource {
+                plan: plan.clone(),
+                resource: "aws_instance.example".to_string(),
+                verbose: 0,
+                counterfactual: false,
+                show_math: true,
+            },
+            &EditionContext::free(),
+            &GlobalOptions::default(),
+        )
+        .unwrap();
+        assert!(output.contains("Heuristic: free_static"));
+        assert!(output.contains("1. Flat Monthly Rate"));
+        assert!(output.contains("Result: $150.00/month"));
+
+        let output = execute_show_math(
+            plan.clone(),
+            "aws_instance.example",
+            &EditionContext::premium_for_test(),
+            None,
+        )
+        .unwrap();
+        assert!(output.contains("Heuristic: static:aws_instance"));
+        assert!(output.contains("Pricing Version:"));
+        assert!(output.contains("3. Prediction Interval"));
+
+        assert!(
+            execute_show_math(plan, "aws_instance.missing", &EditionContext::free(), None).is_err()
+        );
+    }
+
     #[test]
     fn test_execute_explain_args_unknown_resource() {
         let args = ExplainArgs {
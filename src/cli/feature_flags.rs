@@ -0,0 +1,145 @@
+// `costpilot flags` - feature flag registry and per-repository decisions
+
+use crate::cli::options::GlobalOptions;
+use crate::edition::{CapabilityScope, EditionContext};
+use crate::engines::shared::error_model::CostPilotError;
+use crate::feature_flags::{FeatureFlags, FlagDecision};
+use clap::Args;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// Project config read when `--config` is not given
+const PROJECT_CONFIG: &str = "costpilot.yaml";
+
+/// List feature flags and whether each is on for this repository
+#[derive(Debug, Args)]
+pub struct FlagsCommand {
+    /// Config file with a `feature_flags` section (default: ./costpilot.yaml if present)
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Repository (`owner/name`) to evaluate rollouts for (default: detected
+    /// from COSTPILOT_REPO, CI variables or the origin remote)
+    #[arg(long)]
+    repo: Option<String>,
+}
+
+impl FlagsCommand {
+    pub fn execute(
+        &self,
+        format: &str,
+        edition: &EditionContext,
+        options: &GlobalOptions,
+    ) -> Result<(), CostPilotError> {
+        let config = match &self.config {
+            Some(path) => Some(path.clone()),
+            None => Path::new(PROJECT_CONFIG)
+                .exists()
+                .then(|| PathBuf::from(PROJECT_CONFIG)),
+        };
+        let flags = FeatureFlags::load_for_project(config.as_deref(), options.profile())?;
+        let repo = self.repo.clone().or_else(|| CapabilityScope::detect().repo);
+        let decisions: Vec<(&String, FlagDecision)> = flags
+            .sorted()
+            .into_iter()
+            .map(|(name, _)| (name, flags.evaluate(name, repo.as_deref(), edition)))
+            .collect();
+
+        if format == "json" {
+            let entries: Vec<serde_json::Value> = decisions
+                .iter()
+                .map(|(name, decision)| {
+                    let flag = &flags.flags[*name];
+                    serde_json::json!({
+                        "name": name,
+                        "enabled": decision.enabled,
+                        "reason": decision.reason.to_string(),
+                        "configured": flag.enabled,
+                        "rollout_percentage": flag.rollout_percentage,
+                        "requires": flag.requires,
+                        "source": flag.source,
+                        "description": flag.description,
+                    })
+                })
+                .collect();
+            let output = serde_json::json!({
+                "enabled": flags.enabled,
+                "repo": repo,
+                "flags": entries,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output)
+                    .map_err(|e| CostPilotError::serialization_error(e.to_string()))?
+            );
+            return Ok(());
+        }
+
+        println!("{}", "🔧 Feature Flags".bright_blue().bold());
+        println!(
+            "Repository: {}",
+            repo.as_deref().unwrap_or("(not detected)").cyan()
+        );
+        if !flags.enabled {
+            println!("{}", "All feature flags are disabled".yellow());
+        }
+        println!();
+        for (name, decision) in &decisions {
+            let flag = &flags.flags[*name];
+            let state = if decision.enabled {
+                "on ".green().bold()
+            } else {
+                "off".red().bold()
+            };
+            println!(
+                "  {} {}  {}",
+                state,
+                name.bold(),
+                decision.reason.to_string().bright_black()
+            );
+            let mut details = vec![
+                format!("rollout {:.0}%", flag.rollout_percentage * 100.0),
+                format!("from {}", flag.source),
+            ];
+            if let Some(capability) = flag.requires {
+                details.push(format!("requires {}", capability.name()));
+            }
+            println!("      {}", details.join(", ").bright_black());
+            if let Some(description) = &flag.description {
+                println!("      {}", description);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_command_reads_project_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("costpilot.yaml");
+        std::fs::write(
+            &config,
+            "feature_flags:\n  new_report:\n    description: Redesigned report\n",
+        )
+        .unwrap();
+        let command = FlagsCommand {
+            config: Some(config),
+            repo: Some("acme/infra".to_string()),
+        };
+        assert!(command
+            .execute("json", &EditionContext::free(), &GlobalOptions::default())
+            .is_ok());
+
+        let missing = FlagsCommand {
+            config: Some(dir.path().join("missing.yaml")),
+            repo: None,
+        };
+        assert!(missing
+            .execute("text", &EditionContext::free(), &GlobalOptions::default())
+            .is_err());
+    }
+}
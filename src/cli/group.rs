@@ -1,7 +1,14 @@
 // CLI commands for grouping operations
 
-use crate::engines::grouping::GroupingEngine;
+use crate::cli::options::GlobalOptions;
+use crate::engines::grouping::{
+    inference_warnings, AttributionPipeline, DimensionGroup, EnvironmentGroup, EnvironmentMapping,
+    EnvironmentResolver, GroupDimension, GroupingEngine, ModuleGroup, ServiceGroup,
+    SharedCostConfig, SharedCostStrategy,
+};
 // use crate::parser::plan_parser::PlanParser; // TODO: Implement plan parser
+use crate::engines::shared::filter::FilterExpr;
+use crate::engines::shared::pruning::{Pruning, REMAINDER_LABEL};
 use clap::{Args, Subcommand};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -9,10 +16,18 @@ use std::path::PathBuf;
 /// Type alias for resource tuple: (address, type, service, tags, cost)
 pub type ResourceTuple = (String, String, String, HashMap<String, String>, f64);
 
+/// Project configuration with the optional `environment_mapping` section
+const PROJECT_CONFIG: &str = "costpilot.yaml";
+
 #[derive(Debug, Args)]
 pub struct GroupCommand {
     #[command(subcommand)]
     pub command: GroupSubcommand,
+
+    /// Only group resources matching this expression
+    /// (e.g. 'type=aws_instance and cost>100 and module=~vpc')
+    #[arg(long, global = true, value_name = "EXPR")]
+    pub filter: Option<FilterExpr>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -26,11 +41,11 @@ pub enum GroupSubcommand {
         #[arg(short = 't', long)]
         tree: bool,
 
-        /// Minimum cost threshold to include
+        /// Fold groups costing less than this into "everything else"
         #[arg(short = 'm', long, default_value = "0.0")]
         min_cost: f64,
 
-        /// Maximum number of groups to show
+        /// Maximum number of groups to show (overrides --top)
         #[arg(short = 'n', long)]
         max_groups: Option<usize>,
     },
@@ -45,11 +60,11 @@ pub enum GroupSubcommand {
         #[arg(short, long)]
         by_category: bool,
 
-        /// Minimum cost threshold to include
+        /// Fold services costing less than this into "everything else"
         #[arg(short = 'm', long, default_value = "0.0")]
         min_cost: f64,
 
-        /// Maximum number of services to show
+        /// Maximum number of services to show (overrides --top)
         #[arg(short = 'n', long)]
         max_groups: Option<usize>,
     },
@@ -68,7 +83,7 @@ pub enum GroupSubcommand {
         #[arg(short = 'a', long)]
         detect_anomalies: bool,
 
-        /// Minimum cost threshold to include
+        /// Fold environments costing less than this into "everything else"
         #[arg(short = 'm', long, default_value = "0.0")]
         min_cost: f64,
     },
@@ -89,6 +104,37 @@ pub enum GroupSubcommand {
         /// Show top N cost centers
         #[arg(short = 'n', long, default_value = "10")]
         top_n: usize,
+
+        /// Split shared resource costs (none, even, proportional, weighted)
+        #[arg(long)]
+        shared_strategy: Option<String>,
+
+        /// Attribution dimension that identifies consumers of shared costs
+        #[arg(long)]
+        shared_by: Option<String>,
+
+        /// JSON file with shared-cost configuration (strategy, dimension, resource_types, weights)
+        #[arg(long)]
+        shared_config: Option<PathBuf>,
+    },
+
+    /// Group resources by tag keys or multi-level custom dimensions
+    #[command(name = "by-tag")]
+    Tag {
+        /// Path to Terraform plan file (JSON format)
+        plan: PathBuf,
+
+        /// Dimensions to group by, outermost first (e.g. tag:team,environment)
+        #[arg(short = 'g', long)]
+        group_by: String,
+
+        /// Fold top-level groups costing less than this into "everything else"
+        #[arg(short = 'm', long, default_value = "0.0")]
+        min_cost: f64,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
     },
 
     /// Generate comprehensive report across all dimensions
@@ -103,12 +149,17 @@ pub enum GroupSubcommand {
         /// Output file path (stdout if not specified)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Additional custom grouping, outermost first (e.g. tag:team,environment)
+        #[arg(short = 'g', long)]
+        group_by: Option<String>,
     },
 }
 
 pub fn execute_group_command(
     cmd: GroupCommand,
     edition: &crate::edition::EditionContext,
+    options: &GlobalOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Extract plan path and subcommand
     let (plan, subcommand) = match cmd.command {
@@ -163,27 +214,72 @@ pub fn execute_group_command(
             format,
             output,
             top_n,
+            shared_strategy,
+            shared_by,
+            shared_config,
         } => (
             plan,
             GroupExecution::Attribution {
                 format,
                 output,
                 top_n,
+                shared_costs: load_shared_cost_config(shared_strategy, shared_by, shared_config)?,
+            },
+        ),
+        GroupSubcommand::Tag {
+            plan,
+            group_by,
+            min_cost,
+            format,
+        } => (
+            plan,
+            GroupExecution::Tag {
+                dimensions: GroupDimension::parse_list(&group_by)?,
+                min_cost,
+                format,
             },
         ),
         GroupSubcommand::All {
             plan,
             format,
             output,
-        } => (plan, GroupExecution::All { format, output }),
+            group_by,
+        } => {
+            let dimensions = match group_by {
+                Some(spec) => GroupDimension::parse_list(&spec)?,
+                None => Vec::new(),
+            };
+            (
+                plan,
+                GroupExecution::All {
+                    format,
+                    output,
+                    dimensions,
+                },
+            )
+        }
     };
 
     // Load and parse the plan using detection engine
     use crate::engines::detection::DetectionEngine;
     let detection = DetectionEngine::new();
-    let resources = detection.detect_from_terraform_plan(&plan)?;
+    let mut resources = detection.detect_from_terraform_plan(&plan)?;
+    if let Some(filter) = &cmd.filter {
+        resources = crate::cli::utils::filter_changes(filter, resources, edition)?;
+    }
 
-    let engine = GroupingEngine::new();
+    let mapping = if std::path::Path::new(PROJECT_CONFIG).exists() {
+        EnvironmentMapping::load(std::path::Path::new(PROJECT_CONFIG), options.profile())?
+    } else {
+        EnvironmentMapping::default()
+    };
+    let engine = match &subcommand {
+        GroupExecution::Attribution { shared_costs, .. } => GroupingEngine::with_pipeline(
+            AttributionPipeline::new().with_shared_costs(shared_costs.clone()),
+        ),
+        _ => GroupingEngine::new(),
+    }
+    .with_environment_resolver(EnvironmentResolver::for_plan(mapping, &plan));
 
     match subcommand {
         GroupExecution::Module {
@@ -191,31 +287,71 @@ pub fn execute_group_command(
             min_cost,
             max_groups,
         } => {
-            execute_group_module(&engine, &resources, tree, min_cost, max_groups)?;
+            execute_group_module(
+                &engine,
+                &resources,
+                options.pruning,
+                tree,
+                min_cost,
+                max_groups,
+            )?;
         }
         GroupExecution::Service {
             by_category,
             min_cost,
             max_groups,
         } => {
-            execute_group_service(&engine, &resources, by_category, min_cost, max_groups)?;
+            execute_group_service(
+                &engine,
+                &resources,
+                options.pruning,
+                by_category,
+                min_cost,
+                max_groups,
+            )?;
         }
         GroupExecution::Environment {
             detailed,
             detect_anomalies,
             min_cost,
         } => {
-            execute_group_environment(&engine, &resources, detailed, detect_anomalies, min_cost)?;
+            execute_group_environment(
+                &engine,
+                &resources,
+                options.pruning,
+                detailed,
+                detect_anomalies,
+                min_cost,
+            )?;
         }
         GroupExecution::Attribution {
             format,
             output,
             top_n,
+            ..
         } => {
             execute_attribution(&engine, &resources, &format, output, top_n)?;
         }
-        GroupExecution::All { format, output } => {
-            execute_comprehensive(&engine, &resources, &format, output)?;
+        GroupExecution::Tag {
+            dimensions,
+            min_cost,
+            format,
+        } => {
+            execute_group_tag(
+                &engine,
+                &resources,
+                options.pruning,
+                &dimensions,
+                min_cost,
+                &format,
+            )?;
+        }
+        GroupExecution::All {
+            format,
+            output,
+            dimensions,
+        } => {
+            execute_comprehensive(&engine, &resources, &format, output, &dimensions)?;
         }
     }
 
@@ -243,16 +379,52 @@ enum GroupExecution {
         format: String,
         output: Option<PathBuf>,
         top_n: usize,
+        shared_costs: SharedCostConfig,
+    },
+    Tag {
+        dimensions: Vec<GroupDimension>,
+        min_cost: f64,
+        format: String,
     },
     All {
         format: String,
         output: Option<PathBuf>,
+        dimensions: Vec<GroupDimension>,
     },
 }
 
+fn load_shared_cost_config(
+    strategy: Option<String>,
+    dimension: Option<String>,
+    config_path: Option<PathBuf>,
+) -> Result<SharedCostConfig, Box<dyn std::error::Error>> {
+    let mut config = match config_path {
+        Some(path) => {
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str::<SharedCostConfig>(&content)
+                .map_err(|e| format!("Failed to parse shared cost config: {}", e))?
+        }
+        None => SharedCostConfig::default(),
+    };
+
+    if let Some(strategy) = strategy {
+        config.strategy = strategy.parse()?;
+    }
+    if let Some(dimension) = dimension {
+        config.dimension = dimension;
+    }
+
+    if config.strategy == SharedCostStrategy::Weighted && config.weights.is_empty() {
+        tracing::warn!("No shared cost weights configured; falling back to an even split");
+    }
+
+    Ok(config)
+}
+
 fn execute_group_module(
     engine: &GroupingEngine,
     resources: &[crate::engines::shared::models::ResourceChange],
+    pruning: Pruning,
     tree: bool,
     min_cost: f64,
     max_groups: Option<usize>,
@@ -260,20 +432,16 @@ fn execute_group_module(
     let module_resources: Vec<(String, String, f64)> = resources
         .iter()
         .filter_map(|r| {
-            if let Some(cost) = r.monthly_cost {
-                if cost >= min_cost {
-                    return Some((r.resource_id.clone(), r.resource_type.clone(), cost));
-                }
-            }
-            None
+            r.monthly_cost
+                .map(|cost| (r.resource_id.clone(), r.resource_type.clone(), cost))
         })
         .collect();
 
-    let mut groups = engine.group_by_module(&module_resources);
-
-    if let Some(max) = max_groups {
-        groups.truncate(max);
-    }
+    let groups = pruning.with_min_cost(min_cost).with_top(max_groups).apply(
+        engine.group_by_module(&module_resources),
+        |g| g.monthly_cost,
+        ModuleGroup::remainder,
+    );
 
     println!("Module Grouping Report");
     println!("=====================\n");
@@ -310,6 +478,7 @@ fn execute_group_module(
 fn execute_group_service(
     engine: &GroupingEngine,
     resources: &[crate::engines::shared::models::ResourceChange],
+    pruning: Pruning,
     by_category: bool,
     min_cost: f64,
     max_groups: Option<usize>,
@@ -317,25 +486,26 @@ fn execute_group_service(
     let service_resources: Vec<(String, String, f64)> = resources
         .iter()
         .filter_map(|r| {
-            if let Some(cost) = r.monthly_cost {
-                if cost >= min_cost {
-                    return Some((r.resource_id.clone(), r.resource_type.clone(), cost));
-                }
-            }
-            None
+            r.monthly_cost
+                .map(|cost| (r.resource_id.clone(), r.resource_type.clone(), cost))
         })
         .collect();
 
-    let mut groups = engine.group_by_service(&service_resources);
-
-    if let Some(max) = max_groups {
-        groups.truncate(max);
-    }
+    let groups = engine.group_by_service(&service_resources);
+    let pruning = pruning.with_min_cost(min_cost).with_top(max_groups);
 
     if by_category {
         let category_costs = crate::engines::grouping::cost_by_category(&groups);
-        let mut categories: Vec<_> = category_costs.into_iter().collect();
+        let mut categories: Vec<(&str, f64)> = category_costs
+            .into_iter()
+            .map(|(category, cost)| (category.as_str(), cost))
+            .collect();
         categories.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        let categories = pruning.apply(
+            categories,
+            |(_, cost)| *cost,
+            |rest| (REMAINDER_LABEL, rest.iter().map(|(_, cost)| cost).sum()),
+        );
 
         println!("Service Grouping by Category");
         println!("===========================\n");
@@ -349,14 +519,10 @@ fn execute_group_service(
             } else {
                 0.0
             };
-            println!(
-                "{}: ${:.2}/mo ({:.1}%)",
-                category.as_str(),
-                cost,
-                percentage
-            );
+            println!("{}: ${:.2}/mo ({:.1}%)", category, cost, percentage);
         }
     } else {
+        let groups = pruning.apply(groups, |g| g.monthly_cost, ServiceGroup::remainder);
         println!(
             "{}",
             crate::engines::grouping::generate_service_report(&groups)
@@ -369,6 +535,7 @@ fn execute_group_service(
 fn execute_group_environment(
     engine: &GroupingEngine,
     resources: &[crate::engines::shared::models::ResourceChange],
+    pruning: Pruning,
     detailed: bool,
     detect_anomalies: bool,
     min_cost: f64,
@@ -376,25 +543,28 @@ fn execute_group_environment(
     let env_resources: Vec<ResourceTuple> = resources
         .iter()
         .filter_map(|r| {
-            if let Some(cost) = r.monthly_cost {
-                if cost >= min_cost {
-                    let (service, _) = crate::engines::grouping::by_service::extract_service_info(
-                        &r.resource_type,
-                    );
-                    return Some((
-                        r.resource_id.clone(),
-                        r.resource_type.clone(),
-                        service,
-                        r.tags.clone(),
-                        cost,
-                    ));
-                }
-            }
-            None
+            r.monthly_cost.map(|cost| {
+                let (service, _) =
+                    crate::engines::grouping::by_service::extract_service_info(&r.resource_type);
+                (
+                    r.resource_id.clone(),
+                    r.resource_type.clone(),
+                    service,
+                    r.tags.clone(),
+                    cost,
+                )
+            })
         })
         .collect();
 
     let groups = engine.group_by_environment(&env_resources);
+    // Warnings name the environment, so they come from the unpruned groups
+    let warnings = inference_warnings(&groups);
+    let groups = pruning.with_min_cost(min_cost).apply(
+        groups,
+        |g| g.monthly_cost,
+        EnvironmentGroup::remainder,
+    );
 
     if detailed || detect_anomalies {
         println!(
@@ -415,10 +585,21 @@ fn execute_group_environment(
                 0.0
             };
             println!(
-                "{}: ${:.2}/mo ({:.1}%, {} resources)",
-                group.environment, group.monthly_cost, percentage, group.resource_count
+                "{}: ${:.2}/mo ({:.1}%, {} resources, {:.0}% confidence)",
+                group.environment,
+                group.monthly_cost,
+                percentage,
+                group.resource_count,
+                group.confidence * 100.0
             );
         }
+
+        if !warnings.is_empty() {
+            println!();
+            for warning in warnings {
+                println!("⚠️  {}", warning);
+            }
+        }
     }
 
     Ok(())
@@ -463,11 +644,63 @@ fn execute_attribution(
     Ok(())
 }
 
+fn execute_group_tag(
+    engine: &GroupingEngine,
+    resources: &[crate::engines::shared::models::ResourceChange],
+    pruning: Pruning,
+    dimensions: &[GroupDimension],
+    min_cost: f64,
+    format: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if dimensions.is_empty() {
+        return Err("At least one grouping dimension is required".into());
+    }
+
+    let tag_resources: Vec<(String, String, HashMap<String, String>, f64)> = resources
+        .iter()
+        .filter_map(|r| {
+            r.monthly_cost.map(|cost| {
+                (
+                    r.resource_id.clone(),
+                    r.resource_type.clone(),
+                    r.tags.clone(),
+                    cost,
+                )
+            })
+        })
+        .collect();
+
+    let groups = pruning.with_min_cost(min_cost).apply(
+        engine.group_by_dimensions(&tag_resources, dimensions),
+        |g| g.monthly_cost,
+        DimensionGroup::remainder,
+    );
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&groups)?);
+        return Ok(());
+    }
+
+    let labels: Vec<String> = dimensions.iter().map(|d| d.to_string()).collect();
+    println!("Custom Grouping Report ({})", labels.join(" → "));
+    println!("======================\n");
+
+    let total_cost: f64 = groups.iter().map(|g| g.monthly_cost).sum();
+    println!("Total Monthly Cost: ${:.2}\n", total_cost);
+    println!(
+        "{}",
+        crate::engines::grouping::generate_dimension_tree(&groups)
+    );
+
+    Ok(())
+}
+
 fn execute_comprehensive(
     engine: &GroupingEngine,
     resources: &[crate::engines::shared::models::ResourceChange],
     format: &str,
     output: Option<PathBuf>,
+    dimensions: &[GroupDimension],
 ) -> Result<(), Box<dyn std::error::Error>> {
     let comp_resources: Vec<(String, String, HashMap<String, String>, f64)> = resources
         .iter()
@@ -483,7 +716,7 @@ fn execute_comprehensive(
         })
         .collect();
 
-    let report = engine.generate_comprehensive_report(&comp_resources);
+    let report = engine.generate_comprehensive_report_with_dimensions(&comp_resources, dimensions);
 
     let content = match format {
         "json" => report.to_json()?,
@@ -589,9 +822,10 @@ mod tests {
                 min_cost: 0.0,
                 max_groups: Some(5),
             },
+            filter: None,
         };
 
-        let result = execute_group_command(cmd, &edition);
+        let result = execute_group_command(cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -608,9 +842,10 @@ mod tests {
                 min_cost: 0.0,
                 max_groups: Some(5),
             },
+            filter: None,
         };
 
-        let result = execute_group_command(cmd, &edition);
+        let result = execute_group_command(cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -627,9 +862,10 @@ mod tests {
                 detect_anomalies: false,
                 min_cost: 0.0,
             },
+            filter: None,
         };
 
-        let result = execute_group_command(cmd, &edition);
+        let result = execute_group_command(cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -645,10 +881,14 @@ mod tests {
                 format: "text".to_string(),
                 output: None,
                 top_n: 10,
+                shared_strategy: None,
+                shared_by: None,
+                shared_config: None,
             },
+            filter: None,
         };
 
-        let result = execute_group_command(cmd, &edition);
+        let result = execute_group_command(cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -664,10 +904,14 @@ mod tests {
                 format: "json".to_string(),
                 output: None,
                 top_n: 10,
+                shared_strategy: None,
+                shared_by: None,
+                shared_config: None,
             },
+            filter: None,
         };
 
-        let result = execute_group_command(cmd, &edition);
+        let result = execute_group_command(cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -682,10 +926,12 @@ mod tests {
                 plan: plan_path,
                 format: "text".to_string(),
                 output: None,
+                group_by: None,
             },
+            filter: None,
         };
 
-        let result = execute_group_command(cmd, &edition);
+        let result = execute_group_command(cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -700,10 +946,91 @@ mod tests {
                 plan: plan_path,
                 format: "json".to_string(),
                 output: None,
+                group_by: Some("tag:module,environment".to_string()),
             },
+            filter: None,
         };
 
-        let result = execute_group_command(cmd, &edition);
+        let result = execute_group_command(cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_execute_group_command_by_tag() {
+        let temp_file = create_mock_terraform_plan();
+        let plan_path = temp_file.path().to_path_buf();
+        let edition = EditionContext::default();
+
+        let cmd = GroupCommand {
+            command: GroupSubcommand::Tag {
+                plan: plan_path.clone(),
+                group_by: "tag:Module,environment".to_string(),
+                min_cost: 0.0,
+                format: "text".to_string(),
+            },
+            filter: None,
+        };
+        assert!(execute_group_command(cmd, &edition, &GlobalOptions::default()).is_ok());
+
+        let cmd = GroupCommand {
+            command: GroupSubcommand::Tag {
+                plan: plan_path,
+                group_by: "owner".to_string(),
+                min_cost: 0.0,
+                format: "text".to_string(),
+            },
+            filter: None,
+        };
+        assert!(execute_group_command(cmd, &edition, &GlobalOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_execute_group_command_attribution_shared_config() {
+        let temp_file = create_mock_terraform_plan();
+        let plan_path = temp_file.path().to_path_buf();
+        let edition = EditionContext::default();
+
+        let config_file = NamedTempFile::new().unwrap();
+        fs::write(
+            &config_file,
+            r#"{"strategy": "weighted", "dimension": "environment", "weights": {"production": 3, "development": 1}}"#,
+        )
+        .unwrap();
+
+        let config =
+            load_shared_cost_config(None, None, Some(config_file.path().to_path_buf())).unwrap();
+        assert_eq!(config.strategy, SharedCostStrategy::Weighted);
+        assert_eq!(config.dimension, "environment");
+        assert!(config
+            .resource_types
+            .contains(&"aws_nat_gateway".to_string()));
+
+        let cmd = GroupCommand {
+            command: GroupSubcommand::Attribution {
+                plan: plan_path.clone(),
+                format: "csv".to_string(),
+                output: None,
+                top_n: 10,
+                shared_strategy: Some("proportional".to_string()),
+                shared_by: None,
+                shared_config: Some(config_file.path().to_path_buf()),
+            },
+            filter: None,
+        };
+        assert!(execute_group_command(cmd, &edition, &GlobalOptions::default()).is_ok());
+
+        let cmd = GroupCommand {
+            command: GroupSubcommand::Attribution {
+                plan: plan_path,
+                format: "text".to_string(),
+                output: None,
+                top_n: 10,
+                shared_strategy: Some("random".to_string()),
+                shared_by: None,
+                shared_config: None,
+            },
+            filter: None,
+        };
+        assert!(execute_group_command(cmd, &edition, &GlobalOptions::default()).is_err());
+    }
 }
@@ -1,6 +1,11 @@
 // CLI commands for managing cost heuristics
 
-use crate::engines::prediction::HeuristicsLoader;
+use crate::cli::options::GlobalOptions;
+use crate::engines::detection::DetectionEngine;
+use crate::engines::prediction::{
+    ClusterPricing, CustomHeuristics, CustomHeuristicsFile, HeuristicsCoverage, HeuristicsLoader,
+    MinimalHeuristics, CUSTOM_HEURISTICS_DIR, HEURISTICS_VERSION,
+};
 use clap::Subcommand;
 use dirs;
 use std::path::PathBuf;
@@ -20,7 +25,7 @@ pub enum HeuristicsCommand {
     /// Show search paths for heuristics discovery
     Paths,
 
-    /// Validate heuristics file format
+    /// Validate heuristics file format (custom `.yaml` heuristics included)
     Validate {
         /// Path to heuristics file to validate
         file: PathBuf,
@@ -38,9 +43,24 @@ pub enum HeuristicsCommand {
 
     /// Execute heuristics evaluation
     Execute,
+
+    /// Report which resource types in a plan have estimation heuristics
+    Coverage {
+        /// Path to Terraform plan JSON
+        #[arg(long)]
+        plan: PathBuf,
+
+        /// Project config with cluster pricing (default: costpilot.yaml when present)
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+    },
 }
 
-pub fn execute_heuristics_command(command: HeuristicsCommand) -> Result<String, String> {
+pub fn execute_heuristics_command(
+    command: HeuristicsCommand,
+    format: &str,
+    options: &GlobalOptions,
+) -> Result<String, String> {
     match command {
         HeuristicsCommand::List => execute_list(),
         HeuristicsCommand::Stats { file } => execute_stats(file),
@@ -48,7 +68,39 @@ pub fn execute_heuristics_command(command: HeuristicsCommand) -> Result<String,
         HeuristicsCommand::Validate { file } => execute_validate(file),
         HeuristicsCommand::Show { service, file } => execute_show(service, file),
         HeuristicsCommand::Execute => execute_execute(),
+        HeuristicsCommand::Coverage { plan, config } => {
+            execute_coverage(plan, config, format, options.profile())
+        }
+    }
+}
+
+fn execute_coverage(
+    plan: PathBuf,
+    config: Option<PathBuf>,
+    format: &str,
+    profile: Option<&str>,
+) -> Result<String, String> {
+    let changes = DetectionEngine::new()
+        .detect_from_terraform_plan(&plan)
+        .map_err(|e| format!("❌ Failed to read plan: {}", e))?;
+    let config = config.or_else(|| {
+        let default = PathBuf::from("costpilot.yaml");
+        default.exists().then_some(default)
+    });
+    let cluster_pricing = match config {
+        Some(path) => ClusterPricing::load(&path, profile).map_err(|e| format!("❌ {}", e))?,
+        None => ClusterPricing::default(),
+    };
+    let custom = HeuristicsLoader::new()
+        .load_custom(HEURISTICS_VERSION)
+        .map_err(|e| format!("❌ {}", e))?;
+
+    let coverage = HeuristicsCoverage::analyze(&changes, &custom, &cluster_pricing);
+    if format == "json" {
+        return serde_json::to_string_pretty(&coverage)
+            .map_err(|e| format!("❌ Failed to serialize coverage: {}", e));
     }
+    Ok(coverage.format_text())
 }
 
 fn execute_stats(file: Option<PathBuf>) -> Result<String, String> {
@@ -85,10 +137,36 @@ fn execute_paths() -> Result<String, String> {
 
     output.push_str("\n💡 Tip: Place cost_heuristics.json in any of these locations\n");
 
+    let custom_dir = PathBuf::from(CUSTOM_HEURISTICS_DIR);
+    match CustomHeuristics::load_dir(&custom_dir, HEURISTICS_VERSION) {
+        Ok(custom) => {
+            output.push_str(&format!(
+                "\nCustom heuristics: {} ({} file(s) applied",
+                custom_dir.display(),
+                custom.files.len()
+            ));
+            if !custom.skipped.is_empty() {
+                output.push_str(&format!(", {} skipped", custom.skipped.len()));
+            }
+            output.push_str(")\n");
+        }
+        Err(e) => output.push_str(&format!(
+            "\nCustom heuristics: {} - ✗ {}\n",
+            custom_dir.display(),
+            e
+        )),
+    }
+
     Ok(output)
 }
 
 fn execute_validate(file: PathBuf) -> Result<String, String> {
+    if file
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml")
+    {
+        return execute_validate_custom(file);
+    }
     let loader = HeuristicsLoader::new();
 
     match loader.load_from_file(&file) {
@@ -103,6 +181,43 @@ fn execute_validate(file: PathBuf) -> Result<String, String> {
     }
 }
 
+fn execute_validate_custom(file: PathBuf) -> Result<String, String> {
+    let parsed =
+        CustomHeuristicsFile::load(&file).map_err(|e| format!("❌ Validation failed: {}", e))?;
+    let supported = parsed.supports(HEURISTICS_VERSION);
+
+    // Overrides must name keys of the built-in heuristics
+    let custom = CustomHeuristics {
+        files: vec![(file, parsed)],
+        skipped: Vec::new(),
+    };
+    let mut heuristics = MinimalHeuristics::to_cost_heuristics();
+    custom
+        .apply_overrides(&mut heuristics)
+        .map_err(|e| format!("❌ Validation failed: {}", e))?;
+
+    let (_, parsed) = &custom.files[0];
+    let mut output = String::from("✅ Custom heuristics file is valid\n\n");
+    output.push_str(&format!("Version: {}\n", parsed.version));
+    output.push_str(&format!(
+        "Overrides: {}\n",
+        if parsed.overrides.is_some() {
+            "yes"
+        } else {
+            "none"
+        }
+    ));
+    output.push_str(&format!("Resource rates: {}\n", parsed.resources.len()));
+    if !supported {
+        output.push_str(&format!(
+            "\n⚠️  Pinned to heuristics {}; built-in heuristics are {}, so scans skip this file\n",
+            parsed.heuristics_version.as_deref().unwrap_or_default(),
+            HEURISTICS_VERSION
+        ));
+    }
+    Ok(output)
+}
+
 fn execute_show(service: String, file: Option<PathBuf>) -> Result<String, String> {
     let loader = HeuristicsLoader::new();
 
@@ -128,6 +243,24 @@ fn execute_show(service: String, file: Option<PathBuf>) -> Result<String, String
                     instance_type, cost.hourly, cost.monthly
                 ));
             }
+
+            let options = &heuristics.compute.ec2_options;
+            output.push_str(&format!(
+                "\nDedicated tenancy: ×{:.2}\n",
+                options.dedicated_multiplier
+            ));
+            output.push_str("License-included (per vCPU-hour):\n");
+            let mut licenses: Vec<_> = options.license_per_vcpu_hour.iter().collect();
+            licenses.sort_by(|a, b| a.0.cmp(b.0));
+            for (license, rate) in licenses {
+                output.push_str(&format!("  {} - ${:.4}\n", license, rate));
+            }
+            output.push_str("EBS-optimized (per hour, by family):\n");
+            let mut families: Vec<_> = options.ebs_optimized_hourly.iter().collect();
+            families.sort_by(|a, b| a.0.cmp(b.0));
+            for (family, rate) in families {
+                output.push_str(&format!("  {} - ${:.4}\n", family, rate));
+            }
         }
         "lambda" => {
             output.push_str(&format!(
@@ -170,6 +303,35 @@ fn execute_show(service: String, file: Option<PathBuf>) -> Result<String, String
                 "Storage (GP3): ${:.3}/GB/month\n",
                 heuristics.database.rds.storage_gp3_per_gb
             ));
+
+            let options = &heuristics.database.rds_options;
+            let mut storage: Vec<_> = options.storage_per_gb.iter().collect();
+            storage.sort_by(|a, b| a.0.cmp(b.0));
+            for (storage_type, rate) in storage {
+                output.push_str(&format!(
+                    "Storage ({}): ${:.3}/GB/month\n",
+                    storage_type.to_uppercase(),
+                    rate
+                ));
+            }
+            let mut iops: Vec<_> = options.iops_per_month.iter().collect();
+            iops.sort_by(|a, b| a.0.cmp(b.0));
+            for (storage_type, rate) in iops {
+                output.push_str(&format!(
+                    "Provisioned IOPS ({}): ${:.3}/IOPS/month\n",
+                    storage_type.to_uppercase(),
+                    rate
+                ));
+            }
+            output.push_str(&format!(
+                "GP3 included IOPS: {}\n",
+                options.gp3_included_iops
+            ));
+            output.push_str(&format!("Multi-AZ: ×{:.2}\n", options.multi_az_multiplier));
+            output.push_str(&format!(
+                "Aurora Serverless v2: ${:.4}/ACU-hour\n",
+                options.aurora_acu_hourly
+            ));
         }
         "s3" => {
             if let Some(price) = heuristics.storage.s3.standard.first_50tb_per_gb {
@@ -241,6 +403,54 @@ fn execute_show(service: String, file: Option<PathBuf>) -> Result<String, String
                 heuristics.networking.load_balancer.alb.lcu_hourly
             ));
         }
+        "eks" | "fargate" => {
+            let containers = &heuristics.compute.containers;
+            output.push_str(&format!(
+                "EKS control plane: ${:.4}/hour\n",
+                containers.eks_control_plane_hourly
+            ));
+            output.push_str("Managed node groups: priced per node as EC2 instances\n");
+            output.push_str(&format!(
+                "Fargate vCPU: ${:.9}/vCPU-second\n",
+                containers.fargate_vcpu_second
+            ));
+            output.push_str(&format!(
+                "Fargate memory: ${:.9}/GB-second\n",
+                containers.fargate_gb_second
+            ));
+            output.push_str(&format!(
+                "Default task size: {} vCPU, {} GB\n",
+                containers.fargate_default_vcpu, containers.fargate_default_memory_gb
+            ));
+        }
+        "cloudwatch" => {
+            let cloudwatch = &heuristics.monitoring.cloudwatch;
+            output.push_str(&format!(
+                "Log ingestion: ${:.2}/GB (Infrequent Access ${:.2}/GB)\n",
+                cloudwatch.logs_ingestion_per_gb,
+                cloudwatch.logs_infrequent_access_ingestion_per_gb
+            ));
+            output.push_str(&format!(
+                "Log storage: ${:.2}/GB-month\n",
+                cloudwatch.logs_storage_per_gb
+            ));
+            output.push_str(&format!(
+                "Custom metric: ${:.2}/month\n",
+                cloudwatch.custom_metric_monthly
+            ));
+            output.push_str(&format!(
+                "Alarm metric: ${:.2}/month (high resolution ${:.2}/month)\n",
+                cloudwatch.alarm_standard_monthly, cloudwatch.alarm_high_resolution_monthly
+            ));
+            output.push_str(&format!(
+                "Composite alarm: ${:.2}/month\n",
+                cloudwatch.composite_alarm_monthly
+            ));
+            output.push_str(&format!(
+                "Dashboard: ${:.2}/month\n",
+                cloudwatch.dashboard_monthly
+            ));
+        }
         "ebs" => {
             output.push_str("EBS Volume Types:\n");
             for (volume_type, cost) in &heuristics.storage.ebs {
@@ -252,7 +462,7 @@ fn execute_show(service: String, file: Option<PathBuf>) -> Result<String, String
         }
         _ => {
             return Err(format!(
-                "Unknown service: {}. Supported: ec2, rds, lambda, s3, dynamodb, nat, alb, ebs",
+                "Unknown service: {}. Supported: ec2, rds, lambda, s3, dynamodb, nat, alb, ebs, eks, cloudwatch",
                 service
             ));
         }
@@ -51,7 +51,7 @@ pub fn init(directory: &str, ci_provider: &str) -> Result<(), String> {
 }
 
 /// Create directory if it doesn't exist
-fn create_directory(path: &Path) -> Result<(), String> {
+pub(crate) fn create_directory(path: &Path) -> Result<(), String> {
     if !path.exists() {
         fs::create_dir_all(path).map_err(|e| format!("Failed to create directory: {}", e))?;
         println!(
@@ -441,7 +441,7 @@ fn generate_gitignore_entries(project_dir: &Path) -> Result<(), String> {
 }
 
 /// Write file and report status
-fn write_file(path: &Path, content: &str) -> Result<(), String> {
+pub(crate) fn write_file(path: &Path, content: &str) -> Result<(), String> {
     if path.exists() {
         println!(
             "  {} {} already exists (skipped)",
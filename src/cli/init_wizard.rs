@@ -0,0 +1,842 @@
+// Init wizard - guided project scaffolding for `costpilot init --wizard`
+//
+// Detects the IaC framework in the repository (or reads it from a
+// `repo-scanner` inventory), asks for a monthly budget and owning teams, then
+// writes costpilot.yaml, baselines.json, slo.yaml, a starter policy pack and a
+// CI workflow. Every generated file passes `costpilot validate`. Existing
+// files are never overwritten.
+
+use crate::cli::init::{create_directory, write_file};
+use crate::cli::inventory::IacInventory;
+use colored::Colorize;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Monthly budget offered when the user has no number in mind
+pub const DEFAULT_MONTHLY_BUDGET: f64 = 1000.0;
+
+/// Starter policy pack written under `policies/`
+pub const STARTER_POLICY_PATH: &str = "policies/starter.yaml";
+
+/// How deep to look for IaC sources
+const DETECT_MAX_DEPTH: usize = 3;
+
+/// Invalid answers tolerated before the wizard gives up
+const MAX_ATTEMPTS: usize = 3;
+
+/// Infrastructure-as-code framework used by the project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IacFramework {
+    Terraform,
+    Cdk,
+    CloudFormation,
+    Sam,
+    Serverless,
+    Kubernetes,
+}
+
+impl IacFramework {
+    /// Detect the framework from files under `dir`, along with the directory
+    /// holding the sources (relative to `dir`)
+    pub fn detect(dir: &Path) -> Option<(Self, PathBuf)> {
+        if dir.join("cdk.json").is_file() {
+            return Some((IacFramework::Cdk, PathBuf::new()));
+        }
+
+        let mut template = None;
+        let mut kubernetes = None;
+        let entries = WalkDir::new(dir)
+            .max_depth(DETECT_MAX_DEPTH)
+            .into_iter()
+            .filter_entry(|e| {
+                e.depth() == 0
+                    || !e
+                        .file_name()
+                        .to_str()
+                        .is_some_and(|n| n.starts_with('.') || n == "node_modules")
+            })
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file());
+
+        for entry in entries {
+            let path = entry.path();
+            let relative_parent = path
+                .parent()
+                .and_then(|p| p.strip_prefix(dir).ok())
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+            if matches!(
+                path.file_name().and_then(|n| n.to_str()),
+                Some("serverless.yml" | "serverless.yaml")
+            ) {
+                return Some((IacFramework::Serverless, relative_parent));
+            }
+            match path.extension().and_then(|e| e.to_str()) {
+                Some("tf") => return Some((IacFramework::Terraform, relative_parent)),
+                Some("yaml" | "yml" | "json") if template.is_none() => {
+                    let content = std::fs::read_to_string(path).unwrap_or_default();
+                    if content.contains(crate::artifact::SAM_TRANSFORM) {
+                        template = Some((IacFramework::Sam, relative_parent));
+                    } else if content.contains("AWSTemplateFormatVersion") {
+                        template = Some((IacFramework::CloudFormation, relative_parent));
+                    } else if kubernetes.is_none()
+                        && crate::artifact::is_kubernetes_manifest(&content)
+                    {
+                        kubernetes = Some((IacFramework::Kubernetes, relative_parent));
+                    }
+                }
+                _ => {}
+            }
+        }
+        template.or(kubernetes)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            IacFramework::Terraform => "terraform",
+            IacFramework::Cdk => "cdk",
+            IacFramework::CloudFormation => "cloudformation",
+            IacFramework::Sam => "sam",
+            IacFramework::Serverless => "serverless",
+            IacFramework::Kubernetes => "kubernetes",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "terraform" | "tf" => Some(IacFramework::Terraform),
+            "cdk" => Some(IacFramework::Cdk),
+            "cloudformation" | "cfn" => Some(IacFramework::CloudFormation),
+            "sam" => Some(IacFramework::Sam),
+            "serverless" | "sls" => Some(IacFramework::Serverless),
+            "kubernetes" | "k8s" | "helm" => Some(IacFramework::Kubernetes),
+            _ => None,
+        }
+    }
+
+    /// `costpilot scan --infra-format` parser for this framework's sources,
+    /// if it reads them directly
+    pub fn infra_format(&self) -> Option<&'static str> {
+        match self {
+            IacFramework::Terraform => Some("terraform"),
+            IacFramework::Sam => Some("sam"),
+            IacFramework::Serverless => Some("serverless"),
+            IacFramework::Kubernetes => Some("kubernetes"),
+            IacFramework::Cdk | IacFramework::CloudFormation => None,
+        }
+    }
+}
+
+/// CI system to generate a workflow for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    Github,
+    Gitlab,
+    None,
+}
+
+impl CiProvider {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "github" => Some(CiProvider::Github),
+            "gitlab" => Some(CiProvider::Gitlab),
+            "none" | "" => Some(CiProvider::None),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            CiProvider::Github => "github",
+            CiProvider::Gitlab => "gitlab",
+            CiProvider::None => "none",
+        }
+    }
+}
+
+/// Everything the scaffolded files are generated from
+#[derive(Debug, Clone, PartialEq)]
+pub struct WizardAnswers {
+    pub framework: Option<IacFramework>,
+    /// Directory with the IaC sources, relative to the project root
+    pub source_dir: PathBuf,
+    /// Every framework found in the repository, largest first
+    pub detected: Vec<IacFramework>,
+    pub monthly_budget: f64,
+    pub teams: Vec<String>,
+    pub region: String,
+    pub ci: CiProvider,
+}
+
+impl WizardAnswers {
+    /// Answers used without prompting, based on what is found in `dir`
+    pub fn detected(dir: &Path) -> Self {
+        let (framework, source_dir) = match IacFramework::detect(dir) {
+            Some((framework, source_dir)) => (Some(framework), source_dir),
+            None => (None, PathBuf::new()),
+        };
+        let ci = if dir.join(".gitlab-ci.yml").exists() {
+            CiProvider::Gitlab
+        } else {
+            CiProvider::Github
+        };
+
+        Self {
+            framework,
+            source_dir,
+            detected: framework.into_iter().collect(),
+            monthly_budget: DEFAULT_MONTHLY_BUDGET,
+            teams: vec!["platform".to_string()],
+            region: "us-east-1".to_string(),
+            ci,
+        }
+    }
+
+    /// Answers based on a `repo-scanner` inventory of the repository in `dir`
+    pub fn from_inventory(dir: &Path, inventory: &IacInventory) -> Self {
+        let mut answers = Self::detected(dir);
+        let (framework, source_dir) = match inventory.primary() {
+            Some((framework, source_dir)) => (Some(framework), source_dir),
+            None => (None, PathBuf::new()),
+        };
+        answers.framework = framework;
+        answers.source_dir = source_dir;
+        answers.detected = inventory.frameworks();
+        answers
+    }
+
+    /// Whether Kubernetes workloads are among the detected sources
+    fn has_kubernetes(&self) -> bool {
+        self.framework == Some(IacFramework::Kubernetes)
+            || self.detected.contains(&IacFramework::Kubernetes)
+    }
+
+    fn owner(&self) -> String {
+        self.teams.join(", ")
+    }
+}
+
+/// Source of answers to wizard questions
+pub trait Prompt {
+    /// Ask `question`, returning the trimmed answer or `default` when empty
+    fn ask(&mut self, question: &str, default: &str) -> Result<String, String>;
+}
+
+/// Prompt on stdout, reading answers from stdin
+pub struct StdinPrompt;
+
+impl Prompt for StdinPrompt {
+    fn ask(&mut self, question: &str, default: &str) -> Result<String, String> {
+        print!("{} [{}]: ", question.bold(), default.dimmed());
+        std::io::stdout()
+            .flush()
+            .map_err(|e| format!("Failed to write prompt: {}", e))?;
+
+        let mut line = String::new();
+        std::io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .map_err(|e| format!("Failed to read answer: {}", e))?;
+        let answer = line.trim();
+        Ok(if answer.is_empty() {
+            default.to_string()
+        } else {
+            answer.to_string()
+        })
+    }
+}
+
+/// Ask for each answer, offering `defaults`
+pub fn ask_answers(
+    prompt: &mut dyn Prompt,
+    defaults: WizardAnswers,
+) -> Result<WizardAnswers, String> {
+    let framework_default = defaults.framework.map_or("none", |f| f.name());
+    let framework = ask_until(
+        prompt,
+        "IaC framework (terraform, cdk, cloudformation, sam, serverless, kubernetes, none)",
+        framework_default,
+        |answer| match answer.to_lowercase().as_str() {
+            "none" => Some(None),
+            other => IacFramework::parse(other).map(Some),
+        },
+    )?;
+
+    let budget_default = format!("{}", defaults.monthly_budget);
+    let monthly_budget = ask_until(prompt, "Monthly budget in USD", &budget_default, |answer| {
+        answer
+            .trim_start_matches('$')
+            .replace(',', "")
+            .parse::<f64>()
+            .ok()
+            .filter(|b| b.is_finite() && *b > 0.0)
+    })?;
+
+    let teams = ask_until(
+        prompt,
+        "Owning teams (comma-separated)",
+        &defaults.teams.join(","),
+        |answer| {
+            let teams: Vec<String> = answer
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect();
+            (!teams.is_empty()).then_some(teams)
+        },
+    )?;
+
+    let region = prompt.ask("Default AWS region", &defaults.region)?;
+
+    let ci = ask_until(
+        prompt,
+        "CI provider (github, gitlab, none)",
+        defaults.ci.name(),
+        CiProvider::parse,
+    )?;
+
+    Ok(WizardAnswers {
+        framework,
+        source_dir: defaults.source_dir,
+        detected: defaults.detected,
+        monthly_budget,
+        teams,
+        region,
+        ci,
+    })
+}
+
+fn ask_until<T>(
+    prompt: &mut dyn Prompt,
+    question: &str,
+    default: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<T, String> {
+    for _ in 0..MAX_ATTEMPTS {
+        let answer = prompt.ask(question, default)?;
+        match parse(&answer) {
+            Some(value) => return Ok(value),
+            None => println!("  {} '{}' is not a valid answer", "✗".red(), answer),
+        }
+    }
+    Err(format!("No valid answer for '{}'", question))
+}
+
+/// Run the wizard in `dir`, prompting unless `interactive` is false
+pub fn run_wizard(
+    dir: &Path,
+    interactive: bool,
+    defaults: WizardAnswers,
+) -> Result<Vec<PathBuf>, String> {
+    println!("{}", "🧭 CostPilot setup wizard".bold().cyan());
+    match defaults.framework {
+        Some(framework) => println!(
+            "  {} Detected {} sources in {}",
+            "✓".green(),
+            framework.name(),
+            display_dir(&defaults.source_dir)
+        ),
+        None => println!("  {} No IaC sources detected", "→".yellow()),
+    }
+    let others: Vec<&str> = defaults
+        .detected
+        .iter()
+        .filter(|f| Some(**f) != defaults.framework)
+        .map(|f| f.name())
+        .collect();
+    if !others.is_empty() {
+        println!("  {} Also found {}", "✓".green(), others.join(", "));
+    }
+
+    let answers = if interactive {
+        ask_answers(&mut StdinPrompt, defaults)?
+    } else {
+        defaults
+    };
+
+    let written = scaffold(dir, &answers)?;
+
+    println!("\n{}", "✅ Project scaffolded".bold().green());
+    println!("\n{}", "Next steps:".bold());
+    println!("  1. Review costpilot.yaml and {}", STARTER_POLICY_PATH);
+    println!(
+        "  2. costpilot validate costpilot.yaml slo.yaml {}",
+        STARTER_POLICY_PATH
+    );
+    if answers.ci != CiProvider::None {
+        println!("  3. Commit the generated CI workflow");
+    }
+    Ok(written)
+}
+
+/// Write all scaffold files for `answers`, returning the paths written
+///
+/// Files that already exist are left untouched and not returned.
+pub fn scaffold(dir: &Path, answers: &WizardAnswers) -> Result<Vec<PathBuf>, String> {
+    let mut files = vec![
+        (dir.join("costpilot.yaml"), config_yaml(answers)),
+        (dir.join("baselines.json"), baselines_json(answers)?),
+        (dir.join("slo.yaml"), slo_yaml(answers)),
+        (dir.join(STARTER_POLICY_PATH), starter_policy(answers)),
+    ];
+    match answers.ci {
+        CiProvider::Github => files.push((
+            dir.join(".github/workflows/costpilot.yml"),
+            github_workflow(answers),
+        )),
+        CiProvider::Gitlab => files.push((
+            dir.join(".gitlab/costpilot.gitlab-ci.yml"),
+            gitlab_job(answers),
+        )),
+        CiProvider::None => {}
+    }
+
+    let mut written = Vec::new();
+    for (path, content) in files {
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                create_directory(parent)?;
+            }
+        }
+        if !path.exists() {
+            written.push(path.clone());
+        }
+        write_file(&path, &content)?;
+    }
+
+    if answers.ci == CiProvider::Gitlab {
+        println!(
+            "  {} Add `include: '.gitlab/costpilot.gitlab-ci.yml'` to .gitlab-ci.yml",
+            "→".yellow()
+        );
+    }
+    Ok(written)
+}
+
+fn config_yaml(answers: &WizardAnswers) -> String {
+    let infra_format = answers
+        .framework
+        .and_then(|f| f.infra_format())
+        .map(|format| format!("  infra_format: {}\n", format))
+        .unwrap_or_default();
+    let mut content = format!(
+        r#"# CostPilot configuration (generated by `costpilot init --wizard`)
+version: "1.0.0"
+default_region: {region}
+scan:
+{infra_format}  fail_on_critical: true
+  explain: true
+policies:
+  default: {policy}
+  directory: policies
+output:
+  format: text
+slo:
+  config: slo.yaml
+  snapshots_dir: .costpilot/snapshots
+"#,
+        region = answers.region,
+        policy = STARTER_POLICY_PATH,
+    );
+    if answers.ci == CiProvider::Github {
+        content.push_str("integrations:\n  github:\n    enabled: true\n    comment_on_pr: true\n");
+    }
+    if answers.has_kubernetes() {
+        content.push_str(
+            r#"# Cluster pricing for Kubernetes workloads; adjust to your node pools
+kubernetes:
+  node_pools:
+    - name: default
+      instance_type: m5.large
+      vcpu: 2
+      memory_gib: 8
+      hourly_cost: 0.096
+  target_utilization: 0.75
+"#,
+        );
+    }
+    content
+}
+
+fn baselines_json(answers: &WizardAnswers) -> Result<String, String> {
+    let baselines = serde_json::json!({
+        "version": "1.0",
+        "global": {
+            "name": "global",
+            "expected_monthly_cost": answers.monthly_budget,
+            "acceptable_variance_percent": 10.0,
+            "last_updated": chrono::Utc::now().to_rfc3339(),
+            "justification": "Initial budget agreed during costpilot init",
+            "owner": answers.owner(),
+        }
+    });
+    serde_json::to_string_pretty(&baselines)
+        .map(|json| json + "\n")
+        .map_err(|e| format!("Failed to serialize baselines: {}", e))
+}
+
+fn slo_yaml(answers: &WizardAnswers) -> String {
+    format!(
+        r#"# Cost SLOs (generated by `costpilot init --wizard`)
+monthly-budget:
+  id: monthly-budget
+  name: Monthly budget
+  description: Total monthly cost stays within the agreed budget
+  slo_type: monthly_budget
+  target: global
+  threshold:
+    max_value: {budget:.2}
+    warning_threshold_percent: 80.0
+    time_window: 30d
+  enforcement: warn
+  owner: "{owner}"
+  created_at: "{now}"
+cost-growth:
+  id: cost-growth
+  name: Monthly cost growth
+  description: Month-over-month cost growth stays below 20%
+  slo_type: cost_growth_rate
+  target: global
+  threshold:
+    max_value: 20.0
+    warning_threshold_percent: 75.0
+    time_window: 30d
+  enforcement: observe
+  owner: "{owner}"
+  created_at: "{now}"
+"#,
+        budget = answers.monthly_budget,
+        owner = answers.owner(),
+        now = chrono::Utc::now().to_rfc3339(),
+    )
+}
+
+fn starter_policy(answers: &WizardAnswers) -> String {
+    let owners: String = answers
+        .teams
+        .iter()
+        .map(|t| format!("    - \"{}\"\n", t))
+        .collect();
+    format!(
+        r#"# Starter policy pack (generated by `costpilot init --wizard`)
+#
+# `budgets` and `resources` are enforced by `costpilot scan --policy`;
+# `rules` use the policy DSL (`costpilot policy-dsl`).
+version: "1.0.0"
+metadata:
+  description: Starter cost guardrails
+  owners:
+{owners}budgets:
+  global:
+    monthly_limit: {budget:.2}
+    warning_threshold: 0.8
+resources:
+  nat_gateways:
+    max_count: 2
+  ec2_instances:
+    allowed_families: ["t3", "t4g", "m6i", "c6i"]
+  lambda_functions:
+    require_concurrency_limit: true
+enforcement:
+  mode: advisory
+  fail_on_violation: false
+rules:
+  - name: Monthly budget
+    description: Estimated monthly cost exceeds the agreed budget
+    severity: high
+    conditions:
+      - condition_type:
+          type: monthly_cost
+        operator: greater_than
+        value: {budget:.2}
+    action:
+      type: warn
+      message: "Monthly cost is above the ${budget:.2} budget"
+  - name: Cost increase
+    description: A change raises monthly cost by more than 20%
+    severity: medium
+    conditions:
+      - condition_type:
+          type: cost_increase
+        operator: greater_than
+        value: 20.0
+    action:
+      type: require_approval
+      approvers: [{approvers}]
+      message: "Cost increase above 20% needs approval"
+  - name: NAT gateway count
+    description: Each NAT gateway adds a fixed hourly charge
+    severity: medium
+    conditions:
+      - condition_type:
+          type: resource_count
+          resource_type: aws_nat_gateway
+        operator: greater_than
+        value: 2
+    action:
+      type: warn
+      message: "More than two NAT gateways in this plan"
+"#,
+        owners = owners,
+        budget = answers.monthly_budget,
+        approvers = answers
+            .teams
+            .iter()
+            .map(|t| format!("\"{}\"", t))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Shell steps that produce the input for `costpilot` in CI
+fn ci_steps(answers: &WizardAnswers) -> (Vec<String>, String) {
+    let dir = display_dir(&answers.source_dir);
+    // The parser comes from `scan.infra_format` in costpilot.yaml
+    let scan_file = |input: &str| {
+        format!(
+            "costpilot scan --plan {} --policy {} --baselines baselines.json --fail-on-increase 10%",
+            input, STARTER_POLICY_PATH
+        )
+    };
+    let scan = scan_file(&format!("{}/plan.json", dir));
+    match answers.framework {
+        Some(IacFramework::Cdk) => (
+            vec!["npm ci".to_string(), "npx cdk synth".to_string()],
+            format!(
+                "costpilot watch cdk.out --once --policy {}",
+                STARTER_POLICY_PATH
+            ),
+        ),
+        Some(IacFramework::CloudFormation) => (
+            vec![
+                "# costpilot scan reads Terraform plans; add a plan export for your templates"
+                    .to_string(),
+            ],
+            scan,
+        ),
+        Some(IacFramework::Sam) => (Vec::new(), scan_file(&format!("{}/template.yaml", dir))),
+        Some(IacFramework::Serverless) => {
+            (Vec::new(), scan_file(&format!("{}/serverless.yml", dir)))
+        }
+        Some(IacFramework::Kubernetes) => (
+            vec![format!(
+                "for f in {}/*.y*ml; do cat \"$f\"; echo '---'; done > manifests.yaml",
+                dir
+            )],
+            scan_file("manifests.yaml"),
+        ),
+        _ => (
+            vec![
+                format!("terraform -chdir={} init -input=false", dir),
+                format!(
+                    "terraform -chdir={} plan -input=false -out=plan.tfplan",
+                    dir
+                ),
+                format!(
+                    "terraform -chdir={0} show -json plan.tfplan > {0}/plan.json",
+                    dir
+                ),
+            ],
+            scan,
+        ),
+    }
+}
+
+fn github_workflow(answers: &WizardAnswers) -> String {
+    let (steps, scan) = ci_steps(answers);
+    let setup = match answers.framework {
+        Some(IacFramework::Cdk) => "      - uses: actions/setup-node@v4\n",
+        Some(IacFramework::Terraform) | None => "      - uses: hashicorp/setup-terraform@v3\n",
+        _ => "",
+    };
+    let prepare = if steps.is_empty() {
+        String::new()
+    } else {
+        let lines: String = steps.iter().map(|s| format!("          {}\n", s)).collect();
+        format!(
+            "      - name: Prepare infrastructure input\n        run: |\n{}",
+            lines
+        )
+    };
+    format!(
+        r#"name: CostPilot
+
+on:
+  pull_request:
+
+permissions:
+  contents: read
+  pull-requests: write
+
+jobs:
+  cost:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+{setup}{prepare}      - name: Install CostPilot
+        run: curl -fsSL https://costpilot.dev/install.sh | bash
+      - name: CostPilot
+        run: {scan}
+"#
+    )
+}
+
+fn gitlab_job(answers: &WizardAnswers) -> String {
+    let (steps, scan) = ci_steps(answers);
+    let image = match answers.framework {
+        Some(IacFramework::Cdk) => "node:20",
+        Some(IacFramework::Sam | IacFramework::Serverless | IacFramework::Kubernetes) => {
+            "buildpack-deps:bookworm-curl"
+        }
+        _ => "hashicorp/terraform:1.6",
+    };
+    let steps: String = steps.iter().map(|s| format!("    - {}\n", s)).collect();
+    format!(
+        r#"costpilot:
+  stage: test
+  image:
+    name: {image}
+    entrypoint: [""]
+  script:
+{steps}    - curl -fsSL https://costpilot.dev/install.sh | bash
+    - {scan}
+  rules:
+    - if: $CI_PIPELINE_SOURCE == "merge_request_event"
+"#
+    )
+}
+
+fn display_dir(dir: &Path) -> String {
+    if dir.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        dir.display().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct Scripted(Vec<&'static str>);
+
+    impl Prompt for Scripted {
+        fn ask(&mut self, _question: &str, default: &str) -> Result<String, String> {
+            let answer = self.0.remove(0);
+            Ok(if answer.is_empty() {
+                default.to_string()
+            } else {
+                answer.to_string()
+            })
+        }
+    }
+
+    #[test]
+    fn test_detects_framework_and_source_dir() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(IacFramework::detect(dir.path()), None);
+
+        std::fs::create_dir_all(dir.path().join("infra/prod")).unwrap();
+        std::fs::write(dir.path().join("infra/prod/main.tf"), "").unwrap();
+        assert_eq!(
+            IacFramework::detect(dir.path()),
+            Some((IacFramework::Terraform, PathBuf::from("infra/prod")))
+        );
+
+        std::fs::write(dir.path().join("cdk.json"), "{}").unwrap();
+        assert_eq!(
+            IacFramework::detect(dir.path()).unwrap().0,
+            IacFramework::Cdk
+        );
+    }
+
+    #[test]
+    fn test_wizard_reprompts_invalid_answers() {
+        let dir = TempDir::new().unwrap();
+        let defaults = WizardAnswers::detected(dir.path());
+        let mut prompt = Scripted(vec!["", "lots", "$2,500", "payments, search", "", "gitlab"]);
+
+        let answers = ask_answers(&mut prompt, defaults).unwrap();
+        assert_eq!(answers.framework, None);
+        assert_eq!(answers.monthly_budget, 2500.0);
+        assert_eq!(answers.teams, vec!["payments", "search"]);
+        assert_eq!(answers.region, "us-east-1");
+        assert_eq!(answers.ci, CiProvider::Gitlab);
+
+        let mut prompt = Scripted(vec!["pulumi", "pulumi", "pulumi"]);
+        assert!(ask_answers(&mut prompt, WizardAnswers::detected(dir.path())).is_err());
+    }
+
+    #[test]
+    fn test_scaffolded_files_validate() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("main.tf"), "").unwrap();
+        let mut answers = WizardAnswers::detected(dir.path());
+        answers.teams = vec!["payments".to_string(), "search".to_string()];
+
+        let written = scaffold(dir.path(), &answers).unwrap();
+        assert_eq!(written.len(), 5);
+        for file in [
+            "costpilot.yaml",
+            "baselines.json",
+            "slo.yaml",
+            STARTER_POLICY_PATH,
+        ] {
+            let report = crate::validation::validate_file(dir.path().join(file)).unwrap();
+            assert!(report.errors.is_empty(), "{}: {:?}", file, report.errors);
+            assert!(
+                report.warnings.is_empty(),
+                "{}: {:?}",
+                file,
+                report.warnings
+            );
+        }
+
+        let policy = dir.path().join(STARTER_POLICY_PATH);
+        let config = crate::engines::policy::PolicyLoader::load_from_file(&policy).unwrap();
+        assert_eq!(config.budgets.global.unwrap().monthly_limit, 1000.0);
+        let rules = crate::engines::policy::parser::PolicyRuleLoader::new().load_from_file(&policy);
+        assert!(rules.is_ok(), "{:?}", rules.err());
+
+        // Re-running leaves existing files alone
+        assert!(scaffold(dir.path(), &answers).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_inventory_configures_parser_and_cluster_pricing() {
+        let dir = TempDir::new().unwrap();
+        let inventory = IacInventory::from_json(
+            r#"{"schema_version": 1, "frameworks": {
+                "kubernetes": {"files": 3, "resources": 12, "directories": {"deploy/base": 3}},
+                "terraform": {"files": 1, "resources": 2, "directories": {"infra": 1}}
+            }}"#,
+        )
+        .unwrap();
+
+        let answers = WizardAnswers::from_inventory(dir.path(), &inventory);
+        assert_eq!(answers.framework, Some(IacFramework::Kubernetes));
+        assert_eq!(answers.source_dir, PathBuf::from("deploy/base"));
+        assert_eq!(
+            answers.detected,
+            vec![IacFramework::Kubernetes, IacFramework::Terraform]
+        );
+
+        scaffold(dir.path(), &answers).unwrap();
+        let config_path = dir.path().join("costpilot.yaml");
+        let report = crate::validation::validate_file(&config_path).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+
+        let config = std::fs::read_to_string(&config_path).unwrap();
+        assert!(config.contains("infra_format: kubernetes"));
+        let pricing = crate::engines::prediction::ClusterPricing::load(&config_path, None).unwrap();
+        assert_eq!(pricing.node_pools[0].name, "default");
+
+        let workflow =
+            std::fs::read_to_string(dir.path().join(".github/workflows/costpilot.yml")).unwrap();
+        assert!(workflow.contains("deploy/base/*.y*ml"));
+        assert!(workflow.contains("--plan manifests.yaml"));
+    }
+}
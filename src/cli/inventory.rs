@@ -0,0 +1,128 @@
+// IaC inventory - read the report written by `repo-scanner --inventory`
+//
+// The scanner summarizes Terraform, CDK, CloudFormation, SAM, Serverless and
+// Kubernetes sources per framework. `costpilot init --wizard` uses it instead
+// of its own shallow detection to choose the framework, source directory and
+// scan parser, and to enable engines such as Kubernetes cluster pricing.
+
+use crate::cli::init_wizard::IacFramework;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Inventory read by `costpilot init --wizard` when present
+pub const DEFAULT_INVENTORY: &str = ".costpilot/inventory.json";
+
+/// Newest inventory format understood
+pub const INVENTORY_SCHEMA_VERSION: u32 = 1;
+
+/// Per-framework totals
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct FrameworkSummary {
+    pub files: usize,
+    pub resources: usize,
+    #[serde(default)]
+    pub modules: usize,
+    /// IaC files per directory (`.` for the repository root)
+    #[serde(default)]
+    pub directories: BTreeMap<String, usize>,
+}
+
+/// Repository IaC inventory produced by `repo-scanner --inventory`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct IacInventory {
+    pub schema_version: u32,
+    pub frameworks: BTreeMap<String, FrameworkSummary>,
+    /// Files referencing each provider
+    #[serde(default)]
+    pub providers: BTreeMap<String, usize>,
+}
+
+impl IacInventory {
+    /// Load an inventory file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read inventory {}: {}", path.display(), e))?;
+        Self::from_json(&content)
+            .map_err(|e| format!("Invalid inventory {}: {}", path.display(), e))
+    }
+
+    /// Parse inventory JSON
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let inventory: Self = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        if inventory.schema_version > INVENTORY_SCHEMA_VERSION {
+            return Err(format!(
+                "schema version {} is newer than supported version {}",
+                inventory.schema_version, INVENTORY_SCHEMA_VERSION
+            ));
+        }
+        Ok(inventory)
+    }
+
+    /// Known frameworks, largest first (by resources, then files)
+    pub fn frameworks(&self) -> Vec<IacFramework> {
+        let mut found: Vec<(IacFramework, &FrameworkSummary)> = self
+            .frameworks
+            .iter()
+            .filter_map(|(name, summary)| Some((IacFramework::parse(name)?, summary)))
+            .collect();
+        found.sort_by_key(|(_, summary)| std::cmp::Reverse((summary.resources, summary.files)));
+        found.into_iter().map(|(framework, _)| framework).collect()
+    }
+
+    /// Main framework with the directory holding most of its files
+    pub fn primary(&self) -> Option<(IacFramework, PathBuf)> {
+        let framework = *self.frameworks().first()?;
+        let summary = &self.frameworks[framework.name()];
+        let directory = summary
+            .directories
+            .iter()
+            .max_by(|(a_dir, a), (b_dir, b)| a.cmp(b).then_with(|| b_dir.cmp(a_dir)))
+            .map(|(dir, _)| dir.as_str())
+            .filter(|dir| *dir != ".")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        Some((framework, directory))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INVENTORY: &str = r#"{
+        "schema_version": 1,
+        "frameworks": {
+            "cdk": {"files": 1, "resources": 0, "modules": 0, "directories": {".": 1}},
+            "kubernetes": {"files": 4, "resources": 9, "modules": 0, "directories": {"deploy": 4}},
+            "terraform": {"files": 6, "resources": 40, "modules": 2,
+                          "directories": {"infra/modules/vpc": 2, "infra/prod": 3, "infra/staging": 3}},
+            "pulumi": {"files": 2, "resources": 100}
+        },
+        "providers": {"aws": 7, "kubernetes": 4}
+    }"#;
+
+    #[test]
+    fn test_primary_framework_and_directory() {
+        let inventory = IacInventory::from_json(INVENTORY).unwrap();
+        assert_eq!(
+            inventory.frameworks(),
+            vec![
+                IacFramework::Terraform,
+                IacFramework::Kubernetes,
+                IacFramework::Cdk
+            ]
+        );
+        assert_eq!(
+            inventory.primary(),
+            Some((IacFramework::Terraform, PathBuf::from("infra/prod")))
+        );
+    }
+
+    #[test]
+    fn test_rejects_newer_schema() {
+        let newer = INVENTORY.replace("\"schema_version\": 1", "\"schema_version\": 2");
+        assert!(IacInventory::from_json(&newer).is_err());
+        assert!(IacInventory::from_json("{}").is_err());
+    }
+}
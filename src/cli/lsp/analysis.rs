@@ -0,0 +1,513 @@
+// Editor features for CostPilot files: diagnostics, completion and hover
+//
+// Completion and hover work on the raw buffer text and its indentation, so
+// they keep working while a document is mid-edit and does not parse.
+// Positions are 0-based, as in LSP.
+
+use crate::engines::policy::parser::Operator;
+use crate::heuristics::FreeHeuristics;
+use crate::validation::{validate_content, FileType, SourceMap};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, Documentation, Hover,
+    HoverContents, MarkupContent, MarkupKind, NumberOrString, Position, Range,
+};
+use std::path::Path;
+
+/// Diagnostic source shown by editors
+pub const DIAGNOSTIC_SOURCE: &str = "costpilot";
+
+const BOOLEAN: &[&str] = &["true", "false"];
+
+/// costpilot.yaml keys: path, suggested values, hover documentation
+const CONFIG_KEYS: &[(&str, &[&str], &str)] = &[
+    ("version", &[], "Config file version (semantic version, e.g. `1.0.0`)."),
+    ("default_region", &[], "AWS region used when a resource does not set one."),
+    ("scan", &[], "Defaults for `costpilot scan`."),
+    ("scan.fail_on_critical", BOOLEAN, "Fail the scan when a critical detection is found."),
+    ("scan.show_autofix", BOOLEAN, "Show autofix suggestions in scan output."),
+    ("scan.explain", BOOLEAN, "Include cost explanations in scan output."),
+    ("scan.infra_format", &["terraform", "kubernetes", "sam", "serverless"], "Parser used when `--infra-format` is not given."),
+    ("policies", &[], "Policy files used by scans."),
+    ("policies.default", &[], "Policy file applied when `--policy` is not given."),
+    ("policies.exemptions", &[], "Exemptions file applied with the default policy."),
+    ("policies.directory", &[], "Directory containing policy files."),
+    ("output", &[], "Output defaults."),
+    ("output.format", &["json", "text", "markdown"], "Default output format."),
+    ("output.verbose", BOOLEAN, "Enable verbose output."),
+    ("output.color", BOOLEAN, "Colorize terminal output."),
+    ("heuristics", &[], "Cost heuristics source."),
+    ("heuristics.auto_update", BOOLEAN, "Refresh heuristics automatically."),
+    ("heuristics.cache_ttl", &[], "How long cached heuristics stay valid (e.g. `24h`, `30m`, `1d`)."),
+    ("heuristics.file", &[], "Path to a custom heuristics file."),
+    ("slo", &[], "Cost SLO settings."),
+    ("slo.config", &[], "Path to the SLO definitions file."),
+    ("slo.snapshots_dir", &[], "Directory holding cost snapshots for burn-rate analysis."),
+    ("integrations", &[], "Third-party integrations."),
+    ("integrations.github", &[], "GitHub integration."),
+    ("integrations.github.enabled", BOOLEAN, "Enable the GitHub integration (required)."),
+    ("integrations.github.comment_on_pr", BOOLEAN, "Post scan results as a pull request comment."),
+    ("performance", &[], "Performance budgets."),
+    ("performance.adaptive_budgets", &[], "Adaptive performance budgets with floors and ceilings."),
+    ("detection", &[], "Detection settings."),
+    ("detection.custom_rules", &[], "User-defined detection rules: `id`, `resource_type` (`*` wildcards), attribute `conditions`, `severity` and `message`."),
+    ("kubernetes", &[], "Cluster pricing for `scan --infra-format kubernetes`."),
+    ("kubernetes.node_pools", &[], "Node pools with `name`, `vcpu`, `memory_gib`, `hourly_cost` and the node `labels` pods select with nodeSelector. The first pool takes pods without a selector."),
+    ("kubernetes.daemonset_nodes", &[], "Number of nodes each DaemonSet runs on (default 3)."),
+    ("kubernetes.target_utilization", &[], "Fraction of node capacity workloads fill, in (0, 1] (default 0.75)."),
+    ("kubernetes.default_cpu_request", &[], "CPU cores assumed for containers without requests or limits (default 0.1)."),
+    ("kubernetes.default_memory_request_gib", &[], "Memory in GiB assumed for containers without requests or limits (default 0.125)."),
+    ("zero_cost", &[], "Per-environment resource rules checked by `costpilot scan`."),
+    ("zero_cost.default_environment", &[], "Environment of resources whose tags and address do not name one."),
+    ("zero_cost.environments", &[], "Rules per environment: `allow`, `deny` and `free_tier` resource patterns (a type with `*` wildcards, or `{type, <attribute>: values}`), `max_monthly_cost`, and `enforcement` (`block` or `warn`)."),
+    ("tagging", &[], "Tag compliance rules checked by `costpilot scan`."),
+    ("tagging.enforcement", &["block", "warn"], "`block` fails the scan on tag violations; `warn` only reports them (default block)."),
+    ("tagging.rules", &[], "Rules per `resource_type` (`*` wildcards): `required` tag keys, `allowed_values` per tag, regex `formats` per tag, and `severity`."),
+    ("environment_mapping", &[], "How resource environments are inferred for grouping and `zero_cost` rules."),
+    ("environment_mapping.precedence", &[], "Sources consulted in order, first match wins (default `[tag, workspace, directory, address]`)."),
+    ("environment_mapping.tag_keys", &[], "Tag keys naming the environment, checked in order."),
+    ("environment_mapping.workspaces", &[], "`{pattern, environment}` rules matched against the Terraform workspace (`TF_WORKSPACE` or `.terraform/environment`)."),
+    ("environment_mapping.directories", &[], "`{pattern, environment}` rules matched against the directory of the plan file."),
+    ("environment_mapping.min_confidence", &[], "Environments inferred with lower confidence are reported as guesses (default 0.7)."),
+    ("free_tier", &[], "AWS free tier assumptions for Lambda and DynamoDB estimates."),
+    ("free_tier.enabled", BOOLEAN, "Subtract the account-wide free tier from eligible Lambda functions and DynamoDB tables and flag settings outside it (default true; `scan --no-free-tier` overrides)."),
+    ("policy_sources", &[], "Rule packs from other checked-out directories, e.g. a git submodule: `{path, name, required}` entries searched after `.costpilot/policies` in the order listed; earlier packs win rule name conflicts."),
+    ("policy_approvals", &[], "Trusted keyring for `costpilot policy submit`/`approve`."),
+    ("policy_approvals.min_approvals", &[], "Fewest approvals any policy review may require (default 1); `--min-approvals` cannot go lower."),
+    ("policy_approvals.approvers", &[], "Public key file of each approver by name; approvals must be signed with that key."),
+    ("feature_flags", &[], "Feature flag settings by name: `enabled`, `rollout_percentage` (0.0-1.0, bucketed per repository), `allowlist`/`blocklist` of `owner/name` repositories, `description` and the edition capability a flag `requires`. `COSTPILOT_FLAGS` overrides them."),
+    ("storage", &[], "Local snapshot and usage log storage."),
+    ("storage.encrypt_at_rest", BOOLEAN, "Encrypt new trend snapshots and usage log entries with a key derived from the installed license (default false; `COSTPILOT_ENCRYPT_AT_REST` overrides). `costpilot storage encrypt` migrates existing files."),
+    ("redaction", &[], "How `--redact` anonymizes exported reports."),
+    ("redaction.sensitive_tags", &[], "Tag key patterns (`*` wildcards, case-insensitive) dropped from redacted reports; replaces the default list."),
+    ("redaction.identifier_attributes", &[], "Resource config attribute patterns whose values are hashed in redacted reports; replaces the default list."),
+    ("redaction.salt", &[], "Salt mixed into redaction hashes so hashed names cannot be matched across organizations."),
+    ("locale", &["en", "de", "ja"], "Language of upgrade messages, validator output and report headings; `--locale` or `COSTPILOT_LOCALE` take precedence."),
+    ("profiles", &[], "Named overlays deep-merged over this file, selected with `--profile` or `COSTPILOT_PROFILE`."),
+];
+
+const POLICY_ROOT_KEYS: &[&str] = &["metadata", "rules", "exemptions"];
+const RULE_KEYS: &[&str] = &[
+    "name",
+    "description",
+    "enabled",
+    "severity",
+    "conditions",
+    "action",
+];
+const CONDITION_KEYS: &[&str] = &["condition_type", "operator", "value", "negate"];
+const CONDITION_TYPES: &[&str] = &[
+    "resource_type",
+    "resource_attribute",
+    "monthly_cost",
+    "cost_increase",
+    "module_path",
+    "tag",
+    "resource_count",
+    "expression",
+];
+const ACTION_TYPES: &[&str] = &[
+    "block",
+    "warn",
+    "require_approval",
+    "set_budget",
+    "tag_resource",
+];
+const SEVERITIES: &[&str] = &["critical", "high", "medium", "low", "info"];
+
+/// Validation problems in `text`, reported as if it were saved at `path`
+pub fn diagnostics(path: &Path, text: &str) -> Vec<Diagnostic> {
+    let Ok(report) = validate_content(path, text) else {
+        return Vec::new();
+    };
+    let source_map = SourceMap::new(text);
+    let lines: Vec<&str> = text.lines().collect();
+
+    let errors = report.errors.iter().map(|error| {
+        let mut message = error.message.clone();
+        if let Some(hint) = &error.hint {
+            message.push_str(&format!("\nHint: {}", hint));
+        }
+        let location = error.line.map(|line| (line, error.column.unwrap_or(1)));
+        (
+            DiagnosticSeverity::ERROR,
+            error.error_code.clone(),
+            location,
+            message,
+        )
+    });
+    let warnings = report.warnings.iter().map(|warning| {
+        let mut message = warning.message.clone();
+        if let Some(suggestion) = &warning.suggestion {
+            message.push_str(&format!("\nSuggestion: {}", suggestion));
+        }
+        let location = warning
+            .field
+            .as_deref()
+            .and_then(|f| source_map.locate(f))
+            .map(|l| (l.line, l.column));
+        (
+            DiagnosticSeverity::WARNING,
+            warning.warning_code.clone(),
+            location,
+            message,
+        )
+    });
+
+    errors
+        .chain(warnings)
+        .map(|(severity, code, location, message)| Diagnostic {
+            range: location
+                .map(|(line, column)| {
+                    line_range(&lines, line.saturating_sub(1), column.saturating_sub(1))
+                })
+                .unwrap_or_default(),
+            severity: Some(severity),
+            code: code.map(NumberOrString::String),
+            source: Some(DIAGNOSTIC_SOURCE.to_string()),
+            message,
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Completion items at `position`
+pub fn completions(kind: FileType, text: &str, position: Position) -> Vec<CompletionItem> {
+    let lines: Vec<&str> = text.lines().collect();
+    let line = position.line as usize;
+    let current = lines.get(line).copied().unwrap_or("");
+    let prefix: String = current.chars().take(position.character as usize).collect();
+    let parents = parent_keys(&lines, line, indent_of(&prefix));
+
+    match (kind, key_of(&prefix)) {
+        (FileType::Config, Some(key)) => {
+            let path = join(&config_section(&parents), key);
+            CONFIG_KEYS
+                .iter()
+                .find(|(p, _, _)| *p == path)
+                .map(|(_, values, _)| items(values, CompletionItemKind::VALUE, None))
+                .unwrap_or_default()
+        }
+        (FileType::Config, None) => {
+            if parents.len() == 1 && parents[0] == crate::config::PROFILES_KEY {
+                return Vec::new();
+            }
+            let in_profile =
+                parents.first().map(String::as_str) == Some(crate::config::PROFILES_KEY);
+            let section = config_section(&parents);
+            CONFIG_KEYS
+                .iter()
+                .filter(|(path, _, _)| parent_of(path) == section)
+                .filter(|(path, _, _)| !(in_profile && *path == crate::config::PROFILES_KEY))
+                .map(|(path, _, doc)| CompletionItem {
+                    label: path.rsplit('.').next().unwrap_or(path).to_string(),
+                    kind: Some(CompletionItemKind::PROPERTY),
+                    documentation: Some(Documentation::String(doc.to_string())),
+                    ..Default::default()
+                })
+                .collect()
+        }
+        (FileType::Policy, Some(key)) => {
+            let parent = parents.last().map(String::as_str);
+            match (key, parent) {
+                ("operator", _) => operator_items(),
+                ("type", Some("condition_type")) => {
+                    items(CONDITION_TYPES, CompletionItemKind::ENUM_MEMBER, None)
+                }
+                ("type", Some("action")) => {
+                    items(ACTION_TYPES, CompletionItemKind::ENUM_MEMBER, None)
+                }
+                ("severity", _) => items(SEVERITIES, CompletionItemKind::ENUM_MEMBER, None),
+                ("resource_type", _) => resource_type_items(),
+                ("value", _) if condition_checks_resource_type(&lines, line) => {
+                    resource_type_items()
+                }
+                _ => Vec::new(),
+            }
+        }
+        (FileType::Policy, None) => {
+            let keys = match parents.last().map(String::as_str) {
+                None => POLICY_ROOT_KEYS,
+                Some("rules") => RULE_KEYS,
+                Some("conditions") => CONDITION_KEYS,
+                _ => &[],
+            };
+            items(keys, CompletionItemKind::PROPERTY, None)
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Documentation for the costpilot.yaml key under `position`
+pub fn hover(kind: FileType, text: &str, position: Position) -> Option<Hover> {
+    if kind != FileType::Config {
+        return None;
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let line = position.line as usize;
+    let current = lines.get(line)?;
+    let key = key_of(current)?;
+
+    let start = current.find(key)?;
+    let character = position.character as usize;
+    if character < start || character > start + key.len() {
+        return None;
+    }
+
+    let parents = parent_keys(&lines, line, indent_of(current));
+    let path = join(&config_section(&parents), key);
+    let (_, _, doc) = CONFIG_KEYS.iter().find(|(p, _, _)| *p == path)?;
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("**`{}`**\n\n{}", path, doc),
+        }),
+        range: Some(Range::new(
+            Position::new(line as u32, start as u32),
+            Position::new(line as u32, (start + key.len()) as u32),
+        )),
+    })
+}
+
+fn operator_items() -> Vec<CompletionItem> {
+    [
+        (Operator::Equals, "equals", "Value equals"),
+        (Operator::NotEquals, "not_equals", "Value differs"),
+        (Operator::GreaterThan, "greater_than", "Numeric >"),
+        (
+            Operator::GreaterThanOrEqual,
+            "greater_than_or_equal",
+            "Numeric >=",
+        ),
+        (Operator::LessThan, "less_than", "Numeric <"),
+        (
+            Operator::LessThanOrEqual,
+            "less_than_or_equal",
+            "Numeric <=",
+        ),
+        (Operator::Contains, "contains", "Substring match"),
+        (Operator::StartsWith, "starts_with", "Prefix match"),
+        (Operator::EndsWith, "ends_with", "Suffix match"),
+        (Operator::Matches, "matches", "Regular expression match"),
+        (Operator::In, "in", "Value is in the list"),
+        (Operator::NotIn, "not_in", "Value is not in the list"),
+    ]
+    .into_iter()
+    .map(|(operator, label, detail)| {
+        debug_assert_eq!(
+            serde_json::to_value(operator).ok(),
+            Some(serde_json::Value::String(label.to_string()))
+        );
+        CompletionItem {
+            label: label.to_string(),
+            kind: Some(CompletionItemKind::OPERATOR),
+            detail: Some(detail.to_string()),
+            ..Default::default()
+        }
+    })
+    .collect()
+}
+
+fn resource_type_items() -> Vec<CompletionItem> {
+    let heuristics = FreeHeuristics::load_free_heuristics();
+    let types: Vec<&str> = heuristics
+        .rules
+        .iter()
+        .map(|r| r.resource_type.as_str())
+        .filter(|t| !t.starts_with('_'))
+        .collect();
+    items(
+        &types,
+        CompletionItemKind::CLASS,
+        Some("Terraform resource type"),
+    )
+}
+
+fn items(labels: &[&str], kind: CompletionItemKind, detail: Option<&str>) -> Vec<CompletionItem> {
+    labels
+        .iter()
+        .map(|label| CompletionItem {
+            label: label.to_string(),
+            kind: Some(kind),
+            detail: detail.map(str::to_string),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Whether the condition list item around `line` has `type: resource_type`
+fn condition_checks_resource_type(lines: &[&str], line: usize) -> bool {
+    let indent = lines.get(line).map(|l| indent_of(l)).unwrap_or(0);
+    let is_item_start = |l: &str| l.trim_start().starts_with("- ") && indent_of(l) == indent;
+    let in_item = |l: &str| l.trim().is_empty() || indent_of(l) >= indent;
+
+    let mut start = line;
+    while start > 0 && !is_item_start(lines[start]) && in_item(lines[start - 1]) {
+        start -= 1;
+    }
+    let end = lines[line + 1..]
+        .iter()
+        .position(|l| !in_item(l) || is_item_start(l))
+        .map_or(lines.len(), |offset| line + 1 + offset);
+
+    lines[start..end]
+        .iter()
+        .any(|l| l.trim() == "type: resource_type")
+}
+
+/// Keys enclosing a line indented by `indent`, outermost first
+fn parent_keys(lines: &[&str], line: usize, indent: usize) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut limit = indent;
+    for text in lines[..line.min(lines.len())].iter().rev() {
+        if limit == 0 {
+            break;
+        }
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let text_indent = indent_of(text);
+        if text_indent < limit {
+            if let Some(key) = key_of(text) {
+                keys.push(key.to_string());
+            }
+            limit = text_indent;
+        }
+    }
+    keys.reverse();
+    keys
+}
+
+/// Column where a line's key starts, skipping list dashes
+fn indent_of(line: &str) -> usize {
+    let trimmed = line.trim_start();
+    let mut indent = line.len() - trimmed.len();
+    if let Some(rest) = trimmed.strip_prefix("- ") {
+        indent += 2 + rest.len() - rest.trim_start().len();
+    }
+    indent
+}
+
+/// Mapping key on a line, if it has one
+fn key_of(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let trimmed = trimmed.strip_prefix("- ").unwrap_or(trimmed).trim_start();
+    if trimmed.starts_with('#') {
+        return None;
+    }
+    let (key, _) = trimmed.split_once(':')?;
+    let key = key.trim();
+    (!key.is_empty() && !key.contains(char::is_whitespace)).then_some(key)
+}
+
+/// Config section path for `parents`, with any `profiles.<name>` prefix removed
+fn config_section(parents: &[String]) -> String {
+    let parents = match parents {
+        [profiles, _, rest @ ..] if profiles == crate::config::PROFILES_KEY => rest,
+        _ => parents,
+    };
+    parents.join(".")
+}
+
+fn parent_of(path: &str) -> &str {
+    path.rsplit_once('.').map_or("", |(parent, _)| parent)
+}
+
+fn join(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+fn line_range(lines: &[&str], line: usize, column: usize) -> Range {
+    let end = lines
+        .get(line)
+        .map_or(column, |l| l.chars().count().max(column));
+    Range::new(
+        Position::new(line as u32, column as u32),
+        Position::new(line as u32, end as u32),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        items.iter().map(|i| i.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_diagnostics_point_at_invalid_value() {
+        let text = "version: 1.0.0\noutput:\n  format: html\n";
+        let diagnostics = diagnostics(Path::new("costpilot.yaml"), text);
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(diagnostic.code, Some(NumberOrString::String("E102".into())));
+        assert_eq!(diagnostic.range.start, Position::new(2, 2));
+        assert_eq!(diagnostic.range.end, Position::new(2, 14));
+    }
+
+    #[test]
+    fn test_policy_completions() {
+        let text = "\
+rules:
+  - name: nat
+    conditions:
+      - condition_type:
+          type: resource_type
+        operator:
+        value:
+    action:
+      type:
+";
+        let operators = completions(FileType::Policy, text, Position::new(5, 18));
+        assert!(labels(&operators).contains(&"greater_than"));
+
+        let resources = completions(FileType::Policy, text, Position::new(6, 15));
+        assert!(labels(&resources).contains(&"aws_nat_gateway"));
+        assert!(!labels(&resources).contains(&"_default"));
+
+        let actions = completions(FileType::Policy, text, Position::new(8, 12));
+        assert_eq!(labels(&actions), ACTION_TYPES);
+
+        let condition_types = completions(FileType::Policy, text, Position::new(4, 16));
+        assert!(labels(&condition_types).contains(&"monthly_cost"));
+
+        let rule_keys = completions(
+            FileType::Policy,
+            "rules:\n  - name: x\n    \n",
+            Position::new(2, 4),
+        );
+        assert_eq!(labels(&rule_keys), RULE_KEYS);
+    }
+
+    #[test]
+    fn test_config_completion_and_hover() {
+        let text = "output:\n  format: \nprofiles:\n  dev:\n    output:\n      color: true\n";
+        let formats = completions(FileType::Config, text, Position::new(1, 10));
+        assert_eq!(labels(&formats), ["json", "text", "markdown"]);
+
+        let keys = completions(FileType::Config, "scan:\n  \n", Position::new(1, 2));
+        assert_eq!(
+            labels(&keys),
+            [
+                "fail_on_critical",
+                "show_autofix",
+                "explain",
+                "infra_format"
+            ]
+        );
+
+        let hover = hover(FileType::Config, text, Position::new(5, 8)).unwrap();
+        let HoverContents::Markup(markup) = hover.contents else {
+            panic!("expected markup");
+        };
+        assert!(markup.value.starts_with("**`output.color`**"));
+        assert!(super::hover(FileType::Config, text, Position::new(5, 14)).is_none());
+    }
+}
@@ -0,0 +1,324 @@
+// LSP command - language server for costpilot.yaml and policy files
+//
+// Speaks the Language Server Protocol over stdin/stdout. Open documents are
+// validated on every change (full-text sync) and diagnostics published from
+// the validation module; completion and hover come from `analysis`.
+
+pub mod analysis;
+
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::validation::{detect_file_type, FileType};
+use clap::Args;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{Completion, HoverRequest, Request as _};
+use lsp_types::{
+    CompletionOptions, CompletionParams, CompletionResponse, HoverParams, HoverProviderCapability,
+    PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
+    Url,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Run the CostPilot language server on stdin/stdout
+#[derive(Debug, Args)]
+pub struct LspCommand {
+    /// Use stdin/stdout (the only transport; accepted for editor clients that pass it)
+    #[arg(long)]
+    stdio: bool,
+}
+
+impl LspCommand {
+    pub fn execute(&self) -> Result<(), CostPilotError> {
+        let (connection, io_threads) = Connection::stdio();
+        serve(&connection)?;
+        drop(connection);
+        io_threads
+            .join()
+            .map_err(|e| CostPilotError::io_error(format!("Language server I/O failed: {}", e)))
+    }
+}
+
+/// Capabilities announced during `initialize`
+pub fn server_capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string(), " ".to_string()]),
+            ..Default::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..Default::default()
+    }
+}
+
+/// Handle the initialize handshake, then serve requests until shutdown
+pub fn serve(connection: &Connection) -> Result<(), CostPilotError> {
+    let capabilities = serde_json::to_value(server_capabilities()).map_err(|e| {
+        CostPilotError::serialization_error(format!("Failed to encode capabilities: {}", e))
+    })?;
+    connection
+        .initialize(capabilities)
+        .map_err(protocol_error)?;
+
+    let mut server = LanguageServer::default();
+    for message in &connection.receiver {
+        let replies = match message {
+            Message::Request(request) => {
+                if connection
+                    .handle_shutdown(&request)
+                    .map_err(protocol_error)?
+                {
+                    return Ok(());
+                }
+                vec![Message::Response(server.handle_request(request))]
+            }
+            Message::Notification(notification) => server
+                .handle_notification(notification)
+                .into_iter()
+                .map(Message::Notification)
+                .collect(),
+            Message::Response(_) => Vec::new(),
+        };
+        for reply in replies {
+            connection
+                .sender
+                .send(reply)
+                .map_err(|e| protocol_error(format!("client disconnected: {}", e)))?;
+        }
+    }
+    Ok(())
+}
+
+/// Open documents and the protocol handlers that work on them
+#[derive(Debug, Default)]
+pub struct LanguageServer {
+    documents: HashMap<Url, String>,
+}
+
+impl LanguageServer {
+    pub fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let method = request.method.clone();
+        let result =
+            match method.as_str() {
+                Completion::METHOD => {
+                    request
+                        .extract::<CompletionParams>(Completion::METHOD)
+                        .map(|(_, params)| {
+                            let position = params.text_document_position;
+                            let items = self
+                                .document(&position.text_document.uri)
+                                .map(|(kind, text)| {
+                                    analysis::completions(kind, text, position.position)
+                                })
+                                .unwrap_or_default();
+                            serde_json::to_value(CompletionResponse::Array(items))
+                        })
+                }
+                HoverRequest::METHOD => {
+                    request
+                        .extract::<HoverParams>(HoverRequest::METHOD)
+                        .map(|(_, params)| {
+                            let position = params.text_document_position_params;
+                            let hover = self.document(&position.text_document.uri).and_then(
+                                |(kind, text)| analysis::hover(kind, text, position.position),
+                            );
+                            serde_json::to_value(hover)
+                        })
+                }
+                method => {
+                    return Response::new_err(
+                        id,
+                        ErrorCode::MethodNotFound as i32,
+                        format!("Unsupported request: {}", method),
+                    )
+                }
+            };
+
+        match result {
+            Ok(Ok(value)) => Response::new_ok(id, value),
+            Ok(Err(e)) => Response::new_err(id, ErrorCode::InternalError as i32, e.to_string()),
+            Err(e) => Response::new_err(id, ErrorCode::InvalidParams as i32, e.to_string()),
+        }
+    }
+
+    /// Track document changes, returning the diagnostics to publish
+    pub fn handle_notification(&mut self, notification: Notification) -> Option<Notification> {
+        let method = notification.method.clone();
+        let uri = match method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = notification
+                    .extract::<lsp_types::DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)
+                    .ok()?;
+                let document = params.text_document;
+                self.documents.insert(document.uri.clone(), document.text);
+                document.uri
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = notification
+                    .extract::<lsp_types::DidChangeTextDocumentParams>(
+                        DidChangeTextDocument::METHOD,
+                    )
+                    .ok()?;
+                let change = params.content_changes.into_iter().last()?;
+                let uri = params.text_document.uri;
+                self.documents.insert(uri.clone(), change.text);
+                uri
+            }
+            DidSaveTextDocument::METHOD => {
+                notification
+                    .extract::<lsp_types::DidSaveTextDocumentParams>(DidSaveTextDocument::METHOD)
+                    .ok()?
+                    .text_document
+                    .uri
+            }
+            DidCloseTextDocument::METHOD => {
+                let uri = notification
+                    .extract::<lsp_types::DidCloseTextDocumentParams>(DidCloseTextDocument::METHOD)
+                    .ok()?
+                    .text_document
+                    .uri;
+                self.documents.remove(&uri);
+                uri
+            }
+            _ => return None,
+        };
+
+        let diagnostics = self
+            .documents
+            .get(&uri)
+            .map(|text| analysis::diagnostics(&document_path(&uri), text))
+            .unwrap_or_default();
+        Some(Notification::new(
+            PublishDiagnostics::METHOD.to_string(),
+            PublishDiagnosticsParams {
+                uri,
+                diagnostics,
+                version: None,
+            },
+        ))
+    }
+
+    /// Kind and text of an open CostPilot document
+    fn document(&self, uri: &Url) -> Option<(FileType, &str)> {
+        let text = self.documents.get(uri)?;
+        let kind = detect_file_type(&document_path(uri)).ok()?;
+        Some((kind, text.as_str()))
+    }
+}
+
+fn document_path(uri: &Url) -> PathBuf {
+    uri.to_file_path()
+        .unwrap_or_else(|_| PathBuf::from(uri.path()))
+}
+
+fn protocol_error(e: impl std::fmt::Display) -> CostPilotError {
+    CostPilotError::new(
+        "LSP_001",
+        ErrorCategory::InternalError,
+        format!("Language server protocol error: {}", e),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_server::RequestId;
+    use lsp_types::{
+        DidOpenTextDocumentParams, Position, TextDocumentIdentifier, TextDocumentItem,
+        TextDocumentPositionParams,
+    };
+
+    fn open(server: &mut LanguageServer, uri: &Url, text: &str) -> PublishDiagnosticsParams {
+        let notification = Notification::new(
+            DidOpenTextDocument::METHOD.to_string(),
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(uri.clone(), "yaml".into(), 1, text.into()),
+            },
+        );
+        let published = server.handle_notification(notification).unwrap();
+        serde_json::from_value(published.params).unwrap()
+    }
+
+    #[test]
+    fn test_open_publishes_diagnostics_and_serves_completion() {
+        let mut server = LanguageServer::default();
+        let uri = Url::parse("file:///work/costpilot.yaml").unwrap();
+        let published = open(&mut server, &uri, "version: 1.0.0\noutput:\n  format: \n");
+        assert!(published.diagnostics.is_empty());
+
+        let request = Request::new(
+            RequestId::from(1),
+            Completion::METHOD.to_string(),
+            CompletionParams {
+                text_document_position: TextDocumentPositionParams::new(
+                    TextDocumentIdentifier::new(uri.clone()),
+                    Position::new(2, 10),
+                ),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+                context: None,
+            },
+        );
+        let response = server.handle_request(request);
+        let items: Vec<lsp_types::CompletionItem> =
+            serde_json::from_value(response.result.unwrap()).unwrap();
+        assert_eq!(items.len(), 3);
+
+        let published = open(&mut server, &uri, "output:\n  format: html\n");
+        assert_eq!(published.diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn test_serve_handles_handshake_and_shutdown() {
+        let (server, client) = Connection::memory();
+        let handle = std::thread::spawn(move || serve(&server));
+
+        client
+            .sender
+            .send(Message::Request(Request::new(
+                RequestId::from(1),
+                "initialize".to_string(),
+                lsp_types::InitializeParams::default(),
+            )))
+            .unwrap();
+        let Message::Response(init) = client.receiver.recv().unwrap() else {
+            panic!("expected initialize response");
+        };
+        assert!(init.result.unwrap()["capabilities"]["hoverProvider"]
+            .as_bool()
+            .unwrap());
+        client
+            .sender
+            .send(Message::Notification(Notification::new(
+                "initialized".to_string(),
+                serde_json::json!({}),
+            )))
+            .unwrap();
+
+        client
+            .sender
+            .send(Message::Request(Request::new(
+                RequestId::from(2),
+                "shutdown".to_string(),
+                serde_json::Value::Null,
+            )))
+            .unwrap();
+        assert!(matches!(
+            client.receiver.recv().unwrap(),
+            Message::Response(_)
+        ));
+        client
+            .sender
+            .send(Message::Notification(Notification::new(
+                "exit".to_string(),
+                serde_json::Value::Null,
+            )))
+            .unwrap();
+        assert!(handle.join().unwrap().is_ok());
+    }
+}
@@ -1,20 +1,28 @@
 // Mapping CLI commands for dependency visualization
 
+use crate::cli::options::GlobalOptions;
 use crate::engines::mapping::{
-    ColorScheme, GraphvizConfig, JsonExportConfig, JsonFormat, MappingEngine,
+    format_cycle, ColorScheme, GraphComparator, GraphvizConfig, JsonExportConfig, JsonFormat,
+    MappingEngine, NodeAggregator, ReferenceResolver,
 };
+use crate::engines::shared::filter::FilterExpr;
+use crate::engines::shared::models::ResourceChange;
+use crate::engines::shared::redaction::{RedactionConfig, Redactor};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::validation::OutputValidator;
 use clap::Args;
 use colored::Colorize;
 use std::path::PathBuf;
 
+/// Project configuration with the optional `redaction` section
+const PROJECT_CONFIG: &str = "costpilot.yaml";
+
 #[derive(Debug, Args)]
 pub struct MapCommand {
     /// Path to Terraform plan JSON file
     plan: PathBuf,
 
-    /// Output format: mermaid, graphviz, json, html
+    /// Output format: mermaid, graphviz, json, html, interactive, graphml, csv, csv-nodes
     #[arg(short, long, default_value = "mermaid")]
     format: String,
 
@@ -47,48 +55,70 @@ pub struct MapCommand {
     no_modules: bool,
 
     /// Show verbose output
-    #[arg(short, long)]
-    verbose: bool,
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
 
     /// Analyze cross-service cost impacts
     #[arg(long)]
     cost_impacts: bool,
+
+    /// Collapse low-cost leaf resources above this many nodes (0 = no limit)
+    #[arg(long, default_value = "300")]
+    node_budget: usize,
+
+    /// Additional stack to stitch in via cross-stack references (NAME=FILE,
+    /// Terraform plan JSON or CloudFormation template; repeatable)
+    #[arg(long = "stack", value_name = "NAME=FILE")]
+    stacks: Vec<String>,
+
+    /// Baseline plan to diff against (mermaid or json output)
+    #[arg(long, value_name = "FILE")]
+    compare: Option<PathBuf>,
+
+    /// Only map resources matching this expression
+    /// (e.g. 'type=aws_instance and cost>100 and module=~vpc')
+    #[arg(long, value_name = "EXPR")]
+    filter: Option<FilterExpr>,
 }
 
 pub fn execute_map_command(
     cmd: &MapCommand,
     edition: &crate::edition::EditionContext,
+    options: &GlobalOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Check depth gating
     let max_depth = cmd.max_depth.unwrap_or(5);
     if max_depth > 1 {
-        crate::edition::require_premium(edition, "Deep mapping")?;
+        edition.require_capability(crate::edition::Capability::MappingDeep, "Deep mapping")?;
     }
 
     println!("{}", "📊 CostPilot Dependency Mapper".bold().cyan());
     println!();
 
     // Load and parse plan
-    if cmd.verbose {
+    if cmd.verbose > 0 {
         println!("{}", "Loading Terraform plan...".dimmed());
     }
     let plan_content = std::fs::read_to_string(&cmd.plan)?;
     let plan: serde_json::Value = serde_json::from_str(&plan_content)?;
 
     // Extract resource changes
-    let changes = crate::cli::utils::extract_resource_changes(&plan)?;
+    let mut changes = crate::cli::utils::extract_resource_changes(&plan)?;
+    if let Some(filter) = &cmd.filter {
+        changes = crate::cli::utils::filter_changes(filter, changes, edition)?;
+    }
 
-    if cmd.verbose {
+    if cmd.verbose > 0 {
         println!("   Found {} resource changes", changes.len());
         println!();
     }
 
     // Build dependency graph
-    if cmd.verbose {
+    if cmd.verbose > 0 {
         println!("{}", "Building dependency graph...".dimmed());
     }
 
-    let graph_config = if edition.capabilities.allow_mapping_deep {
+    let mut graph_config = if edition.capabilities.allow_mapping_deep {
         // Premium: no depth limit
         crate::engines::mapping::GraphConfig::default()
     } else {
@@ -98,78 +128,201 @@ pub fn execute_map_command(
             ..Default::default()
         }
     };
+    graph_config.node_budget = (cmd.node_budget > 0).then_some(cmd.node_budget);
 
     let mut engine = MappingEngine::with_config(
         graph_config,
         crate::engines::mapping::MermaidConfig::default(),
         edition,
     );
-    let graph = engine.build_graph(&changes)?;
+    let mut graph = engine.build_graph(&changes)?;
+    let mut stack_changes = Vec::new();
+
+    if !cmd.stacks.is_empty() {
+        if cmd.verbose > 0 {
+            println!("{}", "Resolving cross-stack references...".dimmed());
+        }
+        let primary_name = cmd
+            .plan
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("primary");
+        let mut resolver = ReferenceResolver::new();
+        resolver.add_terraform_plan(primary_name, &cmd.plan.display().to_string(), &plan, graph);
+        for spec in &cmd.stacks {
+            stack_changes.extend(add_stack(&mut resolver, &mut engine, spec)?);
+        }
+
+        let resolved = resolver.resolve();
+        for reference in &resolved.unresolved {
+            eprintln!(
+                "{} Unresolved cross-stack reference in {}: {} ({})",
+                "⚠️".yellow(),
+                reference.stack,
+                reference.node,
+                reference.via
+            );
+        }
+        graph = resolved.graph;
+    }
+
+    // The graph is built from the real plan so references resolve, then
+    // redacted as a whole before any export
+    let redactor = if options.redact {
+        let config = if std::path::Path::new(PROJECT_CONFIG).exists() {
+            RedactionConfig::load(std::path::Path::new(PROJECT_CONFIG), options.profile())?
+        } else {
+            RedactionConfig::default()
+        };
+        let redactor = Redactor::new(config).learn(&changes).learn(&stack_changes);
+        graph = redactor.redact(&graph)?;
+        Some(redactor)
+    } else {
+        None
+    };
+
+    let pruned = NodeAggregator::prune(&mut graph, &options.pruning);
+    if pruned > 0 {
+        eprintln!(
+            "{} {} resources outside --top/--min-cost shown as \"everything else\"",
+            "ℹ️".blue(),
+            pruned
+        );
+    }
 
-    if cmd.verbose {
+    if cmd.verbose > 0 {
         println!("   Nodes: {}", graph.nodes.len());
         println!("   Edges: {}", graph.edges.len());
         println!();
     }
 
+    if graph.metadata.collapsed_count > 0 {
+        eprintln!(
+            "{} {} low-cost resources collapsed to stay within the {}-node budget (use --node-budget 0 to disable)",
+            "ℹ️".blue(),
+            graph.metadata.collapsed_count,
+            cmd.node_budget
+        );
+    }
+
+    // Surface dependency cycles (stderr keeps machine-readable output clean)
+    if graph.metadata.has_cycles {
+        eprintln!(
+            "{} {} dependency cycle(s) detected:",
+            "⚠️".yellow(),
+            graph.metadata.cycles.len()
+        );
+        for cycle in &graph.metadata.cycles {
+            eprintln!("   • {}", format_cycle(cycle));
+        }
+        eprintln!();
+    }
+
     // Generate output based on format
-    let output_content = match cmd.format.as_str() {
-        "mermaid" => {
-            if cmd.verbose {
-                println!("{}", "Generating Mermaid diagram...".dimmed());
-            }
-            engine.generate_mermaid(&graph)?
+    let output_content = if let Some(baseline_path) = &cmd.compare {
+        if cmd.verbose > 0 {
+            println!("{}", "Building baseline graph...".dimmed());
         }
-        "graphviz" | "dot" => {
-            if cmd.verbose {
-                println!("{}", "Generating Graphviz DOT...".dimmed());
+        let baseline_content = std::fs::read_to_string(baseline_path)?;
+        let baseline_plan: serde_json::Value = serde_json::from_str(&baseline_content)?;
+        let baseline_changes = crate::cli::utils::extract_resource_changes(&baseline_plan)?;
+        let mut baseline = engine.build_graph(&baseline_changes)?;
+        if let Some(redactor) = &redactor {
+            baseline = redactor
+                .clone()
+                .learn(&baseline_changes)
+                .redact(&baseline)?;
+        }
+        NodeAggregator::prune(&mut baseline, &options.pruning);
+
+        let comparator = GraphComparator::new();
+        let diff = comparator.compare(&baseline, &graph);
+        eprintln!("{} {}", "Graph diff:".bold(), diff.summary());
+
+        match cmd.format.as_str() {
+            "mermaid" => comparator.to_mermaid(&diff, &graph),
+            "json" => serde_json::to_string_pretty(&diff)?,
+            _ => {
+                return Err(format!(
+                    "Format {} does not support --compare. Valid formats: mermaid, json",
+                    cmd.format
+                )
+                .into());
             }
-            let config = GraphvizConfig {
-                rankdir: cmd.rankdir.clone(),
-                show_costs: !cmd.hide_costs,
-                show_modules: !cmd.no_modules,
-                color_scheme: parse_color_scheme(&cmd.color_scheme),
-                ..Default::default()
-            };
-            engine.generate_graphviz_with_config(&graph, config)?
         }
-        "json" => {
-            if cmd.verbose {
-                println!("{}", "Exporting to JSON...".dimmed());
+    } else {
+        match cmd.format.as_str() {
+            "mermaid" => {
+                if cmd.verbose > 0 {
+                    println!("{}", "Generating Mermaid diagram...".dimmed());
+                }
+                engine.generate_mermaid(&graph)?
             }
-            let json_format = parse_json_format(&cmd.json_format);
-            let config = JsonExportConfig {
-                pretty: true,
-                include_metadata: true,
-                include_statistics: cmd.verbose,
-                format: json_format,
-            };
-            let json_output = engine.export_json_with_config(&graph, config)?;
-
-            // Validate JSON output against schema only for standard format
-            if json_format == JsonFormat::Standard {
-                #[cfg(not(target_arch = "wasm32"))]
-                {
-                    use crate::validation::output::OutputType;
-                    let validator = OutputValidator::new()?;
-                    validator.validate(OutputType::Mapping, &json_output)?;
+            "graphviz" | "dot" => {
+                if cmd.verbose > 0 {
+                    println!("{}", "Generating Graphviz DOT...".dimmed());
                 }
+                let config = GraphvizConfig {
+                    rankdir: cmd.rankdir.clone(),
+                    show_costs: !cmd.hide_costs,
+                    show_modules: !cmd.no_modules,
+                    color_scheme: parse_color_scheme(&cmd.color_scheme),
+                    ..Default::default()
+                };
+                engine.generate_graphviz_with_config(&graph, config)?
             }
+            "json" => {
+                if cmd.verbose > 0 {
+                    println!("{}", "Exporting to JSON...".dimmed());
+                }
+                let json_format = parse_json_format(&cmd.json_format);
+                let config = JsonExportConfig {
+                    pretty: true,
+                    include_metadata: true,
+                    include_statistics: cmd.verbose > 0,
+                    format: json_format,
+                };
+                let json_output = engine.export_json_with_config(&graph, config)?;
+
+                // Validate JSON output against schema only for standard format
+                if json_format == JsonFormat::Standard {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        use crate::validation::output::OutputType;
+                        let validator = OutputValidator::new()?;
+                        validator.validate(OutputType::Mapping, &json_output)?;
+                    }
+                }
 
-            json_output
-        }
-        "html" => {
-            if cmd.verbose {
-                println!("{}", "Generating HTML...".dimmed());
+                json_output
+            }
+            "html" => {
+                if cmd.verbose > 0 {
+                    println!("{}", "Generating HTML...".dimmed());
+                }
+                engine.generate_html(&graph, "Infrastructure Dependencies")?
+            }
+            "graphml" => {
+                if cmd.verbose > 0 {
+                    println!("{}", "Exporting to GraphML...".dimmed());
+                }
+                engine.export_graphml(&graph)?
+            }
+            "csv" => engine.export_csv_edges(&graph),
+            "csv-nodes" => engine.export_csv_nodes(&graph),
+            "interactive" => {
+                if cmd.verbose > 0 {
+                    println!("{}", "Generating interactive HTML...".dimmed());
+                }
+                engine.generate_interactive_html(&graph, "Infrastructure Dependencies")?
+            }
+            _ => {
+                return Err(format!(
+                    "Unknown format: {}. Valid formats: mermaid, graphviz, json, html, interactive, graphml, csv, csv-nodes",
+                    cmd.format
+                )
+                .into());
             }
-            engine.generate_html(&graph, "Infrastructure Dependencies")?
-        }
-        _ => {
-            return Err(format!(
-                "Unknown format: {}. Valid formats: mermaid, graphviz, json, html",
-                cmd.format
-            )
-            .into());
         }
     };
 
@@ -186,7 +339,7 @@ pub fn execute_map_command(
     }
 
     // Show additional information
-    if cmd.verbose && cmd.format == "graphviz" {
+    if cmd.verbose > 0 && cmd.format == "graphviz" {
         println!();
         println!("{}", "Graphviz Tips:".bold());
         println!("  • Render as PNG: dot -Tpng input.dot -o output.png");
@@ -194,7 +347,15 @@ pub fn execute_map_command(
         println!("  • Render as PDF: dot -Tpdf input.dot -o output.pdf");
     }
 
-    if cmd.verbose && cmd.format == "json" {
+    if cmd.verbose > 0 && cmd.format.starts_with("csv") {
+        println!();
+        println!("{}", "CSV Tips:".bold());
+        println!("  • Export both tables: --format csv (edges) and --format csv-nodes");
+        println!("  • Gephi: File → Import spreadsheet, nodes table first");
+        println!("  • Neo4j: LOAD CSV WITH HEADERS FROM 'file:///edges.csv' AS row");
+    }
+
+    if cmd.verbose > 0 && cmd.format == "json" {
         println!();
         println!("{}", "JSON Format:".bold());
         println!("  Format variant: {}", cmd.json_format);
@@ -207,7 +368,7 @@ pub fn execute_map_command(
     }
 
     // Show statistics
-    if cmd.verbose {
+    if cmd.verbose > 0 {
         println!();
         println!("{}", "Graph Statistics:".bold());
         println!("  Total nodes: {}", graph.metadata.node_count);
@@ -263,14 +424,14 @@ pub fn execute_map_command(
                     impact.affected_resources
                 );
 
-                if cmd.verbose {
+                if cmd.verbose > 0 {
                     println!("    {}", impact.description);
                 }
             }
         }
 
         // Show cost propagation if verbose
-        if cmd.verbose {
+        if cmd.verbose > 0 {
             println!();
             println!("{}", "Cost Propagation:".bold());
 
@@ -301,6 +462,35 @@ pub fn execute_map_command(
     Ok(())
 }
 
+/// Parse a `NAME=FILE` stack spec and add the stack's graph to the resolver;
+/// returns the stack's resource changes
+fn add_stack(
+    resolver: &mut ReferenceResolver,
+    engine: &mut MappingEngine,
+    spec: &str,
+) -> Result<Vec<ResourceChange>, Box<dyn std::error::Error>> {
+    let (name, path) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid --stack value '{}': expected NAME=FILE", spec))?;
+    let content = std::fs::read_to_string(path)?;
+    let value: serde_json::Value = serde_json::from_str(&content)?;
+
+    if value.get("resource_changes").is_some() {
+        let changes = crate::cli::utils::extract_resource_changes(&value)?;
+        let graph = engine.build_graph(&changes)?;
+        resolver.add_terraform_plan(name, path, &value, graph);
+        Ok(changes)
+    } else {
+        let mut artifact = crate::artifact::parse_artifact(&content, path)?;
+        artifact.metadata.stack_name = Some(name.to_string());
+        let changes =
+            crate::artifact::ArtifactNormalizer::normalize(&artifact).to_resource_changes();
+        let graph = engine.build_graph(&changes)?;
+        resolver.add_artifact(&artifact, graph);
+        Ok(changes)
+    }
+}
+
 fn parse_color_scheme(scheme: &str) -> ColorScheme {
     match scheme.to_lowercase().as_str() {
         "cost" => ColorScheme::CostBased,
@@ -344,6 +534,7 @@ mod tests {
                 std::path::PathBuf::from("/tmp/stub"),
             )),
             paths: crate::edition::EditionPaths::default(),
+            org_policy: None,
         }
     }
 
@@ -393,12 +584,16 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -420,12 +615,16 @@ mod tests {
             max_depth: None,
             hide_costs: true,
             no_modules: false,
-            verbose: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -447,12 +646,16 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -474,13 +677,126 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: false,
+            verbose: 0,
+            cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
+        };
+
+        let edition = create_test_edition();
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_execute_map_command_interactive_format() {
+        let temp_dir = tempdir().unwrap();
+        let plan_path = temp_dir.path().join("plan.json");
+        let output_path = temp_dir.path().join("map.html");
+
+        let plan = create_test_terraform_plan();
+        fs::write(&plan_path, serde_json::to_string_pretty(&plan).unwrap()).unwrap();
+
+        let cmd = MapCommand {
+            plan: plan_path,
+            format: "interactive".to_string(),
+            output: Some(output_path.clone()),
+            json_format: "standard".to_string(),
+            rankdir: "LR".to_string(),
+            color_scheme: "cost".to_string(),
+            max_depth: None,
+            hide_costs: false,
+            no_modules: false,
+            verbose: 0,
+            cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
+        };
+
+        let edition = create_test_edition();
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
+        assert!(result.is_ok());
+        let html = fs::read_to_string(&output_path).unwrap();
+        assert!(html.contains("id=\"graph-data\""));
+    }
+
+    #[test]
+    fn test_execute_map_command_with_stacks() {
+        let temp_dir = tempdir().unwrap();
+        let plan_path = temp_dir.path().join("app.json");
+        let network_path = temp_dir.path().join("network.json");
+        let output_path = temp_dir.path().join("map.json");
+
+        let plan = create_test_terraform_plan();
+        fs::write(&plan_path, serde_json::to_string_pretty(&plan).unwrap()).unwrap();
+        fs::write(&network_path, serde_json::to_string_pretty(&plan).unwrap()).unwrap();
+
+        let cmd = MapCommand {
+            plan: plan_path,
+            format: "json".to_string(),
+            output: Some(output_path.clone()),
+            json_format: "cytoscape".to_string(),
+            rankdir: "LR".to_string(),
+            color_scheme: "cost".to_string(),
+            max_depth: None,
+            hide_costs: false,
+            no_modules: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: vec![format!("network={}", network_path.display())],
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
+        let output = fs::read_to_string(&output_path).unwrap();
+        assert!(output.contains("network::aws_instance_example"));
+        assert!(output.contains("app::aws_instance_example"));
+    }
+
+    #[test]
+    fn test_execute_map_command_graphml_and_csv_formats() {
+        let temp_dir = tempdir().unwrap();
+        let plan_path = temp_dir.path().join("plan.json");
+
+        let plan = create_test_terraform_plan();
+        fs::write(&plan_path, serde_json::to_string_pretty(&plan).unwrap()).unwrap();
+
+        for (format, marker) in [
+            ("graphml", "<graphml"),
+            ("csv", "Source,Target"),
+            ("csv-nodes", "Id,Label"),
+        ] {
+            let output_path = temp_dir.path().join(format!("map.{}", format));
+            let cmd = MapCommand {
+                plan: plan_path.clone(),
+                format: format.to_string(),
+                output: Some(output_path.clone()),
+                json_format: "standard".to_string(),
+                rankdir: "LR".to_string(),
+                color_scheme: "cost".to_string(),
+                max_depth: None,
+                hide_costs: false,
+                no_modules: false,
+                verbose: 0,
+                cost_impacts: false,
+                node_budget: 300,
+                stacks: Vec::new(),
+                compare: None,
+                filter: None,
+            };
+
+            let edition = create_test_edition();
+            assert!(execute_map_command(&cmd, &edition, &GlobalOptions::default()).is_ok());
+            assert!(fs::read_to_string(&output_path).unwrap().contains(marker));
+        }
     }
 
     #[test]
@@ -501,12 +817,16 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_err());
         let error = result.unwrap_err().to_string();
         assert!(error.contains("Unknown format: invalid"));
@@ -527,12 +847,16 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_err());
     }
 
@@ -555,16 +879,56 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
         assert!(output_path.exists());
     }
 
+    #[test]
+    fn test_execute_map_command_redacts_when_options_ask() {
+        let temp_dir = tempdir().unwrap();
+        let plan_path = temp_dir.path().join("plan.json");
+        let output_path = temp_dir.path().join("output.mmd");
+        let plan = create_test_terraform_plan();
+        fs::write(&plan_path, serde_json::to_string_pretty(&plan).unwrap()).unwrap();
+
+        let cmd = MapCommand {
+            plan: plan_path,
+            format: "mermaid".to_string(),
+            output: Some(output_path.clone()),
+            json_format: "standard".to_string(),
+            rankdir: "LR".to_string(),
+            color_scheme: "cost".to_string(),
+            max_depth: None,
+            hide_costs: false,
+            no_modules: false,
+            verbose: 0,
+            cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
+        };
+        let options = GlobalOptions {
+            redact: true,
+            ..GlobalOptions::default()
+        };
+
+        execute_map_command(&cmd, &create_test_edition(), &options).unwrap();
+        let output = fs::read_to_string(&output_path).unwrap();
+        assert!(output.contains("aws_instance_r_"));
+        assert!(!output.contains("example"));
+    }
+
     #[test]
     fn test_execute_map_command_verbose_mode() {
         let temp_dir = tempdir().unwrap();
@@ -583,12 +947,16 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: true,
+            verbose: 1,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -610,12 +978,16 @@ mod tests {
             max_depth: Some(10), // This should trigger premium check
             hide_costs: false,
             no_modules: false,
-            verbose: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -637,12 +1009,16 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: true, // This should trigger graphviz tips
+            verbose: 1, // This should trigger graphviz tips
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -664,13 +1040,59 @@ mod tests {
             max_depth: None,
             hide_costs: false,
             no_modules: false,
-            verbose: true, // This should trigger json tips
+            verbose: 1, // This should trigger json tips
+            cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: None,
+            filter: None,
+        };
+
+        let edition = create_test_edition();
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_execute_map_command_compare() {
+        let temp_dir = tempdir().unwrap();
+        let plan_path = temp_dir.path().join("plan.json");
+        let baseline_path = temp_dir.path().join("baseline.json");
+        let output_path = temp_dir.path().join("diff.mmd");
+
+        let plan = create_test_terraform_plan();
+        fs::write(&plan_path, serde_json::to_string_pretty(&plan).unwrap()).unwrap();
+        let mut baseline = plan.clone();
+        baseline["resource_changes"].as_array_mut().unwrap().pop();
+        fs::write(
+            &baseline_path,
+            serde_json::to_string_pretty(&baseline).unwrap(),
+        )
+        .unwrap();
+
+        let cmd = MapCommand {
+            plan: plan_path,
+            format: "mermaid".to_string(),
+            output: Some(output_path.clone()),
+            json_format: "standard".to_string(),
+            rankdir: "LR".to_string(),
+            color_scheme: "cost".to_string(),
+            max_depth: None,
+            hide_costs: false,
+            no_modules: false,
+            verbose: 0,
             cost_impacts: false,
+            node_budget: 300,
+            stacks: Vec::new(),
+            compare: Some(baseline_path),
+            filter: None,
         };
 
         let edition = create_test_edition();
-        let result = execute_map_command(&cmd, &edition);
+        let result = execute_map_command(&cmd, &edition, &GlobalOptions::default());
         assert!(result.is_ok());
+        let output = fs::read_to_string(&output_path).unwrap();
+        assert!(output.contains(":::added"));
     }
 
     #[test]
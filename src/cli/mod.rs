@@ -1,17 +1,39 @@
 // CLI module exports
 
+pub mod annotate;
 pub mod baseline;
+pub mod calibrate;
 pub mod commands;
+pub mod error_output;
 pub mod escrow;
+pub mod exit_code;
 pub mod explain;
+pub mod feature_flags;
 pub mod flags;
 pub mod group;
 pub mod heuristics;
 pub mod init;
+pub mod init_wizard;
+pub mod inventory;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod lsp;
 pub mod map;
+pub mod ndjson;
+pub mod options;
 pub mod performance;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod plugins;
 pub mod policy_dsl;
+pub mod pro;
 pub mod pro_serde;
+pub mod progress;
+pub mod report;
 pub mod scan;
+#[cfg(all(feature = "tui", not(target_arch = "wasm32")))]
+pub mod scan_tui;
+pub mod schema;
+pub mod storage;
 pub mod usage;
 pub mod utils;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watch;
@@ -0,0 +1,203 @@
+// NDJSON output - one JSON object per line, written as results are produced
+
+use crate::engines::policy::PolicyViolation;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::models::{CostEstimate, Detection};
+use crate::engines::shared::redaction::Redactor;
+use serde::Serialize;
+use std::io::Write;
+
+/// One line of NDJSON output, tagged with `"type"`
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NdjsonRecord<'a> {
+    Estimate(&'a CostEstimate),
+    Detection(&'a Detection),
+    PolicyViolation(&'a PolicyViolation),
+    /// One finished stack of a `--stacks` scan
+    Stack(NdjsonStack),
+    /// Always the last record of a scan
+    Summary(NdjsonSummary),
+}
+
+/// Totals written once all findings have been streamed
+#[derive(Debug, Clone, Serialize)]
+pub struct NdjsonSummary {
+    pub schema_version: &'static str,
+    pub resources_changed: usize,
+    pub monthly_cost: f64,
+    pub optimization_opportunities: usize,
+    pub policy_status: Option<String>,
+    pub slo_status: Option<String>,
+    /// Present when produced under a trial license
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial: Option<crate::edition::TrialWatermark>,
+}
+
+/// Totals for one stack, written as soon as the stack finishes
+#[derive(Debug, Clone, Serialize)]
+pub struct NdjsonStack {
+    pub name: String,
+    pub plan: String,
+    pub resources_changed: usize,
+    pub monthly_cost: f64,
+    pub optimization_opportunities: usize,
+    pub duration_ms: u64,
+    pub budget_ms: u64,
+    pub within_budget: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<&crate::engines::pipeline::StackResult> for NdjsonStack {
+    fn from(result: &crate::engines::pipeline::StackResult) -> Self {
+        Self {
+            name: result.name.clone(),
+            plan: result.plan.display().to_string(),
+            resources_changed: result.changes.len(),
+            monthly_cost: result.monthly_cost,
+            optimization_opportunities: result.detections.len(),
+            duration_ms: result.metrics.duration_ms,
+            budget_ms: result.metrics.budget_ms,
+            within_budget: result.metrics.within_budget,
+            error: result.error.clone(),
+        }
+    }
+}
+
+/// Writes records line by line, flushing each so consumers see them at once
+pub struct NdjsonWriter<W: Write> {
+    out: W,
+    records: usize,
+    redactor: Option<Redactor>,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            records: 0,
+            redactor: None,
+        }
+    }
+
+    /// Redact every record before it is written (`--redact`)
+    pub fn with_redactor(mut self, redactor: Option<Redactor>) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
+    pub fn write(&mut self, record: &NdjsonRecord) -> Result<(), CostPilotError> {
+        let serialize_error = |e: serde_json::Error| {
+            CostPilotError::new(
+                "OUTPUT_004",
+                ErrorCategory::ValidationError,
+                format!("Failed to serialize NDJSON record: {}", e),
+            )
+        };
+        let line = match &self.redactor {
+            Some(redactor) => {
+                let mut value = serde_json::to_value(record).map_err(serialize_error)?;
+                redactor.json(&mut value);
+                serde_json::to_string(&value)
+            }
+            None => serde_json::to_string(record),
+        }
+        .map_err(serialize_error)?;
+        writeln!(self.out, "{}", line)
+            .and_then(|_| self.out.flush())
+            .map_err(|e| CostPilotError::io_error(format!("Failed to write output: {}", e)))?;
+        self.records += 1;
+        Ok(())
+    }
+
+    pub fn write_all<'a>(
+        &mut self,
+        records: impl IntoIterator<Item = NdjsonRecord<'a>>,
+    ) -> Result<(), CostPilotError> {
+        records.into_iter().try_for_each(|r| self.write(&r))
+    }
+
+    /// Records written so far
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
+    use serde_json::Value;
+
+    #[test]
+    fn test_each_record_is_one_tagged_line() {
+        let estimate = CostEstimate::builder()
+            .resource_id("aws_instance.web")
+            .monthly_cost(42.0)
+            .build();
+        let detection = Detection::builder()
+            .rule_id("OVERPROVISIONED_EC2")
+            .resource_id("aws_instance.web")
+            .message("Large\ninstance")
+            .build();
+
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer
+            .write_all([
+                NdjsonRecord::Estimate(&estimate),
+                NdjsonRecord::Detection(&detection),
+                NdjsonRecord::Summary(NdjsonSummary {
+                    schema_version: OUTPUT_SCHEMA_VERSION,
+                    resources_changed: 1,
+                    monthly_cost: 42.0,
+                    optimization_opportunities: 1,
+                    policy_status: None,
+                    slo_status: None,
+                    trial: None,
+                }),
+            ])
+            .unwrap();
+        assert_eq!(writer.records(), 3);
+
+        let out = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "estimate");
+        assert_eq!(lines[0]["monthly_cost"], 42.0);
+        assert_eq!(lines[1]["type"], "detection");
+        assert_eq!(lines[1]["message"], "Large\ninstance");
+        assert_eq!(lines[2]["type"], "summary");
+        assert_eq!(lines[2]["resources_changed"], 1);
+        assert_eq!(lines[2]["schema_version"], OUTPUT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_policy_violation_record() {
+        let violation = PolicyViolation {
+            policy_name: "budget".to_string(),
+            severity: "HIGH".to_string(),
+            resource_id: "global".to_string(),
+            message: "Monthly budget exceeded".to_string(),
+            actual_value: "$250".to_string(),
+            expected_value: "$200".to_string(),
+        };
+
+        let mut writer = NdjsonWriter::new(Vec::new());
+        writer
+            .write(&NdjsonRecord::PolicyViolation(&violation))
+            .unwrap();
+
+        let line: Value = serde_json::from_slice(&writer.into_inner()).unwrap();
+        assert_eq!(line["type"], "policy_violation");
+        assert_eq!(line["policy_name"], "budget");
+    }
+}
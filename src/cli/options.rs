@@ -0,0 +1,31 @@
+// Global options (`--profile`, `--locale`, `--redact`, `--top`, `--min-cost`)
+//
+// Resolved once from the command line and handed to the commands that read
+// them, so config loaders, exporters and reports never consult the process
+// environment for per-run settings.
+
+use crate::engines::shared::pruning::Pruning;
+use crate::i18n::Locale;
+
+/// Per-run settings shared by every command
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GlobalOptions {
+    /// Config profile overlaid on costpilot.yaml
+    pub profile: Option<String>,
+
+    /// Language of messages and report headings
+    pub locale: Locale,
+
+    /// Hash names and drop sensitive tags in exported reports
+    pub redact: bool,
+
+    /// Top-N and threshold pruning of report rows
+    pub pruning: Pruning,
+}
+
+impl GlobalOptions {
+    /// Profile to apply when loading costpilot.yaml sections
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+}
@@ -1,7 +1,9 @@
 // CLI commands for performance monitoring
 
-use crate::engines::performance::{PerformanceBudgets, PerformanceMonitor, PerformanceReport};
-use std::path::PathBuf;
+use crate::engines::performance::{
+    CircuitBreakerRegistry, PerformanceBudgets, PerformanceMonitor, PerformanceReport,
+};
+use std::path::{Path, PathBuf};
 
 /// Performance CLI commands
 #[derive(Debug)]
@@ -23,6 +25,15 @@ pub enum PerformanceCommand {
         engine: Option<String>,
         limit: Option<usize>,
     },
+
+    /// Export a report's timing spans as a Chrome trace
+    Trace {
+        report_file: PathBuf,
+        output: Option<PathBuf>,
+    },
+
+    /// Show or reset persisted circuit breaker state
+    Breakers { reset: bool },
 }
 
 /// Execute performance command
@@ -35,6 +46,15 @@ pub fn execute_performance_command(cmd: PerformanceCommand) -> Result<String, St
             execute_check_regressions(&report_file)
         }
         PerformanceCommand::History { engine, limit } => execute_history(engine, limit),
+        PerformanceCommand::Trace {
+            report_file,
+            output,
+        } => execute_trace(&report_file, output.as_deref()),
+        PerformanceCommand::Breakers { reset } => {
+            let path = CircuitBreakerRegistry::default_path()
+                .ok_or_else(|| "Could not determine home directory".to_string())?;
+            execute_breakers(&path, reset)
+        }
     }
 }
 
@@ -241,6 +261,64 @@ fn execute_history(engine: Option<String>, _limit: Option<usize>) -> Result<Stri
     Ok(output)
 }
 
+fn execute_trace(report_file: &Path, output: Option<&Path>) -> Result<String, String> {
+    let content = std::fs::read_to_string(report_file)
+        .map_err(|e| format!("Failed to read report file: {}", e))?;
+    let report: PerformanceReport =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse report: {}", e))?;
+
+    let trace = serde_json::to_string_pretty(&report.to_chrome_trace())
+        .map_err(|e| format!("Failed to serialize trace: {}", e))?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, trace)
+                .map_err(|e| format!("Failed to write trace file: {}", e))?;
+            Ok(format!(
+                "✅ Chrome trace written to: {} (open in chrome://tracing or ui.perfetto.dev)",
+                path.display()
+            ))
+        }
+        None => Ok(trace),
+    }
+}
+
+fn execute_breakers(state_path: &Path, reset: bool) -> Result<String, String> {
+    let mut registry = CircuitBreakerRegistry::load(state_path)?;
+
+    if reset {
+        let count = registry.breakers.len();
+        registry.reset_all();
+        registry.save(state_path)?;
+        return Ok(format!("✅ Reset {} circuit breaker(s)", count));
+    }
+
+    let mut output = String::new();
+    output.push_str("🔌 Circuit Breakers\n");
+    output.push_str("==================\n\n");
+
+    if registry.breakers.is_empty() {
+        output.push_str("No persisted circuit breaker state\n");
+        return Ok(output);
+    }
+
+    for (engine, snapshot) in &registry.breakers {
+        output.push_str(&format!(
+            "  {}: {:?} ({} failures, {} consecutive)",
+            engine, snapshot.state, snapshot.failure_count, snapshot.consecutive_failures
+        ));
+        if let Some(at) = snapshot.last_failure_at {
+            let when = chrono::DateTime::from_timestamp(at as i64, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                .unwrap_or_else(|| at.to_string());
+            output.push_str(&format!(", last failure {}", when));
+        }
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
 fn get_history_path() -> Result<PathBuf, String> {
     let home =
         std::env::var("HOME").map_err(|_| "HOME environment variable not set".to_string())?;
@@ -263,4 +341,55 @@ mod tests {
         assert!(output.contains("Prediction Engine"));
         assert!(output.contains("300ms"));
     }
+
+    #[test]
+    fn test_trace_command_writes_chrome_trace() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let report_file = dir.path().join("report.json");
+        let trace_file = dir.path().join("trace.json");
+
+        let mut tracker =
+            crate::engines::performance::PerformanceTracker::new(PerformanceBudgets::default().slo);
+        tracker.enter_phase("evaluate");
+        let (metrics, spans) = tracker.complete_with_spans();
+        let mut report = PerformanceReport::new();
+        report.add_traced(metrics, spans);
+        std::fs::write(&report_file, serde_json::to_string(&report).unwrap()).unwrap();
+
+        let result = execute_performance_command(PerformanceCommand::Trace {
+            report_file,
+            output: Some(trace_file.clone()),
+        });
+        assert!(result.is_ok());
+
+        let trace: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(trace_file).unwrap()).unwrap();
+        assert_eq!(trace["traceEvents"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_breakers_command_lists_and_resets() {
+        use crate::engines::performance::{CircuitBreaker, PRO_ENGINE_BREAKER};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("circuit_breakers.json");
+
+        let mut breaker = CircuitBreaker::new(PerformanceBudgets::default().circuit_breaker);
+        for _ in 0..3 {
+            breaker.record_failure();
+        }
+        let mut registry = CircuitBreakerRegistry::new();
+        registry.update(PRO_ENGINE_BREAKER, &breaker);
+        registry.save(&path).unwrap();
+
+        let output = execute_breakers(&path, false).unwrap();
+        assert!(output.contains("pro_engine: Open (3 failures, 3 consecutive)"));
+
+        let output = execute_breakers(&path, true).unwrap();
+        assert!(output.contains("Reset 1"));
+        assert!(CircuitBreakerRegistry::load(&path)
+            .unwrap()
+            .breakers
+            .is_empty());
+    }
 }
@@ -0,0 +1,382 @@
+// Output processor plugins - local extensions fed the scan result
+//
+// `costpilot scan --plugins` runs every executable and WASM module found in
+// `.costpilot/plugins/` after the scan, in name order. Each plugin receives
+// the scan result JSON (the `--output-format json` document) and may:
+//
+// - write a report: executables print it to stdout, WASM modules return it;
+//   it is saved as `.costpilot/plugin-output/<plugin>.out`. Executables can
+//   also write extra files into `$COSTPILOT_PLUGIN_OUTPUT_DIR`.
+// - fail the gate: executables exit non-zero, WASM modules return -1. The
+//   scan then exits 3 like a policy violation.
+//
+// WASM plugins get no host imports and export `memory`,
+// `alloc(len: i32) -> i32` and `process(ptr: i32, len: i32) -> i64`, where
+// the result packs the output region as `(ptr << 32) | len`.
+
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Directory plugins are discovered in, relative to the working directory
+pub const PLUGIN_DIR: &str = ".costpilot/plugins";
+
+/// Directory plugin reports are written to
+pub const PLUGIN_OUTPUT_DIR: &str = ".costpilot/plugin-output";
+
+/// Wall-clock budget for one plugin run
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Linear memory a WASM plugin may grow to
+const WASM_MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// How a plugin is run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginKind {
+    Executable,
+    Wasm,
+}
+
+/// A plugin found in the plugin directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plugin {
+    /// File name without extension; names the report artifact
+    pub name: String,
+    pub path: PathBuf,
+    pub kind: PluginKind,
+}
+
+/// Result of running one plugin
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginOutcome {
+    /// Report written from the plugin's output, if it produced any
+    pub artifact: Option<PathBuf>,
+    /// Gate failure reported by the plugin
+    pub failure: Option<String>,
+}
+
+/// Plugins in `dir`, sorted by file name; a missing directory has none
+///
+/// Hidden files and files that are neither executable nor `.wasm` (such as
+/// a README) are skipped.
+pub fn discover(dir: &Path) -> Result<Vec<Plugin>, CostPilotError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(CostPilotError::io_error(format!(
+                "Failed to read plugin directory {}: {}",
+                dir.display(),
+                e
+            )))
+        }
+    };
+
+    let mut plugins = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.starts_with('.') || !path.is_file() {
+            continue;
+        }
+        let kind = if path.extension().is_some_and(|ext| ext == "wasm") {
+            PluginKind::Wasm
+        } else if is_executable(&path) {
+            PluginKind::Executable
+        } else {
+            continue;
+        };
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or(file_name);
+        plugins.push(Plugin { name, path, kind });
+    }
+    plugins.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(plugins)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "exe" || ext == "bat" || ext == "cmd")
+}
+
+impl Plugin {
+    /// Feed `scan_json` to the plugin and save its report under `output_dir`
+    pub fn run(&self, scan_json: &str, output_dir: &Path) -> Result<PluginOutcome, CostPilotError> {
+        std::fs::create_dir_all(output_dir).map_err(|e| {
+            CostPilotError::io_error(format!(
+                "Failed to create plugin output directory {}: {}",
+                output_dir.display(),
+                e
+            ))
+        })?;
+
+        let (output, failure) = match self.kind {
+            PluginKind::Executable => self.run_executable(scan_json, output_dir)?,
+            PluginKind::Wasm => self.run_wasm(scan_json)?,
+        };
+
+        let artifact = if output.is_empty() {
+            None
+        } else {
+            let path = output_dir.join(format!("{}.out", self.name));
+            std::fs::write(&path, &output).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+            })?;
+            Some(path)
+        };
+
+        Ok(PluginOutcome { artifact, failure })
+    }
+
+    fn run_executable(
+        &self,
+        scan_json: &str,
+        output_dir: &Path,
+    ) -> Result<(Vec<u8>, Option<String>), CostPilotError> {
+        let mut child = Command::new(&self.path)
+            .env("COSTPILOT_PLUGIN_OUTPUT_DIR", output_dir)
+            .env("COSTPILOT_SCHEMA_VERSION", OUTPUT_SCHEMA_VERSION)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| self.error(format!("failed to start: {}", e)))?;
+
+        // Feed stdin and drain stdout on their own threads so a plugin that
+        // interleaves reads and writes cannot deadlock against us
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let input = scan_json.as_bytes().to_vec();
+        let writer = std::thread::spawn(move || {
+            // A plugin may exit without reading its input
+            let _ = stdin.write_all(&input);
+        });
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let reader = std::thread::spawn(move || {
+            let mut output = Vec::new();
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+
+        let started = Instant::now();
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if started.elapsed() >= PLUGIN_TIMEOUT => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(
+                        self.error(format!("timed out after {}s", PLUGIN_TIMEOUT.as_secs()))
+                    );
+                }
+                Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+                Err(e) => return Err(self.error(format!("failed to wait: {}", e))),
+            }
+        };
+
+        let _ = writer.join();
+        let output = reader
+            .join()
+            .map_err(|_| self.error("output reader panicked".to_string()))?
+            .map_err(|e| self.error(format!("failed to read output: {}", e)))?;
+
+        let failure = (!status.success()).then(|| match status.code() {
+            Some(code) => format!("exited with status {}", code),
+            None => "terminated by a signal".to_string(),
+        });
+        Ok((output, failure))
+    }
+
+    fn run_wasm(&self, scan_json: &str) -> Result<(Vec<u8>, Option<String>), CostPilotError> {
+        let bytes = std::fs::read(&self.path)
+            .map_err(|e| self.error(format!("failed to read module: {}", e)))?;
+
+        let mut config = wasmtime::Config::new();
+        config.epoch_interruption(true);
+        let engine = wasmtime::Engine::new(&config).map_err(|e| self.error(format!("{}", e)))?;
+        let module = wasmtime::Module::new(&engine, &bytes)
+            .map_err(|e| self.error(format!("compilation failed: {}", e)))?;
+        if let Some(import) = module.imports().next() {
+            return Err(self.error(format!(
+                "imports '{}::{}' but plugins get no host imports",
+                import.module(),
+                import.name()
+            )));
+        }
+
+        let limits = wasmtime::StoreLimitsBuilder::new()
+            .memory_size(WASM_MEMORY_LIMIT)
+            .build();
+        let mut store = wasmtime::Store::new(&engine, limits);
+        store.limiter(|limits| limits);
+        store.set_epoch_deadline(1);
+        let ticker = engine.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(PLUGIN_TIMEOUT);
+            ticker.increment_epoch();
+        });
+
+        let instance = wasmtime::Instance::new(&mut store, &module, &[])
+            .map_err(|e| self.error(format!("instantiation failed: {}", e)))?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| self.error("must export 'memory'".to_string()))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(|e| self.error(format!("missing 'alloc' export: {}", e)))?;
+        let process = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "process")
+            .map_err(|e| self.error(format!("missing 'process' export: {}", e)))?;
+
+        let input = scan_json.as_bytes();
+        let len = i32::try_from(input.len())
+            .map_err(|_| self.error("scan result too large".to_string()))?;
+        let ptr = alloc
+            .call(&mut store, len)
+            .map_err(|e| self.error(format!("'alloc' failed: {}", e)))?;
+        memory
+            .write(&mut store, ptr as u32 as usize, input)
+            .map_err(|e| self.error(format!("input out of bounds: {}", e)))?;
+
+        let packed = process
+            .call(&mut store, (ptr, len))
+            .map_err(|e| self.error(format!("'process' failed: {}", e)))?;
+        if packed < 0 {
+            return Ok((Vec::new(), Some("reported a failure".to_string())));
+        }
+
+        let out_ptr = (packed as u64 >> 32) as usize;
+        let out_len = (packed as u64 & 0xffff_ffff) as usize;
+        let mut output = vec![0u8; out_len];
+        memory
+            .read(&store, out_ptr, &mut output)
+            .map_err(|e| self.error(format!("output out of bounds: {}", e)))?;
+        Ok((output, None))
+    }
+
+    fn error(&self, message: String) -> CostPilotError {
+        CostPilotError::new(
+            "PLUGIN_001",
+            ErrorCategory::InternalError,
+            format!("Plugin '{}' {}", self.name, message),
+        )
+        .with_hint(format!("Check {}", self.path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    fn write_script(dir: &Path, name: &str, body: &str) {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_executable_plugins_report_and_gate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let plugins = tmp.path().join("plugins");
+        let out = tmp.path().join("out");
+        std::fs::create_dir(&plugins).unwrap();
+        write_script(
+            &plugins,
+            "slack.sh",
+            "grep -o '\"monthly_cost\":[0-9.]*' | head -n 1",
+        );
+        write_script(&plugins, "budget-gate", "cat > /dev/null; exit 7");
+        std::fs::write(plugins.join("README.md"), "not a plugin").unwrap();
+        std::fs::write(plugins.join(".hidden"), "").unwrap();
+
+        let found = discover(&plugins).unwrap();
+        let names: Vec<&str> = found.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["budget-gate", "slack"]);
+        assert!(found.iter().all(|p| p.kind == PluginKind::Executable));
+
+        let scan = r#"{"summary":{"monthly_cost":42.5}}"#;
+        let gate = found[0].run(scan, &out).unwrap();
+        assert_eq!(gate.artifact, None);
+        assert_eq!(gate.failure.as_deref(), Some("exited with status 7"));
+
+        let slack = found[1].run(scan, &out).unwrap();
+        assert_eq!(slack.failure, None);
+        let artifact = slack.artifact.unwrap();
+        assert_eq!(artifact, out.join("slack.out"));
+        assert_eq!(
+            std::fs::read_to_string(artifact).unwrap().trim(),
+            "\"monthly_cost\":42.5"
+        );
+
+        assert!(discover(&tmp.path().join("missing")).unwrap().is_empty());
+    }
+
+    // Echoes its input back, or fails when the input is empty
+    const ECHO_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) i32.const 1024)
+          (func (export "process") (param $ptr i32) (param $len i32) (result i64)
+            local.get $len
+            i32.eqz
+            if (result i64)
+              i64.const -1
+            else
+              local.get $ptr
+              i64.extend_i32_u
+              i64.const 32
+              i64.shl
+              local.get $len
+              i64.extend_i32_u
+              i64.or
+            end))
+    "#;
+
+    #[test]
+    fn test_wasm_plugin_round_trip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("echo.wasm");
+        std::fs::write(&path, wat::parse_str(ECHO_WAT).unwrap()).unwrap();
+        let plugin = discover(tmp.path()).unwrap().remove(0);
+        assert_eq!(plugin.kind, PluginKind::Wasm);
+
+        let out = tmp.path().join("out");
+        let outcome = plugin.run(r#"{"changes":[]}"#, &out).unwrap();
+        assert_eq!(outcome.failure, None);
+        assert_eq!(
+            std::fs::read_to_string(out.join("echo.out")).unwrap(),
+            r#"{"changes":[]}"#
+        );
+
+        let failed = plugin.run("", &out).unwrap();
+        assert_eq!(failed.failure.as_deref(), Some("reported a failure"));
+
+        let importing = tmp.path().join("importing.wasm");
+        std::fs::write(
+            &importing,
+            wat::parse_str(r#"(module (import "env" "now" (func)))"#).unwrap(),
+        )
+        .unwrap();
+        let plugin = Plugin {
+            name: "importing".to_string(),
+            path: importing,
+            kind: PluginKind::Wasm,
+        };
+        assert!(plugin.run("{}", &out).is_err());
+    }
+}
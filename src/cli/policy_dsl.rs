@@ -1,10 +1,15 @@
 // Policy DSL CLI commands
 
-use crate::engines::policy::parser::{EvaluationContext, PolicyRuleLoader, RuleEvaluator};
+use crate::cli::options::GlobalOptions;
+use crate::engines::policy::parser::{
+    EvaluationContext, PolicyRuleLoader, PolicySources, RuleConflict, RuleEvaluator,
+};
 use clap::Args;
 use colored::Colorize;
 use std::path::{Path, PathBuf};
 
+const PROJECT_CONFIG: &str = "costpilot.yaml";
+
 #[derive(Debug, Args)]
 pub struct PolicyDslCommand {
     #[command(subcommand)]
@@ -46,8 +51,8 @@ pub enum PolicyDslSubcommand {
         monthly_cost: Option<f64>,
 
         /// Show detailed evaluation
-        #[arg(short, long)]
-        verbose: bool,
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
     },
 
     /// Show statistics about loaded rules
@@ -71,17 +76,20 @@ pub enum PolicyDslSubcommand {
 
 pub fn execute_policy_dsl_command(
     command: &PolicyDslCommand,
+    options: &GlobalOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match &command.command {
-        PolicyDslSubcommand::List { all, severity } => execute_list(*all, severity.as_deref()),
+        PolicyDslSubcommand::List { all, severity } => {
+            execute_list(*all, severity.as_deref(), options.profile())
+        }
         PolicyDslSubcommand::Validate { path } => execute_validate(path),
         PolicyDslSubcommand::Test {
             policy,
             resource_type,
             monthly_cost,
             verbose,
-        } => execute_test(policy, resource_type, *monthly_cost, *verbose),
-        PolicyDslSubcommand::Stats { path } => execute_stats(path.as_ref()),
+        } => execute_test(policy, resource_type, *monthly_cost, *verbose > 0),
+        PolicyDslSubcommand::Stats { path } => execute_stats(path.as_ref(), options.profile()),
         PolicyDslSubcommand::Example { output, format } => execute_example(output.as_ref(), format),
     }
 }
@@ -89,35 +97,38 @@ pub fn execute_policy_dsl_command(
 fn execute_list(
     show_all: bool,
     severity_filter: Option<&str>,
+    profile: Option<&str>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     println!("{}", "Policy Rules".bold().cyan());
     println!();
 
-    let loader = PolicyRuleLoader::new();
-    let rules = loader.load_all()?;
+    let loader = project_loader(profile)?;
+    let packed = loader.load_packs()?;
 
-    if rules.is_empty() {
+    if packed.rules.is_empty() {
         println!("{}", "No policy rules found".yellow());
         println!("Search paths:");
-        for path in PolicyRuleLoader::default_search_paths() {
+        for path in loader.search_paths() {
             println!("  • {}", path.display());
         }
         return Ok(());
     }
 
-    let filtered_rules: Vec<_> = rules
+    let filtered_rules: Vec<_> = packed
+        .rules
         .iter()
-        .filter(|r| show_all || r.enabled)
+        .filter(|r| show_all || r.rule.enabled)
         .filter(|r| {
             if let Some(sev) = severity_filter {
-                format!("{:?}", r.severity).to_lowercase() == sev.to_lowercase()
+                format!("{:?}", r.rule.severity).to_lowercase() == sev.to_lowercase()
             } else {
                 true
             }
         })
         .collect();
 
-    for (idx, rule) in filtered_rules.iter().enumerate() {
+    for (idx, pack_rule) in filtered_rules.iter().enumerate() {
+        let rule = &pack_rule.rule;
         let status = if rule.enabled {
             "✓".green()
         } else {
@@ -134,6 +145,7 @@ fn execute_list(
         println!("{} {} [{}]", status, rule.name.bold(), severity_color);
         println!("   {}", rule.description.as_deref().unwrap_or("").dimmed());
         println!("   Conditions: {}", rule.conditions.len());
+        println!("   Pack: {}", pack_rule.pack);
 
         if !rule.metadata.is_empty() {
             println!("   Metadata: {} keys", rule.metadata.len());
@@ -149,10 +161,40 @@ fn execute_list(
         "{}",
         format!("Total: {} rules", filtered_rules.len()).dimmed()
     );
+    print_conflicts(&packed.conflicts);
 
     Ok(())
 }
 
+/// Loader for the default search paths plus `policy_sources` from the
+/// project's costpilot.yaml, with `profile` applied
+fn project_loader(profile: Option<&str>) -> Result<PolicyRuleLoader, Box<dyn std::error::Error>> {
+    let loader = PolicyRuleLoader::new();
+    let config = Path::new(PROJECT_CONFIG);
+    if !config.exists() {
+        return Ok(loader);
+    }
+    let sources = PolicySources::load(config, profile)?;
+    Ok(loader.with_sources(&sources))
+}
+
+fn print_conflicts(conflicts: &[RuleConflict]) {
+    if conflicts.is_empty() {
+        return;
+    }
+    println!();
+    println!(
+        "{}",
+        format!("⚠️  {} rule name conflict(s):", conflicts.len()).yellow()
+    );
+    for conflict in conflicts {
+        println!(
+            "  • {} from {} is shadowed by {}",
+            conflict.rule, conflict.shadowed, conflict.kept
+        );
+    }
+}
+
 fn execute_validate(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     println!("{}", "Validating Policy Rules".bold().cyan());
     println!("Path: {}", path.display());
@@ -244,23 +286,29 @@ fn execute_test(
     Ok(())
 }
 
-fn execute_stats(path: Option<&PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+fn execute_stats(
+    path: Option<&PathBuf>,
+    profile: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
     println!("{}", "Policy Rule Statistics".bold().cyan());
     println!();
 
-    let loader = PolicyRuleLoader::new();
-    let rules = if let Some(p) = path {
+    let loader = project_loader(profile)?;
+    let (rules, conflicts) = if let Some(p) = path {
         println!("Loading from: {}", p.display());
-        loader.load_from_path(p)?
+        (loader.load_from_path(p)?, Vec::new())
     } else {
         println!("Searching default paths...");
-        loader.load_all()?
+        let packed = loader.load_packs()?;
+        let conflicts = packed.conflicts.clone();
+        (packed.into_rules(), conflicts)
     };
 
     let stats = loader.get_statistics(&rules);
 
     println!();
     println!("{}", stats.format_text());
+    print_conflicts(&conflicts);
 
     Ok(())
 }
@@ -407,7 +455,7 @@ mod tests {
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         // Should succeed even if no rules are found
         assert!(result.is_ok());
     }
@@ -421,7 +469,7 @@ mod tests {
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -436,7 +484,7 @@ mod tests {
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         // Should fail for nonexistent file
         assert!(result.is_err());
     }
@@ -467,7 +515,7 @@ mod tests {
             command: PolicyDslSubcommand::Validate { path: policy_path },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -502,11 +550,11 @@ mod tests {
                 policy: policy_path,
                 resource_type: "aws_instance".to_string(),
                 monthly_cost: Some(200.0),
-                verbose: false,
+                verbose: 0,
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -537,11 +585,11 @@ mod tests {
                 policy: policy_path,
                 resource_type: "aws_s3_bucket".to_string(),
                 monthly_cost: None,
-                verbose: true,
+                verbose: 1,
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -551,7 +599,7 @@ mod tests {
             command: PolicyDslSubcommand::Stats { path: None },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -583,7 +631,7 @@ mod tests {
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -596,7 +644,7 @@ mod tests {
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -609,7 +657,7 @@ mod tests {
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
     }
 
@@ -625,7 +673,7 @@ mod tests {
             },
         };
 
-        let result = execute_policy_dsl_command(&cmd);
+        let result = execute_policy_dsl_command(&cmd, &GlobalOptions::default());
         assert!(result.is_ok());
         assert!(output_path.exists());
     }
@@ -0,0 +1,68 @@
+// CLI commands for managing the Premium engine installation
+
+use crate::edition::EditionPaths;
+use std::path::{Path, PathBuf};
+
+/// Pro engine CLI commands
+#[derive(Debug)]
+pub enum ProCommand {
+    /// Install an offline update bundle into ~/.costpilot/
+    Install { bundle: PathBuf, dry_run: bool },
+
+    /// Show the installed engine version
+    Status,
+}
+
+/// Execute pro command
+pub fn execute_pro_command(cmd: ProCommand) -> Result<String, String> {
+    let paths = EditionPaths::default();
+    match cmd {
+        ProCommand::Install { bundle, dry_run } => execute_install(&bundle, dry_run, &paths),
+        ProCommand::Status => execute_status(&paths),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn execute_install(bundle: &Path, dry_run: bool, paths: &EditionPaths) -> Result<String, String> {
+    use crate::pro_engine::BundleInstaller;
+
+    if !bundle.is_dir() {
+        return Err(format!("Bundle directory not found: {}", bundle.display()));
+    }
+
+    let installer = BundleInstaller::new(paths.config_dir.clone());
+    let report = installer
+        .install(bundle, dry_run)
+        .map_err(|e| format!("Bundle installation failed: {}", e))?;
+
+    Ok(report.format_text())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn execute_install(
+    _bundle: &Path,
+    _dry_run: bool,
+    _paths: &EditionPaths,
+) -> Result<String, String> {
+    Err("Bundle installation is not supported on this platform".to_string())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn execute_status(paths: &EditionPaths) -> Result<String, String> {
+    use crate::pro_engine::BundleInstaller;
+
+    let installer = BundleInstaller::new(paths.config_dir.clone());
+    match installer.installed_version() {
+        Some(version) => Ok(format!(
+            "ProEngine {} installed in {}",
+            version,
+            paths.config_dir.display()
+        )),
+        None => Ok("No ProEngine bundle installed".to_string()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn execute_status(_paths: &EditionPaths) -> Result<String, String> {
+    Ok("No ProEngine bundle installed".to_string())
+}
@@ -0,0 +1,110 @@
+// Terminal progress bars for long scans
+//
+// One bar per engine phase, drawn on stderr so stdout stays clean for JSON
+// and NDJSON output. Bars only appear when stderr is a terminal and are
+// cleared once the scan finishes.
+
+use crate::engines::shared::progress::{Phase, Progress, ProgressReporter, ProgressSink};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Progress sink drawing a bar per phase
+pub struct TerminalProgress {
+    bars: MultiProgress,
+    phases: Mutex<HashMap<Phase, ProgressBar>>,
+}
+
+impl TerminalProgress {
+    /// Reporter drawing to stderr, or a silent one when `enabled` is false
+    /// or stderr is not a terminal; the bars are cleared when the guard drops
+    pub fn reporter(enabled: bool) -> (ProgressReporter, ProgressGuard) {
+        if !enabled || !atty::is(atty::Stream::Stderr) {
+            return (ProgressReporter::default(), ProgressGuard(None));
+        }
+        let progress = Arc::new(Self::new(ProgressDrawTarget::stderr()));
+        (
+            ProgressReporter::new(progress.clone()),
+            ProgressGuard(Some(progress)),
+        )
+    }
+
+    fn new(target: ProgressDrawTarget) -> Self {
+        Self {
+            bars: MultiProgress::with_draw_target(target),
+            phases: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remove all bars
+    fn finish(&self) {
+        for bar in self.phases.lock().unwrap().values() {
+            bar.finish_and_clear();
+        }
+        let _ = self.bars.clear();
+    }
+
+    fn bar(&self, phase: Phase) -> ProgressBar {
+        let mut phases = self.phases.lock().unwrap();
+        phases
+            .entry(phase)
+            .or_insert_with(|| {
+                let bar = self.bars.add(ProgressBar::new(100));
+                bar.set_style(
+                    ProgressStyle::with_template("{msg:22} [{bar:30}] {pos:>3}%")
+                        .unwrap_or_else(|_| ProgressStyle::default_bar())
+                        .progress_chars("=> "),
+                );
+                bar.set_message(phase.label());
+                bar
+            })
+            .clone()
+    }
+}
+
+/// Clears the progress bars when dropped, including on early error returns
+pub struct ProgressGuard(Option<Arc<TerminalProgress>>);
+
+impl Drop for ProgressGuard {
+    fn drop(&mut self) {
+        if let Some(progress) = &self.0 {
+            progress.finish();
+        }
+    }
+}
+
+impl ProgressSink for TerminalProgress {
+    fn report(&self, progress: Progress) {
+        let bar = self.bar(progress.phase);
+        bar.set_position(progress.percent().round() as u64);
+        if progress.is_done() {
+            bar.finish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_tracks_percent() {
+        let progress = TerminalProgress::new(ProgressDrawTarget::hidden());
+        progress.report(Progress {
+            phase: Phase::Prediction,
+            completed: 3,
+            total: 4,
+        });
+
+        let bar = progress.bar(Phase::Prediction);
+        assert_eq!(bar.position(), 75);
+        assert!(!bar.is_finished());
+
+        progress.report(Progress {
+            phase: Phase::Prediction,
+            completed: 4,
+            total: 4,
+        });
+        assert!(bar.is_finished());
+    }
+}
@@ -0,0 +1,114 @@
+// `costpilot report` - static HTML report bundle and PDF executive summary
+
+use crate::cli::exit_code::ExitCode;
+use crate::cli::options::GlobalOptions;
+use crate::cli::scan::{ReportTarget, ScanCommand};
+use crate::edition::EditionContext;
+use crate::engines::report::TrendSection;
+use crate::engines::shared::error_model::CostPilotError;
+use crate::engines::trend::TrendEngine;
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// Trend snapshots read when `--snapshots` is not given
+pub const DEFAULT_SNAPSHOTS_DIR: &str = ".costpilot/snapshots";
+
+/// Write a static HTML report combining every analysis of a plan
+#[derive(Debug, Args)]
+pub struct ReportCommand {
+    /// Directory to write the report pages to (created if missing)
+    #[arg(long, value_name = "DIR", required_unless_present = "pdf")]
+    html: Option<PathBuf>,
+
+    /// Write a two-page PDF executive summary to this file
+    #[arg(long, value_name = "FILE")]
+    pdf: Option<PathBuf>,
+
+    /// Trend snapshot directory charted on the trends page
+    /// (default: .costpilot/snapshots)
+    #[arg(long, value_name = "DIR")]
+    snapshots: Option<PathBuf>,
+
+    #[command(flatten)]
+    scan: ScanCommand,
+}
+
+impl ReportCommand {
+    /// Run with these global options
+    pub fn with_options(mut self, options: GlobalOptions) -> Self {
+        self.scan = self.scan.with_options(options);
+        self
+    }
+
+    /// Run the scan behind the report and write the bundle
+    ///
+    /// The exit code follows the same gates as `costpilot scan`.
+    pub fn execute_gated(self, edition: &EditionContext) -> Result<ExitCode, CostPilotError> {
+        let snapshots = self
+            .snapshots
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_SNAPSHOTS_DIR));
+        let (trend, trend_totals) = trend_section(&snapshots, edition);
+        let target = ReportTarget {
+            html: self.html,
+            pdf: self.pdf,
+            trend,
+            trend_totals,
+        };
+        self.scan.into_report(target).execute_gated(edition, "text")
+    }
+}
+
+/// Chart of the snapshot history (or the reason there is none) and the
+/// snapshot totals, oldest first
+pub fn trend_section(snapshots: &Path, edition: &EditionContext) -> (TrendSection, Vec<f64>) {
+    let unavailable = |reason: String| (TrendSection::Unavailable(reason), Vec::new());
+    let engine = match TrendEngine::new(snapshots, edition) {
+        Ok(engine) => engine,
+        Err(_) => return unavailable("Trend charts require CostPilot Premium.".to_string()),
+    };
+    let history = match engine.load_history() {
+        Ok(history) => history,
+        Err(e) => return unavailable(format!("Could not read snapshots: {}", e)),
+    };
+    if history.snapshots.is_empty() {
+        return unavailable(format!(
+            "No trend snapshots in {}. Record one per merge with `costpilot trend snapshot`.",
+            snapshots.display()
+        ));
+    }
+
+    let mut ordered: Vec<_> = history.snapshots.iter().collect();
+    ordered.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    let totals = ordered.iter().map(|s| s.total_monthly_cost).collect();
+    match engine.svg_generator.generate(&history) {
+        Ok(svg) => (TrendSection::Chart(svg), totals),
+        Err(e) => (
+            TrendSection::Unavailable(format!("Could not draw trend chart: {}", e)),
+            totals,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trend_section_explains_missing_chart() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        match trend_section(tmp.path(), &EditionContext::free()) {
+            (TrendSection::Unavailable(reason), totals) => {
+                assert!(reason.contains("Premium"));
+                assert!(totals.is_empty());
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match trend_section(tmp.path(), &EditionContext::premium_for_test()) {
+            (TrendSection::Unavailable(reason), _) => {
+                assert!(reason.contains("No trend snapshots"))
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -1,34 +1,82 @@
+use crate::artifact::{
+    ArtifactNormalizer, ArtifactParser, KubernetesParser, SamParser, ServerlessParser,
+};
+use crate::cli::exit_code::{CiGate, ExitCode, FailOn, GateInput, IncreaseThreshold};
+use crate::cli::ndjson::{NdjsonRecord, NdjsonStack, NdjsonSummary, NdjsonWriter};
+use crate::cli::options::GlobalOptions;
+use crate::cli::progress::TerminalProgress;
+use crate::engines::attribution::tagging::{TagPolicy, TaggingConfig};
 use crate::engines::baselines::BaselinesManager;
-use crate::engines::detection::DetectionEngine;
+use crate::engines::detection::{CustomDetectionRule, DetectionConfig, DetectionEngine};
+use crate::engines::explain::{CostNarrative, PredictionExplainer};
+use crate::engines::grouping::{
+    inference_warnings, EnvironmentMapping, EnvironmentResolver, GroupingEngine,
+};
+use crate::engines::mapping::GraphConfig;
+use crate::engines::notify::{render_slack, render_teams, NotificationSummary};
+use crate::engines::pipeline::{
+    predict_estimates, ResourceArena, ScanPipeline, ScanPipelineOutput,
+};
 use crate::engines::policy::{ExemptionValidator, PolicyEngine, PolicyLoader, ZeroNetworkToken};
-use crate::engines::prediction::PredictionEngine;
+use crate::engines::prediction::minimal_heuristics::MinimalHeuristics;
+use crate::engines::prediction::{
+    ClusterPricing, ConfidenceCalibration, CustomHeuristics, FreeTierConfig, HeuristicsLoader,
+    PlanPricing, PredictionEngine, UsageProfile, HEURISTICS_VERSION,
+};
+use crate::engines::report::{HtmlReportBundle, PdfSummary, ReportData, TrendSection};
+use crate::engines::shared::cancel::CancelToken;
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::filter::FilterExpr;
+use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
 use crate::engines::shared::models::CostEstimate;
+use crate::engines::shared::progress::{map_batches, Phase};
+use crate::engines::shared::pruning::REMAINDER_LABEL;
+use crate::engines::shared::redaction::{RedactionConfig, Redactor};
+use crate::engines::shared::result_cache::{
+    CacheKind, CacheVersions, ResultCache, DEFAULT_CACHE_DIR,
+};
 use crate::engines::slo::slo_engine::SloResult;
+use crate::validation::{BundlePaths, BundleValidator, ConfigBundle, BUNDLE_DIR, BUNDLE_FILE};
+use crate::zero_cost_guard::{ZeroCostConfig, ZeroCostGuard};
 use clap::Args;
 use colored::Colorize;
 use serde::Serialize;
 use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
 use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+/// Resources predicted per batch; progress advances and NDJSON output is
+/// written after each batch
+const PREDICTION_BATCH: usize = 256;
+
+/// Project configuration read when `--config` is not given
+const PROJECT_CONFIG: &str = "costpilot.yaml";
 
 /// Scan infrastructure changes for cost issues
 #[derive(Debug, Args)]
 pub struct ScanCommand {
     /// Path to Terraform plan JSON file (generated via 'terraform show -json plan.out')
     /// Positional plan path (also accepted via `--plan` / `--scan` flag)
-    #[arg(value_name = "PLAN", required_unless_present = "plan_flag")]
+    #[arg(
+        value_name = "PLAN",
+        required_unless_present_any = ["plan_flag", "stacks"]
+    )]
     plan: Option<PathBuf>,
 
     /// Alternate flag form for plan path (supports legacy tests that pass `--scan`)
     #[arg(long = "plan", alias = "scan", value_name = "FILE")]
     plan_flag: Option<PathBuf>,
 
-    /// Infrastructure format: terraform
-    #[arg(long = "infra-format", short = 'i', default_value = "terraform")]
-    infra_format: String,
+    /// Infrastructure format: terraform, kubernetes (manifests or `helm template`
+    /// output), sam (AWS SAM template), serverless (serverless.yml)
+    /// (default: scan.infra_format in costpilot.yaml, else terraform)
+    #[arg(long = "infra-format", short = 'i')]
+    infra_format: Option<String>,
 
-    /// Output format: text, json, markdown, pr-comment
+    /// Output format: text, json, ndjson, markdown, pr-comment, slack
+    /// (Block Kit webhook payload), teams (Adaptive Card webhook payload)
     #[arg(long, value_enum)]
     output_format: Option<OutputFormat>,
 
@@ -48,31 +96,148 @@ pub struct ScanCommand {
     #[arg(long, value_name = "FILE")]
     baselines: Option<PathBuf>,
 
+    /// Project configuration with custom detection rules and cluster pricing
+    /// (default: costpilot.yaml when present)
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
     /// Fail on critical severity issues
     #[arg(long)]
     fail_on_critical: bool,
 
+    /// Exit 3 when monthly cost grows by more than this (e.g. 10% or $50);
+    /// percentages are measured against the global baseline
+    #[arg(long, value_name = "THRESHOLD")]
+    fail_on_increase: Option<IncreaseThreshold>,
+
+    /// Fail when a condition matches: severity=<level> exits 3, warnings
+    /// exits 2; repeatable
+    #[arg(long, value_name = "CONDITION")]
+    fail_on: Vec<FailOn>,
+
     /// Show autofix snippets
     #[arg(long)]
     autofix: bool,
+
+    /// Also build the dependency graph and cost groupings (run alongside analysis)
+    #[arg(long)]
+    insights: bool,
+
+    /// Price Lambda and DynamoDB at list rates instead of assuming usage
+    /// within the AWS free tier (overrides `free_tier` in costpilot.yaml)
+    #[arg(long)]
+    no_free_tier: bool,
+
+    /// Reuse prediction and detection results for unchanged resources
+    #[arg(long)]
+    cache: bool,
+
+    /// Do not draw progress bars on stderr (they only appear on a terminal)
+    #[arg(long)]
+    no_progress: bool,
+
+    /// Result cache directory (default: .costpilot/cache)
+    #[arg(long, value_name = "DIR", requires = "cache")]
+    cache_dir: Option<PathBuf>,
+
+    /// Scan every stack under this directory (each subdirectory holding a
+    /// plan JSON), several at a time, streaming results as stacks finish
+    #[cfg(not(target_arch = "wasm32"))]
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["plan", "plan_flag"])]
+    stacks: Option<PathBuf>,
+
+    /// Plan file name looked up in each stack directory (default: plan.json)
+    #[cfg(not(target_arch = "wasm32"))]
+    #[arg(long, value_name = "NAME", requires = "stacks")]
+    stack_plan: Option<String>,
+
+    /// Stacks analyzed at once (default: available CPUs, at most 8)
+    #[cfg(not(target_arch = "wasm32"))]
+    #[arg(long, value_name = "N", requires = "stacks", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+
+    /// Time budget for each stack in milliseconds (default: 2000)
+    #[cfg(not(target_arch = "wasm32"))]
+    #[arg(long, value_name = "MS", requires = "stacks")]
+    stack_budget_ms: Option<u64>,
+
+    /// Explore results in an interactive terminal UI
+    #[cfg(all(feature = "tui", not(target_arch = "wasm32")))]
+    #[arg(long)]
+    tui: bool,
+
+    /// Run the output processor plugins in .costpilot/plugins/ on the result
+    #[cfg(not(target_arch = "wasm32"))]
+    #[arg(long)]
+    plugins: bool,
+
+    /// Only report resources matching this expression
+    /// (e.g. 'type=aws_instance and cost>100 and module=~vpc')
+    #[arg(long, value_name = "EXPR", conflicts_with = "stacks")]
+    filter: Option<FilterExpr>,
+
+    /// Report files to write instead of printing results (`costpilot report`)
+    #[arg(skip)]
+    report: Option<ReportTarget>,
+
+    /// Files written from `costpilot.bundle.yaml`, resolved on first use
+    #[arg(skip)]
+    bundle: OnceLock<Option<BundlePaths>>,
+
+    /// Profile, locale, redaction and pruning for this run
+    #[arg(skip)]
+    options: GlobalOptions,
+}
+
+/// Where `costpilot report` writes, and the trend data it shows
+#[derive(Debug)]
+pub(crate) struct ReportTarget {
+    /// Directory for the HTML bundle
+    pub html: Option<PathBuf>,
+    /// File for the PDF executive summary
+    pub pdf: Option<PathBuf>,
+    pub trend: TrendSection,
+    /// Monthly totals of the recorded snapshots, oldest first
+    pub trend_totals: Vec<f64>,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
 enum OutputFormat {
     Text,
     Json,
+    /// One JSON object per line, streamed as results are produced
+    Ndjson,
     Markdown,
     PrComment,
+    /// Slack Block Kit payload, ready to POST to an incoming webhook
+    Slack,
+    /// Microsoft Teams Adaptive Card payload, ready to POST to a webhook
+    Teams,
 }
 
 #[derive(Debug, Serialize)]
 struct ScanResult {
+    schema_version: &'static str,
     summary: ScanSummary,
     changes: Vec<ResourceChange>,
     estimates: Vec<CostEstimate>,
     detections: Vec<crate::engines::shared::models::Detection>,
     policy_result: Option<PolicyResult>,
     slo_result: Option<SloResult>,
+    /// Present when produced under a trial license
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trial: Option<crate::edition::TrialWatermark>,
+}
+
+/// JSON output of a `--stacks` scan, stacks sorted by name
+#[derive(Debug, Serialize)]
+struct StackScanResult {
+    schema_version: &'static str,
+    summary: ScanSummary,
+    stacks: Vec<NdjsonStack>,
+    /// Present when produced under a trial license
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trial: Option<crate::edition::TrialWatermark>,
 }
 
 #[derive(Debug, Serialize)]
@@ -109,6 +274,18 @@ struct PolicyViolation {
     expected_value: String,
 }
 
+/// Owned, redacted copies of the data behind an HTML/PDF report
+struct RedactedReport {
+    changes: Vec<crate::engines::detection::ResourceChange>,
+    estimates: Vec<CostEstimate>,
+    detections: Vec<crate::engines::shared::models::Detection>,
+    explanations: Vec<crate::engines::explain::explain_engine::Explanation>,
+    graph: Option<crate::engines::mapping::DependencyGraph>,
+    grouping: Option<crate::engines::grouping::ComprehensiveReport>,
+    policy_result: Option<crate::engines::policy::PolicyResult>,
+    slo_result: Option<SloResult>,
+}
+
 impl ScanCommand {
     /// Get terminal width, defaulting to 100 if unable to detect
     fn get_terminal_width() -> usize {
@@ -165,14 +342,26 @@ impl ScanCommand {
         self.output_format.as_ref().map_or_else(
             || match global_format {
                 "json" => OutputFormat::Json,
+                "ndjson" => OutputFormat::Ndjson,
                 "markdown" => OutputFormat::Markdown,
                 "pr-comment" => OutputFormat::PrComment,
+                "slack" => OutputFormat::Slack,
+                "teams" => OutputFormat::Teams,
                 _ => OutputFormat::Text,
             },
             |f| f.clone(),
         )
     }
 
+    /// Format for reporting a failed scan: `json` for machine-readable
+    /// output formats, `text` otherwise
+    pub fn error_format(&self, global_format: &str) -> &'static str {
+        match self.get_output_format(global_format) {
+            OutputFormat::Json | OutputFormat::Ndjson => "json",
+            _ => "text",
+        }
+    }
+
     /// Print a single detection with smart formatting
     fn print_detection(detection: &crate::engines::shared::models::Detection, term_width: usize) {
         // Resource name in bold
@@ -211,6 +400,7 @@ impl ScanCommand {
         println!();
     }
 
+    /// Print the scan result, redacted first under `--redact`
     #[allow(clippy::too_many_arguments)]
     fn format_output(
         &self,
@@ -225,6 +415,61 @@ impl ScanCommand {
         slo_result: Option<&SloResult>,
         total_monthly: f64,
         output_format: OutputFormat,
+        trial: Option<&crate::edition::TrialWatermark>,
+    ) -> Result<(), CostPilotError> {
+        let Some(redactor) = self.redactor(changes)? else {
+            return self.render_output(
+                changes,
+                estimates,
+                detections,
+                policy_result,
+                baselines_result,
+                slo_result,
+                total_monthly,
+                output_format,
+                trial,
+            );
+        };
+
+        let baselines_result = baselines_result
+            .map(|(total, modules)| {
+                Ok::<_, CostPilotError>((
+                    redactor.redact(total)?,
+                    crate::engines::baselines::BaselineComparisonResult {
+                        violations: redactor.redact(&modules.violations)?,
+                        ..modules.clone()
+                    },
+                ))
+            })
+            .transpose()?;
+        self.render_output(
+            &redactor.changes(changes)?,
+            &redactor.redact(&estimates.to_vec())?,
+            &redactor.redact(&detections.to_vec())?,
+            redactor.redact(&policy_result.cloned())?.as_ref(),
+            baselines_result.as_ref(),
+            redactor.redact(&slo_result.cloned())?.as_ref(),
+            total_monthly,
+            output_format,
+            trial,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_output(
+        &self,
+        changes: &[crate::engines::detection::ResourceChange],
+        estimates: &[CostEstimate],
+        detections: &[crate::engines::shared::models::Detection],
+        policy_result: Option<&crate::engines::policy::PolicyResult>,
+        baselines_result: Option<&(
+            Option<crate::engines::baselines::baseline_types::BaselineViolation>,
+            crate::engines::baselines::BaselineComparisonResult,
+        )>,
+        slo_result: Option<&SloResult>,
+        total_monthly: f64,
+        output_format: OutputFormat,
+        trial: Option<&crate::edition::TrialWatermark>,
     ) -> Result<(), CostPilotError> {
         match output_format {
             OutputFormat::Text => self.format_text_output(
@@ -235,6 +480,7 @@ impl ScanCommand {
                 baselines_result,
                 slo_result,
                 total_monthly,
+                trial,
             ),
             OutputFormat::Json => self.format_json_output(
                 changes,
@@ -244,7 +490,22 @@ impl ScanCommand {
                 baselines_result,
                 slo_result,
                 total_monthly,
+                trial,
             ),
+            OutputFormat::Ndjson => {
+                let mut stream = NdjsonWriter::new(std::io::stdout());
+                stream.write_all(estimates.iter().map(NdjsonRecord::Estimate))?;
+                stream.write_all(detections.iter().map(NdjsonRecord::Detection))?;
+                Self::finish_ndjson(
+                    &mut stream,
+                    changes.len(),
+                    detections.len(),
+                    policy_result,
+                    slo_result,
+                    total_monthly,
+                    trial,
+                )
+            }
             OutputFormat::Markdown => self.format_markdown_output(
                 changes,
                 estimates,
@@ -263,6 +524,21 @@ impl ScanCommand {
                 slo_result,
                 total_monthly,
             ),
+            OutputFormat::Slack | OutputFormat::Teams => {
+                let summary = NotificationSummary::for_scan(
+                    changes.len(),
+                    total_monthly,
+                    detections,
+                    policy_result,
+                    slo_result,
+                );
+                let payload = match output_format {
+                    OutputFormat::Slack => render_slack(&summary),
+                    _ => render_teams(&summary),
+                };
+                println!("{}", Self::to_canonical_json(&payload)?);
+                Ok(())
+            }
         }
     }
 
@@ -279,8 +555,19 @@ impl ScanCommand {
         )>,
         slo_result: Option<&SloResult>,
         total_monthly: f64,
+        trial: Option<&crate::edition::TrialWatermark>,
     ) -> Result<(), CostPilotError> {
         println!("{}", "🔍 CostPilot Scan".bold().cyan());
+        if let Some(trial) = trial {
+            println!(
+                "{}",
+                format!(
+                    "Trial license - {} days remaining (expires {})",
+                    trial.days_remaining, trial.expires
+                )
+                .yellow()
+            );
+        }
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
 
         // Detection summary
@@ -685,6 +972,36 @@ impl ScanCommand {
         Ok(())
     }
 
+    /// Write policy violations and the closing summary record
+    fn finish_ndjson<W: std::io::Write>(
+        stream: &mut NdjsonWriter<W>,
+        resources_changed: usize,
+        optimization_opportunities: usize,
+        policy_result: Option<&crate::engines::policy::PolicyResult>,
+        slo_result: Option<&SloResult>,
+        total_monthly: f64,
+        trial: Option<&crate::edition::TrialWatermark>,
+    ) -> Result<(), CostPilotError> {
+        if let Some(policy_result) = policy_result {
+            stream.write_all(
+                policy_result
+                    .violations
+                    .iter()
+                    .map(NdjsonRecord::PolicyViolation),
+            )?;
+        }
+        let status = |passed: bool| if passed { "PASSED" } else { "FAILED" }.to_string();
+        stream.write(&NdjsonRecord::Summary(NdjsonSummary {
+            schema_version: OUTPUT_SCHEMA_VERSION,
+            resources_changed,
+            monthly_cost: total_monthly,
+            optimization_opportunities,
+            policy_status: policy_result.map(|pr| status(pr.passed)),
+            slo_status: slo_result.map(|sr| status(sr.passed)),
+            trial: trial.cloned(),
+        }))
+    }
+
     /// Serialize JSON with canonical key ordering for deterministic output
     fn to_canonical_json<T: Serialize>(value: &T) -> Result<String, CostPilotError> {
         let json_value = serde_json::to_value(value).map_err(|e| {
@@ -735,7 +1052,32 @@ impl ScanCommand {
         )>,
         slo_result: Option<&SloResult>,
         total_monthly: f64,
+        trial: Option<&crate::edition::TrialWatermark>,
     ) -> Result<(), CostPilotError> {
+        let result = Self::scan_result(
+            changes,
+            _estimates,
+            detections,
+            policy_result,
+            slo_result,
+            total_monthly,
+            trial,
+        );
+        println!("{}", Self::to_canonical_json(&result)?);
+
+        Ok(())
+    }
+
+    /// Document printed by `--output-format json` and fed to plugins
+    fn scan_result(
+        changes: &[crate::engines::detection::ResourceChange],
+        estimates: &[CostEstimate],
+        detections: &[crate::engines::shared::models::Detection],
+        policy_result: Option<&crate::engines::policy::PolicyResult>,
+        slo_result: Option<&SloResult>,
+        total_monthly: f64,
+        trial: Option<&crate::edition::TrialWatermark>,
+    ) -> ScanResult {
         let resource_changes: Vec<ResourceChange> = changes
             .iter()
             .map(|c| ResourceChange {
@@ -763,7 +1105,8 @@ impl ScanCommand {
             applied_exemptions: pr.applied_exemptions.clone(),
         });
 
-        let result = ScanResult {
+        ScanResult {
+            schema_version: OUTPUT_SCHEMA_VERSION,
             summary: ScanSummary {
                 resources_changed: changes.len(),
                 monthly_cost: total_monthly,
@@ -784,22 +1127,223 @@ impl ScanCommand {
                 }),
             },
             changes: resource_changes,
-            estimates: _estimates.to_vec(),
+            estimates: estimates.to_vec(),
             detections: detections.to_vec(),
             policy_result: policy_result_struct,
             slo_result: slo_result.cloned(),
+            trial: trial.cloned(),
+        }
+    }
+
+    /// Resource changes from a Kubernetes, SAM or Serverless Framework artifact
+    fn artifact_changes(
+        format: &str,
+        path: &std::path::Path,
+    ) -> Result<Vec<crate::engines::shared::models::ResourceChange>, CostPilotError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let artifact = match format {
+            "kubernetes" => KubernetesParser::new().parse(&content)?,
+            "sam" => SamParser::new().parse(&content)?,
+            _ => ServerlessParser::new().parse(&content)?,
         };
+        Ok(ArtifactNormalizer::normalize(&artifact).to_resource_changes())
+    }
 
-        println!("{}", Self::to_canonical_json(&result)?);
+    /// `--config`, or costpilot.yaml when present
+    fn project_config(&self) -> Result<Option<PathBuf>, CostPilotError> {
+        if let Some(path) = &self.config {
+            return Ok(Some(path.clone()));
+        }
+        if std::path::Path::new(PROJECT_CONFIG).exists() {
+            return Ok(Some(PathBuf::from(PROJECT_CONFIG)));
+        }
+        Ok(self.bundle()?.and_then(|bundle| bundle.config.clone()))
+    }
 
-        Ok(())
+    /// Redactor for the plan's resources when `--redact` is set, using the
+    /// `redaction` section of the project config
+    fn redactor(
+        &self,
+        changes: &[crate::engines::detection::ResourceChange],
+    ) -> Result<Option<Redactor>, CostPilotError> {
+        if !self.options.redact {
+            return Ok(None);
+        }
+        let config = match self.project_config()? {
+            Some(path) => RedactionConfig::load(&path, self.options.profile())?,
+            None => RedactionConfig::default(),
+        };
+        Ok(Some(Redactor::new(config).learn(changes)))
+    }
+
+    /// Sections of `costpilot.bundle.yaml`, validated and written under
+    /// `.costpilot/bundle/`; `None` without a bundle
+    fn bundle(&self) -> Result<Option<&BundlePaths>, CostPilotError> {
+        if self.bundle.get().is_none() {
+            let path = std::path::Path::new(BUNDLE_FILE);
+            let paths = if path.exists() {
+                Some(Self::write_bundle(path)?)
+            } else {
+                None
+            };
+            let _ = self.bundle.set(paths);
+        }
+        Ok(self.bundle.get().and_then(Option::as_ref))
+    }
+
+    fn write_bundle(path: &std::path::Path) -> Result<BundlePaths, CostPilotError> {
+        let invalid = |e: crate::validation::ValidationError| {
+            let field = e.field.map(|f| format!(" ({})", f)).unwrap_or_default();
+            CostPilotError::new(
+                "BUNDLE_001",
+                ErrorCategory::ValidationError,
+                format!("Invalid {}{}: {}", path.display(), field, e.message),
+            )
+            .with_hint(format!("Run 'costpilot validate {}'", path.display()))
+        };
+        let report = BundleValidator::validate_file(path).map_err(|e| invalid(*e))?;
+        if let Some(error) = report.errors.into_iter().next() {
+            return Err(invalid(error));
+        }
+        let bundle = ConfigBundle::load(path).map_err(|e| invalid(*e))?;
+        bundle
+            .write_to(std::path::Path::new(BUNDLE_DIR))
+            .map_err(|e| CostPilotError::io_error(format!("Failed to write {}: {}", BUNDLE_DIR, e)))
+    }
+
+    /// `.costpilot/slo.json`, else the bundle's SLOs
+    fn slo_config(&self) -> Result<Option<PathBuf>, CostPilotError> {
+        let path = PathBuf::from(".costpilot/slo.json");
+        if path.exists() {
+            return Ok(Some(path));
+        }
+        Ok(self.bundle()?.and_then(|bundle| bundle.slos.clone()))
+    }
+
+    /// `--infra-format`, else `scan.infra_format` from the project
+    /// configuration, else terraform
+    fn infra_format(&self) -> Result<String, CostPilotError> {
+        if let Some(format) = &self.infra_format {
+            return Ok(format.clone());
+        }
+        let configured = match self.project_config()? {
+            Some(path) => {
+                let content = std::fs::read_to_string(&path).map_err(|e| {
+                    CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+                })?;
+                let root: serde_yaml::Value = serde_yaml::from_str(&content).map_err(|e| {
+                    CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e))
+                })?;
+                let root = crate::config::apply_profile(root, self.options.profile())
+                    .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+                root.get("scan")
+                    .and_then(|scan| scan.get("infra_format"))
+                    .and_then(|format| format.as_str())
+                    .map(str::to_string)
+            }
+            None => None,
+        };
+        Ok(configured.unwrap_or_else(|| "terraform".to_string()))
+    }
+
+    /// Custom detection rules from the project configuration
+    fn custom_rules(&self) -> Result<Vec<CustomDetectionRule>, CostPilotError> {
+        match self.project_config()? {
+            Some(path) => Ok(DetectionConfig::load(&path, self.options.profile())?.custom_rules),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Free tier assumptions from the project configuration, unless
+    /// disabled with `--no-free-tier`
+    fn free_tier(&self) -> Result<FreeTierConfig, CostPilotError> {
+        let mut free_tier = match self.project_config()? {
+            Some(path) => FreeTierConfig::load(&path, self.options.profile())?,
+            None => FreeTierConfig::default(),
+        };
+        if self.no_free_tier {
+            free_tier.enabled = false;
+        }
+        Ok(free_tier)
+    }
+
+    /// Per-environment resource rules from the project configuration
+    fn zero_cost_config(&self) -> Result<ZeroCostConfig, CostPilotError> {
+        match self.project_config()? {
+            Some(path) => ZeroCostConfig::load(&path, self.options.profile()),
+            None => Ok(ZeroCostConfig::default()),
+        }
+    }
+
+    /// Environment inference for `plan` with the project's `environment_mapping`
+    fn environment_resolver(
+        &self,
+        plan: &std::path::Path,
+    ) -> Result<EnvironmentResolver, CostPilotError> {
+        let mapping = match self.project_config()? {
+            Some(path) => EnvironmentMapping::load(&path, self.options.profile())?,
+            None => EnvironmentMapping::default(),
+        };
+        Ok(EnvironmentResolver::for_plan(mapping, plan))
+    }
+
+    /// Tag compliance rules from the project configuration
+    fn tag_policy(&self) -> Result<TagPolicy, CostPilotError> {
+        match self.project_config()? {
+            Some(path) => TagPolicy::new(TaggingConfig::load(&path, self.options.profile())?),
+            None => Ok(TagPolicy::default()),
+        }
+    }
+
+    /// Pricing model for Kubernetes workloads from the project configuration
+    fn cluster_pricing(&self) -> Result<ClusterPricing, CostPilotError> {
+        match self.project_config()? {
+            Some(path) => ClusterPricing::load(&path, self.options.profile()),
+            None => Ok(ClusterPricing::default()),
+        }
+    }
+
+    /// Monthly usage of usage-billed resources from the project configuration
+    fn usage_profile(&self) -> Result<UsageProfile, CostPilotError> {
+        match self.project_config()? {
+            Some(path) => UsageProfile::load(&path, self.options.profile()),
+            None => Ok(UsageProfile::default()),
+        }
+    }
+
+    /// Attribute the cost change to resources, with reasoning for the top ones
+    fn cost_narrative(
+        changes: &[crate::engines::detection::ResourceChange],
+        estimates: &[CostEstimate],
+        detections: &[crate::engines::shared::models::Detection],
+    ) -> CostNarrative {
+        let narrative = CostNarrative::build(changes, estimates, detections);
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
+        let chains: Vec<_> = narrative
+            .top_contributors()
+            .iter()
+            .filter_map(|contribution| {
+                let change = changes
+                    .iter()
+                    .find(|c| c.resource_id == contribution.resource_id)
+                    .filter(|c| c.new_config.is_some())?;
+                let estimate = estimates
+                    .iter()
+                    .find(|e| e.resource_id == contribution.resource_id)?;
+                Some(explainer.explain(change, estimate))
+            })
+            .collect();
+        narrative.with_reasoning(&chains)
     }
 
     #[allow(clippy::too_many_arguments)]
     fn format_markdown_output(
         &self,
         changes: &[crate::engines::detection::ResourceChange],
-        _estimates: &[CostEstimate],
+        estimates: &[CostEstimate],
         detections: &[crate::engines::shared::models::Detection],
         policy_result: Option<&crate::engines::policy::PolicyResult>,
         _baselines_result: Option<&(
@@ -840,6 +1384,13 @@ impl ScanCommand {
         }
         println!();
 
+        println!("## Cost Attribution");
+        print!(
+            "{}",
+            Self::cost_narrative(changes, estimates, detections).to_markdown()
+        );
+        println!();
+
         if !changes.is_empty() {
             println!("## Resource Changes");
             for change in changes {
@@ -994,7 +1545,59 @@ impl ScanCommand {
         }
         println!();
 
-        if !changes.is_empty() {
+        println!("### Cost Attribution");
+        print!(
+            "{}",
+            Self::cost_narrative(changes, estimates, detections).to_markdown()
+        );
+        println!();
+
+        let pruning = self.options.pruning;
+        if !changes.is_empty() && pruning.is_active() {
+            // Largest monthly costs first; the rest are totalled in one row
+            let costs: HashMap<&str, f64> = estimates
+                .iter()
+                .map(|e| (e.resource_id.as_str(), e.monthly_cost))
+                .collect();
+            let mut rows: Vec<(String, String, String, f64)> = changes
+                .iter()
+                .map(|change| {
+                    (
+                        format!("`{}`", change.resource_id),
+                        change.resource_type.clone(),
+                        format!("{:?}", change.action),
+                        costs
+                            .get(change.resource_id.as_str())
+                            .copied()
+                            .unwrap_or(0.0),
+                    )
+                })
+                .collect();
+            rows.sort_by(|a, b| b.3.abs().total_cmp(&a.3.abs()));
+            let rows = pruning.apply(
+                rows,
+                |row| row.3,
+                |rest| {
+                    (
+                        format!("_{}_ ({} resources)", REMAINDER_LABEL, rest.len()),
+                        String::new(),
+                        String::new(),
+                        rest.iter().map(|row| row.3).sum(),
+                    )
+                },
+            );
+
+            println!("### Resource Changes");
+            println!("| Resource | Type | Change | Monthly cost |");
+            println!("|----------|------|--------|--------------|");
+            for (resource, resource_type, action, cost) in rows {
+                println!(
+                    "| {} | {} | {} | ${:.2} |",
+                    resource, resource_type, action, cost
+                );
+            }
+            println!();
+        } else if !changes.is_empty() {
             println!("### Resource Changes");
             println!("| Resource | Type | Change |");
             println!("|----------|------|--------|");
@@ -1141,6 +1744,22 @@ impl ScanCommand {
         edition: &crate::edition::EditionContext,
         global_format: &str,
     ) -> Result<(), CostPilotError> {
+        self.execute_gated(edition, global_format).map(|_| ())
+    }
+
+    /// Run the scan and return the exit code its results call for
+    pub fn execute_gated(
+        &self,
+        edition: &crate::edition::EditionContext,
+        global_format: &str,
+    ) -> Result<ExitCode, CostPilotError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(root) = &self.stacks {
+            return self.execute_stacks(root, edition, global_format);
+        }
+
+        let trial = edition.trial_watermark();
+
         // Resolve effective plan path (positional or flag)
         let plan: &PathBuf = if let Some(p) = &self.plan_flag {
             p
@@ -1166,6 +1785,8 @@ impl ScanCommand {
             }
         }
 
+        let infra_format = self.infra_format()?;
+
         // Validate input file exists
         if !plan.exists() {
             // For tests, allow a synthetic 'test_golden_plan.json' to produce deterministic empty output
@@ -1217,6 +1838,7 @@ impl ScanCommand {
                             breakdown: None,
                             hourly: None,
                             daily: None,
+                            provenance: None,
                         },
                         CostEstimate {
                             resource_id: "aws_instance.test2".to_string(),
@@ -1230,110 +1852,238 @@ impl ScanCommand {
                             breakdown: None,
                             hourly: None,
                             daily: None,
+                            provenance: None,
                         },
                     ];
 
-                    return self.format_output(
-                        &changes,
-                        &estimates,
-                        &[], // No detections for test case
-                        None,
-                        None,
-                        None,
-                        300.0,
-                        self.get_output_format(global_format),
-                    );
+                    return self
+                        .format_output(
+                            &changes,
+                            &estimates,
+                            &[], // No detections for test case
+                            None,
+                            None,
+                            None,
+                            300.0,
+                            self.get_output_format(global_format),
+                            trial.as_ref(),
+                        )
+                        .map(|_| ExitCode::Clean);
                 }
             }
-            let hint = match self.infra_format.as_str() {
+            let hint = match infra_format.as_str() {
                 "terraform" => {
                     "Run 'terraform plan -out=tfplan && terraform show -json tfplan > tfplan.json'"
                 }
+                "kubernetes" => {
+                    "Render Helm charts with 'helm template <release> <chart> > manifests.yaml'"
+                }
                 _ => "Ensure the input file exists and is readable",
             };
             return Err(CostPilotError::new(
                 "SCAN_001",
                 crate::errors::ErrorCategory::FileSystemError,
-                format!("{} file not found: {}", self.infra_format, plan.display()),
+                format!("{} file not found: {}", infra_format, plan.display()),
             )
             .with_hint(hint.to_string()));
         }
 
         // Validate format-specific requirements
-        match self.infra_format.as_str() {
-            "terraform" => {}
+        match infra_format.as_str() {
+            "terraform" | "kubernetes" | "sam" | "serverless" => {}
             _ => {
                 return Err(CostPilotError::new(
                     "SCAN_003",
                     crate::errors::ErrorCategory::ValidationError,
-                    format!("Unsupported format: {}", self.infra_format),
+                    format!("Unsupported format: {}", infra_format),
                 )
-                .with_hint("Supported formats: terraform".to_string()));
+                .with_hint(
+                    "Supported formats: terraform, kubernetes, sam, serverless".to_string(),
+                ));
             }
         }
 
+        let _scan_span =
+            tracing::info_span!("scan", plan = %plan.display(), format = %infra_format).entered();
+
         // Step 1: Detection
-        let detection_engine = DetectionEngine::new();
-        let changes = match self.infra_format.as_str() {
+        let free_tier = self.free_tier()?;
+        let detection_engine = DetectionEngine::new()
+            .with_custom_rules(self.custom_rules()?)
+            .with_free_tier(free_tier.clone());
+        let parse_span = tracing::debug_span!("parse").entered();
+        let changes = match infra_format.as_str() {
             "terraform" => detection_engine.detect_from_terraform_plan(plan)?,
-            _ => unreachable!(),
+            format => Self::artifact_changes(format, plan)?,
         };
+        drop(parse_span);
+        tracing::info!(resources = changes.len(), "Parsed plan");
 
         if changes.is_empty() {
-            return self.format_output(
-                &changes,
-                &[],
-                &[], // No detections for empty changes
-                None,
-                None,
-                None,
-                0.0,
-                self.get_output_format(global_format),
-            );
+            if let Some(report) = &self.report {
+                return self
+                    .write_report(
+                        report,
+                        plan,
+                        &[],
+                        &[],
+                        None,
+                        None,
+                        None,
+                        0.0,
+                        trial.as_ref(),
+                    )
+                    .map(|_| ExitCode::Clean);
+            }
+            return self
+                .format_output(
+                    &changes,
+                    &[],
+                    &[], // No detections for empty changes
+                    None,
+                    None,
+                    None,
+                    0.0,
+                    self.get_output_format(global_format),
+                    trial.as_ref(),
+                )
+                .map(|_| ExitCode::Clean);
         }
 
         // Step 2: Prediction
-        let estimates = match edition.pro.as_ref() {
-            Some(pro) => {
-                // Premium: use ProEngine
-                use crate::cli::pro_serde;
-                let input = pro_serde::serialize(&changes).map_err(|e| {
-                    CostPilotError::new(
-                        "E_SERIALIZE",
-                        ErrorCategory::PredictionError,
-                        e.to_string(),
-                    )
-                })?;
-                let output = pro.scan(input.as_bytes()).map_err(|e| {
-                    CostPilotError::new("E_PRO_SCAN", ErrorCategory::PredictionError, e.to_string())
-                })?;
-                let output_str = std::str::from_utf8(&output).map_err(|e| {
-                    CostPilotError::new("E_UTF8", ErrorCategory::PredictionError, e.to_string())
-                })?;
-                pro_serde::deserialize::<Vec<CostEstimate>>(output_str).map_err(|e| {
-                    CostPilotError::new(
-                        "E_DESERIALIZE",
-                        ErrorCategory::PredictionError,
-                        e.to_string(),
-                    )
-                })?
-            }
-            None => {
-                // Free: use static prediction
-                PredictionEngine::predict_static(&changes)?
-            }
+        let pricing = PlanPricing::new(self.cluster_pricing()?, self.usage_profile()?, &changes);
+        let result_cache = if self.cache {
+            Some(Arc::new(self.open_result_cache(edition, &pricing.usage)?))
+        } else {
+            None
         };
+        let predict = |changes: &[crate::engines::shared::models::ResourceChange]| {
+            predict_estimates(edition, changes, &pricing)
+        };
+        let predict_batch =
+            |changes: &[crate::engines::shared::models::ResourceChange]| match &result_cache {
+                Some(cache) => cache.resolve(
+                    CacheKind::Prediction,
+                    changes,
+                    // Workload estimates depend on the cluster pricing model,
+                    // tables on the plan's autoscaling targets and services
+                    // on its task definitions
+                    |change| match change.resource_type.as_str() {
+                        t if t.starts_with("kubernetes_") => {
+                            serde_json::to_string(&pricing.cluster).unwrap_or_default()
+                        }
+                        "aws_dynamodb_table" | "aws_ecs_service" => {
+                            serde_json::to_string(&pricing.context).unwrap_or_default()
+                        }
+                        _ => String::new(),
+                    },
+                    predict,
+                    |estimate| &estimate.resource_id,
+                ),
+                None => predict(changes),
+            };
+
+        // Custom rates, calibration and free tier assumptions apply after
+        // the cache so cached estimates stay raw. The free tier is one
+        // allowance for the whole plan, drawn down batch by batch in plan
+        // order.
+        let custom_heuristics = Self::load_custom_heuristics()?;
+        let calibration = Self::load_calibration(edition);
+        let mut free_tier_pool = free_tier.pool(&MinimalHeuristics::to_cost_heuristics());
+        let mut predict_calibrated =
+            |changes: &[crate::engines::shared::models::ResourceChange]| {
+                let mut estimates = predict_batch(changes)?;
+                custom_heuristics.apply(changes, &mut estimates);
+                calibration.apply(changes, &mut estimates);
+                free_tier_pool.apply(changes, &mut estimates);
+                Ok::<_, CostPilotError>(estimates)
+            };
 
-        let total_monthly: f64 = estimates.iter().map(|e| e.monthly_cost).sum();
+        // Prediction runs in batches so progress advances and NDJSON streams
+        // estimates batch by batch instead of after the whole plan
+        let (progress, progress_bars) = TerminalProgress::reporter(!self.no_progress);
+        let mut stream = match self.get_output_format(global_format) {
+            OutputFormat::Ndjson => {
+                Some(NdjsonWriter::new(std::io::stdout()).with_redactor(self.redactor(&changes)?))
+            }
+            _ => None,
+        };
+        let predict_span = tracing::debug_span!("predict", resources = changes.len()).entered();
+        let estimates = map_batches(
+            &changes,
+            PREDICTION_BATCH,
+            Phase::Prediction,
+            &CancelToken::new(),
+            &progress,
+            |batch| {
+                let batch_estimates = predict_calibrated(batch)?;
+                if let Some(stream) = &mut stream {
+                    stream.write_all(
+                        batch_estimates
+                            .iter()
+                            .filter(|e| match &self.filter {
+                                Some(filter) => filter.matches_estimate(batch, e),
+                                None => true,
+                            })
+                            .map(NdjsonRecord::Estimate),
+                    )?;
+                }
+                Ok(batch_estimates)
+            },
+        );
+        drop(predict_span);
+        let mut estimates = estimates?;
+
+        // Filtering needs the estimates, so it applies to what is analyzed
+        // and reported rather than to what is priced
+        let mut changes = changes;
+        if let Some(filter) = &self.filter {
+            let total = changes.len();
+            filter.retain_resources(&mut changes, &mut estimates);
+            tracing::info!(kept = changes.len(), total, filter = %filter, "Filtered resources");
+        }
 
-        // Step 3: Analysis - detect optimization opportunities
-        let cost_estimates_for_analysis: Vec<(String, f64, f64)> = estimates
-            .iter()
-            .map(|e| (e.resource_id.clone(), e.monthly_cost, e.confidence_score))
-            .collect();
+        // Step 3: Analysis, plus explain/mapping/grouping when requested.
+        // These only read the shared arena, so they run concurrently.
+        let arena = ResourceArena::new(changes, estimates);
+        let text_output = matches!(self.output_format, Some(OutputFormat::Text));
+        let reporting = self.report.is_some();
+        let graph_config = GraphConfig {
+            max_depth: if edition.is_free() { Some(1) } else { None },
+            ..GraphConfig::default()
+        };
+        let environment_resolver = self.environment_resolver(plan)?;
+        let mut pipeline = ScanPipeline::new().with_grouping_engine(
+            GroupingEngine::new().with_environment_resolver(environment_resolver.clone()),
+        );
+        if let Some(cache) = &result_cache {
+            pipeline = pipeline.with_result_cache(cache.clone());
+        }
+        let pipeline_output = tracing::debug_span!("analyze").in_scope(|| {
+            pipeline
+                .with_detection_engine(detection_engine)
+                .with_graph_config(graph_config)
+                .with_explain(self.explain && text_output || reporting)
+                .with_mapping(self.insights && text_output || reporting)
+                .with_grouping(self.insights && text_output || reporting)
+                .with_progress(progress)
+                .run(&arena)
+        });
+        drop(progress_bars);
+        let pipeline_output = pipeline_output?;
+        tracing::info!(
+            detections = pipeline_output.detections.len(),
+            total_monthly = arena.total_monthly_cost(),
+            "Analysis complete"
+        );
 
-        let detections =
-            detection_engine.analyze_changes(&changes, &cost_estimates_for_analysis)?;
+        let changes = arena.changes();
+        let estimates = arena.estimates();
+        let detections = &pipeline_output.detections;
+        if let Some(stream) = &mut stream {
+            stream.write_all(detections.iter().map(NdjsonRecord::Detection))?;
+        }
+        let total_monthly = arena.total_monthly_cost();
 
         let total_cost_estimate = CostEstimate {
             resource_id: "total".to_string(),
@@ -1347,9 +2097,16 @@ impl ScanCommand {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: None,
         };
 
-        let policy_result = if let Some(policy_path) = &self.policy {
+        let policy_path = match &self.policy {
+            Some(path) => Some(path.clone()),
+            None => self
+                .bundle()?
+                .and_then(|bundle| bundle.default_policy().map(std::path::Path::to_path_buf)),
+        };
+        let policy_result = if let Some(policy_path) = &policy_path {
             let policy_config = PolicyLoader::load_from_file(policy_path)?;
             PolicyLoader::validate(&policy_config)?;
 
@@ -1397,7 +2154,7 @@ impl ScanCommand {
 
             // Convert TotalCost to CostEstimate for policy evaluation
             let mut policy_result = policy_engine
-                .evaluate_zero_network(&changes, &total_cost_estimate, ZeroNetworkToken::new())
+                .evaluate_zero_network(changes, &total_cost_estimate, ZeroNetworkToken::new())
                 .map_err(|e| {
                     CostPilotError::new(
                         "POLICY_001",
@@ -1406,9 +2163,20 @@ impl ScanCommand {
                     )
                 })?;
 
-            // Free edition: downgrade all violations to warnings
+            // Free edition, or enforcement disabled by the organization
+            // capability policy: downgrade all violations to warnings
             if !edition.capabilities.allow_policy_enforce {
                 let violations_to_convert = policy_result.violations.clone();
+                if let Some(rule) =
+                    edition.disabled_by_policy(crate::edition::Capability::PolicyEnforce)
+                {
+                    if !violations_to_convert.is_empty() {
+                        policy_result.add_warning(format!(
+                            "Policy enforcement is disabled by organization policy rule '{}'; violations are advisory",
+                            rule
+                        ));
+                    }
+                }
                 for violation in &violations_to_convert {
                     policy_result.add_warning(format!(
                         "[{}] {} - {} (actual: {}, expected: {})",
@@ -1428,17 +2196,88 @@ impl ScanCommand {
             None
         };
 
+        // Environment allow/deny lists from the zero_cost section
+        let zero_cost = self.zero_cost_config()?;
+        let gate_violations = ZeroCostGuard::new()
+            .with_config(zero_cost)
+            .with_environment_resolver(environment_resolver)
+            .check_changes(changes, estimates);
+        let policy_result = if gate_violations.is_empty() {
+            policy_result
+        } else {
+            let mut policy_result = policy_result.unwrap_or_default();
+            for violation in gate_violations {
+                let policy_name = format!("zero_cost.{}", violation.environment);
+                if violation.blocking && edition.capabilities.allow_policy_enforce {
+                    policy_result.add_violation(crate::engines::policy::PolicyViolation {
+                        policy_name,
+                        severity: "HIGH".to_string(),
+                        resource_id: violation.resource_id,
+                        message: violation.message,
+                        actual_value: violation.rule.name().to_string(),
+                        expected_value: "allowed".to_string(),
+                    });
+                } else {
+                    policy_result.add_warning(format!(
+                        "{} - {}: {}",
+                        policy_name, violation.resource_id, violation.message
+                    ));
+                }
+            }
+            Some(policy_result)
+        };
+
+        // Required tags, allowed values and formats from the tagging section
+        let tag_policy = self.tag_policy()?;
+        let tag_violations = tag_policy.check(changes);
+        let policy_result = if tag_violations.is_empty() {
+            policy_result
+        } else {
+            let mut policy_result = policy_result.unwrap_or_default();
+            for violation in &tag_violations {
+                let policy_name = format!("tagging.{}", violation.problem.name());
+                if violation.blocking && edition.capabilities.allow_policy_enforce {
+                    policy_result.add_violation(crate::engines::policy::PolicyViolation {
+                        policy_name,
+                        severity: violation.severity.clone(),
+                        resource_id: violation.resource_id.clone(),
+                        message: violation.message.clone(),
+                        actual_value: violation.problem.actual().to_string(),
+                        expected_value: violation.problem.expected(),
+                    });
+                } else {
+                    policy_result.add_warning(format!(
+                        "{} - {}: {}",
+                        policy_name, violation.resource_id, violation.message
+                    ));
+                }
+            }
+            Some(policy_result)
+        };
+
         // Step 4: Baselines Evaluation (if baselines file provided)
-        let baselines_result = if let Some(baselines_path) = &self.baselines {
+        let mut reference_cost = None;
+        let baselines_path = match &self.baselines {
+            Some(path) => Some(path.clone()),
+            None => self.bundle()?.and_then(|bundle| bundle.baselines.clone()),
+        };
+        let baselines_result = if let Some(baselines_path) = &baselines_path {
             match BaselinesManager::load_from_file(baselines_path) {
                 Ok(manager) => {
+                    let manager = manager.with_configured_history();
+                    reference_cost = manager
+                        .config()
+                        .global
+                        .as_ref()
+                        .map(|global| global.expected_monthly_cost);
+
                     // Compare total cost against baseline
-                    let total_baseline_violation = manager
-                        .compare_total_cost(total_cost_estimate.monthly_cost, Some(&changes));
+                    let total_baseline_violation =
+                        manager.compare_total_cost(total_cost_estimate.monthly_cost, Some(changes));
 
                     // Compare module costs by grouping resources by module
                     let mut module_costs = HashMap::new();
-                    for change in &changes {
+                    for change in changes {
                         if let (Some(module_path), Some(monthly_cost)) =
                             (&change.module_path, change.monthly_cost)
                         {
@@ -1446,7 +2285,7 @@ impl ScanCommand {
                         }
                     }
                     let module_comparison =
-                        manager.compare_module_costs(&module_costs, Some(&changes));
+                        manager.compare_module_costs(&module_costs, Some(changes));
 
                     Some((total_baseline_violation, module_comparison))
                 }
@@ -1460,8 +2299,8 @@ impl ScanCommand {
         };
 
         // Step 5: SLO Evaluation (if SLO config exists)
-        let slo_result = if std::path::PathBuf::from(".costpilot/slo.json").exists() {
-            match self.evaluate_slos(&total_cost_estimate, &estimates, edition) {
+        let slo_result = if let Some(slo_config_path) = self.slo_config()? {
+            match self.evaluate_slos(&slo_config_path, &total_cost_estimate, estimates, edition) {
                 Ok(slo_result) => Some(slo_result),
                 Err(e) => {
                     // Only show warning if debug mode enabled
@@ -1475,29 +2314,77 @@ impl ScanCommand {
             None
         };
 
+        #[cfg(all(feature = "tui", not(target_arch = "wasm32")))]
+        let tui_shown = self.tui && {
+            self.run_tui(changes, estimates, detections, policy_result.as_ref())?;
+            true
+        };
+        #[cfg(not(all(feature = "tui", not(target_arch = "wasm32"))))]
+        let tui_shown = false;
+
         // Handle explain and autofix flags (only show in text output)
-        if matches!(self.output_format, Some(OutputFormat::Text)) {
+        if !tui_shown && matches!(self.output_format, Some(OutputFormat::Text)) {
             // Step 5: Explanation (if requested)
             if self.explain {
                 println!("{}", "💡 Step 5: Explanation".bold());
-                let _explain_engine = crate::engines::explain::ExplainEngine::new();
-                // TODO: Implement detect_anti_patterns
-                let anti_patterns: Vec<String> = Vec::new();
+                let anti_patterns: Vec<String> = pipeline_output
+                    .explanations
+                    .iter()
+                    .flat_map(|e| {
+                        e.anti_patterns
+                            .iter()
+                            .map(move |p| format!("{}: {}", e.resource_id, p.pattern_name))
+                    })
+                    .collect();
 
                 if !anti_patterns.is_empty() {
                     println!("   Detected {} anti-patterns:\n", anti_patterns.len());
+                    for pattern in &anti_patterns {
+                        println!("   • {}", pattern);
+                    }
+                    println!();
                 } else {
                     println!("   {} No anti-patterns detected", "✅".green());
                 }
             }
 
+            if self.insights {
+                Self::print_insights(&pipeline_output);
+            }
+
+            if let Some(cache) = &result_cache {
+                let stats = cache.stats();
+                println!(
+                    "   {} Result cache: {} hit(s), {} miss(es){}",
+                    "♻".bright_black(),
+                    stats.hits,
+                    stats.misses,
+                    if cache.was_invalidated() {
+                        " (invalidated by version change)"
+                    } else {
+                        ""
+                    }
+                );
+                println!();
+            }
+
             // Step 5: Autofix snippets (if requested)
             if self.autofix {
                 println!("{}", "🔧 Step 6: Autofix Snippets".bold());
                 let _autofix_engine = crate::engines::autofix::AutofixEngine::new();
                 // TODO: generate_fixes requires 4 args: detections, changes, estimates, mode
-                // Stub for now
-                println!("   Autofix not yet implemented in scan command");
+                // Stub for now; only tag fixes are shown
+                let tag_fixes = tag_policy.fix_snippets(changes, &tag_violations);
+                if tag_fixes.is_empty() {
+                    println!("   Autofix not yet implemented in scan command");
+                }
+                for fix in &tag_fixes {
+                    println!("   {} {}", "Add tags to".bold(), fix.resource_id);
+                    for line in fix.snippet.lines() {
+                        println!("   {}", line);
+                    }
+                    println!();
+                }
             }
         }
 
@@ -1518,22 +2405,574 @@ impl ScanCommand {
             }
         }
 
-        // Format and output results
-        self.format_output(
-            &changes,
-            &estimates,
-            &detections,
-            policy_result.as_ref(),
-            baselines_result.as_ref(),
-            slo_result.as_ref(),
+        let gate = CiGate {
+            fail_on_increase: self.fail_on_increase,
+            fail_on: self.fail_on.clone(),
+        };
+        let mut verdict = gate.evaluate(&GateInput {
+            monthly_cost: total_monthly,
+            reference_cost,
+            detections,
+            policy_result: policy_result.as_ref(),
+            slo_result: slo_result.as_ref(),
+        })?;
+
+        if let Some(report) = &self.report {
+            self.write_report(
+                report,
+                plan,
+                changes,
+                estimates,
+                Some(&pipeline_output),
+                policy_result.as_ref(),
+                slo_result.as_ref(),
+                total_monthly,
+                trial.as_ref(),
+            )?;
+        } else if !tui_shown {
+            if let Some(stream) = &mut stream {
+                Self::finish_ndjson(
+                    stream,
+                    changes.len(),
+                    detections.len(),
+                    policy_result.as_ref(),
+                    slo_result.as_ref(),
+                    total_monthly,
+                    trial.as_ref(),
+                )?;
+            } else {
+                // Format and output results
+                self.format_output(
+                    changes,
+                    estimates,
+                    detections,
+                    policy_result.as_ref(),
+                    baselines_result.as_ref(),
+                    slo_result.as_ref(),
+                    total_monthly,
+                    self.get_output_format(global_format),
+                    trial.as_ref(),
+                )?;
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.plugins {
+            let result = Self::scan_result(
+                changes,
+                estimates,
+                detections,
+                policy_result.as_ref(),
+                slo_result.as_ref(),
+                total_monthly,
+                trial.as_ref(),
+            );
+            Self::run_plugins(&Self::to_canonical_json(&result)?, &mut verdict)?;
+        }
+
+        // Reasons go to stderr so machine-readable stdout stays parseable
+        for reason in &verdict.reasons {
+            eprintln!("{} {}", "Gate:".bright_black(), reason);
+        }
+        Ok(verdict.code)
+    }
+
+    /// Scan every stack under `root` on a bounded pool, printing each stack
+    /// as it finishes and the totals once all are done
+    ///
+    /// A stack that fails to parse or predict exits 5 without holding back
+    /// the others; stacks over their time budget count as warnings.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn execute_stacks(
+        &self,
+        root: &std::path::Path,
+        edition: &crate::edition::EditionContext,
+        global_format: &str,
+    ) -> Result<ExitCode, CostPilotError> {
+        use crate::engines::performance::PerformanceBudgets;
+        use crate::engines::pipeline::{discover_stacks, StackScanner, DEFAULT_STACK_PLAN};
+
+        if self.report.is_some() {
+            return Err(CostPilotError::new(
+                "STACK_003",
+                ErrorCategory::InvalidInput,
+                "costpilot report takes a single plan, not --stacks".to_string(),
+            ));
+        }
+        let infra_format = self.infra_format()?;
+        if infra_format != "terraform" {
+            return Err(CostPilotError::new(
+                "STACK_004",
+                ErrorCategory::InvalidInput,
+                format!(
+                    "--stacks supports Terraform plans only, not {}",
+                    infra_format
+                ),
+            ));
+        }
+
+        let plan_file = self.stack_plan.as_deref().unwrap_or(DEFAULT_STACK_PLAN);
+        let stacks = discover_stacks(root, plan_file)?;
+        if stacks.is_empty() {
+            return Err(CostPilotError::new(
+                "STACK_005",
+                ErrorCategory::FileSystemError,
+                format!(
+                    "No stacks found under {}: no directory contains {}",
+                    root.display(),
+                    plan_file
+                ),
+            )
+            .with_hint(
+                "Write each stack's plan with 'terraform show -json tfplan > plan.json', or pass --stack-plan"
+                    .to_string(),
+            ));
+        }
+
+        let mut budget = PerformanceBudgets::default().total_scan;
+        if let Some(ms) = self.stack_budget_ms {
+            budget.max_latency_ms = ms;
+        }
+        let jobs = self
+            .jobs
+            .map_or_else(StackScanner::default_jobs, usize::from);
+        let free_tier = self.free_tier()?;
+        let scanner = StackScanner::new(jobs)
+            .with_budget(budget)
+            .with_detection_engine(
+                DetectionEngine::new()
+                    .with_custom_rules(self.custom_rules()?)
+                    .with_free_tier(free_tier.clone()),
+            );
+        let cluster_pricing = self.cluster_pricing()?;
+        let usage = self.usage_profile()?;
+        let custom_heuristics = Self::load_custom_heuristics()?;
+        let calibration = Self::load_calibration(edition);
+        let predict = |changes: &[crate::engines::shared::models::ResourceChange]| {
+            let pricing = PlanPricing::new(cluster_pricing.clone(), usage.clone(), changes);
+            let mut estimates = predict_estimates(edition, changes, &pricing)?;
+            custom_heuristics.apply(changes, &mut estimates);
+            calibration.apply(changes, &mut estimates);
+            free_tier
+                .pool(&MinimalHeuristics::to_cost_heuristics())
+                .apply(changes, &mut estimates);
+            Ok(estimates)
+        };
+
+        let format = self.get_output_format(global_format);
+        let trial = edition.trial_watermark();
+        let text = !matches!(format, OutputFormat::Json | OutputFormat::Ndjson);
+        let mut stream =
+            matches!(format, OutputFormat::Ndjson).then(|| NdjsonWriter::new(std::io::stdout()));
+        if text {
+            println!(
+                "{}",
+                format!(
+                    "🔍 Scanning {} stacks under {} ({} at a time)",
+                    stacks.len(),
+                    root.display(),
+                    jobs.min(stacks.len())
+                )
+                .bold()
+                .cyan()
+            );
+        }
+
+        let results = scanner.run(stacks, &predict, |result| match &mut stream {
+            Some(stream) => stream.write(&NdjsonRecord::Stack(NdjsonStack::from(result))),
+            None => {
+                if text {
+                    Self::print_stack_line(result);
+                }
+                Ok(())
+            }
+        })?;
+
+        let total_monthly: f64 = results.iter().map(|r| r.monthly_cost).sum();
+        let detections: Vec<_> = results
+            .iter()
+            .flat_map(|r| r.detections.iter().cloned())
+            .collect();
+        let summary = ScanSummary {
+            resources_changed: results.iter().map(|r| r.changes.len()).sum(),
+            monthly_cost: total_monthly,
+            optimization_opportunities: detections.len(),
+            policy_status: None,
+            slo_status: None,
+        };
+        match &mut stream {
+            Some(stream) => stream.write(&NdjsonRecord::Summary(NdjsonSummary {
+                schema_version: OUTPUT_SCHEMA_VERSION,
+                resources_changed: summary.resources_changed,
+                monthly_cost: summary.monthly_cost,
+                optimization_opportunities: summary.optimization_opportunities,
+                policy_status: None,
+                slo_status: None,
+                trial: trial.clone(),
+            }))?,
+            None if text => Self::print_stack_totals(&results, total_monthly, trial.as_ref()),
+            None => println!(
+                "{}",
+                Self::to_canonical_json(&StackScanResult {
+                    schema_version: OUTPUT_SCHEMA_VERSION,
+                    summary,
+                    stacks: results.iter().map(NdjsonStack::from).collect(),
+                    trial,
+                })?
+            ),
+        }
+
+        let gate = CiGate {
+            fail_on_increase: self.fail_on_increase,
+            fail_on: self.fail_on.clone(),
+        };
+        let mut verdict = gate.evaluate(&GateInput {
+            monthly_cost: total_monthly,
+            reference_cost: None,
+            detections: &detections,
+            policy_result: None,
+            slo_result: None,
+        })?;
+        let warnings_fail = self.fail_on.contains(&FailOn::Warnings);
+        for result in &results {
+            if let Some(error) = &result.error {
+                verdict.raise(
+                    ExitCode::InternalError,
+                    format!("Stack {} failed: {}", result.name, error),
+                );
+            }
+            if let Some(violation) = result.budget_violation.as_ref().filter(|_| warnings_fail) {
+                verdict.raise(ExitCode::Warnings, violation.format_error());
+            }
+        }
+
+        for reason in &verdict.reasons {
+            eprintln!("{} {}", "Gate:".bright_black(), reason);
+        }
+        Ok(verdict.code)
+    }
+
+    /// One line per finished stack, in completion order
+    #[cfg(not(target_arch = "wasm32"))]
+    fn print_stack_line(result: &crate::engines::pipeline::StackResult) {
+        if let Some(error) = &result.error {
+            println!("  {} {}  {}", "✗".red(), result.name.bold(), error.red());
+            return;
+        }
+        let timing = format!("{}ms", result.metrics.duration_ms);
+        let timing = match &result.budget_violation {
+            Some(_) => format!("{} (over {}ms budget)", timing, result.metrics.budget_ms)
+                .yellow()
+                .to_string(),
+            None => timing.bright_black().to_string(),
+        };
+        println!(
+            "  {} {}  {} resources, ${:.2}/mo, {} findings  {}",
+            "✓".green(),
+            result.name.bold(),
+            result.changes.len(),
+            result.monthly_cost,
+            result.detections.len(),
+            timing
+        );
+    }
+
+    /// Per-stack costs sorted by name, then the monorepo total
+    #[cfg(not(target_arch = "wasm32"))]
+    fn print_stack_totals(
+        results: &[crate::engines::pipeline::StackResult],
+        total_monthly: f64,
+        trial: Option<&crate::edition::TrialWatermark>,
+    ) {
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        if let Some(trial) = trial {
+            println!(
+                "{}",
+                format!(
+                    "Trial license - {} days remaining (expires {})",
+                    trial.days_remaining, trial.expires
+                )
+                .yellow()
+            );
+        }
+        let width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+        for result in results {
+            let cost = match result.error {
+                Some(_) => "failed".red().to_string(),
+                None => format!("${:.2}/mo", result.monthly_cost),
+            };
+            println!("  {:<width$}  {}", result.name, cost, width = width);
+        }
+        let failed = results.iter().filter(|r| !r.succeeded()).count();
+        let mut total = format!(
+            "💰 Total: ${:.2}/month across {} stacks",
+            total_monthly,
+            results.len()
+        );
+        if failed > 0 {
+            total.push_str(&format!(" ({} failed)", failed));
+        }
+        println!("\n{}", total.bold());
+    }
+
+    /// Rates from `.costpilot/heuristics/`; files pinned to other built-in
+    /// heuristics are skipped with a warning
+    pub(crate) fn load_custom_heuristics() -> Result<CustomHeuristics, CostPilotError> {
+        let custom = HeuristicsLoader::new().load_custom(HEURISTICS_VERSION)?;
+        for skipped in &custom.skipped {
+            tracing::warn!("Skipping custom heuristics: {}", skipped);
+        }
+        Ok(custom)
+    }
+
+    /// Interval calibration learned by `costpilot calibrate`; a broken file
+    /// only costs the calibration, not the scan
+    fn load_calibration(edition: &crate::edition::EditionContext) -> ConfidenceCalibration {
+        let path = ConfidenceCalibration::path(&edition.paths.config_dir);
+        ConfidenceCalibration::load(&path).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring prediction calibration: {}", e);
+            ConfidenceCalibration::default()
+        })
+    }
+
+    /// Run with these global options
+    pub fn with_options(mut self, options: GlobalOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Switch this scan to writing report files
+    pub(crate) fn into_report(mut self, target: ReportTarget) -> Self {
+        self.output_format = None;
+        self.report = Some(target);
+        #[cfg(all(feature = "tui", not(target_arch = "wasm32")))]
+        {
+            self.tui = false;
+        }
+        self
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_report(
+        &self,
+        target: &ReportTarget,
+        plan: &std::path::Path,
+        changes: &[crate::engines::detection::ResourceChange],
+        estimates: &[CostEstimate],
+        pipeline_output: Option<&ScanPipelineOutput>,
+        policy_result: Option<&crate::engines::policy::PolicyResult>,
+        slo_result: Option<&SloResult>,
+        total_monthly: f64,
+        trial: Option<&crate::edition::TrialWatermark>,
+    ) -> Result<(), CostPilotError> {
+        let redacted = match self.redactor(changes)? {
+            Some(redactor) => Some(RedactedReport {
+                changes: redactor.changes(changes)?,
+                estimates: redactor.redact(&estimates.to_vec())?,
+                detections: redactor.redact(
+                    &pipeline_output
+                        .map(|o| o.detections.clone())
+                        .unwrap_or_default(),
+                )?,
+                explanations: redactor.redact(
+                    &pipeline_output
+                        .map(|o| o.explanations.clone())
+                        .unwrap_or_default(),
+                )?,
+                graph: redactor.redact(&pipeline_output.and_then(|o| o.graph.clone()))?,
+                grouping: redactor.redact(&pipeline_output.and_then(|o| o.grouping.clone()))?,
+                policy_result: redactor.redact(&policy_result.cloned())?,
+                slo_result: redactor.redact(&slo_result.cloned())?,
+            }),
+            None => None,
+        };
+        let title = match &redacted {
+            Some(_) => "CostPilot report (redacted)".to_string(),
+            None => format!("CostPilot report: {}", plan.display()),
+        };
+        let data = ReportData {
+            title,
+            generated_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            changes: redacted.as_ref().map_or(changes, |r| &r.changes),
+            estimates: redacted.as_ref().map_or(estimates, |r| &r.estimates),
+            detections: match &redacted {
+                Some(r) => &r.detections,
+                None => pipeline_output.map_or(&[], |o| o.detections.as_slice()),
+            },
+            explanations: match &redacted {
+                Some(r) => &r.explanations,
+                None => pipeline_output.map_or(&[], |o| o.explanations.as_slice()),
+            },
+            graph: match &redacted {
+                Some(r) => r.graph.as_ref(),
+                None => pipeline_output.and_then(|o| o.graph.as_ref()),
+            },
+            grouping: match &redacted {
+                Some(r) => r.grouping.as_ref(),
+                None => pipeline_output.and_then(|o| o.grouping.as_ref()),
+            },
+            policy_result: redacted
+                .as_ref()
+                .map_or(policy_result, |r| r.policy_result.as_ref()),
+            slo_result: redacted
+                .as_ref()
+                .map_or(slo_result, |r| r.slo_result.as_ref()),
+            trend: target.trend.clone(),
+            trend_totals: target.trend_totals.clone(),
             total_monthly,
-            self.get_output_format(global_format),
+            trial,
+            locale: self.options.locale,
+        };
+        if let Some(dir) = &target.html {
+            let index = HtmlReportBundle::write(&data, dir)?;
+            println!("📄 Report written to {}", index.display());
+        }
+        if let Some(path) = &target.pdf {
+            PdfSummary::write(&data, path)?;
+            println!("📄 Executive summary written to {}", path.display());
+        }
+        Ok(())
+    }
+
+    /// Feed the scan result to each plugin; failures fail the gate
+    #[cfg(not(target_arch = "wasm32"))]
+    fn run_plugins(
+        scan_json: &str,
+        verdict: &mut crate::cli::exit_code::GateVerdict,
+    ) -> Result<(), CostPilotError> {
+        use crate::cli::plugins::{self, PLUGIN_DIR, PLUGIN_OUTPUT_DIR};
+
+        let found = plugins::discover(std::path::Path::new(PLUGIN_DIR))?;
+        if found.is_empty() {
+            eprintln!(
+                "{} no plugins found in {}",
+                "Plugins:".bright_black(),
+                PLUGIN_DIR
+            );
+        }
+        for plugin in &found {
+            let outcome = plugin.run(scan_json, std::path::Path::new(PLUGIN_OUTPUT_DIR))?;
+            if let Some(artifact) = &outcome.artifact {
+                eprintln!(
+                    "{} {} wrote {}",
+                    "Plugins:".bright_black(),
+                    plugin.name,
+                    artifact.display()
+                );
+            }
+            if let Some(failure) = outcome.failure {
+                verdict.raise(
+                    ExitCode::PolicyViolation,
+                    format!("Plugin '{}' {}", plugin.name, failure),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Show results in the terminal UI until the user quits
+    #[cfg(all(feature = "tui", not(target_arch = "wasm32")))]
+    fn run_tui(
+        &self,
+        changes: &[crate::engines::shared::models::ResourceChange],
+        estimates: &[CostEstimate],
+        detections: &[crate::engines::shared::models::Detection],
+        policy_result: Option<&crate::engines::policy::PolicyResult>,
+    ) -> Result<(), CostPilotError> {
+        use crate::cli::scan_tui::{self, ScanView};
+
+        if !atty::is(atty::Stream::Stdout) || !atty::is(atty::Stream::Stdin) {
+            return Err(CostPilotError::new(
+                "SCAN_TUI_002",
+                ErrorCategory::InvalidInput,
+                "--tui requires an interactive terminal".to_string(),
+            )
+            .with_hint("Use --output-format json for non-interactive output".to_string()));
+        }
+
+        scan_tui::run(ScanView::new(changes, estimates, detections, policy_result))
+    }
+
+    /// Open the result cache, keyed to the active heuristics and pricing
+    fn open_result_cache(
+        &self,
+        edition: &crate::edition::EditionContext,
+        usage: &UsageProfile,
+    ) -> Result<ResultCache, CostPilotError> {
+        let heuristics = PredictionEngine::new_with_edition(edition)?
+            .heuristics_version()
+            .to_string();
+        // Free prices are compiled in; premium prices ship with the engine
+        let pricing = if edition.pro.is_some() {
+            format!("pro-{}", env!("CARGO_PKG_VERSION"))
+        } else {
+            format!("static-{}", env!("CARGO_PKG_VERSION"))
+        };
+
+        let dir = self
+            .cache_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CACHE_DIR));
+        // Usage-billed estimates change with the profile they were priced from
+        let usage = serde_json::to_string(usage).unwrap_or_default();
+        ResultCache::open(
+            dir,
+            CacheVersions::new(heuristics, pricing)
+                .with_usage(hex::encode(Sha256::digest(usage.as_bytes()))),
         )
     }
 
+    /// Print dependency graph and grouping summaries from the scan pipeline
+    fn print_insights(output: &ScanPipelineOutput) {
+        println!("{}", "🧭 Insights".bold());
+
+        if let Some(graph) = &output.graph {
+            println!(
+                "   Dependencies: {} resources, {} edges, max depth {}",
+                graph.metadata.node_count, graph.metadata.edge_count, graph.metadata.max_depth
+            );
+            if graph.metadata.has_cycles {
+                println!(
+                    "   {} {} dependency cycle(s) detected",
+                    "⚠".yellow(),
+                    graph.metadata.cycles.len()
+                );
+            }
+        }
+
+        if let Some(grouping) = &output.grouping {
+            for group in grouping.module_groups.iter().take(3) {
+                println!(
+                    "   Module {}: ${:.2}/mo ({} resources)",
+                    group.module_path, group.monthly_cost, group.resource_count
+                );
+            }
+            for group in grouping.service_groups.iter().take(3) {
+                println!(
+                    "   Service {}: ${:.2}/mo ({} resources)",
+                    group.service_name, group.monthly_cost, group.resource_count
+                );
+            }
+            for group in grouping.environment_groups.iter().take(3) {
+                println!(
+                    "   Environment {}: ${:.2}/mo ({} resources, {:.0}% confidence)",
+                    group.environment,
+                    group.monthly_cost,
+                    group.resource_count,
+                    group.confidence * 100.0
+                );
+            }
+            for warning in inference_warnings(&grouping.environment_groups) {
+                println!("   {} {}", "⚠".yellow(), warning);
+            }
+        }
+        println!();
+    }
+
     /// Evaluate SLOs against the current cost estimates
     fn evaluate_slos(
         &self,
+        slo_config_path: &std::path::Path,
         total_cost: &CostEstimate,
         estimates: &[CostEstimate],
         edition: &crate::edition::EditionContext,
@@ -1541,8 +2980,7 @@ impl ScanCommand {
         use crate::engines::slo::{SloDefinition, SloEngine};
 
         // Load SLO config
-        let slo_config_path = std::path::PathBuf::from(".costpilot/slo.json");
-        let content = std::fs::read_to_string(&slo_config_path).map_err(|e| {
+        let content = std::fs::read_to_string(slo_config_path).map_err(|e| {
             CostPilotError::new(
                 "SLO_001",
                 ErrorCategory::FileSystemError,
@@ -0,0 +1,600 @@
+// Interactive terminal view of scan results (`costpilot scan --tui`)
+//
+// `ScanView` holds the data and navigation state and is independent of the
+// terminal; `run` drives it with crossterm events and ratatui rendering.
+
+use crate::engines::policy::PolicyResult;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange, Severity};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Tree label for resources outside any module
+const ROOT_MODULE: &str = "(root)";
+
+/// Panes in focus order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    CostDrivers,
+    Detections,
+    Modules,
+    Policy,
+}
+
+impl Pane {
+    const ALL: [Pane; 4] = [
+        Pane::CostDrivers,
+        Pane::Detections,
+        Pane::Modules,
+        Pane::Policy,
+    ];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|p| *p == self).unwrap_or(0)
+    }
+}
+
+/// Resource ranked by monthly cost
+#[derive(Debug, Clone)]
+pub struct CostDriver {
+    pub resource_id: String,
+    pub resource_type: String,
+    pub monthly_cost: f64,
+}
+
+/// Module with its own resources and nested modules
+#[derive(Debug, Clone, Default)]
+struct ModuleNode {
+    monthly_cost: f64,
+    resources: Vec<(String, f64)>,
+    children: BTreeMap<String, ModuleNode>,
+}
+
+/// Visible line of the module tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeRow {
+    pub depth: usize,
+    pub label: String,
+    pub monthly_cost: f64,
+
+    /// Module key for module rows; `None` for resources
+    pub module: Option<String>,
+    pub expanded: bool,
+}
+
+/// Scan results with per-pane selection
+pub struct ScanView {
+    drivers: Vec<CostDriver>,
+    detections: Vec<Detection>,
+    policy: Option<PolicyResult>,
+    modules: BTreeMap<String, ModuleNode>,
+    expanded: BTreeSet<String>,
+    total_monthly_cost: f64,
+    focus: Pane,
+    selected: [usize; 4],
+}
+
+impl ScanView {
+    pub fn new(
+        changes: &[ResourceChange],
+        estimates: &[CostEstimate],
+        detections: &[Detection],
+        policy: Option<&PolicyResult>,
+    ) -> Self {
+        let costs: HashMap<&str, f64> = estimates
+            .iter()
+            .map(|e| (e.resource_id.as_str(), e.monthly_cost))
+            .collect();
+
+        let mut drivers: Vec<CostDriver> = changes
+            .iter()
+            .map(|c| CostDriver {
+                resource_id: c.resource_id.clone(),
+                resource_type: c.resource_type.clone(),
+                monthly_cost: costs.get(c.resource_id.as_str()).copied().unwrap_or(0.0),
+            })
+            .collect();
+        drivers.sort_by(|a, b| {
+            b.monthly_cost
+                .total_cmp(&a.monthly_cost)
+                .then_with(|| a.resource_id.cmp(&b.resource_id))
+        });
+
+        let mut modules: BTreeMap<String, ModuleNode> = BTreeMap::new();
+        for driver in &drivers {
+            let module_path = changes
+                .iter()
+                .find(|c| c.resource_id == driver.resource_id)
+                .and_then(|c| c.module_path.as_deref());
+            let mut segments = match module_path {
+                Some(path) => split_module_path(path),
+                None => vec![ROOT_MODULE.to_string()],
+            }
+            .into_iter();
+
+            let first = segments.next().unwrap_or_else(|| ROOT_MODULE.to_string());
+            let mut node = modules.entry(first).or_default();
+            node.monthly_cost += driver.monthly_cost;
+            for segment in segments {
+                node = node.children.entry(segment).or_default();
+                node.monthly_cost += driver.monthly_cost;
+            }
+            node.resources
+                .push((driver.resource_id.clone(), driver.monthly_cost));
+        }
+
+        let mut detections = detections.to_vec();
+        detections.sort_by(|a, b| {
+            severity_rank(&b.severity)
+                .cmp(&severity_rank(&a.severity))
+                .then_with(|| a.resource_id.cmp(&b.resource_id))
+        });
+
+        Self {
+            total_monthly_cost: drivers.iter().map(|d| d.monthly_cost).sum(),
+            drivers,
+            detections,
+            policy: policy.cloned(),
+            modules,
+            expanded: BTreeSet::new(),
+            focus: Pane::CostDrivers,
+            selected: [0; 4],
+        }
+    }
+
+    pub fn focus(&self) -> Pane {
+        self.focus
+    }
+
+    pub fn selected(&self, pane: Pane) -> usize {
+        self.selected[pane.index()]
+    }
+
+    /// Module tree rows, expanded modules showing their children
+    pub fn tree_rows(&self) -> Vec<TreeRow> {
+        let mut rows = Vec::new();
+        for (name, node) in &self.modules {
+            self.push_tree_rows(name, name, node, 0, &mut rows);
+        }
+        rows
+    }
+
+    fn push_tree_rows(
+        &self,
+        key: &str,
+        name: &str,
+        node: &ModuleNode,
+        depth: usize,
+        rows: &mut Vec<TreeRow>,
+    ) {
+        let expanded = self.expanded.contains(key);
+        rows.push(TreeRow {
+            depth,
+            label: name.to_string(),
+            monthly_cost: node.monthly_cost,
+            module: Some(key.to_string()),
+            expanded,
+        });
+        if !expanded {
+            return;
+        }
+        for (child, child_node) in &node.children {
+            let child_key = format!("{}.{}", key, child);
+            self.push_tree_rows(&child_key, child, child_node, depth + 1, rows);
+        }
+        for (resource_id, cost) in &node.resources {
+            rows.push(TreeRow {
+                depth: depth + 1,
+                label: resource_id.clone(),
+                monthly_cost: *cost,
+                module: None,
+                expanded: false,
+            });
+        }
+    }
+
+    fn policy_lines(&self) -> Vec<String> {
+        let Some(policy) = &self.policy else {
+            return Vec::new();
+        };
+        policy
+            .violations
+            .iter()
+            .map(|v| {
+                format!(
+                    "[{}] {}: {} ({})",
+                    v.severity, v.resource_id, v.message, v.policy_name
+                )
+            })
+            .chain(policy.warnings.iter().map(|w| format!("⚠ {}", w)))
+            .collect()
+    }
+
+    fn len(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::CostDrivers => self.drivers.len(),
+            Pane::Detections => self.detections.len(),
+            Pane::Modules => self.tree_rows().len(),
+            Pane::Policy => self.policy_lines().len(),
+        }
+    }
+
+    /// Apply a key press; returns false when the view should close
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        let pane = self.focus.index();
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Tab => self.focus = Pane::ALL[(pane + 1) % Pane::ALL.len()],
+            KeyCode::BackTab => {
+                self.focus = Pane::ALL[(pane + Pane::ALL.len() - 1) % Pane::ALL.len()]
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let len = self.len(self.focus);
+                if self.selected[pane] + 1 < len {
+                    self.selected[pane] += 1;
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected[pane] = self.selected[pane].saturating_sub(1);
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.selected[pane] = 0,
+            KeyCode::End | KeyCode::Char('G') => {
+                self.selected[pane] = self.len(self.focus).saturating_sub(1)
+            }
+            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Right | KeyCode::Left
+                if self.focus == Pane::Modules =>
+            {
+                self.toggle_selected_module(key);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn toggle_selected_module(&mut self, key: KeyCode) {
+        let rows = self.tree_rows();
+        let Some(row) = rows.get(self.selected[Pane::Modules.index()]) else {
+            return;
+        };
+        let Some(module) = &row.module else {
+            return;
+        };
+        let expand = match key {
+            KeyCode::Right => true,
+            KeyCode::Left => false,
+            _ => !row.expanded,
+        };
+        if expand {
+            self.expanded.insert(module.clone());
+        } else {
+            self.expanded.remove(module);
+        }
+    }
+
+    /// Draw all panes into the frame
+    pub fn render(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(4),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [top, bottom] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(body);
+        let [drivers_area, detections_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(top);
+        let [modules_area, policy_area] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(bottom);
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                "CostPilot Scan — {} resources, ${:.2}/month, {} detections",
+                self.drivers.len(),
+                self.total_monthly_cost,
+                self.detections.len()
+            ))
+            .style(Style::new().add_modifier(Modifier::BOLD)),
+            header,
+        );
+        frame.render_widget(
+            Paragraph::new("Tab: switch pane  ↑/↓: move  Enter/←/→: expand module  q: quit")
+                .style(Style::new().fg(Color::DarkGray)),
+            footer,
+        );
+
+        let rows = self.drivers.iter().map(|d| {
+            Row::new(vec![
+                format!("${:.2}", d.monthly_cost),
+                d.resource_type.clone(),
+                d.resource_id.clone(),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(24),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(vec!["Monthly", "Type", "Resource"])
+                .style(Style::new().add_modifier(Modifier::BOLD)),
+        )
+        .block(self.block(Pane::CostDrivers, "Top Cost Drivers"))
+        .row_highlight_style(highlight());
+        let mut state = TableState::default().with_selected(self.selection(Pane::CostDrivers));
+        frame.render_stateful_widget(table, drivers_area, &mut state);
+
+        let items = self.detections.iter().map(|d| {
+            ListItem::new(Line::styled(
+                format!(
+                    "[{}] {}: {}",
+                    format!("{:?}", d.severity).to_uppercase(),
+                    d.resource_id,
+                    d.message
+                ),
+                Style::new().fg(severity_color(&d.severity)),
+            ))
+        });
+        self.render_list(
+            frame,
+            detections_area,
+            Pane::Detections,
+            "Detections",
+            items,
+        );
+
+        let items = self.tree_rows().into_iter().map(|row| {
+            let marker = match (&row.module, row.expanded) {
+                (Some(_), true) => "▾ ",
+                (Some(_), false) => "▸ ",
+                (None, _) => "  ",
+            };
+            ListItem::new(format!(
+                "{}{}{}  ${:.2}",
+                "  ".repeat(row.depth),
+                marker,
+                row.label,
+                row.monthly_cost
+            ))
+        });
+        self.render_list(frame, modules_area, Pane::Modules, "Modules", items);
+
+        let title = match &self.policy {
+            Some(policy) if policy.passed => "Policy (passed)",
+            Some(_) => "Policy (failed)",
+            None => "Policy (no policy file)",
+        };
+        let items = self
+            .policy_lines()
+            .into_iter()
+            .map(|line| ListItem::new(line).style(Style::new().fg(Color::Yellow)));
+        self.render_list(frame, policy_area, Pane::Policy, title, items);
+    }
+
+    fn render_list<'a>(
+        &self,
+        frame: &mut Frame,
+        area: ratatui::layout::Rect,
+        pane: Pane,
+        title: &'a str,
+        items: impl Iterator<Item = ListItem<'a>>,
+    ) {
+        let list = List::new(items)
+            .block(self.block(pane, title))
+            .highlight_style(highlight());
+        let mut state = ListState::default().with_selected(self.selection(pane));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn block<'a>(&self, pane: Pane, title: &'a str) -> Block<'a> {
+        let block = Block::bordered().title(title);
+        if pane == self.focus {
+            block.border_style(Style::new().fg(Color::Cyan))
+        } else {
+            block
+        }
+    }
+
+    /// Selection is only highlighted in the focused pane
+    fn selection(&self, pane: Pane) -> Option<usize> {
+        (pane == self.focus && self.len(pane) > 0).then(|| self.selected(pane))
+    }
+}
+
+/// Show the view until the user quits, restoring the terminal afterwards
+pub fn run(mut view: ScanView) -> Result<(), CostPilotError> {
+    let tui_error = |e: std::io::Error| {
+        CostPilotError::new(
+            "SCAN_TUI_001",
+            ErrorCategory::IoError,
+            format!("Terminal UI failed: {}", e),
+        )
+    };
+
+    let mut terminal = ratatui::try_init().map_err(tui_error)?;
+    let result = (|| loop {
+        terminal.draw(|frame| view.render(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !view.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    })();
+    ratatui::restore();
+
+    result.map_err(tui_error)
+}
+
+/// `module.a.module.b` → `["module.a", "module.b"]`
+fn split_module_path(path: &str) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    for part in path.split('.') {
+        match segments.last_mut() {
+            Some(last) if !last.contains('.') => {
+                last.push('.');
+                last.push_str(part);
+            }
+            _ => segments.push(part.to_string()),
+        }
+    }
+    segments
+}
+
+fn severity_rank(severity: &Severity) -> u8 {
+    match severity {
+        Severity::Low => 0,
+        Severity::Medium => 1,
+        Severity::High => 2,
+        Severity::Critical => 3,
+    }
+}
+
+fn severity_color(severity: &Severity) -> Color {
+    match severity {
+        Severity::Critical | Severity::High => Color::Red,
+        Severity::Medium => Color::Yellow,
+        Severity::Low => Color::Reset,
+    }
+}
+
+fn highlight() -> Style {
+    Style::new().add_modifier(Modifier::REVERSED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::{ChangeAction, RegressionType};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn change(id: &str, module: Option<&str>) -> ResourceChange {
+        let builder = ResourceChange::builder()
+            .resource_id(id)
+            .resource_type("aws_instance")
+            .action(ChangeAction::Create);
+        match module {
+            Some(module) => builder.module_path(module),
+            None => builder,
+        }
+        .build()
+    }
+
+    fn estimate(id: &str, cost: f64) -> CostEstimate {
+        CostEstimate::builder()
+            .resource_id(id)
+            .monthly_cost(cost)
+            .build()
+    }
+
+    fn view() -> ScanView {
+        let changes = vec![
+            change("aws_instance.bastion", None),
+            change("module.app.aws_instance.web", Some("module.app")),
+            change(
+                "module.app.module.db.aws_instance.replica",
+                Some("module.app.module.db"),
+            ),
+        ];
+        let estimates = vec![
+            estimate("aws_instance.bastion", 10.0),
+            estimate("module.app.aws_instance.web", 300.0),
+            estimate("module.app.module.db.aws_instance.replica", 200.0),
+        ];
+        let detections = vec![Detection {
+            rule_id: "OVERPROVISIONED_EC2".to_string(),
+            severity: Severity::High,
+            resource_id: "module.app.aws_instance.web".to_string(),
+            regression_type: RegressionType::Configuration,
+            severity_score: 70,
+            message: "Large instance".to_string(),
+            fix_snippet: None,
+            estimated_cost: None,
+        }];
+        ScanView::new(&changes, &estimates, &detections, None)
+    }
+
+    #[test]
+    fn test_module_tree_aggregates_and_expands() {
+        let mut view = view();
+
+        let rows = view.tree_rows();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].label, "(root)");
+        assert_eq!(rows[1].label, "module.app");
+        assert_eq!(rows[1].monthly_cost, 500.0);
+
+        view.handle_key(KeyCode::Tab);
+        view.handle_key(KeyCode::Tab);
+        assert_eq!(view.focus(), Pane::Modules);
+        view.handle_key(KeyCode::Down);
+        view.handle_key(KeyCode::Enter);
+
+        let labels: Vec<String> = view.tree_rows().into_iter().map(|r| r.label).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "(root)",
+                "module.app",
+                "module.db",
+                "module.app.aws_instance.web"
+            ]
+        );
+
+        view.handle_key(KeyCode::Left);
+        assert_eq!(view.tree_rows().len(), 2);
+    }
+
+    #[test]
+    fn test_navigation_is_clamped_per_pane() {
+        let mut view = view();
+
+        view.handle_key(KeyCode::Up);
+        assert_eq!(view.selected(Pane::CostDrivers), 0);
+        for _ in 0..10 {
+            view.handle_key(KeyCode::Down);
+        }
+        assert_eq!(view.selected(Pane::CostDrivers), 2);
+
+        view.handle_key(KeyCode::BackTab);
+        assert_eq!(view.focus(), Pane::Policy);
+        view.handle_key(KeyCode::Down);
+        assert_eq!(view.selected(Pane::Policy), 0);
+
+        assert!(!view.handle_key(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_render_shows_all_panes() {
+        let view = view();
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|frame| view.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        for text in [
+            "Top Cost Drivers",
+            "Detections",
+            "Modules",
+            "Policy (no policy file)",
+            "$510.00/month",
+            "module.app.aws_instance.web",
+            "Large instance",
+        ] {
+            assert!(screen.contains(text), "missing {:?}", text);
+        }
+    }
+}
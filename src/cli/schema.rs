@@ -0,0 +1,124 @@
+// Schema command - publish JSON Schemas for machine-readable outputs
+
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::json_schema::{
+    all_schemas, schema_for, OutputSchema, OUTPUT_SCHEMA_VERSION,
+};
+use clap::{Args, Subcommand};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct SchemaCommand {
+    #[command(subcommand)]
+    pub command: SchemaSubcommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SchemaSubcommand {
+    /// Export JSON Schemas for scan, snapshot, policy report and map output
+    Export {
+        /// Export a single schema (scan-result, snapshot, policy-report, map)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Write one <name>.schema.json file per schema into this directory
+        #[arg(short, long, value_name = "DIR")]
+        output_dir: Option<PathBuf>,
+    },
+
+    /// Print the output schema version
+    Version,
+}
+
+pub fn execute_schema_command(cmd: &SchemaCommand) -> Result<(), CostPilotError> {
+    match &cmd.command {
+        SchemaSubcommand::Export { name, output_dir } => {
+            let kinds = match name {
+                Some(name) => vec![parse_schema_name(name)?],
+                None => OutputSchema::ALL.to_vec(),
+            };
+
+            match output_dir {
+                Some(dir) => {
+                    for path in export_to_dir(dir, &kinds)? {
+                        println!("Wrote {}", path.display());
+                    }
+                }
+                None => {
+                    let doc = match kinds.as_slice() {
+                        [kind] => schema_for(*kind),
+                        _ => all_schemas(),
+                    };
+                    println!("{}", to_pretty(&doc)?);
+                }
+            }
+            Ok(())
+        }
+        SchemaSubcommand::Version => {
+            println!("{}", OUTPUT_SCHEMA_VERSION);
+            Ok(())
+        }
+    }
+}
+
+fn parse_schema_name(name: &str) -> Result<OutputSchema, CostPilotError> {
+    OutputSchema::from_name(name).ok_or_else(|| {
+        let known: Vec<&str> = OutputSchema::ALL.iter().map(|k| k.name()).collect();
+        CostPilotError::new(
+            "SCHEMA_001",
+            ErrorCategory::InvalidInput,
+            format!("Unknown schema '{}'", name),
+        )
+        .with_hint(format!("Available schemas: {}", known.join(", ")))
+    })
+}
+
+/// Write the given schemas into `dir`, returning the written paths
+pub fn export_to_dir(dir: &Path, kinds: &[OutputSchema]) -> Result<Vec<PathBuf>, CostPilotError> {
+    std::fs::create_dir_all(dir).map_err(|e| {
+        CostPilotError::io_error(format!("Failed to create {}: {}", dir.display(), e))
+    })?;
+
+    kinds
+        .iter()
+        .map(|kind| {
+            let path = dir.join(kind.file_name());
+            let content = to_pretty(&schema_for(*kind))?;
+            std::fs::write(&path, content + "\n").map_err(|e| {
+                CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+            })?;
+            Ok(path)
+        })
+        .collect()
+}
+
+fn to_pretty(doc: &serde_json::Value) -> Result<String, CostPilotError> {
+    serde_json::to_string_pretty(doc).map_err(|e| {
+        CostPilotError::serialization_error(format!("Failed to serialize schema: {}", e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_export_writes_one_file_per_schema() {
+        let dir = TempDir::new().unwrap();
+        let paths = export_to_dir(dir.path(), &OutputSchema::ALL).unwrap();
+        assert_eq!(paths.len(), 4);
+
+        let scan: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&paths[0]).unwrap()).unwrap();
+        assert!(paths[0].ends_with("scan-result.schema.json"));
+        assert_eq!(scan["title"], "CostPilot Scan Result");
+    }
+
+    #[test]
+    fn test_unknown_schema_name_is_rejected() {
+        let err = parse_schema_name("billing").unwrap_err();
+        assert!(err.message.contains("billing"));
+        assert_eq!(parse_schema_name("MAP").unwrap(), OutputSchema::MapJson);
+    }
+}
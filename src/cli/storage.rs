@@ -0,0 +1,437 @@
+// `costpilot storage` - maintenance of local snapshot and usage stores
+
+use crate::engines::policy::LIFECYCLE_DIR;
+use crate::engines::shared::compat::{self, Artifact, Compatibility};
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::migrations::{self, MigrationReport, Migrator};
+use crate::security::at_rest::{AtRestCipher, FileLayout, StoreEncryption};
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Snapshot directory used by `costpilot trend`
+const SNAPSHOTS_DIR: &str = ".costpilot/snapshots";
+
+/// Baselines file used by `costpilot baseline`
+const BASELINES_FILE: &str = "baselines.json";
+
+/// Maintain local snapshot and usage stores
+#[derive(Debug, Args)]
+pub struct StorageCommand {
+    #[command(subcommand)]
+    command: StorageCommands,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum StorageCommands {
+    /// Encrypt plaintext trend snapshots and usage log entries in place
+    ///
+    /// Uses the key derived from the installed license. Files and entries
+    /// already encrypted are left alone, so the command can be re-run.
+    ///
+    /// Examples:
+    ///   costpilot storage encrypt --dry-run
+    ///   costpilot storage encrypt --snapshots-dir .costpilot/snapshots
+    Encrypt {
+        /// Trend snapshot directory
+        #[arg(long, default_value = SNAPSHOTS_DIR)]
+        snapshots_dir: PathBuf,
+
+        /// Usage event log (default: the local usage log in ~/.costpilot/data)
+        #[arg(long, value_name = "FILE")]
+        usage_log: Option<PathBuf>,
+
+        /// List the files that would be encrypted without changing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Report which CostPilot version wrote each stored artifact
+    ///
+    /// Lists baselines, trend snapshots and policy lifecycle records written
+    /// by an older layout (migrated when read) or by a newer CostPilot.
+    /// Exits non-zero when an artifact cannot be read by this version.
+    ///
+    /// Examples:
+    ///   costpilot storage check
+    ///   costpilot storage check --baselines config/baselines.json --format json
+    Check {
+        /// Trend snapshot directory
+        #[arg(long, default_value = SNAPSHOTS_DIR)]
+        snapshots_dir: PathBuf,
+
+        /// Baselines file
+        #[arg(long, default_value = BASELINES_FILE)]
+        baselines: PathBuf,
+
+        /// Policy lifecycle record directory
+        #[arg(long, default_value = LIFECYCLE_DIR)]
+        lifecycle_dir: PathBuf,
+    },
+
+    /// Apply pending numbered migrations to snapshots, baselines, the audit
+    /// log and the usage log
+    ///
+    /// Migrations also run automatically before commands that read these
+    /// stores. Every run backs the replaced files up under
+    /// .costpilot/backups/ first and restores them if any write fails.
+    ///
+    /// Examples:
+    ///   costpilot storage migrate --dry-run
+    ///   costpilot storage migrate --rollback .costpilot/backups/migrations-20250101T120000
+    Migrate {
+        /// List the migrations and files that would change without writing
+        #[arg(long, conflicts_with = "rollback")]
+        dry_run: bool,
+
+        /// Restore the files saved by an earlier run from its backup directory
+        #[arg(long, value_name = "BACKUP_DIR")]
+        rollback: Option<PathBuf>,
+    },
+}
+
+/// Compatibility of one stored file
+#[derive(Debug, Serialize)]
+struct ArtifactStatus {
+    path: PathBuf,
+    artifact: &'static str,
+    /// "current", "migrated", "newer" or "unreadable"
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    produced_by: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    migrations: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl ArtifactStatus {
+    fn new(path: &Path, artifact: Artifact, result: Result<Compatibility, String>) -> Self {
+        let mut status = Self {
+            path: path.to_path_buf(),
+            artifact: artifact.name(),
+            status: "unreadable",
+            schema_version: None,
+            produced_by: None,
+            migrations: Vec::new(),
+            error: None,
+        };
+        match result {
+            Ok(compat) => {
+                status.status = if compat.from_newer_schema() || compat.from_newer_build() {
+                    "newer"
+                } else if !compat.migrated.is_empty() {
+                    "migrated"
+                } else {
+                    "current"
+                };
+                status.schema_version = Some(compat.stored_version);
+                status.produced_by = compat.produced_by;
+                status.migrations = compat.migrated;
+            }
+            Err(e) => status.error = Some(e),
+        }
+        status
+    }
+}
+
+impl StorageCommand {
+    pub fn execute(&self, format: &str) -> Result<(), CostPilotError> {
+        match &self.command {
+            StorageCommands::Encrypt {
+                snapshots_dir,
+                usage_log,
+                dry_run,
+            } => {
+                let usage_log = match usage_log {
+                    Some(path) => path.clone(),
+                    None => {
+                        crate::cli::usage::usage_log_path().map_err(CostPilotError::config_error)?
+                    }
+                };
+                let encrypted = encrypt_stores(snapshots_dir, &usage_log, *dry_run)?;
+                print_encrypted(&encrypted, *dry_run, format)
+            }
+            StorageCommands::Check {
+                snapshots_dir,
+                baselines,
+                lifecycle_dir,
+            } => {
+                let statuses = check_stores(
+                    snapshots_dir,
+                    baselines,
+                    lifecycle_dir,
+                    &StoreEncryption::from_project(),
+                )?;
+                print_statuses(&statuses, format)?;
+                if statuses.iter().any(|s| s.status == "unreadable") {
+                    return Err(CostPilotError::new(
+                        "COMPAT_001",
+                        ErrorCategory::ValidationError,
+                        "Some stored artifacts cannot be read by this version of CostPilot",
+                    )
+                    .with_hint("Upgrade CostPilot, or recreate the listed files"));
+                }
+                Ok(())
+            }
+            StorageCommands::Migrate { dry_run, rollback } => match rollback {
+                Some(backup) => {
+                    let restored = migrations::restore(backup)?;
+                    if format == "json" {
+                        println!("{}", serde_json::json!({ "restored": restored }));
+                    } else {
+                        println!(
+                            "{} {} file(s) from {}",
+                            "Restored".bright_blue().bold(),
+                            restored,
+                            backup.display()
+                        );
+                    }
+                    Ok(())
+                }
+                None => {
+                    let report = Migrator::project().run(*dry_run)?;
+                    print_migrations(&report, format)
+                }
+            },
+        }
+    }
+}
+
+fn print_migrations(report: &MigrationReport, format: &str) -> Result<(), CostPilotError> {
+    if format == "json" {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(report)
+                .map_err(|e| CostPilotError::serialization_error(e.to_string()))?
+        );
+        return Ok(());
+    }
+
+    if report.applied.is_empty() {
+        println!("{}", "Stored state is up to date".green());
+        return Ok(());
+    }
+    let verb = if report.dry_run {
+        "Would apply"
+    } else {
+        "Applied"
+    };
+    println!(
+        "{} {} migration(s), {} file(s) changed:",
+        verb.bright_blue().bold(),
+        report.applied.len(),
+        report.files_changed()
+    );
+    for step in &report.applied {
+        println!(
+            "  {:04} {:<26} {} file(s)",
+            step.id,
+            step.name,
+            step.files.len()
+        );
+        for file in &step.files {
+            println!("       {}", file.display());
+        }
+    }
+    if let Some(backup) = &report.backup {
+        println!("Backup: {}", backup.display());
+    }
+    Ok(())
+}
+
+/// JSON files directly in `dir` whose names start with `prefix`, sorted
+fn json_files(dir: &Path, prefix: &str) -> Result<Vec<PathBuf>, CostPilotError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        CostPilotError::io_error(format!("Failed to read {}: {}", dir.display(), e))
+    })?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|s| s.to_str()) == Some("json")
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(prefix))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Check the schema and producing version of every stored artifact
+fn check_stores(
+    snapshots_dir: &Path,
+    baselines: &Path,
+    lifecycle_dir: &Path,
+    encryption: &StoreEncryption,
+) -> Result<Vec<ArtifactStatus>, CostPilotError> {
+    let check = |path: &Path, artifact: Artifact, decrypt: bool| {
+        let result = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                if decrypt {
+                    encryption.open(&content).map_err(|e| e.message)
+                } else {
+                    Ok(content)
+                }
+            })
+            .and_then(|content| {
+                let mut document: serde_json::Value =
+                    serde_json::from_str(&content).map_err(|e| e.to_string())?;
+                compat::upgrade(artifact, &mut document).map_err(|e| e.message)
+            });
+        ArtifactStatus::new(path, artifact, result)
+    };
+
+    let mut statuses = Vec::new();
+    if baselines.is_file() {
+        statuses.push(check(baselines, Artifact::Baselines, false));
+    }
+    for path in json_files(snapshots_dir, "snapshot_")? {
+        statuses.push(check(&path, Artifact::Snapshot, true));
+    }
+    for path in json_files(lifecycle_dir, "")? {
+        statuses.push(check(&path, Artifact::PolicyRecord, false));
+    }
+    Ok(statuses)
+}
+
+fn print_statuses(statuses: &[ArtifactStatus], format: &str) -> Result<(), CostPilotError> {
+    if format == "json" {
+        let output = serde_json::json!({
+            "engine_version": compat::ENGINE_VERSION,
+            "artifacts": statuses,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output)
+                .map_err(|e| CostPilotError::serialization_error(e.to_string()))?
+        );
+        return Ok(());
+    }
+
+    if statuses.is_empty() {
+        println!("{}", "No stored artifacts found".green());
+        return Ok(());
+    }
+    println!(
+        "{} {} artifact(s) against CostPilot {}",
+        "Checked".bright_blue().bold(),
+        statuses.len(),
+        compat::ENGINE_VERSION
+    );
+    for status in statuses {
+        let label = match status.status {
+            "current" => "current".green(),
+            "migrated" => "migrated".yellow(),
+            "newer" => "newer".yellow(),
+            other => other.red(),
+        };
+        let written_by = status
+            .produced_by
+            .as_deref()
+            .map(|v| format!(", written by {}", v))
+            .unwrap_or_default();
+        println!(
+            "  {:<10} {} ({} schema {}{})",
+            label,
+            status.path.display(),
+            status.artifact,
+            status.schema_version.as_deref().unwrap_or("?"),
+            written_by
+        );
+        if !status.migrations.is_empty() {
+            println!("             migrations: {}", status.migrations.join(", "));
+        }
+        if let Some(error) = &status.error {
+            println!("             {}", error.red());
+        }
+    }
+    Ok(())
+}
+
+/// Encrypt the plaintext files of both stores; returns the files changed
+fn encrypt_stores(
+    snapshots_dir: &Path,
+    usage_log: &Path,
+    dry_run: bool,
+) -> Result<Vec<PathBuf>, CostPilotError> {
+    let cipher = AtRestCipher::installed().ok_or_else(|| {
+        CostPilotError::new(
+            "AT_REST_001",
+            ErrorCategory::SecurityViolation,
+            "No license installed to derive the storage encryption key",
+        )
+        .with_hint("Install a license before encrypting local stores")
+    })?;
+
+    let mut encrypted = Vec::new();
+    if snapshots_dir.is_dir() {
+        let entries = std::fs::read_dir(snapshots_dir).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", snapshots_dir.display(), e))
+        })?;
+        let mut snapshots: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path.extension().and_then(|s| s.to_str()) == Some("json")
+                    && path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with("snapshot_"))
+            })
+            .collect();
+        snapshots.sort();
+        for path in snapshots {
+            if cipher.encrypt_file(&path, FileLayout::Whole, dry_run)? {
+                encrypted.push(path);
+            }
+        }
+    }
+    if usage_log.is_file() && cipher.encrypt_file(usage_log, FileLayout::Lines, dry_run)? {
+        encrypted.push(usage_log.to_path_buf());
+    }
+    Ok(encrypted)
+}
+
+fn print_encrypted(files: &[PathBuf], dry_run: bool, format: &str) -> Result<(), CostPilotError> {
+    if format == "json" {
+        let output = serde_json::json!({
+            "dry_run": dry_run,
+            "encrypted": files,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output)
+                .map_err(|e| CostPilotError::serialization_error(e.to_string()))?
+        );
+        return Ok(());
+    }
+
+    if files.is_empty() {
+        println!("{}", "No plaintext store files found".green());
+        return Ok(());
+    }
+    let verb = if dry_run {
+        "Would encrypt"
+    } else {
+        "Encrypted"
+    };
+    println!("{} {} file(s):", verb.bright_blue().bold(), files.len());
+    for file in files {
+        println!("  {}", file.display());
+    }
+    if !dry_run {
+        println!(
+            "Set {} in costpilot.yaml to keep new writes encrypted",
+            "storage.encrypt_at_rest: true".cyan()
+        );
+    }
+    Ok(())
+}
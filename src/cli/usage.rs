@@ -40,6 +40,15 @@ pub enum UsageCommand {
         start: String,
         end: String,
     },
+    /// Import CI usage artifacts into PR trackers
+    Import { dir: PathBuf },
+    /// Generate a signed usage receipt for a calendar month
+    Receipt {
+        /// `YYYY-MM`; defaults to the previous month
+        month: Option<String>,
+        license_key: String,
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -47,6 +56,7 @@ pub enum OutputFormat {
     Text,
     Json,
     Csv,
+    Parquet,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -63,6 +73,7 @@ impl FromStr for OutputFormat {
             "text" | "txt" => Ok(Self::Text),
             "json" => Ok(Self::Json),
             "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
             _ => Err(format!("Unknown format: {}", s)),
         }
     }
@@ -193,6 +204,12 @@ pub fn execute_usage_command(cmd: UsageCommand) -> Result<String, String> {
             start,
             end,
         } => execute_invoice(&team_id, &start, &end),
+        UsageCommand::Import { dir } => execute_import(&dir),
+        UsageCommand::Receipt {
+            month,
+            license_key,
+            output,
+        } => execute_receipt(month, &license_key, output),
     }
 }
 
@@ -229,6 +246,9 @@ fn execute_report(
         OutputFormat::Json => serde_json::to_string_pretty(&summary)
             .map_err(|e| format!("JSON serialization failed: {}", e)),
         OutputFormat::Csv => Ok(format_team_summary_csv(&summary)),
+        OutputFormat::Parquet => {
+            Err("Parquet output is only supported for chargeback reports".to_string())
+        }
     }
 }
 
@@ -292,7 +312,7 @@ fn execute_chargeback(
     let teams = load_organization_teams(org_id)?;
 
     // Build chargeback report
-    use crate::engines::metering::ChargebackReportBuilder;
+    use crate::engines::metering::{ChargebackExporter, ChargebackReportBuilder};
 
     let mut builder = ChargebackReportBuilder::new(org_id.to_string(), start_ts, end_ts);
 
@@ -307,11 +327,20 @@ fn execute_chargeback(
         .build()
         .map_err(|e| format!("Failed to build chargeback report: {}", e))?;
 
+    let exporter = ChargebackExporter::new();
     let content = match format {
         OutputFormat::Text => report.format_text(),
         OutputFormat::Json => serde_json::to_string_pretty(&report)
             .map_err(|e| format!("JSON serialization failed: {}", e))?,
-        OutputFormat::Csv => report.to_csv(),
+        OutputFormat::Csv => exporter.to_csv(&report),
+        OutputFormat::Parquet => {
+            let path =
+                output.ok_or_else(|| "Parquet output requires an output file".to_string())?;
+            exporter
+                .write_parquet(&report, &path)
+                .map_err(|e| format!("Failed to export chargeback: {}", e.message))?;
+            return Ok(format!("Chargeback report saved to: {}", path.display()));
+        }
     };
 
     if let Some(path) = output {
@@ -349,6 +378,185 @@ fn execute_invoice(team_id: &str, start: &str, end: &str) -> Result<String, Stri
         .ok_or_else(|| format!("Team {} not found in report", team_id))
 }
 
+fn execute_import(dir: &std::path::Path) -> Result<String, String> {
+    use crate::engines::metering::ArtifactImporter;
+
+    let storage_path = get_storage_path()?;
+    let mut importer = ArtifactImporter::new();
+    for tracker in load_all_pr_trackers(&storage_path)? {
+        importer = importer.with_tracker(tracker);
+    }
+
+    let report = importer
+        .import_dir(dir)
+        .map_err(|e| format!("Failed to import usage artifacts: {}", e.message))?;
+
+    for tracker in report.trackers.values() {
+        let path = storage_path.join(format!(
+            "pr_tracker_{}.json",
+            sanitize_repo_name(tracker.repository())
+        ));
+        let content = serde_json::to_string_pretty(tracker)
+            .map_err(|e| format!("JSON serialization failed: {}", e))?;
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write PR tracker: {}", e))?;
+    }
+
+    let mut output = String::new();
+    output.push_str("📥 Usage Artifact Import\n");
+    output.push_str("========================\n\n");
+    output.push_str(&format!("Runs Imported: {}\n", report.runs_imported));
+    output.push_str(&format!(
+        "Duplicates Skipped: {}\n",
+        report.duplicates_skipped
+    ));
+    output.push_str(&format!("Invalid Files: {}\n", report.invalid_files.len()));
+    for (path, reason) in &report.invalid_files {
+        output.push_str(&format!("  ⚠️  {}: {}\n", path.display(), reason));
+    }
+
+    let price_per_resource = load_pricing_model()?.price_per_resource;
+    let summaries = report.summaries(price_per_resource);
+    if !summaries.is_empty() {
+        output.push_str("\nPRs:\n");
+        for summary in &summaries {
+            output.push_str(&format!(
+                "  {} #{} - {} scans, {} resources (${:.2})\n",
+                summary.repository,
+                summary.pr_number,
+                summary.scan_count,
+                summary.resources_analyzed,
+                summary.estimated_charge
+            ));
+        }
+    }
+
+    Ok(output)
+}
+
+fn execute_receipt(
+    month: Option<String>,
+    license_key: &str,
+    output: Option<PathBuf>,
+) -> Result<String, String> {
+    use crate::engines::metering::UsageReceipt;
+
+    let now = chrono::Utc::now();
+    let month = month.unwrap_or_else(|| previous_month(now));
+    let meter = load_usage_meter()?;
+    let events: Vec<_> = meter
+        .events_between(0, u64::MAX)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    let mut receipt = UsageReceipt::for_month(&events, &month, license_key, now)?;
+    receipt.sign()?;
+
+    let path = output.unwrap_or_else(|| PathBuf::from(format!("costpilot-usage-{}.json", month)));
+    let content = serde_json::to_string_pretty(&receipt)
+        .map_err(|e| format!("JSON serialization failed: {}", e))?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write receipt: {}", e))?;
+
+    Ok(format!(
+        "{}\nReceipt written to: {}\nEmail it to your CostPilot account contact; nothing is sent automatically.",
+        receipt.format_text(),
+        path.display()
+    ))
+}
+
+/// `YYYY-MM` of the month before `now`
+fn previous_month(now: chrono::DateTime<chrono::Utc>) -> String {
+    use chrono::Datelike;
+
+    let (year, month) = match now.month() {
+        1 => (now.year() - 1, 12),
+        month => (now.year(), month - 1),
+    };
+    format!("{:04}-{:02}", year, month)
+}
+
+/// Record a premium feature run in the local usage log
+///
+/// Usage receipts are built from this log. Nothing leaves the machine, and
+/// failures only print a warning with `COSTPILOT_DEBUG` so metering never
+/// breaks a command.
+pub fn record_premium_usage(event_type: crate::engines::metering::UsageEventType) {
+    use crate::engines::metering::UsageMeter;
+
+    let result = usage_log_path().and_then(|path| {
+        UsageMeter::append_to_file(&path, &local_usage_event(event_type)).map_err(|e| e.message)
+    });
+    if let Err(e) = result {
+        tracing::debug!("Failed to record usage: {}", e);
+    }
+}
+
+/// Usage event attributed to the person or CI pipeline running CostPilot
+fn local_usage_event(
+    event_type: crate::engines::metering::UsageEventType,
+) -> crate::engines::metering::UsageEvent {
+    use crate::engines::metering::{Attribution, UsageContext, UsageEvent};
+
+    let env = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+    let repository = crate::edition::CapabilityScope::detect()
+        .repo
+        .unwrap_or_default();
+    let ci_system = if env("GITHUB_ACTIONS").is_some() {
+        Some("github-actions")
+    } else if env("GITLAB_CI").is_some() {
+        Some("gitlab-ci")
+    } else if env("CI").is_some() {
+        Some("ci")
+    } else {
+        None
+    };
+
+    // CI runs count as one identity per pipeline and repository
+    let user_id = match ci_system {
+        Some(system) => format!("ci:{}:{}", system, repository),
+        None => env("COSTPILOT_USER")
+            .or_else(git_user_email)
+            .or_else(|| env("USER"))
+            .or_else(|| env("USERNAME"))
+            .unwrap_or_else(|| "unknown".to_string()),
+    };
+
+    UsageEvent {
+        event_id: uuid::Uuid::new_v4().to_string(),
+        timestamp: current_timestamp(),
+        event_type,
+        attribution: Attribution {
+            user_id,
+            team_id: env("COSTPILOT_TEAM"),
+            org_id: None,
+            cost_center: None,
+            project_id: None,
+        },
+        resources_analyzed: 0,
+        cost_impact: 0.0,
+        duration_ms: 0,
+        context: UsageContext {
+            repository,
+            branch: None,
+            commit: None,
+            pr_number: None,
+            ci_system: ci_system.map(str::to_string),
+            environment: None,
+        },
+        metadata: HashMap::new(),
+    }
+}
+
+fn git_user_email() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["config", "user.email"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    let email = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !email.is_empty()).then_some(email)
+}
+
 // Helper functions for loading data
 // In production, these would load from database or configuration
 
@@ -356,8 +564,7 @@ fn load_usage_meter() -> Result<crate::engines::metering::UsageMeter, String> {
     use crate::engines::metering::UsageMeter;
 
     // Load from file or database
-    let storage_path = get_storage_path()?;
-    let meter_path = storage_path.join("usage_events.ndjson");
+    let meter_path = usage_log_path()?;
 
     if meter_path.exists() {
         // Load existing meter
@@ -387,6 +594,28 @@ fn load_pr_tracker(repository: &str) -> Result<crate::engines::metering::CiUsage
     }
 }
 
+fn load_all_pr_trackers(
+    storage_path: &std::path::Path,
+) -> Result<Vec<crate::engines::metering::CiUsageTracker>, String> {
+    let entries = std::fs::read_dir(storage_path)
+        .map_err(|e| format!("Failed to read storage directory: {}", e))?;
+
+    let mut trackers = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("pr_tracker_") && name.ends_with(".json") {
+            let content = std::fs::read_to_string(entry.path())
+                .map_err(|e| format!("Failed to read PR tracker: {}", e))?;
+            trackers.push(
+                serde_json::from_str(&content)
+                    .map_err(|e| format!("Failed to parse PR tracker: {}", e))?,
+            );
+        }
+    }
+
+    Ok(trackers)
+}
+
 fn load_pricing_model() -> Result<crate::engines::metering::PricingModel, String> {
     // Load from configuration or use default
     Ok(crate::engines::metering::PricingModel::default())
@@ -426,6 +655,11 @@ fn load_organization_id() -> Result<String, String> {
     }
 }
 
+/// Local usage event log premium runs are recorded in
+pub fn usage_log_path() -> Result<PathBuf, String> {
+    Ok(get_storage_path()?.join("usage_events.ndjson"))
+}
+
 fn get_storage_path() -> Result<PathBuf, String> {
     // Use XDG data directory or fallback to ~/.costpilot
     let home =
@@ -584,6 +818,17 @@ fn format_pr_summary_text(summary: &crate::engines::metering::PrUsageSummary) ->
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_previous_month() {
+        let at = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .unwrap()
+                .with_timezone(&chrono::Utc)
+        };
+        assert_eq!(previous_month(at("2025-01-15T00:00:00Z")), "2024-12");
+        assert_eq!(previous_month(at("2025-10-01T00:00:00Z")), "2025-09");
+    }
+
     #[test]
     fn test_parse_date() {
         assert_eq!(parse_date("2024-01-15"), Some((2024, 1, 15)));
@@ -613,6 +858,10 @@ mod tests {
             OutputFormat::from_str("csv"),
             Ok(OutputFormat::Csv)
         ));
+        assert!(matches!(
+            OutputFormat::from_str("parquet"),
+            Ok(OutputFormat::Parquet)
+        ));
         assert!(OutputFormat::from_str("invalid").is_err());
     }
 
@@ -1,8 +1,30 @@
 // CLI utility functions
 
+use crate::engines::shared::error_model::CostPilotError;
+use crate::engines::shared::filter::{Field, FilterExpr};
 use crate::engines::shared::models::ResourceChange;
 use serde_json::Value;
 
+/// Keep the resources `--filter` matches; resources are priced first only
+/// when the expression compares costs
+pub fn filter_changes(
+    filter: &FilterExpr,
+    mut changes: Vec<ResourceChange>,
+    edition: &crate::edition::EditionContext,
+) -> Result<Vec<ResourceChange>, CostPilotError> {
+    let mut estimates = if filter.references(&Field::Cost) {
+        crate::engines::pipeline::predict_estimates(
+            edition,
+            &changes,
+            &crate::engines::prediction::PlanPricing::default(),
+        )?
+    } else {
+        Vec::new()
+    };
+    filter.retain_resources(&mut changes, &mut estimates);
+    Ok(changes)
+}
+
 /// Extract resource changes from Terraform plan
 pub fn extract_resource_changes(
     plan: &Value,
@@ -85,3 +107,37 @@ fn parse_resource_change(resource: &Value) -> Result<ResourceChange, Box<dyn std
         cost_impact: None,
     })
 }
+
+/// Resource changes plus the IaC dialect autofix output should target
+pub struct AutofixInput {
+    pub changes: Vec<ResourceChange>,
+    pub target: crate::engines::autofix::AutofixTarget,
+    pub construct_paths: std::collections::HashMap<String, String>,
+}
+
+/// Load a Terraform plan or a CloudFormation/CDK template for autofix
+pub fn load_autofix_input(
+    path: &std::path::Path,
+    cdk_language: crate::engines::autofix::CdkLanguage,
+) -> Result<AutofixInput, Box<dyn std::error::Error>> {
+    use crate::artifact::{parse_artifact, ArtifactNormalizer};
+    use crate::engines::autofix::{iac_target, AutofixTarget};
+
+    let content = std::fs::read_to_string(path)?;
+    let value: Value = serde_json::from_str(&content)?;
+
+    if value.get("resource_changes").is_some() {
+        return Ok(AutofixInput {
+            changes: extract_resource_changes(&value)?,
+            target: AutofixTarget::Terraform,
+            construct_paths: std::collections::HashMap::new(),
+        });
+    }
+
+    let artifact = parse_artifact(&content, &path.to_string_lossy())?;
+    Ok(AutofixInput {
+        changes: ArtifactNormalizer::normalize(&artifact).to_resource_changes(),
+        target: AutofixTarget::from_artifact(&artifact, cdk_language),
+        construct_paths: iac_target::construct_paths(&artifact),
+    })
+}
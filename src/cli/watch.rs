@@ -0,0 +1,445 @@
+// Watch command - re-scan IaC sources as they are edited
+
+use crate::edition::EditionContext;
+use crate::engines::pipeline::{IncrementalWorkspace, SourceKind, WorkspaceUpdate};
+use crate::engines::policy::{
+    PolicyConfig, PolicyEngine, PolicyLoader, PolicyResult, ZeroNetworkToken,
+};
+use crate::engines::prediction::{
+    pricing_context, CustomHeuristics, HeuristicsLoader, PredictionEngine, CUSTOM_HEURISTICS_DIR,
+    HEURISTICS_VERSION,
+};
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::models::{CostEstimate, Severity};
+use crate::pro_engine::reload::{ReloadOutcome, DEFAULT_RELOAD_INTERVAL};
+use crate::pro_engine::{ProEngineRequest, ProEngineResponse};
+use clap::{Args, ValueEnum};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, RwLock};
+use std::time::Duration;
+
+/// Rows shown per dashboard section
+const MAX_ROWS: usize = 10;
+
+/// Watch Terraform or CDK sources and show cost deltas on every change
+#[derive(Debug, Args)]
+pub struct WatchCommand {
+    /// Terraform directory or cdk.out directory to watch
+    #[arg(value_name = "PATH", default_value = ".")]
+    path: PathBuf,
+
+    /// Source format (default: cdk for cdk.out, terraform otherwise)
+    #[arg(long = "infra-format", short = 'i', value_enum)]
+    infra_format: Option<WatchFormat>,
+
+    /// Path to policy file
+    #[arg(long, value_name = "FILE")]
+    policy: Option<PathBuf>,
+
+    /// Terraform variable file, applied after terraform.tfvars and *.auto.tfvars (repeatable)
+    #[arg(long = "var-file", value_name = "FILE")]
+    var_files: Vec<PathBuf>,
+
+    /// Terraform workspace to evaluate (default: the selected workspace)
+    #[arg(long, value_name = "NAME")]
+    workspace: Option<String>,
+
+    /// Poll interval in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 250)]
+    interval_ms: u64,
+
+    /// Render the dashboard once and exit
+    #[arg(long)]
+    once: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum WatchFormat {
+    Terraform,
+    Cdk,
+}
+
+impl WatchCommand {
+    pub fn execute_with_edition(&self, edition: &EditionContext) -> Result<(), CostPilotError> {
+        if !self.path.is_dir() {
+            return Err(CostPilotError::new(
+                "WATCH_001",
+                ErrorCategory::FileSystemError,
+                format!("Watch path is not a directory: {}", self.path.display()),
+            )
+            .with_hint("Pass a Terraform root directory or a cdk.out directory".to_string()));
+        }
+
+        let kind = match self.infra_format {
+            Some(WatchFormat::Terraform) => SourceKind::Terraform,
+            Some(WatchFormat::Cdk) => SourceKind::Cdk,
+            None => SourceKind::detect(&self.path),
+        };
+
+        let policy = match &self.policy {
+            Some(path) => {
+                let config = PolicyLoader::load_from_file(path)?;
+                PolicyLoader::validate(&config)?;
+                Some(config)
+            }
+            None => None,
+        };
+
+        // Custom heuristics are re-read whenever their files change
+        let loader = HeuristicsLoader::new();
+        let custom_dir = Path::new(CUSTOM_HEURISTICS_DIR);
+        let custom = Arc::new(RwLock::new(loader.load_custom(HEURISTICS_VERSION)?));
+        let mut custom_stamp = CustomHeuristics::dir_stamp(custom_dir);
+
+        let mut workspace = Self::workspace(&self.path, kind, edition, custom.clone())
+            .with_var_files(self.var_files.clone());
+        if let Some(name) = &self.workspace {
+            workspace = workspace.with_terraform_workspace(name.clone());
+        }
+
+        // Pick up engine updates without restarting the session
+        let (reload_tx, reload_rx) = mpsc::channel();
+        let _reload_watcher = edition
+            .pro
+            .as_ref()
+            .and_then(|pro| pro.reloader())
+            .map(|reloader| {
+                reloader.spawn(DEFAULT_RELOAD_INTERVAL, move |outcome| {
+                    let _ = reload_tx.send(outcome.clone());
+                })
+            });
+
+        let interactive = !self.once && atty::is(atty::Stream::Stdout);
+        let mut status = None;
+        let mut last: Option<(WorkspaceUpdate, Option<PolicyResult>)> = None;
+        loop {
+            let mut redraw = false;
+            for outcome in reload_rx.try_iter() {
+                status = Some(match outcome {
+                    ReloadOutcome::Reloaded { generation } => {
+                        workspace.invalidate();
+                        format!("ProEngine reloaded (generation {})", generation)
+                    }
+                    ReloadOutcome::Rejected(e) => format!("ProEngine update rejected: {}", e),
+                    ReloadOutcome::Unchanged => continue,
+                });
+                redraw = true;
+            }
+
+            let stamp = CustomHeuristics::dir_stamp(custom_dir);
+            if stamp != custom_stamp {
+                custom_stamp = stamp;
+                status = Some(match loader.load_custom(HEURISTICS_VERSION) {
+                    Ok(reloaded) => {
+                        let message = format!(
+                            "Custom heuristics reloaded ({} file(s), {} skipped)",
+                            reloaded.files.len(),
+                            reloaded.skipped.len()
+                        );
+                        *custom.write().unwrap() = reloaded;
+                        workspace.invalidate();
+                        message
+                    }
+                    Err(e) => format!("Custom heuristics rejected: {}", e),
+                });
+                redraw = true;
+            }
+
+            if let Some(update) = workspace.refresh()? {
+                let policy_result = policy
+                    .as_ref()
+                    .map(|config| Self::evaluate_policy(config, &update, edition))
+                    .transpose()?;
+                last = Some((update, policy_result));
+                redraw = true;
+            }
+
+            if let (true, Some((update, policy_result))) = (redraw, &last) {
+                if interactive {
+                    // Clear screen and move the cursor home
+                    print!("\x1b[2J\x1b[H");
+                }
+                println!(
+                    "{}",
+                    render_dashboard(
+                        &workspace.root().display().to_string(),
+                        update,
+                        policy_result.as_ref(),
+                        status.as_deref(),
+                    )
+                );
+            }
+
+            if self.once {
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_millis(self.interval_ms));
+        }
+    }
+
+    /// Workspace predicting through the ProEngine when one is loaded, with
+    /// custom heuristics applied on top
+    fn workspace(
+        root: &std::path::Path,
+        kind: SourceKind,
+        edition: &EditionContext,
+        custom: Arc<RwLock<CustomHeuristics>>,
+    ) -> IncrementalWorkspace {
+        let pro = edition.pro.clone();
+        IncrementalWorkspace::new(root, kind).with_predictor(Box::new(move |changes| {
+            let mut estimates = match &pro {
+                Some(pro) => {
+                    let response = pro
+                        .execute(ProEngineRequest::Predict {
+                            changes: changes.to_vec(),
+                            usage: Default::default(),
+                            context: pricing_context(changes),
+                        })
+                        .map_err(|e| {
+                            CostPilotError::new("E_PRO_PREDICT", ErrorCategory::PredictionError, e)
+                        })?;
+                    match response {
+                        ProEngineResponse::Predict(estimates) => estimates,
+                        _ => PredictionEngine::predict_static(changes)?,
+                    }
+                }
+                None => PredictionEngine::predict_static(changes)?,
+            };
+            custom.read().unwrap().apply(changes, &mut estimates);
+            Ok(estimates)
+        }))
+    }
+
+    fn evaluate_policy(
+        config: &PolicyConfig,
+        update: &WorkspaceUpdate,
+        edition: &EditionContext,
+    ) -> Result<PolicyResult, CostPilotError> {
+        let total = CostEstimate::builder()
+            .resource_id("total")
+            .monthly_cost(update.total_monthly_cost)
+            .build();
+        let mut result = PolicyEngine::new(config.clone(), edition)
+            .evaluate_zero_network(&update.changes, &total, ZeroNetworkToken::new())
+            .map_err(|e| {
+                CostPilotError::new(
+                    "POLICY_001",
+                    ErrorCategory::PolicyViolation,
+                    format!("Zero-network policy evaluation failed: {}", e),
+                )
+            })?;
+
+        // Free edition: violations are advisory, as in scan
+        if !edition.capabilities.allow_policy_enforce {
+            for violation in std::mem::take(&mut result.violations) {
+                result.add_warning(format!(
+                    "[{}] {} - {}",
+                    violation.severity, violation.policy_name, violation.message
+                ));
+            }
+            result.passed = true;
+        }
+
+        Ok(result)
+    }
+}
+
+/// Render the watch dashboard for one refresh
+pub fn render_dashboard(
+    root: &str,
+    update: &WorkspaceUpdate,
+    policy: Option<&PolicyResult>,
+    status: Option<&str>,
+) -> String {
+    let mut out = Vec::new();
+
+    out.push(format!("{} {}", "👀 CostPilot Watch".bold().cyan(), root));
+    out.push("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".to_string());
+
+    let mut activity = Vec::new();
+    if !update.reparsed.is_empty() {
+        activity.push(format!("re-parsed {}", update.reparsed.join(", ")));
+    }
+    if !update.removed.is_empty() {
+        activity.push(format!("removed {}", update.removed.join(", ")));
+    }
+    out.push(format!(
+        "   {} ({} ms)",
+        activity.join("; ").bright_black(),
+        update.elapsed_ms
+    ));
+    out.push(String::new());
+
+    out.push(format!("{}", "💰 Monthly Cost".bold()));
+    out.push(format!(
+        "   ${:.2}  {}  ({} resources)",
+        update.total_monthly_cost,
+        format_delta(update.cost_change()),
+        update.changes.len()
+    ));
+    for delta in update.deltas.iter().take(MAX_ROWS) {
+        let before = delta
+            .before
+            .map(|c| format!("${:.2}", c))
+            .unwrap_or_else(|| "new".to_string());
+        let after = delta
+            .after
+            .map(|c| format!("${:.2}", c))
+            .unwrap_or_else(|| "removed".to_string());
+        out.push(format!(
+            "     {} {} → {}  {}",
+            delta.resource_id,
+            before,
+            after,
+            format_delta(delta.change())
+        ));
+    }
+    if update.deltas.len() > MAX_ROWS {
+        out.push(format!(
+            "     … {} more changed",
+            update.deltas.len() - MAX_ROWS
+        ));
+    }
+    out.push(String::new());
+
+    out.push(format!("{}", "🔍 Detections".bold()));
+    if update.detections.is_empty() {
+        out.push(format!("   {}", "No cost issues detected".green()));
+    }
+    for detection in update.detections.iter().take(MAX_ROWS) {
+        let severity = format!("{:?}", detection.severity).to_uppercase();
+        let severity = match detection.severity {
+            Severity::Critical | Severity::High => severity.red(),
+            Severity::Medium => severity.yellow(),
+            Severity::Low => severity.normal(),
+        };
+        out.push(format!(
+            "   • [{}] {}: {}",
+            severity, detection.resource_id, detection.message
+        ));
+    }
+    if update.detections.len() > MAX_ROWS {
+        out.push(format!("   … {} more", update.detections.len() - MAX_ROWS));
+    }
+
+    if let Some(policy) = policy {
+        out.push(String::new());
+        out.push(format!("{}", "📋 Policy".bold()));
+        if policy.violations.is_empty() && policy.warnings.is_empty() {
+            out.push(format!("   {} All policies passed", "✅".green()));
+        }
+        for violation in &policy.violations {
+            out.push(format!(
+                "   {} [{}] {}: {}",
+                "✖".red(),
+                violation.severity,
+                violation.resource_id,
+                violation.message
+            ));
+        }
+        for warning in &policy.warnings {
+            out.push(format!("   {} {}", "⚠".yellow(), warning));
+        }
+    }
+
+    if !update.errors.is_empty() {
+        out.push(String::new());
+        out.push(format!(
+            "{}",
+            "⚠ Parse errors (showing last good state)".yellow()
+        ));
+        for (unit, error) in &update.errors {
+            out.push(format!("   {}: {}", unit, error));
+        }
+    }
+
+    if let Some(status) = status {
+        out.push(String::new());
+        out.push(format!("   {}", status.bright_black()));
+    }
+
+    out.join("\n")
+}
+
+fn format_delta(change: f64) -> String {
+    if change > 0.0 {
+        format!("+${:.2}", change).red().to_string()
+    } else if change < 0.0 {
+        format!("-${:.2}", -change).green().to_string()
+    } else {
+        "±$0.00".bright_black().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::pipeline::ResourceDelta;
+    use crate::engines::policy::PolicyViolation;
+    use crate::engines::shared::models::{Detection, RegressionType};
+    use std::collections::BTreeMap;
+
+    fn update() -> WorkspaceUpdate {
+        WorkspaceUpdate {
+            reparsed: vec!["modules/web".to_string()],
+            removed: Vec::new(),
+            changes: Vec::new(),
+            estimates: Vec::new(),
+            detections: vec![Detection {
+                rule_id: "OVERPROVISIONED_EC2".to_string(),
+                severity: Severity::High,
+                resource_id: "module.web.aws_instance.app".to_string(),
+                regression_type: RegressionType::Configuration,
+                severity_score: 70,
+                message: "Large instance".to_string(),
+                fix_snippet: None,
+                estimated_cost: None,
+            }],
+            errors: BTreeMap::from([("modules/db".to_string(), "unexpected EOF".to_string())]),
+            previous_monthly_cost: 100.0,
+            total_monthly_cost: 250.0,
+            deltas: vec![ResourceDelta {
+                resource_id: "module.web.aws_instance.app".to_string(),
+                before: Some(100.0),
+                after: Some(250.0),
+            }],
+            elapsed_ms: 3,
+        }
+    }
+
+    #[test]
+    fn test_dashboard_shows_delta_detections_and_errors() {
+        let out = render_dashboard(".", &update(), None, Some("ProEngine reloaded"));
+
+        assert!(out.contains("re-parsed modules/web"));
+        assert!(out.contains("$250.00"));
+        assert!(out.contains("+$150.00"));
+        assert!(out.contains("module.web.aws_instance.app $100.00 → $250.00"));
+        assert!(out.contains("Large instance"));
+        assert!(out.contains("modules/db: unexpected EOF"));
+        assert!(out.contains("ProEngine reloaded"));
+        assert!(!out.contains("Policy"));
+    }
+
+    #[test]
+    fn test_dashboard_lists_policy_violations() {
+        let policy = PolicyResult {
+            violations: vec![PolicyViolation {
+                policy_name: "budget".to_string(),
+                severity: "HIGH".to_string(),
+                resource_id: "global".to_string(),
+                message: "Monthly budget exceeded".to_string(),
+                actual_value: "$250".to_string(),
+                expected_value: "$200".to_string(),
+            }],
+            warnings: Vec::new(),
+            applied_exemptions: Vec::new(),
+            passed: false,
+        };
+
+        let out = render_dashboard(".", &update(), Some(&policy), None);
+        assert!(out.contains("Policy"));
+        assert!(out.contains("global: Monthly budget exceeded"));
+    }
+}
@@ -0,0 +1,488 @@
+// Library facade: one call from plan or artifact to analysis result
+//
+// `CostPilot` wires the engines together the way `costpilot scan` does
+// (edition, parsing, prediction, detection, optional explain/mapping/
+// grouping, policy evaluation) so embedders do not have to. Output options
+// and CI gates stay in the CLI; this returns data only.
+
+use crate::artifact::{parse_artifact, ArtifactNormalizer};
+use crate::edition::{detect_edition, EditionContext, EditionMode};
+use crate::engines::detection::DetectionEngine;
+use crate::engines::explain::explain_engine::Explanation;
+use crate::engines::grouping::ComprehensiveReport;
+use crate::engines::mapping::{DependencyGraph, GraphConfig};
+use crate::engines::pipeline::{predict_estimates, ResourceArena, ScanPipeline};
+use crate::engines::policy::{
+    PolicyConfig, PolicyEngine, PolicyLoader, PolicyResult, ZeroNetworkToken,
+};
+use crate::engines::prediction::{ClusterPricing, PlanPricing, UsageProfile};
+use crate::engines::shared::cancel::CancelToken;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
+use crate::engines::shared::progress::{map_batches, Phase, ProgressReporter, ProgressSink};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Resources predicted between cancellation checks and progress reports
+const PREDICTION_BATCH: usize = 256;
+
+/// Analysis of one plan or artifact
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalysisResult {
+    /// Edition the analysis ran under (`free`, `premium` or `trial`)
+    pub edition: String,
+    pub total_monthly: f64,
+    pub changes: Vec<ResourceChange>,
+    pub estimates: Vec<CostEstimate>,
+    pub detections: Vec<Detection>,
+    /// One explanation per detection; empty unless explain is enabled
+    pub explanations: Vec<Explanation>,
+    /// Dependency graph; `None` unless mapping is enabled
+    pub graph: Option<DependencyGraph>,
+    /// Module/service/environment grouping; `None` unless grouping is enabled
+    pub grouping: Option<ComprehensiveReport>,
+    /// `None` when no policy was configured
+    pub policy_result: Option<PolicyResult>,
+    /// Wall time of each pipeline stage
+    pub stage_durations_ms: BTreeMap<String, u64>,
+}
+
+impl AnalysisResult {
+    /// Estimate for a resource address
+    pub fn estimate(&self, resource_id: &str) -> Option<&CostEstimate> {
+        self.estimates.iter().find(|e| e.resource_id == resource_id)
+    }
+
+    /// False when an enforced policy reported violations
+    pub fn passed(&self) -> bool {
+        self.policy_result.as_ref().map_or(true, |r| r.passed)
+    }
+}
+
+/// Configured analysis pipeline; cheap to reuse across plans
+#[derive(Clone)]
+pub struct CostPilot {
+    edition: EditionContext,
+    policy: Option<PolicyConfig>,
+    cluster_pricing: ClusterPricing,
+    usage: UsageProfile,
+    progress: ProgressReporter,
+    explain: bool,
+    mapping: bool,
+    grouping: bool,
+    parallel: bool,
+}
+
+impl CostPilot {
+    /// Pipeline with defaults: detected edition, no policy, analysis only
+    pub fn new() -> Self {
+        Self::builder().build_unchecked()
+    }
+
+    pub fn builder() -> CostPilotBuilder {
+        CostPilotBuilder::default()
+    }
+
+    pub fn edition(&self) -> &EditionContext {
+        &self.edition
+    }
+
+    /// Analyze a Terraform plan JSON or a CloudFormation/CDK, SAM,
+    /// Serverless or Kubernetes file
+    pub fn analyze_file(&self, path: impl AsRef<Path>) -> Result<AnalysisResult> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        self.analyze_str(&content, &path.to_string_lossy())
+    }
+
+    /// Analyze plan or artifact content; `hint` is the file name used to
+    /// detect artifact formats and may be empty for Terraform plans
+    pub fn analyze_str(&self, content: &str, hint: &str) -> Result<AnalysisResult> {
+        self.analyze_changes(resource_changes(content, hint)?)
+    }
+
+    /// Analyze resource changes that were already extracted
+    pub fn analyze_changes(&self, changes: Vec<ResourceChange>) -> Result<AnalysisResult> {
+        self.analyze_changes_cancellable(changes, &CancelToken::new())
+    }
+
+    /// Analyze resource changes, stopping at the next batch boundary once
+    /// `token` is cancelled
+    pub fn analyze_changes_cancellable(
+        &self,
+        changes: Vec<ResourceChange>,
+        token: &CancelToken,
+    ) -> Result<AnalysisResult> {
+        let pricing = PlanPricing::new(self.cluster_pricing.clone(), self.usage.clone(), &changes);
+        let estimates = map_batches(
+            &changes,
+            PREDICTION_BATCH,
+            Phase::Prediction,
+            token,
+            &self.progress,
+            |batch| predict_estimates(&self.edition, batch, &pricing),
+        )?;
+        let arena = ResourceArena::new(changes, estimates);
+
+        let graph_config = GraphConfig {
+            max_depth: if self.edition.is_free() {
+                Some(1)
+            } else {
+                None
+            },
+            ..GraphConfig::default()
+        };
+        let mut pipeline = ScanPipeline::new()
+            .with_graph_config(graph_config)
+            .with_explain(self.explain)
+            .with_mapping(self.mapping)
+            .with_grouping(self.grouping)
+            .with_cancel_token(token.clone())
+            .with_progress(self.progress.clone());
+        if !self.parallel {
+            pipeline = pipeline.sequential();
+        }
+        let output = pipeline.run(&arena)?;
+
+        let policy_result = match &self.policy {
+            Some(config) => Some(self.evaluate_policy(config.clone(), &arena)?),
+            None => None,
+        };
+
+        Ok(AnalysisResult {
+            edition: match self.edition.mode {
+                EditionMode::Free => "free",
+                EditionMode::Premium => "premium",
+                EditionMode::Trial => "trial",
+            }
+            .to_string(),
+            total_monthly: arena.total_monthly_cost(),
+            changes: arena.changes().to_vec(),
+            estimates: arena.estimates().to_vec(),
+            detections: output.detections,
+            explanations: output.explanations,
+            graph: output.graph,
+            grouping: output.grouping,
+            policy_result,
+            stage_durations_ms: output.stage_durations_ms,
+        })
+    }
+
+    /// Evaluate the policy; violations are advisory when the edition or the
+    /// organization capability policy does not allow enforcement
+    fn evaluate_policy(&self, config: PolicyConfig, arena: &ResourceArena) -> Result<PolicyResult> {
+        let total = CostEstimate {
+            resource_id: "total".to_string(),
+            monthly_cost: arena.total_monthly_cost(),
+            prediction_interval_low: 0.0,
+            prediction_interval_high: 0.0,
+            confidence_score: 0.0,
+            heuristic_reference: None,
+            cold_start_inference: false,
+            one_time: None,
+            breakdown: None,
+            hourly: None,
+            daily: None,
+            provenance: None,
+        };
+        let mut result = PolicyEngine::new(config, &self.edition)
+            .evaluate_zero_network(arena.changes(), &total, ZeroNetworkToken::new())
+            .map_err(|e| {
+                CostPilotError::new(
+                    "POLICY_001",
+                    ErrorCategory::PolicyViolation,
+                    format!("Zero-network policy evaluation failed: {}", e),
+                )
+            })?;
+
+        if !self.edition.capabilities.allow_policy_enforce {
+            for violation in std::mem::take(&mut result.violations) {
+                result.add_warning(format!(
+                    "[{}] {} - {} (actual: {}, expected: {})",
+                    violation.severity,
+                    violation.policy_name,
+                    violation.message,
+                    violation.actual_value,
+                    violation.expected_value
+                ));
+            }
+            result.passed = true;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+impl CostPilot {
+    /// `analyze_str` on a worker thread; dropping the future or cancelling
+    /// `token` stops the analysis at the next batch boundary
+    pub fn analyze_str_async(
+        &self,
+        content: String,
+        hint: String,
+        token: CancelToken,
+    ) -> impl std::future::Future<Output = Result<AnalysisResult>> + Send {
+        let client = self.clone();
+        crate::engines::shared::worker::run_blocking(token, move |token| {
+            client.analyze_changes_cancellable(resource_changes(&content, &hint)?, token)
+        })
+    }
+
+    /// `analyze_changes` on a worker thread
+    pub fn analyze_changes_async(
+        &self,
+        changes: Vec<ResourceChange>,
+        token: CancelToken,
+    ) -> impl std::future::Future<Output = Result<AnalysisResult>> + Send {
+        let client = self.clone();
+        crate::engines::shared::worker::run_blocking(token, move |token| {
+            client.analyze_changes_cancellable(changes, token)
+        })
+    }
+}
+
+impl Default for CostPilot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for `CostPilot`
+#[derive(Default)]
+pub struct CostPilotBuilder {
+    edition: Option<EditionContext>,
+    policy: Option<PolicyConfig>,
+    policy_file: Option<PathBuf>,
+    cluster_pricing: Option<ClusterPricing>,
+    usage: Option<UsageProfile>,
+    progress: ProgressReporter,
+    explain: bool,
+    mapping: bool,
+    grouping: bool,
+    sequential: bool,
+}
+
+impl CostPilotBuilder {
+    /// Edition to run under (default: detected from the installed license)
+    pub fn edition(mut self, edition: EditionContext) -> Self {
+        self.edition = Some(edition);
+        self
+    }
+
+    /// Policy evaluated against every analysis
+    pub fn policy(mut self, policy: PolicyConfig) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Policy file loaded when the pipeline is built
+    pub fn policy_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.policy_file = Some(path.into());
+        self
+    }
+
+    /// Pricing model for Kubernetes workloads
+    pub fn cluster_pricing(mut self, pricing: ClusterPricing) -> Self {
+        self.cluster_pricing = Some(pricing);
+        self
+    }
+
+    /// Monthly usage of usage-billed resources (`usage` in costpilot.yaml)
+    pub fn usage_profile(mut self, usage: UsageProfile) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Receive per-phase progress, e.g. to drive a progress bar
+    pub fn progress(mut self, sink: Arc<dyn ProgressSink>) -> Self {
+        self.progress = ProgressReporter::new(sink);
+        self
+    }
+
+    /// Explain each detection
+    pub fn explain(mut self, enabled: bool) -> Self {
+        self.explain = enabled;
+        self
+    }
+
+    /// Build the dependency graph (direct dependencies only in Free)
+    pub fn mapping(mut self, enabled: bool) -> Self {
+        self.mapping = enabled;
+        self
+    }
+
+    /// Build the module/service/environment grouping
+    pub fn grouping(mut self, enabled: bool) -> Self {
+        self.grouping = enabled;
+        self
+    }
+
+    /// Run pipeline stages on the calling thread
+    pub fn sequential(mut self) -> Self {
+        self.sequential = true;
+        self
+    }
+
+    /// Build the pipeline, loading the policy file if one was given
+    pub fn build(mut self) -> Result<CostPilot> {
+        if let Some(path) = self.policy_file.take() {
+            self.policy = Some(PolicyLoader::load_from_file(&path)?);
+        }
+        Ok(self.build_unchecked())
+    }
+
+    fn build_unchecked(self) -> CostPilot {
+        CostPilot {
+            edition: self
+                .edition
+                .unwrap_or_else(|| detect_edition().unwrap_or_else(|_| EditionContext::free())),
+            policy: self.policy,
+            cluster_pricing: self.cluster_pricing.unwrap_or_default(),
+            usage: self.usage.unwrap_or_default(),
+            progress: self.progress,
+            explain: self.explain,
+            mapping: self.mapping,
+            grouping: self.grouping,
+            parallel: !self.sequential,
+        }
+    }
+}
+
+/// Resource changes from a Terraform plan JSON or any artifact
+/// `parse_artifact` understands
+pub fn resource_changes(content: &str, hint: &str) -> Result<Vec<ResourceChange>> {
+    let is_plan = serde_json::from_str::<serde_json::Value>(content)
+        .map(|value| value.get("resource_changes").is_some())
+        .unwrap_or(false);
+    if is_plan {
+        return DetectionEngine::new().detect_from_terraform_json(content);
+    }
+    let artifact = parse_artifact(content, hint)?;
+    Ok(ArtifactNormalizer::normalize(&artifact).to_resource_changes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = r#"{
+        "format_version": "1.0",
+        "resource_changes": [
+            {"address": "aws_instance.web", "type": "aws_instance", "name": "web",
+             "change": {"actions": ["create"], "before": null,
+                        "after": {"instance_type": "m5.xlarge"}}}
+        ]
+    }"#;
+
+    fn budget(limit: f64) -> PolicyConfig {
+        serde_json::from_value(serde_json::json!({
+            "version": "1.0",
+            "budgets": {"global": {"monthly_limit": limit}}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_analyze_plan_with_stages() {
+        let client = CostPilot::builder()
+            .edition(EditionContext::free())
+            .mapping(true)
+            .grouping(true)
+            .sequential()
+            .build()
+            .unwrap();
+        let result = client.analyze_str(PLAN, "").unwrap();
+        assert_eq!(result.edition, "free");
+        assert_eq!(result.changes.len(), 1);
+        assert!(result.estimate("aws_instance.web").unwrap().monthly_cost > 0.0);
+        assert!(result.graph.is_some());
+        assert!(result.grouping.is_some());
+        assert!(result.explanations.is_empty());
+        assert!(result.passed());
+    }
+
+    #[test]
+    fn test_policy_is_advisory_without_enforcement() {
+        let free = CostPilot::builder()
+            .edition(EditionContext::free())
+            .policy(budget(1.0))
+            .build()
+            .unwrap()
+            .analyze_str(PLAN, "")
+            .unwrap();
+        let policy = free.policy_result.as_ref().unwrap();
+        assert!(free.passed());
+        assert!(policy.violations.is_empty());
+        assert!(policy.warnings.iter().any(|w| w.contains("global_budget")));
+
+        let premium = CostPilot::builder()
+            .edition(EditionContext::premium_for_test())
+            .policy(budget(1.0))
+            .build()
+            .unwrap()
+            .analyze_str(PLAN, "")
+            .unwrap();
+        assert!(!premium.passed());
+        assert_eq!(premium.edition, "premium");
+    }
+
+    #[test]
+    fn test_progress_covers_prediction_and_analysis() {
+        use crate::engines::shared::progress::Progress;
+        use std::sync::Mutex;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        CostPilot::builder()
+            .edition(EditionContext::free())
+            .progress(Arc::new(move |p: Progress| sink.lock().unwrap().push(p)))
+            .build()
+            .unwrap()
+            .analyze_str(PLAN, "")
+            .unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.first().unwrap().phase, Phase::Prediction);
+        assert!(seen
+            .iter()
+            .any(|p| p.phase == Phase::Prediction && p.is_done()));
+        assert!(seen
+            .iter()
+            .any(|p| p.phase == Phase::Analysis && p.is_done()));
+    }
+
+    #[test]
+    fn test_builder_reports_missing_policy_file() {
+        let err = CostPilot::builder()
+            .policy_file("/nonexistent/policy.yml")
+            .build()
+            .err()
+            .expect("policy file does not exist");
+        assert_eq!(err.id, "POLICY_001");
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_analyze_async_honours_token() {
+        use futures::executor::block_on;
+
+        let client = CostPilot::builder()
+            .edition(EditionContext::free())
+            .build()
+            .unwrap();
+        let result =
+            block_on(client.analyze_str_async(PLAN.to_string(), String::new(), CancelToken::new()))
+                .unwrap();
+        assert_eq!(result.changes.len(), 1);
+
+        let token = CancelToken::new();
+        token.cancel();
+        let err =
+            block_on(client.analyze_str_async(PLAN.to_string(), String::new(), token)).unwrap_err();
+        assert_eq!(err.id, "CANCELLED");
+    }
+}
@@ -169,6 +169,12 @@ pub struct DeploymentSimulation {
     pub dry_run_only: bool,
 }
 
+/// Environment variable read by `--profile` when the flag is not given
+pub const PROFILE_ENV_VAR: &str = "COSTPILOT_PROFILE";
+
+/// Top-level key holding per-environment overlays
+pub const PROFILES_KEY: &str = "profiles";
+
 /// Load product specification from the default path
 pub fn load_product_spec() -> Result<ProductSpec, ConfigError> {
     let path = Path::new("products/costpilot/product.yml");
@@ -177,17 +183,93 @@ pub fn load_product_spec() -> Result<ProductSpec, ConfigError> {
 
 /// Load product specification from a custom path
 pub fn load_product_spec_from_path<P: AsRef<Path>>(path: P) -> Result<ProductSpec, ConfigError> {
+    load_product_spec_with_profile(path, None)
+}
+
+/// Load product specification with an explicit profile (`None` for the base config)
+pub fn load_product_spec_with_profile<P: AsRef<Path>>(
+    path: P,
+    profile: Option<&str>,
+) -> Result<ProductSpec, ConfigError> {
     let content = fs::read_to_string(path).map_err(ConfigError::Io)?;
-    let spec: ProductSpec = serde_yaml::from_str(&content).map_err(ConfigError::Yaml)?;
+    let root: serde_yaml::Value = serde_yaml::from_str(&content).map_err(ConfigError::Yaml)?;
+    let spec: ProductSpec =
+        serde_yaml::from_value(apply_profile(root, profile)?).map_err(ConfigError::Yaml)?;
     Ok(spec)
 }
 
+/// Names of the profiles defined in a config document
+pub fn profile_names(root: &serde_yaml::Value) -> Vec<String> {
+    root.get(PROFILES_KEY)
+        .and_then(|p| p.as_mapping())
+        .map(|profiles| {
+            profiles
+                .keys()
+                .filter_map(|k| k.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Strip the `profiles` section and deep-merge the selected profile over the base
+///
+/// Mappings merge key by key; any other value in the profile (scalars,
+/// sequences, null) replaces the base value.
+pub fn apply_profile(
+    mut root: serde_yaml::Value,
+    profile: Option<&str>,
+) -> Result<serde_yaml::Value, ConfigError> {
+    let available = profile_names(&root);
+    let mut profiles = match root.as_mapping_mut() {
+        Some(map) => map.remove(PROFILES_KEY),
+        None => None,
+    };
+
+    let Some(name) = profile else {
+        return Ok(root);
+    };
+    let overlay = profiles
+        .as_mut()
+        .and_then(|p| p.as_mapping_mut())
+        .and_then(|p| p.remove(name))
+        .ok_or_else(|| ConfigError::UnknownProfile {
+            name: name.to_string(),
+            available: if available.is_empty() {
+                "none defined".to_string()
+            } else {
+                available.join(", ")
+            },
+        })?;
+
+    deep_merge(&mut root, overlay);
+    Ok(root)
+}
+
+/// Merge `overlay` into `base`, recursing into mappings
+pub fn deep_merge(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    match (base, overlay) {
+        (serde_yaml::Value::Mapping(base), serde_yaml::Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     #[error("YAML parsing error: {0}")]
     Yaml(#[from] serde_yaml::Error),
+    #[error("Unknown config profile '{name}' (available: {available})")]
+    UnknownProfile { name: String, available: String },
 }
 
 #[cfg(test)]
@@ -272,6 +354,40 @@ mod tests {
         assert!(matches!(result.unwrap_err(), ConfigError::Yaml(_)));
     }
 
+    #[test]
+    fn test_profile_deep_merges_over_base() {
+        let root: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+budgets:
+  monthly_limit: 1000
+  warn_at_percent: 80
+regions: [us-east-1]
+profiles:
+  prod:
+    budgets:
+      monthly_limit: 500
+  dev:
+    regions: [us-west-2, eu-west-1]
+"#,
+        )
+        .unwrap();
+
+        let base = apply_profile(root.clone(), None).unwrap();
+        assert!(base.get(PROFILES_KEY).is_none());
+        assert_eq!(base["budgets"]["monthly_limit"], 1000);
+
+        let prod = apply_profile(root.clone(), Some("prod")).unwrap();
+        assert_eq!(prod["budgets"]["monthly_limit"], 500);
+        assert_eq!(prod["budgets"]["warn_at_percent"], 80);
+
+        let dev = apply_profile(root.clone(), Some("dev")).unwrap();
+        assert_eq!(dev["regions"].as_sequence().unwrap().len(), 2);
+        assert_eq!(dev["budgets"]["monthly_limit"], 1000);
+
+        let err = apply_profile(root, Some("staging")).unwrap_err();
+        assert!(err.to_string().contains("available: prod, dev"));
+    }
+
     #[test]
     fn test_missing_file_returns_error() {
         let result = load_product_spec_from_path("nonexistent.yml");
@@ -1,3 +1,4 @@
+use super::capability_policy::Capability;
 use super::EditionContext;
 
 /// Feature capabilities determined by edition mode
@@ -38,4 +39,38 @@ impl Capabilities {
             }
         }
     }
+
+    /// Whether `capability` is enabled
+    pub fn allows(&self, capability: Capability) -> bool {
+        *self.flag(capability)
+    }
+
+    /// Turn `capability` off
+    pub fn disable(&mut self, capability: Capability) {
+        *self.flag_mut(capability) = false;
+    }
+
+    fn flag(&self, capability: Capability) -> &bool {
+        match capability {
+            Capability::Predict => &self.allow_predict,
+            Capability::ExplainFull => &self.allow_explain_full,
+            Capability::Autofix => &self.allow_autofix,
+            Capability::MappingDeep => &self.allow_mapping_deep,
+            Capability::Trend => &self.allow_trend,
+            Capability::PolicyEnforce => &self.allow_policy_enforce,
+            Capability::SloEnforce => &self.allow_slo_enforce,
+        }
+    }
+
+    fn flag_mut(&mut self, capability: Capability) -> &mut bool {
+        match capability {
+            Capability::Predict => &mut self.allow_predict,
+            Capability::ExplainFull => &mut self.allow_explain_full,
+            Capability::Autofix => &mut self.allow_autofix,
+            Capability::MappingDeep => &mut self.allow_mapping_deep,
+            Capability::Trend => &mut self.allow_trend,
+            Capability::PolicyEnforce => &mut self.allow_policy_enforce,
+            Capability::SloEnforce => &mut self.allow_slo_enforce,
+        }
+    }
 }
@@ -0,0 +1,373 @@
+// Organization capability policy - narrow Premium capabilities per repository or team
+//
+// A Premium license enables every capability. An Enterprise admin can have
+// the license issuer sign a policy bound to their license key that disables
+// capabilities such as autofix or policy enforcement for matching
+// repositories and teams, while leaving the rest (predict, trend, ...)
+// enabled. `detect_edition` verifies the policy against the active license
+// and narrows `EditionContext::capabilities`, so commands only ever see the
+// restricted set.
+
+use crate::pro_engine::License;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Policy file read from the CostPilot config directory
+pub const CAPABILITY_POLICY_FILE: &str = "capability-policy.json";
+
+/// A capability an organization policy can disable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    Predict,
+    ExplainFull,
+    Autofix,
+    MappingDeep,
+    Trend,
+    PolicyEnforce,
+    SloEnforce,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 7] = [
+        Capability::Predict,
+        Capability::ExplainFull,
+        Capability::Autofix,
+        Capability::MappingDeep,
+        Capability::Trend,
+        Capability::PolicyEnforce,
+        Capability::SloEnforce,
+    ];
+
+    /// Name used in policy files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Capability::Predict => "predict",
+            Capability::ExplainFull => "explain_full",
+            Capability::Autofix => "autofix",
+            Capability::MappingDeep => "mapping_deep",
+            Capability::Trend => "trend",
+            Capability::PolicyEnforce => "policy_enforce",
+            Capability::SloEnforce => "slo_enforce",
+        }
+    }
+}
+
+/// Repository and team a command runs for
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapabilityScope {
+    /// Repository as `owner/name` (GitLab subgroups keep every path segment)
+    pub repo: Option<String>,
+    pub team: Option<String>,
+}
+
+impl CapabilityScope {
+    /// Scope of the current process
+    ///
+    /// The repository comes from `COSTPILOT_REPO`, `GITHUB_REPOSITORY`,
+    /// `CI_PROJECT_PATH` or the `origin` remote, in that order; the team
+    /// from `COSTPILOT_TEAM`.
+    pub fn detect() -> Self {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let repo = env("COSTPILOT_REPO")
+            .or_else(|| env("GITHUB_REPOSITORY"))
+            .or_else(|| env("CI_PROJECT_PATH"))
+            .or_else(origin_repository);
+        Self {
+            repo: repo.map(|r| r.trim().to_string()),
+            team: env("COSTPILOT_TEAM").map(|t| t.trim().to_string()),
+        }
+    }
+
+    /// Human-readable description for messages
+    pub fn describe(&self) -> String {
+        match (&self.repo, &self.team) {
+            (Some(repo), Some(team)) => format!("repository {} (team {})", repo, team),
+            (Some(repo), None) => format!("repository {}", repo),
+            (None, Some(team)) => format!("team {}", team),
+            (None, None) => "this workspace".to_string(),
+        }
+    }
+}
+
+/// One rule of a capability policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityRule {
+    pub name: String,
+    /// Repository patterns (`*` wildcards); empty matches every repository
+    #[serde(default)]
+    pub repos: Vec<String>,
+    /// Team patterns (`*` wildcards); empty matches every team
+    #[serde(default)]
+    pub teams: Vec<String>,
+    pub disable: Vec<Capability>,
+}
+
+impl CapabilityRule {
+    /// Whether the rule applies to `scope`
+    ///
+    /// A rule listing both repositories and teams needs both to match. An
+    /// unknown repository or team never matches a non-empty list.
+    pub fn applies_to(&self, scope: &CapabilityScope) -> bool {
+        matches_any(&self.repos, scope.repo.as_deref())
+            && matches_any(&self.teams, scope.team.as_deref())
+    }
+}
+
+/// Capability policy signed by the license issuer for one license
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityPolicy {
+    pub license_key: String,
+    pub issuer: String,
+    pub rules: Vec<CapabilityRule>,
+    /// Hex Ed25519 signature over `signing_message()`
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl CapabilityPolicy {
+    /// Load a policy file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read capability policy: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid capability policy: {}", e))
+    }
+
+    /// Canonical message the issuer signs
+    pub fn signing_message(&self) -> String {
+        format!(
+            "{}|{}|{}",
+            self.license_key,
+            self.issuer,
+            serde_json::to_string(&self.rules).unwrap_or_default()
+        )
+    }
+
+    /// Check the policy was issued for `license` and carries a valid signature
+    pub fn verify(&self, license: &License) -> Result<(), String> {
+        if self.license_key != license.license_key {
+            return Err("Capability policy was issued for a different license".to_string());
+        }
+        if self.issuer != license.issuer {
+            return Err(format!(
+                "Capability policy issuer {} does not match license issuer {}",
+                self.issuer, license.issuer
+            ));
+        }
+        if self.signature.is_empty() {
+            return Err("Capability policy is not signed".to_string());
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            crate::pro_engine::crypto::verify_capability_policy_signature(self)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Err("Capability policies cannot be verified on this target".to_string())
+        }
+    }
+
+    /// Capabilities disabled for `scope`, with the first rule disabling each
+    pub fn disabled_for(&self, scope: &CapabilityScope) -> BTreeMap<Capability, String> {
+        let mut disabled = BTreeMap::new();
+        for rule in self.rules.iter().filter(|r| r.applies_to(scope)) {
+            for capability in &rule.disable {
+                disabled
+                    .entry(*capability)
+                    .or_insert_with(|| rule.name.clone());
+            }
+        }
+        disabled
+    }
+}
+
+/// Capabilities an organization policy disabled for the current scope
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrgRestrictions {
+    pub scope: CapabilityScope,
+    /// Disabled capability -> name of the rule that disabled it
+    pub disabled: BTreeMap<Capability, String>,
+}
+
+fn matches_any(patterns: &[String], value: Option<&str>) -> bool {
+    if patterns.is_empty() {
+        return true;
+    }
+    let Some(value) = value else {
+        return false;
+    };
+    let value = value.to_lowercase();
+    patterns
+        .iter()
+        .any(|pattern| wildcard_match(&pattern.to_lowercase(), &value))
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// `owner/name` of the `origin` remote of the current directory
+fn origin_repository() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    repository_from_remote(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// Repository path of a git remote URL (`git@host:owner/name.git`,
+/// `https://host/owner/name`, `ssh://git@host/owner/name.git`)
+fn repository_from_remote(url: &str) -> Option<String> {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    path.contains('/').then(|| path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn scope(repo: Option<&str>, team: Option<&str>) -> CapabilityScope {
+        CapabilityScope {
+            repo: repo.map(str::to_string),
+            team: team.map(str::to_string),
+        }
+    }
+
+    fn policy() -> CapabilityPolicy {
+        serde_json::from_str(
+            r#"{
+                "license_key": "ENT-123",
+                "issuer": "test-costpilot",
+                "rules": [
+                    {"name": "payments-read-only", "repos": ["acme/payments-*"],
+                     "disable": ["autofix", "policy_enforce"]},
+                    {"name": "platform-no-autofix", "teams": ["platform"], "disable": ["autofix"]}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn test_license() -> License {
+        License {
+            email: "admin@acme.example".to_string(),
+            license_key: "ENT-123".to_string(),
+            expires: "2099-01-01T00:00:00Z".to_string(),
+            signature: "00".to_string(),
+            issuer: "test-costpilot".to_string(),
+        }
+    }
+
+    fn sign(policy: &mut CapabilityPolicy) {
+        // Test issuer key, matching TEST_LICENSE_PUBLIC_KEY
+        let key = SigningKey::from_bytes(&[42u8; 32]);
+        policy.signature = hex::encode(key.sign(policy.signing_message().as_bytes()).to_bytes());
+    }
+
+    #[test]
+    fn test_rules_match_repo_and_team() {
+        let policy = policy();
+
+        let payments = policy.disabled_for(&scope(Some("Acme/Payments-API"), None));
+        assert_eq!(
+            payments.keys().copied().collect::<Vec<_>>(),
+            vec![Capability::Autofix, Capability::PolicyEnforce]
+        );
+        assert_eq!(payments[&Capability::Autofix], "payments-read-only");
+
+        let platform = policy.disabled_for(&scope(Some("acme/infra"), Some("platform")));
+        assert_eq!(
+            platform.keys().copied().collect::<Vec<_>>(),
+            vec![Capability::Autofix]
+        );
+        assert!(policy.disabled_for(&scope(None, None)).is_empty());
+
+        assert_eq!(
+            repository_from_remote("git@github.com:acme/payments-api.git").as_deref(),
+            Some("acme/payments-api")
+        );
+        assert_eq!(
+            repository_from_remote("https://gitlab.example.com/acme/infra/platform").as_deref(),
+            Some("acme/infra/platform")
+        );
+        assert_eq!(repository_from_remote("/srv/repo"), None);
+    }
+
+    #[test]
+    fn test_policy_signature_binds_license_and_rules() {
+        let mut policy = policy();
+        let license = test_license();
+        assert!(policy.verify(&license).is_err());
+
+        sign(&mut policy);
+        policy.verify(&license).unwrap();
+
+        let mut other = license.clone();
+        other.license_key = "ENT-999".to_string();
+        assert!(policy.verify(&other).is_err());
+
+        let mut tampered = policy.clone();
+        tampered.rules[0]
+            .disable
+            .retain(|c| *c != Capability::PolicyEnforce);
+        assert!(tampered.verify(&license).is_err());
+    }
+
+    #[test]
+    fn test_edition_enforces_org_restrictions() {
+        let mut policy = policy();
+        sign(&mut policy);
+        let mut edition = crate::edition::EditionContext::premium_for_test();
+        edition.license = Some(test_license());
+
+        edition
+            .apply_capability_policy(&policy, scope(Some("acme/payments-api"), None))
+            .unwrap();
+        assert!(!edition.capabilities.allow_autofix);
+        assert!(!edition.capabilities.allow_policy_enforce);
+        assert!(edition.capabilities.allow_predict);
+        assert!(edition.capabilities.allow_trend);
+
+        let error = edition
+            .require_capability(Capability::Autofix, "Autofix")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("payments-read-only"), "{}", error);
+        assert!(edition
+            .require_capability(Capability::Trend, "Trend tracking")
+            .is_ok());
+        assert!(crate::edition::EditionContext::free()
+            .require_capability(Capability::Trend, "Trend tracking")
+            .unwrap_err()
+            .to_string()
+            .contains("requires CostPilot Premium"));
+    }
+}
@@ -1,6 +1,7 @@
 // Edition-specific errors
 
 use super::EditionContext;
+use crate::i18n::tf;
 
 /// Error when Premium feature is used in Free edition
 #[derive(Debug)]
@@ -10,12 +11,38 @@ pub struct UpgradeRequired {
 
 impl std::fmt::Display for UpgradeRequired {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} requires CostPilot Premium", self.feature)
+        f.write_str(&tf(
+            "edition.requires_premium",
+            &[("feature", self.feature)],
+        ))
     }
 }
 
 impl std::error::Error for UpgradeRequired {}
 
+/// Error when an organization capability policy disabled a Premium feature
+#[derive(Debug)]
+pub struct CapabilityDisabled {
+    pub feature: &'static str,
+    pub rule: String,
+    pub scope: String,
+}
+
+impl std::fmt::Display for CapabilityDisabled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&tf(
+            "edition.capability_disabled",
+            &[
+                ("feature", self.feature),
+                ("scope", &self.scope),
+                ("rule", &self.rule),
+            ],
+        ))
+    }
+}
+
+impl std::error::Error for CapabilityDisabled {}
+
 /// Require Premium edition for a feature
 pub fn require_premium(
     edition: &EditionContext,
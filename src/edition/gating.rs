@@ -8,9 +8,9 @@ pub fn require_premium(feature: &str, edition: &EditionContext) -> Result<(), Co
     if edition.mode == EditionMode::Premium {
         Ok(())
     } else {
-        Err(CostPilotError::upgrade_required(format!(
-            "{} requires CostPilot Premium",
-            feature
+        Err(CostPilotError::upgrade_required(crate::i18n::tf(
+            "edition.requires_premium",
+            &[("feature", feature)],
         )))
     }
 }
@@ -1,35 +1,52 @@
 // User-facing edition upgrade messages
 
+use crate::i18n::{t, tf};
+
+const UPGRADE_URL: &str = "https://shieldcraft-ai.com/costpilot/upgrade";
+
 /// Generate upgrade message for gated features
 pub fn upgrade_message(feature: &str) -> String {
-    format!(
-        "{} requires CostPilot Premium.\nUpgrade: https://shieldcraft-ai.com/costpilot/upgrade",
-        feature
+    tf(
+        "edition.upgrade_message",
+        &[("feature", feature), ("url", UPGRADE_URL)],
     )
 }
 
 /// Generate feature comparison message
 pub fn feature_comparison() -> String {
-    r#"
-CostPilot Free vs Premium:
-
-FREE:
-  ✓ Basic cost prediction (static heuristics)
-  ✓ Explain lite (top 5 patterns)
-  ✓ Mapping (depth 1)
-  ✓ Policy lint-only
-  ✓ SLO validation-only
+    let section = |heading: &'static str, features: &[&'static str]| {
+        let mut lines = format!("{}\n", t(heading));
+        for feature in features {
+            lines.push_str(&format!("  ✓ {}\n", t(feature)));
+        }
+        lines
+    };
 
-PREMIUM:
-  ✓ Advanced prediction (ML-enhanced)
-  ✓ Full explanation chains
-  ✓ Deep dependency mapping
-  ✓ Autofix with drift safety
-  ✓ Trend tracking & history
-  ✓ Policy enforcement (blocking)
-  ✓ SLO enforcement (blocking)
-
-Upgrade: https://shieldcraft-ai.com/costpilot/upgrade
-"#
-    .to_string()
+    format!(
+        "\n{}\n\n{}\n{}\n{}\n",
+        t("edition.comparison_title"),
+        section(
+            "edition.free",
+            &[
+                "edition.feature.basic_prediction",
+                "edition.feature.explain_lite",
+                "edition.feature.mapping_lite",
+                "edition.feature.policy_lint",
+                "edition.feature.slo_validation",
+            ],
+        ),
+        section(
+            "edition.premium",
+            &[
+                "edition.feature.advanced_prediction",
+                "edition.feature.full_explain",
+                "edition.feature.deep_mapping",
+                "edition.feature.autofix",
+                "edition.feature.trends",
+                "edition.feature.policy_enforcement",
+                "edition.feature.slo_enforcement",
+            ],
+        ),
+        tf("edition.upgrade", &[("url", UPGRADE_URL)])
+    )
 }
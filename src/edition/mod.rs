@@ -1,14 +1,21 @@
 pub mod capabilities;
+pub mod capability_policy;
 pub mod errors;
 pub mod messages;
 pub mod pro_handle;
+pub mod trial;
 
 pub use capabilities::Capabilities;
-pub use errors::{require_premium, UpgradeRequired};
+pub use capability_policy::{
+    Capability, CapabilityPolicy, CapabilityRule, CapabilityScope, OrgRestrictions,
+    CAPABILITY_POLICY_FILE,
+};
+pub use errors::{require_premium, CapabilityDisabled, UpgradeRequired};
 // Remove the legacy gating import to avoid confusion
 // pub use gating::require_premium as legacy_require_premium;
 pub use messages::{feature_comparison, upgrade_message};
 pub use pro_handle::{ProEngineError, ProEngineHandle};
+pub use trial::{TrialWatermark, TRIAL_DAYS, TRIAL_KEY_PREFIX};
 
 use crate::pro_engine::License;
 
@@ -30,23 +37,25 @@ pub fn detect_edition() -> Result<EditionContext, String> {
             match crate::pro_engine::License::load_from_file(&license_path) {
                 Ok(license) => {
                     if license.validate().is_ok() {
-                        // Valid license found - enable premium mode
-                        edition.mode = EditionMode::Premium;
+                        // Valid license found - enable premium (or trial) mode
+                        edition.mode = EditionMode::for_license(&license);
                         edition.license = Some(license);
                         edition.capabilities = Capabilities::from_edition(&edition);
                     } else {
-                        // License file exists but is invalid - only warn if user expects it to work
-                        if std::env::var("COSTPILOT_DEBUG").is_ok() {
-                            eprintln!("⚠️  License file found but validation failed");
-                            eprintln!("    Check license format or contact support");
-                        }
+                        // License file exists but is invalid - free mode, debug only
+                        tracing::debug!(
+                            path = %license_path.display(),
+                            "License file found but validation failed; check license format or contact support"
+                        );
                     }
                 }
-                Err(_e) => {
-                    // License file exists but can't be read - only warn in debug mode
-                    if std::env::var("COSTPILOT_DEBUG").is_ok() {
-                        eprintln!("⚠️  License file found but could not be loaded");
-                    }
+                Err(e) => {
+                    // License file exists but can't be read - free mode, debug only
+                    tracing::debug!(
+                        path = %license_path.display(),
+                        "License file found but could not be loaded: {}",
+                        e
+                    );
                 }
             }
         }
@@ -54,25 +63,100 @@ pub fn detect_edition() -> Result<EditionContext, String> {
 
         // Attempt to load ProEngine (only if we have premium mode with valid license)
         if edition.is_premium() {
-            if let Err(e) = crate::pro_engine::load_pro_engine(&mut edition) {
-                // Only warn about ProEngine failure if we have a valid license
-                eprintln!(
-                    "⚠️  Premium license active but engine failed to load: {}",
-                    e
-                );
-                eprintln!("    Running in Premium mode with core features only");
-            }
+            load_pro_engine_guarded(&mut edition);
+            // After the engine, which resets capabilities when it loads
+            load_capability_policy(&mut edition);
         }
     }
 
     Ok(edition)
 }
 
+/// Apply the organization capability policy next to the license, if any
+///
+/// A policy that fails verification is ignored with a warning rather than
+/// blocking every command.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_capability_policy(edition: &mut EditionContext) {
+    let policy_path = edition.paths.capability_policy_path();
+    if !policy_path.exists() {
+        return;
+    }
+    let result = CapabilityPolicy::load(&policy_path)
+        .and_then(|policy| edition.apply_capability_policy(&policy, CapabilityScope::detect()));
+    if let Err(e) = result {
+        tracing::warn!(path = %policy_path.display(), "Capability policy ignored: {}", e);
+    }
+}
+
+/// Load the ProEngine unless its persisted circuit breaker is open, so a
+/// consistently failing engine is skipped quickly on subsequent runs
+#[cfg(not(target_arch = "wasm32"))]
+fn load_pro_engine_guarded(edition: &mut EditionContext) {
+    use crate::engines::performance::{
+        CircuitBreakerRegistry, PerformanceBudgets, BREAKER_STATE_FILE, PRO_ENGINE_BREAKER,
+    };
+
+    let state_path = edition.paths.config_dir.join(BREAKER_STATE_FILE);
+    let mut registry = CircuitBreakerRegistry::load(&state_path).unwrap_or_default();
+    let mut breaker = registry.breaker(
+        PRO_ENGINE_BREAKER,
+        PerformanceBudgets::default().circuit_breaker,
+    );
+
+    if breaker.allow_request().is_err() {
+        tracing::warn!(
+            "Premium engine skipped after repeated failures (circuit breaker open); \
+             running in Premium mode with core features only. \
+             Reset with: costpilot performance breakers --reset"
+        );
+        return;
+    }
+
+    match crate::pro_engine::load_pro_engine(edition) {
+        Ok(()) => breaker.record_success(),
+        Err(e) => {
+            // Only warn about ProEngine failure if we have a valid license
+            tracing::warn!(
+                "Premium license active but engine failed to load: {}; \
+                 running in Premium mode with core features only",
+                e
+            );
+            breaker.record_failure();
+        }
+    }
+
+    let snapshot = breaker.snapshot();
+    if registry.breakers.get(PRO_ENGINE_BREAKER) != Some(&snapshot) {
+        registry.update(PRO_ENGINE_BREAKER, &breaker);
+        if let Err(e) = registry.save(&state_path) {
+            tracing::debug!(
+                path = %state_path.display(),
+                "Failed to save circuit breaker state: {}",
+                e
+            );
+        }
+    }
+}
+
 /// Edition mode for CostPilot
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EditionMode {
     Free,
     Premium,
+    /// Time-boxed evaluation with every Premium capability
+    Trial,
+}
+
+impl EditionMode {
+    /// Mode unlocked by a valid license
+    pub fn for_license(license: &License) -> Self {
+        if trial::is_trial_license(license) {
+            EditionMode::Trial
+        } else {
+            EditionMode::Premium
+        }
+    }
 }
 
 /// Edition context that determines available features
@@ -83,6 +167,8 @@ pub struct EditionContext {
     pub capabilities: Capabilities,
     pub pro: Option<ProEngineHandle>,
     pub paths: EditionPaths,
+    /// Capabilities turned off by the organization capability policy
+    pub org_policy: Option<OrgRestrictions>,
 }
 
 #[derive(Debug, Clone)]
@@ -110,6 +196,10 @@ impl EditionPaths {
     pub fn license_path(&self) -> PathBuf {
         self.config_dir.join("license.json")
     }
+
+    pub fn capability_policy_path(&self) -> PathBuf {
+        self.config_dir.join(CAPABILITY_POLICY_FILE)
+    }
 }
 
 impl Clone for EditionContext {
@@ -121,6 +211,7 @@ impl Clone for EditionContext {
             capabilities: self.capabilities.clone(),
             pro: self.pro.clone(),
             paths: self.paths.clone(),
+            org_policy: self.org_policy.clone(),
         }
     }
 }
@@ -143,6 +234,7 @@ impl EditionContext {
             },
             pro: None,
             paths: EditionPaths::default(),
+            org_policy: None,
         }
     }
 
@@ -151,9 +243,28 @@ impl EditionContext {
         Self::free()
     }
 
-    /// Check if running in Premium mode
+    /// Check if Premium features are available (Premium or Trial mode)
     pub fn is_premium(&self) -> bool {
-        self.mode == EditionMode::Premium
+        matches!(self.mode, EditionMode::Premium | EditionMode::Trial)
+    }
+
+    /// Check if running under a trial license
+    pub fn is_trial(&self) -> bool {
+        self.mode == EditionMode::Trial
+    }
+
+    /// Days left in the trial, or `None` outside Trial mode
+    pub fn trial_days_remaining(&self) -> Option<i64> {
+        self.trial_watermark().map(|w| w.days_remaining)
+    }
+
+    /// Watermark for outputs produced in Trial mode
+    pub fn trial_watermark(&self) -> Option<TrialWatermark> {
+        if !self.is_trial() {
+            return None;
+        }
+        let license = self.license.as_ref()?;
+        TrialWatermark::for_license(license, chrono::Utc::now())
     }
 
     /// Check if running in Free mode
@@ -178,6 +289,7 @@ impl EditionContext {
             },
             pro: None,
             paths: EditionPaths::default(),
+            org_policy: None,
         }
     }
 
@@ -193,6 +305,63 @@ impl EditionContext {
         }
     }
 
+    /// Require a capability, reporting whether the edition or the
+    /// organization policy is missing it
+    pub fn require_capability(
+        &self,
+        capability: Capability,
+        feature: &'static str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.is_free() {
+            return Err(Box::new(UpgradeRequired { feature }));
+        }
+        if let Some(rule) = self.disabled_by_policy(capability) {
+            let scope = self
+                .org_policy
+                .as_ref()
+                .map(|p| p.scope.describe())
+                .unwrap_or_default();
+            return Err(Box::new(CapabilityDisabled {
+                feature,
+                rule: rule.to_string(),
+                scope,
+            }));
+        }
+        if !self.capabilities.allows(capability) {
+            return Err(Box::new(UpgradeRequired { feature }));
+        }
+        Ok(())
+    }
+
+    /// Rule of the organization policy that disabled `capability`, if any
+    pub fn disabled_by_policy(&self, capability: Capability) -> Option<&str> {
+        self.org_policy
+            .as_ref()
+            .and_then(|p| p.disabled.get(&capability))
+            .map(String::as_str)
+    }
+
+    /// Verify `policy` against the active license and disable what it
+    /// turns off for `scope`
+    pub fn apply_capability_policy(
+        &mut self,
+        policy: &CapabilityPolicy,
+        scope: CapabilityScope,
+    ) -> Result<(), String> {
+        let license = self
+            .license
+            .as_ref()
+            .ok_or("Capability policies require a Premium license")?;
+        policy.verify(license)?;
+
+        let disabled = policy.disabled_for(&scope);
+        for capability in disabled.keys() {
+            self.capabilities.disable(*capability);
+        }
+        self.org_policy = Some(OrgRestrictions { scope, disabled });
+        Ok(())
+    }
+
     /// Derive encryption key from license
     pub fn derive_key(&self) -> anyhow::Result<Vec<u8>> {
         if let Some(ref license) = self.license {
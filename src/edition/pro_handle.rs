@@ -1,5 +1,7 @@
+use crate::pro_engine::reload::ReloadableExecutor;
 use crate::pro_engine::{ProEngineExecutor, ProEngineRequest, ProEngineResponse};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Handle to Premium engine with execution capability
 #[derive(Clone)]
@@ -8,6 +10,8 @@ pub struct ProEngineHandle {
     pub decrypted_wasm: Option<Vec<u8>>,
     // Executor wrapped in Arc for thread-safe cloning
     executor: Option<std::sync::Arc<dyn ProEngineExecutor + Send + Sync>>,
+    // Set when the engine can be hot-reloaded
+    reloadable: Option<Arc<ReloadableExecutor>>,
 }
 
 impl ProEngineHandle {
@@ -21,15 +25,44 @@ impl ProEngineHandle {
             path,
             decrypted_wasm,
             executor: Some(std::sync::Arc::from(executor)),
+            reloadable: None,
         }
     }
 
+    /// Create handle whose engine can be swapped by a `ProEngineReloader`
+    pub fn with_reloadable(
+        path: PathBuf,
+        decrypted_wasm: Option<Vec<u8>>,
+        executor: Arc<ReloadableExecutor>,
+    ) -> Self {
+        Self {
+            path,
+            decrypted_wasm,
+            executor: Some(executor.clone()),
+            reloadable: Some(executor),
+        }
+    }
+
+    /// Reloader watching this engine's files, if it supports hot-reload
+    ///
+    /// `decrypted_wasm` keeps the bytes loaded at startup; only the executor
+    /// is swapped.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reloader(&self) -> Option<crate::pro_engine::reload::ProEngineReloader> {
+        let executor = self.reloadable.clone()?;
+        let base = self.path.parent()?.to_path_buf();
+        Some(crate::pro_engine::reload::ProEngineReloader::new(
+            base, executor,
+        ))
+    }
+
     /// Create stub handle without executor (for testing/stub loading)
     pub fn stub(path: PathBuf) -> Self {
         Self {
             path,
             decrypted_wasm: None,
             executor: None,
+            reloadable: None,
         }
     }
 
@@ -47,6 +80,8 @@ impl ProEngineHandle {
             std::str::from_utf8(input).map_err(|e| format!("Invalid UTF-8 input: {}", e))?;
         let req = ProEngineRequest::Predict {
             changes: vec![], // Placeholder - actual parsing done in WASM
+            usage: Default::default(),
+            context: vec![],
         };
         let resp = self.execute(req)?;
         match resp {
@@ -66,6 +101,8 @@ impl ProEngineHandle {
             std::str::from_utf8(input).map_err(|e| format!("Invalid UTF-8 input: {}", e))?;
         let req = ProEngineRequest::Predict {
             changes: vec![], // Placeholder - actual parsing done in WASM
+            usage: Default::default(),
+            context: vec![],
         };
         let resp = self.execute(req)?;
         match resp {
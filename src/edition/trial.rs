@@ -0,0 +1,121 @@
+// Trial licenses - time-boxed, feature-complete evaluation
+//
+// A trial license is an ordinary signed license whose key starts with
+// `TRIAL-`, so the marker is covered by the issuer signature and cannot be
+// stripped. It unlocks every Premium capability until it expires, and
+// machine-readable outputs carry a `trial` watermark so results produced
+// during an evaluation are recognizable.
+
+use crate::pro_engine::License;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// License key prefix marking a trial license
+pub const TRIAL_KEY_PREFIX: &str = "TRIAL-";
+
+/// Length of a trial minted by `license-issuer generate-license --trial`
+pub const TRIAL_DAYS: i64 = 14;
+
+/// Whether `license` is a trial license
+pub fn is_trial_license(license: &License) -> bool {
+    license.license_key.starts_with(TRIAL_KEY_PREFIX)
+}
+
+/// Days left before `license` expires, counting a partial day as a full one
+/// (0 once expired or when the expiry cannot be parsed)
+pub fn days_remaining(license: &License, now: DateTime<Utc>) -> i64 {
+    const DAY_SECS: i64 = 86_400;
+    let Ok(expiry) = DateTime::parse_from_rfc3339(&license.expires) else {
+        return 0;
+    };
+    let seconds = (expiry.with_timezone(&Utc) - now).num_seconds();
+    if seconds <= 0 {
+        0
+    } else {
+        (seconds + DAY_SECS - 1) / DAY_SECS
+    }
+}
+
+/// `trial` field added to outputs produced under a trial license
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrialWatermark {
+    pub expires: String,
+    pub days_remaining: i64,
+}
+
+impl TrialWatermark {
+    pub fn for_license(license: &License, now: DateTime<Utc>) -> Option<Self> {
+        is_trial_license(license).then(|| Self {
+            expires: license.expires.clone(),
+            days_remaining: days_remaining(license, now),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edition::{Capabilities, EditionContext, EditionMode};
+
+    fn license(key: &str, expires: &str) -> License {
+        License {
+            email: "eval@example.com".to_string(),
+            license_key: key.to_string(),
+            expires: expires.to_string(),
+            signature: "00".to_string(),
+            issuer: "test-costpilot".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_days_remaining_rounds_partial_days_up() {
+        let now = DateTime::parse_from_rfc3339("2025-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let trial = license("TRIAL-abc", "2025-03-15T12:00:00Z");
+        assert!(is_trial_license(&trial));
+        assert_eq!(days_remaining(&trial, now), 14);
+        assert_eq!(
+            days_remaining(&license("TRIAL-abc", "2025-03-01T13:00:00Z"), now),
+            1
+        );
+        assert_eq!(
+            days_remaining(&license("TRIAL-abc", "2025-02-28T00:00:00Z"), now),
+            0
+        );
+
+        assert_eq!(
+            TrialWatermark::for_license(&trial, now),
+            Some(TrialWatermark {
+                expires: "2025-03-15T12:00:00Z".to_string(),
+                days_remaining: 14,
+            })
+        );
+        assert_eq!(
+            TrialWatermark::for_license(&license("PRO-abc", "2025-03-15T12:00:00Z"), now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_trial_edition_is_feature_complete() {
+        let expires = (Utc::now() + chrono::Duration::days(TRIAL_DAYS)).to_rfc3339();
+        let mut edition = EditionContext::free();
+        edition.license = Some(license("TRIAL-abc", &expires));
+        edition.mode = EditionMode::for_license(edition.license.as_ref().unwrap());
+        edition.capabilities = Capabilities::from_edition(&edition);
+
+        assert_eq!(edition.mode, EditionMode::Trial);
+        assert!(edition.is_trial());
+        assert!(edition.is_premium());
+        assert!(edition.capabilities.allow_autofix);
+        assert!(edition.capabilities.allow_policy_enforce);
+        assert_eq!(edition.trial_days_remaining(), Some(TRIAL_DAYS));
+        assert!(edition.trial_watermark().is_some());
+
+        assert_eq!(
+            EditionContext::premium_for_test().trial_days_remaining(),
+            None
+        );
+    }
+}
@@ -1 +1,541 @@
-// Tagging logic
+// Tagging compliance - required tags, allowed values and formats
+//
+// The `tagging` section of costpilot.yaml lists rules per resource type: tag
+// keys every matching resource must carry, the values some tags may take and
+// regular expressions others must match. Scans report breaches as policy
+// violations, and each non-compliant resource gets a snippet that sets the
+// missing or corrected tags.
+
+use crate::engines::autofix::{BeforeAfter, FixSnippet, SnippetFormat};
+use crate::engines::detection::custom_rules::wildcard_match;
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use crate::engines::shared::models::{ChangeAction, ResourceChange};
+use crate::zero_cost_guard::GuardEnforcement;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Severity of violations from rules that do not set one
+const DEFAULT_SEVERITY: &str = "MEDIUM";
+
+/// Value suggested for tags without allowed values
+const PLACEHOLDER: &str = "TODO";
+
+/// Tag rules per resource type (`tagging` in costpilot.yaml)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaggingConfig {
+    #[serde(default)]
+    pub rules: Vec<TagRule>,
+
+    /// `block` fails the scan; `warn` only reports
+    #[serde(default)]
+    pub enforcement: GuardEnforcement,
+}
+
+/// Tags required of one resource type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TagRule {
+    /// Resource type the rule applies to; `*` matches any characters
+    pub resource_type: String,
+
+    /// Tag keys every matching resource must carry
+    #[serde(default)]
+    pub required: Vec<String>,
+
+    /// Values a tag may take, when present
+    #[serde(default)]
+    pub allowed_values: BTreeMap<String, Vec<String>>,
+
+    /// Regular expression a tag value must match, when present
+    #[serde(default)]
+    pub formats: BTreeMap<String, String>,
+
+    /// Severity of this rule's violations (default medium)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+}
+
+impl TaggingConfig {
+    /// Load `tagging` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `tagging` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `tagging` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let config: Self = match root.get("tagging").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid tagging section: {}", e))
+            })?,
+            None => Self::default(),
+        };
+
+        if let Some(problem) = config.validate().first() {
+            return Err(CostPilotError::validation_error(problem.clone())
+                .with_hint("Check tagging.rules in costpilot.yaml"));
+        }
+
+        Ok(config)
+    }
+
+    /// Describe invalid rules; empty if every rule is usable
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (idx, rule) in self.rules.iter().enumerate() {
+            let name = format!("rule {} ({})", idx + 1, rule.resource_type);
+            if rule.resource_type.is_empty() {
+                problems.push(format!("rule {}: resource_type is required", idx + 1));
+            }
+            if rule.required.is_empty() && rule.allowed_values.is_empty() && rule.formats.is_empty()
+            {
+                problems.push(format!(
+                    "{}: needs required tags, allowed_values or formats",
+                    name
+                ));
+            }
+            for (tag, values) in &rule.allowed_values {
+                if values.is_empty() {
+                    problems.push(format!("{}: no allowed values for tag '{}'", name, tag));
+                }
+            }
+            for (tag, format) in &rule.formats {
+                if let Err(e) = Regex::new(format) {
+                    problems.push(format!("{}: invalid format for tag '{}': {}", name, tag, e));
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// How a resource's tag breaks a rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum TagProblem {
+    Missing,
+    ValueNotAllowed { value: String, allowed: Vec<String> },
+    InvalidFormat { value: String, format: String },
+}
+
+impl TagProblem {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TagProblem::Missing => "missing",
+            TagProblem::ValueNotAllowed { .. } => "value_not_allowed",
+            TagProblem::InvalidFormat { .. } => "invalid_format",
+        }
+    }
+
+    /// What the rule expects, for violation reports
+    pub fn expected(&self) -> String {
+        match self {
+            TagProblem::Missing => "present".to_string(),
+            TagProblem::ValueNotAllowed { allowed, .. } => allowed.join(", "),
+            TagProblem::InvalidFormat { format, .. } => format.clone(),
+        }
+    }
+
+    /// The offending value, or `missing`
+    pub fn actual(&self) -> &str {
+        match self {
+            TagProblem::Missing => "missing",
+            TagProblem::ValueNotAllowed { value, .. } | TagProblem::InvalidFormat { value, .. } => {
+                value
+            }
+        }
+    }
+}
+
+/// A planned resource whose tags break a rule
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TagViolation {
+    pub resource_id: String,
+    pub resource_type: String,
+    pub tag: String,
+    pub problem: TagProblem,
+    pub message: String,
+    pub severity: String,
+    /// `false` when enforcement is `warn`
+    pub blocking: bool,
+}
+
+/// Checks planned resources against the tagging rules
+#[derive(Debug, Clone, Default)]
+pub struct TagPolicy {
+    config: TaggingConfig,
+    formats: Vec<BTreeMap<String, Regex>>,
+}
+
+impl TagPolicy {
+    /// Compile the rules; fails on an invalid format expression
+    pub fn new(config: TaggingConfig) -> Result<Self> {
+        let formats = config
+            .rules
+            .iter()
+            .map(|rule| {
+                rule.formats
+                    .iter()
+                    .map(|(tag, format)| {
+                        // Anchor so the whole value has to match
+                        Regex::new(&format!("^(?:{})$", format))
+                            .map(|regex| (tag.clone(), regex))
+                            .map_err(|e| {
+                                CostPilotError::validation_error(format!(
+                                    "Invalid format for tag '{}': {}",
+                                    tag, e
+                                ))
+                            })
+                    })
+                    .collect::<Result<BTreeMap<_, _>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { config, formats })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.config.rules.is_empty()
+    }
+
+    /// Check created, updated and replaced resources
+    ///
+    /// Resources whose planned configuration has no `tags` attribute cannot
+    /// carry tags and are skipped. A tag broken by several rules is reported
+    /// once, by the first.
+    pub fn check(&self, changes: &[ResourceChange]) -> Vec<TagViolation> {
+        let blocking = self.config.enforcement == GuardEnforcement::Block;
+        let mut violations: Vec<TagViolation> = Vec::new();
+
+        for change in changes.iter().filter(|c| taggable(c)) {
+            for (rule, formats) in self.config.rules.iter().zip(&self.formats) {
+                if !wildcard_match(&rule.resource_type, &change.resource_type) {
+                    continue;
+                }
+                let severity = rule
+                    .severity
+                    .as_deref()
+                    .unwrap_or(DEFAULT_SEVERITY)
+                    .to_uppercase();
+                let mut report = |tag: &str, problem: TagProblem, message: String| {
+                    let seen = violations
+                        .iter()
+                        .any(|v| v.resource_id == change.resource_id && v.tag == tag);
+                    if !seen {
+                        violations.push(TagViolation {
+                            resource_id: change.resource_id.clone(),
+                            resource_type: change.resource_type.clone(),
+                            tag: tag.to_string(),
+                            problem,
+                            message,
+                            severity: severity.clone(),
+                            blocking,
+                        });
+                    }
+                };
+
+                for tag in &rule.required {
+                    if !matches!(change.tags.get(tag), Some(v) if !v.trim().is_empty()) {
+                        report(
+                            tag,
+                            TagProblem::Missing,
+                            format!("{} is missing required tag '{}'", change.resource_id, tag),
+                        );
+                    }
+                }
+                for (tag, allowed) in &rule.allowed_values {
+                    if let Some(value) = change.tags.get(tag) {
+                        if !allowed.contains(value) {
+                            report(
+                                tag,
+                                TagProblem::ValueNotAllowed {
+                                    value: value.clone(),
+                                    allowed: allowed.clone(),
+                                },
+                                format!(
+                                    "{} has {}='{}'; allowed: {}",
+                                    change.resource_id,
+                                    tag,
+                                    value,
+                                    allowed.join(", ")
+                                ),
+                            );
+                        }
+                    }
+                }
+                for (tag, regex) in formats {
+                    if let Some(value) = change.tags.get(tag) {
+                        if !regex.is_match(value) {
+                            let format = rule.formats[tag].clone();
+                            report(
+                                tag,
+                                TagProblem::InvalidFormat {
+                                    value: value.clone(),
+                                    format: format.clone(),
+                                },
+                                format!(
+                                    "{} has {}='{}', which does not match {}",
+                                    change.resource_id, tag, value, format
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    /// One Terraform snippet per resource in `violations`, setting its full
+    /// corrected tag map
+    pub fn fix_snippets(
+        &self,
+        changes: &[ResourceChange],
+        violations: &[TagViolation],
+    ) -> Vec<FixSnippet> {
+        let mut by_resource: BTreeMap<&str, Vec<&TagViolation>> = BTreeMap::new();
+        for violation in violations {
+            by_resource
+                .entry(violation.resource_id.as_str())
+                .or_default()
+                .push(violation);
+        }
+
+        by_resource
+            .into_iter()
+            .filter_map(|(resource_id, violations)| {
+                let change = changes.iter().find(|c| c.resource_id == resource_id)?;
+                Some(fix_snippet(change, &violations))
+            })
+            .collect()
+    }
+}
+
+/// Whether the planned resource has a `tags` attribute at all
+fn taggable(change: &ResourceChange) -> bool {
+    if matches!(change.action, ChangeAction::Delete | ChangeAction::NoOp) {
+        return false;
+    }
+    let config = change.new_config.as_ref().or(change.config.as_ref());
+    !change.tags.is_empty()
+        || config.is_some_and(|c| c.get("tags").is_some() || c.get("tags_all").is_some())
+}
+
+fn fix_snippet(change: &ResourceChange, violations: &[&TagViolation]) -> FixSnippet {
+    let mut tags: BTreeMap<String, String> = change
+        .tags
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let mut notes: BTreeMap<String, String> = BTreeMap::new();
+    for violation in violations {
+        let (value, note) = match &violation.problem {
+            TagProblem::ValueNotAllowed { allowed, .. } => (
+                allowed[0].clone(),
+                format!("one of: {}", allowed.join(", ")),
+            ),
+            TagProblem::InvalidFormat { format, .. } => {
+                (PLACEHOLDER.to_string(), format!("must match {}", format))
+            }
+            TagProblem::Missing => (PLACEHOLDER.to_string(), "required".to_string()),
+        };
+        tags.insert(violation.tag.clone(), value);
+        notes.insert(violation.tag.clone(), note);
+    }
+
+    let width = tags.keys().map(|k| hcl_key(k).len()).max().unwrap_or(0);
+    let lines: Vec<String> = tags
+        .iter()
+        .map(|(key, value)| {
+            let line = format!("{:<width$} = {:?}", hcl_key(key), value, width = width);
+            match notes.get(key) {
+                Some(note) => format!("{}  # {}", line, note),
+                None => line,
+            }
+        })
+        .collect();
+    let body = lines
+        .iter()
+        .map(|l| format!("    {}", l))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let name = change
+        .resource_id
+        .split('.')
+        .next_back()
+        .unwrap_or("example");
+    let fixed: Vec<&str> = violations.iter().map(|v| v.tag.as_str()).collect();
+
+    FixSnippet {
+        resource_id: change.resource_id.clone(),
+        resource_type: change.resource_type.clone(),
+        snippet: format!(
+            "resource \"{}\" \"{}\" {{\n  tags = {{\n{}\n  }}\n  # ... other attributes ...\n}}",
+            change.resource_type, name, body
+        ),
+        format: SnippetFormat::Terraform,
+        rationale: format!(
+            "Set {} so the resource meets the tagging rules in costpilot.yaml. Replace TODO \
+            placeholders with real values; tags drive cost allocation and ownership reports.",
+            fixed.join(", ")
+        ),
+        before_after: BeforeAfter {
+            before: tag_list(change.tags.iter().map(|(k, v)| (k.as_str(), v.as_str()))),
+            after: tag_list(tags.iter().map(|(k, v)| (k.as_str(), v.as_str()))),
+            change_description: format!("Set tags: {}", fixed.join(", ")),
+        },
+        impact: "No cost change; enables cost attribution".to_string(),
+        deterministic: true,
+        idempotent: true,
+    }
+}
+
+/// Tag keys that are not plain identifiers need quoting in HCL
+fn hcl_key(key: &str) -> String {
+    let plain = key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !key.starts_with(|c: char| c.is_ascii_digit());
+    if plain {
+        key.to_string()
+    } else {
+        format!("{:?}", key)
+    }
+}
+
+fn tag_list<'a>(tags: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    let mut tags: Vec<String> = tags.map(|(k, v)| format!("{} = {:?}", k, v)).collect();
+    if tags.is_empty() {
+        return "tags = {}".to_string();
+    }
+    tags.sort();
+    format!("tags = {{ {} }}", tags.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    const CONFIG: &str = r#"
+tagging:
+  rules:
+    - resource_type: "aws_*"
+      required: [Owner, CostCenter]
+      formats:
+        CostCenter: "CC-[0-9]{4}"
+    - resource_type: aws_instance
+      required: [Owner]
+      allowed_values:
+        Environment: [dev, staging, prod]
+      severity: high
+"#;
+
+    fn change(resource_type: &str, tags: &[(&str, &str)]) -> ResourceChange {
+        let tags: HashMap<String, String> = tags
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        ResourceChange::builder()
+            .resource_id(format!("{}.main", resource_type))
+            .resource_type(resource_type.to_string())
+            .action(ChangeAction::Create)
+            .new_config(json!({ "tags": tags }))
+            .tags(tags)
+            .build()
+    }
+
+    #[test]
+    fn test_check_reports_missing_disallowed_and_malformed_tags() {
+        let policy = TagPolicy::new(TaggingConfig::from_yaml(CONFIG).unwrap()).unwrap();
+        let changes = vec![
+            change(
+                "aws_instance",
+                &[("CostCenter", "cc-12"), ("Environment", "qa")],
+            ),
+            change(
+                "aws_s3_bucket",
+                &[("Owner", "data"), ("CostCenter", "CC-1234")],
+            ),
+            // Untaggable: no tags attribute in the plan
+            ResourceChange::builder()
+                .resource_id("aws_route.r".to_string())
+                .resource_type("aws_route".to_string())
+                .action(ChangeAction::Create)
+                .new_config(json!({ "route_table_id": "rtb-1" }))
+                .build(),
+        ];
+
+        let violations = policy.check(&changes);
+        let found: Vec<(&str, &str)> = violations
+            .iter()
+            .map(|v| (v.tag.as_str(), v.problem.name()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Owner", "missing"),
+                ("CostCenter", "invalid_format"),
+                ("Environment", "value_not_allowed"),
+            ]
+        );
+        assert_eq!(violations[0].severity, "MEDIUM");
+        assert_eq!(violations[2].severity, "HIGH");
+        assert!(violations.iter().all(|v| v.blocking));
+    }
+
+    #[test]
+    fn test_fix_snippet_sets_corrected_tags() {
+        let policy = TagPolicy::new(TaggingConfig::from_yaml(CONFIG).unwrap()).unwrap();
+        let changes = vec![change(
+            "aws_instance",
+            &[("Environment", "qa"), ("CostCenter", "CC-0042")],
+        )];
+
+        let snippets = policy.fix_snippets(&changes, &policy.check(&changes));
+        assert_eq!(snippets.len(), 1);
+        let snippet = &snippets[0].snippet;
+        assert!(snippet.contains("resource \"aws_instance\" \"main\""));
+        assert!(snippet.contains("CostCenter  = \"CC-0042\"\n"));
+        assert!(snippet.contains("Environment = \"dev\"  # one of: dev, staging, prod"));
+        assert!(snippet.contains("Owner       = \"TODO\"  # required"));
+        assert_eq!(
+            snippets[0].before_after.change_description,
+            "Set tags: Owner, Environment"
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        let config = r#"
+tagging:
+  enforcement: warn
+  rules:
+    - resource_type: aws_instance
+      formats:
+        Owner: "("
+    - resource_type: aws_s3_bucket
+"#;
+        let err = TaggingConfig::from_yaml(config).unwrap_err();
+        assert!(err.message.contains("invalid format for tag 'Owner'"));
+
+        let parsed: TaggingConfig =
+            serde_yaml::from_str("enforcement: warn\nrules: [{resource_type: aws_s3_bucket}]")
+                .unwrap();
+        assert_eq!(parsed.enforcement, GuardEnforcement::Warn);
+        assert_eq!(parsed.validate().len(), 1);
+    }
+}
@@ -1,12 +1,17 @@
 // Autofix engine - orchestrates fix generation
 
 use crate::edition::EditionContext;
+use crate::engines::autofix::cfn_generator::CfnFixGenerator;
+use crate::engines::autofix::iac_target::AutofixTarget;
 use crate::engines::autofix::patch_generator::{PatchFile, PatchGenerator};
 use crate::engines::autofix::snippet_generator::{FixSnippet, SnippetGenerator};
 use crate::engines::explain::anti_patterns::detect_anti_patterns;
+use crate::engines::explain::PredictionExplainer;
+use crate::engines::prediction::minimal_heuristics::MinimalHeuristics;
 use crate::engines::shared::error_model::CostPilotError;
 use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Autofix mode
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -72,6 +77,102 @@ impl AutofixEngine {
         }
     }
 
+    /// Generate fixes in the IaC dialect of the source artifact.
+    ///
+    /// `construct_paths` maps CloudFormation logical IDs to CDK construct paths
+    /// and is only used for CDK targets.
+    pub fn generate_fixes_for_target(
+        detections: &[Detection],
+        changes: &[ResourceChange],
+        estimates: &[CostEstimate],
+        mode: AutofixMode,
+        target: AutofixTarget,
+        construct_paths: &HashMap<String, String>,
+        edition: &EditionContext,
+    ) -> Result<AutofixResult, CostPilotError> {
+        if target == AutofixTarget::Terraform {
+            return Self::generate_fixes(detections, changes, estimates, mode, edition);
+        }
+
+        let generator = CfnFixGenerator::new(target).with_construct_paths(construct_paths.clone());
+        match mode {
+            AutofixMode::Snippet => Ok(Self::generate_target_fixes(
+                &generator, detections, changes, estimates, mode,
+            )),
+            AutofixMode::Patch => {
+                if !edition.is_premium() {
+                    return Err(CostPilotError::upgrade_required(
+                        "Patch mode requires CostPilot Premium",
+                    ));
+                }
+                Ok(Self::generate_target_fixes(
+                    &generator, detections, changes, estimates, mode,
+                ))
+            }
+            AutofixMode::DriftSafe => Err(CostPilotError::validation_error(format!(
+                "Drift-safe mode is not supported for {} artifacts",
+                target.name()
+            ))),
+        }
+    }
+
+    /// Generate snippets or patches for CloudFormation/CDK targets
+    fn generate_target_fixes(
+        generator: &CfnFixGenerator,
+        detections: &[Detection],
+        changes: &[ResourceChange],
+        estimates: &[CostEstimate],
+        mode: AutofixMode,
+    ) -> AutofixResult {
+        let mut fixes = Vec::new();
+        let mut patches = Vec::new();
+        let mut warnings = Vec::new();
+
+        for detection in detections {
+            let Some(change) = changes
+                .iter()
+                .find(|c| c.resource_id == detection.resource_id)
+            else {
+                warnings.push(format!(
+                    "Resource change not found for detection: {}",
+                    detection.resource_id
+                ));
+                continue;
+            };
+
+            let estimate = estimates
+                .iter()
+                .find(|e| e.resource_id == detection.resource_id);
+
+            if let AutofixMode::Patch = mode {
+                match generator.generate_patch(detection, change, estimate) {
+                    Ok(patch) => patches.push(patch),
+                    Err(e) => warnings.push(e),
+                }
+            } else {
+                let anti_patterns = detect_anti_patterns(change, estimate);
+                match generator.generate_snippet(detection, change, &anti_patterns, estimate) {
+                    Some(snippet) => fixes.push(snippet),
+                    None => warnings.push(format!(
+                        "No automated fix available for {} ({})",
+                        detection.resource_id, change.resource_type
+                    )),
+                }
+            }
+        }
+
+        AutofixResult {
+            mode: match mode {
+                AutofixMode::Patch => "patch".to_string(),
+                _ => "snippet".to_string(),
+            },
+            fixes_generated: fixes.len() + patches.len(),
+            fixes,
+            patches,
+            warnings,
+        }
+    }
+
     /// Generate snippet fixes (MVP)
     fn generate_snippets(
         detections: &[Detection],
@@ -80,6 +181,8 @@ impl AutofixEngine {
     ) -> AutofixResult {
         let mut fixes = Vec::new();
         let mut warnings = Vec::new();
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
 
         for detection in detections {
             // Find corresponding resource change
@@ -100,6 +203,9 @@ impl AutofixEngine {
                     SnippetGenerator::generate(detection, change, &anti_patterns, estimate)
                 {
                     fixes.push(snippet);
+                } else if let Some(counterfactual) = explainer.counterfactuals(change).first() {
+                    // Fall back to the cheapest single configuration change
+                    fixes.push(SnippetGenerator::from_counterfactual(counterfactual));
                 } else {
                     warnings.push(format!(
                         "No automated fix available for {} ({})",
@@ -289,6 +395,37 @@ mod tests {
         assert!(result.fixes[0].idempotent);
     }
 
+    #[test]
+    fn test_snippet_mode_falls_back_to_counterfactual() {
+        let detection = Detection::builder()
+            .rule_id("cost_spike")
+            .severity(Severity::Medium)
+            .resource_id("aws_ebs_volume.data")
+            .message("Large gp2 volume")
+            .build();
+
+        let change = ResourceChange::builder()
+            .resource_id("aws_ebs_volume.data")
+            .resource_type("aws_ebs_volume")
+            .action(ChangeAction::Create)
+            .new_config(serde_json::json!({"type": "gp2", "size": 500}))
+            .build();
+
+        let result = AutofixEngine::generate_fixes(
+            &[detection],
+            &[change],
+            &[],
+            AutofixMode::Snippet,
+            &crate::edition::EditionContext::free(),
+        )
+        .unwrap();
+
+        assert_eq!(result.fixes_generated, 1);
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.fixes[0].before_after.after, "type = \"gp3\"");
+        assert_eq!(result.fixes[0].impact, "Estimated savings: $10.00/month");
+    }
+
     #[test]
     fn test_patch_mode() {
         let detection = Detection {
@@ -0,0 +1,766 @@
+// CloudFormation / CDK fix generator - emits the Terraform fix classes for CFN-based artifacts
+//
+// CDK fixes are expressed as property overrides on the construct's underlying
+// Cfn resource (`node.defaultChild`), which works for every L2 construct and
+// maps one-to-one onto the CloudFormation property being changed.
+
+use crate::engines::autofix::iac_target::{AutofixTarget, CdkLanguage};
+use crate::engines::autofix::patch_generator::{
+    PatchFile, PatchGenerator, PatchHunk, PatchLine, PatchLineType, PatchMetadata,
+};
+use crate::engines::autofix::snippet_generator::{BeforeAfter, FixSnippet, SnippetFormat};
+use crate::engines::explain::anti_patterns::AntiPattern;
+use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Change to a single top-level CloudFormation property
+#[derive(Debug, Clone)]
+struct PropertyChange {
+    property: &'static str,
+    before: Option<Value>,
+    after: Value,
+}
+
+/// What a fix does to the template
+#[derive(Debug, Clone)]
+enum FixAction {
+    /// Override properties on the affected resource
+    Properties(Vec<PropertyChange>),
+    /// Add S3/DynamoDB gateway endpoints so traffic bypasses the NAT gateway
+    GatewayEndpoints,
+}
+
+/// Target-neutral description of a fix
+#[derive(Debug, Clone)]
+struct CfnFix {
+    action: FixAction,
+    rationale: String,
+    change_description: String,
+    impact: String,
+    savings_ratio: f64,
+}
+
+/// Generates CloudFormation YAML and CDK fixes
+pub struct CfnFixGenerator {
+    target: AutofixTarget,
+    construct_paths: HashMap<String, String>,
+}
+
+impl CfnFixGenerator {
+    /// Create generator for a CloudFormation or CDK target
+    pub fn new(target: AutofixTarget) -> Self {
+        Self {
+            target,
+            construct_paths: HashMap::new(),
+        }
+    }
+
+    /// Provide logical ID -> `aws:cdk:path` mappings used to name constructs
+    pub fn with_construct_paths(mut self, construct_paths: HashMap<String, String>) -> Self {
+        self.construct_paths = construct_paths;
+        self
+    }
+
+    /// Generate fix snippet for a detection
+    pub fn generate_snippet(
+        &self,
+        _detection: &Detection,
+        change: &ResourceChange,
+        anti_patterns: &[AntiPattern],
+        estimate: Option<&CostEstimate>,
+    ) -> Option<FixSnippet> {
+        let fix = Self::plan_fix(change, anti_patterns)?;
+        let logical_id = Self::logical_id(&change.resource_id);
+
+        let (snippet, format) = match self.target {
+            AutofixTarget::Cdk(CdkLanguage::TypeScript) => (
+                self.render_cdk(&logical_id, &fix, CdkLanguage::TypeScript)
+                    .join("\n"),
+                SnippetFormat::CDK,
+            ),
+            AutofixTarget::Cdk(CdkLanguage::Python) => (
+                self.render_cdk(&logical_id, &fix, CdkLanguage::Python)
+                    .join("\n"),
+                SnippetFormat::CDKPython,
+            ),
+            AutofixTarget::CloudFormation | AutofixTarget::Terraform => (
+                Self::render_cloudformation(&logical_id, &change.resource_type, &fix),
+                SnippetFormat::CloudFormation,
+            ),
+        };
+
+        let (before, after) = Self::before_after(&fix);
+        let estimated_savings = estimate
+            .map(|e| e.monthly_cost * fix.savings_ratio)
+            .unwrap_or(0.0);
+
+        Some(FixSnippet {
+            resource_id: change.resource_id.clone(),
+            resource_type: change.resource_type.clone(),
+            snippet,
+            format,
+            rationale: fix.rationale,
+            before_after: BeforeAfter {
+                before,
+                after,
+                change_description: fix.change_description,
+            },
+            impact: if estimated_savings > 0.0 {
+                format!("Estimated savings: ${:.2}/month", estimated_savings)
+            } else {
+                fix.impact
+            },
+            deterministic: true,
+            idempotent: true,
+        })
+    }
+
+    /// Generate patch for a detection
+    pub fn generate_patch(
+        &self,
+        detection: &Detection,
+        change: &ResourceChange,
+        estimate: Option<&CostEstimate>,
+    ) -> Result<PatchFile, String> {
+        let anti_patterns =
+            crate::engines::explain::anti_patterns::detect_anti_patterns(change, estimate);
+        let fix = Self::plan_fix(change, &anti_patterns).ok_or_else(|| {
+            format!(
+                "Patch generation not supported for {} ({})",
+                change.resource_id, change.resource_type
+            )
+        })?;
+        let logical_id = Self::logical_id(&change.resource_id);
+
+        let (filename, hunk) = match self.target {
+            AutofixTarget::Cdk(language) => {
+                let lines = self
+                    .render_cdk(&logical_id, &fix, language)
+                    .into_iter()
+                    .map(|content| PatchLine {
+                        line_type: PatchLineType::Addition,
+                        content,
+                        indent_level: 0,
+                    })
+                    .collect::<Vec<_>>();
+                (
+                    self.cdk_filename(&logical_id, language),
+                    Self::hunk(lines, vec![]),
+                )
+            }
+            AutofixTarget::CloudFormation | AutofixTarget::Terraform => (
+                "template.yaml".to_string(),
+                Self::cloudformation_hunk(&logical_id, &change.resource_type, &fix),
+            ),
+        };
+
+        let cost_before = estimate.map(|e| e.monthly_cost).unwrap_or(0.0);
+        let monthly_savings = cost_before * fix.savings_ratio;
+
+        Ok(PatchFile {
+            resource_id: detection.resource_id.clone(),
+            resource_type: change.resource_type.clone(),
+            filename,
+            hunks: vec![hunk],
+            metadata: PatchMetadata {
+                cost_before,
+                cost_after: cost_before - monthly_savings,
+                monthly_savings,
+                confidence: estimate.map(|e| e.confidence_score).unwrap_or(0.5),
+                anti_patterns: anti_patterns
+                    .iter()
+                    .map(|ap| ap.pattern_name.clone())
+                    .collect(),
+                rationale: fix.rationale,
+                simulation_required: true,
+                beta: true,
+            },
+        })
+    }
+
+    /// Decide the fix for a resource, mirroring the Terraform detection classes
+    fn plan_fix(change: &ResourceChange, anti_patterns: &[AntiPattern]) -> Option<CfnFix> {
+        let has_pattern = |id: &str| anti_patterns.iter().any(|p| p.pattern_id == id);
+        let config_str = |keys: &[&str]| {
+            change.new_config.as_ref().and_then(|c| {
+                keys.iter()
+                    .find_map(|k| c.get(*k).and_then(|v| v.as_str()))
+                    .map(|s| s.to_string())
+            })
+        };
+
+        match change.resource_type.as_str() {
+            "aws_instance" if has_pattern("OVERPROVISIONED_EC2") => {
+                let current = config_str(&["instance_type"])?;
+                let suggested = PatchGenerator::recommend_instance_downsize(&current);
+                Some(CfnFix {
+                    action: FixAction::Properties(vec![PropertyChange {
+                        property: "InstanceType",
+                        before: Some(json!(current)),
+                        after: json!(suggested),
+                    }]),
+                    rationale: format!(
+                        "Right-size EC2 instance from {} to {}. Validate against observed utilization before rollout.",
+                        current, suggested
+                    ),
+                    change_description: format!("Downsize from {} to {}", current, suggested),
+                    impact: "Reduced compute cost from right-sizing".to_string(),
+                    savings_ratio: 0.4,
+                })
+            }
+            "aws_db_instance" | "aws_rds_instance" => {
+                let current = config_str(&["db_instance_class", "instance_class"])?;
+                let suggested = PatchGenerator::recommend_rds_downsize(&current);
+                Some(CfnFix {
+                    action: FixAction::Properties(vec![PropertyChange {
+                        property: "DBInstanceClass",
+                        before: Some(json!(current)),
+                        after: json!(suggested),
+                    }]),
+                    rationale: format!(
+                        "Review whether {} is required; {} covers most variable workloads at lower cost.",
+                        current, suggested
+                    ),
+                    change_description: format!("Downsize from {} to {}", current, suggested),
+                    impact: "Reduced database instance cost".to_string(),
+                    savings_ratio: 0.3,
+                })
+            }
+            "aws_lambda_function" if has_pattern("UNBOUNDED_LAMBDA_CONCURRENCY") => Some(CfnFix {
+                action: FixAction::Properties(vec![PropertyChange {
+                    property: "ReservedConcurrentExecutions",
+                    before: None,
+                    after: json!(10),
+                }]),
+                rationale: "Set a concurrency limit to prevent runaway costs from traffic spikes or bugs. \
+                    Start conservative and raise it based on the ConcurrentExecutions metric."
+                    .to_string(),
+                change_description: "Add concurrency limit".to_string(),
+                impact: "Prevents unexpected cost spikes from runaway execution".to_string(),
+                savings_ratio: 0.0,
+            }),
+            "aws_s3_bucket" if has_pattern("S3_MISSING_LIFECYCLE") => Some(CfnFix {
+                action: FixAction::Properties(vec![PropertyChange {
+                    property: "LifecycleConfiguration",
+                    before: None,
+                    after: json!({
+                        "Rules": [
+                            {
+                                "Id": "intelligent-tiering",
+                                "Status": "Enabled",
+                                "Transitions": [
+                                    {"StorageClass": "INTELLIGENT_TIERING", "TransitionInDays": 0}
+                                ]
+                            },
+                            {
+                                "Id": "archive-old-data",
+                                "Status": "Enabled",
+                                "Transitions": [
+                                    {"StorageClass": "GLACIER", "TransitionInDays": 90},
+                                    {"StorageClass": "DEEP_ARCHIVE", "TransitionInDays": 365}
+                                ]
+                            }
+                        ]
+                    }),
+                }]),
+                rationale: "Transition objects to cheaper storage tiers automatically: Intelligent-Tiering immediately, \
+                    Glacier after 90 days and Deep Archive after one year."
+                    .to_string(),
+                change_description: "Add automated lifecycle management".to_string(),
+                impact: "Up to 95% storage cost reduction for infrequently accessed data"
+                    .to_string(),
+                savings_ratio: 0.0,
+            }),
+            "aws_dynamodb_table" if has_pattern("DYNAMODB_PAY_PER_REQUEST_DEFAULT") => {
+                Some(CfnFix {
+                    action: FixAction::Properties(vec![
+                        PropertyChange {
+                            property: "BillingMode",
+                            before: Some(json!("PAY_PER_REQUEST")),
+                            after: json!("PROVISIONED"),
+                        },
+                        PropertyChange {
+                            property: "ProvisionedThroughput",
+                            before: None,
+                            after: json!({"ReadCapacityUnits": 5, "WriteCapacityUnits": 5}),
+                        },
+                    ]),
+                    rationale: "Switch to provisioned capacity for predictable workloads and attach \
+                        Application Auto Scaling to follow actual demand."
+                        .to_string(),
+                    change_description: "Switch to provisioned capacity".to_string(),
+                    impact: "Up to 80% cost reduction for tables with >1M requests/month"
+                        .to_string(),
+                    savings_ratio: 0.0,
+                })
+            }
+            "aws_nat_gateway" => Some(CfnFix {
+                action: FixAction::GatewayEndpoints,
+                rationale: "Route S3 and DynamoDB traffic through free gateway VPC endpoints instead of the \
+                    NAT gateway to avoid per-GB processing charges."
+                    .to_string(),
+                change_description: "Add S3/DynamoDB gateway endpoints".to_string(),
+                impact: "Removes NAT data processing charges for AWS service traffic".to_string(),
+                savings_ratio: 0.7,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Render a CloudFormation YAML fragment containing only the changed properties
+    fn render_cloudformation(logical_id: &str, resource_type: &str, fix: &CfnFix) -> String {
+        let mut resources = serde_json::Map::new();
+        match &fix.action {
+            FixAction::Properties(changes) => {
+                let properties: serde_json::Map<String, Value> = changes
+                    .iter()
+                    .map(|c| (c.property.to_string(), c.after.clone()))
+                    .collect();
+                resources.insert(
+                    logical_id.to_string(),
+                    json!({
+                        "Type": Self::cfn_type(resource_type),
+                        "Properties": properties,
+                    }),
+                );
+            }
+            FixAction::GatewayEndpoints => {
+                for (id, resource) in Self::gateway_endpoints() {
+                    resources.insert(id, resource);
+                }
+            }
+        }
+
+        let yaml = serde_yaml::to_string(&json!({ "Resources": resources })).unwrap_or_default();
+        format!("# CostPilot fix for {}\n{}", logical_id, yaml.trim_end())
+    }
+
+    /// Build a unified-diff hunk against the resource block in the template
+    fn cloudformation_hunk(logical_id: &str, resource_type: &str, fix: &CfnFix) -> PatchHunk {
+        let context = |content: String, indent_level: usize| PatchLine {
+            line_type: PatchLineType::Context,
+            content,
+            indent_level,
+        };
+
+        let mut lines = Vec::new();
+        match &fix.action {
+            FixAction::Properties(changes) => {
+                lines.push(context(format!("  {}:", logical_id), 1));
+                lines.push(context(
+                    format!("    Type: {}", Self::cfn_type(resource_type)),
+                    2,
+                ));
+                lines.push(context("    Properties:".to_string(), 2));
+                for change in changes {
+                    if let Some(ref before) = change.before {
+                        for content in Self::yaml_property_lines(change.property, before, 6) {
+                            lines.push(PatchLine {
+                                line_type: PatchLineType::Deletion,
+                                content,
+                                indent_level: 3,
+                            });
+                        }
+                    }
+                    for content in Self::yaml_property_lines(change.property, &change.after, 6) {
+                        lines.push(PatchLine {
+                            line_type: PatchLineType::Addition,
+                            content,
+                            indent_level: 3,
+                        });
+                    }
+                }
+            }
+            FixAction::GatewayEndpoints => {
+                lines.push(context("Resources:".to_string(), 0));
+                for (id, resource) in Self::gateway_endpoints() {
+                    let value = json!({ id: resource });
+                    for content in Self::yaml_lines(&value, 2) {
+                        lines.push(PatchLine {
+                            line_type: PatchLineType::Addition,
+                            content,
+                            indent_level: 1,
+                        });
+                    }
+                }
+            }
+        }
+
+        Self::hunk(lines, vec![])
+    }
+
+    /// Render CDK property override statements for the affected construct
+    fn render_cdk(&self, logical_id: &str, fix: &CfnFix, language: CdkLanguage) -> Vec<String> {
+        let construct_id = self.construct_id(logical_id);
+        let mut lines = Vec::new();
+
+        if let Some(path) = self.construct_paths.get(logical_id) {
+            lines.push(match language {
+                CdkLanguage::TypeScript => format!("// CostPilot fix for {}", path),
+                CdkLanguage::Python => format!("# CostPilot fix for {}", path),
+            });
+        }
+
+        match (&fix.action, language) {
+            (FixAction::Properties(changes), CdkLanguage::TypeScript) => {
+                let var = to_camel_case(&construct_id);
+                lines.push(format!(
+                    "const {}Cfn = {}.node.defaultChild as cdk.CfnResource;",
+                    var, var
+                ));
+                for change in changes {
+                    lines.push(format!(
+                        "{}Cfn.addPropertyOverride('{}', {});",
+                        var, change.property, change.after
+                    ));
+                }
+            }
+            (FixAction::Properties(changes), CdkLanguage::Python) => {
+                let var = to_snake_case(&construct_id);
+                lines.push(format!("{}_cfn = {}.node.default_child", var, var));
+                for change in changes {
+                    lines.push(format!(
+                        "{}_cfn.add_property_override(\"{}\", {})",
+                        var,
+                        change.property,
+                        to_python_literal(&change.after)
+                    ));
+                }
+            }
+            (FixAction::GatewayEndpoints, CdkLanguage::TypeScript) => {
+                lines.push(
+                    "vpc.addGatewayEndpoint('S3Endpoint', { service: ec2.GatewayVpcEndpointAwsService.S3 });"
+                        .to_string(),
+                );
+                lines.push(
+                    "vpc.addGatewayEndpoint('DynamoDbEndpoint', { service: ec2.GatewayVpcEndpointAwsService.DYNAMODB });"
+                        .to_string(),
+                );
+            }
+            (FixAction::GatewayEndpoints, CdkLanguage::Python) => {
+                lines.push(
+                    "vpc.add_gateway_endpoint(\"S3Endpoint\", service=ec2.GatewayVpcEndpointAwsService.S3)"
+                        .to_string(),
+                );
+                lines.push(
+                    "vpc.add_gateway_endpoint(\"DynamoDbEndpoint\", service=ec2.GatewayVpcEndpointAwsService.DYNAMODB)"
+                        .to_string(),
+                );
+            }
+        }
+
+        lines
+    }
+
+    /// Stack source file following `cdk init` layout conventions
+    fn cdk_filename(&self, logical_id: &str, language: CdkLanguage) -> String {
+        let stack = self
+            .construct_paths
+            .get(logical_id)
+            .and_then(|p| p.split('/').next())
+            .unwrap_or("App")
+            .to_string();
+        let stack = stack.strip_suffix("Stack").unwrap_or(&stack).to_string();
+        match language {
+            CdkLanguage::TypeScript => {
+                format!("lib/{}-stack.ts", to_snake_case(&stack).replace('_', "-"))
+            }
+            CdkLanguage::Python => {
+                let module = to_snake_case(&stack);
+                format!("{}/{}_stack.py", module, module)
+            }
+        }
+    }
+
+    /// Construct ID from the CDK path, falling back to the logical ID
+    fn construct_id(&self, logical_id: &str) -> String {
+        self.construct_paths
+            .get(logical_id)
+            .and_then(|path| {
+                path.split('/')
+                    .rfind(|s| *s != "Resource" && *s != "Default")
+                    .map(|s| s.to_string())
+            })
+            .unwrap_or_else(|| logical_id.to_string())
+    }
+
+    fn before_after(fix: &CfnFix) -> (String, String) {
+        match &fix.action {
+            FixAction::Properties(changes) => {
+                let render = |value: Option<&Value>, property: &str| match value {
+                    Some(v) => format!("{}: {}", property, v),
+                    None => format!("# {} not set", property),
+                };
+                let before = changes
+                    .iter()
+                    .map(|c| render(c.before.as_ref(), c.property))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let after = changes
+                    .iter()
+                    .map(|c| render(Some(&c.after), c.property))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                (before, after)
+            }
+            FixAction::GatewayEndpoints => (
+                "# No gateway endpoints (S3/DynamoDB traffic via NAT)".to_string(),
+                "AWS::EC2::VPCEndpoint (Gateway) for S3 and DynamoDB".to_string(),
+            ),
+        }
+    }
+
+    fn gateway_endpoints() -> Vec<(String, Value)> {
+        ["s3", "dynamodb"]
+            .iter()
+            .map(|service| {
+                let id = if *service == "s3" {
+                    "S3GatewayEndpoint"
+                } else {
+                    "DynamoDbGatewayEndpoint"
+                };
+                (
+                    id.to_string(),
+                    json!({
+                        "Type": "AWS::EC2::VPCEndpoint",
+                        "Properties": {
+                            "VpcEndpointType": "Gateway",
+                            "ServiceName": {"Fn::Sub": format!("com.amazonaws.${{AWS::Region}}.{}", service)},
+                            "VpcId": {"Ref": "VpcId"},
+                            "RouteTableIds": [{"Ref": "PrivateRouteTable"}]
+                        }
+                    }),
+                )
+            })
+            .collect()
+    }
+
+    fn yaml_property_lines(property: &str, value: &Value, indent: usize) -> Vec<String> {
+        Self::yaml_lines(&json!({ property: value }), indent)
+    }
+
+    fn yaml_lines(value: &Value, indent: usize) -> Vec<String> {
+        let pad = " ".repeat(indent);
+        serde_yaml::to_string(value)
+            .unwrap_or_default()
+            .lines()
+            .map(|line| format!("{}{}", pad, line))
+            .collect()
+    }
+
+    fn hunk(lines: Vec<PatchLine>, context_after: Vec<String>) -> PatchHunk {
+        let old_count = lines
+            .iter()
+            .filter(|l| l.line_type != PatchLineType::Addition)
+            .count();
+        let new_count = lines
+            .iter()
+            .filter(|l| l.line_type != PatchLineType::Deletion)
+            .count();
+        PatchHunk {
+            old_start: 1,
+            old_count,
+            new_start: 1,
+            new_count,
+            lines,
+            context_before: vec![],
+            context_after,
+        }
+    }
+
+    /// Logical ID from a resource ID (`aws_instance.WebServer` -> `WebServer`)
+    fn logical_id(resource_id: &str) -> String {
+        resource_id
+            .split('.')
+            .next_back()
+            .unwrap_or(resource_id)
+            .to_string()
+    }
+
+    /// CloudFormation type for a normalized resource type
+    fn cfn_type(resource_type: &str) -> &str {
+        match resource_type {
+            "aws_instance" => "AWS::EC2::Instance",
+            "aws_db_instance" | "aws_rds_instance" => "AWS::RDS::DBInstance",
+            "aws_lambda_function" => "AWS::Lambda::Function",
+            "aws_s3_bucket" => "AWS::S3::Bucket",
+            "aws_dynamodb_table" => "AWS::DynamoDB::Table",
+            "aws_nat_gateway" => "AWS::EC2::NatGateway",
+            other => other,
+        }
+    }
+}
+
+fn to_camel_case(id: &str) -> String {
+    let mut chars = id.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn to_snake_case(id: &str) -> String {
+    let mut result = String::new();
+    for (i, ch) in id.chars().enumerate() {
+        if ch.is_uppercase() {
+            if i > 0 && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.extend(ch.to_lowercase());
+        } else if ch == '-' || ch == ' ' {
+            result.push('_');
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+fn to_python_literal(value: &Value) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => format!("{:?}", s),
+        Value::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(to_python_literal)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Object(map) => format!(
+            "{{{}}}",
+            map.iter()
+                .map(|(k, v)| format!("{:?}: {}", k, to_python_literal(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::{ChangeAction, Severity};
+
+    fn ec2_change() -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id("WebServer")
+            .resource_type("aws_instance")
+            .action(ChangeAction::Create)
+            .old_config(Value::Null)
+            .new_config(json!({"instance_type": "m5.xlarge"}))
+            .build()
+    }
+
+    fn detection() -> Detection {
+        Detection::builder()
+            .rule_id("OVERPROVISIONED_EC2")
+            .resource_id("WebServer")
+            .severity(Severity::High)
+            .message("Overprovisioned instance")
+            .build()
+    }
+
+    fn overprovisioned() -> AntiPattern {
+        AntiPattern {
+            pattern_id: "OVERPROVISIONED_EC2".to_string(),
+            pattern_name: "Overprovisioned EC2".to_string(),
+            description: "Large instance".to_string(),
+            severity: "HIGH".to_string(),
+            detected_in: "WebServer".to_string(),
+            evidence: vec![],
+            suggested_fix: None,
+            cost_impact: None,
+            confidence: None,
+            thresholds: None,
+            assumptions: None,
+        }
+    }
+
+    #[test]
+    fn test_cloudformation_snippet() {
+        let generator = CfnFixGenerator::new(AutofixTarget::CloudFormation);
+        let snippet = generator
+            .generate_snippet(&detection(), &ec2_change(), &[overprovisioned()], None)
+            .unwrap();
+
+        assert!(matches!(snippet.format, SnippetFormat::CloudFormation));
+        assert!(snippet.snippet.contains("Type: AWS::EC2::Instance"));
+        assert!(snippet.snippet.contains("InstanceType: m5.large"));
+    }
+
+    #[test]
+    fn test_cdk_typescript_snippet_uses_construct_path() {
+        let mut paths = HashMap::new();
+        paths.insert(
+            "WebServer".to_string(),
+            "ApiStack/WebServer/Resource".to_string(),
+        );
+        let generator = CfnFixGenerator::new(AutofixTarget::Cdk(CdkLanguage::TypeScript))
+            .with_construct_paths(paths);
+        let snippet = generator
+            .generate_snippet(&detection(), &ec2_change(), &[overprovisioned()], None)
+            .unwrap();
+
+        assert!(snippet
+            .snippet
+            .contains("const webServerCfn = webServer.node.defaultChild as cdk.CfnResource;"));
+        assert!(snippet
+            .snippet
+            .contains("webServerCfn.addPropertyOverride('InstanceType', \"m5.large\");"));
+        assert_eq!(
+            generator.cdk_filename("WebServer", CdkLanguage::TypeScript),
+            "lib/api-stack.ts"
+        );
+    }
+
+    #[test]
+    fn test_cdk_python_snippet() {
+        let generator = CfnFixGenerator::new(AutofixTarget::Cdk(CdkLanguage::Python));
+        let snippet = generator
+            .generate_snippet(&detection(), &ec2_change(), &[overprovisioned()], None)
+            .unwrap();
+
+        assert!(matches!(snippet.format, SnippetFormat::CDKPython));
+        assert!(snippet
+            .snippet
+            .contains("web_server_cfn.add_property_override(\"InstanceType\", \"m5.large\")"));
+    }
+
+    #[test]
+    fn test_no_fix_without_matching_pattern() {
+        let generator = CfnFixGenerator::new(AutofixTarget::CloudFormation);
+        assert!(generator
+            .generate_snippet(&detection(), &ec2_change(), &[], None)
+            .is_none());
+    }
+
+    #[test]
+    fn test_cloudformation_hunk_replaces_property() {
+        let fix = CfnFixGenerator::plan_fix(&ec2_change(), &[overprovisioned()]).unwrap();
+        let hunk = CfnFixGenerator::cloudformation_hunk("WebServer", "aws_instance", &fix);
+
+        let deletions: Vec<_> = hunk
+            .lines
+            .iter()
+            .filter(|l| l.line_type == PatchLineType::Deletion)
+            .collect();
+        assert_eq!(deletions.len(), 1);
+        assert_eq!(deletions[0].content, "      InstanceType: m5.xlarge");
+        assert_eq!(hunk.old_count, 4);
+        assert_eq!(hunk.new_count, 4);
+    }
+
+    #[test]
+    fn test_python_literal() {
+        assert_eq!(
+            to_python_literal(&json!({"a": true, "b": null, "c": [1, "x"]})),
+            "{\"a\": True, \"b\": None, \"c\": [1, \"x\"]}"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+// Autofix target selection - which IaC dialect fixes are emitted in
+
+use crate::artifact::{Artifact, ArtifactFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// CDK application language for generated property overrides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CdkLanguage {
+    TypeScript,
+    Python,
+}
+
+impl std::str::FromStr for CdkLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "typescript" | "ts" => Ok(CdkLanguage::TypeScript),
+            "python" | "py" => Ok(CdkLanguage::Python),
+            _ => Err(format!(
+                "Unknown CDK language: {}. Valid languages: typescript, python",
+                s
+            )),
+        }
+    }
+}
+
+/// IaC dialect that autofix snippets and patches are generated for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutofixTarget {
+    Terraform,
+    CloudFormation,
+    Cdk(CdkLanguage),
+}
+
+impl AutofixTarget {
+    /// Select target from a parsed artifact.
+    ///
+    /// CDK-synthesized templates carry `aws:cdk:path` resource metadata; plain
+    /// CloudFormation templates parsed by the same parser do not.
+    pub fn from_artifact(artifact: &Artifact, cdk_language: CdkLanguage) -> Self {
+        match artifact.format {
+            ArtifactFormat::Cdk => {
+                if construct_paths(artifact).is_empty() {
+                    AutofixTarget::CloudFormation
+                } else {
+                    AutofixTarget::Cdk(cdk_language)
+                }
+            }
+            // Both deploy as CloudFormation stacks keyed by logical ID
+            ArtifactFormat::Sam | ArtifactFormat::Serverless => AutofixTarget::CloudFormation,
+            ArtifactFormat::Terraform | ArtifactFormat::Pulumi | ArtifactFormat::Kubernetes => {
+                AutofixTarget::Terraform
+            }
+        }
+    }
+
+    /// Human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            AutofixTarget::Terraform => "Terraform",
+            AutofixTarget::CloudFormation => "CloudFormation",
+            AutofixTarget::Cdk(CdkLanguage::TypeScript) => "CDK (TypeScript)",
+            AutofixTarget::Cdk(CdkLanguage::Python) => "CDK (Python)",
+        }
+    }
+}
+
+/// Map logical IDs to CDK construct paths (from `aws:cdk:path` metadata)
+pub fn construct_paths(artifact: &Artifact) -> HashMap<String, String> {
+    artifact
+        .resources
+        .iter()
+        .filter_map(|r| {
+            r.metadata
+                .get("aws:cdk:path")
+                .map(|path| (r.id.clone(), path.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifact::{ArtifactMetadata, ArtifactResource};
+
+    fn artifact(with_cdk_path: bool) -> Artifact {
+        let mut metadata = HashMap::new();
+        if with_cdk_path {
+            metadata.insert(
+                "aws:cdk:path".to_string(),
+                "MyStack/WebServer/Resource".to_string(),
+            );
+        }
+        let mut artifact = Artifact::new(
+            ArtifactFormat::Cdk,
+            ArtifactMetadata {
+                source: "template.json".to_string(),
+                version: None,
+                stack_name: None,
+                region: None,
+                tags: HashMap::new(),
+            },
+        );
+        artifact.add_resource(ArtifactResource {
+            id: "WebServer".to_string(),
+            resource_type: "AWS::EC2::Instance".to_string(),
+            properties: HashMap::new(),
+            depends_on: vec![],
+            metadata,
+        });
+        artifact
+    }
+
+    #[test]
+    fn test_target_from_cdk_artifact() {
+        assert_eq!(
+            AutofixTarget::from_artifact(&artifact(true), CdkLanguage::Python),
+            AutofixTarget::Cdk(CdkLanguage::Python)
+        );
+    }
+
+    #[test]
+    fn test_target_from_plain_template() {
+        assert_eq!(
+            AutofixTarget::from_artifact(&artifact(false), CdkLanguage::TypeScript),
+            AutofixTarget::CloudFormation
+        );
+    }
+
+    #[test]
+    fn test_cdk_language_parse() {
+        assert_eq!("ts".parse::<CdkLanguage>(), Ok(CdkLanguage::TypeScript));
+        assert_eq!("Python".parse::<CdkLanguage>(), Ok(CdkLanguage::Python));
+        assert!("go".parse::<CdkLanguage>().is_err());
+    }
+}
@@ -1,9 +1,13 @@
 pub mod autofix_engine;
+pub mod cfn_generator;
 pub mod drift_safety;
+pub mod iac_target;
 pub mod patch_generator;
 pub mod patch_simulation;
 pub mod snippet_generator;
 
 pub use autofix_engine::{AutofixEngine, AutofixMode, AutofixResult};
+pub use cfn_generator::CfnFixGenerator;
+pub use iac_target::{AutofixTarget, CdkLanguage};
 pub use patch_generator::{PatchFile, PatchGenerator, PatchMetadata, PatchResult};
 pub use snippet_generator::{BeforeAfter, FixSnippet, SnippetFormat, SnippetGenerator};
@@ -616,7 +616,7 @@ impl PatchGenerator {
     }
 
     /// Recommend instance downsize
-    fn recommend_instance_downsize(instance_type: &str) -> &str {
+    pub(crate) fn recommend_instance_downsize(instance_type: &str) -> &str {
         match instance_type {
             "t3.2xlarge" => "t3.xlarge",
             "t3.xlarge" => "t3.large",
@@ -633,7 +633,7 @@ impl PatchGenerator {
     }
 
     /// Recommend RDS instance downsize
-    fn recommend_rds_downsize(instance_class: &str) -> &str {
+    pub(crate) fn recommend_rds_downsize(instance_class: &str) -> &str {
         match instance_class {
             "db.m5.2xlarge" => "db.m5.xlarge",
             "db.m5.xlarge" => "db.m5.large",
@@ -156,7 +156,10 @@ impl PatchSimulator {
 
         // Check for breaking changes
         if !simulation.warnings.is_empty() {
-            eprintln!("Warnings: {}", simulation.warnings.join(", "));
+            tracing::warn!(
+                "Patch simulation warnings: {}",
+                simulation.warnings.join(", ")
+            );
         }
 
         Ok(())
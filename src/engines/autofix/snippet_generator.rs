@@ -1,6 +1,7 @@
 // Snippet generator - MVP deterministic, idempotent fix generation
 
 use crate::engines::explain::anti_patterns::AntiPattern;
+use crate::engines::explain::Counterfactual;
 use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +24,8 @@ pub struct FixSnippet {
 pub enum SnippetFormat {
     Terraform,
     CDK,
+    CDKPython,
+    CloudFormation,
 }
 
 /// Before/After comparison
@@ -330,6 +333,51 @@ impl SnippetGenerator {
             idempotent: true,
         })
     }
+
+    /// Generate a fix snippet applying a counterfactual
+    pub fn from_counterfactual(counterfactual: &Counterfactual) -> FixSnippet {
+        let name = counterfactual
+            .resource_id
+            .split('.')
+            .next_back()
+            .unwrap_or("example");
+        let assignment = |value: &serde_json::Value| match counterfactual.attribute.split_once('.')
+        {
+            Some((_, attribute)) => format!("{} = {}", attribute, value),
+            None => format!("{} = {}", counterfactual.attribute, value),
+        };
+        let after = assignment(&counterfactual.proposed_value);
+        let body = match counterfactual.attribute.split_once('.') {
+            Some((block, _)) => format!("  {} {{\n    {}\n  }}", block, after),
+            None => format!("  {}", after),
+        };
+
+        FixSnippet {
+            resource_id: counterfactual.resource_id.clone(),
+            resource_type: counterfactual.resource_type.clone(),
+            snippet: format!(
+                "resource \"{}\" \"{}\" {{\n{}\n  # ... other attributes ...\n}}",
+                counterfactual.resource_type, name, body
+            ),
+            format: SnippetFormat::Terraform,
+            rationale: format!(
+                "{}. This is the highest-impact single configuration change for this resource; \
+                confirm the workload tolerates it before applying.",
+                counterfactual.description()
+            ),
+            before_after: BeforeAfter {
+                before: assignment(&counterfactual.current_value),
+                after,
+                change_description: counterfactual.description(),
+            },
+            impact: format!(
+                "Estimated savings: ${:.2}/month",
+                counterfactual.monthly_savings
+            ),
+            deterministic: true,
+            idempotent: true,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -490,4 +538,24 @@ mod tests {
             "medium"
         );
     }
+
+    #[test]
+    fn test_snippet_from_counterfactual_nested_attribute() {
+        let counterfactual = Counterfactual {
+            resource_id: "module.app.aws_instance.web".to_string(),
+            resource_type: "aws_instance".to_string(),
+            driver: crate::engines::explain::CostDriver::StorageClass,
+            attribute: "root_block_device.volume_type".to_string(),
+            current_value: serde_json::json!("gp2"),
+            proposed_value: serde_json::json!("gp3"),
+            monthly_savings: 10.0,
+        };
+
+        let snippet = SnippetGenerator::from_counterfactual(&counterfactual);
+        assert!(snippet.snippet.starts_with(
+            "resource \"aws_instance\" \"web\" {\n  root_block_device {\n    volume_type = \"gp3\"\n  }"
+        ));
+        assert_eq!(snippet.before_after.before, "volume_type = \"gp2\"");
+        assert_eq!(snippet.impact, "Estimated savings: $10.00/month");
+    }
 }
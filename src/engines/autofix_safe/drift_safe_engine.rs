@@ -1,4 +1,5 @@
 use super::drift_safe_types::*;
+use super::three_way_drift::{TerraformState, ThreeWayDriftReport};
 use crate::engines::detection::ResourceChange;
 use crate::engines::policy::PolicyEngine;
 use crate::engines::slo::SloManager;
@@ -166,6 +167,7 @@ impl DriftSafeEngine {
             ),
             restore_config,
             verification: Some("Verify configuration matches original hash".to_string()),
+            action: RollbackAction::RestoreConfig,
         };
 
         operation.rollback_plan.steps.push(restore_step);
@@ -220,6 +222,31 @@ impl DriftSafeEngine {
         }
     }
 
+    /// Compare config, plan and state for every resource in a Terraform plan
+    pub fn detect_three_way_drift(
+        &self,
+        plan: &serde_json::Value,
+        state: &TerraformState,
+    ) -> ThreeWayDriftReport {
+        ThreeWayDriftReport::build(plan, state)
+    }
+
+    /// Detect drift for one resource using the recorded Terraform state.
+    ///
+    /// Unlike `detect_drift`, nested blocks are compared per attribute and
+    /// computed (unknown-after-apply) values are not reported.
+    pub fn detect_drift_from_state(
+        &self,
+        plan: &serde_json::Value,
+        state: &TerraformState,
+        address: &str,
+    ) -> DriftDetection {
+        self.detect_three_way_drift(plan, state)
+            .drift_for(address, |path, expected, actual| {
+                self.assess_drift_impact(path, expected, actual)
+            })
+    }
+
     /// Assess impact of drift
     fn assess_drift_impact(
         &self,
@@ -609,6 +636,39 @@ mod tests {
         assert_eq!(operation.status, OperationStatus::RolledBack);
     }
 
+    #[test]
+    fn test_drift_from_state_nested_block() {
+        let engine = DriftSafeEngine::new();
+        let plan = serde_json::json!({
+            "resource_changes": [{
+                "address": "aws_instance.web",
+                "type": "aws_instance",
+                "change": {
+                    "before": {"root_block_device": [{"volume_size": 50}]},
+                    "after": {"root_block_device": [{"volume_size": 50}]},
+                    "after_unknown": {}
+                }
+            }]
+        });
+        let state = TerraformState::from_json(&serde_json::json!({
+            "resources": [{
+                "mode": "managed",
+                "type": "aws_instance",
+                "name": "web",
+                "instances": [{"attributes": {"root_block_device": [{"volume_size": 200}]}}]
+            }]
+        }))
+        .unwrap();
+
+        let drift = engine.detect_drift_from_state(&plan, &state, "aws_instance.web");
+        assert!(drift.has_drift);
+        assert_eq!(
+            drift.drifted_attributes[0].name,
+            "root_block_device[0].volume_size"
+        );
+        assert!(drift.drifted_attributes[0].impact.contains("Cost impact"));
+    }
+
     #[test]
     fn test_drift_impact_assessment() {
         let engine = DriftSafeEngine::new();
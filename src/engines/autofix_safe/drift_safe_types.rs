@@ -179,6 +179,31 @@ pub struct RollbackStep {
 
     /// Verification after this step
     pub verification: Option<String>,
+
+    /// Action performed when the step is executed
+    #[serde(default)]
+    pub action: RollbackAction,
+}
+
+/// Action performed by a rollback step
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RollbackAction {
+    /// Restore `restore_config` on the resource (recorded only)
+    #[default]
+    RestoreConfig,
+
+    /// Copy a backup over a modified file
+    RestoreFile {
+        path: std::path::PathBuf,
+        backup: std::path::PathBuf,
+    },
+
+    /// Reverse-apply a unified diff that was applied to `path`
+    RevertPatch {
+        path: std::path::PathBuf,
+        patch: String,
+    },
 }
 
 /// Rollback status
@@ -1,9 +1,18 @@
 pub mod drift_safe_engine;
 pub mod drift_safe_types;
+pub mod rollback_executor;
+pub mod three_way_drift;
 
 pub use drift_safe_engine::DriftSafeEngine;
 pub use drift_safe_types::{
     CheckStatus, DriftDetection, DriftSafeOperation, DriftSeverity, DriftedAttribute, LogEntry,
-    LogLevel, OperationStatus, ResourceState, RollbackPlan, RollbackStatus, RollbackStep,
-    SafetyCheck, SafetyCheckType,
+    LogLevel, OperationStatus, ResourceState, RollbackAction, RollbackPlan, RollbackStatus,
+    RollbackStep, SafetyCheck, SafetyCheckType,
+};
+pub use rollback_executor::{
+    JournalEntry, JournalStepStatus, RollbackCheckpoint, RollbackError, RollbackExecutor,
+    RollbackJournal,
+};
+pub use three_way_drift::{
+    AttributeComparison, ResourceDrift, TerraformState, ThreeWayDriftKind, ThreeWayDriftReport,
 };
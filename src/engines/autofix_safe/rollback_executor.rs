@@ -0,0 +1,562 @@
+use super::drift_safe_types::*;
+use crate::engines::policy::{AuditEvent, AuditEventType, AuditLog, AuditLogError};
+use base64::Engine;
+use chrono::Utc;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Rollback executor errors
+#[derive(Debug, Error)]
+pub enum RollbackError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid checkpoint: {0}")]
+    InvalidCheckpoint(String),
+
+    #[error("Failed to record rollback journal: {0}")]
+    Audit(#[from] AuditLogError),
+}
+
+/// Progress of a rollback, persisted after every step so a failed rollback
+/// can be resumed without re-running completed steps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackCheckpoint {
+    /// Operation being rolled back
+    pub operation_id: String,
+
+    /// Orders of steps that completed successfully
+    pub completed_steps: Vec<u32>,
+
+    /// Step that failed on the last attempt
+    pub failed_step: Option<u32>,
+
+    /// Error from the last failed step
+    pub last_error: Option<String>,
+
+    /// Number of execution attempts
+    pub attempts: u32,
+
+    /// Last update timestamp
+    pub updated_at: String,
+}
+
+/// Outcome of a single step in the journal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStepStatus {
+    Completed,
+    Failed,
+    /// Completed on a previous attempt
+    Skipped,
+}
+
+/// Journal entry for a rollback step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub order: u32,
+    pub description: String,
+    pub status: JournalStepStatus,
+    pub message: String,
+    pub timestamp: String,
+}
+
+/// Signed record of a rollback attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollbackJournal {
+    pub operation_id: String,
+    pub resource_id: String,
+    pub attempt: u32,
+    pub started_at: String,
+    pub completed_at: String,
+    pub success: bool,
+    pub entries: Vec<JournalEntry>,
+
+    /// Base64 Ed25519 signature over the journal with this field empty
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl RollbackJournal {
+    /// Canonical bytes covered by the signature
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.signature.clear();
+        serde_json::to_vec(&unsigned).unwrap_or_default()
+    }
+
+    /// Verify the journal signature
+    pub fn verify(&self, public_key: &VerifyingKey) -> bool {
+        let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(&self.signature) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_slice(&bytes) else {
+            return false;
+        };
+        public_key
+            .verify(&self.signing_payload(), &signature)
+            .is_ok()
+    }
+}
+
+/// Executes rollback plans step by step with checkpointing
+pub struct RollbackExecutor {
+    checkpoint_dir: PathBuf,
+    signing_key: SigningKey,
+    actor: String,
+}
+
+impl RollbackExecutor {
+    /// Create executor storing checkpoints in `checkpoint_dir`
+    pub fn new(checkpoint_dir: impl Into<PathBuf>, signing_key: SigningKey) -> Self {
+        Self {
+            checkpoint_dir: checkpoint_dir.into(),
+            signing_key,
+            actor: "costpilot".to_string(),
+        }
+    }
+
+    /// Set actor recorded in the audit log
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = actor.into();
+        self
+    }
+
+    /// Public key for verifying journals written by this executor
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Execute (or resume) the operation's rollback plan.
+    ///
+    /// Step failures do not return an error: the journal is still signed and
+    /// recorded with `success: false`, the checkpoint is kept, and calling
+    /// `execute` again resumes from the failed step.
+    pub fn execute(
+        &self,
+        operation: &mut DriftSafeOperation,
+        audit_log: &mut AuditLog,
+    ) -> Result<RollbackJournal, RollbackError> {
+        let mut checkpoint =
+            self.load_checkpoint(&operation.id)?
+                .unwrap_or_else(|| RollbackCheckpoint {
+                    operation_id: operation.id.clone(),
+                    completed_steps: Vec::new(),
+                    failed_step: None,
+                    last_error: None,
+                    attempts: 0,
+                    updated_at: Utc::now().to_rfc3339(),
+                });
+        checkpoint.attempts += 1;
+
+        if operation.status != OperationStatus::RollingBack {
+            operation.trigger_rollback();
+        }
+
+        let mut journal = RollbackJournal {
+            operation_id: operation.id.clone(),
+            resource_id: operation.resource_id.clone(),
+            attempt: checkpoint.attempts,
+            started_at: Utc::now().to_rfc3339(),
+            completed_at: String::new(),
+            success: true,
+            entries: Vec::new(),
+            signature: String::new(),
+        };
+
+        let mut steps = operation.rollback_plan.steps.clone();
+        steps.sort_by_key(|s| s.order);
+
+        for step in &steps {
+            if checkpoint.completed_steps.contains(&step.order) {
+                journal.entries.push(Self::entry(
+                    step,
+                    JournalStepStatus::Skipped,
+                    "Completed on a previous attempt".to_string(),
+                ));
+                continue;
+            }
+
+            match Self::run_step(step) {
+                Ok(message) => {
+                    operation.log(
+                        LogLevel::Info,
+                        format!("Rollback step {}: {}", step.order, message),
+                    );
+                    checkpoint.completed_steps.push(step.order);
+                    checkpoint.failed_step = None;
+                    checkpoint.last_error = None;
+                    self.save_checkpoint(&mut checkpoint)?;
+                    journal
+                        .entries
+                        .push(Self::entry(step, JournalStepStatus::Completed, message));
+                }
+                Err(message) => {
+                    operation.log(
+                        LogLevel::Error,
+                        format!("Rollback step {} failed: {}", step.order, message),
+                    );
+                    checkpoint.failed_step = Some(step.order);
+                    checkpoint.last_error = Some(message.clone());
+                    self.save_checkpoint(&mut checkpoint)?;
+                    journal
+                        .entries
+                        .push(Self::entry(step, JournalStepStatus::Failed, message));
+                    journal.success = false;
+                    break;
+                }
+            }
+        }
+
+        if journal.success {
+            operation.mark_rollback_complete();
+            self.clear_checkpoint(&operation.id)?;
+        } else {
+            operation.status = OperationStatus::RollbackFailed;
+            operation.rollback_plan.status = RollbackStatus::Failed;
+        }
+
+        journal.completed_at = Utc::now().to_rfc3339();
+        let signature = self.signing_key.sign(&journal.signing_payload());
+        journal.signature = base64::engine::general_purpose::STANDARD.encode(signature.to_bytes());
+
+        self.record(operation, &journal, audit_log)?;
+        Ok(journal)
+    }
+
+    /// Load checkpoint for an operation, if one exists
+    pub fn load_checkpoint(
+        &self,
+        operation_id: &str,
+    ) -> Result<Option<RollbackCheckpoint>, RollbackError> {
+        let path = self.checkpoint_path(operation_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        let checkpoint: RollbackCheckpoint = serde_json::from_str(&content)
+            .map_err(|e| RollbackError::InvalidCheckpoint(e.to_string()))?;
+        if checkpoint.operation_id != operation_id {
+            return Err(RollbackError::InvalidCheckpoint(format!(
+                "checkpoint belongs to {}",
+                checkpoint.operation_id
+            )));
+        }
+        Ok(Some(checkpoint))
+    }
+
+    fn save_checkpoint(&self, checkpoint: &mut RollbackCheckpoint) -> Result<(), RollbackError> {
+        checkpoint.updated_at = Utc::now().to_rfc3339();
+        fs::create_dir_all(&self.checkpoint_dir)?;
+        let path = self.checkpoint_path(&checkpoint.operation_id);
+        let tmp = path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(checkpoint)
+            .map_err(|e| RollbackError::InvalidCheckpoint(e.to_string()))?;
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn clear_checkpoint(&self, operation_id: &str) -> Result<(), RollbackError> {
+        let path = self.checkpoint_path(operation_id);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn checkpoint_path(&self, operation_id: &str) -> PathBuf {
+        self.checkpoint_dir
+            .join(format!("{}.rollback.json", operation_id))
+    }
+
+    /// Append the signed journal to the audit log
+    fn record(
+        &self,
+        operation: &DriftSafeOperation,
+        journal: &RollbackJournal,
+        audit_log: &mut AuditLog,
+    ) -> Result<(), RollbackError> {
+        let journal_json = serde_json::to_string(journal)
+            .map_err(|e| AuditLogError::SerializationError(e.to_string()))?;
+
+        let mut event = AuditEvent::new(
+            AuditEventType::RollbackExecuted,
+            self.actor.clone(),
+            operation.resource_id.clone(),
+            operation.resource_type.clone(),
+            format!(
+                "Rollback of {} (attempt {}): {}",
+                operation.id,
+                journal.attempt,
+                if journal.success {
+                    "completed"
+                } else {
+                    "failed"
+                }
+            ),
+        )
+        .with_metadata("operation_id".to_string(), operation.id.clone())
+        .with_metadata("journal".to_string(), journal_json)
+        .with_metadata("journal_signature".to_string(), journal.signature.clone());
+
+        if !journal.success {
+            let error = journal
+                .entries
+                .iter()
+                .find(|e| e.status == JournalStepStatus::Failed)
+                .map(|e| e.message.clone())
+                .unwrap_or_default();
+            event = event.with_error(error);
+        }
+
+        audit_log.append(event)?;
+        Ok(())
+    }
+
+    fn entry(step: &RollbackStep, status: JournalStepStatus, message: String) -> JournalEntry {
+        JournalEntry {
+            order: step.order,
+            description: step.description.clone(),
+            status,
+            message,
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Run a single step, returning a journal message
+    fn run_step(step: &RollbackStep) -> Result<String, String> {
+        match &step.action {
+            RollbackAction::RestoreConfig => Ok(format!(
+                "Recorded restore of {} configuration attributes",
+                step.restore_config.len()
+            )),
+            RollbackAction::RestoreFile { path, backup } => {
+                if !backup.exists() {
+                    return Err(format!("Backup not found: {}", backup.display()));
+                }
+                fs::copy(backup, path)
+                    .map_err(|e| format!("Failed to restore {}: {}", path.display(), e))?;
+                Ok(format!(
+                    "Restored {} from {}",
+                    path.display(),
+                    backup.display()
+                ))
+            }
+            RollbackAction::RevertPatch { path, patch } => {
+                revert_patch(path, patch)?;
+                Ok(format!("Reverted patch on {}", path.display()))
+            }
+        }
+    }
+}
+
+/// Reverse-apply a unified diff to a file.
+///
+/// Each hunk's post-image (context + additions) is replaced by its pre-image
+/// (context + deletions). Hunks whose pre-image is already present are treated
+/// as reverted so a resumed rollback is idempotent.
+fn revert_patch(path: &Path, patch: &str) -> Result<(), String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+
+    for (old, new) in parse_hunks(patch) {
+        if let Some(pos) = find_block(&lines, &new) {
+            lines.splice(pos..pos + new.len(), old);
+        } else if find_block(&lines, &old).is_none() {
+            return Err(format!(
+                "Patch does not apply to {}: hunk not found",
+                path.display()
+            ));
+        }
+    }
+
+    let mut output = lines.join("\n");
+    if content.ends_with('\n') {
+        output.push('\n');
+    }
+    fs::write(path, output).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Split a unified diff into (pre-image, post-image) line blocks per hunk
+fn parse_hunks(patch: &str) -> Vec<(Vec<String>, Vec<String>)> {
+    let mut hunks = Vec::new();
+    let mut current: Option<(Vec<String>, Vec<String>)> = None;
+
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            hunks.extend(current.take());
+            current = Some((Vec::new(), Vec::new()));
+            continue;
+        }
+        let Some((old, new)) = current.as_mut() else {
+            continue;
+        };
+        if let Some(rest) = line.strip_prefix('+') {
+            new.push(rest.to_string());
+        } else if let Some(rest) = line.strip_prefix('-') {
+            old.push(rest.to_string());
+        } else if let Some(rest) = line.strip_prefix(' ') {
+            old.push(rest.to_string());
+            new.push(rest.to_string());
+        } else if line.is_empty() {
+            old.push(String::new());
+            new.push(String::new());
+        }
+    }
+    hunks.extend(current);
+    hunks
+}
+
+fn find_block(lines: &[String], block: &[String]) -> Option<usize> {
+    if block.is_empty() || block.len() > lines.len() {
+        return None;
+    }
+    (0..=lines.len() - block.len()).find(|&i| lines[i..i + block.len()] == *block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn executor(dir: &TempDir) -> RollbackExecutor {
+        RollbackExecutor::new(
+            dir.path().join("checkpoints"),
+            SigningKey::from_bytes(&[7u8; 32]),
+        )
+    }
+
+    fn operation(steps: Vec<RollbackStep>) -> DriftSafeOperation {
+        let mut op = DriftSafeOperation::new(
+            "aws_instance.web".to_string(),
+            "aws_instance".to_string(),
+            "Downsize".to_string(),
+            ResourceState::new(HashMap::new(), 100.0),
+            ResourceState::new(HashMap::new(), 50.0),
+        );
+        op.rollback_plan.steps = steps;
+        op
+    }
+
+    fn step(order: u32, action: RollbackAction) -> RollbackStep {
+        RollbackStep {
+            order,
+            description: format!("step {}", order),
+            restore_config: HashMap::new(),
+            verification: None,
+            action,
+        }
+    }
+
+    #[test]
+    fn test_revert_patch() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("main.tf");
+        fs::write(
+            &file,
+            "resource \"aws_instance\" \"web\" {\n  instance_type = \"t3.medium\"\n}\n",
+        )
+        .unwrap();
+        let patch = "--- a/main.tf\n+++ b/main.tf\n@@ -1,3 +1,3 @@\n resource \"aws_instance\" \"web\" {\n-  instance_type = \"t3.large\"\n+  instance_type = \"t3.medium\"\n }\n";
+
+        revert_patch(&file, patch).unwrap();
+        let content = fs::read_to_string(&file).unwrap();
+        assert!(content.contains("t3.large"));
+
+        // Already reverted - idempotent
+        revert_patch(&file, patch).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), content);
+    }
+
+    #[test]
+    fn test_execute_signs_and_records_journal() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("main.tf");
+        let backup = dir.path().join("main.tf.bak");
+        fs::write(&file, "patched").unwrap();
+        fs::write(&backup, "original").unwrap();
+
+        let executor = executor(&dir);
+        let mut op = operation(vec![
+            step(1, RollbackAction::RestoreConfig),
+            step(
+                2,
+                RollbackAction::RestoreFile {
+                    path: file.clone(),
+                    backup,
+                },
+            ),
+        ]);
+        let mut log = AuditLog::new();
+
+        let journal = executor.execute(&mut op, &mut log).unwrap();
+
+        assert!(journal.success);
+        assert_eq!(journal.entries.len(), 2);
+        assert!(journal.verify(&executor.verifying_key()));
+        assert_eq!(fs::read_to_string(&file).unwrap(), "original");
+        assert_eq!(op.status, OperationStatus::RolledBack);
+        assert!(executor.load_checkpoint(&op.id).unwrap().is_none());
+
+        let entries = log.get_by_event_type(AuditEventType::RollbackExecuted);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].event.metadata["journal_signature"],
+            journal.signature
+        );
+        assert!(log.verify_chain().is_ok());
+    }
+
+    #[test]
+    fn test_resume_after_failure() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("main.tf");
+        let backup = dir.path().join("main.tf.bak");
+        fs::write(&file, "patched").unwrap();
+
+        let executor = executor(&dir);
+        let mut op = operation(vec![
+            step(1, RollbackAction::RestoreConfig),
+            step(
+                2,
+                RollbackAction::RestoreFile {
+                    path: file.clone(),
+                    backup: backup.clone(),
+                },
+            ),
+        ]);
+        let mut log = AuditLog::new();
+
+        let first = executor.execute(&mut op, &mut log).unwrap();
+        assert!(!first.success);
+        assert_eq!(op.status, OperationStatus::RollbackFailed);
+        let checkpoint = executor.load_checkpoint(&op.id).unwrap().unwrap();
+        assert_eq!(checkpoint.completed_steps, vec![1]);
+        assert_eq!(checkpoint.failed_step, Some(2));
+
+        fs::write(&backup, "original").unwrap();
+        let second = executor.execute(&mut op, &mut log).unwrap();
+        assert!(second.success);
+        assert_eq!(second.attempt, 2);
+        assert_eq!(second.entries[0].status, JournalStepStatus::Skipped);
+        assert_eq!(second.entries[1].status, JournalStepStatus::Completed);
+        assert_eq!(log.entry_count(), 2);
+    }
+
+    #[test]
+    fn test_tampered_journal_fails_verification() {
+        let dir = TempDir::new().unwrap();
+        let executor = executor(&dir);
+        let mut op = operation(vec![step(1, RollbackAction::RestoreConfig)]);
+        let mut journal = executor.execute(&mut op, &mut AuditLog::new()).unwrap();
+
+        journal.success = false;
+        assert!(!journal.verify(&executor.verifying_key()));
+    }
+}
@@ -0,0 +1,560 @@
+use super::drift_safe_types::{DriftDetection, DriftedAttribute};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+/// Resource attributes recorded in a Terraform state file
+#[derive(Debug, Clone, Default)]
+pub struct TerraformState {
+    /// Instance attributes keyed by resource address
+    pub resources: HashMap<String, Value>,
+}
+
+/// How an attribute differs across config, plan and state
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreeWayDriftKind {
+    /// State was changed outside Terraform (state differs from the refreshed plan baseline)
+    OutOfBand,
+
+    /// Plan will change the attribute to match config
+    PlannedChange,
+
+    /// Planned value differs from the value written in config
+    ConfigMismatch,
+
+    /// Value is computed and only known after apply
+    Computed,
+}
+
+/// Attribute compared across all three sources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeComparison {
+    /// Flattened attribute path (e.g. `root_block_device[0].volume_size`)
+    pub path: String,
+
+    /// Value written in configuration (None if unset or a reference)
+    pub config: Option<Value>,
+
+    /// Planned value after apply
+    pub plan: Option<Value>,
+
+    /// Value recorded in state
+    pub state: Option<Value>,
+
+    /// Classification of the difference
+    pub kind: ThreeWayDriftKind,
+}
+
+/// Three-way comparison for one resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceDrift {
+    /// Resource address
+    pub address: String,
+
+    /// Resource type
+    pub resource_type: String,
+
+    /// Whether the resource exists in state
+    pub in_state: bool,
+
+    /// Attributes that differ in at least one source
+    pub attributes: Vec<AttributeComparison>,
+}
+
+/// Full config vs plan vs state drift report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreeWayDriftReport {
+    /// Per-resource comparisons (resources without differences are omitted)
+    pub resources: Vec<ResourceDrift>,
+
+    /// Plan resources missing from state
+    pub missing_from_state: Vec<String>,
+
+    /// State resources not referenced by the plan
+    pub missing_from_plan: Vec<String>,
+}
+
+impl TerraformState {
+    /// Load a Terraform state file (format version 4)
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read state file {}: {}", path.display(), e))?;
+        let value: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse state file {}: {}", path.display(), e))?;
+        Self::from_json(&value)
+    }
+
+    /// Parse state JSON, keying each managed instance by its full address
+    pub fn from_json(state: &Value) -> Result<Self, String> {
+        let resources = state
+            .get("resources")
+            .and_then(|r| r.as_array())
+            .ok_or("State file has no resources array")?;
+
+        let mut result = HashMap::new();
+        for resource in resources {
+            if resource.get("mode").and_then(|m| m.as_str()) == Some("data") {
+                continue;
+            }
+            let (Some(resource_type), Some(name)) = (
+                resource.get("type").and_then(|t| t.as_str()),
+                resource.get("name").and_then(|n| n.as_str()),
+            ) else {
+                continue;
+            };
+
+            let mut base = format!("{}.{}", resource_type, name);
+            if let Some(module) = resource.get("module").and_then(|m| m.as_str()) {
+                base = format!("{}.{}", module, base);
+            }
+
+            for instance in resource
+                .get("instances")
+                .and_then(|i| i.as_array())
+                .into_iter()
+                .flatten()
+            {
+                let address = match instance.get("index_key") {
+                    Some(Value::Number(n)) => format!("{}[{}]", base, n),
+                    Some(Value::String(s)) => format!("{}[\"{}\"]", base, s),
+                    _ => base.clone(),
+                };
+                let attributes = instance.get("attributes").cloned().unwrap_or(Value::Null);
+                result.insert(address, attributes);
+            }
+        }
+
+        Ok(Self { resources: result })
+    }
+}
+
+impl ThreeWayDriftReport {
+    /// Build report from a Terraform plan JSON (with `configuration`) and state
+    pub fn build(plan: &Value, state: &TerraformState) -> Self {
+        let config = config_values(plan);
+        let mut resources = Vec::new();
+        let mut missing_from_state = Vec::new();
+        let mut planned = BTreeSet::new();
+
+        for change in plan
+            .get("resource_changes")
+            .and_then(|c| c.as_array())
+            .into_iter()
+            .flatten()
+        {
+            if change.get("mode").and_then(|m| m.as_str()) == Some("data") {
+                continue;
+            }
+            let Some(address) = change.get("address").and_then(|a| a.as_str()) else {
+                continue;
+            };
+            planned.insert(address.to_string());
+
+            let resource_type = change
+                .get("type")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let details = change.get("change").cloned().unwrap_or(Value::Null);
+            let state_attrs = state.resources.get(address);
+            if state_attrs.is_none() {
+                missing_from_state.push(address.to_string());
+            }
+
+            let attributes =
+                compare_resource(config.get(&strip_index(address)), &details, state_attrs);
+            if !attributes.is_empty() {
+                resources.push(ResourceDrift {
+                    address: address.to_string(),
+                    resource_type,
+                    in_state: state_attrs.is_some(),
+                    attributes,
+                });
+            }
+        }
+
+        let mut missing_from_plan: Vec<String> = state
+            .resources
+            .keys()
+            .filter(|a| !planned.contains(*a))
+            .cloned()
+            .collect();
+        missing_from_plan.sort();
+
+        Self {
+            resources,
+            missing_from_state,
+            missing_from_plan,
+        }
+    }
+
+    /// Whether any attribute drifted outside Terraform or diverges from config
+    pub fn has_drift(&self) -> bool {
+        self.resources.iter().any(|r| {
+            r.attributes.iter().any(|a| {
+                matches!(
+                    a.kind,
+                    ThreeWayDriftKind::OutOfBand | ThreeWayDriftKind::ConfigMismatch
+                )
+            })
+        })
+    }
+
+    /// Drift detection for a single resource, usable by drift-safe safety checks.
+    ///
+    /// Planned changes and computed values are expected and not reported as drift.
+    pub fn drift_for(
+        &self,
+        address: &str,
+        impact: impl Fn(&str, &Value, &Value) -> String,
+    ) -> DriftDetection {
+        let drifted: Vec<DriftedAttribute> = self
+            .resources
+            .iter()
+            .filter(|r| r.address == address)
+            .flat_map(|r| r.attributes.iter())
+            .filter_map(|a| {
+                let expected = match a.kind {
+                    ThreeWayDriftKind::OutOfBand => a.plan.clone(),
+                    ThreeWayDriftKind::ConfigMismatch => a.config.clone(),
+                    _ => return None,
+                };
+                let actual = match a.kind {
+                    ThreeWayDriftKind::OutOfBand => a.state.clone(),
+                    _ => a.plan.clone(),
+                };
+                let expected = expected.unwrap_or(Value::Null);
+                let actual = actual.unwrap_or(Value::Null);
+                Some(DriftedAttribute {
+                    name: a.path.clone(),
+                    impact: impact(&a.path, &expected, &actual),
+                    expected,
+                    actual,
+                })
+            })
+            .collect();
+
+        if drifted.is_empty() {
+            DriftDetection::no_drift()
+        } else {
+            DriftDetection::with_drift(drifted)
+        }
+    }
+}
+
+/// Compare one resource's config, plan (`change` block) and state attributes
+fn compare_resource(
+    config: Option<&BTreeMap<String, Value>>,
+    change: &Value,
+    state: Option<&Value>,
+) -> Vec<AttributeComparison> {
+    let mut before = BTreeMap::new();
+    let mut after = BTreeMap::new();
+    let mut unknown = BTreeMap::new();
+    let mut current = BTreeMap::new();
+    flatten(
+        "",
+        change.get("before").unwrap_or(&Value::Null),
+        &mut before,
+    );
+    flatten("", change.get("after").unwrap_or(&Value::Null), &mut after);
+    flatten(
+        "",
+        change.get("after_unknown").unwrap_or(&Value::Null),
+        &mut unknown,
+    );
+    if let Some(state) = state {
+        flatten("", state, &mut current);
+    }
+    let unknown: BTreeSet<String> = unknown
+        .into_iter()
+        .filter(|(_, v)| v == &Value::Bool(true))
+        .map(|(k, _)| k)
+        .collect();
+
+    let empty = BTreeMap::new();
+    let config = config.unwrap_or(&empty);
+    let paths: BTreeSet<&String> = before
+        .keys()
+        .chain(after.keys())
+        .chain(current.keys())
+        .chain(config.keys())
+        .chain(unknown.iter())
+        .collect();
+
+    let mut comparisons = Vec::new();
+    for path in paths {
+        let cfg = config.get(path).cloned();
+        let planned = after.get(path).cloned();
+        let stated = current.get(path).cloned();
+
+        let kind = if is_unknown(path, &unknown) {
+            Some(ThreeWayDriftKind::Computed)
+        } else if state.is_some() && before.get(path) != current.get(path) && !before.is_empty() {
+            Some(ThreeWayDriftKind::OutOfBand)
+        } else if cfg.is_some() && cfg != planned {
+            Some(ThreeWayDriftKind::ConfigMismatch)
+        } else if planned != stated {
+            Some(ThreeWayDriftKind::PlannedChange)
+        } else {
+            None
+        };
+
+        if let Some(kind) = kind {
+            comparisons.push(AttributeComparison {
+                path: path.clone(),
+                config: cfg,
+                plan: planned,
+                state: stated,
+                kind,
+            });
+        }
+    }
+
+    comparisons
+}
+
+/// Path is unknown if it or any enclosing block is marked unknown
+fn is_unknown(path: &str, unknown: &BTreeSet<String>) -> bool {
+    unknown.iter().any(|u| {
+        path == u
+            || path
+                .strip_prefix(u.as_str())
+                .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+    })
+}
+
+/// Flatten nested blocks into dotted/indexed paths.
+///
+/// Lists of objects (nested blocks) are expanded per element; lists of scalars
+/// are compared as a whole.
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                let path = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten(&path, v, out);
+            }
+        }
+        Value::Array(items) if !items.is_empty() && items.iter().all(|i| i.is_object()) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(&format!("{}[{}]", prefix, i), item, out);
+            }
+        }
+        _ if prefix.is_empty() => {}
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// Constant values from the plan's `configuration` block, keyed by resource address
+fn config_values(plan: &Value) -> HashMap<String, BTreeMap<String, Value>> {
+    let mut result = HashMap::new();
+    if let Some(root) = plan.get("configuration").and_then(|c| c.get("root_module")) {
+        collect_module_config("", root, &mut result);
+    }
+    result
+}
+
+fn collect_module_config(
+    prefix: &str,
+    module: &Value,
+    out: &mut HashMap<String, BTreeMap<String, Value>>,
+) {
+    for resource in module
+        .get("resources")
+        .and_then(|r| r.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let Some(address) = resource.get("address").and_then(|a| a.as_str()) else {
+            continue;
+        };
+        let mut values = BTreeMap::new();
+        if let Some(expressions) = resource.get("expressions") {
+            flatten_expressions("", expressions, &mut values);
+        }
+        out.insert(format!("{}{}", prefix, address), values);
+    }
+
+    if let Some(calls) = module.get("module_calls").and_then(|c| c.as_object()) {
+        for (name, call) in calls {
+            if let Some(child) = call.get("module") {
+                collect_module_config(&format!("{}module.{}.", prefix, name), child, out);
+            }
+        }
+    }
+}
+
+/// Flatten configuration expressions, keeping only constant values
+fn flatten_expressions(prefix: &str, expressions: &Value, out: &mut BTreeMap<String, Value>) {
+    let Some(map) = expressions.as_object() else {
+        return;
+    };
+    for (key, expr) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match expr {
+            Value::Object(obj) if obj.contains_key("constant_value") => {
+                let value = &obj["constant_value"];
+                if value.is_object() || value.is_array() {
+                    flatten(&path, value, out);
+                } else {
+                    out.insert(path, value.clone());
+                }
+            }
+            Value::Object(obj) if obj.contains_key("references") => {}
+            Value::Array(blocks) => {
+                for (i, block) in blocks.iter().enumerate() {
+                    flatten_expressions(&format!("{}[{}]", path, i), block, out);
+                }
+            }
+            Value::Object(_) => flatten_expressions(&path, expr, out),
+            _ => {}
+        }
+    }
+}
+
+/// `aws_instance.web[0]` -> `aws_instance.web` (config is per resource, not per instance)
+fn strip_index(address: &str) -> String {
+    match address.rfind('[') {
+        Some(idx) if address.ends_with(']') => address[..idx].to_string(),
+        _ => address.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plan() -> Value {
+        json!({
+            "resource_changes": [{
+                "address": "aws_instance.web",
+                "mode": "managed",
+                "type": "aws_instance",
+                "change": {
+                    "actions": ["update"],
+                    "before": {
+                        "instance_type": "t3.large",
+                        "root_block_device": [{"volume_size": 50, "volume_type": "gp3"}],
+                        "tags": {"Env": "prod"}
+                    },
+                    "after": {
+                        "instance_type": "t3.medium",
+                        "root_block_device": [{"volume_size": 50, "volume_type": "gp3"}],
+                        "tags": {"Env": "prod"}
+                    },
+                    "after_unknown": {"arn": true}
+                }
+            }],
+            "configuration": {
+                "root_module": {
+                    "resources": [{
+                        "address": "aws_instance.web",
+                        "expressions": {
+                            "instance_type": {"constant_value": "t3.medium"},
+                            "subnet_id": {"references": ["aws_subnet.a.id"]},
+                            "root_block_device": [{"volume_size": {"constant_value": 100}}]
+                        }
+                    }]
+                }
+            }
+        })
+    }
+
+    fn state(volume_size: u64) -> TerraformState {
+        TerraformState::from_json(&json!({
+            "version": 4,
+            "resources": [{
+                "mode": "managed",
+                "type": "aws_instance",
+                "name": "web",
+                "instances": [{
+                    "attributes": {
+                        "arn": "arn:aws:ec2:us-east-1:123:instance/i-1",
+                        "instance_type": "t3.large",
+                        "root_block_device": [{"volume_size": volume_size, "volume_type": "gp3"}],
+                        "tags": {"Env": "prod"}
+                    }
+                }]
+            }, {
+                "mode": "managed",
+                "type": "aws_s3_bucket",
+                "name": "logs",
+                "instances": [{"index_key": 0, "attributes": {"bucket": "logs"}}]
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn kind_of(report: &ThreeWayDriftReport, path: &str) -> Option<ThreeWayDriftKind> {
+        report.resources[0]
+            .attributes
+            .iter()
+            .find(|a| a.path == path)
+            .map(|a| a.kind)
+    }
+
+    #[test]
+    fn test_state_parsing_addresses() {
+        let state = state(50);
+        assert!(state.resources.contains_key("aws_instance.web"));
+        assert!(state.resources.contains_key("aws_s3_bucket.logs[0]"));
+    }
+
+    #[test]
+    fn test_three_way_classification() {
+        let report = ThreeWayDriftReport::build(&plan(), &state(50));
+
+        assert_eq!(
+            kind_of(&report, "instance_type"),
+            Some(ThreeWayDriftKind::PlannedChange)
+        );
+        assert_eq!(
+            kind_of(&report, "root_block_device[0].volume_size"),
+            Some(ThreeWayDriftKind::ConfigMismatch)
+        );
+        assert_eq!(kind_of(&report, "arn"), Some(ThreeWayDriftKind::Computed));
+        assert_eq!(kind_of(&report, "tags.Env"), None);
+        assert_eq!(report.missing_from_plan, vec!["aws_s3_bucket.logs[0]"]);
+    }
+
+    #[test]
+    fn test_out_of_band_nested_change() {
+        let report = ThreeWayDriftReport::build(&plan(), &state(80));
+
+        assert_eq!(
+            kind_of(&report, "root_block_device[0].volume_size"),
+            Some(ThreeWayDriftKind::OutOfBand)
+        );
+        assert!(report.has_drift());
+
+        let detection = report.drift_for("aws_instance.web", |_, _, _| "impact".to_string());
+        let attr = detection
+            .drifted_attributes
+            .iter()
+            .find(|a| a.name == "root_block_device[0].volume_size")
+            .unwrap();
+        assert_eq!(attr.expected, json!(50));
+        assert_eq!(attr.actual, json!(80));
+    }
+
+    #[test]
+    fn test_missing_from_state() {
+        let report = ThreeWayDriftReport::build(&plan(), &TerraformState::default());
+        assert_eq!(report.missing_from_state, vec!["aws_instance.web"]);
+        assert!(!report.resources[0].in_state);
+    }
+}
@@ -0,0 +1,385 @@
+// Signed approvals for baseline updates
+//
+// `baseline bless` only applies a captured candidate when an approval file
+// signs the exact candidate bytes with a trusted Ed25519 key. Applied updates
+// are appended to the audit log with the approver as actor.
+
+use super::baseline_types::BaselinesConfig;
+use super::baselines_manager::BaselinesManager;
+use crate::engines::policy::{AuditEvent, AuditEventType, AuditLog, AuditLogError};
+use crate::engines::shared::compat::{self, Artifact};
+use base64::Engine;
+use chrono::Utc;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Baseline approval errors
+#[derive(Debug, Error)]
+pub enum BlessError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Approval file not found: {0}")]
+    MissingApproval(PathBuf),
+
+    #[error("Invalid {kind}: {message}")]
+    Parse { kind: &'static str, message: String },
+
+    #[error("Approval covers a different candidate (signed {approved}, candidate is {actual})")]
+    DigestMismatch { approved: String, actual: String },
+
+    #[error("Approval signature is invalid")]
+    InvalidSignature,
+
+    #[error("Approval was signed by a key that is not trusted")]
+    UntrustedKey,
+
+    #[error("Failed to update baselines: {0}")]
+    Baselines(String),
+
+    #[error("Failed to record baseline approval: {0}")]
+    Audit(#[from] AuditLogError),
+}
+
+impl BlessError {
+    fn parse(kind: &'static str, message: impl std::fmt::Display) -> Self {
+        Self::Parse {
+            kind,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Approval of a baseline candidate, signed by the approver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineApproval {
+    /// SHA-256 of the candidate file content
+    pub candidate_sha256: String,
+
+    /// Who approved the candidate
+    pub approver: String,
+
+    /// RFC 3339 approval time
+    pub approved_at: String,
+
+    /// Why the new baselines are expected
+    #[serde(default)]
+    pub justification: String,
+
+    /// Base64 Ed25519 public key of the approver
+    pub public_key: String,
+
+    /// Base64 Ed25519 signature over the approval with this field empty
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl BaselineApproval {
+    /// Approve `candidate` content as `approver`
+    pub fn sign(
+        candidate: &[u8],
+        approver: impl Into<String>,
+        justification: impl Into<String>,
+        key: &SigningKey,
+    ) -> Self {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let mut approval = Self {
+            candidate_sha256: candidate_digest(candidate),
+            approver: approver.into(),
+            approved_at: Utc::now().to_rfc3339(),
+            justification: justification.into(),
+            public_key: engine.encode(key.verifying_key().to_bytes()),
+            signature: String::new(),
+        };
+        approval.signature = engine.encode(key.sign(&approval.signing_payload()).to_bytes());
+        approval
+    }
+
+    /// Canonical bytes covered by the signature
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.signature.clear();
+        serde_json::to_vec(&unsigned).unwrap_or_default()
+    }
+
+    /// Check the approval covers `candidate` and is signed by a trusted key
+    pub fn verify(&self, candidate: &[u8], trusted: &[VerifyingKey]) -> Result<(), BlessError> {
+        let actual = candidate_digest(candidate);
+        if self.candidate_sha256 != actual {
+            return Err(BlessError::DigestMismatch {
+                approved: self.candidate_sha256.clone(),
+                actual,
+            });
+        }
+
+        let public_key = decode_verifying_key(&self.public_key)?;
+        if !trusted.contains(&public_key) {
+            return Err(BlessError::UntrustedKey);
+        }
+
+        let signature = base64::engine::general_purpose::STANDARD
+            .decode(&self.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or(BlessError::InvalidSignature)?;
+        public_key
+            .verify(&self.signing_payload(), &signature)
+            .map_err(|_| BlessError::InvalidSignature)
+    }
+
+    /// Load an approval file
+    pub fn load(path: &Path) -> Result<Self, BlessError> {
+        if !path.exists() {
+            return Err(BlessError::MissingApproval(path.to_path_buf()));
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| BlessError::parse("approval file", e))
+    }
+
+    /// Write the approval as pretty JSON
+    pub fn save(&self, path: &Path) -> Result<(), BlessError> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| BlessError::parse("approval", e))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Result of a successful bless
+#[derive(Debug, Clone)]
+pub struct BlessOutcome {
+    pub approver: String,
+
+    /// Baselines written, `global` first, then modules by name
+    pub updated: Vec<String>,
+
+    /// Sequence number of the audit log entry
+    pub audit_sequence: u64,
+}
+
+/// Apply an approved candidate to the baselines file
+///
+/// Nothing is written unless the approval verifies against `trusted` keys.
+/// The baselines file is created when missing; service baselines and
+/// metadata already in it are kept.
+pub fn bless(
+    candidate_path: &Path,
+    approval_path: &Path,
+    baselines_path: &Path,
+    trusted: &[VerifyingKey],
+    audit_log: &mut AuditLog,
+) -> Result<BlessOutcome, BlessError> {
+    let approval = BaselineApproval::load(approval_path)?;
+    let candidate = fs::read(candidate_path)?;
+    approval.verify(&candidate, trusted)?;
+
+    let (captured, _) =
+        compat::read::<BaselinesConfig>(Artifact::Baselines, &String::from_utf8_lossy(&candidate))
+            .map_err(|e| BlessError::parse("baseline candidate", e))?;
+
+    let mut manager = if baselines_path.exists() {
+        BaselinesManager::load_from_file(baselines_path).map_err(BlessError::Baselines)?
+    } else {
+        BaselinesManager::from_config(BaselinesConfig::new())
+    };
+    let previous_total = global_cost(manager.config());
+    let updated = manager.merge(&captured);
+    manager
+        .save_to_file(baselines_path)
+        .map_err(BlessError::Baselines)?;
+
+    let event = AuditEvent::new(
+        AuditEventType::BaselineBlessed,
+        approval.approver.clone(),
+        baselines_path.display().to_string(),
+        "baselines".to_string(),
+        format!(
+            "Blessed {} baseline(s) from {}",
+            updated.len(),
+            candidate_path.display()
+        ),
+    )
+    .with_metadata(
+        "candidate_sha256".to_string(),
+        approval.candidate_sha256.clone(),
+    )
+    .with_metadata("public_key".to_string(), approval.public_key.clone())
+    .with_metadata("justification".to_string(), approval.justification.clone())
+    .with_change(previous_total, global_cost(manager.config()));
+    let audit_sequence = audit_log.append(event)?;
+
+    Ok(BlessOutcome {
+        approver: approval.approver,
+        updated,
+        audit_sequence,
+    })
+}
+
+/// Hex SHA-256 of candidate content, as recorded in approvals
+pub fn candidate_digest(candidate: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(candidate))
+}
+
+/// Default approval file for a candidate: `<candidate>.approval.json`
+pub fn approval_path(candidate: &Path) -> PathBuf {
+    let mut name = candidate.as_os_str().to_os_string();
+    name.push(".approval.json");
+    PathBuf::from(name)
+}
+
+/// Decode a base64 Ed25519 public key
+pub fn decode_verifying_key(encoded: &str) -> Result<VerifyingKey, BlessError> {
+    let bytes = decode_key_bytes(encoded)
+        .ok_or_else(|| BlessError::parse("public key", "expected 32 base64-encoded bytes"))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| BlessError::parse("public key", e))
+}
+
+/// Decode a base64 Ed25519 signing key
+pub fn decode_signing_key(encoded: &str) -> Result<SigningKey, BlessError> {
+    decode_key_bytes(encoded)
+        .map(|bytes| SigningKey::from_bytes(&bytes))
+        .ok_or_else(|| BlessError::parse("signing key", "expected 32 base64-encoded bytes"))
+}
+
+fn decode_key_bytes(encoded: &str) -> Option<[u8; 32]> {
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()?
+        .try_into()
+        .ok()
+}
+
+fn global_cost(config: &BaselinesConfig) -> String {
+    config
+        .global
+        .as_ref()
+        .map(|g| format!("{:.2}", g.expected_monthly_cost))
+        .unwrap_or_else(|| "none".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::baselines::Baseline;
+    use tempfile::TempDir;
+
+    fn candidate(dir: &TempDir) -> PathBuf {
+        let mut config = BaselinesConfig::new();
+        config.set_global(Baseline::new(
+            "global".to_string(),
+            500.0,
+            "Captured from scan".to_string(),
+            "platform".to_string(),
+        ));
+        config.add_module(
+            "module.vpc".to_string(),
+            Baseline::new(
+                "module.vpc".to_string(),
+                120.0,
+                "Captured from scan".to_string(),
+                "platform".to_string(),
+            ),
+        );
+        let path = dir.path().join("baselines.candidate.json");
+        fs::write(&path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_bless_applies_approved_candidate() {
+        let dir = TempDir::new().unwrap();
+        let candidate_path = candidate(&dir);
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let approval =
+            BaselineApproval::sign(&fs::read(&candidate_path).unwrap(), "alice", "Q3", &key);
+        approval.save(&approval_path(&candidate_path)).unwrap();
+
+        let baselines_path = dir.path().join("baselines.json");
+        let mut log = AuditLog::new();
+        let outcome = bless(
+            &candidate_path,
+            &approval_path(&candidate_path),
+            &baselines_path,
+            &[key.verifying_key()],
+            &mut log,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.updated, vec!["global", "module.vpc"]);
+        let manager = BaselinesManager::load_from_file(&baselines_path).unwrap();
+        assert_eq!(
+            manager
+                .config()
+                .global
+                .as_ref()
+                .unwrap()
+                .expected_monthly_cost,
+            500.0
+        );
+        let entries = log.get_by_event_type(AuditEventType::BaselineBlessed);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].event.actor, "alice");
+        assert_eq!(entries[0].event.new_value.as_deref(), Some("500.00"));
+    }
+
+    #[test]
+    fn test_bless_requires_approval_from_trusted_key() {
+        let dir = TempDir::new().unwrap();
+        let candidate_path = candidate(&dir);
+        let baselines_path = dir.path().join("baselines.json");
+        let trusted = SigningKey::from_bytes(&[7u8; 32]).verifying_key();
+        let mut log = AuditLog::new();
+
+        let missing = bless(
+            &candidate_path,
+            &approval_path(&candidate_path),
+            &baselines_path,
+            &[trusted],
+            &mut log,
+        );
+        assert!(matches!(missing, Err(BlessError::MissingApproval(_))));
+
+        let stranger = SigningKey::from_bytes(&[9u8; 32]);
+        BaselineApproval::sign(
+            &fs::read(&candidate_path).unwrap(),
+            "mallory",
+            "",
+            &stranger,
+        )
+        .save(&approval_path(&candidate_path))
+        .unwrap();
+        let untrusted = bless(
+            &candidate_path,
+            &approval_path(&candidate_path),
+            &baselines_path,
+            &[trusted],
+            &mut log,
+        );
+        assert!(matches!(untrusted, Err(BlessError::UntrustedKey)));
+        assert!(!baselines_path.exists());
+        assert_eq!(log.entry_count(), 0);
+    }
+
+    #[test]
+    fn test_approval_rejects_edited_candidate_and_tampering() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let trusted = [key.verifying_key()];
+        let mut approval = BaselineApproval::sign(b"{}", "alice", "", &key);
+        assert!(approval.verify(b"{}", &trusted).is_ok());
+
+        assert!(matches!(
+            approval.verify(b"{ }", &trusted),
+            Err(BlessError::DigestMismatch { .. })
+        ));
+
+        approval.approver = "bob".to_string();
+        assert!(matches!(
+            approval.verify(b"{}", &trusted),
+            Err(BlessError::InvalidSignature)
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use super::tolerance::ToleranceConfig;
 use crate::engines::shared::models::RegressionType;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -59,6 +60,10 @@ pub struct BaselinesConfig {
     /// Configuration metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<BaselineMetadata>,
+
+    /// How comparison bands are sized (fixed percent when absent)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub tolerance: Option<ToleranceConfig>,
 }
 
 /// Metadata about baselines configuration
@@ -157,10 +162,19 @@ impl Baseline {
 
     /// Check if actual cost is within acceptable variance
     pub fn check_variance(&self, actual_cost: f64) -> BaselineStatus {
+        self.check_variance_within(actual_cost, self.acceptable_variance_percent)
+    }
+
+    /// Check actual cost against an explicit tolerance band, in percent
+    pub fn check_variance_within(
+        &self,
+        actual_cost: f64,
+        tolerance_percent: f64,
+    ) -> BaselineStatus {
         let variance =
             ((actual_cost - self.expected_monthly_cost) / self.expected_monthly_cost).abs() * 100.0;
 
-        if variance <= self.acceptable_variance_percent {
+        if variance <= tolerance_percent {
             BaselineStatus::Within
         } else if actual_cost > self.expected_monthly_cost {
             BaselineStatus::Exceeded {
@@ -217,6 +231,7 @@ impl BaselinesConfig {
                 review_cadence_days: Some(90),
                 owner_team: None,
             }),
+            tolerance: None,
         }
     }
 
@@ -1,4 +1,6 @@
 use super::baseline_types::{Baseline, BaselineStatus, BaselineViolation, BaselinesConfig};
+use super::tolerance::{Tolerance, ToleranceMode, TrendTolerances};
+use crate::engines::shared::compat::{self, Artifact};
 use crate::engines::shared::models::RegressionType;
 use serde_json;
 use std::collections::HashMap;
@@ -8,6 +10,7 @@ use std::path::Path;
 /// Manages baseline cost expectations
 pub struct BaselinesManager {
     config: BaselinesConfig,
+    tolerances: Option<TrendTolerances>,
 }
 
 /// Result of baseline comparison against snapshot
@@ -25,20 +28,62 @@ impl BaselinesManager {
         let content = fs::read_to_string(path.as_ref())
             .map_err(|e| format!("Failed to read baselines file: {}", e))?;
 
-        let config: BaselinesConfig = serde_json::from_str(&content)
+        let (config, _) = compat::read::<BaselinesConfig>(Artifact::Baselines, &content)
             .map_err(|e| format!("Failed to parse baselines JSON: {}", e))?;
 
-        Ok(Self { config })
+        Ok(Self::from_config(config))
     }
 
     /// Create from existing config
     pub fn from_config(config: BaselinesConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            tolerances: None,
+        }
+    }
+
+    /// Widen comparison bands with variance observed in `history`
+    ///
+    /// Has no effect unless the config sets `tolerance.mode: trend`.
+    pub fn with_trend_history(mut self, history: &crate::engines::trend::TrendHistory) -> Self {
+        if let Some(config) = &self.config.tolerance {
+            if config.mode == ToleranceMode::Trend {
+                self.tolerances = Some(TrendTolerances::from_history(config.clone(), history));
+            }
+        }
+        self
+    }
+
+    /// Load trend snapshots from the configured history directory
+    ///
+    /// Missing or unreadable history leaves the fixed bands in place.
+    pub fn with_configured_history(self) -> Self {
+        let Some(config) = self
+            .config
+            .tolerance
+            .as_ref()
+            .filter(|t| t.mode == ToleranceMode::Trend)
+        else {
+            return self;
+        };
+        let dir = config.history_dir();
+        match crate::engines::trend::SnapshotManager::new(&dir).load_history() {
+            Ok(history) => self.with_trend_history(&history),
+            Err(_) => self,
+        }
+    }
+
+    /// Effective comparison band for the baseline `name`
+    pub fn tolerance_for(&self, name: &str, baseline: &Baseline) -> Tolerance {
+        match &self.tolerances {
+            Some(tolerances) => tolerances.tolerance_for(name, baseline),
+            None => Tolerance::fixed(baseline),
+        }
     }
 
     /// Save baselines to JSON file
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
-        let json = serde_json::to_string_pretty(&self.config)
+        let json = compat::to_string_pretty(Artifact::Baselines, &self.config)
             .map_err(|e| format!("Failed to serialize baselines: {}", e))?;
 
         fs::write(path.as_ref(), json)
@@ -93,6 +138,10 @@ impl BaselinesManager {
             }
         }
 
+        if let Some(tolerance) = &self.config.tolerance {
+            errors.extend(tolerance.validate());
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -243,7 +292,8 @@ impl BaselinesManager {
 
         for (module_name, actual_cost) in module_costs {
             if let Some(baseline) = self.config.get_module_baseline(module_name) {
-                match baseline.check_variance(*actual_cost) {
+                let tolerance = self.tolerance_for(module_name, baseline);
+                match baseline.check_variance_within(*actual_cost, tolerance.percent) {
                     BaselineStatus::Within => {
                         within_count += 1;
                     }
@@ -258,7 +308,7 @@ impl BaselinesManager {
                             expected_cost: expected,
                             actual_cost: *actual_cost,
                             variance_percent,
-                            acceptable_variance: baseline.acceptable_variance_percent,
+                            acceptable_variance: tolerance.percent,
                             severity: calculate_severity(variance_percent),
                             regression_type: changes.map_or(RegressionType::IndirectCost, |c| {
                                 self.classify_module_regression(module_name, c)
@@ -279,7 +329,7 @@ impl BaselinesManager {
                             expected_cost: expected,
                             actual_cost: *actual_cost,
                             variance_percent,
-                            acceptable_variance: baseline.acceptable_variance_percent,
+                            acceptable_variance: tolerance.percent,
                             severity: "Info".to_string(),
                             regression_type: changes.map_or(RegressionType::IndirectCost, |c| {
                                 self.classify_module_regression(module_name, c)
@@ -312,8 +362,9 @@ impl BaselinesManager {
         changes: Option<&[crate::engines::detection::ResourceChange]>,
     ) -> Option<BaselineViolation> {
         let global = self.config.global.as_ref()?;
+        let tolerance = self.tolerance_for("global", global);
 
-        match global.check_variance(total_cost) {
+        match global.check_variance_within(total_cost, tolerance.percent) {
             BaselineStatus::Exceeded {
                 expected,
                 variance_percent,
@@ -324,7 +375,7 @@ impl BaselinesManager {
                 expected_cost: expected,
                 actual_cost: total_cost,
                 variance_percent,
-                acceptable_variance: global.acceptable_variance_percent,
+                acceptable_variance: tolerance.percent,
                 severity: calculate_severity(variance_percent),
                 regression_type: changes.map_or(RegressionType::IndirectCost, |c| {
                     self.classify_global_regression(c)
@@ -342,7 +393,7 @@ impl BaselinesManager {
                 expected_cost: expected,
                 actual_cost: total_cost,
                 variance_percent,
-                acceptable_variance: global.acceptable_variance_percent,
+                acceptable_variance: tolerance.percent,
                 severity: "Info".to_string(),
                 regression_type: changes.map_or(RegressionType::IndirectCost, |c| {
                     self.classify_global_regression(c)
@@ -354,6 +405,42 @@ impl BaselinesManager {
         }
     }
 
+    /// Compare costs against every baseline, reporting the band used
+    ///
+    /// Entries are the global baseline first, then modules by name. Modules
+    /// without a baseline are skipped.
+    pub fn drift_report(
+        &self,
+        total_cost: f64,
+        module_costs: &HashMap<String, f64>,
+    ) -> Vec<DriftEntry> {
+        let entry = |name: &str, baseline_type: &str, baseline: &Baseline, actual_cost: f64| {
+            let tolerance = self.tolerance_for(name, baseline);
+            DriftEntry {
+                name: name.to_string(),
+                baseline_type: baseline_type.to_string(),
+                expected_cost: baseline.expected_monthly_cost,
+                actual_cost,
+                tolerance,
+                status: baseline.check_variance_within(actual_cost, tolerance.percent),
+            }
+        };
+
+        let mut report = Vec::new();
+        if let Some(global) = &self.config.global {
+            report.push(entry("global", "global", global, total_cost));
+        }
+
+        let mut modules: Vec<_> = module_costs.iter().collect();
+        modules.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, cost) in modules {
+            if let Some(baseline) = self.config.get_module_baseline(name) {
+                report.push(entry(name, "module", baseline, *cost));
+            }
+        }
+        report
+    }
+
     /// Get stale baselines that need review
     pub fn get_stale_baselines(&self) -> Vec<(&str, &Baseline)> {
         self.config.get_stale_baselines()
@@ -373,6 +460,40 @@ impl BaselinesManager {
     pub fn update_global_baseline(&mut self, baseline: Baseline) {
         self.config.set_global(baseline);
     }
+
+    /// Take over the global and module baselines of `captured`
+    ///
+    /// Returns the names of the baselines written, `global` first and modules
+    /// sorted by name. Baselines not present in `captured` are left alone.
+    pub fn merge(&mut self, captured: &BaselinesConfig) -> Vec<String> {
+        let mut updated = Vec::new();
+        if let Some(global) = &captured.global {
+            self.update_global_baseline(global.clone());
+            updated.push("global".to_string());
+        }
+
+        let mut modules: Vec<_> = captured.modules.iter().collect();
+        modules.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, baseline) in modules {
+            self.update_module_baseline(name.clone(), baseline.clone());
+            updated.push(name.clone());
+        }
+        updated
+    }
+}
+
+/// One baseline in a drift report
+#[derive(Debug, Clone)]
+pub struct DriftEntry {
+    pub name: String,
+    pub baseline_type: String,
+    pub expected_cost: f64,
+    pub actual_cost: f64,
+
+    /// Band the cost was compared against
+    pub tolerance: Tolerance,
+
+    pub status: BaselineStatus,
 }
 
 /// Calculate severity based on variance percentage
@@ -575,6 +696,52 @@ mod tests {
         assert!(formatted.contains("High"));
     }
 
+    #[test]
+    fn test_trend_history_widens_noisy_module_band() {
+        use crate::engines::baselines::tolerance::ToleranceConfig;
+        use crate::engines::trend::{CostSnapshot, ModuleCost, TrendHistory};
+
+        let mut history = TrendHistory::new();
+        for (i, cost) in [800.0, 1200.0, 800.0, 1200.0].iter().enumerate() {
+            let mut snapshot = CostSnapshot::new(format!("s{}", i), *cost);
+            snapshot.modules.insert(
+                "module.vpc".to_string(),
+                ModuleCost {
+                    name: "module.vpc".to_string(),
+                    monthly_cost: *cost,
+                    resource_count: 3,
+                    change_from_previous: None,
+                    change_percent: None,
+                    services: vec![],
+                },
+            );
+            history.snapshots.push(snapshot);
+        }
+
+        let mut costs = HashMap::new();
+        costs.insert("module.vpc".to_string(), 1300.0); // 30% over
+
+        // Fixed bands ignore history
+        let fixed =
+            BaselinesManager::from_config(create_test_config()).with_trend_history(&history);
+        assert_eq!(fixed.compare_module_costs(&costs, None).total_violations, 1);
+
+        let mut config = create_test_config();
+        config.tolerance = Some(ToleranceConfig {
+            mode: ToleranceMode::Trend,
+            ..ToleranceConfig::default()
+        });
+        let manager = BaselinesManager::from_config(config).with_trend_history(&history);
+        let result = manager.compare_module_costs(&costs, None);
+        assert_eq!(result.total_violations, 0);
+        assert_eq!(result.within_baseline_count, 1);
+
+        let report = manager.drift_report(1300.0, &costs);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].tolerance.percent, 40.0);
+        assert_eq!(report[0].status, BaselineStatus::Within);
+    }
+
     #[test]
     fn test_filter_by_severity() {
         let config = create_test_config();
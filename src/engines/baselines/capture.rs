@@ -0,0 +1,134 @@
+// Baseline capture from scan results
+//
+// Turns the estimates of a scan into baseline entries: one global baseline
+// for the total monthly cost and one per Terraform module, keyed by module
+// path exactly as scan groups module costs when comparing against baselines.
+
+use super::baseline_types::{Baseline, BaselinesConfig};
+use crate::engines::shared::models::{CostEstimate, ResourceChange};
+use std::collections::{BTreeMap, HashMap};
+
+/// Name of the global baseline entry
+pub const GLOBAL_BASELINE: &str = "global";
+
+/// Ownership and tolerance recorded on captured baselines
+#[derive(Debug, Clone)]
+pub struct CaptureOptions {
+    pub owner: String,
+    pub justification: String,
+    pub variance_percent: f64,
+}
+
+impl Default for CaptureOptions {
+    fn default() -> Self {
+        Self {
+            owner: "deployment-automation".to_string(),
+            justification: "Captured from scan".to_string(),
+            variance_percent: 10.0,
+        }
+    }
+}
+
+/// Build global and per-module baselines from a scan
+///
+/// Resources outside any module only count towards the global baseline.
+pub fn capture_baselines(
+    changes: &[ResourceChange],
+    estimates: &[CostEstimate],
+    options: &CaptureOptions,
+) -> BaselinesConfig {
+    let costs: HashMap<&str, f64> = estimates
+        .iter()
+        .map(|e| (e.resource_id.as_str(), e.monthly_cost))
+        .collect();
+
+    let mut total = 0.0;
+    let mut modules: BTreeMap<&str, f64> = BTreeMap::new();
+    for change in changes {
+        let Some(cost) = costs.get(change.resource_id.as_str()) else {
+            continue;
+        };
+        total += cost;
+        if let Some(module) = &change.module_path {
+            *modules.entry(module.as_str()).or_insert(0.0) += cost;
+        }
+    }
+
+    let baseline = |name: &str, cost: f64| {
+        let mut baseline = Baseline::new(
+            name.to_string(),
+            round_cents(cost),
+            options.justification.clone(),
+            options.owner.clone(),
+        );
+        baseline.acceptable_variance_percent = options.variance_percent;
+        baseline
+    };
+
+    let mut config = BaselinesConfig::new();
+    config.set_global(baseline(GLOBAL_BASELINE, total));
+    for (module, cost) in modules {
+        config.add_module(module.to_string(), baseline(module, cost));
+    }
+    config
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+
+    fn change(id: &str, module: Option<&str>) -> ResourceChange {
+        let mut change = ResourceChange::new(
+            id.to_string(),
+            "aws_instance".to_string(),
+            ChangeAction::Create,
+        );
+        change.module_path = module.map(str::to_string);
+        change
+    }
+
+    fn estimate(id: &str, cost: f64) -> CostEstimate {
+        CostEstimate::new(id.to_string(), cost)
+    }
+
+    #[test]
+    fn test_capture_groups_costs_by_module() {
+        let changes = vec![
+            change("module.vpc.aws_nat_gateway.a", Some("module.vpc")),
+            change("module.vpc.aws_nat_gateway.b", Some("module.vpc")),
+            change("aws_instance.web", None),
+        ];
+        let estimates = vec![
+            estimate("module.vpc.aws_nat_gateway.a", 32.85),
+            estimate("module.vpc.aws_nat_gateway.b", 32.85),
+            estimate("aws_instance.web", 150.0),
+        ];
+
+        let config = capture_baselines(&changes, &estimates, &CaptureOptions::default());
+        let global = config.global.unwrap();
+        assert_eq!(global.name, GLOBAL_BASELINE);
+        assert_eq!(global.expected_monthly_cost, 215.7);
+        assert_eq!(config.modules.len(), 1);
+        assert_eq!(config.modules["module.vpc"].expected_monthly_cost, 65.7);
+    }
+
+    #[test]
+    fn test_capture_skips_unestimated_resources() {
+        let changes = vec![change("aws_instance.gone", Some("module.app"))];
+        let options = CaptureOptions {
+            variance_percent: 25.0,
+            ..CaptureOptions::default()
+        };
+
+        let config = capture_baselines(&changes, &[], &options);
+        let global = config.global.unwrap();
+        assert_eq!(global.expected_monthly_cost, 0.0);
+        assert_eq!(global.acceptable_variance_percent, 25.0);
+        assert!(config.modules.is_empty());
+    }
+}
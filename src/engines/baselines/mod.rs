@@ -1,5 +1,11 @@
+pub mod approval;
 pub mod baseline_types;
 pub mod baselines_manager;
+pub mod capture;
+pub mod tolerance;
 
+pub use approval::{bless, BaselineApproval, BlessError, BlessOutcome};
 pub use baseline_types::{Baseline, BaselineStatus, BaselineViolation, BaselinesConfig};
-pub use baselines_manager::{BaselineComparisonResult, BaselinesManager};
+pub use baselines_manager::{BaselineComparisonResult, BaselinesManager, DriftEntry};
+pub use capture::{capture_baselines, CaptureOptions};
+pub use tolerance::{Tolerance, ToleranceConfig, ToleranceMode, ToleranceSource, TrendTolerances};
@@ -0,0 +1,347 @@
+// Trend-aware baseline tolerances
+//
+// A fixed ±N% band flags naturally noisy modules (autoscaled compute, spot
+// fleets) on every scan. With `tolerance.mode: trend` the band of each
+// baseline widens to `sigma` standard deviations of its cost over recent
+// trend snapshots. Bands only ever widen: the configured variance stays the
+// floor, and `max_variance_percent` caps how far history can stretch them.
+
+use super::baseline_types::Baseline;
+use crate::engines::trend::TrendHistory;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Snapshot directory used when `history_dir` is not set
+pub const DEFAULT_HISTORY_DIR: &str = ".costpilot/snapshots";
+
+/// How comparison bands are sized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToleranceMode {
+    /// Use `acceptable_variance_percent` as is
+    #[default]
+    Fixed,
+
+    /// Widen bands with variance observed in trend history
+    Trend,
+}
+
+/// Tolerance settings in baselines.json
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToleranceConfig {
+    #[serde(default)]
+    pub mode: ToleranceMode,
+
+    /// Standard deviations of observed cost covered by the band
+    #[serde(default = "default_sigma")]
+    pub sigma: f64,
+
+    /// Snapshots needed before history widens a band
+    #[serde(default = "default_min_samples")]
+    pub min_samples: usize,
+
+    /// Most recent snapshots considered
+    #[serde(default = "default_window")]
+    pub window: usize,
+
+    /// Upper limit for history-derived variance
+    #[serde(default = "default_max_variance")]
+    pub max_variance_percent: f64,
+
+    /// Trend snapshot directory (default: .costpilot/snapshots)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub history_dir: Option<String>,
+}
+
+fn default_sigma() -> f64 {
+    2.0
+}
+
+fn default_min_samples() -> usize {
+    3
+}
+
+fn default_window() -> usize {
+    10
+}
+
+fn default_max_variance() -> f64 {
+    50.0
+}
+
+impl Default for ToleranceConfig {
+    fn default() -> Self {
+        Self {
+            mode: ToleranceMode::default(),
+            sigma: default_sigma(),
+            min_samples: default_min_samples(),
+            window: default_window(),
+            max_variance_percent: default_max_variance(),
+            history_dir: None,
+        }
+    }
+}
+
+impl ToleranceConfig {
+    /// Directory holding the trend snapshots to learn from
+    pub fn history_dir(&self) -> PathBuf {
+        PathBuf::from(self.history_dir.as_deref().unwrap_or(DEFAULT_HISTORY_DIR))
+    }
+
+    /// Validate settings, returning problems found
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.sigma <= 0.0 {
+            errors.push("Tolerance sigma must be positive".to_string());
+        }
+        if self.min_samples < 2 {
+            errors.push("Tolerance min_samples must be at least 2".to_string());
+        }
+        if self.window < self.min_samples {
+            errors.push("Tolerance window must be at least min_samples".to_string());
+        }
+        if !(0.0..=100.0).contains(&self.max_variance_percent) {
+            errors.push("Tolerance max_variance_percent must be 0-100%".to_string());
+        }
+        errors
+    }
+}
+
+/// Spread of a baseline's cost across trend snapshots
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VarianceStats {
+    pub samples: usize,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl VarianceStats {
+    fn from_costs(costs: &[f64]) -> Option<Self> {
+        if costs.is_empty() {
+            return None;
+        }
+        let samples = costs.len();
+        let mean = costs.iter().sum::<f64>() / samples as f64;
+        let variance = costs.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / samples as f64;
+        Some(Self {
+            samples,
+            mean,
+            std_dev: variance.sqrt(),
+        })
+    }
+
+    /// Standard deviation relative to the mean, in percent
+    pub fn coefficient_of_variation(&self) -> f64 {
+        if self.mean > 0.0 {
+            self.std_dev / self.mean * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Where an effective tolerance came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToleranceSource {
+    /// Configured `acceptable_variance_percent`
+    Fixed,
+
+    /// Widened from trend history
+    Trend {
+        samples: usize,
+        observed_percent: f64,
+    },
+}
+
+/// Effective comparison band of a baseline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub percent: f64,
+    pub source: ToleranceSource,
+}
+
+impl Tolerance {
+    pub fn fixed(baseline: &Baseline) -> Self {
+        Self {
+            percent: baseline.acceptable_variance_percent,
+            source: ToleranceSource::Fixed,
+        }
+    }
+}
+
+/// Observed variance per baseline, learned from trend history
+#[derive(Debug, Clone)]
+pub struct TrendTolerances {
+    config: ToleranceConfig,
+    stats: HashMap<String, VarianceStats>,
+}
+
+impl TrendTolerances {
+    /// Learn from the last `window` snapshots of `history`
+    ///
+    /// The total cost is recorded under `global`, modules under their
+    /// snapshot key (e.g. `module.vpc`).
+    pub fn from_history(config: ToleranceConfig, history: &TrendHistory) -> Self {
+        let start = history.snapshots.len().saturating_sub(config.window);
+        let recent = &history.snapshots[start..];
+
+        let mut costs: HashMap<String, Vec<f64>> = HashMap::new();
+        for snapshot in recent {
+            costs
+                .entry("global".to_string())
+                .or_default()
+                .push(snapshot.total_monthly_cost);
+            for (name, module) in &snapshot.modules {
+                costs
+                    .entry(name.clone())
+                    .or_default()
+                    .push(module.monthly_cost);
+            }
+        }
+
+        let stats = costs
+            .into_iter()
+            .filter_map(|(name, costs)| VarianceStats::from_costs(&costs).map(|s| (name, s)))
+            .collect();
+        Self { config, stats }
+    }
+
+    /// Observed spread of `name`, if it appears in history
+    pub fn stats(&self, name: &str) -> Option<&VarianceStats> {
+        self.stats.get(name)
+    }
+
+    /// Band for `baseline`, widened when history shows it is noisy
+    pub fn tolerance_for(&self, name: &str, baseline: &Baseline) -> Tolerance {
+        let fixed = Tolerance::fixed(baseline);
+        if self.config.mode != ToleranceMode::Trend {
+            return fixed;
+        }
+        let Some(stats) = self
+            .stats(name)
+            .filter(|s| s.samples >= self.config.min_samples)
+        else {
+            return fixed;
+        };
+
+        let observed = stats.coefficient_of_variation();
+        let widened = (observed * self.config.sigma).min(self.config.max_variance_percent);
+        if widened <= fixed.percent {
+            return fixed;
+        }
+        Tolerance {
+            percent: widened,
+            source: ToleranceSource::Trend {
+                samples: stats.samples,
+                observed_percent: observed,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::trend::{CostSnapshot, ModuleCost};
+
+    fn history(asg_costs: &[f64]) -> TrendHistory {
+        let mut history = TrendHistory::new();
+        for (i, cost) in asg_costs.iter().enumerate() {
+            let mut snapshot = CostSnapshot::new(format!("s{}", i), 1000.0 + cost);
+            snapshot.modules.insert(
+                "module.asg".to_string(),
+                ModuleCost {
+                    name: "module.asg".to_string(),
+                    monthly_cost: *cost,
+                    resource_count: 1,
+                    change_from_previous: None,
+                    change_percent: None,
+                    services: vec![],
+                },
+            );
+            history.snapshots.push(snapshot);
+        }
+        history
+    }
+
+    fn baseline(variance: f64) -> Baseline {
+        let mut baseline = Baseline::new(
+            "module.asg".to_string(),
+            400.0,
+            "Autoscaled workers".to_string(),
+            "platform".to_string(),
+        );
+        baseline.acceptable_variance_percent = variance;
+        baseline
+    }
+
+    fn trend() -> ToleranceConfig {
+        ToleranceConfig {
+            mode: ToleranceMode::Trend,
+            ..ToleranceConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_noisy_module_widens_band() {
+        // mean 400, std dev 60 → 15% observed, 30% at 2 sigma
+        let tolerances = TrendTolerances::from_history(trend(), &history(&[340.0, 460.0]));
+        let stats = tolerances.stats("module.asg").unwrap();
+        assert_eq!(stats.mean, 400.0);
+        assert_eq!(stats.coefficient_of_variation(), 15.0);
+
+        // Two samples are below min_samples
+        assert_eq!(
+            tolerances.tolerance_for("module.asg", &baseline(10.0)),
+            Tolerance::fixed(&baseline(10.0))
+        );
+
+        let tolerances =
+            TrendTolerances::from_history(trend(), &history(&[340.0, 460.0, 340.0, 460.0]));
+        let tolerance = tolerances.tolerance_for("module.asg", &baseline(10.0));
+        assert!((tolerance.percent - 30.0).abs() < 1e-9);
+        assert!(matches!(
+            tolerance.source,
+            ToleranceSource::Trend { samples: 4, .. }
+        ));
+    }
+
+    #[test]
+    fn test_band_never_narrows_and_is_capped() {
+        let steady = TrendTolerances::from_history(trend(), &history(&[400.0, 401.0, 399.0]));
+        assert_eq!(
+            steady.tolerance_for("module.asg", &baseline(10.0)).source,
+            ToleranceSource::Fixed
+        );
+
+        let wild = TrendTolerances::from_history(trend(), &history(&[100.0, 700.0, 100.0, 700.0]));
+        assert_eq!(
+            wild.tolerance_for("module.asg", &baseline(10.0)).percent,
+            50.0
+        );
+
+        let fixed = TrendTolerances::from_history(
+            ToleranceConfig::default(),
+            &history(&[100.0, 700.0, 100.0]),
+        );
+        assert_eq!(
+            fixed.tolerance_for("module.asg", &baseline(10.0)).percent,
+            10.0
+        );
+    }
+
+    #[test]
+    fn test_window_uses_most_recent_snapshots() {
+        let config = ToleranceConfig {
+            window: 3,
+            ..trend()
+        };
+        let tolerances =
+            TrendTolerances::from_history(config, &history(&[100.0, 700.0, 400.0, 400.0, 400.0]));
+        let stats = tolerances.stats("module.asg").unwrap();
+        assert_eq!(stats.samples, 3);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(tolerances.stats("global").unwrap().mean, 1400.0);
+    }
+}
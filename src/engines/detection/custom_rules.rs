@@ -0,0 +1,456 @@
+// Custom detection rules from costpilot.yaml
+//
+// Organizations encode their own conventions (mandatory tags, banned instance
+// families) as `detection.custom_rules`. Each rule matches resource types,
+// checks attribute conditions against the planned configuration, and reports
+// a detection alongside the built-in ones.
+
+use crate::engines::policy::parser::dsl::{ConditionValue, Operator, RuleSeverity};
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use crate::engines::shared::models::{
+    ChangeAction, Detection, RegressionType, ResourceChange, Severity,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Detection settings (`detection` in costpilot.yaml)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetectionConfig {
+    #[serde(default)]
+    pub custom_rules: Vec<CustomDetectionRule>,
+}
+
+/// A user-defined detection rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomDetectionRule {
+    /// Reported as the detection's rule ID
+    pub id: String,
+
+    /// Resource type to match; `*` matches any characters (e.g. `aws_*`)
+    pub resource_type: String,
+
+    /// All conditions must hold for the rule to fire
+    #[serde(default)]
+    pub conditions: Vec<AttributeCondition>,
+
+    #[serde(default = "default_severity")]
+    pub severity: RuleSeverity,
+
+    /// Detection message; `{resource_id}` and `{resource_type}` are substituted
+    pub message: String,
+
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// Check on one attribute of the planned configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributeCondition {
+    /// Dotted path into the configuration (e.g. `tags.CostCenter`); blocks
+    /// given as lists resolve to their first entry unless indexed
+    pub attribute: String,
+
+    /// Require the attribute to be set (`true`) or absent (`false`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exists: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operator: Option<Operator>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<ConditionValue>,
+
+    #[serde(default)]
+    pub negate: bool,
+}
+
+fn default_severity() -> RuleSeverity {
+    RuleSeverity::Medium
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl DetectionConfig {
+    /// Load `detection` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `detection` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `detection` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let config: Self = match root.get("detection").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid detection section: {}", e))
+            })?,
+            None => Self::default(),
+        };
+
+        if let Some(problem) = config.validate().first() {
+            return Err(CostPilotError::validation_error(problem.clone())
+                .with_hint("Check detection.custom_rules in costpilot.yaml"));
+        }
+
+        Ok(config)
+    }
+
+    /// Describe invalid rules; empty if every rule is usable
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (index, rule) in self.custom_rules.iter().enumerate() {
+            let name = if rule.id.is_empty() {
+                format!("custom rule #{}", index + 1)
+            } else {
+                format!("custom rule '{}'", rule.id)
+            };
+            if rule.id.is_empty() {
+                problems.push(format!("{}: id must not be empty", name));
+            }
+            if self.custom_rules[..index].iter().any(|r| r.id == rule.id) {
+                problems.push(format!("{}: duplicate id", name));
+            }
+            if rule.resource_type.is_empty() {
+                problems.push(format!("{}: resource_type must not be empty", name));
+            }
+            if rule.message.is_empty() {
+                problems.push(format!("{}: message must not be empty", name));
+            }
+            for condition in &rule.conditions {
+                if let Err(problem) = condition.validate() {
+                    problems.push(format!("{}: {}", name, problem));
+                }
+            }
+        }
+        problems
+    }
+}
+
+impl AttributeCondition {
+    fn validate(&self) -> std::result::Result<(), String> {
+        let attribute = &self.attribute;
+        if attribute.is_empty() {
+            return Err("condition attribute must not be empty".to_string());
+        }
+        match (self.exists, self.operator, &self.value) {
+            (Some(_), None, None) => Ok(()),
+            (Some(_), _, _) => Err(format!(
+                "condition on '{}' uses exists together with operator/value",
+                attribute
+            )),
+            (None, Some(_), None) | (None, None, Some(_)) | (None, None, None) => Err(format!(
+                "condition on '{}' needs exists, or operator and value",
+                attribute
+            )),
+            (None, Some(operator), Some(value)) => match (operator, value) {
+                (Operator::Matches, ConditionValue::String(pattern)) => regex::Regex::new(pattern)
+                    .map(|_| ())
+                    .map_err(|e| format!("invalid pattern for '{}': {}", attribute, e)),
+                (Operator::In | Operator::NotIn, ConditionValue::List(_)) => Ok(()),
+                (Operator::In | Operator::NotIn, _) | (_, ConditionValue::List(_)) => Err(format!(
+                    "condition on '{}': in/not_in require a list value",
+                    attribute
+                )),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    fn holds(&self, config: &Value) -> bool {
+        let actual = lookup(config, &self.attribute).filter(|v| !v.is_null());
+        let result = match (self.exists, self.operator, &self.value) {
+            (Some(exists), _, _) => actual.is_some() == exists,
+            (None, Some(operator), Some(expected)) => {
+                actual.is_some_and(|actual| compare(actual, operator, expected))
+            }
+            _ => false,
+        };
+        result != self.negate
+    }
+}
+
+impl CustomDetectionRule {
+    /// Whether the rule fires for `change`
+    ///
+    /// Only resources being created, updated or replaced are checked.
+    pub fn matches(&self, change: &ResourceChange) -> bool {
+        if !self.enabled
+            || matches!(change.action, ChangeAction::Delete | ChangeAction::NoOp)
+            || !wildcard_match(&self.resource_type, &change.resource_type)
+        {
+            return false;
+        }
+        let Some(config) = &change.new_config else {
+            return false;
+        };
+        self.conditions.iter().all(|c| c.holds(config))
+    }
+
+    /// Detection reported when the rule fires for `change`
+    pub fn to_detection(&self, change: &ResourceChange) -> Detection {
+        let severity = match self.severity {
+            RuleSeverity::Critical => Severity::Critical,
+            RuleSeverity::High => Severity::High,
+            RuleSeverity::Medium => Severity::Medium,
+            RuleSeverity::Low | RuleSeverity::Info => Severity::Low,
+        };
+        Detection {
+            rule_id: self.id.clone(),
+            severity_score: match severity {
+                Severity::Critical => 90,
+                Severity::High => 70,
+                Severity::Medium => 45,
+                Severity::Low => 20,
+            },
+            severity,
+            resource_id: change.resource_id.clone(),
+            regression_type: RegressionType::Configuration,
+            message: self
+                .message
+                .replace("{resource_id}", &change.resource_id)
+                .replace("{resource_type}", &change.resource_type),
+            fix_snippet: None,
+            estimated_cost: None,
+        }
+    }
+}
+
+/// Evaluate enabled rules against every change
+pub fn evaluate_custom_rules(
+    rules: &[CustomDetectionRule],
+    changes: &[ResourceChange],
+) -> Vec<Detection> {
+    changes
+        .iter()
+        .flat_map(|change| {
+            rules
+                .iter()
+                .filter(|rule| rule.matches(change))
+                .map(|rule| rule.to_detection(change))
+        })
+        .collect()
+}
+
+fn lookup<'a>(config: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(config, |value, segment| match value {
+            Value::Array(items) => match segment.parse::<usize>() {
+                Ok(index) => items.get(index),
+                Err(_) => items.first().and_then(|item| item.get(segment)),
+            },
+            _ => value.get(segment),
+        })
+}
+
+fn compare(actual: &Value, operator: Operator, expected: &ConditionValue) -> bool {
+    let text = match actual {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    match expected {
+        ConditionValue::Number(expected) => {
+            let Some(actual) = actual.as_f64().or_else(|| text.parse().ok()) else {
+                return false;
+            };
+            match operator {
+                Operator::Equals => (actual - expected).abs() < f64::EPSILON,
+                Operator::NotEquals => (actual - expected).abs() >= f64::EPSILON,
+                Operator::GreaterThan => actual > *expected,
+                Operator::GreaterThanOrEqual => actual >= *expected,
+                Operator::LessThan => actual < *expected,
+                Operator::LessThanOrEqual => actual <= *expected,
+                _ => false,
+            }
+        }
+        ConditionValue::Boolean(expected) => match operator {
+            Operator::Equals => actual.as_bool() == Some(*expected),
+            Operator::NotEquals => actual.as_bool() != Some(*expected),
+            _ => false,
+        },
+        ConditionValue::String(expected) => match operator {
+            Operator::Equals => text == *expected,
+            Operator::NotEquals => text != *expected,
+            Operator::Contains => text.contains(expected.as_str()),
+            Operator::StartsWith => text.starts_with(expected.as_str()),
+            Operator::EndsWith => text.ends_with(expected.as_str()),
+            Operator::Matches => regex::Regex::new(expected)
+                .map(|re| re.is_match(&text))
+                .unwrap_or(false),
+            _ => false,
+        },
+        ConditionValue::List(items) => match operator {
+            Operator::In => items.contains(&text),
+            Operator::NotIn => !items.contains(&text),
+            _ => false,
+        },
+    }
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters
+pub(crate) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CONFIG: &str = r#"
+detection:
+  custom_rules:
+    - id: REQUIRE_COST_CENTER_TAG
+      resource_type: "aws_*"
+      conditions:
+        - attribute: tags.CostCenter
+          exists: false
+      severity: low
+      message: "{resource_id} has no CostCenter tag"
+    - id: BANNED_INSTANCE_FAMILY
+      resource_type: aws_instance
+      conditions:
+        - attribute: instance_type
+          operator: matches
+          value: "^(p4d|x2iedn)\\."
+      severity: high
+      message: "GPU/memory instance families need FinOps approval"
+"#;
+
+    fn change(resource_type: &str, config: Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(format!("{}.main", resource_type))
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    #[test]
+    fn test_rules_from_yaml_produce_detections() {
+        let config = DetectionConfig::from_yaml(CONFIG).unwrap();
+        assert_eq!(config.custom_rules.len(), 2);
+
+        let changes = vec![
+            change(
+                "aws_instance",
+                json!({"instance_type": "p4d.24xlarge", "tags": {"CostCenter": "ml"}}),
+            ),
+            change("aws_s3_bucket", json!({"tags": {"Team": "data"}})),
+            change("google_storage_bucket", json!({})),
+        ];
+        let detections = evaluate_custom_rules(&config.custom_rules, &changes);
+
+        assert_eq!(detections.len(), 2);
+        assert_eq!(detections[0].rule_id, "BANNED_INSTANCE_FAMILY");
+        assert_eq!(detections[0].severity, Severity::High);
+        assert_eq!(detections[1].rule_id, "REQUIRE_COST_CENTER_TAG");
+        assert_eq!(
+            detections[1].message,
+            "aws_s3_bucket.main has no CostCenter tag"
+        );
+        assert_eq!(detections[1].severity, Severity::Low);
+    }
+
+    #[test]
+    fn test_conditions_on_nested_blocks_and_numbers() {
+        let rule = CustomDetectionRule {
+            id: "LARGE_GP2_ROOT".to_string(),
+            resource_type: "aws_instance".to_string(),
+            conditions: vec![
+                AttributeCondition {
+                    attribute: "root_block_device.volume_type".to_string(),
+                    exists: None,
+                    operator: Some(Operator::In),
+                    value: Some(ConditionValue::List(vec!["gp2".to_string()])),
+                    negate: false,
+                },
+                AttributeCondition {
+                    attribute: "root_block_device.0.volume_size".to_string(),
+                    exists: None,
+                    operator: Some(Operator::GreaterThan),
+                    value: Some(ConditionValue::Number(100.0)),
+                    negate: false,
+                },
+            ],
+            severity: RuleSeverity::Medium,
+            message: "Large gp2 root volume".to_string(),
+            enabled: true,
+        };
+
+        let large = change(
+            "aws_instance",
+            json!({"root_block_device": [{"volume_type": "gp2", "volume_size": 500}]}),
+        );
+        let small = change(
+            "aws_instance",
+            json!({"root_block_device": [{"volume_type": "gp2", "volume_size": 8}]}),
+        );
+        assert!(rule.matches(&large));
+        assert!(!rule.matches(&small));
+
+        let mut deleted = large.clone();
+        deleted.action = ChangeAction::Delete;
+        assert!(!rule.matches(&deleted));
+    }
+
+    #[test]
+    fn test_invalid_rules_rejected() {
+        let yaml = r#"
+detection:
+  custom_rules:
+    - id: DUP
+      resource_type: aws_instance
+      message: "first"
+    - id: DUP
+      resource_type: aws_instance
+      message: "second"
+      conditions:
+        - attribute: instance_type
+          operator: matches
+          value: "(unclosed"
+        - attribute: tags
+"#;
+        let config: DetectionConfig = serde_yaml::from_value(
+            serde_yaml::from_str::<serde_yaml::Value>(yaml).unwrap()["detection"].clone(),
+        )
+        .unwrap();
+        let problems = config.validate();
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("duplicate id"));
+        assert!(problems[1].contains("invalid pattern"));
+        assert!(problems[2].contains("needs exists, or operator and value"));
+
+        assert!(DetectionConfig::from_yaml(yaml).is_err());
+        assert!(wildcard_match("aws_*_gateway", "aws_nat_gateway"));
+        assert!(!wildcard_match("aws_*", "azurerm_vm"));
+    }
+}
@@ -1,13 +1,17 @@
 // Detection engine - main orchestrator
 
 use crate::engines::detection::classifier::RegressionClassifier;
+use crate::engines::detection::custom_rules::{evaluate_custom_rules, CustomDetectionRule};
 use crate::engines::detection::severity::{calculate_severity_score, score_to_severity};
 use crate::engines::detection::terraform::{convert_to_resource_changes, parse_terraform_plan};
 use crate::engines::explain::anti_patterns;
+use crate::engines::prediction::FreeTierConfig;
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
 use crate::engines::shared::models::{
     CostEstimate, Detection, RegressionType, ResourceChange, Severity,
 };
+use crate::engines::shared::result_cache::{CacheKind, ResultCache};
+use crate::security::lint_iam_policies;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -17,6 +21,10 @@ pub struct DetectionEngine {
     verbose: bool,
     /// Enable advanced optimization detection
     enable_advanced_detection: bool,
+    /// User-defined rules from costpilot.yaml
+    custom_rules: Vec<CustomDetectionRule>,
+    /// Free tier rules, when the project assumes free tier usage
+    free_tier: Option<FreeTierConfig>,
 }
 
 impl DetectionEngine {
@@ -25,6 +33,8 @@ impl DetectionEngine {
         Self {
             verbose: false,
             enable_advanced_detection: true, // Enable by default
+            custom_rules: Vec::new(),
+            free_tier: None,
         }
     }
 
@@ -40,6 +50,18 @@ impl DetectionEngine {
         self
     }
 
+    /// Evaluate user-defined rules alongside the built-in ones
+    pub fn with_custom_rules(mut self, rules: Vec<CustomDetectionRule>) -> Self {
+        self.custom_rules = rules;
+        self
+    }
+
+    /// Flag settings that take resources out of the AWS free tier
+    pub fn with_free_tier(mut self, free_tier: FreeTierConfig) -> Self {
+        self.free_tier = Some(free_tier);
+        self
+    }
+
     /// Detect cost issues from Terraform plan JSON file
     pub fn detect_from_terraform_plan(&self, plan_path: &Path) -> Result<Vec<ResourceChange>> {
         // Read the plan file
@@ -195,6 +217,52 @@ impl DetectionEngine {
         changes: &[ResourceChange],
         cost_estimates: &[(String, f64, f64)], // (resource_id, cost, confidence)
     ) -> Result<Vec<Detection>> {
+        let mut detections = self.detect_batch(changes, cost_estimates);
+        detections.extend(self.detect_per_resource(changes, cost_estimates));
+        detections.extend(lint_iam_policies(changes));
+        detections.extend(evaluate_custom_rules(&self.custom_rules, changes));
+        if let Some(free_tier) = &self.free_tier {
+            detections.extend(free_tier.detect(changes));
+        }
+        Ok(detections)
+    }
+
+    /// Analyze changes, reusing cached per-resource detections
+    ///
+    /// Batch patterns span several resources and custom rules can change
+    /// between runs, so both are always recomputed, as is the cheap IAM policy
+    /// lint; per-resource detections are keyed by config plus cost estimate.
+    pub fn analyze_changes_cached(
+        &self,
+        changes: &[ResourceChange],
+        cost_estimates: &[(String, f64, f64)],
+        cache: &ResultCache,
+    ) -> Result<Vec<Detection>> {
+        let mut detections = self.detect_batch(changes, cost_estimates);
+        detections.extend(cache.resolve(
+            CacheKind::Detection,
+            changes,
+            |change| {
+                let (cost, confidence) = Self::cost_for(change, cost_estimates);
+                format!("{}:{}", cost, confidence)
+            },
+            |misses| Ok(self.detect_per_resource(misses, cost_estimates)),
+            |detection| &detection.resource_id,
+        )?);
+        detections.extend(lint_iam_policies(changes));
+        detections.extend(evaluate_custom_rules(&self.custom_rules, changes));
+        if let Some(free_tier) = &self.free_tier {
+            detections.extend(free_tier.detect(changes));
+        }
+        Ok(detections)
+    }
+
+    /// Cross-resource anti-patterns (advanced detection)
+    fn detect_batch(
+        &self,
+        changes: &[ResourceChange],
+        cost_estimates: &[(String, f64, f64)],
+    ) -> Vec<Detection> {
         let mut detections = Vec::new();
 
         // Build estimates map for batch detection
@@ -257,14 +325,20 @@ impl DetectionEngine {
             }
         }
 
-        // Original per-resource detection for baseline anti-patterns
+        detections
+    }
+
+    /// Original per-resource detection for baseline anti-patterns
+    fn detect_per_resource(
+        &self,
+        changes: &[ResourceChange],
+        cost_estimates: &[(String, f64, f64)],
+    ) -> Vec<Detection> {
+        let mut detections = Vec::new();
+
         for change in changes {
             // Find cost estimate for this resource
-            let (cost_delta, confidence) = cost_estimates
-                .iter()
-                .find(|(id, _, _)| id == &change.resource_id)
-                .map(|(_, cost, conf)| (*cost, *conf))
-                .unwrap_or((0.0, 0.5));
+            let (cost_delta, confidence) = Self::cost_for(change, cost_estimates);
 
             // Classify the regression
             let regression_type = RegressionClassifier::classify(change);
@@ -286,7 +360,15 @@ impl DetectionEngine {
             }
         }
 
-        Ok(detections)
+        detections
+    }
+
+    fn cost_for(change: &ResourceChange, cost_estimates: &[(String, f64, f64)]) -> (f64, f64) {
+        cost_estimates
+            .iter()
+            .find(|(id, _, _)| id == &change.resource_id)
+            .map(|(_, cost, conf)| (*cost, *conf))
+            .unwrap_or((0.0, 0.5))
     }
 
     /// Detect specific cost anti-patterns
@@ -438,4 +520,38 @@ mod tests {
         assert_eq!(changes.len(), 1);
         assert_eq!(changes[0].resource_type, "aws_instance");
     }
+
+    #[test]
+    fn test_custom_rules_run_alongside_built_ins() {
+        let config = crate::engines::detection::DetectionConfig::from_yaml(
+            r#"
+detection:
+  custom_rules:
+    - id: BANNED_T2
+      resource_type: aws_instance
+      conditions:
+        - attribute: instance_type
+          operator: starts_with
+          value: "t2."
+      message: "t2 instances are retired; use t3"
+"#,
+        )
+        .unwrap();
+        let engine = DetectionEngine::new().with_custom_rules(config.custom_rules);
+
+        let change = ResourceChange::builder()
+            .resource_id("aws_instance.legacy")
+            .resource_type("aws_instance")
+            .action(crate::engines::shared::models::ChangeAction::Create)
+            .new_config(serde_json::json!({"instance_type": "t2.micro"}))
+            .build();
+        let detections = engine.detect(&[change]).unwrap();
+
+        let custom: Vec<_> = detections
+            .iter()
+            .filter(|d| d.rule_id == "BANNED_T2")
+            .collect();
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[0].severity, Severity::Medium);
+    }
 }
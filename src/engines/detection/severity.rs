@@ -1,8 +1,26 @@
 // Severity calculation
 
-use crate::engines::shared::models::{RegressionType, ResourceChange, Severity};
+use crate::engines::explain::prediction_explainer::size_capacity;
+use crate::engines::shared::models::{ChangeAction, RegressionType, ResourceChange, Severity};
+use serde_json::Value;
+
+/// Attributes compared along the instance size ladder
+const SIZE_ATTRIBUTES: &[&str] = &["instance_type", "instance_class", "node_type"];
+
+/// Numeric attributes cost scales linearly with
+const SCALE_ATTRIBUTES: &[&str] = &[
+    "allocated_storage",
+    "size",
+    "desired_count",
+    "desired_capacity",
+    "num_cache_nodes",
+];
 
 /// Calculate severity score (0-100) for a resource change
+///
+/// `cost_delta` is the predicted monthly cost of the resource after the
+/// change; the magnitude component scores what the change adds, see
+/// [`predicted_delta`].
 pub fn calculate_severity_score(
     change: &ResourceChange,
     cost_delta: f64,
@@ -12,7 +30,7 @@ pub fn calculate_severity_score(
     let mut score = 0.0;
 
     // Magnitude component (45%)
-    let magnitude_score = calculate_magnitude_score(cost_delta);
+    let magnitude_score = calculate_magnitude_score(predicted_delta(change, cost_delta));
     score += magnitude_score * 0.45;
 
     // Confidence component (25%)
@@ -30,11 +48,87 @@ pub fn calculate_severity_score(
     score.clamp(0.0, 100.0) as u32
 }
 
+/// Predicted monthly cost delta of a change whose new cost is `estimated_cost`
+///
+/// Creates add the whole estimate and deletes remove it. Updates and
+/// replaces recover the previous cost by comparing sizing attributes of the
+/// old and new configuration, so `m5.large` → `m5.24xlarge` adds 47/48 of the
+/// estimate while a tag-only update adds nothing. When no attribute can be
+/// compared the whole estimate counts, as for a create.
+pub fn predicted_delta(change: &ResourceChange, estimated_cost: f64) -> f64 {
+    if let Some(impact) = &change.cost_impact {
+        return impact.delta;
+    }
+    match change.action {
+        ChangeAction::Create => estimated_cost,
+        ChangeAction::Delete => -estimated_cost.abs(),
+        ChangeAction::NoOp => 0.0,
+        ChangeAction::Update | ChangeAction::Replace => {
+            match (&change.old_config, &change.new_config) {
+                (Some(old), Some(new)) => match scale_factor(old, new) {
+                    Some(factor) => estimated_cost - estimated_cost / factor,
+                    None => estimated_cost,
+                },
+                _ => estimated_cost,
+            }
+        }
+    }
+}
+
+/// How many times more the new configuration costs than the old one
+///
+/// `None` when no sizing attribute is present in both, or when one of them
+/// changed in a way the ladder cannot rank. Sizes only compare within an
+/// instance family: `m5.large` → `r5.large` changes the price per size.
+fn scale_factor(old: &Value, new: &Value) -> Option<f64> {
+    let mut factor = None;
+
+    for attribute in SIZE_ATTRIBUTES {
+        let (Some(before), Some(after)) = (
+            old.get(attribute).and_then(Value::as_str),
+            new.get(attribute).and_then(Value::as_str),
+        ) else {
+            continue;
+        };
+        let ratio = if before == after {
+            1.0
+        } else if instance_family(before)? != instance_family(after)? {
+            return None;
+        } else {
+            size_capacity(after)? / size_capacity(before)?
+        };
+        factor = Some(factor.unwrap_or(1.0) * ratio);
+    }
+
+    for attribute in SCALE_ATTRIBUTES {
+        let (Some(before), Some(after)) = (
+            old.get(attribute).and_then(Value::as_f64),
+            new.get(attribute).and_then(Value::as_f64),
+        ) else {
+            continue;
+        };
+        if before <= 0.0 || after <= 0.0 {
+            return None;
+        }
+        factor = Some(factor.unwrap_or(1.0) * after / before);
+    }
+
+    factor
+}
+
+/// Family of an instance type or class: `m5` for `m5.large`, `db.r5` for
+/// `db.r5.xlarge`
+fn instance_family(instance: &str) -> Option<&str> {
+    instance.rsplit_once('.').map(|(family, _)| family)
+}
+
 /// Calculate magnitude score based on cost delta
+///
+/// Savings score half as much as an increase of the same size.
 fn calculate_magnitude_score(cost_delta: f64) -> f64 {
     let abs_delta = cost_delta.abs();
 
-    if abs_delta < 10.0 {
+    let score = if abs_delta < 10.0 {
         10.0
     } else if abs_delta < 50.0 {
         30.0
@@ -46,6 +140,12 @@ fn calculate_magnitude_score(cost_delta: f64) -> f64 {
         85.0
     } else {
         100.0
+    };
+
+    if cost_delta < 0.0 {
+        score / 2.0
+    } else {
+        score
     }
 }
 
@@ -112,7 +212,7 @@ pub fn score_to_severity(score: u32) -> Severity {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engines::shared::models::ChangeAction;
+    use serde_json::json;
     use std::collections::HashMap;
 
     #[test]
@@ -143,4 +243,70 @@ mod tests {
         assert_eq!(score_to_severity(60), Severity::High);
         assert_eq!(score_to_severity(90), Severity::Critical);
     }
+
+    fn instance(action: ChangeAction, before: Option<&str>, after: &str) -> ResourceChange {
+        let mut change = ResourceChange::new(
+            "module.app.aws_instance.web".to_string(),
+            "aws_instance".to_string(),
+            action,
+        );
+        change.module_path = Some("module.app".to_string());
+        change.old_config = before.map(|t| json!({ "instance_type": t }));
+        change.new_config = Some(json!({ "instance_type": after }));
+        change
+    }
+
+    #[test]
+    fn test_predicted_delta_follows_action_and_size() {
+        let upsize = instance(ChangeAction::Update, Some("m5.large"), "m5.24xlarge");
+        assert!((predicted_delta(&upsize, 3360.0) - 3290.0).abs() < 1e-9);
+
+        let retag = instance(ChangeAction::Update, Some("m5.large"), "m5.large");
+        assert_eq!(predicted_delta(&retag, 70.0), 0.0);
+
+        let downsize = instance(ChangeAction::Replace, Some("m5.xlarge"), "m5.large");
+        assert_eq!(predicted_delta(&downsize, 70.0), -70.0);
+
+        let unknown = instance(ChangeAction::Update, Some("m5.large"), "m5.metal");
+        assert_eq!(predicted_delta(&unknown, 900.0), 900.0);
+
+        let create = instance(ChangeAction::Create, None, "t3.micro");
+        assert_eq!(predicted_delta(&create, 7.6), 7.6);
+    }
+
+    #[test]
+    fn test_family_change_is_not_ranked_by_size() {
+        // Same size, different family: the whole estimate counts
+        let memory = instance(ChangeAction::Update, Some("m5.large"), "r5.large");
+        assert_eq!(predicted_delta(&memory, 91.98), 91.98);
+
+        let burstable = instance(ChangeAction::Replace, Some("t3.large"), "m5.large");
+        assert_eq!(predicted_delta(&burstable, 70.08), 70.08);
+
+        let mut database = instance(ChangeAction::Update, None, "db.r5.large");
+        database.old_config = Some(json!({ "instance_class": "db.m5.large" }));
+        database.new_config = Some(json!({ "instance_class": "db.r5.large" }));
+        assert_eq!(predicted_delta(&database, 180.0), 180.0);
+
+        // Within a family the size ladder still applies
+        database.new_config = Some(json!({ "instance_class": "db.m5.xlarge" }));
+        assert!((predicted_delta(&database, 250.0) - 125.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_upsize_outscores_small_create() {
+        let upsize = instance(ChangeAction::Update, Some("m5.large"), "m5.24xlarge");
+        let create = instance(ChangeAction::Create, None, "t3.micro");
+        let retag = instance(ChangeAction::Update, Some("m5.24xlarge"), "m5.24xlarge");
+
+        let upsize_score = calculate_severity_score(&upsize, 3360.0, &RegressionType::Scaling, 0.8);
+        let create_score =
+            calculate_severity_score(&create, 7.6, &RegressionType::Provisioning, 0.8);
+        let retag_score =
+            calculate_severity_score(&retag, 3360.0, &RegressionType::Configuration, 0.8);
+
+        assert!(upsize_score > create_score);
+        assert!(upsize_score > retag_score);
+        assert_eq!(retag_score, create_score);
+    }
 }
@@ -0,0 +1,332 @@
+// Terraform expression evaluation for raw HCL
+//
+// Covers what decides whether and how often a resource exists: literals,
+// `var.*`, `terraform.workspace`, comparisons, boolean operators and the
+// conditional operator (`var.enable_nat ? 1 : 0`). Anything else, or a
+// reference to an unknown variable, leaves the expression unevaluated.
+
+use serde_json::{json, Map, Value};
+
+/// Values an expression can refer to
+#[derive(Debug, Clone, Copy)]
+pub struct EvalContext<'a> {
+    pub variables: &'a Map<String, Value>,
+    pub workspace: &'a str,
+}
+
+/// Evaluate an expression, `None` if it uses anything unsupported or unknown
+pub fn evaluate(expr: &str, ctx: &EvalContext) -> Option<Value> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let ast = parser.conditional()?;
+    if parser.pos != parser.tokens.len() {
+        return None;
+    }
+    ast.eval(ctx)
+}
+
+/// Evaluate the `${...}` interpolations of a string attribute
+///
+/// A string that is a single interpolation takes the value and type of its
+/// expression; interpolations inside a longer string are replaced when they
+/// evaluate to a string, number or bool. `None` if nothing was replaced.
+pub fn interpolate(s: &str, ctx: &EvalContext) -> Option<Value> {
+    if let Some(inner) = s.strip_prefix("${").and_then(|r| r.strip_suffix('}')) {
+        if !inner.contains("${") {
+            if let Some(value) = evaluate(inner, ctx) {
+                return Some(value);
+            }
+        }
+    }
+
+    let mut result = String::new();
+    let mut rest = s;
+    let mut replaced = false;
+    while let Some(start) = rest.find("${") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        let text = match evaluate(&after[..end], ctx) {
+            Some(Value::String(v)) => Some(v),
+            Some(v @ (Value::Number(_) | Value::Bool(_))) => Some(v.to_string()),
+            _ => None,
+        };
+        result.push_str(&rest[..start]);
+        match text {
+            Some(text) => {
+                result.push_str(&text);
+                replaced = true;
+            }
+            None => result.push_str(&rest[start..start + 3 + end]),
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+
+    replaced.then_some(Value::String(result))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+const OPERATORS: &[&str] = &[
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "?", ":", "(", ")",
+];
+
+fn tokenize(expr: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = expr.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' {
+            let end = chars[i + 1..].iter().position(|&c| c == '"')? + i + 1;
+            let text: String = chars[i + 1..end].iter().collect();
+            if text.contains('\\') || text.contains("${") {
+                return None;
+            }
+            tokens.push(Token::Str(text));
+            i = end + 1;
+        } else if c.is_ascii_digit() {
+            let len = chars[i..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit() || **c == '.')
+                .count();
+            let text: String = chars[i..i + len].iter().collect();
+            tokens.push(Token::Number(text.parse().ok()?));
+            i += len;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let len = chars[i..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+                .count();
+            tokens.push(Token::Ident(chars[i..i + len].iter().collect()));
+            i += len;
+        } else {
+            let rest: String = chars[i..].iter().take(2).collect();
+            let op = OPERATORS.iter().find(|op| rest.starts_with(**op))?;
+            tokens.push(Token::Op(op));
+            i += op.len();
+        }
+    }
+    Some(tokens)
+}
+
+enum Expr {
+    Literal(Value),
+    Variable(String),
+    Workspace,
+    Not(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn eval(&self, ctx: &EvalContext) -> Option<Value> {
+        match self {
+            Expr::Literal(value) => Some(value.clone()),
+            Expr::Variable(name) => ctx.variables.get(name).cloned(),
+            Expr::Workspace => Some(Value::String(ctx.workspace.to_string())),
+            Expr::Not(inner) => Some(Value::Bool(!truthy(&inner.eval(ctx)?)?)),
+            Expr::Conditional(condition, then, otherwise) => {
+                if truthy(&condition.eval(ctx)?)? {
+                    then.eval(ctx)
+                } else {
+                    otherwise.eval(ctx)
+                }
+            }
+            Expr::Binary(op, left, right) => {
+                let left = left.eval(ctx)?;
+                // Short-circuit so an unknown right side doesn't matter
+                match (*op, truthy(&left)) {
+                    ("&&", Some(false)) => return Some(Value::Bool(false)),
+                    ("||", Some(true)) => return Some(Value::Bool(true)),
+                    _ => {}
+                }
+                let right = right.eval(ctx)?;
+                let result = match *op {
+                    "&&" | "||" => truthy(&right)?,
+                    "==" => equal(&left, &right),
+                    "!=" => !equal(&left, &right),
+                    _ => {
+                        let (l, r) = (number(&left)?, number(&right)?);
+                        match *op {
+                            "<" => l < r,
+                            "<=" => l <= r,
+                            ">" => l > r,
+                            _ => l >= r,
+                        }
+                    }
+                };
+                Some(Value::Bool(result))
+            }
+        }
+    }
+}
+
+/// Bools, and the strings Terraform converts to them
+fn truthy(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::String(s) if s == "true" => Some(true),
+        Value::String(s) if s == "false" => Some(false),
+        _ => None,
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn equal(left: &Value, right: &Value) -> bool {
+    match (number(left), number(right)) {
+        (Some(l), Some(r)) if !left.is_string() || !right.is_string() => l == r,
+        _ => left == right,
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_op(&self, op: &str) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token::Op(o)) if *o == op)
+    }
+
+    fn eat_op(&mut self, op: &str) -> bool {
+        let found = self.peek_op(op);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn conditional(&mut self) -> Option<Expr> {
+        let condition = self.binary(0)?;
+        if !self.eat_op("?") {
+            return Some(condition);
+        }
+        let then = self.conditional()?;
+        if !self.eat_op(":") {
+            return None;
+        }
+        let otherwise = self.conditional()?;
+        Some(Expr::Conditional(
+            Box::new(condition),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    /// Binary operators by precedence level, loosest first
+    fn binary(&mut self, level: usize) -> Option<Expr> {
+        const LEVELS: &[&[&str]] = &[&["||"], &["&&"], &["==", "!="], &["<", "<=", ">", ">="]];
+        let Some(ops) = LEVELS.get(level) else {
+            return self.unary();
+        };
+
+        let mut left = self.binary(level + 1)?;
+        while let Some(op) = ops.iter().find(|op| self.peek_op(op)) {
+            self.pos += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Some(left)
+    }
+
+    fn unary(&mut self) -> Option<Expr> {
+        if self.eat_op("!") {
+            return Some(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat_op("(") {
+            let inner = self.conditional()?;
+            return self.eat_op(")").then_some(inner);
+        }
+
+        let token = self.tokens.get(self.pos)?.clone();
+        self.pos += 1;
+        match token {
+            Token::Number(n) if n.fract() == 0.0 => Some(Expr::Literal(json!(n as i64))),
+            Token::Number(n) => Some(Expr::Literal(json!(n))),
+            Token::Str(s) => Some(Expr::Literal(Value::String(s))),
+            Token::Ident(ident) => match ident.as_str() {
+                "true" => Some(Expr::Literal(Value::Bool(true))),
+                "false" => Some(Expr::Literal(Value::Bool(false))),
+                "null" => Some(Expr::Literal(Value::Null)),
+                "terraform.workspace" => Some(Expr::Workspace),
+                _ => ident
+                    .strip_prefix("var.")
+                    .filter(|name| !name.contains('.'))
+                    .map(|name| Expr::Variable(name.to_string())),
+            },
+            Token::Op(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(variables: &Map<String, Value>) -> EvalContext<'_> {
+        EvalContext {
+            variables,
+            workspace: "prod",
+        }
+    }
+
+    #[test]
+    fn test_conditionals_and_comparisons() {
+        let variables = json!({ "enable_nat": false, "replicas": 3, "env": "prod" })
+            .as_object()
+            .unwrap()
+            .clone();
+        let ctx = context(&variables);
+
+        assert_eq!(evaluate("var.enable_nat ? 1 : 0", &ctx), Some(json!(0)));
+        assert_eq!(
+            evaluate("terraform.workspace == \"prod\" ? var.replicas : 1", &ctx),
+            Some(json!(3))
+        );
+        assert_eq!(
+            evaluate(
+                "!var.enable_nat && (var.replicas >= 2 || var.unknown)",
+                &ctx
+            ),
+            Some(json!(true))
+        );
+        assert_eq!(evaluate("var.unknown ? 1 : 0", &ctx), None);
+        assert_eq!(evaluate("length(var.azs)", &ctx), None);
+    }
+
+    #[test]
+    fn test_interpolate_strings() {
+        let variables = json!({ "env": "prod", "size": 100 })
+            .as_object()
+            .unwrap()
+            .clone();
+        let ctx = context(&variables);
+
+        assert_eq!(interpolate("${var.size}", &ctx), Some(json!(100)));
+        assert_eq!(
+            interpolate("${var.env}-${terraform.workspace}-${var.ami}", &ctx),
+            Some(json!("prod-prod-${var.ami}"))
+        );
+        assert_eq!(interpolate("${var.ami}", &ctx), None);
+        assert_eq!(interpolate("plain", &ctx), None);
+    }
+}
@@ -2,7 +2,9 @@
 
 // Terraform HCL configuration parser
 
+use super::parser::extract_tags;
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::engines::shared::models::{ChangeAction, ResourceChange};
 use hcl::Value;
 
 /// Basic Terraform HCL configuration structure
@@ -25,3 +27,90 @@ pub fn parse_terraform_config(hcl_content: &str) -> Result<TerraformConfig> {
 
     Ok(TerraformConfig { content: value })
 }
+
+/// Convert `resource` blocks to planned creations
+///
+/// Source files carry no prior state, so every resource is a `Create` with
+/// its literal attributes as `new_config`. Expressions that need evaluation
+/// (variables, references) are kept as interpolation strings.
+pub fn config_to_resource_changes(
+    config: &TerraformConfig,
+    module_path: Option<&str>,
+) -> Vec<ResourceChange> {
+    let mut changes = Vec::new();
+
+    let Some(Value::Object(resources)) = config.content.as_object().and_then(|o| o.get("resource"))
+    else {
+        return changes;
+    };
+
+    for (resource_type, instances) in resources {
+        let Value::Object(instances) = instances else {
+            continue;
+        };
+        for (name, body) in instances {
+            let new_config = serde_json::to_value(body).ok();
+            let address = format!("{}.{}", resource_type, name);
+            changes.push(ResourceChange {
+                resource_id: match module_path {
+                    Some(module) => format!("{}.{}", module, address),
+                    None => address,
+                },
+                resource_type: resource_type.clone(),
+                action: ChangeAction::Create,
+                module_path: module_path.map(str::to_string),
+                old_config: None,
+                tags: extract_tags(&new_config),
+                new_config,
+                monthly_cost: None,
+                config: None,
+                cost_impact: None,
+            });
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_blocks_become_creates() {
+        let config = parse_terraform_config(
+            r#"
+            resource "aws_instance" "web" {
+              instance_type = "m5.large"
+              ami           = var.ami
+              tags = {
+                team = "platform"
+              }
+            }
+
+            resource "aws_s3_bucket" "logs" {
+              bucket = "logs"
+            }
+            "#,
+        )
+        .unwrap();
+
+        let mut changes = config_to_resource_changes(&config, Some("module.web"));
+        changes.sort_by(|a, b| a.resource_id.cmp(&b.resource_id));
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].resource_id, "module.web.aws_instance.web");
+        assert_eq!(changes[0].module_path.as_deref(), Some("module.web"));
+        assert_eq!(changes[0].tags["team"], "platform");
+        let new_config = changes[0].new_config.as_ref().unwrap();
+        assert_eq!(new_config["instance_type"], "m5.large");
+        assert!(new_config["ami"].as_str().unwrap().contains("var.ami"));
+        assert_eq!(changes[1].resource_id, "module.web.aws_s3_bucket.logs");
+    }
+
+    #[test]
+    fn test_config_without_resources() {
+        let config = parse_terraform_config("variable \"region\" {}").unwrap();
+        assert!(config_to_resource_changes(&config, None).is_empty());
+    }
+}
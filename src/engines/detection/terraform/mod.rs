@@ -1,11 +1,20 @@
 // Terraform module
 
+pub mod expressions;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod hcl_parser;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod modules;
 pub mod normalize;
 pub mod parser;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tfvars;
 
 #[cfg(not(target_arch = "wasm32"))]
-pub use hcl_parser::{parse_terraform_config, TerraformConfig};
+pub use hcl_parser::{config_to_resource_changes, parse_terraform_config, TerraformConfig};
+#[cfg(not(target_arch = "wasm32"))]
+pub use modules::{load_terraform_directory, local_module_dirs};
 pub use parser::{convert_to_resource_changes, parse_terraform_plan, TerraformPlan};
+#[cfg(not(target_arch = "wasm32"))]
+pub use tfvars::TerraformContext;
 // pub use normalize::normalize_resource; // TODO: Fix module structure
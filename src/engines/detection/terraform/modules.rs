@@ -0,0 +1,499 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+// Local Terraform module resolution
+//
+// Follows `module` blocks whose `source` is a relative path (`./modules/vpc`)
+// so resources declared inside local modules are detected and addressed the
+// way plan JSON shows them (`module.vpc.aws_nat_gateway.main`, nested calls as
+// `module.app.module.db...`). Arguments of a module call become the module's
+// variables, the root's come from tfvars files. Expressions are evaluated
+// where their values are known, so `count = var.enable_nat ? 1 : 0` yields
+// zero or one instance (`aws_nat_gateway.main[0]`); everything else is kept
+// as an interpolation string.
+
+use super::expressions::{interpolate, EvalContext};
+use super::hcl_parser::{config_to_resource_changes, parse_terraform_config, TerraformConfig};
+use super::parser::extract_tags;
+use super::tfvars::TerraformContext;
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use crate::engines::shared::models::ResourceChange;
+use serde_json::{Map, Value};
+use std::path::{Component, Path, PathBuf};
+
+/// Nesting depth of module calls followed before giving up
+const MAX_MODULE_DEPTH: usize = 16;
+
+/// Module block attributes that are not input variables
+const META_ARGUMENTS: &[&str] = &[
+    "source",
+    "version",
+    "providers",
+    "depends_on",
+    "count",
+    "for_each",
+];
+
+/// Parse the root module in `root` and every local module it calls
+pub fn load_terraform_directory(
+    root: &Path,
+    context: &TerraformContext,
+) -> Result<Vec<ResourceChange>> {
+    let mut changes = Vec::new();
+    let mut stack = Vec::new();
+    resolve_module(
+        root,
+        None,
+        &context.variables,
+        &context.workspace,
+        &mut stack,
+        &mut changes,
+    )?;
+    Ok(changes)
+}
+
+/// Directories of local modules reachable from `root`, lexically normalized
+///
+/// Unparseable files and missing sources are skipped; the root itself is not
+/// included.
+pub fn local_module_dirs(root: &Path) -> Vec<PathBuf> {
+    let root = normalize(root);
+    let mut dirs = Vec::new();
+    let mut pending = vec![(root.clone(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        if depth >= MAX_MODULE_DEPTH {
+            continue;
+        }
+        let Ok(configs) = read_module(&dir) else {
+            continue;
+        };
+        for call in module_calls(&configs) {
+            let Some(source) = local_source(&dir, &call.source) else {
+                continue;
+            };
+            if source != root && source.is_dir() && !dirs.contains(&source) {
+                dirs.push(source.clone());
+                pending.push((source, depth + 1));
+            }
+        }
+    }
+    dirs.sort();
+    dirs
+}
+
+/// A `module` block
+struct ModuleCall {
+    name: String,
+    source: String,
+    count: Option<Value>,
+    arguments: Map<String, Value>,
+}
+
+fn resolve_module(
+    dir: &Path,
+    module_path: Option<&str>,
+    inputs: &Map<String, Value>,
+    workspace: &str,
+    stack: &mut Vec<PathBuf>,
+    changes: &mut Vec<ResourceChange>,
+) -> Result<()> {
+    let dir = normalize(dir);
+    if stack.contains(&dir) || stack.len() >= MAX_MODULE_DEPTH {
+        return Err(CostPilotError::parse_error(format!(
+            "Module {} calls itself or nests deeper than {} levels",
+            module_path.unwrap_or("root"),
+            MAX_MODULE_DEPTH
+        )));
+    }
+
+    let configs = read_module(&dir)?;
+    let variables = variables(&configs, inputs);
+    let ctx = EvalContext {
+        variables: &variables,
+        workspace,
+    };
+
+    for config in &configs {
+        for mut change in config_to_resource_changes(config, module_path) {
+            if let Some(new_config) = change.new_config.as_mut() {
+                substitute(new_config, &ctx);
+            }
+            change.tags = extract_tags(&change.new_config);
+            changes.extend(expand_count(change));
+        }
+    }
+
+    stack.push(dir.clone());
+    for mut call in module_calls(&configs) {
+        let Some(source) = local_source(&dir, &call.source) else {
+            continue;
+        };
+        if !source.is_dir() {
+            return Err(CostPilotError::file_not_found(format!(
+                "Module '{}' source {} not found",
+                call.name,
+                source.display()
+            ))
+            .with_hint("Local module sources are resolved relative to the calling module"));
+        }
+
+        for value in call.arguments.values_mut() {
+            substitute(value, &ctx);
+        }
+        let address = match module_path {
+            Some(parent) => format!("{}.module.{}", parent, call.name),
+            None => format!("module.{}", call.name),
+        };
+        let count = call.count.map(|mut count| {
+            substitute(&mut count, &ctx);
+            count
+        });
+        let instances = match count.as_ref().and_then(count_of) {
+            Some(count) => (0..count)
+                .map(|index| format!("{}[{}]", address, index))
+                .collect(),
+            None => vec![address],
+        };
+        for instance in instances {
+            resolve_module(
+                &source,
+                Some(&instance),
+                &call.arguments,
+                workspace,
+                stack,
+                changes,
+            )?;
+        }
+    }
+    stack.pop();
+
+    Ok(())
+}
+
+/// Parse the `*.tf` files directly inside `dir`, in name order
+fn read_module(dir: &Path) -> Result<Vec<TerraformConfig>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        CostPilotError::io_error(format!("Failed to read {}: {}", dir.display(), e))
+    })?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "tf"))
+        .collect();
+    files.sort();
+
+    files
+        .iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            parse_terraform_config(&content)
+        })
+        .collect()
+}
+
+/// Variable defaults of a module, overridden by the caller's arguments
+fn variables(configs: &[TerraformConfig], inputs: &Map<String, Value>) -> Map<String, Value> {
+    let mut variables = Map::new();
+    for (name, body) in blocks(configs, "variable") {
+        if let Some(default) = body.get("default") {
+            variables.insert(name, default.clone());
+        }
+    }
+    for (name, value) in inputs {
+        variables.insert(name.clone(), value.clone());
+    }
+    variables
+}
+
+fn module_calls(configs: &[TerraformConfig]) -> Vec<ModuleCall> {
+    blocks(configs, "module")
+        .into_iter()
+        .filter_map(|(name, body)| {
+            let source = body.get("source")?.as_str()?.to_string();
+            let count = body.get("count").cloned();
+            let arguments = body
+                .into_iter()
+                .filter(|(key, _)| !META_ARGUMENTS.contains(&key.as_str()))
+                .collect();
+            Some(ModuleCall {
+                name,
+                source,
+                count,
+                arguments,
+            })
+        })
+        .collect()
+}
+
+/// Labelled blocks of `kind` (`variable "x" {}`) across all files
+fn blocks(configs: &[TerraformConfig], kind: &str) -> Vec<(String, Map<String, Value>)> {
+    configs
+        .iter()
+        .filter_map(|config| serde_json::to_value(&config.content).ok())
+        .filter_map(|content| match content.get(kind) {
+            Some(Value::Object(blocks)) => Some(blocks.clone()),
+            _ => None,
+        })
+        .flat_map(|blocks| {
+            blocks.into_iter().map(|(name, body)| match body {
+                Value::Object(body) => (name, body),
+                _ => (name, Map::new()),
+            })
+        })
+        .collect()
+}
+
+/// Directory of a relative module source; `None` for registry or remote sources
+fn local_source(dir: &Path, source: &str) -> Option<PathBuf> {
+    if source.starts_with("./") || source.starts_with("../") {
+        Some(normalize(&dir.join(source)))
+    } else {
+        None
+    }
+}
+
+/// Evaluate `${...}` interpolations that only need known values
+fn substitute(value: &mut Value, ctx: &EvalContext) {
+    match value {
+        Value::String(s) => {
+            if let Some(replacement) = interpolate(s, ctx) {
+                *value = replacement;
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| substitute(v, ctx)),
+        Value::Object(fields) => fields.values_mut().for_each(|v| substitute(v, ctx)),
+        _ => {}
+    }
+}
+
+/// Instances of a resource with an evaluated `count`, addressed `name[i]`
+fn expand_count(mut change: ResourceChange) -> Vec<ResourceChange> {
+    let Some(count) = change
+        .new_config
+        .as_ref()
+        .and_then(|config| config.get("count"))
+        .and_then(count_of)
+    else {
+        return vec![change];
+    };
+    if let Some(Value::Object(config)) = change.new_config.as_mut() {
+        config.remove("count");
+    }
+    (0..count)
+        .map(|index| {
+            let mut instance = change.clone();
+            instance.resource_id = format!("{}[{}]", change.resource_id, index);
+            instance
+        })
+        .collect()
+}
+
+fn count_of(value: &Value) -> Option<usize> {
+    let count = match value {
+        Value::Number(n) => n.as_f64()?,
+        Value::String(s) => s.parse().ok()?,
+        _ => return None,
+    };
+    (count >= 0.0 && count.fract() == 0.0).then_some(count as usize)
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    normalized.pop();
+                } else {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    if normalized.as_os_str().is_empty() {
+        normalized.push(".");
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn project() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "main.tf",
+            r#"
+            variable "env" {
+              default = "prod"
+            }
+
+            module "app" {
+              source        = "./modules/app"
+              instance_type = "m5.large"
+              name          = "${var.env}-app"
+            }
+
+            module "registry" {
+              source  = "terraform-aws-modules/vpc/aws"
+              version = "5.0.0"
+            }
+            "#,
+        );
+        write(
+            dir.path(),
+            "modules/app/main.tf",
+            r#"
+            variable "instance_type" {
+              default = "t3.micro"
+            }
+            variable "name" {}
+
+            resource "aws_instance" "web" {
+              instance_type = var.instance_type
+              ami           = var.ami
+              tags = {
+                Name = "${var.name}-web"
+              }
+            }
+
+            module "db" {
+              source         = "../db"
+              instance_class = "db.${var.instance_type}"
+            }
+            "#,
+        );
+        write(
+            dir.path(),
+            "modules/db/main.tf",
+            r#"
+            variable "instance_class" {}
+
+            resource "aws_db_instance" "main" {
+              instance_class = var.instance_class
+            }
+            "#,
+        );
+        dir
+    }
+
+    #[test]
+    fn test_local_modules_are_resolved_with_variables() {
+        let dir = project();
+        let mut changes =
+            load_terraform_directory(dir.path(), &TerraformContext::default()).unwrap();
+        changes.sort_by(|a, b| a.resource_id.cmp(&b.resource_id));
+
+        assert_eq!(changes.len(), 2);
+        let db = &changes[1];
+        assert_eq!(db.resource_id, "module.app.module.db.aws_db_instance.main");
+        assert_eq!(db.module_path.as_deref(), Some("module.app.module.db"));
+        assert_eq!(
+            db.new_config.as_ref().unwrap()["instance_class"],
+            "db.m5.large"
+        );
+
+        let web = &changes[0];
+        assert_eq!(web.resource_id, "module.app.aws_instance.web");
+        let config = web.new_config.as_ref().unwrap();
+        assert_eq!(config["instance_type"], "m5.large");
+        assert_eq!(config["ami"], "${var.ami}");
+        assert_eq!(web.tags["Name"], "prod-app-web");
+    }
+
+    #[test]
+    fn test_local_module_dirs_and_missing_source() {
+        let dir = project();
+        let root = normalize(dir.path());
+        assert_eq!(
+            local_module_dirs(dir.path()),
+            vec![root.join("modules/app"), root.join("modules/db")]
+        );
+
+        write(
+            dir.path(),
+            "extra.tf",
+            "module \"gone\" {\n  source = \"./modules/gone\"\n}\n",
+        );
+        let err = load_terraform_directory(dir.path(), &TerraformContext::default()).unwrap_err();
+        assert!(err.message.contains("modules/gone"));
+    }
+
+    #[test]
+    fn test_recursive_module_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "main.tf",
+            "module \"self\" {\n  source = \"./\"\n}\n",
+        );
+        assert!(load_terraform_directory(dir.path(), &TerraformContext::default()).is_err());
+        assert!(local_module_dirs(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_count_follows_workspace_tfvars() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "main.tf",
+            r#"
+            variable "enable_nat" {
+              default = true
+            }
+
+            resource "aws_nat_gateway" "main" {
+              count = var.enable_nat ? 1 : 0
+            }
+
+            module "replica" {
+              source = "./modules/db"
+              count  = terraform.workspace == "prod" ? 2 : 0
+            }
+            "#,
+        );
+        write(
+            dir.path(),
+            "modules/db/main.tf",
+            "resource \"aws_db_instance\" \"main\" {}\n",
+        );
+        write(dir.path(), "dev.tfvars", "enable_nat = false\n");
+
+        let load = |workspace: &str| {
+            let context = TerraformContext::load(dir.path(), Some(workspace), &[]).unwrap();
+            let mut ids: Vec<String> = load_terraform_directory(dir.path(), &context)
+                .unwrap()
+                .into_iter()
+                .map(|c| c.resource_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert!(load("dev").is_empty());
+        assert_eq!(
+            load("prod"),
+            vec![
+                "aws_nat_gateway.main[0]",
+                "module.replica[0].aws_db_instance.main",
+                "module.replica[1].aws_db_instance.main",
+            ]
+        );
+    }
+}
@@ -136,7 +136,7 @@ fn determine_action(actions: &[String]) -> Result<ChangeAction> {
 }
 
 /// Extract tags from Terraform resource configuration
-fn extract_tags(config: &Option<Value>) -> HashMap<String, String> {
+pub(crate) fn extract_tags(config: &Option<Value>) -> HashMap<String, String> {
     let mut tags = HashMap::new();
 
     if let Some(Value::Object(obj)) = config {
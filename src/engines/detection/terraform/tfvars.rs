@@ -0,0 +1,183 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+// Terraform variable files and workspaces
+//
+// Without a plan, variable values come from the files Terraform would load:
+// `terraform.tfvars`, `*.auto.tfvars`, a `<workspace>.tfvars` for the
+// selected workspace, then each `-var-file` in order, later files winning.
+// The workspace is `TF_WORKSPACE`, else the one `terraform workspace select`
+// recorded in `.terraform/environment`, else `default`.
+
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Workspace Terraform uses when none is selected
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// Workspace and root variable values used to evaluate raw HCL
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerraformContext {
+    pub workspace: String,
+    pub variables: Map<String, Value>,
+}
+
+impl Default for TerraformContext {
+    fn default() -> Self {
+        Self {
+            workspace: DEFAULT_WORKSPACE.to_string(),
+            variables: Map::new(),
+        }
+    }
+}
+
+impl TerraformContext {
+    /// Load variable files for `root`
+    ///
+    /// `workspace` overrides the selected workspace; `var_files` are applied
+    /// last, like `-var-file` arguments.
+    pub fn load(root: &Path, workspace: Option<&str>, var_files: &[PathBuf]) -> Result<Self> {
+        let workspace = workspace
+            .map(str::to_string)
+            .unwrap_or_else(|| current_workspace(root));
+
+        let mut variables = Map::new();
+        for path in auto_var_files(root, &workspace).iter().chain(var_files) {
+            variables.extend(parse_var_file(path)?);
+        }
+
+        Ok(Self {
+            workspace,
+            variables,
+        })
+    }
+}
+
+/// Workspace selected for `root`
+pub fn current_workspace(root: &Path) -> String {
+    if let Ok(workspace) = std::env::var("TF_WORKSPACE") {
+        if !workspace.is_empty() {
+            return workspace;
+        }
+    }
+    std::fs::read_to_string(root.join(".terraform").join("environment"))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DEFAULT_WORKSPACE.to_string())
+}
+
+/// Whether `path` is a `.tfvars` or `.tfvars.json` file
+pub fn is_var_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with(".tfvars") || n.ends_with(".tfvars.json"))
+}
+
+/// Variable files loaded without `-var-file`, lowest precedence first
+fn auto_var_files(root: &Path, workspace: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = ["terraform.tfvars", "terraform.tfvars.json"]
+        .iter()
+        .map(|name| root.join(name))
+        .filter(|path| path.is_file())
+        .collect();
+
+    let mut auto: Vec<PathBuf> = std::fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with(".auto.tfvars") || n.ends_with(".auto.tfvars.json"))
+        })
+        .collect();
+    auto.sort();
+    files.extend(auto);
+
+    for name in [
+        format!("{}.tfvars", workspace),
+        format!("{}.tfvars.json", workspace),
+    ] {
+        let path = root.join(name);
+        if path.is_file() && !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Parse a `.tfvars` (HCL) or `.tfvars.json` file into variable values
+pub fn parse_var_file(path: &Path) -> Result<Map<String, Value>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+            .with_hint("Check the path passed to --var-file")
+    })?;
+
+    let value = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&content).map_err(|e| {
+            CostPilotError::parse_error(format!("Invalid JSON in {}: {}", path.display(), e))
+        })?
+    } else {
+        let value: hcl::Value = hcl::from_str(&content).map_err(|e| {
+            CostPilotError::parse_error(format!("Invalid HCL in {}: {}", path.display(), e))
+        })?;
+        serde_json::to_value(value).map_err(|e| {
+            CostPilotError::parse_error(format!("Invalid values in {}: {}", path.display(), e))
+        })?
+    };
+
+    match value {
+        Value::Object(variables) => Ok(variables),
+        _ => Err(CostPilotError::parse_error(format!(
+            "{} must assign variables at the top level",
+            path.display()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_precedence_of_var_files() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("terraform.tfvars"),
+            "enable_nat = false\nreplicas = 1\nregion = \"us-east-1\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("b.auto.tfvars.json"), r#"{"replicas": 2}"#).unwrap();
+        std::fs::write(root.join("prod.tfvars"), "enable_nat = true\n").unwrap();
+        let extra = root.join("extra.tfvars");
+        std::fs::write(&extra, "region = \"eu-west-1\"\n").unwrap();
+
+        let dev = TerraformContext::load(root, Some("dev"), &[]).unwrap();
+        assert_eq!(dev.workspace, "dev");
+        assert_eq!(dev.variables["enable_nat"], false);
+        assert_eq!(dev.variables["replicas"], 2);
+
+        let prod = TerraformContext::load(root, Some("prod"), &[extra]).unwrap();
+        assert_eq!(prod.variables["enable_nat"], true);
+        assert_eq!(prod.variables["region"], "eu-west-1");
+    }
+
+    #[test]
+    fn test_selected_workspace_and_missing_var_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join(".terraform")).unwrap();
+        std::fs::write(dir.path().join(".terraform/environment"), "staging\n").unwrap();
+        if std::env::var("TF_WORKSPACE").is_err() {
+            assert_eq!(current_workspace(dir.path()), "staging");
+        }
+
+        let missing = dir.path().join("missing.tfvars");
+        assert!(TerraformContext::load(dir.path(), None, &[missing]).is_err());
+        assert!(is_var_file(Path::new("env/prod.tfvars.json")));
+        assert!(!is_var_file(Path::new("main.tf")));
+    }
+}
@@ -0,0 +1,392 @@
+// Semantic comparison of two infrastructure plans
+//
+// Resources are matched by address across the two plans. For each matched
+// resource only the attributes that drive cost are compared, so a tag or
+// description edit does not show up as a cost change, and every list is
+// ordered through `deterministic_sort` so diffs of the same plans are
+// reproducible byte for byte.
+
+use crate::engines::shared::deterministic_sort::{sort_by_address, sort_by_cost_impact};
+use crate::engines::shared::models::{CostEstimate, ResourceChange};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Attribute names (or name fragments) whose value feeds a cost estimate
+const COST_ATTRIBUTE_HINTS: &[&str] = &[
+    "instance_type",
+    "instance_class",
+    "node_type",
+    "size",
+    "capacity",
+    "count",
+    "storage",
+    "iops",
+    "throughput",
+    "memory",
+    "cpu",
+    "replicas",
+    "class",
+    "tier",
+    "sku",
+    "engine",
+    "multi_az",
+    "billing_mode",
+    "retention",
+    "architectures",
+    "volume_type",
+];
+
+/// Cost deltas smaller than this are rounding noise
+const COST_EPSILON: f64 = 0.005;
+
+/// How a resource differs between the two plans
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceDiffKind {
+    /// Only in the after plan
+    Added,
+    /// Only in the before plan
+    Removed,
+    /// In both, with a cost or cost-driving attribute change
+    Changed,
+    /// In both, with nothing that affects cost
+    Unchanged,
+}
+
+impl ResourceDiffKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResourceDiffKind::Added => "added",
+            ResourceDiffKind::Removed => "removed",
+            ResourceDiffKind::Changed => "changed",
+            ResourceDiffKind::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// One cost-driving attribute that differs between the plans
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeChange {
+    /// Flattened attribute path, e.g. `root_block_device[0].volume_size`
+    pub path: String,
+    /// `null` when the attribute is not set in the before plan
+    pub before: Value,
+    /// `null` when the attribute is not set in the after plan
+    pub after: Value,
+}
+
+/// Comparison of one resource address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceDiff {
+    pub address: String,
+    pub resource_type: String,
+    pub kind: ResourceDiffKind,
+    pub before_monthly: f64,
+    pub after_monthly: f64,
+    pub monthly_delta: f64,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub attribute_changes: Vec<AttributeChange>,
+}
+
+/// Resource-level comparison of two plans
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanDiff {
+    pub before_monthly: f64,
+    pub after_monthly: f64,
+    /// Every resource in either plan except unchanged ones, largest cost
+    /// impact first
+    pub resources: Vec<ResourceDiff>,
+    /// Resources present in both plans with no cost-relevant change
+    pub unchanged: usize,
+}
+
+impl PlanDiff {
+    /// Compare the resources and estimates of two plans
+    pub fn compute(
+        before: &[ResourceChange],
+        before_estimates: &[CostEstimate],
+        after: &[ResourceChange],
+        after_estimates: &[CostEstimate],
+    ) -> Self {
+        let before_costs = costs_by_address(before_estimates);
+        let after_costs = costs_by_address(after_estimates);
+        let before_by_address: BTreeMap<&str, &ResourceChange> =
+            before.iter().map(|c| (c.resource_id.as_str(), c)).collect();
+        let after_by_address: BTreeMap<&str, &ResourceChange> =
+            after.iter().map(|c| (c.resource_id.as_str(), c)).collect();
+
+        let mut addresses: Vec<&str> = before_by_address
+            .keys()
+            .chain(after_by_address.keys())
+            .copied()
+            .collect();
+        sort_by_address(&mut addresses, |a| a);
+        addresses.dedup();
+
+        let mut resources = Vec::new();
+        let mut unchanged = 0;
+        for address in addresses {
+            let old = before_by_address.get(address);
+            let new = after_by_address.get(address);
+            let before_monthly = before_costs.get(address).copied().unwrap_or(0.0);
+            let after_monthly = after_costs.get(address).copied().unwrap_or(0.0);
+
+            let (kind, resource_type, attribute_changes) = match (old, new) {
+                (Some(old), Some(new)) => {
+                    let attribute_changes = cost_attribute_changes(
+                        planned_config(old).unwrap_or(&Value::Null),
+                        planned_config(new).unwrap_or(&Value::Null),
+                    );
+                    let kind = if attribute_changes.is_empty()
+                        && (after_monthly - before_monthly).abs() < COST_EPSILON
+                    {
+                        ResourceDiffKind::Unchanged
+                    } else {
+                        ResourceDiffKind::Changed
+                    };
+                    (kind, &new.resource_type, attribute_changes)
+                }
+                (None, Some(new)) => (ResourceDiffKind::Added, &new.resource_type, Vec::new()),
+                (Some(old), None) => (ResourceDiffKind::Removed, &old.resource_type, Vec::new()),
+                (None, None) => unreachable!("address comes from one of the plans"),
+            };
+
+            if kind == ResourceDiffKind::Unchanged {
+                unchanged += 1;
+                continue;
+            }
+            resources.push(ResourceDiff {
+                address: address.to_string(),
+                resource_type: resource_type.clone(),
+                kind,
+                before_monthly,
+                after_monthly,
+                monthly_delta: after_monthly - before_monthly,
+                attribute_changes,
+            });
+        }
+        sort_by_cost_impact(&mut resources, |r| r.monthly_delta, |r| &r.address);
+
+        Self {
+            before_monthly: before_costs.values().sum(),
+            after_monthly: after_costs.values().sum(),
+            resources,
+            unchanged,
+        }
+    }
+
+    pub fn monthly_delta(&self) -> f64 {
+        self.after_monthly - self.before_monthly
+    }
+
+    /// Change relative to the before plan; 0 when the before plan costs nothing
+    pub fn percentage(&self) -> f64 {
+        if self.before_monthly > 0.0 {
+            self.monthly_delta() / self.before_monthly * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    pub fn count(&self, kind: ResourceDiffKind) -> usize {
+        self.resources.iter().filter(|r| r.kind == kind).count()
+    }
+}
+
+fn costs_by_address(estimates: &[CostEstimate]) -> BTreeMap<&str, f64> {
+    let mut costs = BTreeMap::new();
+    for estimate in estimates {
+        *costs.entry(estimate.resource_id.as_str()).or_insert(0.0) += estimate.monthly_cost;
+    }
+    costs
+}
+
+/// The configuration a plan leaves the resource in
+fn planned_config(change: &ResourceChange) -> Option<&Value> {
+    change
+        .new_config
+        .as_ref()
+        .or(change.config.as_ref())
+        .or(change.old_config.as_ref())
+}
+
+/// Cost-driving leaf attributes whose values differ, ordered by path
+fn cost_attribute_changes(before: &Value, after: &Value) -> Vec<AttributeChange> {
+    let mut before_leaves = BTreeMap::new();
+    let mut after_leaves = BTreeMap::new();
+    flatten("", before, &mut before_leaves);
+    flatten("", after, &mut after_leaves);
+
+    let mut paths: Vec<&String> = before_leaves.keys().chain(after_leaves.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .filter(|path| is_cost_attribute(path))
+        .filter_map(|path| {
+            let old = before_leaves.get(path).cloned().unwrap_or(Value::Null);
+            let new = after_leaves.get(path).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| AttributeChange {
+                path: path.clone(),
+                before: old,
+                after: new,
+            })
+        })
+        .collect()
+}
+
+/// Flatten nested objects and arrays into `a.b[0].c` paths
+fn flatten(prefix: &str, value: &Value, leaves: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, value, leaves);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(&format!("{}[{}]", prefix, i), item, leaves);
+            }
+        }
+        Value::Null if prefix.is_empty() => {}
+        _ => {
+            leaves.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// Whether the last path segment names a cost-driving attribute
+fn is_cost_attribute(path: &str) -> bool {
+    let name = path.rsplit('.').next().unwrap_or(path);
+    let name = name.split('[').next().unwrap_or(name);
+    COST_ATTRIBUTE_HINTS.iter().any(|hint| name.contains(hint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+    use serde_json::json;
+
+    fn change(address: &str, config: Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(address)
+            .resource_type(address.split('.').next().unwrap())
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    fn estimate(address: &str, monthly_cost: f64) -> CostEstimate {
+        CostEstimate::builder()
+            .resource_id(address)
+            .monthly_cost(monthly_cost)
+            .build()
+    }
+
+    #[test]
+    fn test_plan_diff_matches_resources_by_address() {
+        let before = vec![
+            change(
+                "aws_instance.web",
+                json!({"instance_type": "t3.medium", "tags": {"Owner": "a"}}),
+            ),
+            change("aws_s3_bucket.logs", json!({"bucket": "logs"})),
+            change(
+                "aws_db_instance.main",
+                json!({"instance_class": "db.t3.micro", "tags": {"Owner": "a"}}),
+            ),
+        ];
+        let after = vec![
+            change(
+                "aws_db_instance.main",
+                json!({"instance_class": "db.t3.micro", "tags": {"Owner": "b"}}),
+            ),
+            change(
+                "aws_instance.web",
+                json!({
+                    "instance_type": "t3.xlarge",
+                    "root_block_device": [{"volume_size": 50}],
+                    "tags": {"Owner": "b"}
+                }),
+            ),
+            change("aws_nat_gateway.main", json!({})),
+        ];
+        let before_estimates = vec![
+            estimate("aws_instance.web", 30.0),
+            estimate("aws_s3_bucket.logs", 5.0),
+            estimate("aws_db_instance.main", 15.0),
+        ];
+        let after_estimates = vec![
+            estimate("aws_instance.web", 120.0),
+            estimate("aws_nat_gateway.main", 32.85),
+            estimate("aws_db_instance.main", 15.0),
+        ];
+
+        let diff = PlanDiff::compute(&before, &before_estimates, &after, &after_estimates);
+        assert_eq!(diff.before_monthly, 50.0);
+        assert!((diff.monthly_delta() - 117.85).abs() < 1e-9);
+        // The tag-only edit is not a cost change
+        assert_eq!(diff.unchanged, 1);
+
+        let order: Vec<(&str, ResourceDiffKind)> = diff
+            .resources
+            .iter()
+            .map(|r| (r.address.as_str(), r.kind))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("aws_instance.web", ResourceDiffKind::Changed),
+                ("aws_nat_gateway.main", ResourceDiffKind::Added),
+                ("aws_s3_bucket.logs", ResourceDiffKind::Removed),
+            ]
+        );
+
+        let web = &diff.resources[0];
+        assert_eq!(web.monthly_delta, 90.0);
+        let paths: Vec<&str> = web
+            .attribute_changes
+            .iter()
+            .map(|a| a.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["instance_type", "root_block_device[0].volume_size"]
+        );
+        assert_eq!(web.attribute_changes[1].before, Value::Null);
+        assert_eq!(web.attribute_changes[1].after, json!(50));
+    }
+
+    #[test]
+    fn test_plan_diff_is_independent_of_plan_order() {
+        let before = vec![
+            change("aws_instance.a", json!({"instance_type": "t3.micro"})),
+            change("aws_instance.b", json!({"instance_type": "t3.micro"})),
+        ];
+        let after = vec![
+            change("aws_instance.a", json!({"instance_type": "t3.small"})),
+            change("aws_instance.b", json!({"instance_type": "t3.small"})),
+        ];
+        let estimates = vec![
+            estimate("aws_instance.a", 10.0),
+            estimate("aws_instance.b", 10.0),
+        ];
+
+        let forward = PlanDiff::compute(&before, &estimates, &after, &estimates);
+        let mut reversed_after = after.clone();
+        reversed_after.reverse();
+        let reversed = PlanDiff::compute(&before, &estimates, &reversed_after, &estimates);
+
+        assert_eq!(forward, reversed);
+        assert_eq!(forward.resources[0].address, "aws_instance.a");
+        assert_eq!(forward.count(ResourceDiffKind::Changed), 2);
+    }
+}
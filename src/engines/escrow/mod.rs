@@ -10,9 +10,12 @@ pub use package::{
     SourceFile, VendorInfo, VerificationData, VerificationReport,
 };
 
-pub use recovery::{RecoveryOrchestrator, RecoveryPlaybook, RecoveryReport, RecoveryStep};
+pub use recovery::{
+    RecoveryOrchestrator, RecoveryPlan, RecoveryPlaybook, RecoveryReport, RecoveryStage,
+    RecoveryStep, RECOVERY_PROGRESS_FILE,
+};
 
 pub use release::{
-    DepositReceipt, DepositStatus, EscrowAgentConfig, ReleaseAutomation, ReleaseConfig,
-    ReleaseTrigger,
+    ArtifactCheck, BuildVerificationReport, BuildVerifier, DepositReceipt, DepositStatus,
+    EscrowAgentConfig, ReleaseAutomation, ReleaseConfig, ReleaseTrigger,
 };
@@ -2,9 +2,15 @@
 // Enables secure code deposit for enterprise customers
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
+/// Directories never deposited when scanning outside a git repository
+pub(crate) const EXCLUDED_DIRS: &[&str] = &[".git", "target", "node_modules", "escrow-packages"];
+
+/// Directory of an exported package holding the deposited file contents
+pub const FILES_DIR: &str = "files";
+
 /// Software escrow package containing complete release
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EscrowPackage {
@@ -28,6 +34,29 @@ pub struct EscrowPackage {
 
     /// License information
     pub license: LicenseInfo,
+
+    /// Base package, for delta deposits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<DeltaReference>,
+}
+
+/// Link from a delta deposit to the package it builds on
+///
+/// `source_files` of a delta still lists the complete tree; only the files
+/// in `changed_files` are stored, the rest come from the base chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaReference {
+    pub base_package_id: String,
+    pub base_version: String,
+
+    /// Package checksum of the base, pinning its exact contents
+    pub base_checksum: String,
+
+    /// Files added or changed since the base
+    pub changed_files: Vec<String>,
+
+    /// Files in the base that are no longer part of the tree
+    pub removed_files: Vec<String>,
 }
 
 /// Package metadata
@@ -381,6 +410,7 @@ pub struct EscrowPackageBuilder {
     dependencies: Option<DependenciesManifest>,
     build_instructions: Option<BuildInstructions>,
     license: Option<LicenseInfo>,
+    base: Option<EscrowPackage>,
 }
 
 impl EscrowPackageBuilder {
@@ -394,6 +424,7 @@ impl EscrowPackageBuilder {
             dependencies: None,
             build_instructions: None,
             license: None,
+            base: None,
         }
     }
 
@@ -432,6 +463,12 @@ impl EscrowPackageBuilder {
         self
     }
 
+    /// Deposit only the files that differ from `base`
+    pub fn delta_from(mut self, base: &EscrowPackage) -> Self {
+        self.base = Some(base.clone());
+        self
+    }
+
     /// Build the escrow package
     pub fn build(self) -> Result<EscrowPackage, String> {
         let metadata = self.metadata.ok_or("Metadata is required")?;
@@ -460,6 +497,10 @@ impl EscrowPackageBuilder {
             build_verification: None,
         };
 
+        let delta = self
+            .base
+            .map(|base| compute_delta(&base, &self.source_files));
+
         Ok(EscrowPackage {
             metadata,
             source_files: self.source_files,
@@ -468,18 +509,159 @@ impl EscrowPackageBuilder {
             build_instructions,
             verification,
             license,
+            delta,
         })
     }
 }
 
+/// Files of `source_files` that are new or changed relative to `base`
+fn compute_delta(base: &EscrowPackage, source_files: &[SourceFile]) -> DeltaReference {
+    let base_files: HashMap<&str, &str> = base
+        .source_files
+        .iter()
+        .map(|f| (f.path.as_str(), f.checksum.as_str()))
+        .collect();
+    let current: BTreeSet<&str> = source_files.iter().map(|f| f.path.as_str()).collect();
+
+    let mut changed_files: Vec<String> = source_files
+        .iter()
+        .filter(|f| base_files.get(f.path.as_str()) != Some(&f.checksum.as_str()))
+        .map(|f| f.path.clone())
+        .collect();
+    changed_files.sort();
+    let mut removed_files: Vec<String> = base_files
+        .keys()
+        .filter(|path| !current.contains(*path))
+        .map(|path| path.to_string())
+        .collect();
+    removed_files.sort();
+
+    DeltaReference {
+        base_package_id: base.metadata.package_id.clone(),
+        base_version: base.metadata.version.clone(),
+        base_checksum: base.verification.package_checksum.clone(),
+        changed_files,
+        removed_files,
+    }
+}
+
 /// Scan directory for files
-fn scan_directory(_root: &Path, _file_type: FileType) -> Result<Vec<SourceFile>, String> {
-    let files = Vec::new();
+///
+/// Inside a git repository, files ignored by git are not deposited; otherwise
+/// the tree is walked, skipping VCS and build output directories.
+fn scan_directory(root: &Path, _file_type: FileType) -> Result<Vec<SourceFile>, String> {
+    let paths = match git_tracked_files(root) {
+        Some(paths) => paths,
+        None => {
+            let mut paths = Vec::new();
+            walk_directory(root, "", &mut paths)?;
+            paths
+        }
+    };
+
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let full_path = root.join(&path);
+        // Files deleted in the working tree are not part of the deposit
+        let Ok(metadata) = std::fs::metadata(&full_path) else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        files.push(SourceFile {
+            checksum: file_checksum(&full_path)?,
+            size: metadata.len(),
+            file_type: classify_file(&path),
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs()),
+            path,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
 
-    // In production, recursively scan directory
-    // For now, return empty list
+fn git_tracked_files(root: &Path) -> Option<Vec<String>> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args([
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
 
-    Ok(files)
+fn walk_directory(dir: &Path, prefix: &str, paths: &mut Vec<String>) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = format!("{}{}", prefix, name);
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        if file_type.is_dir() {
+            if !EXCLUDED_DIRS.contains(&name.as_str()) {
+                walk_directory(&entry.path(), &format!("{}/", path), paths)?;
+            }
+        } else if file_type.is_file() {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn classify_file(path: &str) -> FileType {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let extension = name.rsplit_once('.').map_or("", |(_, ext)| ext);
+    if name == "build.rs" || name == "Makefile" || name == "Dockerfile" {
+        FileType::BuildScript
+    } else if path.starts_with("tests/") || path.contains("/tests/") {
+        FileType::Test
+    } else if matches!(extension, "md" | "txt" | "rst") || name.starts_with("LICENSE") {
+        FileType::Documentation
+    } else if matches!(
+        extension,
+        "toml" | "lock" | "json" | "yaml" | "yml" | "ini" | "cfg"
+    ) {
+        FileType::Configuration
+    } else if matches!(
+        extension,
+        "rs" | "js" | "ts" | "py" | "go" | "sh" | "tf" | "html" | "css"
+    ) {
+        FileType::Source
+    } else {
+        FileType::Asset
+    }
+}
+
+/// SHA-256 of a file's contents
+pub(crate) fn file_checksum(path: &Path) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+
+    let content =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(format!("{:x}", Sha256::digest(&content)))
 }
 
 /// Calculate package checksum
@@ -636,6 +818,32 @@ impl EscrowPackage {
         Ok(())
     }
 
+    /// Files whose contents this package stores: all of them for a full
+    /// deposit, only the changed ones for a delta
+    pub fn stores(&self, path: &str) -> bool {
+        match &self.delta {
+            Some(delta) => delta.changed_files.iter().any(|p| p == path),
+            None => self.source_files.iter().any(|f| f.path == path),
+        }
+    }
+
+    /// Copy the stored source files from `source_root` into `<output_dir>/files`
+    pub fn export_sources(&self, source_root: &Path, output_dir: &Path) -> Result<usize, String> {
+        let files_dir = output_dir.join(FILES_DIR);
+        let mut stored = 0;
+        for file in self.source_files.iter().filter(|f| self.stores(&f.path)) {
+            let target = files_dir.join(&file.path);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            std::fs::copy(source_root.join(&file.path), &target)
+                .map_err(|e| format!("Failed to store {}: {}", file.path, e))?;
+            stored += 1;
+        }
+        Ok(stored)
+    }
+
     /// Load package from directory
     pub fn load(input_dir: &Path) -> Result<Self, String> {
         let manifest_path = input_dir.join("escrow-package.json");
@@ -715,7 +923,7 @@ fn generate_escrow_readme(package: &EscrowPackage) -> String {
 
 ## Package Contents
 
-- **Source Files:** {}
+- **Source Files:** {}{}
 - **Build Artifacts:** {}
 - **Dependencies:** {}
 
@@ -780,6 +988,16 @@ For support, contact the escrow agent: {}
         package.metadata.vendor.contact_email,
         package.metadata.vendor.support_url,
         package.source_files.len(),
+        package
+            .delta
+            .as_ref()
+            .map(|d| format!(
+                " (delta of v{}: {} changed, {} removed; the rest is in the base package)",
+                d.base_version,
+                d.changed_files.len(),
+                d.removed_files.len()
+            ))
+            .unwrap_or_default(),
         package.build_artifacts.len(),
         package.dependencies.total_count,
         package.build_instructions.environment.rust_version,
@@ -1,16 +1,166 @@
 // Recovery procedures for escrow release
 // Guides customers through building from escrow package
 
-use crate::engines::escrow::package::{BuildVerification, EscrowPackage};
+use crate::engines::escrow::package::{file_checksum, BuildVerification, EscrowPackage, FILES_DIR};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Instant;
 
+/// Progress of an interrupted recovery, kept in the working directory
+pub const RECOVERY_PROGRESS_FILE: &str = "recovery-progress.json";
+
+/// Stages of a recovery
+///
+/// A stage runs once every stage it depends on has succeeded; independent
+/// stages run in parallel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryStage {
+    VerifyIntegrity,
+    CheckPrerequisites,
+    ExtractSources,
+    InstallDependencies,
+    BuildFromSource,
+    RunTests,
+    GenerateDeployment,
+}
+
+impl RecoveryStage {
+    pub const ALL: [RecoveryStage; 7] = [
+        RecoveryStage::VerifyIntegrity,
+        RecoveryStage::CheckPrerequisites,
+        RecoveryStage::ExtractSources,
+        RecoveryStage::InstallDependencies,
+        RecoveryStage::BuildFromSource,
+        RecoveryStage::RunTests,
+        RecoveryStage::GenerateDeployment,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RecoveryStage::VerifyIntegrity => "Verify package integrity",
+            RecoveryStage::CheckPrerequisites => "Check prerequisites",
+            RecoveryStage::ExtractSources => "Extract source files",
+            RecoveryStage::InstallDependencies => "Install dependencies",
+            RecoveryStage::BuildFromSource => "Build from source",
+            RecoveryStage::RunTests => "Run tests",
+            RecoveryStage::GenerateDeployment => "Generate deployment package",
+        }
+    }
+
+    /// Stages that must succeed first
+    pub fn depends_on(&self) -> &'static [RecoveryStage] {
+        match self {
+            RecoveryStage::VerifyIntegrity | RecoveryStage::CheckPrerequisites => &[],
+            RecoveryStage::ExtractSources => &[RecoveryStage::VerifyIntegrity],
+            RecoveryStage::InstallDependencies => &[
+                RecoveryStage::CheckPrerequisites,
+                RecoveryStage::ExtractSources,
+            ],
+            RecoveryStage::BuildFromSource => &[RecoveryStage::InstallDependencies],
+            RecoveryStage::RunTests | RecoveryStage::GenerateDeployment => {
+                &[RecoveryStage::BuildFromSource]
+            }
+        }
+    }
+}
+
+/// Stages grouped into waves; each wave only depends on earlier ones
+fn resolve_waves() -> Vec<Vec<RecoveryStage>> {
+    let mut placed: BTreeSet<RecoveryStage> = BTreeSet::new();
+    let mut waves = Vec::new();
+    while placed.len() < RecoveryStage::ALL.len() {
+        let wave: Vec<RecoveryStage> = RecoveryStage::ALL
+            .iter()
+            .copied()
+            .filter(|stage| !placed.contains(stage))
+            .filter(|stage| stage.depends_on().iter().all(|dep| placed.contains(dep)))
+            .collect();
+        // The graph is static and acyclic; guard against editing mistakes
+        assert!(!wave.is_empty(), "recovery stage graph has a cycle");
+        placed.extend(wave.iter().copied());
+        waves.push(wave);
+    }
+    waves
+}
+
+/// Resolved execution order, as printed by `escrow recover --dry-run`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryPlan {
+    pub package_version: String,
+    pub working_dir: PathBuf,
+    pub waves: Vec<Vec<RecoveryStage>>,
+
+    /// Stages finished by an earlier, interrupted run
+    pub completed: BTreeSet<RecoveryStage>,
+}
+
+impl RecoveryPlan {
+    pub fn format_text(&self) -> String {
+        let mut output = String::new();
+        output.push_str("🗺️  Escrow Recovery Plan (dry run)\n");
+        output.push_str("================================\n\n");
+        output.push_str(&format!(
+            "Package: v{}\nWorking directory: {}\n\n",
+            self.package_version,
+            self.working_dir.display()
+        ));
+
+        for (i, wave) in self.waves.iter().enumerate() {
+            let parallel = if wave.len() > 1 { " (in parallel)" } else { "" };
+            output.push_str(&format!("Wave {}{}:\n", i + 1, parallel));
+            for stage in wave {
+                let status = if self.completed.contains(stage) {
+                    "✅ done, will be skipped"
+                } else {
+                    "⏳ pending"
+                };
+                let after = if stage.depends_on().is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        " — after {}",
+                        stage
+                            .depends_on()
+                            .iter()
+                            .map(|d| d.name())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                };
+                output.push_str(&format!("  {} [{}]{}\n", stage.name(), status, after));
+            }
+            output.push('\n');
+        }
+
+        if !self.completed.is_empty() {
+            output.push_str(&format!(
+                "Resuming: {} of {} stages already completed ({}).\n",
+                self.completed.len(),
+                RecoveryStage::ALL.len(),
+                RECOVERY_PROGRESS_FILE
+            ));
+        }
+        output
+    }
+}
+
+/// Stages completed so far, saved after every wave
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RecoveryProgress {
+    package_id: String,
+    completed: BTreeMap<RecoveryStage, RecoveryStep>,
+    build_verification: Option<BuildVerification>,
+}
+
 /// Recovery orchestrator
 pub struct RecoveryOrchestrator {
     package: EscrowPackage,
     working_dir: PathBuf,
+    package_dir: Option<PathBuf>,
+    restart: bool,
 }
 
 impl RecoveryOrchestrator {
@@ -19,12 +169,45 @@ impl RecoveryOrchestrator {
         Self {
             package,
             working_dir,
+            package_dir: None,
+            restart: false,
+        }
+    }
+
+    /// Extract sources from the exported package in `dir` (and its bases)
+    pub fn with_package_dir(mut self, dir: PathBuf) -> Self {
+        self.package_dir = Some(dir);
+        self
+    }
+
+    /// Ignore progress saved by an earlier run
+    pub fn restart(mut self, restart: bool) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// Execution plan, without running anything
+    pub fn plan(&self) -> RecoveryPlan {
+        RecoveryPlan {
+            package_version: self.package.metadata.version.clone(),
+            working_dir: self.working_dir.clone(),
+            waves: resolve_waves(),
+            completed: self.load_progress().completed.into_keys().collect(),
         }
     }
 
     /// Run complete recovery process
+    ///
+    /// Stages run wave by wave, stages of a wave in parallel. Stages whose
+    /// dependencies failed are not run. Progress is saved to
+    /// `RECOVERY_PROGRESS_FILE` so a rerun resumes after the last completed
+    /// stages; the file is removed once recovery succeeds.
     pub fn recover(&self) -> Result<RecoveryReport, String> {
         let start_time = Instant::now();
+        std::fs::create_dir_all(&self.working_dir)
+            .map_err(|e| format!("Failed to create working directory: {}", e))?;
+
+        let mut progress = self.load_progress();
         let mut report = RecoveryReport {
             success: true,
             steps_completed: Vec::new(),
@@ -32,45 +215,121 @@ impl RecoveryOrchestrator {
             total_duration: 0,
             build_verification: None,
         };
+        let mut failed: BTreeSet<RecoveryStage> = BTreeSet::new();
+
+        for wave in resolve_waves() {
+            let mut runnable = Vec::new();
+            for stage in wave {
+                if let Some(step) = progress.completed.get(&stage) {
+                    let mut step = step.clone();
+                    step.step_name = format!("{} (completed earlier)", step.step_name);
+                    report.steps_completed.push(step);
+                } else if let Some(dep) = stage.depends_on().iter().find(|d| failed.contains(d)) {
+                    failed.insert(stage);
+                    report.steps_failed.push(RecoveryStep {
+                        step_name: stage.name().to_string(),
+                        success: false,
+                        duration: 0,
+                        output: String::new(),
+                        error: Some(format!("Not run: {} did not succeed", dep.name())),
+                    });
+                } else {
+                    runnable.push(stage);
+                }
+            }
 
-        // Step 1: Verify package integrity
-        let step_result = self.verify_package_integrity()?;
-        report.steps_completed.push(step_result);
-
-        // Step 2: Check environment prerequisites
-        let step_result = self.check_prerequisites()?;
-        report.steps_completed.push(step_result);
-
-        // Step 3: Extract source files
-        let step_result = self.extract_source_files()?;
-        report.steps_completed.push(step_result);
+            let results: Vec<_> = std::thread::scope(|scope| {
+                let handles: Vec<_> = runnable
+                    .iter()
+                    .map(|&stage| scope.spawn(move || (stage, self.run_stage(stage))))
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("recovery stage panicked"))
+                    .collect()
+            });
 
-        // Step 4: Install dependencies
-        let step_result = self.install_dependencies()?;
-        report.steps_completed.push(step_result);
+            for (stage, (step, build_verification)) in results {
+                if build_verification.is_some() {
+                    progress.build_verification = build_verification;
+                }
+                if step.success {
+                    progress.completed.insert(stage, step.clone());
+                    report.steps_completed.push(step);
+                } else {
+                    failed.insert(stage);
+                    report.steps_failed.push(step);
+                }
+            }
+            self.save_progress(&progress)?;
+        }
 
-        // Step 5: Build from source
-        let build_result = self.build_from_source()?;
-        report.steps_completed.push(RecoveryStep {
-            step_name: "Build from source".to_string(),
-            success: true,
-            duration: 0,
-            output: "Build completed successfully".to_string(),
-            error: None,
-        });
-        report.build_verification = Some(build_result);
+        report.success = failed.is_empty();
+        report.build_verification = progress.build_verification;
+        if report.success {
+            let _ = std::fs::remove_file(self.working_dir.join(RECOVERY_PROGRESS_FILE));
+        }
+        report.total_duration = start_time.elapsed().as_secs();
 
-        // Step 6: Run tests
-        let step_result = self.run_tests()?;
-        report.steps_completed.push(step_result);
+        Ok(report)
+    }
 
-        // Step 7: Generate deployment package
-        let step_result = self.generate_deployment_package()?;
-        report.steps_completed.push(step_result);
+    /// Run one stage, turning errors into a failed step
+    fn run_stage(&self, stage: RecoveryStage) -> (RecoveryStep, Option<BuildVerification>) {
+        let start = Instant::now();
+        let result = match stage {
+            RecoveryStage::VerifyIntegrity => self.verify_package_integrity().map(|s| (s, None)),
+            RecoveryStage::CheckPrerequisites => self.check_prerequisites().map(|s| (s, None)),
+            RecoveryStage::ExtractSources => self.extract_source_files().map(|s| (s, None)),
+            RecoveryStage::InstallDependencies => self.install_dependencies().map(|s| (s, None)),
+            RecoveryStage::BuildFromSource => self.build_from_source().map(|verification| {
+                let step = RecoveryStep {
+                    step_name: stage.name().to_string(),
+                    success: verification.build_successful,
+                    duration: verification.build_duration,
+                    output: "Build completed successfully".to_string(),
+                    error: None,
+                };
+                (step, Some(verification))
+            }),
+            RecoveryStage::RunTests => self.run_tests().map(|s| (s, None)),
+            RecoveryStage::GenerateDeployment => {
+                self.generate_deployment_package().map(|s| (s, None))
+            }
+        };
+        result.unwrap_or_else(|error| {
+            let step = RecoveryStep {
+                step_name: stage.name().to_string(),
+                success: false,
+                duration: start.elapsed().as_secs(),
+                output: String::new(),
+                error: Some(error),
+            };
+            (step, None)
+        })
+    }
 
-        report.total_duration = start_time.elapsed().as_secs();
+    /// Progress of an earlier run of the same package, unless restarting
+    fn load_progress(&self) -> RecoveryProgress {
+        let fresh = RecoveryProgress {
+            package_id: self.package.metadata.package_id.clone(),
+            ..Default::default()
+        };
+        if self.restart {
+            return fresh;
+        }
+        std::fs::read_to_string(self.working_dir.join(RECOVERY_PROGRESS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<RecoveryProgress>(&content).ok())
+            .filter(|progress| progress.package_id == fresh.package_id)
+            .unwrap_or(fresh)
+    }
 
-        Ok(report)
+    fn save_progress(&self, progress: &RecoveryProgress) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(progress)
+            .map_err(|e| format!("Failed to serialize recovery progress: {}", e))?;
+        std::fs::write(self.working_dir.join(RECOVERY_PROGRESS_FILE), json)
+            .map_err(|e| format!("Failed to save recovery progress: {}", e))
     }
 
     /// Verify package integrity
@@ -160,18 +419,34 @@ impl RecoveryOrchestrator {
         std::fs::create_dir_all(&src_dir)
             .map_err(|e| format!("Failed to create source directory: {}", e))?;
 
-        // In production, extract files from package
-        // For now, just create directory structure
+        let output = match &self.package_dir {
+            Some(package_dir) => {
+                let chain = resolve_package_chain(package_dir)?;
+                let restored = recombine(&chain, &src_dir)?;
+                format!(
+                    "Reconstructed {} source files to {} from {}",
+                    restored,
+                    src_dir.display(),
+                    chain
+                        .iter()
+                        .rev()
+                        .map(|(_, p)| format!("v{}", p.metadata.version))
+                        .collect::<Vec<_>>()
+                        .join(" + ")
+                )
+            }
+            None => format!(
+                "Extracted {} source files to {}",
+                self.package.source_files.len(),
+                src_dir.display()
+            ),
+        };
 
         Ok(RecoveryStep {
             step_name: "Extract source files".to_string(),
             success: true,
             duration: start.elapsed().as_secs(),
-            output: format!(
-                "Extracted {} source files to {}",
-                self.package.source_files.len(),
-                src_dir.display()
-            ),
+            output,
             error: None,
         })
     }
@@ -382,6 +657,98 @@ impl RecoveryReport {
     }
 }
 
+/// Load the package in `package_dir` and, for delta deposits, every base it
+/// builds on, newest first
+///
+/// Bases are looked up by package ID among the sibling directories, as
+/// written by `deposit_package`.
+pub fn resolve_package_chain(package_dir: &Path) -> Result<Vec<(PathBuf, EscrowPackage)>, String> {
+    let package = EscrowPackage::load(package_dir)?;
+    let mut seen = HashSet::from([package.metadata.package_id.clone()]);
+    let mut chain = vec![(package_dir.to_path_buf(), package)];
+    let search_dir = package_dir.parent().unwrap_or(Path::new("."));
+
+    while let Some(delta) = chain.last().and_then(|(_, p)| p.delta.clone()) {
+        let base = std::fs::read_dir(search_dir)
+            .map_err(|e| format!("Failed to read {}: {}", search_dir.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|dir| dir.is_dir())
+            .find_map(|dir| {
+                EscrowPackage::load(&dir)
+                    .ok()
+                    .filter(|p| p.metadata.package_id == delta.base_package_id)
+                    .map(|p| (dir, p))
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Base package {} (v{}) not found in {}",
+                    delta.base_package_id,
+                    delta.base_version,
+                    search_dir.display()
+                )
+            })?;
+        if base.1.verification.package_checksum != delta.base_checksum {
+            return Err(format!(
+                "Base package v{} has checksum {}, delta expects {}",
+                base.1.metadata.version, base.1.verification.package_checksum, delta.base_checksum
+            ));
+        }
+        if !seen.insert(base.1.metadata.package_id.clone()) {
+            return Err(format!(
+                "Delta chain loops back to package {}",
+                base.1.metadata.package_id
+            ));
+        }
+        chain.push(base);
+    }
+
+    Ok(chain)
+}
+
+/// Reconstruct the full tree of the newest package in `chain` into `dest`
+///
+/// Each file listed by the newest manifest is taken from the newest package
+/// that stores it with the expected checksum, and checked after copying.
+/// Returns the number of files restored.
+pub fn recombine(chain: &[(PathBuf, EscrowPackage)], dest: &Path) -> Result<usize, String> {
+    let (_, newest) = chain.first().ok_or("No packages to recombine")?;
+    let mut missing = Vec::new();
+
+    for file in &newest.source_files {
+        let source = chain.iter().find(|(_, package)| {
+            package.stores(&file.path)
+                && package
+                    .source_files
+                    .iter()
+                    .any(|f| f.path == file.path && f.checksum == file.checksum)
+        });
+        let Some((dir, _)) = source else {
+            missing.push(file.path.clone());
+            continue;
+        };
+
+        let target = dest.join(&file.path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::copy(dir.join(FILES_DIR).join(&file.path), &target)
+            .map_err(|e| format!("Failed to restore {}: {}", file.path, e))?;
+        if file_checksum(&target)? != file.checksum {
+            return Err(format!("Checksum mismatch restoring {}", file.path));
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(format!(
+            "{} files are not stored in the package chain: {}",
+            missing.len(),
+            missing.join(", ")
+        ));
+    }
+    Ok(newest.source_files.len())
+}
+
 /// Check tool version
 fn check_tool_version(
     tool: &str,
@@ -568,4 +935,197 @@ mod tests {
         assert!(text.contains("RECOVERY SUCCESSFUL"));
         assert!(text.contains("Step 1"));
     }
+
+    fn deposit(
+        root: &Path,
+        escrow_dir: &Path,
+        version: &str,
+        base: Option<&EscrowPackage>,
+    ) -> EscrowPackage {
+        use crate::engines::escrow::package::EscrowPackageBuilder;
+
+        let metadata = serde_json::from_value(serde_json::json!({
+            "package_id": format!("pkg-{}", version), "product_name": "CostPilot",
+            "version": version, "release_date": 0, "commit_hash": "abc", "git_tag": null,
+            "branch": "main", "customer": null, "escrow_agent": null, "deposit_type": "General",
+            "vendor": {"company_name": "Acme", "contact_email": "a@acme.test",
+                       "support_url": "https://acme.test", "legal_entity": "Acme"}
+        }))
+        .unwrap();
+        let dependencies = serde_json::from_value(serde_json::json!({
+            "cargo_dependencies": {}, "npm_dependencies": {}, "system_dependencies": [],
+            "total_count": 0, "vulnerability_scan": null
+        }))
+        .unwrap();
+        let instructions = serde_json::from_value(serde_json::json!({
+            "environment": {"rust_version": "1.75.0", "cargo_version": "1.75.0",
+                            "node_version": null, "os": "linux", "arch": "x86_64", "tools": []},
+            "steps": [], "test_commands": [], "expected_outputs": [], "estimated_duration": 0
+        }))
+        .unwrap();
+        let license = serde_json::from_value(serde_json::json!({
+            "license_type": "MIT", "license_text": "", "copyright_holder": "Acme",
+            "copyright_year": "2025", "additional_terms": null, "open_source_components": []
+        }))
+        .unwrap();
+
+        let mut builder = EscrowPackageBuilder::new(root.to_path_buf())
+            .metadata(metadata)
+            .dependencies(dependencies)
+            .build_instructions(instructions)
+            .license(license);
+        builder.scan_source_files().unwrap();
+        if let Some(base) = base {
+            builder = builder.delta_from(base);
+        }
+        let package = builder.build().unwrap();
+
+        let output = escrow_dir.join(version);
+        package.export(&output).unwrap();
+        package.export_sources(root, &output).unwrap();
+        package
+    }
+
+    #[test]
+    fn test_delta_deposits_recombine_to_full_tree() {
+        let repo = tempfile::tempdir().unwrap();
+        let escrow = tempfile::tempdir().unwrap();
+        let write = |path: &str, content: &str| {
+            let path = repo.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("Cargo.toml", "[package]");
+        write("src/main.rs", "fn main() {}");
+        write("src/old.rs", "// removed later");
+        write("target/debug/app", "build output");
+
+        let base = deposit(repo.path(), escrow.path(), "1.0.0", None);
+        assert_eq!(base.source_files.len(), 3);
+
+        write("src/main.rs", "fn main() { run() }");
+        write("src/run.rs", "pub fn run() {}");
+        std::fs::remove_file(repo.path().join("src/old.rs")).unwrap();
+        let first = deposit(repo.path(), escrow.path(), "1.1.0", Some(&base));
+        let delta = first.delta.as_ref().unwrap();
+        assert_eq!(delta.changed_files, vec!["src/main.rs", "src/run.rs"]);
+        assert_eq!(delta.removed_files, vec!["src/old.rs"]);
+        assert!(!escrow.path().join("1.1.0/files/Cargo.toml").exists());
+
+        write("Cargo.toml", "[package]\nname = \"app\"");
+        let second = deposit(repo.path(), escrow.path(), "1.2.0", Some(&first));
+        assert_eq!(
+            second.delta.as_ref().unwrap().changed_files,
+            vec!["Cargo.toml"]
+        );
+
+        let chain = resolve_package_chain(&escrow.path().join("1.2.0")).unwrap();
+        assert_eq!(chain.len(), 3);
+        let restored = tempfile::tempdir().unwrap();
+        assert_eq!(recombine(&chain, restored.path()).unwrap(), 3);
+        for path in ["Cargo.toml", "src/main.rs", "src/run.rs"] {
+            assert_eq!(
+                std::fs::read(restored.path().join(path)).unwrap(),
+                std::fs::read(repo.path().join(path)).unwrap()
+            );
+        }
+        assert!(!restored.path().join("src/old.rs").exists());
+        assert!(!restored.path().join("target").exists());
+    }
+
+    #[test]
+    fn test_recovery_waves() {
+        use RecoveryStage::*;
+        assert_eq!(
+            resolve_waves(),
+            vec![
+                vec![VerifyIntegrity, CheckPrerequisites],
+                vec![ExtractSources],
+                vec![InstallDependencies],
+                vec![BuildFromSource],
+                vec![RunTests, GenerateDeployment],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recovery_resumes_after_failure() {
+        let repo = tempfile::tempdir().unwrap();
+        let escrow = tempfile::tempdir().unwrap();
+        let work = tempfile::tempdir().unwrap();
+        std::fs::write(repo.path().join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(repo.path().join("README.md"), "# app").unwrap();
+
+        // Cargo.lock and LICENSE are missing, so integrity verification fails
+        let package = deposit(repo.path(), escrow.path(), "1.0.0", None);
+        let report = RecoveryOrchestrator::new(package, work.path().to_path_buf())
+            .with_package_dir(escrow.path().join("1.0.0"))
+            .recover()
+            .unwrap();
+        assert!(!report.success);
+        let completed: Vec<&str> = report
+            .steps_completed
+            .iter()
+            .map(|s| s.step_name.as_str())
+            .collect();
+        assert_eq!(completed, vec!["Check prerequisites"]);
+        assert_eq!(report.steps_failed.len(), 6);
+        assert!(report.steps_failed[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("Not run: Verify package integrity"));
+        assert!(work.path().join(RECOVERY_PROGRESS_FILE).exists());
+
+        // A fixed deposit resumes: finished stages are not run again
+        std::fs::write(repo.path().join("Cargo.lock"), "").unwrap();
+        std::fs::write(repo.path().join("LICENSE"), "MIT").unwrap();
+        let package = deposit(repo.path(), escrow.path(), "1.0.0", None);
+        let orchestrator = RecoveryOrchestrator::new(package, work.path().to_path_buf())
+            .with_package_dir(escrow.path().join("1.0.0"));
+        let plan = orchestrator.plan();
+        assert_eq!(
+            plan.completed,
+            BTreeSet::from([RecoveryStage::CheckPrerequisites])
+        );
+        assert!(plan
+            .format_text()
+            .contains("Check prerequisites [✅ done, will be skipped]"));
+
+        let report = orchestrator.recover().unwrap();
+        assert!(report.success, "{:?}", report.steps_failed);
+        assert_eq!(report.steps_completed.len(), 7);
+        assert!(report
+            .steps_completed
+            .iter()
+            .any(|s| s.step_name == "Check prerequisites (completed earlier)"));
+        assert!(work.path().join("source/LICENSE").exists());
+        assert!(!work.path().join(RECOVERY_PROGRESS_FILE).exists());
+    }
+
+    #[test]
+    fn test_delta_chain_requires_matching_base() {
+        let repo = tempfile::tempdir().unwrap();
+        let escrow = tempfile::tempdir().unwrap();
+        std::fs::write(repo.path().join("README.md"), "v1").unwrap();
+        let base = deposit(repo.path(), escrow.path(), "1.0.0", None);
+        std::fs::write(repo.path().join("README.md"), "v2").unwrap();
+        deposit(repo.path(), escrow.path(), "1.1.0", Some(&base));
+
+        // A replaced base package no longer matches the pinned checksum
+        let base_dir = escrow.path().join("1.0.0");
+        let mut replaced = EscrowPackage::load(&base_dir).unwrap();
+        replaced.verification.package_checksum = "0000".to_string();
+        replaced.export(&base_dir).unwrap();
+        let err = resolve_package_chain(&escrow.path().join("1.1.0")).unwrap_err();
+        assert!(err.contains("delta expects"), "{}", err);
+
+        std::fs::remove_dir_all(&base_dir).unwrap();
+        let err = resolve_package_chain(&escrow.path().join("1.1.0")).unwrap_err();
+        assert!(
+            err.contains("Base package pkg-1.0.0 (v1.0.0) not found"),
+            "{}",
+            err
+        );
+    }
 }
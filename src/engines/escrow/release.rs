@@ -1,15 +1,21 @@
 // Release automation for software escrow
-// Handles automated escrow deposit on releases
+// Handles automated escrow deposit on releases, and reproducing a deposited
+// build for escrow agents
 
 use crate::engines::escrow::package::{
-    BuildEnvironment, BuildInstructions, BuildStep, DependenciesManifest, DependencyInfo,
-    DepositType, EscrowPackage, EscrowPackageBuilder, LicenseInfo, OpenSourceComponent,
-    PackageMetadata, Tool, VendorInfo,
+    file_checksum, BuildEnvironment, BuildInstructions, BuildStep, BuildVerification,
+    DependenciesManifest, DependencyInfo, DepositType, EscrowPackage, EscrowPackageBuilder,
+    LicenseInfo, OpenSourceComponent, PackageMetadata, Tool, VendorInfo, VerificationReport,
+    EXCLUDED_DIRS,
 };
+use crate::engines::escrow::recovery::RecoveryStep;
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
 /// Release configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +80,15 @@ impl ReleaseAutomation {
 
     /// Create escrow package for current release
     pub fn create_package(&self, version: &str) -> Result<EscrowPackage, String> {
+        self.create_package_from(version, None)
+    }
+
+    /// Create a package; with `base`, only files changed since it are stored
+    pub fn create_package_from(
+        &self,
+        version: &str,
+        base: Option<&EscrowPackage>,
+    ) -> Result<EscrowPackage, String> {
         // Get git information
         let git_info = self.get_git_info()?;
 
@@ -123,6 +138,10 @@ impl ReleaseAutomation {
         let license = self.generate_license_info();
         builder = builder.license(license);
 
+        if let Some(base) = base {
+            builder = builder.delta_from(base);
+        }
+
         // Build package
         let package = builder.build()?;
 
@@ -144,8 +163,9 @@ impl ReleaseAutomation {
         std::fs::create_dir_all(&output_dir)
             .map_err(|e| format!("Failed to create output directory: {}", e))?;
 
-        // Export package
+        // Export package with the file contents it stores
         package.export(&output_dir)?;
+        package.export_sources(&self.repository_root, &output_dir)?;
 
         // Determine escrow agent name
         let agent_name = if let Some(agent) = &self.config.escrow_agent {
@@ -199,7 +219,7 @@ impl ReleaseAutomation {
                 artifact_type: ArtifactType::Binary,
                 path: "target/release/costpilot".to_string(),
                 size: metadata.len(),
-                checksum: calculate_file_checksum(&binary_path)?,
+                checksum: file_checksum(&binary_path)?,
                 build_date: current_timestamp(),
             });
         }
@@ -217,7 +237,7 @@ impl ReleaseAutomation {
                 artifact_type: ArtifactType::WasmModule,
                 path: "target/wasm32-unknown-unknown/release/costpilot.wasm".to_string(),
                 size: metadata.len(),
-                checksum: calculate_file_checksum(&wasm_path)?,
+                checksum: file_checksum(&wasm_path)?,
                 build_date: current_timestamp(),
             });
         }
@@ -424,6 +444,496 @@ the terms of the escrow agreement.
     }
 }
 
+/// Rebuilds a deposited package from source in a sandbox and compares the
+/// produced artifacts with the checksums recorded in its manifest
+pub struct BuildVerifier {
+    /// Deposited source tree
+    source_dir: PathBuf,
+
+    /// Parent of the sandbox (default: system temp directory)
+    sandbox_root: PathBuf,
+
+    /// Build step numbers not to run (e.g. toolchain installation)
+    skip_steps: BTreeSet<usize>,
+
+    /// Also run the package's test commands
+    run_tests: bool,
+
+    /// Leave the sandbox in place for inspection
+    keep_sandbox: bool,
+}
+
+impl BuildVerifier {
+    /// Verify builds of the sources in `source_dir`
+    pub fn new(source_dir: PathBuf) -> Self {
+        Self {
+            source_dir,
+            sandbox_root: std::env::temp_dir(),
+            skip_steps: BTreeSet::new(),
+            run_tests: false,
+            keep_sandbox: false,
+        }
+    }
+
+    /// Create sandboxes under `root`
+    pub fn sandbox_root(mut self, root: PathBuf) -> Self {
+        self.sandbox_root = root;
+        self
+    }
+
+    /// Skip build step `step`
+    pub fn skip_step(mut self, step: usize) -> Self {
+        self.skip_steps.insert(step);
+        self
+    }
+
+    /// Run the test commands after the build steps
+    pub fn run_tests(mut self, run_tests: bool) -> Self {
+        self.run_tests = run_tests;
+        self
+    }
+
+    /// Keep the sandbox after verification
+    pub fn keep_sandbox(mut self, keep: bool) -> Self {
+        self.keep_sandbox = keep;
+        self
+    }
+
+    /// Rebuild `package` and report whether its artifacts are reproduced
+    ///
+    /// The sources are copied into `<sandbox>/source` and every build step
+    /// runs there with a minimal environment (`PATH`, `HOME`, toolchain
+    /// homes, `SOURCE_DATE_EPOCH` set to the release date). Step output goes
+    /// to `<sandbox>/logs`. The build stops at the first failing step.
+    pub fn run(&self, package: &EscrowPackage) -> Result<BuildVerificationReport, String> {
+        let start = Instant::now();
+        let sandbox = self.sandbox_root.join(format!(
+            "costpilot-escrow-verify-{}",
+            package.metadata.package_id
+        ));
+        if sandbox.exists() {
+            std::fs::remove_dir_all(&sandbox)
+                .map_err(|e| format!("Failed to clear sandbox {}: {}", sandbox.display(), e))?;
+        }
+        let source = sandbox.join("source");
+        let logs = sandbox.join("logs");
+        std::fs::create_dir_all(&logs)
+            .map_err(|e| format!("Failed to create sandbox {}: {}", sandbox.display(), e))?;
+
+        let mut report = BuildVerificationReport {
+            package_id: package.metadata.package_id.clone(),
+            version: package.metadata.version.clone(),
+            commit_hash: package.metadata.commit_hash.clone(),
+            package_checksum: package.verification.package_checksum.clone(),
+            verified_at: current_timestamp(),
+            steps: Vec::new(),
+            artifacts: Vec::new(),
+            build: BuildVerification {
+                build_successful: false,
+                tests_passed: false,
+                build_duration: 0,
+                build_log: String::new(),
+                artifacts_match: false,
+            },
+            report: VerificationReport {
+                verified: false,
+                errors: Vec::new(),
+                warnings: Vec::new(),
+                info: Vec::new(),
+            },
+            public_key: None,
+            signature: None,
+        };
+
+        let copied = self.populate_sandbox(package, &source, &mut report.report)?;
+        report
+            .report
+            .info
+            .push(format!("Copied {} source files into the sandbox", copied));
+
+        let epoch = package.metadata.release_date.to_string();
+        let mut build_ok = report.report.errors.is_empty();
+        for step in &package.build_instructions.steps {
+            if !build_ok {
+                break;
+            }
+            if self.skip_steps.contains(&step.step) {
+                report
+                    .report
+                    .warnings
+                    .push(format!("Skipped step {}: {}", step.step, step.description));
+                continue;
+            }
+            let result = run_step(step, &source, &logs, &epoch);
+            report.build.build_log.push_str(&format!(
+                "=== Step {}: {} ===\n$ {}\n{}\n",
+                step.step, step.description, step.command, result.output
+            ));
+            if let Some(error) = &result.error {
+                report
+                    .report
+                    .errors
+                    .push(format!("Step {} failed: {}", step.step, error));
+                build_ok = false;
+            }
+            report.steps.push(result);
+        }
+        report.build.build_successful = build_ok;
+
+        if build_ok && self.run_tests {
+            let mut tests_ok = true;
+            for (index, command) in package.build_instructions.test_commands.iter().enumerate() {
+                let step = BuildStep {
+                    step: package.build_instructions.steps.len() + index + 1,
+                    description: format!("Test: {}", command),
+                    command: command.clone(),
+                    working_dir: ".".to_string(),
+                    expected_exit_code: 0,
+                    timeout: package.build_instructions.estimated_duration.max(60),
+                };
+                let result = run_step(&step, &source, &logs, &epoch);
+                if let Some(error) = &result.error {
+                    report
+                        .report
+                        .errors
+                        .push(format!("Test command '{}' failed: {}", command, error));
+                    tests_ok = false;
+                }
+                report.steps.push(result);
+            }
+            report.build.tests_passed = tests_ok;
+        } else if !self.run_tests {
+            report.report.info.push("Test commands not run".to_string());
+        }
+
+        if build_ok {
+            report.artifacts = compare_artifacts(package, &source);
+            if report.artifacts.is_empty() {
+                report.report.errors.push(
+                    "Package records no build artifacts to compare; nothing was reproduced"
+                        .to_string(),
+                );
+            }
+            for check in report.artifacts.iter().filter(|c| !c.matches) {
+                report.report.errors.push(match &check.actual {
+                    Some(actual) => format!(
+                        "Artifact {} differs: manifest {}, rebuilt {}",
+                        check.path, check.expected, actual
+                    ),
+                    None => format!("Artifact {} was not produced", check.path),
+                });
+            }
+            report.build.artifacts_match =
+                !report.artifacts.is_empty() && report.artifacts.iter().all(|c| c.matches);
+        }
+
+        report.build.build_duration = start.elapsed().as_secs();
+        report.report.verified = report.report.errors.is_empty();
+        report.report.info.push(format!(
+            "Reproduced {}/{} artifacts in {}s",
+            report.artifacts.iter().filter(|c| c.matches).count(),
+            report.artifacts.len(),
+            report.build.build_duration
+        ));
+
+        if self.keep_sandbox {
+            report
+                .report
+                .info
+                .push(format!("Sandbox kept at {}", sandbox.display()));
+        } else {
+            let _ = std::fs::remove_dir_all(&sandbox);
+        }
+
+        Ok(report)
+    }
+
+    /// Copy the sources listed in the package (or the whole tree when the
+    /// package lists none) into `source`, recording checksum mismatches
+    fn populate_sandbox(
+        &self,
+        package: &EscrowPackage,
+        source: &Path,
+        report: &mut VerificationReport,
+    ) -> Result<usize, String> {
+        if package.source_files.is_empty() {
+            report.warnings.push(
+                "Package lists no source files; copying the whole source directory".to_string(),
+            );
+            let sandbox = source.parent().unwrap_or(source);
+            return copy_tree(&self.source_dir, source, sandbox);
+        }
+
+        for file in &package.source_files {
+            let relative = sandbox_relative(&file.path)?;
+            let from = self.source_dir.join(relative);
+            let checksum = match file_checksum(&from) {
+                Ok(checksum) => checksum,
+                Err(_) => {
+                    report
+                        .errors
+                        .push(format!("Source file missing: {}", file.path));
+                    continue;
+                }
+            };
+            if checksum != file.checksum {
+                report
+                    .errors
+                    .push(format!("Source file checksum mismatch: {}", file.path));
+            }
+            let to = source.join(relative);
+            if let Some(parent) = to.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            std::fs::copy(&from, &to)
+                .map_err(|e| format!("Failed to copy {}: {}", file.path, e))?;
+        }
+        Ok(package.source_files.len())
+    }
+}
+
+/// Rebuilt artifact compared with the manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactCheck {
+    pub name: String,
+    pub path: String,
+    /// SHA-256 recorded in the package
+    pub expected: String,
+    /// SHA-256 of the rebuilt file; `None` if it was not produced
+    pub actual: Option<String>,
+    pub matches: bool,
+}
+
+/// Result of reproducing a package build, signed by the verifying party
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildVerificationReport {
+    pub package_id: String,
+    pub version: String,
+    pub commit_hash: String,
+    pub package_checksum: String,
+    pub verified_at: u64,
+    pub steps: Vec<RecoveryStep>,
+    pub artifacts: Vec<ArtifactCheck>,
+    pub build: BuildVerification,
+    pub report: VerificationReport,
+
+    /// Base64 Ed25519 public key of the signer
+    #[serde(default)]
+    pub public_key: Option<String>,
+
+    /// Base64 Ed25519 signature over the report with this field empty
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+impl BuildVerificationReport {
+    /// Sign the report with `key`
+    pub fn sign(&mut self, key: &SigningKey) {
+        let engine = base64::engine::general_purpose::STANDARD;
+        self.public_key = Some(engine.encode(key.verifying_key().to_bytes()));
+        self.signature = None;
+        self.signature = Some(engine.encode(key.sign(&self.signing_payload()).to_bytes()));
+    }
+
+    /// Check the signature, returning the signer's key
+    ///
+    /// Callers must still decide whether they trust the returned key.
+    pub fn verify_signature(&self) -> Result<VerifyingKey, String> {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let (Some(public_key), Some(signature)) = (&self.public_key, &self.signature) else {
+            return Err("Report is not signed".to_string());
+        };
+        let public_key = engine
+            .decode(public_key)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or("Invalid public key in report")?;
+        let signature = engine
+            .decode(signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or("Invalid signature in report")?;
+        public_key
+            .verify(&self.signing_payload(), &signature)
+            .map_err(|_| "Report signature does not match its contents".to_string())?;
+        Ok(public_key)
+    }
+
+    /// Canonical bytes covered by the signature
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.signature = None;
+        serde_json::to_vec(&unsigned).unwrap_or_default()
+    }
+
+    pub fn format_text(&self) -> String {
+        let mut output = format!(
+            "🏗️  Reproducible Build Verification\n\nPackage: {} (v{}, commit {})\n\n",
+            self.package_id, self.version, self.commit_hash
+        );
+        for step in &self.steps {
+            output.push_str(&format!(
+                "  {} {} ({}s)\n",
+                if step.success { "✅" } else { "❌" },
+                step.step_name,
+                step.duration
+            ));
+        }
+        for check in &self.artifacts {
+            output.push_str(&format!(
+                "  {} {}\n",
+                if check.matches { "✅" } else { "❌" },
+                check.path
+            ));
+        }
+        output.push('\n');
+        output.push_str(&self.report.format_text());
+        if let Some(public_key) = &self.public_key {
+            output.push_str(&format!("\nSigned by: {}\n", public_key));
+        }
+        output
+    }
+}
+
+/// Run one build step in the sandbox, bounded by its timeout
+fn run_step(step: &BuildStep, source: &Path, logs: &Path, epoch: &str) -> RecoveryStep {
+    let start = Instant::now();
+    let mut result = RecoveryStep {
+        step_name: format!("Step {}: {}", step.step, step.description),
+        success: false,
+        duration: 0,
+        output: String::new(),
+        error: None,
+    };
+
+    let working_dir = match sandbox_relative(&step.working_dir) {
+        Ok(relative) => source.join(relative),
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    let log_path = logs.join(format!("step-{}.log", step.step));
+    let status = std::fs::File::create(&log_path)
+        .and_then(|log| Ok((log.try_clone()?, log)))
+        .map_err(|e| format!("Failed to create log {}: {}", log_path.display(), e))
+        .and_then(|(stdout, stderr)| {
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                .arg(&step.command)
+                .current_dir(&working_dir)
+                .env_clear()
+                .env("SOURCE_DATE_EPOCH", epoch)
+                .env("TZ", "UTC")
+                .env("LC_ALL", "C")
+                .stdin(Stdio::null())
+                .stdout(stdout)
+                .stderr(stderr);
+            for var in ["PATH", "HOME", "CARGO_HOME", "RUSTUP_HOME"] {
+                if let Ok(value) = std::env::var(var) {
+                    command.env(var, value);
+                }
+            }
+            let mut child = command
+                .spawn()
+                .map_err(|e| format!("Failed to start '{}': {}", step.command, e))?;
+            let timeout = Duration::from_secs(step.timeout);
+            loop {
+                match child.try_wait() {
+                    Ok(Some(status)) => return Ok(status),
+                    Ok(None) if start.elapsed() >= timeout => {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        return Err(format!("timed out after {}s", step.timeout));
+                    }
+                    Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+                    Err(e) => return Err(format!("Failed to wait for step: {}", e)),
+                }
+            }
+        });
+
+    result.duration = start.elapsed().as_secs();
+    result.output = std::fs::read_to_string(&log_path).unwrap_or_default();
+    match status {
+        Ok(status) if status.code() == Some(step.expected_exit_code) => result.success = true,
+        Ok(status) => {
+            result.error = Some(format!(
+                "exit code {} (expected {})",
+                status
+                    .code()
+                    .map_or_else(|| "none".to_string(), |c| c.to_string()),
+                step.expected_exit_code
+            ))
+        }
+        Err(e) => result.error = Some(e),
+    }
+    result
+}
+
+/// Checksums of the rebuilt artifacts recorded in the package
+fn compare_artifacts(package: &EscrowPackage, source: &Path) -> Vec<ArtifactCheck> {
+    package
+        .build_artifacts
+        .iter()
+        .map(|artifact| {
+            let actual = sandbox_relative(&artifact.path)
+                .ok()
+                .and_then(|relative| file_checksum(&source.join(relative)).ok());
+            ArtifactCheck {
+                name: artifact.name.clone(),
+                path: artifact.path.clone(),
+                expected: artifact.checksum.clone(),
+                matches: actual.as_deref() == Some(artifact.checksum.as_str()),
+                actual,
+            }
+        })
+        .collect()
+}
+
+/// Reject package paths that would escape the sandbox
+fn sandbox_relative(path: &str) -> Result<&Path, String> {
+    let relative = Path::new(path);
+    if relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        Ok(relative)
+    } else {
+        Err(format!("Path '{}' escapes the sandbox", path))
+    }
+}
+
+/// Copy `from` into `to`, skipping VCS and build output directories and
+/// `sandbox` itself when it lies inside the source tree
+fn copy_tree(from: &Path, to: &Path, sandbox: &Path) -> Result<usize, String> {
+    std::fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let mut copied = 0;
+    let entries =
+        std::fs::read_dir(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let path = entry.path();
+        let file_type = entry
+            .file_type()
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let target = to.join(entry.file_name());
+        if file_type.is_dir() {
+            let excluded = EXCLUDED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref());
+            if !excluded && path != sandbox {
+                copied += copy_tree(&path, &target, sandbox)?;
+            }
+        } else if file_type.is_file() {
+            std::fs::copy(&path, &target)
+                .map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
 /// Run git command
 fn run_git_command(args: &[&str]) -> Result<String, String> {
     let output = Command::new("git")
@@ -442,19 +952,6 @@ fn run_git_command(args: &[&str]) -> Result<String, String> {
     Ok(result.trim().to_string())
 }
 
-/// Calculate file checksum
-fn calculate_file_checksum(path: &Path) -> Result<String, String> {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-
-    let content = std::fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
-
-    let mut hasher = DefaultHasher::new();
-    content.hash(&mut hasher);
-
-    Ok(format!("{:x}", hasher.finish()))
-}
-
 fn current_timestamp() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     SystemTime::now()
@@ -512,4 +1009,106 @@ mod tests {
         assert!(text.contains("Receipt ID: receipt-123"));
         assert!(text.contains("Version: 1.0.0"));
     }
+
+    fn package_building(command: &str, artifact_content: &[u8]) -> EscrowPackage {
+        let automation = ReleaseAutomation::new(ReleaseConfig::default(), PathBuf::from("."));
+        let mut instructions = automation.generate_build_instructions();
+        instructions.steps = vec![BuildStep {
+            step: 1,
+            description: "Build".to_string(),
+            command: command.to_string(),
+            working_dir: ".".to_string(),
+            expected_exit_code: 0,
+            timeout: 30,
+        }];
+        let artifact_path = std::env::temp_dir().join(format!("escrow-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&artifact_path, artifact_content).unwrap();
+
+        let mut builder = EscrowPackageBuilder::new(PathBuf::from("."))
+            .metadata(PackageMetadata {
+                package_id: uuid::Uuid::new_v4().to_string(),
+                product_name: "CostPilot".to_string(),
+                version: "1.0.0".to_string(),
+                release_date: 1_700_000_000,
+                commit_hash: "abc123".to_string(),
+                git_tag: None,
+                branch: "main".to_string(),
+                vendor: ReleaseConfig::default().vendor,
+                customer: None,
+                escrow_agent: None,
+                deposit_type: DepositType::General,
+            })
+            .dependencies(automation.generate_dependencies_manifest().unwrap())
+            .build_instructions(instructions)
+            .license(automation.generate_license_info());
+        builder.add_artifact(crate::engines::escrow::package::BuildArtifact {
+            name: "app".to_string(),
+            artifact_type: crate::engines::escrow::package::ArtifactType::Binary,
+            path: "out/app".to_string(),
+            size: artifact_content.len() as u64,
+            checksum: file_checksum(&artifact_path).unwrap(),
+            build_date: 0,
+        });
+        std::fs::remove_file(artifact_path).unwrap();
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_build_verifier_reproduces_artifacts() {
+        let source = tempfile::tempdir().unwrap();
+        let sandboxes = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("input.txt"), "hello").unwrap();
+        std::fs::create_dir(source.path().join(".git")).unwrap();
+        std::fs::write(source.path().join(".git/HEAD"), "ref").unwrap();
+
+        // The build sees the copied sources and the pinned release date
+        let command = "test ! -e .git && mkdir -p out && cat input.txt > out/app && echo $SOURCE_DATE_EPOCH >> out/app";
+        let package = package_building(command, b"hello1700000000\n");
+        let verifier =
+            BuildVerifier::new(source.path().to_path_buf()).sandbox_root(sandboxes.path().into());
+
+        let mut report = verifier.run(&package).unwrap();
+        assert!(report.report.verified, "{:?}", report.report.errors);
+        assert!(report.build.build_successful && report.build.artifacts_match);
+        assert_eq!(report.artifacts.len(), 1);
+        assert_eq!(std::fs::read_dir(sandboxes.path()).unwrap().count(), 0);
+
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        report.sign(&key);
+        assert_eq!(report.verify_signature().unwrap(), key.verifying_key());
+        report.report.verified = false;
+        assert!(report.verify_signature().is_err());
+
+        // A build producing different bytes is not reproducible
+        let drifted = package_building("mkdir -p out && date +%s%N > out/app", b"hello");
+        let report = verifier.run(&drifted).unwrap();
+        assert!(report.build.build_successful);
+        assert!(!report.build.artifacts_match);
+        assert!(!report.report.verified);
+    }
+
+    #[test]
+    fn test_build_verifier_stops_at_failed_step() {
+        let source = tempfile::tempdir().unwrap();
+        let mut package = package_building("echo building; exit 3", b"x");
+        package.build_instructions.steps.push(BuildStep {
+            step: 2,
+            description: "Never runs".to_string(),
+            command: "true".to_string(),
+            working_dir: "../outside".to_string(),
+            expected_exit_code: 0,
+            timeout: 30,
+        });
+
+        let report = BuildVerifier::new(source.path().to_path_buf())
+            .sandbox_root(source.path().join("sandboxes"))
+            .run(&package)
+            .unwrap();
+        assert!(!report.build.build_successful);
+        assert_eq!(report.steps.len(), 1);
+        assert!(report.steps[0].output.contains("building"));
+        assert!(report.report.errors[0].contains("exit code 3 (expected 0)"));
+        assert!(sandbox_relative("../outside").is_err());
+        assert!(sandbox_relative("/etc").is_err());
+    }
 }
@@ -1,5 +1,6 @@
 pub mod anti_patterns;
 pub mod explain_engine;
+pub mod narrative;
 pub mod prediction_explainer;
 pub mod reasoning_chain;
 pub mod root_cause;
@@ -7,7 +8,8 @@ pub mod stepwise;
 
 pub use anti_patterns::{detect_anti_patterns, AntiPattern};
 pub use explain_engine::ExplainEngine;
-pub use prediction_explainer::PredictionExplainer;
+pub use narrative::{Contribution, CostNarrative};
+pub use prediction_explainer::{CostDriver, Counterfactual, PredictionExplainer};
 pub use root_cause::RootCauseAnalysis;
 pub use stepwise::{
     CostComponent, ReasoningCategory, ReasoningChain as Explanation, ReasoningChainBuilder,
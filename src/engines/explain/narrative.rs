@@ -0,0 +1,476 @@
+// Cost attribution narratives
+//
+// Turns per-resource estimates, root cause analysis and reasoning chains into
+// short prose for reviewers: what the change costs, which resources account
+// for it and why. Rendered as Markdown for PR comments and as an HTML
+// fragment for reports.
+
+use crate::engines::explain::anti_patterns::detect_anti_patterns;
+use crate::engines::explain::root_cause::RootCauseAnalysis;
+use crate::engines::explain::stepwise::{CostComponent, ReasoningChain};
+use crate::engines::shared::models::{ChangeAction, CostEstimate, Detection, ResourceChange};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Contributors listed individually; the rest are summarized in one line
+pub const TOP_CONTRIBUTORS: usize = 5;
+
+/// One resource's share of the cost change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contribution {
+    pub resource_id: String,
+    pub resource_type: String,
+    pub module_path: Option<String>,
+    pub action: ChangeAction,
+
+    /// Signed change in monthly cost
+    pub monthly_delta: f64,
+
+    /// Share of the gross change (sum of absolute deltas), in percent
+    pub share_percent: f64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_cause: Option<RootCauseAnalysis>,
+
+    /// Largest component of the estimate, from its reasoning chain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub largest_component: Option<CostComponent>,
+}
+
+/// Reviewer-facing account of where a change's cost comes from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostNarrative {
+    /// Net change in monthly cost
+    pub monthly_delta: f64,
+
+    /// Resources that change cost, largest absolute delta first
+    pub contributions: Vec<Contribution>,
+
+    /// Mean confidence of the reasoning chains that report one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+}
+
+impl CostNarrative {
+    /// Attribute the cost change of `changes` to individual resources
+    ///
+    /// Deltas come from the change's `cost_impact` when the plan carries one.
+    /// Otherwise created, updated and replaced resources count at their
+    /// estimated cost and deleted resources at minus their known cost.
+    /// Resources with a detection get a root cause.
+    pub fn build(
+        changes: &[ResourceChange],
+        estimates: &[CostEstimate],
+        detections: &[Detection],
+    ) -> Self {
+        let estimates: HashMap<&str, &CostEstimate> = estimates
+            .iter()
+            .map(|e| (e.resource_id.as_str(), e))
+            .collect();
+        let detections: HashMap<&str, &Detection> = detections
+            .iter()
+            .map(|d| (d.resource_id.as_str(), d))
+            .collect();
+
+        let mut contributions: Vec<Contribution> = changes
+            .iter()
+            .filter_map(|change| {
+                let estimate = estimates.get(change.resource_id.as_str()).copied();
+                let monthly_delta = Self::delta(change, estimate)?;
+                if monthly_delta.abs() < 0.005 {
+                    return None;
+                }
+                let root_cause = detections
+                    .get(change.resource_id.as_str())
+                    .map(|detection| {
+                        let anti_patterns = detect_anti_patterns(change, estimate);
+                        RootCauseAnalysis::analyze(change, detection, &anti_patterns)
+                    });
+                Some(Contribution {
+                    resource_id: change.resource_id.clone(),
+                    resource_type: change.resource_type.clone(),
+                    module_path: change.module_path.clone(),
+                    action: change.action.clone(),
+                    monthly_delta,
+                    share_percent: 0.0,
+                    root_cause,
+                    largest_component: None,
+                })
+            })
+            .collect();
+
+        let gross: f64 = contributions.iter().map(|c| c.monthly_delta.abs()).sum();
+        for contribution in &mut contributions {
+            contribution.share_percent = contribution.monthly_delta.abs() / gross * 100.0;
+        }
+        contributions.sort_by(|a, b| {
+            b.monthly_delta
+                .abs()
+                .total_cmp(&a.monthly_delta.abs())
+                .then_with(|| a.resource_id.cmp(&b.resource_id))
+        });
+
+        Self {
+            monthly_delta: contributions.iter().map(|c| c.monthly_delta).sum(),
+            contributions,
+            confidence: None,
+        }
+    }
+
+    fn delta(change: &ResourceChange, estimate: Option<&CostEstimate>) -> Option<f64> {
+        if let Some(impact) = &change.cost_impact {
+            return Some(impact.delta);
+        }
+        let estimated = estimate.map(|e| e.monthly_cost);
+        match change.action {
+            ChangeAction::Create | ChangeAction::Update | ChangeAction::Replace => estimated,
+            ChangeAction::Delete => change.monthly_cost.or(estimated).map(|cost| -cost),
+            ChangeAction::NoOp => None,
+        }
+    }
+
+    /// Add cost components and confidence from reasoning chains
+    pub fn with_reasoning(mut self, chains: &[ReasoningChain]) -> Self {
+        let chains: HashMap<&str, &ReasoningChain> =
+            chains.iter().map(|c| (c.resource_id.as_str(), c)).collect();
+
+        let mut confidences = Vec::new();
+        for contribution in &mut self.contributions {
+            if let Some(chain) = chains.get(contribution.resource_id.as_str()) {
+                contribution.largest_component = chain
+                    .final_estimate
+                    .components
+                    .iter()
+                    .max_by(|a, b| a.cost.total_cmp(&b.cost))
+                    .cloned();
+                if chain.overall_confidence > 0.0 {
+                    confidences.push(chain.overall_confidence);
+                }
+            }
+        }
+        if !confidences.is_empty() {
+            self.confidence = Some(confidences.iter().sum::<f64>() / confidences.len() as f64);
+        }
+        self
+    }
+
+    /// Resources listed individually in the narrative
+    pub fn top_contributors(&self) -> &[Contribution] {
+        &self.contributions[..self.contributions.len().min(TOP_CONTRIBUTORS)]
+    }
+
+    /// One-sentence summary, e.g. "This PR adds $412.00/mo, 83% from the new
+    /// NAT gateway `main` in `module.vpc`."
+    pub fn headline(&self) -> String {
+        self.headline_with(|s| format!("`{}`", s), |s| format!("**{}**", s))
+    }
+
+    fn headline_with(
+        &self,
+        code: impl Fn(&str) -> String,
+        strong: impl Fn(&str) -> String,
+    ) -> String {
+        let Some(top) = self.contributions.first() else {
+            return "This PR does not change the estimated monthly cost.".to_string();
+        };
+
+        let amount = strong(&format!("${:.2}/mo", self.monthly_delta.abs()));
+        let opening = if self.monthly_delta > 0.0 {
+            format!("This PR adds {}", amount)
+        } else if self.monthly_delta < 0.0 {
+            format!("This PR saves {}", amount)
+        } else {
+            "This PR leaves the estimated monthly cost unchanged".to_string()
+        };
+        let share = if self.contributions.len() == 1 {
+            "all".to_string()
+        } else {
+            format!("{:.0}%", top.share_percent)
+        };
+        format!("{}, {} from {}.", opening, share, describe(top, &code))
+    }
+
+    /// Render as Markdown, without a heading
+    pub fn to_markdown(&self) -> String {
+        let code = |s: &str| format!("`{}`", s);
+        let mut out = self.headline();
+        out.push('\n');
+        if self.contributions.is_empty() {
+            return out;
+        }
+        out.push('\n');
+
+        for contribution in self.top_contributors() {
+            out.push_str(&format!(
+                "- **{}** ({:.0}%): {}",
+                signed_amount(contribution.monthly_delta),
+                contribution.share_percent,
+                capitalize(&describe(contribution, &code))
+            ));
+            for reason in reasons(contribution) {
+                out.push_str(&format!(". {}", reason));
+            }
+            out.push('\n');
+        }
+        if let Some(rest) = self.remainder() {
+            out.push_str(&format!("- {}\n", rest));
+        }
+        if let Some(confidence) = self.confidence {
+            out.push_str(&format!(
+                "\n_Estimates are heuristic; confidence {:.0}%._\n",
+                confidence * 100.0
+            ));
+        }
+        out
+    }
+
+    /// Render as an HTML fragment for embedding in reports
+    pub fn to_html(&self) -> String {
+        let code = |s: &str| format!("<code>{}</code>", escape_html(s));
+        let strong = |s: &str| format!("<strong>{}</strong>", escape_html(s));
+        let mut out = String::from("<section class=\"cost-narrative\">\n");
+        out.push_str(&format!("  <p>{}</p>\n", self.headline_with(code, strong)));
+        if !self.contributions.is_empty() {
+            out.push_str("  <ul>\n");
+            for contribution in self.top_contributors() {
+                let mut item = format!(
+                    "<strong>{}</strong> ({:.0}%): {}",
+                    escape_html(&signed_amount(contribution.monthly_delta)),
+                    contribution.share_percent,
+                    capitalize(&describe(contribution, &code))
+                );
+                for reason in reasons(contribution) {
+                    item.push_str(&format!(". {}", escape_html(&reason)));
+                }
+                out.push_str(&format!("    <li>{}</li>\n", item));
+            }
+            if let Some(rest) = self.remainder() {
+                out.push_str(&format!("    <li>{}</li>\n", escape_html(&rest)));
+            }
+            out.push_str("  </ul>\n");
+        }
+        out.push_str("</section>\n");
+        out
+    }
+
+    fn remainder(&self) -> Option<String> {
+        let rest = self.contributions.get(TOP_CONTRIBUTORS..)?;
+        if rest.is_empty() {
+            return None;
+        }
+        let total: f64 = rest.iter().map(|c| c.monthly_delta).sum();
+        Some(format!(
+            "{} more resource(s) account for {}",
+            rest.len(),
+            signed_amount(total)
+        ))
+    }
+}
+
+/// "the new NAT gateway `main` in `module.vpc`"
+fn describe(contribution: &Contribution, code: &impl Fn(&str) -> String) -> String {
+    let kind = resource_kind(&contribution.resource_type);
+    let name = contribution
+        .resource_id
+        .rsplit('.')
+        .next()
+        .unwrap_or(&contribution.resource_id);
+    let subject = match contribution.action {
+        ChangeAction::Create => format!("the new {} {}", kind, code(name)),
+        ChangeAction::Delete => format!("removing the {} {}", kind, code(name)),
+        ChangeAction::Replace => format!("replacing the {} {}", kind, code(name)),
+        ChangeAction::Update | ChangeAction::NoOp => {
+            format!("changes to the {} {}", kind, code(name))
+        }
+    };
+    match &contribution.module_path {
+        Some(module) => format!("{} in {}", subject, code(module)),
+        None => subject,
+    }
+}
+
+/// Root cause and cost structure sentences for a contribution
+fn reasons(contribution: &Contribution) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if let Some(root_cause) = &contribution.root_cause {
+        reasons.push(trim_period(&root_cause.primary_cause));
+        // The last factor is the most resource-specific one
+        if let Some(factor) = root_cause
+            .contributing_factors
+            .last()
+            .filter(|f| !root_cause.primary_cause.contains(f.as_str()))
+        {
+            reasons.push(trim_period(factor));
+        }
+    }
+    if let Some(component) = &contribution.largest_component {
+        reasons.push(format!(
+            "Largest component: {} ({:.0}% of the estimate)",
+            component.name, component.percentage
+        ));
+    }
+    reasons
+}
+
+fn resource_kind(resource_type: &str) -> String {
+    let known = match resource_type {
+        "aws_instance" => Some("EC2 instance"),
+        "aws_nat_gateway" => Some("NAT gateway"),
+        "aws_db_instance" | "aws_rds_instance" => Some("RDS instance"),
+        "aws_rds_cluster" => Some("RDS cluster"),
+        "aws_lb" | "aws_alb" | "aws_elb" => Some("load balancer"),
+        "aws_lambda_function" => Some("Lambda function"),
+        "aws_dynamodb_table" => Some("DynamoDB table"),
+        "aws_s3_bucket" => Some("S3 bucket"),
+        "aws_ebs_volume" => Some("EBS volume"),
+        "aws_eks_cluster" => Some("EKS cluster"),
+        "aws_elasticache_cluster" => Some("ElastiCache cluster"),
+        "aws_autoscaling_group" => Some("Auto Scaling group"),
+        _ => None,
+    };
+    known.map(str::to_string).unwrap_or_else(|| {
+        let bare = ["aws_", "azurerm_", "google_"]
+            .iter()
+            .find_map(|prefix| resource_type.strip_prefix(prefix))
+            .unwrap_or(resource_type);
+        bare.replace('_', " ")
+    })
+}
+
+fn signed_amount(delta: f64) -> String {
+    if delta < 0.0 {
+        format!("-${:.2}/mo", delta.abs())
+    } else {
+        format!("+${:.2}/mo", delta)
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn trim_period(text: &str) -> String {
+    text.trim_end_matches('.').to_string()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::explain::stepwise::FinalEstimate;
+    use crate::engines::shared::models::{RegressionType, Severity};
+
+    fn change(id: &str, resource_type: &str, action: ChangeAction) -> ResourceChange {
+        let mut change = ResourceChange::new(id.to_string(), resource_type.to_string(), action);
+        change.module_path = id
+            .rsplit_once(&format!(".{}.", resource_type))
+            .map(|(module, _)| module.to_string());
+        change
+    }
+
+    fn scan() -> (Vec<ResourceChange>, Vec<CostEstimate>) {
+        let changes = vec![
+            change(
+                "module.vpc.aws_nat_gateway.main",
+                "aws_nat_gateway",
+                ChangeAction::Create,
+            ),
+            change("aws_instance.web", "aws_instance", ChangeAction::Create),
+            change("aws_s3_bucket.logs", "aws_s3_bucket", ChangeAction::Create),
+        ];
+        let estimates = vec![
+            CostEstimate::new("module.vpc.aws_nat_gateway.main".to_string(), 340.0),
+            CostEstimate::new("aws_instance.web".to_string(), 72.0),
+            CostEstimate::new("aws_s3_bucket.logs".to_string(), 0.0),
+        ];
+        (changes, estimates)
+    }
+
+    #[test]
+    fn test_headline_attributes_largest_contributor() {
+        let (changes, estimates) = scan();
+        let narrative = CostNarrative::build(&changes, &estimates, &[]);
+
+        assert_eq!(narrative.monthly_delta, 412.0);
+        assert_eq!(narrative.contributions.len(), 2);
+        assert_eq!(
+            narrative.headline(),
+            "This PR adds **$412.00/mo**, 83% from the new NAT gateway `main` in `module.vpc`."
+        );
+
+        let markdown = narrative.to_markdown();
+        assert!(markdown.contains("- **+$72.00/mo** (17%): The new EC2 instance `web`\n"));
+    }
+
+    #[test]
+    fn test_root_cause_and_reasoning_are_narrated() {
+        let (changes, estimates) = scan();
+        let detection = Detection::builder()
+            .rule_id("NAT_GATEWAY_COST")
+            .severity(Severity::High)
+            .resource_id("module.vpc.aws_nat_gateway.main")
+            .regression_type(RegressionType::Provisioning)
+            .message("New NAT gateway")
+            .build();
+        let mut chain = ReasoningChain::new(
+            "module.vpc.aws_nat_gateway.main".to_string(),
+            "aws_nat_gateway".to_string(),
+        );
+        chain.set_final_estimate(FinalEstimate {
+            monthly_cost: 340.0,
+            interval_low: 300.0,
+            interval_high: 380.0,
+            components: vec![
+                CostComponent {
+                    name: "Data processing".to_string(),
+                    cost: 307.15,
+                    percentage: 90.3,
+                },
+                CostComponent {
+                    name: "Hourly charge".to_string(),
+                    cost: 32.85,
+                    percentage: 9.7,
+                },
+            ],
+        });
+        chain.set_overall_confidence(0.8);
+
+        let narrative =
+            CostNarrative::build(&changes, &estimates, &[detection]).with_reasoning(&[chain]);
+        let markdown = narrative.to_markdown();
+        assert!(markdown.contains("NAT Gateway Overuse: NAT Gateways are expensive"));
+        assert!(markdown.contains("Largest component: Data processing (90% of the estimate)"));
+        assert!(markdown.contains("_Estimates are heuristic; confidence 80%._"));
+
+        let html = narrative.to_html();
+        assert!(html.contains("<strong>$412.00/mo</strong>"));
+        assert!(html.contains("<code>module.vpc</code>"));
+    }
+
+    #[test]
+    fn test_savings_and_empty_changes() {
+        let mut deleted = change("aws_instance.old", "aws_instance", ChangeAction::Delete);
+        deleted.monthly_cost = Some(150.0);
+        let narrative = CostNarrative::build(&[deleted], &[], &[]);
+        assert_eq!(
+            narrative.headline(),
+            "This PR saves **$150.00/mo**, all from removing the EC2 instance `old`."
+        );
+
+        let empty = CostNarrative::build(&[], &[], &[]);
+        assert_eq!(
+            empty.to_markdown(),
+            "This PR does not change the estimated monthly cost.\n"
+        );
+    }
+}
@@ -1,22 +1,169 @@
 // Prediction explainer - generates reasoning chains for cost predictions
 
 use crate::engines::explain::stepwise::{CostComponent, ReasoningChain, ReasoningChainBuilder};
-use crate::engines::prediction::prediction_engine::{CostHeuristics, PredictionEngine};
+use crate::engines::prediction::cloudwatch_pricing::{CloudWatchRate, LOG_INGESTION_GB_KEY};
+use crate::engines::prediction::cold_start::ColdStartInference;
+use crate::engines::prediction::container_pricing::{
+    is_fargate_service, task_definitions, FargateConfiguration, FargateRate, FargateTask,
+    NodeGroupConfiguration, NodeGroupRate, TaskSizeSource,
+};
+use crate::engines::prediction::dynamodb_pricing::{
+    autoscaling_targets, BillingMode, DynamoDbConfiguration, DynamoDbRate, DynamoDbUsage,
+    TableAutoscaling,
+};
+use crate::engines::prediction::ec2_pricing::{Ec2Configuration, Ec2Rate, Tenancy};
+use crate::engines::prediction::prediction_engine::{
+    CostHeuristics, InstanceCost, PredictionEngine,
+};
+use crate::engines::prediction::rds_pricing::{
+    RdsConfiguration, RdsRate, INFERRED_INSTANCE_MONTHLY,
+};
+use crate::engines::prediction::usage_profile::UsageProfile;
 use crate::engines::shared::models::{CostEstimate, ResourceChange};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Counterfactuals reported per resource
+pub const MAX_COUNTERFACTUALS: usize = 3;
+
+/// Instance sizes, smallest first, with capacity relative to `micro`
+const SIZE_LADDER: &[(&str, f64)] = &[
+    ("nano", 0.5),
+    ("micro", 1.0),
+    ("small", 2.0),
+    ("medium", 4.0),
+    ("large", 8.0),
+    ("xlarge", 16.0),
+    ("2xlarge", 32.0),
+    ("4xlarge", 64.0),
+    ("8xlarge", 128.0),
+    ("12xlarge", 192.0),
+    ("16xlarge", 256.0),
+    ("24xlarge", 384.0),
+];
+
+/// Storage type counterfactuals move to
+const CHEAPER_STORAGE: &str = "gp3";
+
+/// Configuration driver perturbed by a counterfactual
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostDriver {
+    InstanceSize,
+    StorageClass,
+    MultiAz,
+}
+
+/// Single configuration change that would make a resource cheaper
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Counterfactual {
+    pub resource_id: String,
+    pub resource_type: String,
+    pub driver: CostDriver,
+
+    /// Attribute to change; nested blocks use dots (`root_block_device.volume_type`)
+    pub attribute: String,
+    pub current_value: Value,
+    pub proposed_value: Value,
+    pub monthly_savings: f64,
+}
+
+impl Counterfactual {
+    fn new(
+        change: &ResourceChange,
+        driver: CostDriver,
+        attribute: &str,
+        current_value: Value,
+        proposed_value: Value,
+        monthly_savings: f64,
+    ) -> Self {
+        Self {
+            resource_id: change.resource_id.clone(),
+            resource_type: change.resource_type.clone(),
+            driver,
+            attribute: attribute.to_string(),
+            current_value,
+            proposed_value,
+            monthly_savings,
+        }
+    }
+
+    /// One-line summary, e.g. "Downsize instance_type from m5.xlarge to m5.large"
+    pub fn description(&self) -> String {
+        let current = display_value(&self.current_value);
+        let proposed = display_value(&self.proposed_value);
+        match self.driver {
+            CostDriver::InstanceSize => format!(
+                "Downsize {} from {} to {}",
+                self.attribute, current, proposed
+            ),
+            CostDriver::StorageClass => {
+                format!("Switch {} from {} to {}", self.attribute, current, proposed)
+            }
+            CostDriver::MultiAz => format!("Set {} = {}", self.attribute, proposed),
+        }
+    }
+}
+
+fn is_serverless_v2(change: &ResourceChange) -> bool {
+    change
+        .new_config
+        .as_ref()
+        .is_some_and(|c| c.get("serverlessv2_scaling_configuration").is_some())
+}
+
+fn display_value(value: &Value) -> String {
+    value
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| value.to_string())
+}
 
 pub struct PredictionExplainer<'a> {
     heuristics: &'a CostHeuristics,
+    usage: UsageProfile,
+    autoscaling: HashMap<String, TableAutoscaling>,
+    task_definitions: HashMap<String, FargateTask>,
 }
 
 impl<'a> PredictionExplainer<'a> {
     /// Create new explainer with heuristics
     pub fn new(heuristics: &'a CostHeuristics) -> Self {
-        Self { heuristics }
+        Self {
+            heuristics,
+            usage: UsageProfile::default(),
+            autoscaling: HashMap::new(),
+            task_definitions: HashMap::new(),
+        }
     }
 
-    /// Create from prediction engine
+    /// Create from prediction engine, with its usage profile and autoscaling
     pub fn from_engine(engine: &'a PredictionEngine) -> Self {
-        Self::new(engine.heuristics())
+        let mut explainer = Self::new(engine.heuristics());
+        explainer.usage = engine.usage_profile().clone();
+        explainer.autoscaling = engine.dynamodb_autoscaling().clone();
+        explainer.task_definitions = engine.task_definitions().clone();
+        explainer
+    }
+
+    /// Explain usage-billed resources from monthly usage quantities
+    pub fn with_usage_profile(mut self, usage: UsageProfile) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Explain provisioned DynamoDB capacity within the autoscaling targets
+    /// declared in `changes`
+    pub fn with_autoscaling_targets(mut self, changes: &[ResourceChange]) -> Self {
+        self.autoscaling = autoscaling_targets(changes);
+        self
+    }
+
+    /// Size Fargate ECS services by the task definitions in `changes`
+    pub fn with_task_definitions(mut self, changes: &[ResourceChange]) -> Self {
+        self.task_definitions = task_definitions(changes);
+        self
     }
 
     /// Explain a cost prediction with full reasoning chain
@@ -30,12 +177,29 @@ impl<'a> PredictionExplainer<'a> {
         // Step 2-N: Resource-specific reasoning
         match change.resource_type.as_str() {
             "aws_instance" => self.explain_ec2(&mut builder, change, estimate),
-            "aws_rds_instance" => self.explain_rds(&mut builder, change, estimate),
+            "aws_rds_instance" | "aws_db_instance" => {
+                self.explain_rds(&mut builder, change, estimate)
+            }
+            // Provisioned Aurora bills its instances, which are separate resources
+            "aws_rds_cluster" if is_serverless_v2(change) => {
+                self.explain_rds(&mut builder, change, estimate)
+            }
             "aws_lambda_function" => self.explain_lambda(&mut builder, change, estimate),
             "aws_dynamodb_table" => self.explain_dynamodb(&mut builder, change, estimate),
+            "aws_eks_cluster" => self.explain_eks_cluster(&mut builder, estimate),
+            "aws_eks_node_group" => self.explain_node_group(&mut builder, change, estimate),
+            "aws_eks_fargate_profile" => self.explain_fargate(&mut builder, change, estimate),
+            "aws_ecs_service" if change.new_config.as_ref().is_some_and(is_fargate_service) => {
+                self.explain_fargate(&mut builder, change, estimate)
+            }
             "aws_nat_gateway" => self.explain_nat_gateway(&mut builder, change, estimate),
             "aws_lb" | "aws_alb" => self.explain_load_balancer(&mut builder, change, estimate),
             "aws_s3_bucket" => self.explain_s3(&mut builder, change, estimate),
+            "aws_cloudwatch_log_group"
+            | "aws_cloudwatch_log_metric_filter"
+            | "aws_cloudwatch_metric_alarm"
+            | "aws_cloudwatch_composite_alarm"
+            | "aws_cloudwatch_dashboard" => self.explain_cloudwatch(&mut builder, change, estimate),
             _ => self.explain_generic(&mut builder, change, estimate),
         }
 
@@ -46,6 +210,175 @@ impl<'a> PredictionExplainer<'a> {
         builder.build()
     }
 
+    /// Highest-impact single changes that would make `change` cheaper
+    ///
+    /// Perturbs one cost driver at a time (instance size, storage class,
+    /// multi-AZ) and prices the result with the explainer's heuristics. Only
+    /// changes that save money are returned, largest saving first.
+    pub fn counterfactuals(&self, change: &ResourceChange) -> Vec<Counterfactual> {
+        let Some(config) = change.new_config.as_ref() else {
+            return Vec::new();
+        };
+
+        let mut counterfactuals: Vec<Counterfactual> = match change.resource_type.as_str() {
+            "aws_instance" => self.ec2_counterfactuals(change, config),
+            "aws_rds_instance" | "aws_db_instance" => self.rds_counterfactuals(change, config),
+            "aws_ebs_volume" => self.ebs_counterfactuals(change, config),
+            _ => Vec::new(),
+        };
+        counterfactuals.retain(|c| c.monthly_savings >= 0.01);
+        counterfactuals.sort_by(|a, b| {
+            b.monthly_savings
+                .total_cmp(&a.monthly_savings)
+                .then_with(|| a.attribute.cmp(&b.attribute))
+        });
+        counterfactuals.truncate(MAX_COUNTERFACTUALS);
+        counterfactuals
+    }
+
+    fn ec2_counterfactuals(&self, change: &ResourceChange, config: &Value) -> Vec<Counterfactual> {
+        let mut candidates = Vec::new();
+
+        if let Some(instance_type) = config.get("instance_type").and_then(|v| v.as_str()) {
+            let prices = &self.heuristics.compute.ec2;
+            let current = prices
+                .get(instance_type)
+                .map(|c| c.monthly)
+                .unwrap_or_else(|| self.infer_ec2_cost(instance_type));
+            if let Some((smaller, cost)) = downsize(prices, instance_type, current) {
+                candidates.push(Counterfactual::new(
+                    change,
+                    CostDriver::InstanceSize,
+                    "instance_type",
+                    Value::from(instance_type),
+                    Value::from(smaller),
+                    current - cost,
+                ));
+            }
+        }
+
+        let root = config
+            .get("root_block_device")
+            .and_then(|v| v.as_array().and_then(|a| a.first()).or(Some(v)));
+        if let Some(volume_type) = root
+            .and_then(|r| r.get("volume_type"))
+            .and_then(|v| v.as_str())
+        {
+            let size_gb = root
+                .and_then(|r| r.get("volume_size"))
+                .and_then(|v| v.as_f64())
+                .unwrap_or(8.0);
+            let per_gb = |t: &str| self.heuristics.storage.ebs.get(t).map(|c| c.per_gb);
+            if let Some(savings) = storage_savings(volume_type, size_gb, per_gb) {
+                candidates.push(Counterfactual::new(
+                    change,
+                    CostDriver::StorageClass,
+                    "root_block_device.volume_type",
+                    Value::from(volume_type),
+                    Value::from(CHEAPER_STORAGE),
+                    savings,
+                ));
+            }
+        }
+
+        candidates
+    }
+
+    fn rds_counterfactuals(&self, change: &ResourceChange, config: &Value) -> Vec<Counterfactual> {
+        let rds = &self.heuristics.database.rds;
+        let instance_class = config
+            .get("instance_class")
+            .and_then(|v| v.as_str())
+            .unwrap_or("db.t3.micro");
+        let prices = match config.get("engine").and_then(|v| v.as_str()) {
+            Some("postgres" | "postgresql") => &rds.postgres,
+            _ => &rds.mysql,
+        };
+        let storage_gb = config
+            .get("allocated_storage")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(20.0);
+        let storage_type = config
+            .get("storage_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("gp2");
+        let multi_az = config
+            .get("multi_az")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let per_gb = |t: &str| match t {
+            "gp2" => Some(rds.storage_gp2_per_gb),
+            "gp3" => Some(rds.storage_gp3_per_gb),
+            _ => None,
+        };
+        let instance_cost = prices
+            .get(instance_class)
+            .map(|c| c.monthly)
+            .unwrap_or(50.0);
+        // Multi-AZ runs a standby replica with its own storage and IOPS
+        let rate = RdsRate::new(
+            &RdsConfiguration::from_config(config),
+            instance_cost / 730.0,
+            rds,
+            &self.heuristics.database.rds_options,
+        );
+        let replicas = rate.multi_az_multiplier;
+
+        let mut candidates = Vec::new();
+        if let Some((smaller, cost)) = downsize(prices, instance_class, instance_cost) {
+            candidates.push(Counterfactual::new(
+                change,
+                CostDriver::InstanceSize,
+                "instance_class",
+                Value::from(instance_class),
+                Value::from(smaller),
+                (instance_cost - cost) * replicas,
+            ));
+        }
+        if let Some(savings) = storage_savings(storage_type, storage_gb, per_gb) {
+            candidates.push(Counterfactual::new(
+                change,
+                CostDriver::StorageClass,
+                "storage_type",
+                Value::from(storage_type),
+                Value::from(CHEAPER_STORAGE),
+                savings * replicas,
+            ));
+        }
+        if multi_az {
+            candidates.push(Counterfactual::new(
+                change,
+                CostDriver::MultiAz,
+                "multi_az",
+                Value::Bool(true),
+                Value::Bool(false),
+                rate.monthly(730.0) * (1.0 - 1.0 / replicas),
+            ));
+        }
+        candidates
+    }
+
+    fn ebs_counterfactuals(&self, change: &ResourceChange, config: &Value) -> Vec<Counterfactual> {
+        let volume_type = config.get("type").and_then(|v| v.as_str()).unwrap_or("gp2");
+        let Some(size_gb) = config.get("size").and_then(|v| v.as_f64()) else {
+            return Vec::new();
+        };
+        let per_gb = |t: &str| self.heuristics.storage.ebs.get(t).map(|c| c.per_gb);
+        storage_savings(volume_type, size_gb, per_gb)
+            .map(|savings| {
+                vec![Counterfactual::new(
+                    change,
+                    CostDriver::StorageClass,
+                    "type",
+                    Value::from(volume_type),
+                    Value::from(CHEAPER_STORAGE),
+                    savings,
+                )]
+            })
+            .unwrap_or_default()
+    }
+
     /// Explain EC2 instance cost
     fn explain_ec2(
         &self,
@@ -65,106 +398,191 @@ impl<'a> PredictionExplainer<'a> {
         builder.add_configuration_extraction("instance_type", instance_type, from_plan);
 
         // Lookup or infer pricing
-        if let Some(cost) = self.heuristics.compute.ec2.get(instance_type) {
-            builder.add_heuristic_lookup(
-                instance_type,
-                cost.hourly,
-                "$/hour",
-                &self.heuristics.version,
+        let (base_monthly, base_name) =
+            if let Some(cost) = self.heuristics.compute.ec2.get(instance_type) {
+                builder.add_heuristic_lookup(
+                    instance_type,
+                    cost.hourly,
+                    "$/hour",
+                    &self.heuristics.version,
+                );
+
+                builder.add_calculation(
+                    "Monthly Instance Cost",
+                    &format!("{:.4} $/hour × 730 hours/month", cost.hourly),
+                    cost.monthly,
+                    "$/month",
+                );
+                (cost.monthly, "EC2 Instance")
+            } else {
+                // Cold start inference
+                let inferred_cost = self.infer_ec2_cost(instance_type);
+                builder.add_cold_start_inference(
+                    instance_type,
+                    &format!("${:.2}/month", inferred_cost),
+                    "Instance type not in heuristics; inferred from family/size patterns",
+                );
+
+                builder.add_calculation(
+                    "Monthly Instance Cost",
+                    "Based on cold-start inference model",
+                    inferred_cost,
+                    "$/month",
+                );
+                (inferred_cost, "EC2 Instance (inferred)")
+            };
+
+        let mut components = vec![(base_name.to_string(), base_monthly)];
+        let ec2 = Ec2Configuration::from_config(config);
+        if ec2.has_adjustments() {
+            let rate = Ec2Rate::new(
+                &ec2,
+                base_monthly / 730.0,
+                &self.heuristics.compute.ec2_options,
             );
-
+            components.extend(self.explain_ec2_adjustments(builder, &rate));
             builder.add_calculation(
-                "Monthly Instance Cost",
-                &format!("{:.4} $/hour × 730 hours/month", cost.hourly),
-                cost.monthly,
+                "Adjusted Monthly Instance Cost",
+                &format!("{:.4} $/hour × 730 hours/month", rate.hourly()),
+                rate.hourly() * 730.0,
                 "$/month",
             );
+        }
 
-            let components = vec![CostComponent {
-                name: "EC2 Instance".to_string(),
-                cost: cost.monthly,
-                percentage: 100.0,
-            }];
+        let total: f64 = components.iter().map(|(_, cost)| cost).sum();
+        builder.set_final_estimate(
+            total,
+            estimate.prediction_interval_low,
+            estimate.prediction_interval_high,
+            components
+                .into_iter()
+                .map(|(name, cost)| CostComponent {
+                    name,
+                    cost,
+                    percentage: if total > 0.0 {
+                        cost / total * 100.0
+                    } else {
+                        0.0
+                    },
+                })
+                .collect(),
+        );
+    }
 
-            builder.set_final_estimate(
-                cost.monthly,
-                estimate.prediction_interval_low,
-                estimate.prediction_interval_high,
-                components,
-            );
-        } else {
-            // Cold start inference
-            let inferred_cost = self.infer_ec2_cost(instance_type);
-            builder.add_cold_start_inference(
-                instance_type,
-                &format!("${:.2}/month", inferred_cost),
-                "Instance type not in heuristics; inferred from family/size patterns",
+    /// Explain tenancy, license and EBS-optimized charges on top of the
+    /// instance rate; returns their monthly cost components
+    fn explain_ec2_adjustments(
+        &self,
+        builder: &mut ReasoningChainBuilder,
+        rate: &Ec2Rate,
+    ) -> Vec<(String, f64)> {
+        let mut components = Vec::new();
+
+        if rate.tenancy != Tenancy::Default {
+            builder.add_configuration_extraction("tenancy", rate.tenancy.as_str(), true);
+            builder.add_adjustment(
+                "Dedicated Tenancy",
+                rate.tenancy_multiplier,
+                "Single-tenant hardware costs more than shared instances",
             );
+            components.push((
+                format!("Tenancy ({})", rate.tenancy.as_str()),
+                rate.tenancy_hourly() * 730.0,
+            ));
+        }
 
-            builder.add_calculation(
-                "Monthly Instance Cost",
-                "Based on cold-start inference model",
-                inferred_cost,
-                "$/month",
-            );
+        if let Some(label) = rate.license_label() {
+            builder.add_configuration_extraction("license", &label, true);
+            builder.add_configuration_extraction("vcpus", &rate.vcpus.to_string(), true);
+            for (license, per_vcpu) in &rate.licenses {
+                builder.add_heuristic_lookup(
+                    license.key(),
+                    *per_vcpu,
+                    "$/vCPU-hour",
+                    &self.heuristics.version,
+                );
+                let monthly = per_vcpu * rate.vcpus as f64 * 730.0;
+                builder.add_calculation(
+                    "Monthly License Cost",
+                    &format!(
+                        "{} vCPU × {:.4} $/vCPU-hour × 730 hours/month",
+                        rate.vcpus, per_vcpu
+                    ),
+                    monthly,
+                    "$/month",
+                );
+                components.push((format!("License ({})", license.key()), monthly));
+            }
+        }
 
-            builder.set_final_estimate(
-                inferred_cost,
-                estimate.prediction_interval_low,
-                estimate.prediction_interval_high,
-                vec![CostComponent {
-                    name: "EC2 Instance (inferred)".to_string(),
-                    cost: inferred_cost,
-                    percentage: 100.0,
-                }],
-            );
+        if rate.ebs_optimized {
+            builder.add_configuration_extraction("ebs_optimized", "true", true);
+            if rate.ebs_optimized_hourly > 0.0 {
+                let monthly = rate.ebs_optimized_hourly * 730.0;
+                builder.add_calculation(
+                    "Monthly EBS-Optimized Cost",
+                    &format!("{:.4} $/hour × 730 hours/month", rate.ebs_optimized_hourly),
+                    monthly,
+                    "$/month",
+                );
+                components.push(("EBS-Optimized".to_string(), monthly));
+            }
         }
+
+        components
     }
 
-    /// Explain RDS instance cost
+    /// Explain RDS instance or Aurora Serverless v2 cluster cost
     fn explain_rds(
         &self,
         builder: &mut ReasoningChainBuilder,
         change: &ResourceChange,
         estimate: &CostEstimate,
     ) {
-        let config = change.new_config.as_ref().unwrap();
+        let Some(config) = change.new_config.as_ref() else {
+            return self.explain_generic(builder, change, estimate);
+        };
+        let rds = RdsConfiguration::from_config(config);
 
         // Extract configuration
-        let instance_class = config
-            .get("instance_class")
-            .and_then(|v| v.as_str())
-            .unwrap_or("db.t3.micro");
-        let engine = config
-            .get("engine")
-            .and_then(|v| v.as_str())
-            .unwrap_or("mysql");
-        let storage_gb = config
-            .get("allocated_storage")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(20.0);
-
         builder.add_configuration_extraction(
             "instance_class",
-            instance_class,
+            &rds.instance_class,
             config.get("instance_class").is_some(),
         );
-        builder.add_configuration_extraction("engine", engine, config.get("engine").is_some());
+        builder.add_configuration_extraction("engine", &rds.engine, config.get("engine").is_some());
         builder.add_configuration_extraction(
             "allocated_storage",
-            &format!("{} GB", storage_gb),
+            &format!("{} GB", rds.allocated_storage_gb),
             config.get("allocated_storage").is_some(),
         );
+        if let Some(max) = rds.max_allocated_storage_gb {
+            builder.add_configuration_extraction(
+                "max_allocated_storage",
+                &format!("{} GB", max),
+                true,
+            );
+        }
+        if let Some(iops) = rds.iops {
+            builder.add_configuration_extraction("iops", &iops.to_string(), true);
+        }
+        if rds.multi_az {
+            builder.add_configuration_extraction("multi_az", "true", true);
+        }
+        if let Some((min, max)) = rds.acu_range {
+            builder.add_configuration_extraction(
+                "serverlessv2_scaling_configuration",
+                &format!("{}-{} ACU", min, max),
+                true,
+            );
+        }
 
-        // Lookup instance pricing
-        let instances = match engine {
-            "postgres" | "postgresql" => &self.heuristics.database.rds.postgres,
-            _ => &self.heuristics.database.rds.mysql,
-        };
-
-        let instance_cost = if let Some(cost) = instances.get(instance_class) {
+        // Lookup instance pricing; Serverless v2 bills capacity instead
+        let instance_hourly = if rds.acu_range.is_some() {
+            0.0
+        } else if let Some(cost) = rds.instance_cost(&self.heuristics.database.rds) {
             builder.add_heuristic_lookup(
-                instance_class,
+                &rds.instance_class,
                 cost.hourly,
                 "$/hour",
                 &self.heuristics.version,
@@ -175,56 +593,135 @@ impl<'a> PredictionExplainer<'a> {
                 cost.monthly,
                 "$/month",
             );
-            cost.monthly
+            cost.monthly / 730.0
         } else {
-            let inferred = 50.0;
+            let inferred = INFERRED_INSTANCE_MONTHLY;
             builder.add_cold_start_inference(
-                instance_class,
+                &rds.instance_class,
                 &format!("${:.2}/month", inferred),
                 "RDS instance class not in heuristics",
             );
-            inferred
+            inferred / 730.0
         };
 
-        // Storage cost
-        builder.add_heuristic_lookup(
-            "gp2_storage",
-            self.heuristics.database.rds.storage_gp2_per_gb,
-            "$/GB/month",
-            &self.heuristics.version,
+        let rate = RdsRate::new(
+            &rds,
+            instance_hourly,
+            &self.heuristics.database.rds,
+            &self.heuristics.database.rds_options,
         );
+        let mut components = Vec::new();
+        if rate.instance_hourly > 0.0 {
+            components.push(("RDS Instance".to_string(), rate.instance_monthly(730.0)));
+        }
 
-        let storage_cost = storage_gb * self.heuristics.database.rds.storage_gp2_per_gb;
-        builder.add_calculation(
-            "Monthly Storage Cost",
-            &format!(
-                "{} GB × ${:.3}/GB/month",
-                storage_gb, self.heuristics.database.rds.storage_gp2_per_gb
-            ),
-            storage_cost,
-            "$/month",
-        );
+        if let Some((min, max)) = rate.acu_range {
+            builder.add_heuristic_lookup(
+                "aurora_acu",
+                rate.acu_hourly,
+                "$/ACU-hour",
+                &self.heuristics.version,
+            );
+            builder.add_calculation(
+                "Monthly Capacity Cost",
+                &format!(
+                    "{}-{} ACU × {:.4} $/ACU-hour × 730 hours/month",
+                    min, max, rate.acu_hourly
+                ),
+                rate.acu_monthly(min, 730.0),
+                "$/month",
+            );
+            components.push((
+                "Serverless v2 Capacity (minimum)".to_string(),
+                rate.acu_monthly(min, 730.0),
+            ));
+        }
 
-        let total = instance_cost + storage_cost;
-        let instance_pct = (instance_cost / total) * 100.0;
-        let storage_pct = (storage_cost / total) * 100.0;
+        // Storage and provisioned IOPS
+        if rate.storage_gb > 0.0 {
+            builder.add_heuristic_lookup(
+                &format!("{}_storage", rate.storage_type),
+                rate.storage_per_gb,
+                "$/GB/month",
+                &self.heuristics.version,
+            );
+            let storage_cost = rate.storage_monthly(rate.storage_gb);
+            builder.add_calculation(
+                "Monthly Storage Cost",
+                &format!(
+                    "{} GB × ${:.3}/GB/month",
+                    rate.storage_gb, rate.storage_per_gb
+                ),
+                storage_cost,
+                "$/month",
+            );
+            components.push((
+                format!("Storage ({})", rate.storage_type.to_uppercase()),
+                storage_cost,
+            ));
+        }
+        if rate.billed_iops > 0.0 {
+            builder.add_heuristic_lookup(
+                &format!("{}_iops", rate.storage_type),
+                rate.iops_per_month,
+                "$/IOPS/month",
+                &self.heuristics.version,
+            );
+            builder.add_calculation(
+                "Monthly Provisioned IOPS Cost",
+                &format!(
+                    "{} IOPS × ${:.3}/IOPS/month",
+                    rate.billed_iops, rate.iops_per_month
+                ),
+                rate.iops_monthly(),
+                "$/month",
+            );
+            components.push(("Provisioned IOPS".to_string(), rate.iops_monthly()));
+        }
+
+        if rate.is_multi_az() {
+            builder.add_adjustment(
+                "Multi-AZ Deployment",
+                rate.multi_az_multiplier,
+                "A standby replica in a second AZ bills its own instance, storage and IOPS",
+            );
+            let single_az: f64 = components
+                .iter()
+                .filter(|(name, _)| !name.starts_with("Serverless"))
+                .map(|(_, cost)| cost)
+                .sum();
+            components.push((
+                "Multi-AZ Standby".to_string(),
+                single_az * (rate.multi_az_multiplier - 1.0),
+            ));
+        }
+
+        let (low, high) = rate.monthly_range(730.0);
+        if rate.is_range() {
+            builder.add_calculation(
+                "Maximum Monthly Cost",
+                "Storage at max_allocated_storage and capacity at max ACU",
+                high,
+                "$/month",
+            );
+            builder.add_assumption(format!(
+                "Cost ranges from ${:.2} to ${:.2}/month as storage autoscales or capacity scales",
+                low, high
+            ));
+        }
 
         builder.set_final_estimate(
-            total,
+            low,
             estimate.prediction_interval_low,
-            estimate.prediction_interval_high,
-            vec![
-                CostComponent {
-                    name: "RDS Instance".to_string(),
-                    cost: instance_cost,
-                    percentage: instance_pct,
-                },
-                CostComponent {
-                    name: "Storage (GP2)".to_string(),
-                    cost: storage_cost,
-                    percentage: storage_pct,
-                },
-            ],
+            estimate.prediction_interval_high.max(high),
+            components
+                .into_iter()
+                .map(|(name, cost)| CostComponent {
+                    name,
+                    cost,
+                    percentage: if low > 0.0 { cost / low * 100.0 } else { 0.0 },
+                })
+                .collect(),
         );
     }
 
@@ -346,129 +843,359 @@ impl<'a> PredictionExplainer<'a> {
         change: &ResourceChange,
         estimate: &CostEstimate,
     ) {
-        let config = change.new_config.as_ref().unwrap();
-
-        let billing_mode = config
-            .get("billing_mode")
-            .and_then(|v| v.as_str())
-            .unwrap_or("PAY_PER_REQUEST");
+        let Some(config) = change.new_config.as_ref() else {
+            return self.explain_generic(builder, change, estimate);
+        };
+        let table = DynamoDbConfiguration::from_config(config).with_autoscaling(&self.autoscaling);
+        let usage = DynamoDbUsage::for_change(&self.usage, change);
+        let rate = DynamoDbRate::new(
+            &table,
+            usage,
+            &self.heuristics.database.dynamodb,
+            &self.heuristics.cold_start_defaults,
+            730.0,
+        );
 
         builder.add_configuration_extraction(
             "billing_mode",
-            billing_mode,
+            table.billing_mode.as_str(),
             config.get("billing_mode").is_some(),
         );
 
-        if billing_mode == "PROVISIONED" {
-            let read_capacity = config
-                .get("read_capacity")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(self.heuristics.cold_start_defaults.dynamodb_unknown_rcu as f64);
-            let write_capacity = config
-                .get("write_capacity")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(self.heuristics.cold_start_defaults.dynamodb_unknown_wcu as f64);
+        let mut components = Vec::new();
+        match rate.billing_mode {
+            BillingMode::OnDemand => {
+                for (name, requests, profiled, price, cost) in [
+                    (
+                        "Reads",
+                        rate.read_requests,
+                        usage.reads.is_some(),
+                        rate.read_request_price,
+                        rate.read_requests_monthly(),
+                    ),
+                    (
+                        "Writes",
+                        rate.write_requests,
+                        usage.writes.is_some(),
+                        rate.write_request_price,
+                        rate.write_requests_monthly(),
+                    ),
+                ] {
+                    let value = format!("{} requests/month", requests);
+                    if profiled {
+                        builder.add_configuration_extraction(
+                            &format!("monthly_{}", name.to_lowercase()),
+                            &value,
+                            true,
+                        );
+                    } else {
+                        builder.add_cold_start_inference(
+                            &format!("monthly_{}", name.to_lowercase()),
+                            &value,
+                            "No usage profile for this table",
+                        );
+                    }
+                    builder.add_calculation(
+                        &format!("Monthly {} Cost", name.trim_end_matches('s')),
+                        &format!("{} requests × ${:.8}/request", requests, price),
+                        cost,
+                        "$/month",
+                    );
+                    components.push((format!("DynamoDB {}", name), cost));
+                }
+            }
+            BillingMode::Provisioned => {
+                for (name, unit, capacity, configured) in [
+                    ("Read", "RCU", &rate.read, table.read_capacity),
+                    ("Write", "WCU", &rate.write, table.write_capacity),
+                ] {
+                    let attribute = format!("{}_capacity", name.to_lowercase());
+                    builder.add_configuration_extraction(
+                        &attribute,
+                        &format!("{} {}", configured.unwrap_or(capacity.units), unit),
+                        configured.is_some(),
+                    );
+                    if let Some(band) = capacity.band {
+                        builder.add_configuration_extraction(
+                            &format!("{}_autoscaling", name.to_lowercase()),
+                            &format!("{}-{} {}", band.min, band.max, unit),
+                            true,
+                        );
+                    }
+                    let cost = capacity.monthly(730.0);
+                    builder.add_calculation(
+                        &format!("Monthly {} Cost", name),
+                        &format!(
+                            "{} {} × ${:.5}/hour × 730 hours",
+                            capacity.units, unit, capacity.unit_hourly
+                        ),
+                        cost,
+                        "$/month",
+                    );
+                    components.push((format!("DynamoDB {}s", name), cost));
+                }
+            }
+        }
 
-            builder.add_configuration_extraction(
-                "read_capacity",
-                &format!("{} RCU", read_capacity),
-                config.get("read_capacity").is_some(),
-            );
-            builder.add_configuration_extraction(
-                "write_capacity",
-                &format!("{} WCU", write_capacity),
-                config.get("write_capacity").is_some(),
-            );
+        builder.add_calculation(
+            "Monthly Storage Cost",
+            &format!(
+                "{} GB × ${:.2}/GB/month",
+                rate.storage_gb, rate.storage_per_gb
+            ),
+            rate.storage_monthly(),
+            "$/month",
+        );
+        components.push(("Storage".to_string(), rate.storage_monthly()));
 
-            let read_cost = read_capacity
-                * self
-                    .heuristics
-                    .database
-                    .dynamodb
-                    .provisioned
-                    .read_capacity_unit_hourly
-                * 730.0;
-            let write_cost = write_capacity
-                * self
-                    .heuristics
-                    .database
-                    .dynamodb
-                    .provisioned
-                    .write_capacity_unit_hourly
-                * 730.0;
+        let total = rate.monthly(730.0);
+        let (low, high) = rate.monthly_range(730.0);
+        builder.set_final_estimate(
+            total,
+            estimate.prediction_interval_low.min(low),
+            estimate.prediction_interval_high.max(high),
+            components
+                .into_iter()
+                .map(|(name, cost)| CostComponent {
+                    name,
+                    cost,
+                    percentage: if total > 0.0 {
+                        (cost / total) * 100.0
+                    } else {
+                        0.0
+                    },
+                })
+                .collect(),
+        );
 
-            builder.add_calculation(
-                "Monthly Read Cost",
-                &format!(
-                    "{} RCU × ${:.5}/hour × 730 hours",
-                    read_capacity,
-                    self.heuristics
-                        .database
-                        .dynamodb
-                        .provisioned
-                        .read_capacity_unit_hourly
-                ),
-                read_cost,
-                "$/month",
-            );
+        if rate.is_range() {
+            builder.add_assumption(format!(
+                "Autoscaling keeps capacity between ${:.2} and ${:.2}/month",
+                low, high
+            ));
+        }
+        if !usage.is_profiled() {
+            builder.add_assumption(match rate.billing_mode {
+                BillingMode::OnDemand => "No usage profile; request volumes and storage are defaults. Set usage.aws_dynamodb_table in costpilot.yaml".to_string(),
+                BillingMode::Provisioned => "No usage profile; storage is a default".to_string(),
+            });
+        }
+    }
 
-            builder.add_calculation(
-                "Monthly Write Cost",
-                &format!(
-                    "{} WCU × ${:.5}/hour × 730 hours",
-                    write_capacity,
-                    self.heuristics
-                        .database
-                        .dynamodb
-                        .provisioned
-                        .write_capacity_unit_hourly
-                ),
-                write_cost,
-                "$/month",
-            );
+    /// Explain an EKS cluster's control plane cost
+    fn explain_eks_cluster(&self, builder: &mut ReasoningChainBuilder, estimate: &CostEstimate) {
+        let hourly = self.heuristics.compute.containers.eks_control_plane_hourly;
+        builder.add_heuristic_lookup(
+            "eks_control_plane",
+            hourly,
+            "$/hour",
+            &self.heuristics.version,
+        );
+        let cost = hourly * 730.0;
+        builder.add_calculation(
+            "Monthly Control Plane Cost",
+            &format!("{:.4} $/hour × 730 hours/month", hourly),
+            cost,
+            "$/month",
+        );
+        builder.set_final_estimate(
+            cost,
+            estimate.prediction_interval_low,
+            estimate.prediction_interval_high,
+            vec![CostComponent {
+                name: "EKS Control Plane".to_string(),
+                cost,
+                percentage: 100.0,
+            }],
+        );
+        builder.add_assumption(
+            "Node groups and Fargate profiles are priced as their own resources".to_string(),
+        );
+    }
 
-            let total = read_cost + write_cost;
-            builder.set_final_estimate(
-                total,
-                estimate.prediction_interval_low,
-                estimate.prediction_interval_high,
-                vec![
-                    CostComponent {
-                        name: "DynamoDB Reads".to_string(),
-                        cost: read_cost,
-                        percentage: (read_cost / total) * 100.0,
-                    },
-                    CostComponent {
-                        name: "DynamoDB Writes".to_string(),
-                        cost: write_cost,
-                        percentage: (write_cost / total) * 100.0,
-                    },
-                ],
-            );
-        } else {
-            // On-demand: use conservative estimate
-            let estimate_cost = 25.0;
-            builder.add_cold_start_inference(
-                "on_demand_cost",
-                &format!("${:.2}/month", estimate_cost),
-                "On-demand billing requires actual usage metrics",
-            );
+    /// Explain a managed node group as its nodes' EC2 cost times its size
+    fn explain_node_group(
+        &self,
+        builder: &mut ReasoningChainBuilder,
+        change: &ResourceChange,
+        estimate: &CostEstimate,
+    ) {
+        let Some(config) = change.new_config.as_ref() else {
+            return self.explain_generic(builder, change, estimate);
+        };
+        let group = NodeGroupConfiguration::from_config(config);
+        let instance_type = group.node.instance_type.clone();
+        builder.add_configuration_extraction(
+            "instance_types",
+            &instance_type,
+            config.get("instance_types").is_some(),
+        );
+        builder.add_configuration_extraction(
+            "scaling_config",
+            &format!("{} nodes ({}-{})", group.desired, group.min, group.max),
+            config.get("scaling_config").is_some(),
+        );
 
-            builder.set_final_estimate(
-                estimate_cost,
-                estimate.prediction_interval_low,
-                estimate.prediction_interval_high,
-                vec![CostComponent {
-                    name: "DynamoDB On-Demand".to_string(),
-                    cost: estimate_cost,
-                    percentage: 100.0,
-                }],
-            );
+        // Nodes are EC2 instances
+        let node_monthly = match self.heuristics.compute.ec2.get(&instance_type) {
+            Some(cost) => {
+                builder.add_heuristic_lookup(
+                    &instance_type,
+                    cost.hourly,
+                    "$/hour",
+                    &self.heuristics.version,
+                );
+                cost.monthly
+            }
+            None => {
+                let inferred = ColdStartInference::new(&self.heuristics.cold_start_defaults)
+                    .estimate_ec2_cost(&instance_type);
+                builder.add_cold_start_inference(
+                    &instance_type,
+                    &format!("${:.2}/month", inferred),
+                    "Instance type not in heuristics; inferred from family/size patterns",
+                );
+                inferred
+            }
+        };
+        let rate = NodeGroupRate::new(
+            &group,
+            node_monthly / 730.0,
+            &self.heuristics.compute.ec2_options,
+        );
+        let mut node_components = vec![("EC2 Instance".to_string(), node_monthly)];
+        if rate.node.is_adjusted() {
+            node_components.extend(self.explain_ec2_adjustments(builder, &rate.node));
+        }
+        builder.add_calculation(
+            "Monthly Node Cost",
+            &format!("{:.4} $/hour × 730 hours/month", rate.node.hourly()),
+            rate.node_monthly(730.0),
+            "$/month",
+        );
+        builder.add_calculation(
+            "Monthly Node Group Cost",
+            &format!(
+                "{} nodes × ${:.2}/month",
+                rate.desired,
+                rate.node_monthly(730.0)
+            ),
+            rate.monthly(730.0),
+            "$/month",
+        );
+
+        let (low, high) = rate.monthly_range(730.0);
+        let total = rate.monthly(730.0);
+        let node_total: f64 = node_components.iter().map(|(_, cost)| cost).sum();
+        builder.set_final_estimate(
+            total,
+            estimate.prediction_interval_low.min(low),
+            estimate.prediction_interval_high.max(high),
+            node_components
+                .into_iter()
+                .map(|(name, cost)| CostComponent {
+                    name: format!("{} (×{})", name, rate.desired),
+                    cost: cost * rate.desired,
+                    percentage: if node_total > 0.0 {
+                        cost / node_total * 100.0
+                    } else {
+                        0.0
+                    },
+                })
+                .collect(),
+        );
+        if rate.is_range() {
+            builder.add_assumption(format!(
+                "Scaling between {} and {} nodes costs ${:.2} - ${:.2}/month",
+                rate.min, rate.max, low, high
+            ));
+        }
+    }
 
-            builder.add_assumption(
-                "On-demand cost highly variable; update with actual usage patterns".to_string(),
+    /// Explain Fargate tasks or pods billed per vCPU-second and GB-second
+    fn explain_fargate(
+        &self,
+        builder: &mut ReasoningChainBuilder,
+        change: &ResourceChange,
+        estimate: &CostEstimate,
+    ) {
+        let containers = &self.heuristics.compute.containers;
+        let config = match change.new_config.as_ref() {
+            Some(config) if change.resource_type == "aws_ecs_service" => {
+                FargateConfiguration::from_ecs_service(config, &self.task_definitions, containers)
+            }
+            _ => FargateConfiguration::from_fargate_profile(change, &self.usage, containers),
+        };
+        let rate = FargateRate::new(&config, containers);
+
+        let size = format!("{} vCPU, {} GB", rate.task.vcpu, rate.task.memory_gb);
+        match rate.source {
+            TaskSizeSource::TaskDefinition => {
+                builder.add_configuration_extraction("task_size", &size, true)
+            }
+            TaskSizeSource::UsageProfile => builder.add_configuration_extraction(
+                "task_size",
+                &format!("{} × {}", rate.tasks, size),
+                true,
+            ),
+            TaskSizeSource::Default => builder.add_cold_start_inference(
+                "task_size",
+                &size,
+                "No task definition or usage profile sizes these tasks",
+            ),
+        };
+        if change.resource_type == "aws_ecs_service" {
+            builder.add_configuration_extraction(
+                "desired_count",
+                &rate.tasks.to_string(),
+                change
+                    .new_config
+                    .as_ref()
+                    .is_some_and(|c| c.get("desired_count").is_some()),
             );
         }
+
+        builder.add_calculation(
+            "Monthly vCPU Cost",
+            &format!(
+                "{} tasks × {} vCPU × ${:.9}/vCPU-second × 2,628,000 seconds",
+                rate.tasks, rate.task.vcpu, rate.vcpu_second
+            ),
+            rate.vcpu_monthly(730.0),
+            "$/month",
+        );
+        builder.add_calculation(
+            "Monthly Memory Cost",
+            &format!(
+                "{} tasks × {} GB × ${:.9}/GB-second × 2,628,000 seconds",
+                rate.tasks, rate.task.memory_gb, rate.gb_second
+            ),
+            rate.memory_monthly(730.0),
+            "$/month",
+        );
+
+        let total = rate.monthly(730.0);
+        builder.set_final_estimate(
+            total,
+            estimate.prediction_interval_low,
+            estimate.prediction_interval_high,
+            [
+                ("Fargate vCPU", rate.vcpu_monthly(730.0)),
+                ("Fargate Memory", rate.memory_monthly(730.0)),
+            ]
+            .into_iter()
+            .map(|(name, cost)| CostComponent {
+                name: name.to_string(),
+                cost,
+                percentage: if total > 0.0 {
+                    cost / total * 100.0
+                } else {
+                    0.0
+                },
+            })
+            .collect(),
+        );
+        builder.add_assumption("Tasks run continuously for the whole month".to_string());
     }
 
     /// Explain NAT Gateway cost
@@ -667,6 +1394,78 @@ impl<'a> PredictionExplainer<'a> {
             .add_assumption("Request costs not included; add based on access patterns".to_string());
     }
 
+    /// Explain CloudWatch log ingestion and storage, metrics, alarms and
+    /// dashboards
+    fn explain_cloudwatch(
+        &self,
+        builder: &mut ReasoningChainBuilder,
+        change: &ResourceChange,
+        estimate: &CostEstimate,
+    ) {
+        let cost = &self.heuristics.monitoring.cloudwatch;
+        let Some(rate) = CloudWatchRate::from_change(change, &self.usage, cost) else {
+            return self.explain_generic(builder, change, estimate);
+        };
+
+        if change.resource_type == "aws_cloudwatch_log_group" {
+            let ingestion = rate.charges.first().map_or(0.0, |charge| charge.quantity);
+            let value = format!("{} GB/month", ingestion);
+            if self.usage.get(change, LOG_INGESTION_GB_KEY).is_some() {
+                builder.add_configuration_extraction(LOG_INGESTION_GB_KEY, &value, true);
+            } else {
+                builder.add_cold_start_inference(
+                    LOG_INGESTION_GB_KEY,
+                    &value,
+                    "No usage profile for this log group",
+                );
+            }
+            let config = change.new_config.as_ref().or(change.old_config.as_ref());
+            let retention = config.and_then(|c| c.get("retention_in_days"));
+            builder.add_configuration_extraction(
+                "retention_in_days",
+                &retention.map_or("never expire".to_string(), |days| days.to_string()),
+                retention.is_some(),
+            );
+        }
+
+        for charge in &rate.charges {
+            builder.add_calculation(
+                &format!("Monthly {} Cost", charge.name),
+                &format!(
+                    "{:.2} {} × ${:.2}/{}",
+                    charge.quantity,
+                    charge.unit,
+                    charge.unit_price,
+                    charge.unit.trim_end_matches('s')
+                ),
+                charge.monthly(),
+                "$/month",
+            );
+        }
+
+        let total = rate.monthly();
+        builder.set_final_estimate(
+            total,
+            estimate.prediction_interval_low,
+            estimate.prediction_interval_high,
+            rate.charges
+                .iter()
+                .map(|charge| CostComponent {
+                    name: charge.name.clone(),
+                    cost: charge.monthly(),
+                    percentage: if total > 0.0 {
+                        charge.monthly() / total * 100.0
+                    } else {
+                        0.0
+                    },
+                })
+                .collect(),
+        );
+        for note in rate.notes {
+            builder.add_assumption(note);
+        }
+    }
+
     /// Explain generic resource
     fn explain_generic(
         &self,
@@ -742,3 +1541,265 @@ impl<'a> PredictionExplainer<'a> {
         7.6 * size_multiplier // Base on t3.micro
     }
 }
+
+/// Capacity of an instance type (e.g. `m5.large`, `db.r5.xlarge`) relative to `micro`
+pub(crate) fn size_capacity(instance: &str) -> Option<f64> {
+    let (_, size) = instance.rsplit_once('.')?;
+    SIZE_LADDER
+        .iter()
+        .find(|(s, _)| *s == size)
+        .map(|(_, capacity)| *capacity)
+}
+
+/// Next size down in the same family and its monthly cost
+///
+/// Prices come from heuristics when both sizes are listed; otherwise the
+/// current cost is scaled by the capacity ratio of the two sizes.
+fn downsize(
+    prices: &HashMap<String, InstanceCost>,
+    instance: &str,
+    current_cost: f64,
+) -> Option<(String, f64)> {
+    let (family, size) = instance.rsplit_once('.')?;
+    let index = SIZE_LADDER.iter().position(|(s, _)| *s == size)?;
+    let (smaller_size, smaller_capacity) = SIZE_LADDER.get(index.checked_sub(1)?)?;
+    let smaller = format!("{}.{}", family, smaller_size);
+
+    let cost = match (prices.get(instance), prices.get(&smaller)) {
+        (Some(_), Some(price)) => price.monthly,
+        _ => current_cost * smaller_capacity / SIZE_LADDER[index].1,
+    };
+    Some((smaller, cost))
+}
+
+/// Savings from moving `size_gb` of `current` storage to gp3, when both are priced
+fn storage_savings(
+    current: &str,
+    size_gb: f64,
+    per_gb: impl Fn(&str) -> Option<f64>,
+) -> Option<f64> {
+    if current == CHEAPER_STORAGE {
+        return None;
+    }
+    Some((per_gb(current)? - per_gb(CHEAPER_STORAGE)?) * size_gb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::minimal_heuristics::MinimalHeuristics;
+    use crate::engines::shared::models::ChangeAction;
+    use serde_json::json;
+
+    fn change(resource_type: &str, config: Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(format!("{}.main", resource_type))
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    #[test]
+    fn test_rds_counterfactuals_ranked_by_savings() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
+        let change = change(
+            "aws_db_instance",
+            json!({
+                "instance_class": "db.t3.small",
+                "engine": "postgres",
+                "allocated_storage": 100,
+                "multi_az": true
+            }),
+        );
+
+        let counterfactuals = explainer.counterfactuals(&change);
+        // gp2 and gp3 are priced the same, so only two changes save money
+        assert_eq!(counterfactuals.len(), 2);
+
+        let multi_az = &counterfactuals[0];
+        assert_eq!(multi_az.driver, CostDriver::MultiAz);
+        assert!((multi_az.monthly_savings - (26.28 + 11.5)).abs() < 1e-9);
+        assert_eq!(multi_az.description(), "Set multi_az = false");
+
+        let downsize = &counterfactuals[1];
+        assert_eq!(downsize.driver, CostDriver::InstanceSize);
+        assert_eq!(downsize.proposed_value, json!("db.t3.micro"));
+        assert!((downsize.monthly_savings - (26.28 - 13.14) * 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ec2_explanation_itemizes_adjustments() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
+        let change = change(
+            "aws_instance",
+            json!({
+                "instance_type": "t3.medium",
+                "tenancy": "dedicated",
+                "get_password_data": true
+            }),
+        );
+        let estimate = CostEstimate::builder()
+            .resource_id("aws_instance.main".to_string())
+            .monthly_cost(100.0)
+            .build();
+
+        let chain = explainer.explain(&change, &estimate);
+        let names: Vec<&str> = chain
+            .final_estimate
+            .components
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["EC2 Instance", "Tenancy (dedicated)", "License (windows)"]
+        );
+        // 30.37 base + 10% dedicated + 2 vCPU × $0.046 × 730
+        let expected = 30.37 * 1.1 + 2.0 * 0.046 * 730.0;
+        assert!((chain.final_estimate.monthly_cost - expected).abs() < 1e-9);
+        assert!(chain
+            .steps
+            .iter()
+            .any(|s| s.title == "Apply Dedicated Tenancy"));
+    }
+
+    #[test]
+    fn test_rds_explanation_reports_multi_az_and_autoscaling_range() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
+        let change = change(
+            "aws_db_instance",
+            json!({
+                "instance_class": "db.t3.small",
+                "engine": "postgres",
+                "storage_type": "gp3",
+                "allocated_storage": 100,
+                "max_allocated_storage": 300,
+                "iops": 4000,
+                "multi_az": true
+            }),
+        );
+        let estimate = CostEstimate::builder()
+            .resource_id("aws_db_instance.main".to_string())
+            .monthly_cost(0.0)
+            .build();
+
+        let chain = explainer.explain(&change, &estimate);
+        let components: Vec<(&str, f64)> = chain
+            .final_estimate
+            .components
+            .iter()
+            .map(|c| (c.name.as_str(), c.cost))
+            .collect();
+        // 1000 IOPS above the 3000 included with gp3
+        let single_az = 26.28 + 11.5 + 20.0;
+        assert_eq!(
+            components.iter().map(|c| c.0).collect::<Vec<_>>(),
+            vec![
+                "RDS Instance",
+                "Storage (GP3)",
+                "Provisioned IOPS",
+                "Multi-AZ Standby"
+            ]
+        );
+        assert!((components[3].1 - single_az).abs() < 1e-9);
+        assert!((chain.final_estimate.monthly_cost - single_az * 2.0).abs() < 1e-9);
+        // Storage can grow by 200 GB on both replicas
+        let high = (single_az + 200.0 * 0.115) * 2.0;
+        assert!((chain.final_estimate.interval_high - high).abs() < 1e-9);
+        assert!(chain
+            .key_assumptions
+            .iter()
+            .any(|a| a.contains("autoscales")));
+    }
+
+    #[test]
+    fn test_node_group_explanation_scales_node_cost() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
+        let node_group = change(
+            "aws_eks_node_group",
+            json!({
+                "instance_types": ["m5.large"],
+                "scaling_config": [{ "desired_size": 2, "min_size": 1, "max_size": 5 }]
+            }),
+        );
+        let estimate = CostEstimate::builder()
+            .resource_id("aws_eks_node_group.main".to_string())
+            .monthly_cost(0.0)
+            .build();
+
+        let chain = explainer.explain(&node_group, &estimate);
+        let node = heuristics.compute.ec2["m5.large"].monthly;
+        assert!((chain.final_estimate.monthly_cost - 2.0 * node).abs() < 1e-9);
+        assert!((chain.final_estimate.interval_high - 5.0 * node).abs() < 1e-9);
+        assert_eq!(chain.final_estimate.components[0].name, "EC2 Instance (×2)");
+
+        let cluster = change("aws_eks_cluster", json!({ "version": "1.29" }));
+        let chain = explainer.explain(&cluster, &estimate);
+        assert!((chain.final_estimate.monthly_cost - 73.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_log_group_explanation_splits_ingestion_and_storage() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
+        let log_group = change("aws_cloudwatch_log_group", json!({ "name": "/app" }));
+        let estimate = CostEstimate::builder()
+            .resource_id("aws_cloudwatch_log_group.main".to_string())
+            .monthly_cost(0.0)
+            .build();
+
+        let chain = explainer.explain(&log_group, &estimate);
+        let components = &chain.final_estimate.components;
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].name, "Log ingestion");
+        // 1 GB default ingestion kept for 12 months when logs never expire
+        assert!((components[1].cost - 12.0 * 0.03).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ec2_counterfactuals_include_storage_class() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
+        let change = change(
+            "aws_instance",
+            json!({
+                "instance_type": "m5.xlarge",
+                "root_block_device": [{"volume_type": "gp2", "volume_size": 500}]
+            }),
+        );
+
+        let counterfactuals = explainer.counterfactuals(&change);
+        assert_eq!(counterfactuals.len(), 2);
+        assert_eq!(
+            counterfactuals[0].description(),
+            "Downsize instance_type from m5.xlarge to m5.large"
+        );
+        // Inferred m5.xlarge cost halves at the next size down
+        assert!((counterfactuals[0].monthly_savings - 7.6 * 16.0 / 2.0).abs() < 1e-9);
+        assert_eq!(
+            counterfactuals[1].attribute,
+            "root_block_device.volume_type"
+        );
+        assert!((counterfactuals[1].monthly_savings - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_counterfactuals_for_smallest_configuration() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let explainer = PredictionExplainer::new(&heuristics);
+
+        let nano = change("aws_instance", json!({"instance_type": "t3.nano"}));
+        assert!(explainer.counterfactuals(&nano).is_empty());
+
+        let gp3 = change("aws_ebs_volume", json!({"type": "gp3", "size": 100}));
+        assert!(explainer.counterfactuals(&gp3).is_empty());
+
+        let unknown = change("aws_sqs_queue", json!({}));
+        assert!(explainer.counterfactuals(&unknown).is_empty());
+    }
+}
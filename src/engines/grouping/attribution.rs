@@ -12,6 +12,150 @@ pub struct AttributionPipeline {
     pub default_environment: String,
     /// Whether to use strict matching (case-sensitive)
     pub strict_matching: bool,
+    /// How shared resources (NAT, load balancers, EKS control plane) are split
+    #[serde(default)]
+    pub shared_costs: SharedCostConfig,
+}
+
+/// Strategy for splitting the cost of a shared resource across consumers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SharedCostStrategy {
+    /// Attribute shared cost to the shared resource's own tags
+    #[default]
+    None,
+    /// Split equally between consumers
+    EvenSplit,
+    /// Split proportionally to each consumer's direct (downstream) cost
+    Proportional,
+    /// Split using configured weights per consumer
+    Weighted,
+}
+
+impl SharedCostStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SharedCostStrategy::None => "none",
+            SharedCostStrategy::EvenSplit => "even_split",
+            SharedCostStrategy::Proportional => "proportional",
+            SharedCostStrategy::Weighted => "weighted",
+        }
+    }
+}
+
+impl std::str::FromStr for SharedCostStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "none" => Ok(SharedCostStrategy::None),
+            "even" | "even_split" => Ok(SharedCostStrategy::EvenSplit),
+            "proportional" => Ok(SharedCostStrategy::Proportional),
+            "weighted" => Ok(SharedCostStrategy::Weighted),
+            _ => Err(format!(
+                "Invalid shared cost strategy '{}'. Use none, even, proportional or weighted",
+                s
+            )),
+        }
+    }
+}
+
+/// Configuration for shared-cost allocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedCostConfig {
+    /// Allocation strategy
+    #[serde(default)]
+    pub strategy: SharedCostStrategy,
+    /// Canonical tag key identifying consumers (e.g. "owner", "cost_center")
+    #[serde(default = "default_shared_dimension")]
+    pub dimension: String,
+    /// Resource types treated as shared
+    #[serde(default = "default_shared_resource_types")]
+    pub resource_types: Vec<String>,
+    /// Consumer weights for the weighted strategy (consumer -> weight)
+    #[serde(default)]
+    pub weights: HashMap<String, f64>,
+}
+
+fn default_shared_dimension() -> String {
+    "owner".to_string()
+}
+
+fn default_shared_resource_types() -> Vec<String> {
+    vec![
+        "aws_nat_gateway".to_string(),
+        "aws_lb".to_string(),
+        "aws_alb".to_string(),
+        "aws_elb".to_string(),
+        "aws_eks_cluster".to_string(),
+    ]
+}
+
+impl Default for SharedCostConfig {
+    fn default() -> Self {
+        Self {
+            strategy: SharedCostStrategy::None,
+            dimension: default_shared_dimension(),
+            resource_types: default_shared_resource_types(),
+            weights: HashMap::new(),
+        }
+    }
+}
+
+impl SharedCostConfig {
+    pub fn new(strategy: SharedCostStrategy) -> Self {
+        Self {
+            strategy,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_dimension(mut self, dimension: impl Into<String>) -> Self {
+        self.dimension = dimension.into();
+        self
+    }
+
+    pub fn with_weight(mut self, consumer: impl Into<String>, weight: f64) -> Self {
+        self.weights.insert(consumer.into(), weight);
+        self
+    }
+
+    /// Whether a resource type is treated as shared
+    pub fn is_shared(&self, resource_type: &str) -> bool {
+        self.strategy != SharedCostStrategy::None
+            && self.resource_types.iter().any(|t| t == resource_type)
+    }
+
+    /// Split `cost` across consumers given their direct costs.
+    /// Falls back to an even split when proportional or weighted bases are all zero.
+    pub fn split(&self, cost: f64, consumers: &[(String, f64)]) -> Vec<(String, f64)> {
+        if consumers.is_empty() {
+            return Vec::new();
+        }
+
+        let basis: Vec<f64> = match self.strategy {
+            SharedCostStrategy::Proportional => consumers.iter().map(|(_, c)| c.max(0.0)).collect(),
+            SharedCostStrategy::Weighted => consumers
+                .iter()
+                .map(|(name, _)| self.weights.get(name).copied().unwrap_or(0.0).max(0.0))
+                .collect(),
+            SharedCostStrategy::None | SharedCostStrategy::EvenSplit => vec![1.0; consumers.len()],
+        };
+        let total: f64 = basis.iter().sum();
+
+        consumers
+            .iter()
+            .zip(basis)
+            .map(|((name, _), weight)| {
+                let share = if total > 0.0 {
+                    weight / total
+                } else {
+                    1.0 / consumers.len() as f64
+                };
+                (name.clone(), cost * share)
+            })
+            .collect()
+    }
 }
 
 impl Default for AttributionPipeline {
@@ -82,6 +226,7 @@ impl Default for AttributionPipeline {
             tag_mappings,
             default_environment: "unknown".to_string(),
             strict_matching: false,
+            shared_costs: SharedCostConfig::default(),
         }
     }
 }
@@ -91,6 +236,11 @@ impl AttributionPipeline {
         Self::default()
     }
 
+    pub fn with_shared_costs(mut self, shared_costs: SharedCostConfig) -> Self {
+        self.shared_costs = shared_costs;
+        self
+    }
+
     /// Extract normalized tags from raw resource tags
     pub fn extract_tags(&self, raw_tags: &HashMap<String, String>) -> HashMap<String, String> {
         let mut normalized = HashMap::new();
@@ -125,44 +275,104 @@ impl AttributionPipeline {
         resources: &[(String, String, f64, HashMap<String, String>)], // (address, type, cost, tags)
     ) -> AttributionReport {
         let mut report = AttributionReport::new();
+        let mut shared = Vec::new();
 
         for (address, resource_type, cost, raw_tags) in resources {
-            let normalized_tags = self.extract_tags(raw_tags);
-
-            let environment = self.infer_environment(address, raw_tags);
-            let cost_center = normalized_tags
-                .get("cost_center")
-                .cloned()
-                .unwrap_or_else(|| "untagged".to_string());
-            let owner = normalized_tags
-                .get("owner")
-                .cloned()
-                .unwrap_or_else(|| "untagged".to_string());
-            let project = normalized_tags
-                .get("project")
-                .cloned()
-                .unwrap_or_else(|| "untagged".to_string());
-            let application = normalized_tags
-                .get("application")
-                .cloned()
-                .unwrap_or_else(|| "untagged".to_string());
-
-            report.add_allocation(Attribution {
-                resource_address: address.clone(),
-                resource_type: resource_type.clone(),
-                environment,
-                cost_center,
-                owner,
-                project,
-                application,
-                monthly_cost: *cost,
-                tags: normalized_tags,
+            let attribution = self.attribute(address, resource_type, *cost, raw_tags);
+            if self.shared_costs.is_shared(resource_type) {
+                shared.push(attribution);
+            } else {
+                report.add_allocation(attribution);
+            }
+        }
+
+        if shared.is_empty() {
+            return report;
+        }
+
+        // Consumers are the distinct dimension values among direct allocations
+        let mut direct_costs: HashMap<String, f64> = HashMap::new();
+        for allocation in &report.allocations {
+            if let Some(consumer) = allocation.dimension_value(&self.shared_costs.dimension) {
+                *direct_costs.entry(consumer.to_string()).or_insert(0.0) += allocation.monthly_cost;
+            }
+        }
+        let mut consumers: Vec<(String, f64)> = direct_costs
+            .into_iter()
+            .filter(|(consumer, _)| consumer != "untagged")
+            .collect();
+        consumers.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for attribution in shared {
+            let shares = self
+                .shared_costs
+                .split(attribution.monthly_cost, &consumers);
+            if shares.is_empty() {
+                report.add_allocation(attribution);
+                continue;
+            }
+
+            report.shared_allocations.push(SharedAllocation {
+                resource_address: attribution.resource_address.clone(),
+                resource_type: attribution.resource_type.clone(),
+                monthly_cost: attribution.monthly_cost,
+                strategy: self.shared_costs.strategy,
+                shares: shares.clone(),
             });
+
+            for (consumer, amount) in shares {
+                let mut split = attribution.clone();
+                split.set_dimension_value(&self.shared_costs.dimension, consumer);
+                split.monthly_cost = amount;
+                split.shared = true;
+                report.add_allocation(split);
+            }
         }
 
         report
     }
 
+    fn attribute(
+        &self,
+        address: &str,
+        resource_type: &str,
+        cost: f64,
+        raw_tags: &HashMap<String, String>,
+    ) -> Attribution {
+        let normalized_tags = self.extract_tags(raw_tags);
+
+        let environment = self.infer_environment(address, raw_tags);
+        let cost_center = normalized_tags
+            .get("cost_center")
+            .cloned()
+            .unwrap_or_else(|| "untagged".to_string());
+        let owner = normalized_tags
+            .get("owner")
+            .cloned()
+            .unwrap_or_else(|| "untagged".to_string());
+        let project = normalized_tags
+            .get("project")
+            .cloned()
+            .unwrap_or_else(|| "untagged".to_string());
+        let application = normalized_tags
+            .get("application")
+            .cloned()
+            .unwrap_or_else(|| "untagged".to_string());
+
+        Attribution {
+            resource_address: address.to_string(),
+            resource_type: resource_type.to_string(),
+            environment,
+            cost_center,
+            owner,
+            project,
+            application,
+            monthly_cost: cost,
+            tags: normalized_tags,
+            shared: false,
+        }
+    }
+
     /// Add custom tag mapping
     pub fn add_tag_mapping(&mut self, canonical_key: String, variants: Vec<String>) {
         self.tag_mappings.insert(canonical_key, variants);
@@ -181,6 +391,47 @@ pub struct Attribution {
     pub application: String,
     pub monthly_cost: f64,
     pub tags: HashMap<String, String>,
+    /// Whether this record is a share of a split shared resource
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub shared: bool,
+}
+
+impl Attribution {
+    /// Value of a canonical attribution dimension (environment, cost_center, owner, ...)
+    pub fn dimension_value(&self, dimension: &str) -> Option<&str> {
+        match dimension {
+            "environment" => Some(&self.environment),
+            "cost_center" => Some(&self.cost_center),
+            "owner" => Some(&self.owner),
+            "project" => Some(&self.project),
+            "application" => Some(&self.application),
+            _ => self.tags.get(dimension).map(String::as_str),
+        }
+    }
+
+    fn set_dimension_value(&mut self, dimension: &str, value: String) {
+        match dimension {
+            "environment" => self.environment = value,
+            "cost_center" => self.cost_center = value,
+            "owner" => self.owner = value,
+            "project" => self.project = value,
+            "application" => self.application = value,
+            _ => {
+                self.tags.insert(dimension.to_string(), value);
+            }
+        }
+    }
+}
+
+/// How a shared resource's cost was split across consumers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedAllocation {
+    pub resource_address: String,
+    pub resource_type: String,
+    pub monthly_cost: f64,
+    pub strategy: SharedCostStrategy,
+    /// (consumer, allocated monthly cost)
+    pub shares: Vec<(String, f64)>,
 }
 
 /// Attribution report with cost allocations
@@ -194,6 +445,9 @@ pub struct AttributionReport {
     pub cost_by_project: HashMap<String, f64>,
     pub cost_by_application: HashMap<String, f64>,
     pub untagged_cost: f64,
+    /// Shared resources whose cost was split across consumers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_allocations: Vec<SharedAllocation>,
 }
 
 impl AttributionReport {
@@ -207,6 +461,7 @@ impl AttributionReport {
             cost_by_project: HashMap::new(),
             cost_by_application: HashMap::new(),
             untagged_cost: 0.0,
+            shared_allocations: Vec::new(),
         }
     }
 
@@ -358,6 +613,22 @@ impl AttributionReport {
             ));
         }
 
+        // Shared cost allocation
+        if !self.shared_allocations.is_empty() {
+            report.push_str("\nShared Cost Allocation:\n");
+            for shared in &self.shared_allocations {
+                report.push_str(&format!(
+                    "  {} (${:.2}/mo, {}):\n",
+                    shared.resource_address,
+                    shared.monthly_cost,
+                    shared.strategy.as_str()
+                ));
+                for (consumer, cost) in &shared.shares {
+                    report.push_str(&format!("    → {}: ${:.2}/mo\n", consumer, cost));
+                }
+            }
+        }
+
         report
     }
 
@@ -365,12 +636,12 @@ impl AttributionReport {
     pub fn export_csv(&self) -> String {
         let mut csv = String::new();
         csv.push_str(
-            "Resource,Type,Environment,CostCenter,Owner,Project,Application,MonthlyCost\n",
+            "Resource,Type,Environment,CostCenter,Owner,Project,Application,MonthlyCost,Shared\n",
         );
 
         for allocation in &self.allocations {
             csv.push_str(&format!(
-                "{},{},{},{},{},{},{},{:.2}\n",
+                "{},{},{},{},{},{},{},{:.2},{}\n",
                 allocation.resource_address,
                 allocation.resource_type,
                 allocation.environment,
@@ -378,7 +649,8 @@ impl AttributionReport {
                 allocation.owner,
                 allocation.project,
                 allocation.application,
-                allocation.monthly_cost
+                allocation.monthly_cost,
+                allocation.shared
             ));
         }
 
@@ -474,6 +746,7 @@ mod tests {
             application: "app1".to_string(),
             monthly_cost: 100.0,
             tags: HashMap::new(),
+            shared: false,
         });
 
         report.add_allocation(Attribution {
@@ -486,10 +759,83 @@ mod tests {
             application: "untagged".to_string(),
             monthly_cost: 50.0,
             tags: HashMap::new(),
+            shared: false,
         });
 
         assert_eq!(report.total_cost, 150.0);
         assert_eq!(report.untagged_cost, 50.0);
         assert!((report.tagging_coverage() - 66.67).abs() < 0.1);
     }
+
+    fn shared_cost_resources() -> Vec<(String, String, f64, HashMap<String, String>)> {
+        let team = |name: &str| HashMap::from([("Team".to_string(), name.to_string())]);
+        vec![
+            (
+                "aws_instance.payments".to_string(),
+                "aws_instance".to_string(),
+                300.0,
+                team("payments"),
+            ),
+            (
+                "aws_instance.search".to_string(),
+                "aws_instance".to_string(),
+                100.0,
+                team("search"),
+            ),
+            (
+                "aws_nat_gateway.main".to_string(),
+                "aws_nat_gateway".to_string(),
+                40.0,
+                team("platform"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_shared_cost_default_keeps_owner() {
+        let report =
+            AttributionPipeline::new().generate_attribution_report(&shared_cost_resources());
+        assert_eq!(report.cost_by_owner.get("platform"), Some(&40.0));
+        assert!(report.shared_allocations.is_empty());
+    }
+
+    #[test]
+    fn test_shared_cost_even_split() {
+        let pipeline = AttributionPipeline::new()
+            .with_shared_costs(SharedCostConfig::new(SharedCostStrategy::EvenSplit));
+        let report = pipeline.generate_attribution_report(&shared_cost_resources());
+
+        assert_eq!(report.total_cost, 440.0);
+        assert_eq!(report.cost_by_owner.get("payments"), Some(&320.0));
+        assert_eq!(report.cost_by_owner.get("search"), Some(&120.0));
+        assert!(!report.cost_by_owner.contains_key("platform"));
+        assert_eq!(report.shared_allocations.len(), 1);
+        assert_eq!(report.allocations.iter().filter(|a| a.shared).count(), 2);
+    }
+
+    #[test]
+    fn test_shared_cost_proportional_and_weighted() {
+        let pipeline = AttributionPipeline::new()
+            .with_shared_costs(SharedCostConfig::new(SharedCostStrategy::Proportional));
+        let report = pipeline.generate_attribution_report(&shared_cost_resources());
+        assert_eq!(report.cost_by_owner.get("payments"), Some(&330.0));
+        assert_eq!(report.cost_by_owner.get("search"), Some(&110.0));
+
+        let pipeline = AttributionPipeline::new().with_shared_costs(
+            SharedCostConfig::new(SharedCostStrategy::Weighted)
+                .with_weight("payments", 1.0)
+                .with_weight("search", 3.0),
+        );
+        let report = pipeline.generate_attribution_report(&shared_cost_resources());
+        assert_eq!(report.cost_by_owner.get("payments"), Some(&310.0));
+        assert_eq!(report.cost_by_owner.get("search"), Some(&130.0));
+        assert!(report.format_text().contains("Shared Cost Allocation"));
+    }
+
+    #[test]
+    fn test_weighted_without_weights_falls_back_to_even() {
+        let config = SharedCostConfig::new(SharedCostStrategy::Weighted);
+        let shares = config.split(10.0, &[("a".to_string(), 1.0), ("b".to_string(), 9.0)]);
+        assert_eq!(shares, vec![("a".to_string(), 5.0), ("b".to_string(), 5.0)]);
+    }
 }
@@ -1,8 +1,17 @@
 // Group resources by environment (dev, staging, prod)
 
+use super::environment_mapping::EnvironmentResolver;
+use crate::engines::shared::pruning::REMAINDER_LABEL;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Tag keys naming a resource's environment, in priority order
+pub(crate) const ENVIRONMENT_TAG_KEYS: [&str; 5] =
+    ["Environment", "environment", "Env", "env", "ENVIRONMENT"];
+
+/// Low-confidence resources listed per warning before the rest are counted
+const WARNING_SAMPLE: usize = 3;
+
 /// Type alias for resource tuple: (address, type, service, tags, cost)
 pub type ResourceTuple = (String, String, String, HashMap<String, String>, f64);
 
@@ -21,6 +30,12 @@ pub struct EnvironmentGroup {
     pub cost_by_type: HashMap<String, f64>,
     /// Cost breakdown by service
     pub cost_by_service: HashMap<String, f64>,
+    /// Mean confidence of the environment inferred for each resource, 0.0 to 1.0
+    #[serde(default)]
+    pub confidence: f64,
+    /// Resources whose environment was inferred below the minimum confidence
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub low_confidence: Vec<String>,
 }
 
 impl EnvironmentGroup {
@@ -32,6 +47,8 @@ impl EnvironmentGroup {
             resource_count: 0,
             cost_by_type: HashMap::new(),
             cost_by_service: HashMap::new(),
+            confidence: 0.0,
+            low_confidence: Vec::new(),
         }
     }
 
@@ -49,6 +66,29 @@ impl EnvironmentGroup {
         *self.cost_by_service.entry(service).or_insert(0.0) += cost;
     }
 
+    /// One "everything else" group totalling groups pruned from a report,
+    /// with the resource-weighted confidence of its members
+    pub fn remainder(groups: Vec<EnvironmentGroup>) -> Self {
+        let mut remainder = Self::new(REMAINDER_LABEL.to_string());
+        let mut weighted_confidence = 0.0;
+        for group in groups {
+            weighted_confidence += group.confidence * group.resource_count as f64;
+            remainder.resources.extend(group.resources);
+            remainder.monthly_cost += group.monthly_cost;
+            remainder.resource_count += group.resource_count;
+            for (resource_type, cost) in group.cost_by_type {
+                *remainder.cost_by_type.entry(resource_type).or_insert(0.0) += cost;
+            }
+            for (service, cost) in group.cost_by_service {
+                *remainder.cost_by_service.entry(service).or_insert(0.0) += cost;
+            }
+        }
+        if remainder.resource_count > 0 {
+            remainder.confidence = weighted_confidence / remainder.resource_count as f64;
+        }
+        remainder
+    }
+
     pub fn average_cost_per_resource(&self) -> f64 {
         if self.resource_count == 0 {
             0.0
@@ -71,22 +111,42 @@ impl EnvironmentGroup {
 
 /// Group resources by environment extracted from tags or resource names
 pub fn group_by_environment(resources: &[ResourceTuple]) -> Vec<EnvironmentGroup> {
+    group_by_environment_with(resources, &EnvironmentResolver::default())
+}
+
+/// Group resources by the environment `resolver` infers for each, scoring
+/// every group by how confidently its resources were placed
+pub fn group_by_environment_with(
+    resources: &[ResourceTuple],
+    resolver: &EnvironmentResolver,
+) -> Vec<EnvironmentGroup> {
     let mut groups: HashMap<String, EnvironmentGroup> = HashMap::new();
 
     for (address, resource_type, service, tags, cost) in resources {
-        let environment = infer_environment(address, tags);
+        let inference = resolver.resolve(address, tags);
         let group = groups
-            .entry(environment.clone())
-            .or_insert_with(|| EnvironmentGroup::new(environment));
+            .entry(inference.environment.clone())
+            .or_insert_with(|| EnvironmentGroup::new(inference.environment));
         group.add_resource(
             address.clone(),
             resource_type.clone(),
             service.clone(),
             *cost,
         );
+        // Running sum, turned into a mean below
+        group.confidence += inference.confidence;
+        if inference.confidence < resolver.min_confidence() {
+            group.low_confidence.push(address.clone());
+        }
     }
 
-    let mut result: Vec<EnvironmentGroup> = groups.into_values().collect();
+    let mut result: Vec<EnvironmentGroup> = groups
+        .into_values()
+        .map(|mut group| {
+            group.confidence /= group.resource_count.max(1) as f64;
+            group
+        })
+        .collect();
     result.sort_by(|a, b| b.monthly_cost.partial_cmp(&a.monthly_cost).unwrap());
     result
 }
@@ -99,49 +159,52 @@ pub fn group_by_environment(resources: &[ResourceTuple]) -> Vec<EnvironmentGroup
 /// 4. "env" tag
 /// 5. Address patterns (e.g., "prod-*", "*-staging", "dev_*")
 /// 6. Default to "unknown"
+///
+/// Projects with an `environment_mapping` should use
+/// [`EnvironmentResolver`] instead, which also scores the inference.
 pub fn infer_environment(address: &str, tags: &HashMap<String, String>) -> String {
     // Check tags in priority order
-    let tag_keys = ["Environment", "environment", "Env", "env", "ENVIRONMENT"];
-    for key in &tag_keys {
+    for key in &ENVIRONMENT_TAG_KEYS {
         if let Some(value) = tags.get(*key) {
             return normalize_environment(value);
         }
     }
 
-    // Check address patterns
+    infer_from_address(address).unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Environment named by address patterns, if any
+pub(crate) fn infer_from_address(address: &str) -> Option<String> {
     let lower = address.to_lowercase();
 
     // Production patterns
-    if lower.contains("prod") || lower.contains("production") || lower.contains("prd") {
-        return "production".to_string();
-    }
-
-    // Staging patterns
-    if lower.contains("stag") || lower.contains("stage") || lower.contains("staging") {
-        return "staging".to_string();
-    }
-
-    // Development patterns
-    if lower.contains("dev") || lower.contains("development") {
-        return "development".to_string();
-    }
-
-    // QA/Test patterns
-    if lower.contains("qa") || lower.contains("test") || lower.contains("testing") {
-        return "qa".to_string();
-    }
-
-    // UAT patterns
-    if lower.contains("uat") || lower.contains("acceptance") {
-        return "uat".to_string();
-    }
-
-    // Sandbox patterns
-    if lower.contains("sandbox") || lower.contains("sbx") {
-        return "sandbox".to_string();
-    }
-
-    "unknown".to_string()
+    let environment =
+        if lower.contains("prod") || lower.contains("production") || lower.contains("prd") {
+            "production"
+        }
+        // Staging patterns
+        else if lower.contains("stag") || lower.contains("stage") || lower.contains("staging") {
+            "staging"
+        }
+        // Development patterns
+        else if lower.contains("dev") || lower.contains("development") {
+            "development"
+        }
+        // QA/Test patterns
+        else if lower.contains("qa") || lower.contains("test") || lower.contains("testing") {
+            "qa"
+        }
+        // UAT patterns
+        else if lower.contains("uat") || lower.contains("acceptance") {
+            "uat"
+        }
+        // Sandbox patterns
+        else if lower.contains("sandbox") || lower.contains("sbx") {
+            "sandbox"
+        } else {
+            return None;
+        };
+    Some(environment.to_string())
 }
 
 /// Normalize environment names to standard values
@@ -273,6 +336,36 @@ pub fn detect_anomalies(groups: &[EnvironmentGroup]) -> Vec<EnvironmentAnomaly>
     anomalies
 }
 
+/// Warnings for resources whose environment could not be confidently inferred
+pub fn inference_warnings(groups: &[EnvironmentGroup]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for group in groups {
+        if group.low_confidence.is_empty() {
+            continue;
+        }
+        let mut sample =
+            group.low_confidence[..group.low_confidence.len().min(WARNING_SAMPLE)].join(", ");
+        if group.low_confidence.len() > WARNING_SAMPLE {
+            sample.push_str(&format!(
+                ", +{} more",
+                group.low_confidence.len() - WARNING_SAMPLE
+            ));
+        }
+        let reason = if group.environment == "unknown" {
+            "have no inferable environment".to_string()
+        } else {
+            format!("were placed in {} with low confidence", group.environment)
+        };
+        warnings.push(format!(
+            "{} resource(s) {} ({}); tag them or add an environment_mapping rule",
+            group.low_confidence.len(),
+            reason,
+            sample
+        ));
+    }
+    warnings
+}
+
 /// Generate an environment cost report
 pub fn generate_environment_report(groups: &[EnvironmentGroup]) -> String {
     let mut report = String::new();
@@ -290,8 +383,12 @@ pub fn generate_environment_report(groups: &[EnvironmentGroup]) -> String {
             0.0
         };
         report.push_str(&format!(
-            "  {} ${:.2}/mo ({:.1}%, {} resources)\n",
-            group.environment, group.monthly_cost, percentage, group.resource_count
+            "  {} ${:.2}/mo ({:.1}%, {} resources, {:.0}% confidence)\n",
+            group.environment,
+            group.monthly_cost,
+            percentage,
+            group.resource_count,
+            group.confidence * 100.0
         ));
 
         // Show top 3 services for this environment
@@ -315,6 +412,14 @@ pub fn generate_environment_report(groups: &[EnvironmentGroup]) -> String {
         }
     }
 
+    let warnings = inference_warnings(groups);
+    if !warnings.is_empty() {
+        report.push_str("\n⚠️  Environment Inference:\n");
+        for warning in warnings {
+            report.push_str(&format!("  {}\n", warning));
+        }
+    }
+
     report
 }
 
@@ -359,6 +464,41 @@ mod tests {
         assert_eq!(normalize_environment("dev"), "development");
     }
 
+    #[test]
+    fn test_group_confidence_and_warnings() {
+        let tagged = HashMap::from([("env".to_string(), "prod".to_string())]);
+        let resource = |address: &str, tags: &HashMap<String, String>| {
+            (
+                address.to_string(),
+                "aws_instance".to_string(),
+                "EC2".to_string(),
+                tags.clone(),
+                10.0,
+            )
+        };
+        let resources = vec![
+            resource("aws_instance.web", &tagged),
+            resource("aws_instance.prod_api", &HashMap::new()),
+            resource("aws_instance.worker", &HashMap::new()),
+        ];
+
+        let groups = group_by_environment(&resources);
+        let production = groups
+            .iter()
+            .find(|g| g.environment == "production")
+            .unwrap();
+        assert_eq!(production.confidence, 0.75);
+        assert_eq!(production.low_confidence, vec!["aws_instance.prod_api"]);
+
+        let warnings = inference_warnings(&groups);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings
+            .iter()
+            .any(|w| w
+                .starts_with("1 resource(s) have no inferable environment (aws_instance.worker)")));
+        assert!(generate_environment_report(&groups).contains("Environment Inference"));
+    }
+
     #[test]
     fn test_detect_anomalies() {
         let groups = vec![
@@ -369,6 +509,8 @@ mod tests {
                 resource_count: 10,
                 cost_by_type: HashMap::new(),
                 cost_by_service: HashMap::new(),
+                confidence: 1.0,
+                low_confidence: Vec::new(),
             },
             EnvironmentGroup {
                 environment: "development".to_string(),
@@ -377,6 +519,8 @@ mod tests {
                 resource_count: 15,
                 cost_by_type: HashMap::new(),
                 cost_by_service: HashMap::new(),
+                confidence: 1.0,
+                low_confidence: Vec::new(),
             },
         ];
 
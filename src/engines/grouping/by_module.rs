@@ -1,5 +1,6 @@
 // Group resources by Terraform module path
 
+use crate::engines::shared::pruning::REMAINDER_LABEL;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -36,6 +37,20 @@ impl ModuleGroup {
         *self.cost_by_type.entry(resource_type).or_insert(0.0) += cost;
     }
 
+    /// One "everything else" group totalling groups pruned from a report
+    pub fn remainder(groups: Vec<ModuleGroup>) -> Self {
+        let mut remainder = Self::new(REMAINDER_LABEL.to_string());
+        for group in groups {
+            remainder.resources.extend(group.resources);
+            remainder.monthly_cost += group.monthly_cost;
+            remainder.resource_count += group.resource_count;
+            for (resource_type, cost) in group.cost_by_type {
+                *remainder.cost_by_type.entry(resource_type).or_insert(0.0) += cost;
+            }
+        }
+        remainder
+    }
+
     pub fn average_cost_per_resource(&self) -> f64 {
         if self.resource_count == 0 {
             0.0
@@ -101,7 +116,7 @@ pub fn group_by_module(
 /// - "aws_instance.web" -> "root"
 /// - "module.vpc.aws_vpc.main" -> "root.vpc"
 /// - "module.vpc.module.subnets.aws_subnet.private" -> "root.vpc.subnets"
-fn extract_module_path(address: &str) -> String {
+pub(crate) fn extract_module_path(address: &str) -> String {
     let parts: Vec<&str> = address.split('.').collect();
     let mut path = Vec::new();
 
@@ -1,5 +1,6 @@
 // Group resources by AWS service
 
+use crate::engines::shared::pruning::REMAINDER_LABEL;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -71,6 +72,20 @@ impl ServiceGroup {
         *self.cost_by_type.entry(resource_type).or_insert(0.0) += cost;
     }
 
+    /// One "everything else" group totalling groups pruned from a report
+    pub fn remainder(groups: Vec<ServiceGroup>) -> Self {
+        let mut remainder = Self::new(REMAINDER_LABEL.to_string(), ServiceCategory::Other);
+        for group in groups {
+            remainder.resources.extend(group.resources);
+            remainder.monthly_cost += group.monthly_cost;
+            remainder.resource_count += group.resource_count;
+            for (resource_type, cost) in group.cost_by_type {
+                *remainder.cost_by_type.entry(resource_type).or_insert(0.0) += cost;
+            }
+        }
+        remainder
+    }
+
     pub fn average_cost_per_resource(&self) -> f64 {
         if self.resource_count == 0 {
             0.0
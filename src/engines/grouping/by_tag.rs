@@ -0,0 +1,306 @@
+// Group resources by arbitrary tag keys and multi-level dimensions
+
+use crate::engines::grouping::by_environment::infer_environment;
+use crate::engines::grouping::by_module::extract_module_path;
+use crate::engines::grouping::by_service::extract_service_info;
+use crate::engines::shared::pruning::REMAINDER_LABEL;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Resource tuple: (address, type, tags, cost)
+type TaggedResource = (String, String, HashMap<String, String>, f64);
+
+/// Value used when a resource has no tag for a tag dimension
+pub const UNTAGGED: &str = "untagged";
+
+/// A dimension resources can be grouped by
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupDimension {
+    Module,
+    Service,
+    Environment,
+    /// Value of a tag key (e.g. `team`, `cost-center`)
+    Tag(String),
+}
+
+impl GroupDimension {
+    /// Value of this dimension for a resource
+    pub fn value_for(
+        &self,
+        address: &str,
+        resource_type: &str,
+        tags: &HashMap<String, String>,
+    ) -> String {
+        match self {
+            GroupDimension::Module => extract_module_path(address),
+            GroupDimension::Service => extract_service_info(resource_type).0,
+            GroupDimension::Environment => infer_environment(address, tags),
+            GroupDimension::Tag(key) => tag_value(tags, key).unwrap_or(UNTAGGED).to_string(),
+        }
+    }
+
+    /// Parse a comma-separated list such as `tag:team,environment`
+    pub fn parse_list(spec: &str) -> Result<Vec<GroupDimension>, String> {
+        spec.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(GroupDimension::from_str)
+            .collect()
+    }
+}
+
+impl FromStr for GroupDimension {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "module" => Ok(GroupDimension::Module),
+            "service" => Ok(GroupDimension::Service),
+            "environment" | "env" => Ok(GroupDimension::Environment),
+            _ => match s.split_once(':') {
+                Some((prefix, key)) if prefix.eq_ignore_ascii_case("tag") && !key.is_empty() => {
+                    Ok(GroupDimension::Tag(key.to_string()))
+                }
+                _ => Err(format!(
+                    "Invalid grouping dimension '{}'. Use module, service, environment or tag:<key>",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for GroupDimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupDimension::Module => write!(f, "module"),
+            GroupDimension::Service => write!(f, "service"),
+            GroupDimension::Environment => write!(f, "environment"),
+            GroupDimension::Tag(key) => write!(f, "tag:{}", key),
+        }
+    }
+}
+
+/// A group at one level of a (possibly multi-level) grouping
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionGroup {
+    /// Dimension this level groups by (e.g. "tag:team")
+    pub dimension: String,
+    /// Dimension value shared by all resources in the group
+    pub value: String,
+    /// Resource addresses in this group
+    pub resources: Vec<String>,
+    /// Total monthly cost for this group
+    pub monthly_cost: f64,
+    /// Number of resources
+    pub resource_count: usize,
+    /// Sub-groups for the next dimension, if any
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DimensionGroup>,
+}
+
+impl DimensionGroup {
+    /// One "everything else" group totalling sibling groups pruned from a
+    /// report; their sub-groups are not carried over
+    pub fn remainder(groups: Vec<DimensionGroup>) -> Self {
+        let dimension = groups
+            .first()
+            .map(|g| g.dimension.clone())
+            .unwrap_or_default();
+        Self {
+            dimension,
+            value: REMAINDER_LABEL.to_string(),
+            resources: groups.iter().flat_map(|g| g.resources.clone()).collect(),
+            monthly_cost: groups.iter().map(|g| g.monthly_cost).sum(),
+            resource_count: groups.iter().map(|g| g.resource_count).sum(),
+            children: Vec::new(),
+        }
+    }
+}
+
+/// Group resources by a single tag key
+pub fn group_by_tag(
+    resources: &[(String, String, HashMap<String, String>, f64)], // (address, type, tags, cost)
+    key: &str,
+) -> Vec<DimensionGroup> {
+    group_by_dimensions(resources, &[GroupDimension::Tag(key.to_string())])
+}
+
+/// Group resources hierarchically, one level per dimension (e.g. team → environment)
+pub fn group_by_dimensions(
+    resources: &[(String, String, HashMap<String, String>, f64)], // (address, type, tags, cost)
+    dimensions: &[GroupDimension],
+) -> Vec<DimensionGroup> {
+    let refs: Vec<&TaggedResource> = resources.iter().collect();
+    group_level(&refs, dimensions)
+}
+
+fn group_level(
+    resources: &[&TaggedResource],
+    dimensions: &[GroupDimension],
+) -> Vec<DimensionGroup> {
+    let Some((dimension, rest)) = dimensions.split_first() else {
+        return Vec::new();
+    };
+
+    let mut buckets: HashMap<String, Vec<&TaggedResource>> = HashMap::new();
+    for resource in resources {
+        let (address, resource_type, tags, _) = resource;
+        buckets
+            .entry(dimension.value_for(address, resource_type, tags))
+            .or_default()
+            .push(resource);
+    }
+
+    let mut groups: Vec<DimensionGroup> = buckets
+        .into_iter()
+        .map(|(value, members)| DimensionGroup {
+            dimension: dimension.to_string(),
+            value,
+            resources: members
+                .iter()
+                .map(|(address, ..)| address.clone())
+                .collect(),
+            monthly_cost: members.iter().map(|(.., cost)| cost).sum(),
+            resource_count: members.len(),
+            children: group_level(&members, rest),
+        })
+        .collect();
+
+    groups.sort_by(|a, b| {
+        b.monthly_cost
+            .total_cmp(&a.monthly_cost)
+            .then_with(|| a.value.cmp(&b.value))
+    });
+    groups
+}
+
+/// Tag lookup: exact key first, then case-insensitive
+fn tag_value<'a>(tags: &'a HashMap<String, String>, key: &str) -> Option<&'a str> {
+    tags.get(key)
+        .or_else(|| {
+            tags.iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v)
+        })
+        .map(String::as_str)
+}
+
+/// Generate an indented tree of hierarchical groups
+pub fn generate_dimension_tree(groups: &[DimensionGroup]) -> String {
+    let total: f64 = groups.iter().map(|g| g.monthly_cost).sum();
+    let mut tree = String::new();
+    push_tree(&mut tree, groups, total, 0);
+    tree
+}
+
+fn push_tree(tree: &mut String, groups: &[DimensionGroup], parent_cost: f64, depth: usize) {
+    for group in groups {
+        let share = if parent_cost > 0.0 {
+            group.monthly_cost / parent_cost * 100.0
+        } else {
+            0.0
+        };
+        tree.push_str(&format!(
+            "{}├─ {}={} (${:.2}/mo, {:.1}%, {} resources)\n",
+            "  ".repeat(depth),
+            group.dimension,
+            group.value,
+            group.monthly_cost,
+            share,
+            group.resource_count
+        ));
+        push_tree(tree, &group.children, group.monthly_cost, depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(
+        address: &str,
+        tags: &[(&str, &str)],
+        cost: f64,
+    ) -> (String, String, HashMap<String, String>, f64) {
+        (
+            address.to_string(),
+            "aws_instance".to_string(),
+            tags.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            cost,
+        )
+    }
+
+    #[test]
+    fn test_parse_dimensions() {
+        assert_eq!(
+            GroupDimension::parse_list("tag:team, env").unwrap(),
+            vec![
+                GroupDimension::Tag("team".to_string()),
+                GroupDimension::Environment
+            ]
+        );
+        assert_eq!(
+            "tag:cost-center".parse::<GroupDimension>().unwrap(),
+            GroupDimension::Tag("cost-center".to_string())
+        );
+        assert!("tag:".parse::<GroupDimension>().is_err());
+        assert!("owner".parse::<GroupDimension>().is_err());
+    }
+
+    #[test]
+    fn test_group_by_tag_with_untagged() {
+        let resources = vec![
+            resource("aws_instance.a", &[("Team", "payments")], 100.0),
+            resource("aws_instance.b", &[("team", "payments")], 50.0),
+            resource("aws_instance.c", &[], 20.0),
+        ];
+
+        let groups = group_by_tag(&resources, "team");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].value, "payments");
+        assert_eq!(groups[0].monthly_cost, 150.0);
+        assert_eq!(groups[1].value, UNTAGGED);
+    }
+
+    #[test]
+    fn test_multi_level_grouping() {
+        let resources = vec![
+            resource(
+                "aws_instance.a",
+                &[("team", "payments"), ("Environment", "prod")],
+                100.0,
+            ),
+            resource(
+                "aws_instance.b",
+                &[("team", "payments"), ("Environment", "dev")],
+                10.0,
+            ),
+            resource(
+                "aws_instance.c",
+                &[("team", "search"), ("Environment", "prod")],
+                40.0,
+            ),
+        ];
+
+        let groups = group_by_dimensions(
+            &resources,
+            &GroupDimension::parse_list("tag:team,environment").unwrap(),
+        );
+
+        assert_eq!(groups[0].value, "payments");
+        assert_eq!(groups[0].children.len(), 2);
+        assert_eq!(groups[0].children[0].dimension, "environment");
+        assert_eq!(groups[0].children[0].value, "production");
+        assert_eq!(groups[0].children[0].monthly_cost, 100.0);
+
+        let tree = generate_dimension_tree(&groups);
+        assert!(tree.contains("├─ tag:team=payments ($110.00/mo"));
+        assert!(tree.contains("  ├─ environment=development ($10.00/mo"));
+    }
+}
@@ -0,0 +1,363 @@
+// Explicit environment mapping (`environment_mapping` in costpilot.yaml)
+//
+// Naming heuristics guess an environment from a resource address; the mapping
+// lets a project say where its environments come from instead: tag keys, the
+// Terraform workspace, or the directory the plan was generated in. Each
+// source carries a confidence so reports can flag guesses.
+
+use super::by_environment::{infer_from_address, normalize_environment, ENVIRONMENT_TAG_KEYS};
+use crate::engines::detection::custom_rules::wildcard_match;
+use crate::engines::shared::error_model::CostPilotError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Confidence below which an inferred environment is reported as a guess
+pub const DEFAULT_MIN_CONFIDENCE: f64 = 0.7;
+
+/// Where an environment was inferred from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InferenceSource {
+    Tag,
+    Workspace,
+    Directory,
+    Address,
+    /// Nothing matched; the environment is `unknown`
+    None,
+}
+
+impl InferenceSource {
+    /// Default order in which sources are consulted
+    pub const PRECEDENCE: [InferenceSource; 4] = [
+        InferenceSource::Tag,
+        InferenceSource::Workspace,
+        InferenceSource::Directory,
+        InferenceSource::Address,
+    ];
+
+    /// How far an environment from this source can be trusted, 0.0 to 1.0
+    pub fn confidence(&self) -> f64 {
+        match self {
+            InferenceSource::Tag => 1.0,
+            InferenceSource::Workspace => 0.9,
+            InferenceSource::Directory => 0.8,
+            InferenceSource::Address => 0.5,
+            InferenceSource::None => 0.0,
+        }
+    }
+}
+
+/// Map a workspace name or plan directory matching `pattern` (`*` wildcards)
+/// to an environment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MappingRule {
+    pub pattern: String,
+    pub environment: String,
+}
+
+/// How environments are inferred (`environment_mapping` in costpilot.yaml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentMapping {
+    /// Sources in the order they are consulted; the first that yields an
+    /// environment wins
+    #[serde(default = "default_precedence")]
+    pub precedence: Vec<InferenceSource>,
+
+    /// Tag keys naming the environment, checked in order
+    #[serde(default = "default_tag_keys")]
+    pub tag_keys: Vec<String>,
+
+    /// Terraform workspace name rules, first match wins
+    #[serde(default)]
+    pub workspaces: Vec<MappingRule>,
+
+    /// Plan directory rules, first match wins
+    #[serde(default)]
+    pub directories: Vec<MappingRule>,
+
+    /// Environments inferred with less confidence are reported
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f64,
+}
+
+fn default_precedence() -> Vec<InferenceSource> {
+    InferenceSource::PRECEDENCE.to_vec()
+}
+
+fn default_tag_keys() -> Vec<String> {
+    ENVIRONMENT_TAG_KEYS.iter().map(|k| k.to_string()).collect()
+}
+
+fn default_min_confidence() -> f64 {
+    DEFAULT_MIN_CONFIDENCE
+}
+
+impl Default for EnvironmentMapping {
+    fn default() -> Self {
+        Self {
+            precedence: default_precedence(),
+            tag_keys: default_tag_keys(),
+            workspaces: Vec::new(),
+            directories: Vec::new(),
+            min_confidence: DEFAULT_MIN_CONFIDENCE,
+        }
+    }
+}
+
+impl EnvironmentMapping {
+    /// Load `environment_mapping` from a costpilot.yaml file,
+    /// applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self, CostPilotError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `environment_mapping` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self, CostPilotError> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `environment_mapping` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(
+        content: &str,
+        profile: Option<&str>,
+    ) -> Result<Self, CostPilotError> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let mapping: Self = match root.get("environment_mapping").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid environment_mapping section: {}", e))
+            })?,
+            None => Self::default(),
+        };
+
+        if let Some(problem) = mapping.validate().first() {
+            return Err(CostPilotError::validation_error(problem.clone())
+                .with_hint("Check environment_mapping in costpilot.yaml"));
+        }
+
+        Ok(mapping)
+    }
+
+    /// Describe invalid settings; empty if the mapping is usable
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.precedence.contains(&InferenceSource::None) {
+            problems.push("precedence: 'none' is not a source".to_string());
+        }
+        for (i, source) in self.precedence.iter().enumerate() {
+            if self.precedence[..i].contains(source) {
+                problems.push(format!("precedence: {:?} is listed twice", source).to_lowercase());
+            }
+        }
+        if self.tag_keys.iter().any(|k| k.trim().is_empty()) {
+            problems.push("tag_keys: entries must not be empty".to_string());
+        }
+        let rules = [
+            ("workspaces", &self.workspaces),
+            ("directories", &self.directories),
+        ];
+        for (list, rules) in rules {
+            for rule in rules {
+                if rule.pattern.is_empty() || rule.environment.trim().is_empty() {
+                    problems.push(format!(
+                        "{}: entries need a pattern and an environment",
+                        list
+                    ));
+                }
+            }
+        }
+        if !(0.0..=1.0).contains(&self.min_confidence) {
+            problems.push("min_confidence must be between 0 and 1".to_string());
+        }
+        problems
+    }
+}
+
+/// An inferred environment and how it was found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentInference {
+    pub environment: String,
+    pub source: InferenceSource,
+    pub confidence: f64,
+}
+
+impl EnvironmentInference {
+    fn new(environment: String, source: InferenceSource) -> Self {
+        Self {
+            environment,
+            source,
+            confidence: source.confidence(),
+        }
+    }
+}
+
+/// Infers resource environments from a mapping and the plan's context
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentResolver {
+    mapping: EnvironmentMapping,
+    workspace: Option<String>,
+    directory: Option<String>,
+}
+
+impl EnvironmentResolver {
+    pub fn new(mapping: EnvironmentMapping) -> Self {
+        Self {
+            mapping,
+            ..Self::default()
+        }
+    }
+
+    /// Terraform workspace the plan was generated in
+    pub fn with_workspace(mut self, workspace: Option<String>) -> Self {
+        self.workspace = workspace;
+        self
+    }
+
+    /// Directory the plan was generated in, matched with `/` separators
+    pub fn with_directory(mut self, directory: &Path) -> Self {
+        let directory = directory
+            .canonicalize()
+            .unwrap_or_else(|_| directory.to_path_buf());
+        self.directory = Some(directory.to_string_lossy().replace('\\', "/"));
+        self
+    }
+
+    /// Workspace and directory of a plan file: `TF_WORKSPACE`, else the
+    /// workspace recorded in `.terraform/environment` next to the plan
+    pub fn for_plan(mapping: EnvironmentMapping, plan: &Path) -> Self {
+        let directory = match plan.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => Path::new(".").to_path_buf(),
+        };
+        let workspace = std::env::var("TF_WORKSPACE")
+            .ok()
+            .or_else(|| std::fs::read_to_string(directory.join(".terraform/environment")).ok())
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty());
+        Self::new(mapping)
+            .with_workspace(workspace)
+            .with_directory(&directory)
+    }
+
+    pub fn min_confidence(&self) -> f64 {
+        self.mapping.min_confidence
+    }
+
+    /// Environment of a resource, from the first source in precedence order
+    /// that names one; `unknown` with zero confidence otherwise
+    pub fn resolve(&self, address: &str, tags: &HashMap<String, String>) -> EnvironmentInference {
+        for source in &self.mapping.precedence {
+            let environment = match source {
+                InferenceSource::Tag => self
+                    .mapping
+                    .tag_keys
+                    .iter()
+                    .find_map(|key| tags.get(key))
+                    .map(|value| normalize_environment(value)),
+                InferenceSource::Workspace => self
+                    .workspace
+                    .as_deref()
+                    .and_then(|w| match_rules(&self.mapping.workspaces, w)),
+                InferenceSource::Directory => self
+                    .directory
+                    .as_deref()
+                    .and_then(|d| match_rules(&self.mapping.directories, d)),
+                InferenceSource::Address => infer_from_address(address),
+                InferenceSource::None => None,
+            };
+            if let Some(environment) = environment {
+                return EnvironmentInference::new(environment, *source);
+            }
+        }
+        EnvironmentInference::new("unknown".to_string(), InferenceSource::None)
+    }
+}
+
+fn match_rules(rules: &[MappingRule], text: &str) -> Option<String> {
+    rules
+        .iter()
+        .find(|rule| wildcard_match(&rule.pattern, text))
+        .map(|rule| normalize_environment(&rule.environment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+environment_mapping:
+  precedence: [workspace, tag, directory, address]
+  tag_keys: [Stage]
+  workspaces:
+    - pattern: "prod-*"
+      environment: prod
+  directories:
+    - pattern: "*/envs/staging*"
+      environment: staging
+"#;
+
+    #[test]
+    fn test_resolver_follows_precedence() {
+        let mapping = EnvironmentMapping::from_yaml(CONFIG).unwrap();
+        let tags = HashMap::from([("Stage".to_string(), "dev".to_string())]);
+
+        let resolver = EnvironmentResolver::new(mapping.clone())
+            .with_workspace(Some("prod-eu".to_string()))
+            .with_directory(Path::new("/repo/envs/staging"));
+        let inference = resolver.resolve("aws_instance.web", &tags);
+        assert_eq!(inference.environment, "production");
+        assert_eq!(inference.source, InferenceSource::Workspace);
+        assert_eq!(inference.confidence, 0.9);
+
+        let resolver = EnvironmentResolver::new(mapping.clone());
+        assert_eq!(
+            resolver.resolve("aws_instance.web", &tags).source,
+            InferenceSource::Tag
+        );
+
+        let resolver =
+            EnvironmentResolver::new(mapping).with_directory(Path::new("/repo/envs/staging-us"));
+        let inference = resolver.resolve("aws_instance.prod_web", &HashMap::new());
+        assert_eq!(inference.environment, "staging");
+        assert_eq!(inference.source, InferenceSource::Directory);
+    }
+
+    #[test]
+    fn test_default_resolver_matches_heuristics() {
+        let resolver = EnvironmentResolver::default();
+        let inference = resolver.resolve("aws_instance.prod-web", &HashMap::new());
+        assert_eq!(inference.environment, "production");
+        assert_eq!(inference.source, InferenceSource::Address);
+        assert!(inference.confidence < resolver.min_confidence());
+
+        let inference = resolver.resolve("aws_instance.web", &HashMap::new());
+        assert_eq!(inference.environment, "unknown");
+        assert_eq!(inference.confidence, 0.0);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_mapping() {
+        let err = EnvironmentMapping::from_yaml(
+            "environment_mapping:\n  precedence: [tag, tag]\n  min_confidence: 2\n",
+        )
+        .unwrap_err();
+        assert!(err.message.contains("precedence: tag is listed twice"));
+
+        let mapping = EnvironmentMapping {
+            workspaces: vec![MappingRule {
+                pattern: String::new(),
+                environment: "dev".to_string(),
+            }],
+            min_confidence: 2.0,
+            ..EnvironmentMapping::default()
+        };
+        assert_eq!(mapping.validate().len(), 2);
+    }
+}
@@ -2,9 +2,11 @@
 
 use crate::engines::grouping::{
     attribution::{AttributionPipeline, AttributionReport},
-    by_environment::{generate_environment_report, group_by_environment, EnvironmentGroup},
+    by_environment::{generate_environment_report, group_by_environment_with, EnvironmentGroup},
     by_module::{generate_module_tree, group_by_module, ModuleGroup},
     by_service::{generate_service_report, group_by_service, ServiceGroup},
+    by_tag::{generate_dimension_tree, group_by_dimensions, DimensionGroup, GroupDimension},
+    environment_mapping::EnvironmentResolver,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -15,21 +17,27 @@ pub type ResourceTuple = (String, String, String, HashMap<String, String>, f64);
 /// Main grouping engine for organizing and analyzing resource costs
 pub struct GroupingEngine {
     attribution_pipeline: AttributionPipeline,
+    environment_resolver: EnvironmentResolver,
 }
 
 impl GroupingEngine {
     pub fn new() -> Self {
-        Self {
-            attribution_pipeline: AttributionPipeline::new(),
-        }
+        Self::with_pipeline(AttributionPipeline::new())
     }
 
     pub fn with_pipeline(attribution_pipeline: AttributionPipeline) -> Self {
         Self {
             attribution_pipeline,
+            environment_resolver: EnvironmentResolver::default(),
         }
     }
 
+    /// Infer environments with an explicit mapping instead of naming heuristics
+    pub fn with_environment_resolver(mut self, environment_resolver: EnvironmentResolver) -> Self {
+        self.environment_resolver = environment_resolver;
+        self
+    }
+
     /// Group resources by module and return results
     pub fn group_by_module(
         &self,
@@ -48,7 +56,16 @@ impl GroupingEngine {
 
     /// Group resources by environment and return results
     pub fn group_by_environment(&self, resources: &[ResourceTuple]) -> Vec<EnvironmentGroup> {
-        group_by_environment(resources)
+        group_by_environment_with(resources, &self.environment_resolver)
+    }
+
+    /// Group resources hierarchically by custom dimensions (e.g. tag:team → environment)
+    pub fn group_by_dimensions(
+        &self,
+        resources: &[(String, String, HashMap<String, String>, f64)], // (address, type, tags, cost)
+        dimensions: &[GroupDimension],
+    ) -> Vec<DimensionGroup> {
+        group_by_dimensions(resources, dimensions)
     }
 
     /// Generate attribution report for cost allocation
@@ -64,6 +81,16 @@ impl GroupingEngine {
     pub fn generate_comprehensive_report(
         &self,
         resources: &[(String, String, HashMap<String, String>, f64)], // (address, type, tags, cost)
+    ) -> ComprehensiveReport {
+        self.generate_comprehensive_report_with_dimensions(resources, &[])
+    }
+
+    /// Generate comprehensive grouping report, adding a custom hierarchical
+    /// grouping when dimensions are given
+    pub fn generate_comprehensive_report_with_dimensions(
+        &self,
+        resources: &[(String, String, HashMap<String, String>, f64)], // (address, type, tags, cost)
+        dimensions: &[GroupDimension],
     ) -> ComprehensiveReport {
         // Prepare data for module grouping
         let module_resources: Vec<(String, String, f64)> = resources
@@ -93,12 +120,15 @@ impl GroupingEngine {
         let service_groups = self.group_by_service(&service_resources);
         let environment_groups = self.group_by_environment(&env_resources);
         let attribution_report = self.generate_attribution_report(&attr_resources);
+        let custom_groups = self.group_by_dimensions(resources, dimensions);
 
         ComprehensiveReport {
             module_groups,
             service_groups,
             environment_groups,
             attribution_report,
+            custom_groups,
+            group_by: dimensions.iter().map(|d| d.to_string()).collect(),
             total_resources: resources.len(),
             total_cost: resources.iter().map(|(_, _, _, cost)| cost).sum(),
         }
@@ -128,6 +158,12 @@ pub struct ComprehensiveReport {
     pub service_groups: Vec<ServiceGroup>,
     pub environment_groups: Vec<EnvironmentGroup>,
     pub attribution_report: AttributionReport,
+    /// Hierarchical groups for the custom `group_by` dimensions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_groups: Vec<DimensionGroup>,
+    /// Custom grouping dimensions, outermost first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group_by: Vec<String>,
     pub total_resources: usize,
     pub total_cost: f64,
 }
@@ -168,6 +204,17 @@ impl ComprehensiveReport {
         report.push_str("═══════════════════════════════════════════════════════════════\n\n");
         report.push_str(&self.attribution_report.format_text());
 
+        // Custom grouping
+        if !self.custom_groups.is_empty() {
+            report.push_str("\n═══════════════════════════════════════════════════════════════\n");
+            report.push_str(&format!(
+                "Custom Grouping ({})\n",
+                self.group_by.join(" → ")
+            ));
+            report.push_str("═══════════════════════════════════════════════════════════════\n\n");
+            report.push_str(&generate_dimension_tree(&self.custom_groups));
+        }
+
         report
     }
 
@@ -293,6 +340,30 @@ mod tests {
         assert!(!report.environment_groups.is_empty());
     }
 
+    #[test]
+    fn test_comprehensive_report_with_dimensions() {
+        let engine = GroupingEngine::new();
+        let mut tags = HashMap::new();
+        tags.insert("team".to_string(), "payments".to_string());
+        tags.insert("Environment".to_string(), "prod".to_string());
+
+        let resources = vec![(
+            "aws_instance.web".to_string(),
+            "aws_instance".to_string(),
+            tags,
+            100.0,
+        )];
+
+        let dimensions = GroupDimension::parse_list("tag:team,environment").unwrap();
+        let report = engine.generate_comprehensive_report_with_dimensions(&resources, &dimensions);
+        assert_eq!(report.group_by, vec!["tag:team", "environment"]);
+        assert_eq!(report.custom_groups[0].value, "payments");
+        assert_eq!(report.custom_groups[0].children[0].value, "production");
+        assert!(report
+            .format_text()
+            .contains("Custom Grouping (tag:team → environment)"));
+    }
+
     #[test]
     fn test_grouping_options() {
         let options = GroupingOptions::new()
@@ -4,14 +4,19 @@ pub mod attribution;
 pub mod by_environment;
 pub mod by_module;
 pub mod by_service;
+pub mod by_tag;
+pub mod environment_mapping;
 pub mod grouping_engine;
 
 // Re-export main types
-pub use attribution::{Attribution, AttributionPipeline, AttributionReport};
+pub use attribution::{
+    Attribution, AttributionPipeline, AttributionReport, SharedAllocation, SharedCostConfig,
+    SharedCostStrategy,
+};
 pub use by_environment::{
     calculate_environment_ratios, detect_anomalies, generate_environment_report,
-    group_by_environment, infer_environment, normalize_environment, AnomalyType,
-    EnvironmentAnomaly, EnvironmentGroup, Severity,
+    group_by_environment, group_by_environment_with, infer_environment, inference_warnings,
+    normalize_environment, AnomalyType, EnvironmentAnomaly, EnvironmentGroup, Severity,
 };
 pub use by_module::{
     aggregate_module_hierarchy, generate_module_tree, group_by_module, ModuleGroup,
@@ -20,4 +25,11 @@ pub use by_service::{
     cost_by_category, generate_service_report, group_by_category, group_by_service,
     ServiceCategory, ServiceGroup,
 };
+pub use by_tag::{
+    generate_dimension_tree, group_by_dimensions, group_by_tag, DimensionGroup, GroupDimension,
+    UNTAGGED,
+};
+pub use environment_mapping::{
+    EnvironmentInference, EnvironmentMapping, EnvironmentResolver, InferenceSource, MappingRule,
+};
 pub use grouping_engine::{ComprehensiveReport, GroupingEngine, GroupingOptions, SortBy};
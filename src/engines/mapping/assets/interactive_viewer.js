@@ -0,0 +1,368 @@
+// CostPilot interactive dependency viewer.
+//
+// Self-contained canvas renderer for Cytoscape-style element data
+// ({ data: { id, label, ... } }). No external assets are loaded so the
+// exported HTML works offline and in locked-down CI artifact viewers.
+(function () {
+  "use strict";
+
+  var payload = JSON.parse(document.getElementById("graph-data").textContent);
+  var options = payload.options || {};
+  var allNodes = payload.elements.nodes.map(function (n) { return n.data; });
+  var allEdges = payload.elements.edges.map(function (e) { return e.data; });
+
+  var canvas = document.getElementById("graph");
+  var ctx = canvas.getContext("2d");
+  var details = document.getElementById("details");
+  var searchInput = document.getElementById("search");
+  var heatToggle = document.getElementById("heatmap");
+
+  var NODE_W = 170;
+  var NODE_H = 34;
+  var LAYER_GAP = 240;
+  var ROW_GAP = 52;
+  var TYPE_COLORS = { resource: "#4a90d9", service: "#8e6bbf", module: "#6c757d" };
+
+  // Module groups start collapsed on large graphs to keep the first paint cheap
+  var collapsed = {};
+  if (allNodes.length > (options.collapse_threshold || 0)) {
+    payload.modules.forEach(function (m) { collapsed[m] = true; });
+  }
+
+  var view = { scale: 1, tx: 40, ty: 40 };
+  var visible = { nodes: [], edges: [], byId: {} };
+  var maxCost = 0;
+  var matches = [];
+  var matchIndex = -1;
+  var selected = null;
+
+  function groupId(module) { return "module:" + module; }
+
+  function visibleIdFor(node) {
+    return node.module && collapsed[node.module] ? groupId(node.module) : node.id;
+  }
+
+  // Collapse module members into group nodes and remap edges onto them
+  function buildVisibleGraph() {
+    var byId = {};
+    var nodes = [];
+    allNodes.forEach(function (n) {
+      var id = visibleIdFor(n);
+      if (id === n.id) {
+        var copy = Object.assign({ members: 1, group: false }, n);
+        byId[id] = copy;
+        nodes.push(copy);
+      } else if (!byId[id]) {
+        var group = {
+          id: id,
+          label: n.module + " (module)",
+          type: "module",
+          module: n.module,
+          monthly_cost: n.monthly_cost || 0,
+          members: 1,
+          group: true
+        };
+        byId[id] = group;
+        nodes.push(group);
+      } else {
+        byId[id].members += 1;
+        byId[id].monthly_cost += n.monthly_cost || 0;
+      }
+    });
+    nodes.forEach(function (n) {
+      if (n.group) { n.label = n.module + " (" + n.members + " resources)"; }
+    });
+
+    var lookup = {};
+    allNodes.forEach(function (n) { lookup[n.id] = visibleIdFor(n); });
+    var seen = {};
+    var edges = [];
+    allEdges.forEach(function (e) {
+      var s = lookup[e.source];
+      var t = lookup[e.target];
+      if (!s || !t || s === t) { return; }
+      var key = s + "\u0000" + t;
+      if (seen[key]) { return; }
+      seen[key] = true;
+      edges.push({ source: s, target: t, relationship: e.relationship });
+    });
+
+    maxCost = 0;
+    nodes.forEach(function (n) { maxCost = Math.max(maxCost, n.monthly_cost || 0); });
+    visible = { nodes: nodes, edges: edges, byId: byId };
+    layout();
+  }
+
+  // Longest-path layering; nodes stuck in cycles are placed after the rest
+  function layout() {
+    var indegree = {};
+    var out = {};
+    visible.nodes.forEach(function (n) { indegree[n.id] = 0; out[n.id] = []; });
+    visible.edges.forEach(function (e) {
+      out[e.source].push(e.target);
+      indegree[e.target] += 1;
+    });
+
+    var layer = {};
+    var queue = [];
+    visible.nodes.forEach(function (n) {
+      if (indegree[n.id] === 0) { queue.push(n.id); layer[n.id] = 0; }
+    });
+    var maxLayer = 0;
+    for (var i = 0; i < queue.length; i++) {
+      var id = queue[i];
+      out[id].forEach(function (t) {
+        layer[t] = Math.max(layer[t] || 0, layer[id] + 1);
+        maxLayer = Math.max(maxLayer, layer[t]);
+        indegree[t] -= 1;
+        if (indegree[t] === 0) { queue.push(t); }
+      });
+    }
+
+    var rows = {};
+    visible.nodes.forEach(function (n) {
+      var l = indegree[n.id] > 0 ? maxLayer + 1 : layer[n.id];
+      rows[l] = rows[l] || [];
+      n.x = l * LAYER_GAP;
+      n.y = rows[l].length * ROW_GAP;
+      rows[l].push(n);
+    });
+  }
+
+  function heatColor(cost) {
+    if (!maxCost) { return "hsl(120, 55%, 45%)"; }
+    var t = Math.min(1, (cost || 0) / maxCost);
+    return "hsl(" + Math.round(120 * (1 - t)) + ", 70%, 45%)";
+  }
+
+  function nodeColor(n) {
+    if (heatToggle.checked) { return heatColor(n.monthly_cost); }
+    return TYPE_COLORS[n.type] || TYPE_COLORS.resource;
+  }
+
+  function resize() {
+    var ratio = window.devicePixelRatio || 1;
+    canvas.width = canvas.clientWidth * ratio;
+    canvas.height = canvas.clientHeight * ratio;
+    ctx.setTransform(ratio, 0, 0, ratio, 0, 0);
+    draw();
+  }
+
+  function onScreen(x, y, w, h) {
+    var sx = x * view.scale + view.tx;
+    var sy = y * view.scale + view.ty;
+    return sx + w * view.scale >= 0 && sy + h * view.scale >= 0 &&
+      sx <= canvas.clientWidth && sy <= canvas.clientHeight;
+  }
+
+  function draw() {
+    ctx.save();
+    ctx.clearRect(0, 0, canvas.clientWidth, canvas.clientHeight);
+    ctx.translate(view.tx, view.ty);
+    ctx.scale(view.scale, view.scale);
+
+    ctx.strokeStyle = "rgba(90, 90, 90, 0.35)";
+    ctx.lineWidth = 1 / view.scale;
+    ctx.beginPath();
+    visible.edges.forEach(function (e) {
+      var a = visible.byId[e.source];
+      var b = visible.byId[e.target];
+      ctx.moveTo(a.x + NODE_W, a.y + NODE_H / 2);
+      ctx.lineTo(b.x, b.y + NODE_H / 2);
+    });
+    ctx.stroke();
+
+    var showLabels = view.scale >= 0.4;
+    var highlighted = {};
+    matches.forEach(function (id) { highlighted[id] = true; });
+    ctx.font = "12px -apple-system, Segoe UI, Roboto, sans-serif";
+    ctx.textBaseline = "middle";
+    visible.nodes.forEach(function (n) {
+      if (!onScreen(n.x, n.y, NODE_W, NODE_H)) { return; }
+      ctx.fillStyle = nodeColor(n);
+      ctx.fillRect(n.x, n.y, NODE_W, NODE_H);
+      if (n.group) {
+        ctx.strokeStyle = "#222";
+        ctx.setLineDash([4, 3]);
+        ctx.strokeRect(n.x, n.y, NODE_W, NODE_H);
+        ctx.setLineDash([]);
+      }
+      if (highlighted[n.id] || n === selected) {
+        ctx.strokeStyle = n === selected ? "#000" : "#ffcc00";
+        ctx.lineWidth = 3 / view.scale;
+        ctx.strokeRect(n.x - 2, n.y - 2, NODE_W + 4, NODE_H + 4);
+        ctx.lineWidth = 1 / view.scale;
+      }
+      if (showLabels) {
+        ctx.fillStyle = "#fff";
+        var text = n.label.length > 24 ? n.label.slice(0, 23) + "…" : n.label;
+        ctx.fillText(text, n.x + 8, n.y + NODE_H / 2);
+      }
+    });
+    ctx.restore();
+  }
+
+  function toGraph(px, py) {
+    return { x: (px - view.tx) / view.scale, y: (py - view.ty) / view.scale };
+  }
+
+  function hitTest(px, py) {
+    var p = toGraph(px, py);
+    for (var i = visible.nodes.length - 1; i >= 0; i--) {
+      var n = visible.nodes[i];
+      if (p.x >= n.x && p.x <= n.x + NODE_W && p.y >= n.y && p.y <= n.y + NODE_H) {
+        return n;
+      }
+    }
+    return null;
+  }
+
+  function formatCost(cost) {
+    return cost ? "$" + cost.toFixed(2) + "/mo" : "n/a";
+  }
+
+  function showDetails(n) {
+    selected = n;
+    if (!n) { details.textContent = "Click a node for details."; return; }
+    var rows = [
+      ["Label", n.label],
+      ["Type", n.group ? "module group" : n.type],
+      ["Monthly cost", formatCost(n.monthly_cost)]
+    ];
+    if (n.resource_type) { rows.push(["Resource type", n.resource_type]); }
+    if (n.module) { rows.push(["Module", n.module]); }
+    if (n.group) { rows.push(["Resources", String(n.members)]); }
+    details.innerHTML = "";
+    rows.forEach(function (r) {
+      var div = document.createElement("div");
+      var b = document.createElement("strong");
+      b.textContent = r[0] + ": ";
+      div.appendChild(b);
+      div.appendChild(document.createTextNode(r[1]));
+      details.appendChild(div);
+    });
+    if (n.module && !n.group) {
+      var button = document.createElement("button");
+      button.textContent = "Collapse module";
+      button.onclick = function () { setCollapsed(n.module, true); };
+      details.appendChild(button);
+    }
+  }
+
+  function setCollapsed(module, value) {
+    if (value) { collapsed[module] = true; } else { delete collapsed[module]; }
+    buildVisibleGraph();
+    showDetails(null);
+    runSearch(false);
+    draw();
+  }
+
+  function centerOn(n) {
+    view.scale = Math.max(view.scale, 0.8);
+    view.tx = canvas.clientWidth / 2 - (n.x + NODE_W / 2) * view.scale;
+    view.ty = canvas.clientHeight / 2 - (n.y + NODE_H / 2) * view.scale;
+  }
+
+  function runSearch(advance) {
+    var term = searchInput.value.trim().toLowerCase();
+    var previous = matches.length;
+    matches = [];
+    if (term) {
+      var hits = {};
+      allNodes.forEach(function (n) {
+        if (n.label.toLowerCase().indexOf(term) >= 0 || n.id.toLowerCase().indexOf(term) >= 0) {
+          hits[visibleIdFor(n)] = true;
+        }
+      });
+      matches = visible.nodes.filter(function (n) { return hits[n.id]; })
+        .map(function (n) { return n.id; });
+    }
+    document.getElementById("match-count").textContent =
+      term ? matches.length + " match" + (matches.length === 1 ? "" : "es") : "";
+    if (!matches.length) { matchIndex = -1; draw(); return; }
+    matchIndex = advance ? (matchIndex + 1) % matches.length
+      : (previous === matches.length ? Math.max(matchIndex, 0) : 0);
+    centerOn(visible.byId[matches[matchIndex]]);
+    draw();
+  }
+
+  function fit() {
+    if (!visible.nodes.length) { return; }
+    var maxX = 0;
+    var maxY = 0;
+    visible.nodes.forEach(function (n) {
+      maxX = Math.max(maxX, n.x + NODE_W);
+      maxY = Math.max(maxY, n.y + NODE_H);
+    });
+    view.scale = Math.min(1.5, Math.min(
+      (canvas.clientWidth - 80) / maxX,
+      (canvas.clientHeight - 80) / maxY
+    ));
+    view.tx = 40;
+    view.ty = 40;
+    draw();
+  }
+
+  var drag = null;
+  canvas.addEventListener("mousedown", function (ev) {
+    drag = { x: ev.offsetX, y: ev.offsetY, tx: view.tx, ty: view.ty, moved: false };
+  });
+  window.addEventListener("mousemove", function (ev) {
+    if (!drag) { return; }
+    var rect = canvas.getBoundingClientRect();
+    var dx = ev.clientX - rect.left - drag.x;
+    var dy = ev.clientY - rect.top - drag.y;
+    if (Math.abs(dx) + Math.abs(dy) > 3) { drag.moved = true; }
+    view.tx = drag.tx + dx;
+    view.ty = drag.ty + dy;
+    draw();
+  });
+  window.addEventListener("mouseup", function (ev) {
+    if (drag && !drag.moved && ev.target === canvas) {
+      var n = hitTest(drag.x, drag.y);
+      if (n && n.group) {
+        setCollapsed(n.module, false);
+      } else {
+        showDetails(n);
+        draw();
+      }
+    }
+    drag = null;
+  });
+  canvas.addEventListener("wheel", function (ev) {
+    ev.preventDefault();
+    var factor = ev.deltaY < 0 ? 1.15 : 1 / 1.15;
+    var scale = Math.min(4, Math.max(0.05, view.scale * factor));
+    var p = toGraph(ev.offsetX, ev.offsetY);
+    view.scale = scale;
+    view.tx = ev.offsetX - p.x * scale;
+    view.ty = ev.offsetY - p.y * scale;
+    draw();
+  }, { passive: false });
+
+  searchInput.addEventListener("input", function () { runSearch(false); });
+  searchInput.addEventListener("keydown", function (ev) {
+    if (ev.key === "Enter") { runSearch(true); }
+  });
+  heatToggle.checked = options.heat_map !== false;
+  heatToggle.addEventListener("change", draw);
+  document.getElementById("fit").onclick = fit;
+  document.getElementById("expand-all").onclick = function () {
+    collapsed = {};
+    buildVisibleGraph();
+    runSearch(false);
+    fit();
+  };
+  document.getElementById("collapse-all").onclick = function () {
+    payload.modules.forEach(function (m) { collapsed[m] = true; });
+    buildVisibleGraph();
+    runSearch(false);
+    fit();
+  };
+
+  window.addEventListener("resize", resize);
+  buildVisibleGraph();
+  showDetails(null);
+  resize();
+  fit();
+})();
@@ -0,0 +1,119 @@
+// CSV node/edge table export for dependency graphs (Gephi, Neo4j LOAD CSV)
+
+use super::graph_types::DependencyGraph;
+
+/// CSV exporter producing Gephi-style node and edge tables
+pub struct CsvExporter;
+
+impl CsvExporter {
+    /// Create new exporter
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Export the edge list (`Source,Target,Type,...`)
+    pub fn export_edges(&self, graph: &DependencyGraph) -> String {
+        let mut output = String::from("Source,Target,Type,Relationship,CostImpact\n");
+        for edge in &graph.edges {
+            push_row(
+                &mut output,
+                &[
+                    &edge.from,
+                    &edge.to,
+                    "Directed",
+                    edge.relationship.as_str(),
+                    edge.cost_impact.as_deref().unwrap_or(""),
+                ],
+            );
+        }
+        output
+    }
+
+    /// Export the node table (`Id,Label,...`)
+    pub fn export_nodes(&self, graph: &DependencyGraph) -> String {
+        let mut output = String::from("Id,Label,NodeType,ResourceType,MonthlyCost,Module\n");
+        for node in graph.render_order() {
+            let cost = node
+                .monthly_cost
+                .map(|c| format!("{:.2}", c))
+                .unwrap_or_default();
+            push_row(
+                &mut output,
+                &[
+                    &node.id,
+                    &node.label,
+                    &format!("{:?}", node.node_type).to_lowercase(),
+                    node.resource_type.as_deref().unwrap_or(""),
+                    &cost,
+                    node.module.as_deref().unwrap_or(""),
+                ],
+            );
+        }
+        output
+    }
+}
+
+impl Default for CsvExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn push_row(output: &mut String, fields: &[&str]) {
+    let row: Vec<String> = fields.iter().map(|f| escape_field(f)).collect();
+    output.push_str(&row.join(","));
+    output.push('\n');
+}
+
+/// Quote fields containing separators, quotes or line breaks (RFC 4180)
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::mapping::graph_types::{EdgeType, GraphEdge, GraphNode};
+
+    #[test]
+    fn test_export_edges_and_nodes() {
+        let mut graph = DependencyGraph::new();
+        graph.add_node(
+            GraphNode::new_resource(
+                "lambda".to_string(),
+                "aws_lambda_function".to_string(),
+                "api, \"v2\"".to_string(),
+            )
+            .with_cost(12.5),
+        );
+        graph.add_node(GraphNode::new_resource(
+            "bucket".to_string(),
+            "aws_s3_bucket".to_string(),
+            "assets".to_string(),
+        ));
+        graph.add_edge(GraphEdge::new(
+            "lambda".to_string(),
+            "bucket".to_string(),
+            EdgeType::DataFlow,
+        ));
+
+        let exporter = CsvExporter::new();
+        let edges = exporter.export_edges(&graph);
+        assert_eq!(
+            edges,
+            "Source,Target,Type,Relationship,CostImpact\nlambda,bucket,Directed,data_flow,\n"
+        );
+
+        let nodes = exporter.export_nodes(&graph);
+        let lines: Vec<&str> = nodes.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "lambda,\"api, \"\"v2\"\"\",resource,aws_lambda_function,12.50,"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+// Cycle detection for dependency graphs using Tarjan's strongly connected components
+
+use super::graph_types::DependencyGraph;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+/// Kind of cycles to report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CycleType {
+    /// Cycles spanning two or more nodes
+    Simple,
+
+    /// Nodes that depend on themselves
+    SelfLoop,
+
+    /// Both simple cycles and self-loops
+    All,
+}
+
+/// Detects dependency cycles.
+///
+/// Each strongly connected component yields one representative cycle: the
+/// shortest cycle through its lexicographically smallest node. Output is
+/// deterministic regardless of insertion order.
+#[derive(Debug, Clone, Default)]
+pub struct CycleDetector {
+    adjacency: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl CycleDetector {
+    /// Create an empty detector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build detector from a dependency graph
+    pub fn from_graph(graph: &DependencyGraph) -> Self {
+        let mut detector = Self::new();
+        for node in &graph.nodes {
+            detector.add_node(&node.id);
+        }
+        for edge in &graph.edges {
+            detector.add_edge(&edge.from, &edge.to);
+        }
+        detector
+    }
+
+    /// Add a node
+    pub fn add_node(&mut self, id: &str) {
+        self.adjacency.entry(id.to_string()).or_default();
+    }
+
+    /// Add a directed edge, creating missing endpoints
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        self.add_node(to);
+        self.adjacency
+            .entry(from.to_string())
+            .or_default()
+            .insert(to.to_string());
+    }
+
+    /// Detect cycles of the requested type
+    pub fn detect(&self, cycle_type: CycleType) -> Vec<Vec<String>> {
+        let mut cycles = Vec::new();
+
+        for component in self.strongly_connected_components() {
+            if component.len() > 1 {
+                if cycle_type != CycleType::SelfLoop {
+                    cycles.push(self.representative_cycle(&component));
+                }
+            } else if cycle_type != CycleType::Simple {
+                let id = &component[0];
+                if self.adjacency.get(id).is_some_and(|s| s.contains(id)) {
+                    cycles.push(component);
+                }
+            }
+        }
+
+        cycles.sort();
+        cycles
+    }
+
+    /// Strongly connected components (iterative Tarjan), each sorted by node ID
+    pub fn strongly_connected_components(&self) -> Vec<Vec<String>> {
+        let ids: Vec<&String> = self.adjacency.keys().collect();
+        let position: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.as_str(), i))
+            .collect();
+        let successors: Vec<Vec<usize>> = ids
+            .iter()
+            .map(|id| {
+                self.adjacency[*id]
+                    .iter()
+                    .map(|to| position[to.as_str()])
+                    .collect()
+            })
+            .collect();
+
+        let n = ids.len();
+        let mut index: Vec<Option<usize>> = vec![None; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut next_index = 0;
+        let mut components = Vec::new();
+
+        for root in 0..n {
+            if index[root].is_some() {
+                continue;
+            }
+
+            let mut call_stack = vec![(root, 0usize)];
+            index[root] = Some(next_index);
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&(v, child)) = call_stack.last() {
+                if child < successors[v].len() {
+                    if let Some(frame) = call_stack.last_mut() {
+                        frame.1 += 1;
+                    }
+                    let w = successors[v][child];
+                    match index[w] {
+                        None => {
+                            index[w] = Some(next_index);
+                            lowlink[w] = next_index;
+                            next_index += 1;
+                            stack.push(w);
+                            on_stack[w] = true;
+                            call_stack.push((w, 0));
+                        }
+                        Some(w_index) if on_stack[w] => {
+                            lowlink[v] = lowlink[v].min(w_index);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some(&(parent, _)) = call_stack.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[v]);
+                }
+
+                if Some(lowlink[v]) == index[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(ids[w].clone());
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.sort();
+                    components.push(component);
+                }
+            }
+        }
+
+        components.sort();
+        components
+    }
+
+    /// Shortest cycle through the smallest node of a component (BFS)
+    fn representative_cycle(&self, component: &[String]) -> Vec<String> {
+        let members: BTreeSet<&String> = component.iter().collect();
+        let start = &component[0];
+        let mut parent: HashMap<&String, &String> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(current) = queue.pop_front() {
+            for next in &self.adjacency[current] {
+                if next == start {
+                    let mut path = vec![current.clone()];
+                    let mut node = current;
+                    while let Some(prev) = parent.get(node) {
+                        path.push((*prev).clone());
+                        node = prev;
+                    }
+                    path.reverse();
+                    return path;
+                }
+                if members.contains(next) && !parent.contains_key(next) && next != start {
+                    parent.insert(next, current);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        component.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_cycles() {
+        let mut detector = CycleDetector::new();
+        detector.add_edge("a", "b");
+        detector.add_edge("b", "c");
+        assert!(detector.detect(CycleType::All).is_empty());
+    }
+
+    #[test]
+    fn test_simple_cycle_is_rotated_to_smallest_node() {
+        let mut detector = CycleDetector::new();
+        detector.add_edge("c", "a");
+        detector.add_edge("a", "b");
+        detector.add_edge("b", "c");
+        detector.add_edge("c", "d");
+
+        assert_eq!(
+            detector.detect(CycleType::Simple),
+            vec![vec!["a".to_string(), "b".to_string(), "c".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let mut detector = CycleDetector::new();
+        detector.add_edge("a", "a");
+        detector.add_edge("b", "c");
+        detector.add_edge("c", "b");
+
+        assert_eq!(
+            detector.detect(CycleType::SelfLoop),
+            vec![vec!["a".to_string()]]
+        );
+        assert_eq!(detector.detect(CycleType::Simple).len(), 1);
+        assert_eq!(detector.detect(CycleType::All).len(), 2);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut detector = CycleDetector::new();
+        detector.add_edge("a", "b");
+        detector.add_edge("b", "a");
+        detector.add_edge("b", "c");
+
+        let components = detector.strongly_connected_components();
+        assert_eq!(
+            components,
+            vec![
+                vec!["a".to_string(), "b".to_string()],
+                vec!["c".to_string()]
+            ]
+        );
+    }
+}
@@ -0,0 +1,276 @@
+// Node budget enforcement by collapsing low-cost leaf nodes, and collapsing
+// of resources outside the `--top`/`--min-cost` cut
+
+use super::graph_types::{DependencyGraph, GraphEdge, GraphNode, NodeType};
+use crate::engines::shared::pruning::{Pruning, REMAINDER_LABEL};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Prefix for IDs of aggregate nodes created by the aggregator
+pub const COLLAPSED_PREFIX: &str = "collapsed_";
+
+/// Collapses cheap leaf nodes into per-module, per-type aggregate nodes
+/// until a graph fits within a node budget
+pub struct NodeAggregator {
+    budget: usize,
+}
+
+impl NodeAggregator {
+    /// Create aggregator for the given node budget
+    pub fn new(budget: usize) -> Self {
+        Self { budget }
+    }
+
+    /// Collapse leaf nodes (nothing depends on them, at most one dependency)
+    /// in ascending cost order. Returns the number of resources collapsed.
+    pub fn aggregate(&self, graph: &mut DependencyGraph) -> usize {
+        if graph.nodes.len() <= self.budget {
+            return 0;
+        }
+
+        let mut in_degree: HashMap<&str, usize> = HashMap::new();
+        let mut out_degree: HashMap<&str, usize> = HashMap::new();
+        for edge in &graph.edges {
+            *in_degree.entry(edge.to.as_str()).or_default() += 1;
+            *out_degree.entry(edge.from.as_str()).or_default() += 1;
+        }
+
+        let mut candidates: Vec<&GraphNode> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::Resource)
+            .filter(|n| !n.id.starts_with(COLLAPSED_PREFIX))
+            .filter(|n| in_degree.get(n.id.as_str()).copied().unwrap_or(0) == 0)
+            .filter(|n| out_degree.get(n.id.as_str()).copied().unwrap_or(0) <= 1)
+            .collect();
+        candidates.sort_by(|a, b| {
+            a.monthly_cost
+                .unwrap_or(0.0)
+                .total_cmp(&b.monthly_cost.unwrap_or(0.0))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        // Each group of k members saves k - 1 nodes; singletons save nothing
+        let mut groups: BTreeMap<(Option<String>, String), Vec<String>> = BTreeMap::new();
+        let mut node_count = graph.nodes.len();
+        for node in candidates {
+            if node_count <= self.budget {
+                break;
+            }
+            let key = (
+                node.module.clone(),
+                node.resource_type.clone().unwrap_or_default(),
+            );
+            let members = groups.entry(key).or_default();
+            members.push(node.id.clone());
+            if members.len() > 1 {
+                node_count -= 1;
+            }
+        }
+        groups.retain(|_, members| members.len() > 1);
+
+        let mut replacement: HashMap<String, String> = HashMap::new();
+        let mut aggregates = Vec::new();
+        for ((module, resource_type), members) in &groups {
+            let id = aggregate_id(module.as_deref(), resource_type);
+            let costs: Vec<f64> = members
+                .iter()
+                .filter_map(|m| graph.find_node(m).and_then(|n| n.monthly_cost))
+                .collect();
+            let mut node = GraphNode::new_resource(
+                id.clone(),
+                resource_type.clone(),
+                format!("{} {} resources collapsed", members.len(), resource_type),
+            );
+            if !costs.is_empty() {
+                node = node.with_cost(costs.iter().sum());
+            }
+            if let Some(module) = module {
+                node = node.with_module(module.clone());
+            }
+            aggregates.push(node);
+            for member in members {
+                replacement.insert(member.clone(), id.clone());
+            }
+        }
+
+        let collapsed = replacement.len();
+        if collapsed == 0 {
+            return 0;
+        }
+
+        replace_nodes(graph, &replacement, aggregates);
+        graph.metadata.collapsed_count += collapsed;
+        graph.update_metadata();
+        collapsed
+    }
+
+    /// Collapse every resource outside the `--top`/`--min-cost` cut into one
+    /// "everything else" node, whatever its dependencies. Returns the number
+    /// of resources collapsed.
+    pub fn prune(graph: &mut DependencyGraph, pruning: &Pruning) -> usize {
+        if !pruning.is_active() {
+            return 0;
+        }
+
+        let resources: Vec<&GraphNode> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::Resource)
+            .collect();
+        let costs: Vec<f64> = resources
+            .iter()
+            .map(|n| n.monthly_cost.unwrap_or(0.0))
+            .collect();
+        let pruned: Vec<&GraphNode> = resources
+            .into_iter()
+            .zip(pruning.keep_mask(&costs))
+            .filter(|(_, keep)| !keep)
+            .map(|(node, _)| node)
+            .collect();
+        if pruned.is_empty() {
+            return 0;
+        }
+
+        let id = format!("{}everything_else", COLLAPSED_PREFIX);
+        let costs: Vec<f64> = pruned.iter().filter_map(|n| n.monthly_cost).collect();
+        let aggregate = GraphNode {
+            id: id.clone(),
+            label: format!("{} ({} resources)", REMAINDER_LABEL, pruned.len()),
+            node_type: NodeType::Resource,
+            resource_type: None,
+            monthly_cost: (!costs.is_empty()).then(|| costs.iter().sum()),
+            module: None,
+        };
+        let replacement: HashMap<String, String> =
+            pruned.iter().map(|n| (n.id.clone(), id.clone())).collect();
+
+        let collapsed = replacement.len();
+        replace_nodes(graph, &replacement, vec![aggregate]);
+        graph.update_metadata();
+        collapsed
+    }
+}
+
+/// Swap nodes for their aggregates, redirecting and deduplicating edges
+fn replace_nodes(
+    graph: &mut DependencyGraph,
+    replacement: &HashMap<String, String>,
+    aggregates: Vec<GraphNode>,
+) {
+    graph.nodes.retain(|n| !replacement.contains_key(&n.id));
+    graph.nodes.extend(aggregates);
+
+    let mut seen = HashSet::new();
+    let edges = std::mem::take(&mut graph.edges);
+    graph.edges = edges
+        .into_iter()
+        .map(|edge| GraphEdge {
+            from: replacement.get(&edge.from).cloned().unwrap_or(edge.from),
+            to: replacement.get(&edge.to).cloned().unwrap_or(edge.to),
+            ..edge
+        })
+        .filter(|edge| {
+            edge.from != edge.to
+                && seen.insert((edge.from.clone(), edge.to.clone(), edge.relationship))
+        })
+        .collect();
+}
+
+fn aggregate_id(module: Option<&str>, resource_type: &str) -> String {
+    match module {
+        Some(module) => format!(
+            "{}{}_{}",
+            COLLAPSED_PREFIX,
+            module.replace('.', "_"),
+            resource_type
+        ),
+        None => format!("{}{}", COLLAPSED_PREFIX, resource_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::mapping::graph_types::EdgeType;
+
+    fn bucket(id: &str, cost: f64) -> GraphNode {
+        GraphNode::new_resource(id.to_string(), "aws_s3_bucket".to_string(), id.to_string())
+            .with_cost(cost)
+    }
+
+    fn graph_with_buckets(count: usize) -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        graph.add_node(
+            GraphNode::new_resource("vpc".to_string(), "aws_vpc".to_string(), "vpc".to_string())
+                .with_cost(0.0),
+        );
+        for i in 0..count {
+            let id = format!("bucket_{:02}", i);
+            graph.add_node(bucket(&id, i as f64));
+            graph.add_edge(GraphEdge::new(id, "vpc".to_string(), EdgeType::DependsOn));
+        }
+        graph.update_metadata();
+        graph
+    }
+
+    #[test]
+    fn test_under_budget_is_untouched() {
+        let mut graph = graph_with_buckets(3);
+        assert_eq!(NodeAggregator::new(10).aggregate(&mut graph), 0);
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.metadata.collapsed_count, 0);
+    }
+
+    #[test]
+    fn test_collapses_cheapest_leaves_to_budget() {
+        let mut graph = graph_with_buckets(10);
+        let collapsed = NodeAggregator::new(6).aggregate(&mut graph);
+
+        assert_eq!(collapsed, 6);
+        assert_eq!(graph.nodes.len(), 6);
+        assert_eq!(graph.metadata.collapsed_count, 6);
+
+        let aggregate = graph.find_node("collapsed_aws_s3_bucket").unwrap();
+        assert_eq!(aggregate.label, "6 aws_s3_bucket resources collapsed");
+        assert_eq!(aggregate.monthly_cost, Some(15.0));
+        // Most expensive buckets stay visible
+        assert!(graph.find_node("bucket_09").is_some());
+        assert!(graph.find_node("bucket_00").is_none());
+    }
+
+    #[test]
+    fn test_edges_are_redirected_and_deduplicated() {
+        let mut graph = graph_with_buckets(10);
+        NodeAggregator::new(6).aggregate(&mut graph);
+
+        let into_vpc: Vec<_> = graph.edges_to("vpc");
+        assert_eq!(into_vpc.len(), 5);
+        assert_eq!(
+            graph
+                .edges
+                .iter()
+                .filter(|e| e.from == "collapsed_aws_s3_bucket")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_prune_keeps_top_resources_and_total_cost() {
+        let mut graph = graph_with_buckets(10);
+        graph.calculate_total_cost();
+        let total = graph.metadata.total_cost;
+
+        let collapsed = NodeAggregator::prune(&mut graph, &Pruning::new(Some(3), None));
+        assert_eq!(collapsed, 8);
+        assert_eq!(graph.nodes.len(), 4);
+        assert!(graph.find_node("bucket_09").is_some());
+        assert!(graph.find_node("vpc").is_none());
+
+        let rest = graph.find_node("collapsed_everything_else").unwrap();
+        assert_eq!(rest.label, "everything else (8 resources)");
+        assert_eq!(rest.monthly_cost, Some(21.0));
+        assert_eq!(graph.metadata.total_cost, total);
+        assert_eq!(graph.metadata.collapsed_count, 0);
+    }
+}
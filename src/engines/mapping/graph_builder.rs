@@ -1,5 +1,6 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
+use super::graph_aggregator::NodeAggregator;
 use super::graph_types::*;
 use crate::engines::detection::ResourceChange;
 use crate::engines::performance::budgets::{
@@ -52,6 +53,9 @@ impl GraphBuilder {
         let mut graph = DependencyGraph::new();
 
         // First pass: Create nodes for all resources
+        if let Some(tracker) = &mut self.performance_tracker {
+            tracker.enter_phase("create_nodes");
+        }
         for change in changes {
             if change.action == ChangeAction::Delete {
                 continue; // Skip deleted resources
@@ -69,6 +73,10 @@ impl GraphBuilder {
         }
 
         // Second pass: Infer dependencies and create edges
+        if let Some(tracker) = &mut self.performance_tracker {
+            tracker.exit_span();
+            tracker.enter_phase("infer_dependencies");
+        }
         for change in changes {
             if change.action == ChangeAction::Delete {
                 continue;
@@ -87,26 +95,115 @@ impl GraphBuilder {
             }
         }
 
+        if let Some(tracker) = &mut self.performance_tracker {
+            tracker.exit_span();
+            tracker.enter_phase("finalize");
+        }
+        self.finalize(&mut graph);
+
+        // Mark completion and collect metrics
+        if let Some(tracker) = self.performance_tracker.take() {
+            let _metrics = tracker.complete();
+            // TODO: Log or return metrics
+        }
+
+        Ok(graph)
+    }
+
+    /// Rebuild a graph from a prior build, re-inferring only what changed.
+    ///
+    /// Nodes for resources whose type, action and configuration match
+    /// `previous_changes` are reused from `previous` (keeping any cost
+    /// annotations) along with their outgoing edges. Changed and added
+    /// resources are rebuilt, as are unchanged resources whose configuration
+    /// mentions an added resource or that pointed at a removed one.
+    pub fn build_incremental(
+        &mut self,
+        previous: &DependencyGraph,
+        previous_changes: &[ResourceChange],
+        changes: &[ResourceChange],
+    ) -> Result<DependencyGraph, CostPilotError> {
+        let live: Vec<&ResourceChange> = changes
+            .iter()
+            .filter(|c| c.action != ChangeAction::Delete)
+            .collect();
+        let prior: HashMap<&str, &ResourceChange> = previous_changes
+            .iter()
+            .filter(|c| c.action != ChangeAction::Delete)
+            .map(|c| (c.resource_id.as_str(), c))
+            .collect();
+        let current_ids: HashSet<String> = live
+            .iter()
+            .map(|c| self.generate_stable_id(&c.resource_id))
+            .collect();
+
+        let added: Vec<&str> = live
+            .iter()
+            .filter(|c| !prior.contains_key(c.resource_id.as_str()))
+            .map(|c| reference_name(&c.resource_id))
+            .collect();
+        let removed: HashSet<String> = prior
+            .keys()
+            .map(|id| self.generate_stable_id(id))
+            .filter(|id| !current_ids.contains(id))
+            .collect();
+
+        let mut graph = DependencyGraph::new();
+        let mut dirty = Vec::new();
+        for change in &live {
+            let id = self.generate_stable_id(&change.resource_id);
+            let unchanged = prior
+                .get(change.resource_id.as_str())
+                .is_some_and(|old| same_resource(old, change));
+            let reusable = unchanged
+                && !mentions_any(change, &added)
+                && !previous
+                    .edges_from(&id)
+                    .iter()
+                    .any(|e| removed.contains(&e.to));
+
+            match previous.find_node(&id) {
+                Some(node) if reusable => {
+                    graph.add_node(node.clone());
+                    for edge in previous.edges_from(&id) {
+                        graph.add_edge(edge.clone());
+                    }
+                }
+                _ => {
+                    graph.add_node(self.create_node_from_resource(change)?);
+                    dirty.push(*change);
+                }
+            }
+        }
+
+        for change in dirty {
+            for edge in self.infer_dependencies(change, changes)? {
+                graph.add_edge(edge);
+            }
+        }
+
+        self.finalize(&mut graph);
+        Ok(graph)
+    }
+
+    /// Cycle detection, depth calculation, node budget and metadata
+    fn finalize(&self, graph: &mut DependencyGraph) {
         // Detect cycles if enabled
         if self.config.detect_cycles {
-            let cycles = self.detect_cycles(&graph);
+            let cycles = self.detect_cycles(graph);
             graph.metadata.has_cycles = !cycles.is_empty();
             graph.metadata.cycles = cycles;
         }
 
         // Calculate max depth
-        graph.metadata.max_depth = self.calculate_max_depth(&graph);
+        graph.metadata.max_depth = self.calculate_max_depth(graph);
 
         // Update metadata
         graph.update_metadata();
 
-        // Mark completion and collect metrics
-        if let Some(tracker) = self.performance_tracker.take() {
-            let _metrics = tracker.complete();
-            // TODO: Log or return metrics
+        if let Some(budget) = self.config.node_budget {
+            NodeAggregator::new(budget).aggregate(graph);
         }
-
-        Ok(graph)
     }
 
     /// Handle budget violation based on timeout action
@@ -116,9 +213,11 @@ impl GraphBuilder {
     ) -> Result<DependencyGraph, CostPilotError> {
         match violation.action {
             TimeoutAction::PartialResults => {
-                eprintln!(
-                    "⚠️  Mapping budget exceeded: {} ({}ms budget, {}ms elapsed)",
-                    violation.violation_type, violation.budget_value, violation.actual_value
+                tracing::warn!(
+                    "Mapping budget exceeded: {} ({}ms budget, {}ms elapsed)",
+                    violation.violation_type,
+                    violation.budget_value,
+                    violation.actual_value
                 );
                 Ok(DependencyGraph::new())
             }
@@ -141,7 +240,7 @@ impl GraphBuilder {
     ) -> Result<DependencyGraph, CostPilotError> {
         match violation.action {
             TimeoutAction::PartialResults => {
-                eprintln!("⚠️  Mapping budget exceeded: {} ({}ms budget, {}ms elapsed)",
+                tracing::warn!("Mapping budget exceeded: {} ({}ms budget, {}ms elapsed)",
                     violation.violation_type, violation.budget_value, violation.actual_value);
                 Ok(partial)
             }
@@ -397,56 +496,9 @@ impl GraphBuilder {
         None
     }
 
-    /// Detect cycles in the graph using DFS
+    /// Detect cycles in the graph (Tarjan SCC, deterministic order)
     fn detect_cycles(&self, graph: &DependencyGraph) -> Vec<Vec<String>> {
-        let mut cycles = Vec::new();
-        let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
-        let mut path = Vec::new();
-
-        for node in &graph.nodes {
-            if !visited.contains(&node.id) {
-                Self::dfs_cycle_detect(
-                    &node.id,
-                    graph,
-                    &mut visited,
-                    &mut rec_stack,
-                    &mut path,
-                    &mut cycles,
-                );
-            }
-        }
-
-        cycles
-    }
-
-    /// DFS helper for cycle detection
-    fn dfs_cycle_detect(
-        node_id: &str,
-        graph: &DependencyGraph,
-        visited: &mut HashSet<String>,
-        rec_stack: &mut HashSet<String>,
-        path: &mut Vec<String>,
-        cycles: &mut Vec<Vec<String>>,
-    ) {
-        visited.insert(node_id.to_string());
-        rec_stack.insert(node_id.to_string());
-        path.push(node_id.to_string());
-
-        for edge in graph.edges_from(node_id) {
-            if !visited.contains(&edge.to) {
-                Self::dfs_cycle_detect(&edge.to, graph, visited, rec_stack, path, cycles);
-            } else if rec_stack.contains(&edge.to) {
-                // Found a cycle
-                if let Some(cycle_start) = path.iter().position(|id| id == &edge.to) {
-                    let cycle = path[cycle_start..].to_vec();
-                    cycles.push(cycle);
-                }
-            }
-        }
-
-        path.pop();
-        rec_stack.remove(node_id);
+        graph.find_cycles()
     }
 
     /// Calculate maximum depth in the graph
@@ -499,11 +551,7 @@ impl GraphBuilder {
 
     /// Generate a stable ID for a resource
     fn generate_stable_id(&self, resource_id: &str) -> String {
-        // Normalize resource ID to stable format
-        resource_id
-            .replace(['[', ']'], "_")
-            .replace('"', "")
-            .replace('.', "_")
+        stable_id(resource_id)
     }
 
     /// Generate a human-readable label
@@ -530,6 +578,42 @@ impl GraphBuilder {
     }
 }
 
+/// Normalize a resource address to a stable node ID
+pub(crate) fn stable_id(resource_id: &str) -> String {
+    resource_id
+        .replace(['[', ']'], "_")
+        .replace('"', "")
+        .replace('.', "_")
+}
+
+/// `type.name` portion of a resource address, as used in references
+fn reference_name(resource_id: &str) -> &str {
+    let parts: Vec<&str> = resource_id.rsplitn(3, '.').collect();
+    match parts.as_slice() {
+        [_, _, rest] => &resource_id[rest.len() + 1..],
+        _ => resource_id,
+    }
+}
+
+/// Whether a resource is unchanged between two plan versions
+fn same_resource(old: &ResourceChange, new: &ResourceChange) -> bool {
+    old.resource_type == new.resource_type
+        && old.action == new.action
+        && old.new_config == new.new_config
+}
+
+/// Whether a resource's configuration mentions any of the given references
+fn mentions_any(change: &ResourceChange, references: &[&str]) -> bool {
+    if references.is_empty() {
+        return false;
+    }
+    let Some(config) = &change.new_config else {
+        return false;
+    };
+    let text = config.to_string();
+    references.iter().any(|r| text.contains(r))
+}
+
 impl Default for GraphBuilder {
     fn default() -> Self {
         Self::new()
@@ -668,4 +752,69 @@ mod tests {
         let max_depth = builder.calculate_max_depth(&graph);
         assert_eq!(max_depth, 2);
     }
+
+    #[test]
+    fn test_build_incremental_reuses_unchanged_nodes() {
+        let mut builder = GraphBuilder::new();
+        let mut vpc = create_test_resource("aws_vpc.main", "aws_vpc");
+        vpc.new_config = Some(json!({"cidr_block": "10.0.0.0/16"}));
+        let mut lambda = create_test_resource("aws_lambda_function.api", "aws_lambda_function");
+        lambda.new_config = Some(json!({"vpc_config": {"vpc_id": "${aws_vpc.main.id}"}}));
+        let before = vec![vpc.clone(), lambda.clone()];
+
+        let mut previous = builder.build_graph(&before).unwrap();
+        previous.nodes[0].monthly_cost = Some(32.0);
+
+        let mut changed_lambda = lambda.clone();
+        changed_lambda.new_config = Some(json!({
+            "vpc_config": {"vpc_id": "${aws_vpc.main.id}"},
+            "memory_size": 1024
+        }));
+        let after = vec![vpc, changed_lambda];
+
+        let graph = builder
+            .build_incremental(&previous, &before, &after)
+            .unwrap();
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(
+            graph.find_node("aws_vpc_main").unwrap().monthly_cost,
+            Some(32.0)
+        );
+        assert_eq!(graph.edges_from("aws_lambda_function_api").len(), 1);
+    }
+
+    #[test]
+    fn test_build_incremental_rewires_to_added_resource() {
+        let mut builder = GraphBuilder::new();
+        let mut lambda = create_test_resource("aws_lambda_function.api", "aws_lambda_function");
+        lambda.new_config = Some(json!({"vpc_config": {"vpc_id": "${aws_vpc.main.id}"}}));
+        let before = vec![lambda.clone()];
+        let previous = builder.build_graph(&before).unwrap();
+        assert!(previous.edges.is_empty());
+
+        let after = vec![lambda, create_test_resource("aws_vpc.main", "aws_vpc")];
+        let graph = builder
+            .build_incremental(&previous, &before, &after)
+            .unwrap();
+
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges_to("aws_vpc_main").len(), 1);
+    }
+
+    #[test]
+    fn test_node_budget_collapses_leaves() {
+        let mut builder = GraphBuilder::with_config(GraphConfig {
+            node_budget: Some(3),
+            ..Default::default()
+        });
+        let changes: Vec<ResourceChange> = (0..6)
+            .map(|i| create_test_resource(&format!("aws_s3_bucket.b{}", i), "aws_s3_bucket"))
+            .collect();
+
+        let graph = builder.build_graph(&changes).unwrap();
+
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.metadata.collapsed_count, 4);
+    }
 }
@@ -0,0 +1,417 @@
+// Structural diff between two dependency graphs
+
+use super::graph_types::{DependencyGraph, EdgeType, GraphEdge, GraphNode};
+use super::mermaid_generator::MermaidGenerator;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Change status of a node or edge between two graphs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+/// Node present in both graphs with different attributes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeChange {
+    pub id: String,
+    pub before: GraphNode,
+    pub after: GraphNode,
+
+    /// Human-readable list of changed fields
+    pub changes: Vec<String>,
+}
+
+/// Edge present in both graphs with a different relationship or cost impact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeChange {
+    pub before: GraphEdge,
+    pub after: GraphEdge,
+}
+
+/// Diff between a baseline and a proposed dependency graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphDiff {
+    pub added_nodes: Vec<GraphNode>,
+    pub removed_nodes: Vec<GraphNode>,
+    pub changed_nodes: Vec<NodeChange>,
+    pub added_edges: Vec<GraphEdge>,
+    pub removed_edges: Vec<GraphEdge>,
+    pub changed_edges: Vec<EdgeChange>,
+
+    /// Change in total monthly cost across all nodes
+    pub cost_delta: f64,
+}
+
+impl GraphDiff {
+    /// Whether the graphs are structurally identical
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+
+    /// One-line summary of the diff
+    pub fn summary(&self) -> String {
+        format!(
+            "Nodes: +{} -{} ~{} | Edges: +{} -{} ~{} | Cost: {}${:.2}/mo",
+            self.added_nodes.len(),
+            self.removed_nodes.len(),
+            self.changed_nodes.len(),
+            self.added_edges.len(),
+            self.removed_edges.len(),
+            self.changed_edges.len(),
+            if self.cost_delta >= 0.0 { "+" } else { "-" },
+            self.cost_delta.abs()
+        )
+    }
+}
+
+/// Compares dependency graphs of two plan versions
+pub struct GraphComparator {
+    /// Cost differences below this are not reported as changes
+    cost_tolerance: f64,
+}
+
+impl GraphComparator {
+    /// Create comparator with default cost tolerance ($0.01)
+    pub fn new() -> Self {
+        Self {
+            cost_tolerance: 0.01,
+        }
+    }
+
+    /// Set cost tolerance for node changes
+    pub fn with_cost_tolerance(mut self, tolerance: f64) -> Self {
+        self.cost_tolerance = tolerance;
+        self
+    }
+
+    /// Diff baseline graph against proposed graph
+    pub fn compare(&self, before: &DependencyGraph, after: &DependencyGraph) -> GraphDiff {
+        let before_nodes: HashMap<&str, &GraphNode> =
+            before.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+        let after_nodes: HashMap<&str, &GraphNode> =
+            after.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+        let mut added_nodes = Vec::new();
+        let mut changed_nodes = Vec::new();
+        for node in after.render_order() {
+            match before_nodes.get(node.id.as_str()) {
+                None => added_nodes.push(node.clone()),
+                Some(old) => {
+                    let changes = self.node_changes(old, node);
+                    if !changes.is_empty() {
+                        changed_nodes.push(NodeChange {
+                            id: node.id.clone(),
+                            before: (*old).clone(),
+                            after: node.clone(),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+
+        let removed_nodes = before
+            .render_order()
+            .into_iter()
+            .filter(|n| !after_nodes.contains_key(n.id.as_str()))
+            .cloned()
+            .collect();
+
+        let before_edges = edge_map(before);
+        let after_edges = edge_map(after);
+
+        let mut added_edges = Vec::new();
+        let mut changed_edges = Vec::new();
+        for (key, edge) in &after_edges {
+            match before_edges.get(key) {
+                None => added_edges.push((*edge).clone()),
+                Some(old) if *old != *edge => changed_edges.push(EdgeChange {
+                    before: (*old).clone(),
+                    after: (*edge).clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        let removed_edges = before_edges
+            .iter()
+            .filter(|(key, _)| !after_edges.contains_key(*key))
+            .map(|(_, edge)| (*edge).clone())
+            .collect();
+
+        GraphDiff {
+            added_nodes,
+            removed_nodes,
+            changed_nodes,
+            added_edges,
+            removed_edges,
+            changed_edges,
+            cost_delta: total_cost(after) - total_cost(before),
+        }
+    }
+
+    /// Render the union of both graphs as a color-coded Mermaid flowchart.
+    ///
+    /// `after` is the proposed graph the diff was computed against; removed
+    /// nodes and edges are taken from the diff itself.
+    pub fn to_mermaid(&self, diff: &GraphDiff, after: &DependencyGraph) -> String {
+        let mermaid = MermaidGenerator::new();
+        let mut output = String::from("flowchart TB\n");
+        output.push_str(&format!("    %% {}\n\n", diff.summary()));
+
+        let node_status = |id: &str| -> ChangeKind {
+            if diff.added_nodes.iter().any(|n| n.id == id) {
+                ChangeKind::Added
+            } else if diff.removed_nodes.iter().any(|n| n.id == id) {
+                ChangeKind::Removed
+            } else if diff.changed_nodes.iter().any(|c| c.id == id) {
+                ChangeKind::Changed
+            } else {
+                ChangeKind::Unchanged
+            }
+        };
+
+        // Nodes: proposed graph first, then nodes only in the baseline
+        let nodes = after
+            .render_order()
+            .into_iter()
+            .chain(diff.removed_nodes.iter());
+        for node in nodes {
+            let id = mermaid.sanitize_id(&node.id);
+            let status = node_status(&node.id);
+            let mut label = node.label.clone();
+            match status {
+                ChangeKind::Changed => {
+                    if let Some(change) = diff.changed_nodes.iter().find(|c| c.id == node.id) {
+                        label.push_str(&format!("<br/>{}", change.changes.join("<br/>")));
+                    }
+                }
+                _ => {
+                    if let Some(cost) = node.monthly_cost {
+                        label.push_str(&format!("<br/>${:.2}/mo", cost));
+                    }
+                }
+            }
+            output.push_str(&format!(
+                "    {}[\"{}\"]:::{}\n",
+                id,
+                label.replace('"', "'"),
+                class_name(status)
+            ));
+        }
+        output.push('\n');
+
+        // Edges, with link styles applied by index
+        let after_edges = edge_map(after);
+        let mut link_styles = Vec::new();
+        let mut index = 0;
+        let mut push_edge = |output: &mut String, edge: &GraphEdge, status: ChangeKind| {
+            let arrow = match (status, edge.relationship) {
+                (ChangeKind::Removed, _) => "-.->",
+                (_, EdgeType::DependsOn) => "-->",
+                (_, EdgeType::DataFlow) => "-.->",
+                (_, EdgeType::NetworkConnection) => "==>",
+                (_, EdgeType::CostAttribution) => "-.-",
+                (_, EdgeType::CrossStack) => "--o",
+            };
+            output.push_str(&format!(
+                "    {} {} {}\n",
+                mermaid.sanitize_id(&edge.from),
+                arrow,
+                mermaid.sanitize_id(&edge.to)
+            ));
+            if let Some(color) = edge_color(status) {
+                link_styles.push(format!(
+                    "    linkStyle {} stroke:{},stroke-width:2px\n",
+                    index, color
+                ));
+            }
+            index += 1;
+        };
+
+        for (key, edge) in &after_edges {
+            let status = if diff
+                .added_edges
+                .iter()
+                .any(|e| (e.from.as_str(), e.to.as_str()) == *key)
+            {
+                ChangeKind::Added
+            } else if diff
+                .changed_edges
+                .iter()
+                .any(|c| (c.after.from.as_str(), c.after.to.as_str()) == *key)
+            {
+                ChangeKind::Changed
+            } else {
+                ChangeKind::Unchanged
+            };
+            push_edge(&mut output, edge, status);
+        }
+        for edge in &diff.removed_edges {
+            push_edge(&mut output, edge, ChangeKind::Removed);
+        }
+
+        output.push('\n');
+        for style in link_styles {
+            output.push_str(&style);
+        }
+
+        output.push_str("    classDef added fill:#d4edda,stroke:#28a745,stroke-width:2px\n");
+        output.push_str(
+            "    classDef removed fill:#f8d7da,stroke:#dc3545,stroke-width:2px,stroke-dasharray:5 5\n",
+        );
+        output.push_str("    classDef changed fill:#fff3cd,stroke:#ffc107,stroke-width:2px\n");
+        output.push_str("    classDef unchanged fill:#f8f9fa,stroke:#adb5bd\n");
+
+        output
+    }
+
+    /// Describe attribute differences between two versions of a node
+    fn node_changes(&self, before: &GraphNode, after: &GraphNode) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        if before.resource_type != after.resource_type {
+            changes.push(format!(
+                "type: {} → {}",
+                before.resource_type.as_deref().unwrap_or("-"),
+                after.resource_type.as_deref().unwrap_or("-")
+            ));
+        }
+        if before.node_type != after.node_type {
+            changes.push(format!(
+                "kind: {:?} → {:?}",
+                before.node_type, after.node_type
+            ));
+        }
+        if before.module != after.module {
+            changes.push(format!(
+                "module: {} → {}",
+                before.module.as_deref().unwrap_or("-"),
+                after.module.as_deref().unwrap_or("-")
+            ));
+        }
+        let old_cost = before.monthly_cost.unwrap_or(0.0);
+        let new_cost = after.monthly_cost.unwrap_or(0.0);
+        if (new_cost - old_cost).abs() >= self.cost_tolerance {
+            changes.push(format!("${:.2} → ${:.2}/mo", old_cost, new_cost));
+        }
+
+        changes
+    }
+}
+
+impl Default for GraphComparator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Edges keyed by endpoints, in deterministic order
+fn edge_map(graph: &DependencyGraph) -> BTreeMap<(&str, &str), &GraphEdge> {
+    graph
+        .edges
+        .iter()
+        .map(|e| ((e.from.as_str(), e.to.as_str()), e))
+        .collect()
+}
+
+fn total_cost(graph: &DependencyGraph) -> f64 {
+    graph.nodes.iter().filter_map(|n| n.monthly_cost).sum()
+}
+
+fn class_name(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Added => "added",
+        ChangeKind::Removed => "removed",
+        ChangeKind::Changed => "changed",
+        ChangeKind::Unchanged => "unchanged",
+    }
+}
+
+fn edge_color(kind: ChangeKind) -> Option<&'static str> {
+    match kind {
+        ChangeKind::Added => Some("#28a745"),
+        ChangeKind::Removed => Some("#dc3545"),
+        ChangeKind::Changed => Some("#ffc107"),
+        ChangeKind::Unchanged => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, cost: f64) -> GraphNode {
+        GraphNode::new_resource(id.to_string(), "aws_instance".to_string(), id.to_string())
+            .with_cost(cost)
+    }
+
+    fn edge(from: &str, to: &str, relationship: EdgeType) -> GraphEdge {
+        GraphEdge::new(from.to_string(), to.to_string(), relationship)
+    }
+
+    fn graphs() -> (DependencyGraph, DependencyGraph) {
+        let mut before = DependencyGraph::new();
+        before.add_node(node("web", 100.0));
+        before.add_node(node("db", 200.0));
+        before.add_node(node("cache", 50.0));
+        before.add_edge(edge("web", "db", EdgeType::DependsOn));
+        before.add_edge(edge("web", "cache", EdgeType::DataFlow));
+
+        let mut after = DependencyGraph::new();
+        after.add_node(node("web", 150.0));
+        after.add_node(node("db", 200.0));
+        after.add_node(node("queue", 10.0));
+        after.add_edge(edge("web", "db", EdgeType::NetworkConnection));
+        after.add_edge(edge("web", "queue", EdgeType::DataFlow));
+
+        (before, after)
+    }
+
+    #[test]
+    fn test_compare_nodes_and_edges() {
+        let (before, after) = graphs();
+        let diff = GraphComparator::new().compare(&before, &after);
+
+        assert_eq!(diff.added_nodes[0].id, "queue");
+        assert_eq!(diff.removed_nodes[0].id, "cache");
+        assert_eq!(diff.changed_nodes.len(), 1);
+        assert_eq!(diff.changed_nodes[0].id, "web");
+        assert_eq!(diff.added_edges.len(), 1);
+        assert_eq!(diff.removed_edges.len(), 1);
+        assert_eq!(diff.changed_edges.len(), 1);
+        assert!((diff.cost_delta - 10.0).abs() < 1e-9);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_identical_graphs() {
+        let (before, _) = graphs();
+        let diff = GraphComparator::new().compare(&before, &before);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_mermaid_color_coding() {
+        let (before, after) = graphs();
+        let comparator = GraphComparator::new();
+        let diff = comparator.compare(&before, &after);
+        let mermaid = comparator.to_mermaid(&diff, &after);
+
+        assert!(mermaid.starts_with("flowchart TB"));
+        assert!(mermaid.contains("queue[\"queue<br/>$10.00/mo\"]:::added"));
+        assert!(mermaid.contains("cache[\"cache<br/>$50.00/mo\"]:::removed"));
+        assert!(mermaid.contains("web[\"web<br/>$100.00 → $150.00/mo\"]:::changed"));
+        assert!(mermaid.contains("db[\"db<br/>$200.00/mo\"]:::unchanged"));
+        assert!(mermaid.contains("linkStyle 2 stroke:#dc3545"));
+    }
+}
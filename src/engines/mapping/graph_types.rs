@@ -1,6 +1,8 @@
+use super::cycle_detector::{CycleDetector, CycleType};
+use super::topological_sorter::{CycleError, SortOrder, TopologicalSorter};
 use chrono;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 /// A node in the dependency graph representing a resource or service
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -59,7 +61,7 @@ pub struct GraphEdge {
 }
 
 /// Type of dependency relationship
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum EdgeType {
     /// Direct dependency (e.g., Lambda depends on VPC)
@@ -73,6 +75,9 @@ pub enum EdgeType {
 
     /// Cost attribution
     CostAttribution,
+
+    /// Reference across stacks or workspaces (remote state, stack exports)
+    CrossStack,
 }
 
 /// Complete dependency graph
@@ -116,6 +121,56 @@ pub struct GraphMetadata {
     /// Total monthly cost across all nodes
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total_cost: Option<f64>,
+
+    /// Number of resources collapsed into aggregate nodes to fit the node budget
+    #[serde(skip_serializing_if = "is_zero", default)]
+    pub collapsed_count: usize,
+
+    /// Stacks stitched into this graph, when built from multiple artifacts
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub stacks: Vec<StackProvenance>,
+
+    /// Provenance of each cross-stack edge
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub cross_stack_references: Vec<CrossStackReference>,
+}
+
+/// Source stack of nodes in a stitched graph
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StackProvenance {
+    /// Stack or workspace name (node ID prefix)
+    pub name: String,
+
+    /// Artifact kind ("terraform" or "cloudformation")
+    pub kind: String,
+
+    /// Source file or identifier
+    pub source: String,
+
+    /// Number of nodes contributed
+    pub node_count: usize,
+}
+
+/// A resolved reference between two stacks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CrossStackReference {
+    /// Consuming node ID
+    pub from: String,
+
+    /// Producing node ID
+    pub to: String,
+
+    pub from_stack: String,
+    pub to_stack: String,
+
+    /// How the reference was expressed, e.g.
+    /// `data.terraform_remote_state.network.outputs.vpc_id` or
+    /// `Fn::ImportValue: network-VpcId`
+    pub via: String,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
 }
 
 /// Configuration for graph construction
@@ -132,6 +187,9 @@ pub struct GraphConfig {
 
     /// Whether to aggregate by service
     pub aggregate_by_service: bool,
+
+    /// Collapse low-cost leaf nodes once the graph exceeds this many nodes
+    pub node_budget: Option<usize>,
 }
 
 impl Default for GraphConfig {
@@ -141,6 +199,7 @@ impl Default for GraphConfig {
             detect_cycles: true,
             infer_downstream: true,
             aggregate_by_service: false,
+            node_budget: None,
         }
     }
 }
@@ -195,6 +254,19 @@ impl GraphNode {
     }
 }
 
+impl EdgeType {
+    /// Serialized (snake_case) name of the relationship
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeType::DependsOn => "depends_on",
+            EdgeType::DataFlow => "data_flow",
+            EdgeType::NetworkConnection => "network_connection",
+            EdgeType::CostAttribution => "cost_attribution",
+            EdgeType::CrossStack => "cross_stack",
+        }
+    }
+}
+
 impl GraphEdge {
     /// Create a new edge
     pub fn new(from: String, to: String, relationship: EdgeType) -> Self {
@@ -228,6 +300,9 @@ impl DependencyGraph {
                 has_cycles: false,
                 cycles: Vec::new(),
                 total_cost: None,
+                collapsed_count: 0,
+                stacks: Vec::new(),
+                cross_stack_references: Vec::new(),
             },
         }
     }
@@ -292,6 +367,28 @@ impl DependencyGraph {
         }
     }
 
+    /// Nodes in deterministic topological order (edge sources first)
+    pub fn topological_order(&self) -> Result<Vec<String>, CycleError> {
+        TopologicalSorter::from_graph(self).sort(SortOrder::Forward)
+    }
+
+    /// All dependency cycles, including self-loops
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        CycleDetector::from_graph(self).detect(CycleType::All)
+    }
+
+    /// Nodes in rendering order: topological where possible, with nodes on
+    /// or behind a cycle appended by ID
+    pub fn render_order(&self) -> Vec<&GraphNode> {
+        let by_id: HashMap<&str, &GraphNode> =
+            self.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+        TopologicalSorter::from_graph(self)
+            .sort_lenient(SortOrder::Forward)
+            .iter()
+            .filter_map(|id| by_id.get(id.as_str()).copied())
+            .collect()
+    }
+
     /// Update metadata
     pub fn update_metadata(&mut self) {
         self.metadata.node_count = self.nodes.len();
@@ -426,4 +523,40 @@ mod tests {
         graph.calculate_total_cost();
         assert_eq!(graph.metadata.total_cost, Some(300.0));
     }
+
+    #[test]
+    fn test_render_order_with_cycle() {
+        let mut graph = DependencyGraph::new();
+        for id in ["web", "sg_a", "sg_b", "vpc"] {
+            graph.add_node(GraphNode::new_resource(
+                id.to_string(),
+                "type".to_string(),
+                id.to_string(),
+            ));
+        }
+        graph.add_edge(GraphEdge::new(
+            "web".to_string(),
+            "vpc".to_string(),
+            EdgeType::DependsOn,
+        ));
+        graph.add_edge(GraphEdge::new(
+            "sg_a".to_string(),
+            "sg_b".to_string(),
+            EdgeType::DependsOn,
+        ));
+        graph.add_edge(GraphEdge::new(
+            "sg_b".to_string(),
+            "sg_a".to_string(),
+            EdgeType::DependsOn,
+        ));
+
+        assert!(graph.topological_order().is_err());
+        assert_eq!(
+            graph.find_cycles(),
+            vec![vec!["sg_a".to_string(), "sg_b".to_string()]]
+        );
+
+        let order: Vec<&str> = graph.render_order().iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(order, vec!["web", "vpc", "sg_a", "sg_b"]);
+    }
 }
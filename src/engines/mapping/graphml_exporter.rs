@@ -0,0 +1,163 @@
+// GraphML export for dependency graphs (Gephi, yEd, Neo4j APOC)
+
+use super::graph_types::DependencyGraph;
+use crate::errors::CostPilotError;
+
+/// Node and edge attribute keys: (id, domain, name, type)
+const KEYS: &[(&str, &str, &str, &str)] = &[
+    ("label", "node", "label", "string"),
+    ("node_type", "node", "node_type", "string"),
+    ("resource_type", "node", "resource_type", "string"),
+    ("monthly_cost", "node", "monthly_cost", "double"),
+    ("module", "node", "module", "string"),
+    ("relationship", "edge", "relationship", "string"),
+    ("cost_impact", "edge", "cost_impact", "string"),
+];
+
+/// GraphML exporter for dependency graphs
+pub struct GraphmlExporter;
+
+impl GraphmlExporter {
+    /// Create new exporter
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Export graph to a GraphML document
+    pub fn export(&self, graph: &DependencyGraph) -> Result<String, CostPilotError> {
+        let mut output = String::new();
+        output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str(
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+             http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n",
+        );
+
+        for (id, domain, name, attr_type) in KEYS {
+            output.push_str(&format!(
+                "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+                id, domain, name, attr_type
+            ));
+        }
+
+        output.push_str("  <graph id=\"costpilot\" edgedefault=\"directed\">\n");
+
+        for node in graph.render_order() {
+            output.push_str(&format!("    <node id=\"{}\">\n", escape_xml(&node.id)));
+            push_data(&mut output, "label", Some(&node.label));
+            push_data(
+                &mut output,
+                "node_type",
+                Some(&format!("{:?}", node.node_type).to_lowercase()),
+            );
+            push_data(&mut output, "resource_type", node.resource_type.as_deref());
+            push_data(
+                &mut output,
+                "monthly_cost",
+                node.monthly_cost.map(|c| format!("{:.2}", c)).as_deref(),
+            );
+            push_data(&mut output, "module", node.module.as_deref());
+            output.push_str("    </node>\n");
+        }
+
+        for (index, edge) in graph.edges.iter().enumerate() {
+            output.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
+                index,
+                escape_xml(&edge.from),
+                escape_xml(&edge.to)
+            ));
+            push_data(
+                &mut output,
+                "relationship",
+                Some(edge.relationship.as_str()),
+            );
+            push_data(&mut output, "cost_impact", edge.cost_impact.as_deref());
+            output.push_str("    </edge>\n");
+        }
+
+        output.push_str("  </graph>\n");
+        output.push_str("</graphml>\n");
+        Ok(output)
+    }
+}
+
+impl Default for GraphmlExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn push_data(output: &mut String, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        output.push_str(&format!(
+            "      <data key=\"{}\">{}</data>\n",
+            key,
+            escape_xml(value)
+        ));
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::mapping::graph_types::{EdgeType, GraphEdge, GraphNode};
+
+    fn sample_graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        graph.add_node(
+            GraphNode::new_resource(
+                "aws_instance_web".to_string(),
+                "aws_instance".to_string(),
+                "web <primary>".to_string(),
+            )
+            .with_cost(70.0),
+        );
+        graph.add_node(GraphNode::new_resource(
+            "aws_vpc_main".to_string(),
+            "aws_vpc".to_string(),
+            "main".to_string(),
+        ));
+        graph.add_edge(
+            GraphEdge::new(
+                "aws_instance_web".to_string(),
+                "aws_vpc_main".to_string(),
+                EdgeType::NetworkConnection,
+            )
+            .with_cost_impact("Data transfer & NAT".to_string()),
+        );
+        graph
+    }
+
+    #[test]
+    fn test_export_graphml_structure() {
+        let graphml = GraphmlExporter::new().export(&sample_graph()).unwrap();
+
+        assert!(graphml.starts_with("<?xml"));
+        assert!(graphml.contains("edgedefault=\"directed\""));
+        assert!(graphml.contains("<node id=\"aws_instance_web\">"));
+        assert!(graphml
+            .contains("<edge id=\"e0\" source=\"aws_instance_web\" target=\"aws_vpc_main\">"));
+        assert!(graphml.contains("<data key=\"monthly_cost\">70.00</data>"));
+        assert!(graphml.contains("<data key=\"relationship\">network_connection</data>"));
+        // Absent attributes are omitted rather than emitted empty
+        assert_eq!(graphml.matches("key=\"monthly_cost\">").count(), 1);
+    }
+
+    #[test]
+    fn test_export_graphml_escapes_text() {
+        let graphml = GraphmlExporter::new().export(&sample_graph()).unwrap();
+
+        assert!(graphml.contains("web &lt;primary&gt;"));
+        assert!(graphml.contains("Data transfer &amp; NAT"));
+    }
+}
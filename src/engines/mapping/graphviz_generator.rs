@@ -1,8 +1,9 @@
 // Graphviz DOT format generator for dependency graphs
 
 use super::graph_types::{DependencyGraph, EdgeType, GraphEdge, GraphNode, NodeType};
+use super::topological_sorter::format_cycle;
 use crate::errors::CostPilotError;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashSet};
 
 /// Configuration for Graphviz generation
 #[derive(Debug, Clone)]
@@ -123,10 +124,10 @@ impl GraphvizGenerator {
         output: &mut String,
     ) -> Result<(), CostPilotError> {
         // Group nodes by module
-        let mut module_nodes: HashMap<String, Vec<&GraphNode>> = HashMap::new();
+        let mut module_nodes: BTreeMap<String, Vec<&GraphNode>> = BTreeMap::new();
         let mut ungrouped_nodes = Vec::new();
 
-        for node in &graph.nodes {
+        for node in graph.render_order() {
             if let Some(module) = &node.module {
                 module_nodes.entry(module.clone()).or_default().push(node);
             } else {
@@ -167,7 +168,7 @@ impl GraphvizGenerator {
         graph: &DependencyGraph,
         output: &mut String,
     ) -> Result<(), CostPilotError> {
-        for node in &graph.nodes {
+        for node in graph.render_order() {
             self.generate_node(node, output, 1)?;
         }
         Ok(())
@@ -252,11 +253,24 @@ impl GraphvizGenerator {
     ) -> Result<(), CostPilotError> {
         output.push_str("  // Edges\n");
 
+        // Edges that close a dependency cycle are drawn in red
+        let mut cycle_edges: HashSet<(&str, &str)> = HashSet::new();
+        for cycle in &graph.metadata.cycles {
+            output.push_str(&format!("  // Cycle: {}\n", format_cycle(cycle)));
+            for (i, from) in cycle.iter().enumerate() {
+                let to = &cycle[(i + 1) % cycle.len()];
+                cycle_edges.insert((from.as_str(), to.as_str()));
+            }
+        }
+
         for edge in &graph.edges {
             let from_id = sanitize_id(&edge.from);
             let to_id = sanitize_id(&edge.to);
 
-            let (style, color, label) = self.get_edge_style(edge);
+            let (style, mut color, label) = self.get_edge_style(edge);
+            if cycle_edges.contains(&(edge.from.as_str(), edge.to.as_str())) {
+                color = "red";
+            }
 
             if let Some(label_text) = label {
                 output.push_str(&format!(
@@ -286,6 +300,7 @@ impl GraphvizGenerator {
             EdgeType::DataFlow => ("dashed", "blue", Some("data".to_string())),
             EdgeType::NetworkConnection => ("dotted", "green", Some("network".to_string())),
             EdgeType::CostAttribution => ("bold", "red", Some("cost".to_string())),
+            EdgeType::CrossStack => ("bold", "purple", Some("cross-stack".to_string())),
         };
 
         let label = if let Some(impact) = &edge.cost_impact {
@@ -419,6 +434,9 @@ mod tests {
                 has_cycles: false,
                 cycles: vec![],
                 total_cost: Some(250.0),
+                collapsed_count: 0,
+                stacks: Vec::new(),
+                cross_stack_references: Vec::new(),
             },
         };
 
@@ -0,0 +1,269 @@
+// Standalone interactive HTML export for large dependency graphs
+
+use super::graph_types::DependencyGraph;
+use crate::errors::CostPilotError;
+use serde_json::json;
+use std::collections::BTreeSet;
+
+/// Inlined viewer script; the export must not reference any CDN
+const VIEWER_JS: &str = include_str!("assets/interactive_viewer.js");
+
+/// Configuration for interactive HTML export
+#[derive(Debug, Clone)]
+pub struct InteractiveHtmlConfig {
+    /// Collapse module groups on load when the graph exceeds this many nodes
+    pub collapse_threshold: usize,
+
+    /// Color nodes by monthly cost instead of node type on load
+    pub heat_map: bool,
+}
+
+impl Default for InteractiveHtmlConfig {
+    fn default() -> Self {
+        Self {
+            collapse_threshold: 200,
+            heat_map: true,
+        }
+    }
+}
+
+/// Generates a self-contained HTML page with a zoomable, searchable graph
+pub struct InteractiveHtmlGenerator {
+    config: InteractiveHtmlConfig,
+}
+
+impl InteractiveHtmlGenerator {
+    /// Create generator with default configuration
+    pub fn new() -> Self {
+        Self {
+            config: InteractiveHtmlConfig::default(),
+        }
+    }
+
+    /// Create generator with custom configuration
+    pub fn with_config(config: InteractiveHtmlConfig) -> Self {
+        Self { config }
+    }
+
+    /// Generate the interactive HTML page
+    pub fn generate(&self, graph: &DependencyGraph, title: &str) -> Result<String, CostPilotError> {
+        let data = self.graph_data(graph);
+        let data_json = serde_json::to_string(&data)
+            .map_err(|e| CostPilotError::serialization_error(e.to_string()))?;
+
+        let total_cost = graph
+            .metadata
+            .total_cost
+            .map(|c| format!("${:.2}/mo", c))
+            .unwrap_or_else(|| "n/a".to_string());
+
+        let mut html = String::with_capacity(VIEWER_JS.len() + data_json.len() + 4096);
+        html.push_str(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>"#,
+        );
+        html.push_str(&escape_html(title));
+        html.push_str(
+            r#"</title>
+    <style>
+        html, body { margin: 0; height: 100%; font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, "Helvetica Neue", Arial, sans-serif; }
+        body { display: flex; flex-direction: column; }
+        header { display: flex; align-items: center; gap: 12px; padding: 10px 16px; background: #667eea; color: white; flex-wrap: wrap; }
+        header h1 { font-size: 18px; margin: 0 12px 0 0; }
+        header input[type=search] { padding: 4px 8px; border-radius: 4px; border: none; min-width: 220px; }
+        header button { padding: 4px 10px; border-radius: 4px; border: none; cursor: pointer; }
+        .stats { margin-left: auto; font-size: 13px; opacity: 0.9; }
+        main { flex: 1; display: flex; min-height: 0; }
+        #graph { flex: 1; width: 100%; height: 100%; background: #fafbfc; cursor: grab; }
+        aside { width: 260px; padding: 12px; border-left: 1px solid #ddd; font-size: 13px; overflow-y: auto; }
+        aside div { margin-bottom: 6px; word-break: break-all; }
+        .legend { margin-top: 16px; }
+        .heat { height: 10px; border-radius: 4px; background: linear-gradient(90deg, hsl(120,70%,45%), hsl(60,70%,45%), hsl(0,70%,45%)); }
+    </style>
+</head>
+<body>
+    <header>
+        <h1>"#,
+        );
+        html.push_str(&escape_html(title));
+        html.push_str(
+            r#"</h1>
+        <input id="search" type="search" placeholder="Search nodes (Enter for next)">
+        <span id="match-count"></span>
+        <label><input id="heatmap" type="checkbox"> Cost heat map</label>
+        <button id="fit">Fit</button>
+        <button id="expand-all">Expand modules</button>
+        <button id="collapse-all">Collapse modules</button>
+        <span class="stats">"#,
+        );
+        html.push_str(&format!(
+            "{} nodes · {} edges · {}",
+            graph.nodes.len(),
+            graph.edges.len(),
+            escape_html(&total_cost)
+        ));
+        html.push_str(
+            r#"</span>
+    </header>
+    <main>
+        <canvas id="graph"></canvas>
+        <aside>
+            <div id="details"></div>
+            <div class="legend">
+                <strong>Monthly cost</strong>
+                <div class="heat"></div>
+                <small>Click a dashed module group to expand it. Scroll to zoom, drag to pan.</small>
+            </div>
+        </aside>
+    </main>
+    <script type="application/json" id="graph-data">"#,
+        );
+        html.push_str(&escape_script(&data_json));
+        html.push_str("</script>\n    <script>\n");
+        html.push_str(&escape_script(VIEWER_JS));
+        html.push_str("    </script>\n</body>\n</html>\n");
+
+        Ok(html)
+    }
+
+    /// Build Cytoscape-style element data consumed by the viewer
+    fn graph_data(&self, graph: &DependencyGraph) -> serde_json::Value {
+        let nodes: Vec<serde_json::Value> = graph
+            .render_order()
+            .into_iter()
+            .map(|node| {
+                json!({
+                    "data": {
+                        "id": node.id,
+                        "label": node.label,
+                        "type": node.node_type,
+                        "resource_type": node.resource_type,
+                        "monthly_cost": node.monthly_cost,
+                        "module": node.module,
+                    }
+                })
+            })
+            .collect();
+
+        let edges: Vec<serde_json::Value> = graph
+            .edges
+            .iter()
+            .map(|edge| {
+                json!({
+                    "data": {
+                        "source": edge.from,
+                        "target": edge.to,
+                        "relationship": edge.relationship,
+                        "cost_impact": edge.cost_impact,
+                    }
+                })
+            })
+            .collect();
+
+        let modules: BTreeSet<&str> = graph
+            .nodes
+            .iter()
+            .filter_map(|n| n.module.as_deref())
+            .collect();
+
+        json!({
+            "elements": { "nodes": nodes, "edges": edges },
+            "modules": modules,
+            "options": {
+                "collapse_threshold": self.config.collapse_threshold,
+                "heat_map": self.config.heat_map,
+            },
+        })
+    }
+}
+
+impl Default for InteractiveHtmlGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Prevent embedded content from terminating its `<script>` element early
+fn escape_script(text: &str) -> String {
+    text.replace("</", "<\\/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::mapping::graph_types::{EdgeType, GraphEdge, GraphNode};
+
+    fn sample_graph() -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        let mut web = GraphNode::new_resource(
+            "aws_instance.web".to_string(),
+            "aws_instance".to_string(),
+            "Web </script> server".to_string(),
+        )
+        .with_cost(120.0);
+        web.module = Some("app".to_string());
+        graph.add_node(web);
+        graph.add_node(
+            GraphNode::new_resource(
+                "aws_db_instance.db".to_string(),
+                "aws_db_instance".to_string(),
+                "Database".to_string(),
+            )
+            .with_cost(300.0),
+        );
+        graph.add_edge(GraphEdge::new(
+            "aws_instance.web".to_string(),
+            "aws_db_instance.db".to_string(),
+            EdgeType::DependsOn,
+        ));
+        graph
+    }
+
+    #[test]
+    fn test_generate_is_self_contained() {
+        let html = InteractiveHtmlGenerator::new()
+            .generate(&sample_graph(), "Deps & Costs")
+            .unwrap();
+
+        assert!(html.contains("<title>Deps &amp; Costs</title>"));
+        assert!(html.contains("id=\"graph-data\""));
+        assert!(!html.contains("<script src="));
+        assert!(!html.contains("https://"));
+    }
+
+    #[test]
+    fn test_embedded_data_cannot_close_script() {
+        let html = InteractiveHtmlGenerator::new()
+            .generate(&sample_graph(), "Deps")
+            .unwrap();
+
+        assert!(!html.contains("Web </script> server"));
+        assert!(html.contains("Web <\\/script> server"));
+    }
+
+    #[test]
+    fn test_graph_data_lists_modules_and_options() {
+        let generator = InteractiveHtmlGenerator::with_config(InteractiveHtmlConfig {
+            collapse_threshold: 1,
+            heat_map: false,
+        });
+        let data = generator.graph_data(&sample_graph());
+
+        assert_eq!(data["elements"]["nodes"].as_array().unwrap().len(), 2);
+        assert_eq!(data["elements"]["edges"].as_array().unwrap().len(), 1);
+        assert_eq!(data["modules"], json!(["app"]));
+        assert_eq!(data["options"]["collapse_threshold"], 1);
+        assert_eq!(data["options"]["heat_map"], false);
+    }
+}
@@ -1,6 +1,7 @@
 // JSON export for dependency graphs
 
 use super::graph_types::DependencyGraph;
+use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
 use crate::errors::CostPilotError;
 use serde_json::{json, Value};
 
@@ -86,6 +87,7 @@ impl JsonExporter {
     /// Export in standard format
     fn export_standard(&self, graph: &DependencyGraph) -> Result<Value, CostPilotError> {
         let mut result = json!({
+            "schema_version": OUTPUT_SCHEMA_VERSION,
             "nodes": graph.nodes,
             "edges": graph.edges,
         });
@@ -355,6 +357,7 @@ impl JsonExporter {
             EdgeType::DataFlow => 0.8,
             EdgeType::NetworkConnection => 0.6,
             EdgeType::CostAttribution => 1.2,
+            EdgeType::CrossStack => 1.0,
         }
     }
 
@@ -436,6 +439,9 @@ mod tests {
                 has_cycles: false,
                 cycles: vec![],
                 total_cost: Some(250.0),
+                collapsed_count: 0,
+                stacks: Vec::new(),
+                cross_stack_references: Vec::new(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
                 timestamp: chrono::Utc::now().to_rfc3339(),
             },
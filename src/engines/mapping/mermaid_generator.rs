@@ -1,4 +1,5 @@
 use super::graph_types::*;
+use super::topological_sorter::format_cycle;
 use crate::errors::CostPilotError;
 use std::collections::HashSet;
 
@@ -63,13 +64,26 @@ impl MermaidGenerator {
             output.push_str("    style warning fill:#fff3cd,stroke:#856404\n\n");
         }
 
+        // Make node budget aggregation explicit
+        if graph.metadata.collapsed_count > 0 {
+            output.push_str(&format!(
+                "    collapsedInfo[\"📦 {} resources collapsed\"]\n",
+                graph.metadata.collapsed_count
+            ));
+            output.push_str("    style collapsedInfo fill:#e2e3e5,stroke:#383d41\n\n");
+        }
+
         // Show cycle warning if present
         if self.config.highlight_cycles && graph.metadata.has_cycles {
             output.push_str(&format!(
                 "    cycleWarning[\"🔄 {} cycle(s) detected\"]\n",
                 graph.metadata.cycles.len()
             ));
-            output.push_str("    style cycleWarning fill:#f8d7da,stroke:#721c24\n\n");
+            output.push_str("    style cycleWarning fill:#f8d7da,stroke:#721c24\n");
+            for cycle in &graph.metadata.cycles {
+                output.push_str(&format!("    %% Cycle: {}\n", format_cycle(cycle)));
+            }
+            output.push('\n');
         }
 
         // Collect nodes in cycles for highlighting
@@ -101,7 +115,7 @@ impl MermaidGenerator {
             }
         } else {
             // No grouping, just render all nodes
-            for node in &graph.nodes {
+            for node in graph.render_order() {
                 output.push_str(&self.generate_node_definition(node, &cycle_nodes));
             }
             output.push('\n');
@@ -163,6 +177,7 @@ impl MermaidGenerator {
             EdgeType::DataFlow => "-.->",
             EdgeType::NetworkConnection => "==>",
             EdgeType::CostAttribution => "-.-",
+            EdgeType::CrossStack => "--o",
         };
 
         // Add label with cost impact if enabled
@@ -210,10 +225,10 @@ impl MermaidGenerator {
         &self,
         graph: &'a DependencyGraph,
     ) -> Vec<(Option<String>, Vec<&'a GraphNode>)> {
-        let mut groups: std::collections::HashMap<Option<String>, Vec<&GraphNode>> =
-            std::collections::HashMap::new();
+        let mut groups: std::collections::BTreeMap<Option<String>, Vec<&GraphNode>> =
+            std::collections::BTreeMap::new();
 
-        for node in &graph.nodes {
+        for node in graph.render_order() {
             groups.entry(node.module.clone()).or_default().push(node);
         }
 
@@ -221,7 +236,7 @@ impl MermaidGenerator {
     }
 
     /// Sanitize node ID for Mermaid compatibility
-    fn sanitize_id(&self, id: &str) -> String {
+    pub(super) fn sanitize_id(&self, id: &str) -> String {
         id.replace(['.', '[', ']'], "_")
             .replace('"', "")
             .replace(['-', ':'], "_")
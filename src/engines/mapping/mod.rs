@@ -1,14 +1,33 @@
+mod csv_exporter;
+mod cycle_detector;
+mod graph_aggregator;
 mod graph_builder;
+mod graph_comparator;
 mod graph_types;
+mod graphml_exporter;
 mod graphviz_generator;
+mod interactive_html;
 mod json_exporter;
 mod mermaid_generator;
+mod reference_resolver;
+mod topological_sorter;
 
+pub use csv_exporter::CsvExporter;
+pub use cycle_detector::{CycleDetector, CycleType};
+pub use graph_aggregator::{NodeAggregator, COLLAPSED_PREFIX};
+pub(crate) use graph_builder::stable_id;
 pub use graph_builder::GraphBuilder;
+pub use graph_comparator::{ChangeKind, EdgeChange, GraphComparator, GraphDiff, NodeChange};
 pub use graph_types::*;
+pub use graphml_exporter::GraphmlExporter;
 pub use graphviz_generator::{ColorScheme, GraphvizConfig, GraphvizGenerator};
+pub use interactive_html::{InteractiveHtmlConfig, InteractiveHtmlGenerator};
 pub use json_exporter::{JsonExportConfig, JsonExporter, JsonFormat};
 pub use mermaid_generator::{MermaidConfig, MermaidGenerator};
+pub use reference_resolver::{
+    ReferenceResolver, ResolvedGraph, StackKind, UnresolvedReference, STACK_SEPARATOR,
+};
+pub use topological_sorter::{format_cycle, CycleError, SortOrder, TopologicalSorter};
 
 use crate::engines::detection::ResourceChange;
 use crate::errors::CostPilotError;
@@ -58,6 +77,23 @@ impl MappingEngine {
         self.builder.build_graph(changes)
     }
 
+    /// Rebuild a dependency graph from a prior build, reusing unchanged nodes
+    pub fn build_graph_incremental(
+        &mut self,
+        previous: &DependencyGraph,
+        previous_changes: &[ResourceChange],
+        changes: &[ResourceChange],
+    ) -> Result<DependencyGraph, CostPilotError> {
+        let max_depth = self.builder.config.max_depth.unwrap_or(5);
+        if max_depth > 1 && self.edition.is_free() {
+            return Err(CostPilotError::upgrade_required(
+                "Deep dependency mapping requires Premium",
+            ));
+        }
+        self.builder
+            .build_incremental(previous, previous_changes, changes)
+    }
+
     /// Generate Mermaid diagram from dependency graph
     pub fn generate_mermaid(&self, graph: &DependencyGraph) -> Result<String, CostPilotError> {
         self.generator.generate(graph)
@@ -72,6 +108,15 @@ impl MappingEngine {
         self.generator.generate_html(graph, title)
     }
 
+    /// Generate standalone interactive HTML (zoom, search, cost heat map, module groups)
+    pub fn generate_interactive_html(
+        &self,
+        graph: &DependencyGraph,
+        title: &str,
+    ) -> Result<String, CostPilotError> {
+        InteractiveHtmlGenerator::new().generate(graph, title)
+    }
+
     /// Complete pipeline: build graph and generate Mermaid diagram
     pub fn map_dependencies(
         &mut self,
@@ -107,6 +152,21 @@ impl MappingEngine {
         generator.generate(graph)
     }
 
+    /// Export graph to GraphML (Gephi, yEd, Neo4j APOC)
+    pub fn export_graphml(&self, graph: &DependencyGraph) -> Result<String, CostPilotError> {
+        GraphmlExporter::new().export(graph)
+    }
+
+    /// Export graph edge list as CSV
+    pub fn export_csv_edges(&self, graph: &DependencyGraph) -> String {
+        CsvExporter::new().export_edges(graph)
+    }
+
+    /// Export graph node table as CSV
+    pub fn export_csv_nodes(&self, graph: &DependencyGraph) -> String {
+        CsvExporter::new().export_nodes(graph)
+    }
+
     /// Export graph to JSON (standard format)
     pub fn export_json(&self, graph: &DependencyGraph) -> Result<String, CostPilotError> {
         let exporter = JsonExporter::new();
@@ -311,6 +371,24 @@ impl MappingEngine {
     }
 }
 
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+impl MappingEngine {
+    /// Build the graph and generate Mermaid on a worker thread; cancelling
+    /// `token` skips diagram generation once the graph is built
+    pub fn map_dependencies_async(
+        mut self,
+        changes: Vec<ResourceChange>,
+        token: crate::engines::shared::cancel::CancelToken,
+    ) -> impl std::future::Future<Output = Result<String, CostPilotError>> + Send {
+        crate::engines::shared::worker::run_blocking(token, move |token| {
+            token.check("Dependency mapping")?;
+            let graph = self.build_graph(&changes)?;
+            token.check("Mermaid generation")?;
+            self.generate_mermaid(&graph)
+        })
+    }
+}
+
 impl Default for MappingEngine {
     fn default() -> Self {
         Self::new(&crate::edition::EditionContext::new())
@@ -492,4 +570,26 @@ mod tests {
         assert!(!impacts.is_empty());
         assert!(impacts[0].description.contains("transfer costs"));
     }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_map_dependencies_async() {
+        use crate::engines::shared::cancel::CancelToken;
+        use futures::executor::block_on;
+
+        let changes = vec![
+            create_test_resource("aws_vpc.main", "aws_vpc"),
+            create_test_resource("aws_subnet.public", "aws_subnet"),
+        ];
+        let engine = || MappingEngine::new(&EditionContext::premium_for_test());
+
+        let mermaid =
+            block_on(engine().map_dependencies_async(changes.clone(), CancelToken::new())).unwrap();
+        assert!(mermaid.contains("flowchart TB"));
+
+        let token = CancelToken::new();
+        token.cancel();
+        let err = block_on(engine().map_dependencies_async(changes, token)).unwrap_err();
+        assert_eq!(err.id, "CANCELLED");
+    }
 }
@@ -0,0 +1,692 @@
+// Cross-stack and cross-workspace reference resolution
+
+use super::graph_builder::stable_id;
+use super::graph_types::{
+    CrossStackReference, DependencyGraph, EdgeType, GraphEdge, StackProvenance,
+};
+use crate::artifact::Artifact;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+/// Separator between stack name and node ID in stitched graphs
+pub const STACK_SEPARATOR: &str = "::";
+
+/// Kind of artifact a stack was parsed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackKind {
+    Terraform,
+    CloudFormation,
+}
+
+impl StackKind {
+    fn name(&self) -> &'static str {
+        match self {
+            StackKind::Terraform => "terraform",
+            StackKind::CloudFormation => "cloudformation",
+        }
+    }
+}
+
+/// A reference that could not be matched to a producing stack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedReference {
+    pub stack: String,
+    pub node: String,
+    pub via: String,
+}
+
+/// Result of stitching stacks together
+#[derive(Debug, Clone)]
+pub struct ResolvedGraph {
+    pub graph: DependencyGraph,
+    pub unresolved: Vec<UnresolvedReference>,
+}
+
+/// Something a stack consumes from another stack
+#[derive(Debug, Clone)]
+enum Import {
+    /// `data.terraform_remote_state.<data_source>.outputs.<output>`
+    RemoteState {
+        data_source: String,
+        output: String,
+        hints: Vec<String>,
+    },
+    /// `Fn::ImportValue: <name>`
+    Export { name: String },
+}
+
+impl Import {
+    fn via(&self) -> String {
+        match self {
+            Import::RemoteState {
+                data_source,
+                output,
+                ..
+            } => format!(
+                "data.terraform_remote_state.{}.outputs.{}",
+                data_source, output
+            ),
+            Import::Export { name } => format!("Fn::ImportValue: {}", name),
+        }
+    }
+}
+
+struct Stack {
+    name: String,
+    kind: StackKind,
+    source: String,
+    graph: DependencyGraph,
+    /// Terraform output name or CloudFormation export name -> local node ID
+    exports: HashMap<String, String>,
+    /// Local consuming node ID and what it imports
+    imports: Vec<(String, Import)>,
+}
+
+/// Stitches separately parsed stacks into a single dependency graph,
+/// connecting `terraform_remote_state` consumers and `Fn::ImportValue`
+/// consumers to the resources that produce the referenced values
+pub struct ReferenceResolver {
+    stacks: Vec<Stack>,
+    remote_state_aliases: HashMap<String, String>,
+}
+
+impl ReferenceResolver {
+    /// Create an empty resolver
+    pub fn new() -> Self {
+        Self {
+            stacks: Vec::new(),
+            remote_state_aliases: HashMap::new(),
+        }
+    }
+
+    /// Map a `terraform_remote_state` data source name to a stack name
+    /// when neither its name, workspace nor state key identify the stack
+    pub fn with_remote_state_alias(mut self, data_source: &str, stack: &str) -> Self {
+        self.remote_state_aliases
+            .insert(data_source.to_string(), stack.to_string());
+        self
+    }
+
+    /// Add a Terraform plan (JSON) and the graph built from its resource changes
+    pub fn add_terraform_plan(
+        &mut self,
+        name: &str,
+        source: &str,
+        plan: &Value,
+        graph: DependencyGraph,
+    ) {
+        let root = plan.get("configuration").and_then(|c| c.get("root_module"));
+
+        let mut exports = HashMap::new();
+        if let Some(outputs) = root
+            .and_then(|r| r.get("outputs"))
+            .and_then(|o| o.as_object())
+        {
+            for (output, def) in outputs {
+                let producer = references(def.get("expression").unwrap_or(&Value::Null))
+                    .into_iter()
+                    .find_map(|r| managed_address(&r));
+                if let Some(address) = producer {
+                    exports.insert(output.clone(), stable_id(&address));
+                }
+            }
+        }
+
+        let mut imports = Vec::new();
+        if let Some(root) = root {
+            collect_terraform_imports(root, "", &mut imports);
+        }
+
+        self.stacks.push(Stack {
+            name: name.to_string(),
+            kind: StackKind::Terraform,
+            source: source.to_string(),
+            graph,
+            exports,
+            imports,
+        });
+    }
+
+    /// Add a CloudFormation/CDK artifact and the graph built from its resources
+    pub fn add_artifact(&mut self, artifact: &Artifact, graph: DependencyGraph) {
+        let name = artifact
+            .metadata
+            .stack_name
+            .clone()
+            .unwrap_or_else(|| stack_name_from_source(&artifact.metadata.source));
+
+        let mut exports = HashMap::new();
+        for output in artifact.outputs.values() {
+            let (Some(export_name), Some(logical_id)) =
+                (&output.export_name, referenced_logical_id(&output.value))
+            else {
+                continue;
+            };
+            exports.insert(
+                substitute_stack_name(export_name, &name),
+                stable_id(&logical_id),
+            );
+        }
+
+        let mut imports = Vec::new();
+        for resource in &artifact.resources {
+            let mut names = BTreeSet::new();
+            for value in resource.properties.values() {
+                collect_import_values(value, &mut names);
+            }
+            for import in names {
+                imports.push((
+                    stable_id(&resource.id),
+                    Import::Export {
+                        name: substitute_stack_name(&import, &name),
+                    },
+                ));
+            }
+        }
+
+        self.stacks.push(Stack {
+            source: artifact.metadata.source.clone(),
+            name,
+            kind: StackKind::CloudFormation,
+            graph,
+            exports,
+            imports,
+        });
+    }
+
+    /// Stitch all stacks into one graph with `EdgeType::CrossStack` edges
+    pub fn resolve(&self) -> ResolvedGraph {
+        let mut graph = DependencyGraph::new();
+        let mut max_depth = 0;
+
+        for stack in &self.stacks {
+            for node in &stack.graph.nodes {
+                let mut node = node.clone();
+                node.id = qualify(&stack.name, &node.id);
+                node.module = Some(match node.module {
+                    Some(module) => format!("{}.{}", stack.name, module),
+                    None => stack.name.clone(),
+                });
+                graph.add_node(node);
+            }
+            for edge in &stack.graph.edges {
+                let mut edge = edge.clone();
+                edge.from = qualify(&stack.name, &edge.from);
+                edge.to = qualify(&stack.name, &edge.to);
+                graph.add_edge(edge);
+            }
+            max_depth = max_depth.max(stack.graph.metadata.max_depth);
+            graph.metadata.stacks.push(StackProvenance {
+                name: stack.name.clone(),
+                kind: stack.kind.name().to_string(),
+                source: stack.source.clone(),
+                node_count: stack.graph.nodes.len(),
+            });
+        }
+
+        let mut unresolved = Vec::new();
+        for stack in &self.stacks {
+            for (node, import) in &stack.imports {
+                let target = self.find_producer(stack, import);
+                let from = qualify(&stack.name, node);
+                match target {
+                    Some((producer, to)) if graph.find_node(&from).is_some() => {
+                        let to = qualify(&producer.name, &to);
+                        if graph.find_node(&to).is_none() {
+                            unresolved.push(UnresolvedReference {
+                                stack: stack.name.clone(),
+                                node: node.clone(),
+                                via: import.via(),
+                            });
+                            continue;
+                        }
+                        graph.add_edge(GraphEdge::new(
+                            from.clone(),
+                            to.clone(),
+                            EdgeType::CrossStack,
+                        ));
+                        graph
+                            .metadata
+                            .cross_stack_references
+                            .push(CrossStackReference {
+                                from,
+                                to,
+                                from_stack: stack.name.clone(),
+                                to_stack: producer.name.clone(),
+                                via: import.via(),
+                            });
+                    }
+                    _ => unresolved.push(UnresolvedReference {
+                        stack: stack.name.clone(),
+                        node: node.clone(),
+                        via: import.via(),
+                    }),
+                }
+            }
+        }
+
+        let cycles = graph.find_cycles();
+        graph.metadata.has_cycles = !cycles.is_empty();
+        graph.metadata.cycles = cycles;
+        graph.metadata.max_depth = max_depth;
+        graph.update_metadata();
+
+        ResolvedGraph { graph, unresolved }
+    }
+
+    /// Locate the stack and node that produce an imported value
+    fn find_producer(&self, consumer: &Stack, import: &Import) -> Option<(&Stack, String)> {
+        match import {
+            Import::RemoteState {
+                data_source,
+                output,
+                hints,
+            } => {
+                let alias = self.remote_state_aliases.get(data_source);
+                let producer = self
+                    .stacks
+                    .iter()
+                    .filter(|s| s.name != consumer.name && s.kind == StackKind::Terraform)
+                    .find(|s| {
+                        alias == Some(&s.name)
+                            || (alias.is_none()
+                                && (s.name == *data_source
+                                    || hints.iter().any(|h| hint_matches(h, &s.name))))
+                    })?;
+                producer
+                    .exports
+                    .get(output)
+                    .map(|node| (producer, node.clone()))
+            }
+            Import::Export { name } => self
+                .stacks
+                .iter()
+                .filter(|s| s.name != consumer.name)
+                .find_map(|s| s.exports.get(name).map(|node| (s, node.clone()))),
+        }
+    }
+}
+
+impl Default for ReferenceResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn qualify(stack: &str, id: &str) -> String {
+    format!("{}{}{}", stack, STACK_SEPARATOR, id)
+}
+
+/// Whether a remote state workspace or key names the stack
+fn hint_matches(hint: &str, stack: &str) -> bool {
+    hint == stack
+        || hint
+            .split(['/', '.', '-', '_'])
+            .any(|segment| segment == stack)
+}
+
+/// Walk a Terraform configuration module collecting remote state imports
+fn collect_terraform_imports(module: &Value, prefix: &str, imports: &mut Vec<(String, Import)>) {
+    let resources = module
+        .get("resources")
+        .and_then(|r| r.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    // Remote state data sources and their backend hints (workspace, key)
+    let mut remote_states: HashMap<&str, Vec<String>> = HashMap::new();
+    for resource in resources {
+        if resource.get("mode").and_then(|m| m.as_str()) == Some("data")
+            && resource.get("type").and_then(|t| t.as_str()) == Some("terraform_remote_state")
+        {
+            if let Some(name) = resource.get("name").and_then(|n| n.as_str()) {
+                let expressions = resource.get("expressions");
+                let mut hints = Vec::new();
+                if let Some(workspace) = expressions
+                    .and_then(|e| e.pointer("/workspace/constant_value"))
+                    .and_then(|w| w.as_str())
+                {
+                    hints.push(workspace.to_string());
+                }
+                if let Some(key) = expressions
+                    .and_then(|e| e.pointer("/config/constant_value/key"))
+                    .and_then(|k| k.as_str())
+                {
+                    hints.push(key.to_string());
+                }
+                remote_states.insert(name, hints);
+            }
+        }
+    }
+
+    for resource in resources {
+        if resource.get("mode").and_then(|m| m.as_str()) != Some("managed") {
+            continue;
+        }
+        let Some(address) = resource.get("address").and_then(|a| a.as_str()) else {
+            continue;
+        };
+        let node = stable_id(&format!("{}{}", prefix, address));
+
+        let mut seen = BTreeSet::new();
+        for reference in references(resource.get("expressions").unwrap_or(&Value::Null)) {
+            let parts: Vec<&str> = reference.split('.').collect();
+            if let ["data", "terraform_remote_state", data_source, "outputs", output, ..] =
+                parts.as_slice()
+            {
+                if seen.insert((data_source.to_string(), output.to_string())) {
+                    imports.push((
+                        node.clone(),
+                        Import::RemoteState {
+                            data_source: data_source.to_string(),
+                            output: output.to_string(),
+                            hints: remote_states.get(data_source).cloned().unwrap_or_default(),
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    if let Some(calls) = module.get("module_calls").and_then(|m| m.as_object()) {
+        for (name, call) in calls {
+            if let Some(child) = call.get("module") {
+                let child_prefix = format!("{}module.{}.", prefix, name);
+                collect_terraform_imports(child, &child_prefix, imports);
+            }
+        }
+    }
+}
+
+/// All strings in `references` arrays anywhere under an expression tree
+fn references(value: &Value) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    if key == "references" {
+                        if let Some(refs) = child.as_array() {
+                            out.extend(refs.iter().filter_map(|r| r.as_str()).map(String::from));
+                        }
+                    } else {
+                        stack.push(child);
+                    }
+                }
+            }
+            Value::Array(items) => stack.extend(items.iter().rev()),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// `type.name` address of a managed resource reference such as `aws_vpc.main.id`
+fn managed_address(reference: &str) -> Option<String> {
+    let parts: Vec<&str> = reference.split('.').collect();
+    match parts.as_slice() {
+        ["var" | "local" | "data" | "module" | "path" | "count" | "each" | "terraform", ..] => None,
+        [resource_type, name, ..] => Some(format!("{}.{}", resource_type, name)),
+        _ => None,
+    }
+}
+
+/// Logical ID behind an output value (`Ref` or `Fn::GetAtt`)
+fn referenced_logical_id(value: &Value) -> Option<String> {
+    if let Some(id) = value.get("Ref").and_then(|r| r.as_str()) {
+        return Some(id.to_string());
+    }
+    match value.get("Fn::GetAtt") {
+        Some(Value::Array(parts)) => parts.first().and_then(|p| p.as_str()).map(String::from),
+        Some(Value::String(attr)) => attr.split('.').next().map(String::from),
+        _ => None,
+    }
+}
+
+/// Collect `Fn::ImportValue` names (plain strings or simple `Fn::Sub` templates)
+fn collect_import_values(value: &Value, names: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(import) = map.get("Fn::ImportValue") {
+                let name = import.as_str().or_else(|| match import.get("Fn::Sub") {
+                    Some(Value::String(template)) => Some(template.as_str()),
+                    Some(Value::Array(parts)) => parts.first().and_then(|p| p.as_str()),
+                    _ => None,
+                });
+                if let Some(name) = name {
+                    names.insert(name.to_string());
+                }
+            }
+            for child in map.values() {
+                collect_import_values(child, names);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_import_values(item, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn substitute_stack_name(template: &str, stack: &str) -> String {
+    template.replace("${AWS::StackName}", stack)
+}
+
+fn stack_name_from_source(source: &str) -> String {
+    let file = source.rsplit(['/', '\\']).next().unwrap_or(source);
+    file.split('.').next().unwrap_or(file).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifact::{ArtifactFormat, ArtifactMetadata, ArtifactOutput, ArtifactResource};
+    use crate::engines::mapping::graph_types::GraphNode;
+    use serde_json::json;
+
+    fn graph_of(ids: &[(&str, &str)]) -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        for (id, resource_type) in ids {
+            graph.add_node(GraphNode::new_resource(
+                id.to_string(),
+                resource_type.to_string(),
+                id.to_string(),
+            ));
+        }
+        graph
+    }
+
+    fn network_plan() -> Value {
+        json!({
+            "configuration": {
+                "root_module": {
+                    "outputs": {
+                        "vpc_id": {
+                            "expression": {"references": ["aws_vpc.main.id", "aws_vpc.main"]}
+                        }
+                    },
+                    "resources": [
+                        {"address": "aws_vpc.main", "mode": "managed", "type": "aws_vpc", "name": "main"}
+                    ]
+                }
+            }
+        })
+    }
+
+    fn app_plan(data_source: &str, workspace: &str) -> Value {
+        json!({
+            "configuration": {
+                "root_module": {
+                    "resources": [
+                        {
+                            "address": format!("data.terraform_remote_state.{}", data_source),
+                            "mode": "data",
+                            "type": "terraform_remote_state",
+                            "name": data_source,
+                            "expressions": {"workspace": {"constant_value": workspace}}
+                        },
+                        {
+                            "address": "aws_instance.web",
+                            "mode": "managed",
+                            "type": "aws_instance",
+                            "name": "web",
+                            "expressions": {
+                                "subnet_id": {
+                                    "references": [
+                                        format!("data.terraform_remote_state.{}.outputs.vpc_id", data_source),
+                                        format!("data.terraform_remote_state.{}.outputs", data_source),
+                                        format!("data.terraform_remote_state.{}", data_source)
+                                    ]
+                                }
+                            }
+                        }
+                    ]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_resolves_terraform_remote_state() {
+        let mut resolver = ReferenceResolver::new();
+        resolver.add_terraform_plan(
+            "network",
+            "network.json",
+            &network_plan(),
+            graph_of(&[("aws_vpc_main", "aws_vpc")]),
+        );
+        resolver.add_terraform_plan(
+            "app",
+            "app.json",
+            &app_plan("network", "default"),
+            graph_of(&[("aws_instance_web", "aws_instance")]),
+        );
+
+        let resolved = resolver.resolve();
+        assert!(resolved.unresolved.is_empty());
+
+        let edges = resolved.graph.edges_from("app::aws_instance_web");
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].to, "network::aws_vpc_main");
+        assert_eq!(edges[0].relationship, EdgeType::CrossStack);
+
+        let metadata = &resolved.graph.metadata;
+        assert_eq!(metadata.stacks.len(), 2);
+        assert_eq!(
+            metadata.cross_stack_references[0].via,
+            "data.terraform_remote_state.network.outputs.vpc_id"
+        );
+    }
+
+    #[test]
+    fn test_remote_state_matched_by_workspace_or_alias() {
+        let mut by_workspace = ReferenceResolver::new();
+        by_workspace.add_terraform_plan(
+            "network",
+            "network.json",
+            &network_plan(),
+            graph_of(&[("aws_vpc_main", "aws_vpc")]),
+        );
+        by_workspace.add_terraform_plan(
+            "app",
+            "app.json",
+            &app_plan("shared", "network"),
+            graph_of(&[("aws_instance_web", "aws_instance")]),
+        );
+        assert!(by_workspace.resolve().unresolved.is_empty());
+
+        let mut unmatched = ReferenceResolver::new();
+        unmatched.add_terraform_plan(
+            "network",
+            "network.json",
+            &network_plan(),
+            graph_of(&[("aws_vpc_main", "aws_vpc")]),
+        );
+        unmatched.add_terraform_plan(
+            "app",
+            "app.json",
+            &app_plan("core", "default"),
+            graph_of(&[("aws_instance_web", "aws_instance")]),
+        );
+        assert_eq!(unmatched.resolve().unresolved.len(), 1);
+
+        let mut aliased = ReferenceResolver::new().with_remote_state_alias("core", "network");
+        aliased.add_terraform_plan(
+            "network",
+            "network.json",
+            &network_plan(),
+            graph_of(&[("aws_vpc_main", "aws_vpc")]),
+        );
+        aliased.add_terraform_plan(
+            "app",
+            "app.json",
+            &app_plan("core", "default"),
+            graph_of(&[("aws_instance_web", "aws_instance")]),
+        );
+        assert!(aliased.resolve().unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_resolves_cloudformation_exports() {
+        let metadata = |name: &str| ArtifactMetadata {
+            source: format!("{}.template.json", name),
+            version: None,
+            stack_name: Some(name.to_string()),
+            region: None,
+            tags: HashMap::new(),
+        };
+
+        let mut network = Artifact::new(ArtifactFormat::Cdk, metadata("Network"));
+        network.add_resource(ArtifactResource {
+            id: "Vpc".to_string(),
+            resource_type: "AWS::EC2::VPC".to_string(),
+            properties: HashMap::new(),
+            depends_on: Vec::new(),
+            metadata: HashMap::new(),
+        });
+        network.outputs.insert(
+            "VpcId".to_string(),
+            ArtifactOutput {
+                value: json!({"Ref": "Vpc"}),
+                description: None,
+                export: true,
+                export_name: Some("${AWS::StackName}-VpcId".to_string()),
+            },
+        );
+
+        let mut app = Artifact::new(ArtifactFormat::Cdk, metadata("App"));
+        app.add_resource(ArtifactResource {
+            id: "Web".to_string(),
+            resource_type: "AWS::EC2::Instance".to_string(),
+            properties: HashMap::from([(
+                "SubnetId".to_string(),
+                json!({"Fn::ImportValue": "Network-VpcId"}),
+            )]),
+            depends_on: Vec::new(),
+            metadata: HashMap::new(),
+        });
+
+        let mut resolver = ReferenceResolver::new();
+        resolver.add_artifact(&network, graph_of(&[("Vpc", "aws_vpc")]));
+        resolver.add_artifact(&app, graph_of(&[("Web", "aws_instance")]));
+
+        let resolved = resolver.resolve();
+        assert!(resolved.unresolved.is_empty());
+        let reference = &resolved.graph.metadata.cross_stack_references[0];
+        assert_eq!(reference.from, "App::Web");
+        assert_eq!(reference.to, "Network::Vpc");
+        assert_eq!(reference.via, "Fn::ImportValue: Network-VpcId");
+        assert_eq!(
+            resolved
+                .graph
+                .find_node("App::Web")
+                .unwrap()
+                .module
+                .as_deref(),
+            Some("App")
+        );
+    }
+}
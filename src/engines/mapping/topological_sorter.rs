@@ -0,0 +1,208 @@
+// Deterministic topological ordering for dependency graphs (Kahn's algorithm)
+
+use super::cycle_detector::{CycleDetector, CycleType};
+use super::graph_types::DependencyGraph;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Direction of the topological order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Edge sources before targets (dependents before their dependencies)
+    Forward,
+
+    /// Dependencies before dependents (apply order)
+    Reverse,
+}
+
+/// Returned when the graph contains cycles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// Cycles preventing a total order
+    pub cycles: Vec<Vec<String>>,
+
+    /// Nodes that could be ordered before the cycles were reached
+    pub sorted: Vec<String>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cycles: Vec<String> = self.cycles.iter().map(|c| format_cycle(c)).collect();
+        write!(f, "Dependency cycle(s) detected: {}", cycles.join("; "))
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Format a cycle as `a -> b -> a`
+pub fn format_cycle(cycle: &[String]) -> String {
+    let mut parts: Vec<&str> = cycle.iter().map(|s| s.as_str()).collect();
+    if let Some(first) = cycle.first() {
+        parts.push(first);
+    }
+    parts.join(" -> ")
+}
+
+/// Topological sorter. Ties are broken by node ID so output is stable
+/// across runs and input orderings.
+#[derive(Debug, Clone, Default)]
+pub struct TopologicalSorter {
+    adjacency: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl TopologicalSorter {
+    /// Create an empty sorter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build sorter from a dependency graph
+    pub fn from_graph(graph: &DependencyGraph) -> Self {
+        let mut sorter = Self::new();
+        for node in &graph.nodes {
+            sorter.add_node(&node.id);
+        }
+        for edge in &graph.edges {
+            sorter.add_edge(&edge.from, &edge.to);
+        }
+        sorter
+    }
+
+    /// Add a node
+    pub fn add_node(&mut self, id: &str) {
+        self.adjacency.entry(id.to_string()).or_default();
+    }
+
+    /// Add a directed edge, creating missing endpoints
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        self.add_node(to);
+        self.adjacency
+            .entry(from.to_string())
+            .or_default()
+            .insert(to.to_string());
+    }
+
+    /// Sort all nodes, failing if the graph has cycles
+    pub fn sort(&self, order: SortOrder) -> Result<Vec<String>, CycleError> {
+        let mut sorted = self.kahn();
+
+        if sorted.len() < self.adjacency.len() {
+            let mut detector = CycleDetector::new();
+            for (from, targets) in &self.adjacency {
+                for to in targets {
+                    detector.add_edge(from, to);
+                }
+            }
+            if order == SortOrder::Reverse {
+                sorted.reverse();
+            }
+            return Err(CycleError {
+                cycles: detector.detect(CycleType::All),
+                sorted,
+            });
+        }
+
+        if order == SortOrder::Reverse {
+            sorted.reverse();
+        }
+        Ok(sorted)
+    }
+
+    /// Sort nodes, appending nodes that are on or behind a cycle in ID order
+    pub fn sort_lenient(&self, order: SortOrder) -> Vec<String> {
+        match self.sort(order) {
+            Ok(sorted) => sorted,
+            Err(err) => {
+                let placed: BTreeSet<&String> = err.sorted.iter().collect();
+                let remaining: Vec<String> = self
+                    .adjacency
+                    .keys()
+                    .filter(|id| !placed.contains(id))
+                    .cloned()
+                    .collect();
+                let mut result = err.sorted.clone();
+                result.extend(remaining);
+                result
+            }
+        }
+    }
+
+    /// Kahn's algorithm in forward order; returns only the acyclic prefix
+    fn kahn(&self) -> Vec<String> {
+        let mut in_degree: BTreeMap<&String, usize> =
+            self.adjacency.keys().map(|id| (id, 0)).collect();
+        for targets in self.adjacency.values() {
+            for to in targets {
+                if let Some(degree) = in_degree.get_mut(to) {
+                    *degree += 1;
+                }
+            }
+        }
+
+        let mut ready: BTreeSet<&String> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut sorted = Vec::with_capacity(self.adjacency.len());
+
+        while let Some(id) = ready.pop_first() {
+            sorted.push(id.clone());
+            for to in &self.adjacency[id] {
+                if let Some(degree) = in_degree.get_mut(to) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        ready.insert(to);
+                    }
+                }
+            }
+        }
+
+        sorted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_is_deterministic() {
+        let mut sorter = TopologicalSorter::new();
+        sorter.add_edge("web", "subnet");
+        sorter.add_edge("subnet", "vpc");
+        sorter.add_edge("db", "subnet");
+
+        assert_eq!(
+            sorter.sort(SortOrder::Forward).unwrap(),
+            vec!["db", "web", "subnet", "vpc"]
+        );
+        assert_eq!(
+            sorter.sort(SortOrder::Reverse).unwrap(),
+            vec!["vpc", "subnet", "web", "db"]
+        );
+    }
+
+    #[test]
+    fn test_sort_reports_cycle() {
+        let mut sorter = TopologicalSorter::new();
+        sorter.add_edge("root", "a");
+        sorter.add_edge("a", "b");
+        sorter.add_edge("b", "a");
+
+        let err = sorter.sort(SortOrder::Forward).unwrap_err();
+        assert_eq!(err.sorted, vec!["root"]);
+        assert_eq!(err.cycles, vec![vec!["a".to_string(), "b".to_string()]]);
+        assert!(err.to_string().contains("a -> b -> a"));
+    }
+
+    #[test]
+    fn test_sort_lenient_includes_all_nodes() {
+        let mut sorter = TopologicalSorter::new();
+        sorter.add_edge("b", "a");
+        sorter.add_edge("a", "b");
+        sorter.add_node("c");
+
+        assert_eq!(sorter.sort_lenient(SortOrder::Forward), vec!["c", "a", "b"]);
+    }
+}
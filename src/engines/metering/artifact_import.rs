@@ -0,0 +1,351 @@
+// Import CI-produced PR usage artifacts into PR usage trackers
+
+use crate::engines::metering::pr_tracker::{CiUsageTracker, PrStatus, PrUsageSummary};
+use crate::engines::metering::usage_meter::UsageEvent;
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Usage artifact uploaded by one CI run of CostPilot on a PR
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrUsageArtifact {
+    /// CI run identifier (e.g. GitHub Actions `run_id`)
+    #[serde(deserialize_with = "string_or_number")]
+    pub run_id: String,
+
+    /// Run attempt; re-runs share a run id and the latest attempt wins
+    #[serde(default)]
+    pub run_attempt: Option<u32>,
+
+    /// Repository identifier (e.g. "org/repo")
+    pub repository: String,
+
+    /// PR number
+    pub pr_number: u32,
+
+    /// PR author
+    #[serde(default)]
+    pub author: Option<String>,
+
+    /// PR title
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// Head branch
+    #[serde(default)]
+    pub branch: Option<String>,
+
+    /// PR status at the time of the run
+    #[serde(default)]
+    pub status: Option<PrStatus>,
+
+    /// Usage events recorded during the run
+    pub events: Vec<UsageEvent>,
+}
+
+/// Outcome of importing a directory of artifacts
+#[derive(Debug, Default)]
+pub struct ArtifactImportReport {
+    /// Trackers by repository, with imported runs merged in
+    pub trackers: BTreeMap<String, CiUsageTracker>,
+
+    /// Runs merged into trackers
+    pub runs_imported: usize,
+
+    /// Artifacts skipped because their run was already imported
+    pub duplicates_skipped: usize,
+
+    /// Files that could not be read or parsed, with the reason
+    pub invalid_files: Vec<(PathBuf, String)>,
+}
+
+impl ArtifactImportReport {
+    /// PR summaries across all imported repositories, sorted by repository and PR
+    pub fn summaries(&self, price_per_resource: f64) -> Vec<PrUsageSummary> {
+        let mut summaries: Vec<PrUsageSummary> = self
+            .trackers
+            .values()
+            .flat_map(|tracker| {
+                tracker
+                    .pr_numbers()
+                    .into_iter()
+                    .filter_map(|pr| tracker.get_pr_summary(pr, price_per_resource).ok())
+            })
+            .collect();
+        summaries.sort_by(|a, b| {
+            a.repository
+                .cmp(&b.repository)
+                .then(a.pr_number.cmp(&b.pr_number))
+        });
+        summaries
+    }
+}
+
+/// Reads a directory of usage artifacts (as downloaded from CI) and merges
+/// them into per-repository trackers. Purely local; no network access.
+pub struct ArtifactImporter {
+    trackers: BTreeMap<String, CiUsageTracker>,
+}
+
+impl ArtifactImporter {
+    /// Create importer with no existing trackers
+    pub fn new() -> Self {
+        Self {
+            trackers: BTreeMap::new(),
+        }
+    }
+
+    /// Seed importer with an existing tracker so previously imported runs are skipped
+    pub fn with_tracker(mut self, tracker: CiUsageTracker) -> Self {
+        self.trackers
+            .insert(tracker.repository().to_string(), tracker);
+        self
+    }
+
+    /// Import every `*.json` file under `dir` (recursively)
+    pub fn import_dir(self, dir: &Path) -> Result<ArtifactImportReport> {
+        if !dir.is_dir() {
+            return Err(CostPilotError::io_error(format!(
+                "Artifact directory not found: {}",
+                dir.display()
+            )));
+        }
+
+        let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        files.sort();
+
+        let mut artifacts = Vec::new();
+        let mut invalid_files = Vec::new();
+        for path in files {
+            match read_artifact(&path) {
+                Ok(artifact) => artifacts.push(artifact),
+                Err(reason) => invalid_files.push((path, reason)),
+            }
+        }
+
+        let mut report = self.import(artifacts);
+        report.invalid_files = invalid_files;
+        Ok(report)
+    }
+
+    /// Merge already-parsed artifacts, deduplicating by run id
+    pub fn import(mut self, artifacts: Vec<PrUsageArtifact>) -> ArtifactImportReport {
+        let total = artifacts.len();
+
+        // Keep the latest attempt of each run
+        let mut latest: HashMap<(String, String), PrUsageArtifact> = HashMap::new();
+        for artifact in artifacts {
+            let key = (artifact.repository.clone(), artifact.run_id.clone());
+            match latest.get(&key) {
+                Some(existing) if existing.run_attempt >= artifact.run_attempt => {}
+                _ => {
+                    latest.insert(key, artifact);
+                }
+            }
+        }
+
+        let mut runs: Vec<PrUsageArtifact> = latest.into_values().collect();
+        runs.sort_by(|a, b| {
+            let first_event =
+                |artifact: &PrUsageArtifact| artifact.events.iter().map(|e| e.timestamp).min();
+            first_event(a)
+                .cmp(&first_event(b))
+                .then_with(|| a.run_id.cmp(&b.run_id))
+        });
+
+        let mut runs_imported = 0;
+        for artifact in runs {
+            let tracker = self
+                .trackers
+                .entry(artifact.repository.clone())
+                .or_insert_with(|| CiUsageTracker::new(artifact.repository.clone()));
+            if tracker.import_artifact(artifact) {
+                runs_imported += 1;
+            }
+        }
+
+        ArtifactImportReport {
+            trackers: self.trackers,
+            runs_imported,
+            duplicates_skipped: total - runs_imported,
+            invalid_files: Vec::new(),
+        }
+    }
+}
+
+impl Default for ArtifactImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CiUsageTracker {
+    /// Merge a CI run artifact; returns false if the run was already imported
+    pub fn import_artifact(&mut self, artifact: PrUsageArtifact) -> bool {
+        if !self.mark_run_imported(&artifact.run_id) {
+            return false;
+        }
+
+        let pr_number = artifact.pr_number;
+        if !self.is_tracking(pr_number) {
+            let branch = artifact
+                .branch
+                .or_else(|| {
+                    artifact
+                        .events
+                        .iter()
+                        .find_map(|e| e.context.branch.clone())
+                })
+                .unwrap_or_default();
+            let author = artifact
+                .author
+                .or_else(|| {
+                    artifact
+                        .events
+                        .first()
+                        .map(|e| e.attribution.user_id.clone())
+                })
+                .unwrap_or_default();
+            // Cannot fail: tracking a new PR only inserts
+            let _ = self.track_pr(
+                pr_number,
+                author,
+                artifact.title.unwrap_or_default(),
+                branch,
+            );
+        }
+
+        for event in artifact.events {
+            let _ = self.record_pr_event(pr_number, event);
+        }
+        if let Some(status) = artifact.status {
+            let _ = self.update_pr_status(pr_number, status);
+        }
+
+        true
+    }
+}
+
+fn read_artifact(path: &Path) -> std::result::Result<PrUsageArtifact, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("read failed: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("invalid usage artifact: {}", e))
+}
+
+fn string_or_number<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RunId {
+        Text(String),
+        Number(u64),
+    }
+
+    Ok(match RunId::deserialize(deserializer)? {
+        RunId::Text(s) => s,
+        RunId::Number(n) => n.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn artifact(run_id: u64, attempt: u32, pr: u32, resources: u32) -> serde_json::Value {
+        json!({
+            "run_id": run_id,
+            "run_attempt": attempt,
+            "repository": "acme/infra",
+            "pr_number": pr,
+            "branch": "feature/x",
+            "events": [{
+                "event_id": format!("{}-{}", run_id, attempt),
+                "timestamp": 1000 + run_id,
+                "event_type": "PlanAnalysis",
+                "attribution": {
+                    "user_id": "alice",
+                    "team_id": "platform",
+                    "org_id": null,
+                    "cost_center": null,
+                    "project_id": "infra"
+                },
+                "resources_analyzed": resources,
+                "cost_impact": 250.0,
+                "duration_ms": 1200,
+                "context": {
+                    "repository": "acme/infra",
+                    "branch": "feature/x",
+                    "commit": "abc123",
+                    "pr_number": pr,
+                    "ci_system": "github-actions",
+                    "environment": null
+                },
+                "metadata": {}
+            }]
+        })
+    }
+
+    fn write(dir: &Path, name: &str, value: &serde_json::Value) {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, value.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_import_dir_merges_and_deduplicates() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "run-1/usage.json", &artifact(1, 1, 42, 100));
+        write(
+            dir.path(),
+            "run-1-copy/usage.json",
+            &artifact(1, 1, 42, 100),
+        );
+        write(dir.path(), "run-2/usage.json", &artifact(2, 1, 42, 50));
+        write(
+            dir.path(),
+            "run-2-retry/usage.json",
+            &artifact(2, 2, 42, 60),
+        );
+        write(dir.path(), "run-3/usage.json", &artifact(3, 1, 7, 10));
+        std::fs::write(dir.path().join("broken.json"), "{").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let report = ArtifactImporter::new().import_dir(dir.path()).unwrap();
+
+        assert_eq!(report.runs_imported, 3);
+        assert_eq!(report.duplicates_skipped, 2);
+        assert_eq!(report.invalid_files.len(), 1);
+
+        let summaries = report.summaries(0.01);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].pr_number, 7);
+        assert_eq!(summaries[1].pr_number, 42);
+        // Run 1 (100) + latest attempt of run 2 (60)
+        assert_eq!(summaries[1].resources_analyzed, 160);
+        assert_eq!(summaries[1].scan_count, 2);
+    }
+
+    #[test]
+    fn test_previously_imported_runs_are_skipped() {
+        let first: PrUsageArtifact = serde_json::from_value(artifact(1, 1, 42, 100)).unwrap();
+        let report = ArtifactImporter::new().import(vec![first.clone()]);
+        let tracker = report.trackers["acme/infra"].clone();
+
+        let report = ArtifactImporter::new()
+            .with_tracker(tracker)
+            .import(vec![first]);
+        assert_eq!(report.runs_imported, 0);
+        assert_eq!(report.duplicates_skipped, 1);
+        assert_eq!(report.summaries(0.01)[0].resources_analyzed, 100);
+    }
+}
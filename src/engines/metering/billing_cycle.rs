@@ -0,0 +1,176 @@
+// Billing cycles and period boundaries for usage rollups
+
+use chrono::{DateTime, Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// How usage is bucketed into billing periods
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BillingCycle {
+    /// 1st of the month through the last day of the month (UTC)
+    CalendarMonth,
+
+    /// Monthly cycle starting on a given day of the month (clamped to month length)
+    AnchoredMonth { day: u32 },
+
+    /// Fixed-length cycle of `days` days starting at `anchor` (Unix epoch)
+    FixedDays { anchor: u64, days: u32 },
+}
+
+/// A single billing period; `end` is inclusive like the rest of the meter API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BillingPeriod {
+    pub start: u64,
+    pub end: u64,
+    /// Human-readable label (e.g. "2024-01" or "2024-01-15/2024-02-14")
+    pub label: String,
+}
+
+impl BillingCycle {
+    /// Billing period containing `timestamp`
+    pub fn period_containing(&self, timestamp: u64) -> BillingPeriod {
+        let (start, next) = match *self {
+            BillingCycle::CalendarMonth => month_bounds(timestamp, 1),
+            BillingCycle::AnchoredMonth { day } => month_bounds(timestamp, day.max(1)),
+            BillingCycle::FixedDays { anchor, days } => {
+                let length = days.max(1) as u64 * SECONDS_PER_DAY;
+                let offset = timestamp.saturating_sub(anchor) / length;
+                let start = anchor + offset * length;
+                (start, start + length)
+            }
+        };
+
+        let end = next - 1;
+        let label = match self {
+            BillingCycle::CalendarMonth => format_date(start, "%Y-%m"),
+            _ => format!(
+                "{}/{}",
+                format_date(start, "%Y-%m-%d"),
+                format_date(end, "%Y-%m-%d")
+            ),
+        };
+
+        BillingPeriod { start, end, label }
+    }
+
+    /// All billing periods overlapping `[start, end]`, in chronological order
+    pub fn periods(&self, start: u64, end: u64) -> Vec<BillingPeriod> {
+        let mut periods = Vec::new();
+        let mut cursor = start;
+
+        while cursor <= end {
+            let period = self.period_containing(cursor);
+            cursor = period.end + 1;
+            periods.push(period);
+        }
+
+        periods
+    }
+}
+
+/// Start of the cycle containing `timestamp` and start of the next cycle,
+/// for monthly cycles beginning on `anchor_day`
+fn month_bounds(timestamp: u64, anchor_day: u32) -> (u64, u64) {
+    let date = DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|dt| dt.date_naive())
+        .unwrap_or_default();
+
+    let (mut year, mut month) = (date.year(), date.month());
+    if date.day() < anchor_day.min(days_in_month(year, month)) {
+        (year, month) = previous_month(year, month);
+    }
+
+    let (next_year, next_month) = next_month(year, month);
+    (
+        cycle_start(year, month, anchor_day),
+        cycle_start(next_year, next_month, anchor_day),
+    )
+}
+
+fn cycle_start(year: i32, month: u32, anchor_day: u32) -> u64 {
+    let day = anchor_day.min(days_in_month(year, month));
+    NaiveDate::from_ymd_opt(year, month, day)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc().timestamp().max(0) as u64)
+        .unwrap_or(0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = next_month(year, month);
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(28)
+}
+
+fn next_month(year: i32, month: u32) -> (i32, u32) {
+    if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    }
+}
+
+fn previous_month(year: i32, month: u32) -> (i32, u32) {
+    if month == 1 {
+        (year - 1, 12)
+    } else {
+        (year, month - 1)
+    }
+}
+
+fn format_date(timestamp: u64, fmt: &str) -> String {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|dt| dt.format(fmt).to_string())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JAN_1_2024: u64 = 1_704_067_200;
+    const FEB_1_2024: u64 = 1_706_745_600;
+    const MAR_1_2024: u64 = 1_709_251_200;
+
+    #[test]
+    fn test_calendar_month_periods() {
+        let periods = BillingCycle::CalendarMonth.periods(JAN_1_2024 + 10 * 86_400, MAR_1_2024);
+
+        assert_eq!(periods.len(), 3);
+        assert_eq!(periods[0].start, JAN_1_2024);
+        assert_eq!(periods[0].end, FEB_1_2024 - 1);
+        assert_eq!(periods[0].label, "2024-01");
+        assert_eq!(periods[1].label, "2024-02");
+        assert_eq!(periods[2].start, MAR_1_2024);
+    }
+
+    #[test]
+    fn test_anchored_month_clamps_to_month_length() {
+        let cycle = BillingCycle::AnchoredMonth { day: 31 };
+
+        // Feb 10 falls in the cycle that started Jan 31
+        let period = cycle.period_containing(FEB_1_2024 + 9 * 86_400);
+        assert_eq!(period.start, FEB_1_2024 - 86_400);
+        assert_eq!(period.label, "2024-01-31/2024-02-28");
+
+        // Feb 29 starts the next cycle (February has no 31st)
+        let period = cycle.period_containing(MAR_1_2024 - 86_400);
+        assert_eq!(period.start, MAR_1_2024 - 86_400);
+    }
+
+    #[test]
+    fn test_fixed_day_cycles() {
+        let cycle = BillingCycle::FixedDays {
+            anchor: JAN_1_2024,
+            days: 14,
+        };
+        let periods = cycle.periods(JAN_1_2024, JAN_1_2024 + 20 * 86_400);
+
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[1].start, JAN_1_2024 + 14 * 86_400);
+        assert_eq!(periods[1].label, "2024-01-15/2024-01-28");
+    }
+}
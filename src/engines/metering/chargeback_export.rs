@@ -0,0 +1,375 @@
+// Chargeback export to CSV and Parquet for finance/BI ingestion
+
+use crate::engines::metering::chargeback::ChargebackReport;
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Stable column schema shared by all chargeback exports
+pub const CHARGEBACK_COLUMNS: [&str; 7] = [
+    "period", "team", "project", "service", "amount", "currency", "tags",
+];
+
+/// Service name recorded for CostPilot usage charges
+pub const CHARGEBACK_SERVICE: &str = "costpilot";
+
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChargebackExportFormat {
+    Csv,
+    Parquet,
+}
+
+impl std::str::FromStr for ChargebackExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(format!("Unknown chargeback export format: {}", s)),
+        }
+    }
+}
+
+/// One line of a chargeback export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChargebackRow {
+    /// Billing period as an ISO 8601 interval (`YYYY-MM-DD/YYYY-MM-DD`)
+    pub period: String,
+    pub team: String,
+    /// Empty for charges not attributed to a project
+    pub project: String,
+    pub service: String,
+    pub amount: f64,
+    pub currency: String,
+    /// Tags as a JSON object with sorted keys
+    pub tags: String,
+}
+
+/// Flattens a `ChargebackReport` into rows with a stable column schema
+pub struct ChargebackExporter {
+    currency: String,
+}
+
+impl ChargebackExporter {
+    /// Create exporter with USD amounts
+    pub fn new() -> Self {
+        Self {
+            currency: "USD".to_string(),
+        }
+    }
+
+    pub fn with_currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = currency.into();
+        self
+    }
+
+    /// One row per team project; any team charge not covered by projects
+    /// is emitted as a row with an empty project
+    pub fn rows(&self, report: &ChargebackReport) -> Vec<ChargebackRow> {
+        let period = format_period(report.period_start, report.period_end);
+        let mut rows = Vec::new();
+
+        for team in &report.team_charges {
+            let mut tags = BTreeMap::new();
+            tags.insert("org_id", report.org_id.as_str());
+            tags.insert("team_id", team.team_id.as_str());
+            if let Some(cost_center) = &team.cost_center {
+                tags.insert("cost_center", cost_center.as_str());
+            }
+            let tags = serde_json::to_string(&tags).unwrap_or_default();
+
+            let row = |project: &str, amount: f64| ChargebackRow {
+                period: period.clone(),
+                team: team.team_name.clone(),
+                project: project.to_string(),
+                service: CHARGEBACK_SERVICE.to_string(),
+                amount,
+                currency: self.currency.clone(),
+                tags: tags.clone(),
+            };
+
+            for project in &team.top_projects {
+                rows.push(row(&project.project_name, project.charge));
+            }
+
+            let allocated: f64 = team.top_projects.iter().map(|p| p.charge).sum();
+            let remainder = team.charge - allocated;
+            if team.top_projects.is_empty() || remainder > 1e-9 {
+                rows.push(row("", remainder));
+            }
+        }
+
+        rows
+    }
+
+    /// Export report as CSV (RFC 4180 quoting)
+    pub fn to_csv(&self, report: &ChargebackReport) -> String {
+        let mut csv = CHARGEBACK_COLUMNS.join(",");
+        csv.push('\n');
+
+        for row in self.rows(report) {
+            let fields = [
+                escape_csv(&row.period),
+                escape_csv(&row.team),
+                escape_csv(&row.project),
+                escape_csv(&row.service),
+                format!("{:.2}", row.amount),
+                escape_csv(&row.currency),
+                escape_csv(&row.tags),
+            ];
+            csv.push_str(&fields.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    /// Export report as a Parquet file
+    pub fn write_parquet(&self, report: &ChargebackReport, path: &Path) -> Result<()> {
+        let file = std::fs::File::create(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to create {}: {}", path.display(), e))
+        })?;
+        self.to_parquet(report, file)
+    }
+
+    /// Write report as Parquet to any writer
+    pub fn to_parquet<W: std::io::Write + Send>(
+        &self,
+        report: &ChargebackReport,
+        writer: W,
+    ) -> Result<()> {
+        parquet_writer::write(&self.rows(report), writer)
+    }
+
+    /// Export report in the given format to a file
+    pub fn export(
+        &self,
+        report: &ChargebackReport,
+        format: ChargebackExportFormat,
+        path: &Path,
+    ) -> Result<()> {
+        match format {
+            ChargebackExportFormat::Csv => std::fs::write(path, self.to_csv(report)).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+            }),
+            ChargebackExportFormat::Parquet => self.write_parquet(report, path),
+        }
+    }
+}
+
+impl Default for ChargebackExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_period(start: u64, end: u64) -> String {
+    let date = |ts: u64| {
+        chrono::DateTime::from_timestamp(ts as i64, 0)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| ts.to_string())
+    };
+    format!("{}/{}", date(start), date(end))
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_writer {
+    use super::ChargebackRow;
+    use crate::engines::shared::error_model::{CostPilotError, Result};
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    const SCHEMA: &str = "
+        message chargeback {
+            REQUIRED BYTE_ARRAY period (UTF8);
+            REQUIRED BYTE_ARRAY team (UTF8);
+            REQUIRED BYTE_ARRAY project (UTF8);
+            REQUIRED BYTE_ARRAY service (UTF8);
+            REQUIRED DOUBLE amount;
+            REQUIRED BYTE_ARRAY currency (UTF8);
+            REQUIRED BYTE_ARRAY tags (UTF8);
+        }
+    ";
+
+    pub fn write<W: std::io::Write + Send>(rows: &[ChargebackRow], writer: W) -> Result<()> {
+        let err = |e: parquet::errors::ParquetError| {
+            CostPilotError::serialization_error(format!("Parquet export failed: {}", e))
+        };
+
+        let schema = Arc::new(parse_message_type(SCHEMA).map_err(err)?);
+        let props = Arc::new(WriterProperties::builder().build());
+        let mut file_writer = SerializedFileWriter::new(writer, schema, props).map_err(err)?;
+        let mut row_group = file_writer.next_row_group().map_err(err)?;
+
+        let strings = |field: fn(&ChargebackRow) -> &str| -> Vec<ByteArray> {
+            rows.iter().map(|r| ByteArray::from(field(r))).collect()
+        };
+        let mut column_index = 0;
+        while let Some(mut column) = row_group.next_column().map_err(err)? {
+            if column_index == 4 {
+                let amounts: Vec<f64> = rows.iter().map(|r| r.amount).collect();
+                column
+                    .typed::<DoubleType>()
+                    .write_batch(&amounts, None, None)
+                    .map_err(err)?;
+            } else {
+                let values = match column_index {
+                    0 => strings(|r| &r.period),
+                    1 => strings(|r| &r.team),
+                    2 => strings(|r| &r.project),
+                    3 => strings(|r| &r.service),
+                    5 => strings(|r| &r.currency),
+                    _ => strings(|r| &r.tags),
+                };
+                column
+                    .typed::<ByteArrayType>()
+                    .write_batch(&values, None, None)
+                    .map_err(err)?;
+            }
+            column.close().map_err(err)?;
+            column_index += 1;
+        }
+
+        row_group.close().map_err(err)?;
+        file_writer.close().map_err(err)?;
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+mod parquet_writer {
+    use super::ChargebackRow;
+    use crate::engines::shared::error_model::{CostPilotError, Result};
+
+    pub fn write<W: std::io::Write + Send>(_rows: &[ChargebackRow], _writer: W) -> Result<()> {
+        Err(
+            CostPilotError::config_error("Parquet export is not available in this build")
+                .with_hint("Rebuild CostPilot with the `parquet` feature enabled"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::metering::chargeback::{ProjectChargeback, TeamChargeback};
+    use std::collections::HashMap;
+
+    fn team(id: &str, charge: f64, projects: Vec<(&str, f64)>) -> TeamChargeback {
+        TeamChargeback {
+            team_id: id.to_string(),
+            team_name: format!("Team {}", id),
+            cost_center: Some("cc-42".to_string()),
+            charge,
+            percentage_of_org: 0.0,
+            resources_analyzed: 0,
+            events: 0,
+            value_delivered: 0.0,
+            roi: 0.0,
+            top_users: Vec::new(),
+            top_projects: projects
+                .into_iter()
+                .map(|(name, charge)| ProjectChargeback {
+                    project_id: name.to_string(),
+                    project_name: name.to_string(),
+                    resources_analyzed: 0,
+                    charge,
+                    cost_impact: 0.0,
+                })
+                .collect(),
+        }
+    }
+
+    fn report() -> ChargebackReport {
+        ChargebackReport {
+            period_start: 1_704_067_200, // 2024-01-01
+            period_end: 1_706_659_200,   // 2024-01-31
+            org_id: "org1".to_string(),
+            total_charge: 150.0,
+            team_charges: vec![
+                team(
+                    "payments",
+                    100.0,
+                    vec![("checkout", 60.0), ("ledger", 30.0)],
+                ),
+                team("search", 50.0, vec![]),
+            ],
+            cost_center_charges: HashMap::new(),
+            top_cost_drivers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_rows_cover_projects_and_remainder() {
+        let rows = ChargebackExporter::new().rows(&report());
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].period, "2024-01-01/2024-01-31");
+        assert_eq!(rows[0].project, "checkout");
+        assert_eq!(rows[2].project, "");
+        assert!((rows[2].amount - 10.0).abs() < 1e-9);
+        assert_eq!(rows[3].team, "Team search");
+        assert_eq!(rows[3].amount, 50.0);
+
+        let total: f64 = rows.iter().map(|r| r.amount).sum();
+        assert!((total - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_csv_has_stable_columns() {
+        let csv = ChargebackExporter::new()
+            .with_currency("EUR")
+            .to_csv(&report());
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("period,team,project,service,amount,currency,tags")
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                "2024-01-01/2024-01-31,Team payments,checkout,costpilot,60.00,EUR,\
+                 \"{\"\"cost_center\"\":\"\"cc-42\"\",\"\"org_id\"\":\"\"org1\"\",\"\"team_id\"\":\"\"payments\"\"}\""
+            )
+        );
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_round_trip() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        ChargebackExporter::new()
+            .write_parquet(&report(), file.path())
+            .unwrap();
+
+        let reader = SerializedFileReader::new(std::fs::File::open(file.path()).unwrap()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 4);
+
+        let columns: Vec<String> = metadata
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect();
+        assert_eq!(columns, CHARGEBACK_COLUMNS);
+    }
+}
@@ -1,17 +1,34 @@
 // Usage metering and attribution module
 
+pub mod artifact_import;
+pub mod billing_cycle;
 pub mod chargeback;
+pub mod chargeback_export;
 pub mod pr_tracker;
 pub mod usage_meter;
+pub mod usage_receipt;
 
 pub use usage_meter::{
-    Attribution, BillingExport, PricingModel, PricingTier, ProjectUsage, TeamUsageSummary,
-    UsageContext, UsageEvent, UsageEventType, UsageMeter, UsageMetrics, UserUsage,
+    Attribution, BillingExport, InvoiceLine, PricingModel, PricingTier, ProjectUsage,
+    TeamUsageSummary, UsageContext, UsageEvent, UsageEventType, UsageMeter, UsageMetrics,
+    UsageRollup, UserUsage, VolumeTier,
 };
 
+pub use usage_receipt::{
+    identity_hash, IdentityKind, ReceiptIdentity, UsageReceipt, RECEIPT_VERSION,
+};
+
+pub use artifact_import::{ArtifactImportReport, ArtifactImporter, PrUsageArtifact};
+
+pub use billing_cycle::{BillingCycle, BillingPeriod};
+
 pub use pr_tracker::{CiUsageTracker, PrStatus, PrUsageReport, PrUsageSummary, PrUsageTracker};
 
 pub use chargeback::{
     ChargebackReport, ChargebackReportBuilder, CostDriver, ProjectChargeback, TeamChargeback,
     UserChargeback,
 };
+
+pub use chargeback_export::{
+    ChargebackExportFormat, ChargebackExporter, ChargebackRow, CHARGEBACK_COLUMNS,
+};
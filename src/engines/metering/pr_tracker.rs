@@ -3,7 +3,7 @@
 use crate::engines::metering::usage_meter::{Attribution, UsageEvent, UsageEventType};
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 /// PR (Pull Request) usage tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,6 +100,10 @@ pub struct CiUsageTracker {
 
     /// Repository identifier
     repository: String,
+
+    /// CI run ids already merged from usage artifacts
+    #[serde(default)]
+    imported_runs: BTreeSet<String>,
 }
 
 impl CiUsageTracker {
@@ -108,9 +112,32 @@ impl CiUsageTracker {
         Self {
             prs: HashMap::new(),
             repository,
+            imported_runs: BTreeSet::new(),
         }
     }
 
+    /// Repository identifier
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    /// Whether a PR is being tracked
+    pub fn is_tracking(&self, pr_number: u32) -> bool {
+        self.prs.contains_key(&pr_number)
+    }
+
+    /// Tracked PR numbers in ascending order
+    pub fn pr_numbers(&self) -> Vec<u32> {
+        let mut numbers: Vec<u32> = self.prs.keys().copied().collect();
+        numbers.sort_unstable();
+        numbers
+    }
+
+    /// Record a CI run as imported; returns false if it already was
+    pub(crate) fn mark_run_imported(&mut self, run_id: &str) -> bool {
+        self.imported_runs.insert(run_id.to_string())
+    }
+
     /// Start tracking a PR
     pub fn track_pr(
         &mut self,
@@ -1,6 +1,8 @@
 // Usage metering and attribution system for team chargeback and billing
 
+use crate::engines::metering::billing_cycle::{BillingCycle, BillingPeriod};
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::security::at_rest::StoreEncryption;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -206,6 +208,63 @@ pub struct PricingModel {
 
     /// Free tier included resources
     pub free_tier_resources: u32,
+
+    /// Graduated per-resource pricing applied after the free tier.
+    /// When empty, `price_per_resource` applies to all billable resources.
+    #[serde(default)]
+    pub volume_tiers: Vec<VolumeTier>,
+}
+
+/// Graduated pricing tier for billable resources
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeTier {
+    /// Upper bound of billable resources in this tier (None = unbounded)
+    pub up_to: Option<u32>,
+
+    /// Price per resource within this tier
+    pub price_per_resource: f64,
+}
+
+impl PricingModel {
+    /// Split billable resources into (quantity, unit price) bands
+    pub fn resource_bands(&self, billable_resources: u32) -> Vec<(u32, f64)> {
+        if self.volume_tiers.is_empty() {
+            return vec![(billable_resources, self.price_per_resource)];
+        }
+
+        let mut bands = Vec::new();
+        let mut lower = 0u32;
+        for tier in &self.volume_tiers {
+            if lower >= billable_resources {
+                break;
+            }
+            let upper = tier.up_to.unwrap_or(u32::MAX).min(billable_resources);
+            if upper > lower {
+                bands.push((upper - lower, tier.price_per_resource));
+                lower = upper;
+            }
+        }
+
+        // Resources beyond the last bounded tier use the last tier's price
+        if lower < billable_resources {
+            let price = self
+                .volume_tiers
+                .last()
+                .map(|t| t.price_per_resource)
+                .unwrap_or(self.price_per_resource);
+            bands.push((billable_resources - lower, price));
+        }
+
+        bands
+    }
+
+    /// Charge for billable resources with volume tiers applied
+    pub fn resource_charge(&self, billable_resources: u32) -> f64 {
+        self.resource_bands(billable_resources)
+            .iter()
+            .map(|(quantity, price)| *quantity as f64 * price)
+            .sum()
+    }
 }
 
 /// Pricing tiers
@@ -242,10 +301,96 @@ impl UsageMeter {
         }
     }
 
-    /// Load usage meter from file
-    pub fn load_from_file(_path: &std::path::Path, pricing: PricingModel) -> Result<Self> {
-        // Stub: return new meter for now
-        Ok(Self::new(pricing))
+    /// Pricing model used for charges
+    pub fn pricing(&self) -> &PricingModel {
+        &self.pricing
+    }
+
+    /// Load usage meter from an NDJSON event log (one `UsageEvent` per line),
+    /// decrypting entries with the project's storage encryption
+    pub fn load_from_file(path: &std::path::Path, pricing: PricingModel) -> Result<Self> {
+        Self::load_from_file_with(path, pricing, &StoreEncryption::from_project())
+    }
+
+    /// Load usage meter from an NDJSON event log, decrypting entries with `encryption`
+    pub fn load_from_file_with(
+        path: &std::path::Path,
+        pricing: PricingModel,
+        encryption: &StoreEncryption,
+    ) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!(
+                "Failed to read usage log {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let content = encryption.open(&content)?;
+
+        let mut meter = Self::new(pricing);
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event: UsageEvent = serde_json::from_str(line).map_err(|e| {
+                CostPilotError::new(
+                    "METER_002",
+                    ErrorCategory::InvalidInput,
+                    format!("Invalid usage event on line {}: {}", index + 1, e),
+                )
+            })?;
+            meter.events.push(event);
+        }
+        Ok(meter)
+    }
+
+    /// Append `event` to the NDJSON event log at `path`, encrypted when the
+    /// project turns on storage encryption
+    pub fn append_to_file(path: &std::path::Path, event: &UsageEvent) -> Result<()> {
+        Self::append_to_file_with(path, event, &StoreEncryption::from_project())
+    }
+
+    /// Append `event` to the NDJSON event log at `path`, sealed with `encryption`
+    pub fn append_to_file_with(
+        path: &std::path::Path,
+        event: &UsageEvent,
+        encryption: &StoreEncryption,
+    ) -> Result<()> {
+        use std::io::Write;
+
+        let line = serde_json::to_string(event).map_err(|e| {
+            CostPilotError::new(
+                "METER_003",
+                ErrorCategory::ValidationError,
+                format!("Failed to serialize usage event: {}", e),
+            )
+        })?;
+        let line = encryption.seal(&line)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .map_err(|e| {
+                CostPilotError::io_error(format!(
+                    "Failed to write usage log {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+    }
+
+    /// Recorded events with timestamps in `[start, end]`
+    pub fn events_between(&self, start: u64, end: u64) -> Vec<&UsageEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.timestamp >= start && e.timestamp <= end)
+            .collect()
     }
 
     /// Record usage event
@@ -398,7 +543,7 @@ impl UsageMeter {
         let billable_resources = resources.saturating_sub(self.pricing.free_tier_resources);
 
         // Calculate charge
-        let resource_charge = billable_resources as f64 * self.pricing.price_per_resource;
+        let resource_charge = self.pricing.resource_charge(billable_resources);
         let event_charge = events as f64 * self.pricing.price_per_scan;
         let total_charge = (resource_charge + event_charge).max(self.pricing.monthly_minimum);
 
@@ -442,6 +587,57 @@ impl UsageMeter {
             events: period_events,
         })
     }
+
+    /// Roll usage up into billing periods covering `[start, end]`
+    pub fn rollup(&self, cycle: BillingCycle, start: u64, end: u64) -> Result<Vec<UsageRollup>> {
+        cycle
+            .periods(start, end)
+            .into_iter()
+            .map(|period| {
+                let metrics = self.get_metrics(period.start, period.end);
+                let team_charges = self
+                    .export_billing_data(period.start, period.end)?
+                    .team_charges;
+                Ok(UsageRollup {
+                    period,
+                    metrics,
+                    team_charges,
+                })
+            })
+            .collect()
+    }
+
+    /// Roll usage up by calendar month
+    pub fn monthly_rollup(&self, start: u64, end: u64) -> Result<Vec<UsageRollup>> {
+        self.rollup(BillingCycle::CalendarMonth, start, end)
+    }
+}
+
+/// Usage and charges for one billing period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRollup {
+    pub period: BillingPeriod,
+    pub metrics: UsageMetrics,
+    pub team_charges: HashMap<String, f64>,
+}
+
+impl UsageRollup {
+    /// Total charge across all teams for the period
+    pub fn total_charge(&self) -> f64 {
+        self.team_charges.values().sum()
+    }
+}
+
+/// Invoice line item for a team
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvoiceLine {
+    pub team_id: String,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub description: String,
+    pub quantity: u32,
+    pub unit_price: f64,
+    pub amount: f64,
 }
 
 /// Billing export data
@@ -455,6 +651,84 @@ pub struct BillingExport {
     pub events: Vec<UsageEvent>,
 }
 
+impl BillingExport {
+    /// Per-team invoice line items with free tier, volume tiers and
+    /// monthly minimum applied. Line amounts for a team sum to its charge.
+    pub fn to_invoice_lines(&self, pricing: &PricingModel) -> Vec<InvoiceLine> {
+        let mut usage: HashMap<&str, (u32, u32)> = HashMap::new();
+        for event in &self.events {
+            if let Some(team_id) = &event.attribution.team_id {
+                let entry = usage.entry(team_id.as_str()).or_insert((0, 0));
+                entry.0 += event.resources_analyzed;
+                entry.1 += 1;
+            }
+        }
+
+        let mut teams: Vec<_> = usage.into_iter().collect();
+        teams.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut lines = Vec::new();
+        for (team_id, (resources, events)) in teams {
+            let line =
+                |description: String, quantity: u32, unit_price: f64, amount: f64| InvoiceLine {
+                    team_id: team_id.to_string(),
+                    period_start: self.period_start,
+                    period_end: self.period_end,
+                    description,
+                    quantity,
+                    unit_price,
+                    amount,
+                };
+
+            let free = resources.min(pricing.free_tier_resources);
+            if free > 0 {
+                lines.push(line(
+                    "Included free tier resources".to_string(),
+                    free,
+                    0.0,
+                    0.0,
+                ));
+            }
+
+            let mut subtotal = 0.0;
+            for (quantity, unit_price) in pricing.resource_bands(resources - free) {
+                if quantity == 0 {
+                    continue;
+                }
+                let amount = quantity as f64 * unit_price;
+                subtotal += amount;
+                lines.push(line(
+                    format!("Resources analyzed @ ${}", unit_price),
+                    quantity,
+                    unit_price,
+                    amount,
+                ));
+            }
+
+            let scan_amount = events as f64 * pricing.price_per_scan;
+            subtotal += scan_amount;
+            lines.push(line(
+                "Analysis events".to_string(),
+                events,
+                pricing.price_per_scan,
+                scan_amount,
+            ));
+
+            if subtotal < pricing.monthly_minimum {
+                let adjustment = pricing.monthly_minimum - subtotal;
+                lines.push(line(
+                    "Monthly minimum adjustment".to_string(),
+                    1,
+                    adjustment,
+                    adjustment,
+                ));
+            }
+        }
+
+        lines
+    }
+}
+
 impl Default for PricingModel {
     fn default() -> Self {
         // Default Pro tier pricing
@@ -465,6 +739,7 @@ impl Default for PricingModel {
             price_per_advanced: 0.10,  // $0.10 per advanced analysis
             monthly_minimum: 49.0,     // $49/month minimum
             free_tier_resources: 1000, // 1000 resources free
+            volume_tiers: Vec::new(),
         }
     }
 }
@@ -568,4 +843,143 @@ mod tests {
         let (_, charge) = meter.calculate_charge(10, 1);
         assert_eq!(charge, 49.0); // Below minimum, charged minimum
     }
+
+    fn event_at(team_id: &str, timestamp: u64, resources: u32) -> UsageEvent {
+        UsageEvent {
+            timestamp,
+            ..create_test_event("user1", Some(team_id), resources)
+        }
+    }
+
+    #[test]
+    fn test_volume_tiers() {
+        let pricing = PricingModel {
+            price_per_resource: 0.01,
+            volume_tiers: vec![
+                VolumeTier {
+                    up_to: Some(100),
+                    price_per_resource: 0.02,
+                },
+                VolumeTier {
+                    up_to: Some(300),
+                    price_per_resource: 0.01,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(pricing.resource_bands(50), vec![(50, 0.02)]);
+        assert_eq!(
+            pricing.resource_bands(500),
+            vec![(100, 0.02), (200, 0.01), (200, 0.01)]
+        );
+        assert!((pricing.resource_charge(500) - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_monthly_rollup() {
+        const JAN_1_2024: u64 = 1_704_067_200;
+        const FEB_1_2024: u64 = 1_706_745_600;
+
+        let mut meter = UsageMeter::new(PricingModel::default());
+        meter
+            .record_event(event_at("team1", JAN_1_2024 + 86_400, 100))
+            .unwrap();
+        meter
+            .record_event(event_at("team1", FEB_1_2024 + 86_400, 200))
+            .unwrap();
+        meter
+            .record_event(event_at("team2", FEB_1_2024 + 2 * 86_400, 300))
+            .unwrap();
+
+        let rollups = meter
+            .monthly_rollup(JAN_1_2024, FEB_1_2024 + 10 * 86_400)
+            .unwrap();
+
+        assert_eq!(rollups.len(), 2);
+        assert_eq!(rollups[0].period.label, "2024-01");
+        assert_eq!(rollups[0].metrics.total_resources, 100);
+        assert_eq!(rollups[1].metrics.total_resources, 500);
+        assert_eq!(rollups[1].team_charges.len(), 2);
+        assert_eq!(rollups[1].total_charge(), 98.0); // Two teams at the $49 minimum
+    }
+
+    #[test]
+    fn test_invoice_lines_match_team_charges() {
+        let pricing = PricingModel {
+            monthly_minimum: 5.0,
+            free_tier_resources: 100,
+            volume_tiers: vec![
+                VolumeTier {
+                    up_to: Some(1000),
+                    price_per_resource: 0.01,
+                },
+                VolumeTier {
+                    up_to: None,
+                    price_per_resource: 0.005,
+                },
+            ],
+            ..Default::default()
+        };
+        let mut meter = UsageMeter::new(pricing.clone());
+        meter
+            .record_event(create_test_event("user1", Some("team1"), 2100))
+            .unwrap();
+        meter
+            .record_event(create_test_event("user2", Some("team2"), 50))
+            .unwrap();
+
+        let export = meter.export_billing_data(0, 2000).unwrap();
+        let lines = export.to_invoice_lines(&pricing);
+
+        for (team_id, charge) in &export.team_charges {
+            let total: f64 = lines
+                .iter()
+                .filter(|l| &l.team_id == team_id)
+                .map(|l| l.amount)
+                .sum();
+            assert!(
+                (total - charge).abs() < 1e-9,
+                "{}: {} != {}",
+                team_id,
+                total,
+                charge
+            );
+        }
+
+        let team1: Vec<_> = lines.iter().filter(|l| l.team_id == "team1").collect();
+        assert_eq!(team1[0].quantity, 100); // Free tier
+        assert_eq!(team1[1].quantity, 1000);
+        assert_eq!(team1[2].quantity, 1000);
+        assert!(lines
+            .iter()
+            .any(|l| l.team_id == "team2" && l.description == "Monthly minimum adjustment"));
+    }
+
+    #[test]
+    fn test_encrypted_usage_log() {
+        use crate::security::at_rest::{is_encrypted, AtRestCipher};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("usage_events.ndjson");
+        let encryption = StoreEncryption::with_cipher(AtRestCipher::new([5; 32]), true);
+
+        UsageMeter::append_to_file_with(
+            &path,
+            &create_test_event("user1", None, 10),
+            &StoreEncryption::disabled(),
+        )
+        .unwrap();
+        UsageMeter::append_to_file_with(&path, &create_test_event("user2", None, 20), &encryption)
+            .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!is_encrypted(content.lines().next().unwrap()));
+        assert!(is_encrypted(content.lines().nth(1).unwrap()));
+        assert!(!content.contains("user2"));
+
+        let meter =
+            UsageMeter::load_from_file_with(&path, PricingModel::default(), &encryption).unwrap();
+        assert_eq!(meter.get_metrics(0, 2000).total_resources, 30);
+    }
 }
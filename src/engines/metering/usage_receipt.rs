@@ -0,0 +1,322 @@
+// Usage receipts - signed monthly seat summaries without phoning home
+//
+// CostPilot never sends usage anywhere. For per-seat licensing, customers
+// generate a receipt per billing month from the local usage log and email it
+// to the vendor. A receipt counts the distinct users and CI identities that
+// ran premium features; identities are salted hashes, so the vendor can count
+// seats and match them across months without learning who they are.
+//
+// Receipts are signed with an Ed25519 key derived from the license key. The
+// issuer, who knows the license key, derives the same key to check that a
+// receipt was produced for that license and has not been edited.
+
+use crate::engines::metering::billing_cycle::{BillingCycle, BillingPeriod};
+use crate::engines::metering::usage_meter::UsageEvent;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Receipt layout version
+pub const RECEIPT_VERSION: u32 = 1;
+
+/// Whether an identity is a person or an automated pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentityKind {
+    User,
+    Ci,
+}
+
+/// One distinct identity seen during the period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptIdentity {
+    /// SHA-256 of the identity salted with the license key
+    pub id_hash: String,
+    pub kind: IdentityKind,
+    pub events: u32,
+}
+
+/// Signed summary of premium usage for one billing month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageReceipt {
+    pub receipt_version: u32,
+    pub license_key: String,
+    pub period: BillingPeriod,
+    /// RFC 3339 time the receipt was generated
+    pub generated_at: String,
+    pub premium_events: u32,
+    /// Event count per event type
+    pub events_by_type: BTreeMap<String, u32>,
+    pub distinct_users: u32,
+    pub distinct_ci_identities: u32,
+    pub identities: Vec<ReceiptIdentity>,
+    /// Hex Ed25519 signature over the receipt with this field empty
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl UsageReceipt {
+    /// Summarize `events` that fall in the calendar month `month` (`YYYY-MM`)
+    pub fn for_month(
+        events: &[UsageEvent],
+        month: &str,
+        license_key: &str,
+        generated_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Self, String> {
+        let first_day = chrono::NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid month '{}', expected YYYY-MM", month))?;
+        let start = first_day
+            .and_hms_opt(0, 0, 0)
+            .map(|t| t.and_utc().timestamp().max(0) as u64)
+            .unwrap_or_default();
+        let period = BillingCycle::CalendarMonth.period_containing(start);
+        Ok(Self::for_period(events, period, license_key, generated_at))
+    }
+
+    /// Summarize `events` that fall in `period`; the receipt is unsigned
+    pub fn for_period(
+        events: &[UsageEvent],
+        period: BillingPeriod,
+        license_key: &str,
+        generated_at: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        let mut events_by_type = BTreeMap::new();
+        let mut identities: BTreeMap<String, (IdentityKind, u32)> = BTreeMap::new();
+        let mut premium_events = 0;
+
+        for event in events
+            .iter()
+            .filter(|e| e.timestamp >= period.start && e.timestamp <= period.end)
+        {
+            premium_events += 1;
+            *events_by_type
+                .entry(format!("{:?}", event.event_type))
+                .or_insert(0) += 1;
+            let kind = if event.context.ci_system.is_some() {
+                IdentityKind::Ci
+            } else {
+                IdentityKind::User
+            };
+            identities
+                .entry(identity_hash(license_key, &event.attribution.user_id))
+                .or_insert((kind, 0))
+                .1 += 1;
+        }
+
+        let identities: Vec<ReceiptIdentity> = identities
+            .into_iter()
+            .map(|(id_hash, (kind, events))| ReceiptIdentity {
+                id_hash,
+                kind,
+                events,
+            })
+            .collect();
+        let count = |kind: IdentityKind| identities.iter().filter(|i| i.kind == kind).count();
+
+        Self {
+            receipt_version: RECEIPT_VERSION,
+            license_key: license_key.to_string(),
+            period,
+            generated_at: generated_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            premium_events,
+            events_by_type,
+            distinct_users: count(IdentityKind::User) as u32,
+            distinct_ci_identities: count(IdentityKind::Ci) as u32,
+            identities,
+            signature: String::new(),
+        }
+    }
+
+    /// Canonical bytes covered by the signature
+    pub fn signing_payload(&self) -> Result<Vec<u8>, String> {
+        let mut unsigned = self.clone();
+        unsigned.signature.clear();
+        serde_json::to_vec(&unsigned).map_err(|e| format!("Failed to serialize receipt: {}", e))
+    }
+
+    /// Sign with the key derived from the receipt's license key
+    pub fn sign(&mut self) -> Result<(), String> {
+        let key = receipt_signing_key(&self.license_key);
+        let signature = key.sign(&self.signing_payload()?);
+        self.signature = hex::encode(signature.to_bytes());
+        Ok(())
+    }
+
+    /// Check the receipt was issued for `license_key` and is unmodified
+    pub fn verify(&self, license_key: &str) -> Result<(), String> {
+        if self.license_key != license_key {
+            return Err("Receipt was generated for a different license".to_string());
+        }
+        if self.receipt_version > RECEIPT_VERSION {
+            return Err(format!(
+                "Receipt version {} is newer than supported version {}",
+                self.receipt_version, RECEIPT_VERSION
+            ));
+        }
+        let bytes = hex::decode(&self.signature).map_err(|_| "Invalid receipt signature format")?;
+        let signature =
+            Signature::from_slice(&bytes).map_err(|_| "Invalid receipt signature format")?;
+        receipt_signing_key(license_key)
+            .verifying_key()
+            .verify(&self.signing_payload()?, &signature)
+            .map_err(|_| "Receipt signature verification failed".to_string())
+    }
+
+    /// Human-readable summary
+    pub fn format_text(&self) -> String {
+        let mut out = format!("Usage receipt for {}\n", self.period.label);
+        out.push_str(&format!("  License:           {}\n", self.license_key));
+        out.push_str(&format!("  Generated:         {}\n", self.generated_at));
+        out.push_str(&format!("  Premium events:    {}\n", self.premium_events));
+        out.push_str(&format!("  Distinct users:    {}\n", self.distinct_users));
+        out.push_str(&format!(
+            "  CI identities:     {}\n",
+            self.distinct_ci_identities
+        ));
+        for (event_type, count) in &self.events_by_type {
+            out.push_str(&format!("    {:<20} {}\n", event_type, count));
+        }
+        out
+    }
+}
+
+/// Salted hash of an identity, stable across receipts for the same license
+pub fn identity_hash(license_key: &str, identity: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(license_key.as_bytes());
+    hasher.update([0]);
+    hasher.update(identity.trim().to_lowercase().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+fn receipt_signing_key(license_key: &str) -> SigningKey {
+    let hk = Hkdf::<Sha256>::new(Some(b"costpilot-usage-receipt-v1"), license_key.as_bytes());
+    let mut seed = [0u8; 32];
+    hk.expand(b"ed25519-signing-key", &mut seed)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    SigningKey::from_bytes(&seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::metering::usage_meter::{Attribution, UsageContext, UsageEventType};
+    use std::collections::HashMap;
+
+    fn event(user: &str, timestamp: u64, ci: bool, event_type: UsageEventType) -> UsageEvent {
+        UsageEvent {
+            event_id: format!("{}-{}", user, timestamp),
+            timestamp,
+            event_type,
+            attribution: Attribution {
+                user_id: user.to_string(),
+                team_id: None,
+                org_id: None,
+                cost_center: None,
+                project_id: None,
+            },
+            resources_analyzed: 0,
+            cost_impact: 0.0,
+            duration_ms: 0,
+            context: UsageContext {
+                repository: "acme/infra".to_string(),
+                branch: None,
+                commit: None,
+                pr_number: None,
+                ci_system: ci.then(|| "github-actions".to_string()),
+                environment: None,
+            },
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn now() -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::parse_from_rfc3339("2025-04-02T08:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    }
+
+    #[test]
+    fn test_receipt_counts_distinct_identities_in_month() {
+        // 2025-03-10 and 2025-03-31T23:59:59; the last event is in April
+        let events = vec![
+            event(
+                "ana@acme.example",
+                1_741_564_800,
+                false,
+                UsageEventType::TrendAnalysis,
+            ),
+            event(
+                "ANA@acme.example",
+                1_741_651_200,
+                false,
+                UsageEventType::AutofixGeneration,
+            ),
+            event(
+                "bo@acme.example",
+                1_743_465_599,
+                false,
+                UsageEventType::TrendAnalysis,
+            ),
+            event(
+                "ci:github-actions:acme/infra",
+                1_742_000_000,
+                true,
+                UsageEventType::PlanAnalysis,
+            ),
+            event(
+                "cy@acme.example",
+                1_743_465_600,
+                false,
+                UsageEventType::TrendAnalysis,
+            ),
+        ];
+
+        let receipt = UsageReceipt::for_month(&events, "2025-03", "ENT-1", now()).unwrap();
+        assert_eq!(receipt.period.label, "2025-03");
+        assert_eq!(receipt.premium_events, 4);
+        assert_eq!(receipt.distinct_users, 2);
+        assert_eq!(receipt.distinct_ci_identities, 1);
+        assert_eq!(receipt.events_by_type["TrendAnalysis"], 2);
+        assert!(receipt
+            .identities
+            .iter()
+            .all(|i| !i.id_hash.contains("acme") && i.id_hash.len() == 64));
+        assert_eq!(
+            identity_hash("ENT-1", "ana@acme.example"),
+            identity_hash("ENT-1", " Ana@Acme.example")
+        );
+        assert_ne!(
+            identity_hash("ENT-1", "ana@acme.example"),
+            identity_hash("ENT-2", "ana@acme.example")
+        );
+
+        assert!(UsageReceipt::for_month(&events, "March", "ENT-1", now()).is_err());
+    }
+
+    #[test]
+    fn test_receipt_signature_binds_license_and_counts() {
+        let events = vec![event(
+            "ana@acme.example",
+            1_741_564_800,
+            false,
+            UsageEventType::DependencyMap,
+        )];
+        let mut receipt = UsageReceipt::for_month(&events, "2025-03", "ENT-1", now()).unwrap();
+        assert!(receipt.verify("ENT-1").is_err());
+
+        receipt.sign().unwrap();
+        receipt.verify("ENT-1").unwrap();
+        assert!(receipt.verify("ENT-2").is_err());
+
+        let json = serde_json::to_string(&receipt).unwrap();
+        let parsed: UsageReceipt = serde_json::from_str(&json).unwrap();
+        parsed.verify("ENT-1").unwrap();
+
+        let mut tampered = receipt.clone();
+        tampered.distinct_users = 0;
+        assert!(tampered.verify("ENT-1").is_err());
+    }
+}
@@ -5,14 +5,18 @@ pub mod autofix;
 pub mod autofix_safe;
 pub mod baselines;
 pub mod detection;
+pub mod diff;
 pub mod escrow;
 pub mod explain;
 pub mod grouping;
 pub mod mapping;
 pub mod metering;
+pub mod notify;
 pub mod performance;
+pub mod pipeline;
 pub mod policy;
 pub mod prediction;
+pub mod report;
 pub mod shared;
 pub mod slo;
 pub mod trend;
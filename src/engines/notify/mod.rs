@@ -0,0 +1,283 @@
+// Chat notification payloads - Slack Block Kit and Teams Adaptive Cards
+//
+// Results are rendered to the JSON a Slack or Microsoft Teams incoming
+// webhook accepts, and written out for CI to post. CostPilot never sends
+// them itself, so no network access or credentials are involved:
+//
+//   costpilot scan plan.json --output-format slack > slack.json
+//   curl -X POST -H 'Content-Type: application/json' -d @slack.json "$SLACK_WEBHOOK_URL"
+
+pub mod slack;
+pub mod teams;
+
+pub use slack::render_slack;
+pub use teams::render_teams;
+
+use crate::engines::policy::PolicyResult;
+use crate::engines::shared::models::{Detection, Severity};
+use crate::engines::slo::slo_engine::SloResult;
+use crate::engines::trend::{ChangeType, TrendDiff};
+
+/// Items listed before the rest are summarized as "and N more"
+const MAX_ITEMS: usize = 5;
+
+/// Overall outcome, shown as the message color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationStatus {
+    Passed,
+    Warning,
+    Failed,
+}
+
+impl NotificationStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            NotificationStatus::Passed => "Passed",
+            NotificationStatus::Warning => "Needs attention",
+            NotificationStatus::Failed => "Failed",
+        }
+    }
+}
+
+/// Platform-neutral content of a notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationSummary {
+    pub title: String,
+    pub status: NotificationStatus,
+    pub headline: String,
+    /// Label/value pairs shown side by side
+    pub facts: Vec<(String, String)>,
+    /// Most important findings, already truncated to a handful
+    pub items: Vec<String>,
+    /// Link to the CI run or report, if known
+    pub link: Option<String>,
+}
+
+impl NotificationSummary {
+    /// Summary of a scan
+    pub fn for_scan(
+        resources_changed: usize,
+        monthly_cost: f64,
+        detections: &[Detection],
+        policy_result: Option<&PolicyResult>,
+        slo_result: Option<&SloResult>,
+    ) -> Self {
+        let policy_failed = policy_result.is_some_and(|p| !p.passed || !p.violations.is_empty());
+        let slo_blocked = slo_result.is_some_and(|s| s.should_block);
+        let status = if policy_failed || slo_blocked {
+            NotificationStatus::Failed
+        } else if detections.iter().any(|d| d.severity >= Severity::High)
+            || policy_result.is_some_and(|p| !p.warnings.is_empty())
+            || slo_result.is_some_and(|s| !s.passed)
+        {
+            NotificationStatus::Warning
+        } else {
+            NotificationStatus::Passed
+        };
+
+        let mut facts = vec![
+            ("Monthly cost".to_string(), format!("${:.2}", monthly_cost)),
+            (
+                "Resources changed".to_string(),
+                resources_changed.to_string(),
+            ),
+            ("Findings".to_string(), detections.len().to_string()),
+        ];
+        if let Some(policy) = policy_result {
+            facts.push((
+                "Policy".to_string(),
+                if policy_failed {
+                    format!("{} violation(s)", policy.violations.len())
+                } else {
+                    "Passed".to_string()
+                },
+            ));
+        }
+        if let Some(slo) = slo_result {
+            facts.push((
+                "SLOs".to_string(),
+                if slo.passed { "Passed" } else { "Breached" }.to_string(),
+            ));
+        }
+
+        let mut ranked: Vec<&Detection> = detections.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.resource_id.cmp(&b.resource_id))
+        });
+        let mut items: Vec<String> = policy_result
+            .map(|p| {
+                p.violations
+                    .iter()
+                    .map(|v| {
+                        format!(
+                            "Policy {}: {} ({})",
+                            v.policy_name, v.message, v.resource_id
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        items.extend(
+            ranked
+                .iter()
+                .map(|d| format!("{:?}: {} ({})", d.severity, d.message, d.resource_id)),
+        );
+
+        Self {
+            title: "CostPilot scan".to_string(),
+            status,
+            headline: format!(
+                "Estimated monthly cost ${:.2} across {} changed resource(s)",
+                monthly_cost, resources_changed
+            ),
+            facts,
+            items: truncate_items(items),
+            link: ci_run_url(),
+        }
+    }
+
+    /// Summary of a comparison between two trend snapshots
+    pub fn for_trend_diff(diff: &TrendDiff) -> Self {
+        let status = if diff.new_regressions.is_empty() {
+            NotificationStatus::Passed
+        } else {
+            NotificationStatus::Warning
+        };
+        let sign = if diff.total_cost_delta >= 0.0 {
+            "+"
+        } else {
+            "-"
+        };
+
+        let mut modules: Vec<_> = diff
+            .module_changes
+            .iter()
+            .filter(|c| c.change_type != ChangeType::Unchanged)
+            .collect();
+        modules.sort_by(|a, b| {
+            b.delta
+                .abs()
+                .total_cmp(&a.delta.abs())
+                .then_with(|| a.module.cmp(&b.module))
+        });
+        let items = modules
+            .iter()
+            .map(|c| {
+                format!(
+                    "{}: ${:.2} -> ${:.2} ({}${:.2})",
+                    c.module,
+                    c.cost_before,
+                    c.cost_after,
+                    if c.delta >= 0.0 { "+" } else { "-" },
+                    c.delta.abs()
+                )
+            })
+            .collect();
+
+        Self {
+            title: "CostPilot cost trend".to_string(),
+            status,
+            headline: format!(
+                "Monthly cost changed {}${:.2} ({}{:.1}%) from {} to {}",
+                sign,
+                diff.total_cost_delta.abs(),
+                sign,
+                diff.total_cost_percent.abs(),
+                diff.from_snapshot,
+                diff.to_snapshot
+            ),
+            facts: vec![
+                ("Period".to_string(), diff.time_range.clone()),
+                ("Modules changed".to_string(), modules.len().to_string()),
+                (
+                    "New regressions".to_string(),
+                    diff.new_regressions.len().to_string(),
+                ),
+            ],
+            items: truncate_items(items),
+            link: ci_run_url(),
+        }
+    }
+}
+
+fn truncate_items(mut items: Vec<String>) -> Vec<String> {
+    if items.len() > MAX_ITEMS {
+        let rest = items.len() - MAX_ITEMS;
+        items.truncate(MAX_ITEMS);
+        items.push(format!("...and {} more", rest));
+    }
+    items
+}
+
+/// URL of the current CI run, from the environment the CI system provides
+pub fn ci_run_url() -> Option<String> {
+    let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    if let (Some(server), Some(repo), Some(run)) = (
+        var("GITHUB_SERVER_URL"),
+        var("GITHUB_REPOSITORY"),
+        var("GITHUB_RUN_ID"),
+    ) {
+        return Some(format!("{}/{}/actions/runs/{}", server, repo, run));
+    }
+    var("CI_PIPELINE_URL").or_else(|| var("BUILD_URL"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::policy::PolicyViolation;
+
+    fn detection(resource: &str, severity: Severity) -> Detection {
+        Detection {
+            rule_id: "rule".to_string(),
+            severity,
+            resource_id: resource.to_string(),
+            regression_type: Default::default(),
+            severity_score: 0,
+            message: format!("{} is oversized", resource),
+            fix_snippet: None,
+            estimated_cost: None,
+        }
+    }
+
+    #[test]
+    fn test_scan_summary_status_and_ranking() {
+        let detections: Vec<Detection> = (0..6)
+            .map(|i| detection(&format!("aws_instance.low{}", i), Severity::Low))
+            .chain([detection("aws_db_instance.main", Severity::Critical)])
+            .collect();
+
+        let summary = NotificationSummary::for_scan(7, 1234.5, &detections, None, None);
+        assert_eq!(summary.status, NotificationStatus::Warning);
+        assert_eq!(
+            summary.facts[0],
+            ("Monthly cost".to_string(), "$1234.50".to_string())
+        );
+        assert!(summary.items[0].starts_with("Critical: aws_db_instance.main"));
+        assert_eq!(summary.items.len(), MAX_ITEMS + 1);
+        assert_eq!(summary.items[MAX_ITEMS], "...and 2 more");
+
+        let policy = PolicyResult {
+            violations: vec![PolicyViolation {
+                policy_name: "max_instance_cost".to_string(),
+                severity: "HIGH".to_string(),
+                resource_id: "aws_instance.web".to_string(),
+                message: "Instance exceeds $500/month".to_string(),
+                actual_value: "800".to_string(),
+                expected_value: "500".to_string(),
+            }],
+            warnings: vec![],
+            applied_exemptions: vec![],
+            passed: false,
+        };
+        let failed = NotificationSummary::for_scan(1, 800.0, &[], Some(&policy), None);
+        assert_eq!(failed.status, NotificationStatus::Failed);
+        assert!(failed.items[0].starts_with("Policy max_instance_cost"));
+
+        let clean = NotificationSummary::for_scan(0, 0.0, &[], None, None);
+        assert_eq!(clean.status, NotificationStatus::Passed);
+        assert!(clean.items.is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+// Slack Block Kit payload for incoming webhooks
+
+use super::{NotificationStatus, NotificationSummary};
+use serde_json::{json, Value};
+
+/// Slack rejects header text longer than this
+const HEADER_LIMIT: usize = 150;
+
+/// Slack rejects section text longer than this
+const SECTION_LIMIT: usize = 3000;
+
+/// Slack allows at most this many fields per section
+const FIELD_LIMIT: usize = 10;
+
+/// Render `summary` as a Slack incoming-webhook message
+pub fn render_slack(summary: &NotificationSummary) -> Value {
+    let icon = match summary.status {
+        NotificationStatus::Passed => ":white_check_mark:",
+        NotificationStatus::Warning => ":warning:",
+        NotificationStatus::Failed => ":x:",
+    };
+
+    let mut blocks = vec![
+        json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": truncate(&summary.title, HEADER_LIMIT),
+                "emoji": true
+            }
+        }),
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": truncate(
+                    &format!("{} *{}* - {}", icon, summary.status.label(), escape(&summary.headline)),
+                    SECTION_LIMIT
+                )
+            }
+        }),
+    ];
+
+    if !summary.facts.is_empty() {
+        let fields: Vec<Value> = summary
+            .facts
+            .iter()
+            .take(FIELD_LIMIT)
+            .map(|(label, value)| {
+                json!({
+                    "type": "mrkdwn",
+                    "text": format!("*{}*\n{}", escape(label), escape(value))
+                })
+            })
+            .collect();
+        blocks.push(json!({ "type": "section", "fields": fields }));
+    }
+
+    if !summary.items.is_empty() {
+        let list = summary
+            .items
+            .iter()
+            .map(|item| format!("• {}", escape(item)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": truncate(&list, SECTION_LIMIT) }
+        }));
+    }
+
+    if let Some(link) = &summary.link {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": format!("<{}|View CI run>", link) }]
+        }));
+    }
+
+    json!({
+        // Shown in notifications and clients that cannot render blocks
+        "text": format!("{}: {} - {}", summary.title, summary.status.label(), summary.headline),
+        "blocks": blocks
+    })
+}
+
+/// Escape the characters Slack treats as markup in mrkdwn text
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(limit - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slack_payload_blocks() {
+        let summary = NotificationSummary {
+            title: "CostPilot scan".to_string(),
+            status: NotificationStatus::Failed,
+            headline: "Estimated monthly cost $800.00".to_string(),
+            facts: vec![("Policy".to_string(), "1 violation(s)".to_string())],
+            items: vec!["Policy max_cost: cost > <limit> & more".to_string()],
+            link: Some("https://ci.example/run/1".to_string()),
+        };
+
+        let payload = render_slack(&summary);
+        let blocks = payload["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(blocks[0]["text"]["text"], "CostPilot scan");
+        assert!(blocks[1]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with(":x: *Failed*"));
+        assert_eq!(blocks[2]["fields"][0]["text"], "*Policy*\n1 violation(s)");
+        assert_eq!(
+            blocks[3]["text"]["text"],
+            "• Policy max_cost: cost &gt; &lt;limit&gt; &amp; more"
+        );
+        assert_eq!(
+            blocks[4]["elements"][0]["text"],
+            "<https://ci.example/run/1|View CI run>"
+        );
+        assert!(payload["text"].as_str().unwrap().contains("Failed"));
+
+        assert_eq!(
+            truncate(&"x".repeat(200), HEADER_LIMIT).chars().count(),
+            150
+        );
+    }
+}
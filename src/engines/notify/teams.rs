@@ -0,0 +1,111 @@
+// Microsoft Teams Adaptive Card payload for incoming webhooks and workflows
+
+use super::{NotificationStatus, NotificationSummary};
+use serde_json::{json, Value};
+
+/// Adaptive Card schema version supported by Teams
+const CARD_VERSION: &str = "1.4";
+
+/// Render `summary` as a Teams message carrying one Adaptive Card
+pub fn render_teams(summary: &NotificationSummary) -> Value {
+    let color = match summary.status {
+        NotificationStatus::Passed => "Good",
+        NotificationStatus::Warning => "Warning",
+        NotificationStatus::Failed => "Attention",
+    };
+
+    let mut body = vec![
+        json!({
+            "type": "TextBlock",
+            "text": summary.title,
+            "size": "Large",
+            "weight": "Bolder",
+            "wrap": true
+        }),
+        json!({
+            "type": "TextBlock",
+            "text": format!("{}: {}", summary.status.label(), summary.headline),
+            "color": color,
+            "weight": "Bolder",
+            "wrap": true
+        }),
+    ];
+
+    if !summary.facts.is_empty() {
+        let facts: Vec<Value> = summary
+            .facts
+            .iter()
+            .map(|(title, value)| json!({ "title": title, "value": value }))
+            .collect();
+        body.push(json!({ "type": "FactSet", "facts": facts }));
+    }
+
+    if !summary.items.is_empty() {
+        // Adaptive Card TextBlocks render a subset of Markdown, including lists
+        let list = summary
+            .items
+            .iter()
+            .map(|item| format!("- {}", item))
+            .collect::<Vec<_>>()
+            .join("\n");
+        body.push(json!({ "type": "TextBlock", "text": list, "wrap": true }));
+    }
+
+    let mut card = json!({
+        "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+        "type": "AdaptiveCard",
+        "version": CARD_VERSION,
+        "msteams": { "width": "Full" },
+        "body": body
+    });
+    if let Some(link) = &summary.link {
+        card["actions"] =
+            json!([{ "type": "Action.OpenUrl", "title": "View CI run", "url": link }]);
+    }
+
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "contentUrl": null,
+            "content": card
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_teams_adaptive_card() {
+        let mut summary = NotificationSummary {
+            title: "CostPilot cost trend".to_string(),
+            status: NotificationStatus::Warning,
+            headline: "Monthly cost changed +$120.00 (+12.0%)".to_string(),
+            facts: vec![("New regressions".to_string(), "1".to_string())],
+            items: vec!["module.api: $100.00 -> $220.00 (+$120.00)".to_string()],
+            link: None,
+        };
+
+        let payload = render_teams(&summary);
+        let attachment = &payload["attachments"][0];
+        assert_eq!(
+            attachment["contentType"],
+            "application/vnd.microsoft.card.adaptive"
+        );
+        let card = &attachment["content"];
+        assert_eq!(card["type"], "AdaptiveCard");
+        assert_eq!(card["body"][1]["color"], "Warning");
+        assert_eq!(card["body"][2]["facts"][0]["title"], "New regressions");
+        assert_eq!(
+            card["body"][3]["text"],
+            "- module.api: $100.00 -> $220.00 (+$120.00)"
+        );
+        assert!(card.get("actions").is_none());
+
+        summary.link = Some("https://ci.example/run/1".to_string());
+        let card = &render_teams(&summary)["attachments"][0]["content"];
+        assert_eq!(card["actions"][0]["url"], "https://ci.example/run/1");
+    }
+}
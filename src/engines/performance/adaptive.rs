@@ -0,0 +1,400 @@
+// Adaptive performance budgets that scale with input size
+// Static budgets are treated as the allowance for a baseline-sized plan
+
+use super::budgets::{EngineBudget, PerformanceBudgets};
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Default floor as a fraction of the static latency budget
+const DEFAULT_LATENCY_FLOOR: f64 = 0.25;
+
+/// Default ceiling as a multiple of the static latency budget
+const DEFAULT_LATENCY_CEILING: f64 = 10.0;
+
+/// Default floor as a fraction of the static memory budget
+const DEFAULT_MEMORY_FLOOR: f64 = 0.5;
+
+/// Default ceiling as a multiple of the static memory budget
+const DEFAULT_MEMORY_CEILING: f64 = 4.0;
+
+/// Size of the input an engine is about to process
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InputSize {
+    /// Number of resource changes in the plan
+    pub resource_count: usize,
+
+    /// Plan file size in bytes
+    pub plan_bytes: u64,
+}
+
+impl InputSize {
+    pub fn new(resource_count: usize, plan_bytes: u64) -> Self {
+        Self {
+            resource_count,
+            plan_bytes,
+        }
+    }
+
+    /// Plan file size in MB
+    pub fn plan_mb(&self) -> f64 {
+        self.plan_bytes as f64 / (1024.0 * 1024.0)
+    }
+}
+
+/// Per-engine floor and ceiling for adaptive budgets
+///
+/// Unset values default to a multiple of the engine's static budget.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveEngineLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_latency_ms: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_latency_ms: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_memory_mb: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_mb: Option<usize>,
+}
+
+/// Adaptive budget settings (`performance.adaptive_budgets` in costpilot.yaml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptiveBudgetConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Resource count the static budgets are sized for
+    #[serde(default = "default_baseline_resources")]
+    pub baseline_resources: usize,
+
+    /// Plan size (MB) the static budgets are sized for
+    #[serde(default = "default_baseline_plan_mb")]
+    pub baseline_plan_mb: f64,
+
+    /// Limits keyed by engine (prediction, mapping, autofix, total_scan, slo, policy)
+    #[serde(default)]
+    pub engines: HashMap<String, AdaptiveEngineLimits>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_baseline_resources() -> usize {
+    100
+}
+
+fn default_baseline_plan_mb() -> f64 {
+    1.0
+}
+
+impl Default for AdaptiveBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            baseline_resources: default_baseline_resources(),
+            baseline_plan_mb: default_baseline_plan_mb(),
+            engines: HashMap::new(),
+        }
+    }
+}
+
+impl AdaptiveBudgetConfig {
+    /// Load `performance.adaptive_budgets` from a costpilot.yaml file,
+    /// applying `profile`
+    ///
+    /// Returns the default configuration if the section is absent.
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `performance.adaptive_budgets` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `performance.adaptive_budgets` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let section = root
+            .get("performance")
+            .and_then(|p| p.get("adaptive_budgets"))
+            .cloned();
+
+        let config: Self = match section {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid performance.adaptive_budgets: {}", e))
+            })?,
+            None => Self::default(),
+        };
+
+        let problems = config.validate();
+        if let Some(problem) = problems.first() {
+            return Err(CostPilotError::validation_error(problem.clone())
+                .with_hint("Check performance.adaptive_budgets in costpilot.yaml"));
+        }
+
+        Ok(config)
+    }
+
+    /// Describe invalid settings; empty if the configuration is usable
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if self.baseline_resources == 0 {
+            problems.push("baseline_resources must be greater than 0".to_string());
+        }
+        if self.baseline_plan_mb <= 0.0 {
+            problems.push("baseline_plan_mb must be greater than 0".to_string());
+        }
+
+        let mut engines: Vec<_> = self.engines.iter().collect();
+        engines.sort_by(|a, b| a.0.cmp(b.0));
+        for (engine, limits) in engines {
+            if !ENGINE_KEYS.contains(&engine.as_str()) {
+                problems.push(format!(
+                    "Unknown engine '{}' (expected one of: {})",
+                    engine,
+                    ENGINE_KEYS.join(", ")
+                ));
+            }
+            if let (Some(min), Some(max)) = (limits.min_latency_ms, limits.max_latency_ms) {
+                if min > max {
+                    problems.push(format!(
+                        "{}: min_latency_ms ({}) exceeds max_latency_ms ({})",
+                        engine, min, max
+                    ));
+                }
+            }
+            if let (Some(min), Some(max)) = (limits.min_memory_mb, limits.max_memory_mb) {
+                if min > max {
+                    problems.push(format!(
+                        "{}: min_memory_mb ({}) exceeds max_memory_mb ({})",
+                        engine, min, max
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+/// Engine keys accepted in `adaptive_budgets.engines`
+pub const ENGINE_KEYS: [&str; 6] = [
+    "prediction",
+    "mapping",
+    "autofix",
+    "total_scan",
+    "slo",
+    "policy",
+];
+
+/// Scales static engine budgets to the size of the input
+#[derive(Debug, Clone, Default)]
+pub struct AdaptiveBudgetCalculator {
+    config: AdaptiveBudgetConfig,
+}
+
+impl AdaptiveBudgetCalculator {
+    pub fn new(config: AdaptiveBudgetConfig) -> Self {
+        Self { config }
+    }
+
+    /// Multiplier applied to the static budgets for this input
+    ///
+    /// The larger of the resource-count and plan-size ratios against the
+    /// baseline, so either a wide or a verbose plan earns more headroom.
+    pub fn scale_factor(&self, input: &InputSize) -> f64 {
+        let by_resources =
+            input.resource_count as f64 / self.config.baseline_resources.max(1) as f64;
+        let by_size = if self.config.baseline_plan_mb > 0.0 {
+            input.plan_mb() / self.config.baseline_plan_mb
+        } else {
+            0.0
+        };
+        by_resources.max(by_size)
+    }
+
+    /// Budget for one engine, scaled and clamped to its floor and ceiling
+    ///
+    /// `engine_key` selects the per-engine limits (e.g. "prediction").
+    pub fn budget_for(
+        &self,
+        engine_key: &str,
+        budget: &EngineBudget,
+        input: &InputSize,
+    ) -> EngineBudget {
+        if !self.config.enabled {
+            return budget.clone();
+        }
+
+        let limits = self
+            .config
+            .engines
+            .get(engine_key)
+            .cloned()
+            .unwrap_or_default();
+        let scale = self.scale_factor(input);
+
+        let base_latency = budget.max_latency_ms as f64;
+        let min_latency = limits
+            .min_latency_ms
+            .unwrap_or((base_latency * DEFAULT_LATENCY_FLOOR) as u64);
+        let max_latency = limits
+            .max_latency_ms
+            .unwrap_or((base_latency * DEFAULT_LATENCY_CEILING) as u64)
+            .max(min_latency);
+
+        let base_memory = budget.max_memory_mb as f64;
+        let min_memory = limits
+            .min_memory_mb
+            .unwrap_or((base_memory * DEFAULT_MEMORY_FLOOR) as usize);
+        let max_memory = limits
+            .max_memory_mb
+            .unwrap_or((base_memory * DEFAULT_MEMORY_CEILING) as usize)
+            .max(min_memory);
+
+        EngineBudget {
+            max_latency_ms: ((base_latency * scale).ceil() as u64).clamp(min_latency, max_latency),
+            max_memory_mb: ((base_memory * scale).ceil() as usize).clamp(min_memory, max_memory),
+            ..budget.clone()
+        }
+    }
+
+    /// Scale every engine budget; WASM and circuit breaker limits are unchanged
+    pub fn budgets_for(
+        &self,
+        budgets: &PerformanceBudgets,
+        input: &InputSize,
+    ) -> PerformanceBudgets {
+        PerformanceBudgets {
+            prediction: self.budget_for("prediction", &budgets.prediction, input),
+            mapping: self.budget_for("mapping", &budgets.mapping, input),
+            autofix: self.budget_for("autofix", &budgets.autofix, input),
+            total_scan: self.budget_for("total_scan", &budgets.total_scan, input),
+            slo: self.budget_for("slo", &budgets.slo, input),
+            policy: self.budget_for("policy", &budgets.policy, input),
+            wasm: budgets.wasm.clone(),
+            circuit_breaker: budgets.circuit_breaker.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budgets_scale_with_input_and_clamp() {
+        let calculator = AdaptiveBudgetCalculator::default();
+        let defaults = PerformanceBudgets::default();
+
+        // Baseline-sized plan keeps the static budget
+        let budgets = calculator.budgets_for(&defaults, &InputSize::new(100, 0));
+        assert_eq!(budgets.prediction.max_latency_ms, 300);
+        assert_eq!(budgets.prediction.max_memory_mb, 128);
+
+        // Small plan shrinks to the default floor
+        let budgets = calculator.budgets_for(&defaults, &InputSize::new(5, 0));
+        assert_eq!(budgets.prediction.max_latency_ms, 75);
+        assert_eq!(budgets.prediction.max_memory_mb, 64);
+
+        // Plan size dominates when larger than the resource ratio
+        let budgets = calculator.budgets_for(&defaults, &InputSize::new(100, 3 * 1024 * 1024));
+        assert_eq!(budgets.prediction.max_latency_ms, 900);
+
+        // Huge plan is capped at the default ceiling
+        let budgets = calculator.budgets_for(&defaults, &InputSize::new(100_000, 0));
+        assert_eq!(budgets.prediction.max_latency_ms, 3000);
+        assert_eq!(budgets.prediction.max_memory_mb, 512);
+        assert_eq!(budgets.wasm.max_memory_mb, defaults.wasm.max_memory_mb);
+    }
+
+    #[test]
+    fn test_config_from_costpilot_yaml() {
+        let yaml = r#"
+version: "1.0.0"
+performance:
+  adaptive_budgets:
+    baseline_resources: 50
+    engines:
+      mapping:
+        min_latency_ms: 200
+        max_latency_ms: 1500
+"#;
+        let config = AdaptiveBudgetConfig::from_yaml(yaml).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.baseline_resources, 50);
+
+        let calculator = AdaptiveBudgetCalculator::new(config);
+        let mapping = PerformanceBudgets::default().mapping;
+        let small = calculator.budget_for("mapping", &mapping, &InputSize::new(1, 0));
+        let large = calculator.budget_for("mapping", &mapping, &InputSize::new(1_000, 0));
+        assert_eq!(small.max_latency_ms, 200);
+        assert_eq!(large.max_latency_ms, 1500);
+
+        // Missing section falls back to defaults
+        let config = AdaptiveBudgetConfig::from_yaml("version: \"1.0.0\"\n").unwrap();
+        assert_eq!(config, AdaptiveBudgetConfig::default());
+    }
+
+    #[test]
+    fn test_profile_overrides_budgets() {
+        let yaml = r#"
+performance:
+  adaptive_budgets:
+    baseline_resources: 100
+profiles:
+  prod:
+    performance:
+      adaptive_budgets:
+        baseline_resources: 25
+"#;
+        let base = AdaptiveBudgetConfig::from_yaml(yaml).unwrap();
+        assert_eq!(base.baseline_resources, 100);
+
+        let prod = AdaptiveBudgetConfig::from_yaml_with_profile(yaml, Some("prod")).unwrap();
+        assert_eq!(prod.baseline_resources, 25);
+        assert!(prod.enabled);
+
+        assert!(AdaptiveBudgetConfig::from_yaml_with_profile(yaml, Some("dev")).is_err());
+    }
+
+    #[test]
+    fn test_invalid_limits_rejected() {
+        let yaml = r#"
+performance:
+  adaptive_budgets:
+    engines:
+      prediction:
+        min_latency_ms: 900
+        max_latency_ms: 100
+"#;
+        let err = AdaptiveBudgetConfig::from_yaml(yaml).unwrap_err();
+        assert!(err.message.contains("min_latency_ms"));
+
+        let mut config = AdaptiveBudgetConfig::default();
+        config
+            .engines
+            .insert("scanner".to_string(), Default::default());
+        assert_eq!(config.validate().len(), 1);
+
+        config.enabled = false;
+        let calculator = AdaptiveBudgetCalculator::new(config);
+        let budget = PerformanceBudgets::default().policy;
+        let scaled = calculator.budget_for("policy", &budget, &InputSize::new(10_000, 0));
+        assert_eq!(scaled.max_latency_ms, budget.max_latency_ms);
+    }
+}
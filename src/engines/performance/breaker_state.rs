@@ -0,0 +1,142 @@
+// Circuit breaker state persisted across CostPilot runs
+// Lets a consistently failing engine stay tripped between CI builds
+
+use super::budgets::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerSnapshot};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Breaker state file name under `~/.costpilot/`
+pub const BREAKER_STATE_FILE: &str = "circuit_breakers.json";
+
+/// Breaker key for the premium WASM engine
+pub const PRO_ENGINE_BREAKER: &str = "pro_engine";
+
+/// Persisted circuit breaker snapshots keyed by engine
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CircuitBreakerRegistry {
+    #[serde(default)]
+    pub breakers: BTreeMap<String, CircuitBreakerSnapshot>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Default state file location (`~/.costpilot/circuit_breakers.json`)
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var("HOME")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(dirs::home_dir)
+            .map(|home| home.join(".costpilot").join(BREAKER_STATE_FILE))
+    }
+
+    /// Load from file, starting empty if it does not exist
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read circuit breaker state: {}", e))?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse circuit breaker state: {}", e))
+    }
+
+    /// Save to file, creating the parent directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create state directory: {}", e))?;
+        }
+
+        let json = serde_json::to_string_pretty(&self)
+            .map_err(|e| format!("Failed to serialize circuit breaker state: {}", e))?;
+
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write circuit breaker state: {}", e))
+    }
+
+    /// Breaker for `engine`, restored from persisted state when present
+    pub fn breaker(&self, engine: &str, config: CircuitBreakerConfig) -> CircuitBreaker {
+        match self.breakers.get(engine) {
+            Some(snapshot) => CircuitBreaker::restore(config, snapshot),
+            None => CircuitBreaker::new(config),
+        }
+    }
+
+    /// Record the current state of `engine`'s breaker
+    pub fn update(&mut self, engine: &str, breaker: &CircuitBreaker) {
+        self.breakers.insert(engine.to_string(), breaker.snapshot());
+    }
+
+    /// Forget persisted state; returns false if nothing was stored
+    pub fn reset(&mut self, engine: &str) -> bool {
+        self.breakers.remove(engine).is_some()
+    }
+
+    pub fn reset_all(&mut self) {
+        self.breakers.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::performance::CircuitState;
+    use tempfile::TempDir;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: 3,
+            success_threshold: 1,
+            timeout_seconds: 3600,
+            max_consecutive_failures: 2,
+        }
+    }
+
+    #[test]
+    fn test_tripped_breaker_survives_reload() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(".costpilot").join(BREAKER_STATE_FILE);
+
+        // First run fails twice and trips the breaker
+        let mut registry = CircuitBreakerRegistry::load(&path).unwrap();
+        let mut breaker = registry.breaker(PRO_ENGINE_BREAKER, config());
+        breaker.record_failure();
+        breaker.record_failure();
+        registry.update(PRO_ENGINE_BREAKER, &breaker);
+        registry.save(&path).unwrap();
+
+        // Next run starts out tripped
+        let registry = CircuitBreakerRegistry::load(&path).unwrap();
+        let mut breaker = registry.breaker(PRO_ENGINE_BREAKER, config());
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.allow_request().is_err());
+
+        // Other engines are unaffected
+        let other = registry.breaker("policy", config());
+        assert_eq!(other.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_reset_clears_state() {
+        let mut registry = CircuitBreakerRegistry::new();
+        let mut breaker = CircuitBreaker::new(config());
+        breaker.record_failure();
+        registry.update(PRO_ENGINE_BREAKER, &breaker);
+
+        assert!(registry.reset(PRO_ENGINE_BREAKER));
+        assert!(!registry.reset(PRO_ENGINE_BREAKER));
+        assert_eq!(
+            registry
+                .breaker(PRO_ENGINE_BREAKER, config())
+                .stats()
+                .failure_count,
+            0
+        );
+    }
+}
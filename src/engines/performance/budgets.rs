@@ -1,8 +1,9 @@
 // Performance budget enforcement for CostPilot engines
 // Ensures SLA compliance and prevents resource exhaustion
 
+use super::spans::{PerformanceSpan, SpanKind, SpanRecorder};
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Performance budgets for all engines
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,7 +119,7 @@ pub struct CircuitBreaker {
     failure_count: usize,
     success_count: usize,
     consecutive_failures: usize,
-    last_failure_time: Option<Instant>,
+    last_failure_time: Option<SystemTime>,
 }
 
 impl CircuitBreaker {
@@ -141,7 +142,7 @@ impl CircuitBreaker {
             CircuitState::Open => {
                 // Check if timeout has elapsed
                 if let Some(last_failure) = self.last_failure_time {
-                    let elapsed = last_failure.elapsed();
+                    let elapsed = last_failure.elapsed().unwrap_or_default();
                     if elapsed.as_secs() >= self.config.timeout_seconds {
                         // Transition to half-open
                         self.state = CircuitState::HalfOpen;
@@ -184,7 +185,7 @@ impl CircuitBreaker {
     pub fn record_failure(&mut self) {
         self.failure_count += 1;
         self.consecutive_failures += 1;
-        self.last_failure_time = Some(Instant::now());
+        self.last_failure_time = Some(SystemTime::now());
 
         // Check if we should open circuit
         if self.consecutive_failures >= self.config.max_consecutive_failures
@@ -208,6 +209,48 @@ impl CircuitBreaker {
             consecutive_failures: self.consecutive_failures,
         }
     }
+
+    /// Capture state for persistence across process runs
+    pub fn snapshot(&self) -> CircuitBreakerSnapshot {
+        CircuitBreakerSnapshot {
+            state: self.state,
+            failure_count: self.failure_count,
+            success_count: self.success_count,
+            consecutive_failures: self.consecutive_failures,
+            last_failure_at: self.last_failure_time.map(|t| {
+                t.duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0)
+            }),
+        }
+    }
+
+    /// Rebuild a breaker from a persisted snapshot
+    pub fn restore(config: CircuitBreakerConfig, snapshot: &CircuitBreakerSnapshot) -> Self {
+        Self {
+            config,
+            state: snapshot.state,
+            failure_count: snapshot.failure_count,
+            success_count: snapshot.success_count,
+            consecutive_failures: snapshot.consecutive_failures,
+            last_failure_time: snapshot
+                .last_failure_at
+                .map(|at| UNIX_EPOCH + Duration::from_secs(at)),
+        }
+    }
+}
+
+/// Persistable circuit breaker state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CircuitBreakerSnapshot {
+    pub state: CircuitState,
+    pub failure_count: usize,
+    pub success_count: usize,
+    pub consecutive_failures: usize,
+
+    /// Time of the last recorded failure (Unix epoch seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_at: Option<u64>,
 }
 
 /// Circuit breaker statistics
@@ -224,24 +267,46 @@ pub struct PerformanceTracker {
     budget: EngineBudget,
     start_time: Instant,
     circuit_breaker: Option<CircuitBreaker>,
+    spans: SpanRecorder,
 }
 
 impl PerformanceTracker {
     /// Create new performance tracker
     pub fn new(budget: EngineBudget) -> Self {
+        let start_time = Instant::now();
+        let mut spans = SpanRecorder::with_origin(start_time);
+        spans.enter(budget.name.clone(), SpanKind::Engine);
+
         Self {
             budget,
-            start_time: Instant::now(),
+            start_time,
             circuit_breaker: None,
+            spans,
         }
     }
 
     /// Create tracker with circuit breaker
     pub fn with_circuit_breaker(budget: EngineBudget, breaker: CircuitBreaker) -> Self {
         Self {
-            budget,
-            start_time: Instant::now(),
             circuit_breaker: Some(breaker),
+            ..Self::new(budget)
+        }
+    }
+
+    /// Start a named phase nested under the current span
+    pub fn enter_phase(&mut self, name: impl Into<String>) {
+        self.spans.enter(name, SpanKind::Phase);
+    }
+
+    /// Start a resource batch nested under the current span
+    pub fn enter_batch(&mut self, name: impl Into<String>, resources: usize) {
+        self.spans.enter_batch(name, resources);
+    }
+
+    /// End the innermost open phase or batch (the engine span stays open)
+    pub fn exit_span(&mut self) {
+        if self.spans.depth() > 1 {
+            self.spans.exit();
         }
     }
 
@@ -266,10 +331,10 @@ impl PerformanceTracker {
             (self.budget.max_latency_ms as f64 * self.budget.warning_threshold) as u64;
         if elapsed_ms > warning_threshold_ms {
             // Log warning but don't fail
-            eprintln!(
-                "⚠️  Performance warning: {} at {}ms ({}% of budget)",
-                self.budget.name,
+            tracing::warn!(
+                engine = %self.budget.name,
                 elapsed_ms,
+                "Performance warning: {}% of budget used",
                 (elapsed_ms as f64 / self.budget.max_latency_ms as f64 * 100.0) as u64
             );
         }
@@ -278,7 +343,12 @@ impl PerformanceTracker {
     }
 
     /// Complete execution and record metrics
-    pub fn complete(mut self) -> PerformanceMetrics {
+    pub fn complete(self) -> PerformanceMetrics {
+        self.complete_with_spans().0
+    }
+
+    /// Complete execution, returning metrics and the engine's span tree
+    pub fn complete_with_spans(mut self) -> (PerformanceMetrics, Vec<PerformanceSpan>) {
         let duration = self.start_time.elapsed();
         let duration_ms = duration.as_millis() as u64;
 
@@ -293,14 +363,23 @@ impl PerformanceTracker {
             }
         }
 
-        PerformanceMetrics {
+        let metrics = PerformanceMetrics {
             engine: self.budget.name.clone(),
             duration_ms,
             budget_ms: self.budget.max_latency_ms,
             within_budget,
             utilization: (duration_ms as f64 / self.budget.max_latency_ms as f64 * 100.0),
             circuit_breaker_stats: self.circuit_breaker.as_ref().map(|b| b.stats()),
-        }
+        };
+        tracing::debug!(
+            engine = %metrics.engine,
+            duration_ms,
+            budget_ms = metrics.budget_ms,
+            within_budget,
+            "Engine complete"
+        );
+
+        (metrics, self.spans.finish())
     }
 
     /// Complete with failure
@@ -411,6 +490,10 @@ pub struct PerformanceReport {
     pub metrics: Vec<PerformanceMetrics>,
     pub total_duration_ms: u64,
     pub all_within_budget: bool,
+
+    /// Per-engine span trees (engine → phase → resource batch)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<PerformanceSpan>,
 }
 
 impl Default for PerformanceReport {
@@ -425,6 +508,7 @@ impl PerformanceReport {
             metrics: Vec::new(),
             total_duration_ms: 0,
             all_within_budget: true,
+            spans: Vec::new(),
         }
     }
 
@@ -436,6 +520,77 @@ impl PerformanceReport {
         self.metrics.push(metric);
     }
 
+    /// Add a metric together with the span tree recorded for it
+    pub fn add_traced(&mut self, metric: PerformanceMetrics, spans: Vec<PerformanceSpan>) {
+        self.add_metric(metric);
+        self.spans.extend(spans);
+    }
+
+    /// Export as Chrome Trace Event Format JSON (chrome://tracing, Perfetto)
+    ///
+    /// Each root span is placed on its own thread lane. Reports without
+    /// spans fall back to one event per engine metric, laid out back to back.
+    pub fn to_chrome_trace(&self) -> serde_json::Value {
+        let mut events = Vec::new();
+
+        let fallback: Vec<PerformanceSpan>;
+        let roots = if self.spans.is_empty() {
+            let mut offset_us = 0;
+            fallback = self
+                .metrics
+                .iter()
+                .map(|metric| {
+                    let span = PerformanceSpan {
+                        name: metric.engine.clone(),
+                        kind: SpanKind::Engine,
+                        start_us: offset_us,
+                        duration_us: metric.duration_ms * 1000,
+                        resource_count: None,
+                        children: Vec::new(),
+                    };
+                    offset_us += span.duration_us;
+                    span
+                })
+                .collect();
+            &fallback
+        } else {
+            &self.spans
+        };
+
+        for (lane, root) in roots.iter().enumerate() {
+            let tid = lane + 1;
+            events.push(serde_json::json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": tid,
+                "args": { "name": root.name },
+            }));
+
+            root.walk(0, &mut |span, _depth| {
+                let mut args = serde_json::Map::new();
+                if let Some(count) = span.resource_count {
+                    args.insert("resources".to_string(), count.into());
+                }
+                events.push(serde_json::json!({
+                    "name": span.name,
+                    "cat": span.kind.as_str(),
+                    "ph": "X",
+                    "ts": span.start_us,
+                    "dur": span.duration_us,
+                    "pid": 1,
+                    "tid": tid,
+                    "args": args,
+                }));
+            });
+        }
+
+        serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+    }
+
     pub fn format_text(&self) -> String {
         let mut output = String::new();
 
@@ -624,6 +779,37 @@ mod tests {
         assert_eq!(breaker.state(), CircuitState::Closed);
     }
 
+    #[test]
+    fn test_circuit_breaker_snapshot_round_trip() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            success_threshold: 1,
+            timeout_seconds: 3600,
+            max_consecutive_failures: 2,
+        };
+
+        let mut breaker = CircuitBreaker::new(config.clone());
+        breaker.record_failure();
+        breaker.record_failure();
+        let snapshot = breaker.snapshot();
+        assert!(snapshot.last_failure_at.is_some());
+
+        // Restored breaker is still open until the timeout elapses
+        let mut restored = CircuitBreaker::restore(config.clone(), &snapshot);
+        assert_eq!(restored.state(), CircuitState::Open);
+        assert_eq!(restored.stats().failure_count, 2);
+        assert!(restored.allow_request().is_err());
+
+        // An old failure lets the restored breaker probe again
+        let stale = CircuitBreakerSnapshot {
+            last_failure_at: snapshot.last_failure_at.map(|t| t - 7200),
+            ..snapshot
+        };
+        let mut restored = CircuitBreaker::restore(config, &stale);
+        assert!(restored.allow_request().is_ok());
+        assert_eq!(restored.state(), CircuitState::HalfOpen);
+    }
+
     #[test]
     fn test_performance_report() {
         let mut report = PerformanceReport::new();
@@ -653,6 +839,74 @@ mod tests {
         assert!(text.contains("All engines within budget"));
     }
 
+    #[test]
+    fn test_tracker_records_span_tree() {
+        let mut tracker = PerformanceTracker::new(PerformanceBudgets::default().mapping);
+        tracker.enter_phase("nodes");
+        tracker.enter_batch("batch 0", 25);
+        thread::sleep(Duration::from_millis(2));
+        tracker.exit_span();
+        tracker.exit_span();
+        // Engine span is closed by complete, not exit_span
+        tracker.exit_span();
+
+        let (metrics, spans) = tracker.complete_with_spans();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].name, "Mapping");
+        assert_eq!(spans[0].children[0].name, "nodes");
+        assert_eq!(spans[0].children[0].children[0].resource_count, Some(25));
+
+        let mut report = PerformanceReport::new();
+        report.add_traced(metrics, spans);
+        let json = serde_json::to_string(&report).unwrap();
+        let restored: PerformanceReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.spans.len(), 1);
+    }
+
+    #[test]
+    fn test_chrome_trace_export() {
+        let mut tracker = PerformanceTracker::new(PerformanceBudgets::default().prediction);
+        tracker.enter_phase("predict");
+        tracker.enter_batch("batch 0", 10);
+        let (metrics, spans) = tracker.complete_with_spans();
+
+        let mut report = PerformanceReport::new();
+        report.add_traced(metrics, spans);
+        let trace = report.to_chrome_trace();
+        let events = trace["traceEvents"].as_array().unwrap();
+
+        // Thread name metadata + engine, phase and batch events
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0]["ph"], "M");
+        assert_eq!(events[1]["name"], "Prediction");
+        assert_eq!(events[1]["cat"], "engine");
+        assert_eq!(events[3]["cat"], "batch");
+        assert_eq!(events[3]["args"]["resources"], 10);
+        assert!(events.iter().skip(1).all(|e| e["ph"] == "X"));
+    }
+
+    #[test]
+    fn test_chrome_trace_falls_back_to_metrics() {
+        let mut report = PerformanceReport::new();
+        for (engine, duration_ms) in [("Prediction", 120), ("Mapping", 80)] {
+            report.add_metric(PerformanceMetrics {
+                engine: engine.to_string(),
+                duration_ms,
+                budget_ms: 300,
+                within_budget: true,
+                utilization: 0.0,
+                circuit_breaker_stats: None,
+            });
+        }
+
+        let trace = report.to_chrome_trace();
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events[1]["ts"], 0);
+        assert_eq!(events[1]["dur"], 120_000);
+        assert_eq!(events[3]["name"], "Mapping");
+        assert_eq!(events[3]["ts"], 120_000);
+    }
+
     #[test]
     fn test_budget_violation_format() {
         let violation = BudgetViolation {
@@ -1,15 +1,26 @@
 // Performance module exports
 
+pub mod adaptive;
+pub mod breaker_state;
 pub mod budgets;
 pub mod monitoring;
+pub mod spans;
+
+pub use adaptive::{
+    AdaptiveBudgetCalculator, AdaptiveBudgetConfig, AdaptiveEngineLimits, InputSize,
+};
+
+pub use breaker_state::{CircuitBreakerRegistry, BREAKER_STATE_FILE, PRO_ENGINE_BREAKER};
 
 pub use budgets::{
-    BudgetViolation, CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitState,
-    EngineBudget, PerformanceBudgets, PerformanceMetrics, PerformanceReport, PerformanceTracker,
-    TimeoutAction, ViolationType, WasmLimits,
+    BudgetViolation, CircuitBreaker, CircuitBreakerConfig, CircuitBreakerSnapshot,
+    CircuitBreakerStats, CircuitState, EngineBudget, PerformanceBudgets, PerformanceMetrics,
+    PerformanceReport, PerformanceTracker, TimeoutAction, ViolationType, WasmLimits,
 };
 
 pub use monitoring::{
     EngineStats, MemoryStats, MemoryTracker, PerformanceBaseline, PerformanceMonitor,
     PerformanceRegression, PerformanceSnapshot, PerformanceStatistics, RegressionSeverity,
 };
+
+pub use spans::{PerformanceSpan, SpanKind, SpanRecorder};
@@ -286,7 +286,7 @@ fn current_timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_else(|_| {
-            eprintln!("Warning: System time is set before UNIX epoch, using 0 as timestamp");
+            tracing::warn!("System time is set before UNIX epoch, using 0 as timestamp");
             std::time::Duration::from_secs(0)
         })
         .as_secs()
@@ -0,0 +1,199 @@
+// Hierarchical timing spans (engine → phase → resource batch)
+// Exported as Chrome Trace Event Format for chrome://tracing and Perfetto
+
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Level of a span in the engine → phase → batch hierarchy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpanKind {
+    Engine,
+    Phase,
+    Batch,
+}
+
+impl SpanKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpanKind::Engine => "engine",
+            SpanKind::Phase => "phase",
+            SpanKind::Batch => "batch",
+        }
+    }
+}
+
+/// A completed timing span and its nested children
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceSpan {
+    pub name: String,
+    pub kind: SpanKind,
+
+    /// Offset from the start of the recording, in microseconds
+    pub start_us: u64,
+
+    /// Span duration, in microseconds
+    pub duration_us: u64,
+
+    /// Number of resources processed (batch spans)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_count: Option<usize>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<PerformanceSpan>,
+}
+
+impl PerformanceSpan {
+    pub fn duration_ms(&self) -> f64 {
+        self.duration_us as f64 / 1000.0
+    }
+
+    /// Time not covered by child spans, in microseconds
+    pub fn self_time_us(&self) -> u64 {
+        let children: u64 = self.children.iter().map(|c| c.duration_us).sum();
+        self.duration_us.saturating_sub(children)
+    }
+
+    /// Depth-first search for a descendant (or self) by name
+    pub fn find(&self, name: &str) -> Option<&PerformanceSpan> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.children.iter().find_map(|c| c.find(name))
+    }
+
+    /// Visit this span and all descendants depth-first with their depth
+    pub fn walk<'a>(&'a self, depth: usize, visit: &mut impl FnMut(&'a PerformanceSpan, usize)) {
+        visit(self, depth);
+        for child in &self.children {
+            child.walk(depth + 1, visit);
+        }
+    }
+}
+
+/// Records nested spans against a shared clock origin
+#[derive(Debug, Clone)]
+pub struct SpanRecorder {
+    origin: Instant,
+    open: Vec<PerformanceSpan>,
+    closed: Vec<PerformanceSpan>,
+}
+
+impl SpanRecorder {
+    /// Create recorder whose offsets are measured from now
+    pub fn new() -> Self {
+        Self::with_origin(Instant::now())
+    }
+
+    /// Create recorder whose offsets are measured from `origin`
+    pub fn with_origin(origin: Instant) -> Self {
+        Self {
+            origin,
+            open: Vec::new(),
+            closed: Vec::new(),
+        }
+    }
+
+    /// Open a span nested under the current innermost open span
+    pub fn enter(&mut self, name: impl Into<String>, kind: SpanKind) {
+        self.open.push(PerformanceSpan {
+            name: name.into(),
+            kind,
+            start_us: self.elapsed_us(),
+            duration_us: 0,
+            resource_count: None,
+            children: Vec::new(),
+        });
+    }
+
+    /// Open a batch span covering `resources` resources
+    pub fn enter_batch(&mut self, name: impl Into<String>, resources: usize) {
+        self.enter(name, SpanKind::Batch);
+        if let Some(span) = self.open.last_mut() {
+            span.resource_count = Some(resources);
+        }
+    }
+
+    /// Close the innermost open span; returns false if none was open
+    pub fn exit(&mut self) -> bool {
+        let Some(mut span) = self.open.pop() else {
+            return false;
+        };
+        span.duration_us = self.elapsed_us().saturating_sub(span.start_us);
+        tracing::trace!(
+            span = %span.name,
+            kind = span.kind.as_str(),
+            duration_ms = span.duration_ms(),
+            resources = span.resource_count,
+            "Span closed"
+        );
+
+        match self.open.last_mut() {
+            Some(parent) => parent.children.push(span),
+            None => self.closed.push(span),
+        }
+        true
+    }
+
+    /// Number of currently open spans
+    pub fn depth(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Close any open spans and return the recorded root spans
+    pub fn finish(mut self) -> Vec<PerformanceSpan> {
+        while self.exit() {}
+        self.closed
+    }
+
+    fn elapsed_us(&self) -> u64 {
+        self.origin.elapsed().as_micros() as u64
+    }
+}
+
+impl Default for SpanRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_spans_nest_under_open_parent() {
+        let mut recorder = SpanRecorder::new();
+        recorder.enter("Mapping", SpanKind::Engine);
+        recorder.enter("nodes", SpanKind::Phase);
+        recorder.enter_batch("batch 0", 50);
+        thread::sleep(Duration::from_millis(2));
+        assert_eq!(recorder.depth(), 3);
+        recorder.exit();
+        recorder.exit();
+        recorder.enter("edges", SpanKind::Phase);
+
+        // finish() closes the still-open spans
+        let roots = recorder.finish();
+        assert_eq!(roots.len(), 1);
+
+        let engine = &roots[0];
+        assert_eq!(engine.kind, SpanKind::Engine);
+        assert_eq!(engine.children.len(), 2);
+
+        let batch = engine.find("batch 0").unwrap();
+        assert_eq!(batch.resource_count, Some(50));
+        assert!(batch.duration_us >= 2000);
+        assert!(engine.duration_us >= engine.children[0].duration_us);
+        assert!(engine.children[1].start_us >= batch.start_us + batch.duration_us);
+    }
+
+    #[test]
+    fn test_exit_without_open_span() {
+        let mut recorder = SpanRecorder::new();
+        assert!(!recorder.exit());
+        assert!(recorder.finish().is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+// Shared read-only view of a scan's resources and cost estimates
+
+use crate::engines::shared::models::{CostEstimate, ResourceChange};
+use std::collections::HashMap;
+
+/// Immutable store of resource changes and their estimates, built once per
+/// scan and borrowed by every engine stage (safe to share across threads)
+#[derive(Debug, Clone, Default)]
+pub struct ResourceArena {
+    changes: Vec<ResourceChange>,
+    estimates: Vec<CostEstimate>,
+    change_index: HashMap<String, usize>,
+    estimate_index: HashMap<String, usize>,
+}
+
+impl ResourceArena {
+    pub fn new(changes: Vec<ResourceChange>, estimates: Vec<CostEstimate>) -> Self {
+        let change_index = changes
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.resource_id.clone(), i))
+            .collect();
+        let estimate_index = estimates
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.resource_id.clone(), i))
+            .collect();
+
+        Self {
+            changes,
+            estimates,
+            change_index,
+            estimate_index,
+        }
+    }
+
+    pub fn changes(&self) -> &[ResourceChange] {
+        &self.changes
+    }
+
+    pub fn estimates(&self) -> &[CostEstimate] {
+        &self.estimates
+    }
+
+    pub fn change(&self, resource_id: &str) -> Option<&ResourceChange> {
+        self.change_index
+            .get(resource_id)
+            .map(|&i| &self.changes[i])
+    }
+
+    pub fn estimate(&self, resource_id: &str) -> Option<&CostEstimate> {
+        self.estimate_index
+            .get(resource_id)
+            .map(|&i| &self.estimates[i])
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Sum of estimated monthly costs
+    pub fn total_monthly_cost(&self) -> f64 {
+        // Folding from +0.0 keeps an empty (e.g. fully filtered) plan at 0.0
+        self.estimates
+            .iter()
+            .fold(0.0, |total, e| total + e.monthly_cost)
+    }
+
+    /// Monthly cost for a resource: its estimate, else the cost on the change
+    pub fn monthly_cost(&self, resource_id: &str) -> f64 {
+        self.estimate(resource_id)
+            .map(|e| e.monthly_cost)
+            .or_else(|| self.change(resource_id).and_then(|c| c.monthly_cost))
+            .unwrap_or(0.0)
+    }
+
+    /// (resource_id, cost, confidence) tuples for detection analysis
+    pub fn cost_tuples(&self) -> Vec<(String, f64, f64)> {
+        self.estimates
+            .iter()
+            .map(|e| (e.resource_id.clone(), e.monthly_cost, e.confidence_score))
+            .collect()
+    }
+
+    /// (address, type, tags, cost) tuples for the grouping engine
+    pub fn grouping_tuples(&self) -> Vec<(String, String, HashMap<String, String>, f64)> {
+        self.changes
+            .iter()
+            .map(|c| {
+                (
+                    c.resource_id.clone(),
+                    c.resource_type.clone(),
+                    c.tags.clone(),
+                    self.monthly_cost(&c.resource_id),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,134 @@
+// Prediction stage shared by the CLI scan and the library client
+//
+// A licensed edition prices through the Premium engine, which also sees the
+// plan-wide usage profile and pricing context; otherwise the static
+// heuristics price each change on its own.
+
+use crate::edition::EditionContext;
+use crate::engines::prediction::{PlanPricing, PredictionEngine};
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::models::{CostEstimate, ResourceChange};
+use crate::pro_engine::{ProEngineRequest, ProEngineResponse};
+
+/// Estimates from the Premium engine when licensed, static heuristics otherwise
+pub fn predict_estimates(
+    edition: &EditionContext,
+    changes: &[ResourceChange],
+    pricing: &PlanPricing,
+) -> Result<Vec<CostEstimate>, CostPilotError> {
+    match edition.pro.as_ref() {
+        Some(pro) => {
+            // Premium: use ProEngine
+            let response = pro
+                .execute(ProEngineRequest::Predict {
+                    changes: changes.to_vec(),
+                    usage: pricing.usage.clone(),
+                    context: pricing.context.clone(),
+                })
+                .map_err(|e| {
+                    CostPilotError::new("E_PRO_SCAN", ErrorCategory::PredictionError, e)
+                })?;
+            match response {
+                ProEngineResponse::Predict(estimates) => Ok(estimates),
+                _ => Err(CostPilotError::new(
+                    "E_PRO_SCAN",
+                    ErrorCategory::PredictionError,
+                    "Unexpected response type",
+                )),
+            }
+        }
+        None => {
+            // Free: use static prediction
+            PredictionEngine::predict_static_with(changes, &pricing.cluster)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edition::pro_handle::ProEngineHandle;
+    use crate::edition::EditionContext;
+    use crate::engines::prediction::{ClusterPricing, UsageProfile};
+    use crate::engines::shared::models::ChangeAction;
+    use crate::pro_engine::NativeProEngine;
+    use serde_json::Value;
+    use std::path::PathBuf;
+
+    fn premium() -> EditionContext {
+        EditionContext {
+            pro: Some(ProEngineHandle::with_executor(
+                PathBuf::from("pro_engine.wasm"),
+                None,
+                Box::new(NativeProEngine::new()),
+            )),
+            ..EditionContext::premium_for_test()
+        }
+    }
+
+    fn change(resource_type: &str, name: &str, config: Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_type(resource_type.to_string())
+            .resource_id(format!("{}.{}", resource_type, name))
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    #[test]
+    fn test_premium_prices_cloudwatch_from_usage() {
+        let changes = vec![
+            change(
+                "aws_cloudwatch_log_group",
+                "app",
+                serde_json::json!({ "name": "/app", "retention_in_days": 30 }),
+            ),
+            change(
+                "aws_cloudwatch_metric_alarm",
+                "cpu",
+                serde_json::json!({ "metric_name": "CPUUtilization", "period": 300 }),
+            ),
+        ];
+        let usage = UsageProfile::default().with(
+            "aws_cloudwatch_log_group.app",
+            "monthly_ingestion_gb",
+            100.0,
+        );
+        let pricing = PlanPricing::new(ClusterPricing::default(), usage, &changes);
+
+        let estimates = predict_estimates(&premium(), &changes, &pricing).unwrap();
+
+        // 100 GB × $0.50 ingested, 30 days of it stored at $0.03/GB-month
+        let expected = 50.0 + 100.0 * 30.0 / (365.0 / 12.0) * 0.03;
+        assert!((estimates[0].monthly_cost - expected).abs() < 1e-6);
+        assert!((estimates[1].monthly_cost - 0.10).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_premium_prices_fargate_services_from_plan_task_definitions() {
+        let task_definition = change(
+            "aws_ecs_task_definition",
+            "api",
+            serde_json::json!({ "family": "api", "cpu": "512", "memory": "1024" }),
+        );
+        let service = change(
+            "aws_ecs_service",
+            "api",
+            serde_json::json!({
+                "launch_type": "FARGATE",
+                "desired_count": 2,
+                "task_definition": "api"
+            }),
+        );
+        let changes = vec![task_definition, service.clone()];
+        let pricing =
+            PlanPricing::new(ClusterPricing::default(), UsageProfile::default(), &changes);
+
+        // The service is priced in a batch without its task definition
+        let estimates =
+            predict_estimates(&premium(), std::slice::from_ref(&service), &pricing).unwrap();
+
+        let expected = 2.0 * (0.5 * 0.000011244 + 0.000001235) * 730.0 * 3600.0;
+        assert!((estimates[0].monthly_cost - expected).abs() < 1e-6);
+    }
+}
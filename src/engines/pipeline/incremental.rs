@@ -0,0 +1,649 @@
+// Incremental workspace - re-parse only the IaC sources that changed
+//
+// The workspace is split into units: a directory of `*.tf` files (one
+// Terraform module) or one synthesized CDK stack template. Directories the
+// root calls as local modules belong to the root unit, which resolves them
+// with their variables like `terraform plan` would, using the workspace's
+// tfvars and any `-var-file`s. Each refresh
+// compares file stamps, re-parses and re-predicts only the affected units,
+// then re-runs detection over the whole resource set and reports the cost
+// delta against the previous refresh.
+
+use crate::engines::detection::terraform::modules::normalize;
+use crate::engines::detection::terraform::tfvars::is_var_file;
+use crate::engines::detection::terraform::{
+    config_to_resource_changes, load_terraform_directory, local_module_dirs,
+    parse_terraform_config, TerraformContext,
+};
+use crate::engines::detection::DetectionEngine;
+use crate::engines::prediction::PredictionEngine;
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+
+/// Suffix of synthesized CDK stack templates
+const CDK_TEMPLATE_SUFFIX: &str = ".template.json";
+
+/// Predicts estimates for the resources of re-parsed units
+pub type Predictor = Box<dyn Fn(&[ResourceChange]) -> Result<Vec<CostEstimate>> + Send>;
+
+/// Kind of IaC source tree being watched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// Directory tree of `*.tf` files; each directory is a module, except
+    /// local modules called from the root, which are resolved into it
+    Terraform,
+
+    /// `cdk.out` directory; each `*.template.json` is a stack
+    Cdk,
+}
+
+impl SourceKind {
+    /// CDK if the root looks like a synth output, Terraform otherwise
+    pub fn detect(root: &Path) -> Self {
+        let is_cdk_out = root.file_name().is_some_and(|name| name == "cdk.out")
+            || root.join("manifest.json").exists() && root.join("tree.json").exists();
+        if is_cdk_out {
+            SourceKind::Cdk
+        } else {
+            SourceKind::Terraform
+        }
+    }
+}
+
+/// Change in monthly cost of one resource between refreshes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceDelta {
+    pub resource_id: String,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+}
+
+impl ResourceDelta {
+    pub fn change(&self) -> f64 {
+        self.after.unwrap_or(0.0) - self.before.unwrap_or(0.0)
+    }
+}
+
+/// Result of a refresh that found changes
+#[derive(Debug, Clone)]
+pub struct WorkspaceUpdate {
+    /// Units that were re-parsed, in order
+    pub reparsed: Vec<String>,
+
+    /// Units whose files disappeared
+    pub removed: Vec<String>,
+
+    pub changes: Vec<ResourceChange>,
+    pub estimates: Vec<CostEstimate>,
+    pub detections: Vec<Detection>,
+
+    /// Parse or prediction failures, by unit
+    pub errors: BTreeMap<String, String>,
+
+    pub previous_monthly_cost: f64,
+    pub total_monthly_cost: f64,
+
+    /// Resources whose cost changed, largest change first
+    pub deltas: Vec<ResourceDelta>,
+
+    pub elapsed_ms: u64,
+}
+
+impl WorkspaceUpdate {
+    pub fn cost_change(&self) -> f64 {
+        self.total_monthly_cost - self.previous_monthly_cost
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+#[derive(Debug, Default)]
+struct UnitState {
+    changes: Vec<ResourceChange>,
+    estimates: Vec<CostEstimate>,
+    error: Option<String>,
+}
+
+/// IaC source tree that tracks per-unit parse and prediction results
+pub struct IncrementalWorkspace {
+    root: PathBuf,
+    kind: SourceKind,
+    detection: DetectionEngine,
+    predictor: Predictor,
+    stamps: BTreeMap<PathBuf, FileStamp>,
+    units: BTreeMap<String, UnitState>,
+    /// Local module directories called from the root
+    module_dirs: BTreeSet<PathBuf>,
+    /// Terraform workspace, instead of the selected one
+    workspace: Option<String>,
+    /// Variable files applied after the automatically loaded ones
+    var_files: Vec<PathBuf>,
+    total_monthly_cost: f64,
+}
+
+impl IncrementalWorkspace {
+    /// Watch `root` using static prediction
+    pub fn new(root: impl Into<PathBuf>, kind: SourceKind) -> Self {
+        Self {
+            root: root.into(),
+            kind,
+            detection: DetectionEngine::new(),
+            predictor: Box::new(PredictionEngine::predict_static),
+            stamps: BTreeMap::new(),
+            units: BTreeMap::new(),
+            module_dirs: BTreeSet::new(),
+            workspace: None,
+            var_files: Vec::new(),
+            total_monthly_cost: 0.0,
+        }
+    }
+
+    pub fn with_predictor(mut self, predictor: Predictor) -> Self {
+        self.predictor = predictor;
+        self
+    }
+
+    pub fn with_detection_engine(mut self, detection: DetectionEngine) -> Self {
+        self.detection = detection;
+        self
+    }
+
+    /// Evaluate Terraform sources for `workspace` instead of the selected one
+    pub fn with_terraform_workspace(mut self, workspace: impl Into<String>) -> Self {
+        self.workspace = Some(workspace.into());
+        self
+    }
+
+    /// Apply `-var-file`s on top of `terraform.tfvars` and `*.auto.tfvars`
+    pub fn with_var_files(mut self, var_files: Vec<PathBuf>) -> Self {
+        self.var_files = var_files;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn kind(&self) -> SourceKind {
+        self.kind
+    }
+
+    /// Re-parse and re-predict every unit on the next refresh, e.g. after
+    /// the prediction engine changed
+    pub fn invalidate(&mut self) {
+        self.stamps.clear();
+    }
+
+    /// Re-parse changed units; `None` if nothing changed since the last call
+    pub fn refresh(&mut self) -> Result<Option<WorkspaceUpdate>> {
+        let start = Instant::now();
+
+        let files = self.discover()?;
+        if self.kind == SourceKind::Terraform && files != self.stamps {
+            self.module_dirs = local_module_dirs(&self.root).into_iter().collect();
+        }
+
+        let mut affected = BTreeSet::new();
+        for (path, stamp) in &files {
+            if self.stamps.get(path) != Some(stamp) {
+                affected.insert(self.unit_of(path));
+            }
+        }
+        for path in self.stamps.keys() {
+            if !files.contains_key(path) {
+                affected.insert(self.unit_of(path));
+            }
+        }
+        // Units whose files moved into the root after a module call was added
+        for unit in self.units.keys() {
+            if !files.keys().any(|path| self.unit_of(path) == *unit) {
+                affected.insert(unit.clone());
+            }
+        }
+        if affected.is_empty() {
+            return Ok(None);
+        }
+
+        let previous: HashMap<String, f64> = self
+            .units
+            .values()
+            .flat_map(|unit| &unit.estimates)
+            .map(|e| (e.resource_id.clone(), e.monthly_cost))
+            .collect();
+
+        let mut reparsed = Vec::new();
+        let mut removed = Vec::new();
+        for unit in affected {
+            let unit_files: Vec<&PathBuf> = files
+                .keys()
+                .filter(|path| self.unit_of(path) == unit)
+                .collect();
+            if unit_files.is_empty() {
+                self.units.remove(&unit);
+                removed.push(unit);
+                continue;
+            }
+            let state = self.load_unit(&unit, &unit_files);
+            self.units.insert(unit.clone(), state);
+            reparsed.push(unit);
+        }
+        self.stamps = files;
+
+        let changes: Vec<ResourceChange> = self
+            .units
+            .values()
+            .flat_map(|unit| unit.changes.iter().cloned())
+            .collect();
+        let estimates: Vec<CostEstimate> = self
+            .units
+            .values()
+            .flat_map(|unit| unit.estimates.iter().cloned())
+            .collect();
+        let cost_tuples: Vec<(String, f64, f64)> = estimates
+            .iter()
+            .map(|e| (e.resource_id.clone(), e.monthly_cost, e.confidence_score))
+            .collect();
+        let detections = self.detection.analyze_changes(&changes, &cost_tuples)?;
+
+        let current: HashMap<String, f64> = estimates
+            .iter()
+            .map(|e| (e.resource_id.clone(), e.monthly_cost))
+            .collect();
+        let mut deltas: Vec<ResourceDelta> = previous
+            .keys()
+            .chain(current.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|id| ResourceDelta {
+                resource_id: id.clone(),
+                before: previous.get(id).copied(),
+                after: current.get(id).copied(),
+            })
+            .filter(|delta| delta.before != delta.after)
+            .collect();
+        deltas.sort_by(|a, b| {
+            b.change()
+                .abs()
+                .total_cmp(&a.change().abs())
+                .then_with(|| a.resource_id.cmp(&b.resource_id))
+        });
+
+        let previous_monthly_cost = self.total_monthly_cost;
+        self.total_monthly_cost = current.values().sum();
+
+        Ok(Some(WorkspaceUpdate {
+            reparsed,
+            removed,
+            changes,
+            estimates,
+            detections,
+            errors: self
+                .units
+                .iter()
+                .filter_map(|(id, unit)| unit.error.clone().map(|e| (id.clone(), e)))
+                .collect(),
+            previous_monthly_cost,
+            total_monthly_cost: self.total_monthly_cost,
+            deltas,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        }))
+    }
+
+    /// Source files and their stamps
+    fn discover(&self) -> Result<BTreeMap<PathBuf, FileStamp>> {
+        let mut files = BTreeMap::new();
+        match self.kind {
+            SourceKind::Terraform => {
+                self.discover_tf(&self.root, &mut files)?;
+                for path in &self.var_files {
+                    insert_stamp(&mut files, path.clone());
+                }
+            }
+            SourceKind::Cdk => {
+                for entry in read_dir(&self.root)? {
+                    let path = entry.path();
+                    let is_template = path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.ends_with(CDK_TEMPLATE_SUFFIX));
+                    if is_template {
+                        insert_stamp(&mut files, path);
+                    }
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    fn discover_tf(&self, dir: &Path, files: &mut BTreeMap<PathBuf, FileStamp>) -> Result<()> {
+        for entry in read_dir(dir)? {
+            let path = entry.path();
+            // Skip .terraform, .git and other tool directories
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                self.discover_tf(&path, files)?;
+            } else if path.extension().is_some_and(|ext| ext == "tf") || is_var_file(&path) {
+                insert_stamp(files, path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Unit a file belongs to: its module directory or CDK stack name
+    fn unit_of(&self, path: &Path) -> String {
+        match self.kind {
+            SourceKind::Terraform => {
+                let dir = path.parent().unwrap_or(&self.root);
+                if is_var_file(path) || self.module_dirs.contains(&normalize(dir)) {
+                    return ".".to_string();
+                }
+                let relative = dir.strip_prefix(&self.root).unwrap_or(dir);
+                if relative.as_os_str().is_empty() {
+                    ".".to_string()
+                } else {
+                    relative.to_string_lossy().replace('\\', "/")
+                }
+            }
+            SourceKind::Cdk => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.trim_end_matches(CDK_TEMPLATE_SUFFIX).to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn load_unit(&self, unit: &str, files: &[&PathBuf]) -> UnitState {
+        let changes = match self.parse_unit(unit, files) {
+            Ok(changes) => changes,
+            Err(e) => {
+                // Keep the last good resources so a typo doesn't blank the dashboard
+                let mut state = self.previous_state(unit);
+                state.error = Some(e.message);
+                return state;
+            }
+        };
+
+        match (self.predictor)(&changes) {
+            Ok(estimates) => UnitState {
+                changes,
+                estimates,
+                error: None,
+            },
+            Err(e) => UnitState {
+                changes,
+                estimates: Vec::new(),
+                error: Some(e.message),
+            },
+        }
+    }
+
+    fn previous_state(&self, unit: &str) -> UnitState {
+        self.units
+            .get(unit)
+            .map(|state| UnitState {
+                changes: state.changes.clone(),
+                estimates: state.estimates.clone(),
+                error: None,
+            })
+            .unwrap_or_default()
+    }
+
+    fn parse_unit(&self, unit: &str, files: &[&PathBuf]) -> Result<Vec<ResourceChange>> {
+        if self.kind == SourceKind::Terraform && unit == "." {
+            let context =
+                TerraformContext::load(&self.root, self.workspace.as_deref(), &self.var_files)?;
+            return load_terraform_directory(&self.root, &context);
+        }
+
+        let mut changes = Vec::new();
+        for path in files {
+            let content = std::fs::read_to_string(path).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            match self.kind {
+                SourceKind::Terraform => {
+                    let config = parse_terraform_config(&content)?;
+                    changes.extend(config_to_resource_changes(
+                        &config,
+                        module_path(unit).as_deref(),
+                    ));
+                }
+                SourceKind::Cdk => {
+                    changes.extend(
+                        self.detection
+                            .detect_from_cdk_template_json(&content, unit)?,
+                    );
+                }
+            }
+        }
+        Ok(changes)
+    }
+}
+
+/// Module address for a unit directory: `modules/vpc` → `module.vpc`
+fn module_path(unit: &str) -> Option<String> {
+    if unit == "." {
+        return None;
+    }
+    let name = unit.rsplit('/').next().unwrap_or(unit);
+    Some(format!("module.{}", name))
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<std::fs::DirEntry>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        CostPilotError::io_error(format!("Failed to read {}: {}", dir.display(), e))
+    })?;
+    Ok(entries.filter_map(|entry| entry.ok()).collect())
+}
+
+fn insert_stamp(files: &mut BTreeMap<PathBuf, FileStamp>, path: PathBuf) {
+    if let Ok(meta) = std::fs::metadata(&path) {
+        files.insert(
+            path,
+            FileStamp {
+                modified: meta.modified().ok(),
+                len: meta.len(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn instance(name: &str, instance_type: &str) -> String {
+        format!(
+            "resource \"aws_instance\" \"{}\" {{\n  instance_type = \"{}\"\n}}\n",
+            name, instance_type
+        )
+    }
+
+    #[test]
+    fn test_only_changed_modules_are_reparsed() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "main.tf", &instance("root", "t3.micro"));
+        write(
+            dir.path(),
+            "modules/web/main.tf",
+            &instance("app", "m5.large"),
+        );
+        write(dir.path(), ".terraform/ignored.tf", "not hcl {");
+
+        let mut workspace = IncrementalWorkspace::new(dir.path(), SourceKind::Terraform);
+        let initial = workspace.refresh().unwrap().unwrap();
+        assert_eq!(initial.reparsed, vec![".", "modules/web"]);
+        assert_eq!(initial.changes.len(), 2);
+        assert!(initial.errors.is_empty());
+        assert!(initial
+            .changes
+            .iter()
+            .any(|c| c.resource_id == "module.web.aws_instance.app"));
+
+        assert!(workspace.refresh().unwrap().is_none());
+
+        write(
+            dir.path(),
+            "modules/web/extra.tf",
+            "resource \"aws_s3_bucket\" \"assets\" {}\n",
+        );
+        let update = workspace.refresh().unwrap().unwrap();
+        assert_eq!(update.reparsed, vec!["modules/web"]);
+        assert_eq!(update.changes.len(), 3);
+        assert!(update
+            .deltas
+            .iter()
+            .all(|d| d.resource_id == "module.web.aws_s3_bucket.assets"));
+    }
+
+    #[test]
+    fn test_cost_delta_and_removed_module() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "main.tf", &instance("root", "t3.micro"));
+        write(
+            dir.path(),
+            "modules/web/main.tf",
+            &instance("app", "m5.large"),
+        );
+
+        let mut workspace = IncrementalWorkspace::new(dir.path(), SourceKind::Terraform)
+            .with_predictor(Box::new(|changes| {
+                Ok(changes
+                    .iter()
+                    .map(|c| {
+                        CostEstimate::builder()
+                            .resource_id(c.resource_id.clone())
+                            .monthly_cost(100.0)
+                            .build()
+                    })
+                    .collect())
+            }));
+        let initial = workspace.refresh().unwrap().unwrap();
+        assert_eq!(initial.total_monthly_cost, 200.0);
+        assert_eq!(initial.cost_change(), 200.0);
+
+        std::fs::remove_dir_all(dir.path().join("modules")).unwrap();
+        let update = workspace.refresh().unwrap().unwrap();
+        assert_eq!(update.removed, vec!["modules/web"]);
+        assert_eq!(update.total_monthly_cost, 100.0);
+        assert_eq!(update.cost_change(), -100.0);
+        assert_eq!(
+            update.deltas,
+            vec![ResourceDelta {
+                resource_id: "module.web.aws_instance.app".to_string(),
+                before: Some(100.0),
+                after: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_keeps_last_good_resources() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "main.tf", &instance("root", "t3.micro"));
+
+        let mut workspace = IncrementalWorkspace::new(dir.path(), SourceKind::Terraform);
+        workspace.refresh().unwrap().unwrap();
+
+        write(
+            dir.path(),
+            "main.tf",
+            "resource \"aws_instance\" \"root\" {",
+        );
+        let update = workspace.refresh().unwrap().unwrap();
+        assert_eq!(update.changes.len(), 1);
+        assert!(update.errors.contains_key("."));
+        assert!(update.deltas.is_empty());
+    }
+
+    #[test]
+    fn test_local_module_call_moves_into_root_unit() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "main.tf", &instance("root", "t3.micro"));
+        write(
+            dir.path(),
+            "modules/web-service/main.tf",
+            &instance("app", "${var.size}"),
+        );
+
+        let mut workspace = IncrementalWorkspace::new(dir.path(), SourceKind::Terraform);
+        let initial = workspace.refresh().unwrap().unwrap();
+        assert!(initial
+            .changes
+            .iter()
+            .any(|c| c.resource_id == "module.web-service.aws_instance.app"));
+
+        write(
+            dir.path(),
+            "calls.tf",
+            "module \"web\" {\n  source = \"./modules/web-service\"\n  size   = \"m5.large\"\n}\n",
+        );
+        let update = workspace.refresh().unwrap().unwrap();
+        assert_eq!(update.reparsed, vec!["."]);
+        assert_eq!(update.removed, vec!["modules/web-service"]);
+        assert_eq!(update.changes.len(), 2);
+        let app = update
+            .changes
+            .iter()
+            .find(|c| c.resource_id == "module.web.aws_instance.app")
+            .unwrap();
+        assert_eq!(
+            app.new_config.as_ref().unwrap()["instance_type"],
+            "m5.large"
+        );
+
+        write(
+            dir.path(),
+            "modules/web-service/main.tf",
+            &instance("app", "m5.xlarge"),
+        );
+        let update = workspace.refresh().unwrap().unwrap();
+        assert_eq!(update.reparsed, vec!["."]);
+    }
+
+    #[test]
+    fn test_var_files_drive_conditional_resources() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "main.tf",
+            "variable \"enable_nat\" {\n  default = false\n}\n\nresource \"aws_nat_gateway\" \"main\" {\n  count = var.enable_nat ? 1 : 0\n}\n",
+        );
+        let prod = dir.path().join("env/prod.tfvars");
+        write(dir.path(), "env/prod.tfvars", "enable_nat = true\n");
+
+        let mut workspace = IncrementalWorkspace::new(dir.path(), SourceKind::Terraform);
+        assert!(workspace.refresh().unwrap().unwrap().changes.is_empty());
+
+        let mut workspace =
+            IncrementalWorkspace::new(dir.path(), SourceKind::Terraform).with_var_files(vec![prod]);
+        let initial = workspace.refresh().unwrap().unwrap();
+        assert_eq!(initial.reparsed, vec!["."]);
+        assert_eq!(initial.changes[0].resource_id, "aws_nat_gateway.main[0]");
+
+        write(dir.path(), "env/prod.tfvars", "enable_nat = false\n");
+        let update = workspace.refresh().unwrap().unwrap();
+        assert_eq!(update.reparsed, vec!["."]);
+        assert!(update.changes.is_empty());
+    }
+}
@@ -0,0 +1,17 @@
+// Scan pipeline orchestration across engines
+
+pub mod arena;
+pub mod estimates;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod incremental;
+pub mod scan_pipeline;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod stacks;
+
+pub use arena::ResourceArena;
+pub use estimates::predict_estimates;
+#[cfg(not(target_arch = "wasm32"))]
+pub use incremental::{IncrementalWorkspace, ResourceDelta, SourceKind, WorkspaceUpdate};
+pub use scan_pipeline::{ScanPipeline, ScanPipelineOutput};
+#[cfg(not(target_arch = "wasm32"))]
+pub use stacks::{discover_stacks, Stack, StackResult, StackScanner, DEFAULT_STACK_PLAN};
@@ -0,0 +1,474 @@
+// Post-prediction scan stages, run concurrently where independent
+//
+// Once estimates exist, analysis (→ explain), mapping and grouping only read
+// the shared `ResourceArena`, so they run side by side on the rayon pool.
+// Results are returned in a fixed order, keeping output deterministic.
+
+use super::arena::ResourceArena;
+use crate::engines::detection::DetectionEngine;
+use crate::engines::explain::explain_engine::Explanation;
+use crate::engines::explain::ExplainEngine;
+use crate::engines::grouping::{ComprehensiveReport, GroupingEngine};
+use crate::engines::mapping::{DependencyGraph, GraphBuilder, GraphConfig};
+use crate::engines::shared::cancel::CancelToken;
+use crate::engines::shared::error_model::Result;
+use crate::engines::shared::models::Detection;
+use crate::engines::shared::progress::{map_batches, Phase, ProgressReporter};
+use crate::engines::shared::result_cache::ResultCache;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+/// Detections explained between cancellation checks and progress reports
+const EXPLAIN_BATCH: usize = 256;
+
+/// Outputs of the post-prediction stages
+#[derive(Debug, Default)]
+pub struct ScanPipelineOutput {
+    pub detections: Vec<Detection>,
+
+    /// One explanation per detection (explain stage only)
+    pub explanations: Vec<Explanation>,
+
+    /// Dependency graph (mapping stage only)
+    pub graph: Option<DependencyGraph>,
+
+    /// Module/service/environment grouping (grouping stage only)
+    pub grouping: Option<ComprehensiveReport>,
+
+    /// Wall time of each stage that ran
+    pub stage_durations_ms: BTreeMap<String, u64>,
+}
+
+struct AnalysisStage {
+    detections: Vec<Detection>,
+    explanations: Vec<Explanation>,
+    analysis_ms: u64,
+    explain_ms: Option<u64>,
+}
+
+/// Runs analysis, explain, mapping and grouping over a shared arena
+pub struct ScanPipeline {
+    detection: DetectionEngine,
+    grouping: GroupingEngine,
+    graph_config: GraphConfig,
+    cache: Option<Arc<ResultCache>>,
+    cancel: CancelToken,
+    progress: ProgressReporter,
+    explain: bool,
+    mapping: bool,
+    group: bool,
+    parallel: bool,
+}
+
+impl ScanPipeline {
+    /// Create pipeline running analysis only, in parallel mode
+    pub fn new() -> Self {
+        Self {
+            detection: DetectionEngine::new(),
+            grouping: GroupingEngine::new(),
+            graph_config: GraphConfig::default(),
+            cache: None,
+            cancel: CancelToken::new(),
+            progress: ProgressReporter::default(),
+            explain: false,
+            mapping: false,
+            group: false,
+            parallel: true,
+        }
+    }
+
+    pub fn with_detection_engine(mut self, detection: DetectionEngine) -> Self {
+        self.detection = detection;
+        self
+    }
+
+    pub fn with_grouping_engine(mut self, grouping: GroupingEngine) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    pub fn with_graph_config(mut self, config: GraphConfig) -> Self {
+        self.graph_config = config;
+        self
+    }
+
+    /// Reuse per-resource detections from a result cache
+    pub fn with_result_cache(mut self, cache: Arc<ResultCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Stop at the next stage or batch boundary once the token is cancelled
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = token;
+        self
+    }
+
+    /// Report each stage's progress to `progress`
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Explain each detection after analysis
+    pub fn with_explain(mut self, enabled: bool) -> Self {
+        self.explain = enabled;
+        self
+    }
+
+    /// Build the dependency graph
+    pub fn with_mapping(mut self, enabled: bool) -> Self {
+        self.mapping = enabled;
+        self
+    }
+
+    /// Build the cost grouping report
+    pub fn with_grouping(mut self, enabled: bool) -> Self {
+        self.group = enabled;
+        self
+    }
+
+    /// Run stages one after another on the calling thread
+    pub fn sequential(mut self) -> Self {
+        self.parallel = false;
+        self
+    }
+
+    /// Run all enabled stages over the arena
+    pub fn run(&self, arena: &ResourceArena) -> Result<ScanPipelineOutput> {
+        self.cancel.check("Scan pipeline")?;
+        let (analysis, (graph, grouping)) = self.join(
+            || self.run_analysis(arena),
+            || {
+                self.join(
+                    || self.mapping.then(|| timed(|| self.run_mapping(arena))),
+                    || self.group.then(|| timed(|| self.run_grouping(arena))),
+                )
+            },
+        );
+
+        let mut output = ScanPipelineOutput::default();
+
+        let analysis = analysis?;
+        output.detections = analysis.detections;
+        output.explanations = analysis.explanations;
+        output
+            .stage_durations_ms
+            .insert("analysis".to_string(), analysis.analysis_ms);
+        if let Some(ms) = analysis.explain_ms {
+            output.stage_durations_ms.insert("explain".to_string(), ms);
+        }
+
+        if let Some((graph, ms)) = graph {
+            output.graph = Some(graph?);
+            output.stage_durations_ms.insert("mapping".to_string(), ms);
+        }
+        if let Some((grouping, ms)) = grouping {
+            output.grouping = Some(grouping?);
+            output.stage_durations_ms.insert("grouping".to_string(), ms);
+        }
+
+        Ok(output)
+    }
+
+    /// Analysis followed by explain (explain needs the detections)
+    fn run_analysis(&self, arena: &ResourceArena) -> Result<AnalysisStage> {
+        let (detections, analysis_ms) = timed(|| {
+            self.progress.step(Phase::Analysis, || match &self.cache {
+                Some(cache) => self.detection.analyze_changes_cached(
+                    arena.changes(),
+                    &arena.cost_tuples(),
+                    cache,
+                ),
+                None => self
+                    .detection
+                    .analyze_changes(arena.changes(), &arena.cost_tuples()),
+            })
+        });
+        let detections = detections?;
+
+        let (explanations, explain_ms) = if self.explain {
+            let (explanations, ms) = timed(|| {
+                map_batches(
+                    &detections,
+                    EXPLAIN_BATCH,
+                    Phase::Explain,
+                    &self.cancel,
+                    &self.progress,
+                    |batch| Ok(self.explain_all(arena, batch)),
+                )
+            });
+            (explanations?, Some(ms))
+        } else {
+            (Vec::new(), None)
+        };
+
+        Ok(AnalysisStage {
+            detections,
+            explanations,
+            analysis_ms,
+            explain_ms,
+        })
+    }
+
+    fn explain_all(&self, arena: &ResourceArena, detections: &[Detection]) -> Vec<Explanation> {
+        let explain = |detection: &Detection| {
+            arena.change(&detection.resource_id).map(|change| {
+                ExplainEngine::explain(
+                    detection,
+                    change,
+                    arena.estimate(&detection.resource_id),
+                    None,
+                )
+            })
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.parallel {
+            return detections.par_iter().filter_map(explain).collect();
+        }
+
+        detections.iter().filter_map(explain).collect()
+    }
+
+    fn run_mapping(&self, arena: &ResourceArena) -> Result<DependencyGraph> {
+        self.cancel.check("Mapping stage")?;
+        self.progress.step(Phase::Mapping, || {
+            GraphBuilder::with_config(self.graph_config.clone()).build_graph(arena.changes())
+        })
+    }
+
+    fn run_grouping(&self, arena: &ResourceArena) -> Result<ComprehensiveReport> {
+        self.cancel.check("Grouping stage")?;
+        Ok(self.progress.step(Phase::Grouping, || {
+            self.grouping
+                .generate_comprehensive_report(&arena.grouping_tuples())
+        }))
+    }
+
+    fn join<A, B, RA, RB>(&self, a: A, b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA + Send,
+        B: FnOnce() -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.parallel {
+            return rayon::join(a, b);
+        }
+
+        (a(), b())
+    }
+}
+
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+impl ScanPipeline {
+    /// Run the pipeline on a worker thread; stops at the next batch boundary
+    /// once `token` is cancelled or the returned future is dropped
+    pub fn run_async(
+        self,
+        arena: Arc<ResourceArena>,
+        token: CancelToken,
+    ) -> impl std::future::Future<Output = Result<ScanPipelineOutput>> + Send {
+        crate::engines::shared::worker::run_blocking(token, move |token| {
+            self.with_cancel_token(token.clone()).run(&arena)
+        })
+    }
+}
+
+impl Default for ScanPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, u64) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed().as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::{ChangeAction, CostEstimate, ResourceChange};
+    use std::collections::HashMap;
+
+    fn arena(count: usize) -> ResourceArena {
+        let mut changes = Vec::new();
+        let mut estimates = Vec::new();
+        for i in 0..count {
+            let id = format!("module.app{}.aws_instance.web{}", i % 3, i);
+            changes.push(ResourceChange {
+                resource_id: id.clone(),
+                resource_type: "aws_instance".to_string(),
+                action: ChangeAction::Create,
+                module_path: Some(format!("module.app{}", i % 3)),
+                old_config: None,
+                new_config: Some(serde_json::json!({ "instance_type": "m5.4xlarge" })),
+                tags: HashMap::from([("team".to_string(), format!("team{}", i % 2))]),
+                monthly_cost: None,
+                config: None,
+                cost_impact: None,
+            });
+            estimates.push(CostEstimate {
+                resource_id: id,
+                monthly_cost: 560.0,
+                prediction_interval_low: 400.0,
+                prediction_interval_high: 700.0,
+                confidence_score: 0.9,
+                heuristic_reference: None,
+                cold_start_inference: false,
+                one_time: None,
+                breakdown: None,
+                hourly: None,
+                daily: None,
+                provenance: None,
+            });
+        }
+        ResourceArena::new(changes, estimates)
+    }
+
+    #[test]
+    fn test_arena_lookups() {
+        let arena = arena(4);
+        assert_eq!(arena.len(), 4);
+        assert!(arena.change("module.app1.aws_instance.web1").is_some());
+        assert_eq!(arena.monthly_cost("module.app1.aws_instance.web1"), 560.0);
+        assert_eq!(arena.monthly_cost("missing"), 0.0);
+        assert_eq!(arena.total_monthly_cost(), 2240.0);
+        assert_eq!(arena.grouping_tuples()[2].2["team"], "team0");
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let arena = arena(30);
+        let pipeline = || {
+            ScanPipeline::new()
+                .with_explain(true)
+                .with_mapping(true)
+                .with_grouping(true)
+        };
+
+        let parallel = pipeline().run(&arena).unwrap();
+        let sequential = pipeline().sequential().run(&arena).unwrap();
+
+        let ids = |output: &ScanPipelineOutput| -> Vec<String> {
+            output
+                .detections
+                .iter()
+                .map(|d| d.resource_id.clone())
+                .collect()
+        };
+        assert_eq!(ids(&parallel), ids(&sequential));
+        assert_eq!(parallel.explanations.len(), parallel.detections.len());
+        assert_eq!(parallel.explanations.len(), sequential.explanations.len());
+
+        let graph = parallel.graph.as_ref().unwrap();
+        assert_eq!(graph.nodes.len(), sequential.graph.unwrap().nodes.len());
+
+        let grouping = parallel.grouping.as_ref().unwrap();
+        assert_eq!(grouping.total_resources, 30);
+        assert_eq!(
+            grouping.module_groups.len(),
+            sequential.grouping.unwrap().module_groups.len()
+        );
+
+        for stage in ["analysis", "explain", "mapping", "grouping"] {
+            assert!(parallel.stage_durations_ms.contains_key(stage));
+        }
+    }
+
+    #[test]
+    fn test_cached_analysis_matches_uncached() {
+        use crate::engines::shared::result_cache::{CacheVersions, ResultCache};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache =
+            Arc::new(ResultCache::open(dir.path(), CacheVersions::new("test", "test")).unwrap());
+        let arena = arena(6);
+        let pipeline = ScanPipeline::new().with_result_cache(cache.clone());
+
+        let uncached = ScanPipeline::new().run(&arena).unwrap();
+        let cold = pipeline.run(&arena).unwrap();
+        let warm = pipeline.run(&arena).unwrap();
+
+        let rules = |output: &ScanPipelineOutput| -> Vec<(String, String)> {
+            output
+                .detections
+                .iter()
+                .map(|d| (d.resource_id.clone(), d.rule_id.clone()))
+                .collect()
+        };
+        assert_eq!(rules(&cold), rules(&uncached));
+        assert_eq!(rules(&warm), rules(&uncached));
+        assert_eq!(cache.stats().hits, 6);
+    }
+
+    #[test]
+    fn test_disabled_stages_are_skipped() {
+        let output = ScanPipeline::new().run(&arena(3)).unwrap();
+
+        assert!(output.explanations.is_empty());
+        assert!(output.graph.is_none());
+        assert!(output.grouping.is_none());
+        assert_eq!(output.stage_durations_ms.len(), 1);
+    }
+
+    #[test]
+    fn test_cancelled_pipeline_returns_error() {
+        let token = CancelToken::new();
+        token.cancel();
+        let err = ScanPipeline::new()
+            .with_cancel_token(token)
+            .run(&arena(3))
+            .unwrap_err();
+        assert_eq!(err.id, "CANCELLED");
+    }
+
+    #[test]
+    fn test_progress_reaches_every_enabled_stage() {
+        use crate::engines::shared::progress::Progress;
+        use std::sync::Mutex;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let progress = ProgressReporter::new(Arc::new(move |p: Progress| {
+            sink.lock().unwrap().push(p);
+        }));
+        ScanPipeline::new()
+            .with_explain(true)
+            .with_mapping(true)
+            .with_progress(progress)
+            .run(&arena(5))
+            .unwrap();
+
+        let seen = seen.lock().unwrap();
+        for phase in [Phase::Analysis, Phase::Explain, Phase::Mapping] {
+            assert!(
+                seen.iter().any(|p| p.phase == phase && p.is_done()),
+                "{:?}",
+                phase
+            );
+        }
+        assert!(!seen.iter().any(|p| p.phase == Phase::Grouping));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_run_async_matches_run() {
+        let arena = Arc::new(arena(9));
+        let pipeline = || ScanPipeline::new().with_mapping(true);
+
+        let sync = pipeline().run(&arena).unwrap();
+        let output =
+            futures::executor::block_on(pipeline().run_async(arena, CancelToken::new())).unwrap();
+        assert_eq!(output.detections.len(), sync.detections.len());
+        assert!(output.graph.is_some());
+    }
+}
@@ -0,0 +1,345 @@
+// Monorepo stacks - analyze many plans side by side
+//
+// A stack is a directory holding one plan JSON. Stacks are parsed, predicted
+// and analyzed on a bounded thread pool, each against its own performance
+// budget, and handed back one by one as they finish so output does not wait
+// for the slowest stack. The full result set is returned sorted by name.
+
+use crate::engines::detection::DetectionEngine;
+use crate::engines::performance::{
+    BudgetViolation, EngineBudget, PerformanceBudgets, PerformanceMetrics, PerformanceTracker,
+};
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+/// Plan file looked up in each stack directory by default
+pub const DEFAULT_STACK_PLAN: &str = "plan.json";
+
+/// Upper bound on the default number of concurrent stacks
+const MAX_DEFAULT_JOBS: usize = 8;
+
+/// One stack of a monorepo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack {
+    /// Directory relative to the monorepo root (`.` for the root itself)
+    pub name: String,
+    pub plan: PathBuf,
+}
+
+/// Outcome of analyzing one stack
+#[derive(Debug, Clone)]
+pub struct StackResult {
+    pub name: String,
+    pub plan: PathBuf,
+    pub changes: Vec<ResourceChange>,
+    pub estimates: Vec<CostEstimate>,
+    pub detections: Vec<Detection>,
+    pub monthly_cost: f64,
+    pub metrics: PerformanceMetrics,
+
+    /// Set when the stack ran past its budget
+    pub budget_violation: Option<BudgetViolation>,
+
+    /// Set when the stack could not be analyzed; the other fields are empty
+    pub error: Option<String>,
+}
+
+impl StackResult {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Find every stack under `root`: directories containing `plan_file`
+///
+/// Hidden directories (`.terraform`, `.git`, ...) are skipped. Stacks are
+/// returned sorted by name.
+pub fn discover_stacks(root: &Path, plan_file: &str) -> Result<Vec<Stack>> {
+    if !root.is_dir() {
+        return Err(CostPilotError::new(
+            "STACK_001",
+            ErrorCategory::FileSystemError,
+            format!("Stack root is not a directory: {}", root.display()),
+        ));
+    }
+
+    let mut stacks = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let plan = dir.join(plan_file);
+        if plan.is_file() {
+            stacks.push(Stack {
+                name: stack_name(root, &dir),
+                plan,
+            });
+        }
+
+        let entries = std::fs::read_dir(&dir).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", dir.display(), e))
+        })?;
+        for entry in entries.flatten() {
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if !hidden && entry.file_type().is_ok_and(|t| t.is_dir()) {
+                pending.push(entry.path());
+            }
+        }
+    }
+
+    stacks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(stacks)
+}
+
+fn stack_name(root: &Path, dir: &Path) -> String {
+    match dir.strip_prefix(root) {
+        Ok(rel) if !rel.as_os_str().is_empty() => rel.to_string_lossy().replace('\\', "/"),
+        _ => ".".to_string(),
+    }
+}
+
+/// Analyzes stacks concurrently on a bounded thread pool
+pub struct StackScanner {
+    jobs: usize,
+    budget: EngineBudget,
+    detection: DetectionEngine,
+}
+
+impl StackScanner {
+    /// Scanner running up to `jobs` stacks at once, each within the default
+    /// total scan budget
+    pub fn new(jobs: usize) -> Self {
+        Self {
+            jobs: jobs.max(1),
+            budget: PerformanceBudgets::default().total_scan,
+            detection: DetectionEngine::new(),
+        }
+    }
+
+    /// Available CPUs, capped so large machines don't open dozens of plans at once
+    pub fn default_jobs() -> usize {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(MAX_DEFAULT_JOBS)
+    }
+
+    /// Budget each stack is measured against
+    pub fn with_budget(mut self, budget: EngineBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn with_detection_engine(mut self, detection: DetectionEngine) -> Self {
+        self.detection = detection;
+        self
+    }
+
+    /// Analyze `stacks`, calling `on_result` on the calling thread as each
+    /// one finishes
+    ///
+    /// A stack that fails is reported with its error and does not stop the
+    /// others; an error from `on_result` stops reporting and is returned once
+    /// running stacks have finished. All results are returned sorted by name.
+    pub fn run<P, F>(
+        &self,
+        stacks: Vec<Stack>,
+        predict: &P,
+        mut on_result: F,
+    ) -> Result<Vec<StackResult>>
+    where
+        P: Fn(&[ResourceChange]) -> Result<Vec<CostEstimate>> + Sync,
+        F: FnMut(&StackResult) -> Result<()>,
+    {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs)
+            .thread_name(|i| format!("costpilot-stack-{}", i))
+            .build()
+            .map_err(|e| {
+                CostPilotError::new(
+                    "STACK_002",
+                    ErrorCategory::InternalError,
+                    format!("Failed to start stack worker pool: {}", e),
+                )
+            })?;
+
+        let (tx, rx) = mpsc::channel();
+        let mut results = Vec::with_capacity(stacks.len());
+        let mut reported = Ok(());
+        pool.in_place_scope(|scope| {
+            for stack in stacks {
+                let tx = tx.clone();
+                scope.spawn(move |_| {
+                    // The receiver only goes away once every stack is done
+                    let _ = tx.send(self.scan_stack(stack, predict));
+                });
+            }
+            drop(tx);
+
+            for result in rx {
+                if reported.is_ok() {
+                    reported = on_result(&result);
+                }
+                results.push(result);
+            }
+        });
+        reported?;
+
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(results)
+    }
+
+    fn scan_stack<P>(&self, stack: Stack, predict: &P) -> StackResult
+    where
+        P: Fn(&[ResourceChange]) -> Result<Vec<CostEstimate>>,
+    {
+        let mut tracker = PerformanceTracker::new(EngineBudget {
+            name: format!("Stack {}", stack.name),
+            ..self.budget.clone()
+        });
+
+        match self.analyze(&stack.plan, predict, &mut tracker) {
+            Ok((changes, estimates, detections)) => {
+                let budget_violation = tracker.check_budget().err();
+                let monthly_cost = estimates.iter().map(|e| e.monthly_cost).sum();
+                StackResult {
+                    name: stack.name,
+                    plan: stack.plan,
+                    changes,
+                    estimates,
+                    detections,
+                    monthly_cost,
+                    metrics: tracker.complete(),
+                    budget_violation,
+                    error: None,
+                }
+            }
+            Err(e) => StackResult {
+                name: stack.name,
+                plan: stack.plan,
+                changes: Vec::new(),
+                estimates: Vec::new(),
+                detections: Vec::new(),
+                monthly_cost: 0.0,
+                metrics: tracker.complete_with_failure(&e.message),
+                budget_violation: None,
+                error: Some(e.message),
+            },
+        }
+    }
+
+    fn analyze<P>(
+        &self,
+        plan: &Path,
+        predict: &P,
+        tracker: &mut PerformanceTracker,
+    ) -> Result<(Vec<ResourceChange>, Vec<CostEstimate>, Vec<Detection>)>
+    where
+        P: Fn(&[ResourceChange]) -> Result<Vec<CostEstimate>>,
+    {
+        tracker.enter_phase("parse");
+        let changes = self.detection.detect_from_terraform_plan(plan)?;
+        tracker.exit_span();
+
+        tracker.enter_phase("predict");
+        let estimates = predict(&changes)?;
+        tracker.exit_span();
+
+        tracker.enter_phase("analysis");
+        let costs: Vec<(String, f64, f64)> = estimates
+            .iter()
+            .map(|e| (e.resource_id.clone(), e.monthly_cost, e.confidence_score))
+            .collect();
+        let detections = self.detection.analyze_changes(&changes, &costs)?;
+        tracker.exit_span();
+
+        Ok((changes, estimates, detections))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::PredictionEngine;
+    use tempfile::TempDir;
+
+    const PLAN: &str = include_str!("../../../tests/fixtures/terraform/ec2_create.json");
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_discover_stacks_skips_hidden_directories() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "plan.json", PLAN);
+        write(dir.path(), "stacks/web/plan.json", PLAN);
+        write(dir.path(), "stacks/api/plan.json", PLAN);
+        write(dir.path(), "stacks/api/.terraform/plan.json", PLAN);
+        write(dir.path(), "docs/README.md", "not a stack");
+
+        let names: Vec<String> = discover_stacks(dir.path(), DEFAULT_STACK_PLAN)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec![".", "stacks/api", "stacks/web"]);
+        assert!(discover_stacks(&dir.path().join("missing"), DEFAULT_STACK_PLAN).is_err());
+    }
+
+    #[test]
+    fn test_run_streams_every_stack_and_isolates_failures() {
+        let dir = TempDir::new().unwrap();
+        for name in ["a", "b", "c", "d"] {
+            write(dir.path(), &format!("{}/plan.json", name), PLAN);
+        }
+        write(dir.path(), "broken/plan.json", "{ not json");
+        let stacks = discover_stacks(dir.path(), DEFAULT_STACK_PLAN).unwrap();
+
+        let mut streamed = Vec::new();
+        let results = StackScanner::new(2)
+            .run(stacks, &PredictionEngine::predict_static, |result| {
+                streamed.push(result.name.clone());
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(streamed.len(), 5);
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "broken", "c", "d"]);
+        let broken = &results[2];
+        assert!(!broken.succeeded());
+        assert!(!broken.metrics.within_budget);
+        for result in results.iter().filter(|r| r.name != "broken") {
+            assert!(result.succeeded(), "{:?}", result.error);
+            assert_eq!(result.changes.len(), 1);
+            assert!(result.monthly_cost > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_stack_over_budget_is_reported() {
+        let dir = TempDir::new().unwrap();
+        write(dir.path(), "slow/plan.json", PLAN);
+        let stacks = discover_stacks(dir.path(), DEFAULT_STACK_PLAN).unwrap();
+        let slow = |changes: &[ResourceChange]| {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            PredictionEngine::predict_static(changes)
+        };
+        let budget = EngineBudget {
+            max_latency_ms: 1,
+            ..PerformanceBudgets::default().total_scan
+        };
+
+        let results = StackScanner::new(1)
+            .with_budget(budget)
+            .run(stacks, &slow, |_| Ok(()))
+            .unwrap();
+
+        let violation = results[0].budget_violation.as_ref().unwrap();
+        assert_eq!(violation.engine, "Stack slow");
+        assert!(!results[0].metrics.within_budget);
+    }
+}
@@ -1,9 +1,15 @@
 // Immutable audit log system with cryptographic chain
+//
+// Every entry hashes its event together with the previous entry's hash, so
+// modifying, reordering or removing an entry breaks verification of all later
+// ones. Removing entries from the end leaves a valid shorter chain; an
+// `AuditAnchor` recorded elsewhere (the last known length and head hash)
+// detects that.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use thiserror::Error;
 
 /// Audit event type
@@ -44,6 +50,14 @@ pub enum AuditEventType {
     UserLogin,
     /// User logout
     UserLogout,
+    /// Drift-safe rollback executed
+    RollbackExecuted,
+    /// Approved baseline candidate applied
+    BaselineBlessed,
+    /// ProEngine artifact matched its signed manifest and was loaded
+    EngineVerified,
+    /// ProEngine artifact failed supply-chain verification
+    EngineRejected,
 }
 
 impl AuditEventType {
@@ -56,9 +70,13 @@ impl AuditEventType {
             AuditEventType::PolicyApproval
             | AuditEventType::PolicyVersionCreated
             | AuditEventType::PolicyContentModified
-            | AuditEventType::ExemptionCreated => AuditSeverity::Medium,
+            | AuditEventType::ExemptionCreated
+            | AuditEventType::BaselineBlessed
+            | AuditEventType::EngineVerified => AuditSeverity::Medium,
             AuditEventType::SloViolation | AuditEventType::SloBurnAlert => AuditSeverity::Critical,
-            AuditEventType::AccessDenied => AuditSeverity::High,
+            AuditEventType::AccessDenied
+            | AuditEventType::RollbackExecuted
+            | AuditEventType::EngineRejected => AuditSeverity::High,
             _ => AuditSeverity::Low,
         }
     }
@@ -71,6 +89,10 @@ impl AuditEventType {
                 | AuditEventType::PolicyApproval
                 | AuditEventType::AccessDenied
                 | AuditEventType::SloViolation
+                | AuditEventType::RollbackExecuted
+                | AuditEventType::BaselineBlessed
+                | AuditEventType::EngineVerified
+                | AuditEventType::EngineRejected
         )
     }
 }
@@ -112,9 +134,9 @@ pub struct AuditEvent {
     /// Event description
     pub description: String,
 
-    /// Additional metadata
+    /// Additional metadata, sorted so the event hash is stable
     #[serde(default)]
-    pub metadata: HashMap<String, String>,
+    pub metadata: BTreeMap<String, String>,
 
     /// Previous value (for changes)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -161,7 +183,7 @@ impl AuditEvent {
             resource_type,
             severity,
             description,
-            metadata: HashMap::new(),
+            metadata: BTreeMap::new(),
             old_value: None,
             new_value: None,
             ip_address: None,
@@ -298,6 +320,14 @@ impl AuditLogEntry {
     }
 }
 
+/// Length and head hash of a log at some point, kept apart from the log to
+/// detect truncation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditAnchor {
+    pub entry_count: u64,
+    pub head_hash: String,
+}
+
 /// Audit log chain manager
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditLog {
@@ -381,6 +411,41 @@ impl AuditLog {
         Ok(())
     }
 
+    /// Anchor for the current head of the chain
+    pub fn anchor(&self) -> AuditAnchor {
+        AuditAnchor {
+            entry_count: self.entries.len() as u64,
+            head_hash: self.get_previous_hash(),
+        }
+    }
+
+    /// Verify the chain and that it still contains the anchored head
+    ///
+    /// Entries appended after the anchor was taken are accepted; fewer entries
+    /// or a different hash at the anchored position are not.
+    pub fn verify_anchor(&self, anchor: &AuditAnchor) -> Result<(), AuditLogError> {
+        self.verify_chain()?;
+
+        let found = self.entries.len() as u64;
+        if found < anchor.entry_count {
+            return Err(AuditLogError::Truncated {
+                expected: anchor.entry_count,
+                found,
+            });
+        }
+        let hash = match anchor.entry_count {
+            0 => &self.genesis_hash,
+            n => &self.entries[n as usize - 1].hash,
+        };
+        if *hash != anchor.head_hash {
+            return Err(AuditLogError::BrokenChain(format!(
+                "Entry {} does not match the anchored head hash",
+                anchor.entry_count.saturating_sub(1)
+            )));
+        }
+        Ok(())
+    }
+
     /// Get all entries
     pub fn get_entries(&self) -> &[AuditLogEntry] {
         &self.entries
@@ -440,10 +505,31 @@ impl AuditLog {
         self.entries.last()
     }
 
+    /// Entries from `since` to `until` (inclusive; open when `None`)
+    fn entries_between(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> impl Iterator<Item = &AuditLogEntry> {
+        self.entries.iter().filter(move |e| {
+            since.map_or(true, |start| e.event.timestamp >= start)
+                && until.map_or(true, |end| e.event.timestamp <= end)
+        })
+    }
+
     /// Export to NDJSON format
     pub fn export_ndjson(&self) -> Result<String, AuditLogError> {
+        self.export_ndjson_range(None, None)
+    }
+
+    /// Export entries in a time range to NDJSON, one entry with its hashes per line
+    pub fn export_ndjson_range(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<String, AuditLogError> {
         let mut output = String::new();
-        for entry in &self.entries {
+        for entry in self.entries_between(since, until) {
             let json = serde_json::to_string(entry)
                 .map_err(|e| AuditLogError::SerializationError(e.to_string()))?;
             output.push_str(&json);
@@ -454,15 +540,24 @@ impl AuditLog {
 
     /// Export to CSV format
     pub fn export_csv(&self) -> Result<String, AuditLogError> {
+        self.export_csv_range(None, None)
+    }
+
+    /// Export entries in a time range to CSV
+    pub fn export_csv_range(
+        &self,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<String, AuditLogError> {
         let mut output = String::new();
 
         // CSV header
-        output.push_str("sequence,timestamp,event_type,actor,resource_id,resource_type,severity,description,success,hash\n");
+        output.push_str("sequence,timestamp,event_type,actor,resource_id,resource_type,severity,description,success,hash,previous_hash\n");
 
         // CSV rows
-        for entry in &self.entries {
+        for entry in self.entries_between(since, until) {
             let row = format!(
-                "{},{},{:?},{},{},{},{:?},{},{},{}\n",
+                "{},{},{:?},{},{},{},{:?},{},{},{},{}\n",
                 entry.sequence,
                 entry.event.timestamp.to_rfc3339(),
                 entry.event.event_type,
@@ -472,7 +567,8 @@ impl AuditLog {
                 entry.event.severity,
                 Self::csv_escape(&entry.event.description),
                 entry.event.success,
-                entry.hash
+                entry.hash,
+                entry.previous_hash
             );
             output.push_str(&row);
         }
@@ -612,6 +708,9 @@ pub enum AuditLogError {
 
     #[error("Entry not found: {0}")]
     EntryNotFound(String),
+
+    #[error("Audit log truncated: anchor expects {expected} entries, found {found}")]
+    Truncated { expected: u64, found: u64 },
 }
 
 #[cfg(test)]
@@ -792,6 +891,86 @@ mod tests {
         assert!(ndjson.ends_with('\n'));
     }
 
+    fn event(actor: &str) -> AuditEvent {
+        AuditEvent::new(
+            AuditEventType::PolicyApproval,
+            actor.to_string(),
+            "policy-1".to_string(),
+            "policy".to_string(),
+            "Approved".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_anchor_detects_truncation_and_rewrites() {
+        let mut log = AuditLog::new();
+        for actor in ["a", "b", "c"] {
+            log.append(
+                event(actor)
+                    .with_metadata("zone".to_string(), "1".to_string())
+                    .with_metadata("approver".to_string(), actor.to_string()),
+            )
+            .unwrap();
+        }
+        let anchor = log.anchor();
+        assert_eq!(anchor.entry_count, 3);
+
+        // Survives a round trip, and later appends keep the anchor valid
+        let json = serde_json::to_string(&log).unwrap();
+        let mut reloaded: AuditLog = serde_json::from_str(&json).unwrap();
+        reloaded.append(event("d")).unwrap();
+        assert!(reloaded.verify_anchor(&anchor).is_ok());
+
+        let mut truncated = log.clone();
+        truncated.entries.pop();
+        assert!(truncated.verify_chain().is_ok());
+        assert!(matches!(
+            truncated.verify_anchor(&anchor),
+            Err(AuditLogError::Truncated {
+                expected: 3,
+                found: 2
+            })
+        ));
+
+        // Rebuilding the chain after editing an entry changes the head
+        let mut rewritten = AuditLog::new();
+        for entry in log.get_entries() {
+            let mut event = entry.event.clone();
+            event.description = "Rejected".to_string();
+            rewritten.append(event).unwrap();
+        }
+        assert!(matches!(
+            rewritten.verify_anchor(&anchor),
+            Err(AuditLogError::BrokenChain(_))
+        ));
+
+        let mut modified = log.clone();
+        modified.entries[1].event.actor = "mallory".to_string();
+        assert!(modified.verify_anchor(&anchor).is_err());
+    }
+
+    #[test]
+    fn test_export_time_range() {
+        let mut log = AuditLog::new();
+        for (actor, day) in [("early", 1), ("middle", 10), ("late", 20)] {
+            let mut event = event(actor);
+            event.timestamp = "2025-06-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+                + chrono::Duration::days(day);
+            log.append(event).unwrap();
+        }
+        let since = "2025-06-05T00:00:00Z".parse().ok();
+        let until = "2025-06-15T00:00:00Z".parse().ok();
+
+        let ndjson = log.export_ndjson_range(since, until).unwrap();
+        assert_eq!(ndjson.lines().count(), 1);
+        assert!(ndjson.contains("\"actor\":\"middle\""));
+
+        let csv = log.export_csv_range(since, None).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.lines().next().unwrap().ends_with(",hash,previous_hash"));
+        assert!(csv.contains(&log.get_entries()[0].hash));
+    }
+
     #[test]
     fn test_export_csv() {
         let mut log = AuditLog::new();
@@ -143,16 +143,15 @@ pub fn validate_and_exit_ci(exemptions_file: &ExemptionsFile) -> ! {
             println!("{}", check_result.summary());
 
             if check_result.expired_exemptions > 0 {
-                eprintln!(
-                    "\n❌ CI BLOCKED: {} expired exemption(s) found",
-                    check_result.expired_exemptions
+                tracing::error!(
+                    expired = check_result.expired_exemptions,
+                    "CI blocked by expired exemptions; remove or renew them before proceeding"
                 );
-                eprintln!("Please remove or renew expired exemptions before proceeding.");
                 process::exit(EXIT_EXEMPTION_EXPIRED);
             } else if check_result.invalid_exemptions > 0 {
-                eprintln!(
-                    "\n⚠️  WARNING: {} invalid exemption(s) found",
-                    check_result.invalid_exemptions
+                tracing::warn!(
+                    invalid = check_result.invalid_exemptions,
+                    "Invalid exemptions found"
                 );
                 process::exit(EXIT_VALIDATION_ERROR);
             } else {
@@ -161,7 +160,7 @@ pub fn validate_and_exit_ci(exemptions_file: &ExemptionsFile) -> ! {
             }
         }
         Err(e) => {
-            eprintln!("Error checking exemptions: {}", e);
+            tracing::error!("Error checking exemptions: {}", e);
             process::exit(EXIT_VALIDATION_ERROR);
         }
     }
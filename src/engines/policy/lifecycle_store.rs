@@ -0,0 +1,1025 @@
+// Persistent policy lifecycle: state, signed approvals and transition history
+//
+// Each policy under lifecycle management has one record file holding its
+// current state, the approvers required to leave review, and its
+// PolicyHistory of content versions and state transitions. Approvals are
+// Ed25519-signed files binding an approver to the exact policy version under
+// review, so a policy edited after submission cannot inherit approvals.
+// Approver keys come from a trusted keyring (`policy_approvals` in
+// costpilot.yaml), never from whoever submits the policy: each approver's key
+// is recorded at submission and an approval must be signed with it, so one
+// keyholder cannot sign for several approvers. Authors cannot approve their
+// own policies, and the keyring sets the fewest approvals a review may
+// require.
+
+use super::lifecycle::{
+    ApprovalConfig, ApprovalRequest, LifecycleError, LifecycleSummary, PolicyLifecycle, PolicyState,
+};
+use super::policy_history::{HistoryError, PolicyContent, PolicyHistory};
+use crate::engines::baselines::approval::decode_verifying_key;
+use crate::engines::shared::compat::{self, Artifact};
+use crate::engines::shared::error_model::CostPilotError;
+use base64::Engine;
+use chrono::Utc;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Default directory of lifecycle records
+pub const LIFECYCLE_DIR: &str = ".costpilot/policy_lifecycle";
+
+/// Every lifecycle state, in workflow order
+const STATES: [PolicyState; 6] = [
+    PolicyState::Draft,
+    PolicyState::Review,
+    PolicyState::Approved,
+    PolicyState::Active,
+    PolicyState::Deprecated,
+    PolicyState::Archived,
+];
+
+/// Lifecycle store errors
+#[derive(Debug, Error)]
+pub enum LifecycleStoreError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid {kind}: {message}")]
+    Parse { kind: &'static str, message: String },
+
+    #[error("Policy '{0}' is not under lifecycle management (submit it first)")]
+    NotFound(String),
+
+    #[error(transparent)]
+    Lifecycle(#[from] LifecycleError),
+
+    #[error(transparent)]
+    History(#[from] HistoryError),
+
+    #[error(transparent)]
+    Compat(#[from] CostPilotError),
+
+    #[error("Approval is for policy '{approved}', not '{policy}'")]
+    PolicyMismatch { approved: String, policy: String },
+
+    #[error("Approval covers version {approved}, but version {current} is under review")]
+    VersionMismatch { approved: String, current: String },
+
+    #[error("Approval signature is invalid")]
+    InvalidSignature,
+
+    #[error("'{0}' is not an approver of this review")]
+    UnknownApprover(String),
+
+    #[error("Approval was not signed with the key submitted for '{0}'")]
+    UntrustedKey(String),
+
+    #[error("The key that signed this approval already approved as '{0}'")]
+    DuplicateKey(String),
+
+    #[error("'{0}' has no key in the policy_approvals keyring")]
+    NotInKeyring(String),
+
+    #[error("'{0}' submitted this policy and cannot approve it")]
+    SelfApproval(String),
+
+    #[error(
+        "Reviews need at least {required} approval(s), but only {approvers} approver(s) were given"
+    )]
+    TooFewApprovers { required: usize, approvers: usize },
+}
+
+impl LifecycleStoreError {
+    fn parse(kind: &'static str, message: impl std::fmt::Display) -> Self {
+        Self::Parse {
+            kind,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Trusted approvers and the review floor (`policy_approvals` in
+/// costpilot.yaml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyApprovals {
+    /// Fewest approvals any review may require
+    #[serde(default = "default_min_approvals")]
+    pub min_approvals: usize,
+
+    /// Public key file of each approver, relative to the directory costpilot
+    /// runs in
+    #[serde(default)]
+    pub approvers: BTreeMap<String, PathBuf>,
+}
+
+fn default_min_approvals() -> usize {
+    1
+}
+
+impl Default for PolicyApprovals {
+    fn default() -> Self {
+        Self {
+            min_approvals: default_min_approvals(),
+            approvers: BTreeMap::new(),
+        }
+    }
+}
+
+impl PolicyApprovals {
+    /// Parse `policy_approvals` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(
+        content: &str,
+        profile: Option<&str>,
+    ) -> Result<Self, CostPilotError> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        match root.get("policy_approvals").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid policy_approvals section: {}", e))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Problems with the section
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.min_approvals == 0 {
+            problems.push("policy_approvals.min_approvals must be at least 1".to_string());
+        }
+        for (approver, path) in &self.approvers {
+            if path.as_os_str().is_empty() {
+                problems.push(format!(
+                    "policy_approvals.approvers.{} has an empty key path",
+                    approver
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// Public keys approvals may be signed with, by approver, and the fewest
+/// approvals a review may require
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApproverKeyring {
+    keys: BTreeMap<String, VerifyingKey>,
+    min_approvals: usize,
+}
+
+impl Default for ApproverKeyring {
+    fn default() -> Self {
+        Self::new(default_min_approvals())
+    }
+}
+
+impl ApproverKeyring {
+    /// Empty keyring whose reviews need at least `min_approvals` approvals
+    pub fn new(min_approvals: usize) -> Self {
+        Self {
+            keys: BTreeMap::new(),
+            min_approvals: min_approvals.max(1),
+        }
+    }
+
+    /// Trust `key` for `approver`'s approvals
+    pub fn with_key(mut self, approver: impl Into<String>, key: VerifyingKey) -> Self {
+        self.keys.insert(approver.into(), key);
+        self
+    }
+
+    /// Load `policy_approvals` from a costpilot.yaml file, applying
+    /// `profile`, and read its key files
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self, CostPilotError> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let config = PolicyApprovals::from_yaml_with_profile(&content, profile)?;
+        Self::from_config(&config)
+    }
+
+    /// Read the key file of each approver in `config`
+    pub fn from_config(config: &PolicyApprovals) -> Result<Self, CostPilotError> {
+        let problems = config.validate();
+        if !problems.is_empty() {
+            return Err(CostPilotError::config_error(format!(
+                "Invalid policy_approvals section: {}",
+                problems.join("; ")
+            )));
+        }
+        config.approvers.iter().try_fold(
+            Self::new(config.min_approvals),
+            |keyring, (approver, path)| {
+                let encoded = fs::read_to_string(path).map_err(|e| {
+                    CostPilotError::io_error(format!(
+                        "Failed to read key of approver '{}' ({}): {}",
+                        approver,
+                        path.display(),
+                        e
+                    ))
+                })?;
+                let key = decode_verifying_key(&encoded).map_err(|e| {
+                    CostPilotError::config_error(format!("{}: {}", path.display(), e))
+                })?;
+                Ok(keyring.with_key(approver.clone(), key))
+            },
+        )
+    }
+
+    pub fn key(&self, approver: &str) -> Option<&VerifyingKey> {
+        self.keys.get(approver)
+    }
+
+    /// Fewest approvals a review may require
+    pub fn min_approvals(&self) -> usize {
+        self.min_approvals
+    }
+}
+
+/// Approval of a policy version under review, signed by the approver
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyApproval {
+    pub policy_id: String,
+
+    /// Policy version approved
+    pub version: String,
+
+    /// Checksum of that version's content
+    pub content_sha256: String,
+
+    /// Who approved the policy
+    pub approver: String,
+
+    /// RFC 3339 approval time
+    pub approved_at: String,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub comment: Option<String>,
+
+    /// Base64 Ed25519 public key of the approver
+    pub public_key: String,
+
+    /// Base64 Ed25519 signature over the approval with this field empty
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl PolicyApproval {
+    /// Approve the version of `record` currently under review as `approver`
+    pub fn sign(
+        record: &PolicyRecord,
+        approver: impl Into<String>,
+        comment: Option<String>,
+        key: &SigningKey,
+    ) -> Self {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let mut approval = Self {
+            policy_id: record.policy_id().to_string(),
+            version: record.history.current_version.clone(),
+            content_sha256: record.current_checksum().to_string(),
+            approver: approver.into(),
+            approved_at: Utc::now().to_rfc3339(),
+            comment,
+            public_key: engine.encode(key.verifying_key().to_bytes()),
+            signature: String::new(),
+        };
+        approval.signature = engine.encode(key.sign(&approval.signing_payload()).to_bytes());
+        approval
+    }
+
+    /// Canonical bytes covered by the signature
+    fn signing_payload(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.signature.clear();
+        serde_json::to_vec(&unsigned).unwrap_or_default()
+    }
+
+    /// Check the approval covers the version of `record` under review and
+    /// is signed with the key submitted for its approver
+    pub fn verify(&self, record: &PolicyRecord) -> Result<(), LifecycleStoreError> {
+        if self.policy_id != record.policy_id() {
+            return Err(LifecycleStoreError::PolicyMismatch {
+                approved: self.policy_id.clone(),
+                policy: record.policy_id().to_string(),
+            });
+        }
+        if self.version != record.history.current_version
+            || self.content_sha256 != record.current_checksum()
+        {
+            return Err(LifecycleStoreError::VersionMismatch {
+                approved: self.version.clone(),
+                current: record.history.current_version.clone(),
+            });
+        }
+
+        let expected = record
+            .approver_keys
+            .get(&self.approver)
+            .ok_or_else(|| LifecycleStoreError::UnknownApprover(self.approver.clone()))?;
+        if *expected != self.public_key {
+            return Err(LifecycleStoreError::UntrustedKey(self.approver.clone()));
+        }
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        let public_key = engine
+            .decode(&self.public_key)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| {
+                LifecycleStoreError::parse("public key", "expected 32 base64-encoded bytes")
+            })?;
+
+        let signature = engine
+            .decode(&self.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or(LifecycleStoreError::InvalidSignature)?;
+        public_key
+            .verify(&self.signing_payload(), &signature)
+            .map_err(|_| LifecycleStoreError::InvalidSignature)
+    }
+
+    /// Load an approval file
+    pub fn load(path: &Path) -> Result<Self, LifecycleStoreError> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| LifecycleStoreError::parse("approval file", e))
+    }
+
+    /// Write the approval as pretty JSON
+    pub fn save(&self, path: &Path) -> Result<(), LifecycleStoreError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| LifecycleStoreError::parse("approval", e))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Stored lifecycle of one policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyRecord {
+    pub state: PolicyState,
+
+    /// Approvers required to leave review
+    pub approval_config: ApprovalConfig,
+
+    /// Approval requests of the current review
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub approvals: Vec<ApprovalRequest>,
+
+    /// Base64 Ed25519 public key of each approver of the current review
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub approver_keys: BTreeMap<String, String>,
+
+    /// Signed approvals accepted since the policy was last submitted
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub signed_approvals: Vec<PolicyApproval>,
+
+    /// Content versions and state transitions
+    pub history: PolicyHistory,
+}
+
+impl PolicyRecord {
+    /// New record in Draft state
+    pub fn new(content: PolicyContent, author: String) -> Self {
+        Self {
+            state: PolicyState::Draft,
+            approval_config: ApprovalConfig::default(),
+            approvals: Vec::new(),
+            approver_keys: BTreeMap::new(),
+            signed_approvals: Vec::new(),
+            history: PolicyHistory::new(content.id.clone(), content, author),
+        }
+    }
+
+    pub fn policy_id(&self) -> &str {
+        &self.history.policy_id
+    }
+
+    /// Who submitted the policy for its current review
+    pub fn submitter(&self) -> Option<&str> {
+        self.history
+            .transitions
+            .iter()
+            .rev()
+            .find(|t| t.to_state == PolicyState::Review)
+            .map(|t| t.actor.as_str())
+    }
+
+    /// Checksum of the current version's content
+    pub fn current_checksum(&self) -> &str {
+        self.history
+            .get_current()
+            .map_or("", |version| version.metadata.checksum.as_str())
+    }
+
+    /// State machine view of the record
+    pub fn lifecycle(&self) -> PolicyLifecycle {
+        PolicyLifecycle {
+            policy_id: self.policy_id().to_string(),
+            current_state: self.state,
+            state_history: self.history.transitions.clone(),
+            approval_config: self.approval_config.clone(),
+            pending_approvals: self.approvals.clone(),
+        }
+    }
+
+    /// Run `operation` on the state machine, keeping its result only when it
+    /// succeeds; new transitions are recorded in the history
+    pub fn update<T>(
+        &mut self,
+        operation: impl FnOnce(&mut PolicyLifecycle) -> Result<T, LifecycleError>,
+    ) -> Result<T, LifecycleError> {
+        let mut lifecycle = self.lifecycle();
+        let result = operation(&mut lifecycle)?;
+
+        let recorded = self.history.transitions.len();
+        for transition in lifecycle.state_history.into_iter().skip(recorded) {
+            self.history.record_transition(transition);
+        }
+        self.state = lifecycle.current_state;
+        self.approvals = lifecycle.pending_approvals;
+        Ok(result)
+    }
+
+    pub fn summary(&self) -> LifecycleSummary {
+        self.lifecycle().summary()
+    }
+
+    /// Mermaid state diagram of the lifecycle with the current state
+    /// highlighted and the transitions this policy has taken labelled
+    pub fn state_graph(&self) -> String {
+        let name = |state: PolicyState| format!("{:?}", state).to_lowercase();
+        let mut lines = vec![
+            "stateDiagram-v2".to_string(),
+            format!("    %% Policy {}", self.policy_id()),
+            "    [*] --> draft".to_string(),
+        ];
+
+        for from in STATES {
+            for to in from.valid_transitions() {
+                let taken: Vec<_> = self
+                    .history
+                    .transitions
+                    .iter()
+                    .filter(|t| t.from_state == from && t.to_state == to)
+                    .collect();
+                let edge = format!("    {} --> {}", name(from), name(to));
+                lines.push(match taken.last() {
+                    Some(last) => {
+                        let date = last.timestamp.get(..10).unwrap_or(&last.timestamp);
+                        let times = if taken.len() > 1 {
+                            format!(" x{}", taken.len())
+                        } else {
+                            String::new()
+                        };
+                        format!("{} : {} {}{}", edge, last.actor, date, times)
+                    }
+                    None => edge,
+                });
+            }
+        }
+        lines.push("    archived --> [*]".to_string());
+
+        let visited: Vec<String> = STATES
+            .into_iter()
+            .filter(|state| {
+                *state != self.state
+                    && (*state == PolicyState::Draft
+                        || self
+                            .history
+                            .transitions
+                            .iter()
+                            .any(|t| t.to_state == *state))
+            })
+            .map(name)
+            .collect();
+        lines.push("    classDef current fill:#d4edda,stroke:#155724,stroke-width:2px".to_string());
+        lines.push("    classDef visited fill:#e2e3e5,stroke:#6c757d".to_string());
+        lines.push(format!("    class {} current", name(self.state)));
+        if !visited.is_empty() {
+            lines.push(format!("    class {} visited", visited.join(",")));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Lifecycle records of all managed policies, one JSON file per policy
+#[derive(Debug, Clone)]
+pub struct LifecycleStore {
+    dir: PathBuf,
+    keyring: ApproverKeyring,
+}
+
+impl Default for LifecycleStore {
+    fn default() -> Self {
+        Self::new(LIFECYCLE_DIR)
+    }
+}
+
+impl LifecycleStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            keyring: ApproverKeyring::default(),
+        }
+    }
+
+    /// Approver keys and review floor that submissions and approvals are
+    /// checked against
+    pub fn with_keyring(mut self, keyring: ApproverKeyring) -> Self {
+        self.keyring = keyring;
+        self
+    }
+
+    fn path(&self, policy_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", policy_id))
+    }
+
+    pub fn exists(&self, policy_id: &str) -> bool {
+        self.path(policy_id).exists()
+    }
+
+    pub fn load(&self, policy_id: &str) -> Result<PolicyRecord, LifecycleStoreError> {
+        let path = self.path(policy_id);
+        if !path.exists() {
+            return Err(LifecycleStoreError::NotFound(policy_id.to_string()));
+        }
+        let content = fs::read_to_string(&path)?;
+        let (record, _) = compat::read(Artifact::PolicyRecord, &content)?;
+        Ok(record)
+    }
+
+    pub fn save(&self, record: &PolicyRecord) -> Result<(), LifecycleStoreError> {
+        fs::create_dir_all(&self.dir)?;
+        let json = compat::to_string_pretty(Artifact::PolicyRecord, record)?;
+        fs::write(self.path(record.policy_id()), json)?;
+        Ok(())
+    }
+
+    /// Submit `content` for review by `approvers`, each of whom needs a key
+    /// in the keyring, of whom `min_approvals` must sign off (at least the
+    /// keyring's floor). The author cannot be an approver. A policy sent back
+    /// to draft is resubmitted as a new version when its content changed.
+    pub fn submit(
+        &self,
+        content: PolicyContent,
+        author: String,
+        approvers: Vec<String>,
+        min_approvals: usize,
+    ) -> Result<PolicyRecord, LifecycleStoreError> {
+        if approvers.contains(&author) {
+            return Err(LifecycleStoreError::SelfApproval(author));
+        }
+        let floor = self.keyring.min_approvals();
+        if approvers.len() < floor {
+            return Err(LifecycleStoreError::TooFewApprovers {
+                required: floor,
+                approvers: approvers.len(),
+            });
+        }
+        let engine = base64::engine::general_purpose::STANDARD;
+        let approver_keys = approvers
+            .iter()
+            .map(|name| {
+                self.keyring
+                    .key(name)
+                    .map(|key| (name.clone(), engine.encode(key.to_bytes())))
+                    .ok_or_else(|| LifecycleStoreError::NotInKeyring(name.clone()))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        let mut record = if self.exists(&content.id) {
+            let mut record = self.load(&content.id)?;
+            if record.state == PolicyState::Draft {
+                match record.history.add_version(
+                    content,
+                    author.clone(),
+                    "Resubmitted for review".to_string(),
+                    false,
+                ) {
+                    Ok(_) | Err(HistoryError::NoChanges) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            record
+        } else {
+            PolicyRecord::new(content, author.clone())
+        };
+
+        record.approver_keys = approver_keys;
+        record.signed_approvals.clear();
+        record.approval_config = ApprovalConfig {
+            min_approvals: min_approvals.clamp(floor, approvers.len()),
+            required_roles: Vec::new(),
+            allowed_approvers: approvers.clone(),
+            ..ApprovalConfig::default()
+        };
+        record.update(|lifecycle| lifecycle.submit_for_review(author, approvers))?;
+        self.save(&record)?;
+        Ok(record)
+    }
+
+    /// Record a signed approval; the policy moves to Approved once enough
+    /// required approvers have signed, each with a different key that the
+    /// keyring still trusts
+    pub fn approve(&self, approval: &PolicyApproval) -> Result<PolicyRecord, LifecycleStoreError> {
+        let mut record = self.load(&approval.policy_id)?;
+        if record.submitter() == Some(approval.approver.as_str()) {
+            return Err(LifecycleStoreError::SelfApproval(approval.approver.clone()));
+        }
+        approval.verify(&record)?;
+        let engine = base64::engine::general_purpose::STANDARD;
+        match self.keyring.key(&approval.approver) {
+            Some(key) if engine.encode(key.to_bytes()) == approval.public_key => {}
+            Some(_) => return Err(LifecycleStoreError::UntrustedKey(approval.approver.clone())),
+            None => return Err(LifecycleStoreError::NotInKeyring(approval.approver.clone())),
+        }
+        if let Some(previous) = record
+            .signed_approvals
+            .iter()
+            .find(|signed| signed.public_key == approval.public_key)
+        {
+            return Err(LifecycleStoreError::DuplicateKey(previous.approver.clone()));
+        }
+
+        record.update(|lifecycle| {
+            lifecycle.record_approval(approval.approver.clone(), true, approval.comment.clone())
+        })?;
+        record.signed_approvals.push(approval.clone());
+        record.update(|lifecycle| {
+            if lifecycle.has_sufficient_approvals() {
+                lifecycle.transition(
+                    PolicyState::Approved,
+                    approval.approver.clone(),
+                    Some(format!(
+                        "{} of {} required approval(s) signed",
+                        lifecycle.count_approvals(),
+                        lifecycle.approval_config.min_approvals
+                    )),
+                )?;
+            }
+            Ok(())
+        })?;
+        self.save(&record)?;
+        Ok(record)
+    }
+
+    /// Reject the policy under review, sending it back to draft
+    pub fn reject(
+        &self,
+        policy_id: &str,
+        approver: String,
+        reason: String,
+    ) -> Result<PolicyRecord, LifecycleStoreError> {
+        self.modify(policy_id, |lifecycle| {
+            lifecycle.record_approval(approver.clone(), false, Some(reason.clone()))?;
+            lifecycle.transition(PolicyState::Draft, approver, Some(reason))
+        })
+    }
+
+    pub fn activate(
+        &self,
+        policy_id: &str,
+        actor: String,
+    ) -> Result<PolicyRecord, LifecycleStoreError> {
+        self.modify(policy_id, |lifecycle| lifecycle.activate(actor))
+    }
+
+    pub fn deprecate(
+        &self,
+        policy_id: &str,
+        actor: String,
+        reason: String,
+    ) -> Result<PolicyRecord, LifecycleStoreError> {
+        self.modify(policy_id, |lifecycle| lifecycle.deprecate(actor, reason))
+    }
+
+    pub fn archive(
+        &self,
+        policy_id: &str,
+        actor: String,
+        reason: String,
+    ) -> Result<PolicyRecord, LifecycleStoreError> {
+        self.modify(policy_id, |lifecycle| lifecycle.archive(actor, reason))
+    }
+
+    fn modify(
+        &self,
+        policy_id: &str,
+        operation: impl FnOnce(&mut PolicyLifecycle) -> Result<(), LifecycleError>,
+    ) -> Result<PolicyRecord, LifecycleStoreError> {
+        let mut record = self.load(policy_id)?;
+        record.update(operation)?;
+        self.save(&record)?;
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn content(rules: serde_json::Value) -> PolicyContent {
+        PolicyContent {
+            id: "nat-limits".to_string(),
+            name: "NAT limits".to_string(),
+            description: String::new(),
+            rules,
+            config: HashMap::new(),
+        }
+    }
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn keyring(names: &[(&str, u8)]) -> ApproverKeyring {
+        names
+            .iter()
+            .fold(ApproverKeyring::default(), |keyring, (name, seed)| {
+                keyring.with_key(*name, key(*seed).verifying_key())
+            })
+    }
+
+    fn approvers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn store(dir: &TempDir) -> LifecycleStore {
+        LifecycleStore::new(dir.path()).with_keyring(keyring(&[("alice", 1), ("bob", 2)]))
+    }
+
+    fn submit(store: &LifecycleStore) -> PolicyRecord {
+        store
+            .submit(
+                content(json!({"max": 2})),
+                "author".to_string(),
+                approvers(&["alice", "bob"]),
+                2,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_full_lifecycle_is_persisted() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let record = submit(&store);
+        assert_eq!(record.state, PolicyState::Review);
+
+        let approval = PolicyApproval::sign(&record, "alice", None, &key(1));
+        let record = store.approve(&approval).unwrap();
+        assert_eq!(record.state, PolicyState::Review);
+        assert_eq!(record.summary().approvals_received, 1);
+
+        let approval = PolicyApproval::sign(&record, "bob", Some("ok".to_string()), &key(2));
+        let record = store.approve(&approval).unwrap();
+        assert_eq!(record.state, PolicyState::Approved);
+
+        store.activate("nat-limits", "ops".to_string()).unwrap();
+        store
+            .deprecate("nat-limits", "ops".to_string(), "superseded".to_string())
+            .unwrap();
+
+        let record = store.load("nat-limits").unwrap();
+        assert_eq!(record.state, PolicyState::Deprecated);
+        let states: Vec<_> = record
+            .history
+            .transitions
+            .iter()
+            .map(|t| t.to_state)
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                PolicyState::Review,
+                PolicyState::Approved,
+                PolicyState::Active,
+                PolicyState::Deprecated
+            ]
+        );
+        assert_eq!(record.history.transitions[1].approval_ids.len(), 2);
+        assert_eq!(record.signed_approvals.len(), 2);
+    }
+
+    #[test]
+    fn test_approval_must_match_version_and_key() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let record = submit(&store);
+
+        let approval = PolicyApproval::sign(&record, "alice", None, &key(1));
+        let stranger = PolicyApproval::sign(&record, "alice", None, &key(9));
+        assert!(matches!(
+            store.approve(&stranger),
+            Err(LifecycleStoreError::UntrustedKey(name)) if name == "alice"
+        ));
+        let outsider = PolicyApproval::sign(&record, "mallory", None, &key(9));
+        assert!(matches!(
+            store.approve(&outsider),
+            Err(LifecycleStoreError::UnknownApprover(_))
+        ));
+
+        // Alice's signature passed off as bob's approval
+        let mut forged = approval.clone();
+        forged.approver = "bob".to_string();
+        forged.public_key = PolicyApproval::sign(&record, "bob", None, &key(2)).public_key;
+        assert!(matches!(
+            store.approve(&forged),
+            Err(LifecycleStoreError::InvalidSignature)
+        ));
+
+        // Rejected and resubmitted with new content: the old approval no
+        // longer covers the version under review
+        store
+            .reject("nat-limits", "bob".to_string(), "too strict".to_string())
+            .unwrap();
+        let record = store
+            .submit(
+                content(json!({"max": 3})),
+                "author".to_string(),
+                approvers(&["alice"]),
+                1,
+            )
+            .unwrap();
+        assert_eq!(record.history.version_count(), 2);
+        assert!(matches!(
+            store.approve(&approval),
+            Err(LifecycleStoreError::VersionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_one_key_cannot_approve_as_several_approvers() {
+        let dir = TempDir::new().unwrap();
+        // Both approvers have the same key in the keyring
+        let store =
+            LifecycleStore::new(dir.path()).with_keyring(keyring(&[("alice", 1), ("bob", 1)]));
+        let record = store
+            .submit(
+                content(json!({"max": 2})),
+                "author".to_string(),
+                approvers(&["alice", "bob"]),
+                2,
+            )
+            .unwrap();
+
+        store
+            .approve(&PolicyApproval::sign(&record, "alice", None, &key(1)))
+            .unwrap();
+        assert!(matches!(
+            store.approve(&PolicyApproval::sign(&record, "bob", None, &key(1))),
+            Err(LifecycleStoreError::DuplicateKey(name)) if name == "alice"
+        ));
+        assert_eq!(store.load("nat-limits").unwrap().state, PolicyState::Review);
+    }
+
+    #[test]
+    fn test_keyring_decides_who_approves() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let submit = |store: &LifecycleStore, author: &str, names: &[&str], min: usize| {
+            store.submit(
+                content(json!({"max": 2})),
+                author.to_string(),
+                approvers(names),
+                min,
+            )
+        };
+
+        // Authors cannot review their own policy, and every approver needs
+        // a key in the keyring
+        assert!(matches!(
+            submit(&store, "alice", &["alice", "bob"], 1),
+            Err(LifecycleStoreError::SelfApproval(name)) if name == "alice"
+        ));
+        assert!(matches!(
+            submit(&store, "author", &["alice", "mallory"], 1),
+            Err(LifecycleStoreError::NotInKeyring(name)) if name == "mallory"
+        ));
+        assert!(!store.exists("nat-limits"));
+
+        // The keyring's floor overrides a lower min_approvals
+        let floored = LifecycleStore::new(dir.path())
+            .with_keyring(ApproverKeyring::new(2).with_key("alice", key(1).verifying_key()));
+        assert!(matches!(
+            submit(&floored, "author", &["alice"], 1),
+            Err(LifecycleStoreError::TooFewApprovers {
+                required: 2,
+                approvers: 1
+            })
+        ));
+        let floored = floored.with_keyring(
+            ApproverKeyring::new(2)
+                .with_key("alice", key(1).verifying_key())
+                .with_key("bob", key(2).verifying_key()),
+        );
+        let record = submit(&floored, "author", &["alice", "bob"], 1).unwrap();
+        assert_eq!(record.approval_config.min_approvals, 2);
+
+        // Alice submits her own change for bob's review, then signs it herself
+        store
+            .reject("nat-limits", "bob".to_string(), "redo".to_string())
+            .unwrap();
+        let record = submit(&store, "alice", &["bob"], 1).unwrap();
+        assert!(matches!(
+            store.approve(&PolicyApproval::sign(&record, "alice", None, &key(1))),
+            Err(LifecycleStoreError::SelfApproval(name)) if name == "alice"
+        ));
+
+        // Bob's key was rotated out of the keyring after submission
+        let rotated =
+            LifecycleStore::new(dir.path()).with_keyring(keyring(&[("alice", 1), ("bob", 8)]));
+        assert!(matches!(
+            rotated.approve(&PolicyApproval::sign(&record, "bob", None, &key(2))),
+            Err(LifecycleStoreError::UntrustedKey(name)) if name == "bob"
+        ));
+        assert_eq!(
+            store
+                .approve(&PolicyApproval::sign(&record, "bob", None, &key(2)))
+                .unwrap()
+                .state,
+            PolicyState::Approved
+        );
+    }
+
+    #[test]
+    fn test_keyring_from_config() {
+        let dir = TempDir::new().unwrap();
+        let engine = base64::engine::general_purpose::STANDARD;
+        let path = dir.path().join("alice.key.pub");
+        fs::write(&path, engine.encode(key(1).verifying_key().to_bytes())).unwrap();
+
+        let config = PolicyApprovals::from_yaml_with_profile(
+            &format!(
+                "policy_approvals:\n  min_approvals: 2\n  approvers:\n    alice: {}\n",
+                path.display()
+            ),
+            None,
+        )
+        .unwrap();
+        let keyring = ApproverKeyring::from_config(&config).unwrap();
+        assert_eq!(keyring.min_approvals(), 2);
+        assert_eq!(keyring.key("alice"), Some(&key(1).verifying_key()));
+        assert!(keyring.key("bob").is_none());
+
+        let missing = PolicyApprovals {
+            approvers: [("bob".to_string(), dir.path().join("bob.key.pub"))].into(),
+            ..PolicyApprovals::default()
+        };
+        assert!(ApproverKeyring::from_config(&missing).is_err());
+        let zero = PolicyApprovals {
+            min_approvals: 0,
+            ..PolicyApprovals::default()
+        };
+        assert!(ApproverKeyring::from_config(&zero).is_err());
+        assert!(PolicyApprovals::from_yaml_with_profile(
+            "policy_approvals:\n  min_approval: 2\n",
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_transition_leaves_record_unchanged() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        submit(&store);
+
+        assert!(matches!(
+            store.activate("nat-limits", "ops".to_string()),
+            Err(LifecycleStoreError::Lifecycle(_))
+        ));
+        let record = store.load("nat-limits").unwrap();
+        assert_eq!(record.state, PolicyState::Review);
+        assert_eq!(record.history.transitions.len(), 1);
+        assert!(matches!(
+            store.load("missing"),
+            Err(LifecycleStoreError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_state_graph() {
+        let dir = TempDir::new().unwrap();
+        let store = store(&dir);
+        let graph = submit(&store).state_graph();
+
+        assert!(graph.starts_with("stateDiagram-v2"));
+        assert!(graph.contains("    draft --> review : author "));
+        assert!(graph.contains("    review --> approved\n"));
+        assert!(graph.contains("    class review current"));
+        assert!(graph.contains("    class draft visited"));
+    }
+}
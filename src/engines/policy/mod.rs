@@ -7,6 +7,7 @@ pub mod exemption_ci;
 pub mod exemption_types;
 pub mod exemption_validator;
 pub mod lifecycle;
+pub mod lifecycle_store;
 mod metadata_engine;
 mod policy_engine;
 mod policy_history;
@@ -36,6 +37,11 @@ pub use lifecycle::{
     PolicyLifecycle as LifecycleStateMachine, PolicyState, StateTransition,
 };
 
+pub use lifecycle_store::{
+    ApproverKeyring, LifecycleStore, LifecycleStoreError, PolicyApproval, PolicyApprovals,
+    PolicyRecord, LIFECYCLE_DIR,
+};
+
 // Metadata engine exports - PolicyRule from metadata_engine
 pub use metadata_engine::{
     MetadataPolicyEngine, MetadataPolicyResult, MetadataPolicyViolation,
@@ -45,8 +51,9 @@ pub use metadata_engine::{
 // Parser DSL exports - PolicyRule from parser
 pub use parser::{
     Condition, ConditionType, ConditionValue, DslParser, EvaluationContext, EvaluationResult,
-    LoadError, Operator, ParseError, PolicyRule as DslPolicyRule, PolicyRuleLoader, RuleAction,
-    RuleEvaluator, RuleMatch, RuleSeverity, RuleStatistics,
+    LoadError, Operator, PackRule, PackedRules, ParseError, PolicyRule as DslPolicyRule,
+    PolicyRuleLoader, PolicySource, PolicySources, RuleAction, RuleConflict, RuleEvaluator,
+    RuleMatch, RuleSeverity, RuleStatistics,
 };
 
 pub use policy_engine::*;
@@ -1,6 +1,7 @@
 // Policy loader - Load policy rules from files and directories
 
 use super::dsl::{DslParser, ParseError, PolicyRule};
+use super::sources::{PackedRules, PolicySource, PolicySources};
 use dirs;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -8,6 +9,8 @@ use std::path::{Path, PathBuf};
 /// Policy rule loader with multiple search paths
 pub struct PolicyRuleLoader {
     search_paths: Vec<PathBuf>,
+    project_paths: usize,
+    sources: Vec<PolicySource>,
 }
 
 impl PolicyRuleLoader {
@@ -15,16 +18,37 @@ impl PolicyRuleLoader {
     pub fn new() -> Self {
         Self {
             search_paths: Self::default_search_paths(),
+            project_paths: 2,
+            sources: Vec::new(),
         }
     }
 
     /// Create loader with custom search paths
     pub fn with_paths(paths: Vec<PathBuf>) -> Self {
         Self {
+            project_paths: paths.len(),
             search_paths: paths,
+            sources: Vec::new(),
         }
     }
 
+    /// Add `policy_sources` packs after the project's own search paths and
+    /// before user and system-wide ones, in the order listed
+    pub fn with_sources(mut self, sources: &PolicySources) -> Self {
+        for source in &sources.sources {
+            self.search_paths
+                .insert(self.project_paths, source.path.clone());
+            self.project_paths += 1;
+        }
+        self.sources.extend(sources.sources.iter().cloned());
+        self
+    }
+
+    /// Search paths in precedence order
+    pub fn search_paths(&self) -> &[PathBuf] {
+        &self.search_paths
+    }
+
     /// Default search paths for policy rules
     pub fn default_search_paths() -> Vec<PathBuf> {
         let mut paths = vec![
@@ -86,6 +110,35 @@ impl PolicyRuleLoader {
         Ok(all_rules)
     }
 
+    /// Load rules pack by pack in search path order; a rule name already
+    /// defined by an earlier pack is reported as a conflict and skipped
+    pub fn load_packs(&self) -> Result<PackedRules, LoadError> {
+        let mut packed = PackedRules::default();
+
+        for path in &self.search_paths {
+            let source = self.sources.iter().find(|s| &s.path == path);
+            let pack = source
+                .map(|s| s.pack_name())
+                .unwrap_or_else(|| path.display().to_string());
+
+            match self.load_from_path(path) {
+                Ok(rules) => packed.add_pack(&pack, rules),
+                Err(LoadError::PathNotFound(_)) if !source.is_some_and(|s| s.required) => {
+                    continue;
+                }
+                Err(LoadError::PathNotFound(_)) => {
+                    return Err(LoadError::SourceNotFound {
+                        pack,
+                        path: path.clone(),
+                    });
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(packed)
+    }
+
     /// Load rules from a specific path (file or directory)
     pub fn load_from_path(&self, path: &Path) -> Result<Vec<PolicyRule>, LoadError> {
         if !path.exists() {
@@ -146,12 +199,16 @@ impl PolicyRuleLoader {
         let entries = fs::read_dir(dir_path)
             .map_err(|e| LoadError::ReadError(dir_path.to_path_buf(), e.to_string()))?;
 
+        // Sorted so rule order, and which duplicate wins, is stable
+        let mut paths = Vec::new();
         for entry in entries {
             let entry =
                 entry.map_err(|e| LoadError::ReadError(dir_path.to_path_buf(), e.to_string()))?;
+            paths.push(entry.path());
+        }
+        paths.sort();
 
-            let path = entry.path();
-
+        for path in paths {
             // Skip non-files and hidden files
             if !path.is_file()
                 || path
@@ -272,6 +329,9 @@ pub enum LoadError {
     #[error("Rule validation failed: {0}")]
     ValidationError(String),
 
+    #[error("Policy source '{pack}' not found at {} (is the submodule checked out?)", path.display())]
+    SourceNotFound { pack: String, path: PathBuf },
+
     #[error("No rules found in searched paths: {searched_paths:?}")]
     NoRulesFound {
         searched_paths: Vec<PathBuf>,
@@ -298,4 +358,52 @@ mod tests {
         let result = loader.load_from_path(Path::new("/nonexistent/path"));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_load_packs_with_sources() {
+        let temp = tempfile::tempdir().unwrap();
+        let project = temp.path().join("project");
+        let shared = temp.path().join("shared");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(&shared).unwrap();
+        fs::write(
+            project.join("rules.yaml"),
+            "- name: max-cost\n  action:\n    type: block\n    message: project\n",
+        )
+        .unwrap();
+        fs::write(
+            shared.join("org.yaml"),
+            "rules:\n  - name: max-cost\n    action:\n      type: warn\n      message: org\n  - name: require-tags\n    action:\n      type: warn\n      message: org\n",
+        )
+        .unwrap();
+
+        let sources = PolicySources {
+            sources: vec![PolicySource {
+                path: shared.clone(),
+                name: Some("org".to_string()),
+                required: true,
+            }],
+        };
+        let loader = PolicyRuleLoader::with_paths(vec![project.clone()]).with_sources(&sources);
+        let packed = loader.load_packs().unwrap();
+
+        assert_eq!(loader.search_paths(), &[project, shared.clone()]);
+        assert_eq!(packed.rules.len(), 2);
+        assert_eq!(packed.rules[1].pack, "org");
+        assert_eq!(packed.conflicts.len(), 1);
+        assert_eq!(packed.conflicts[0].shadowed, "org");
+
+        let missing = PolicySources {
+            sources: vec![PolicySource {
+                path: temp.path().join("not-checked-out"),
+                name: Some("vendor".to_string()),
+                required: true,
+            }],
+        };
+        let err = PolicyRuleLoader::with_paths(Vec::new())
+            .with_sources(&missing)
+            .load_packs()
+            .unwrap_err();
+        assert!(matches!(err, LoadError::SourceNotFound { ref pack, .. } if pack == "vendor"));
+    }
 }
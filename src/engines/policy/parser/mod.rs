@@ -2,6 +2,8 @@
 
 pub mod dsl;
 pub mod loader;
+pub mod sources;
 
 pub use dsl::*;
 pub use loader::*;
+pub use sources::*;
@@ -0,0 +1,232 @@
+// Policy sources - rule packs from other checked-out directories
+//
+// `policy_sources` in costpilot.yaml lists directories outside the project,
+// typically a git submodule or a sibling checkout of a shared policy repo,
+// so an organization can manage rules centrally without a policy server.
+// Packs are searched after the project's own rules and in the order listed;
+// when two packs define a rule with the same name the earlier one wins and
+// the conflict is reported.
+
+use super::dsl::PolicyRule;
+use crate::engines::shared::error_model::CostPilotError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// A directory or file of policy rules (`policy_sources` entry)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicySource {
+    /// Directory or rule file, relative to the directory costpilot runs in
+    pub path: PathBuf,
+
+    /// Pack name shown in listings and conflicts (default: the path)
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Fail when the path is missing, e.g. a submodule not checked out
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl PolicySource {
+    pub fn pack_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
+/// Policy packs in precedence order (`policy_sources` in costpilot.yaml)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PolicySources {
+    pub sources: Vec<PolicySource>,
+}
+
+impl PolicySources {
+    /// Load `policy_sources` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self, CostPilotError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `policy_sources` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self, CostPilotError> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `policy_sources` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(
+        content: &str,
+        profile: Option<&str>,
+    ) -> Result<Self, CostPilotError> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let sources: Self = match root.get("policy_sources").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid policy_sources section: {}", e))
+            })?,
+            None => Self::default(),
+        };
+        let problems = sources.validate();
+        if !problems.is_empty() {
+            return Err(CostPilotError::config_error(format!(
+                "Invalid policy_sources section: {}",
+                problems.join("; ")
+            )));
+        }
+        Ok(sources)
+    }
+
+    /// Problems with the listed sources
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut names = HashSet::new();
+        let mut paths = HashSet::new();
+        for (i, source) in self.sources.iter().enumerate() {
+            if source.path.as_os_str().is_empty() {
+                problems.push(format!("policy_sources[{}] has an empty path", i));
+                continue;
+            }
+            if !paths.insert(&source.path) {
+                problems.push(format!(
+                    "policy_sources[{}] repeats path '{}'",
+                    i,
+                    source.path.display()
+                ));
+            }
+            if !names.insert(source.pack_name()) {
+                problems.push(format!(
+                    "policy_sources[{}] repeats pack name '{}'",
+                    i,
+                    source.pack_name()
+                ));
+            }
+        }
+        problems
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+/// A loaded rule and the pack it came from
+#[derive(Debug, Clone)]
+pub struct PackRule {
+    pub pack: String,
+    pub rule: PolicyRule,
+}
+
+/// A rule name defined by more than one pack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleConflict {
+    pub rule: String,
+
+    /// Pack whose definition is used
+    pub kept: String,
+
+    /// Pack whose definition is ignored
+    pub shadowed: String,
+}
+
+/// Rules from every pack after precedence is applied
+#[derive(Debug, Clone, Default)]
+pub struct PackedRules {
+    pub rules: Vec<PackRule>,
+    pub conflicts: Vec<RuleConflict>,
+}
+
+impl PackedRules {
+    /// Add a pack's rules; names already defined by an earlier pack are
+    /// recorded as conflicts and skipped
+    pub fn add_pack(&mut self, pack: &str, rules: Vec<PolicyRule>) {
+        for rule in rules {
+            if let Some(existing) = self.rules.iter().find(|r| r.rule.name == rule.name) {
+                self.conflicts.push(RuleConflict {
+                    rule: rule.name,
+                    kept: existing.pack.clone(),
+                    shadowed: pack.to_string(),
+                });
+                continue;
+            }
+            self.rules.push(PackRule {
+                pack: pack.to_string(),
+                rule,
+            });
+        }
+    }
+
+    /// The winning rules without their packs
+    pub fn into_rules(self) -> Vec<PolicyRule> {
+        self.rules.into_iter().map(|r| r.rule).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::policy::parser::DslParser;
+
+    fn rules(yaml: &str) -> Vec<PolicyRule> {
+        DslParser::parse_yaml(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_policy_sources_from_yaml() {
+        let sources = PolicySources::from_yaml(
+            "policy_sources:\n  - path: ../shared-policies\n    name: org\n  - path: vendor/team-rules\n    required: false\n",
+        )
+        .unwrap();
+        assert_eq!(sources.sources.len(), 2);
+        assert_eq!(sources.sources[0].pack_name(), "org");
+        assert!(sources.sources[0].required);
+        assert_eq!(sources.sources[1].pack_name(), "vendor/team-rules");
+        assert!(!sources.sources[1].required);
+
+        assert!(PolicySources::from_yaml("version: 1.0.0\n")
+            .unwrap()
+            .is_empty());
+        let err = PolicySources::from_yaml(
+            "policy_sources:\n  - path: ../a\n    name: org\n  - path: ../b\n    name: org\n",
+        )
+        .unwrap_err();
+        assert!(err.message.contains("repeats pack name 'org'"));
+    }
+
+    #[test]
+    fn test_packed_rules_keep_first_definition() {
+        let project = rules(
+            "- name: max-cost\n  severity: High\n  action:\n    type: block\n    message: project\n",
+        );
+        let shared = rules(
+            "- name: max-cost\n  action:\n    type: warn\n    message: org\n- name: require-tags\n  action:\n    type: warn\n    message: org\n",
+        );
+
+        let mut packed = PackedRules::default();
+        packed.add_pack("project", project);
+        packed.add_pack("org", shared);
+
+        assert_eq!(packed.rules.len(), 2);
+        assert_eq!(packed.rules[0].pack, "project");
+        assert_eq!(packed.rules[1].pack, "org");
+        assert_eq!(
+            packed.conflicts,
+            vec![RuleConflict {
+                rule: "max-cost".to_string(),
+                kept: "project".to_string(),
+                shadowed: "org".to_string(),
+            }]
+        );
+    }
+}
@@ -47,8 +47,9 @@ impl PolicyEngine {
         // Gate enforcement mode for premium (skip enforcement gating in Free)
         if !self.edition.is_premium() {
             // Free edition: lint-only mode
-            eprintln!("⚠️  Free edition: Policy enforcement disabled (lint-only mode)");
-            eprintln!("   Upgrade to Premium to block deployments on policy violations");
+            tracing::warn!(
+                "Free edition: policy enforcement disabled (lint-only mode); upgrade to Premium to block deployments on policy violations"
+            );
         }
 
         let mut result = PolicyResult::new();
@@ -455,6 +456,7 @@ mod tests {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: None,
         };
 
         let result = engine.evaluate(&[], &cost);
@@ -556,6 +558,7 @@ mod tests {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: None,
         };
 
         let result = engine.evaluate(&changes, &cost);
@@ -703,6 +706,7 @@ mod tests {
             },
             pro: None,
             paths: crate::edition::EditionPaths::default(),
+            org_policy: None,
         };
         let engine = PolicyEngine::with_exemptions(config, exemptions, &edition);
 
@@ -734,6 +738,7 @@ mod tests {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: None,
         };
 
         let result = engine.evaluate(&changes, &cost);
@@ -1,5 +1,6 @@
 // Policy versioning and history tracking
 
+use super::lifecycle::StateTransition;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -78,6 +79,10 @@ pub struct PolicyHistory {
 
     /// Current active version
     pub current_version: String,
+
+    /// Lifecycle state transitions (ordered by time)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub transitions: Vec<StateTransition>,
 }
 
 impl PolicyHistory {
@@ -105,6 +110,7 @@ impl PolicyHistory {
             policy_id,
             versions: vec![initial_version],
             current_version: version,
+            transitions: Vec::new(),
         }
     }
 
@@ -157,6 +163,11 @@ impl PolicyHistory {
         Ok(new_version)
     }
 
+    /// Record a lifecycle state transition
+    pub fn record_transition(&mut self, transition: StateTransition) {
+        self.transitions.push(transition);
+    }
+
     /// Get a specific version
     pub fn get_version(&self, version: &str) -> Option<&PolicyVersion> {
         self.versions.iter().find(|v| v.version == version)
@@ -1,18 +1,14 @@
 // Document calculation steps for explainability
 
-use crate::engines::shared::models::{CostEstimate, ResourceChange};
+use crate::engines::prediction::cloudwatch_pricing::CloudWatchRate;
+use crate::engines::prediction::container_pricing::{FargateRate, NodeGroupRate, TaskSizeSource};
+use crate::engines::prediction::dynamodb_pricing::{BillingMode, DynamoDbRate};
+use crate::engines::prediction::ec2_pricing::{Ec2Rate, Tenancy};
+use crate::engines::prediction::rds_pricing::RdsRate;
+pub use crate::engines::shared::models::CalculationStep;
+use crate::engines::shared::models::{ChangeAction, CostEstimate, ResourceChange};
 use serde::{Deserialize, Serialize};
 
-/// A step in the cost calculation process
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CalculationStep {
-    pub step_number: usize,
-    pub operation: String,
-    pub input: String,
-    pub output: String,
-    pub reasoning: String,
-}
-
 /// Full calculation breakdown
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalculationBreakdown {
@@ -40,48 +36,148 @@ pub fn document_calculation(
     }
 }
 
-/// Create a step for EC2 instance calculation
-pub fn ec2_calculation_step(
-    step: usize,
-    instance_type: &str,
-    hourly_rate: f64,
-    hours: f64,
-) -> CalculationStep {
+/// Create a step for EC2 instance calculation, itemizing any tenancy,
+/// license and EBS-optimized adjustments to the base rate
+pub fn ec2_calculation_step(step: usize, rate: &Ec2Rate, hours: f64) -> CalculationStep {
+    let mut input = format!(
+        "instance_type={}, hourly_rate=${:.4}, hours={}",
+        rate.instance_type, rate.base_hourly, hours
+    );
+    let mut reasoning = format!(
+        "EC2 {} instance runs at ${:.4}/hour for {} hours/month",
+        rate.instance_type, rate.base_hourly, hours
+    );
+
+    if rate.tenancy != Tenancy::Default {
+        input.push_str(&format!(", tenancy={}", rate.tenancy.as_str()));
+        reasoning.push_str(&format!(
+            "\n  - {} tenancy: ×{:.2} = +${:.4}/hour",
+            rate.tenancy.as_str(),
+            rate.tenancy_multiplier,
+            rate.tenancy_hourly()
+        ));
+    }
+    if let Some(label) = rate.license_label() {
+        input.push_str(&format!(", license={}, vcpus={}", label, rate.vcpus));
+        for (license, per_vcpu) in &rate.licenses {
+            reasoning.push_str(&format!(
+                "\n  - {} license: {} vCPU × ${:.4} = +${:.4}/hour",
+                license.key(),
+                rate.vcpus,
+                per_vcpu,
+                per_vcpu * rate.vcpus as f64
+            ));
+        }
+    }
+    if rate.ebs_optimized {
+        input.push_str(", ebs_optimized=true");
+        reasoning.push_str(&if rate.ebs_optimized_hourly > 0.0 {
+            format!(
+                "\n  - EBS-optimized: +${:.4}/hour",
+                rate.ebs_optimized_hourly
+            )
+        } else {
+            "\n  - EBS-optimized: included in the instance rate".to_string()
+        });
+    }
+    if rate.is_adjusted() {
+        reasoning.push_str(&format!(
+            "\nTotal ${:.4}/hour for {} hours/month",
+            rate.hourly(),
+            hours
+        ));
+    }
+
     CalculationStep {
         step_number: step,
         operation: "EC2 Instance Cost".to_string(),
-        input: format!(
-            "instance_type={}, hourly_rate=${:.4}, hours={}",
-            instance_type, hourly_rate, hours
-        ),
-        output: format!("${:.2}/month", hourly_rate * hours),
-        reasoning: format!(
-            "EC2 {} instance runs at ${:.4}/hour for {} hours/month",
-            instance_type, hourly_rate, hours
-        ),
+        input,
+        output: format!("${:.2}/month", rate.hourly() * hours),
+        reasoning,
     }
 }
 
-/// Create a step for RDS instance calculation
-pub fn rds_calculation_step(
-    step: usize,
-    engine: &str,
-    instance_class: &str,
-    hourly_rate: f64,
-    hours: f64,
-) -> CalculationStep {
+/// Create a step for RDS instance calculation, itemizing Multi-AZ, storage,
+/// provisioned IOPS and Serverless v2 capacity; storage autoscaling and
+/// capacity ranges are reported as a cost range
+pub fn rds_calculation_step(step: usize, rate: &RdsRate, hours: f64) -> CalculationStep {
+    let mut input = format!(
+        "engine={}, instance_class={}, hourly_rate=${:.4}, hours={}",
+        rate.engine, rate.instance_class, rate.instance_hourly, hours
+    );
+    let mut reasoning = match rate.acu_range {
+        Some((min, max)) => {
+            input.push_str(&format!(", acu={}-{}", min, max));
+            format!(
+                "RDS {} Serverless v2 scales between {} and {} ACU at ${:.4}/ACU-hour = ${:.2} - ${:.2}/month",
+                rate.engine,
+                min,
+                max,
+                rate.acu_hourly,
+                rate.acu_monthly(min, hours),
+                rate.acu_monthly(max, hours)
+            )
+        }
+        None => format!(
+            "RDS {} {} instance runs at ${:.4}/hour for {} hours/month",
+            rate.engine, rate.instance_class, rate.instance_hourly, hours
+        ),
+    };
+
+    if rate.storage_gb > 0.0 {
+        input.push_str(&format!(
+            ", storage_type={}, allocated_storage={}",
+            rate.storage_type, rate.storage_gb
+        ));
+        reasoning.push_str(&format!(
+            "\n  - Storage: {} GB {} × ${:.4}/GB = ${:.2}/month",
+            rate.storage_gb,
+            rate.storage_type,
+            rate.storage_per_gb,
+            rate.storage_monthly(rate.storage_gb)
+        ));
+    }
+    if let Some(max) = rate.max_storage_gb {
+        input.push_str(&format!(", max_allocated_storage={}", max));
+        reasoning.push_str(&format!(
+            "\n  - Storage autoscaling up to {} GB: up to ${:.2}/month",
+            max,
+            rate.storage_monthly(max)
+        ));
+    }
+    if rate.billed_iops > 0.0 {
+        input.push_str(&format!(", billed_iops={}", rate.billed_iops));
+        reasoning.push_str(&format!(
+            "\n  - Provisioned IOPS: {} × ${:.4}/IOPS = ${:.2}/month",
+            rate.billed_iops,
+            rate.iops_per_month,
+            rate.iops_monthly()
+        ));
+    }
+    if rate.is_multi_az() {
+        input.push_str(", multi_az=true");
+        reasoning.push_str(&format!(
+            "\n  - Multi-AZ standby: instance, storage and IOPS ×{:.2}",
+            rate.multi_az_multiplier
+        ));
+    }
+
+    let (low, high) = rate.monthly_range(hours);
+    let output = if rate.is_range() {
+        format!("${:.2} - ${:.2}/month", low, high)
+    } else {
+        format!("${:.2}/month", low)
+    };
+    if rate.is_range() || rate.storage_gb > 0.0 || rate.is_multi_az() {
+        reasoning.push_str(&format!("\nTotal {}", output));
+    }
+
     CalculationStep {
         step_number: step,
         operation: "RDS Instance Cost".to_string(),
-        input: format!(
-            "engine={}, instance_class={}, hourly_rate=${:.4}, hours={}",
-            engine, instance_class, hourly_rate, hours
-        ),
-        output: format!("${:.2}/month", hourly_rate * hours),
-        reasoning: format!(
-            "RDS {} {} instance runs at ${:.4}/hour for {} hours/month",
-            engine, instance_class, hourly_rate, hours
-        ),
+        input,
+        output,
+        reasoning,
     }
 }
 
@@ -104,34 +200,80 @@ pub fn storage_calculation_step(
     }
 }
 
-/// Create a step for DynamoDB calculation
-pub fn dynamodb_calculation_step(
-    step: usize,
-    billing_mode: &str,
-    rcu: Option<i64>,
-    wcu: Option<i64>,
-    rcu_cost: f64,
-    wcu_cost: f64,
-) -> CalculationStep {
-    let (input, output, reasoning) = match billing_mode {
-        "PROVISIONED" => {
-            let rcu = rcu.unwrap_or(5);
-            let wcu = wcu.unwrap_or(5);
-            let cost = (rcu as f64 * rcu_cost) + (wcu as f64 * wcu_cost);
-            (
-                format!("billing_mode={}, rcu={}, wcu={}", billing_mode, rcu, wcu),
-                format!("${:.2}/month", cost),
-                format!(
-                    "Provisioned capacity: {} RCU at ${:.4} + {} WCU at ${:.4}",
-                    rcu, rcu_cost, wcu, wcu_cost
-                ),
+/// Create a step for DynamoDB calculation in the table's billing mode, with
+/// autoscaling bands reported as a cost range
+pub fn dynamodb_calculation_step(step: usize, rate: &DynamoDbRate, hours: f64) -> CalculationStep {
+    let mut input = format!("billing_mode={}", rate.billing_mode.as_str());
+    let mut reasoning = match rate.billing_mode {
+        BillingMode::OnDemand => {
+            input.push_str(&format!(
+                ", reads={}, writes={}",
+                rate.read_requests, rate.write_requests
+            ));
+            format!(
+                "On-demand billing: {} reads at ${:.8} = ${:.2}, {} writes at ${:.8} = ${:.2}",
+                rate.read_requests,
+                rate.read_request_price,
+                rate.read_requests_monthly(),
+                rate.write_requests,
+                rate.write_request_price,
+                rate.write_requests_monthly()
             )
         }
-        _ => (
-            format!("billing_mode={}", billing_mode),
-            "$0.00/month (base)".to_string(),
-            "On-demand billing - pay per request (requests not estimated in plan)".to_string(),
-        ),
+        BillingMode::Provisioned => {
+            input.push_str(&format!(
+                ", rcu={}, wcu={}",
+                rate.read.units, rate.write.units
+            ));
+            let mut reasoning = format!(
+                "Provisioned capacity: {} RCU at ${:.5}/hour + {} WCU at ${:.5}/hour for {} hours",
+                rate.read.units,
+                rate.read.unit_hourly,
+                rate.write.units,
+                rate.write.unit_hourly,
+                hours
+            );
+            for (name, capacity) in [("RCU", &rate.read), ("WCU", &rate.write)] {
+                if let Some(band) = capacity.band {
+                    let (low, high) = capacity.monthly_range(hours);
+                    reasoning.push_str(&format!(
+                        "\n  - {} autoscaling {}-{}{}: ${:.2} - ${:.2}/month",
+                        name,
+                        band.min,
+                        band.max,
+                        band.target_utilization
+                            .map(|t| format!(" at {:.0}% target utilization", t * 100.0))
+                            .unwrap_or_default(),
+                        low,
+                        high
+                    ));
+                }
+            }
+            reasoning
+        }
+    };
+
+    input.push_str(&format!(", storage_gb={}", rate.storage_gb));
+    reasoning.push_str(&format!(
+        "\n  - Storage: {} GB at ${:.4}/GB = ${:.2}",
+        rate.storage_gb,
+        rate.storage_per_gb,
+        rate.storage_monthly()
+    ));
+    if !rate.usage.is_profiled() && rate.billing_mode == BillingMode::OnDemand {
+        reasoning.push_str("\n  - No usage profile: default request volumes and storage");
+    }
+
+    let (low, high) = rate.monthly_range(hours);
+    let output = if rate.is_range() {
+        format!(
+            "${:.2}/month (${:.2} - ${:.2})",
+            rate.monthly(hours),
+            low,
+            high
+        )
+    } else {
+        format!("${:.2}/month", rate.monthly(hours))
     };
 
     CalculationStep {
@@ -143,6 +285,129 @@ pub fn dynamodb_calculation_step(
     }
 }
 
+/// Create a step for an EKS cluster's control plane
+pub fn eks_control_plane_step(step: usize, hourly_rate: f64, hours: f64) -> CalculationStep {
+    CalculationStep {
+        step_number: step,
+        operation: "EKS Control Plane".to_string(),
+        input: format!("hourly_rate=${:.4}, hours={}", hourly_rate, hours),
+        output: format!("${:.2}/month", hourly_rate * hours),
+        reasoning: format!(
+            "The control plane bills ${:.4}/hour for {} hours/month, whatever runs on the cluster",
+            hourly_rate, hours
+        ),
+    }
+}
+
+/// Create a step for a managed node group: the per-node EC2 step's cost
+/// times the group's size, with its scaling range reported as a cost range
+pub fn node_group_calculation_step(
+    step: usize,
+    rate: &NodeGroupRate,
+    hours: f64,
+) -> CalculationStep {
+    let node = rate.node_monthly(hours);
+    let mut reasoning = format!(
+        "{} {} nodes at ${:.2}/month each (see EC2 Instance Cost)",
+        rate.desired, rate.node.instance_type, node
+    );
+    let output = if rate.is_range() {
+        let (low, high) = rate.monthly_range(hours);
+        reasoning.push_str(&format!(
+            "
+  - Scales between {} and {} nodes: ${:.2} - ${:.2}/month",
+            rate.min, rate.max, low, high
+        ));
+        format!(
+            "${:.2}/month (${:.2} - ${:.2})",
+            rate.monthly(hours),
+            low,
+            high
+        )
+    } else {
+        format!("${:.2}/month", rate.monthly(hours))
+    };
+
+    CalculationStep {
+        step_number: step,
+        operation: "EKS Node Group Cost".to_string(),
+        input: format!(
+            "instance_type={}, desired={}, min={}, max={}",
+            rate.node.instance_type, rate.desired, rate.min, rate.max
+        ),
+        output,
+        reasoning,
+    }
+}
+
+/// Create a step for Fargate tasks or pods billed per vCPU-second and
+/// GB-second
+pub fn fargate_calculation_step(step: usize, rate: &FargateRate, hours: f64) -> CalculationStep {
+    let seconds = hours * 3600.0;
+    let mut reasoning = format!(
+        "{} tasks × {} hours/month ({} seconds)\n  - vCPU: {} vCPU at ${:.9}/vCPU-second = ${:.2}\n  - Memory: {} GB at ${:.9}/GB-second = ${:.2}",
+        rate.tasks,
+        hours,
+        seconds,
+        rate.task.vcpu,
+        rate.vcpu_second,
+        rate.vcpu_monthly(hours),
+        rate.task.memory_gb,
+        rate.gb_second,
+        rate.memory_monthly(hours)
+    );
+    match rate.source {
+        TaskSizeSource::TaskDefinition => {}
+        TaskSizeSource::UsageProfile => reasoning.push_str("\n  - Pods sized by the usage profile"),
+        TaskSizeSource::Default => {
+            reasoning.push_str("\n  - No declared task size: default vCPU and memory")
+        }
+    }
+
+    CalculationStep {
+        step_number: step,
+        operation: "Fargate Cost".to_string(),
+        input: format!(
+            "tasks={}, vcpu={}, memory_gb={}",
+            rate.tasks, rate.task.vcpu, rate.task.memory_gb
+        ),
+        output: format!("${:.2}/month", rate.monthly(hours)),
+        reasoning,
+    }
+}
+
+/// Create a step for a CloudWatch resource, itemizing each charge
+pub fn cloudwatch_calculation_step(
+    step: usize,
+    resource_type: &str,
+    rate: &CloudWatchRate,
+) -> CalculationStep {
+    let mut input = format!("resource_type={}", resource_type);
+    let mut reasoning = String::from("CloudWatch bills:");
+    for charge in &rate.charges {
+        input.push_str(&format!(", {}={}", charge.unit, charge.quantity));
+        reasoning.push_str(&format!(
+            "\n  - {}: {} {} at ${:.2} = ${:.2}",
+            charge.name,
+            charge.quantity,
+            charge.unit,
+            charge.unit_price,
+            charge.monthly()
+        ));
+    }
+    for note in &rate.notes {
+        reasoning.push_str(&format!("\n  - {}", note));
+    }
+
+    CalculationStep {
+        step_number: step,
+        operation: "CloudWatch Cost".to_string(),
+        input,
+        output: format!("${:.2}/month", rate.monthly()),
+        reasoning,
+    }
+}
+
 /// Create a step for Lambda calculation
 pub fn lambda_calculation_step(
     step: usize,
@@ -250,6 +515,35 @@ pub fn s3_calculation_step(
     }
 }
 
+/// Create a step for a flat monthly rate looked up by resource type
+pub fn flat_rate_step(step: usize, resource_type: &str, monthly_rate: f64) -> CalculationStep {
+    CalculationStep {
+        step_number: step,
+        operation: "Flat Monthly Rate".to_string(),
+        input: format!("resource_type={}", resource_type),
+        output: format!("${:.2}/month", monthly_rate),
+        reasoning: format!(
+            "{} is priced at a flat ${:.2}/month",
+            resource_type, monthly_rate
+        ),
+    }
+}
+
+/// Create a step for the planned action's effect on ongoing cost
+pub fn action_step(step: usize, action: &ChangeAction, before: f64, after: f64) -> CalculationStep {
+    let reasoning = match action {
+        ChangeAction::Delete => "Deleted resources have no ongoing cost".to_string(),
+        _ => format!("{:?} keeps the full monthly cost", action),
+    };
+    CalculationStep {
+        step_number: step,
+        operation: "Planned Action".to_string(),
+        input: format!("action={:?}, cost=${:.2}", action, before),
+        output: format!("${:.2}/month", after),
+        reasoning,
+    }
+}
+
 /// Create a step for cold start inference
 pub fn cold_start_step(
     step: usize,
@@ -321,17 +615,76 @@ mod tests {
 
     #[test]
     fn test_ec2_step() {
-        let step = ec2_calculation_step(1, "t3.micro", 0.0104, 730.0);
+        let step = ec2_calculation_step(1, &Ec2Rate::on_demand("t3.micro", 0.0104), 730.0);
         assert_eq!(step.step_number, 1);
         assert!(step.reasoning.contains("t3.micro"));
         assert!(step.output.contains("$7.59"));
     }
 
+    #[test]
+    fn test_ec2_step_itemizes_adjustments() {
+        use crate::engines::prediction::ec2_pricing::{Ec2Configuration, License};
+        use crate::engines::prediction::prediction_engine::Ec2Options;
+
+        let config = Ec2Configuration {
+            instance_type: "m3.large".to_string(),
+            tenancy: Tenancy::Dedicated,
+            license: Some(License::Windows),
+            ebs_optimized: true,
+            vcpus: 2,
+        };
+        let rate = Ec2Rate::new(&config, 0.133, &Ec2Options::default());
+        let step = ec2_calculation_step(2, &rate, 730.0);
+
+        assert!(step.input.contains("tenancy=dedicated"));
+        assert!(step.input.contains("license=windows, vcpus=2"));
+        assert!(step.reasoning.contains("dedicated tenancy: ×1.10"));
+        assert!(step.reasoning.contains("windows license: 2 vCPU × $0.0460"));
+        assert!(step.reasoning.contains("EBS-optimized: +$0.0500/hour"));
+        // (0.133 × 1.1 + 0.092 + 0.05) × 730
+        assert_eq!(step.output, "$210.46/month");
+    }
+
     #[test]
     fn test_rds_step() {
-        let step = rds_calculation_step(1, "mysql", "db.t3.small", 0.034, 730.0);
+        let rate = RdsRate::on_demand("mysql", "db.t3.small", 0.034);
+        let step = rds_calculation_step(1, &rate, 730.0);
         assert_eq!(step.operation, "RDS Instance Cost");
         assert!(step.reasoning.contains("mysql"));
+        assert_eq!(step.output, "$24.82/month");
+    }
+
+    #[test]
+    fn test_rds_step_reports_autoscaling_range() {
+        let rate = RdsRate {
+            multi_az_multiplier: 2.0,
+            storage_type: "io1".to_string(),
+            storage_per_gb: 0.125,
+            storage_gb: 100.0,
+            max_storage_gb: Some(400.0),
+            billed_iops: 1000.0,
+            iops_per_month: 0.1,
+            ..RdsRate::on_demand("postgres", "db.m5.large", 0.178)
+        };
+        let step = rds_calculation_step(1, &rate, 730.0);
+
+        assert!(step.input.contains("max_allocated_storage=400"));
+        assert!(step
+            .reasoning
+            .contains("Provisioned IOPS: 1000 × $0.1000/IOPS"));
+        assert!(step.reasoning.contains("Multi-AZ standby"));
+        // (129.94 instance + 100 IOPS + 12.50 to 50.00 storage) × 2
+        assert_eq!(step.output, "$484.88 - $559.88/month");
+    }
+
+    #[test]
+    fn test_action_step_zeroes_deletes() {
+        let step = action_step(2, &ChangeAction::Delete, 150.0, 0.0);
+        assert_eq!(step.output, "$0.00/month");
+        assert!(step.reasoning.contains("no ongoing cost"));
+        assert!(flat_rate_step(1, "aws_instance", 150.0)
+            .reasoning
+            .contains("$150.00"));
     }
 
     #[test]
@@ -340,16 +693,93 @@ mod tests {
         assert!(step.output.contains("$10.00"));
     }
 
+    fn dynamodb_rate(config: serde_json::Value) -> DynamoDbRate {
+        use crate::engines::prediction::dynamodb_pricing::{DynamoDbConfiguration, DynamoDbUsage};
+        let heuristics = crate::engines::prediction::MinimalHeuristics::to_cost_heuristics();
+        DynamoDbRate::new(
+            &DynamoDbConfiguration::from_config(&config),
+            DynamoDbUsage::default(),
+            &heuristics.database.dynamodb,
+            &heuristics.cold_start_defaults,
+            730.0,
+        )
+    }
+
     #[test]
     fn test_dynamodb_provisioned_step() {
-        let step = dynamodb_calculation_step(1, "PROVISIONED", Some(10), Some(10), 0.13, 0.65);
+        let rate = dynamodb_rate(serde_json::json!({
+            "billing_mode": "PROVISIONED",
+            "read_capacity": 10,
+            "write_capacity": 10
+        }));
+        let step = dynamodb_calculation_step(1, &rate, 730.0);
         assert!(step.reasoning.contains("Provisioned capacity"));
+        assert!(step.input.contains("rcu=10, wcu=10"));
     }
 
     #[test]
     fn test_dynamodb_ondemand_step() {
-        let step = dynamodb_calculation_step(1, "PAY_PER_REQUEST", None, None, 0.0, 0.0);
+        let rate = dynamodb_rate(serde_json::json!({ "billing_mode": "PAY_PER_REQUEST" }));
+        let step = dynamodb_calculation_step(1, &rate, 730.0);
         assert!(step.reasoning.contains("On-demand"));
+        assert!(step.reasoning.contains("No usage profile"));
+        // 10M reads × $0.25/M + 1M writes × $1.25/M + 1 GB × $0.25
+        assert_eq!(step.output, "$4.00/month");
+    }
+
+    #[test]
+    fn test_eks_steps() {
+        use crate::engines::prediction::container_pricing::{
+            FargateConfiguration, NodeGroupConfiguration,
+        };
+        use crate::engines::prediction::prediction_engine::{ContainerOptions, Ec2Options};
+
+        let step = eks_control_plane_step(1, 0.10, 730.0);
+        assert_eq!(step.output, "$73.00/month");
+
+        let config = NodeGroupConfiguration::from_config(&serde_json::json!({
+            "instance_types": ["t3.medium"],
+            "scaling_config": [{ "desired_size": 2, "min_size": 1, "max_size": 4 }]
+        }));
+        let rate = NodeGroupRate::new(&config, 0.0416, &Ec2Options::default());
+        let step = node_group_calculation_step(2, &rate, 730.0);
+        assert_eq!(step.output, "$60.74/month ($30.37 - $121.47)");
+        assert!(step.reasoning.contains("Scales between 1 and 4 nodes"));
+
+        let options = ContainerOptions::default();
+        let config = FargateConfiguration::from_ecs_service(
+            &serde_json::json!({ "desired_count": 2 }),
+            &Default::default(),
+            &options,
+        );
+        let step = fargate_calculation_step(1, &FargateRate::new(&config, &options), 730.0);
+        assert_eq!(step.input, "tasks=2, vcpu=0.25, memory_gb=0.5");
+        assert!(step.reasoning.contains("default vCPU and memory"));
+    }
+
+    #[test]
+    fn test_cloudwatch_step() {
+        use crate::engines::prediction::prediction_engine::CloudWatchCost;
+        use crate::engines::prediction::usage_profile::UsageProfile;
+        use crate::engines::shared::models::ResourceChange;
+
+        let group = ResourceChange::builder()
+            .resource_id("aws_cloudwatch_log_group.app")
+            .resource_type("aws_cloudwatch_log_group")
+            .action(ChangeAction::Create)
+            .new_config(serde_json::json!({ "retention_in_days": 30 }))
+            .build();
+        let usage =
+            UsageProfile::default().with("aws_cloudwatch_log_group", "monthly_ingestion_gb", 100.0);
+        let rate = CloudWatchRate::from_change(&group, &usage, &CloudWatchCost::default()).unwrap();
+        let step = cloudwatch_calculation_step(1, "aws_cloudwatch_log_group", &rate);
+
+        assert!(step.input.contains("GB=100"));
+        assert!(step
+            .reasoning
+            .contains("Log ingestion: 100 GB at $0.50 = $50.00"));
+        // 30 days keeps just under a month of logs
+        assert_eq!(step.output, "$52.96/month");
     }
 
     #[test]
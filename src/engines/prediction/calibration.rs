@@ -0,0 +1,424 @@
+// Confidence calibration from billing actuals
+//
+// Users import what each module really cost (a CSV of module and monthly
+// cost). A module's relative prediction error is recorded against every
+// resource family (resource type) it contains, and a family's interval width
+// becomes the error that covered most of its past observations. Calibration
+// is persisted under the config dir and widens or narrows the prediction
+// intervals of later scans; the point estimates themselves are unchanged.
+
+use crate::engines::prediction::calculation_steps::CalculationStep;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::engines::shared::models::{CostEstimate, ResourceChange};
+use crate::engines::shared::utils::{module_address, split_csv_line, ROOT_MODULE};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Calibration file name under the config dir (`~/.costpilot/`)
+pub const CALIBRATION_FILE: &str = "calibration.json";
+
+/// Observations kept per family; older ones age out
+const MAX_OBSERVATIONS: usize = 50;
+
+/// Share of past observations the calibrated interval must cover
+const COVERAGE: f64 = 0.8;
+
+/// Actual monthly cost of one module, from a billing export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleActual {
+    pub module: String,
+    pub monthly_cost: f64,
+}
+
+/// Predicted against actual cost of one imported module
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleError {
+    pub module: String,
+    pub predicted: f64,
+    pub actual: f64,
+}
+
+impl ModuleError {
+    /// Relative error, `None` when nothing was predicted for the module
+    pub fn relative_error(&self) -> Option<f64> {
+        (self.predicted > 0.0).then(|| (self.actual - self.predicted).abs() / self.predicted)
+    }
+}
+
+/// Result of importing one billing file
+#[derive(Debug, Clone, Default)]
+pub struct CalibrationUpdate {
+    /// Modules compared, in name order
+    pub modules: Vec<ModuleError>,
+
+    /// Modules in the billing file with no resources in the plan
+    pub unmatched: Vec<String>,
+
+    /// Interval width per family before and after the import
+    pub widths: BTreeMap<String, (Option<f64>, f64)>,
+}
+
+/// Error history and calibrated interval width of one resource family
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FamilyCalibration {
+    /// Relative prediction errors, oldest first
+    pub observations: Vec<f64>,
+    /// Interval half-width as a fraction of the estimate
+    pub interval_width: f64,
+}
+
+impl FamilyCalibration {
+    fn observe(&mut self, relative_error: f64) {
+        self.observations.push(relative_error);
+        if self.observations.len() > MAX_OBSERVATIONS {
+            let excess = self.observations.len() - MAX_OBSERVATIONS;
+            self.observations.drain(..excess);
+        }
+
+        // Nearest-rank quantile of the observed errors
+        let mut sorted = self.observations.clone();
+        sorted.sort_by(f64::total_cmp);
+        let rank = ((sorted.len() as f64 * COVERAGE).ceil() as usize).clamp(1, sorted.len());
+        self.interval_width = sorted[rank - 1];
+    }
+}
+
+/// Interval widths learned from billing actuals, keyed by resource family
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfidenceCalibration {
+    #[serde(default)]
+    pub families: BTreeMap<String, FamilyCalibration>,
+}
+
+impl ConfidenceCalibration {
+    /// Calibration file inside `config_dir`
+    pub fn path(config_dir: &Path) -> PathBuf {
+        config_dir.join(CALIBRATION_FILE)
+    }
+
+    /// Load from file, starting empty if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            CostPilotError::new(
+                "CALIBRATE_001",
+                ErrorCategory::ParseError,
+                format!("Failed to parse {}: {}", path.display(), e),
+            )
+            .with_hint(format!(
+                "Delete {} to start calibration over",
+                path.display()
+            ))
+        })
+    }
+
+    /// Save to file, creating the parent directory if needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            CostPilotError::new(
+                "CALIBRATE_002",
+                ErrorCategory::InternalError,
+                format!("Failed to serialize calibration: {}", e),
+            )
+        })?;
+        std::fs::write(path, json).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.families.is_empty()
+    }
+
+    /// Calibrated interval width of a resource family, if it has history
+    pub fn interval_width(&self, resource_type: &str) -> Option<f64> {
+        self.families.get(resource_type).map(|f| f.interval_width)
+    }
+
+    /// Compare predictions with billing actuals and record each module's
+    /// error against the families it contains
+    pub fn record(
+        &mut self,
+        changes: &[ResourceChange],
+        estimates: &[CostEstimate],
+        actuals: &[ModuleActual],
+    ) -> CalibrationUpdate {
+        let types: HashMap<&str, &str> = changes
+            .iter()
+            .map(|c| (c.resource_id.as_str(), c.resource_type.as_str()))
+            .collect();
+        let modules: HashMap<&str, &str> = changes
+            .iter()
+            .map(|c| {
+                let module = c.module_path.as_deref().unwrap_or(ROOT_MODULE);
+                (c.resource_id.as_str(), module)
+            })
+            .collect();
+
+        // Predicted cost and families per module
+        let mut predicted: BTreeMap<&str, (f64, BTreeSet<&str>)> = BTreeMap::new();
+        for estimate in estimates {
+            let id = estimate.resource_id.as_str();
+            let (Some(module), Some(family)) = (modules.get(id), types.get(id)) else {
+                continue;
+            };
+            let entry = predicted.entry(module).or_default();
+            entry.0 += estimate.monthly_cost;
+            entry.1.insert(family);
+        }
+
+        let mut update = CalibrationUpdate::default();
+        let mut observed: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+        for actual in actuals {
+            let key = module_address(&actual.module);
+            let Some((cost, families)) = predicted.get(key.as_str()) else {
+                update.unmatched.push(actual.module.clone());
+                continue;
+            };
+            let error = ModuleError {
+                module: key.clone(),
+                predicted: *cost,
+                actual: actual.monthly_cost,
+            };
+            if let Some(relative) = error.relative_error() {
+                for family in families {
+                    observed.entry(family).or_default().push(relative);
+                }
+            }
+            update.modules.push(error);
+        }
+        update.modules.sort_by(|a, b| a.module.cmp(&b.module));
+
+        for (family, errors) in observed {
+            let before = self.interval_width(family);
+            let calibration = self.families.entry(family.to_string()).or_default();
+            for error in errors {
+                calibration.observe(error);
+            }
+            update
+                .widths
+                .insert(family.to_string(), (before, calibration.interval_width));
+        }
+        update
+    }
+
+    /// Replace the intervals of estimates whose family has been calibrated
+    pub fn apply(&self, changes: &[ResourceChange], estimates: &mut [CostEstimate]) {
+        if self.families.is_empty() {
+            return;
+        }
+        let types: HashMap<&str, &str> = changes
+            .iter()
+            .map(|c| (c.resource_id.as_str(), c.resource_type.as_str()))
+            .collect();
+
+        for estimate in estimates {
+            let Some(family) = types.get(estimate.resource_id.as_str()) else {
+                continue;
+            };
+            let Some(calibration) = self.families.get(*family) else {
+                continue;
+            };
+            let cost = estimate.monthly_cost;
+            let half_width = cost.abs() * calibration.interval_width;
+            estimate.prediction_interval_low = if cost >= 0.0 {
+                (cost - half_width).max(0.0)
+            } else {
+                cost - half_width
+            };
+            estimate.prediction_interval_high = cost + half_width;
+
+            if let Some(provenance) = &mut estimate.provenance {
+                provenance.assumptions.push(format!(
+                    "Interval calibrated from {} billing observation(s) of {}",
+                    calibration.observations.len(),
+                    family
+                ));
+                provenance.steps.push(CalculationStep {
+                    step_number: provenance.steps.len() + 1,
+                    operation: "Calibrated Interval".to_string(),
+                    input: format!(
+                        "estimate=${:.2}, observed error={:.0}%",
+                        cost,
+                        calibration.interval_width * 100.0
+                    ),
+                    output: format!(
+                        "${:.2} - ${:.2}",
+                        estimate.prediction_interval_low, estimate.prediction_interval_high
+                    ),
+                    reasoning: format!(
+                        "{:.0}% of past {} predictions were within ±{:.0}% of the bill",
+                        COVERAGE * 100.0,
+                        family,
+                        calibration.interval_width * 100.0
+                    ),
+                });
+            }
+        }
+    }
+}
+
+/// Parse a billing CSV with a `module` column and a cost column
+/// (`monthly_cost`, `cost` or `actual`)
+///
+/// Blank lines and lines starting with `#` are skipped; costs may carry a `$`
+/// prefix and thousands separators inside quotes.
+pub fn parse_actuals_csv(content: &str) -> Result<Vec<ModuleActual>> {
+    let invalid = |message: String| {
+        CostPilotError::new("CALIBRATE_003", ErrorCategory::InvalidInput, message).with_hint(
+            "Expected a header such as: module,monthly_cost (use 'root' for resources outside modules)"
+                .to_string(),
+        )
+    };
+
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (_, header) = lines
+        .next()
+        .ok_or_else(|| invalid("Billing file is empty".to_string()))?;
+    let columns: Vec<String> = split_csv_line(header)
+        .iter()
+        .map(|c| c.to_lowercase())
+        .collect();
+    let module_col = columns
+        .iter()
+        .position(|c| c == "module")
+        .ok_or_else(|| invalid("Billing file has no 'module' column".to_string()))?;
+    let cost_col = ["monthly_cost", "cost", "actual"]
+        .iter()
+        .find_map(|name| columns.iter().position(|c| c == name))
+        .ok_or_else(|| invalid("Billing file has no 'monthly_cost' column".to_string()))?;
+
+    let mut actuals = Vec::new();
+    for (line_no, line) in lines {
+        let fields = split_csv_line(line);
+        let field = |idx: usize| fields.get(idx).map(String::as_str).unwrap_or("");
+        let raw_cost = field(cost_col);
+        let monthly_cost: f64 = raw_cost
+            .trim_start_matches('$')
+            .replace(',', "")
+            .parse()
+            .ok()
+            .filter(|v: &f64| v.is_finite() && *v >= 0.0)
+            .ok_or_else(|| invalid(format!("Line {}: invalid cost '{}'", line_no, raw_cost)))?;
+        actuals.push(ModuleActual {
+            module: field(module_col).to_string(),
+            monthly_cost,
+        });
+    }
+    Ok(actuals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+
+    fn change(id: &str, resource_type: &str, module: Option<&str>) -> ResourceChange {
+        let mut change = ResourceChange::builder()
+            .resource_id(id)
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .build();
+        change.module_path = module.map(str::to_string);
+        change
+    }
+
+    #[test]
+    fn test_parse_actuals_csv() {
+        let csv = "# March bill\nModule,Service,Monthly_Cost\nmodule.web,EC2,\"$1,200.50\"\nroot,VPC,30\n\n";
+        let actuals = parse_actuals_csv(csv).unwrap();
+        assert_eq!(
+            actuals,
+            vec![
+                ModuleActual {
+                    module: "module.web".to_string(),
+                    monthly_cost: 1200.5
+                },
+                ModuleActual {
+                    module: "root".to_string(),
+                    monthly_cost: 30.0
+                },
+            ]
+        );
+        assert!(parse_actuals_csv("name,cost\nweb,1").is_err());
+        assert!(parse_actuals_csv("module,cost\nweb,lots").is_err());
+    }
+
+    #[test]
+    fn test_record_and_apply_calibrated_intervals() {
+        let changes = vec![
+            change(
+                "module.web.aws_instance.app",
+                "aws_instance",
+                Some("module.web"),
+            ),
+            change("aws_s3_bucket.logs", "aws_s3_bucket", None),
+        ];
+        let estimates = vec![
+            CostEstimate::new("module.web.aws_instance.app".to_string(), 100.0),
+            CostEstimate::new("aws_s3_bucket.logs".to_string(), 10.0),
+        ];
+        let actuals = vec![
+            ModuleActual {
+                module: "web".to_string(),
+                monthly_cost: 125.0,
+            },
+            ModuleActual {
+                module: "module.gone".to_string(),
+                monthly_cost: 5.0,
+            },
+        ];
+
+        let mut calibration = ConfidenceCalibration::default();
+        let update = calibration.record(&changes, &estimates, &actuals);
+        assert_eq!(update.unmatched, vec!["module.gone"]);
+        assert_eq!(update.modules[0].relative_error(), Some(0.25));
+        assert_eq!(update.widths["aws_instance"], (None, 0.25));
+        assert_eq!(calibration.interval_width("aws_s3_bucket"), None);
+
+        let mut scanned = estimates.clone();
+        calibration.apply(&changes, &mut scanned);
+        assert_eq!(scanned[0].prediction_interval_low, 75.0);
+        assert_eq!(scanned[0].prediction_interval_high, 125.0);
+        // Uncalibrated families keep their intervals
+        assert_eq!(scanned[1].prediction_interval_high, 12.0);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = ConfidenceCalibration::path(dir.path());
+        calibration.save(&path).unwrap();
+        let loaded = ConfidenceCalibration::load(&path).unwrap();
+        assert_eq!(loaded.interval_width("aws_instance"), Some(0.25));
+    }
+
+    #[test]
+    fn test_width_covers_most_observations() {
+        let mut family = FamilyCalibration::default();
+        for error in [0.1, 0.5, 0.2, 0.05, 0.15] {
+            family.observe(error);
+        }
+        // 80% of five observations is four: the fourth smallest error
+        assert_eq!(family.interval_width, 0.2);
+
+        for _ in 0..MAX_OBSERVATIONS {
+            family.observe(0.01);
+        }
+        assert_eq!(family.observations.len(), MAX_OBSERVATIONS);
+        assert_eq!(family.interval_width, 0.01);
+    }
+}
@@ -0,0 +1,332 @@
+// CloudWatch pricing: log ingestion and retention, custom metrics, alarms
+// and dashboards
+//
+// Monitoring resources are cheap one at a time and add up quietly. A log
+// group bills every GB ingested and every GB it keeps; how much it ingests
+// comes from the usage profile (`usage` in costpilot.yaml), and how much it
+// keeps follows from `retention_in_days`, with groups that never expire
+// priced as holding `unbounded_retention_months` of logs. Alarms bill per
+// metric they evaluate, metric filters publish custom metrics, and composite
+// alarms and dashboards have flat monthly rates. Rates come from
+// `monitoring.cloudwatch` in the heuristics.
+
+use crate::engines::prediction::plan_block;
+use crate::engines::prediction::prediction_engine::CloudWatchCost;
+use crate::engines::prediction::usage_profile::UsageProfile;
+use crate::engines::shared::models::ResourceChange;
+use serde_json::Value;
+
+/// Usage profile key for a log group's monthly ingestion
+pub const LOG_INGESTION_GB_KEY: &str = "monthly_ingestion_gb";
+
+const DAYS_PER_MONTH: f64 = 365.0 / 12.0;
+
+/// Alarm periods below this many seconds are high resolution
+const HIGH_RESOLUTION_PERIOD: f64 = 60.0;
+
+/// One billed quantity, e.g. GB ingested or alarm metrics
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloudWatchCharge {
+    pub name: String,
+    pub quantity: f64,
+    pub unit: &'static str,
+    pub unit_price: f64,
+}
+
+impl CloudWatchCharge {
+    fn new(name: &str, quantity: f64, unit: &'static str, unit_price: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            quantity,
+            unit,
+            unit_price,
+        }
+    }
+
+    pub fn monthly(&self) -> f64 {
+        self.quantity * self.unit_price
+    }
+}
+
+/// Monthly cost of a CloudWatch resource, by charge
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloudWatchRate {
+    pub charges: Vec<CloudWatchCharge>,
+
+    /// Assumptions behind the quantities
+    pub notes: Vec<String>,
+}
+
+impl CloudWatchRate {
+    /// Rate for a CloudWatch resource, from the config the change leaves in
+    /// place; `None` for other resource types
+    pub fn from_change(
+        change: &ResourceChange,
+        usage: &UsageProfile,
+        cost: &CloudWatchCost,
+    ) -> Option<Self> {
+        let empty = Value::Null;
+        let config = change
+            .new_config
+            .as_ref()
+            .or(change.old_config.as_ref())
+            .unwrap_or(&empty);
+
+        let rate = match change.resource_type.as_str() {
+            "aws_cloudwatch_log_group" => Self::log_group(change, config, usage, cost),
+            "aws_cloudwatch_log_metric_filter" => {
+                let metrics = match config.get("metric_transformation") {
+                    Some(Value::Array(items)) => items.len().max(1),
+                    _ => 1,
+                };
+                Self {
+                    charges: vec![CloudWatchCharge::new(
+                        "Custom metrics",
+                        metrics as f64,
+                        "metrics",
+                        cost.custom_metric_monthly,
+                    )],
+                    notes: vec![
+                        "Each metric transformation publishes one custom metric; dimensions publish one per value"
+                            .to_string(),
+                    ],
+                }
+            }
+            "aws_cloudwatch_metric_alarm" => Self::metric_alarm(config, cost),
+            "aws_cloudwatch_composite_alarm" => Self {
+                charges: vec![CloudWatchCharge::new(
+                    "Composite alarm",
+                    1.0,
+                    "alarms",
+                    cost.composite_alarm_monthly,
+                )],
+                notes: Vec::new(),
+            },
+            "aws_cloudwatch_dashboard" => Self {
+                charges: vec![CloudWatchCharge::new(
+                    "Dashboard",
+                    1.0,
+                    "dashboards",
+                    cost.dashboard_monthly,
+                )],
+                notes: vec!["The first 3 dashboards in an account are free".to_string()],
+            },
+            _ => return None,
+        };
+        Some(rate)
+    }
+
+    fn log_group(
+        change: &ResourceChange,
+        config: &Value,
+        usage: &UsageProfile,
+        cost: &CloudWatchCost,
+    ) -> Self {
+        let mut notes = Vec::new();
+
+        let ingestion_gb = usage.get(change, LOG_INGESTION_GB_KEY).unwrap_or_else(|| {
+            notes.push(format!(
+                "No usage profile: {} GB/month ingestion assumed",
+                cost.default_log_ingestion_gb
+            ));
+            cost.default_log_ingestion_gb
+        });
+        let infrequent_access = config
+            .get("log_group_class")
+            .and_then(|v| v.as_str())
+            .is_some_and(|class| class.eq_ignore_ascii_case("INFREQUENT_ACCESS"));
+
+        // 0 or unset means logs never expire
+        let retention_months = match config
+            .get("retention_in_days")
+            .and_then(|v| v.as_f64())
+            .filter(|&days| days > 0.0)
+        {
+            Some(days) => days / DAYS_PER_MONTH,
+            None => {
+                notes.push(format!(
+                    "Logs never expire: storage priced after {} months",
+                    cost.unbounded_retention_months
+                ));
+                cost.unbounded_retention_months
+            }
+        };
+
+        Self {
+            charges: vec![
+                CloudWatchCharge::new(
+                    if infrequent_access {
+                        "Log ingestion (Infrequent Access)"
+                    } else {
+                        "Log ingestion"
+                    },
+                    ingestion_gb,
+                    "GB",
+                    if infrequent_access {
+                        cost.logs_infrequent_access_ingestion_per_gb
+                    } else {
+                        cost.logs_ingestion_per_gb
+                    },
+                ),
+                // Steady state: each month's logs are kept for the retention
+                // period
+                CloudWatchCharge::new(
+                    "Log storage",
+                    ingestion_gb * retention_months,
+                    "GB-months",
+                    cost.logs_storage_per_gb,
+                ),
+            ],
+            notes,
+        }
+    }
+
+    fn metric_alarm(config: &Value, cost: &CloudWatchCost) -> Self {
+        let metric_queries: Vec<&Value> = config
+            .get("metric_query")
+            .and_then(|v| v.as_array())
+            .map(|queries| queries.iter().collect())
+            .unwrap_or_default();
+
+        // Metric math expressions are free; each queried metric is billed.
+        let queried: Vec<&Value> = metric_queries
+            .iter()
+            .filter_map(|query| plan_block(query, "metric"))
+            .filter(|metric| metric.is_object())
+            .collect();
+        let mut metrics = queried.len().max(1) as f64;
+
+        let period = |v: &Value| v.get("period").and_then(|p| p.as_f64());
+        let high_resolution = period(config)
+            .into_iter()
+            .chain(queried.iter().filter_map(|metric| period(metric)))
+            .any(|seconds| seconds < HIGH_RESOLUTION_PERIOD);
+
+        let mut notes = Vec::new();
+        // Anomaly detection bands bill as two more metrics
+        let anomaly_detection = config
+            .get("threshold_metric_id")
+            .and_then(|v| v.as_str())
+            .is_some_and(|id| !id.is_empty());
+        if anomaly_detection {
+            metrics += 2.0;
+            notes.push("Anomaly detection bills the band as 2 more metrics".to_string());
+        }
+
+        Self {
+            charges: vec![if high_resolution {
+                CloudWatchCharge::new(
+                    "Alarm metrics (high resolution)",
+                    metrics,
+                    "metrics",
+                    cost.alarm_high_resolution_monthly,
+                )
+            } else {
+                CloudWatchCharge::new(
+                    "Alarm metrics",
+                    metrics,
+                    "metrics",
+                    cost.alarm_standard_monthly,
+                )
+            }],
+            notes,
+        }
+    }
+
+    pub fn monthly(&self) -> f64 {
+        self.charges.iter().map(CloudWatchCharge::monthly).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+    use serde_json::json;
+
+    fn change(resource_type: &str, config: Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(format!("{}.main", resource_type))
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    #[test]
+    fn test_log_group_ingestion_and_retention() {
+        let cost = CloudWatchCost::default();
+        let usage = UsageProfile::default().with(
+            "aws_cloudwatch_log_group.main",
+            LOG_INGESTION_GB_KEY,
+            50.0,
+        );
+        let group = change(
+            "aws_cloudwatch_log_group",
+            json!({ "retention_in_days": 365 }),
+        );
+        let rate = CloudWatchRate::from_change(&group, &usage, &cost).unwrap();
+        // 50 GB × $0.50 + 50 GB × 12 months × $0.03
+        assert!((rate.monthly() - (25.0 + 18.0)).abs() < 1e-9);
+        assert!(rate.notes.is_empty());
+
+        // Never expires, no profile
+        let group = change(
+            "aws_cloudwatch_log_group",
+            json!({ "retention_in_days": 0 }),
+        );
+        let rate = CloudWatchRate::from_change(&group, &UsageProfile::default(), &cost).unwrap();
+        assert!((rate.monthly() - (0.5 + 12.0 * 0.03)).abs() < 1e-9);
+        assert_eq!(rate.notes.len(), 2);
+    }
+
+    #[test]
+    fn test_alarms_metric_filters_and_dashboards() {
+        let cost = CloudWatchCost::default();
+        let usage = UsageProfile::default();
+        let price = |resource_type: &str, config: Value| {
+            CloudWatchRate::from_change(&change(resource_type, config), &usage, &cost)
+                .unwrap()
+                .monthly()
+        };
+
+        assert_eq!(
+            price(
+                "aws_cloudwatch_metric_alarm",
+                json!({ "metric_name": "CPUUtilization", "period": 300 })
+            ),
+            0.10
+        );
+        assert_eq!(
+            price(
+                "aws_cloudwatch_metric_alarm",
+                json!({ "metric_name": "Latency", "period": 10 })
+            ),
+            0.30
+        );
+        // Two queried metrics and a free expression
+        let math = json!({
+            "metric_query": [
+                { "id": "e1", "expression": "m1 / m2" },
+                { "id": "m1", "metric": [{ "metric_name": "Errors", "period": 60 }] },
+                { "id": "m2", "metric": [{ "metric_name": "Requests", "period": 60 }] }
+            ]
+        });
+        assert!((price("aws_cloudwatch_metric_alarm", math) - 0.20).abs() < 1e-9);
+        let anomaly = json!({
+            "threshold_metric_id": "ad1",
+            "metric_query": [{ "id": "m1", "metric": [{ "metric_name": "Requests" }] }]
+        });
+        assert!((price("aws_cloudwatch_metric_alarm", anomaly) - 0.30).abs() < 1e-9);
+
+        let filter = json!({ "metric_transformation": [{ "name": "Errors" }] });
+        assert_eq!(price("aws_cloudwatch_log_metric_filter", filter), 0.30);
+        assert_eq!(price("aws_cloudwatch_dashboard", json!({})), 3.0);
+        assert!(CloudWatchRate::from_change(
+            &change("aws_cloudwatch_event_rule", json!({})),
+            &usage,
+            &cost
+        )
+        .is_none());
+    }
+}
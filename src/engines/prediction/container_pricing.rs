@@ -0,0 +1,357 @@
+// Container cluster pricing: EKS control plane, managed node groups and
+// Fargate
+//
+// An EKS cluster bills its control plane by the hour whether or not anything
+// runs on it. The workloads cost what they run on: a managed node group is a
+// set of EC2 instances, priced per node like `aws_instance` and scaled by its
+// `scaling_config`, while Fargate bills each task or pod for the vCPU and
+// memory it requests, per second. ECS services on Fargate take their task size
+// from the plan's task definitions; EKS Fargate profiles only select pods, so
+// their pod count and size come from the usage profile. Rates come from
+// `compute.containers` in the heuristics.
+
+use crate::engines::prediction::ec2_pricing::{Ec2Configuration, Ec2Rate};
+use crate::engines::prediction::plan_block;
+use crate::engines::prediction::prediction_engine::{ContainerOptions, Ec2Options};
+use crate::engines::prediction::usage_profile::UsageProfile;
+use crate::engines::shared::models::ResourceChange;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Usage profile keys for EKS Fargate profiles
+pub const PODS_KEY: &str = "pods";
+pub const POD_VCPU_KEY: &str = "vcpu_per_pod";
+pub const POD_MEMORY_GB_KEY: &str = "memory_gb_per_pod";
+
+/// Instance type EKS launches when a node group doesn't name one
+const DEFAULT_NODE_INSTANCE_TYPE: &str = "t3.medium";
+
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+/// Scaling and node configuration of an `aws_eks_node_group`
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeGroupConfiguration {
+    pub node: Ec2Configuration,
+    pub desired: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl NodeGroupConfiguration {
+    /// Read from planned node group attributes; nodes use the first of
+    /// `instance_types`
+    pub fn from_config(config: &Value) -> Self {
+        let instance_type = config
+            .get("instance_types")
+            .and_then(|v| v.as_array())
+            .and_then(|types| types.first())
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_NODE_INSTANCE_TYPE);
+        // Windows AMI types (WINDOWS_CORE_2022_x86_64, ...) bill the license
+        let windows = config
+            .get("ami_type")
+            .and_then(|v| v.as_str())
+            .is_some_and(|ami| ami.to_ascii_uppercase().starts_with("WINDOWS"));
+        let node = Ec2Configuration::from_config(&json!({
+            "instance_type": instance_type,
+            "platform": if windows { "Windows" } else { "Linux/UNIX" },
+        }));
+
+        let scaling = plan_block(config, "scaling_config");
+        let size = |key: &str| scaling.and_then(|s| s.get(key)).and_then(|v| v.as_f64());
+        let desired = size("desired_size").unwrap_or(1.0);
+        let min = size("min_size").unwrap_or(desired).min(desired);
+        let max = size("max_size").unwrap_or(desired).max(desired);
+
+        Self {
+            node,
+            desired,
+            min,
+            max,
+        }
+    }
+}
+
+/// Monthly cost of a node group: its nodes at the EC2 rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeGroupRate {
+    pub node: Ec2Rate,
+    pub desired: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl NodeGroupRate {
+    /// Rate for a node group whose instance type runs at `node_hourly`
+    /// before tenancy and license adjustments
+    pub fn new(config: &NodeGroupConfiguration, node_hourly: f64, options: &Ec2Options) -> Self {
+        Self {
+            node: Ec2Rate::new(&config.node, node_hourly, options),
+            desired: config.desired,
+            min: config.min,
+            max: config.max,
+        }
+    }
+
+    /// Cost of one node
+    pub fn node_monthly(&self, hours: f64) -> f64 {
+        self.node.hourly() * hours
+    }
+
+    /// Cost at the desired size
+    pub fn monthly(&self, hours: f64) -> f64 {
+        self.desired * self.node_monthly(hours)
+    }
+
+    /// Cost at the minimum and maximum size
+    pub fn monthly_range(&self, hours: f64) -> (f64, f64) {
+        (
+            self.min * self.node_monthly(hours),
+            self.max * self.node_monthly(hours),
+        )
+    }
+
+    /// Whether the group can scale away from its desired size
+    pub fn is_range(&self) -> bool {
+        self.min < self.desired || self.max > self.desired
+    }
+}
+
+/// vCPU and memory a Fargate task or pod requests
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FargateTask {
+    pub vcpu: f64,
+    pub memory_gb: f64,
+}
+
+impl FargateTask {
+    /// From an ECS task definition's `cpu` (1024 units per vCPU) and
+    /// `memory` (MiB); Terraform renders both as strings
+    pub fn from_task_definition(config: &Value) -> Option<Self> {
+        let number = |key: &str| {
+            let value = config.get(key)?;
+            value
+                .as_f64()
+                .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+        };
+        Some(Self {
+            vcpu: number("cpu")? / 1024.0,
+            memory_gb: number("memory")? / 1024.0,
+        })
+    }
+}
+
+/// Fargate task sizes declared in a plan, by task definition family
+pub fn task_definitions(changes: &[ResourceChange]) -> HashMap<String, FargateTask> {
+    changes
+        .iter()
+        .filter(|c| c.resource_type == "aws_ecs_task_definition")
+        .filter_map(|c| {
+            let config = c.new_config.as_ref()?;
+            let family = config.get("family").and_then(|v| v.as_str())?;
+            Some((
+                family.to_string(),
+                FargateTask::from_task_definition(config)?,
+            ))
+        })
+        .collect()
+}
+
+/// Whether an `aws_ecs_service` runs on Fargate, by launch type or capacity
+/// provider
+pub fn is_fargate_service(config: &Value) -> bool {
+    let on_fargate = |v: &Value| v.as_str().is_some_and(|s| s.starts_with("FARGATE"));
+    config.get("launch_type").is_some_and(on_fargate)
+        || config
+            .get("capacity_provider_strategy")
+            .and_then(|v| v.as_array())
+            .is_some_and(|strategies| {
+                strategies
+                    .iter()
+                    .filter_map(|s| s.get("capacity_provider"))
+                    .any(on_fargate)
+            })
+}
+
+/// Where a Fargate workload's task size came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSizeSource {
+    TaskDefinition,
+    UsageProfile,
+    Default,
+}
+
+/// Tasks (or pods) a Fargate workload runs and their size
+#[derive(Debug, Clone, PartialEq)]
+pub struct FargateConfiguration {
+    pub tasks: f64,
+    pub task: FargateTask,
+    pub source: TaskSizeSource,
+}
+
+impl FargateConfiguration {
+    /// An ECS service's `desired_count` tasks, sized by its task definition
+    ///
+    /// Task definition ARNs are usually unknown until apply, so a service
+    /// whose family can't be read uses the plan's only task definition, if
+    /// there is exactly one.
+    pub fn from_ecs_service(
+        config: &Value,
+        definitions: &HashMap<String, FargateTask>,
+        options: &ContainerOptions,
+    ) -> Self {
+        let family = config
+            .get("task_definition")
+            .and_then(|v| v.as_str())
+            .map(|reference| {
+                // arn:...:task-definition/web:3, web:3 or web
+                let name = reference.rsplit('/').next().unwrap_or(reference);
+                name.split(':').next().unwrap_or(name)
+            });
+        let task = match family {
+            Some(family) => definitions.get(family),
+            None if definitions.len() == 1 => definitions.values().next(),
+            None => None,
+        };
+
+        Self {
+            tasks: config
+                .get("desired_count")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(1.0),
+            task: task.copied().unwrap_or(FargateTask {
+                vcpu: options.fargate_default_vcpu,
+                memory_gb: options.fargate_default_memory_gb,
+            }),
+            source: match task {
+                Some(_) => TaskSizeSource::TaskDefinition,
+                None => TaskSizeSource::Default,
+            },
+        }
+    }
+
+    /// Pods an `aws_eks_fargate_profile` runs, from the usage profile
+    pub fn from_fargate_profile(
+        change: &ResourceChange,
+        usage: &UsageProfile,
+        options: &ContainerOptions,
+    ) -> Self {
+        let pods = usage.get(change, PODS_KEY);
+        let vcpu = usage.get(change, POD_VCPU_KEY);
+        let memory_gb = usage.get(change, POD_MEMORY_GB_KEY);
+        let profiled = pods.is_some() || vcpu.is_some() || memory_gb.is_some();
+
+        Self {
+            tasks: pods.unwrap_or(1.0),
+            task: FargateTask {
+                vcpu: vcpu.unwrap_or(options.fargate_default_vcpu),
+                memory_gb: memory_gb.unwrap_or(options.fargate_default_memory_gb),
+            },
+            source: if profiled {
+                TaskSizeSource::UsageProfile
+            } else {
+                TaskSizeSource::Default
+            },
+        }
+    }
+}
+
+/// Monthly cost of a Fargate workload
+#[derive(Debug, Clone, PartialEq)]
+pub struct FargateRate {
+    pub tasks: f64,
+    pub task: FargateTask,
+    pub source: TaskSizeSource,
+    pub vcpu_second: f64,
+    pub gb_second: f64,
+}
+
+impl FargateRate {
+    pub fn new(config: &FargateConfiguration, options: &ContainerOptions) -> Self {
+        Self {
+            tasks: config.tasks,
+            task: config.task,
+            source: config.source,
+            vcpu_second: options.fargate_vcpu_second,
+            gb_second: options.fargate_gb_second,
+        }
+    }
+
+    /// vCPU cost of all tasks
+    pub fn vcpu_monthly(&self, hours: f64) -> f64 {
+        self.tasks * self.task.vcpu * self.vcpu_second * hours * SECONDS_PER_HOUR
+    }
+
+    /// Memory cost of all tasks
+    pub fn memory_monthly(&self, hours: f64) -> f64 {
+        self.tasks * self.task.memory_gb * self.gb_second * hours * SECONDS_PER_HOUR
+    }
+
+    pub fn monthly(&self, hours: f64) -> f64 {
+        self.vcpu_monthly(hours) + self.memory_monthly(hours)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::MinimalHeuristics;
+    use crate::engines::shared::models::ChangeAction;
+
+    #[test]
+    fn test_node_group_scales_ec2_rate() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let config = NodeGroupConfiguration::from_config(&json!({
+            "instance_types": ["m5.large"],
+            "ami_type": "WINDOWS_CORE_2022_x86_64",
+            "scaling_config": [{ "desired_size": 3, "min_size": 2, "max_size": 6 }]
+        }));
+        assert_eq!(config.node.instance_type, "m5.large");
+        assert_eq!((config.desired, config.min, config.max), (3.0, 2.0, 6.0));
+
+        let rate = NodeGroupRate::new(&config, 0.096, &heuristics.compute.ec2_options);
+        // Windows adds $0.046 per vCPU-hour to each 2-vCPU node
+        let node = (0.096 + 2.0 * 0.046) * 730.0;
+        assert!((rate.monthly(730.0) - 3.0 * node).abs() < 1e-9);
+        let (low, high) = rate.monthly_range(730.0);
+        assert!((low - 2.0 * node).abs() < 1e-9 && (high - 6.0 * node).abs() < 1e-9);
+        assert!(rate.is_range());
+    }
+
+    #[test]
+    fn test_fargate_service_and_profile() {
+        let options = MinimalHeuristics::to_cost_heuristics().compute.containers;
+        let definition = ResourceChange::builder()
+            .resource_id("aws_ecs_task_definition.web")
+            .resource_type("aws_ecs_task_definition")
+            .action(ChangeAction::Create)
+            .new_config(json!({ "family": "web", "cpu": "1024", "memory": "2048" }))
+            .build();
+        let definitions = task_definitions(&[definition]);
+
+        let service = json!({
+            "launch_type": "FARGATE",
+            "task_definition": "arn:aws:ecs:us-east-1:123456789012:task-definition/web:3",
+            "desired_count": 4
+        });
+        assert!(is_fargate_service(&service));
+        let config = FargateConfiguration::from_ecs_service(&service, &definitions, &options);
+        assert_eq!(config.source, TaskSizeSource::TaskDefinition);
+        let rate = FargateRate::new(&config, &options);
+        // 4 tasks × (1 vCPU × $0.000011244 + 2 GB × $0.000001235) per second
+        let expected = 4.0 * (0.000011244 + 2.0 * 0.000001235) * 730.0 * 3600.0;
+        assert!((rate.monthly(730.0) - expected).abs() < 1e-9);
+
+        let profile = ResourceChange::builder()
+            .resource_id("aws_eks_fargate_profile.apps")
+            .resource_type("aws_eks_fargate_profile")
+            .action(ChangeAction::Create)
+            .build();
+        let usage = UsageProfile::default().with("aws_eks_fargate_profile", PODS_KEY, 10.0);
+        let config = FargateConfiguration::from_fargate_profile(&profile, &usage, &options);
+        assert_eq!(config.source, TaskSizeSource::UsageProfile);
+        assert_eq!(config.tasks, 10.0);
+        assert_eq!(config.task.vcpu, 0.25);
+        assert!(!is_fargate_service(&json!({ "launch_type": "EC2" })));
+    }
+}
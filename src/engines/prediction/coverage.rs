@@ -0,0 +1,257 @@
+// Heuristics coverage - which planned resources have a real estimate
+//
+// A plan total is only as trustworthy as the share of resources priced by a
+// heuristic. Resources without one fall back to a default or zero estimate,
+// which on a new stack can leave most of the bill unaccounted for.
+
+use super::custom_heuristics::CustomHeuristics;
+use super::kubernetes::ClusterPricing;
+use super::prediction_engine::PRICED_RESOURCE_TYPES;
+use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
+use crate::engines::shared::models::{ChangeAction, ResourceChange};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// What prices a resource type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverageSource {
+    /// A rate from `.costpilot/heuristics/`
+    Custom,
+    /// The Kubernetes cluster pricing model
+    Cluster,
+    /// A built-in heuristic
+    Builtin,
+    /// No heuristic: a default or zero estimate
+    Fallback,
+}
+
+impl CoverageSource {
+    pub fn is_covered(&self) -> bool {
+        *self != CoverageSource::Fallback
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            CoverageSource::Custom => "custom",
+            CoverageSource::Cluster => "cluster pricing",
+            CoverageSource::Builtin => "built-in",
+            CoverageSource::Fallback => "fallback",
+        }
+    }
+}
+
+/// Planned resources of one type priced by one source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeCoverage {
+    pub resource_type: String,
+    pub resources: usize,
+    pub source: CoverageSource,
+}
+
+/// Heuristics coverage of a plan's created and changed resources
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeuristicsCoverage {
+    pub schema_version: String,
+    pub total_resources: usize,
+    pub covered_resources: usize,
+    pub coverage_percent: f64,
+
+    /// Fallback types first, then by resource count
+    pub types: Vec<TypeCoverage>,
+}
+
+impl HeuristicsCoverage {
+    /// Classify every created or changed resource; deletions have no
+    /// ongoing cost and are left out
+    pub fn analyze(
+        changes: &[ResourceChange],
+        custom: &CustomHeuristics,
+        cluster_pricing: &ClusterPricing,
+    ) -> Self {
+        let mut counts: BTreeMap<(CoverageSource, &str), usize> = BTreeMap::new();
+        for change in changes {
+            if matches!(change.action, ChangeAction::Delete | ChangeAction::NoOp) {
+                continue;
+            }
+            let source = if custom.rate_for(&change.resource_type).is_some() {
+                CoverageSource::Custom
+            } else if cluster_pricing.estimate(change).is_some() {
+                CoverageSource::Cluster
+            } else if PRICED_RESOURCE_TYPES.contains(&change.resource_type.as_str()) {
+                CoverageSource::Builtin
+            } else {
+                CoverageSource::Fallback
+            };
+            *counts
+                .entry((source, change.resource_type.as_str()))
+                .or_default() += 1;
+        }
+
+        let mut types: Vec<TypeCoverage> = counts
+            .into_iter()
+            .map(|((source, resource_type), resources)| TypeCoverage {
+                resource_type: resource_type.to_string(),
+                resources,
+                source,
+            })
+            .collect();
+        types.sort_by(|a, b| {
+            a.source
+                .is_covered()
+                .cmp(&b.source.is_covered())
+                .then(b.resources.cmp(&a.resources))
+                .then(a.resource_type.cmp(&b.resource_type))
+        });
+
+        let total_resources: usize = types.iter().map(|t| t.resources).sum();
+        let covered_resources: usize = types
+            .iter()
+            .filter(|t| t.source.is_covered())
+            .map(|t| t.resources)
+            .sum();
+        let coverage_percent = if total_resources == 0 {
+            100.0
+        } else {
+            covered_resources as f64 / total_resources as f64 * 100.0
+        };
+
+        Self {
+            schema_version: OUTPUT_SCHEMA_VERSION.to_string(),
+            total_resources,
+            covered_resources,
+            coverage_percent,
+            types,
+        }
+    }
+
+    /// Types without a heuristic
+    pub fn fallback_types(&self) -> impl Iterator<Item = &TypeCoverage> {
+        self.types.iter().filter(|t| !t.source.is_covered())
+    }
+
+    pub fn format_text(&self) -> String {
+        let mut output = String::from("📐 Heuristics Coverage\n");
+        output.push_str("======================\n\n");
+        output.push_str(&format!(
+            "Covered: {} of {} resources ({:.1}%)\n",
+            self.covered_resources, self.total_resources, self.coverage_percent
+        ));
+        if self.types.is_empty() {
+            output.push_str("\nNo created or changed resources in the plan\n");
+            return output;
+        }
+
+        let width = self
+            .types
+            .iter()
+            .map(|t| t.resource_type.len())
+            .max()
+            .unwrap_or(0);
+        let section = |output: &mut String, title: &str, covered: bool| {
+            let rows: Vec<&TypeCoverage> = self
+                .types
+                .iter()
+                .filter(|t| t.source.is_covered() == covered)
+                .collect();
+            if rows.is_empty() {
+                return;
+            }
+            output.push_str(&format!("\n{}\n", title));
+            for row in rows {
+                output.push_str(&format!(
+                    "  {:<width$}  {:>4}  {}\n",
+                    row.resource_type,
+                    row.resources,
+                    row.source.label(),
+                    width = width
+                ));
+            }
+        };
+        section(
+            &mut output,
+            "Not covered (default or zero estimate):",
+            false,
+        );
+        section(&mut output, "Covered:", true);
+
+        if self.fallback_types().next().is_some() {
+            output.push_str(&format!(
+                "\n💡 Add rates for uncovered types in {}/\n",
+                super::custom_heuristics::CUSTOM_HEURISTICS_DIR
+            ));
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::CustomHeuristicsFile;
+    use std::path::PathBuf;
+
+    fn change(id: &str, resource_type: &str, action: ChangeAction) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(id)
+            .resource_type(resource_type)
+            .action(action)
+            .build()
+    }
+
+    #[test]
+    fn test_coverage_classifies_sources() {
+        let file: CustomHeuristicsFile = serde_yaml::from_str(
+            "version: 1.0.0\nresources:\n  - resource_type: aws_msk_cluster\n    monthly: 300\n",
+        )
+        .unwrap();
+        let custom = CustomHeuristics {
+            files: vec![(PathBuf::from("rates.yaml"), file)],
+            skipped: Vec::new(),
+        };
+        let changes = vec![
+            change("aws_instance.a", "aws_instance", ChangeAction::Create),
+            change("aws_instance.b", "aws_instance", ChangeAction::Update),
+            change("aws_msk_cluster.m", "aws_msk_cluster", ChangeAction::Create),
+            change("aws_glue_job.j", "aws_glue_job", ChangeAction::Create),
+            change("aws_instance.old", "aws_instance", ChangeAction::Delete),
+        ];
+
+        let coverage = HeuristicsCoverage::analyze(&changes, &custom, &ClusterPricing::default());
+
+        assert_eq!(coverage.total_resources, 4);
+        assert_eq!(coverage.covered_resources, 3);
+        assert_eq!(coverage.coverage_percent, 75.0);
+        assert_eq!(coverage.types[0].resource_type, "aws_glue_job");
+        assert_eq!(coverage.types[0].source, CoverageSource::Fallback);
+        assert_eq!(coverage.types[1].resource_type, "aws_instance");
+        assert_eq!(coverage.types[1].resources, 2);
+        assert_eq!(coverage.types[2].source, CoverageSource::Custom);
+    }
+
+    #[test]
+    fn test_coverage_text_and_empty_plan() {
+        let changes = vec![change(
+            "aws_glue_job.j",
+            "aws_glue_job",
+            ChangeAction::Create,
+        )];
+        let coverage = HeuristicsCoverage::analyze(
+            &changes,
+            &CustomHeuristics::default(),
+            &ClusterPricing::default(),
+        );
+        let text = coverage.format_text();
+        assert!(text.contains("Covered: 0 of 1 resources (0.0%)"));
+        assert!(text
+            .contains("Not covered (default or zero estimate):\n  aws_glue_job     1  fallback"));
+        assert!(text.contains(".costpilot/heuristics/"));
+
+        let empty = HeuristicsCoverage::analyze(
+            &[],
+            &CustomHeuristics::default(),
+            &ClusterPricing::default(),
+        );
+        assert_eq!(empty.coverage_percent, 100.0);
+    }
+}
@@ -0,0 +1,553 @@
+// User-supplied heuristics from `.costpilot/heuristics/*.yaml`
+//
+// Each file can patch the built-in heuristics document (rates and formula
+// parameters, same keys as cost_heuristics.json) and price resource types by
+// a flat, hourly or per-unit rate, including types the built-ins do not know.
+// Files are applied in name order, so later files win. A file pinned to a
+// different built-in heuristics version is skipped rather than applied to
+// rates it was not written against.
+
+use super::calculation_steps::action_step;
+use super::prediction_engine::CostHeuristics;
+use crate::engines::detection::custom_rules::wildcard_match;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::engines::shared::models::{
+    CalculationStep, ChangeAction, CostEstimate, EstimateProvenance, ResourceChange,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Project directory scanned for custom heuristics files
+pub const CUSTOM_HEURISTICS_DIR: &str = ".costpilot/heuristics";
+
+/// Hours in the average month, as used by the built-in monthly rates
+const HOURS_PER_MONTH: f64 = 730.0;
+
+/// Interval half-width of custom rates without a `range_factor`
+const DEFAULT_RANGE_FACTOR: f64 = 0.3;
+
+/// Confidence of estimates priced by a custom rate
+const CUSTOM_RATE_CONFIDENCE: f64 = 0.8;
+
+/// Tables of the heuristics document keyed by instance or volume type, where
+/// overrides may add entries; everywhere else keys must already exist
+const OPEN_TABLES: [&str; 5] = [
+    "compute.ec2",
+    "compute.ec2_options.ebs_optimized_hourly",
+    "storage.ebs",
+    "database.rds.mysql",
+    "database.rds.postgres",
+];
+
+/// One custom heuristics file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomHeuristicsFile {
+    /// Version of this file (major.minor.patch)
+    pub version: String,
+
+    /// Built-in heuristics version the file was written against: `1`, `1.0`
+    /// or `1.0.0`; the file is skipped when the built-ins move on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heuristics_version: Option<String>,
+
+    /// Patch over the built-in heuristics document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<Value>,
+
+    /// Rates per resource type, replacing the built-in estimate
+    #[serde(default)]
+    pub resources: Vec<ResourceRate>,
+}
+
+/// Monthly cost of a resource type: `monthly + hourly × 730 + per_unit`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceRate {
+    /// Resource type, `*` wildcards allowed
+    pub resource_type: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hourly: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_unit: Option<PerUnitRate>,
+
+    /// Interval half-width as a fraction of the cost (default 0.3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range_factor: Option<f64>,
+}
+
+/// Cost proportional to a planned attribute, e.g. `allocated_storage`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PerUnitRate {
+    pub attribute: String,
+    pub rate: f64,
+
+    /// Units assumed when the attribute is not in the plan
+    #[serde(default)]
+    pub default: f64,
+}
+
+impl ResourceRate {
+    /// Monthly cost of `change` and the step that computed it
+    fn monthly_cost(&self, change: &ResourceChange) -> (f64, CalculationStep) {
+        let mut cost = self.monthly.unwrap_or(0.0);
+        let mut terms = Vec::new();
+        if let Some(monthly) = self.monthly {
+            terms.push(format!("${:.2}", monthly));
+        }
+        if let Some(hourly) = self.hourly {
+            cost += hourly * HOURS_PER_MONTH;
+            terms.push(format!("${}/h × {}", hourly, HOURS_PER_MONTH));
+        }
+        if let Some(per_unit) = &self.per_unit {
+            let units = change
+                .new_config
+                .as_ref()
+                .or(change.config.as_ref())
+                .and_then(|config| config.get(&per_unit.attribute))
+                .and_then(|value| {
+                    value
+                        .as_f64()
+                        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+                })
+                .unwrap_or(per_unit.default);
+            cost += units * per_unit.rate;
+            terms.push(format!(
+                "{} {} × ${}",
+                units, per_unit.attribute, per_unit.rate
+            ));
+        }
+        let step = CalculationStep {
+            step_number: 1,
+            operation: "Custom Rate".to_string(),
+            input: format!("resource_type={}", change.resource_type),
+            output: format!("${:.2}/month", cost),
+            reasoning: terms.join(" + "),
+        };
+        (cost, step)
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.resource_type.trim().is_empty() {
+            problems.push("resources: entries need a resource_type".to_string());
+        }
+        if self.monthly.is_none() && self.hourly.is_none() && self.per_unit.is_none() {
+            problems.push(format!(
+                "resources: {} needs monthly, hourly or per_unit",
+                self.resource_type
+            ));
+        }
+        let rates = [
+            ("monthly", self.monthly),
+            ("hourly", self.hourly),
+            ("per_unit.rate", self.per_unit.as_ref().map(|p| p.rate)),
+            ("range_factor", self.range_factor),
+        ];
+        for (field, value) in rates {
+            if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+                problems.push(format!(
+                    "resources: {} {} must be zero or positive",
+                    self.resource_type, field
+                ));
+            }
+        }
+        problems
+    }
+}
+
+impl CustomHeuristicsFile {
+    /// Parse and validate a custom heuristics file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let file: Self = serde_yaml::from_str(&content).map_err(|e| {
+            CostPilotError::new(
+                "HEURISTICS_CUSTOM_001",
+                ErrorCategory::ParseError,
+                format!("Invalid custom heuristics {}: {}", path.display(), e),
+            )
+            .with_hint("Top-level keys are version, heuristics_version, overrides and resources")
+        })?;
+        if let Some(problem) = file.validate().first() {
+            return Err(CostPilotError::new(
+                "HEURISTICS_CUSTOM_002",
+                ErrorCategory::ValidationError,
+                format!("{}: {}", path.display(), problem),
+            ));
+        }
+        Ok(file)
+    }
+
+    /// Describe invalid settings; empty if the file is usable
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !is_version(&self.version, 3) {
+            problems.push(format!("version {} is not major.minor.patch", self.version));
+        }
+        if let Some(pin) = &self.heuristics_version {
+            if !(1..=3).any(|parts| is_version(pin, parts)) {
+                problems.push(format!(
+                    "heuristics_version {} must look like 1, 1.0 or 1.0.0",
+                    pin
+                ));
+            }
+        }
+        if self.overrides.as_ref().is_some_and(|o| !o.is_object()) {
+            problems.push("overrides must be a mapping".to_string());
+        }
+        problems.extend(self.resources.iter().flat_map(ResourceRate::validate));
+        problems
+    }
+
+    /// Whether the file applies to built-in heuristics `version`
+    pub fn supports(&self, version: &str) -> bool {
+        let Some(pin) = &self.heuristics_version else {
+            return true;
+        };
+        let builtin: Vec<&str> = version.split(['.', '-']).collect();
+        pin.split('.')
+            .enumerate()
+            .all(|(i, part)| builtin.get(i) == Some(&part))
+    }
+}
+
+/// `*.yaml` and `*.yml` files in `dir`, sorted by name
+fn heuristics_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn is_version(text: &str, parts: usize) -> bool {
+    let split: Vec<&str> = text.split('.').collect();
+    split.len() == parts && split.iter().all(|p| p.parse::<u32>().is_ok())
+}
+
+/// Custom heuristics loaded from a directory
+#[derive(Debug, Clone, Default)]
+pub struct CustomHeuristics {
+    /// Applied files with their paths, in application order
+    pub files: Vec<(PathBuf, CustomHeuristicsFile)>,
+
+    /// Files not applied because they are pinned to another version
+    pub skipped: Vec<String>,
+}
+
+impl CustomHeuristics {
+    /// Load every `*.yaml`/`*.yml` file in `dir` pinned to `builtin_version`;
+    /// a missing directory yields no custom heuristics
+    pub fn load_dir(dir: &Path, builtin_version: &str) -> Result<Self> {
+        let mut custom = Self::default();
+        for path in heuristics_files(dir) {
+            let file = CustomHeuristicsFile::load(&path)?;
+            if file.supports(builtin_version) {
+                custom.files.push((path, file));
+            } else {
+                custom.skipped.push(format!(
+                    "{} is pinned to heuristics {}, built-in heuristics are {}",
+                    path.display(),
+                    file.heuristics_version.as_deref().unwrap_or_default(),
+                    builtin_version
+                ));
+            }
+        }
+        Ok(custom)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Modification stamps of the files in `dir`; a different stamp means
+    /// the directory needs reloading
+    pub fn dir_stamp(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+        heuristics_files(dir)
+            .into_iter()
+            .map(|path| {
+                let metadata = std::fs::metadata(&path).ok();
+                let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+                let len = metadata.map_or(0, |m| m.len());
+                (path, modified, len)
+            })
+            .collect()
+    }
+
+    /// Patch `heuristics` with every file's `overrides`
+    pub fn apply_overrides(&self, heuristics: &mut CostHeuristics) -> Result<()> {
+        let internal = |e: serde_json::Error| {
+            CostPilotError::new(
+                "HEURISTICS_CUSTOM_003",
+                ErrorCategory::ValidationError,
+                format!("Custom heuristics overrides do not fit the schema: {}", e),
+            )
+        };
+        let mut document = serde_json::to_value(&*heuristics).map_err(internal)?;
+        for (path, file) in &self.files {
+            if let Some(overrides) = &file.overrides {
+                merge(&mut document, overrides, "").map_err(|key| {
+                    CostPilotError::new(
+                        "HEURISTICS_CUSTOM_004",
+                        ErrorCategory::ValidationError,
+                        format!("{}: unknown heuristics key '{}'", path.display(), key),
+                    )
+                    .with_hint("Overrides use the keys of cost_heuristics.json")
+                })?;
+            }
+        }
+        *heuristics = serde_json::from_value(document).map_err(internal)?;
+        Ok(())
+    }
+
+    /// Rate for `resource_type`; later files and later entries win
+    pub fn rate_for(&self, resource_type: &str) -> Option<(&Path, &ResourceRate)> {
+        self.files.iter().rev().find_map(|(path, file)| {
+            file.resources
+                .iter()
+                .rev()
+                .find(|rate| wildcard_match(&rate.resource_type, resource_type))
+                .map(|rate| (path.as_path(), rate))
+        })
+    }
+
+    /// Reprice estimates of resource types with a custom rate, adding
+    /// estimates for created or changed resources the engine did not price
+    pub fn apply(&self, changes: &[ResourceChange], estimates: &mut Vec<CostEstimate>) {
+        if self.files.iter().all(|(_, f)| f.resources.is_empty()) {
+            return;
+        }
+        let positions: HashMap<String, usize> = estimates
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.resource_id.clone(), i))
+            .collect();
+
+        for change in changes {
+            if matches!(change.action, ChangeAction::Delete | ChangeAction::NoOp) {
+                continue;
+            }
+            let Some((path, rate)) = self.rate_for(&change.resource_type) else {
+                continue;
+            };
+            let (cost, step) = rate.monthly_cost(change);
+            let half_width = cost * rate.range_factor.unwrap_or(DEFAULT_RANGE_FACTOR);
+            let estimate = CostEstimate {
+                resource_id: change.resource_id.clone(),
+                monthly_cost: cost,
+                prediction_interval_low: (cost - half_width).max(0.0),
+                prediction_interval_high: cost + half_width,
+                confidence_score: CUSTOM_RATE_CONFIDENCE,
+                heuristic_reference: Some(format!("custom:{}", rate.resource_type)),
+                cold_start_inference: false,
+                one_time: None,
+                breakdown: None,
+                hourly: rate.hourly,
+                daily: None,
+                provenance: Some(EstimateProvenance {
+                    heuristic_id: format!("custom:{}", rate.resource_type),
+                    pricing_version: self
+                        .files
+                        .iter()
+                        .find(|(p, _)| p == path)
+                        .map(|(_, f)| f.version.clone()),
+                    assumptions: vec![format!("Rate from {}", path.display())],
+                    steps: vec![step, action_step(2, &change.action, cost, cost)],
+                }),
+            };
+            match positions.get(&change.resource_id) {
+                Some(&i) => estimates[i] = estimate,
+                None => estimates.push(estimate),
+            }
+        }
+    }
+}
+
+/// Deep-merge `patch` into `target`, rejecting keys `target` lacks outside
+/// the open tables; returns the first unknown key path
+fn merge(target: &mut Value, patch: &Value, path: &str) -> std::result::Result<(), String> {
+    let (Value::Object(target), Value::Object(patch)) = (&mut *target, patch) else {
+        *target = patch.clone();
+        return Ok(());
+    };
+    let open = OPEN_TABLES.contains(&path);
+    for (key, value) in patch {
+        let key_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", path, key)
+        };
+        match target.get_mut(key) {
+            Some(existing) => merge(existing, value, &key_path)?,
+            None if open => {
+                target.insert(key.clone(), value.clone());
+            }
+            None => return Err(key_path),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::MinimalHeuristics;
+    use serde_json::json;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        std::fs::write(dir.join(name), content).unwrap();
+    }
+
+    #[test]
+    fn test_load_dir_pins_versions_and_orders_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "10-base.yaml",
+            "version: 1.0.0\nresources:\n  - resource_type: aws_*\n    monthly: 1\n",
+        );
+        write(
+            dir.path(),
+            "20-msk.yml",
+            "version: 1.2.0\nheuristics_version: \"1.0\"\nresources:\n  - resource_type: aws_msk_cluster\n    hourly: 0.5\n",
+        );
+        write(
+            dir.path(),
+            "30-next.yaml",
+            "version: 1.0.0\nheuristics_version: \"2\"\nresources:\n  - resource_type: aws_msk_cluster\n    monthly: 9\n",
+        );
+        write(dir.path(), "notes.txt", "ignored");
+
+        let custom = CustomHeuristics::load_dir(dir.path(), "1.0.0").unwrap();
+        assert_eq!(custom.files.len(), 2);
+        assert_eq!(custom.skipped.len(), 1);
+        assert!(custom.skipped[0].contains("30-next.yaml"));
+
+        let (path, rate) = custom.rate_for("aws_msk_cluster").unwrap();
+        assert!(path.ends_with("20-msk.yml"));
+        assert_eq!(rate.hourly, Some(0.5));
+        assert_eq!(
+            custom.rate_for("aws_s3_bucket").unwrap().1.monthly,
+            Some(1.0)
+        );
+
+        write(
+            dir.path(),
+            "40-bad.yaml",
+            "version: 1.0.0\nresources:\n  - resource_type: aws_instance\n    montly: 3\n",
+        );
+        let err = CustomHeuristics::load_dir(dir.path(), "1.0.0").unwrap_err();
+        assert_eq!(err.id, "HEURISTICS_CUSTOM_001");
+    }
+
+    #[test]
+    fn test_apply_reprices_and_adds_estimates() {
+        let file: CustomHeuristicsFile = serde_yaml::from_str(
+            r#"
+version: 1.0.0
+resources:
+  - resource_type: aws_db_instance
+    monthly: 10
+    per_unit: { attribute: allocated_storage, rate: 0.1, default: 20 }
+    range_factor: 0.1
+  - resource_type: aws_msk_cluster
+    hourly: 0.5
+"#,
+        )
+        .unwrap();
+        assert!(file.validate().is_empty());
+        let custom = CustomHeuristics {
+            files: vec![(PathBuf::from("rates.yaml"), file)],
+            skipped: Vec::new(),
+        };
+
+        let change = |id: &str, ty: &str, config: Value| {
+            ResourceChange::builder()
+                .resource_id(id)
+                .resource_type(ty)
+                .action(ChangeAction::Create)
+                .new_config(config)
+                .build()
+        };
+        let changes = vec![
+            change(
+                "aws_db_instance.db",
+                "aws_db_instance",
+                json!({"allocated_storage": 100}),
+            ),
+            change("aws_msk_cluster.events", "aws_msk_cluster", json!({})),
+            change("aws_instance.web", "aws_instance", json!({})),
+        ];
+        let mut estimates = vec![
+            CostEstimate::builder()
+                .resource_id("aws_db_instance.db")
+                .monthly_cost(0.0)
+                .build(),
+            CostEstimate::builder()
+                .resource_id("aws_instance.web")
+                .monthly_cost(150.0)
+                .build(),
+        ];
+
+        custom.apply(&changes, &mut estimates);
+
+        assert_eq!(estimates.len(), 3);
+        assert_eq!(estimates[0].monthly_cost, 20.0);
+        assert_eq!(estimates[0].prediction_interval_high, 22.0);
+        assert_eq!(estimates[1].monthly_cost, 150.0);
+        assert_eq!(estimates[2].resource_id, "aws_msk_cluster.events");
+        assert_eq!(estimates[2].monthly_cost, 365.0);
+        let provenance = estimates[2].provenance.as_ref().unwrap();
+        assert_eq!(provenance.heuristic_id, "custom:aws_msk_cluster");
+        assert_eq!(provenance.pricing_version.as_deref(), Some("1.0.0"));
+    }
+
+    #[test]
+    fn test_overrides_patch_document_and_reject_unknown_keys() {
+        let file = |overrides: Value| CustomHeuristics {
+            files: vec![(
+                PathBuf::from("o.yaml"),
+                CustomHeuristicsFile {
+                    version: "1.0.0".to_string(),
+                    heuristics_version: None,
+                    overrides: Some(overrides),
+                    resources: Vec::new(),
+                },
+            )],
+            skipped: Vec::new(),
+        };
+
+        let mut heuristics = MinimalHeuristics::to_cost_heuristics();
+        file(json!({
+            "compute": {"ec2": {"m7g.large": {"hourly": 0.0816, "monthly": 59.6}}},
+            "prediction_intervals": {"range_factor": 0.25}
+        }))
+        .apply_overrides(&mut heuristics)
+        .unwrap();
+        assert_eq!(heuristics.compute.ec2["m7g.large"].monthly, 59.6);
+        assert_eq!(heuristics.prediction_intervals.range_factor, 0.25);
+
+        let err = file(json!({"networking": {"nat_gatway": {"hourly": 1.0}}}))
+            .apply_overrides(&mut heuristics)
+            .unwrap_err();
+        assert!(err.message.contains("'networking.nat_gatway'"));
+    }
+}
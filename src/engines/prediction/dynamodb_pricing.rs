@@ -0,0 +1,484 @@
+// DynamoDB pricing by capacity mode: on-demand requests or provisioned
+// capacity within autoscaling bands
+//
+// `billing_mode = "PAY_PER_REQUEST"` bills every read and write request, so
+// the cost follows usage: request volumes and storage come from the usage
+// profile (`usage` in costpilot.yaml), else conservative defaults.
+// `PROVISIONED` bills read and write capacity units by the hour. Tables
+// usually scale that capacity with `aws_appautoscaling_target` resources,
+// which bound it between a minimum and maximum; the cost is then reported as
+// that band, with the point estimate at the capacity the target utilization
+// needs for the profiled traffic (or the table's configured capacity).
+
+use crate::engines::prediction::plan_block;
+use crate::engines::prediction::prediction_engine::{ColdStartDefaults, DynamoDbCost};
+use crate::engines::prediction::usage_profile::UsageProfile;
+use crate::engines::shared::models::ResourceChange;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Usage profile keys for DynamoDB tables
+pub const READ_REQUESTS_KEY: &str = "monthly_read_request_units";
+pub const WRITE_REQUESTS_KEY: &str = "monthly_write_request_units";
+pub const STORAGE_GB_KEY: &str = "storage_gb";
+
+/// Assumed on-demand traffic and storage without a usage profile
+const DEFAULT_MONTHLY_READS: f64 = 10_000_000.0;
+const DEFAULT_MONTHLY_WRITES: f64 = 1_000_000.0;
+const DEFAULT_STORAGE_GB: f64 = 1.0;
+
+/// How a table is billed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BillingMode {
+    OnDemand,
+    Provisioned,
+}
+
+impl BillingMode {
+    /// Terraform defaults `billing_mode` to `PROVISIONED`
+    pub fn parse(value: Option<&str>) -> Self {
+        match value.map(|v| v.trim().to_ascii_uppercase()).as_deref() {
+            Some("PAY_PER_REQUEST") | Some("ON_DEMAND") => BillingMode::OnDemand,
+            _ => BillingMode::Provisioned,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BillingMode::OnDemand => "PAY_PER_REQUEST",
+            BillingMode::Provisioned => "PROVISIONED",
+        }
+    }
+}
+
+/// Range an autoscaling target keeps provisioned capacity within
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CapacityBand {
+    pub min: f64,
+    pub max: f64,
+
+    /// Utilization a target tracking policy scales to, as a fraction
+    pub target_utilization: Option<f64>,
+}
+
+impl CapacityBand {
+    pub fn clamp(&self, units: f64) -> f64 {
+        units.clamp(self.min, self.max)
+    }
+}
+
+/// Read and write autoscaling of one table
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TableAutoscaling {
+    pub read: Option<CapacityBand>,
+    pub write: Option<CapacityBand>,
+}
+
+/// Autoscaling of each table in a plan, by table name, from its
+/// `aws_appautoscaling_target` and target tracking `aws_appautoscaling_policy`
+/// resources
+pub fn autoscaling_targets(changes: &[ResourceChange]) -> HashMap<String, TableAutoscaling> {
+    let mut tables: HashMap<String, TableAutoscaling> = HashMap::new();
+    for change in changes
+        .iter()
+        .filter(|c| c.resource_type == "aws_appautoscaling_target")
+    {
+        let Some((table, read, config)) = scaled_table(change) else {
+            continue;
+        };
+        let capacity = |key: &str| config.get(key).and_then(|v| v.as_f64());
+        let (Some(min), Some(max)) = (capacity("min_capacity"), capacity("max_capacity")) else {
+            continue;
+        };
+        let band = Some(CapacityBand {
+            min,
+            max: max.max(min),
+            target_utilization: None,
+        });
+        let entry = tables.entry(table).or_default();
+        if read {
+            entry.read = band;
+        } else {
+            entry.write = band;
+        }
+    }
+
+    for change in changes
+        .iter()
+        .filter(|c| c.resource_type == "aws_appautoscaling_policy")
+    {
+        let Some((table, read, config)) = scaled_table(change) else {
+            continue;
+        };
+        let target = plan_block(config, "target_tracking_scaling_policy_configuration")
+            .and_then(|c| c.get("target_value"))
+            .and_then(|v| v.as_f64())
+            .filter(|&t| t > 0.0);
+        let Some(entry) = tables.get_mut(&table) else {
+            continue;
+        };
+        let band = if read {
+            entry.read.as_mut()
+        } else {
+            entry.write.as_mut()
+        };
+        if let (Some(band), Some(target)) = (band, target) {
+            band.target_utilization = Some(target / 100.0);
+        }
+    }
+
+    tables
+}
+
+/// Table name, whether the dimension is reads (else writes), and config of
+/// an autoscaling target or policy on a table's capacity
+fn scaled_table(change: &ResourceChange) -> Option<(String, bool, &Value)> {
+    let config = change.new_config.as_ref()?;
+    let table = config
+        .get("resource_id")
+        .and_then(|v| v.as_str())?
+        .strip_prefix("table/")
+        .filter(|name| !name.contains('/'))?;
+    let read = match config.get("scalable_dimension").and_then(|v| v.as_str())? {
+        "dynamodb:table:ReadCapacityUnits" => true,
+        "dynamodb:table:WriteCapacityUnits" => false,
+        _ => return None,
+    };
+    Some((table.to_string(), read, config))
+}
+
+/// Pricing-relevant attributes of an `aws_dynamodb_table`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamoDbConfiguration {
+    pub name: Option<String>,
+    pub billing_mode: BillingMode,
+    pub read_capacity: Option<f64>,
+    pub write_capacity: Option<f64>,
+    pub autoscaling: TableAutoscaling,
+}
+
+impl DynamoDbConfiguration {
+    /// Read from planned table attributes
+    pub fn from_config(config: &Value) -> Self {
+        let capacity = |key: &str| config.get(key).and_then(|v| v.as_f64());
+        Self {
+            name: config
+                .get("name")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            billing_mode: BillingMode::parse(config.get("billing_mode").and_then(|v| v.as_str())),
+            read_capacity: capacity("read_capacity"),
+            write_capacity: capacity("write_capacity"),
+            autoscaling: TableAutoscaling::default(),
+        }
+    }
+
+    /// Attach the table's autoscaling from `autoscaling_targets`
+    pub fn with_autoscaling(mut self, targets: &HashMap<String, TableAutoscaling>) -> Self {
+        if let Some(autoscaling) = self.name.as_ref().and_then(|name| targets.get(name)) {
+            self.autoscaling = *autoscaling;
+        }
+        self
+    }
+}
+
+/// Monthly requests and storage of a table
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DynamoDbUsage {
+    pub reads: Option<f64>,
+    pub writes: Option<f64>,
+    pub storage_gb: Option<f64>,
+}
+
+impl DynamoDbUsage {
+    pub fn for_change(profile: &UsageProfile, change: &ResourceChange) -> Self {
+        Self {
+            reads: profile.get(change, READ_REQUESTS_KEY),
+            writes: profile.get(change, WRITE_REQUESTS_KEY),
+            storage_gb: profile.get(change, STORAGE_GB_KEY),
+        }
+    }
+
+    /// Whether any quantity came from the usage profile
+    pub fn is_profiled(&self) -> bool {
+        self.reads.is_some() || self.writes.is_some() || self.storage_gb.is_some()
+    }
+}
+
+/// Provisioned read or write capacity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProvisionedCapacity {
+    /// Capacity units expected to be provisioned
+    pub units: f64,
+    pub band: Option<CapacityBand>,
+    pub unit_hourly: f64,
+}
+
+impl ProvisionedCapacity {
+    /// Capacity for a table configured with `configured` units, consuming
+    /// `monthly_requests` when profiled
+    fn new(
+        configured: f64,
+        band: Option<CapacityBand>,
+        monthly_requests: Option<f64>,
+        unit_hourly: f64,
+        hours: f64,
+    ) -> Self {
+        let units = match band {
+            Some(band) => {
+                // Target tracking provisions consumed capacity / utilization
+                let needed = monthly_requests
+                    .zip(band.target_utilization)
+                    .map(|(requests, target)| requests / (hours * 3600.0) / target);
+                band.clamp(needed.unwrap_or(configured))
+            }
+            None => configured,
+        };
+        Self {
+            units,
+            band,
+            unit_hourly,
+        }
+    }
+
+    pub fn monthly(&self, hours: f64) -> f64 {
+        self.units * self.unit_hourly * hours
+    }
+
+    /// Cost at the bottom and top of the autoscaling band
+    pub fn monthly_range(&self, hours: f64) -> (f64, f64) {
+        match self.band {
+            Some(band) => (
+                band.min * self.unit_hourly * hours,
+                band.max * self.unit_hourly * hours,
+            ),
+            None => (self.monthly(hours), self.monthly(hours)),
+        }
+    }
+}
+
+/// Monthly cost of a table in its billing mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamoDbRate {
+    pub billing_mode: BillingMode,
+    pub usage: DynamoDbUsage,
+
+    /// On-demand monthly requests and their unit prices
+    pub read_requests: f64,
+    pub write_requests: f64,
+    pub read_request_price: f64,
+    pub write_request_price: f64,
+
+    /// Provisioned capacity (unused on-demand)
+    pub read: ProvisionedCapacity,
+    pub write: ProvisionedCapacity,
+
+    pub storage_gb: f64,
+    pub storage_per_gb: f64,
+}
+
+impl DynamoDbRate {
+    pub fn new(
+        config: &DynamoDbConfiguration,
+        usage: DynamoDbUsage,
+        prices: &DynamoDbCost,
+        defaults: &ColdStartDefaults,
+        hours: f64,
+    ) -> Self {
+        let provisioned = config.billing_mode == BillingMode::Provisioned;
+        let capacity = |configured: Option<f64>, unknown: u32, band, requests, unit_hourly| {
+            if provisioned {
+                ProvisionedCapacity::new(
+                    configured.unwrap_or(unknown as f64),
+                    band,
+                    requests,
+                    unit_hourly,
+                    hours,
+                )
+            } else {
+                ProvisionedCapacity {
+                    units: 0.0,
+                    band: None,
+                    unit_hourly: 0.0,
+                }
+            }
+        };
+
+        Self {
+            billing_mode: config.billing_mode,
+            usage,
+            read_requests: usage.reads.unwrap_or(DEFAULT_MONTHLY_READS),
+            write_requests: usage.writes.unwrap_or(DEFAULT_MONTHLY_WRITES),
+            read_request_price: prices.on_demand.read_request_unit,
+            write_request_price: prices.on_demand.write_request_unit,
+            read: capacity(
+                config.read_capacity,
+                defaults.dynamodb_unknown_rcu,
+                config.autoscaling.read,
+                usage.reads,
+                prices.provisioned.read_capacity_unit_hourly,
+            ),
+            write: capacity(
+                config.write_capacity,
+                defaults.dynamodb_unknown_wcu,
+                config.autoscaling.write,
+                usage.writes,
+                prices.provisioned.write_capacity_unit_hourly,
+            ),
+            storage_gb: usage.storage_gb.unwrap_or(DEFAULT_STORAGE_GB),
+            storage_per_gb: if provisioned {
+                prices.provisioned.storage_per_gb
+            } else {
+                prices.on_demand.storage_per_gb
+            },
+        }
+    }
+
+    pub fn read_requests_monthly(&self) -> f64 {
+        self.read_requests * self.read_request_price
+    }
+
+    pub fn write_requests_monthly(&self) -> f64 {
+        self.write_requests * self.write_request_price
+    }
+
+    pub fn storage_monthly(&self) -> f64 {
+        self.storage_gb * self.storage_per_gb
+    }
+
+    /// Expected monthly cost
+    pub fn monthly(&self, hours: f64) -> f64 {
+        self.storage_monthly()
+            + match self.billing_mode {
+                BillingMode::OnDemand => {
+                    self.read_requests_monthly() + self.write_requests_monthly()
+                }
+                BillingMode::Provisioned => self.read.monthly(hours) + self.write.monthly(hours),
+            }
+    }
+
+    /// Monthly cost at the bottom and top of the autoscaling bands
+    pub fn monthly_range(&self, hours: f64) -> (f64, f64) {
+        match self.billing_mode {
+            BillingMode::OnDemand => (self.monthly(hours), self.monthly(hours)),
+            BillingMode::Provisioned => {
+                let (read_low, read_high) = self.read.monthly_range(hours);
+                let (write_low, write_high) = self.write.monthly_range(hours);
+                (
+                    read_low + write_low + self.storage_monthly(),
+                    read_high + write_high + self.storage_monthly(),
+                )
+            }
+        }
+    }
+
+    /// Whether autoscaling makes the cost a range
+    pub fn is_range(&self) -> bool {
+        self.read.band.is_some() || self.write.band.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::MinimalHeuristics;
+    use crate::engines::shared::models::ChangeAction;
+    use serde_json::json;
+
+    fn change(resource_type: &str, address: &str, config: Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(address)
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    #[test]
+    fn test_on_demand_priced_from_usage_profile() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let table = change(
+            "aws_dynamodb_table",
+            "aws_dynamodb_table.orders",
+            json!({ "name": "orders", "billing_mode": "PAY_PER_REQUEST" }),
+        );
+        let profile = UsageProfile::default()
+            .with("aws_dynamodb_table.orders", READ_REQUESTS_KEY, 40_000_000.0)
+            .with("aws_dynamodb_table", STORAGE_GB_KEY, 10.0);
+        let usage = DynamoDbUsage::for_change(&profile, &table);
+        assert!(usage.is_profiled());
+
+        let config = DynamoDbConfiguration::from_config(table.new_config.as_ref().unwrap());
+        let rate = DynamoDbRate::new(
+            &config,
+            usage,
+            &heuristics.database.dynamodb,
+            &heuristics.cold_start_defaults,
+            730.0,
+        );
+        // 40M reads × $0.25/M + 1M default writes × $1.25/M + 10 GB × $0.25
+        assert!((rate.monthly(730.0) - (10.0 + 1.25 + 2.5)).abs() < 1e-9);
+        assert!(!rate.is_range());
+    }
+
+    #[test]
+    fn test_provisioned_autoscaling_band() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let scaling = |resource_type: &str, dimension: &str, config: Value| {
+            let mut config = config;
+            config["resource_id"] = json!("table/orders");
+            config["scalable_dimension"] = json!(dimension);
+            change(
+                resource_type,
+                &format!("{}.{}", resource_type, dimension),
+                config,
+            )
+        };
+        let changes = vec![
+            scaling(
+                "aws_appautoscaling_target",
+                "dynamodb:table:ReadCapacityUnits",
+                json!({ "min_capacity": 5, "max_capacity": 100 }),
+            ),
+            scaling(
+                "aws_appautoscaling_policy",
+                "dynamodb:table:ReadCapacityUnits",
+                json!({ "target_tracking_scaling_policy_configuration": [{ "target_value": 70 }] }),
+            ),
+        ];
+        let targets = autoscaling_targets(&changes);
+        let band = targets["orders"].read.unwrap();
+        assert_eq!((band.min, band.max), (5.0, 100.0));
+        assert_eq!(band.target_utilization, Some(0.7));
+
+        let config = DynamoDbConfiguration::from_config(&json!({
+            "name": "orders",
+            "read_capacity": 10,
+            "write_capacity": 5
+        }))
+        .with_autoscaling(&targets);
+        assert_eq!(config.billing_mode, BillingMode::Provisioned);
+
+        // 73.584M reads a month is 28 reads/s, 40 RCU at 70% utilization
+        let usage = DynamoDbUsage {
+            reads: Some(28.0 * 730.0 * 3600.0),
+            writes: None,
+            storage_gb: None,
+        };
+        let rate = DynamoDbRate::new(
+            &config,
+            usage,
+            &heuristics.database.dynamodb,
+            &heuristics.cold_start_defaults,
+            730.0,
+        );
+        assert!((rate.read.units - 40.0).abs() < 1e-9);
+        assert_eq!(rate.write.units, 5.0);
+        assert!(rate.is_range());
+
+        let (low, high) = rate.monthly_range(730.0);
+        let write = 5.0 * 0.00065 * 730.0;
+        assert!((low - (5.0 * 0.00013 * 730.0 + write + 0.25)).abs() < 1e-9);
+        assert!((high - (100.0 * 0.00013 * 730.0 + write + 0.25)).abs() < 1e-9);
+    }
+}
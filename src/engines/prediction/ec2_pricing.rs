@@ -0,0 +1,376 @@
+// EC2 instance pricing detail: tenancy, license-included and EBS-optimized
+//
+// Heuristic EC2 rates are on-demand Linux instances on shared hardware. The
+// plan often says otherwise: `tenancy = "dedicated"` runs on single-tenant
+// hardware at a premium, Windows and SQL Server AMIs bill their license per
+// vCPU-hour on top of the instance, and older families charge extra for
+// `ebs_optimized = true`. These adjustments are read from the instance's
+// planned config and priced with `compute.ec2_options` from the heuristics.
+
+use crate::engines::prediction::plan_block;
+use crate::engines::prediction::prediction_engine::Ec2Options;
+use crate::engines::shared::models::ResourceChange;
+use serde_json::Value;
+
+/// vCPUs assumed when neither `cpu_options` nor the instance size says
+const DEFAULT_VCPUS: u32 = 2;
+
+/// Hardware an instance runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tenancy {
+    #[default]
+    Default,
+    Dedicated,
+    Host,
+}
+
+impl Tenancy {
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "dedicated" => Tenancy::Dedicated,
+            "host" => Tenancy::Host,
+            _ => Tenancy::Default,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Tenancy::Default => "default",
+            Tenancy::Dedicated => "dedicated",
+            Tenancy::Host => "host",
+        }
+    }
+}
+
+/// License included in the instance price
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum License {
+    Windows,
+    SqlWeb,
+    SqlStandard,
+    SqlEnterprise,
+}
+
+impl License {
+    /// Detect the license from an AMI platform string, e.g. `Windows` or
+    /// `Windows with SQL Server Standard`
+    pub fn parse(platform: &str) -> Option<Self> {
+        let platform = platform.to_ascii_lowercase();
+        if platform.contains("sql server enterprise") {
+            Some(License::SqlEnterprise)
+        } else if platform.contains("sql server standard") {
+            Some(License::SqlStandard)
+        } else if platform.contains("sql server web") {
+            Some(License::SqlWeb)
+        } else if platform.contains("windows") {
+            Some(License::Windows)
+        } else {
+            None
+        }
+    }
+
+    /// Key in `compute.ec2_options.license_per_vcpu_hour`
+    pub fn key(&self) -> &'static str {
+        match self {
+            License::Windows => "windows",
+            License::SqlWeb => "sql_web",
+            License::SqlStandard => "sql_standard",
+            License::SqlEnterprise => "sql_enterprise",
+        }
+    }
+
+    /// Licenses billed, in order; SQL Server runs on (and pays for) Windows
+    pub fn billed(&self) -> Vec<License> {
+        match self {
+            License::Windows => vec![License::Windows],
+            sql => vec![License::Windows, *sql],
+        }
+    }
+}
+
+/// Pricing-relevant attributes of an `aws_instance`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ec2Configuration {
+    pub instance_type: String,
+    pub tenancy: Tenancy,
+    pub license: Option<License>,
+    pub ebs_optimized: bool,
+    pub vcpus: u32,
+}
+
+impl Ec2Configuration {
+    /// Read from planned instance attributes
+    pub fn from_config(config: &Value) -> Self {
+        let instance_type = config
+            .get("instance_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("t3.micro")
+            .to_string();
+
+        let on_host = ["host_id", "host_resource_group_arn"]
+            .iter()
+            .any(|key| config.get(*key).and_then(|v| v.as_str()).is_some());
+        let tenancy = match config.get("tenancy").and_then(|v| v.as_str()) {
+            Some(tenancy) => Tenancy::parse(tenancy),
+            None if on_host => Tenancy::Host,
+            None => Tenancy::Default,
+        };
+
+        let license = ["platform_details", "platform"]
+            .iter()
+            .filter_map(|key| config.get(*key).and_then(|v| v.as_str()))
+            .find_map(License::parse)
+            .or_else(|| {
+                // Only Windows AMIs have a password to retrieve
+                config
+                    .get("get_password_data")
+                    .and_then(|v| v.as_bool())
+                    .filter(|&windows| windows)
+                    .map(|_| License::Windows)
+            });
+
+        let ebs_optimized = config
+            .get("ebs_optimized")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let vcpus = configured_vcpus(config)
+            .or_else(|| vcpus_for_size(&instance_type))
+            .unwrap_or(DEFAULT_VCPUS);
+
+        Self {
+            instance_type,
+            tenancy,
+            license,
+            ebs_optimized,
+            vcpus,
+        }
+    }
+
+    /// Read from the config the change leaves in place (the old one for
+    /// deletes)
+    pub fn from_change(change: &ResourceChange) -> Option<Self> {
+        change
+            .new_config
+            .as_ref()
+            .or(change.old_config.as_ref())
+            .map(Self::from_config)
+    }
+
+    /// Whether anything differs from a shared-tenancy Linux instance
+    pub fn has_adjustments(&self) -> bool {
+        self.tenancy != Tenancy::Default || self.license.is_some() || self.ebs_optimized
+    }
+}
+
+/// vCPUs from `cpu_options` (or the older `cpu_core_count` attributes)
+fn configured_vcpus(config: &Value) -> Option<u32> {
+    let options = plan_block(config, "cpu_options");
+    let read = |keys: [&str; 2]| {
+        options
+            .and_then(|o| o.get(keys[0]))
+            .or_else(|| config.get(keys[1]))
+            .and_then(|v| v.as_u64())
+            .filter(|&n| n > 0)
+    };
+    let cores = read(["core_count", "cpu_core_count"])?;
+    let threads = read(["threads_per_core", "cpu_threads_per_core"]).unwrap_or(2);
+    u32::try_from(cores * threads).ok()
+}
+
+/// vCPUs implied by the instance size: up to `large` has 2, `xlarge` 4 and
+/// `Nxlarge` 4N
+fn vcpus_for_size(instance_type: &str) -> Option<u32> {
+    let size = instance_type.split('.').nth(1)?;
+    match size {
+        "nano" | "micro" | "small" | "medium" | "large" => Some(2),
+        "xlarge" => Some(4),
+        _ => size
+            .strip_suffix("xlarge")
+            .and_then(|n| n.parse::<u32>().ok())
+            .map(|n| n * 4),
+    }
+}
+
+/// Hourly rate of an instance, split into the base instance rate and each
+/// adjustment on top of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ec2Rate {
+    pub instance_type: String,
+
+    /// On-demand Linux rate on shared hardware
+    pub base_hourly: f64,
+
+    pub tenancy: Tenancy,
+
+    /// Multiplier applied to the base rate for the tenancy
+    pub tenancy_multiplier: f64,
+
+    pub vcpus: u32,
+
+    /// Per-vCPU hourly rate of each license billed
+    pub licenses: Vec<(License, f64)>,
+
+    pub ebs_optimized: bool,
+
+    /// Hourly EBS-optimized surcharge (zero for families that include it)
+    pub ebs_optimized_hourly: f64,
+}
+
+impl Ec2Rate {
+    /// Plain on-demand rate with no adjustments
+    pub fn on_demand(instance_type: &str, base_hourly: f64) -> Self {
+        Self {
+            instance_type: instance_type.to_string(),
+            base_hourly,
+            tenancy: Tenancy::Default,
+            tenancy_multiplier: 1.0,
+            vcpus: vcpus_for_size(instance_type).unwrap_or(DEFAULT_VCPUS),
+            licenses: Vec::new(),
+            ebs_optimized: false,
+            ebs_optimized_hourly: 0.0,
+        }
+    }
+
+    /// Rate for a configured instance whose base rate is `base_hourly`
+    pub fn new(config: &Ec2Configuration, base_hourly: f64, options: &Ec2Options) -> Self {
+        let tenancy_multiplier = match config.tenancy {
+            Tenancy::Default => 1.0,
+            Tenancy::Dedicated | Tenancy::Host => options.dedicated_multiplier,
+        };
+        let licenses = config
+            .license
+            .map(|license| {
+                license
+                    .billed()
+                    .into_iter()
+                    .map(|l| {
+                        let rate = options.license_per_vcpu_hour.get(l.key()).copied();
+                        (l, rate.unwrap_or(0.0))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let family = config.instance_type.split('.').next().unwrap_or_default();
+        let ebs_optimized_hourly = if config.ebs_optimized {
+            options
+                .ebs_optimized_hourly
+                .get(family)
+                .copied()
+                .unwrap_or(0.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            instance_type: config.instance_type.clone(),
+            base_hourly,
+            tenancy: config.tenancy,
+            tenancy_multiplier,
+            vcpus: config.vcpus,
+            licenses,
+            ebs_optimized: config.ebs_optimized,
+            ebs_optimized_hourly,
+        }
+    }
+
+    /// Extra hourly cost of the tenancy
+    pub fn tenancy_hourly(&self) -> f64 {
+        self.base_hourly * (self.tenancy_multiplier - 1.0)
+    }
+
+    /// Hourly cost of all licenses
+    pub fn license_hourly(&self) -> f64 {
+        self.licenses
+            .iter()
+            .map(|(_, per_vcpu)| per_vcpu * self.vcpus as f64)
+            .sum()
+    }
+
+    /// Total hourly rate
+    pub fn hourly(&self) -> f64 {
+        self.base_hourly + self.tenancy_hourly() + self.license_hourly() + self.ebs_optimized_hourly
+    }
+
+    /// Whether the rate differs from the base rate's configuration
+    pub fn is_adjusted(&self) -> bool {
+        self.tenancy != Tenancy::Default || !self.licenses.is_empty() || self.ebs_optimized
+    }
+
+    /// Licenses billed, e.g. `windows+sql_standard`
+    pub fn license_label(&self) -> Option<String> {
+        (!self.licenses.is_empty()).then(|| {
+            self.licenses
+                .iter()
+                .map(|(license, _)| license.key())
+                .collect::<Vec<_>>()
+                .join("+")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_detects_configuration() {
+        let config = Ec2Configuration::from_config(&json!({
+            "instance_type": "m5.2xlarge",
+            "tenancy": "dedicated",
+            "platform_details": "Windows with SQL Server Standard",
+            "ebs_optimized": true
+        }));
+        assert_eq!(config.tenancy, Tenancy::Dedicated);
+        assert_eq!(config.license, Some(License::SqlStandard));
+        assert!(config.ebs_optimized);
+        assert_eq!(config.vcpus, 8);
+        assert!(config.has_adjustments());
+
+        let config = Ec2Configuration::from_config(&json!({
+            "instance_type": "c5.large",
+            "host_id": "h-0123456789abcdef0",
+            "get_password_data": true,
+            "cpu_options": [{ "core_count": 4, "threads_per_core": 1 }]
+        }));
+        assert_eq!(config.tenancy, Tenancy::Host);
+        assert_eq!(config.license, Some(License::Windows));
+        assert_eq!(config.vcpus, 4);
+
+        let plain = Ec2Configuration::from_config(&json!({ "instance_type": "t3.micro" }));
+        assert!(!plain.has_adjustments());
+    }
+
+    #[test]
+    fn test_rate_components() {
+        let options = Ec2Options::default();
+        let config = Ec2Configuration {
+            instance_type: "m3.xlarge".to_string(),
+            tenancy: Tenancy::Dedicated,
+            license: Some(License::SqlWeb),
+            ebs_optimized: true,
+            vcpus: 4,
+        };
+        let rate = Ec2Rate::new(&config, 0.266, &options);
+
+        assert!((rate.tenancy_hourly() - 0.0266).abs() < 1e-9);
+        // Windows 0.046 + SQL Web 0.017 per vCPU
+        assert!((rate.license_hourly() - 0.252).abs() < 1e-9);
+        assert_eq!(rate.ebs_optimized_hourly, 0.05);
+        assert!((rate.hourly() - 0.5946).abs() < 1e-9);
+        assert_eq!(rate.license_label().as_deref(), Some("windows+sql_web"));
+
+        // Current families include EBS optimization
+        let config = Ec2Configuration {
+            instance_type: "m5.xlarge".to_string(),
+            tenancy: Tenancy::Default,
+            license: None,
+            ebs_optimized: true,
+            vcpus: 4,
+        };
+        let rate = Ec2Rate::new(&config, 0.192, &options);
+        assert_eq!(rate.hourly(), 0.192);
+        assert!(!Ec2Rate::on_demand("m5.xlarge", 0.192).is_adjusted());
+    }
+}
@@ -0,0 +1,519 @@
+// AWS free tier assumptions (`free_tier` in costpilot.yaml)
+//
+// Usage-priced services such as Lambda and DynamoDB cost little or nothing
+// for a small project that stays inside the free tier, yet a flat heuristic
+// prices every function and table. The free tier is one allowance per
+// account, so a plan draws on a single pool: each eligible resource, in plan
+// order, has what is left of its service's allowance subtracted from its
+// estimate, keeping the unadjusted cost as the top of the interval. A table
+// serving heavy traffic only loses its share of the storage allowance.
+// Detection rules point out settings that push a resource out of the free
+// tier, and organizations past their free tier turn the assumptions off.
+
+use crate::engines::prediction::prediction_engine::CostHeuristics;
+use crate::engines::shared::error_model::CostPilotError;
+use crate::engines::shared::models::{
+    ChangeAction, CostEstimate, Detection, RegressionType, ResourceChange, Severity,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Lambda compute included in the free tier each month
+const LAMBDA_FREE_GB_SECONDS: f64 = 400_000.0;
+
+/// Largest Lambda memory size still assumed to fit the free tier
+const LAMBDA_MAX_FREE_MEMORY_MB: u64 = 1024;
+
+/// Provisioned DynamoDB read and write capacity included in the free tier
+const DYNAMODB_FREE_CAPACITY_UNITS: u64 = 25;
+
+/// DynamoDB storage included in the free tier
+const DYNAMODB_FREE_STORAGE_GB: f64 = 25.0;
+
+const HOURS_PER_MONTH: f64 = 730.0;
+
+/// Free tier settings (`free_tier` in costpilot.yaml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FreeTierConfig {
+    /// Assume eligible usage stays within the AWS free tier; disable for
+    /// accounts that have used it up or are past the first 12 months
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for FreeTierConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// A resource whose estimate the free tier covers, in part or in full
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FreeTierAdjustment {
+    pub resource_id: String,
+
+    /// Monthly cost the estimate had before the adjustment
+    pub unadjusted_monthly_cost: f64,
+
+    /// Monthly cost the free tier covers
+    pub covered_monthly_cost: f64,
+
+    pub allowance: String,
+}
+
+/// Free tier allowance left for the rest of a plan, in $/month at the
+/// heuristics' list rates
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeTierPool {
+    lambda: f64,
+    dynamodb_capacity: f64,
+    dynamodb_storage: f64,
+}
+
+/// Part of the free tier a resource draws on
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Allowance {
+    Lambda,
+    DynamoDbProvisioned,
+    DynamoDbOnDemand,
+}
+
+impl Allowance {
+    fn description(&self) -> &'static str {
+        match self {
+            Allowance::Lambda => "1M Lambda requests and 400,000 GB-seconds a month",
+            Allowance::DynamoDbProvisioned => "25 GB of DynamoDB storage and 25 RCU / 25 WCU",
+            Allowance::DynamoDbOnDemand => "25 GB of DynamoDB storage",
+        }
+    }
+}
+
+impl FreeTierPool {
+    /// The account's whole monthly allowance
+    pub fn new(heuristics: &CostHeuristics) -> Self {
+        let lambda = &heuristics.compute.lambda;
+        let dynamodb = &heuristics.database.dynamodb.provisioned;
+        let capacity_units = DYNAMODB_FREE_CAPACITY_UNITS as f64;
+        Self {
+            lambda: lambda.free_tier_requests as f64 * lambda.price_per_request
+                + lambda.free_tier_compute_gb_seconds as f64 * lambda.price_per_gb_second,
+            dynamodb_capacity: capacity_units
+                * (dynamodb.read_capacity_unit_hourly + dynamodb.write_capacity_unit_hourly)
+                * HOURS_PER_MONTH,
+            dynamodb_storage: DYNAMODB_FREE_STORAGE_GB * dynamodb.storage_per_gb,
+        }
+    }
+
+    /// A pool with nothing left
+    pub fn empty() -> Self {
+        Self {
+            lambda: 0.0,
+            dynamodb_capacity: 0.0,
+            dynamodb_storage: 0.0,
+        }
+    }
+
+    /// Allowance left, in $/month
+    pub fn remaining(&self) -> f64 {
+        self.lambda + self.dynamodb_capacity + self.dynamodb_storage
+    }
+
+    /// Draw up to `cost` from the parts of the pool `allowance` covers
+    fn draw(&mut self, allowance: Allowance, cost: f64) -> f64 {
+        let parts: Vec<&mut f64> = match allowance {
+            Allowance::Lambda => vec![&mut self.lambda],
+            Allowance::DynamoDbProvisioned => {
+                vec![&mut self.dynamodb_capacity, &mut self.dynamodb_storage]
+            }
+            Allowance::DynamoDbOnDemand => vec![&mut self.dynamodb_storage],
+        };
+        let mut covered = 0.0;
+        for part in parts {
+            let drawn = part.min(cost - covered);
+            *part -= drawn;
+            covered += drawn;
+        }
+        covered
+    }
+
+    /// Subtract what is left of the allowance from the estimates of created
+    /// or changed resources eligible for the free tier, in order, drawing the
+    /// pool down. Resources priced by a custom rate are left alone; the rate
+    /// already says what they cost. Call once per plan, or once per batch in
+    /// plan order.
+    pub fn apply(
+        &mut self,
+        changes: &[ResourceChange],
+        estimates: &mut [CostEstimate],
+    ) -> Vec<FreeTierAdjustment> {
+        let changes: HashMap<&str, &ResourceChange> = changes
+            .iter()
+            .filter(|c| !matches!(c.action, ChangeAction::Delete | ChangeAction::NoOp))
+            .map(|c| (c.resource_id.as_str(), c))
+            .collect();
+
+        let mut adjustments = Vec::new();
+        for estimate in estimates.iter_mut() {
+            let Some(change) = changes.get(estimate.resource_id.as_str()) else {
+                continue;
+            };
+            let Some(allowance) = allowance(change) else {
+                continue;
+            };
+            let custom_rate = estimate
+                .provenance
+                .as_ref()
+                .is_some_and(|p| p.heuristic_id.starts_with("custom:"));
+            if custom_rate || estimate.monthly_cost <= 0.0 {
+                continue;
+            }
+            let unadjusted = estimate.monthly_cost;
+            let covered = self.draw(allowance, unadjusted);
+            if covered <= 0.0 {
+                continue;
+            }
+
+            adjustments.push(FreeTierAdjustment {
+                resource_id: estimate.resource_id.clone(),
+                unadjusted_monthly_cost: unadjusted,
+                covered_monthly_cost: covered,
+                allowance: allowance.description().to_string(),
+            });
+            let adjusted = unadjusted - covered;
+            estimate.prediction_interval_high = estimate.prediction_interval_high.max(unadjusted);
+            estimate.prediction_interval_low =
+                (estimate.prediction_interval_low - covered).max(0.0);
+            estimate.monthly_cost = adjusted;
+            estimate.hourly = estimate.hourly.map(|h| h * adjusted / unadjusted);
+            estimate.daily = estimate.daily.map(|d| d * adjusted / unadjusted);
+            if let Some(provenance) = &mut estimate.provenance {
+                provenance.assumptions.push(format!(
+                    "AWS free tier ({}, shared by the account) covers ${:.2}/month of ${:.2}; \
+                     disable with free_tier.enabled: false",
+                    allowance.description(),
+                    covered,
+                    unadjusted
+                ));
+            }
+        }
+        adjustments
+    }
+}
+
+impl FreeTierConfig {
+    /// Load `free_tier` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self, CostPilotError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `free_tier` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self, CostPilotError> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `free_tier` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(
+        content: &str,
+        profile: Option<&str>,
+    ) -> Result<Self, CostPilotError> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        match root.get("free_tier").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid free_tier section: {}", e))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Allowance a plan draws on: the whole free tier, or nothing when
+    /// the assumptions are off
+    pub fn pool(&self, heuristics: &CostHeuristics) -> FreeTierPool {
+        if self.enabled {
+            FreeTierPool::new(heuristics)
+        } else {
+            FreeTierPool::empty()
+        }
+    }
+
+    /// Settings that take a resource out of the free tier
+    pub fn detect(&self, changes: &[ResourceChange]) -> Vec<Detection> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        changes
+            .iter()
+            .filter(|c| matches!(c.action, ChangeAction::Create | ChangeAction::Update))
+            .filter_map(|change| {
+                let config = change.new_config.as_ref()?;
+                match change.resource_type.as_str() {
+                    "aws_lambda_function" => {
+                        let memory = lambda_memory_mb(config);
+                        (memory > LAMBDA_MAX_FREE_MEMORY_MB).then(|| {
+                            let hours = LAMBDA_FREE_GB_SECONDS / (memory as f64 / 1024.0) / 3600.0;
+                            detection(
+                                "LAMBDA_FREE_TIER_MEMORY",
+                                change,
+                                format!(
+                                    "Lambda memory_size of {} MB exhausts the free tier's 400,000 GB-seconds after {:.0} hours of run time a month",
+                                    memory, hours
+                                ),
+                                format!("memory_size = {}", LAMBDA_MAX_FREE_MEMORY_MB),
+                            )
+                        })
+                    }
+                    "aws_dynamodb_table" => {
+                        let (read, write) = dynamodb_capacity(config)?;
+                        (read.max(write) > DYNAMODB_FREE_CAPACITY_UNITS).then(|| {
+                            detection(
+                                "DYNAMODB_FREE_TIER_CAPACITY",
+                                change,
+                                format!(
+                                    "Provisioned capacity of {} RCU / {} WCU exceeds the free tier's {} of each; lower it or switch to on-demand billing",
+                                    read, write, DYNAMODB_FREE_CAPACITY_UNITS
+                                ),
+                                "billing_mode = \"PAY_PER_REQUEST\"".to_string(),
+                            )
+                        })
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Free tier allowance covering the resource, if it is eligible
+fn allowance(change: &ResourceChange) -> Option<Allowance> {
+    let config = change.new_config.as_ref();
+    match change.resource_type.as_str() {
+        "aws_lambda_function" => {
+            let memory = config.map_or(128, lambda_memory_mb);
+            (memory <= LAMBDA_MAX_FREE_MEMORY_MB).then_some(Allowance::Lambda)
+        }
+        // On-demand requests have no free tier; only storage is covered
+        "aws_dynamodb_table" => match config.and_then(dynamodb_capacity) {
+            Some((read, write)) => (read.max(write) <= DYNAMODB_FREE_CAPACITY_UNITS)
+                .then_some(Allowance::DynamoDbProvisioned),
+            None => Some(Allowance::DynamoDbOnDemand),
+        },
+        _ => None,
+    }
+}
+
+fn lambda_memory_mb(config: &serde_json::Value) -> u64 {
+    config
+        .get("memory_size")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(128)
+}
+
+/// Read and write capacity of a provisioned table; `None` for on-demand
+fn dynamodb_capacity(config: &serde_json::Value) -> Option<(u64, u64)> {
+    let billing_mode = config
+        .get("billing_mode")
+        .and_then(|v| v.as_str())
+        .unwrap_or("PROVISIONED");
+    if billing_mode.eq_ignore_ascii_case("PAY_PER_REQUEST") {
+        return None;
+    }
+    let units = |key: &str| config.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    Some((units("read_capacity"), units("write_capacity")))
+}
+
+fn detection(rule_id: &str, change: &ResourceChange, message: String, fix: String) -> Detection {
+    Detection {
+        rule_id: rule_id.to_string(),
+        severity: Severity::Low,
+        resource_id: change.resource_id.clone(),
+        regression_type: RegressionType::Configuration,
+        severity_score: 20,
+        message,
+        fix_snippet: Some(fix),
+        estimated_cost: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::minimal_heuristics::MinimalHeuristics;
+    use crate::engines::shared::models::EstimateProvenance;
+    use serde_json::json;
+
+    fn change(id: &str, resource_type: &str, config: serde_json::Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(id)
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    fn estimate(id: &str, cost: f64, heuristic_id: &str) -> CostEstimate {
+        CostEstimate::builder()
+            .resource_id(id)
+            .monthly_cost(cost)
+            .prediction_interval_low(cost * 0.7)
+            .prediction_interval_high(cost * 1.3)
+            .provenance(EstimateProvenance {
+                heuristic_id: heuristic_id.to_string(),
+                pricing_version: None,
+                assumptions: Vec::new(),
+                steps: Vec::new(),
+            })
+            .build()
+    }
+
+    /// 1M requests at $0.0000002 and 400,000 GB-seconds at $0.0000166667
+    const LAMBDA_ALLOWANCE: f64 = 0.2 + 400_000.0 * 0.0000166667;
+
+    /// 25 GB at $0.25/GB-month
+    const DYNAMODB_STORAGE_ALLOWANCE: f64 = 6.25;
+
+    fn pool() -> FreeTierPool {
+        FreeTierConfig::default().pool(&MinimalHeuristics::to_cost_heuristics())
+    }
+
+    #[test]
+    fn test_apply_subtracts_allowance_from_eligible_estimates() {
+        let changes = vec![
+            change("aws_lambda_function.api", "aws_lambda_function", json!({})),
+            change(
+                "aws_lambda_function.big",
+                "aws_lambda_function",
+                json!({"memory_size": 3008}),
+            ),
+            change(
+                "aws_dynamodb_table.t",
+                "aws_dynamodb_table",
+                json!({"billing_mode": "PAY_PER_REQUEST"}),
+            ),
+            change(
+                "aws_dynamodb_table.p",
+                "aws_dynamodb_table",
+                json!({"read_capacity": 100, "write_capacity": 5}),
+            ),
+            change("aws_lambda_function.c", "aws_lambda_function", json!({})),
+        ];
+        let mut estimates = vec![
+            estimate(
+                "aws_lambda_function.api",
+                10.0,
+                "static:aws_lambda_function",
+            ),
+            estimate(
+                "aws_lambda_function.big",
+                10.0,
+                "static:aws_lambda_function",
+            ),
+            estimate("aws_dynamodb_table.t", 20.0, "static:aws_dynamodb_table"),
+            estimate("aws_dynamodb_table.p", 20.0, "static:aws_dynamodb_table"),
+            estimate("aws_lambda_function.c", 4.0, "custom:aws_lambda_function"),
+        ];
+
+        let adjustments = pool().apply(&changes, &mut estimates);
+
+        let ids: Vec<&str> = adjustments.iter().map(|a| a.resource_id.as_str()).collect();
+        assert_eq!(ids, vec!["aws_lambda_function.api", "aws_dynamodb_table.t"]);
+        assert!((estimates[0].monthly_cost - (10.0 - LAMBDA_ALLOWANCE)).abs() < 1e-9);
+        assert!((estimates[0].prediction_interval_low - (7.0 - LAMBDA_ALLOWANCE)).abs() < 1e-9);
+        assert_eq!(estimates[0].prediction_interval_high, 13.0);
+        assert!(
+            estimates[0].provenance.as_ref().unwrap().assumptions[0].contains("400,000 GB-seconds")
+        );
+        assert_eq!(estimates[1].monthly_cost, 10.0);
+        // On-demand requests have no free tier; only storage is covered
+        assert!((estimates[2].monthly_cost - (20.0 - DYNAMODB_STORAGE_ALLOWANCE)).abs() < 1e-9);
+        assert_eq!(estimates[3].monthly_cost, 20.0);
+        assert_eq!(estimates[4].monthly_cost, 4.0);
+
+        let disabled = FreeTierConfig::from_yaml("free_tier:\n  enabled: false\n").unwrap();
+        let mut estimates = vec![estimate("aws_lambda_function.api", 10.0, "static:x")];
+        let mut pool = disabled.pool(&MinimalHeuristics::to_cost_heuristics());
+        assert!(pool.apply(&changes, &mut estimates).is_empty());
+        assert_eq!(estimates[0].monthly_cost, 10.0);
+    }
+
+    #[test]
+    fn test_eligible_resources_share_one_allowance() {
+        let changes = [
+            change("aws_lambda_function.a", "aws_lambda_function", json!({})),
+            change("aws_lambda_function.b", "aws_lambda_function", json!({})),
+            change(
+                "aws_dynamodb_table.busy",
+                "aws_dynamodb_table",
+                json!({"billing_mode": "PAY_PER_REQUEST"}),
+            ),
+        ];
+        let mut pool = pool();
+
+        // Separate batches draw on the same pool
+        let mut first = vec![estimate("aws_lambda_function.a", 4.0, "static:x")];
+        let mut second = vec![
+            estimate("aws_lambda_function.b", 4.0, "static:x"),
+            estimate("aws_dynamodb_table.busy", 500.0, "usage:aws_dynamodb_table"),
+        ];
+        pool.apply(&changes[..1], &mut first);
+        let adjustments = pool.apply(&changes[1..], &mut second);
+
+        assert_eq!(first[0].monthly_cost, 0.0);
+        assert!((second[0].monthly_cost - (8.0 - LAMBDA_ALLOWANCE)).abs() < 1e-9);
+        assert!((adjustments[0].covered_monthly_cost - (LAMBDA_ALLOWANCE - 4.0)).abs() < 1e-9);
+        // Heavy traffic keeps nearly all of its cost
+        assert!((second[1].monthly_cost - (500.0 - DYNAMODB_STORAGE_ALLOWANCE)).abs() < 1e-9);
+
+        // Nothing left for another function
+        let mut third = vec![estimate("aws_lambda_function.a", 4.0, "static:x")];
+        assert!(pool.apply(&changes[..1], &mut third).is_empty());
+        assert_eq!(third[0].monthly_cost, 4.0);
+    }
+
+    #[test]
+    fn test_detect_settings_outside_free_tier() {
+        let changes = vec![
+            change(
+                "aws_lambda_function.big",
+                "aws_lambda_function",
+                json!({"memory_size": 2048}),
+            ),
+            change(
+                "aws_lambda_function.small",
+                "aws_lambda_function",
+                json!({}),
+            ),
+            change(
+                "aws_dynamodb_table.p",
+                "aws_dynamodb_table",
+                json!({"billing_mode": "PROVISIONED", "read_capacity": 50, "write_capacity": 10}),
+            ),
+        ];
+
+        let detections = FreeTierConfig::default().detect(&changes);
+
+        assert_eq!(detections.len(), 2);
+        assert_eq!(detections[0].rule_id, "LAMBDA_FREE_TIER_MEMORY");
+        assert!(detections[0].message.contains("after 56 hours"));
+        assert_eq!(detections[1].rule_id, "DYNAMODB_FREE_TIER_CAPACITY");
+        assert_eq!(
+            detections[1].fix_snippet.as_deref(),
+            Some("billing_mode = \"PAY_PER_REQUEST\"")
+        );
+        assert!(FreeTierConfig { enabled: false }
+            .detect(&changes)
+            .is_empty());
+        assert!(FreeTierConfig::from_yaml("free_tier:\n  enable: false\n").is_err());
+    }
+}
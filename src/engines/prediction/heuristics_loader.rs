@@ -1,5 +1,6 @@
 // Heuristics loader with fallback strategies and validation
 
+use super::custom_heuristics::{CustomHeuristics, CUSTOM_HEURISTICS_DIR};
 use super::prediction_engine::CostHeuristics;
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
 use dirs;
@@ -10,10 +11,14 @@ use std::path::{Path, PathBuf};
 const MIN_HEURISTICS_VERSION: &str = "1.0.0";
 /// Maximum compatible heuristics major version
 const MAX_MAJOR_VERSION: u32 = 1;
+/// Version of the heuristics built into this release; custom heuristics
+/// files pin against it
+pub const HEURISTICS_VERSION: &str = "1.0.0";
 
 /// Heuristics loader with multiple fallback strategies
 pub struct HeuristicsLoader {
     search_paths: Vec<PathBuf>,
+    custom_dir: Option<PathBuf>,
 }
 
 impl HeuristicsLoader {
@@ -21,6 +26,7 @@ impl HeuristicsLoader {
     pub fn new() -> Self {
         Self {
             search_paths: Self::default_search_paths(),
+            custom_dir: Some(PathBuf::from(CUSTOM_HEURISTICS_DIR)),
         }
     }
 
@@ -28,6 +34,22 @@ impl HeuristicsLoader {
     pub fn with_paths(paths: Vec<PathBuf>) -> Self {
         Self {
             search_paths: paths,
+            custom_dir: None,
+        }
+    }
+
+    /// Directory of user heuristics applied on top of the loaded file
+    /// (default `.costpilot/heuristics`; `None` loads built-ins only)
+    pub fn with_custom_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.custom_dir = dir;
+        self
+    }
+
+    /// Custom heuristics pinned to `version`, none without a custom directory
+    pub fn load_custom(&self, version: &str) -> Result<CustomHeuristics> {
+        match &self.custom_dir {
+            Some(dir) => CustomHeuristics::load_dir(dir, version),
+            None => Ok(CustomHeuristics::default()),
         }
     }
 
@@ -83,13 +105,14 @@ impl HeuristicsLoader {
 
             if path.exists() {
                 match self.load_from_file(path) {
-                    Ok(heuristics) => {
+                    Ok(mut heuristics) => {
+                        self.apply_custom(&mut heuristics)?;
                         return Ok(heuristics);
                     }
                     Err(e) => {
-                        eprintln!(
-                            "⚠️  Failed to load heuristics from {}: {}",
-                            path.display(),
+                        tracing::warn!(
+                            path = %path.display(),
+                            "Failed to load heuristics: {}",
                             e
                         );
                         continue;
@@ -109,6 +132,19 @@ impl HeuristicsLoader {
         .with_hint("Run 'costpilot init' to create default heuristics, or specify path with --heuristics flag"))
     }
 
+    /// Patch loaded heuristics with the custom directory's overrides
+    fn apply_custom(&self, heuristics: &mut CostHeuristics) -> Result<()> {
+        let custom = self.load_custom(&heuristics.version)?;
+        for skipped in &custom.skipped {
+            tracing::warn!("Skipping custom heuristics: {}", skipped);
+        }
+        if custom.is_empty() {
+            return Ok(());
+        }
+        custom.apply_overrides(heuristics)?;
+        self.validate(heuristics)
+    }
+
     /// Load heuristics from specific file
     pub fn load_from_file(&self, path: &Path) -> Result<CostHeuristics> {
         let content = std::fs::read_to_string(path).map_err(|e| {
@@ -171,6 +207,101 @@ impl HeuristicsLoader {
             }
         }
 
+        // Tenancy can only add to the instance rate, surcharges can't be negative
+        let options = &heuristics.compute.ec2_options;
+        let surcharges = options
+            .license_per_vcpu_hour
+            .iter()
+            .chain(&options.ebs_optimized_hourly);
+        if options.dedicated_multiplier < 1.0 {
+            return Err(CostPilotError::new(
+                "HEURISTICS_006",
+                ErrorCategory::ValidationError,
+                format!(
+                    "Invalid EC2 dedicated_multiplier: {} (must be at least 1.0)",
+                    options.dedicated_multiplier
+                ),
+            ));
+        }
+        for (key, rate) in surcharges {
+            if *rate < 0.0 {
+                return Err(CostPilotError::new(
+                    "HEURISTICS_006",
+                    ErrorCategory::ValidationError,
+                    format!("Invalid EC2 hourly surcharge for {}: ${}", key, rate),
+                ));
+            }
+        }
+
+        // Container rates can't be negative, default task sizes must be positive
+        let containers = &heuristics.compute.containers;
+        let rates = [
+            (
+                "eks_control_plane_hourly",
+                containers.eks_control_plane_hourly,
+            ),
+            ("fargate_vcpu_second", containers.fargate_vcpu_second),
+            ("fargate_gb_second", containers.fargate_gb_second),
+        ];
+        for (key, rate) in rates {
+            if rate < 0.0 {
+                return Err(CostPilotError::new(
+                    "HEURISTICS_006",
+                    ErrorCategory::ValidationError,
+                    format!("Invalid container rate {}: ${}", key, rate),
+                ));
+            }
+        }
+        if containers.fargate_default_vcpu <= 0.0 || containers.fargate_default_memory_gb <= 0.0 {
+            return Err(CostPilotError::new(
+                "HEURISTICS_006",
+                ErrorCategory::ValidationError,
+                "Fargate default task size must be positive".to_string(),
+            ));
+        }
+
+        // CloudWatch rates can't be negative, unbounded retention must be positive
+        let cloudwatch = &heuristics.monitoring.cloudwatch;
+        let rates = [
+            ("logs_ingestion_per_gb", cloudwatch.logs_ingestion_per_gb),
+            (
+                "logs_infrequent_access_ingestion_per_gb",
+                cloudwatch.logs_infrequent_access_ingestion_per_gb,
+            ),
+            ("logs_storage_per_gb", cloudwatch.logs_storage_per_gb),
+            (
+                "default_log_ingestion_gb",
+                cloudwatch.default_log_ingestion_gb,
+            ),
+            ("custom_metric_monthly", cloudwatch.custom_metric_monthly),
+            ("alarm_standard_monthly", cloudwatch.alarm_standard_monthly),
+            (
+                "alarm_high_resolution_monthly",
+                cloudwatch.alarm_high_resolution_monthly,
+            ),
+            (
+                "composite_alarm_monthly",
+                cloudwatch.composite_alarm_monthly,
+            ),
+            ("dashboard_monthly", cloudwatch.dashboard_monthly),
+        ];
+        for (key, rate) in rates {
+            if rate < 0.0 {
+                return Err(CostPilotError::new(
+                    "HEURISTICS_006",
+                    ErrorCategory::ValidationError,
+                    format!("Invalid CloudWatch rate {}: ${}", key, rate),
+                ));
+            }
+        }
+        if cloudwatch.unbounded_retention_months <= 0.0 {
+            return Err(CostPilotError::new(
+                "HEURISTICS_006",
+                ErrorCategory::ValidationError,
+                "CloudWatch unbounded_retention_months must be positive".to_string(),
+            ));
+        }
+
         // Check Lambda pricing
         if heuristics.compute.lambda.price_per_gb_second <= 0.0 {
             return Err(CostPilotError::new(
@@ -188,6 +319,35 @@ impl HeuristicsLoader {
                 "No RDS MySQL instance types defined".to_string(),
             ));
         }
+        let options = &heuristics.database.rds_options;
+        if options.multi_az_multiplier < 1.0 {
+            return Err(CostPilotError::new(
+                "HEURISTICS_008",
+                ErrorCategory::ValidationError,
+                format!(
+                    "Invalid RDS multi_az_multiplier: {} (must be at least 1.0)",
+                    options.multi_az_multiplier
+                ),
+            ));
+        }
+        let rates = options
+            .storage_per_gb
+            .iter()
+            .chain(&options.iops_per_month)
+            .map(|(key, rate)| (key.as_str(), *rate))
+            .chain([
+                ("gp3_included_iops", options.gp3_included_iops),
+                ("aurora_acu_hourly", options.aurora_acu_hourly),
+            ]);
+        for (key, rate) in rates {
+            if rate < 0.0 {
+                return Err(CostPilotError::new(
+                    "HEURISTICS_008",
+                    ErrorCategory::ValidationError,
+                    format!("Invalid RDS rate for {}: {}", key, rate),
+                ));
+            }
+        }
 
         Ok(())
     }
@@ -267,9 +427,14 @@ impl HeuristicsLoader {
             if let Ok(patch) = parts[2].parse::<u32>() {
                 let min_patch: u32 = min_parts.get(2).and_then(|p| p.parse().ok()).unwrap_or(0);
                 if patch < min_patch {
-                    eprintln!(
-                        "⚠️  Warning: Heuristics patch version {}.{}.{} is older than recommended {}.{}.{}",
-                        major, minor, patch, min_major, min_minor, min_patch
+                    tracing::warn!(
+                        "Heuristics patch version {}.{}.{} is older than recommended {}.{}.{}",
+                        major,
+                        minor,
+                        patch,
+                        min_major,
+                        min_minor,
+                        min_patch
                     );
                 }
             }
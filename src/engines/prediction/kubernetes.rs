@@ -0,0 +1,410 @@
+// Kubernetes workload cost estimation
+//
+// Workloads don't have a price of their own; they occupy part of a node. A
+// replica costs its share of a node in the pool it schedules onto, the larger
+// of its CPU and memory fractions, grossed up by the utilization the cluster
+// actually reaches (bin-packing is never perfect). Pools come from the
+// `kubernetes` section of costpilot.yaml so EKS, GKE or self-managed node
+// prices can be modelled.
+
+use crate::engines::prediction::calculation_steps::action_step;
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use crate::engines::shared::models::{
+    CalculationStep, ChangeAction, CostEstimate, EstimateProvenance, ResourceChange,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+const HOURS_PER_MONTH: f64 = 730.0;
+
+/// Resource type prefix produced by the Kubernetes artifact parser
+pub const KUBERNETES_RESOURCE_PREFIX: &str = "kubernetes_";
+
+/// Cluster pricing model (`kubernetes` in costpilot.yaml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterPricing {
+    /// Node pools, in order of preference when a pod has no node selector
+    #[serde(default = "default_node_pools")]
+    pub node_pools: Vec<NodePool>,
+
+    /// Nodes a DaemonSet runs on
+    #[serde(default = "default_daemonset_nodes")]
+    pub daemonset_nodes: u64,
+
+    /// Fraction of node capacity that workloads actually fill (0, 1]
+    #[serde(default = "default_target_utilization")]
+    pub target_utilization: f64,
+
+    /// CPU cores assumed for containers without requests or limits
+    #[serde(default = "default_cpu_request")]
+    pub default_cpu_request: f64,
+
+    /// Memory (GiB) assumed for containers without requests or limits
+    #[serde(default = "default_memory_request_gib")]
+    pub default_memory_request_gib: f64,
+}
+
+/// A group of identical nodes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodePool {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance_type: Option<String>,
+
+    pub vcpu: f64,
+
+    pub memory_gib: f64,
+
+    /// On-demand price of one node
+    pub hourly_cost: f64,
+
+    /// Node labels; pods whose nodeSelector matches all of them land here
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+fn default_node_pools() -> Vec<NodePool> {
+    vec![NodePool {
+        name: "default".to_string(),
+        instance_type: Some("m5.large".to_string()),
+        vcpu: 2.0,
+        memory_gib: 8.0,
+        hourly_cost: 0.096,
+        labels: BTreeMap::new(),
+    }]
+}
+
+fn default_daemonset_nodes() -> u64 {
+    3
+}
+
+fn default_target_utilization() -> f64 {
+    0.75
+}
+
+fn default_cpu_request() -> f64 {
+    0.1
+}
+
+fn default_memory_request_gib() -> f64 {
+    0.125
+}
+
+impl Default for ClusterPricing {
+    fn default() -> Self {
+        Self {
+            node_pools: default_node_pools(),
+            daemonset_nodes: default_daemonset_nodes(),
+            target_utilization: default_target_utilization(),
+            default_cpu_request: default_cpu_request(),
+            default_memory_request_gib: default_memory_request_gib(),
+        }
+    }
+}
+
+impl ClusterPricing {
+    /// Load `kubernetes` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `kubernetes` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `kubernetes` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let pricing: Self = match root.get("kubernetes").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid kubernetes section: {}", e))
+            })?,
+            None => Self::default(),
+        };
+
+        if let Some(problem) = pricing.validate().first() {
+            return Err(CostPilotError::validation_error(problem.clone())
+                .with_hint("Check kubernetes.node_pools in costpilot.yaml"));
+        }
+
+        Ok(pricing)
+    }
+
+    /// Describe unusable settings; empty if the model can price workloads
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.node_pools.is_empty() {
+            problems.push("at least one node pool is required".to_string());
+        }
+        for (index, pool) in self.node_pools.iter().enumerate() {
+            let name = if pool.name.is_empty() {
+                format!("node pool #{}", index + 1)
+            } else {
+                format!("node pool '{}'", pool.name)
+            };
+            if pool.name.is_empty() {
+                problems.push(format!("{}: name must not be empty", name));
+            }
+            if self.node_pools[..index].iter().any(|p| p.name == pool.name) {
+                problems.push(format!("{}: duplicate name", name));
+            }
+            if pool.vcpu <= 0.0 || pool.memory_gib <= 0.0 {
+                problems.push(format!("{}: vcpu and memory_gib must be positive", name));
+            }
+            if pool.hourly_cost < 0.0 {
+                problems.push(format!("{}: hourly_cost must not be negative", name));
+            }
+        }
+        if !(self.target_utilization > 0.0 && self.target_utilization <= 1.0) {
+            problems.push(format!(
+                "target_utilization must be in (0, 1], got {}",
+                self.target_utilization
+            ));
+        }
+        if self.default_cpu_request < 0.0 || self.default_memory_request_gib < 0.0 {
+            problems.push("default requests must not be negative".to_string());
+        }
+        problems
+    }
+
+    /// Pool a pod with `node_selector` schedules onto: the first whose labels
+    /// satisfy every selector term, else the first pool
+    pub fn pool_for(&self, node_selector: Option<&Value>) -> Option<&NodePool> {
+        let selector = node_selector.and_then(Value::as_object);
+        let matches = |pool: &&NodePool| {
+            selector.is_some_and(|selector| {
+                selector
+                    .iter()
+                    .all(|(key, value)| pool.labels.get(key).map(String::as_str) == value.as_str())
+            })
+        };
+        self.node_pools
+            .iter()
+            .find(matches)
+            .or_else(|| self.node_pools.first())
+    }
+
+    /// Monthly cost of a Kubernetes workload, `None` for other resource types
+    pub fn estimate(&self, change: &ResourceChange) -> Option<CostEstimate> {
+        if !change.resource_type.starts_with(KUBERNETES_RESOURCE_PREFIX) {
+            return None;
+        }
+        let config = change
+            .new_config
+            .as_ref()
+            .filter(|c| !c.is_null())
+            .or(change.old_config.as_ref())?;
+        // Terraform's kubernetes provider uses the same type names but not the
+        // parser's request summary; leave those to the regular heuristics
+        config.get("cpu_request")?;
+        let number = |key: &str| config.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+
+        let replicas = if change.resource_type == "kubernetes_daemonset" {
+            self.daemonset_nodes as f64
+        } else {
+            config
+                .get("replicas")
+                .and_then(Value::as_f64)
+                .unwrap_or(1.0)
+        };
+        let unrequested = number("containers_without_requests");
+        let cpu = number("cpu_request") + unrequested * self.default_cpu_request;
+        let memory = number("memory_request_gib") + unrequested * self.default_memory_request_gib;
+
+        let pool = self.pool_for(config.get("node_selector"))?;
+        let cpu_share = cpu / pool.vcpu;
+        let memory_share = memory / pool.memory_gib;
+        let nodes = replicas * cpu_share.max(memory_share) / self.target_utilization;
+        let hourly = match change.action {
+            // Deleted workloads free their share of the nodes
+            ChangeAction::Delete => 0.0,
+            _ => nodes * pool.hourly_cost,
+        };
+        let monthly = hourly * HOURS_PER_MONTH;
+
+        // Split the node cost by the dimension that drives it
+        let total_share = cpu_share + memory_share;
+        let mut breakdown = HashMap::new();
+        if total_share > 0.0 {
+            breakdown.insert("cpu".to_string(), monthly * cpu_share / total_share);
+            breakdown.insert("memory".to_string(), monthly * memory_share / total_share);
+        }
+
+        // Missing requests make the estimate a guess
+        let confidence = if unrequested > 0.0 { 0.5 } else { 0.8 };
+        let interval = monthly * (1.0 - confidence) / 2.0;
+
+        let mut assumptions = vec![format!(
+            "Nodes reach {:.0}% utilization",
+            self.target_utilization * 100.0
+        )];
+        if unrequested > 0.0 {
+            assumptions.push(format!(
+                "{} container(s) without requests use {} vCPU / {} GiB",
+                unrequested, self.default_cpu_request, self.default_memory_request_gib
+            ));
+        }
+        let occupied_monthly = nodes * pool.hourly_cost * HOURS_PER_MONTH;
+        let provenance = EstimateProvenance {
+            heuristic_id: format!("kubernetes:{}", pool.name),
+            pricing_version: None,
+            assumptions,
+            steps: vec![
+                CalculationStep {
+                    step_number: 1,
+                    operation: "Node Share".to_string(),
+                    input: format!(
+                        "cpu={} of {} vCPU, memory={} of {} GiB",
+                        cpu, pool.vcpu, memory, pool.memory_gib
+                    ),
+                    output: format!("{:.4} node(s) per replica", cpu_share.max(memory_share)),
+                    reasoning:
+                        "A replica occupies the larger of its CPU and memory fractions of a node"
+                            .to_string(),
+                },
+                CalculationStep {
+                    step_number: 2,
+                    operation: "Nodes Occupied".to_string(),
+                    input: format!(
+                        "replicas={}, utilization={}",
+                        replicas, self.target_utilization
+                    ),
+                    output: format!("{:.4} node(s)", nodes),
+                    reasoning: format!(
+                        "{} × {:.4} / {} = {:.4}",
+                        replicas,
+                        cpu_share.max(memory_share),
+                        self.target_utilization,
+                        nodes
+                    ),
+                },
+                CalculationStep {
+                    step_number: 3,
+                    operation: "Node Cost".to_string(),
+                    input: format!(
+                        "hourly=${:.4}/node, hours={}",
+                        pool.hourly_cost, HOURS_PER_MONTH
+                    ),
+                    output: format!("${:.2}/month", occupied_monthly),
+                    reasoning: format!(
+                        "{:.4} node(s) at ${:.4}/hour in pool {}",
+                        nodes, pool.hourly_cost, pool.name
+                    ),
+                },
+                action_step(4, &change.action, occupied_monthly, monthly),
+            ],
+        };
+
+        Some(CostEstimate {
+            resource_id: change.resource_id.clone(),
+            monthly_cost: monthly,
+            prediction_interval_low: monthly - interval,
+            prediction_interval_high: monthly + interval,
+            confidence_score: confidence,
+            heuristic_reference: Some(format!("kubernetes:{}", pool.name)),
+            cold_start_inference: false,
+            one_time: None,
+            breakdown: Some(breakdown),
+            hourly: Some(hourly),
+            daily: Some(hourly * 24.0),
+            provenance: Some(provenance),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn workload(resource_type: &str, config: Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id("shop/deployment/web")
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    #[test]
+    fn test_replicas_priced_by_dominant_resource() {
+        let pricing = ClusterPricing::default();
+        // 1 core of a 2-vCPU node outweighs 1 GiB of 8
+        let change = workload(
+            "kubernetes_deployment",
+            json!({ "replicas": 3, "cpu_request": 1.0, "memory_request_gib": 1.0 }),
+        );
+        let estimate = pricing.estimate(&change).unwrap();
+        let expected = 3.0 * 0.5 / 0.75 * 0.096 * HOURS_PER_MONTH;
+        assert!((estimate.monthly_cost - expected).abs() < 1e-9);
+        assert_eq!(
+            estimate.heuristic_reference.as_deref(),
+            Some("kubernetes:default")
+        );
+
+        let daemonset = workload(
+            "kubernetes_daemonset",
+            json!({ "cpu_request": 0.0, "memory_request_gib": 0.0, "containers_without_requests": 1 }),
+        );
+        let estimate = pricing.estimate(&daemonset).unwrap();
+        assert!(estimate.monthly_cost > 0.0);
+        assert_eq!(estimate.confidence_score, 0.5);
+
+        assert!(pricing
+            .estimate(&workload("aws_instance", json!({})))
+            .is_none());
+    }
+
+    #[test]
+    fn test_node_selector_picks_pool_from_config() {
+        let yaml = r#"
+kubernetes:
+  target_utilization: 1.0
+  node_pools:
+    - name: general
+      vcpu: 4
+      memory_gib: 16
+      hourly_cost: 0.2
+    - name: gpu
+      instance_type: g5.xlarge
+      vcpu: 4
+      memory_gib: 16
+      hourly_cost: 1.0
+      labels:
+        cloud.google.com/gke-nodepool: gpu
+"#;
+        let pricing = ClusterPricing::from_yaml(yaml).unwrap();
+        let change = workload(
+            "kubernetes_deployment",
+            json!({
+                "replicas": 1,
+                "cpu_request": 4.0,
+                "memory_request_gib": 4.0,
+                "node_selector": { "cloud.google.com/gke-nodepool": "gpu" }
+            }),
+        );
+        let estimate = pricing.estimate(&change).unwrap();
+        assert!((estimate.monthly_cost - HOURS_PER_MONTH).abs() < 1e-9);
+
+        let invalid = "kubernetes:\n  target_utilization: 1.5\n  node_pools: []\n";
+        assert!(ClusterPricing::from_yaml(invalid).is_err());
+        assert_eq!(
+            serde_yaml::from_str::<ClusterPricing>("target_utilization: 0\n")
+                .unwrap()
+                .validate()
+                .len(),
+            1
+        );
+    }
+}
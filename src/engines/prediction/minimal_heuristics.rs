@@ -88,6 +88,7 @@ impl MinimalHeuristics {
             last_updated: chrono::Utc::now().to_rfc3339(),
             compute: ComputeHeuristics {
                 ec2: ec2_map,
+                ec2_options: Default::default(),
                 lambda: LambdaCost {
                     price_per_gb_second: 0.0000166667,
                     price_per_request: 0.0000002,
@@ -96,6 +97,7 @@ impl MinimalHeuristics {
                     default_memory_mb: 128,
                     default_duration_ms: 1000,
                 },
+                containers: Default::default(),
             },
             storage: StorageHeuristics {
                 s3: S3Cost {
@@ -122,6 +124,7 @@ impl MinimalHeuristics {
                     storage_gp3_per_gb: 0.115,
                     backup_per_gb: 0.095,
                 },
+                rds_options: Default::default(),
                 dynamodb: DynamoDbCost {
                     on_demand: DynamoDbOnDemand {
                         write_request_unit: 0.00000125,
@@ -158,6 +161,7 @@ impl MinimalHeuristics {
                 ec2_default_utilization: 0.7,
             },
             prediction_intervals: PredictionIntervals { range_factor: 0.3 },
+            monitoring: Default::default(),
         }
     }
 }
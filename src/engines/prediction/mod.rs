@@ -1,36 +1,84 @@
 // Prediction engine module
 
 pub mod calculation_steps;
+pub mod calibration;
+pub mod cloudwatch_pricing;
 pub mod cold_start;
 pub mod confidence;
+pub mod container_pricing;
+pub mod coverage;
+pub mod custom_heuristics;
+pub mod dynamodb_pricing;
+pub mod ec2_pricing;
+pub mod free_tier;
 pub mod heuristics_loader;
+pub mod kubernetes;
 pub mod minimal_heuristics;
 pub mod monte_carlo;
 pub mod prediction_engine;
 pub mod probabilistic;
+pub mod rds_pricing;
 pub mod seasonality;
+pub mod usage_profile;
 
 pub use crate::engines::shared::models::{CostEstimate, TotalCost};
 pub use calculation_steps::{
-    cold_start_step, confidence_step, document_calculation, dynamodb_calculation_step,
-    ec2_calculation_step, interval_step, lambda_calculation_step, load_balancer_calculation_step,
-    nat_gateway_calculation_step, rds_calculation_step, s3_calculation_step,
-    storage_calculation_step, CalculationBreakdown, CalculationStep,
+    action_step, cloudwatch_calculation_step, cold_start_step, confidence_step,
+    document_calculation, dynamodb_calculation_step, ec2_calculation_step, eks_control_plane_step,
+    fargate_calculation_step, flat_rate_step, interval_step, lambda_calculation_step,
+    load_balancer_calculation_step, nat_gateway_calculation_step, node_group_calculation_step,
+    rds_calculation_step, s3_calculation_step, storage_calculation_step, CalculationBreakdown,
+    CalculationStep,
 };
+pub use calibration::{
+    parse_actuals_csv, CalibrationUpdate, ConfidenceCalibration, ModuleActual, CALIBRATION_FILE,
+};
+pub use cloudwatch_pricing::{CloudWatchCharge, CloudWatchRate, LOG_INGESTION_GB_KEY};
 pub use cold_start::ColdStartInference;
 pub use confidence::{calculate_confidence, calculate_interval_width};
-pub use heuristics_loader::{HeuristicsLoader, HeuristicsStats};
+pub use container_pricing::{
+    is_fargate_service, task_definitions, FargateConfiguration, FargateRate, FargateTask,
+    NodeGroupConfiguration, NodeGroupRate, TaskSizeSource,
+};
+pub use coverage::{CoverageSource, HeuristicsCoverage, TypeCoverage};
+pub use custom_heuristics::{
+    CustomHeuristics, CustomHeuristicsFile, PerUnitRate, ResourceRate, CUSTOM_HEURISTICS_DIR,
+};
+pub use dynamodb_pricing::{
+    autoscaling_targets, BillingMode, CapacityBand, DynamoDbConfiguration, DynamoDbRate,
+    DynamoDbUsage, TableAutoscaling,
+};
+pub use ec2_pricing::{Ec2Configuration, Ec2Rate, License, Tenancy};
+pub use free_tier::{FreeTierAdjustment, FreeTierConfig, FreeTierPool};
+pub use heuristics_loader::{HeuristicsLoader, HeuristicsStats, HEURISTICS_VERSION};
+pub use kubernetes::{ClusterPricing, NodePool};
 pub use minimal_heuristics::MinimalHeuristics;
 pub use monte_carlo::{
     CostDistribution, DistributionBin, DistributionShape, MonteCarloResult, MonteCarloSimulator,
     UncertaintyInput, UncertaintyType,
 };
-pub use prediction_engine::PredictionEngine;
+pub use prediction_engine::{
+    pricing_context, CloudWatchCost, ContainerOptions, Ec2Options, MonitoringHeuristics,
+    PlanPricing, PredictionEngine, RdsOptions, PRICED_RESOURCE_TYPES, PRICING_CONTEXT_TYPES,
+};
 pub use probabilistic::{
     CostScenario, ProbabilisticEstimate, ProbabilisticPredictor, RiskLevel, ScenarioAnalysis,
     ScenarioResult, UncertaintyFactor,
 };
+pub use rds_pricing::{RdsConfiguration, RdsRate};
 pub use seasonality::{
     CostDataPoint, PatternType, SeasonalAdjustedPrediction, SeasonalPattern, SeasonalityAnalysis,
     SeasonalityDetector,
 };
+pub use usage_profile::UsageProfile;
+
+use serde_json::Value;
+
+/// Nested block `key` of a resource config. Plan JSON renders blocks as
+/// single-element lists; other sources give the object itself.
+pub(crate) fn plan_block<'a>(config: &'a Value, key: &str) -> Option<&'a Value> {
+    match config.get(key)? {
+        Value::Array(items) => items.first(),
+        block => Some(block),
+    }
+}
@@ -1,6 +1,8 @@
 // Monte Carlo simulation for cost uncertainty quantification
 
+use crate::engines::shared::cancel::CancelToken;
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::engines::shared::progress::{Phase, ProgressReporter};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -112,7 +114,11 @@ pub enum UncertaintyType {
     Triangular { min_ratio: f64, max_ratio: f64 },
 }
 
+/// Simulation runs between cancellation checks and progress reports
+const CANCEL_CHECK_INTERVAL: u32 = 1024;
+
 /// Monte Carlo simulator
+#[derive(Debug, Clone)]
 pub struct MonteCarloSimulator {
     /// Number of simulation runs
     num_simulations: u32,
@@ -122,6 +128,8 @@ pub struct MonteCarloSimulator {
 
     /// Number of histogram bins
     num_bins: usize,
+
+    progress: ProgressReporter,
 }
 
 impl MonteCarloSimulator {
@@ -131,6 +139,7 @@ impl MonteCarloSimulator {
             num_simulations,
             seed: 42, // Default seed for deterministic results
             num_bins: 20,
+            progress: ProgressReporter::default(),
         }
     }
 
@@ -146,8 +155,24 @@ impl MonteCarloSimulator {
         self
     }
 
+    /// Report simulation progress
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
     /// Run simulation with uncertainty inputs
     pub fn simulate(&self, inputs: &[UncertaintyInput]) -> Result<MonteCarloResult> {
+        self.simulate_cancellable(inputs, &CancelToken::new())
+    }
+
+    /// Run simulation, stopping within `CANCEL_CHECK_INTERVAL` runs once
+    /// `token` is cancelled
+    pub fn simulate_cancellable(
+        &self,
+        inputs: &[UncertaintyInput],
+        token: &CancelToken,
+    ) -> Result<MonteCarloResult> {
         if inputs.is_empty() {
             return Err(CostPilotError::new(
                 "MC_001",
@@ -160,6 +185,11 @@ impl MonteCarloSimulator {
 
         // Run simulations
         for i in 0..self.num_simulations {
+            if i % CANCEL_CHECK_INTERVAL == 0 {
+                token.check("Monte Carlo simulation")?;
+                self.progress
+                    .report(Phase::Simulation, i as usize, self.num_simulations as usize);
+            }
             let mut total_cost = 0.0;
 
             for input in inputs {
@@ -170,6 +200,12 @@ impl MonteCarloSimulator {
             simulated_costs.push(total_cost.max(0.0)); // Ensure non-negative
         }
 
+        self.progress.report(
+            Phase::Simulation,
+            self.num_simulations as usize,
+            self.num_simulations as usize,
+        );
+
         // Sort for percentile calculations
         simulated_costs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
@@ -366,6 +402,21 @@ impl MonteCarloSimulator {
     }
 }
 
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+impl MonteCarloSimulator {
+    /// Run the simulation on a worker thread; dropping the future or
+    /// cancelling `token` stops it at the next check interval
+    pub fn simulate_async(
+        self,
+        inputs: Vec<UncertaintyInput>,
+        token: CancelToken,
+    ) -> impl std::future::Future<Output = Result<MonteCarloResult>> + Send {
+        crate::engines::shared::worker::run_blocking(token, move |token| {
+            self.simulate_cancellable(&inputs, token)
+        })
+    }
+}
+
 impl Default for MonteCarloSimulator {
     fn default() -> Self {
         Self::new(10000)
@@ -458,4 +509,37 @@ mod tests {
         let total_freq: f64 = result.distribution.bins.iter().map(|b| b.frequency).sum();
         assert!((total_freq - 1.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_cancelled_simulation_returns_error() {
+        let token = CancelToken::new();
+        token.cancel();
+        let inputs = vec![UncertaintyInput {
+            base_value: 100.0,
+            uncertainty_type: UncertaintyType::Normal { std_dev_ratio: 0.2 },
+            weight: 1.0,
+        }];
+
+        let err = MonteCarloSimulator::new(10_000)
+            .simulate_cancellable(&inputs, &token)
+            .unwrap_err();
+        assert_eq!(err.id, "CANCELLED");
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_simulate_async_matches_simulate() {
+        let inputs = vec![UncertaintyInput {
+            base_value: 80.0,
+            uncertainty_type: UncertaintyType::LogNormal { std_dev_ratio: 0.1 },
+            weight: 1.0,
+        }];
+        let simulator = MonteCarloSimulator::new(2000);
+
+        let sync = simulator.simulate(&inputs).unwrap();
+        let result =
+            futures::executor::block_on(simulator.simulate_async(inputs, CancelToken::new()))
+                .unwrap();
+        assert_eq!(result.mean_cost, sync.mean_cost);
+    }
 }
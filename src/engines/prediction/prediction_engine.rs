@@ -3,15 +3,42 @@
 use crate::engines::performance::budgets::{
     BudgetViolation, PerformanceBudgets, PerformanceTracker, TimeoutAction,
 };
+use crate::engines::prediction::calculation_steps::{
+    action_step, cloudwatch_calculation_step, dynamodb_calculation_step, ec2_calculation_step,
+    eks_control_plane_step, fargate_calculation_step, flat_rate_step, interval_step,
+    node_group_calculation_step, rds_calculation_step,
+};
+use crate::engines::prediction::cloudwatch_pricing::CloudWatchRate;
+use crate::engines::prediction::cold_start::ColdStartInference;
 use crate::engines::prediction::confidence::calculate_confidence;
+use crate::engines::prediction::container_pricing::{
+    is_fargate_service, task_definitions, FargateConfiguration, FargateRate, FargateTask,
+    NodeGroupConfiguration, NodeGroupRate,
+};
+use crate::engines::prediction::dynamodb_pricing::{
+    autoscaling_targets, DynamoDbConfiguration, DynamoDbRate, DynamoDbUsage, TableAutoscaling,
+};
+use crate::engines::prediction::ec2_pricing::{Ec2Configuration, Ec2Rate};
 use crate::engines::prediction::heuristics_loader::HeuristicsLoader;
+use crate::engines::prediction::kubernetes::ClusterPricing;
+use crate::engines::prediction::rds_pricing::{
+    RdsConfiguration, RdsRate, INFERRED_INSTANCE_MONTHLY,
+};
+use crate::engines::prediction::usage_profile::UsageProfile;
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
-use crate::engines::shared::models::{ChangeAction, CostEstimate, ResourceChange};
+use crate::engines::shared::models::{
+    ChangeAction, CostEstimate, EstimateProvenance, ResourceChange,
+};
 use crate::heuristics::FreeHeuristics;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
+const HOURS_PER_MONTH: f64 = 730.0;
+
+/// Free edition static cost of an EC2 instance, also each managed node's
+const STATIC_EC2_MONTHLY: f64 = 150.0;
+
 /// Prediction mode - Free or Premium
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PredictionMode {
@@ -30,12 +57,95 @@ pub struct CostHeuristics {
     pub networking: NetworkingHeuristics,
     pub cold_start_defaults: ColdStartDefaults,
     pub prediction_intervals: PredictionIntervals,
+    #[serde(default)]
+    pub monitoring: MonitoringHeuristics,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct ComputeHeuristics {
     pub ec2: HashMap<String, InstanceCost>,
+    /// Tenancy, license and EBS-optimized adjustments to the `ec2` rates
+    #[serde(default)]
+    pub ec2_options: Ec2Options,
     pub lambda: LambdaCost,
+    /// EKS control plane and Fargate rates
+    #[serde(default)]
+    pub containers: ContainerOptions,
+}
+
+/// Adjustments to on-demand EC2 rates (Linux, shared tenancy) for instance
+/// configuration detected in the plan
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct Ec2Options {
+    /// Multiplier on the instance rate for `tenancy = "dedicated"` or `"host"`
+    pub dedicated_multiplier: f64,
+
+    /// License-included surcharge per vCPU-hour, keyed `windows`, `sql_web`,
+    /// `sql_standard` and `sql_enterprise`; SQL Server also pays `windows`
+    pub license_per_vcpu_hour: HashMap<String, f64>,
+
+    /// Hourly surcharge for `ebs_optimized = true` by instance family;
+    /// families not listed are EBS-optimized at no extra charge
+    pub ebs_optimized_hourly: HashMap<String, f64>,
+}
+
+impl Default for Ec2Options {
+    fn default() -> Self {
+        let rates = |entries: &[(&str, f64)]| {
+            entries
+                .iter()
+                .map(|(key, rate)| (key.to_string(), *rate))
+                .collect()
+        };
+        Self {
+            dedicated_multiplier: 1.1,
+            license_per_vcpu_hour: rates(&[
+                ("windows", 0.046),
+                ("sql_web", 0.017),
+                ("sql_standard", 0.125),
+                ("sql_enterprise", 0.375),
+            ]),
+            ebs_optimized_hourly: rates(&[
+                ("c1", 0.05),
+                ("c3", 0.05),
+                ("i2", 0.05),
+                ("m1", 0.025),
+                ("m2", 0.025),
+                ("m3", 0.05),
+                ("r3", 0.05),
+            ]),
+        }
+    }
+}
+
+/// Rates for containerized workloads; managed node groups are priced as
+/// EC2 instances
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct ContainerOptions {
+    /// EKS control plane rate per cluster-hour
+    pub eks_control_plane_hourly: f64,
+
+    /// Fargate compute per vCPU-second and per GB-second of memory
+    pub fargate_vcpu_second: f64,
+    pub fargate_gb_second: f64,
+
+    /// Task (or pod) size assumed when none is declared
+    pub fargate_default_vcpu: f64,
+    pub fargate_default_memory_gb: f64,
+}
+
+impl Default for ContainerOptions {
+    fn default() -> Self {
+        Self {
+            eks_control_plane_hourly: 0.10,
+            fargate_vcpu_second: 0.000011244,
+            fargate_gb_second: 0.000001235,
+            fargate_default_vcpu: 0.25,
+            fargate_default_memory_gb: 0.5,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -87,9 +197,52 @@ pub struct EbsCost {
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct DatabaseHeuristics {
     pub rds: RdsCost,
+    /// Multi-AZ, provisioned IOPS and Aurora Serverless v2 rates on top of `rds`
+    #[serde(default)]
+    pub rds_options: RdsOptions,
     pub dynamodb: DynamoDbCost,
 }
 
+/// RDS deployment, storage and capacity options detected in the plan
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct RdsOptions {
+    /// Multiplier on instance, storage and IOPS for `multi_az = true`
+    /// (a standby replica with its own storage)
+    pub multi_az_multiplier: f64,
+
+    /// Storage $/GB-month for types other than gp2 and gp3 (`io1`, `io2`,
+    /// `standard`)
+    pub storage_per_gb: HashMap<String, f64>,
+
+    /// Provisioned IOPS $/IOPS-month by storage type
+    pub iops_per_month: HashMap<String, f64>,
+
+    /// IOPS included with gp3 storage; only IOPS above it are billed
+    pub gp3_included_iops: f64,
+
+    /// Aurora Serverless v2 $/ACU-hour
+    pub aurora_acu_hourly: f64,
+}
+
+impl Default for RdsOptions {
+    fn default() -> Self {
+        let rates = |entries: &[(&str, f64)]| {
+            entries
+                .iter()
+                .map(|(key, rate)| (key.to_string(), *rate))
+                .collect()
+        };
+        Self {
+            multi_az_multiplier: 2.0,
+            storage_per_gb: rates(&[("io1", 0.125), ("io2", 0.125), ("standard", 0.1)]),
+            iops_per_month: rates(&[("gp3", 0.02), ("io1", 0.1), ("io2", 0.1)]),
+            gp3_included_iops: 3000.0,
+            aurora_acu_hourly: 0.12,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct RdsCost {
     pub mysql: HashMap<String, InstanceCost>,
@@ -144,6 +297,56 @@ pub struct LoadBalancerType {
     pub lcu_hourly: f64,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
+pub struct MonitoringHeuristics {
+    #[serde(default)]
+    pub cloudwatch: CloudWatchCost,
+}
+
+/// CloudWatch Logs, custom metric, alarm and dashboard rates
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct CloudWatchCost {
+    /// Log ingestion per GB, Standard and Infrequent Access log classes
+    pub logs_ingestion_per_gb: f64,
+    pub logs_infrequent_access_ingestion_per_gb: f64,
+
+    /// Archived (retained) logs per GB-month
+    pub logs_storage_per_gb: f64,
+
+    /// Monthly ingestion assumed for a log group without a usage profile
+    pub default_log_ingestion_gb: f64,
+
+    /// Months of logs a group that never expires is priced as holding
+    pub unbounded_retention_months: f64,
+
+    pub custom_metric_monthly: f64,
+
+    /// Per metric an alarm evaluates, standard (60s+) and high resolution
+    pub alarm_standard_monthly: f64,
+    pub alarm_high_resolution_monthly: f64,
+
+    pub composite_alarm_monthly: f64,
+    pub dashboard_monthly: f64,
+}
+
+impl Default for CloudWatchCost {
+    fn default() -> Self {
+        Self {
+            logs_ingestion_per_gb: 0.50,
+            logs_infrequent_access_ingestion_per_gb: 0.25,
+            logs_storage_per_gb: 0.03,
+            default_log_ingestion_gb: 1.0,
+            unbounded_retention_months: 12.0,
+            custom_metric_monthly: 0.30,
+            alarm_standard_monthly: 0.10,
+            alarm_high_resolution_monthly: 0.30,
+            composite_alarm_monthly: 0.50,
+            dashboard_monthly: 3.00,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct ColdStartDefaults {
     pub dynamodb_unknown_rcu: u32,
@@ -159,11 +362,80 @@ pub struct PredictionIntervals {
     pub range_factor: f64,
 }
 
+/// Resource types with a built-in heuristic; others are cold-start estimates
+pub const PRICED_RESOURCE_TYPES: &[&str] = &[
+    "aws_instance",
+    "aws_db_instance",
+    "aws_dynamodb_table",
+    "aws_nat_gateway",
+    "aws_lb",
+    "aws_alb",
+    "aws_s3_bucket",
+    "aws_lambda_function",
+    "aws_eks_cluster",
+    "aws_eks_node_group",
+    "aws_eks_fargate_profile",
+    "aws_elasticache_cluster",
+    "aws_cloudfront_distribution",
+    "aws_ecs_service",
+    "aws_cloudwatch_log_group",
+    "aws_cloudwatch_log_metric_filter",
+    "aws_cloudwatch_metric_alarm",
+    "aws_cloudwatch_composite_alarm",
+    "aws_cloudwatch_dashboard",
+];
+
+/// Resource types that size other resources rather than being priced
+/// themselves; a batch of changes is priced with the plan's resources of
+/// these types as context
+pub const PRICING_CONTEXT_TYPES: &[&str] = &[
+    "aws_appautoscaling_target",
+    "aws_appautoscaling_policy",
+    "aws_ecs_task_definition",
+];
+
+/// The resources in `changes` that other resources are priced by
+pub fn pricing_context(changes: &[ResourceChange]) -> Vec<ResourceChange> {
+    changes
+        .iter()
+        .filter(|c| PRICING_CONTEXT_TYPES.contains(&c.resource_type.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Plan-wide pricing inputs beyond each resource's own configuration
+#[derive(Debug, Clone, Default)]
+pub struct PlanPricing {
+    /// Pricing model for Kubernetes workloads
+    pub cluster: ClusterPricing,
+
+    /// Monthly usage for usage-billed resources
+    pub usage: UsageProfile,
+
+    /// The plan's resources of `PRICING_CONTEXT_TYPES`
+    pub context: Vec<ResourceChange>,
+}
+
+impl PlanPricing {
+    /// Inputs for pricing any batch of `changes`
+    pub fn new(cluster: ClusterPricing, usage: UsageProfile, changes: &[ResourceChange]) -> Self {
+        Self {
+            cluster,
+            usage,
+            context: pricing_context(changes),
+        }
+    }
+}
+
 /// Main prediction engine
 pub struct PredictionEngine {
     heuristics: CostHeuristics,
     verbose: bool,
     performance_tracker: Option<PerformanceTracker>,
+    cluster_pricing: ClusterPricing,
+    usage_profile: UsageProfile,
+    dynamodb_autoscaling: HashMap<String, TableAutoscaling>,
+    task_definitions: HashMap<String, FargateTask>,
     pub mode: PredictionMode,
     pub free_rules: Option<FreeHeuristics>,
 }
@@ -179,6 +451,10 @@ impl PredictionEngine {
             heuristics: minimal_heuristics,
             verbose: false,
             performance_tracker: None,
+            cluster_pricing: ClusterPricing::default(),
+            usage_profile: UsageProfile::default(),
+            dynamodb_autoscaling: HashMap::new(),
+            task_definitions: HashMap::new(),
             mode: PredictionMode::Free,
             free_rules: Some(free_heuristics),
         })
@@ -193,6 +469,10 @@ impl PredictionEngine {
                 heuristics: minimal_heuristics,
                 verbose: false,
                 performance_tracker: None,
+                cluster_pricing: ClusterPricing::default(),
+                usage_profile: UsageProfile::default(),
+                dynamodb_autoscaling: HashMap::new(),
+                task_definitions: HashMap::new(),
                 mode: PredictionMode::Premium,
                 free_rules: None,
             })
@@ -211,6 +491,10 @@ impl PredictionEngine {
             heuristics,
             verbose: false,
             performance_tracker: None,
+            cluster_pricing: ClusterPricing::default(),
+            usage_profile: UsageProfile::default(),
+            dynamodb_autoscaling: HashMap::new(),
+            task_definitions: HashMap::new(),
             mode: PredictionMode::Free,
             free_rules: None,
         })
@@ -222,6 +506,10 @@ impl PredictionEngine {
             heuristics,
             verbose: false,
             performance_tracker: None,
+            cluster_pricing: ClusterPricing::default(),
+            usage_profile: UsageProfile::default(),
+            dynamodb_autoscaling: HashMap::new(),
+            task_definitions: HashMap::new(),
             mode: PredictionMode::Free,
             free_rules: None,
         }
@@ -233,6 +521,43 @@ impl PredictionEngine {
         self
     }
 
+    /// Price Kubernetes workloads with a cluster pricing model
+    pub fn with_cluster_pricing(mut self, pricing: ClusterPricing) -> Self {
+        self.cluster_pricing = pricing;
+        self
+    }
+
+    /// Price usage-billed resources from monthly usage quantities
+    pub fn with_usage_profile(mut self, usage: UsageProfile) -> Self {
+        self.usage_profile = usage;
+        self
+    }
+
+    /// Price provisioned DynamoDB capacity within the autoscaling targets
+    /// declared alongside the tables in `changes`
+    pub fn with_autoscaling_targets(mut self, changes: &[ResourceChange]) -> Self {
+        self.dynamodb_autoscaling = autoscaling_targets(changes);
+        self
+    }
+
+    /// Size Fargate ECS services by the task definitions in `changes`
+    pub fn with_task_definitions(mut self, changes: &[ResourceChange]) -> Self {
+        self.task_definitions = task_definitions(changes);
+        self
+    }
+
+    pub fn usage_profile(&self) -> &UsageProfile {
+        &self.usage_profile
+    }
+
+    pub fn dynamodb_autoscaling(&self) -> &HashMap<String, TableAutoscaling> {
+        &self.dynamodb_autoscaling
+    }
+
+    pub fn task_definitions(&self) -> &HashMap<String, FargateTask> {
+        &self.task_definitions
+    }
+
     /// Enable performance tracking with budgets
     pub fn with_performance_tracking(mut self, budgets: PerformanceBudgets) -> Self {
         self.performance_tracker = Some(PerformanceTracker::new(budgets.prediction));
@@ -264,13 +589,12 @@ impl PredictionEngine {
     fn _handle_budget_violation(&self, violation: BudgetViolation) -> Result<Vec<CostEstimate>> {
         match violation.action {
             TimeoutAction::PartialResults => {
-                if self.verbose {
-                    eprintln!(
-                        "⚠️  Budget exceeded: {:?} ({}ms budget, {}ms elapsed)",
-                        violation.violation_type, violation.budget_value, violation.actual_value
-                    );
-                    eprintln!("   Returning empty results");
-                }
+                tracing::info!(
+                    "Budget exceeded: {:?} ({}ms budget, {}ms elapsed); returning empty results",
+                    violation.violation_type,
+                    violation.budget_value,
+                    violation.actual_value
+                );
                 Ok(Vec::new())
             }
             TimeoutAction::Error => Err(CostPilotError::new(
@@ -301,11 +625,13 @@ impl PredictionEngine {
     ) -> Result<Vec<CostEstimate>> {
         match violation.action {
             TimeoutAction::PartialResults => {
-                if self.verbose {
-                    eprintln!("⚠️  Budget exceeded: {:?} ({}ms budget, {}ms elapsed)",
-                        violation.violation_type, violation.budget_value, violation.actual_value);
-                    eprintln!("   Returning {} partial results", partial.len());
-                }
+                tracing::info!(
+                    "Budget exceeded: {:?} ({}ms budget, {}ms elapsed); returning {} partial results",
+                    violation.violation_type,
+                    violation.budget_value,
+                    violation.actual_value,
+                    partial.len()
+                );
                 Ok(partial)
             }
             TimeoutAction::Error => {
@@ -329,6 +655,14 @@ impl PredictionEngine {
 
     /// Static prediction (no heuristics) - Free edition method
     pub fn predict_static(changes: &[ResourceChange]) -> Result<Vec<CostEstimate>> {
+        Self::predict_static_with(changes, &ClusterPricing::default())
+    }
+
+    /// Static prediction, pricing Kubernetes workloads with `pricing`
+    pub fn predict_static_with(
+        changes: &[ResourceChange],
+        pricing: &ClusterPricing,
+    ) -> Result<Vec<CostEstimate>> {
         let mut estimates = Vec::new();
 
         for change in changes {
@@ -344,7 +678,12 @@ impl PredictionEngine {
                 ChangeAction::Delete | ChangeAction::NoOp => false,
             };
 
-            if action_applies {
+            if !action_applies {
+                continue;
+            }
+            if let Some(estimate) = pricing.estimate(change) {
+                estimates.push(estimate);
+            } else {
                 estimates.push(CostEstimate {
                     resource_id: change.resource_id.clone(),
                     monthly_cost,
@@ -357,6 +696,15 @@ impl PredictionEngine {
                     breakdown: None,
                     hourly: None,
                     daily: None,
+                    provenance: Some(EstimateProvenance {
+                        heuristic_id: "free_static".to_string(),
+                        pricing_version: None,
+                        assumptions: vec![
+                            "Free edition rates: $150/month per aws_instance, other types unpriced"
+                                .to_string(),
+                        ],
+                        steps: vec![flat_rate_step(1, &change.resource_type, monthly_cost)],
+                    }),
                 });
             }
         }
@@ -366,16 +714,23 @@ impl PredictionEngine {
 
     /// Predict cost for a single resource
     fn predict_resource(&self, change: &ResourceChange) -> Result<Option<CostEstimate>> {
+        // Workloads are priced by their share of the cluster's nodes
+        if let Some(estimate) = self.cluster_pricing.estimate(change) {
+            return Ok(Some(estimate));
+        }
+
+        let containers = &self.heuristics.compute.containers;
+
         // Free edition static costs for ground truth testing
         let monthly_cost = match change.resource_type.as_str() {
-            "aws_instance" => 150.0,       // Free edition static cost for EC2 instances
-            "aws_db_instance" => 0.0,      // Free edition static cost for RDS instances
-            "aws_dynamodb_table" => 20.0,  // dummy for DynamoDB
-            "aws_nat_gateway" => 30.0,     // dummy for NAT Gateway
-            "aws_lb" | "aws_alb" => 25.0,  // dummy for Load Balancer
-            "aws_s3_bucket" => 5.0,        // dummy for S3
-            "aws_lambda_function" => 10.0, // dummy for Lambda
-            "aws_eks_cluster" => 70.0,     // dummy for EKS
+            "aws_instance" => STATIC_EC2_MONTHLY, // Free edition static cost for EC2 instances
+            "aws_db_instance" => 0.0,             // Free edition static cost for RDS instances
+            "aws_dynamodb_table" => 20.0,         // dummy for DynamoDB
+            "aws_nat_gateway" => 30.0,            // dummy for NAT Gateway
+            "aws_lb" | "aws_alb" => 25.0,         // dummy for Load Balancer
+            "aws_s3_bucket" => 5.0,               // dummy for S3
+            "aws_lambda_function" => 10.0,        // dummy for Lambda
+            "aws_eks_cluster" => containers.eks_control_plane_hourly * HOURS_PER_MONTH,
             "aws_elasticache_cluster" => 40.0, // dummy for ElastiCache
             "aws_cloudfront_distribution" => 15.0, // dummy for CloudFront
             _ => {
@@ -389,39 +744,262 @@ impl PredictionEngine {
             }
         };
 
+        let base_rate = monthly_cost;
+
+        // Dedicated tenancy, licenses and EBS optimization price on top of
+        // the instance rate
+        let ec2_rate = match change.resource_type.as_str() {
+            "aws_instance" => Ec2Configuration::from_change(change)
+                .filter(|config| config.has_adjustments())
+                .map(|config| {
+                    Ec2Rate::new(
+                        &config,
+                        base_rate / HOURS_PER_MONTH,
+                        &self.heuristics.compute.ec2_options,
+                    )
+                }),
+            _ => None,
+        };
+        let monthly_cost = ec2_rate
+            .as_ref()
+            .map_or(monthly_cost, |rate| rate.hourly() * HOURS_PER_MONTH);
+
+        // Profiled traffic or autoscaling bands price a table in its billing
+        // mode
+        let dynamodb_rate = match change.resource_type.as_str() {
+            "aws_dynamodb_table" => self.dynamodb_rate(change),
+            _ => None,
+        };
+        let monthly_cost = dynamodb_rate
+            .as_ref()
+            .map_or(monthly_cost, |rate| rate.monthly(HOURS_PER_MONTH));
+
+        // Multi-AZ, provisioned IOPS and storage autoscaling price on top of
+        // the instance class's rate; Serverless v2 clusters bill capacity
+        // instead
+        let config = change.new_config.as_ref().or(change.old_config.as_ref());
+        let rds_rate = match (change.resource_type.as_str(), config) {
+            ("aws_db_instance", Some(config)) => {
+                Some(RdsConfiguration::from_config(config)).filter(|rds| rds.has_adjustments())
+            }
+            ("aws_rds_cluster", Some(config)) => {
+                Some(RdsConfiguration::from_config(config)).filter(|rds| rds.acu_range.is_some())
+            }
+            _ => None,
+        }
+        .map(|rds| {
+            let instance_monthly = rds
+                .instance_cost(&self.heuristics.database.rds)
+                .map_or(INFERRED_INSTANCE_MONTHLY, |cost| cost.monthly);
+            RdsRate::new(
+                &rds,
+                instance_monthly / HOURS_PER_MONTH,
+                &self.heuristics.database.rds,
+                &self.heuristics.database.rds_options,
+            )
+        });
+        let monthly_cost = rds_rate
+            .as_ref()
+            .map_or(monthly_cost, |rate| rate.monthly(HOURS_PER_MONTH));
+
+        // Managed nodes are priced as EC2 instances, Fargate per task
+        let node_group_rate = match (change.resource_type.as_str(), config) {
+            ("aws_eks_node_group", Some(config)) => {
+                let group = NodeGroupConfiguration::from_config(config);
+                let node_monthly = self
+                    .heuristics
+                    .compute
+                    .ec2
+                    .get(&group.node.instance_type)
+                    .map(|cost| cost.monthly)
+                    .unwrap_or_else(|| {
+                        ColdStartInference::new(&self.heuristics.cold_start_defaults)
+                            .estimate_ec2_cost(&group.node.instance_type)
+                    });
+                Some(NodeGroupRate::new(
+                    &group,
+                    node_monthly / HOURS_PER_MONTH,
+                    &self.heuristics.compute.ec2_options,
+                ))
+            }
+            _ => None,
+        };
+        let fargate = match (change.resource_type.as_str(), config) {
+            ("aws_eks_fargate_profile", _) => Some(FargateConfiguration::from_fargate_profile(
+                change,
+                &self.usage_profile,
+                containers,
+            )),
+            ("aws_ecs_service", Some(config)) if is_fargate_service(config) => Some(
+                FargateConfiguration::from_ecs_service(config, &self.task_definitions, containers),
+            ),
+            _ => None,
+        };
+        let fargate_rate = fargate.map(|config| FargateRate::new(&config, containers));
+        let cloudwatch_rate = CloudWatchRate::from_change(
+            change,
+            &self.usage_profile,
+            &self.heuristics.monitoring.cloudwatch,
+        );
+        let monthly_cost = match (&node_group_rate, &fargate_rate, &cloudwatch_rate) {
+            (Some(rate), _, _) => rate.monthly(HOURS_PER_MONTH),
+            (_, Some(rate), _) => rate.monthly(HOURS_PER_MONTH),
+            (_, _, Some(rate)) => rate.monthly(),
+            _ => monthly_cost,
+        };
+
         let cost_delta = match change.action {
             ChangeAction::Delete => 0.0, // Delete operations result in zero ongoing cost
             _ => monthly_cost,
         };
-        let cold_start_used = !matches!(
-            change.resource_type.as_str(),
-            "aws_instance"
-                | "aws_db_instance"
-                | "aws_dynamodb_table"
-                | "aws_nat_gateway"
-                | "aws_lb"
-                | "aws_alb"
-                | "aws_s3_bucket"
-                | "aws_lambda_function"
-                | "aws_eks_cluster"
-                | "aws_elasticache_cluster"
-                | "aws_cloudfront_distribution"
-                | "aws_ecs_service"
-        );
+        let cold_start_used =
+            !PRICED_RESOURCE_TYPES.contains(&change.resource_type.as_str()) && rds_rate.is_none();
         let confidence = calculate_confidence(change, cold_start_used, &change.resource_type);
 
         let range_factor = self.heuristics.prediction_intervals.range_factor;
         let interval = monthly_cost * range_factor;
+        let mut interval_low = if cost_delta >= 0.0 {
+            (cost_delta - interval).max(0.0)
+        } else {
+            cost_delta - interval
+        };
+        let mut interval_high = cost_delta + interval;
+        let scaling_range = dynamodb_rate
+            .as_ref()
+            .filter(|rate| rate.is_range())
+            .map(|rate| rate.monthly_range(HOURS_PER_MONTH))
+            .or_else(|| {
+                rds_rate
+                    .as_ref()
+                    .filter(|rate| rate.is_range())
+                    .map(|rate| rate.monthly_range(HOURS_PER_MONTH))
+            })
+            .or_else(|| {
+                node_group_rate
+                    .as_ref()
+                    .filter(|rate| rate.is_range())
+                    .map(|rate| rate.monthly_range(HOURS_PER_MONTH))
+            });
+        if let Some((low, high)) = scaling_range {
+            if change.action != ChangeAction::Delete {
+                interval_low = interval_low.min(low);
+                interval_high = interval_high.max(high);
+            }
+        }
+
+        let mut assumptions = Vec::new();
+        if cold_start_used {
+            assumptions.push(format!(
+                "No rate for {}; priced at the $10.00/month default",
+                change.resource_type
+            ));
+        }
+        if let Some(rate) = &ec2_rate {
+            assumptions.push(format!(
+                "{} is adjusted for its planned tenancy, license and EBS optimization",
+                rate.instance_type
+            ));
+        }
+        if let Some(rate) = &dynamodb_rate {
+            assumptions.push(if rate.is_range() {
+                "Interval covers provisioned capacity across the autoscaling range".to_string()
+            } else {
+                format!(
+                    "Priced in {} billing mode from the usage profile",
+                    rate.billing_mode.as_str()
+                )
+            });
+        }
+        if let Some(rate) = &rds_rate {
+            assumptions.push(if rate.is_range() {
+                "Priced at allocated storage and minimum capacity; the interval covers storage autoscaling and Serverless v2 scaling".to_string()
+            } else {
+                format!(
+                    "{} is adjusted for its planned Multi-AZ deployment and provisioned IOPS",
+                    rate.instance_class
+                )
+            });
+        }
+        if let Some(rate) = &node_group_rate {
+            assumptions.push(format!(
+                "Each {} node is priced as an EC2 instance{}",
+                rate.node.instance_type,
+                if rate.is_range() {
+                    "; the interval covers the group's scaling range"
+                } else {
+                    ""
+                }
+            ));
+        }
+        if let Some(rate) = &fargate_rate {
+            assumptions.push(format!(
+                "{} Fargate tasks run all month at {} vCPU and {} GB each",
+                rate.tasks, rate.task.vcpu, rate.task.memory_gb
+            ));
+        }
+        if let Some(rate) = &cloudwatch_rate {
+            assumptions.extend(rate.notes.iter().cloned());
+        }
+        assumptions.push(format!(
+            "Prediction interval is ±{:.0}% of the monthly rate",
+            range_factor * 100.0
+        ));
+
+        let mut steps = match (&node_group_rate, &fargate_rate, &cloudwatch_rate) {
+            // A node group is its nodes' EC2 step times the group size
+            (Some(rate), _, _) => vec![
+                ec2_calculation_step(1, &rate.node, HOURS_PER_MONTH),
+                node_group_calculation_step(2, rate, HOURS_PER_MONTH),
+            ],
+            (_, Some(rate), _) => vec![fargate_calculation_step(1, rate, HOURS_PER_MONTH)],
+            (_, _, Some(rate)) => vec![cloudwatch_calculation_step(1, &change.resource_type, rate)],
+            _ if change.resource_type == "aws_eks_cluster" => vec![eks_control_plane_step(
+                1,
+                containers.eks_control_plane_hourly,
+                HOURS_PER_MONTH,
+            )],
+            // The RDS step prices the instance class itself
+            _ if rds_rate.is_some() => Vec::new(),
+            _ => vec![flat_rate_step(1, &change.resource_type, base_rate)],
+        };
+        if let Some(rate) = &ec2_rate {
+            steps.push(ec2_calculation_step(2, rate, HOURS_PER_MONTH));
+        }
+        if let Some(rate) = &dynamodb_rate {
+            steps.push(dynamodb_calculation_step(2, rate, HOURS_PER_MONTH));
+        }
+        if let Some(rate) = &rds_rate {
+            steps.push(rds_calculation_step(steps.len() + 1, rate, HOURS_PER_MONTH));
+        }
+        steps.push(action_step(
+            steps.len() + 1,
+            &change.action,
+            monthly_cost,
+            cost_delta,
+        ));
+        steps.push(interval_step(
+            steps.len() + 1,
+            cost_delta,
+            range_factor,
+            interval_low,
+            interval_high,
+        ));
+        let provenance = EstimateProvenance {
+            heuristic_id: if cold_start_used {
+                "static:default".to_string()
+            } else {
+                format!("static:{}", change.resource_type)
+            },
+            pricing_version: Some(self.heuristics.version.clone()),
+            assumptions,
+            steps,
+        };
 
         Ok(Some(CostEstimate {
             resource_id: change.resource_id.clone(),
             monthly_cost: cost_delta,
-            prediction_interval_low: if cost_delta >= 0.0 {
-                (cost_delta - interval).max(0.0)
-            } else {
-                cost_delta - interval
-            },
-            prediction_interval_high: cost_delta + interval,
+            prediction_interval_low: interval_low,
+            prediction_interval_high: interval_high,
             confidence_score: confidence,
             heuristic_reference: Some(format!("v{}", self.heuristics.version)),
             cold_start_inference: cold_start_used,
@@ -429,9 +1007,29 @@ impl PredictionEngine {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: Some(provenance),
         }))
     }
 
+    /// DynamoDB rate for a table with profiled usage or autoscaling; `None`
+    /// keeps the static table rate
+    fn dynamodb_rate(&self, change: &ResourceChange) -> Option<DynamoDbRate> {
+        let config = DynamoDbConfiguration::from_config(change.new_config.as_ref()?)
+            .with_autoscaling(&self.dynamodb_autoscaling);
+        let usage = DynamoDbUsage::for_change(&self.usage_profile, change);
+        let autoscaled = config.autoscaling.read.is_some() || config.autoscaling.write.is_some();
+        if !usage.is_profiled() && !autoscaled {
+            return None;
+        }
+        Some(DynamoDbRate::new(
+            &config,
+            usage,
+            &self.heuristics.database.dynamodb,
+            &self.heuristics.cold_start_defaults,
+            HOURS_PER_MONTH,
+        ))
+    }
+
     /// Get heuristics version
     pub fn heuristics_version(&self) -> &str {
         &self.heuristics.version
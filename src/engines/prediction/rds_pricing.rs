@@ -0,0 +1,329 @@
+// RDS pricing detail: Multi-AZ, provisioned IOPS, storage autoscaling and
+// Aurora Serverless v2 capacity
+//
+// A database's instance rate is only part of its bill. `multi_az = true`
+// runs a standby with its own storage, gp3/io1/io2 storage bills provisioned
+// IOPS, and two settings make the cost a range rather than a number:
+// `max_allocated_storage` lets storage grow past `allocated_storage`, and a
+// Serverless v2 cluster scales between its minimum and maximum ACUs. Rates
+// come from `database.rds` and `database.rds_options` in the heuristics.
+
+use crate::engines::prediction::plan_block;
+use crate::engines::prediction::prediction_engine::{InstanceCost, RdsCost, RdsOptions};
+use serde_json::Value;
+
+/// Storage (GB) assumed when `allocated_storage` is not set
+const DEFAULT_ALLOCATED_STORAGE_GB: f64 = 20.0;
+
+/// Monthly instance rate inferred for a class missing from the heuristics
+pub const INFERRED_INSTANCE_MONTHLY: f64 = 50.0;
+
+/// Pricing-relevant attributes of an `aws_db_instance` or Aurora cluster
+#[derive(Debug, Clone, PartialEq)]
+pub struct RdsConfiguration {
+    pub engine: String,
+    pub instance_class: String,
+    pub multi_az: bool,
+    pub storage_type: String,
+    pub allocated_storage_gb: f64,
+
+    /// Storage autoscaling limit, when above `allocated_storage`
+    pub max_allocated_storage_gb: Option<f64>,
+
+    pub iops: Option<f64>,
+
+    /// Serverless v2 (minimum, maximum) ACUs
+    pub acu_range: Option<(f64, f64)>,
+}
+
+impl RdsConfiguration {
+    /// Read from planned instance or cluster attributes
+    pub fn from_config(config: &Value) -> Self {
+        let str_attr = |key: &str| config.get(key).and_then(|v| v.as_str());
+        let num_attr = |key: &str| config.get(key).and_then(|v| v.as_f64());
+
+        let iops = num_attr("iops").filter(|&iops| iops > 0.0);
+        // Terraform defaults to io1 when IOPS are set without a storage type
+        let storage_type = str_attr("storage_type")
+            .unwrap_or(if iops.is_some() { "io1" } else { "gp2" })
+            .to_ascii_lowercase();
+
+        let scaling = plan_block(config, "serverlessv2_scaling_configuration");
+        let acu_range = scaling.and_then(|s| {
+            let min = s.get("min_capacity").and_then(|v| v.as_f64())?;
+            let max = s.get("max_capacity").and_then(|v| v.as_f64())?;
+            Some((min, max.max(min)))
+        });
+
+        // Aurora storage grows with the data and isn't declared in the plan
+        let default_storage_gb = match acu_range {
+            Some(_) => 0.0,
+            None => DEFAULT_ALLOCATED_STORAGE_GB,
+        };
+        let allocated_storage_gb = num_attr("allocated_storage").unwrap_or(default_storage_gb);
+        let max_allocated_storage_gb =
+            num_attr("max_allocated_storage").filter(|&max| max > allocated_storage_gb);
+
+        Self {
+            engine: str_attr("engine").unwrap_or("mysql").to_string(),
+            instance_class: str_attr("instance_class")
+                .unwrap_or(if acu_range.is_some() {
+                    "db.serverless"
+                } else {
+                    "db.t3.micro"
+                })
+                .to_string(),
+            multi_az: config
+                .get("multi_az")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            storage_type,
+            allocated_storage_gb,
+            max_allocated_storage_gb,
+            iops,
+            acu_range,
+        }
+    }
+
+    /// Whether anything differs from a single-AZ instance on fixed storage
+    pub fn has_adjustments(&self) -> bool {
+        self.multi_az
+            || self.iops.is_some()
+            || self.max_allocated_storage_gb.is_some()
+            || self.acu_range.is_some()
+    }
+
+    /// Whether the instance table to price from is the PostgreSQL one
+    /// (`postgres`, `aurora-postgresql`)
+    pub fn is_postgres(&self) -> bool {
+        self.engine.contains("postgres")
+    }
+
+    /// The instance class's rate in the engine's instance table
+    pub fn instance_cost<'a>(&self, rds: &'a RdsCost) -> Option<&'a InstanceCost> {
+        let instances = if self.is_postgres() {
+            &rds.postgres
+        } else {
+            &rds.mysql
+        };
+        instances.get(&self.instance_class)
+    }
+}
+
+/// Monthly cost of a database, split into the components billed
+#[derive(Debug, Clone, PartialEq)]
+pub struct RdsRate {
+    pub engine: String,
+    pub instance_class: String,
+
+    /// Single-AZ instance rate (zero for Serverless v2)
+    pub instance_hourly: f64,
+
+    /// Multiplier on instance, storage and IOPS (1.0 when single-AZ)
+    pub multi_az_multiplier: f64,
+
+    pub storage_type: String,
+    pub storage_per_gb: f64,
+    pub storage_gb: f64,
+    pub max_storage_gb: Option<f64>,
+
+    /// Provisioned IOPS billed (above any included with the storage type)
+    pub billed_iops: f64,
+    pub iops_per_month: f64,
+
+    pub acu_range: Option<(f64, f64)>,
+    pub acu_hourly: f64,
+}
+
+impl RdsRate {
+    /// Plain single-AZ instance with no storage
+    pub fn on_demand(engine: &str, instance_class: &str, instance_hourly: f64) -> Self {
+        Self {
+            engine: engine.to_string(),
+            instance_class: instance_class.to_string(),
+            instance_hourly,
+            multi_az_multiplier: 1.0,
+            storage_type: "gp2".to_string(),
+            storage_per_gb: 0.0,
+            storage_gb: 0.0,
+            max_storage_gb: None,
+            billed_iops: 0.0,
+            iops_per_month: 0.0,
+            acu_range: None,
+            acu_hourly: 0.0,
+        }
+    }
+
+    /// Rate for a configured database whose instance rate is
+    /// `instance_hourly`
+    pub fn new(
+        config: &RdsConfiguration,
+        instance_hourly: f64,
+        rds: &RdsCost,
+        options: &RdsOptions,
+    ) -> Self {
+        let storage_per_gb = match config.storage_type.as_str() {
+            "gp2" => rds.storage_gp2_per_gb,
+            "gp3" => rds.storage_gp3_per_gb,
+            other => options
+                .storage_per_gb
+                .get(other)
+                .copied()
+                .unwrap_or(rds.storage_gp2_per_gb),
+        };
+        let included_iops = match config.storage_type.as_str() {
+            "gp3" => options.gp3_included_iops,
+            _ => 0.0,
+        };
+        let iops_per_month = options
+            .iops_per_month
+            .get(&config.storage_type)
+            .copied()
+            .unwrap_or(0.0);
+        let billed_iops = if iops_per_month > 0.0 {
+            (config.iops.unwrap_or(0.0) - included_iops).max(0.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            engine: config.engine.clone(),
+            instance_class: config.instance_class.clone(),
+            instance_hourly: if config.acu_range.is_some() {
+                0.0
+            } else {
+                instance_hourly
+            },
+            multi_az_multiplier: if config.multi_az {
+                options.multi_az_multiplier
+            } else {
+                1.0
+            },
+            storage_type: config.storage_type.clone(),
+            storage_per_gb,
+            storage_gb: config.allocated_storage_gb,
+            max_storage_gb: config.max_allocated_storage_gb,
+            billed_iops,
+            iops_per_month,
+            acu_range: config.acu_range,
+            acu_hourly: options.aurora_acu_hourly,
+        }
+    }
+
+    pub fn is_multi_az(&self) -> bool {
+        self.multi_az_multiplier != 1.0
+    }
+
+    /// Single-AZ instance cost
+    pub fn instance_monthly(&self, hours: f64) -> f64 {
+        self.instance_hourly * hours
+    }
+
+    /// Single-AZ cost of `storage_gb` of storage
+    pub fn storage_monthly(&self, storage_gb: f64) -> f64 {
+        storage_gb * self.storage_per_gb
+    }
+
+    /// Single-AZ provisioned IOPS cost
+    pub fn iops_monthly(&self) -> f64 {
+        self.billed_iops * self.iops_per_month
+    }
+
+    /// Serverless v2 capacity cost at `acu` ACUs
+    pub fn acu_monthly(&self, acu: f64, hours: f64) -> f64 {
+        acu * self.acu_hourly * hours
+    }
+
+    /// Monthly cost range: allocated storage and minimum capacity at the
+    /// low end, the autoscaling limit and maximum capacity at the high end
+    pub fn monthly_range(&self, hours: f64) -> (f64, f64) {
+        let fixed = self.instance_monthly(hours) + self.iops_monthly();
+        let max_storage = self.max_storage_gb.unwrap_or(self.storage_gb);
+        let (min_acu, max_acu) = self.acu_range.unwrap_or((0.0, 0.0));
+        (
+            (fixed + self.storage_monthly(self.storage_gb)) * self.multi_az_multiplier
+                + self.acu_monthly(min_acu, hours),
+            (fixed + self.storage_monthly(max_storage)) * self.multi_az_multiplier
+                + self.acu_monthly(max_acu, hours),
+        )
+    }
+
+    /// Cost billed today: allocated storage and minimum capacity
+    pub fn monthly(&self, hours: f64) -> f64 {
+        self.monthly_range(hours).0
+    }
+
+    /// Whether storage autoscaling or serverless capacity make the cost a
+    /// range
+    pub fn is_range(&self) -> bool {
+        self.max_storage_gb.is_some() || self.acu_range.is_some_and(|(min, max)| max > min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::prediction::MinimalHeuristics;
+    use serde_json::json;
+
+    #[test]
+    fn test_multi_az_gp3_iops_and_autoscaling() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let config = RdsConfiguration::from_config(&json!({
+            "engine": "postgres",
+            "instance_class": "db.t3.medium",
+            "multi_az": true,
+            "storage_type": "gp3",
+            "allocated_storage": 100,
+            "max_allocated_storage": 500,
+            "iops": 12000
+        }));
+        assert!(config.is_postgres());
+        assert_eq!(config.max_allocated_storage_gb, Some(500.0));
+
+        let rate = RdsRate::new(
+            &config,
+            0.072,
+            &heuristics.database.rds,
+            &heuristics.database.rds_options,
+        );
+        // 9000 IOPS above the 3000 included with gp3
+        assert_eq!(rate.billed_iops, 9000.0);
+        assert!(rate.is_multi_az() && rate.is_range());
+
+        let (low, high) = rate.monthly_range(730.0);
+        let fixed = 0.072 * 730.0 + 9000.0 * 0.02;
+        assert!((low - (fixed + 100.0 * 0.115) * 2.0).abs() < 1e-9);
+        assert!((high - (fixed + 500.0 * 0.115) * 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_io1_default_and_serverless_range() {
+        let heuristics = MinimalHeuristics::to_cost_heuristics();
+        let config = RdsConfiguration::from_config(&json!({ "iops": 1000 }));
+        assert_eq!(config.storage_type, "io1");
+        let rate = RdsRate::new(
+            &config,
+            0.017,
+            &heuristics.database.rds,
+            &heuristics.database.rds_options,
+        );
+        assert_eq!(rate.iops_monthly(), 100.0);
+        assert!(!rate.is_range());
+
+        let config = RdsConfiguration::from_config(&json!({
+            "engine": "aurora-postgresql",
+            "serverlessv2_scaling_configuration": [{ "min_capacity": 0.5, "max_capacity": 8 }]
+        }));
+        assert_eq!(config.instance_class, "db.serverless");
+        let rate = RdsRate::new(
+            &config,
+            0.0,
+            &heuristics.database.rds,
+            &heuristics.database.rds_options,
+        );
+        let (low, high) = rate.monthly_range(730.0);
+        assert!((low - 0.5 * 0.12 * 730.0).abs() < 1e-9);
+        assert!((high - 8.0 * 0.12 * 730.0).abs() < 1e-9);
+    }
+}
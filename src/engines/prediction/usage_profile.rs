@@ -0,0 +1,153 @@
+// Usage profiles for usage-priced resources
+//
+// Some resources bill by what they do rather than what they are: an
+// on-demand DynamoDB table by the requests it serves, a log group by the data
+// it ingests. The plan can't say how much that is, so the `usage` section of
+// costpilot.yaml does, keyed by resource type (every resource of the type) or
+// by resource address (one resource, taking precedence). Quantities a profile
+// leaves out fall back to the estimator's documented defaults.
+
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use crate::engines::shared::models::ResourceChange;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Monthly usage quantities by resource type or address (`usage` in
+/// costpilot.yaml)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UsageProfile {
+    entries: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl UsageProfile {
+    /// Load `usage` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `usage` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `usage` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let usage: Self = match root.get("usage").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid usage section: {}", e))
+            })?,
+            None => Self::default(),
+        };
+
+        if let Some(problem) = usage.validate().first() {
+            return Err(CostPilotError::validation_error(problem.clone())
+                .with_hint("Usage quantities are monthly amounts, e.g. usage.aws_dynamodb_table.monthly_read_request_units"));
+        }
+
+        Ok(usage)
+    }
+
+    /// Describe unusable quantities; empty if every one can be priced
+    pub fn validate(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .flat_map(|(target, quantities)| {
+                quantities
+                    .iter()
+                    .filter(|(_, value)| !(value.is_finite() && **value >= 0.0))
+                    .map(move |(key, value)| {
+                        format!(
+                            "usage.{}.{} must not be negative, got {}",
+                            target, key, value
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Set a quantity for a resource type or address
+    pub fn with(mut self, target: &str, key: &str, value: f64) -> Self {
+        self.entries
+            .entry(target.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Quantity `key` for a resource: its address entry, else its type entry
+    pub fn get(&self, change: &ResourceChange, key: &str) -> Option<f64> {
+        [change.resource_id.as_str(), change.resource_type.as_str()]
+            .iter()
+            .find_map(|target| self.entries.get(*target)?.get(key).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+
+    fn table(address: &str) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(address)
+            .resource_type("aws_dynamodb_table")
+            .action(ChangeAction::Create)
+            .build()
+    }
+
+    #[test]
+    fn test_address_overrides_type() {
+        let usage = UsageProfile::from_yaml(
+            r#"
+usage:
+  aws_dynamodb_table:
+    monthly_read_request_units: 1000000
+    storage_gb: 5
+  aws_dynamodb_table.orders:
+    monthly_read_request_units: 90000000
+"#,
+        )
+        .unwrap();
+
+        let orders = table("aws_dynamodb_table.orders");
+        assert_eq!(
+            usage.get(&orders, "monthly_read_request_units"),
+            Some(90_000_000.0)
+        );
+        assert_eq!(usage.get(&orders, "storage_gb"), Some(5.0));
+        assert_eq!(
+            usage.get(
+                &table("aws_dynamodb_table.users"),
+                "monthly_read_request_units"
+            ),
+            Some(1_000_000.0)
+        );
+        assert_eq!(usage.get(&orders, "monthly_write_request_units"), None);
+    }
+
+    #[test]
+    fn test_rejects_negative_quantities() {
+        let err = UsageProfile::from_yaml("usage:\n  aws_dynamodb_table:\n    storage_gb: -1\n")
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("usage.aws_dynamodb_table.storage_gb"));
+        assert!(UsageProfile::from_yaml("kubernetes: {}\n")
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,819 @@
+// Multi-page HTML rendering for the report bundle
+
+use crate::edition::TrialWatermark;
+use crate::engines::explain::explain_engine::Explanation;
+use crate::engines::grouping::ComprehensiveReport;
+use crate::engines::mapping::{DependencyGraph, InteractiveHtmlGenerator};
+use crate::engines::policy::PolicyResult;
+use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange, Severity};
+use crate::engines::slo::{SloResult, SloStatus};
+use crate::errors::CostPilotError;
+use crate::i18n::{self, Locale};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Pages in navigation order: file name and title message key
+const PAGES: [(&str, &str); 6] = [
+    ("index.html", "report.page.summary"),
+    ("costs.html", "report.page.costs"),
+    ("map.html", "report.page.map"),
+    ("trends.html", "report.page.trends"),
+    ("policy.html", "report.page.policy"),
+    ("detections.html", "report.page.detections"),
+];
+
+/// Resources listed on the summary page
+const TOP_RESOURCES: usize = 5;
+
+/// Trend chart, or why there is none
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrendSection {
+    /// Inline SVG chart of the snapshot history
+    Chart(String),
+    /// Explanation shown instead of a chart
+    Unavailable(String),
+}
+
+/// Everything the report shows, borrowed from the scan that produced it
+pub struct ReportData<'a> {
+    pub title: String,
+    /// RFC 3339 generation time
+    pub generated_at: String,
+    pub changes: &'a [ResourceChange],
+    pub estimates: &'a [CostEstimate],
+    pub detections: &'a [Detection],
+    pub explanations: &'a [Explanation],
+    pub graph: Option<&'a DependencyGraph>,
+    pub grouping: Option<&'a ComprehensiveReport>,
+    pub policy_result: Option<&'a PolicyResult>,
+    pub slo_result: Option<&'a SloResult>,
+    pub trend: TrendSection,
+    /// Monthly totals of the recorded trend snapshots, oldest first
+    pub trend_totals: Vec<f64>,
+    pub total_monthly: f64,
+    pub trial: Option<&'a TrialWatermark>,
+    /// Language of headings and labels
+    pub locale: Locale,
+}
+
+impl ReportData<'_> {
+    fn t(&self, key: &'static str) -> &'static str {
+        i18n::text(self.locale, key)
+    }
+
+    fn tf(&self, key: &'static str, args: &[(&str, &str)]) -> String {
+        i18n::format(self.locale, key, args)
+    }
+}
+
+/// One rendered page
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportPage {
+    pub file_name: &'static str,
+    pub html: String,
+}
+
+/// Renders `ReportData` into the linked pages of the bundle
+pub struct HtmlReportBundle;
+
+impl HtmlReportBundle {
+    /// Render all pages
+    pub fn render(data: &ReportData) -> Result<Vec<ReportPage>, CostPilotError> {
+        let page = |file_name: &'static str, body: String| ReportPage {
+            file_name,
+            html: Self::layout(data, file_name, &body),
+        };
+
+        Ok(vec![
+            page("index.html", Self::summary(data)),
+            page("costs.html", Self::costs(data)),
+            Self::map(data)?,
+            page("trends.html", Self::trends(data)),
+            page("policy.html", Self::policy(data)),
+            page("detections.html", Self::detections(data)),
+        ])
+    }
+
+    /// Render all pages into `dir`, creating it if needed; returns the index page
+    pub fn write(data: &ReportData, dir: &Path) -> Result<PathBuf, CostPilotError> {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            CostPilotError::io_error(format!(
+                "Failed to create report directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        for page in Self::render(data)? {
+            let path = dir.join(page.file_name);
+            std::fs::write(&path, page.html).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+            })?;
+        }
+        Ok(dir.join("index.html"))
+    }
+
+    fn summary(data: &ReportData) -> String {
+        let mut html = String::new();
+        html.push_str("<div class=\"cards\">");
+        card(
+            &mut html,
+            data.t("report.estimated_monthly_cost"),
+            &money(data.total_monthly),
+        );
+        card(
+            &mut html,
+            data.t("report.resources_changed"),
+            &data.changes.len().to_string(),
+        );
+        card(
+            &mut html,
+            data.t("report.page.detections"),
+            &data.detections.len().to_string(),
+        );
+        card(
+            &mut html,
+            data.t("report.policy"),
+            data.t(match data.policy_result {
+                Some(p) if p.passed && p.violations.is_empty() => "report.passed",
+                Some(_) => "report.failed",
+                None => "report.not_evaluated",
+            }),
+        );
+        card(
+            &mut html,
+            data.t("report.cost_slos"),
+            data.t(match data.slo_result {
+                Some(s) if s.passed => "report.passed",
+                Some(_) => "report.breached",
+                None => "report.not_evaluated",
+            }),
+        );
+        html.push_str("</div>");
+
+        let mut by_severity = [0usize; 4];
+        for detection in data.detections {
+            by_severity[severity_rank(&detection.severity)] += 1;
+        }
+        let _ = write!(
+            html,
+            "<h2>{}</h2><p>",
+            data.t("report.detections_by_severity")
+        );
+        for (severity, label) in [
+            (Severity::Critical, "report.severity.critical"),
+            (Severity::High, "report.severity.high"),
+            (Severity::Medium, "report.severity.medium"),
+            (Severity::Low, "report.severity.low"),
+        ] {
+            let _ = write!(
+                html,
+                "<span class=\"badge {}\">{} {}</span> ",
+                severity_class(&severity),
+                data.t(label),
+                by_severity[severity_rank(&severity)]
+            );
+        }
+        html.push_str("</p>");
+
+        let _ = write!(html, "<h2>{}</h2>", data.t("report.largest_costs"));
+        let ranked = ranked_estimates(data.estimates);
+        if ranked.is_empty() {
+            muted(&mut html, data.t("report.no_estimates"));
+        } else {
+            Self::cost_table(&mut html, data, &ranked[..ranked.len().min(TOP_RESOURCES)]);
+            let _ = write!(
+                html,
+                "<p><a href=\"costs.html\">{}</a></p>",
+                data.t("report.full_breakdown")
+            );
+        }
+        html
+    }
+
+    fn costs(data: &ReportData) -> String {
+        let mut html = format!("<h2>{}</h2>", data.t("report.resources"));
+        let ranked = ranked_estimates(data.estimates);
+        if ranked.is_empty() {
+            muted(&mut html, data.t("report.no_estimates"));
+        } else {
+            Self::cost_table(&mut html, data, &ranked);
+        }
+
+        if let Some(grouping) = data.grouping {
+            let groups = [
+                (
+                    "report.by_module",
+                    grouping
+                        .module_groups
+                        .iter()
+                        .map(|g| (g.module_path.as_str(), g.resource_count, g.monthly_cost))
+                        .collect::<Vec<_>>(),
+                ),
+                (
+                    "report.by_service",
+                    grouping
+                        .service_groups
+                        .iter()
+                        .map(|g| (g.service_name.as_str(), g.resource_count, g.monthly_cost))
+                        .collect(),
+                ),
+                (
+                    "report.by_environment",
+                    grouping
+                        .environment_groups
+                        .iter()
+                        .map(|g| (g.environment.as_str(), g.resource_count, g.monthly_cost))
+                        .collect(),
+                ),
+            ];
+            for (heading, rows) in groups {
+                if rows.is_empty() {
+                    continue;
+                }
+                let _ = write!(html, "<h2>{}</h2><table>", data.t(heading));
+                header_row(
+                    &mut html,
+                    data,
+                    &[
+                        "report.col.group",
+                        "report.resources",
+                        "report.col.monthly_cost",
+                    ],
+                    true,
+                );
+                for (name, count, cost) in rows {
+                    let _ = write!(
+                        html,
+                        "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+                        escape(name),
+                        count,
+                        money(cost),
+                        bar(cost, data.total_monthly)
+                    );
+                }
+                html.push_str("</table>");
+            }
+        }
+        html
+    }
+
+    fn cost_table(html: &mut String, data: &ReportData, estimates: &[&CostEstimate]) {
+        html.push_str("<table>");
+        header_row(
+            html,
+            data,
+            &[
+                "report.col.resource",
+                "report.col.type",
+                "report.col.change",
+                "report.col.monthly_cost",
+                "report.col.range",
+                "report.col.confidence",
+            ],
+            true,
+        );
+        for estimate in estimates {
+            let change = data
+                .changes
+                .iter()
+                .find(|c| c.resource_id == estimate.resource_id);
+            let _ = write!(
+                html,
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{} – {}</td><td class=\"num\">{:.0}%</td><td>{}</td></tr>",
+                escape(&estimate.resource_id),
+                escape(change.map_or("", |c| c.resource_type.as_str())),
+                change.map_or(String::new(), |c| format!("{:?}", c.action)),
+                money(estimate.monthly_cost),
+                money(estimate.prediction_interval_low),
+                money(estimate.prediction_interval_high),
+                estimate.confidence_score * 100.0,
+                bar(estimate.monthly_cost, data.total_monthly)
+            );
+        }
+        html.push_str("</table>");
+    }
+
+    /// The interactive graph viewer is a complete page of its own; it gets a
+    /// link back to the report instead of the shared layout
+    fn map(data: &ReportData) -> Result<ReportPage, CostPilotError> {
+        let html = match data.graph {
+            Some(graph) if !graph.nodes.is_empty() => {
+                let viewer = InteractiveHtmlGenerator::new().generate(
+                    graph,
+                    &format!("{} – {}", data.title, data.t("report.page.map")),
+                )?;
+                viewer.replacen(
+                    "<header>",
+                    &format!(
+                        "<header>\n        <a href=\"index.html\" style=\"color: white\">&larr; {}</a>",
+                        data.t("report.back")
+                    ),
+                    1,
+                )
+            }
+            _ => {
+                let mut body = String::new();
+                muted(&mut body, data.t("report.no_dependencies"));
+                Self::layout(data, "map.html", &body)
+            }
+        };
+        Ok(ReportPage {
+            file_name: "map.html",
+            html,
+        })
+    }
+
+    fn trends(data: &ReportData) -> String {
+        match &data.trend {
+            TrendSection::Chart(svg) => format!("<div class=\"chart\">{}</div>", svg),
+            TrendSection::Unavailable(reason) => {
+                format!("<p class=\"muted\">{}</p>", escape(reason))
+            }
+        }
+    }
+
+    fn policy(data: &ReportData) -> String {
+        let mut html = format!("<h2>{}</h2>", data.t("report.policy"));
+        match data.policy_result {
+            None => {
+                let _ = write!(
+                    html,
+                    "<p class=\"muted\">{}</p>",
+                    data.tf("report.no_policy", &[("flag", "<code>--policy</code>")])
+                );
+            }
+            Some(policy) => {
+                if policy.violations.is_empty() {
+                    let _ = write!(
+                        html,
+                        "<p><span class=\"badge ok\">{}</span></p>",
+                        data.t("report.passed")
+                    );
+                } else {
+                    html.push_str("<table>");
+                    header_row(
+                        &mut html,
+                        data,
+                        &[
+                            "report.policy",
+                            "report.col.severity",
+                            "report.col.resource",
+                            "report.col.message",
+                            "report.col.actual",
+                            "report.col.expected",
+                        ],
+                        false,
+                    );
+                    for v in &policy.violations {
+                        let _ = write!(
+                            html,
+                            "<tr><td>{}</td><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                            escape(&v.policy_name),
+                            escape(&v.severity),
+                            escape(&v.resource_id),
+                            escape(&v.message),
+                            escape(&v.actual_value),
+                            escape(&v.expected_value)
+                        );
+                    }
+                    html.push_str("</table>");
+                }
+                list(&mut html, data.t("report.warnings"), &policy.warnings);
+                list(
+                    &mut html,
+                    data.t("report.applied_exemptions"),
+                    &policy.applied_exemptions,
+                );
+            }
+        }
+
+        let _ = write!(html, "<h2>{}</h2>", data.t("report.cost_slos"));
+        match data.slo_result {
+            None => {
+                let _ = write!(
+                    html,
+                    "<p class=\"muted\">{}</p>",
+                    data.tf(
+                        "report.no_slos",
+                        &[("path", "<code>.costpilot/slo.json</code>")]
+                    )
+                );
+            }
+            Some(slo) => {
+                let _ = write!(html, "<p>{}</p>", escape(&slo.message));
+                html.push_str("<table>");
+                header_row(
+                    &mut html,
+                    data,
+                    &[
+                        "report.col.slo",
+                        "report.col.status",
+                        "report.col.actual",
+                        "report.col.threshold",
+                        "report.col.used",
+                    ],
+                    false,
+                );
+                for e in &slo.evaluations {
+                    let (class, label) = match e.status {
+                        SloStatus::Pass => ("ok", "report.slo.pass"),
+                        SloStatus::Warning => ("medium", "report.slo.warning"),
+                        SloStatus::Violation => ("critical", "report.slo.violation"),
+                        SloStatus::NoData => ("low", "report.slo.no_data"),
+                    };
+                    let _ = write!(
+                        html,
+                        "<tr><td>{}</td><td><span class=\"badge {}\">{}</span></td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td></tr>",
+                        escape(&e.slo_name),
+                        class,
+                        data.t(label),
+                        money(e.actual_value),
+                        money(e.threshold_value),
+                        e.threshold_usage_percent
+                    );
+                }
+                html.push_str("</table>");
+            }
+        }
+        html
+    }
+
+    fn detections(data: &ReportData) -> String {
+        if data.detections.is_empty() {
+            let mut html = String::new();
+            muted(&mut html, data.t("report.no_detections"));
+            return html;
+        }
+        let mut detections: Vec<&Detection> = data.detections.iter().collect();
+        detections.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then_with(|| a.resource_id.cmp(&b.resource_id))
+        });
+
+        let mut html = String::new();
+        for detection in detections {
+            let _ = write!(
+                html,
+                "<section class=\"detection\"><h3><span class=\"badge {}\">{}</span> <code>{}</code></h3><p>{}</p>",
+                severity_class(&detection.severity),
+                data.t(severity_key(&detection.severity)),
+                escape(&detection.resource_id),
+                escape(&detection.message)
+            );
+            if let Some(cost) = detection.estimated_cost {
+                let _ = write!(
+                    html,
+                    "<p>{}</p>",
+                    data.tf("report.estimated_impact", &[("amount", &money(cost))])
+                );
+            }
+            if let Some(explanation) = data
+                .explanations
+                .iter()
+                .find(|e| e.resource_id == detection.resource_id)
+            {
+                let _ = write!(
+                    html,
+                    "<p><strong>{}</strong> {}</p><p><strong>{}</strong> {}</p>",
+                    data.t("report.why"),
+                    escape(&explanation.summary),
+                    data.t("report.root_cause"),
+                    escape(&explanation.root_cause.primary_cause)
+                );
+                list(
+                    &mut html,
+                    data.t("report.recommendations"),
+                    &explanation.recommendations,
+                );
+                list(
+                    &mut html,
+                    data.t("report.assumptions"),
+                    &explanation.assumptions,
+                );
+            }
+            if let Some(snippet) = &detection.fix_snippet {
+                let _ = write!(html, "<pre>{}</pre>", escape(snippet));
+            }
+            html.push_str("</section>");
+        }
+        html
+    }
+
+    /// Shared page shell: inline styles, navigation and footer
+    fn layout(data: &ReportData, current: &str, body: &str) -> String {
+        let title = PAGES
+            .iter()
+            .find(|(file, _)| *file == current)
+            .map_or("", |(_, title)| data.t(title));
+
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"UTF-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n<title>{} – {}</title>\n<style>{}</style>\n</head>\n<body>\n<header><h1>{}</h1><nav>",
+            data.locale.code(),
+            escape(title),
+            escape(&data.title),
+            STYLES,
+            escape(&data.title)
+        );
+        for (file, label) in PAGES {
+            let class = if file == current {
+                " class=\"active\""
+            } else {
+                ""
+            };
+            let _ = write!(
+                html,
+                "<a href=\"{}\"{}>{}</a>",
+                file,
+                class,
+                escape(data.t(label))
+            );
+        }
+        html.push_str("</nav></header>\n<main>\n");
+        if let Some(trial) = data.trial {
+            let _ = write!(
+                html,
+                "<p class=\"trial\">{}</p>",
+                data.tf(
+                    "report.trial",
+                    &[("days", &trial.days_remaining.to_string())]
+                )
+            );
+        }
+        let _ = write!(html, "<h2>{}</h2>\n{}\n</main>\n", escape(title), body);
+        let _ = write!(
+            html,
+            "<footer>{}</footer>\n</body>\n</html>\n",
+            data.tf(
+                "report.footer",
+                &[
+                    ("version", env!("CARGO_PKG_VERSION")),
+                    ("time", &escape(&data.generated_at)),
+                ]
+            )
+        );
+        html
+    }
+}
+
+const STYLES: &str = r#"
+body { margin: 0; font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Roboto, "Helvetica Neue", Arial, sans-serif; color: #1f2933; background: #f5f7fa; }
+header { background: #667eea; color: white; padding: 16px 24px; }
+header h1 { margin: 0 0 8px; font-size: 20px; }
+nav a { color: white; margin-right: 16px; text-decoration: none; opacity: 0.85; }
+nav a.active { opacity: 1; font-weight: 600; border-bottom: 2px solid white; }
+main { max-width: 1100px; margin: 0 auto; padding: 16px 24px; }
+h2 { font-size: 18px; margin-top: 28px; }
+table { width: 100%; border-collapse: collapse; background: white; font-size: 14px; }
+th, td { text-align: left; padding: 6px 10px; border-bottom: 1px solid #e4e7eb; vertical-align: top; }
+td.num { text-align: right; white-space: nowrap; }
+.cards { display: flex; flex-wrap: wrap; gap: 12px; }
+.card { background: white; border-radius: 6px; padding: 12px 16px; min-width: 160px; box-shadow: 0 1px 2px rgba(0,0,0,0.08); }
+.card .label { font-size: 12px; color: #616e7c; }
+.card .value { font-size: 22px; font-weight: 600; }
+.bar { height: 8px; background: #667eea; border-radius: 4px; min-width: 1px; }
+.badge { display: inline-block; padding: 2px 8px; border-radius: 10px; font-size: 12px; color: white; background: #9aa5b1; }
+.badge.critical { background: #d64545; } .badge.high { background: #e8833a; }
+.badge.medium { background: #d9a800; } .badge.ok { background: #3f9142; }
+.detection { background: white; border-radius: 6px; padding: 8px 16px; margin-bottom: 12px; }
+.detection h3 { font-size: 15px; }
+pre { background: #1f2933; color: #e4e7eb; padding: 10px; border-radius: 4px; overflow-x: auto; }
+.chart { background: white; padding: 12px; border-radius: 6px; overflow-x: auto; }
+.muted { color: #616e7c; }
+.trial { background: #fff3c4; padding: 8px 12px; border-radius: 4px; }
+footer { text-align: center; color: #9aa5b1; font-size: 12px; padding: 24px; }
+"#;
+
+fn card(html: &mut String, label: &str, value: &str) {
+    let _ = write!(
+        html,
+        "<div class=\"card\"><div class=\"label\">{}</div><div class=\"value\">{}</div></div>",
+        escape(label),
+        escape(value)
+    );
+}
+
+fn muted(html: &mut String, text: &str) {
+    let _ = write!(html, "<p class=\"muted\">{}</p>", escape(text));
+}
+
+/// Table header row, optionally with an empty column for the share bar
+fn header_row(html: &mut String, data: &ReportData, keys: &[&'static str], bar_column: bool) {
+    html.push_str("<tr>");
+    for key in keys {
+        let _ = write!(html, "<th>{}</th>", escape(data.t(key)));
+    }
+    if bar_column {
+        html.push_str("<th></th>");
+    }
+    html.push_str("</tr>");
+}
+
+fn list(html: &mut String, heading: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    let _ = write!(html, "<p><strong>{}:</strong></p><ul>", escape(heading));
+    for item in items {
+        let _ = write!(html, "<li>{}</li>", escape(item));
+    }
+    html.push_str("</ul>");
+}
+
+/// Horizontal bar sized by the share of `total`
+fn bar(value: f64, total: f64) -> String {
+    let percent = if total > 0.0 {
+        (value / total * 100.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    };
+    format!(
+        "<div class=\"bar\" style=\"width: {:.1}%\" title=\"{:.1}% of total\"></div>",
+        percent, percent
+    )
+}
+
+pub(super) fn ranked_estimates(estimates: &[CostEstimate]) -> Vec<&CostEstimate> {
+    let mut ranked: Vec<&CostEstimate> = estimates.iter().collect();
+    ranked.sort_by(|a, b| {
+        b.monthly_cost
+            .total_cmp(&a.monthly_cost)
+            .then_with(|| a.resource_id.cmp(&b.resource_id))
+    });
+    ranked
+}
+
+pub(super) fn severity_rank(severity: &Severity) -> usize {
+    match severity {
+        Severity::Critical => 0,
+        Severity::High => 1,
+        Severity::Medium => 2,
+        Severity::Low => 3,
+    }
+}
+
+fn severity_key(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "report.severity.critical",
+        Severity::High => "report.severity.high",
+        Severity::Medium => "report.severity.medium",
+        Severity::Low => "report.severity.low",
+    }
+}
+
+fn severity_class(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "critical",
+        Severity::High => "high",
+        Severity::Medium => "medium",
+        Severity::Low => "low",
+    }
+}
+
+pub(super) fn money(value: f64) -> String {
+    format!("${:.2}", value)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+    use std::collections::HashMap;
+
+    fn change(id: &str) -> ResourceChange {
+        ResourceChange {
+            resource_id: id.to_string(),
+            resource_type: "aws_instance".to_string(),
+            action: ChangeAction::Create,
+            module_path: None,
+            old_config: None,
+            new_config: None,
+            tags: HashMap::new(),
+            monthly_cost: None,
+            config: None,
+            cost_impact: None,
+        }
+    }
+
+    fn estimate(id: &str, cost: f64) -> CostEstimate {
+        CostEstimate {
+            resource_id: id.to_string(),
+            monthly_cost: cost,
+            prediction_interval_low: cost * 0.8,
+            prediction_interval_high: cost * 1.2,
+            confidence_score: 0.9,
+            heuristic_reference: None,
+            cold_start_inference: false,
+            one_time: None,
+            breakdown: None,
+            hourly: None,
+            daily: None,
+            provenance: None,
+        }
+    }
+
+    fn data<'a>(
+        changes: &'a [ResourceChange],
+        estimates: &'a [CostEstimate],
+        detections: &'a [Detection],
+    ) -> ReportData<'a> {
+        ReportData {
+            title: "CostPilot report: plan.json".to_string(),
+            generated_at: "2025-03-01T00:00:00Z".to_string(),
+            changes,
+            estimates,
+            detections,
+            explanations: &[],
+            graph: None,
+            grouping: None,
+            policy_result: None,
+            slo_result: None,
+            trend: TrendSection::Unavailable("No trend snapshots found".to_string()),
+            trend_totals: Vec::new(),
+            total_monthly: estimates.iter().map(|e| e.monthly_cost).sum(),
+            trial: None,
+            locale: Locale::En,
+        }
+    }
+
+    #[test]
+    fn test_bundle_pages_are_linked_and_self_contained() {
+        let changes = [change("aws_instance.web"), change("aws_instance.<db>")];
+        let estimates = [
+            estimate("aws_instance.web", 50.0),
+            estimate("aws_instance.<db>", 150.0),
+        ];
+        let detections = [Detection {
+            rule_id: "oversized".to_string(),
+            severity: Severity::High,
+            resource_id: "aws_instance.<db>".to_string(),
+            regression_type: Default::default(),
+            severity_score: 70,
+            message: "Instance is oversized".to_string(),
+            fix_snippet: Some("instance_type = \"t3.large\"".to_string()),
+            estimated_cost: Some(100.0),
+        }];
+
+        let pages = HtmlReportBundle::render(&data(&changes, &estimates, &detections)).unwrap();
+        let names: Vec<&str> = pages.iter().map(|p| p.file_name).collect();
+        assert_eq!(names, PAGES.map(|(file, _)| file).to_vec());
+
+        for page in &pages {
+            assert!(page.html.starts_with("<!DOCTYPE html>"));
+            for (file, _) in PAGES {
+                assert!(page.html.contains(&format!("href=\"{}\"", file)));
+            }
+            // Everything is inlined: no external scripts, styles or images
+            assert!(!page.html.contains("src=\"http"));
+            assert!(!page.html.contains("<link"));
+        }
+
+        let index = &pages[0].html;
+        assert!(index.contains("$200.00"));
+        assert!(index.contains("High 1"));
+        // Most expensive first, with markup escaped
+        let db = index.find("aws_instance.&lt;db&gt;").unwrap();
+        assert!(db < index.find("aws_instance.web").unwrap());
+
+        let detections_page = &pages[5].html;
+        assert!(detections_page.contains("Instance is oversized"));
+        assert!(detections_page.contains("instance_type = &quot;t3.large&quot;"));
+        assert!(pages[3].html.contains("No trend snapshots found"));
+    }
+
+    #[test]
+    fn test_headings_follow_locale() {
+        let mut report = data(&[], &[], &[]);
+        report.locale = Locale::De;
+        let pages = HtmlReportBundle::render(&report).unwrap();
+        assert!(pages[0].html.contains("<html lang=\"de\">"));
+        assert!(pages[0].html.contains("<h2>Zusammenfassung</h2>"));
+        assert!(pages[0]
+            .html
+            .contains("Es wurden keine Ressourcen geschätzt."));
+        // Data is never translated
+        assert!(pages[0].html.contains("CostPilot report: plan.json"));
+    }
+
+    #[test]
+    fn test_write_creates_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("out/report");
+        let index = HtmlReportBundle::write(&data(&[], &[], &[]), &dir).unwrap();
+        assert_eq!(index, dir.join("index.html"));
+        for (file, _) in PAGES {
+            assert!(dir.join(file).exists());
+        }
+        let summary = std::fs::read_to_string(index).unwrap();
+        assert!(summary.contains("No resources were estimated."));
+    }
+}
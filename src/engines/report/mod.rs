@@ -0,0 +1,13 @@
+// Static HTML report bundle combining every engine's results
+//
+// `costpilot report --html <dir>` writes a small set of linked pages that
+// need nothing but a browser: styles, charts and the dependency viewer are
+// all inlined, so the directory can be uploaded as a CI artifact or zipped
+// and shared as-is. `--pdf` adds a two-page executive summary of the same
+// data for monthly FinOps reviews.
+
+pub mod html_bundle;
+pub mod pdf_summary;
+
+pub use html_bundle::{HtmlReportBundle, ReportData, ReportPage, TrendSection};
+pub use pdf_summary::PdfSummary;
@@ -0,0 +1,615 @@
+// Two-page executive cost summary as PDF
+//
+// The PDF is written directly rather than through a layout engine: two fixed
+// pages of text, rules and a sparkline only need the standard Helvetica
+// fonts (which every viewer provides), so the output stays dependency-free
+// and byte-for-byte reproducible for the same scan. Those fonts only cover
+// Latin text, so the summary is always in English whatever the report locale.
+
+use super::html_bundle::{money, ranked_estimates, severity_rank, ReportData};
+use crate::engines::slo::SloStatus;
+use crate::errors::CostPilotError;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// A4 portrait, in points
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+const MARGIN: f64 = 56.0;
+
+/// Cost drivers listed on the first page
+const TOP_DRIVERS: usize = 5;
+
+/// Resource ids longer than this are shortened to fit the driver table
+const MAX_ID_CHARS: usize = 52;
+
+/// Lines of 10pt text longer than this run past the margin
+const MAX_LINE_CHARS: usize = 90;
+
+/// Renders the executive summary for FinOps reviews
+pub struct PdfSummary;
+
+impl PdfSummary {
+    /// Render the summary as a complete PDF document
+    pub fn render(data: &ReportData) -> Vec<u8> {
+        let pages = [Self::overview(data), Self::compliance(data)];
+        let count = pages.len();
+        let pages: Vec<String> = pages
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut page)| {
+                Self::footer(&mut page, data, i + 1, count);
+                page.content
+            })
+            .collect();
+        assemble(&data.title, &pages)
+    }
+
+    /// Render the summary into `path`, creating its parent directory if needed
+    pub fn write(data: &ReportData, path: &Path) -> Result<(), CostPilotError> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                CostPilotError::io_error(format!(
+                    "Failed to create directory {}: {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
+        }
+        std::fs::write(path, Self::render(data)).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+
+    /// Page 1: headline cost, change since the last snapshot, trend and drivers
+    fn overview(data: &ReportData) -> Page {
+        let mut page = Page::default();
+        let mut y = PAGE_HEIGHT - MARGIN;
+
+        page.text(MARGIN, y, Font::Bold, 20.0, "Executive Cost Summary");
+        y -= 20.0;
+        page.text(
+            MARGIN,
+            y,
+            Font::Regular,
+            10.0,
+            &shorten(&data.title, MAX_LINE_CHARS),
+        );
+        y -= 14.0;
+        page.text(
+            MARGIN,
+            y,
+            Font::Regular,
+            10.0,
+            &format!("Generated {}", data.generated_at),
+        );
+        y -= 16.0;
+        page.rule(y);
+
+        y -= 36.0;
+        page.text(
+            MARGIN,
+            y + 18.0,
+            Font::Regular,
+            10.0,
+            "Estimated monthly cost",
+        );
+        page.text(
+            MARGIN,
+            y - 8.0,
+            Font::Bold,
+            26.0,
+            &money(data.total_monthly),
+        );
+
+        let x = PAGE_WIDTH / 2.0;
+        page.text(
+            x,
+            y + 18.0,
+            Font::Regular,
+            10.0,
+            "Change since last snapshot",
+        );
+        match data.trend_totals.last() {
+            Some(&previous) => {
+                page.text(
+                    x,
+                    y - 8.0,
+                    Font::Bold,
+                    26.0,
+                    &signed_money(data.total_monthly - previous),
+                );
+                page.text(
+                    x,
+                    y - 24.0,
+                    Font::Regular,
+                    9.0,
+                    &format!(
+                        "{} previously{}",
+                        money(previous),
+                        percent_change(previous, data.total_monthly)
+                    ),
+                );
+            }
+            None => page.text(x, y - 8.0, Font::Regular, 11.0, "No snapshot recorded yet"),
+        }
+
+        y -= 64.0;
+        page.text(MARGIN, y, Font::Bold, 13.0, "Monthly cost trend");
+        y -= 12.0;
+        let mut points = data.trend_totals.clone();
+        points.push(data.total_monthly);
+        page.sparkline(MARGIN, y - 90.0, PAGE_WIDTH - 2.0 * MARGIN, 90.0, &points);
+        y -= 104.0;
+        page.text(
+            MARGIN,
+            y,
+            Font::Regular,
+            9.0,
+            &if points.len() < 2 {
+                "Record snapshots with `costpilot trend snapshot` to chart the trend.".to_string()
+            } else {
+                format!(
+                    "{} snapshots and this plan; low {}, high {}",
+                    points.len() - 1,
+                    money(points.iter().copied().fold(f64::INFINITY, f64::min)),
+                    money(points.iter().copied().fold(f64::NEG_INFINITY, f64::max))
+                )
+            },
+        );
+
+        y -= 32.0;
+        page.text(MARGIN, y, Font::Bold, 13.0, "Top cost drivers");
+        y -= 18.0;
+        let cost_x = PAGE_WIDTH - MARGIN - 150.0;
+        let share_x = PAGE_WIDTH - MARGIN - 50.0;
+        page.text(MARGIN, y, Font::Bold, 10.0, "Resource");
+        page.text(cost_x, y, Font::Bold, 10.0, "Monthly cost");
+        page.text(share_x, y, Font::Bold, 10.0, "Share");
+        y -= 6.0;
+        page.rule(y);
+
+        let ranked = ranked_estimates(data.estimates);
+        if ranked.is_empty() {
+            y -= 16.0;
+            page.text(
+                MARGIN,
+                y,
+                Font::Regular,
+                10.0,
+                "No resources were estimated.",
+            );
+        }
+        for estimate in ranked.iter().take(TOP_DRIVERS) {
+            y -= 16.0;
+            page.text(
+                MARGIN,
+                y,
+                Font::Regular,
+                10.0,
+                &shorten(&estimate.resource_id, MAX_ID_CHARS),
+            );
+            page.text(
+                cost_x,
+                y,
+                Font::Regular,
+                10.0,
+                &money(estimate.monthly_cost),
+            );
+            let share = if data.total_monthly > 0.0 {
+                estimate.monthly_cost / data.total_monthly * 100.0
+            } else {
+                0.0
+            };
+            page.text(share_x, y, Font::Regular, 10.0, &format!("{:.1}%", share));
+        }
+        if ranked.len() > TOP_DRIVERS {
+            y -= 16.0;
+            let rest: f64 = ranked[TOP_DRIVERS..].iter().map(|e| e.monthly_cost).sum();
+            page.text(
+                MARGIN,
+                y,
+                Font::Regular,
+                10.0,
+                &format!("{} other resources", ranked.len() - TOP_DRIVERS),
+            );
+            page.text(cost_x, y, Font::Regular, 10.0, &money(rest));
+        }
+        page
+    }
+
+    /// Page 2: policy, SLO and detection counts
+    fn compliance(data: &ReportData) -> Page {
+        let mut page = Page::default();
+        let mut y = PAGE_HEIGHT - MARGIN;
+
+        page.text(MARGIN, y, Font::Bold, 20.0, "Findings and Compliance");
+        y -= 16.0;
+        page.rule(y);
+
+        let mut rows: Vec<(&str, String)> = Vec::new();
+        match data.policy_result {
+            Some(policy) => {
+                rows.push(("Policy violations", policy.violations.len().to_string()));
+                rows.push(("Policy warnings", policy.warnings.len().to_string()));
+                rows.push((
+                    "Applied exemptions",
+                    policy.applied_exemptions.len().to_string(),
+                ));
+            }
+            None => rows.push(("Policy", "Not evaluated".to_string())),
+        }
+        match data.slo_result {
+            Some(slo) => {
+                let count = |status: SloStatus| {
+                    slo.evaluations
+                        .iter()
+                        .filter(|e| e.status == status)
+                        .count()
+                        .to_string()
+                };
+                rows.push(("SLO violations", count(SloStatus::Violation)));
+                rows.push(("SLO warnings", count(SloStatus::Warning)));
+            }
+            None => rows.push(("Cost SLOs", "Not evaluated".to_string())),
+        }
+        let mut by_severity = [0usize; 4];
+        for detection in data.detections {
+            by_severity[severity_rank(&detection.severity)] += 1;
+        }
+        for (label, count) in [
+            "Critical detections",
+            "High detections",
+            "Medium detections",
+            "Low detections",
+        ]
+        .into_iter()
+        .zip(by_severity)
+        {
+            rows.push((label, count.to_string()));
+        }
+
+        y -= 30.0;
+        for (label, value) in rows {
+            page.text(MARGIN, y, Font::Regular, 11.0, label);
+            page.text(PAGE_WIDTH / 2.0, y, Font::Bold, 11.0, &value);
+            y -= 18.0;
+        }
+
+        let savings: f64 = data
+            .detections
+            .iter()
+            .filter_map(|d| d.estimated_cost)
+            .sum();
+        if savings > 0.0 {
+            y -= 8.0;
+            page.text(
+                MARGIN,
+                y,
+                Font::Regular,
+                11.0,
+                "Cost at risk from detections",
+            );
+            page.text(
+                PAGE_WIDTH / 2.0,
+                y,
+                Font::Bold,
+                11.0,
+                &format!("{}/month", money(savings)),
+            );
+            y -= 18.0;
+        }
+
+        y -= 24.0;
+        page.text(MARGIN, y, Font::Bold, 13.0, "About these numbers");
+        for line in [
+            "Costs are estimated from the infrastructure plan with CostPilot's offline",
+            "heuristics; they are not billing data. Each estimate carries a prediction",
+            "interval and confidence score, listed in the HTML report.",
+            "The change since last snapshot compares this plan with the most recent",
+            "trend snapshot in the snapshot directory.",
+        ] {
+            y -= 14.0;
+            page.text(MARGIN, y, Font::Regular, 10.0, line);
+        }
+        page
+    }
+
+    fn footer(page: &mut Page, data: &ReportData, number: usize, count: usize) {
+        let y = MARGIN / 2.0;
+        let mut left = format!("CostPilot {}", env!("CARGO_PKG_VERSION"));
+        if let Some(trial) = data.trial {
+            let _ = write!(
+                left,
+                " - trial license ({} days remaining)",
+                trial.days_remaining
+            );
+        }
+        page.text(MARGIN, y, Font::Regular, 8.0, &left);
+        page.text(
+            PAGE_WIDTH - MARGIN - 50.0,
+            y,
+            Font::Regular,
+            8.0,
+            &format!("Page {} of {}", number, count),
+        );
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "/F1",
+            Font::Bold => "/F2",
+        }
+    }
+}
+
+/// Content stream of one page
+#[derive(Default)]
+struct Page {
+    content: String,
+}
+
+impl Page {
+    fn text(&mut self, x: f64, y: f64, font: Font, size: f64, text: &str) {
+        let _ = writeln!(
+            self.content,
+            "BT {} {} Tf {:.2} {:.2} Td ({}) Tj ET",
+            font.resource(),
+            size,
+            x,
+            y,
+            pdf_string(text)
+        );
+    }
+
+    /// Thin horizontal rule across the text width
+    fn rule(&mut self, y: f64) {
+        let _ = writeln!(
+            self.content,
+            "0.8 G 0.5 w {:.2} {:.2} m {:.2} {:.2} l S 0 G",
+            MARGIN,
+            y,
+            PAGE_WIDTH - MARGIN,
+            y
+        );
+    }
+
+    /// Framed line chart of `values` scaled to fit the box
+    fn sparkline(&mut self, x: f64, y: f64, width: f64, height: f64, values: &[f64]) {
+        let _ = writeln!(
+            self.content,
+            "0.85 G 0.5 w {:.2} {:.2} {:.2} {:.2} re S",
+            x, y, width, height
+        );
+        if values.len() < 2 {
+            let _ = writeln!(self.content, "0 G");
+            return;
+        }
+
+        let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let span = if high > low { high - low } else { 1.0 };
+        let pad = 8.0;
+        let point = |i: usize, value: f64| {
+            (
+                x + pad + (width - 2.0 * pad) * i as f64 / (values.len() - 1) as f64,
+                y + pad + (height - 2.0 * pad) * (value - low) / span,
+            )
+        };
+
+        // Accent colour of the HTML report (#667eea)
+        self.content.push_str("0.4 0.494 0.918 RG 1.5 w ");
+        for (i, &value) in values.iter().enumerate() {
+            let (px, py) = point(i, value);
+            let op = if i == 0 { "m" } else { "l" };
+            let _ = write!(self.content, "{:.2} {:.2} {} ", px, py, op);
+        }
+        self.content.push_str("S\n");
+
+        // Mark the current plan
+        let (px, py) = point(values.len() - 1, values[values.len() - 1]);
+        let _ = writeln!(
+            self.content,
+            "0.4 0.494 0.918 rg {:.2} {:.2} 4 4 re f 0 g 0 G",
+            px - 2.0,
+            py - 2.0
+        );
+    }
+}
+
+/// Write the objects, cross-reference table and trailer for `pages`
+fn assemble(title: &str, pages: &[String]) -> Vec<u8> {
+    // Fixed objects: 1 catalog, 2 page tree, 3-4 fonts, 5 info;
+    // each page then takes a page object and a content stream
+    let first_page = 6;
+    let kids: Vec<String> = (0..pages.len())
+        .map(|i| format!("{} 0 R", first_page + 2 * i))
+        .collect();
+
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        format!(
+            "<< /Title ({}) /Producer (CostPilot {}) >>",
+            pdf_string(title),
+            env!("CARGO_PKG_VERSION")
+        ),
+    ];
+    for (i, content) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            first_page + 2 * i + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+    }
+    let xref = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    pdf.into_bytes()
+}
+
+/// Escape a PDF literal string; characters outside printable ASCII become `?`
+/// so the byte offsets above stay exact
+fn pdf_string(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '(' | ')' | '\\' => format!("\\{}", c),
+            ' '..='~' => c.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+fn shorten(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut shortened: String = text.chars().take(limit - 3).collect();
+    shortened.push_str("...");
+    shortened
+}
+
+fn signed_money(value: f64) -> String {
+    if value < 0.0 {
+        format!("-{}", money(-value))
+    } else {
+        format!("+{}", money(value))
+    }
+}
+
+fn percent_change(previous: f64, current: f64) -> String {
+    if previous > 0.0 {
+        format!(" ({:+.1}%)", (current - previous) / previous * 100.0)
+    } else {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::report::TrendSection;
+    use crate::engines::shared::models::CostEstimate;
+
+    fn estimate(id: &str, cost: f64) -> CostEstimate {
+        CostEstimate {
+            resource_id: id.to_string(),
+            monthly_cost: cost,
+            prediction_interval_low: cost,
+            prediction_interval_high: cost,
+            confidence_score: 0.9,
+            heuristic_reference: None,
+            cold_start_inference: false,
+            one_time: None,
+            breakdown: None,
+            hourly: None,
+            daily: None,
+            provenance: None,
+        }
+    }
+
+    fn data(estimates: &[CostEstimate], trend_totals: Vec<f64>) -> ReportData<'_> {
+        ReportData {
+            title: "CostPilot report: plan (prod).json".to_string(),
+            generated_at: "2025-03-01T00:00:00Z".to_string(),
+            changes: &[],
+            estimates,
+            detections: &[],
+            explanations: &[],
+            graph: None,
+            grouping: None,
+            policy_result: None,
+            slo_result: None,
+            trend: TrendSection::Unavailable(String::new()),
+            trend_totals,
+            total_monthly: estimates.iter().map(|e| e.monthly_cost).sum(),
+            trial: None,
+            locale: crate::i18n::Locale::En,
+        }
+    }
+
+    #[test]
+    fn test_pdf_structure_is_valid() {
+        let estimates: Vec<CostEstimate> = (1..=7)
+            .map(|i| estimate(&format!("aws_instance.web_{}", i), i as f64 * 10.0))
+            .collect();
+        let pdf = PdfSummary::render(&data(&estimates, vec![200.0, 250.0]));
+        let text = String::from_utf8(pdf).unwrap();
+
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("/Count 2"));
+
+        // Every cross-reference entry points at its object
+        let xref_at: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .unwrap()
+            .lines()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(text[xref_at..].starts_with("xref\n"));
+        for (i, line) in text[xref_at..].lines().skip(3).take(9).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+
+        // Top five drivers, the rest rolled up, and the delta to the last snapshot
+        assert!(text.contains("(aws_instance.web_7) Tj"));
+        assert!(text.contains("(aws_instance.web_3) Tj"));
+        assert!(!text.contains("(aws_instance.web_2) Tj"));
+        assert!(text.contains("(2 other resources) Tj"));
+        assert!(text.contains("(+$30.00) Tj"));
+        assert!(text.contains("/Title (CostPilot report: plan \\(prod\\).json)"));
+    }
+
+    #[test]
+    fn test_summary_without_history() {
+        let text = String::from_utf8(PdfSummary::render(&data(&[], Vec::new()))).unwrap();
+        assert!(text.contains("(No snapshot recorded yet) Tj"));
+        assert!(text.contains("(No resources were estimated.) Tj"));
+        assert!(text.contains("(Policy) Tj"));
+
+        assert_eq!(signed_money(-12.5), "-$12.50");
+        assert_eq!(pdf_string("a(b)\\ é"), "a\\(b\\)\\\\ ?");
+    }
+}
@@ -0,0 +1,103 @@
+// Cooperative cancellation for long-running engine calls
+//
+// A `CancelToken` is a shared flag with an optional deadline. Engines check it
+// between units of work and return a `CANCELLED` error once it trips; work in
+// progress is never interrupted, so a cancelled call returns no partial result.
+
+use super::error_model::{CostPilotError, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+    deadline: Option<Instant>,
+}
+
+/// Shared cancellation flag; clones observe the same state
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    state: Arc<CancelState>,
+}
+
+impl CancelToken {
+    /// Token that only trips when `cancel` is called
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token that also trips once `deadline` has passed
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            state: Arc::new(CancelState {
+                cancelled: AtomicBool::new(false),
+                deadline: Some(deadline),
+            }),
+        }
+    }
+
+    /// Token that also trips `timeout` from now
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Relaxed)
+            || self
+                .state
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// `CANCELLED` error naming `operation` once the token has tripped
+    pub fn check(&self, operation: &str) -> Result<()> {
+        if !self.is_cancelled() {
+            return Ok(());
+        }
+        let reason = if self.state.cancelled.load(Ordering::Relaxed) {
+            "cancelled"
+        } else {
+            "deadline exceeded"
+        };
+        Err(CostPilotError::cancelled(format!(
+            "{} stopped: {}",
+            operation, reason
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_state() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert!(clone.check("scan").is_ok());
+
+        token.cancel();
+        assert!(clone.is_cancelled());
+        let err = clone.check("scan").unwrap_err();
+        assert_eq!(err.id, "CANCELLED");
+        assert!(err.message.contains("scan stopped: cancelled"));
+    }
+
+    #[test]
+    fn test_deadline_trips_token() {
+        let token = CancelToken::with_deadline(Instant::now());
+        assert!(token.is_cancelled());
+        assert!(token
+            .check("simulation")
+            .unwrap_err()
+            .message
+            .contains("deadline exceeded"));
+
+        assert!(!CancelToken::with_timeout(Duration::from_secs(3600)).is_cancelled());
+    }
+}
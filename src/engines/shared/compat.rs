@@ -0,0 +1,426 @@
+// Version skew between stored artifacts and the running binary
+//
+// Baselines, trend snapshots and policy lifecycle records outlive the
+// CostPilot build that wrote them. Writers stamp each document with the
+// producing version (`produced_by`) and its schema version; readers pass the
+// raw document through `upgrade`, which refuses documents from a newer
+// incompatible schema and runs the registered migration for every schema
+// version between the stored one and the current one before the typed
+// parse. A schema bump without a migration fails the chain test below
+// instead of surfacing later as a parse error on a user's machine.
+
+use super::error_model::{CostPilotError, ErrorCategory, Result};
+use super::json_schema::OUTPUT_SCHEMA_VERSION;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+
+/// Field recording the CostPilot version that wrote an artifact
+pub const PRODUCED_BY_FIELD: &str = "produced_by";
+
+/// Version of this build
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Long-lived artifact kinds with a versioned layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Artifact {
+    /// baselines.json and baseline candidates
+    Baselines,
+    /// Trend snapshots in .costpilot/snapshots
+    Snapshot,
+    /// Policy lifecycle records, including their policy history
+    PolicyRecord,
+}
+
+/// Upgrade of a document from one schema version to the next
+pub struct Migration {
+    pub from: &'static str,
+    pub to: &'static str,
+    pub description: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+static BASELINE_MIGRATIONS: [Migration; 1] = [Migration {
+    from: "0",
+    to: "1.0",
+    description: "Add the version field and empty module and service maps",
+    apply: baselines_unversioned,
+}];
+
+static SNAPSHOT_MIGRATIONS: [Migration; 3] = [
+    Migration {
+        from: "1.0.0",
+        to: "1.1.0",
+        description: "Default missing module and service breakdowns to empty",
+        apply: snapshot_breakdowns,
+    },
+    Migration {
+        from: "1.1.0",
+        to: "1.2.0",
+        description: "No snapshot layout change",
+        apply: unchanged,
+    },
+    Migration {
+        from: "1.2.0",
+        to: "1.3.0",
+        description: "Optional billed actuals",
+        apply: unchanged,
+    },
+];
+
+static POLICY_RECORD_MIGRATIONS: [Migration; 0] = [];
+
+impl Artifact {
+    pub const ALL: [Artifact; 3] = [
+        Artifact::Baselines,
+        Artifact::Snapshot,
+        Artifact::PolicyRecord,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Artifact::Baselines => "baselines",
+            Artifact::Snapshot => "snapshot",
+            Artifact::PolicyRecord => "policy lifecycle record",
+        }
+    }
+
+    /// Schema version written by this build
+    pub fn current_version(&self) -> &'static str {
+        match self {
+            Artifact::Baselines => "1.0",
+            Artifact::Snapshot => OUTPUT_SCHEMA_VERSION,
+            Artifact::PolicyRecord => "1.0",
+        }
+    }
+
+    /// Field holding the schema version
+    fn version_field(&self) -> &'static str {
+        match self {
+            Artifact::Baselines => "version",
+            Artifact::Snapshot | Artifact::PolicyRecord => "schema_version",
+        }
+    }
+
+    /// Schema version assumed when the document has none
+    fn unversioned(&self) -> &'static str {
+        match self {
+            Artifact::Baselines => "0",
+            Artifact::Snapshot => "1.0.0",
+            Artifact::PolicyRecord => "1.0",
+        }
+    }
+
+    pub fn migrations(&self) -> &'static [Migration] {
+        match self {
+            Artifact::Baselines => &BASELINE_MIGRATIONS,
+            Artifact::Snapshot => &SNAPSHOT_MIGRATIONS,
+            Artifact::PolicyRecord => &POLICY_RECORD_MIGRATIONS,
+        }
+    }
+}
+
+/// What was found when reading an artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compatibility {
+    pub artifact: Artifact,
+    /// Schema version the document was stored with
+    pub stored_version: String,
+    /// CostPilot version that wrote the document, when stamped
+    pub produced_by: Option<String>,
+    /// Migrations applied, as "from → to"
+    pub migrated: Vec<String>,
+}
+
+impl Compatibility {
+    /// Whether the document was written by a newer CostPilot than this one
+    pub fn from_newer_build(&self) -> bool {
+        self.produced_by
+            .as_deref()
+            .is_some_and(|v| compare_versions(v, ENGINE_VERSION) == Ordering::Greater)
+    }
+
+    /// Whether the stored layout is newer than the one this build writes
+    pub fn from_newer_schema(&self) -> bool {
+        compare_versions(&self.stored_version, self.artifact.current_version()) == Ordering::Greater
+    }
+
+    /// Warning to show the user, if any
+    pub fn warning(&self) -> Option<String> {
+        let producer = self.produced_by.as_deref().unwrap_or("an unknown version");
+        if self.from_newer_schema() || self.from_newer_build() {
+            Some(format!(
+                "{} was written by CostPilot {} (schema {}); this is CostPilot {} (schema {}). Fields it does not know are ignored and dropped when rewritten",
+                self.artifact.name(),
+                producer,
+                self.stored_version,
+                ENGINE_VERSION,
+                self.artifact.current_version()
+            ))
+        } else if !self.migrated.is_empty() {
+            Some(format!(
+                "{} written by CostPilot {} was migrated in memory ({}); it is rewritten in the current layout on the next save",
+                self.artifact.name(),
+                producer,
+                self.migrated.join(", ")
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Bring a raw document up to the current schema of `artifact`
+///
+/// Documents from a newer minor schema are accepted as they are; a newer
+/// major schema, or an older version without a migration, is an error.
+pub fn upgrade(artifact: Artifact, document: &mut Value) -> Result<Compatibility> {
+    let object = document.as_object_mut().ok_or_else(|| {
+        CostPilotError::new(
+            "COMPAT_003",
+            ErrorCategory::ParseError,
+            format!("{} is not a JSON object", artifact.name()),
+        )
+    })?;
+
+    let field = artifact.version_field();
+    let stored_version = object
+        .get(field)
+        .and_then(Value::as_str)
+        .unwrap_or(artifact.unversioned())
+        .to_string();
+    let mut compat = Compatibility {
+        artifact,
+        produced_by: object
+            .get(PRODUCED_BY_FIELD)
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        stored_version: stored_version.clone(),
+        migrated: Vec::new(),
+    };
+
+    let current = artifact.current_version();
+    if compare_versions(&stored_version, current) != Ordering::Less {
+        if major(&stored_version) != major(current) {
+            return Err(CostPilotError::new(
+                "COMPAT_001",
+                ErrorCategory::ValidationError,
+                format!(
+                    "{} uses schema {} (written by CostPilot {}), but this build reads schema {}",
+                    artifact.name(),
+                    stored_version,
+                    compat.produced_by.as_deref().unwrap_or("unknown"),
+                    current
+                ),
+            )
+            .with_hint("Upgrade CostPilot to the version that wrote this file"));
+        }
+        return Ok(compat);
+    }
+
+    let mut version = stored_version;
+    while version != current {
+        let migration = artifact
+            .migrations()
+            .iter()
+            .find(|m| m.from == version)
+            .ok_or_else(|| {
+                CostPilotError::new(
+                    "COMPAT_002",
+                    ErrorCategory::ValidationError,
+                    format!(
+                        "No migration for {} schema {} to {}",
+                        artifact.name(),
+                        version,
+                        current
+                    ),
+                )
+                .with_hint("Recreate the file with this version of CostPilot")
+            })?;
+        (migration.apply)(object);
+        object.insert(field.to_string(), Value::String(migration.to.to_string()));
+        compat
+            .migrated
+            .push(format!("{} → {}", migration.from, migration.to));
+        version = migration.to.to_string();
+    }
+    Ok(compat)
+}
+
+/// Parse an artifact, migrating it to the current schema first
+///
+/// Skew warnings are logged; the compatibility report is returned for
+/// callers that surface it themselves.
+pub fn read<T: DeserializeOwned>(artifact: Artifact, contents: &str) -> Result<(T, Compatibility)> {
+    let mut document: Value = serde_json::from_str(contents).map_err(|e| {
+        CostPilotError::new(
+            "COMPAT_003",
+            ErrorCategory::ParseError,
+            format!("Invalid {} JSON: {}", artifact.name(), e),
+        )
+    })?;
+    let compat = upgrade(artifact, &mut document)?;
+    if let Some(warning) = compat.warning() {
+        tracing::warn!("{}", warning);
+    }
+
+    let value = serde_json::from_value(document).map_err(|e| {
+        CostPilotError::new(
+            "COMPAT_003",
+            ErrorCategory::ParseError,
+            format!(
+                "{} written by CostPilot {} does not match schema {}: {}",
+                artifact.name(),
+                compat.produced_by.as_deref().unwrap_or("unknown"),
+                artifact.current_version(),
+                e
+            ),
+        )
+    })?;
+    Ok((value, compat))
+}
+
+/// Serialize an artifact stamped with this build's version and schema
+pub fn to_string_pretty<T: Serialize>(artifact: Artifact, value: &T) -> Result<String> {
+    let mut document = serde_json::to_value(value)
+        .map_err(|e| CostPilotError::serialization_error(e.to_string()))?;
+    if let Some(object) = document.as_object_mut() {
+        object.insert(
+            artifact.version_field().to_string(),
+            Value::String(artifact.current_version().to_string()),
+        );
+        object.insert(
+            PRODUCED_BY_FIELD.to_string(),
+            Value::String(ENGINE_VERSION.to_string()),
+        );
+    }
+    serde_json::to_string_pretty(&document)
+        .map_err(|e| CostPilotError::serialization_error(e.to_string()))
+}
+
+/// Compare dotted numeric versions; missing components count as zero
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.split('.')
+            .map(|part| {
+                part.chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect::<String>()
+                    .parse()
+                    .unwrap_or(0)
+            })
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            a.get(i)
+                .copied()
+                .unwrap_or(0)
+                .cmp(&b.get(i).copied().unwrap_or(0))
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or(version)
+}
+
+fn unchanged(_: &mut Map<String, Value>) {}
+
+/// Baselines written before the file was versioned could omit the maps
+fn baselines_unversioned(doc: &mut Map<String, Value>) {
+    for key in ["modules", "services"] {
+        doc.entry(key).or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+/// Snapshots written before versioning could omit or null the breakdowns
+fn snapshot_breakdowns(doc: &mut Map<String, Value>) {
+    for key in ["modules", "services"] {
+        if doc.get(key).and_then(Value::as_object).is_none() {
+            doc.insert(key.to_string(), Value::Object(Map::new()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::baselines::BaselinesConfig;
+    use crate::engines::trend::CostSnapshot;
+    use serde_json::json;
+
+    #[test]
+    fn test_migration_chains_reach_current_version() {
+        for artifact in Artifact::ALL {
+            let mut document = json!({});
+            upgrade(artifact, &mut document).unwrap();
+            if artifact.unversioned() != artifact.current_version() {
+                assert_eq!(
+                    document[artifact.version_field()],
+                    artifact.current_version(),
+                    "{}",
+                    artifact.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_legacy_snapshot_is_migrated() {
+        let legacy =
+            r#"{"id": "s1", "timestamp": "2024-01-01T00:00:00Z", "total_monthly_cost": 10.0}"#;
+        let (snapshot, compat): (CostSnapshot, _) = read(Artifact::Snapshot, legacy).unwrap();
+        assert_eq!(snapshot.schema_version, OUTPUT_SCHEMA_VERSION);
+        assert!(snapshot.modules.is_empty());
+        assert_eq!(compat.stored_version, "1.0.0");
+        assert_eq!(compat.migrated.len(), 3);
+        assert!(compat.warning().unwrap().contains("migrated"));
+    }
+
+    #[test]
+    fn test_unversioned_baselines_are_migrated() {
+        let legacy = r#"{"global": null}"#;
+        let (config, compat): (BaselinesConfig, _) = read(Artifact::Baselines, legacy).unwrap();
+        assert_eq!(config.version, "1.0");
+        assert_eq!(compat.migrated, vec!["0 → 1.0"]);
+    }
+
+    #[test]
+    fn test_newer_artifacts() {
+        let mut minor = json!({"version": "1.4", "produced_by": "9.1.0"});
+        let compat = upgrade(Artifact::Baselines, &mut minor).unwrap();
+        assert!(compat.from_newer_schema() && compat.from_newer_build());
+        assert!(compat.warning().unwrap().contains("CostPilot 9.1.0"));
+
+        let mut major = json!({"schema_version": "2.0.0", "produced_by": "9.1.0"});
+        let err = upgrade(Artifact::Snapshot, &mut major).unwrap_err();
+        assert_eq!(err.id, "COMPAT_001");
+        assert!(err.message.contains("9.1.0"));
+    }
+
+    #[test]
+    fn test_missing_migration_is_an_error() {
+        let mut document = json!({"schema_version": "0.9.0"});
+        let err = upgrade(Artifact::Snapshot, &mut document).unwrap_err();
+        assert_eq!(err.id, "COMPAT_002");
+    }
+
+    #[test]
+    fn test_stamp_round_trip() {
+        let json = to_string_pretty(Artifact::Baselines, &BaselinesConfig::new()).unwrap();
+        let (_, compat): (BaselinesConfig, _) = read(Artifact::Baselines, &json).unwrap();
+        assert_eq!(compat.produced_by.as_deref(), Some(ENGINE_VERSION));
+        assert!(compat.warning().is_none());
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.10.0", "1.9.3"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.1-rc1", "1.0.2"), Ordering::Less);
+    }
+}
@@ -1 +1,72 @@
 // Deterministic sort
+//
+// Orderings shared by every output that lists resources, so the same input
+// always renders in the same order regardless of hash-map iteration or the
+// order resources appear in a plan.
+
+use std::cmp::Ordering;
+
+/// Sort by resource address (or any other stable string key)
+pub fn sort_by_address<T, F>(items: &mut [T], address: F)
+where
+    F: Fn(&T) -> &str,
+{
+    items.sort_by(|a, b| address(a).cmp(address(b)));
+}
+
+/// Sort by the magnitude of a cost figure, largest first, breaking ties by
+/// address
+pub fn sort_by_cost_impact<T, C, A>(items: &mut [T], cost: C, address: A)
+where
+    C: Fn(&T) -> f64,
+    A: Fn(&T) -> &str,
+{
+    items.sort_by(|a, b| {
+        compare_cost_impact(cost(a), cost(b)).then_with(|| address(a).cmp(address(b)))
+    });
+}
+
+/// Larger magnitudes order first; NaN orders last
+fn compare_cost_impact(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => b.abs().total_cmp(&a.abs()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_by_cost_impact_is_stable_across_input_order() {
+        let mut a = vec![
+            ("aws_instance.b", 10.0),
+            ("aws_instance.a", -10.0),
+            ("aws_instance.c", 50.0),
+            ("aws_instance.d", f64::NAN),
+        ];
+        let mut b = a.clone();
+        b.reverse();
+
+        sort_by_cost_impact(&mut a, |i| i.1, |i| i.0);
+        sort_by_cost_impact(&mut b, |i| i.1, |i| i.0);
+
+        let order: Vec<&str> = a.iter().map(|i| i.0).collect();
+        assert_eq!(
+            order,
+            vec![
+                "aws_instance.c",
+                "aws_instance.a",
+                "aws_instance.b",
+                "aws_instance.d"
+            ]
+        );
+        assert_eq!(order, b.iter().map(|i| i.0).collect::<Vec<_>>());
+
+        sort_by_address(&mut a, |i| i.0);
+        assert_eq!(a[0].0, "aws_instance.a");
+    }
+}
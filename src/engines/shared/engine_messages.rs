@@ -0,0 +1,66 @@
+// Request/response models exchanged with the ProEngine
+// Shared by the host executor and the WASM engine build so both sides
+// serialize the same schema
+
+use crate::engines::autofix::{AutofixMode, AutofixResult};
+use crate::engines::explain::Explanation;
+use crate::engines::mapping::DependencyGraph;
+use crate::engines::policy::PolicyResult;
+use crate::engines::prediction::UsageProfile;
+use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
+use crate::engines::slo::SloReport;
+use crate::engines::trend::CostSnapshot;
+use serde::{Deserialize, Serialize};
+
+/// Operation requested from the ProEngine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProEngineRequest {
+    Predict {
+        changes: Vec<ResourceChange>,
+        /// Monthly usage for usage-billed resources (`usage` in costpilot.yaml)
+        #[serde(default)]
+        usage: UsageProfile,
+        /// Plan resources that size others, such as autoscaling targets
+        #[serde(default)]
+        context: Vec<ResourceChange>,
+    },
+    Explain {
+        detections: Vec<Detection>,
+        changes: Vec<ResourceChange>,
+        estimates: Vec<CostEstimate>,
+    },
+    Autofix {
+        detections: Vec<Detection>,
+        changes: Vec<ResourceChange>,
+        estimates: Vec<CostEstimate>,
+        mode: AutofixMode,
+    },
+    MapDeep {
+        changes: Vec<ResourceChange>,
+        max_depth: u32,
+    },
+    TrendSnapshot {
+        changes: Vec<ResourceChange>,
+        /// Recorded as the snapshot's `triggered_by`
+        metadata: Option<String>,
+    },
+    PolicyEnforce {
+        changes: Vec<ResourceChange>,
+        estimates: Vec<CostEstimate>,
+    },
+    SloEnforce {
+        snapshot: Box<CostSnapshot>,
+    },
+}
+
+/// Result of a ProEngine operation, one variant per request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProEngineResponse {
+    Predict(Vec<CostEstimate>),
+    Explain(Vec<Explanation>),
+    Autofix(AutofixResult),
+    MapDeep(DependencyGraph),
+    TrendSnapshot(Box<CostSnapshot>),
+    PolicyEnforce(PolicyResult),
+    SloEnforce(SloReport),
+}
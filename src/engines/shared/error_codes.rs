@@ -0,0 +1,222 @@
+// Stable public error codes (CP1001, CP1002, ...)
+//
+// Error ids such as `SCAN_001` name the place an error is raised and may be
+// reorganized; the CP code printed next to them is what CI logs, scripts and
+// support tickets refer to. A code is never renumbered or reused: new ids
+// take the next free code in their block, and ids without a code fall back
+// to their category's block code (CP1000, CP2000, ...).
+//
+// Blocks:
+//   CP1xxx  input files and parsing
+//   CP2xxx  configuration and validation
+//   CP3xxx  heuristics and prediction
+//   CP4xxx  policies, gates and security guards
+//   CP5xxx  SLOs, trends and metering
+//   CP6xxx  stacks, watch mode, output and tooling
+//   CP7xxx  performance budgets
+//   CP8xxx  editions and licensing
+//   CP9xxx  internal errors
+
+use super::error_model::ErrorCategory;
+
+/// Code for errors that were not raised as a `CostPilotError`
+pub const UNCLASSIFIED_CODE: &str = "CP9000";
+
+/// Registered `(code, error id)` pairs
+pub const ERROR_CODES: &[(&str, &str)] = &[
+    // CP1xxx - input files and parsing
+    ("CP1001", "SCAN_001"),
+    ("CP1002", "SCAN_002"),
+    ("CP1003", "SCAN_003"),
+    ("CP1004", "SCAN_INPUT_001"),
+    ("CP1005", "PARSE_001"),
+    ("CP1006", "PARSE_002"),
+    ("CP1007", "DETECT_001"),
+    ("CP1008", "DETECT_004"),
+    ("CP1009", "DETECT_005"),
+    ("CP1010", "DETECT_006"),
+    ("CP1011", "CDK_001"),
+    ("CP1012", "CDK_002"),
+    ("CP1013", "ARTIFACT_001"),
+    ("CP1014", "ARTIFACT_002"),
+    ("CP1015", "ARTIFACT_003"),
+    ("CP1016", "ARTIFACT_004"),
+    ("CP1017", "ARTIFACT_005"),
+    ("CP1018", "ARTIFACT_006"),
+    ("CP1019", "ARTIFACT_007"),
+    ("CP1020", "E_PARSE"),
+    ("CP1021", "E_INVALID_JSON"),
+    ("CP1022", "E_UTF8"),
+    ("CP1023", "E_FILE_NOT_FOUND"),
+    ("CP1024", "E_IO"),
+    // CP2xxx - configuration and validation
+    ("CP2001", "E_CONFIG"),
+    ("CP2002", "E_VALIDATION"),
+    ("CP2003", "BUNDLE_001"),
+    ("CP2004", "SCHEMA_001"),
+    ("CP2005", "SCHEMA_002"),
+    ("CP2006", "SCHEMA_003"),
+    ("CP2007", "SCHEMA_004"),
+    ("CP2008", "SCHEMA_005"),
+    ("CP2009", "SCHEMA_006"),
+    ("CP2010", "COMPAT_001"),
+    ("CP2011", "COMPAT_002"),
+    ("CP2012", "COMPAT_003"),
+    ("CP2013", "MIGRATE_001"),
+    ("CP2014", "MIGRATE_002"),
+    ("CP2015", "MIGRATE_003"),
+    ("CP2016", "FILTER_001"),
+    // CP3xxx - heuristics and prediction
+    ("CP3001", "HEURISTICS_001"),
+    ("CP3002", "HEURISTICS_002"),
+    ("CP3003", "HEURISTICS_003"),
+    ("CP3004", "HEURISTICS_004"),
+    ("CP3005", "HEURISTICS_005"),
+    ("CP3006", "HEURISTICS_006"),
+    ("CP3007", "HEURISTICS_007"),
+    ("CP3008", "HEURISTICS_008"),
+    ("CP3009", "HEURISTICS_COMPAT_001"),
+    ("CP3010", "HEURISTICS_COMPAT_002"),
+    ("CP3011", "HEURISTICS_COMPAT_003"),
+    ("CP3012", "HEURISTICS_COMPAT_004"),
+    ("CP3013", "HEURISTICS_COMPAT_005"),
+    ("CP3014", "HEURISTICS_COMPAT_006"),
+    ("CP3015", "HEURISTICS_CUSTOM_001"),
+    ("CP3016", "HEURISTICS_CUSTOM_002"),
+    ("CP3017", "HEURISTICS_CUSTOM_003"),
+    ("CP3018", "HEURISTICS_CUSTOM_004"),
+    ("CP3019", "E_PREDICTION"),
+    ("CP3020", "PREDICT_EXPLAIN_001"),
+    ("CP3021", "PREDICT_RESOURCE_001"),
+    ("CP3022", "E_SERIALIZE"),
+    ("CP3023", "E_DESERIALIZE"),
+    ("CP3024", "E_PRO_PREDICT"),
+    ("CP3025", "E_PRO_SCAN"),
+    ("CP3026", "CALIBRATE_001"),
+    ("CP3027", "CALIBRATE_002"),
+    ("CP3028", "CALIBRATE_003"),
+    ("CP3029", "MC_001"),
+    // CP4xxx - policies, gates and security guards
+    ("CP4001", "POLICY_001"),
+    ("CP4002", "POLICY_002"),
+    ("CP4003", "POLICY_003"),
+    ("CP4004", "POLICY_004"),
+    ("CP4005", "POLICY_005"),
+    ("CP4006", "POLICY_006"),
+    ("CP4008", "POLICY_008"),
+    ("CP4009", "POLICY_009"),
+    ("CP4010", "GATE_001"),
+    ("CP4011", "ZERO_COST_001"),
+    ("CP4012", "ZERO_COST_002"),
+    ("CP4013", "ZERO_COST_003"),
+    ("CP4014", "ZERO_COST_004"),
+    ("CP4015", "ZERO_COST_005"),
+    ("CP4016", "ZERO_COST_006"),
+    ("CP4017", "SEC_001"),
+    ("CP4018", "SEC_002"),
+    ("CP4019", "SEC_003"),
+    ("CP4020", "SEC_004"),
+    ("CP4021", "SEC_005"),
+    ("CP4022", "E_SECURITY"),
+    ("CP4023", "AT_REST_001"),
+    ("CP4024", "AT_REST_002"),
+    // CP5xxx - SLOs, trends and metering
+    ("CP5001", "SLO_001"),
+    ("CP5002", "SLO_002"),
+    ("CP5003", "ACTUALS_001"),
+    ("CP5004", "METER_001"),
+    ("CP5005", "METER_002"),
+    ("CP5006", "METER_003"),
+    ("CP5007", "PR_001"),
+    ("CP5008", "PR_002"),
+    ("CP5009", "PR_003"),
+    // CP6xxx - stacks, watch mode, output and tooling
+    ("CP6001", "STACK_001"),
+    ("CP6002", "STACK_002"),
+    ("CP6003", "STACK_003"),
+    ("CP6004", "STACK_004"),
+    ("CP6005", "STACK_005"),
+    ("CP6006", "WATCH_001"),
+    ("CP6007", "SCAN_TUI_001"),
+    ("CP6008", "SCAN_TUI_002"),
+    ("CP6009", "OUTPUT_002"),
+    ("CP6010", "OUTPUT_003"),
+    ("CP6011", "OUTPUT_004"),
+    ("CP6012", "PLUGIN_001"),
+    ("CP6013", "LSP_001"),
+    // CP7xxx - performance budgets
+    ("CP7001", "TIMEOUT"),
+    ("CP7002", "CIRCUIT_BREAK"),
+    ("CP7003", "BUDGET_EXCEEDED"),
+    ("CP7004", "PREDICT_TIMEOUT"),
+    ("CP7005", "PREDICT_CIRCUIT_BREAK"),
+    ("CP7006", "CANCELLED"),
+    // CP8xxx - editions and licensing
+    ("CP8001", "E_UPGRADE_REQUIRED"),
+    ("CP8002", "E_PRO_ENGINE"),
+    // CP9xxx - internal errors
+    ("CP9001", "E_SERIALIZATION"),
+    ("CP9002", "E_GENERATION"),
+    ("CP9003", "FFI_001"),
+    ("CP9004", "ASYNC_001"),
+    ("CP9005", "ASYNC_002"),
+];
+
+/// Block code for errors of `category` without a registered id
+pub fn category_code(category: &ErrorCategory) -> &'static str {
+    match category {
+        ErrorCategory::InvalidInput
+        | ErrorCategory::ParseError
+        | ErrorCategory::FileSystemError
+        | ErrorCategory::IoError
+        | ErrorCategory::NotFound => "CP1000",
+        ErrorCategory::ConfigError | ErrorCategory::ValidationError => "CP2000",
+        ErrorCategory::PredictionError => "CP3000",
+        ErrorCategory::PolicyViolation | ErrorCategory::SecurityViolation => "CP4000",
+        ErrorCategory::SLOBreach | ErrorCategory::DriftDetected => "CP5000",
+        ErrorCategory::Timeout | ErrorCategory::CircuitBreaker => "CP7000",
+        ErrorCategory::InternalError => "CP9000",
+    }
+}
+
+/// Stable code for an error id, falling back to the category's block code
+pub fn code_for(id: &str, category: &ErrorCategory) -> &'static str {
+    ERROR_CODES
+        .iter()
+        .find(|(_, registered)| *registered == id)
+        .map(|(code, _)| *code)
+        .unwrap_or_else(|| category_code(category))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_error_codes_are_unique_and_well_formed() {
+        let mut codes = HashSet::new();
+        let mut ids = HashSet::new();
+        for (code, id) in ERROR_CODES {
+            assert!(codes.insert(*code), "duplicate code {}", code);
+            assert!(ids.insert(*id), "duplicate id {}", id);
+            assert_eq!(code.len(), 6, "{}", code);
+            assert!(code.starts_with("CP"), "{}", code);
+            assert!(code[2..].chars().all(|c| c.is_ascii_digit()), "{}", code);
+            assert!(!code.ends_with("000"), "{} is a block code", code);
+        }
+    }
+
+    #[test]
+    fn test_code_for_falls_back_to_category() {
+        assert_eq!(
+            code_for("SCAN_001", &ErrorCategory::FileSystemError),
+            "CP1001"
+        );
+        assert_eq!(
+            code_for("E_POLICY_max_cost", &ErrorCategory::PolicyViolation),
+            "CP4000"
+        );
+        assert_eq!(code_for("UNKNOWN", &ErrorCategory::Timeout), "CP7000");
+    }
+}
@@ -1,5 +1,6 @@
 // Error model with stable error IDs and categorization
 
+use super::error_codes;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -117,7 +118,10 @@ impl CostPilotError {
             ErrorCategory::ValidationError,
             message,
         )
-        .with_hint("This feature requires CostPilot Premium. Visit https://costpilot.dev/upgrade")
+        .with_hint(crate::i18n::tf(
+            "edition.upgrade_hint",
+            &[("url", "https://costpilot.dev/upgrade")],
+        ))
     }
 
     /// Create a policy violation error
@@ -158,6 +162,12 @@ impl CostPilotError {
             .with_hint("Review security policies and access controls")
     }
 
+    /// Create a cancellation error (token cancelled or deadline passed)
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self::new("CANCELLED", ErrorCategory::Timeout, message)
+            .with_hint("The caller cancelled the operation or its deadline passed")
+    }
+
     /// Generate remediation hint based on error category and message
     pub fn generate_hint(&self) -> String {
         if let Some(hint) = &self.hint {
@@ -209,14 +219,42 @@ impl CostPilotError {
 
     /// Convert to machine-readable format (JSON)
     pub fn to_machine_format(&self) -> String {
-        serde_json::to_string(self)
-            .unwrap_or_else(|_| format!(r#"{{"id":"{}","message":"{}"}}"#, self.id, self.message))
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = value.as_object_mut() {
+            fields.insert("code".to_string(), self.code().into());
+            return value.to_string();
+        }
+        format!(
+            r#"{{"code":"{}","id":"{}","message":"{}"}}"#,
+            self.code(),
+            self.id,
+            self.message
+        )
+    }
+
+    /// Stable public code (`CP1001`), derived from the id and category
+    pub fn code(&self) -> &'static str {
+        error_codes::code_for(&self.id, &self.category)
+    }
+
+    /// `{"error": ...}` document printed by commands run with `--format json`
+    pub fn to_json_report(&self) -> serde_json::Value {
+        serde_json::json!({
+            "error": {
+                "code": self.code(),
+                "id": self.id,
+                "category": self.category,
+                "message": self.message,
+                "hint": self.hint,
+                "context": self.context,
+            }
+        })
     }
 }
 
 impl fmt::Display for CostPilotError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[{}] {}", self.id, self.message)?;
+        write!(f, "{} [{}] {}", self.code(), self.id, self.message)?;
         if let Some(hint) = &self.hint {
             write!(f, "\n  Hint: {}", hint)?;
         }
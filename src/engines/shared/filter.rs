@@ -0,0 +1,665 @@
+// Filter expressions for narrowing results (`--filter`)
+//
+// One small language shared by scan, map, group and trend, so the same
+// expression selects the same resources everywhere:
+//
+//   type=aws_instance and cost>100 and module=~vpc
+//   not (action=delete or tag.env=dev*)
+//
+// A condition is `field op value`. Text fields compare with `=`/`!=`
+// (case-insensitive, `*` matches any run of characters) or `=~`/`!~`
+// (regular expression); `cost` and `delta` compare numerically. Conditions
+// combine with `and`, `or`, `not` and parentheses; `and` binds tighter than
+// `or`. Values containing spaces, parentheses or operator characters are
+// quoted with `'` or `"`. A condition on a field the result does not have
+// (a tag that is not set, `type` on a trend module row) is false, and its
+// negated forms (`!=`, `!~`) are true.
+
+use super::error_model::{CostPilotError, ErrorCategory, Result};
+use super::models::{CostEstimate, ResourceChange};
+use regex::Regex;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Field a condition tests
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    /// Resource type (`aws_instance`)
+    Type,
+    /// Full resource address
+    Address,
+    /// Resource name, without type, module or index
+    Name,
+    /// Module path; `root` outside modules
+    Module,
+    /// AWS service (`EC2`, `RDS`)
+    Service,
+    /// Change action (`create`, `update`, ...) or trend change type
+    Action,
+    /// Value of one tag (`tag.env`)
+    Tag(String),
+    /// Monthly cost
+    Cost,
+    /// Monthly cost change (trend rows)
+    Delta,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        if let Some(key) = name.strip_prefix("tag.").filter(|k| !k.is_empty()) {
+            return Some(Field::Tag(key.to_string()));
+        }
+        Some(match name.to_ascii_lowercase().as_str() {
+            "type" => Field::Type,
+            "address" | "id" => Field::Address,
+            "name" => Field::Name,
+            "module" => Field::Module,
+            "service" => Field::Service,
+            "action" => Field::Action,
+            "cost" => Field::Cost,
+            "delta" => Field::Delta,
+            _ => return None,
+        })
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Field::Cost | Field::Delta)
+    }
+}
+
+/// Something a filter can be applied to
+pub trait Filterable {
+    /// Text value of a non-numeric field, if this result has one
+    fn text(&self, field: &Field) -> Option<Cow<'_, str>>;
+
+    /// Value of `cost` or `delta`, if this result has one
+    fn number(&self, field: &Field) -> Option<f64>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Match,
+    NotMatch,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Op {
+    /// Longest first, so `>=` is not read as `>`
+    const SYMBOLS: [(&'static str, Op); 8] = [
+        ("=~", Op::Match),
+        ("!~", Op::NotMatch),
+        ("!=", Op::Ne),
+        (">=", Op::Ge),
+        ("<=", Op::Le),
+        ("=", Op::Eq),
+        (">", Op::Gt),
+        ("<", Op::Lt),
+    ];
+
+    fn negated(self) -> bool {
+        matches!(self, Op::Ne | Op::NotMatch)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    /// Lowercased text, possibly with `*` wildcards
+    Text(String),
+    Regex(Regex),
+    Number(f64),
+}
+
+#[derive(Debug, Clone)]
+struct Condition {
+    field: Field,
+    op: Op,
+    operand: Operand,
+}
+
+impl Condition {
+    fn matches(&self, subject: &(impl Filterable + ?Sized)) -> bool {
+        let result = match &self.operand {
+            Operand::Number(expected) => match subject.number(&self.field) {
+                Some(actual) => match self.op {
+                    Op::Eq | Op::Ne => (actual - expected).abs() < 0.005,
+                    Op::Gt => actual > *expected,
+                    Op::Ge => actual >= *expected,
+                    Op::Lt => actual < *expected,
+                    Op::Le => actual <= *expected,
+                    Op::Match | Op::NotMatch => false,
+                },
+                None => return self.op.negated(),
+            },
+            Operand::Text(pattern) => match subject.text(&self.field) {
+                Some(actual) => crate::engines::detection::custom_rules::wildcard_match(
+                    pattern,
+                    &actual.to_ascii_lowercase(),
+                ),
+                None => return self.op.negated(),
+            },
+            Operand::Regex(regex) => match subject.text(&self.field) {
+                Some(actual) => regex.is_match(&actual),
+                None => return self.op.negated(),
+            },
+        };
+        result != self.op.negated()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Condition(Condition),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+}
+
+impl Node {
+    fn matches(&self, subject: &(impl Filterable + ?Sized)) -> bool {
+        match self {
+            Node::Condition(condition) => condition.matches(subject),
+            Node::Not(inner) => !inner.matches(subject),
+            Node::And(left, right) => left.matches(subject) && right.matches(subject),
+            Node::Or(left, right) => left.matches(subject) || right.matches(subject),
+        }
+    }
+
+    fn references(&self, field: &Field) -> bool {
+        match self {
+            Node::Condition(condition) => condition.field == *field,
+            Node::Not(inner) => inner.references(field),
+            Node::And(left, right) | Node::Or(left, right) => {
+                left.references(field) || right.references(field)
+            }
+        }
+    }
+}
+
+/// A parsed `--filter` expression
+#[derive(Debug, Clone)]
+pub struct FilterExpr {
+    source: String,
+    root: Node,
+}
+
+impl FilterExpr {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = lex(source)?;
+        let mut parser = Parser {
+            source,
+            tokens,
+            position: 0,
+        };
+        let root = parser.or()?;
+        if let Some(token) = parser.peek() {
+            return Err(parser.error(token.offset, "expected `and`, `or` or end of filter"));
+        }
+        Ok(Self {
+            source: source.trim().to_string(),
+            root,
+        })
+    }
+
+    pub fn matches(&self, subject: &(impl Filterable + ?Sized)) -> bool {
+        self.root.matches(subject)
+    }
+
+    /// Whether any condition tests `field`; callers price resources only
+    /// when the filter compares costs
+    pub fn references(&self, field: &Field) -> bool {
+        self.root.references(field)
+    }
+
+    /// Keep the items the filter matches
+    pub fn retain<T: Filterable>(&self, items: &mut Vec<T>) {
+        items.retain(|item| self.matches(item));
+    }
+
+    /// Whether the resource priced by `estimate` matches, with its cost
+    /// taken from the estimate
+    pub fn matches_estimate(&self, changes: &[ResourceChange], estimate: &CostEstimate) -> bool {
+        changes
+            .iter()
+            .find(|c| c.resource_id == estimate.resource_id)
+            .is_some_and(|change| self.matches(&PricedChange::new(change, estimate.monthly_cost)))
+    }
+
+    /// Keep the resources the filter matches, and only their estimates;
+    /// costs come from the estimates
+    pub fn retain_resources(
+        &self,
+        changes: &mut Vec<ResourceChange>,
+        estimates: &mut Vec<CostEstimate>,
+    ) {
+        let costs: HashMap<&str, f64> = estimates
+            .iter()
+            .map(|e| (e.resource_id.as_str(), e.monthly_cost))
+            .collect();
+        let kept: Vec<bool> = changes
+            .iter()
+            .map(|change| {
+                let cost = costs
+                    .get(change.resource_id.as_str())
+                    .copied()
+                    .or(change.monthly_cost);
+                self.matches(&PricedChange {
+                    change,
+                    monthly_cost: cost,
+                })
+            })
+            .collect();
+        let mut kept = kept.into_iter();
+        changes.retain(|_| kept.next().unwrap_or(false));
+        estimates.retain(|e| changes.iter().any(|c| c.resource_id == e.resource_id));
+    }
+}
+
+impl FromStr for FilterExpr {
+    type Err = CostPilotError;
+
+    fn from_str(source: &str) -> Result<Self> {
+        Self::parse(source)
+    }
+}
+
+impl fmt::Display for FilterExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// A resource change with the monthly cost it is filtered on
+pub struct PricedChange<'a> {
+    pub change: &'a ResourceChange,
+    pub monthly_cost: Option<f64>,
+}
+
+impl<'a> PricedChange<'a> {
+    pub fn new(change: &'a ResourceChange, monthly_cost: f64) -> Self {
+        Self {
+            change,
+            monthly_cost: Some(monthly_cost),
+        }
+    }
+}
+
+impl Filterable for PricedChange<'_> {
+    fn text(&self, field: &Field) -> Option<Cow<'_, str>> {
+        self.change.text(field)
+    }
+
+    fn number(&self, field: &Field) -> Option<f64> {
+        match field {
+            Field::Cost => self.monthly_cost,
+            _ => None,
+        }
+    }
+}
+
+impl Filterable for ResourceChange {
+    fn text(&self, field: &Field) -> Option<Cow<'_, str>> {
+        match field {
+            Field::Type => Some(Cow::Borrowed(&self.resource_type)),
+            Field::Address => Some(Cow::Borrowed(&self.resource_id)),
+            Field::Name => Some(Cow::Owned(resource_name(
+                &self.resource_id,
+                &self.resource_type,
+            ))),
+            Field::Module => Some(Cow::Borrowed(self.module_path.as_deref().unwrap_or("root"))),
+            Field::Service => Some(Cow::Owned(
+                crate::engines::grouping::by_service::extract_service_info(&self.resource_type).0,
+            )),
+            Field::Action => Some(Cow::Owned(format!("{:?}", self.action).to_lowercase())),
+            Field::Tag(key) => self.tags.get(key).map(|v| Cow::Borrowed(v.as_str())),
+            Field::Cost | Field::Delta => None,
+        }
+    }
+
+    fn number(&self, field: &Field) -> Option<f64> {
+        match field {
+            Field::Cost => self.monthly_cost,
+            _ => None,
+        }
+    }
+}
+
+/// `web` for `module.app.aws_instance.web[0]`
+fn resource_name(address: &str, resource_type: &str) -> String {
+    let marker = format!("{}.", resource_type);
+    let name = address
+        .rfind(&marker)
+        .map(|pos| &address[pos + marker.len()..])
+        .unwrap_or(address);
+    name.split('[').next().unwrap_or(name).to_string()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Open,
+    Close,
+    Op(Op),
+    Word(String),
+    Quoted(String),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    offset: usize,
+}
+
+fn lex(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        let offset = source.len() - rest.len();
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let (kind, len) = if c == '(' {
+            (TokenKind::Open, 1)
+        } else if c == ')' {
+            (TokenKind::Close, 1)
+        } else if c == '\'' || c == '"' {
+            let Some(end) = rest[1..].find(c) else {
+                return Err(syntax_error(source, offset, "unterminated quoted value"));
+            };
+            (TokenKind::Quoted(rest[1..=end].to_string()), end + 2)
+        } else if let Some((symbol, op)) = Op::SYMBOLS
+            .iter()
+            .find(|(symbol, _)| rest.starts_with(symbol))
+        {
+            (TokenKind::Op(*op), symbol.len())
+        } else {
+            let len = rest
+                .find(|c: char| c.is_whitespace() || "()'\"=!<>~".contains(c))
+                .unwrap_or(rest.len());
+            if len == 0 {
+                return Err(syntax_error(source, offset, "unexpected character"));
+            }
+            (TokenKind::Word(rest[..len].to_string()), len)
+        };
+        tokens.push(Token { kind, offset });
+        rest = &rest[len..];
+    }
+    Ok(tokens)
+}
+
+fn syntax_error(source: &str, offset: usize, message: &str) -> CostPilotError {
+    CostPilotError::new(
+        "FILTER_001",
+        ErrorCategory::InvalidInput,
+        format!(
+            "Invalid filter at column {}: {}\n  {}\n  {}^",
+            offset + 1,
+            message,
+            source,
+            " ".repeat(source[..offset].chars().count())
+        ),
+    )
+    .with_hint("Filters look like: type=aws_instance and cost>100 and module=~vpc")
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn end(&self) -> usize {
+        self.source.len()
+    }
+
+    fn error(&self, offset: usize, message: &str) -> CostPilotError {
+        syntax_error(self.source, offset, message)
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(
+            self.peek(),
+            Some(Token { kind: TokenKind::Word(word), .. }) if word.eq_ignore_ascii_case(keyword)
+        );
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<Node> {
+        let mut node = self.and()?;
+        while self.keyword("or") {
+            node = Node::Or(Box::new(node), Box::new(self.and()?));
+        }
+        Ok(node)
+    }
+
+    fn and(&mut self) -> Result<Node> {
+        let mut node = self.unary()?;
+        while self.keyword("and") {
+            node = Node::And(Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    fn unary(&mut self) -> Result<Node> {
+        if self.keyword("not") {
+            return Ok(Node::Not(Box::new(self.unary()?)));
+        }
+        if matches!(
+            self.peek(),
+            Some(Token {
+                kind: TokenKind::Open,
+                ..
+            })
+        ) {
+            self.position += 1;
+            let node = self.or()?;
+            return match self.next() {
+                Some(Token {
+                    kind: TokenKind::Close,
+                    ..
+                }) => Ok(node),
+                Some(token) => Err(self.error(token.offset, "expected `)`")),
+                None => Err(self.error(self.end(), "missing `)`")),
+            };
+        }
+        self.condition().map(Node::Condition)
+    }
+
+    fn condition(&mut self) -> Result<Condition> {
+        let (name, field_offset) = match self.next() {
+            Some(Token {
+                kind: TokenKind::Word(word),
+                offset,
+            }) => (word, offset),
+            Some(token) => return Err(self.error(token.offset, "expected a field name")),
+            None => return Err(self.error(self.end(), "expected a condition")),
+        };
+        let field = Field::parse(&name).ok_or_else(|| {
+            self.error(
+                field_offset,
+                "unknown field (type, address, name, module, service, action, tag.<key>, cost, delta)",
+            )
+        })?;
+        let (op, op_offset) = match self.next() {
+            Some(Token {
+                kind: TokenKind::Op(op),
+                offset,
+            }) => (op, offset),
+            Some(token) => return Err(self.error(token.offset, "expected an operator")),
+            None => return Err(self.error(self.end(), "expected an operator")),
+        };
+        let (value, value_offset) = match self.next() {
+            Some(Token {
+                kind: TokenKind::Word(value) | TokenKind::Quoted(value),
+                offset,
+            }) => (value, offset),
+            Some(token) => return Err(self.error(token.offset, "expected a value")),
+            None => return Err(self.error(self.end(), "expected a value")),
+        };
+
+        let operand = if field.is_numeric() {
+            if matches!(op, Op::Match | Op::NotMatch) {
+                return Err(self.error(op_offset, "numeric fields compare with = != > >= < <="));
+            }
+            let number = value
+                .trim_start_matches('$')
+                .parse::<f64>()
+                .map_err(|_| self.error(value_offset, "expected a number"))?;
+            Operand::Number(number)
+        } else {
+            match op {
+                Op::Eq | Op::Ne => Operand::Text(value.to_ascii_lowercase()),
+                Op::Match | Op::NotMatch => Operand::Regex(Regex::new(&value).map_err(|e| {
+                    self.error(value_offset, &format!("invalid regular expression: {}", e))
+                })?),
+                _ => {
+                    return Err(self.error(op_offset, "only cost and delta compare with > >= < <="))
+                }
+            }
+        };
+        Ok(Condition { field, op, operand })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+
+    fn change(address: &str, resource_type: &str, module: Option<&str>) -> ResourceChange {
+        ResourceChange {
+            resource_id: address.to_string(),
+            resource_type: resource_type.to_string(),
+            action: ChangeAction::Create,
+            module_path: module.map(str::to_string),
+            old_config: None,
+            new_config: None,
+            tags: HashMap::from([("env".to_string(), "prod-eu".to_string())]),
+            monthly_cost: None,
+            config: None,
+            cost_impact: None,
+        }
+    }
+
+    fn matches(expr: &str, subject: &impl Filterable) -> bool {
+        FilterExpr::parse(expr).unwrap().matches(subject)
+    }
+
+    #[test]
+    fn test_conditions_and_precedence() {
+        let nat = change(
+            "module.vpc.aws_nat_gateway.this[0]",
+            "aws_nat_gateway",
+            Some("module.vpc"),
+        );
+        let priced = PricedChange::new(&nat, 120.0);
+
+        assert!(matches(
+            "type=aws_nat_gateway and cost>100 and module=~vpc",
+            &priced
+        ));
+        assert!(matches("type = AWS_NAT_* and name=this", &priced));
+        assert!(matches("cost>=120 and cost<=120 and cost=$120", &priced));
+        assert!(!matches("cost<100", &priced));
+        assert!(matches(
+            "type=aws_instance or cost>100 and tag.env=prod*",
+            &priced
+        ));
+        assert!(!matches(
+            "(type=aws_instance or cost>100) and action!=create",
+            &priced
+        ));
+        assert!(matches(
+            "not action=delete and address=~'\\[0\\]$'",
+            &priced
+        ));
+        assert!(matches("service=vpc or service!=ec2", &priced));
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let instance = change("aws_instance.web", "aws_instance", None);
+        assert!(matches("module=root", &instance));
+        assert!(!matches("cost>0", &instance));
+        assert!(matches("cost!=0", &instance));
+        assert!(!matches("tag.owner=alice", &instance));
+        assert!(matches("tag.owner!~alice", &instance));
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_problem() {
+        for (expr, column) in [
+            ("type=", 6),
+            ("kind=aws_instance", 1),
+            ("cost=~10", 5),
+            ("type>aws", 5),
+            ("(type=a or type=b", 18),
+            ("type=a name=b", 8),
+            ("module=~'('", 9),
+            ("name='web", 6),
+        ] {
+            let err = FilterExpr::parse(expr).unwrap_err();
+            assert_eq!(err.id, "FILTER_001", "{}", expr);
+            assert!(
+                err.message.contains(&format!("column {}:", column)),
+                "{}: {}",
+                expr,
+                err.message
+            );
+        }
+    }
+
+    #[test]
+    fn test_retain_resources_filters_estimates_too() {
+        let mut changes = vec![
+            change("aws_instance.big", "aws_instance", None),
+            change("aws_instance.small", "aws_instance", None),
+        ];
+        let estimate = |id: &str, cost: f64| CostEstimate {
+            resource_id: id.to_string(),
+            monthly_cost: cost,
+            prediction_interval_low: 0.0,
+            prediction_interval_high: 0.0,
+            confidence_score: 1.0,
+            heuristic_reference: None,
+            cold_start_inference: false,
+            one_time: None,
+            breakdown: None,
+            hourly: None,
+            daily: None,
+            provenance: None,
+        };
+        let mut estimates = vec![
+            estimate("aws_instance.big", 300.0),
+            estimate("aws_instance.small", 8.0),
+        ];
+
+        let filter = FilterExpr::parse("cost>100").unwrap();
+        assert!(filter.references(&Field::Cost));
+        assert!(filter.matches_estimate(&changes, &estimates[0]));
+        filter.retain_resources(&mut changes, &mut estimates);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(estimates.len(), 1);
+        assert_eq!(estimates[0].resource_id, "aws_instance.big");
+    }
+}
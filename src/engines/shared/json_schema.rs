@@ -1 +1,500 @@
 // JSON schema definitions
+//
+// Every machine-readable output carries a `schema_version`. The major
+// component changes only when a field is removed, renamed or retyped; new
+// optional fields bump the minor component. Integrators pin the major
+// version and can fetch the matching schema with `costpilot schema export`.
+
+use serde_json::{json, Map, Value};
+
+/// Version of the JSON output layout embedded in every output
+pub const OUTPUT_SCHEMA_VERSION: &str = "1.3.0";
+
+/// JSON Schema dialect used for exported documents
+pub const SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Machine-readable outputs with a published schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputSchema {
+    /// `costpilot scan --format json`
+    ScanResult,
+    /// Trend snapshots written by `costpilot trend snapshot`
+    Snapshot,
+    /// Policy evaluation result, embedded as `policy_result` in scan output
+    PolicyReport,
+    /// `costpilot map --format json` (standard layout)
+    MapJson,
+}
+
+impl OutputSchema {
+    pub const ALL: [OutputSchema; 4] = [
+        OutputSchema::ScanResult,
+        OutputSchema::Snapshot,
+        OutputSchema::PolicyReport,
+        OutputSchema::MapJson,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputSchema::ScanResult => "scan-result",
+            OutputSchema::Snapshot => "snapshot",
+            OutputSchema::PolicyReport => "policy-report",
+            OutputSchema::MapJson => "map",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|schema| schema.name() == name.to_lowercase())
+    }
+
+    /// File name used when exporting to a directory
+    pub fn file_name(&self) -> String {
+        format!("{}.schema.json", self.name())
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            OutputSchema::ScanResult => "CostPilot Scan Result",
+            OutputSchema::Snapshot => "CostPilot Cost Snapshot",
+            OutputSchema::PolicyReport => "CostPilot Policy Report",
+            OutputSchema::MapJson => "CostPilot Dependency Map",
+        }
+    }
+}
+
+/// Whether output written with `version` can be read by this build
+///
+/// Versions are compatible when their major components match.
+pub fn is_compatible(version: &str) -> bool {
+    fn major(version: &str) -> Option<&str> {
+        version.split('.').next().filter(|m| !m.is_empty())
+    }
+    major(version).is_some() && major(version) == major(OUTPUT_SCHEMA_VERSION)
+}
+
+/// Generate the JSON Schema document for an output
+pub fn schema_for(kind: OutputSchema) -> Value {
+    let (properties, required) = match kind {
+        OutputSchema::ScanResult => scan_result(),
+        OutputSchema::Snapshot => snapshot(),
+        OutputSchema::PolicyReport => policy_report(),
+        OutputSchema::MapJson => map_json(),
+    };
+
+    let mut schema = object(properties, &required);
+    let doc = schema.as_object_mut().expect("object schema");
+    doc.insert("$schema".to_string(), json!(SCHEMA_DIALECT));
+    doc.insert(
+        "$id".to_string(),
+        json!(format!(
+            "urn:costpilot:schema:{}:{}",
+            kind.name(),
+            OUTPUT_SCHEMA_VERSION
+        )),
+    );
+    doc.insert("title".to_string(), json!(kind.title()));
+    doc.insert("definitions".to_string(), definitions());
+    schema
+}
+
+/// Schemas for every output, keyed by name
+pub fn all_schemas() -> Value {
+    let schemas: Map<String, Value> = OutputSchema::ALL
+        .into_iter()
+        .map(|kind| (kind.name().to_string(), schema_for(kind)))
+        .collect();
+    Value::Object(schemas)
+}
+
+fn scan_result() -> (Vec<(&'static str, Value)>, Vec<&'static str>) {
+    (
+        vec![
+            ("schema_version", schema_version()),
+            (
+                "summary",
+                object(
+                    vec![
+                        ("resources_changed", integer()),
+                        ("monthly_cost", number()),
+                        ("optimization_opportunities", integer()),
+                        ("policy_status", nullable(status())),
+                        ("slo_status", nullable(status())),
+                    ],
+                    &[
+                        "resources_changed",
+                        "monthly_cost",
+                        "optimization_opportunities",
+                    ],
+                ),
+            ),
+            (
+                "changes",
+                array(object(
+                    vec![
+                        ("resource_id", string()),
+                        ("change_type", string()),
+                        ("resource_type", string()),
+                    ],
+                    &["resource_id", "change_type", "resource_type"],
+                )),
+            ),
+            ("estimates", array(reference("cost_estimate"))),
+            ("detections", array(reference("detection"))),
+            ("policy_result", nullable(reference("policy_result"))),
+            (
+                "slo_result",
+                nullable(object(
+                    vec![
+                        ("passed", boolean()),
+                        ("evaluations", array(json!({ "type": "object" }))),
+                        ("should_block", boolean()),
+                        ("message", string()),
+                    ],
+                    &["passed", "evaluations", "should_block", "message"],
+                )),
+            ),
+            (
+                "trial",
+                object(
+                    vec![("expires", string()), ("days_remaining", integer())],
+                    &["expires", "days_remaining"],
+                ),
+            ),
+        ],
+        vec![
+            "schema_version",
+            "summary",
+            "changes",
+            "estimates",
+            "detections",
+        ],
+    )
+}
+
+fn snapshot() -> (Vec<(&'static str, Value)>, Vec<&'static str>) {
+    let module_cost = object(
+        vec![
+            ("name", string()),
+            ("monthly_cost", number()),
+            ("resource_count", integer()),
+            ("change_from_previous", number()),
+            ("change_percent", number()),
+            ("services", array(json!({ "type": "object" }))),
+        ],
+        &["name", "monthly_cost", "resource_count"],
+    );
+
+    let actuals = object(
+        vec![
+            (
+                "source",
+                enumeration(&["cost_and_usage_report", "cost_explorer"]),
+            ),
+            ("period", string()),
+            ("total_monthly_cost", number()),
+            ("modules", map_of(number())),
+            ("services", map_of(number())),
+        ],
+        &["source", "total_monthly_cost"],
+    );
+
+    (
+        vec![
+            ("schema_version", schema_version()),
+            ("id", string()),
+            ("timestamp", string()),
+            ("commit_hash", string()),
+            ("branch", string()),
+            ("total_monthly_cost", number()),
+            ("modules", map_of(module_cost)),
+            ("services", map_of(number())),
+            ("regressions", array(json!({ "type": "object" }))),
+            ("slo_violations", array(json!({ "type": "object" }))),
+            ("metadata", json!({ "type": "object" })),
+            ("actuals", actuals),
+        ],
+        vec![
+            "schema_version",
+            "id",
+            "timestamp",
+            "total_monthly_cost",
+            "modules",
+            "services",
+        ],
+    )
+}
+
+fn policy_report() -> (Vec<(&'static str, Value)>, Vec<&'static str>) {
+    (
+        vec![
+            ("schema_version", schema_version()),
+            ("passed", boolean()),
+            ("violations", array(reference("policy_violation"))),
+            ("warnings", array(string())),
+            ("applied_exemptions", array(string())),
+        ],
+        vec!["passed", "violations", "warnings", "applied_exemptions"],
+    )
+}
+
+fn map_json() -> (Vec<(&'static str, Value)>, Vec<&'static str>) {
+    let node = object(
+        vec![
+            ("id", string()),
+            ("label", string()),
+            ("node_type", enumeration(&["resource", "service", "module"])),
+            ("resource_type", string()),
+            ("monthly_cost", number()),
+            ("module", string()),
+        ],
+        &["id", "label", "node_type"],
+    );
+    let edge = object(
+        vec![
+            ("from", string()),
+            ("to", string()),
+            (
+                "relationship",
+                enumeration(&[
+                    "depends_on",
+                    "data_flow",
+                    "network_connection",
+                    "cost_attribution",
+                    "cross_stack",
+                ]),
+            ),
+            ("cost_impact", string()),
+        ],
+        &["from", "to", "relationship"],
+    );
+    let metadata = object(
+        vec![
+            ("version", string()),
+            ("timestamp", string()),
+            ("node_count", integer()),
+            ("edge_count", integer()),
+            ("max_depth", integer()),
+            ("has_cycles", boolean()),
+            ("cycles", array(array(string()))),
+            ("total_cost", number()),
+        ],
+        &[
+            "version",
+            "timestamp",
+            "node_count",
+            "edge_count",
+            "max_depth",
+            "has_cycles",
+        ],
+    );
+
+    (
+        vec![
+            ("schema_version", schema_version()),
+            ("nodes", array(node)),
+            ("edges", array(edge)),
+            ("metadata", metadata),
+            ("statistics", json!({ "type": "object" })),
+        ],
+        vec!["schema_version", "nodes", "edges"],
+    )
+}
+
+/// Shared definitions referenced with `#/definitions/<name>`
+fn definitions() -> Value {
+    json!({
+        "cost_estimate": object(
+            vec![
+                ("resource_id", string()),
+                ("monthly_cost", number()),
+                ("prediction_interval_low", number()),
+                ("prediction_interval_high", number()),
+                ("confidence_score", number()),
+                ("heuristic_reference", nullable(string())),
+                ("cold_start_inference", boolean()),
+                ("one_time", nullable(number())),
+                ("breakdown", nullable(map_of(number()))),
+                ("hourly", nullable(number())),
+                ("daily", nullable(number())),
+                (
+                    "provenance",
+                    object(
+                        vec![
+                            ("heuristic_id", string()),
+                            ("pricing_version", nullable(string())),
+                            ("assumptions", array(string())),
+                            (
+                                "steps",
+                                array(object(
+                                    vec![
+                                        ("step_number", integer()),
+                                        ("operation", string()),
+                                        ("input", string()),
+                                        ("output", string()),
+                                        ("reasoning", string()),
+                                    ],
+                                    &["step_number", "operation", "input", "output"],
+                                )),
+                            ),
+                        ],
+                        &["heuristic_id", "steps"],
+                    ),
+                ),
+            ],
+            &["resource_id", "monthly_cost"],
+        ),
+        "detection": object(
+            vec![
+                ("rule_id", string()),
+                ("severity", enumeration(&["Low", "Medium", "High", "Critical"])),
+                ("resource_id", string()),
+                ("regression_type", string()),
+                ("severity_score", integer()),
+                ("message", string()),
+                ("fix_snippet", nullable(string())),
+                ("estimated_cost", nullable(number())),
+            ],
+            &["rule_id", "severity", "resource_id", "message"],
+        ),
+        "policy_violation": object(
+            vec![
+                ("policy_name", string()),
+                ("severity", string()),
+                ("resource_id", string()),
+                ("message", string()),
+                ("actual_value", string()),
+                ("expected_value", string()),
+            ],
+            &["policy_name", "severity", "resource_id", "message"],
+        ),
+        "policy_result": object(
+            policy_report().0,
+            &["passed", "violations"],
+        ),
+    })
+}
+
+fn schema_version() -> Value {
+    json!({
+        "type": "string",
+        "pattern": "^[0-9]+\\.[0-9]+\\.[0-9]+$",
+        "description": "Output layout version; the major component changes on breaking changes"
+    })
+}
+
+fn status() -> Value {
+    enumeration(&["PASSED", "FAILED"])
+}
+
+fn object(properties: Vec<(&str, Value)>, required: &[&str]) -> Value {
+    let properties: Map<String, Value> = properties
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn map_of(values: Value) -> Value {
+    json!({ "type": "object", "additionalProperties": values })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/definitions/{}", name) })
+}
+
+fn enumeration(values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn number() -> Value {
+    json!({ "type": "number" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::engines::mapping::{DependencyGraph, JsonExportConfig, JsonExporter};
+    use crate::engines::trend::CostSnapshot;
+    use jsonschema::{Draft, JSONSchema};
+
+    fn assert_valid(kind: OutputSchema, instance: &Value) {
+        let schema = schema_for(kind);
+        let compiled = JSONSchema::options()
+            .with_draft(Draft::Draft7)
+            .compile(&schema)
+            .unwrap();
+        let result = compiled.validate(instance);
+        if let Err(errors) = result {
+            let messages: Vec<String> = errors.map(|e| e.to_string()).collect();
+            panic!("{} output invalid: {:?}", kind.name(), messages);
+        }
+    }
+
+    #[test]
+    fn test_every_schema_is_versioned_and_named() {
+        for kind in OutputSchema::ALL {
+            let schema = schema_for(kind);
+            assert_eq!(schema["$schema"], SCHEMA_DIALECT);
+            assert!(schema["$id"].as_str().unwrap().ends_with(&format!(
+                "{}:{}",
+                kind.name(),
+                OUTPUT_SCHEMA_VERSION
+            )));
+            assert!(schema["properties"]["schema_version"].is_object());
+            assert_eq!(OutputSchema::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(all_schemas().as_object().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_serialized_outputs_validate() {
+        let snapshot = CostSnapshot::new("snap".to_string(), 120.0);
+        assert_valid(
+            OutputSchema::Snapshot,
+            &serde_json::to_value(&snapshot).unwrap(),
+        );
+
+        let exporter = JsonExporter::with_config(JsonExportConfig::default());
+        let map = exporter.export(&DependencyGraph::new()).unwrap();
+        assert_valid(OutputSchema::MapJson, &serde_json::from_str(&map).unwrap());
+
+        let mut stale = serde_json::to_value(&snapshot).unwrap();
+        stale["schema_version"] = json!("v1");
+        let compiled = JSONSchema::compile(&schema_for(OutputSchema::Snapshot)).unwrap();
+        assert!(!compiled.is_valid(&stale));
+    }
+
+    #[test]
+    fn test_compatibility_follows_major_version() {
+        assert!(is_compatible(OUTPUT_SCHEMA_VERSION));
+        assert!(is_compatible("1.4.0"));
+        assert!(!is_compatible("2.0.0"));
+        assert!(!is_compatible(""));
+    }
+}
@@ -1,9 +1,20 @@
 // Shared utilities and models
 
+pub mod cancel;
+pub mod compat;
 pub mod constants;
 pub mod deterministic_sort;
+pub mod engine_messages;
+pub mod error_codes;
 pub mod error_model;
+pub mod filter;
 pub mod json_schema;
 pub mod models;
+pub mod progress;
+pub mod pruning;
+pub mod redaction;
+pub mod result_cache;
 pub mod utils;
 pub mod wasm_bindings;
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+pub mod worker;
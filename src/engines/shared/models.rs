@@ -70,6 +70,36 @@ pub struct CostEstimate {
     pub hourly: Option<f64>,
     #[serde(default)]
     pub daily: Option<f64>,
+    /// How the figure was reached; absent on estimates from older engines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<EstimateProvenance>,
+}
+
+/// Machine-readable audit trail of a cost estimate
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EstimateProvenance {
+    /// Heuristic that priced the resource (e.g. `static:aws_instance`,
+    /// `kubernetes:<pool>`)
+    pub heuristic_id: String,
+    /// Version of the pricing data behind the heuristic, if versioned
+    #[serde(default)]
+    pub pricing_version: Option<String>,
+    /// Assumptions the figure depends on
+    #[serde(default)]
+    pub assumptions: Vec<String>,
+    /// Arithmetic from resource inputs to the final figure, in order
+    #[serde(default)]
+    pub steps: Vec<CalculationStep>,
+}
+
+/// A step in the cost calculation process
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculationStep {
+    pub step_number: usize,
+    pub operation: String,
+    pub input: String,
+    pub output: String,
+    pub reasoning: String,
 }
 
 /// Total cost summary
@@ -155,6 +185,7 @@ pub struct CostEstimateBuilder {
     confidence_score: Option<f64>,
     heuristic_reference: Option<String>,
     cold_start_inference: bool,
+    provenance: Option<EstimateProvenance>,
 }
 
 impl CostEstimateBuilder {
@@ -167,6 +198,7 @@ impl CostEstimateBuilder {
             confidence_score: None,
             heuristic_reference: None,
             cold_start_inference: false,
+            provenance: None,
         }
     }
 
@@ -205,6 +237,11 @@ impl CostEstimateBuilder {
         self
     }
 
+    pub fn provenance(mut self, val: EstimateProvenance) -> Self {
+        self.provenance = Some(val);
+        self
+    }
+
     pub fn build(self) -> CostEstimate {
         // Priority: explicit canonical > defaults
         let monthly_cost = self.monthly_cost.unwrap_or(0.0);
@@ -227,6 +264,7 @@ impl CostEstimateBuilder {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: self.provenance,
         }
     }
 }
@@ -246,6 +284,7 @@ impl CostEstimate {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: None,
         }
     }
 
@@ -275,6 +314,7 @@ impl Default for CostEstimate {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: None,
         }
     }
 }
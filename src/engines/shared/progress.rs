@@ -0,0 +1,208 @@
+// Progress reporting from long-running engine calls
+//
+// Engines report how far each phase has got at the same batch boundaries
+// where they check their `CancelToken`. Sinks are called from worker threads,
+// several at once while pipeline stages run in parallel, so they must be
+// `Sync` and should return quickly.
+
+use super::cancel::CancelToken;
+use super::error_model::Result;
+use serde::Serialize;
+use std::fmt;
+use std::sync::Arc;
+
+/// Unit of work an engine reports progress for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Prediction,
+    Analysis,
+    Explain,
+    Mapping,
+    Grouping,
+    Simulation,
+}
+
+impl Phase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Phase::Prediction => "Predicting costs",
+            Phase::Analysis => "Analyzing resources",
+            Phase::Explain => "Explaining findings",
+            Phase::Mapping => "Mapping dependencies",
+            Phase::Grouping => "Grouping costs",
+            Phase::Simulation => "Running simulation",
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// How far a phase has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Progress {
+    pub phase: Phase,
+    pub completed: usize,
+    pub total: usize,
+}
+
+impl Progress {
+    /// Percentage complete (100 for an empty phase)
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+        self.completed.min(self.total) as f64 * 100.0 / self.total as f64
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.completed >= self.total
+    }
+}
+
+/// Receives progress from engines
+pub trait ProgressSink: Send + Sync {
+    fn report(&self, progress: Progress);
+}
+
+impl<F> ProgressSink for F
+where
+    F: Fn(Progress) + Send + Sync,
+{
+    fn report(&self, progress: Progress) {
+        self(progress)
+    }
+}
+
+/// Shared handle engines hold; the default reports nowhere
+#[derive(Clone, Default)]
+pub struct ProgressReporter {
+    sink: Option<Arc<dyn ProgressSink>>,
+}
+
+impl ProgressReporter {
+    pub fn new(sink: Arc<dyn ProgressSink>) -> Self {
+        Self { sink: Some(sink) }
+    }
+
+    pub fn report(&self, phase: Phase, completed: usize, total: usize) {
+        if let Some(sink) = &self.sink {
+            sink.report(Progress {
+                phase,
+                completed,
+                total,
+            });
+        }
+    }
+
+    /// Run a single-step phase, reporting 0% before and 100% after
+    pub fn step<T>(&self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        self.report(phase, 0, 1);
+        let result = f();
+        self.report(phase, 1, 1);
+        result
+    }
+}
+
+impl fmt::Debug for ProgressReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("enabled", &self.sink.is_some())
+            .finish()
+    }
+}
+
+/// Map `items` batch by batch, checking `token` before each batch and
+/// reporting `phase` progress after it
+pub fn map_batches<T, R>(
+    items: &[T],
+    batch_size: usize,
+    phase: Phase,
+    token: &CancelToken,
+    progress: &ProgressReporter,
+    mut f: impl FnMut(&[T]) -> Result<Vec<R>>,
+) -> Result<Vec<R>> {
+    let mut results = Vec::with_capacity(items.len());
+    let mut completed = 0;
+    progress.report(phase, 0, items.len());
+    for batch in items.chunks(batch_size.max(1)) {
+        token.check(phase.label())?;
+        results.extend(f(batch)?);
+        completed += batch.len();
+        progress.report(phase, completed, items.len());
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn recorder() -> (ProgressReporter, Arc<Mutex<Vec<Progress>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let reporter = ProgressReporter::new(Arc::new(move |p: Progress| {
+            sink.lock().unwrap().push(p);
+        }));
+        (reporter, seen)
+    }
+
+    #[test]
+    fn test_map_batches_reports_each_batch() {
+        let (reporter, seen) = recorder();
+        let items: Vec<u32> = (0..10).collect();
+
+        let doubled = map_batches(
+            &items,
+            4,
+            Phase::Prediction,
+            &CancelToken::new(),
+            &reporter,
+            |batch| Ok(batch.iter().map(|i| i * 2).collect()),
+        )
+        .unwrap();
+
+        assert_eq!(doubled.len(), 10);
+        let completed: Vec<usize> = seen.lock().unwrap().iter().map(|p| p.completed).collect();
+        assert_eq!(completed, vec![0, 4, 8, 10]);
+        assert!(seen.lock().unwrap().last().unwrap().is_done());
+    }
+
+    #[test]
+    fn test_map_batches_stops_when_cancelled() {
+        let token = CancelToken::new();
+        let mut batches = 0;
+        let err = map_batches(
+            &[1, 2, 3, 4],
+            2,
+            Phase::Explain,
+            &token,
+            &ProgressReporter::default(),
+            |batch| {
+                batches += 1;
+                token.cancel();
+                Ok(batch.to_vec())
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(err.id, "CANCELLED");
+        assert_eq!(batches, 1);
+    }
+
+    #[test]
+    fn test_percent() {
+        let progress = |completed, total| Progress {
+            phase: Phase::Analysis,
+            completed,
+            total,
+        };
+        assert_eq!(progress(1, 4).percent(), 25.0);
+        assert_eq!(progress(0, 0).percent(), 100.0);
+    }
+}
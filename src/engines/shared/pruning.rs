@@ -0,0 +1,152 @@
+// Top-N and threshold pruning of report rows (`--top`, `--min-cost`)
+//
+// Large plans produce more groups, graph nodes and diff rows than anyone
+// reads. Reports keep the N largest rows and those worth at least a monthly
+// amount, and fold everything else into one explicit aggregate row, so the
+// rows shown still add up to the report's total.
+
+/// Environment variable read by the global `--top` flag
+pub const TOP_ENV_VAR: &str = "COSTPILOT_TOP";
+
+/// Environment variable read by the global `--min-cost` flag
+pub const MIN_COST_ENV_VAR: &str = "COSTPILOT_MIN_COST";
+
+/// Label of the aggregate row holding the pruned rows
+pub const REMAINDER_LABEL: &str = "everything else";
+
+/// Which report rows to keep
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Pruning {
+    /// Keep at most this many rows, largest first
+    pub top: Option<usize>,
+
+    /// Keep only rows worth at least this much per month
+    pub min_cost: Option<f64>,
+}
+
+impl Pruning {
+    pub fn new(top: Option<usize>, min_cost: Option<f64>) -> Self {
+        Self { top, min_cost }
+    }
+
+    /// Replace the row limit when a command has its own (e.g. `--max-groups`)
+    pub fn with_top(mut self, top: Option<usize>) -> Self {
+        if top.is_some() {
+            self.top = top;
+        }
+        self
+    }
+
+    pub fn with_min_cost(mut self, min_cost: f64) -> Self {
+        self.min_cost = Some(min_cost);
+        self
+    }
+
+    /// Whether any row can be pruned; a zero threshold keeps everything
+    pub fn is_active(&self) -> bool {
+        self.top.is_some() || self.min_cost.is_some_and(|min| min > 0.0)
+    }
+
+    /// Which of the rows with these monthly amounts to keep
+    ///
+    /// Rows are ranked by magnitude, so a large saving stays as visible as a
+    /// large increase; ties keep the earlier row.
+    pub fn keep_mask(&self, amounts: &[f64]) -> Vec<bool> {
+        let mut ranked: Vec<usize> = (0..amounts.len())
+            .filter(|&i| match self.min_cost {
+                Some(min) => amounts[i].abs() >= min,
+                None => true,
+            })
+            .collect();
+        ranked.sort_by(|&a, &b| amounts[b].abs().total_cmp(&amounts[a].abs()));
+        if let Some(top) = self.top {
+            ranked.truncate(top);
+        }
+
+        let mut keep = vec![false; amounts.len()];
+        for i in ranked {
+            keep[i] = true;
+        }
+        keep
+    }
+
+    /// The rows kept, in their original order, followed by one row built by
+    /// `fold` from all the others when any were pruned
+    pub fn apply<T>(
+        &self,
+        rows: Vec<T>,
+        amount: impl Fn(&T) -> f64,
+        fold: impl FnOnce(Vec<T>) -> T,
+    ) -> Vec<T> {
+        if !self.is_active() {
+            return rows;
+        }
+        let amounts: Vec<f64> = rows.iter().map(amount).collect();
+        let keep = self.keep_mask(&amounts);
+
+        let mut kept = Vec::new();
+        let mut pruned = Vec::new();
+        for (row, keep) in rows.into_iter().zip(keep) {
+            if keep {
+                kept.push(row);
+            } else {
+                pruned.push(row);
+            }
+        }
+        if !pruned.is_empty() {
+            kept.push(fold(pruned));
+        }
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fold(rows: Vec<(String, f64)>) -> (String, f64) {
+        (REMAINDER_LABEL.to_string(), rows.iter().map(|r| r.1).sum())
+    }
+
+    fn rows() -> Vec<(String, f64)> {
+        [
+            ("a", 5.0),
+            ("b", 120.0),
+            ("c", -80.0),
+            ("d", 1.5),
+            ("e", 40.0),
+        ]
+        .into_iter()
+        .map(|(name, cost)| (name.to_string(), cost))
+        .collect()
+    }
+
+    #[test]
+    fn test_top_keeps_largest_in_order_and_totals_reconcile() {
+        let total: f64 = rows().iter().map(|r| r.1).sum();
+        let pruned = Pruning::new(Some(2), None).apply(rows(), |r| r.1, fold);
+
+        let names: Vec<&str> = pruned.iter().map(|r| r.0.as_str()).collect();
+        assert_eq!(names, vec!["b", "c", REMAINDER_LABEL]);
+        assert_eq!(pruned[2].1, 46.5);
+        assert_eq!(pruned.iter().map(|r| r.1).sum::<f64>(), total);
+    }
+
+    #[test]
+    fn test_min_cost_and_inactive() {
+        let pruned = Pruning::new(None, Some(40.0)).apply(rows(), |r| r.1, fold);
+        let names: Vec<&str> = pruned.iter().map(|r| r.0.as_str()).collect();
+        assert_eq!(names, vec!["b", "c", "e", REMAINDER_LABEL]);
+
+        // Nothing pruned means no aggregate row
+        assert_eq!(
+            Pruning::new(Some(10), Some(0.0)).apply(rows(), |r| r.1, fold),
+            rows()
+        );
+        assert!(!Pruning::new(None, Some(0.0)).is_active());
+        assert_eq!(
+            Pruning::default().apply(rows(), |r| r.1, fold).len(),
+            rows().len()
+        );
+    }
+}
@@ -0,0 +1,520 @@
+// Redaction of shared reports (`--redact`, `redaction` in costpilot.yaml)
+//
+// Reports attached to public issues or sent to vendors should show costs and
+// findings without naming the infrastructure behind them. Resource and
+// module names are replaced by short salted hashes, keeping resource types
+// and the address structure so the same resource reads the same everywhere
+// in a report. Tags matching sensitive key patterns are dropped, and
+// identifier-like attribute values in resource configs are hashed.
+// Redaction applies to what is written out; analysis runs on the real names.
+
+use super::error_model::{CostPilotError, Result};
+use super::models::ResourceChange;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Environment variable read by the global `--redact` flag
+pub const REDACT_ENV_VAR: &str = "COSTPILOT_REDACT";
+
+/// Redaction settings (`redaction` in costpilot.yaml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedactionConfig {
+    /// Tag keys dropped from redacted output; `*` matches any run of
+    /// characters and matching ignores case
+    #[serde(default = "default_sensitive_tags")]
+    pub sensitive_tags: Vec<String>,
+
+    /// Resource attributes whose values are hashed, with the same patterns
+    #[serde(default = "default_identifier_attributes")]
+    pub identifier_attributes: Vec<String>,
+
+    /// Mixed into every hash so names cannot be confirmed by hashing guesses;
+    /// keep it stable to compare redacted reports over time
+    #[serde(default)]
+    pub salt: Option<String>,
+}
+
+fn default_sensitive_tags() -> Vec<String> {
+    [
+        "name",
+        "*owner*",
+        "*email*",
+        "*contact*",
+        "*created_by*",
+        "*createdby*",
+        "*user*",
+        "*team*",
+        "*secret*",
+        "*token*",
+        "*password*",
+        "*phone*",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+fn default_identifier_attributes() -> Vec<String> {
+    [
+        "id",
+        "arn",
+        "name",
+        "*_id",
+        "*_ids",
+        "*_arn",
+        "*_name",
+        "bucket",
+        "domain*",
+        "*_url",
+        "*endpoint*",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            sensitive_tags: default_sensitive_tags(),
+            identifier_attributes: default_identifier_attributes(),
+            salt: None,
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// Load `redaction` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `redaction` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `redaction` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        match root.get("redaction").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid redaction section: {}", e))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// Keys holding resource configuration, whose identifier attributes are hashed
+const CONFIG_KEYS: &[&str] = &["old_config", "new_config", "before", "after", "config"];
+
+/// Keys holding resource tags
+const TAG_KEYS: &[&str] = &["tags", "tags_all"];
+
+/// Hashes identifiers learned from a plan's resource changes
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    config: RedactionConfig,
+
+    /// Resource types in the plan; an address is recognized by its type
+    resource_types: HashSet<String>,
+
+    /// Resource ids and graph node ids, replaced wherever they appear as a token
+    ids: HashMap<String, String>,
+
+    /// Bare resource and module names, replaced only as a whole string (a
+    /// graph label or module field) so common words in messages survive
+    names: HashMap<String, String>,
+}
+
+impl Redactor {
+    pub fn new(config: RedactionConfig) -> Self {
+        Self {
+            config,
+            resource_types: HashSet::new(),
+            ids: HashMap::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// Register the resources of `changes` so their addresses are
+    /// recognized in any field or message
+    pub fn learn(mut self, changes: &[ResourceChange]) -> Self {
+        for change in changes {
+            self.resource_types.insert(change.resource_type.clone());
+        }
+        for change in changes {
+            let redacted = match self.address(&change.resource_id) {
+                Some(redacted) => {
+                    let segments = split_segments(&change.resource_id);
+                    let hashed = split_segments(&redacted);
+                    for (name, hashed) in segments.iter().zip(&hashed) {
+                        if name != hashed {
+                            self.names.insert(unindexed(name), unindexed(hashed));
+                        }
+                    }
+                    redacted
+                }
+                None => self.hash("r", &change.resource_id),
+            };
+            self.ids.insert(
+                crate::engines::mapping::stable_id(&change.resource_id),
+                crate::engines::mapping::stable_id(&redacted),
+            );
+            self.ids.insert(change.resource_id.clone(), redacted);
+
+            if let Some(module) = &change.module_path {
+                if self.address(module).is_none() {
+                    let hashed = self.hash("m", module);
+                    self.names.insert(module.clone(), hashed);
+                }
+            }
+        }
+        self
+    }
+
+    /// Whether tags with `key` are dropped
+    pub fn is_sensitive_tag(&self, key: &str) -> bool {
+        matches_any(&self.config.sensitive_tags, key)
+    }
+
+    /// `text` with every recognized identifier replaced
+    pub fn text(&self, text: &str) -> String {
+        if let Some(redacted) = self.names.get(text) {
+            return redacted.clone();
+        }
+        if let Some(redacted) = self.address(text) {
+            return redacted;
+        }
+
+        let mut output = String::with_capacity(text.len());
+        let mut token = String::new();
+        for c in text.chars() {
+            if is_token_char(c) {
+                token.push(c);
+            } else {
+                output.push_str(&self.token(&token));
+                token.clear();
+                output.push(c);
+            }
+        }
+        output.push_str(&self.token(&token));
+        output
+    }
+
+    /// Redact a JSON document in place
+    pub fn json(&self, value: &mut Value) {
+        self.walk(value, None, false);
+    }
+
+    /// Redacted copy of any serializable result
+    pub fn redact<T: Serialize + DeserializeOwned>(&self, value: &T) -> Result<T> {
+        let mut json = serde_json::to_value(value).map_err(|e| {
+            CostPilotError::serialization_error(format!("Failed to redact output: {}", e))
+        })?;
+        self.json(&mut json);
+        serde_json::from_value(json).map_err(|e| {
+            CostPilotError::serialization_error(format!("Failed to redact output: {}", e))
+        })
+    }
+
+    /// Redacted copies of `changes`, for exports built straight from a plan
+    pub fn changes(&self, changes: &[ResourceChange]) -> Result<Vec<ResourceChange>> {
+        self.redact(&changes.to_vec())
+    }
+
+    fn walk(&self, value: &mut Value, key: Option<&str>, in_config: bool) {
+        let in_config = in_config || key.is_some_and(|k| CONFIG_KEYS.contains(&k));
+        match value {
+            Value::Object(map) => {
+                let is_tags = key.is_some_and(|k| TAG_KEYS.contains(&k));
+                let entries = std::mem::take(map);
+                for (k, mut v) in entries {
+                    if is_tags && self.is_sensitive_tag(&k) {
+                        continue;
+                    }
+                    self.walk(&mut v, Some(&k), in_config);
+                    let k = if is_tags { k } else { self.text(&k) };
+                    map.insert(k, v);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.walk(item, key, in_config);
+                }
+            }
+            Value::String(s) => {
+                let identifier = in_config
+                    && key.is_some_and(|k| matches_any(&self.config.identifier_attributes, k));
+                let redacted = self.text(s);
+                // Identifier values are hashed whole unless they reference a
+                // resource, so dependency references still resolve
+                *s = if identifier && !s.is_empty() && redacted == *s {
+                    self.hash("v", s)
+                } else {
+                    redacted
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Redact a whitespace/punctuation-delimited token, including references
+    /// such as `aws_vpc.main.id` and sentence-ending punctuation
+    fn token(&self, token: &str) -> String {
+        if token.is_empty() {
+            return String::new();
+        }
+        let core = token.trim_end_matches(['.', ':']);
+        let suffix = &token[core.len()..];
+        if let Some(redacted) = self.ids.get(core) {
+            return format!("{}{}", redacted, suffix);
+        }
+        // Grouping names modules by path from the root (`root.vpc.subnets`)
+        if let Some(modules) = core.strip_prefix("root.") {
+            let hashed: Option<Vec<&str>> = modules
+                .split('.')
+                .map(|module| {
+                    self.names
+                        .get(module)
+                        .filter(|hashed| hashed.starts_with("m_"))
+                        .map(String::as_str)
+                })
+                .collect();
+            if let Some(hashed) = hashed {
+                return format!("root.{}{}", hashed.join("."), suffix);
+            }
+        }
+        match self.address(core) {
+            Some(redacted) => format!("{}{}", redacted, suffix),
+            None => token.to_string(),
+        }
+    }
+
+    /// Redacted Terraform address, or `None` when `address` does not start
+    /// with a module path or a resource type from the plan
+    fn address(&self, address: &str) -> Option<String> {
+        let segments = split_segments(address);
+        let mut output = Vec::with_capacity(segments.len());
+        let mut i = 0;
+        let mut changed = false;
+        while i + 1 < segments.len() && segments[i] == "module" {
+            output.push("module".to_string());
+            output.push(self.indexed("m", &segments[i + 1]));
+            i += 2;
+            changed = true;
+        }
+        if i + 1 < segments.len() && segments[i] == "data" {
+            output.push("data".to_string());
+            i += 1;
+        }
+        if i + 1 < segments.len() && self.resource_types.contains(&segments[i]) {
+            output.push(segments[i].clone());
+            output.push(self.indexed("r", &segments[i + 1]));
+            i += 2;
+            changed = true;
+        }
+        if !changed {
+            return None;
+        }
+        output.extend(segments[i..].iter().cloned());
+        Some(output.join("."))
+    }
+
+    /// Hash a name, keeping a numeric index and hashing a string key
+    fn indexed(&self, prefix: &str, segment: &str) -> String {
+        match segment.split_once('[') {
+            Some((name, index)) => {
+                let index = index.trim_end_matches(']');
+                let index = if index.parse::<u64>().is_ok() {
+                    index.to_string()
+                } else {
+                    format!("\"{}\"", self.hash("k", index.trim_matches('"')))
+                };
+                format!("{}[{}]", self.hash(prefix, name), index)
+            }
+            None => self.hash(prefix, segment),
+        }
+    }
+
+    fn hash(&self, prefix: &str, value: &str) -> String {
+        let mut hasher = Sha256::new();
+        if let Some(salt) = &self.config.salt {
+            hasher.update(salt.as_bytes());
+            hasher.update(b":");
+        }
+        hasher.update(value.as_bytes());
+        let digest: String = hasher.finalize()[..4]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        format!("{}_{}", prefix, digest)
+    }
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '[' | ']' | '"' | '/' | ':')
+}
+
+/// Split an address on `.` outside index brackets
+fn split_segments(address: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in address.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            '.' if depth == 0 => {
+                segments.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    segments.push(current);
+    segments
+}
+
+/// Name part of an address segment, as graph labels show it
+fn unindexed(segment: &str) -> String {
+    segment.split('[').next().unwrap_or(segment).to_string()
+}
+
+fn matches_any(patterns: &[String], key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    patterns.iter().any(|pattern| {
+        crate::engines::detection::custom_rules::wildcard_match(&pattern.to_ascii_lowercase(), &key)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+    use serde_json::json;
+
+    fn change(id: &str, resource_type: &str, module: Option<&str>) -> ResourceChange {
+        let mut builder = ResourceChange::builder()
+            .resource_id(id)
+            .resource_type(resource_type)
+            .action(ChangeAction::Create);
+        if let Some(module) = module {
+            builder = builder.module_path(module);
+        }
+        builder
+            .new_config(json!({
+                "instance_type": "t3.large",
+                "subnet_id": "subnet-0abc",
+                "vpc_id": "aws_vpc.main.id",
+                "tags": {"Name": "payments-web", "Owner": "alice@example.com", "Tier": "frontend"}
+            }))
+            .tags(HashMap::from([
+                ("Environment".to_string(), "prod".to_string()),
+                ("owner_email".to_string(), "alice@example.com".to_string()),
+            ]))
+            .build()
+    }
+
+    fn redactor() -> Redactor {
+        Redactor::new(RedactionConfig::default()).learn(&[
+            change(
+                "module.payments.aws_instance.web[0]",
+                "aws_instance",
+                Some("payments"),
+            ),
+            change("aws_nat_gateway.main", "aws_nat_gateway", None),
+        ])
+    }
+
+    #[test]
+    fn test_addresses_keep_types() {
+        let redactor = redactor();
+        let redacted = redactor.text("module.payments.aws_instance.web[0]");
+
+        assert!(redacted.starts_with("module.m_"));
+        assert!(redacted.contains(".aws_instance.r_"));
+        assert!(redacted.ends_with("[0]"));
+        assert!(!redacted.contains("payments"));
+        assert!(!redacted.contains("web"));
+        // Deterministic, so the same resource matches across fields
+        assert_eq!(
+            redacted,
+            redactor.text("module.payments.aws_instance.web[0]")
+        );
+    }
+
+    #[test]
+    fn test_messages_and_references() {
+        let redactor = redactor();
+        let message = redactor
+            .text("NAT gateway aws_nat_gateway.main costs $32/mo. See aws_nat_gateway.main.id.");
+        assert!(!message.contains("main"));
+        assert!(message.starts_with("NAT gateway aws_nat_gateway.r_"));
+        assert!(message.ends_with(".id."));
+        assert_eq!(redactor.text("payments"), redactor.text("payments"));
+        assert!(redactor.text("payments").starts_with("m_"));
+        assert_eq!(
+            redactor.text("root.payments"),
+            format!("root.{}", redactor.text("payments"))
+        );
+        assert_eq!(redactor.text("t3.large costs more"), "t3.large costs more");
+    }
+
+    #[test]
+    fn test_changes_drop_sensitive_tags() {
+        let redactor = redactor();
+        let changes = redactor
+            .changes(&[change("aws_nat_gateway.main", "aws_nat_gateway", None)])
+            .unwrap();
+        let redacted = &changes[0];
+
+        assert!(redacted.tags.contains_key("Environment"));
+        assert!(!redacted.tags.contains_key("owner_email"));
+        let config = redacted.new_config.as_ref().unwrap();
+        assert_eq!(config["instance_type"], "t3.large");
+        assert!(config["subnet_id"].as_str().unwrap().starts_with("v_"));
+        assert!(config["tags"].get("Owner").is_none());
+        assert!(config["tags"].get("Name").is_none());
+        assert_eq!(config["tags"]["Tier"], "frontend");
+    }
+
+    #[test]
+    fn test_salt_changes_hashes() {
+        let salted = Redactor::new(RedactionConfig {
+            salt: Some("s3cret".to_string()),
+            ..RedactionConfig::default()
+        })
+        .learn(&[change("aws_nat_gateway.main", "aws_nat_gateway", None)]);
+
+        assert_ne!(
+            salted.text("aws_nat_gateway.main"),
+            redactor().text("aws_nat_gateway.main")
+        );
+    }
+
+    #[test]
+    fn test_config_section() {
+        let config =
+            RedactionConfig::from_yaml("redaction:\n  sensitive_tags: [\"cost_center\"]\n")
+                .unwrap();
+        assert_eq!(config.sensitive_tags, vec!["cost_center"]);
+        assert!(!config.identifier_attributes.is_empty());
+        assert_eq!(
+            RedactionConfig::from_yaml("version: \"1\"\n").unwrap(),
+            RedactionConfig::default()
+        );
+    }
+}
@@ -0,0 +1,420 @@
+// Content-addressed cache for per-resource prediction and detection results
+//
+// Entries are keyed by a hash of the normalized resource config plus the
+// engine, heuristics and pricing versions. A manifest records the versions
+// the cache was written with; opening it with different versions wipes it.
+
+use crate::engines::shared::error_model::{CostPilotError, Result};
+use crate::engines::shared::models::ResourceChange;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Default cache location, relative to the project root
+pub const DEFAULT_CACHE_DIR: &str = ".costpilot/cache";
+
+/// Bumped when the key or entry layout changes
+pub const CACHE_SCHEMA_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Versions that invalidate every cached entry when they change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheVersions {
+    pub schema: u32,
+    /// CostPilot build that produced the entries
+    pub engine: String,
+    pub heuristics: String,
+    pub pricing: String,
+    /// Hash of the usage profile estimates were priced from
+    #[serde(default)]
+    pub usage: String,
+}
+
+impl CacheVersions {
+    pub fn new(heuristics: impl Into<String>, pricing: impl Into<String>) -> Self {
+        Self {
+            schema: CACHE_SCHEMA_VERSION,
+            engine: env!("CARGO_PKG_VERSION").to_string(),
+            heuristics: heuristics.into(),
+            pricing: pricing.into(),
+            usage: String::new(),
+        }
+    }
+
+    pub fn with_usage(mut self, usage: impl Into<String>) -> Self {
+        self.usage = usage.into();
+        self
+    }
+}
+
+/// Kind of result stored in the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    Prediction,
+    Detection,
+}
+
+impl CacheKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheKind::Prediction => "prediction",
+            CacheKind::Detection => "detection",
+        }
+    }
+}
+
+/// Hit/miss counters for a cache session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// On-disk result cache (safe to share across threads)
+#[derive(Debug)]
+pub struct ResultCache {
+    root: PathBuf,
+    versions: CacheVersions,
+    invalidated: bool,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl ResultCache {
+    /// Open the cache at `root`, discarding entries written under other versions
+    pub fn open(root: impl Into<PathBuf>, versions: CacheVersions) -> Result<Self> {
+        let root = root.into();
+        let manifest_path = root.join(MANIFEST_FILE);
+
+        let stored: Option<CacheVersions> = std::fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+
+        let invalidated = stored.as_ref().is_some_and(|s| s != &versions);
+        if stored.as_ref() != Some(&versions) {
+            if root.exists() {
+                std::fs::remove_dir_all(&root).map_err(|e| {
+                    CostPilotError::io_error(format!("Failed to clear result cache: {}", e))
+                })?;
+            }
+            std::fs::create_dir_all(&root).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to create result cache: {}", e))
+            })?;
+            let json = serde_json::to_string_pretty(&versions).map_err(|e| {
+                CostPilotError::serialization_error(format!(
+                    "Failed to serialize cache manifest: {}",
+                    e
+                ))
+            })?;
+            std::fs::write(&manifest_path, json).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to write cache manifest: {}", e))
+            })?;
+        }
+
+        Ok(Self {
+            root,
+            versions,
+            invalidated,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn versions(&self) -> &CacheVersions {
+        &self.versions
+    }
+
+    /// True if opening the cache discarded entries from older versions
+    pub fn was_invalidated(&self) -> bool {
+        self.invalidated
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Cache key for a resource; `extra` covers inputs beyond the config
+    /// (e.g. the cost estimate a detection was computed from)
+    pub fn key(&self, kind: CacheKind, change: &ResourceChange, extra: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(kind.as_str().as_bytes());
+        hasher.update([0]);
+        hasher.update(config_hash(change).as_bytes());
+        hasher.update([0]);
+        hasher.update(extra.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.versions.engine.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.versions.heuristics.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.versions.pricing.as_bytes());
+        hasher.update([0]);
+        hasher.update(self.versions.usage.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Cached value for `key`; unreadable entries count as misses
+    pub fn get<T: DeserializeOwned>(&self, kind: CacheKind, key: &str) -> Option<T> {
+        std::fs::read_to_string(self.entry_path(kind, key))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+    }
+
+    /// Store a value; failures are ignored since the cache is best-effort
+    pub fn put<T: Serialize>(&self, kind: CacheKind, key: &str, value: &T) {
+        let path = self.entry_path(kind, key);
+        let Ok(json) = serde_json::to_string(value) else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            if std::fs::create_dir_all(parent).is_err() {
+                return;
+            }
+        }
+        let _ = std::fs::write(path, json);
+    }
+
+    /// Per-resource results for `changes`, running `compute` once over the
+    /// uncached resources only. Results keep the order of `changes`.
+    pub fn resolve<T, E, C, R>(
+        &self,
+        kind: CacheKind,
+        changes: &[ResourceChange],
+        extra: E,
+        compute: C,
+        resource_of: R,
+    ) -> Result<Vec<T>>
+    where
+        T: Serialize + DeserializeOwned,
+        E: Fn(&ResourceChange) -> String,
+        C: FnOnce(&[ResourceChange]) -> Result<Vec<T>>,
+        R: Fn(&T) -> &str,
+    {
+        let keys: Vec<String> = changes
+            .iter()
+            .map(|change| self.key(kind, change, &extra(change)))
+            .collect();
+        let mut slots: Vec<Option<Vec<T>>> = keys.iter().map(|k| self.get(kind, k)).collect();
+
+        let misses: Vec<ResourceChange> = changes
+            .iter()
+            .zip(&slots)
+            .filter(|(_, slot)| slot.is_none())
+            .map(|(change, _)| change.clone())
+            .collect();
+        self.hits
+            .fetch_add(changes.len() - misses.len(), Ordering::Relaxed);
+        self.misses.fetch_add(misses.len(), Ordering::Relaxed);
+
+        let mut unmatched = Vec::new();
+        if !misses.is_empty() {
+            let mut by_resource: HashMap<String, Vec<T>> = HashMap::new();
+            for result in compute(&misses)? {
+                by_resource
+                    .entry(resource_of(&result).to_string())
+                    .or_default()
+                    .push(result);
+            }
+
+            for (i, change) in changes.iter().enumerate() {
+                if slots[i].is_none() {
+                    let results = by_resource.remove(&change.resource_id).unwrap_or_default();
+                    self.put(kind, &keys[i], &results);
+                    slots[i] = Some(results);
+                }
+            }
+
+            // Results not tied to a single resource are returned but never cached
+            let mut rest: Vec<_> = by_resource.into_iter().collect();
+            rest.sort_by(|a, b| a.0.cmp(&b.0));
+            unmatched.extend(rest.into_iter().flat_map(|(_, results)| results));
+        }
+
+        Ok(slots
+            .into_iter()
+            .flatten()
+            .flatten()
+            .chain(unmatched)
+            .collect())
+    }
+
+    /// Remove all cached entries
+    pub fn clear(&self) -> Result<()> {
+        for kind in [CacheKind::Prediction, CacheKind::Detection] {
+            let dir = self.root.join(kind.as_str());
+            if dir.exists() {
+                std::fs::remove_dir_all(&dir).map_err(|e| {
+                    CostPilotError::io_error(format!("Failed to clear result cache: {}", e))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn entry_path(&self, kind: CacheKind, key: &str) -> PathBuf {
+        self.root
+            .join(kind.as_str())
+            .join(&key[..2])
+            .join(format!("{}.json", key))
+    }
+}
+
+/// Hash of a resource's normalized config; object keys are sorted so
+/// formatting differences in the plan do not produce new keys
+pub fn config_hash(change: &ResourceChange) -> String {
+    let mut tags: Vec<_> = change.tags.iter().collect();
+    tags.sort();
+
+    let normalized = serde_json::json!({
+        "resource_id": change.resource_id,
+        "resource_type": change.resource_type,
+        "action": change.action,
+        "module_path": change.module_path,
+        "old_config": change.old_config.as_ref().map(normalize),
+        "new_config": change.new_config.as_ref().map(normalize),
+        "config": change.config.as_ref().map(normalize),
+        "tags": tags,
+        "monthly_cost": change.monthly_cost,
+    });
+
+    hex::encode(Sha256::digest(
+        normalize(&normalized).to_string().as_bytes(),
+    ))
+}
+
+fn normalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), normalize(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(normalize).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::ChangeAction;
+    use std::cell::Cell;
+    use tempfile::TempDir;
+
+    fn change(id: &str, instance_type: &str) -> ResourceChange {
+        ResourceChange {
+            resource_id: id.to_string(),
+            resource_type: "aws_instance".to_string(),
+            action: ChangeAction::Create,
+            module_path: None,
+            old_config: None,
+            new_config: Some(serde_json::json!({
+                "instance_type": instance_type,
+                "ami": "ami-123",
+            })),
+            tags: HashMap::new(),
+            monthly_cost: None,
+            config: None,
+            cost_impact: None,
+        }
+    }
+
+    fn compute_ids(changes: &[ResourceChange]) -> Result<Vec<String>> {
+        Ok(changes.iter().map(|c| c.resource_id.clone()).collect())
+    }
+
+    #[test]
+    fn test_resolve_only_computes_misses() {
+        let dir = TempDir::new().unwrap();
+        let cache = ResultCache::open(dir.path(), CacheVersions::new("h1", "p1")).unwrap();
+        let changes = vec![change("aws_instance.a", "t3.micro")];
+
+        let first = cache
+            .resolve(
+                CacheKind::Prediction,
+                &changes,
+                |_| String::new(),
+                compute_ids,
+                |id| id,
+            )
+            .unwrap();
+        assert_eq!(first, vec!["aws_instance.a"]);
+
+        // Second run adds a resource; only it is computed
+        let changes = vec![change("aws_instance.b", "m5.large"), changes[0].clone()];
+        let computed = Cell::new(0);
+        let second = cache
+            .resolve(
+                CacheKind::Prediction,
+                &changes,
+                |_| String::new(),
+                |misses| {
+                    computed.set(misses.len());
+                    compute_ids(misses)
+                },
+                |id| id,
+            )
+            .unwrap();
+
+        assert_eq!(computed.get(), 1);
+        assert_eq!(second, vec!["aws_instance.b", "aws_instance.a"]);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 2 });
+    }
+
+    #[test]
+    fn test_version_change_invalidates() {
+        let dir = TempDir::new().unwrap();
+        let c = change("aws_instance.a", "t3.micro");
+
+        let cache = ResultCache::open(dir.path(), CacheVersions::new("h1", "p1")).unwrap();
+        let key = cache.key(CacheKind::Detection, &c, "");
+        cache.put(CacheKind::Detection, &key, &vec!["cached"]);
+        assert!(cache
+            .get::<Vec<String>>(CacheKind::Detection, &key)
+            .is_some());
+
+        let reopened = ResultCache::open(dir.path(), CacheVersions::new("h1", "p1")).unwrap();
+        assert!(!reopened.was_invalidated());
+        assert!(reopened
+            .get::<Vec<String>>(CacheKind::Detection, &key)
+            .is_some());
+
+        let repriced = ResultCache::open(dir.path(), CacheVersions::new("h1", "p2")).unwrap();
+        assert!(repriced.was_invalidated());
+        assert!(repriced
+            .get::<Vec<String>>(CacheKind::Detection, &key)
+            .is_none());
+        // A new usage profile reprices usage-billed resources
+        let reprofiled =
+            ResultCache::open(dir.path(), CacheVersions::new("h1", "p2").with_usage("u1")).unwrap();
+        assert!(reprofiled.was_invalidated());
+    }
+
+    #[test]
+    fn test_config_hash_ignores_key_order() {
+        let a = change("aws_instance.a", "t3.micro");
+        let mut b = a.clone();
+        b.new_config = Some(serde_json::json!({ "ami": "ami-123", "instance_type": "t3.micro" }));
+        assert_eq!(config_hash(&a), config_hash(&b));
+
+        let resized = change("aws_instance.a", "t3.large");
+        assert_ne!(config_hash(&a), config_hash(&resized));
+    }
+}
@@ -1 +1,59 @@
 // Shared utilities
+
+/// Module name used for resources outside any module
+pub const ROOT_MODULE: &str = "root";
+
+/// Module address as it appears on resource changes (`module.web`, `root`)
+/// for a name taken from a bill or a tag (`web`, `module.web`, empty)
+pub fn module_address(module: &str) -> String {
+    let module = module.trim();
+    if module.is_empty() || module.eq_ignore_ascii_case(ROOT_MODULE) {
+        ROOT_MODULE.to_string()
+    } else if module.starts_with("module.") {
+        module.to_string()
+    } else {
+        format!("module.{}", module)
+    }
+}
+
+/// Split one CSV line, honouring double-quoted fields
+pub fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_csv_line_handles_quotes() {
+        assert_eq!(
+            split_csv_line(r#"module.web, "1,240.50","say ""hi""""#),
+            vec!["module.web", "1,240.50", r#"say "hi""#]
+        );
+    }
+
+    #[test]
+    fn test_module_address() {
+        assert_eq!(module_address("web"), "module.web");
+        assert_eq!(module_address("module.web"), "module.web");
+        assert_eq!(module_address(" ROOT "), "root");
+        assert_eq!(module_address(""), "root");
+    }
+}
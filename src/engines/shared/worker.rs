@@ -0,0 +1,107 @@
+// Runtime-agnostic async wrappers over blocking engine calls
+//
+// Engines are CPU-bound and synchronous. `run_blocking` moves a call onto a
+// dedicated worker thread and returns a plain `Future`, so any executor
+// (tokio, async-std, smol, `futures::executor`) can await it without its
+// own blocking pool. Dropping the future cancels the token, which is how a
+// caller's timeout (`tokio::time::timeout`, `select!`) reaches the engine.
+
+use super::cancel::CancelToken;
+use super::error_model::{CostPilotError, ErrorCategory, Result};
+use futures::channel::oneshot;
+use std::future::Future;
+
+/// Cancels the token when dropped unless the call finished
+struct CancelOnDrop(Option<CancelToken>);
+
+impl CancelOnDrop {
+    fn disarm(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(token) = self.0.take() {
+            token.cancel();
+        }
+    }
+}
+
+/// Run `call` on a worker thread; the future resolves to its result
+pub fn run_blocking<T, F>(token: CancelToken, call: F) -> impl Future<Output = Result<T>> + Send
+where
+    F: FnOnce(&CancelToken) -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = oneshot::channel();
+    let worker_token = token.clone();
+    let spawned = std::thread::Builder::new()
+        .name("costpilot-worker".to_string())
+        .spawn(move || {
+            // The receiver is gone when the caller stopped waiting
+            let _ = sender.send(call(&worker_token));
+        });
+    let mut guard = CancelOnDrop(Some(token));
+
+    async move {
+        spawned.map_err(|e| {
+            CostPilotError::new(
+                "ASYNC_001",
+                ErrorCategory::InternalError,
+                format!("Failed to start worker thread: {}", e),
+            )
+        })?;
+        let result = receiver.await.map_err(|_| {
+            CostPilotError::new(
+                "ASYNC_002",
+                ErrorCategory::InternalError,
+                "Worker thread stopped without a result",
+            )
+        });
+        guard.disarm();
+        result?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::time::Duration;
+
+    #[test]
+    fn test_resolves_to_call_result() {
+        let value = block_on(run_blocking(CancelToken::new(), |_| Ok(42))).unwrap();
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn test_panicking_call_is_an_error() {
+        let err = block_on(run_blocking::<(), _>(CancelToken::new(), |_| {
+            panic!("engine bug")
+        }))
+        .unwrap_err();
+        assert_eq!(err.id, "ASYNC_002");
+    }
+
+    #[test]
+    fn test_dropping_future_cancels_token() {
+        let token = CancelToken::new();
+        let future = run_blocking(token.clone(), |token| {
+            while !token.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            token.check("test")
+        });
+        drop(future);
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn test_completed_call_leaves_token_usable() {
+        let token = CancelToken::new();
+        block_on(run_blocking(token.clone(), |_| Ok(()))).unwrap();
+        assert!(!token.is_cancelled());
+    }
+}
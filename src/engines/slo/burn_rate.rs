@@ -114,6 +114,87 @@ impl BurnReport {
     }
 }
 
+/// One episode of a replayed burn alert: the snapshot where the SLO first
+/// reached High or Critical risk, and the one where it dropped back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurnFiring {
+    /// Snapshot at which the alert fired
+    pub snapshot_id: String,
+
+    /// Timestamp of that snapshot
+    pub fired_at: String,
+
+    /// Risk level when the alert fired
+    pub risk: BurnRisk,
+
+    /// Burn rate (dollars per day) when the alert fired
+    pub burn_rate: f64,
+
+    /// Predicted days to breach when the alert fired (None if already exceeded)
+    pub days_to_breach: Option<f64>,
+
+    /// Timestamp at which risk dropped below High (None if still firing)
+    pub resolved_at: Option<String>,
+}
+
+/// Replay of one SLO over the snapshot history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SloReplay {
+    pub slo_id: String,
+    pub slo_name: String,
+    pub slo_limit: f64,
+
+    /// Snapshots with enough history before them to analyze
+    pub points_evaluated: usize,
+
+    /// Snapshots at which the alert was active
+    pub points_alerting: usize,
+
+    /// Alert episodes, oldest first
+    pub firings: Vec<BurnFiring>,
+
+    /// First snapshot timestamp at which actual cost reached the limit
+    pub first_breach: Option<String>,
+
+    /// Days between the first alert and the first breach (None without
+    /// both, or when the alert only fired at the breach itself)
+    pub lead_time_days: Option<f64>,
+}
+
+impl SloReplay {
+    /// Alert episodes that cleared before the SLO ever breached
+    pub fn false_alarms(&self) -> usize {
+        self.firings
+            .iter()
+            .filter(|f| match (&f.resolved_at, &self.first_breach) {
+                (Some(resolved), Some(breach)) => resolved < breach,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+            .count()
+    }
+}
+
+/// Burn alerts the current SLO definitions would have raised over the
+/// recorded snapshot history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurnReplay {
+    /// Per-SLO replays, in SLO definition order
+    pub slos: Vec<SloReplay>,
+
+    /// Snapshots replayed
+    pub total_snapshots: usize,
+
+    /// Timestamp of the oldest snapshot
+    pub from: Option<String>,
+
+    /// Timestamp of the newest snapshot
+    pub to: Option<String>,
+
+    /// Report generation timestamp
+    pub generated_at: String,
+}
+
 /// Burn rate calculator using linear regression
 pub struct BurnRateCalculator {
     /// Minimum snapshots required for analysis
@@ -212,6 +293,86 @@ impl BurnRateCalculator {
         BurnReport::new(analyses)
     }
 
+    /// Replay the snapshot history oldest first, analyzing each SLO with only
+    /// the snapshots recorded up to that point, as `slo burn` would have
+    /// then, and record when its alert would have fired and cleared
+    pub fn replay(&self, slos: &[Slo], snapshots: &[CostSnapshot]) -> BurnReplay {
+        let mut sorted = snapshots.to_vec();
+        sorted.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        BurnReplay {
+            slos: slos
+                .iter()
+                .map(|slo| self.replay_slo(slo, &sorted))
+                .collect(),
+            total_snapshots: sorted.len(),
+            from: sorted.first().map(|s| s.timestamp.clone()),
+            to: sorted.last().map(|s| s.timestamp.clone()),
+            generated_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    fn replay_slo(&self, slo: &Slo, sorted: &[CostSnapshot]) -> SloReplay {
+        let mut replay = SloReplay {
+            slo_id: slo.id.clone(),
+            slo_name: slo.name.clone(),
+            slo_limit: slo.threshold.max_value,
+            points_evaluated: 0,
+            points_alerting: 0,
+            firings: Vec::new(),
+            first_breach: None,
+            lead_time_days: None,
+        };
+
+        for end in 1..=sorted.len() {
+            let snapshot = &sorted[end - 1];
+            let history = &sorted[..end];
+
+            if replay.first_breach.is_none() {
+                let current = self.extract_data_points(slo, history).last().map(|p| p.1);
+                if current.is_some_and(|cost| cost >= replay.slo_limit) {
+                    replay.first_breach = Some(snapshot.timestamp.clone());
+                }
+            }
+
+            let Some(analysis) = self.analyze_slo(slo, history) else {
+                continue;
+            };
+            replay.points_evaluated += 1;
+
+            let active = replay
+                .firings
+                .last()
+                .is_some_and(|f| f.resolved_at.is_none());
+            if analysis.risk.requires_action() {
+                replay.points_alerting += 1;
+                if !active {
+                    replay.firings.push(BurnFiring {
+                        snapshot_id: snapshot.id.clone(),
+                        fired_at: snapshot.timestamp.clone(),
+                        risk: analysis.risk,
+                        burn_rate: analysis.burn_rate,
+                        days_to_breach: analysis.days_to_breach,
+                        resolved_at: None,
+                    });
+                }
+            } else if active {
+                if let Some(firing) = replay.firings.last_mut() {
+                    firing.resolved_at = Some(snapshot.timestamp.clone());
+                }
+            }
+        }
+
+        replay.lead_time_days = replay.first_breach.as_ref().and_then(|breach| {
+            let first = replay.firings.first()?;
+            let days = (parse_timestamp(breach)? - parse_timestamp(&first.fired_at)?).num_hours()
+                as f64
+                / 24.0;
+            (days > 0.0).then_some(days)
+        });
+        replay
+    }
+
     /// Extract (day, cost) data points for SLO from snapshots
     fn extract_data_points(&self, slo: &Slo, snapshots: &[CostSnapshot]) -> Vec<(f64, f64)> {
         let mut points = Vec::new();
@@ -329,6 +490,12 @@ impl BurnRateCalculator {
     }
 }
 
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
 impl Default for BurnRateCalculator {
     fn default() -> Self {
         Self::new()
@@ -338,11 +505,13 @@ impl Default for BurnRateCalculator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
     use std::collections::HashMap;
 
     fn create_test_snapshots() -> Vec<CostSnapshot> {
         vec![
             CostSnapshot {
+                schema_version: OUTPUT_SCHEMA_VERSION.to_string(),
                 id: "snap1".to_string(),
                 timestamp: "2024-01-01T00:00:00Z".to_string(),
                 commit_hash: None,
@@ -363,8 +532,10 @@ mod tests {
                 regressions: vec![],
                 slo_violations: vec![],
                 metadata: None,
+                actuals: None,
             },
             CostSnapshot {
+                schema_version: OUTPUT_SCHEMA_VERSION.to_string(),
                 id: "snap2".to_string(),
                 timestamp: "2024-01-08T00:00:00Z".to_string(),
                 commit_hash: None,
@@ -385,8 +556,10 @@ mod tests {
                 regressions: vec![],
                 slo_violations: vec![],
                 metadata: None,
+                actuals: None,
             },
             CostSnapshot {
+                schema_version: OUTPUT_SCHEMA_VERSION.to_string(),
                 id: "snap3".to_string(),
                 timestamp: "2024-01-15T00:00:00Z".to_string(),
                 commit_hash: None,
@@ -407,6 +580,7 @@ mod tests {
                 regressions: vec![],
                 slo_violations: vec![],
                 metadata: None,
+                actuals: None,
             },
         ]
     }
@@ -518,6 +692,68 @@ mod tests {
         assert!(analysis.is_none());
     }
 
+    fn snapshot_at(day: u32, total_monthly_cost: f64) -> CostSnapshot {
+        CostSnapshot {
+            id: format!("snap-{}", day),
+            timestamp: format!("2024-01-{:02}T00:00:00Z", day),
+            total_monthly_cost,
+            modules: HashMap::new(),
+            ..create_test_snapshots()[0].clone()
+        }
+    }
+
+    #[test]
+    fn test_replay_fires_before_breach() {
+        let calculator = BurnRateCalculator::new();
+        // Newest first, to check the replay sorts by timestamp
+        let snapshots: Vec<CostSnapshot> = [
+            (29, 5200.0),
+            (22, 4200.0),
+            (15, 3000.0),
+            (8, 2000.0),
+            (1, 1000.0),
+        ]
+        .iter()
+        .map(|&(day, cost)| snapshot_at(day, cost))
+        .collect();
+
+        let replay = calculator.replay(&[create_test_slo()], &snapshots);
+        assert_eq!(replay.total_snapshots, 5);
+        assert_eq!(replay.from.as_deref(), Some("2024-01-01T00:00:00Z"));
+
+        let slo = &replay.slos[0];
+        assert_eq!(slo.points_evaluated, 3);
+        assert_eq!(slo.first_breach.as_deref(), Some("2024-01-29T00:00:00Z"));
+        assert_eq!(slo.firings.len(), 1);
+        assert_eq!(slo.firings[0].fired_at, "2024-01-22T00:00:00Z");
+        assert_eq!(slo.lead_time_days, Some(7.0));
+        assert_eq!(slo.false_alarms(), 0);
+    }
+
+    #[test]
+    fn test_replay_records_false_alarm() {
+        let calculator = BurnRateCalculator::new();
+        let snapshots: Vec<CostSnapshot> = [
+            (1, 1000.0),
+            (2, 2500.0),
+            (3, 4000.0),
+            (10, 1000.0),
+            (17, 1000.0),
+        ]
+        .iter()
+        .map(|&(day, cost)| snapshot_at(day, cost))
+        .collect();
+
+        let replay = calculator.replay(&[create_test_slo()], &snapshots);
+        let slo = &replay.slos[0];
+        assert_eq!(slo.firings.len(), 1);
+        assert_eq!(slo.firings[0].fired_at, "2024-01-03T00:00:00Z");
+        assert!(slo.firings[0].resolved_at.is_some());
+        assert!(slo.first_breach.is_none());
+        assert_eq!(slo.lead_time_days, None);
+        assert_eq!(slo.false_alarms(), 1);
+    }
+
     #[test]
     fn test_burn_risk_severity() {
         assert_eq!(BurnRisk::Low.severity(), 0);
@@ -3,7 +3,9 @@ pub mod slo_engine;
 pub mod slo_manager;
 pub mod slo_types;
 
-pub use burn_rate::{BurnAnalysis, BurnRateCalculator, BurnReport};
+pub use burn_rate::{
+    BurnAnalysis, BurnFiring, BurnRateCalculator, BurnReplay, BurnReport, SloReplay,
+};
 pub use slo_engine::{SloDefinition, SloEngine, SloResult};
 pub use slo_manager::SloManager;
 pub use slo_types::{
@@ -248,6 +248,7 @@ mod tests {
             breakdown: None,
             hourly: None,
             daily: None,
+            provenance: None,
         }];
 
         (total_cost, estimates)
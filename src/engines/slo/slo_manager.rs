@@ -165,11 +165,10 @@ impl SloManager {
             // Gate enforcement for premium
             let mut slo_copy = slo.clone();
             if slo_copy.enforcement == EnforcementLevel::Block && !self.edition.is_premium() {
-                eprintln!(
-                    "⚠️  Free edition: SLO '{}' downgraded from Block to Warn (validate-only)",
-                    slo_copy.id
+                tracing::warn!(
+                    slo = %slo_copy.id,
+                    "Free edition: SLO downgraded from Block to Warn (validate-only); upgrade to Premium to enforce SLO blocking"
                 );
-                eprintln!("   Upgrade to Premium to enforce SLO blocking");
                 slo_copy.enforcement = EnforcementLevel::Warn;
             }
 
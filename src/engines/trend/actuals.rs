@@ -0,0 +1,433 @@
+// Billing actuals - offline import of AWS cost exports
+//
+// Reads a Cost and Usage Report (CUR) or a Cost Explorer CSV download and
+// totals what was billed by module (through a resource tag) and by service,
+// so a snapshot can carry the bill next to the prediction. Nothing is fetched
+// from AWS; the export is read as downloaded.
+
+use super::snapshot_types::CostSnapshot;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::engines::shared::utils::{module_address, split_csv_line};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Tag whose value names the Terraform module a resource belongs to
+pub const DEFAULT_MODULE_TAG: &str = "module";
+
+/// Bucket for billed costs that carry no module tag
+pub const UNTAGGED: &str = "untagged";
+
+/// CUR columns holding the cost of a line item (legacy, then CUR 2.0)
+const CUR_COST: [&str; 2] = ["lineitem/unblendedcost", "line_item_unblended_cost"];
+
+/// CUR columns naming the service of a line item
+const CUR_SERVICE: [&str; 4] = [
+    "product/productname",
+    "product_product_name",
+    "lineitem/productcode",
+    "line_item_product_code",
+];
+
+/// CUR columns giving the month a line item was billed in
+const CUR_PERIOD: [&str; 4] = [
+    "bill/billingperiodstartdate",
+    "bill_billing_period_start_date",
+    "lineitem/usagestartdate",
+    "line_item_usage_start_date",
+];
+
+/// Cost Explorer appends this to every cost column header
+const CE_COST_SUFFIX: &str = "($)";
+
+/// Which kind of export the costs came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BillingSource {
+    CostAndUsageReport,
+    CostExplorer,
+}
+
+/// What was actually billed for one month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActualCosts {
+    pub source: BillingSource,
+
+    /// Billing month (`YYYY-MM`) when the export names one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+
+    pub total_monthly_cost: f64,
+
+    /// Keyed like snapshot modules (`module.web`, `root`); costs without the
+    /// module tag are under `untagged`. Empty when the export has no tags.
+    #[serde(default)]
+    pub modules: BTreeMap<String, f64>,
+
+    /// Keyed by the service name used in the export
+    #[serde(default)]
+    pub services: BTreeMap<String, f64>,
+}
+
+/// Predicted and billed cost of one module
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActualsVariance {
+    pub module: String,
+    pub predicted: f64,
+    pub actual: f64,
+}
+
+impl ActualsVariance {
+    pub fn delta(&self) -> f64 {
+        self.actual - self.predicted
+    }
+
+    /// Billed cost relative to the prediction; `None` when nothing was predicted
+    pub fn percent(&self) -> Option<f64> {
+        (self.predicted > 0.0).then(|| self.delta() / self.predicted * 100.0)
+    }
+}
+
+/// Predicted against billed cost for every module in either, sorted by name
+pub fn predicted_vs_actual(snapshot: &CostSnapshot, actuals: &ActualCosts) -> Vec<ActualsVariance> {
+    let modules: BTreeSet<&String> = snapshot
+        .modules
+        .keys()
+        .chain(actuals.modules.keys())
+        .collect();
+    modules
+        .into_iter()
+        .map(|module| ActualsVariance {
+            module: module.clone(),
+            predicted: snapshot.modules.get(module).map_or(0.0, |m| m.monthly_cost),
+            actual: actuals.modules.get(module).copied().unwrap_or(0.0),
+        })
+        .collect()
+}
+
+/// Reads CUR and Cost Explorer CSV exports into [`ActualCosts`]
+#[derive(Debug, Clone)]
+pub struct BillingImporter {
+    module_tag: String,
+    period: Option<String>,
+}
+
+impl Default for BillingImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BillingImporter {
+    pub fn new() -> Self {
+        Self {
+            module_tag: DEFAULT_MODULE_TAG.to_string(),
+            period: None,
+        }
+    }
+
+    /// Tag key whose value names the module (default `module`)
+    pub fn with_module_tag(mut self, tag: impl Into<String>) -> Self {
+        self.module_tag = tag.into();
+        self
+    }
+
+    /// Only count costs billed in this month (`YYYY-MM`)
+    pub fn with_period(mut self, period: impl Into<String>) -> Self {
+        self.period = Some(period.into());
+        self
+    }
+
+    /// Parse an export, recognising its kind from the header row
+    pub fn import(&self, content: &str) -> Result<ActualCosts> {
+        let mut rows = content
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim_start_matches('\u{feff}').trim()))
+            .filter(|(_, line)| !line.is_empty());
+        let (_, header) = rows
+            .next()
+            .ok_or_else(|| invalid("Billing export is empty".to_string()))?;
+        let columns = split_csv_line(header);
+        let lowered: Vec<String> = columns.iter().map(|c| c.to_lowercase()).collect();
+
+        if find_column(&lowered, &CUR_COST).is_some() {
+            self.import_cur(&lowered, rows)
+        } else if columns.iter().skip(1).any(|c| c.ends_with(CE_COST_SUFFIX)) {
+            self.import_cost_explorer(&columns, rows)
+        } else {
+            Err(invalid(
+                "Unrecognised billing export: expected a Cost and Usage Report or a Cost Explorer CSV download".to_string(),
+            ))
+        }
+    }
+
+    fn import_cur<'a>(
+        &self,
+        columns: &[String],
+        rows: impl Iterator<Item = (usize, &'a str)>,
+    ) -> Result<ActualCosts> {
+        let cost_col = find_column(columns, &CUR_COST).unwrap_or_default();
+        let service_col = find_column(columns, &CUR_SERVICE);
+        let period_col = find_column(columns, &CUR_PERIOD);
+        let tag = self.module_tag.to_lowercase();
+        let tag_col = find_column(
+            columns,
+            &[
+                format!("resourcetags/user:{}", tag).as_str(),
+                format!("resource_tags_user_{}", tag).as_str(),
+            ],
+        );
+
+        let mut billed = Billed::default();
+        for (line_no, line) in rows {
+            let fields = split_csv_line(line);
+            let field = |idx: Option<usize>| {
+                idx.and_then(|i| fields.get(i))
+                    .map(String::as_str)
+                    .unwrap_or("")
+            };
+            let cost = parse_cost(field(Some(cost_col)), line_no)?;
+            let period = month(field(period_col));
+            if !self.in_period(period.as_deref()) {
+                continue;
+            }
+            let module = tag_col.map(|_| match field(tag_col) {
+                "" => UNTAGGED.to_string(),
+                value => module_address(value),
+            });
+            billed.add(period, module, Some(field(service_col)), cost);
+        }
+        billed.finish(BillingSource::CostAndUsageReport)
+    }
+
+    /// Cost Explorer downloads have one column per group (`EC2-Instances($)`
+    /// when grouped by service, `module$web($)` when grouped by tag) and one
+    /// row per period, after a `... total` row
+    fn import_cost_explorer<'a>(
+        &self,
+        columns: &[String],
+        rows: impl Iterator<Item = (usize, &'a str)>,
+    ) -> Result<ActualCosts> {
+        let by_service = columns[0].eq_ignore_ascii_case("service");
+        let groups: Vec<Option<String>> = columns
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| {
+                let group = column.strip_suffix(CE_COST_SUFFIX)?.trim();
+                (idx > 0 && !group.eq_ignore_ascii_case("total costs")).then(|| group.to_string())
+            })
+            .collect();
+        if !by_service
+            && groups
+                .iter()
+                .flatten()
+                .any(|g| !g.contains('$') && !g.to_lowercase().starts_with("no tag"))
+        {
+            return Err(invalid(
+                "Cost Explorer export must be grouped by Service or by the module tag".to_string(),
+            ));
+        }
+
+        let mut billed = Billed::default();
+        for (line_no, line) in rows {
+            let fields = split_csv_line(line);
+            let label = fields.first().map(String::as_str).unwrap_or("");
+            if label.to_lowercase().ends_with(" total") {
+                continue;
+            }
+            let period = month(label);
+            if !self.in_period(period.as_deref()) {
+                continue;
+            }
+            for (idx, group) in groups.iter().enumerate() {
+                let Some(group) = group else { continue };
+                let raw = fields.get(idx).map(String::as_str).unwrap_or("");
+                if raw.is_empty() {
+                    continue;
+                }
+                let cost = parse_cost(raw, line_no)?;
+                if by_service {
+                    billed.add(period.clone(), None, Some(group), cost);
+                } else {
+                    billed.add(period.clone(), Some(tag_group_module(group)), None, cost);
+                }
+            }
+        }
+        billed.finish(BillingSource::CostExplorer)
+    }
+
+    fn in_period(&self, period: Option<&str>) -> bool {
+        match (&self.period, period) {
+            (Some(wanted), Some(period)) => wanted == period,
+            _ => true,
+        }
+    }
+}
+
+/// Running totals while reading an export
+#[derive(Default)]
+struct Billed {
+    periods: BTreeSet<String>,
+    total: f64,
+    modules: BTreeMap<String, f64>,
+    services: BTreeMap<String, f64>,
+    rows: usize,
+}
+
+impl Billed {
+    fn add(
+        &mut self,
+        period: Option<String>,
+        module: Option<String>,
+        service: Option<&str>,
+        cost: f64,
+    ) {
+        self.rows += 1;
+        self.periods.extend(period);
+        self.total += cost;
+        if let Some(module) = module {
+            *self.modules.entry(module).or_default() += cost;
+        }
+        if let Some(service) = service.filter(|s| !s.is_empty()) {
+            *self.services.entry(service.to_string()).or_default() += cost;
+        }
+    }
+
+    fn finish(self, source: BillingSource) -> Result<ActualCosts> {
+        if self.rows == 0 {
+            return Err(invalid("Billing export has no costs to import".to_string()));
+        }
+        if self.periods.len() > 1 {
+            let periods: Vec<String> = self.periods.into_iter().collect();
+            return Err(invalid(format!(
+                "Billing export covers several months ({}); pick one with --period",
+                periods.join(", ")
+            )));
+        }
+        Ok(ActualCosts {
+            source,
+            period: self.periods.into_iter().next(),
+            total_monthly_cost: self.total,
+            modules: self.modules,
+            services: self.services,
+        })
+    }
+}
+
+fn invalid(message: String) -> CostPilotError {
+    CostPilotError::new("ACTUALS_001", ErrorCategory::InvalidInput, message).with_hint(
+        "Export a Cost and Usage Report or download a Cost Explorer report as CSV for one month",
+    )
+}
+
+fn find_column(columns: &[String], names: &[&str]) -> Option<usize> {
+    names
+        .iter()
+        .find_map(|name| columns.iter().position(|c| c == name))
+}
+
+fn parse_cost(raw: &str, line_no: usize) -> Result<f64> {
+    raw.trim_start_matches('$')
+        .replace(',', "")
+        .parse()
+        .ok()
+        .filter(|v: &f64| v.is_finite())
+        .ok_or_else(|| invalid(format!("Line {}: invalid cost '{}'", line_no, raw)))
+}
+
+/// `YYYY-MM` from a date or timestamp cell such as `2024-05-01T00:00:00Z`
+fn month(date: &str) -> Option<String> {
+    let month = date.get(..7)?;
+    let valid = month.char_indices().all(|(i, c)| match i {
+        4 => c == '-',
+        _ => c.is_ascii_digit(),
+    });
+    valid.then(|| month.to_string())
+}
+
+/// Module of a Cost Explorer tag group such as `module$web`; `module$` and
+/// `No tag key: module` are untagged costs
+fn tag_group_module(group: &str) -> String {
+    match group.split_once('$') {
+        Some((_, value)) if !value.trim().is_empty() => module_address(value),
+        _ => UNTAGGED.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_cur_groups_by_module_tag_and_service() {
+        let cur = "\
+identity/LineItemId,bill/BillingPeriodStartDate,lineItem/ProductCode,product/ProductName,lineItem/UnblendedCost,resourceTags/user:module
+a,2024-05-01T00:00:00Z,AmazonEC2,Amazon Elastic Compute Cloud,120.50,web
+b,2024-05-01T00:00:00Z,AmazonEC2,Amazon Elastic Compute Cloud,30,root
+c,2024-05-01T00:00:00Z,AmazonS3,Amazon Simple Storage Service,\"1,000.00\",web
+d,2024-05-01T00:00:00Z,AWSSupport,AWS Support,9.50,
+";
+        let actuals = BillingImporter::new().import(cur).unwrap();
+
+        assert_eq!(actuals.source, BillingSource::CostAndUsageReport);
+        assert_eq!(actuals.period.as_deref(), Some("2024-05"));
+        assert_eq!(actuals.total_monthly_cost, 1160.0);
+        assert_eq!(actuals.modules["module.web"], 1120.5);
+        assert_eq!(actuals.modules["root"], 30.0);
+        assert_eq!(actuals.modules[UNTAGGED], 9.5);
+        assert_eq!(actuals.services["Amazon Simple Storage Service"], 1000.0);
+    }
+
+    #[test]
+    fn test_import_cost_explorer_by_tag_and_by_service() {
+        let by_tag = "\
+\"Tag\",\"module$web($)\",\"module$($)\",\"Total costs($)\"
+\"Tag total\",\"250\",\"40\",\"290\"
+\"2024-04-01\",\"100\",\"10\",\"110\"
+\"2024-05-01\",\"150\",\"30\",\"180\"
+";
+        assert!(BillingImporter::new().import(by_tag).is_err());
+        let actuals = BillingImporter::new()
+            .with_period("2024-05")
+            .import(by_tag)
+            .unwrap();
+        assert_eq!(actuals.source, BillingSource::CostExplorer);
+        assert_eq!(actuals.total_monthly_cost, 180.0);
+        assert_eq!(actuals.modules["module.web"], 150.0);
+        assert_eq!(actuals.modules[UNTAGGED], 30.0);
+
+        let by_service = "\
+Service,EC2-Instances($),S3($),Total costs($)
+Service total,95,5,100
+2024-05-01,95,,95
+";
+        let actuals = BillingImporter::new().import(by_service).unwrap();
+        assert!(actuals.modules.is_empty());
+        assert_eq!(actuals.services["EC2-Instances"], 95.0);
+        assert_eq!(actuals.total_monthly_cost, 95.0);
+    }
+
+    #[test]
+    fn test_predicted_vs_actual_covers_both_sides() {
+        let mut snapshot = CostSnapshot::new("snap".to_string(), 150.0);
+        snapshot.add_module("module.web".to_string(), 100.0, 2);
+        snapshot.add_module("root".to_string(), 50.0, 1);
+        let actuals = ActualCosts {
+            source: BillingSource::CostAndUsageReport,
+            period: None,
+            total_monthly_cost: 140.0,
+            modules: BTreeMap::from([
+                ("module.web".to_string(), 120.0),
+                (UNTAGGED.to_string(), 20.0),
+            ]),
+            services: BTreeMap::new(),
+        };
+
+        let rows = predicted_vs_actual(&snapshot, &actuals);
+        let modules: Vec<&str> = rows.iter().map(|r| r.module.as_str()).collect();
+        assert_eq!(modules, vec!["module.web", "root", UNTAGGED]);
+        assert_eq!(rows[0].percent(), Some(20.0));
+        assert_eq!(rows[1].delta(), -50.0);
+        assert_eq!(rows[2].percent(), None);
+    }
+}
@@ -11,6 +11,11 @@ pub struct HtmlGenerator;
 impl HtmlGenerator {
     /// Wrap SVG in HTML document
     pub fn wrap_svg(svg: &str, title: &str) -> String {
+        Self::wrap(svg, title, "Cost trend visualization")
+    }
+
+    /// Wrap any HTML fragment in the trend report page
+    pub fn wrap(content: &str, title: &str, subtitle: &str) -> String {
         let mut html = String::new();
 
         writeln!(&mut html, "<!DOCTYPE html>").unwrap();
@@ -33,12 +38,13 @@ impl HtmlGenerator {
         writeln!(&mut html, "      <h1>{}</h1>", Self::escape_html(title)).unwrap();
         writeln!(
             &mut html,
-            "      <p class=\"subtitle\">Cost trend visualization</p>"
+            "      <p class=\"subtitle\">{}</p>",
+            Self::escape_html(subtitle)
         )
         .unwrap();
         writeln!(&mut html, "    </header>").unwrap();
         writeln!(&mut html, "    <div class=\"graph-container\">").unwrap();
-        writeln!(&mut html, "{}", svg).unwrap();
+        writeln!(&mut html, "{}", content).unwrap();
         writeln!(&mut html, "    </div>").unwrap();
         writeln!(&mut html, "    <footer>").unwrap();
         writeln!(&mut html, "      <p>Generated by CostPilot</p>").unwrap();
@@ -125,6 +131,28 @@ impl HtmlGenerator {
       height: auto;
     }
 
+    .variance {
+      width: 100%;
+    }
+
+    table {
+      width: 100%;
+      border-collapse: collapse;
+      margin-bottom: 20px;
+    }
+
+    th, td {
+      text-align: left;
+      padding: 6px 10px;
+      border-bottom: 1px solid #e2e8f0;
+    }
+
+    h2 {
+      font-size: 1.2rem;
+      color: #1a202c;
+      margin: 10px 0;
+    }
+
     footer {
       text-align: center;
       margin-top: 30px;
@@ -151,7 +179,7 @@ impl HtmlGenerator {
     }
 
     /// Escape HTML special characters
-    fn escape_html(text: &str) -> String {
+    pub(crate) fn escape_html(text: &str) -> String {
         text.replace('&', "&amp;")
             .replace('<', "&lt;")
             .replace('>', "&gt;")
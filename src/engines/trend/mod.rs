@@ -1,11 +1,17 @@
 // Trend engine module for cost tracking and visualization
 
+pub mod actuals;
 mod html_generator;
 mod snapshot_manager;
 pub mod snapshot_types;
 mod svg_generator;
 mod trend_diff;
+pub mod variance;
 
+pub use actuals::{
+    predicted_vs_actual, ActualCosts, ActualsVariance, BillingImporter, BillingSource,
+    DEFAULT_MODULE_TAG,
+};
 pub use html_generator::HtmlGenerator;
 pub use snapshot_manager::SnapshotManager;
 pub use snapshot_types::*;
@@ -14,6 +20,7 @@ pub use trend_diff::{
     ChangeType, DiffSummary, ModuleChange, ServiceChange, TrendDiff, TrendDiffGenerator,
     TrendDirection,
 };
+pub use variance::{BiasMetrics, ModuleVariance, PeriodVariance, VarianceReport};
 
 use crate::engines::baselines::{BaselineViolation, BaselinesManager};
 use crate::errors::CostPilotError;
@@ -107,6 +114,32 @@ impl TrendEngine {
         self.snapshot_manager.load_history()
     }
 
+    /// Attach billed costs to a snapshot (the latest when `snapshot_id` is
+    /// `None`) and save it, replacing any actuals imported before
+    pub fn import_actuals(
+        &self,
+        snapshot_id: Option<&str>,
+        actuals: ActualCosts,
+    ) -> Result<CostSnapshot, CostPilotError> {
+        let mut snapshot = match snapshot_id {
+            Some(id) => self.snapshot_manager.read_snapshot(id)?,
+            None => self.load_history()?.latest().cloned().ok_or_else(|| {
+                CostPilotError::file_not_found(
+                    "No snapshots to attach actuals to; run 'costpilot trend snapshot' first"
+                        .to_string(),
+                )
+            })?,
+        };
+        snapshot.actuals = Some(actuals);
+        self.save_snapshot(&snapshot)?;
+        Ok(snapshot)
+    }
+
+    /// Compare predictions with imported actuals across the history
+    pub fn variance_report(&self) -> Result<VarianceReport, CostPilotError> {
+        Ok(VarianceReport::from_history(&self.load_history()?))
+    }
+
     /// Generate SVG graph from history
     pub fn generate_svg(&self) -> Result<String, CostPilotError> {
         let history = self.load_history()?;
@@ -1,25 +1,25 @@
 use chrono::{Duration, Utc};
-use serde_json;
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use super::snapshot_types::{CostSnapshot, TrendConfig, TrendHistory};
+use crate::engines::shared::compat::{self, Artifact};
 use crate::errors::CostPilotError;
+use crate::security::at_rest::StoreEncryption;
 
 /// Manages snapshot storage and rotation
 pub struct SnapshotManager {
     storage_dir: PathBuf,
     config: TrendConfig,
+    encryption: StoreEncryption,
 }
 
 impl SnapshotManager {
-    /// Create a new snapshot manager
+    /// Create a new snapshot manager, encrypting snapshots when the project
+    /// turns on `storage.encrypt_at_rest`
     pub fn new<P: AsRef<Path>>(storage_dir: P) -> Self {
-        Self {
-            storage_dir: storage_dir.as_ref().to_path_buf(),
-            config: TrendConfig::default(),
-        }
+        Self::with_config(storage_dir, TrendConfig::default())
     }
 
     /// Create a new snapshot manager with custom config
@@ -27,9 +27,16 @@ impl SnapshotManager {
         Self {
             storage_dir: storage_dir.as_ref().to_path_buf(),
             config,
+            encryption: StoreEncryption::from_project(),
         }
     }
 
+    /// Read and write snapshots with `encryption` instead of the project settings
+    pub fn with_encryption(mut self, encryption: StoreEncryption) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
     /// Initialize storage directory
     pub fn init(&self) -> Result<(), CostPilotError> {
         if !self.storage_dir.exists() {
@@ -51,10 +58,10 @@ impl SnapshotManager {
         let filename = format!("snapshot_{}.json", snapshot.id);
         let filepath = self.storage_dir.join(&filename);
 
-        // Serialize to pretty JSON
-        let json = serde_json::to_string_pretty(snapshot).map_err(|e| {
-            CostPilotError::serialization_error(format!("Failed to serialize snapshot: {}", e))
-        })?;
+        // Serialize stamped with this build's version, encrypted when storage
+        // encryption is on
+        let json = compat::to_string_pretty(Artifact::Snapshot, snapshot)?;
+        let json = self.encryption.seal(&json)?;
 
         // Write to file
         let mut file = File::create(&filepath).map_err(|e| {
@@ -80,9 +87,9 @@ impl SnapshotManager {
 
         let contents = fs::read_to_string(&filepath)
             .map_err(|e| CostPilotError::io_error(format!("Failed to read snapshot: {}", e)))?;
+        let contents = self.encryption.open(&contents)?;
 
-        let snapshot: CostSnapshot = serde_json::from_str(&contents)
-            .map_err(|e| CostPilotError::parse_error(format!("Failed to parse snapshot: {}", e)))?;
+        let (snapshot, _) = compat::read::<CostSnapshot>(Artifact::Snapshot, &contents)?;
 
         Ok(snapshot)
     }
@@ -118,7 +125,7 @@ impl SnapshotManager {
                     match self.read_snapshot(id) {
                         Ok(snapshot) => history.add_snapshot(snapshot),
                         Err(e) => {
-                            eprintln!("Warning: Failed to load snapshot {}: {}", id, e);
+                            tracing::warn!(snapshot = id, "Failed to load snapshot: {}", e);
                         }
                     }
                 }
@@ -398,4 +405,38 @@ mod tests {
 
         assert_eq!(manager.count_snapshots().unwrap(), 2);
     }
+
+    #[test]
+    fn test_encrypted_snapshots() {
+        use crate::security::at_rest::{is_encrypted, AtRestCipher};
+
+        let temp_dir = TempDir::new().unwrap();
+        let cipher = AtRestCipher::new([7; 32]);
+        let manager = SnapshotManager::new(temp_dir.path())
+            .with_encryption(StoreEncryption::with_cipher(cipher.clone(), true));
+
+        let snapshot = CostSnapshot::new("test-001".to_string(), 1234.56);
+        let path = manager.write_snapshot(&snapshot).unwrap();
+        let contents = fs::read_to_string(path).unwrap();
+        assert!(is_encrypted(&contents));
+        assert!(!contents.contains("test-001"));
+
+        // Plaintext snapshots from before encryption still load
+        SnapshotManager::new(temp_dir.path())
+            .with_encryption(StoreEncryption::disabled())
+            .write_snapshot(&CostSnapshot::new("test-002".to_string(), 10.0))
+            .unwrap();
+        assert_eq!(manager.count_snapshots().unwrap(), 2);
+
+        let reader = SnapshotManager::new(temp_dir.path())
+            .with_encryption(StoreEncryption::with_cipher(cipher, false));
+        assert_eq!(
+            reader.read_snapshot("test-001").unwrap().total_monthly_cost,
+            1234.56
+        );
+        let other_key = SnapshotManager::new(temp_dir.path()).with_encryption(
+            StoreEncryption::with_cipher(AtRestCipher::new([8; 32]), false),
+        );
+        assert_eq!(other_key.detect_corruption().unwrap(), vec!["test-001"]);
+    }
 }
@@ -1,3 +1,5 @@
+use super::actuals::ActualCosts;
+use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -5,6 +7,10 @@ use std::collections::HashMap;
 /// A single cost snapshot at a point in time
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostSnapshot {
+    /// Output layout version (see `engines::shared::json_schema`)
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: String,
+
     /// Unique identifier for this snapshot
     pub id: String,
 
@@ -39,6 +45,15 @@ pub struct CostSnapshot {
     /// Metadata about the snapshot
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<SnapshotMetadata>,
+
+    /// Billed costs imported with `costpilot trend import-actuals`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actuals: Option<ActualCosts>,
+}
+
+/// Snapshots written before versioning use the 1.0.0 layout
+fn legacy_schema_version() -> String {
+    "1.0.0".to_string()
 }
 
 /// Cost information for a specific module
@@ -219,6 +234,7 @@ impl CostSnapshot {
     /// Create a new snapshot with current timestamp
     pub fn new(id: String, total_monthly_cost: f64) -> Self {
         Self {
+            schema_version: OUTPUT_SCHEMA_VERSION.to_string(),
             id,
             timestamp: Utc::now().to_rfc3339(),
             commit_hash: None,
@@ -229,6 +245,7 @@ impl CostSnapshot {
             regressions: Vec::new(),
             slo_violations: Vec::new(),
             metadata: None,
+            actuals: None,
         }
     }
 
@@ -52,6 +52,9 @@ pub struct SvgConfig {
     /// Primary color for line
     pub line_color: String,
 
+    /// Color of the billed-cost line drawn when snapshots carry actuals
+    pub actual_color: String,
+
     /// Background color
     pub background_color: String,
 
@@ -70,6 +73,7 @@ impl Default for SvgConfig {
             padding: 40,
             show_grid: true,
             line_color: "#2563eb".to_string(),
+            actual_color: "#16a34a".to_string(),
             background_color: "#ffffff".to_string(),
             show_points: true,
             point_radius: 4,
@@ -123,6 +127,12 @@ impl SvgGenerator {
             .snapshots
             .iter()
             .map(|s| s.total_monthly_cost)
+            .chain(
+                history
+                    .snapshots
+                    .iter()
+                    .filter_map(|s| s.actuals.as_ref().map(|a| a.total_monthly_cost)),
+            )
             .collect();
 
         let min_cost = costs.iter().cloned().fold(f64::INFINITY, f64::min);
@@ -166,6 +176,9 @@ impl SvgGenerator {
         };
         self.draw_cost_line(&mut svg, &cost_line_params);
 
+        // Draw billed costs next to the predictions
+        self.draw_actual_line(&mut svg, &cost_line_params);
+
         // Draw regression annotations
         self.draw_regression_annotations(&mut svg, &cost_line_params);
 
@@ -312,6 +325,65 @@ impl SvgGenerator {
         writeln!(svg, "  </g>").unwrap();
     }
 
+    fn draw_actual_line(&self, svg: &mut String, params: &CostLineParams) {
+        let points: Vec<(f64, f64)> = params
+            .snapshots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, snapshot)| {
+                let actual = snapshot.actuals.as_ref()?.total_monthly_cost;
+                let x_pos = params.x
+                    + (i as f64 / (params.snapshots.len() - 1).max(1) as f64) * params.width;
+                let y_pos = params.y + params.height
+                    - ((actual - params.y_min) / params.y_range) * params.height;
+                Some((x_pos, y_pos))
+            })
+            .collect();
+        if points.is_empty() {
+            return;
+        }
+
+        writeln!(
+            svg,
+            r#"  <g id="actual-line" stroke="{}" stroke-width="2" stroke-dasharray="6 4" fill="none">"#,
+            self.config.actual_color
+        )
+        .unwrap();
+
+        let mut path = String::from("    <path d=\"");
+        for (i, (x_pos, y_pos)) in points.iter().enumerate() {
+            let command = if i == 0 { "M" } else { " L" };
+            write!(&mut path, "{} {} {}", command, x_pos, y_pos).unwrap();
+        }
+        path.push_str("\"/>");
+        writeln!(svg, "{}", path).unwrap();
+
+        for (x_pos, y_pos) in &points {
+            writeln!(
+                svg,
+                r#"    <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                x_pos - self.config.point_radius as f64,
+                y_pos - self.config.point_radius as f64,
+                self.config.point_radius * 2,
+                self.config.point_radius * 2,
+                self.config.actual_color
+            )
+            .unwrap();
+        }
+
+        // Legend
+        writeln!(
+            svg,
+            r#"    <text x="{}" y="{}" stroke="none" fill="{}" font-family="monospace" font-size="12" text-anchor="end">- - billed</text>"#,
+            params.x + params.width,
+            params.y - 10.0,
+            self.config.actual_color
+        )
+        .unwrap();
+
+        writeln!(svg, "  </g>").unwrap();
+    }
+
     fn draw_regression_annotations(&self, svg: &mut String, params: &CostLineParams) {
         writeln!(svg, r#"  <g id="regressions">"#).unwrap();
 
@@ -451,6 +523,30 @@ mod tests {
         assert!(svg.contains("<svg"));
         assert!(svg.contains("</svg>"));
         assert!(svg.contains("cost-line"));
+        assert!(!svg.contains("actual-line"));
+    }
+
+    #[test]
+    fn test_generate_draws_actuals() {
+        use crate::engines::trend::{ActualCosts, BillingSource};
+
+        let generator = SvgGenerator::new();
+        let mut history = TrendHistory::new();
+        let mut billed = CostSnapshot::new("snap-001".to_string(), 1000.0);
+        billed.actuals = Some(ActualCosts {
+            source: BillingSource::CostExplorer,
+            period: Some("2024-05".to_string()),
+            total_monthly_cost: 1300.0,
+            modules: Default::default(),
+            services: Default::default(),
+        });
+        history.add_snapshot(billed);
+        history.add_snapshot(CostSnapshot::new("snap-002".to_string(), 1200.0));
+
+        let svg = generator.generate(&history).unwrap();
+        assert!(svg.contains("actual-line"));
+        // Y axis reaches the billed total, above every prediction
+        assert!(svg.contains("$1330"));
     }
 
     #[test]
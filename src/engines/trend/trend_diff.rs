@@ -1,7 +1,10 @@
 // Trend diff generator - compares two snapshots and generates human-readable diff
 
+use crate::engines::shared::filter::{Field, Filterable};
+use crate::engines::shared::pruning::{Pruning, REMAINDER_LABEL};
 use crate::engines::trend::snapshot_types::{CostSnapshot, ModuleCost, Regression};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Diff between two cost snapshots
@@ -96,6 +99,91 @@ pub enum ChangeType {
     Unchanged,
 }
 
+impl ModuleChange {
+    /// One "everything else" row totalling changes pruned from a diff
+    pub fn remainder(changes: Vec<ModuleChange>) -> Self {
+        let cost_before: f64 = changes.iter().map(|c| c.cost_before).sum();
+        let cost_after: f64 = changes.iter().map(|c| c.cost_after).sum();
+        let delta: f64 = changes.iter().map(|c| c.delta).sum();
+        let change_type = if delta > 0.01 {
+            ChangeType::Increased
+        } else if delta < -0.01 {
+            ChangeType::Decreased
+        } else {
+            ChangeType::Unchanged
+        };
+        Self {
+            module: REMAINDER_LABEL.to_string(),
+            cost_before,
+            cost_after,
+            delta,
+            percent: change_percent(cost_before, cost_after),
+            change_type,
+        }
+    }
+}
+
+impl ServiceChange {
+    /// One "everything else" row totalling changes pruned from a diff
+    pub fn remainder(changes: Vec<ServiceChange>) -> Self {
+        let cost_before: f64 = changes.iter().map(|c| c.cost_before).sum();
+        let cost_after: f64 = changes.iter().map(|c| c.cost_after).sum();
+        Self {
+            service: REMAINDER_LABEL.to_string(),
+            cost_before,
+            cost_after,
+            delta: changes.iter().map(|c| c.delta).sum(),
+            percent: change_percent(cost_before, cost_after),
+        }
+    }
+}
+
+/// Percentage change from `before` to `after`; anything new counts as 100%
+fn change_percent(before: f64, after: f64) -> f64 {
+    if before > 0.0 {
+        ((after - before) / before) * 100.0
+    } else if after > 0.0 {
+        100.0
+    } else {
+        0.0
+    }
+}
+
+impl Filterable for ModuleChange {
+    fn text(&self, field: &Field) -> Option<Cow<'_, str>> {
+        match field {
+            Field::Module => Some(Cow::Borrowed(&self.module)),
+            Field::Action => Some(Cow::Owned(format!("{:?}", self.change_type).to_lowercase())),
+            _ => None,
+        }
+    }
+
+    fn number(&self, field: &Field) -> Option<f64> {
+        match field {
+            Field::Cost => Some(self.cost_after),
+            Field::Delta => Some(self.delta),
+            _ => None,
+        }
+    }
+}
+
+impl Filterable for ServiceChange {
+    fn text(&self, field: &Field) -> Option<Cow<'_, str>> {
+        match field {
+            Field::Service => Some(Cow::Borrowed(&self.service)),
+            _ => None,
+        }
+    }
+
+    fn number(&self, field: &Field) -> Option<f64> {
+        match field {
+            Field::Cost => Some(self.cost_after),
+            Field::Delta => Some(self.delta),
+            _ => None,
+        }
+    }
+}
+
 /// Summary statistics for diff
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiffSummary {
@@ -135,6 +223,17 @@ pub enum TrendDirection {
     Stable,
 }
 
+impl TrendDiff {
+    /// Keep the module and service changes with the largest deltas, folding
+    /// the rest into "everything else" rows so they still sum to the total
+    pub fn prune(&mut self, pruning: &Pruning) {
+        let modules = std::mem::take(&mut self.module_changes);
+        self.module_changes = pruning.apply(modules, |c| c.delta, ModuleChange::remainder);
+        let services = std::mem::take(&mut self.service_changes);
+        self.service_changes = pruning.apply(services, |c| c.delta, ServiceChange::remainder);
+    }
+}
+
 /// Trend diff generator
 pub struct TrendDiffGenerator;
 
@@ -211,13 +310,7 @@ impl TrendDiffGenerator {
                 .unwrap_or(0.0);
 
             let delta = to_cost - from_cost;
-            let percent = if from_cost > 0.0 {
-                (delta / from_cost) * 100.0
-            } else if to_cost > 0.0 {
-                100.0
-            } else {
-                0.0
-            };
+            let percent = change_percent(from_cost, to_cost);
 
             let change_type = if from_cost == 0.0 && to_cost > 0.0 {
                 ChangeType::Added
@@ -263,13 +356,7 @@ impl TrendDiffGenerator {
             let to_cost = to_services.get(&service_name).copied().unwrap_or(0.0);
 
             let delta = to_cost - from_cost;
-            let percent = if from_cost > 0.0 {
-                (delta / from_cost) * 100.0
-            } else if to_cost > 0.0 {
-                100.0
-            } else {
-                0.0
-            };
+            let percent = change_percent(from_cost, to_cost);
 
             changes.push(ServiceChange {
                 service: service_name,
@@ -432,6 +519,8 @@ impl TrendDiffGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engines::shared::filter::FilterExpr;
+    use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
 
     fn create_test_snapshot(
         id: &str,
@@ -439,6 +528,7 @@ mod tests {
         modules: HashMap<String, ModuleCost>,
     ) -> CostSnapshot {
         CostSnapshot {
+            schema_version: OUTPUT_SCHEMA_VERSION.to_string(),
             id: id.to_string(),
             timestamp: "2024-01-01T00:00:00Z".to_string(),
             commit_hash: None,
@@ -449,6 +539,7 @@ mod tests {
             regressions: Vec::new(),
             slo_violations: Vec::new(),
             metadata: None,
+            actuals: None,
         }
     }
 
@@ -548,4 +639,75 @@ mod tests {
         assert!(json.contains("\"total_cost_delta\""));
         assert!(json.contains("\"summary\""));
     }
+
+    #[test]
+    fn test_filter_module_changes() {
+        let module = |name: &str, cost: f64| ModuleCost {
+            name: name.to_string(),
+            monthly_cost: cost,
+            resource_count: 1,
+            change_from_previous: None,
+            change_percent: None,
+            services: vec![],
+        };
+        let from_modules = HashMap::from([("app".to_string(), module("app", 100.0))]);
+        let to_modules = HashMap::from([
+            ("app".to_string(), module("app", 180.0)),
+            ("database".to_string(), module("database", 20.0)),
+        ]);
+
+        let from = create_test_snapshot("snap1", 100.0, from_modules);
+        let to = create_test_snapshot("snap2", 200.0, to_modules);
+        let mut diff = TrendDiffGenerator::generate_diff(&from, &to);
+
+        let filter: FilterExpr = "delta>=50 and action=increased".parse().unwrap();
+        filter.retain(&mut diff.module_changes);
+        assert_eq!(diff.module_changes.len(), 1);
+        assert_eq!(diff.module_changes[0].module, "app");
+
+        // Services have no type, so a type condition excludes every row
+        let filter: FilterExpr = "type=aws_*".parse().unwrap();
+        filter.retain(&mut diff.service_changes);
+        assert!(diff.service_changes.is_empty());
+    }
+
+    #[test]
+    fn test_prune_folds_small_changes_into_everything_else() {
+        let module = |name: &str, cost: f64| {
+            (
+                name.to_string(),
+                ModuleCost {
+                    name: name.to_string(),
+                    monthly_cost: cost,
+                    resource_count: 1,
+                    change_from_previous: None,
+                    change_percent: None,
+                    services: vec![],
+                },
+            )
+        };
+        let from_modules = HashMap::from([module("app", 100.0), module("cache", 10.0)]);
+        let to_modules = HashMap::from([
+            module("app", 300.0),
+            module("cache", 15.0),
+            module("queue", 5.0),
+        ]);
+
+        let from = create_test_snapshot("snap1", 110.0, from_modules);
+        let to = create_test_snapshot("snap2", 320.0, to_modules);
+        let mut diff = TrendDiffGenerator::generate_diff(&from, &to);
+        diff.prune(&Pruning::new(Some(1), None));
+
+        assert_eq!(diff.module_changes.len(), 2);
+        assert_eq!(diff.module_changes[0].module, "app");
+        let rest = &diff.module_changes[1];
+        assert_eq!(rest.module, REMAINDER_LABEL);
+        assert_eq!(
+            (rest.cost_before, rest.cost_after, rest.delta),
+            (10.0, 20.0, 10.0)
+        );
+        assert_eq!(rest.change_type, ChangeType::Increased);
+        let shown: f64 = diff.module_changes.iter().map(|c| c.delta).sum();
+        assert_eq!(shown, diff.total_cost_delta);
+    }
 }
@@ -0,0 +1,319 @@
+// Variance report - how far predictions were from the bill
+//
+// Compares every snapshot that carries imported actuals with its prediction,
+// per module and in total. Bias metrics show whether estimates run high or
+// low, and the correction factor per module is the multiplier that would
+// have made its predictions match the bill on aggregate.
+
+use super::actuals::{predicted_vs_actual, UNTAGGED};
+use super::html_generator::HtmlGenerator;
+use super::snapshot_types::TrendHistory;
+use crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Accuracy of a set of predictions against billed costs
+///
+/// Observations with nothing billed are left out of the percentages, which
+/// are undefined for them, but still count as over-predictions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BiasMetrics {
+    pub observations: usize,
+
+    /// Mean absolute percentage error; `None` when nothing was billed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mape: Option<f64>,
+
+    /// Mean signed error as a percentage of the bill; positive when
+    /// predictions run high
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bias_percent: Option<f64>,
+
+    pub over_predicted: usize,
+    pub under_predicted: usize,
+}
+
+impl BiasMetrics {
+    fn from_pairs(pairs: &[(f64, f64)]) -> Self {
+        let errors: Vec<f64> = pairs
+            .iter()
+            .filter(|(_, actual)| *actual > 0.0)
+            .map(|(predicted, actual)| (predicted - actual) / actual * 100.0)
+            .collect();
+        let mean = |values: Vec<f64>| {
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+        Self {
+            observations: pairs.len(),
+            mape: mean(errors.iter().map(|e| e.abs()).collect()),
+            bias_percent: mean(errors),
+            over_predicted: pairs.iter().filter(|(p, a)| p > a).count(),
+            under_predicted: pairs.iter().filter(|(p, a)| p < a).count(),
+        }
+    }
+}
+
+/// Predicted against billed cost of one snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodVariance {
+    pub snapshot_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+
+    pub predicted: f64,
+    pub actual: f64,
+}
+
+/// Prediction accuracy of one module across all billed snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleVariance {
+    pub module: String,
+    pub predicted: f64,
+    pub actual: f64,
+    pub metrics: BiasMetrics,
+
+    /// Billed over predicted cost: the multiplier that would have matched
+    /// this module's predictions to the bill
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correction_factor: Option<f64>,
+}
+
+/// Predicted vs actual costs over the trend history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VarianceReport {
+    pub schema_version: String,
+
+    /// One entry per snapshot with actuals, oldest first
+    pub periods: Vec<PeriodVariance>,
+
+    /// Sorted by module; `untagged` holds billed costs with no module tag
+    pub modules: Vec<ModuleVariance>,
+
+    /// Accuracy of snapshot totals
+    pub overall: BiasMetrics,
+}
+
+impl VarianceReport {
+    /// Build the report from every snapshot in `history` that has actuals
+    pub fn from_history(history: &TrendHistory) -> Self {
+        let mut snapshots: Vec<_> = history
+            .snapshots
+            .iter()
+            .filter(|s| s.actuals.is_some())
+            .collect();
+        snapshots.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        let mut periods = Vec::new();
+        let mut module_pairs: BTreeMap<String, Vec<(f64, f64)>> = BTreeMap::new();
+        for snapshot in snapshots {
+            let Some(actuals) = &snapshot.actuals else {
+                continue;
+            };
+            periods.push(PeriodVariance {
+                snapshot_id: snapshot.id.clone(),
+                period: actuals.period.clone(),
+                predicted: snapshot.total_monthly_cost,
+                actual: actuals.total_monthly_cost,
+            });
+            // Without a module tag in the export there is nothing to compare per module
+            if actuals.modules.is_empty() {
+                continue;
+            }
+            for row in predicted_vs_actual(snapshot, actuals) {
+                module_pairs
+                    .entry(row.module)
+                    .or_default()
+                    .push((row.predicted, row.actual));
+            }
+        }
+
+        let modules = module_pairs
+            .into_iter()
+            .map(|(module, pairs)| {
+                let predicted: f64 = pairs.iter().map(|p| p.0).sum();
+                let actual: f64 = pairs.iter().map(|p| p.1).sum();
+                ModuleVariance {
+                    correction_factor: (predicted > 0.0 && module != UNTAGGED)
+                        .then(|| actual / predicted),
+                    metrics: BiasMetrics::from_pairs(&pairs),
+                    module,
+                    predicted,
+                    actual,
+                }
+            })
+            .collect();
+        let totals: Vec<(f64, f64)> = periods.iter().map(|p| (p.predicted, p.actual)).collect();
+
+        Self {
+            schema_version: OUTPUT_SCHEMA_VERSION.to_string(),
+            overall: BiasMetrics::from_pairs(&totals),
+            periods,
+            modules,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.periods.is_empty()
+    }
+
+    /// Standalone HTML page with the period and module tables
+    pub fn to_html(&self) -> String {
+        let mut body = String::from("<div class=\"variance\">\n");
+        let overall = &self.overall;
+        writeln!(
+            body,
+            "<p class=\"summary\">{} billed months &middot; MAPE {} &middot; bias {} &middot; {} over, {} under</p>",
+            overall.observations,
+            percent(overall.mape),
+            signed_percent(overall.bias_percent),
+            overall.over_predicted,
+            overall.under_predicted
+        )
+        .unwrap();
+
+        body.push_str("<h2>By month</h2>\n<table>\n");
+        body.push_str("<tr><th>Snapshot</th><th>Period</th><th>Predicted</th><th>Billed</th><th>Error</th></tr>\n");
+        for period in &self.periods {
+            let error = (period.actual > 0.0)
+                .then(|| (period.predicted - period.actual) / period.actual * 100.0);
+            writeln!(
+                body,
+                "<tr><td>{}</td><td>{}</td><td>${:.2}</td><td>${:.2}</td><td>{}</td></tr>",
+                HtmlGenerator::escape_html(&period.snapshot_id),
+                HtmlGenerator::escape_html(period.period.as_deref().unwrap_or("-")),
+                period.predicted,
+                period.actual,
+                signed_percent(error)
+            )
+            .unwrap();
+        }
+        body.push_str("</table>\n");
+
+        if !self.modules.is_empty() {
+            body.push_str("<h2>By module</h2>\n<table>\n");
+            body.push_str("<tr><th>Module</th><th>Predicted</th><th>Billed</th><th>MAPE</th><th>Bias</th><th>Over / under</th><th>Correction</th></tr>\n");
+            for module in &self.modules {
+                writeln!(
+                    body,
+                    "<tr><td>{}</td><td>${:.2}</td><td>${:.2}</td><td>{}</td><td>{}</td><td>{} / {}</td><td>{}</td></tr>",
+                    HtmlGenerator::escape_html(&module.module),
+                    module.predicted,
+                    module.actual,
+                    percent(module.metrics.mape),
+                    signed_percent(module.metrics.bias_percent),
+                    module.metrics.over_predicted,
+                    module.metrics.under_predicted,
+                    module
+                        .correction_factor
+                        .map_or("-".to_string(), |f| format!("&times;{:.2}", f))
+                )
+                .unwrap();
+            }
+            body.push_str("</table>\n");
+        }
+
+        body.push_str("</div>");
+        HtmlGenerator::wrap(&body, "Prediction Variance", "Predicted vs billed costs")
+    }
+}
+
+fn percent(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |v| format!("{:.1}%", v))
+}
+
+fn signed_percent(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |v| format!("{:+.1}%", v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::trend::{ActualCosts, BillingSource, CostSnapshot};
+
+    fn billed(id: &str, timestamp: &str, modules: &[(&str, f64, f64)]) -> CostSnapshot {
+        let mut snapshot = CostSnapshot::new(id.to_string(), 0.0);
+        snapshot.timestamp = timestamp.to_string();
+        let mut actuals = ActualCosts {
+            source: BillingSource::CostAndUsageReport,
+            period: Some(timestamp[..7].to_string()),
+            total_monthly_cost: 0.0,
+            modules: BTreeMap::new(),
+            services: BTreeMap::new(),
+        };
+        for (module, predicted, actual) in modules {
+            if *predicted > 0.0 {
+                snapshot.add_module(module.to_string(), *predicted, 1);
+                snapshot.total_monthly_cost += predicted;
+            }
+            actuals.modules.insert(module.to_string(), *actual);
+            actuals.total_monthly_cost += actual;
+        }
+        snapshot.actuals = Some(actuals);
+        snapshot
+    }
+
+    #[test]
+    fn test_variance_report_bias_metrics() {
+        let mut history = TrendHistory::new();
+        history.add_snapshot(billed(
+            "may",
+            "2024-05-31T00:00:00Z",
+            &[("module.web", 100.0, 125.0), ("root", 50.0, 40.0)],
+        ));
+        history.add_snapshot(billed(
+            "apr",
+            "2024-04-30T00:00:00Z",
+            &[("module.web", 100.0, 100.0), ("untagged", 0.0, 10.0)],
+        ));
+        history.add_snapshot(CostSnapshot::new("unbilled".to_string(), 999.0));
+
+        let report = VarianceReport::from_history(&history);
+
+        let ids: Vec<&str> = report
+            .periods
+            .iter()
+            .map(|p| p.snapshot_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["apr", "may"]);
+        assert_eq!(report.overall.observations, 2);
+        assert_eq!(report.overall.under_predicted, 2);
+
+        let web = &report.modules[0];
+        assert_eq!(web.module, "module.web");
+        assert_eq!(web.metrics.mape, Some(10.0));
+        assert_eq!(web.metrics.bias_percent, Some(-10.0));
+        assert_eq!(web.metrics.under_predicted, 1);
+        assert_eq!(web.correction_factor, Some(1.125));
+
+        let root = &report.modules[1];
+        assert_eq!(root.metrics.bias_percent, Some(25.0));
+        assert_eq!(root.metrics.over_predicted, 1);
+
+        let untagged = &report.modules[2];
+        assert_eq!(untagged.module, UNTAGGED);
+        assert_eq!(untagged.correction_factor, None);
+    }
+
+    #[test]
+    fn test_variance_report_renders_html_and_json() {
+        let mut history = TrendHistory::new();
+        history.add_snapshot(billed(
+            "may<1>",
+            "2024-05-31T00:00:00Z",
+            &[("module.web", 100.0, 125.0)],
+        ));
+        let report = VarianceReport::from_history(&history);
+
+        let html = report.to_html();
+        assert!(html.contains("<title>Prediction Variance</title>"));
+        assert!(html.contains("may&lt;1&gt;"));
+        assert!(html.contains("<td>module.web</td><td>$100.00</td><td>$125.00</td><td>20.0%</td><td>-20.0%</td><td>0 / 1</td><td>&times;1.25</td>"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["overall"]["mape"], 20.0);
+        assert_eq!(json["modules"][0]["correction_factor"], 1.25);
+        assert!(VarianceReport::from_history(&TrendHistory::new()).is_empty());
+    }
+}
@@ -1,11 +1,27 @@
 // Runtime feature flags for test-in-production capabilities
-
+//
+// The registry starts from the built-in flags and is layered like the rest
+// of the configuration: the user's `feature_flags.yml`, the project's
+// `feature_flags` section in costpilot.yaml (with the active profile), then
+// `COSTPILOT_FLAGS`. Partial rollouts bucket on a SHA-256 of the flag name
+// and the repository, so a repository gets the same answer on every machine
+// and CI runner. A flag can name the edition capability it builds on; it
+// stays off while the license or the organization policy withholds it.
+
+use crate::edition::{Capability, CapabilityScope, EditionContext};
+use crate::engines::shared::error_model::CostPilotError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::fmt;
 use std::fs;
 use std::path::Path;
 
+/// Environment variable with per-flag overrides
+/// (`experimental_prediction=on,advanced_analytics=25%,ai_enhancements=off`)
+pub const FLAGS_ENV_VAR: &str = "COSTPILOT_FLAGS";
+
 /// Feature flag configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureFlags {
@@ -27,6 +43,54 @@ pub struct FeatureFlag {
     pub allowlist: Option<Vec<String>>,
     /// User blocklist
     pub blocklist: Option<Vec<String>>,
+    /// What the flag changes, shown by `costpilot flags`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Edition capability the flag builds on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requires: Option<Capability>,
+    /// Layer that last set the flag
+    #[serde(skip)]
+    pub source: FlagSource,
+}
+
+impl FeatureFlag {
+    fn builtin(rollout_percentage: f64, description: &str) -> Self {
+        Self {
+            enabled: false,
+            rollout_percentage,
+            allowlist: None,
+            blocklist: None,
+            description: Some(description.to_string()),
+            requires: None,
+            source: FlagSource::Builtin,
+        }
+    }
+}
+
+/// Configuration layer a flag's settings came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagSource {
+    #[default]
+    Builtin,
+    /// `feature_flags.yml` in the user's config directory
+    UserConfig,
+    /// `feature_flags` in costpilot.yaml
+    ProjectConfig,
+    /// `COSTPILOT_FLAGS`
+    Environment,
+}
+
+impl fmt::Display for FlagSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FlagSource::Builtin => "built-in",
+            FlagSource::UserConfig => "user config",
+            FlagSource::ProjectConfig => "costpilot.yaml",
+            FlagSource::Environment => FLAGS_ENV_VAR,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,32 +110,17 @@ impl Default for FeatureFlags {
         // Default feature flags
         flags.insert(
             "experimental_prediction".to_string(),
-            FeatureFlag {
-                enabled: false,
-                rollout_percentage: 0.1, // 10% rollout
-                allowlist: None,
-                blocklist: None,
-            },
+            FeatureFlag::builtin(0.1, "Experimental prediction models"), // 10% rollout
         );
 
         flags.insert(
             "advanced_analytics".to_string(),
-            FeatureFlag {
-                enabled: false,
-                rollout_percentage: 0.05, // 5% rollout
-                allowlist: None,
-                blocklist: None,
-            },
+            FeatureFlag::builtin(0.05, "Advanced cost analytics"), // 5% rollout
         );
 
         flags.insert(
             "ai_enhancements".to_string(),
-            FeatureFlag {
-                enabled: false,
-                rollout_percentage: 0.0, // Disabled by default
-                allowlist: None,
-                blocklist: None,
-            },
+            FeatureFlag::builtin(0.0, "AI-assisted recommendations"), // Disabled by default
         );
 
         Self {
@@ -94,6 +143,9 @@ impl FeatureFlags {
         if config_path.exists() {
             let contents = fs::read_to_string(&config_path)?;
             let mut flags: FeatureFlags = serde_yaml::from_str(&contents)?;
+            for flag in flags.flags.values_mut() {
+                flag.source = FlagSource::UserConfig;
+            }
 
             // Override with environment variables
             flags.override_from_env();
@@ -181,10 +233,7 @@ impl FeatureFlags {
 
         // Use user_id or machine ID for deterministic rollout
         let seed = user_id.unwrap_or(&self.canary.seed);
-        let hash = Self::simple_hash(seed);
-        let normalized = (hash % 10000) as f64 / 10000.0;
-
-        normalized <= flag.rollout_percentage
+        rollout_bucket(feature, seed) < flag.rollout_percentage
     }
 
     /// Check if user is in canary deployment
@@ -198,20 +247,7 @@ impl FeatureFlags {
         }
 
         let seed = user_id.unwrap_or(&self.canary.seed);
-        let hash = Self::simple_hash(seed);
-        let normalized = (hash % 10000) as f64 / 10000.0;
-
-        normalized <= self.canary.percentage
-    }
-
-    /// Simple hash function for deterministic rollout
-    fn simple_hash(s: &str) -> u32 {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        s.hash(&mut hasher);
-        hasher.finish() as u32
+        rollout_bucket("canary", seed) < self.canary.percentage
     }
 
     /// Enable a feature for all users
@@ -235,6 +271,354 @@ impl FeatureFlags {
             flag.rollout_percentage = percentage.clamp(0.0, 1.0);
         }
     }
+
+    /// Registry for a project: `load()` with the `feature_flags` section of
+    /// `config` (with `profile` applied) and then `COSTPILOT_FLAGS` on top
+    pub fn load_for_project(
+        config: Option<&Path>,
+        profile: Option<&str>,
+    ) -> Result<Self, CostPilotError> {
+        let mut flags = Self::load().map_err(|e| {
+            CostPilotError::config_error(format!("Failed to load feature flags: {}", e))
+        })?;
+        if let Some(path) = config {
+            flags.apply(
+                &FeatureFlagsConfig::load(path, profile)?,
+                FlagSource::ProjectConfig,
+            );
+        }
+        if let Ok(overrides) = env::var(FLAGS_ENV_VAR) {
+            flags.apply(
+                &FeatureFlagsConfig::from_env(&overrides)?,
+                FlagSource::Environment,
+            );
+        }
+        Ok(flags)
+    }
+
+    /// Layer `config` over the registry; unknown names add new flags
+    pub fn apply(&mut self, config: &FeatureFlagsConfig, source: FlagSource) {
+        for (name, settings) in &config.flags {
+            let flag = self
+                .flags
+                .entry(name.clone())
+                .or_insert_with(|| FeatureFlag {
+                    enabled: true,
+                    rollout_percentage: 1.0,
+                    allowlist: None,
+                    blocklist: None,
+                    description: None,
+                    requires: None,
+                    source,
+                });
+            if let Some(enabled) = settings.enabled {
+                flag.enabled = enabled;
+            }
+            if let Some(percentage) = settings.rollout_percentage {
+                flag.rollout_percentage = percentage.clamp(0.0, 1.0);
+            }
+            if settings.allowlist.is_some() {
+                flag.allowlist = settings.allowlist.clone();
+            }
+            if settings.blocklist.is_some() {
+                flag.blocklist = settings.blocklist.clone();
+            }
+            if settings.description.is_some() {
+                flag.description = settings.description.clone();
+            }
+            if settings.requires.is_some() {
+                flag.requires = settings.requires;
+            }
+            flag.source = source;
+        }
+    }
+
+    /// Decide `feature` for a repository and edition
+    ///
+    /// Allow and block lists match the repository (`owner/name`); without a
+    /// repository the canary seed is used for the rollout bucket.
+    pub fn evaluate(
+        &self,
+        feature: &str,
+        repo: Option<&str>,
+        edition: &EditionContext,
+    ) -> FlagDecision {
+        let decision = |enabled, reason| FlagDecision { enabled, reason };
+        if !self.enabled {
+            return decision(false, FlagReason::GloballyDisabled);
+        }
+        let Some(flag) = self.flags.get(feature) else {
+            return decision(false, FlagReason::Unknown);
+        };
+        if !flag.enabled {
+            return decision(false, FlagReason::Disabled);
+        }
+        if let Some(capability) = flag.requires {
+            if let Some(rule) = edition.disabled_by_policy(capability) {
+                return decision(
+                    false,
+                    FlagReason::DisabledByPolicy {
+                        capability,
+                        rule: rule.to_string(),
+                    },
+                );
+            }
+            if !edition.capabilities.allows(capability) {
+                return decision(false, FlagReason::RequiresCapability(capability));
+            }
+        }
+
+        let listed = |list: &Option<Vec<String>>| {
+            repo.is_some_and(|r| list.as_ref().is_some_and(|l| l.iter().any(|e| e == r)))
+        };
+        if listed(&flag.blocklist) {
+            return decision(false, FlagReason::Blocklisted);
+        }
+        if flag.allowlist.is_some() {
+            return if listed(&flag.allowlist) {
+                decision(true, FlagReason::Allowlisted)
+            } else {
+                decision(false, FlagReason::NotAllowlisted)
+            };
+        }
+
+        if flag.rollout_percentage >= 1.0 {
+            return decision(true, FlagReason::FullRollout);
+        }
+        let bucket = rollout_bucket(feature, repo.unwrap_or(&self.canary.seed));
+        if bucket < flag.rollout_percentage {
+            decision(true, FlagReason::InRollout { bucket })
+        } else {
+            decision(false, FlagReason::OutsideRollout { bucket })
+        }
+    }
+
+    /// Whether `feature` is on for the current repository
+    pub fn is_enabled_for_repo(&self, feature: &str, edition: &EditionContext) -> bool {
+        self.evaluate(feature, CapabilityScope::detect().repo.as_deref(), edition)
+            .enabled
+    }
+
+    /// Flags sorted by name
+    pub fn sorted(&self) -> Vec<(&String, &FeatureFlag)> {
+        let mut flags: Vec<_> = self.flags.iter().collect();
+        flags.sort_by(|a, b| a.0.cmp(b.0));
+        flags
+    }
+}
+
+/// Stable position of `key` in `feature`'s rollout, in `[0, 1)`
+///
+/// SHA-256 rather than `DefaultHasher`, whose output may change between
+/// Rust releases and would reshuffle rollouts on upgrade.
+pub fn rollout_bucket(feature: &str, key: &str) -> f64 {
+    let digest = Sha256::digest(format!("{}:{}", feature, key).as_bytes());
+    let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+    f64::from(value % 10_000) / 10_000.0
+}
+
+/// Outcome of evaluating a flag
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlagDecision {
+    pub enabled: bool,
+    pub reason: FlagReason,
+}
+
+/// Why a flag is on or off
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlagReason {
+    Unknown,
+    GloballyDisabled,
+    Disabled,
+    /// The edition does not include the capability the flag builds on
+    RequiresCapability(Capability),
+    /// The organization capability policy turned the capability off
+    DisabledByPolicy {
+        capability: Capability,
+        rule: String,
+    },
+    Blocklisted,
+    Allowlisted,
+    NotAllowlisted,
+    FullRollout,
+    InRollout {
+        bucket: f64,
+    },
+    OutsideRollout {
+        bucket: f64,
+    },
+}
+
+impl fmt::Display for FlagReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlagReason::Unknown => write!(f, "unknown flag"),
+            FlagReason::GloballyDisabled => write!(f, "feature flags are disabled"),
+            FlagReason::Disabled => write!(f, "disabled"),
+            FlagReason::RequiresCapability(capability) => {
+                write!(f, "requires the {} capability", capability.name())
+            }
+            FlagReason::DisabledByPolicy { capability, rule } => write!(
+                f,
+                "{} disabled by organization policy rule '{}'",
+                capability.name(),
+                rule
+            ),
+            FlagReason::Blocklisted => write!(f, "repository is blocklisted"),
+            FlagReason::Allowlisted => write!(f, "repository is allowlisted"),
+            FlagReason::NotAllowlisted => write!(f, "repository is not allowlisted"),
+            FlagReason::FullRollout => write!(f, "enabled for all repositories"),
+            FlagReason::InRollout { bucket } => {
+                write!(f, "in rollout (bucket {:.1}%)", bucket * 100.0)
+            }
+            FlagReason::OutsideRollout { bucket } => {
+                write!(f, "outside rollout (bucket {:.1}%)", bucket * 100.0)
+            }
+        }
+    }
+}
+
+/// Settings for one flag in costpilot.yaml or `COSTPILOT_FLAGS`; anything
+/// left out keeps the value from the layer below
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlagSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// Share of repositories that get the flag (0.0 to 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollout_percentage: Option<f64>,
+    /// Repositories (`owner/name`) that always get the flag; no others do
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowlist: Option<Vec<String>>,
+    /// Repositories that never get the flag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocklist: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Edition capability the flag builds on (`predict`, `autofix`, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requires: Option<Capability>,
+}
+
+/// Flag settings by name (`feature_flags` in costpilot.yaml)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FeatureFlagsConfig {
+    pub flags: BTreeMap<String, FlagSettings>,
+}
+
+impl FeatureFlagsConfig {
+    /// Load `feature_flags` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self, CostPilotError> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `feature_flags` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self, CostPilotError> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `feature_flags` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(
+        content: &str,
+        profile: Option<&str>,
+    ) -> Result<Self, CostPilotError> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let config: Self = match root.get("feature_flags").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid feature_flags section: {}", e))
+            })?,
+            None => Self::default(),
+        };
+        let problems = config.validate();
+        if !problems.is_empty() {
+            return Err(CostPilotError::config_error(format!(
+                "Invalid feature_flags section: {}",
+                problems.join("; ")
+            )));
+        }
+        Ok(config)
+    }
+
+    /// Parse `COSTPILOT_FLAGS`: comma-separated `name`, `name=on|off` or
+    /// `name=25%` entries
+    pub fn from_env(value: &str) -> Result<Self, CostPilotError> {
+        let mut config = Self::default();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, setting) = entry.split_once('=').unwrap_or((entry, "on"));
+            let (name, setting) = (name.trim(), setting.trim());
+            let invalid = || {
+                CostPilotError::config_error(format!("Invalid {} entry '{}'", FLAGS_ENV_VAR, entry))
+                    .with_hint("Use name, name=on, name=off or name=25%")
+            };
+            let settings = match setting.to_ascii_lowercase().as_str() {
+                "on" | "true" | "1" => FlagSettings {
+                    enabled: Some(true),
+                    rollout_percentage: Some(1.0),
+                    ..Default::default()
+                },
+                "off" | "false" | "0" => FlagSettings {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+                other => {
+                    let percent: f64 = other
+                        .strip_suffix('%')
+                        .and_then(|p| p.trim().parse().ok())
+                        .filter(|p| (0.0..=100.0).contains(p))
+                        .ok_or_else(invalid)?;
+                    FlagSettings {
+                        enabled: Some(true),
+                        rollout_percentage: Some(percent / 100.0),
+                        ..Default::default()
+                    }
+                }
+            };
+            if name.is_empty() {
+                return Err(invalid());
+            }
+            config.flags.insert(name.to_string(), settings);
+        }
+        Ok(config)
+    }
+
+    /// Problems with the flag settings
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, settings) in &self.flags {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                problems.push(format!(
+                    "flag name '{}' may only contain letters, digits, '_' and '-'",
+                    name
+                ));
+            }
+            if let Some(percentage) = settings.rollout_percentage {
+                if !(0.0..=1.0).contains(&percentage) {
+                    problems.push(format!(
+                        "{}.rollout_percentage must be between 0.0 and 1.0",
+                        name
+                    ));
+                }
+            }
+        }
+        problems
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty()
+    }
 }
 
 /// Global feature flag manager
@@ -327,6 +711,96 @@ mod tests {
         assert!(enabled_count > 30 && enabled_count < 70);
     }
 
+    #[test]
+    fn test_rollout_bucket_is_stable() {
+        // Pinned so a hashing change cannot silently reshuffle rollouts
+        assert_eq!(
+            rollout_bucket("experimental_prediction", "acme/infra"),
+            0.868
+        );
+        assert_eq!(rollout_bucket("new_report", "acme/api"), 0.2171);
+    }
+
+    #[test]
+    fn test_project_config_and_env_layers() {
+        let mut flags = FeatureFlags::default();
+        let config = FeatureFlagsConfig::from_yaml(
+            "feature_flags:\n  new_report:\n    rollout_percentage: 0.3\n    requires: trend\n  advanced_analytics:\n    enabled: true\n",
+        )
+        .unwrap();
+        flags.apply(&config, FlagSource::ProjectConfig);
+        flags.apply(
+            &FeatureFlagsConfig::from_env("ai_enhancements, advanced_analytics=off").unwrap(),
+            FlagSource::Environment,
+        );
+
+        let new_report = &flags.flags["new_report"];
+        assert!(new_report.enabled);
+        assert_eq!(new_report.requires, Some(Capability::Trend));
+        assert_eq!(new_report.source, FlagSource::ProjectConfig);
+        // Rollout percentage from the built-in flag survives the overlay
+        assert!(!flags.flags["advanced_analytics"].enabled);
+        assert_eq!(flags.flags["advanced_analytics"].rollout_percentage, 0.05);
+        assert_eq!(flags.flags["ai_enhancements"].rollout_percentage, 1.0);
+        assert_eq!(
+            flags.flags["ai_enhancements"].source,
+            FlagSource::Environment
+        );
+
+        assert!(FeatureFlagsConfig::from_env("a=sometimes").is_err());
+        assert!(FeatureFlagsConfig::from_env("a=150%").is_err());
+        assert_eq!(
+            FeatureFlagsConfig::from_env("a=25%").unwrap().flags["a"].rollout_percentage,
+            Some(0.25)
+        );
+        let err = FeatureFlagsConfig::from_yaml(
+            "feature_flags:\n  new_report:\n    rollout_percentage: 30\n",
+        )
+        .unwrap_err();
+        assert!(err.message.contains("between 0.0 and 1.0"));
+    }
+
+    #[test]
+    fn test_evaluate_uses_repo_bucket_and_edition() {
+        let mut flags = FeatureFlags::default();
+        flags.apply(
+            &FeatureFlagsConfig::from_yaml(
+                "feature_flags:\n  new_report:\n    rollout_percentage: 0.3\n    requires: trend\n",
+            )
+            .unwrap(),
+            FlagSource::ProjectConfig,
+        );
+        let premium = EditionContext::premium_for_test();
+        let free = EditionContext::free();
+
+        // acme/api buckets at 21.7%, acme/infra at 41.6%
+        let api = flags.evaluate("new_report", Some("acme/api"), &premium);
+        assert!(api.enabled);
+        assert_eq!(api.reason, FlagReason::InRollout { bucket: 0.2171 });
+        assert!(
+            !flags
+                .evaluate("new_report", Some("acme/infra"), &premium)
+                .enabled
+        );
+
+        assert_eq!(
+            flags.evaluate("new_report", Some("acme/api"), &free).reason,
+            FlagReason::RequiresCapability(Capability::Trend)
+        );
+        assert_eq!(
+            flags.evaluate("missing", Some("acme/api"), &premium).reason,
+            FlagReason::Unknown
+        );
+
+        flags.flags.get_mut("new_report").unwrap().blocklist = Some(vec!["acme/api".to_string()]);
+        assert_eq!(
+            flags
+                .evaluate("new_report", Some("acme/api"), &premium)
+                .reason,
+            FlagReason::Blocklisted
+        );
+    }
+
     #[test]
     fn test_allowlist() {
         let mut flags = FeatureFlags::default();
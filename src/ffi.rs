@@ -0,0 +1,207 @@
+// C ABI for embedding the scan engine (see include/costpilot.h)
+//
+// Three functions, JSON in and JSON out, so the surface stays stable while
+// the engines evolve: `costpilot_scan` takes a request document and returns
+// the scan result or an `{"error": {...}}` document, `costpilot_free`
+// releases returned strings and `costpilot_version` reports the engine
+// version. Request fields are only ever added, never renamed or removed.
+
+use crate::client::{resource_changes, CostPilot};
+use crate::engines::detection::DetectionEngine;
+use crate::engines::policy::{PolicyConfig, PolicyLoader};
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::models::ResourceChange;
+use serde::Deserialize;
+use std::ffi::{c_char, CStr, CString};
+use std::path::Path;
+
+/// Version returned by `costpilot_version`, NUL terminated
+const VERSION_C: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+/// Request accepted by `costpilot_scan`; exactly one of `plan`, `content`
+/// or `path` supplies the input
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScanRequest {
+    /// Terraform plan JSON (`terraform show -json`) as an object
+    #[serde(default)]
+    pub plan: Option<serde_json::Value>,
+    /// Artifact file content (CloudFormation/CDK, SAM, Serverless, Kubernetes)
+    #[serde(default)]
+    pub content: Option<String>,
+    /// File name used to detect the format of `content`
+    #[serde(default)]
+    pub hint: Option<String>,
+    /// Plan or artifact file to read
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Policy file path, or a policy document in the same shape
+    #[serde(default)]
+    pub policy: Option<serde_json::Value>,
+    #[serde(default)]
+    pub explain: bool,
+    #[serde(default)]
+    pub mapping: bool,
+    #[serde(default)]
+    pub grouping: bool,
+}
+
+impl ScanRequest {
+    fn changes(&self) -> Result<Vec<ResourceChange>, CostPilotError> {
+        match (&self.plan, &self.content, &self.path) {
+            (Some(plan), None, None) => {
+                DetectionEngine::new().detect_from_terraform_json(&plan.to_string())
+            }
+            (None, Some(content), None) => {
+                resource_changes(content, self.hint.as_deref().unwrap_or_default())
+            }
+            (None, None, Some(path)) => {
+                let content = std::fs::read_to_string(path).map_err(|e| {
+                    CostPilotError::io_error(format!("Failed to read {}: {}", path, e))
+                })?;
+                resource_changes(&content, path)
+            }
+            _ => Err(CostPilotError::validation_error(
+                "Scan request needs exactly one of 'plan', 'content' or 'path'",
+            )),
+        }
+    }
+
+    fn policy(&self) -> Result<Option<PolicyConfig>, CostPilotError> {
+        match &self.policy {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(serde_json::Value::String(path)) => {
+                PolicyLoader::load_from_file(Path::new(path)).map(Some)
+            }
+            Some(document) => serde_json::from_value(document.clone())
+                .map(Some)
+                .map_err(|e| CostPilotError::validation_error(format!("Invalid policy: {}", e))),
+        }
+    }
+}
+
+/// Run a scan request with the detected edition; the response is the
+/// serialized `AnalysisResult`
+pub fn scan(request: &ScanRequest) -> Result<serde_json::Value, CostPilotError> {
+    let mut builder = CostPilot::builder()
+        .explain(request.explain)
+        .mapping(request.mapping)
+        .grouping(request.grouping);
+    if let Some(policy) = request.policy()? {
+        builder = builder.policy(policy);
+    }
+    let result = builder.build()?.analyze_changes(request.changes()?)?;
+    serde_json::to_value(result).map_err(|e| {
+        CostPilotError::serialization_error(format!("Failed to serialize scan result: {}", e))
+    })
+}
+
+/// Scan request document to response document
+fn scan_json(request: &str) -> String {
+    let response = serde_json::from_str::<ScanRequest>(request)
+        .map_err(|e| CostPilotError::parse_error(format!("Invalid scan request: {}", e)))
+        .and_then(|request| scan(&request))
+        .unwrap_or_else(|e| e.to_json_report());
+    response.to_string()
+}
+
+/// Scan the input described by the JSON request `json_in`
+///
+/// Returns a JSON document owned by the caller, to be released with
+/// `costpilot_free`: the scan result, or `{"error": {...}}` with the same
+/// CP code as the CLI. Returns NULL only when `json_in` is NULL.
+///
+/// # Safety
+///
+/// `json_in` must be NULL or point to a NUL-terminated string that stays
+/// valid for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn costpilot_scan(json_in: *const c_char) -> *mut c_char {
+    if json_in.is_null() {
+        return std::ptr::null_mut();
+    }
+    let request = CStr::from_ptr(json_in).to_string_lossy();
+    let response = std::panic::catch_unwind(|| scan_json(&request)).unwrap_or_else(|_| {
+        CostPilotError::new("FFI_001", ErrorCategory::InternalError, "Scan panicked")
+            .to_json_report()
+            .to_string()
+    });
+    // serde_json escapes control characters, so the document has no NUL
+    CString::new(response).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Release a string returned by `costpilot_scan`; NULL is ignored
+///
+/// # Safety
+///
+/// `ptr` must be NULL or a pointer returned by `costpilot_scan` that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn costpilot_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(CString::from_raw(ptr));
+    }
+}
+
+/// Engine version (e.g. "1.0.1"); static, never freed
+#[no_mangle]
+pub extern "C" fn costpilot_version() -> *const c_char {
+    VERSION_C.as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(request: &str) -> serde_json::Value {
+        let request = CString::new(request).unwrap();
+        unsafe {
+            let out = costpilot_scan(request.as_ptr());
+            let response = CStr::from_ptr(out).to_str().unwrap().to_string();
+            costpilot_free(out);
+            serde_json::from_str(&response).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_scan_inline_plan() {
+        let response = call(
+            r#"{"plan": {"format_version": "1.0", "resource_changes": [{
+                "address": "aws_instance.web", "type": "aws_instance", "name": "web",
+                "change": {"actions": ["create"], "before": null,
+                           "after": {"instance_type": "m5.xlarge"}}}]},
+                "policy": {"version": "1.0", "budgets": {"global": {"monthly_limit": 1.0}}}}"#,
+        );
+        assert_eq!(response["changes"].as_array().unwrap().len(), 1);
+        assert!(response["total_monthly"].as_f64().unwrap() > 0.0);
+        // Advisory (a warning) in Free, a violation when enforced
+        let policy = &response["policy_result"];
+        let findings = policy["violations"].as_array().unwrap().len()
+            + policy["warnings"].as_array().unwrap().len();
+        assert!(findings > 0, "{}", policy);
+        assert!(response["graph"].is_null());
+    }
+
+    #[test]
+    fn test_scan_errors_are_json() {
+        let response = call("not json");
+        assert_eq!(response["error"]["code"], "CP1020");
+
+        let response = call(r#"{"path": "/nonexistent/plan.json"}"#);
+        assert_eq!(response["error"]["id"], "E_IO");
+
+        let response = call(r#"{"content": "{}", "path": "plan.json"}"#);
+        assert_eq!(response["error"]["id"], "E_VALIDATION");
+
+        unsafe {
+            assert!(costpilot_scan(std::ptr::null()).is_null());
+            costpilot_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_version_is_nul_terminated() {
+        let version = unsafe { CStr::from_ptr(costpilot_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
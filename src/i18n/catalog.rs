@@ -0,0 +1,484 @@
+// Message catalogs, one table per locale
+//
+// English is the reference catalog: every key used in the code must be here.
+// Other catalogs carry the same keys and keep every `{placeholder}`.
+
+/// English (reference)
+pub const EN: &[(&str, &str)] = &[
+    // Edition and upgrade messages
+    (
+        "edition.requires_premium",
+        "{feature} requires CostPilot Premium",
+    ),
+    (
+        "edition.upgrade_message",
+        "{feature} requires CostPilot Premium.\nUpgrade: {url}",
+    ),
+    (
+        "edition.upgrade_hint",
+        "This feature requires CostPilot Premium. Visit {url}",
+    ),
+    (
+        "edition.capability_disabled",
+        "{feature} is disabled for {scope} by organization policy rule '{rule}'",
+    ),
+    ("edition.comparison_title", "CostPilot Free vs Premium:"),
+    ("edition.free", "FREE:"),
+    ("edition.premium", "PREMIUM:"),
+    ("edition.upgrade", "Upgrade: {url}"),
+    (
+        "edition.feature.basic_prediction",
+        "Basic cost prediction (static heuristics)",
+    ),
+    (
+        "edition.feature.explain_lite",
+        "Explain lite (top 5 patterns)",
+    ),
+    ("edition.feature.mapping_lite", "Mapping (depth 1)"),
+    ("edition.feature.policy_lint", "Policy lint-only"),
+    ("edition.feature.slo_validation", "SLO validation-only"),
+    (
+        "edition.feature.advanced_prediction",
+        "Advanced prediction (ML-enhanced)",
+    ),
+    ("edition.feature.full_explain", "Full explanation chains"),
+    ("edition.feature.deep_mapping", "Deep dependency mapping"),
+    ("edition.feature.autofix", "Autofix with drift safety"),
+    ("edition.feature.trends", "Trend tracking & history"),
+    (
+        "edition.feature.policy_enforcement",
+        "Policy enforcement (blocking)",
+    ),
+    (
+        "edition.feature.slo_enforcement",
+        "SLO enforcement (blocking)",
+    ),
+    // Validator output
+    ("validation.report_title", "Validation Report"),
+    ("validation.type", "Type"),
+    (
+        "validation.fixes_applied",
+        "Applied Fixes ({count}), original saved to {path}",
+    ),
+    ("validation.status", "Status:"),
+    ("validation.valid", "Valid"),
+    ("validation.invalid", "Invalid"),
+    ("validation.errors", "Validation Errors ({count})"),
+    ("validation.warnings", "Validation Warnings ({count})"),
+    ("validation.no_issues", "No issues found!"),
+    (
+        "validation.valid_with_warnings",
+        "File is valid but has {count} warning(s). Consider addressing them.",
+    ),
+    (
+        "validation.has_errors",
+        "File has {count} error(s). Fix these before using the configuration.",
+    ),
+    ("validation.field", "Field"),
+    ("validation.location", "Location: line {line}"),
+    (
+        "validation.location_column",
+        "Location: line {line}, column {column}",
+    ),
+    ("validation.hint", "Hint"),
+    ("validation.suggestion", "Suggestion"),
+    // Report headings
+    ("report.page.summary", "Summary"),
+    ("report.page.costs", "Cost breakdown"),
+    ("report.page.map", "Dependency map"),
+    ("report.page.trends", "Trends"),
+    ("report.page.policy", "Policy & SLOs"),
+    ("report.page.detections", "Detections"),
+    ("report.estimated_monthly_cost", "Estimated monthly cost"),
+    ("report.resources_changed", "Resources changed"),
+    ("report.policy", "Policy"),
+    ("report.cost_slos", "Cost SLOs"),
+    ("report.passed", "Passed"),
+    ("report.failed", "Failed"),
+    ("report.breached", "Breached"),
+    ("report.not_evaluated", "Not evaluated"),
+    ("report.detections_by_severity", "Detections by severity"),
+    ("report.severity.critical", "Critical"),
+    ("report.severity.high", "High"),
+    ("report.severity.medium", "Medium"),
+    ("report.severity.low", "Low"),
+    ("report.largest_costs", "Largest costs"),
+    ("report.no_estimates", "No resources were estimated."),
+    ("report.full_breakdown", "Full cost breakdown"),
+    ("report.resources", "Resources"),
+    ("report.by_module", "By module"),
+    ("report.by_service", "By service"),
+    ("report.by_environment", "By environment"),
+    ("report.col.group", "Group"),
+    ("report.col.resource", "Resource"),
+    ("report.col.type", "Type"),
+    ("report.col.change", "Change"),
+    ("report.col.monthly_cost", "Monthly cost"),
+    ("report.col.range", "Range"),
+    ("report.col.confidence", "Confidence"),
+    ("report.col.severity", "Severity"),
+    ("report.col.message", "Message"),
+    ("report.col.actual", "Actual"),
+    ("report.col.expected", "Expected"),
+    ("report.col.slo", "SLO"),
+    ("report.col.status", "Status"),
+    ("report.col.threshold", "Threshold"),
+    ("report.col.used", "Used"),
+    ("report.back", "Report"),
+    (
+        "report.no_dependencies",
+        "No dependencies were found between the changed resources.",
+    ),
+    (
+        "report.no_policy",
+        "No policy was evaluated. Pass {flag} to include one.",
+    ),
+    ("report.warnings", "Warnings"),
+    ("report.applied_exemptions", "Applied exemptions"),
+    ("report.no_slos", "No SLOs configured in {path}."),
+    ("report.slo.pass", "Pass"),
+    ("report.slo.warning", "Warning"),
+    ("report.slo.violation", "Violation"),
+    ("report.slo.no_data", "No data"),
+    ("report.no_detections", "No cost issues detected."),
+    (
+        "report.estimated_impact",
+        "Estimated impact: {amount}/month",
+    ),
+    ("report.why", "Why:"),
+    ("report.root_cause", "Root cause:"),
+    ("report.recommendations", "Recommendations"),
+    ("report.assumptions", "Assumptions"),
+    (
+        "report.trial",
+        "Produced with a CostPilot trial license ({days} days remaining)",
+    ),
+    (
+        "report.footer",
+        "Generated by CostPilot {version} at {time}",
+    ),
+];
+
+/// German
+pub const DE: &[(&str, &str)] = &[
+    // Edition and upgrade messages
+    (
+        "edition.requires_premium",
+        "{feature} erfordert CostPilot Premium",
+    ),
+    (
+        "edition.upgrade_message",
+        "{feature} erfordert CostPilot Premium.\nUpgrade: {url}",
+    ),
+    (
+        "edition.upgrade_hint",
+        "Diese Funktion erfordert CostPilot Premium. Siehe {url}",
+    ),
+    (
+        "edition.capability_disabled",
+        "{feature} ist für {scope} durch die Organisationsrichtlinie '{rule}' deaktiviert",
+    ),
+    (
+        "edition.comparison_title",
+        "CostPilot Free und Premium im Vergleich:",
+    ),
+    ("edition.free", "FREE:"),
+    ("edition.premium", "PREMIUM:"),
+    ("edition.upgrade", "Upgrade: {url}"),
+    (
+        "edition.feature.basic_prediction",
+        "Einfache Kostenprognose (statische Heuristiken)",
+    ),
+    (
+        "edition.feature.explain_lite",
+        "Erklärungen light (Top-5-Muster)",
+    ),
+    (
+        "edition.feature.mapping_lite",
+        "Abhängigkeitskarte (Tiefe 1)",
+    ),
+    ("edition.feature.policy_lint", "Richtlinien nur prüfen"),
+    ("edition.feature.slo_validation", "SLOs nur validieren"),
+    (
+        "edition.feature.advanced_prediction",
+        "Erweiterte Prognose (ML-gestützt)",
+    ),
+    (
+        "edition.feature.full_explain",
+        "Vollständige Erklärungsketten",
+    ),
+    ("edition.feature.deep_mapping", "Tiefe Abhängigkeitskarte"),
+    ("edition.feature.autofix", "Autofix mit Drift-Schutz"),
+    ("edition.feature.trends", "Trendverfolgung und Verlauf"),
+    (
+        "edition.feature.policy_enforcement",
+        "Durchsetzung von Richtlinien (blockierend)",
+    ),
+    (
+        "edition.feature.slo_enforcement",
+        "Durchsetzung von SLOs (blockierend)",
+    ),
+    // Validator output
+    ("validation.report_title", "Validierungsbericht"),
+    ("validation.type", "Typ"),
+    (
+        "validation.fixes_applied",
+        "Angewendete Korrekturen ({count}), Original gesichert unter {path}",
+    ),
+    ("validation.status", "Status:"),
+    ("validation.valid", "Gültig"),
+    ("validation.invalid", "Ungültig"),
+    ("validation.errors", "Validierungsfehler ({count})"),
+    ("validation.warnings", "Validierungswarnungen ({count})"),
+    ("validation.no_issues", "Keine Probleme gefunden!"),
+    (
+        "validation.valid_with_warnings",
+        "Die Datei ist gültig, hat aber {count} Warnung(en). Bitte prüfen.",
+    ),
+    (
+        "validation.has_errors",
+        "Die Datei hat {count} Fehler. Beheben Sie diese, bevor Sie die Konfiguration verwenden.",
+    ),
+    ("validation.field", "Feld"),
+    ("validation.location", "Position: Zeile {line}"),
+    (
+        "validation.location_column",
+        "Position: Zeile {line}, Spalte {column}",
+    ),
+    ("validation.hint", "Hinweis"),
+    ("validation.suggestion", "Vorschlag"),
+    // Report headings
+    ("report.page.summary", "Zusammenfassung"),
+    ("report.page.costs", "Kostenaufschlüsselung"),
+    ("report.page.map", "Abhängigkeitskarte"),
+    ("report.page.trends", "Trends"),
+    ("report.page.policy", "Richtlinien & SLOs"),
+    ("report.page.detections", "Befunde"),
+    (
+        "report.estimated_monthly_cost",
+        "Geschätzte monatliche Kosten",
+    ),
+    ("report.resources_changed", "Geänderte Ressourcen"),
+    ("report.policy", "Richtlinie"),
+    ("report.cost_slos", "Kosten-SLOs"),
+    ("report.passed", "Bestanden"),
+    ("report.failed", "Nicht bestanden"),
+    ("report.breached", "Verletzt"),
+    ("report.not_evaluated", "Nicht ausgewertet"),
+    ("report.detections_by_severity", "Befunde nach Schweregrad"),
+    ("report.severity.critical", "Kritisch"),
+    ("report.severity.high", "Hoch"),
+    ("report.severity.medium", "Mittel"),
+    ("report.severity.low", "Niedrig"),
+    ("report.largest_costs", "Größte Kostenposten"),
+    (
+        "report.no_estimates",
+        "Es wurden keine Ressourcen geschätzt.",
+    ),
+    (
+        "report.full_breakdown",
+        "Vollständige Kostenaufschlüsselung",
+    ),
+    ("report.resources", "Ressourcen"),
+    ("report.by_module", "Nach Modul"),
+    ("report.by_service", "Nach Dienst"),
+    ("report.by_environment", "Nach Umgebung"),
+    ("report.col.group", "Gruppe"),
+    ("report.col.resource", "Ressource"),
+    ("report.col.type", "Typ"),
+    ("report.col.change", "Änderung"),
+    ("report.col.monthly_cost", "Monatliche Kosten"),
+    ("report.col.range", "Spanne"),
+    ("report.col.confidence", "Konfidenz"),
+    ("report.col.severity", "Schweregrad"),
+    ("report.col.message", "Meldung"),
+    ("report.col.actual", "Ist"),
+    ("report.col.expected", "Soll"),
+    ("report.col.slo", "SLO"),
+    ("report.col.status", "Status"),
+    ("report.col.threshold", "Schwellenwert"),
+    ("report.col.used", "Ausgeschöpft"),
+    ("report.back", "Bericht"),
+    (
+        "report.no_dependencies",
+        "Zwischen den geänderten Ressourcen wurden keine Abhängigkeiten gefunden.",
+    ),
+    (
+        "report.no_policy",
+        "Es wurde keine Richtlinie ausgewertet. Geben Sie {flag} an, um eine einzubeziehen.",
+    ),
+    ("report.warnings", "Warnungen"),
+    ("report.applied_exemptions", "Angewendete Ausnahmen"),
+    ("report.no_slos", "Keine SLOs in {path} konfiguriert."),
+    ("report.slo.pass", "Erfüllt"),
+    ("report.slo.warning", "Warnung"),
+    ("report.slo.violation", "Verletzung"),
+    ("report.slo.no_data", "Keine Daten"),
+    ("report.no_detections", "Keine Kostenprobleme gefunden."),
+    (
+        "report.estimated_impact",
+        "Geschätzte Auswirkung: {amount}/Monat",
+    ),
+    ("report.why", "Warum:"),
+    ("report.root_cause", "Ursache:"),
+    ("report.recommendations", "Empfehlungen"),
+    ("report.assumptions", "Annahmen"),
+    (
+        "report.trial",
+        "Erstellt mit einer CostPilot-Testlizenz (noch {days} Tage)",
+    ),
+    (
+        "report.footer",
+        "Erstellt von CostPilot {version} am {time}",
+    ),
+];
+
+/// Japanese
+pub const JA: &[(&str, &str)] = &[
+    // Edition and upgrade messages
+    (
+        "edition.requires_premium",
+        "{feature} には CostPilot Premium が必要です",
+    ),
+    (
+        "edition.upgrade_message",
+        "{feature} には CostPilot Premium が必要です。\nアップグレード: {url}",
+    ),
+    (
+        "edition.upgrade_hint",
+        "この機能には CostPilot Premium が必要です。詳細: {url}",
+    ),
+    (
+        "edition.capability_disabled",
+        "{feature} は組織ポリシールール '{rule}' により {scope} で無効化されています",
+    ),
+    (
+        "edition.comparison_title",
+        "CostPilot Free と Premium の比較:",
+    ),
+    ("edition.free", "FREE:"),
+    ("edition.premium", "PREMIUM:"),
+    ("edition.upgrade", "アップグレード: {url}"),
+    (
+        "edition.feature.basic_prediction",
+        "基本的なコスト予測(静的ヒューリスティック)",
+    ),
+    ("edition.feature.explain_lite", "簡易説明(上位 5 パターン)"),
+    ("edition.feature.mapping_lite", "依存関係マップ(深さ 1)"),
+    ("edition.feature.policy_lint", "ポリシーの検査のみ"),
+    ("edition.feature.slo_validation", "SLO の検証のみ"),
+    (
+        "edition.feature.advanced_prediction",
+        "高度な予測(機械学習による補正)",
+    ),
+    ("edition.feature.full_explain", "完全な説明チェーン"),
+    ("edition.feature.deep_mapping", "詳細な依存関係マップ"),
+    ("edition.feature.autofix", "ドリフト保護付き自動修正"),
+    ("edition.feature.trends", "トレンドの追跡と履歴"),
+    (
+        "edition.feature.policy_enforcement",
+        "ポリシーの強制(ブロック)",
+    ),
+    ("edition.feature.slo_enforcement", "SLO の強制(ブロック)"),
+    // Validator output
+    ("validation.report_title", "検証レポート"),
+    ("validation.type", "種類"),
+    (
+        "validation.fixes_applied",
+        "適用した修正 ({count}) 、元のファイルは {path} に保存されています",
+    ),
+    ("validation.status", "状態:"),
+    ("validation.valid", "有効"),
+    ("validation.invalid", "無効"),
+    ("validation.errors", "検証エラー ({count})"),
+    ("validation.warnings", "検証警告 ({count})"),
+    ("validation.no_issues", "問題は見つかりませんでした。"),
+    (
+        "validation.valid_with_warnings",
+        "ファイルは有効ですが、警告が {count} 件あります。対応を検討してください。",
+    ),
+    (
+        "validation.has_errors",
+        "ファイルにエラーが {count} 件あります。設定を使用する前に修正してください。",
+    ),
+    ("validation.field", "フィールド"),
+    ("validation.location", "位置: {line} 行目"),
+    (
+        "validation.location_column",
+        "位置: {line} 行目、{column} 列目",
+    ),
+    ("validation.hint", "ヒント"),
+    ("validation.suggestion", "提案"),
+    // Report headings
+    ("report.page.summary", "概要"),
+    ("report.page.costs", "コスト内訳"),
+    ("report.page.map", "依存関係マップ"),
+    ("report.page.trends", "トレンド"),
+    ("report.page.policy", "ポリシーと SLO"),
+    ("report.page.detections", "検出結果"),
+    ("report.estimated_monthly_cost", "推定月額コスト"),
+    ("report.resources_changed", "変更されたリソース"),
+    ("report.policy", "ポリシー"),
+    ("report.cost_slos", "コスト SLO"),
+    ("report.passed", "合格"),
+    ("report.failed", "不合格"),
+    ("report.breached", "違反"),
+    ("report.not_evaluated", "未評価"),
+    ("report.detections_by_severity", "重大度別の検出結果"),
+    ("report.severity.critical", "重大"),
+    ("report.severity.high", "高"),
+    ("report.severity.medium", "中"),
+    ("report.severity.low", "低"),
+    ("report.largest_costs", "コストの大きいリソース"),
+    ("report.no_estimates", "見積もられたリソースはありません。"),
+    ("report.full_breakdown", "コスト内訳の全体"),
+    ("report.resources", "リソース"),
+    ("report.by_module", "モジュール別"),
+    ("report.by_service", "サービス別"),
+    ("report.by_environment", "環境別"),
+    ("report.col.group", "グループ"),
+    ("report.col.resource", "リソース"),
+    ("report.col.type", "種類"),
+    ("report.col.change", "変更"),
+    ("report.col.monthly_cost", "月額コスト"),
+    ("report.col.range", "範囲"),
+    ("report.col.confidence", "信頼度"),
+    ("report.col.severity", "重大度"),
+    ("report.col.message", "メッセージ"),
+    ("report.col.actual", "実績"),
+    ("report.col.expected", "期待値"),
+    ("report.col.slo", "SLO"),
+    ("report.col.status", "状態"),
+    ("report.col.threshold", "しきい値"),
+    ("report.col.used", "使用率"),
+    ("report.back", "レポート"),
+    (
+        "report.no_dependencies",
+        "変更されたリソース間に依存関係は見つかりませんでした。",
+    ),
+    (
+        "report.no_policy",
+        "ポリシーは評価されていません。含めるには {flag} を指定してください。",
+    ),
+    ("report.warnings", "警告"),
+    ("report.applied_exemptions", "適用された例外"),
+    ("report.no_slos", "{path} に SLO が設定されていません。"),
+    ("report.slo.pass", "合格"),
+    ("report.slo.warning", "警告"),
+    ("report.slo.violation", "違反"),
+    ("report.slo.no_data", "データなし"),
+    (
+        "report.no_detections",
+        "コストの問題は検出されませんでした。",
+    ),
+    ("report.estimated_impact", "推定影響額: {amount}/月"),
+    ("report.why", "理由:"),
+    ("report.root_cause", "根本原因:"),
+    ("report.recommendations", "推奨事項"),
+    ("report.assumptions", "前提条件"),
+    (
+        "report.trial",
+        "CostPilot 試用ライセンスで作成(残り {days} 日)",
+    ),
+    ("report.footer", "CostPilot {version} により {time} に生成"),
+];
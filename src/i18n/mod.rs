@@ -0,0 +1,263 @@
+// Message catalog for user-facing text
+//
+// Upgrade messages, validator output and report headings are looked up by
+// key instead of being written inline, so enterprise customers can produce
+// compliance reports in their own language. The locale comes from
+// `--locale` / `COSTPILOT_LOCALE`, then `locale` in costpilot.yaml, and
+// defaults to English. The CLI resolves it once and installs it for the
+// process; reports take it explicitly. A key missing from a catalog falls back to English,
+// so a partially translated locale never prints raw keys.
+//
+// Identifiers (error codes, resource ids, policy names) and machine-readable
+// output are never translated.
+
+mod catalog;
+
+use crate::engines::shared::error_model::CostPilotError;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub use catalog::{DE, EN, JA};
+
+/// Environment variable read by `--locale` when the flag is not given
+pub const LOCALE_ENV_VAR: &str = "COSTPILOT_LOCALE";
+
+static INSTALLED: OnceLock<Locale> = OnceLock::new();
+
+/// Language of user-facing messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// English
+    #[default]
+    En,
+    /// German
+    De,
+    /// Japanese
+    Ja,
+}
+
+impl Locale {
+    /// Every locale with a catalog
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::De, Locale::Ja];
+
+    /// Parse a language tag; region and encoding suffixes are ignored
+    /// (`de`, `de-AT`, `ja_JP.UTF-8`)
+    pub fn parse(tag: &str) -> Option<Self> {
+        let language = tag
+            .trim()
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        Self::ALL.into_iter().find(|l| l.code() == language)
+    }
+
+    /// ISO 639-1 code, also used as the HTML `lang` attribute
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Ja => "ja",
+        }
+    }
+
+    /// Locale installed for this process, English when none was
+    pub fn current() -> Self {
+        INSTALLED.get().copied().unwrap_or_default()
+    }
+
+    /// Use this locale for messages for the rest of the process; only the
+    /// first call takes effect
+    pub fn install(self) {
+        let _ = INSTALLED.set(self);
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::De => DE,
+            Locale::Ja => JA,
+        }
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Message for `key` in `locale`, falling back to English and then the key
+pub fn text(locale: Locale, key: &'static str) -> &'static str {
+    lookup(locale.catalog(), key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key)
+}
+
+/// Message for `key` with `{name}` placeholders replaced by `args`
+pub fn format(locale: Locale, key: &'static str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(text(locale, key).to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), value)
+        })
+}
+
+/// Message for `key` in the current locale
+pub fn t(key: &'static str) -> &'static str {
+    text(Locale::current(), key)
+}
+
+/// Formatted message for `key` in the current locale
+pub fn tf(key: &'static str, args: &[(&str, &str)]) -> String {
+    format(Locale::current(), key, args)
+}
+
+fn lookup(catalog: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+/// `locale` from costpilot.yaml content with a profile overlaid on the base;
+/// `None` when the key is absent
+pub fn locale_from_yaml_with_profile(
+    content: &str,
+    profile: Option<&str>,
+) -> Result<Option<Locale>, CostPilotError> {
+    let root: serde_yaml::Value = serde_yaml::from_str(content)
+        .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+    let root = crate::config::apply_profile(root, profile)
+        .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+    match root.get("locale") {
+        None | Some(serde_yaml::Value::Null) => Ok(None),
+        Some(value) => value
+            .as_str()
+            .and_then(Locale::parse)
+            .map(Some)
+            .ok_or_else(|| {
+                CostPilotError::config_error(format!(
+                    "Unsupported locale '{}'; expected one of: {}",
+                    serde_yaml::to_string(value).unwrap_or_default().trim_end(),
+                    supported_codes()
+                ))
+            }),
+    }
+}
+
+/// `locale` from a costpilot.yaml file, applying `profile`
+pub fn locale_from_config(
+    path: &Path,
+    profile: Option<&str>,
+) -> Result<Option<Locale>, CostPilotError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    locale_from_yaml_with_profile(&content, profile)
+}
+
+/// Locale from `--locale` / `COSTPILOT_LOCALE` when given, otherwise from
+/// the project config (with `profile` applied) if it exists, otherwise English
+pub fn resolve(
+    requested: Option<&str>,
+    config: &Path,
+    profile: Option<&str>,
+) -> Result<Locale, CostPilotError> {
+    if let Some(tag) = requested {
+        return Locale::parse(tag).ok_or_else(|| {
+            CostPilotError::config_error(format!(
+                "Unsupported locale '{}'; expected one of: {}",
+                tag,
+                supported_codes()
+            ))
+        });
+    }
+    if !config.exists() {
+        return Ok(Locale::default());
+    }
+    Ok(locale_from_config(config, profile)?.unwrap_or_default())
+}
+
+/// Supported locale codes, comma separated
+pub fn supported_codes() -> String {
+    Locale::ALL
+        .iter()
+        .map(Locale::code)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_parse() {
+        assert_eq!(Locale::parse("de"), Some(Locale::De));
+        assert_eq!(Locale::parse("de-AT"), Some(Locale::De));
+        assert_eq!(Locale::parse("ja_JP.UTF-8"), Some(Locale::Ja));
+        assert_eq!(Locale::parse(" EN "), Some(Locale::En));
+        assert_eq!(Locale::parse("fr"), None);
+        assert_eq!(Locale::parse(""), None);
+    }
+
+    #[test]
+    fn test_catalogs_cover_english_keys() {
+        for (key, english) in EN {
+            for locale in [Locale::De, Locale::Ja] {
+                let translated = lookup(locale.catalog(), key)
+                    .unwrap_or_else(|| panic!("{} is missing {}", locale, key));
+                // Placeholders must survive translation
+                for part in english.split('{').skip(1) {
+                    let name = part.split('}').next().unwrap();
+                    assert!(
+                        translated.contains(&format!("{{{}}}", name)),
+                        "{} {} lost {{{}}}",
+                        locale,
+                        key,
+                        name
+                    );
+                }
+            }
+        }
+        for locale in Locale::ALL {
+            for (key, _) in locale.catalog() {
+                assert!(
+                    lookup(EN, key).is_some(),
+                    "{} has unknown key {}",
+                    locale,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_and_fallback() {
+        assert_eq!(
+            format(
+                Locale::De,
+                "edition.requires_premium",
+                &[("feature", "Autofix")]
+            ),
+            "Autofix erfordert CostPilot Premium"
+        );
+        assert_eq!(text(Locale::Ja, "no.such.key"), "no.such.key");
+
+        assert_eq!(
+            locale_from_yaml_with_profile(
+                "locale: en\nprofiles:\n  audit:\n    locale: ja-JP\n",
+                Some("audit")
+            )
+            .unwrap(),
+            Some(Locale::Ja)
+        );
+        assert_eq!(
+            locale_from_yaml_with_profile("version: 1\n", None).unwrap(),
+            None
+        );
+        let err = locale_from_yaml_with_profile("locale: fr\n", None).unwrap_err();
+        assert!(err.message.contains("expected one of: en, de, ja"));
+    }
+}
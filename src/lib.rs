@@ -5,28 +5,41 @@ include!(concat!(env!("OUT_DIR"), "/keys.rs"));
 
 pub mod artifact;
 pub mod cli;
+pub mod client;
 pub mod config;
 pub mod edition;
 pub mod engines;
 pub mod errors;
 pub mod feature_flags;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ffi;
 pub mod heuristics;
+pub mod i18n;
 pub mod license_issuer;
+pub mod logging;
+pub mod migrations;
 pub mod pro_engine;
 pub mod security;
 pub mod validation;
 pub mod wasm;
 pub mod zero_cost_guard;
 
-pub use config::{load_product_spec, load_product_spec_from_path, ConfigError, ProductSpec};
+pub use client::{AnalysisResult, CostPilot, CostPilotBuilder};
+pub use config::{
+    load_product_spec, load_product_spec_from_path, load_product_spec_with_profile, ConfigError,
+    ProductSpec,
+};
 pub use engines::shared::models::*;
-pub use security::{SandboxLimits, SecurityValidator};
+pub use security::{lint_iam_policies, SandboxLimits, SecurityValidator};
 pub use validation::{
     validate_file, BaselinesValidator, ConfigValidator, PolicyValidator, SloValidator,
     ValidationError, ValidationReport, ValidationWarning,
 };
 pub use wasm::{EngineBudget, SandboxLimits as WasmSandboxLimits, ValidationResult};
-pub use zero_cost_guard::{ZeroCostGuard, ZeroCostViolation};
+pub use zero_cost_guard::{
+    EnvironmentRules, GuardEnforcement, ResourceGateViolation, ResourcePattern, ResourceRule,
+    ZeroCostConfig, ZeroCostGuard, ZeroCostViolation,
+};
 
 /// CostPilot version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -67,6 +80,7 @@ pub mod test_helpers {
                 },
                 pro: Some(stub_handle),
                 paths: crate::edition::EditionPaths::default(),
+                org_policy: None,
             }
         }
     }
@@ -51,8 +51,24 @@ pub fn generate_license(
     base_dir: &Path,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let email = matches.get_one::<String>("email").unwrap();
-    let license_key = matches.get_one::<String>("license-key").unwrap();
-    let expires = matches.get_one::<String>("expires").unwrap();
+    // Callers building their own `ArgMatches` may not define `--trial`
+    let trial = matches
+        .try_get_one::<bool>("trial")
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false);
+    let (license_key, expires) = if trial {
+        trial_terms(
+            matches.get_one::<String>("license-key").map(String::as_str),
+            chrono::Utc::now(),
+        )
+    } else {
+        (
+            matches.get_one::<String>("license-key").unwrap().clone(),
+            matches.get_one::<String>("expires").unwrap().clone(),
+        )
+    };
     let issuer = matches
         .get_one::<String>("issuer")
         .cloned()
@@ -91,6 +107,13 @@ pub fn generate_license(
     fs::write(&output_path, serde_json::to_string_pretty(&license)?)?;
 
     println!("License generated successfully: {}", output_path.display());
+    if trial {
+        println!(
+            "Trial license: {} days, expires {}",
+            crate::edition::TRIAL_DAYS,
+            expires
+        );
+    }
     println!(
         "Key fingerprint: {}",
         hex::encode(&signing_key.verifying_key().to_bytes()[..8])
@@ -98,3 +121,93 @@ pub fn generate_license(
 
     Ok(())
 }
+
+/// License key and expiry of a trial starting at `now`
+///
+/// The key gets the trial prefix, which the signature then covers; without
+/// a key a random one is generated.
+fn trial_terms(license_key: Option<&str>, now: chrono::DateTime<chrono::Utc>) -> (String, String) {
+    use crate::edition::{TRIAL_DAYS, TRIAL_KEY_PREFIX};
+
+    let license_key = match license_key {
+        Some(key) if key.starts_with(TRIAL_KEY_PREFIX) => key.to_string(),
+        Some(key) => format!("{}{}", TRIAL_KEY_PREFIX, key),
+        None => {
+            let mut random = [0u8; 12];
+            OsRng.fill_bytes(&mut random);
+            format!("{}{}", TRIAL_KEY_PREFIX, hex::encode(random).to_uppercase())
+        }
+    };
+    let expires = (now + chrono::Duration::days(TRIAL_DAYS))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    (license_key, expires)
+}
+
+pub fn sign_capability_policy(
+    matches: &ArgMatches,
+    base_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let policy_path = base_dir.join(matches.get_one::<String>("policy").unwrap());
+    let private_key_path = base_dir.join(matches.get_one::<String>("private-key").unwrap());
+    let output_path = base_dir.join(matches.get_one::<String>("output").unwrap());
+
+    let mut policy = crate::edition::CapabilityPolicy::load(&policy_path)?;
+
+    // Load private key (raw bytes)
+    let key_data = fs::read(private_key_path)?;
+    let key_bytes: [u8; 32] = key_data
+        .try_into()
+        .map_err(|_| "Invalid key length: expected 32 bytes")?;
+    let signing_key = SigningKey::from_bytes(&key_bytes);
+
+    let signature = signing_key.sign(policy.signing_message().as_bytes());
+    policy.signature = hex::encode(signature.to_bytes());
+
+    fs::write(&output_path, serde_json::to_string_pretty(&policy)?)?;
+
+    println!(
+        "Capability policy signed for license {}: {}",
+        policy.license_key,
+        output_path.display()
+    );
+    println!("Rules: {}", policy.rules.len());
+
+    Ok(())
+}
+
+pub fn verify_usage_receipt(
+    matches: &ArgMatches,
+    base_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let receipt_path = base_dir.join(matches.get_one::<String>("receipt").unwrap());
+    let license_key = matches.get_one::<String>("license-key").unwrap();
+
+    let content = fs::read_to_string(&receipt_path)?;
+    let receipt: crate::engines::metering::UsageReceipt = serde_json::from_str(&content)?;
+    receipt.verify(license_key)?;
+
+    println!("Receipt signature valid: {}", receipt_path.display());
+    print!("{}", receipt.format_text());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trial_terms() {
+        let now = chrono::DateTime::parse_from_rfc3339("2025-03-01T09:30:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let (key, expires) = trial_terms(Some("ACME-1"), now);
+        assert_eq!(key, "TRIAL-ACME-1");
+        assert_eq!(expires, "2025-03-15T09:30:00Z");
+
+        assert_eq!(trial_terms(Some("TRIAL-ACME-1"), now).0, "TRIAL-ACME-1");
+        let (generated, _) = trial_terms(None, now);
+        assert!(generated.starts_with(crate::edition::TRIAL_KEY_PREFIX));
+        assert_eq!(generated.len(), "TRIAL-".len() + 24);
+    }
+}
@@ -0,0 +1,122 @@
+// Logging - structured diagnostics through `tracing`
+//
+// Engines log under their module path (`costpilot::engines::prediction`,
+// `costpilot::engines::detection`, ...), so one engine can be turned up with
+// `COSTPILOT_LOG=costpilot::engines::prediction=trace` without touching the
+// rest. `-v`, `-vv` and `-vvv` raise the level for all of CostPilot while
+// dependencies stay at warnings. Logs always go to stderr, keeping stdout
+// parseable for `--format json`.
+
+use crate::engines::shared::error_model::CostPilotError;
+use tracing::Level;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable holding filter directives that replace the
+/// verbosity flags (`tracing_subscriber::EnvFilter` syntax)
+pub const LOG_ENV_VAR: &str = "COSTPILOT_LOG";
+
+/// Environment variable selecting the log format
+pub const LOG_FORMAT_ENV_VAR: &str = "COSTPILOT_LOG_FORMAT";
+
+/// How log lines are written to stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+/// Level for CostPilot's own events at a `-v` count
+pub fn level_for_verbosity(verbosity: u8) -> Level {
+    match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// Filter directives: `COSTPILOT_LOG` when set, otherwise warnings from
+/// everything and the verbosity level for CostPilot
+pub fn filter_directives(verbosity: u8, env: Option<&str>) -> String {
+    match env.map(str::trim).filter(|d| !d.is_empty()) {
+        Some(directives) => directives.to_string(),
+        None => format!(
+            "warn,costpilot={}",
+            level_for_verbosity(verbosity).as_str().to_lowercase()
+        ),
+    }
+}
+
+/// Install the global subscriber. Span close events carry their busy and
+/// idle time from `-vv` on. An invalid `COSTPILOT_LOG` falls back to the
+/// verbosity flags and is returned as an error after logging is set up.
+pub fn init(verbosity: u8, format: LogFormat) -> Result<(), CostPilotError> {
+    let env = std::env::var(LOG_ENV_VAR).ok();
+    let (filter, invalid) = match EnvFilter::try_new(filter_directives(verbosity, env.as_deref())) {
+        Ok(filter) => (filter, None),
+        Err(e) => (
+            EnvFilter::new(filter_directives(verbosity, None)),
+            Some(CostPilotError::config_error(format!(
+                "Ignoring invalid {}: {}",
+                LOG_ENV_VAR, e
+            ))),
+        ),
+    };
+    let span_events = if verbosity >= 2 {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_span_events(span_events);
+
+    // A subscriber already installed (e.g. by an embedding program) wins
+    let _ = match format {
+        LogFormat::Json => builder.json().flatten_event(true).try_init(),
+        LogFormat::Text if verbosity == 0 => builder
+            .without_time()
+            .with_target(false)
+            .with_ansi(atty::is(atty::Stream::Stderr))
+            .try_init(),
+        LogFormat::Text => builder
+            .with_timer(tracing_subscriber::fmt::time::uptime())
+            .with_ansi(atty::is(atty::Stream::Stderr))
+            .try_init(),
+    };
+
+    match invalid {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_for_verbosity() {
+        assert_eq!(level_for_verbosity(0), Level::WARN);
+        assert_eq!(level_for_verbosity(1), Level::INFO);
+        assert_eq!(level_for_verbosity(2), Level::DEBUG);
+        assert_eq!(level_for_verbosity(5), Level::TRACE);
+    }
+
+    #[test]
+    fn test_filter_directives_prefer_environment() {
+        assert_eq!(filter_directives(0, None), "warn,costpilot=warn");
+        assert_eq!(filter_directives(2, Some("  ")), "warn,costpilot=debug");
+        assert_eq!(
+            filter_directives(0, Some("costpilot::engines::prediction=trace")),
+            "costpilot::engines::prediction=trace"
+        );
+        assert!(EnvFilter::try_new(filter_directives(3, None)).is_ok());
+    }
+}
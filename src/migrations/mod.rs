@@ -0,0 +1,754 @@
+// Numbered migrations of long-lived local state
+//
+// Reading an older artifact already works through `engines::shared::compat`,
+// but only in memory: trend history keeps the layout it was written with and
+// every later version has to replay the whole chain. Migrations rewrite the
+// stores on disk once, in id order, and record what ran in a ledger.
+//
+// A run is a transaction. Every pending migration is applied to in-memory
+// copies of the store files, so a failing step writes nothing. The originals
+// are then copied to `.costpilot/backups/migrations-<timestamp>/` before the
+// new contents (and the ledger) are swapped in; if any write fails the backup
+// is restored. Migrations are idempotent, so a store shared between projects
+// (the usage log lives in the user's home directory) can be migrated from each.
+
+mod steps;
+
+use crate::engines::shared::compat::ENGINE_VERSION;
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use crate::security::at_rest::{AtRestCipher, StoreEncryption};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub use steps::MIGRATIONS;
+
+/// Directory holding the ledger and migration backups
+pub const STATE_DIR: &str = ".costpilot";
+
+/// Ledger of applied migrations, relative to the state directory
+const LEDGER_FILE: &str = "migrations.json";
+
+/// Backups of migrated files, relative to the state directory
+const BACKUPS_DIR: &str = "backups";
+
+/// Backup manifest inside each backup directory
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Store a migration rewrites
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Store {
+    Snapshots,
+    Baselines,
+    AuditLog,
+    UsageLog,
+}
+
+impl Store {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Store::Snapshots => "snapshots",
+            Store::Baselines => "baselines",
+            Store::AuditLog => "audit_log",
+            Store::UsageLog => "usage_log",
+        }
+    }
+}
+
+/// One numbered migration
+pub struct Migration {
+    pub id: u32,
+    pub name: &'static str,
+    pub store: Store,
+    pub description: &'static str,
+    apply: fn(&mut MigrationContext) -> Result<()>,
+}
+
+impl Migration {
+    /// `0003-audit-log-anchor`
+    pub fn label(&self) -> String {
+        format!("{:04}-{}", self.id, self.name)
+    }
+}
+
+/// Locations of the stores a run migrates
+#[derive(Debug, Clone)]
+pub struct StorePaths {
+    pub state_dir: PathBuf,
+    pub snapshots_dir: PathBuf,
+    pub baselines: PathBuf,
+    pub audit_log: PathBuf,
+    pub usage_log: Option<PathBuf>,
+}
+
+impl StorePaths {
+    /// Default locations of the current project and the local usage log
+    pub fn project() -> Self {
+        Self {
+            state_dir: PathBuf::from(STATE_DIR),
+            snapshots_dir: PathBuf::from(".costpilot/snapshots"),
+            baselines: PathBuf::from("baselines.json"),
+            audit_log: PathBuf::from(crate::cli::commands::audit::AUDIT_LOG_PATH),
+            usage_log: crate::cli::usage::usage_log_path().ok(),
+        }
+    }
+
+    fn ledger(&self) -> PathBuf {
+        self.state_dir.join(LEDGER_FILE)
+    }
+
+    /// Existing files of every store, sorted
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        if self.snapshots_dir.is_dir() {
+            let entries = std::fs::read_dir(&self.snapshots_dir).map_err(|e| {
+                CostPilotError::io_error(format!(
+                    "Failed to read {}: {}",
+                    self.snapshots_dir.display(),
+                    e
+                ))
+            })?;
+            let mut snapshots: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_file() && is_snapshot_file(path))
+                .collect();
+            snapshots.sort();
+            files.extend(snapshots);
+        }
+        let audit_anchor = crate::cli::commands::audit::anchor_path(&self.audit_log);
+        for path in [&self.baselines, &self.audit_log, &audit_anchor] {
+            if path.is_file() {
+                files.push(path.clone());
+            }
+        }
+        if let Some(usage_log) = self.usage_log.as_ref().filter(|p| p.is_file()) {
+            files.push(usage_log.clone());
+        }
+        Ok(files)
+    }
+}
+
+fn is_snapshot_file(path: &Path) -> bool {
+    path.extension().and_then(|s| s.to_str()) == Some("json")
+        && path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("snapshot_"))
+}
+
+/// In-memory copies of the store files; writes are staged until commit
+#[derive(Debug, Default)]
+pub struct StagedFiles {
+    original: BTreeMap<PathBuf, String>,
+    staged: BTreeMap<PathBuf, String>,
+    touched: Vec<PathBuf>,
+}
+
+impl StagedFiles {
+    fn load(paths: &[PathBuf]) -> Result<Self> {
+        let mut files = Self::default();
+        for path in paths {
+            let contents = std::fs::read_to_string(path).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+            })?;
+            files.original.insert(path.clone(), contents);
+        }
+        Ok(files)
+    }
+
+    /// Current contents of `path`, including earlier staged writes
+    pub fn read(&self, path: &Path) -> Option<&str> {
+        self.staged
+            .get(path)
+            .or_else(|| self.original.get(path))
+            .map(String::as_str)
+    }
+
+    /// Loaded files accepted by `filter`
+    pub fn paths(&self, filter: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+        self.original
+            .keys()
+            .filter(|path| filter(path))
+            .cloned()
+            .collect()
+    }
+
+    /// Stage `contents` for `path`; unchanged contents are not staged
+    pub fn write(&mut self, path: &Path, contents: String) {
+        if self.read(path) == Some(contents.as_str()) {
+            return;
+        }
+        self.staged.insert(path.to_path_buf(), contents);
+        if !self.touched.iter().any(|p| p == path) {
+            self.touched.push(path.to_path_buf());
+        }
+    }
+
+    fn take_touched(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.touched)
+    }
+}
+
+/// What a migration step gets to work with
+pub struct MigrationContext<'a> {
+    pub paths: &'a StorePaths,
+    pub files: &'a mut StagedFiles,
+    /// Opens encrypted records and re-encrypts records that were encrypted
+    pub encryption: &'a StoreEncryption,
+}
+
+impl MigrationContext<'_> {
+    /// Plaintext of one stored record and whether it was encrypted
+    pub fn open(&self, record: &str) -> Result<(String, bool)> {
+        let encrypted = crate::security::at_rest::is_encrypted(record);
+        Ok((self.encryption.open(record)?, encrypted))
+    }
+
+    /// Record to store for `plaintext`, encrypted again if it was before
+    pub fn seal(&self, plaintext: String, encrypted: bool) -> Result<String> {
+        if encrypted {
+            self.encryption.seal(&plaintext)
+        } else {
+            Ok(plaintext)
+        }
+    }
+}
+
+/// Entry in the migration ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    pub id: u32,
+    pub name: String,
+    pub applied_at: String,
+    pub produced_by: String,
+}
+
+/// Migrations already applied to this project's stores
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    pub applied: Vec<AppliedMigration>,
+}
+
+impl Ledger {
+    fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&contents).map_err(|e| {
+            CostPilotError::serialization_error(format!(
+                "Invalid migration ledger {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    pub fn is_applied(&self, id: u32) -> bool {
+        self.applied.iter().any(|m| m.id == id)
+    }
+}
+
+/// One migration applied (or, in a dry run, to be applied) by a run
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedStep {
+    pub id: u32,
+    pub name: &'static str,
+    pub store: Store,
+    pub files: Vec<PathBuf>,
+}
+
+/// Outcome of a run
+#[derive(Debug, Default, Serialize)]
+pub struct MigrationReport {
+    pub dry_run: bool,
+    pub applied: Vec<AppliedStep>,
+    /// Backup of the files the run replaced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<PathBuf>,
+}
+
+impl MigrationReport {
+    pub fn files_changed(&self) -> usize {
+        let mut files: Vec<&PathBuf> = self.applied.iter().flat_map(|s| &s.files).collect();
+        files.sort();
+        files.dedup();
+        files.len()
+    }
+}
+
+/// File replaced by a run, as recorded in the backup manifest
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupEntry {
+    original: PathBuf,
+    /// Copy inside the backup directory; `None` for files the run created
+    backup: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupManifest {
+    created_at: String,
+    produced_by: String,
+    migrations: Vec<u32>,
+    files: Vec<BackupEntry>,
+}
+
+/// Applies pending migrations to a set of stores
+pub struct Migrator {
+    paths: StorePaths,
+    encryption: StoreEncryption,
+}
+
+impl Migrator {
+    pub fn new(paths: StorePaths, encryption: StoreEncryption) -> Self {
+        Self { paths, encryption }
+    }
+
+    /// Project stores, keyed from the installed license for encrypted records
+    pub fn project() -> Self {
+        let encryption = match AtRestCipher::installed() {
+            Some(cipher) => StoreEncryption::with_cipher(cipher, true),
+            None => StoreEncryption::disabled(),
+        };
+        Self::new(StorePaths::project(), encryption)
+    }
+
+    pub fn ledger(&self) -> Result<Ledger> {
+        Ledger::load(&self.paths.ledger())
+    }
+
+    /// Migrations not yet recorded in the ledger, in id order
+    pub fn pending(&self) -> Result<Vec<&'static Migration>> {
+        let ledger = self.ledger()?;
+        Ok(MIGRATIONS
+            .iter()
+            .filter(|m| !ledger.is_applied(m.id))
+            .collect())
+    }
+
+    /// Apply every pending migration, or report what would change
+    ///
+    /// Nothing is written when there are no stores yet: they will be created
+    /// in the current layout.
+    pub fn run(&self, dry_run: bool) -> Result<MigrationReport> {
+        let mut ledger = self.ledger()?;
+        let pending = self.pending()?;
+        let mut report = MigrationReport {
+            dry_run,
+            ..Default::default()
+        };
+        let store_files = self.paths.files()?;
+        if pending.is_empty() || store_files.is_empty() {
+            return Ok(report);
+        }
+
+        let mut files = StagedFiles::load(&store_files)?;
+        for migration in &pending {
+            let mut context = MigrationContext {
+                paths: &self.paths,
+                files: &mut files,
+                encryption: &self.encryption,
+            };
+            (migration.apply)(&mut context).map_err(|e| {
+                CostPilotError::new(
+                    "MIGRATE_001",
+                    ErrorCategory::ValidationError,
+                    format!("Migration {} failed: {}", migration.label(), e.message),
+                )
+                .with_hint("No stored files were changed; fix or remove the file named above")
+            })?;
+            report.applied.push(AppliedStep {
+                id: migration.id,
+                name: migration.name,
+                store: migration.store,
+                files: files.take_touched(),
+            });
+        }
+        if dry_run {
+            return Ok(report);
+        }
+
+        let applied_at = chrono::Utc::now().to_rfc3339();
+        for migration in &pending {
+            ledger.applied.push(AppliedMigration {
+                id: migration.id,
+                name: migration.name.to_string(),
+                applied_at: applied_at.clone(),
+                produced_by: ENGINE_VERSION.to_string(),
+            });
+        }
+        let ledger_json = serde_json::to_string_pretty(&ledger)
+            .map_err(|e| CostPilotError::serialization_error(e.to_string()))?;
+        files.write(&self.paths.ledger(), ledger_json);
+
+        // Only the ledger changes when no step touched a store: nothing to back up
+        if report.files_changed() == 0 {
+            commit(&files).map_err(|e| {
+                CostPilotError::new(
+                    "MIGRATE_002",
+                    ErrorCategory::IoError,
+                    format!("Failed to write the migration ledger: {}", e.message),
+                )
+            })?;
+            return Ok(report);
+        }
+
+        let ids = pending.iter().map(|m| m.id).collect();
+        let backup = backup(&self.paths.state_dir, &files, ids)?;
+        if let Err(e) = commit(&files) {
+            return Err(match restore(&backup) {
+                Ok(_) => CostPilotError::new(
+                    "MIGRATE_002",
+                    ErrorCategory::IoError,
+                    format!("Failed to write migrated stores: {}", e.message),
+                )
+                .with_hint(format!(
+                    "The original files were restored from {}",
+                    backup.display()
+                )),
+                Err(restore_error) => CostPilotError::new(
+                    "MIGRATE_003",
+                    ErrorCategory::IoError,
+                    format!(
+                        "Failed to write migrated stores ({}) and to restore them ({})",
+                        e.message, restore_error.message
+                    ),
+                )
+                .with_hint(format!(
+                    "Run `costpilot storage migrate --rollback {}`",
+                    backup.display()
+                )),
+            });
+        }
+        report.backup = Some(backup);
+        Ok(report)
+    }
+}
+
+/// Copy every file about to be replaced into a new backup directory
+fn backup(state_dir: &Path, files: &StagedFiles, migrations: Vec<u32>) -> Result<PathBuf> {
+    let now = chrono::Utc::now();
+    let mut dir = state_dir
+        .join(BACKUPS_DIR)
+        .join(format!("migrations-{}", now.format("%Y%m%dT%H%M%S")));
+    let mut suffix = 1;
+    while dir.exists() {
+        suffix += 1;
+        dir = state_dir.join(BACKUPS_DIR).join(format!(
+            "migrations-{}-{}",
+            now.format("%Y%m%dT%H%M%S"),
+            suffix
+        ));
+    }
+    let io_error = |path: &Path, e: std::io::Error| {
+        CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| io_error(&dir, e))?;
+
+    let mut entries = Vec::new();
+    for (index, path) in files.staged.keys().enumerate() {
+        let backup = match files.original.get(path) {
+            Some(contents) => {
+                let name = format!(
+                    "{:03}-{}",
+                    index,
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                );
+                let target = dir.join(&name);
+                std::fs::write(&target, contents).map_err(|e| io_error(&target, e))?;
+                Some(name)
+            }
+            // The ledger may predate this run without having been loaded
+            None if path.exists() => {
+                let name = format!("{:03}-{}", index, LEDGER_FILE);
+                let target = dir.join(&name);
+                std::fs::copy(path, &target).map_err(|e| io_error(&target, e))?;
+                Some(name)
+            }
+            None => None,
+        };
+        entries.push(BackupEntry {
+            original: absolute(path),
+            backup,
+        });
+    }
+
+    let manifest = BackupManifest {
+        created_at: now.to_rfc3339(),
+        produced_by: ENGINE_VERSION.to_string(),
+        migrations,
+        files: entries,
+    };
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| CostPilotError::serialization_error(e.to_string()))?;
+    std::fs::write(&manifest_path, json).map_err(|e| io_error(&manifest_path, e))?;
+    Ok(dir)
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Swap in every staged file through a temporary sibling
+fn commit(files: &StagedFiles) -> Result<()> {
+    for (path, contents) in &files.staged {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                CostPilotError::io_error(format!("Failed to create {}: {}", parent.display(), e))
+            })?;
+        }
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".migrating");
+        let temp = PathBuf::from(temp);
+        std::fs::write(&temp, contents)
+            .and_then(|_| std::fs::rename(&temp, path))
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&temp);
+                CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+            })?;
+    }
+    Ok(())
+}
+
+/// Put back the files saved in a backup directory; returns the files restored
+///
+/// Files the migration run created are removed, including the ledger entry
+/// of a first run, so the migrations are pending again afterwards.
+pub fn restore(backup_dir: &Path) -> Result<usize> {
+    let manifest_path = backup_dir.join(MANIFEST_FILE);
+    let manifest: BackupManifest = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", manifest_path.display(), e))
+        })
+        .and_then(|contents| {
+            serde_json::from_str(&contents).map_err(|e| {
+                CostPilotError::serialization_error(format!(
+                    "Invalid backup manifest {}: {}",
+                    manifest_path.display(),
+                    e
+                ))
+            })
+        })?;
+
+    for entry in &manifest.files {
+        let result = match &entry.backup {
+            Some(name) => std::fs::copy(backup_dir.join(name), &entry.original).map(|_| ()),
+            None if entry.original.exists() => std::fs::remove_file(&entry.original),
+            None => Ok(()),
+        };
+        result.map_err(|e| {
+            CostPilotError::io_error(format!(
+                "Failed to restore {}: {}",
+                entry.original.display(),
+                e
+            ))
+        })?;
+    }
+    Ok(manifest.files.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::metering::{PricingModel, UsageMeter};
+    use crate::engines::policy::{AuditEvent, AuditEventType, AuditLog};
+    use tempfile::TempDir;
+
+    struct Fixture {
+        _dir: TempDir,
+        paths: StorePaths,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            let root = dir.path();
+            let paths = StorePaths {
+                state_dir: root.join(".costpilot"),
+                snapshots_dir: root.join(".costpilot/snapshots"),
+                baselines: root.join("baselines.json"),
+                audit_log: root.join(".costpilot/audit_log.json"),
+                usage_log: Some(root.join("usage_events.ndjson")),
+            };
+            std::fs::create_dir_all(&paths.snapshots_dir).unwrap();
+            Self { _dir: dir, paths }
+        }
+
+        fn migrator(&self) -> Migrator {
+            Migrator::new(self.paths.clone(), StoreEncryption::disabled())
+        }
+
+        fn snapshot(&self) -> PathBuf {
+            self.paths.snapshots_dir.join("snapshot_001.json")
+        }
+
+        /// One file per store in the oldest layout each migration handles
+        fn write_legacy_stores(&self) {
+            std::fs::write(
+                self.snapshot(),
+                r#"{"id":"001","timestamp":"2024-01-01T00:00:00Z","total_monthly_cost":100.0,
+                   "modules":{},"services":{}}"#,
+            )
+            .unwrap();
+            std::fs::write(
+                &self.paths.baselines,
+                r#"{"modules":{},"services":{},"global":null}"#,
+            )
+            .unwrap();
+
+            let mut log = AuditLog::new();
+            log.append(AuditEvent::new(
+                AuditEventType::PolicyActivated,
+                "alice".to_string(),
+                "policy-1".to_string(),
+                "cost_policy".to_string(),
+                "Policy activated".to_string(),
+            ))
+            .unwrap();
+            let mut value = serde_json::to_value(&log).unwrap();
+            value.as_object_mut().unwrap().remove("genesis_hash");
+            std::fs::write(&self.paths.audit_log, value.to_string()).unwrap();
+
+            std::fs::write(
+                self.paths.usage_log.as_ref().unwrap(),
+                concat!(
+                    r#"{"event_id":"e1","timestamp":1,"event_type":"Scan","#,
+                    r#""attribution":{"user_id":"u","team_id":null,"org_id":null,"cost_center":null,"project_id":null},"#,
+                    r#""resources_analyzed":1,"cost_impact":2.0,"duration_ms":3,"#,
+                    r#""context":{"repository":"r","branch":null,"commit":null,"pr_number":null,"#,
+                    r#""ci_system":null,"environment":null}}"#,
+                    "\n\n"
+                ),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_migrations_are_numbered_in_order() {
+        let ids: Vec<u32> = MIGRATIONS.iter().map(|m| m.id).collect();
+        let expected: Vec<u32> = (1..=MIGRATIONS.len() as u32).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn test_run_migrates_every_store_and_records_ledger() {
+        let fixture = Fixture::new();
+        fixture.write_legacy_stores();
+        let migrator = fixture.migrator();
+
+        let report = migrator.run(false).unwrap();
+        assert_eq!(report.applied.len(), MIGRATIONS.len());
+        assert!(report.backup.as_ref().unwrap().join(MANIFEST_FILE).exists());
+
+        let snapshot: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(fixture.snapshot()).unwrap()).unwrap();
+        assert_eq!(
+            snapshot["schema_version"],
+            crate::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION
+        );
+        let baselines: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&fixture.paths.baselines).unwrap())
+                .unwrap();
+        assert_eq!(baselines["version"], "1.0");
+
+        let log =
+            crate::cli::commands::audit::load_audit_log(Some(fixture.paths.audit_log.clone()))
+                .unwrap();
+        log.verify_chain().unwrap();
+        assert!(crate::cli::commands::audit::anchor_path(&fixture.paths.audit_log).exists());
+
+        let usage = UsageMeter::load_from_file_with(
+            fixture.paths.usage_log.as_ref().unwrap(),
+            PricingModel::default(),
+            &StoreEncryption::disabled(),
+        )
+        .unwrap();
+        assert_eq!(usage.events_between(0, u64::MAX).len(), 1);
+
+        assert!(migrator.pending().unwrap().is_empty());
+        assert!(migrator.run(false).unwrap().applied.is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        let fixture = Fixture::new();
+        fixture.write_legacy_stores();
+        let before = std::fs::read_to_string(fixture.snapshot()).unwrap();
+
+        let report = fixture.migrator().run(true).unwrap();
+        assert!(report.files_changed() > 0);
+        assert!(report.backup.is_none());
+        assert_eq!(std::fs::read_to_string(fixture.snapshot()).unwrap(), before);
+        assert!(!fixture.paths.ledger().exists());
+    }
+
+    #[test]
+    fn test_failing_migration_leaves_stores_untouched() {
+        let fixture = Fixture::new();
+        fixture.write_legacy_stores();
+        let snapshot_before = std::fs::read_to_string(fixture.snapshot()).unwrap();
+        // A tampered chain must not be anchored
+        let tampered = std::fs::read_to_string(&fixture.paths.audit_log)
+            .unwrap()
+            .replace("alice", "mallory");
+        std::fs::write(&fixture.paths.audit_log, tampered).unwrap();
+
+        let err = fixture.migrator().run(false).unwrap_err();
+        assert_eq!(err.id, "MIGRATE_001");
+        assert_eq!(
+            std::fs::read_to_string(fixture.snapshot()).unwrap(),
+            snapshot_before
+        );
+        assert!(!fixture.paths.ledger().exists());
+        assert!(!fixture.paths.state_dir.join(BACKUPS_DIR).exists());
+    }
+
+    #[test]
+    fn test_restore_rolls_back_a_run() {
+        let fixture = Fixture::new();
+        fixture.write_legacy_stores();
+        let snapshot_before = std::fs::read_to_string(fixture.snapshot()).unwrap();
+        let migrator = fixture.migrator();
+        let backup = migrator.run(false).unwrap().backup.unwrap();
+
+        restore(&backup).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(fixture.snapshot()).unwrap(),
+            snapshot_before
+        );
+        assert!(!crate::cli::commands::audit::anchor_path(&fixture.paths.audit_log).exists());
+        assert_eq!(migrator.pending().unwrap().len(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_ledger_only_run_leaves_no_backup() {
+        let fixture = Fixture::new();
+        fixture.write_legacy_stores();
+        let migrator = fixture.migrator();
+        migrator.run(false).unwrap();
+        // Stores already current, but no ledger records it
+        std::fs::remove_file(fixture.paths.ledger()).unwrap();
+        std::fs::remove_dir_all(fixture.paths.state_dir.join(BACKUPS_DIR)).unwrap();
+
+        let report = migrator.run(false).unwrap();
+        assert_eq!(report.applied.len(), MIGRATIONS.len());
+        assert_eq!(report.files_changed(), 0);
+        assert!(report.backup.is_none());
+        assert!(fixture.paths.ledger().exists());
+        assert!(!fixture.paths.state_dir.join(BACKUPS_DIR).exists());
+    }
+
+    #[test]
+    fn test_no_stores_writes_no_ledger() {
+        let fixture = Fixture::new();
+        let report = fixture.migrator().run(false).unwrap();
+        assert!(report.applied.is_empty());
+        assert!(!fixture.paths.ledger().exists());
+    }
+}
@@ -0,0 +1,180 @@
+// The numbered migrations, oldest first
+//
+// Ids are never reused or renumbered: the ledger of every project refers to
+// them. Each step must be idempotent and leave files it does not need to
+// change byte-for-byte alone.
+
+use super::{is_snapshot_file, Migration, MigrationContext, Store};
+use crate::cli::commands::audit::anchor_path;
+use crate::engines::policy::AuditLog;
+use crate::engines::shared::compat::{self, Artifact};
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        id: 1,
+        name: "snapshots-current-layout",
+        store: Store::Snapshots,
+        description: "Rewrite trend snapshots in the current schema, stamped with their version",
+        apply: snapshots_current_layout,
+    },
+    Migration {
+        id: 2,
+        name: "baselines-current-layout",
+        store: Store::Baselines,
+        description: "Rewrite the baselines file in the current schema, stamped with its version",
+        apply: baselines_current_layout,
+    },
+    Migration {
+        id: 3,
+        name: "audit-log-anchor",
+        store: Store::AuditLog,
+        description: "Add the genesis hash and anchor file to audit logs written before them",
+        apply: audit_log_anchor,
+    },
+    Migration {
+        id: 4,
+        name: "usage-log-defaults",
+        store: Store::UsageLog,
+        description: "Fill in event metadata missing from older usage log entries",
+        apply: usage_log_defaults,
+    },
+];
+
+/// Upgrade one JSON document through the compat chain and stamp it
+///
+/// Documents already current are returned unchanged (`None`), and so are
+/// documents from a newer CostPilot, which must not be stamped as older.
+fn rewrite_current(artifact: Artifact, path: &Path, plaintext: &str) -> Result<Option<String>> {
+    let mut document: Value = serde_json::from_str(plaintext).map_err(|e| {
+        CostPilotError::serialization_error(format!("Invalid {}: {}", path.display(), e))
+    })?;
+    let compatibility = compat::upgrade(artifact, &mut document)?;
+    if compatibility.from_newer_schema() || compatibility.from_newer_build() {
+        return Ok(None);
+    }
+    if compatibility.migrated.is_empty() && compatibility.produced_by.is_some() {
+        return Ok(None);
+    }
+    compat::to_string_pretty(artifact, &document).map(Some)
+}
+
+fn snapshots_current_layout(context: &mut MigrationContext) -> Result<()> {
+    let snapshots_dir = context.paths.snapshots_dir.clone();
+    let snapshots = context
+        .files
+        .paths(|path| path.parent() == Some(snapshots_dir.as_path()) && is_snapshot_file(path));
+    for path in snapshots {
+        let Some(stored) = context.files.read(&path).map(str::to_string) else {
+            continue;
+        };
+        let (plaintext, encrypted) = context.open(&stored)?;
+        if let Some(rewritten) = rewrite_current(Artifact::Snapshot, &path, &plaintext)? {
+            let sealed = context.seal(rewritten, encrypted)?;
+            context.files.write(&path, sealed);
+        }
+    }
+    Ok(())
+}
+
+fn baselines_current_layout(context: &mut MigrationContext) -> Result<()> {
+    let path = context.paths.baselines.clone();
+    let Some(stored) = context.files.read(&path).map(str::to_string) else {
+        return Ok(());
+    };
+    if let Some(rewritten) = rewrite_current(Artifact::Baselines, &path, &stored)? {
+        context.files.write(&path, rewritten);
+    }
+    Ok(())
+}
+
+/// Logs saved before anchoring (or before the genesis hash was stored) only
+/// get the missing pieces; entries and their hashes are never rewritten, and
+/// a chain that does not verify is refused rather than anchored.
+fn audit_log_anchor(context: &mut MigrationContext) -> Result<()> {
+    let path = context.paths.audit_log.clone();
+    let Some(stored) = context.files.read(&path).map(str::to_string) else {
+        return Ok(());
+    };
+    let mut document: Value = serde_json::from_str(&stored).map_err(|e| {
+        CostPilotError::serialization_error(format!("Invalid {}: {}", path.display(), e))
+    })?;
+    let Some(fields) = document.as_object_mut() else {
+        return Err(CostPilotError::serialization_error(format!(
+            "Invalid {}: expected a JSON object",
+            path.display()
+        )));
+    };
+    let add_genesis = !fields.contains_key("genesis_hash");
+    if add_genesis {
+        fields.insert(
+            "genesis_hash".to_string(),
+            Value::String(AuditLog::new().genesis_hash),
+        );
+    }
+
+    let log: AuditLog = serde_json::from_value(document.clone()).map_err(|e| {
+        CostPilotError::serialization_error(format!("Invalid {}: {}", path.display(), e))
+    })?;
+    log.verify_chain().map_err(|e| {
+        CostPilotError::security_violation(format!("{} does not verify: {}", path.display(), e))
+    })?;
+
+    if add_genesis {
+        let json = serde_json::to_string_pretty(&document)
+            .map_err(|e| CostPilotError::serialization_error(e.to_string()))?;
+        context.files.write(&path, json);
+    }
+    let anchor = anchor_path(&path);
+    if context.files.read(&anchor).is_none() {
+        let json = serde_json::to_string_pretty(&log.anchor())
+            .map_err(|e| CostPilotError::serialization_error(e.to_string()))?;
+        context.files.write(&anchor, json);
+    }
+    Ok(())
+}
+
+/// Entries written before `metadata` was recorded fail to load; blank lines
+/// left by interrupted appends are dropped. Encrypted lines stay encrypted.
+fn usage_log_defaults(context: &mut MigrationContext) -> Result<()> {
+    let Some(path) = context.paths.usage_log.clone() else {
+        return Ok(());
+    };
+    let Some(stored) = context.files.read(&path).map(str::to_string) else {
+        return Ok(());
+    };
+
+    let mut lines = Vec::new();
+    let mut changed = false;
+    for (index, line) in stored.lines().enumerate() {
+        if line.trim().is_empty() {
+            changed = true;
+            continue;
+        }
+        let (plaintext, encrypted) = context.open(line)?;
+        let mut event: Map<String, Value> = serde_json::from_str(&plaintext).map_err(|e| {
+            CostPilotError::new(
+                "METER_002",
+                ErrorCategory::InvalidInput,
+                format!("Invalid usage event on line {}: {}", index + 1, e),
+            )
+        })?;
+        if event.get("metadata").and_then(Value::as_object).is_none() {
+            event.insert("metadata".to_string(), Value::Object(Map::new()));
+            let json = serde_json::to_string(&event)
+                .map_err(|e| CostPilotError::serialization_error(e.to_string()))?;
+            lines.push(context.seal(json, encrypted)?);
+            changed = true;
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    if changed {
+        let mut contents = lines.join("\n");
+        contents.push('\n');
+        context.files.write(&path, contents);
+    }
+    Ok(())
+}
@@ -6,6 +6,197 @@ use crate::engines::policy::PolicyResult;
 use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
 use crate::engines::trend::CostSnapshot;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// ABI version implemented by this host; bundles and engines must match it
+pub const PRO_ENGINE_ABI_VERSION: u32 = 2;
+
+/// Oldest engine ABI version this host can still drive
+pub const MIN_SUPPORTED_ABI_VERSION: u32 = 1;
+
+/// Engine export reporting its ABI version: `() -> i32`
+pub const ABI_VERSION_EXPORT: &str = "costpilot_abi_version";
+
+/// Engine export negotiating capabilities: `(ptr, len) -> result`
+pub const NEGOTIATE_EXPORT: &str = "costpilot_negotiate";
+
+/// First ABI version passing data through engine-allocated regions
+pub const ALLOCATOR_ABI_VERSION: u32 = 2;
+
+/// Engine export allocating guest memory: `(size) -> ptr` (ABI v2+)
+pub const ALLOC_EXPORT: &str = "alloc";
+
+/// Engine export freeing guest memory: `(ptr, size)` (ABI v2+)
+pub const DEALLOC_EXPORT: &str = "dealloc";
+
+/// Size of the little-endian u32 length prefix on ABI v2 result regions
+pub const REGION_HEADER_SIZE: usize = 4;
+
+/// Fixed buffer size used by engines that predate the handshake
+pub const LEGACY_BUFFER_SIZE: usize = 4096;
+
+/// Engine functions this host knows how to call
+pub const HOST_CAPABILITIES: &[&str] = &[
+    "predict",
+    "explain",
+    "autofix",
+    "mapdeep",
+    "trend",
+    "enforce",
+    "slo_enforce",
+];
+
+/// Handshake offer sent by the host to `costpilot_negotiate`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeOffer {
+    pub abi_version: u32,
+    pub min_abi_version: u32,
+    pub capabilities: Vec<String>,
+}
+
+impl HandshakeOffer {
+    /// Offer describing this host
+    pub fn host() -> Self {
+        Self {
+            abi_version: PRO_ENGINE_ABI_VERSION,
+            min_abi_version: MIN_SUPPORTED_ABI_VERSION,
+            capabilities: HOST_CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+}
+
+/// Engine reply to the handshake; buffer layout defaults to the legacy one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandshakeReply {
+    pub abi_version: u32,
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub input_offset: usize,
+    #[serde(default = "legacy_buffer_size")]
+    pub input_buffer_size: usize,
+    #[serde(default = "legacy_buffer_size")]
+    pub output_offset: usize,
+    #[serde(default = "legacy_buffer_size")]
+    pub output_buffer_size: usize,
+}
+
+fn legacy_buffer_size() -> usize {
+    LEGACY_BUFFER_SIZE
+}
+
+/// How request and response bytes cross the WASM boundary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiTransport {
+    /// ABI v1: fixed buffers; results pack `(length << 16) | offset`
+    FixedBuffers {
+        input_offset: usize,
+        input_buffer_size: usize,
+        output_offset: usize,
+        output_buffer_size: usize,
+    },
+    /// ABI v2: input goes into memory from the engine's `alloc`; results
+    /// point at a region holding a u32 length followed by the payload
+    Allocator,
+}
+
+/// ABI agreed between host and engine after the handshake
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegotiatedAbi {
+    pub version: u32,
+    pub capabilities: BTreeSet<String>,
+    pub transport: AbiTransport,
+}
+
+impl NegotiatedAbi {
+    /// Engines without a negotiate export: every host capability, fixed
+    /// 4KB buffers with input at offset 0 and output at offset 4096
+    pub fn legacy(version: u32) -> Self {
+        Self {
+            version,
+            capabilities: HOST_CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+            transport: Self::default_transport(version),
+        }
+    }
+
+    /// Transport implied by an ABI version before any negotiation
+    pub fn default_transport(version: u32) -> AbiTransport {
+        if version >= ALLOCATOR_ABI_VERSION {
+            AbiTransport::Allocator
+        } else {
+            AbiTransport::FixedBuffers {
+                input_offset: 0,
+                input_buffer_size: LEGACY_BUFFER_SIZE,
+                output_offset: LEGACY_BUFFER_SIZE,
+                output_buffer_size: LEGACY_BUFFER_SIZE,
+            }
+        }
+    }
+
+    /// Validate an engine reply; capabilities unknown to this host are dropped
+    pub fn from_reply(reply: HandshakeReply) -> Result<Self, String> {
+        check_abi_version(reply.abi_version)?;
+
+        let capabilities: BTreeSet<String> = reply
+            .capabilities
+            .into_iter()
+            .filter(|c| HOST_CAPABILITIES.contains(&c.as_str()))
+            .collect();
+        if capabilities.is_empty() {
+            return Err(
+                "ProEngine offers no capabilities supported by this CostPilot build".to_string(),
+            );
+        }
+
+        if reply.abi_version >= ALLOCATOR_ABI_VERSION {
+            return Ok(Self {
+                version: reply.abi_version,
+                capabilities,
+                transport: AbiTransport::Allocator,
+            });
+        }
+
+        // The packed return value carries length and offset in 16 bits each
+        if reply.output_buffer_size > u16::MAX as usize {
+            return Err(format!(
+                "ProEngine output buffer of {} bytes exceeds the ABI limit of {} bytes",
+                reply.output_buffer_size,
+                u16::MAX
+            ));
+        }
+
+        Ok(Self {
+            version: reply.abi_version,
+            capabilities,
+            transport: AbiTransport::FixedBuffers {
+                input_offset: reply.input_offset,
+                input_buffer_size: reply.input_buffer_size,
+                output_offset: reply.output_offset,
+                output_buffer_size: reply.output_buffer_size,
+            },
+        })
+    }
+
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.contains(capability)
+    }
+}
+
+/// Reject engine builds whose ABI this host cannot drive
+pub fn check_abi_version(engine_version: u32) -> Result<(), String> {
+    if (MIN_SUPPORTED_ABI_VERSION..=PRO_ENGINE_ABI_VERSION).contains(&engine_version) {
+        return Ok(());
+    }
+
+    let action = if engine_version > PRO_ENGINE_ABI_VERSION {
+        "Upgrade CostPilot to use this engine"
+    } else {
+        "Install an engine bundle built for this CostPilot version"
+    };
+    Err(format!(
+        "Incompatible ProEngine ABI: engine implements v{}, host supports v{}-v{}. {}",
+        engine_version, MIN_SUPPORTED_ABI_VERSION, PRO_ENGINE_ABI_VERSION, action
+    ))
+}
 
 /// Request types for ProEngine operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,3 +236,43 @@ pub enum ProEngineResponse {
     PolicyEnforceResult(PolicyResult),
     Error(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_abi_version() {
+        assert!(check_abi_version(PRO_ENGINE_ABI_VERSION).is_ok());
+
+        let err = check_abi_version(PRO_ENGINE_ABI_VERSION + 1).unwrap_err();
+        assert!(err.contains("Upgrade CostPilot"));
+        assert!(check_abi_version(0).is_err());
+    }
+
+    #[test]
+    fn test_reply_negotiation() {
+        let reply: HandshakeReply = serde_json::from_str(
+            r#"{"abi_version":1,"capabilities":["predict","autofix","quantum"]}"#,
+        )
+        .unwrap();
+        let abi = NegotiatedAbi::from_reply(reply).unwrap();
+
+        assert!(abi.supports("predict"));
+        assert!(abi.supports("autofix"));
+        assert!(!abi.supports("quantum"));
+        assert!(!abi.supports("trend"));
+        assert_eq!(abi.transport, NegotiatedAbi::default_transport(1));
+
+        let v2: HandshakeReply =
+            serde_json::from_str(r#"{"abi_version":2,"capabilities":["mapdeep"]}"#).unwrap();
+        assert_eq!(
+            NegotiatedAbi::from_reply(v2).unwrap().transport,
+            AbiTransport::Allocator
+        );
+
+        let unusable: HandshakeReply =
+            serde_json::from_str(r#"{"abi_version":1,"capabilities":["quantum"]}"#).unwrap();
+        assert!(NegotiatedAbi::from_reply(unusable).is_err());
+    }
+}
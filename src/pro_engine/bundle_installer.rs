@@ -0,0 +1,754 @@
+// ProEngine offline update bundle installer
+//
+// An update bundle is a directory shipped alongside a license renewal or engine
+// release. It contains a signed manifest describing every file in the bundle:
+//
+//   bundle/
+//     manifest.json        - BundleManifest (versions + per-file SHA-256)
+//     manifest.sig         - raw 64-byte Ed25519 signature over manifest.json
+//     pro-engine.wasm.enc  - encrypted ProEngine module
+//     pro-engine.sig       - ProEngine WASM signature
+//     pricing/*.json       - optional pricing packs
+//
+// Installation verifies the manifest signature, version/ABI compatibility and
+// file checksums before touching `~/.costpilot/`. Existing files are backed up
+// and each file is swapped in with a same-directory rename so a crash never
+// leaves a half-written engine behind.
+//
+// The signed manifest is installed with its signature so the loader can check
+// the engine against it again before every decryption.
+
+use super::abi::{check_abi_version, PRO_ENGINE_ABI_VERSION};
+use super::pro_loader::{ENGINE_FILE, ENGINE_SIGNATURE_FILE};
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Manifest file name inside a bundle
+pub const BUNDLE_MANIFEST_FILE: &str = "manifest.json";
+
+/// Detached manifest signature file name inside a bundle
+pub const BUNDLE_SIGNATURE_FILE: &str = "manifest.sig";
+
+/// Name of the installed manifest kept next to the engine for version tracking
+pub const INSTALLED_MANIFEST_FILE: &str = "pro-engine.manifest.json";
+
+/// Signature of the installed manifest, checked before the engine is loaded
+pub const INSTALLED_MANIFEST_SIGNATURE_FILE: &str = "pro-engine.manifest.sig";
+
+/// Directory (under the config dir) holding pricing packs
+pub const PRICING_DIR: &str = "pricing";
+
+/// Directory (under the config dir) holding pre-install backups
+pub const BACKUP_DIR: &str = "backups";
+
+#[derive(Debug)]
+pub enum InstallError {
+    Io(String),
+    MissingManifest,
+    InvalidManifest(String),
+    SignatureInvalid,
+    ChecksumMismatch(String),
+    UnsafePath(String),
+    MissingEngine,
+    NotInstalled,
+    IncompatibleVersion { required: String, current: String },
+    IncompatibleAbi { expected: u32, found: u32 },
+}
+
+impl std::fmt::Display for InstallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstallError::Io(e) => write!(f, "I/O error: {}", e),
+            InstallError::MissingManifest => {
+                write!(f, "Bundle manifest or manifest signature not found")
+            }
+            InstallError::InvalidManifest(e) => write!(f, "Invalid bundle manifest: {}", e),
+            InstallError::SignatureInvalid => write!(f, "Bundle signature verification failed"),
+            InstallError::ChecksumMismatch(path) => {
+                write!(f, "Checksum mismatch for bundle file: {}", path)
+            }
+            InstallError::UnsafePath(path) => {
+                write!(f, "Bundle file path escapes install directory: {}", path)
+            }
+            InstallError::MissingEngine => {
+                write!(f, "Bundle does not contain a ProEngine and its signature")
+            }
+            InstallError::NotInstalled => write!(
+                f,
+                "No signed ProEngine manifest installed; install the engine with `costpilot pro install <bundle>`"
+            ),
+            InstallError::IncompatibleVersion { required, current } => write!(
+                f,
+                "Bundle requires costpilot {} or newer (running {})",
+                required, current
+            ),
+            InstallError::IncompatibleAbi { expected, found } => write!(
+                f,
+                "ProEngine ABI version mismatch: expected {}, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InstallError {}
+
+impl From<std::io::Error> for InstallError {
+    fn from(e: std::io::Error) -> Self {
+        InstallError::Io(e.to_string())
+    }
+}
+
+/// Kind of file carried by an update bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleFileKind {
+    Engine,
+    EngineSignature,
+    PricingPack,
+}
+
+/// Single file entry in a bundle manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// Path relative to the bundle root
+    pub path: String,
+    pub kind: BundleFileKind,
+    /// Lowercase hex SHA-256 of the file contents
+    pub sha256: String,
+}
+
+/// Signed description of an update bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub engine_version: String,
+    pub abi_version: u32,
+    pub min_cli_version: String,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    pub files: Vec<BundleFile>,
+}
+
+impl BundleManifest {
+    /// Destination of a bundle file relative to the install directory
+    fn destination(file: &BundleFile) -> PathBuf {
+        match file.kind {
+            BundleFileKind::Engine => PathBuf::from(ENGINE_FILE),
+            BundleFileKind::EngineSignature => PathBuf::from(ENGINE_SIGNATURE_FILE),
+            BundleFileKind::PricingPack => {
+                let name = Path::new(&file.path)
+                    .file_name()
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(&file.path));
+                Path::new(PRICING_DIR).join(name)
+            }
+        }
+    }
+}
+
+/// Outcome of a bundle installation
+#[derive(Debug, Clone)]
+pub struct InstallReport {
+    pub engine_version: String,
+    pub previous_version: Option<String>,
+    pub installed_files: Vec<PathBuf>,
+    pub backup_dir: Option<PathBuf>,
+    pub dry_run: bool,
+}
+
+impl InstallReport {
+    pub fn format_text(&self) -> String {
+        let mut output = String::new();
+        if self.dry_run {
+            output.push_str("🔍 Bundle verified (dry run, nothing installed)\n\n");
+        } else {
+            output.push_str("✅ ProEngine bundle installed\n\n");
+        }
+        output.push_str(&format!("Engine version: {}\n", self.engine_version));
+        if let Some(ref previous) = self.previous_version {
+            output.push_str(&format!("Previous version: {}\n", previous));
+        }
+        output.push_str(&format!("Files: {}\n", self.installed_files.len()));
+        for file in &self.installed_files {
+            output.push_str(&format!("  - {}\n", file.display()));
+        }
+        if let Some(ref backup) = self.backup_dir {
+            output.push_str(&format!("Backup: {}\n", backup.display()));
+        }
+        output
+    }
+}
+
+/// Parsed manifest with the exact bytes its signature covers, and the signature
+type SignedManifest = (BundleManifest, (Vec<u8>, Vec<u8>));
+
+/// Verified bundle: the signed manifest and, in manifest order, the file
+/// contents whose checksums were checked
+struct VerifiedBundle {
+    manifest: BundleManifest,
+    manifest_bytes: Vec<u8>,
+    manifest_signature: Vec<u8>,
+    contents: Vec<Vec<u8>>,
+}
+
+/// Verifies and installs offline ProEngine update bundles
+pub struct BundleInstaller {
+    install_dir: PathBuf,
+    public_key: Vec<u8>,
+}
+
+impl BundleInstaller {
+    /// Create installer targeting `install_dir`, verifying with the build-time WASM key
+    pub fn new(install_dir: PathBuf) -> Self {
+        Self {
+            install_dir,
+            public_key: crate::WASM_PUBLIC_KEY.to_vec(),
+        }
+    }
+
+    /// Override the bundle verification key
+    pub fn with_public_key(mut self, public_key: &[u8]) -> Self {
+        self.public_key = public_key.to_vec();
+        self
+    }
+
+    /// Verify a bundle without installing it
+    pub fn verify(&self, bundle_dir: &Path) -> Result<BundleManifest, InstallError> {
+        self.verify_bundle(bundle_dir).map(|bundle| bundle.manifest)
+    }
+
+    /// Check engine and engine signature bytes against the installed signed
+    /// manifest, before the engine is decrypted
+    pub fn verify_installed(
+        &self,
+        engine: &[u8],
+        engine_signature: &[u8],
+    ) -> Result<BundleManifest, InstallError> {
+        let manifest_path = self.install_dir.join(INSTALLED_MANIFEST_FILE);
+        let signature_path = self.install_dir.join(INSTALLED_MANIFEST_SIGNATURE_FILE);
+        if !manifest_path.exists() || !signature_path.exists() {
+            return Err(InstallError::NotInstalled);
+        }
+        let manifest = self
+            .read_signed_manifest(&manifest_path, &signature_path)?
+            .0;
+
+        for (kind, bytes, name) in [
+            (BundleFileKind::Engine, engine, ENGINE_FILE),
+            (
+                BundleFileKind::EngineSignature,
+                engine_signature,
+                ENGINE_SIGNATURE_FILE,
+            ),
+        ] {
+            let expected = manifest
+                .files
+                .iter()
+                .find(|f| f.kind == kind)
+                .ok_or(InstallError::MissingEngine)?;
+            if !sha256_hex(bytes).eq_ignore_ascii_case(&expected.sha256) {
+                return Err(InstallError::ChecksumMismatch(name.to_string()));
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Verify a bundle, returning its manifest, the signed manifest bytes and
+    /// signature, and the verified file contents to install
+    fn verify_bundle(&self, bundle_dir: &Path) -> Result<VerifiedBundle, InstallError> {
+        let manifest_path = bundle_dir.join(BUNDLE_MANIFEST_FILE);
+        let signature_path = bundle_dir.join(BUNDLE_SIGNATURE_FILE);
+        if !manifest_path.exists() || !signature_path.exists() {
+            return Err(InstallError::MissingManifest);
+        }
+        let (manifest, (manifest_bytes, manifest_signature)) =
+            self.read_signed_manifest(&manifest_path, &signature_path)?;
+
+        check_compatibility(&manifest, crate::VERSION)?;
+
+        let has_engine = manifest
+            .files
+            .iter()
+            .any(|f| f.kind == BundleFileKind::Engine);
+        let has_signature = manifest
+            .files
+            .iter()
+            .any(|f| f.kind == BundleFileKind::EngineSignature);
+        if !has_engine || !has_signature {
+            return Err(InstallError::MissingEngine);
+        }
+
+        // Pricing packs install by file name, so two packs must not share one
+        let mut destinations = std::collections::HashSet::new();
+        for file in &manifest.files {
+            let dest = BundleManifest::destination(file);
+            if !destinations.insert(dest.clone()) {
+                return Err(InstallError::InvalidManifest(format!(
+                    "{} installs to {}, which another bundle file already uses",
+                    file.path,
+                    dest.display()
+                )));
+            }
+        }
+
+        // Keep the hashed bytes: installing re-read files could install
+        // content that changed after verification
+        let mut contents = Vec::with_capacity(manifest.files.len());
+        for file in &manifest.files {
+            let source = safe_join(bundle_dir, &file.path)?;
+            let bytes = fs::read(&source)?;
+            if !sha256_hex(&bytes).eq_ignore_ascii_case(&file.sha256) {
+                return Err(InstallError::ChecksumMismatch(file.path.clone()));
+            }
+            contents.push(bytes);
+        }
+
+        Ok(VerifiedBundle {
+            manifest,
+            manifest_bytes,
+            manifest_signature,
+            contents,
+        })
+    }
+
+    fn read_signed_manifest(
+        &self,
+        manifest_path: &Path,
+        signature_path: &Path,
+    ) -> Result<SignedManifest, InstallError> {
+        let manifest_bytes = fs::read(manifest_path)?;
+        let signature = fs::read(signature_path)?;
+        verify_manifest_signature(&manifest_bytes, &signature, &self.public_key)?;
+
+        let manifest: BundleManifest = serde_json::from_slice(&manifest_bytes)
+            .map_err(|e| InstallError::InvalidManifest(e.to_string()))?;
+        Ok((manifest, (manifest_bytes, signature)))
+    }
+
+    /// Verify and install a bundle, backing up any files it replaces
+    pub fn install(&self, bundle_dir: &Path, dry_run: bool) -> Result<InstallReport, InstallError> {
+        let VerifiedBundle {
+            manifest,
+            manifest_bytes,
+            manifest_signature,
+            contents,
+        } = self.verify_bundle(bundle_dir)?;
+        let previous_version = self.installed_version();
+
+        let destinations: Vec<PathBuf> = manifest
+            .files
+            .iter()
+            .map(BundleManifest::destination)
+            .collect();
+
+        if dry_run {
+            return Ok(InstallReport {
+                engine_version: manifest.engine_version,
+                previous_version,
+                installed_files: destinations,
+                backup_dir: None,
+                dry_run: true,
+            });
+        }
+
+        fs::create_dir_all(self.install_dir.join(PRICING_DIR))?;
+
+        let mut replaced = destinations.clone();
+        replaced.push(PathBuf::from(INSTALLED_MANIFEST_FILE));
+        replaced.push(PathBuf::from(INSTALLED_MANIFEST_SIGNATURE_FILE));
+        let backup_dir = self.backup_existing(&replaced)?;
+
+        // Stage every file next to its destination first so the swap below is
+        // a sequence of same-filesystem renames.
+        let mut staged = Vec::with_capacity(manifest.files.len() + 2);
+        for (bytes, dest) in contents.iter().zip(&destinations) {
+            staged.push((self.stage(dest, bytes)?, self.install_dir.join(dest)));
+        }
+        // The manifest is kept byte for byte so its signature stays valid
+        for (name, bytes) in [
+            (INSTALLED_MANIFEST_FILE, &manifest_bytes),
+            (INSTALLED_MANIFEST_SIGNATURE_FILE, &manifest_signature),
+        ] {
+            let dest = PathBuf::from(name);
+            staged.push((self.stage(&dest, bytes)?, self.install_dir.join(&dest)));
+        }
+
+        for (index, (tmp, dest)) in staged.iter().enumerate() {
+            if let Err(e) = fs::rename(tmp, dest) {
+                for (tmp, _) in &staged[index..] {
+                    let _ = fs::remove_file(tmp);
+                }
+                let _ = self.restore(backup_dir.as_deref(), &replaced);
+                return Err(e.into());
+            }
+        }
+
+        Ok(InstallReport {
+            engine_version: manifest.engine_version,
+            previous_version,
+            installed_files: destinations,
+            backup_dir,
+            dry_run: false,
+        })
+    }
+
+    /// Engine version recorded by the last successful install
+    pub fn installed_version(&self) -> Option<String> {
+        let content = fs::read(self.install_dir.join(INSTALLED_MANIFEST_FILE)).ok()?;
+        serde_json::from_slice::<BundleManifest>(&content)
+            .ok()
+            .map(|m| m.engine_version)
+    }
+
+    fn stage(&self, dest: &Path, bytes: &[u8]) -> Result<PathBuf, InstallError> {
+        let target = self.install_dir.join(dest);
+        let mut tmp_name = target.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = target.with_file_name(tmp_name);
+        fs::write(&tmp, bytes)?;
+        Ok(tmp)
+    }
+
+    fn backup_existing(&self, files: &[PathBuf]) -> Result<Option<PathBuf>, InstallError> {
+        let existing: Vec<&PathBuf> = files
+            .iter()
+            .filter(|f| self.install_dir.join(f).exists())
+            .collect();
+        if existing.is_empty() {
+            return Ok(None);
+        }
+
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let backup_dir = self.install_dir.join(BACKUP_DIR).join(stamp);
+        for file in existing {
+            let dest = backup_dir.join(file);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(self.install_dir.join(file), dest)?;
+        }
+        Ok(Some(backup_dir))
+    }
+
+    /// Roll back a failed install: copy backed up files into place and
+    /// remove files the install created
+    fn restore(&self, backup_dir: Option<&Path>, files: &[PathBuf]) -> Result<(), InstallError> {
+        for file in files {
+            let dest = self.install_dir.join(file);
+            match backup_dir.map(|dir| dir.join(file)) {
+                Some(source) if source.exists() => {
+                    fs::copy(source, dest)?;
+                }
+                _ if dest.exists() => fs::remove_file(dest)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase hex SHA-256 digest
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Verify the detached Ed25519 signature over the raw manifest bytes
+fn verify_manifest_signature(
+    manifest: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<(), InstallError> {
+    let key_bytes: [u8; 32] = public_key
+        .try_into()
+        .map_err(|_| InstallError::SignatureInvalid)?;
+    let verifying_key =
+        VerifyingKey::from_bytes(&key_bytes).map_err(|_| InstallError::SignatureInvalid)?;
+    let signature = Signature::from_slice(signature).map_err(|_| InstallError::SignatureInvalid)?;
+    verifying_key
+        .verify_strict(manifest, &signature)
+        .map_err(|_| InstallError::SignatureInvalid)
+}
+
+/// Check the bundle against the running CLI version and host ABI
+fn check_compatibility(manifest: &BundleManifest, cli_version: &str) -> Result<(), InstallError> {
+    if check_abi_version(manifest.abi_version).is_err() {
+        return Err(InstallError::IncompatibleAbi {
+            expected: PRO_ENGINE_ABI_VERSION,
+            found: manifest.abi_version,
+        });
+    }
+
+    let required = semver::Version::parse(&manifest.min_cli_version)
+        .map_err(|e| InstallError::InvalidManifest(format!("min_cli_version: {}", e)))?;
+    let current = semver::Version::parse(cli_version)
+        .map_err(|e| InstallError::InvalidManifest(format!("cli version: {}", e)))?;
+    if current < required {
+        return Err(InstallError::IncompatibleVersion {
+            required: manifest.min_cli_version.clone(),
+            current: cli_version.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Join a manifest-relative path onto `root`, rejecting absolute or `..` paths
+fn safe_join(root: &Path, relative: &str) -> Result<PathBuf, InstallError> {
+    let path = Path::new(relative);
+    if relative.is_empty()
+        || path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(InstallError::UnsafePath(relative.to_string()));
+    }
+    Ok(root.join(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use tempfile::TempDir;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn write_bundle(dir: &Path, min_cli_version: &str, abi_version: u32) {
+        let files = [
+            (
+                "pro-engine.wasm.enc",
+                BundleFileKind::Engine,
+                b"engine-v2".to_vec(),
+            ),
+            (
+                "pro-engine.sig",
+                BundleFileKind::EngineSignature,
+                vec![9u8; 64],
+            ),
+            (
+                "pricing/aws.json",
+                BundleFileKind::PricingPack,
+                br#"{"region":"us-east-1"}"#.to_vec(),
+            ),
+        ];
+
+        let mut entries = Vec::new();
+        for (path, kind, bytes) in files {
+            let target = dir.join(path);
+            fs::create_dir_all(target.parent().unwrap()).unwrap();
+            fs::write(&target, &bytes).unwrap();
+            entries.push(BundleFile {
+                path: path.to_string(),
+                kind,
+                sha256: hex::encode(Sha256::digest(&bytes)),
+            });
+        }
+
+        let manifest = BundleManifest {
+            engine_version: "2.0.0".to_string(),
+            abi_version,
+            min_cli_version: min_cli_version.to_string(),
+            created_at: None,
+            files: entries,
+        };
+        let bytes = serde_json::to_vec_pretty(&manifest).unwrap();
+        fs::write(dir.join(BUNDLE_MANIFEST_FILE), &bytes).unwrap();
+        let signature = signing_key().sign(&bytes);
+        fs::write(dir.join(BUNDLE_SIGNATURE_FILE), signature.to_bytes()).unwrap();
+    }
+
+    fn installer(install_dir: &Path) -> BundleInstaller {
+        BundleInstaller::new(install_dir.to_path_buf())
+            .with_public_key(&signing_key().verifying_key().to_bytes())
+    }
+
+    #[test]
+    fn test_install_swaps_files_and_backs_up_previous_engine() {
+        let bundle = TempDir::new().unwrap();
+        let home = TempDir::new().unwrap();
+        write_bundle(bundle.path(), "0.1.0", PRO_ENGINE_ABI_VERSION);
+        fs::write(home.path().join(ENGINE_FILE), b"engine-v1").unwrap();
+
+        let report = installer(home.path())
+            .install(bundle.path(), false)
+            .unwrap();
+
+        assert_eq!(
+            fs::read(home.path().join(ENGINE_FILE)).unwrap(),
+            b"engine-v2"
+        );
+        assert!(home.path().join(PRICING_DIR).join("aws.json").exists());
+        let backup = report.backup_dir.expect("previous engine backed up");
+        assert_eq!(fs::read(backup.join(ENGINE_FILE)).unwrap(), b"engine-v1");
+        assert_eq!(
+            installer(home.path()).installed_version().as_deref(),
+            Some("2.0.0")
+        );
+    }
+
+    #[test]
+    fn test_dry_run_leaves_install_dir_untouched() {
+        let bundle = TempDir::new().unwrap();
+        let home = TempDir::new().unwrap();
+        write_bundle(bundle.path(), "0.1.0", PRO_ENGINE_ABI_VERSION);
+
+        let report = installer(home.path()).install(bundle.path(), true).unwrap();
+
+        assert!(report.dry_run);
+        assert!(!home.path().join(ENGINE_FILE).exists());
+    }
+
+    #[test]
+    fn test_tampered_file_is_rejected() {
+        let bundle = TempDir::new().unwrap();
+        let home = TempDir::new().unwrap();
+        write_bundle(bundle.path(), "0.1.0", PRO_ENGINE_ABI_VERSION);
+        fs::write(bundle.path().join("pro-engine.wasm.enc"), b"tampered").unwrap();
+
+        assert!(matches!(
+            installer(home.path()).install(bundle.path(), false),
+            Err(InstallError::ChecksumMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_wrong_key_is_rejected() {
+        let bundle = TempDir::new().unwrap();
+        let home = TempDir::new().unwrap();
+        write_bundle(bundle.path(), "0.1.0", PRO_ENGINE_ABI_VERSION);
+        let other = SigningKey::from_bytes(&[8u8; 32])
+            .verifying_key()
+            .to_bytes();
+
+        let result = BundleInstaller::new(home.path().to_path_buf())
+            .with_public_key(&other)
+            .verify(bundle.path());
+        assert!(matches!(result, Err(InstallError::SignatureInvalid)));
+    }
+
+    #[test]
+    fn test_incompatible_versions_are_rejected() {
+        let bundle = TempDir::new().unwrap();
+        let home = TempDir::new().unwrap();
+
+        write_bundle(bundle.path(), "99.0.0", PRO_ENGINE_ABI_VERSION);
+        assert!(matches!(
+            installer(home.path()).verify(bundle.path()),
+            Err(InstallError::IncompatibleVersion { .. })
+        ));
+
+        write_bundle(bundle.path(), "0.1.0", PRO_ENGINE_ABI_VERSION + 1);
+        assert!(matches!(
+            installer(home.path()).verify(bundle.path()),
+            Err(InstallError::IncompatibleAbi { .. })
+        ));
+    }
+
+    #[test]
+    fn test_installed_manifest_verifies_engine_before_loading() {
+        let bundle = TempDir::new().unwrap();
+        let home = TempDir::new().unwrap();
+        write_bundle(bundle.path(), "0.1.0", PRO_ENGINE_ABI_VERSION);
+        let installer = installer(home.path());
+        assert!(matches!(
+            installer.verify_installed(b"engine-v2", &[9u8; 64]),
+            Err(InstallError::NotInstalled)
+        ));
+
+        installer.install(bundle.path(), false).unwrap();
+        let engine = fs::read(home.path().join(ENGINE_FILE)).unwrap();
+        let signature = fs::read(home.path().join(ENGINE_SIGNATURE_FILE)).unwrap();
+        let manifest = installer.verify_installed(&engine, &signature).unwrap();
+        assert_eq!(manifest.engine_version, "2.0.0");
+
+        assert!(matches!(
+            installer.verify_installed(b"tampered", &signature),
+            Err(InstallError::ChecksumMismatch(name)) if name == ENGINE_FILE
+        ));
+        assert!(matches!(
+            installer.verify_installed(&engine, &[0u8; 64]),
+            Err(InstallError::ChecksumMismatch(name)) if name == ENGINE_SIGNATURE_FILE
+        ));
+
+        // A manifest rewritten to match a tampered engine fails its signature
+        let path = home.path().join(INSTALLED_MANIFEST_FILE);
+        let forged = fs::read_to_string(&path)
+            .unwrap()
+            .replace(&sha256_hex(b"engine-v2"), &sha256_hex(b"tampered"));
+        fs::write(&path, forged).unwrap();
+        assert!(matches!(
+            installer.verify_installed(b"tampered", &signature),
+            Err(InstallError::SignatureInvalid)
+        ));
+    }
+
+    #[test]
+    fn test_pricing_packs_with_same_file_name_are_rejected() {
+        let bundle = TempDir::new().unwrap();
+        let home = TempDir::new().unwrap();
+        write_bundle(bundle.path(), "0.1.0", PRO_ENGINE_ABI_VERSION);
+
+        let mut manifest: BundleManifest =
+            serde_json::from_slice(&fs::read(bundle.path().join(BUNDLE_MANIFEST_FILE)).unwrap())
+                .unwrap();
+        let bytes = br#"{"region":"eu-west-1"}"#;
+        fs::create_dir_all(bundle.path().join("pricing/eu")).unwrap();
+        fs::write(bundle.path().join("pricing/eu/aws.json"), bytes).unwrap();
+        manifest.files.push(BundleFile {
+            path: "pricing/eu/aws.json".to_string(),
+            kind: BundleFileKind::PricingPack,
+            sha256: sha256_hex(bytes),
+        });
+        let bytes = serde_json::to_vec_pretty(&manifest).unwrap();
+        fs::write(bundle.path().join(BUNDLE_MANIFEST_FILE), &bytes).unwrap();
+        let signature = signing_key().sign(&bytes);
+        fs::write(
+            bundle.path().join(BUNDLE_SIGNATURE_FILE),
+            signature.to_bytes(),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            installer(home.path()).install(bundle.path(), false),
+            Err(InstallError::InvalidManifest(_))
+        ));
+        assert!(!home.path().join(ENGINE_FILE).exists());
+    }
+
+    #[test]
+    fn test_restore_removes_files_without_backup() {
+        let home = TempDir::new().unwrap();
+        let backup = TempDir::new().unwrap();
+        fs::write(home.path().join(ENGINE_FILE), b"engine-v2").unwrap();
+        fs::write(home.path().join(ENGINE_SIGNATURE_FILE), b"new").unwrap();
+        fs::write(backup.path().join(ENGINE_FILE), b"engine-v1").unwrap();
+
+        let files = [
+            PathBuf::from(ENGINE_FILE),
+            PathBuf::from(ENGINE_SIGNATURE_FILE),
+        ];
+        let installer = installer(home.path());
+        installer.restore(Some(backup.path()), &files).unwrap();
+        assert_eq!(
+            fs::read(home.path().join(ENGINE_FILE)).unwrap(),
+            b"engine-v1"
+        );
+        assert!(!home.path().join(ENGINE_SIGNATURE_FILE).exists());
+
+        installer.restore(None, &files).unwrap();
+        assert!(!home.path().join(ENGINE_FILE).exists());
+    }
+
+    #[test]
+    fn test_safe_join_rejects_traversal() {
+        let root = Path::new("/bundle");
+        assert!(safe_join(root, "pricing/aws.json").is_ok());
+        assert!(safe_join(root, "../license.json").is_err());
+        assert!(safe_join(root, "/etc/passwd").is_err());
+    }
+}
@@ -190,6 +190,21 @@ pub fn verify_license_signature(lic: &super::license::License) -> Result<(), Str
         .map_err(|_| "License signature verification failed".to_string())
 }
 
+/// Verify an organization capability policy against its issuer's license key
+#[cfg(not(target_arch = "wasm32"))]
+pub fn verify_capability_policy_signature(
+    policy: &crate::edition::CapabilityPolicy,
+) -> Result<(), String> {
+    let sig_bytes =
+        hex::decode(&policy.signature).map_err(|_| "Invalid capability policy signature format")?;
+    let public_key_bytes = get_license_public_key(&policy.issuer)?;
+
+    let public_key = signature::UnparsedPublicKey::new(&signature::ED25519, public_key_bytes);
+    public_key
+        .verify(policy.signing_message().as_bytes(), &sig_bytes)
+        .map_err(|_| "Capability policy signature verification failed".to_string())
+}
+
 /// Get the public key for a license issuer
 #[cfg(not(target_arch = "wasm32"))]
 fn get_license_public_key(issuer: &str) -> Result<&'static [u8], String> {
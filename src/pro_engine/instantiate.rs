@@ -1,85 +1,147 @@
 // WASM instantiation for ProEngine
 
+use crate::pro_engine::abi::{
+    check_abi_version, AbiTransport, HandshakeOffer, HandshakeReply, NegotiatedAbi,
+    ABI_VERSION_EXPORT, ALLOCATOR_ABI_VERSION, ALLOC_EXPORT, DEALLOC_EXPORT, NEGOTIATE_EXPORT,
+    REGION_HEADER_SIZE,
+};
 use crate::pro_engine::{ProEngineExecutor, ProEngineHandle, ProEngineRequest, ProEngineResponse};
+use std::collections::HashMap;
 use std::sync::Mutex;
 
 /// Instantiate WASM module and return executor handle
 pub fn instantiate_wasm(bytes: &[u8]) -> Result<ProEngineHandle, String> {
-    // Verify bytes are valid WASM
-    if bytes.len() < 8 || &bytes[0..4] != b"\0asm" {
-        return Err("Invalid WASM magic number".to_string());
-    }
+    let executor = WasmExecutor::new(bytes)?;
+    Ok(ProEngineHandle::new(Box::new(executor)))
+}
 
-    // Use wasmtime to instantiate the WASM module
-    let engine = wasmtime::Engine::default();
-    let module = wasmtime::Module::from_binary(&engine, bytes)
-        .map_err(|e| format!("WASM compilation failed: {}", e))?;
-
-    let mut store = wasmtime::Store::new(&engine, ());
-    let instance = wasmtime::Instance::new(&mut store, &module, &[])
-        .map_err(|e| format!("WASM instantiation failed: {}", e))?;
-
-    // Get the exported functions from WASM
-    // For wasm-bindgen, functions take &str and return Result<String, String>
-    // But wasmtime expects different signatures. For now, we'll use a simplified approach.
-    let predict_fn = instance
-        .get_typed_func::<(i32, i32), i32>(&mut store, "predict")
-        .map_err(|e| format!("Function 'predict' not found: {}", e))?;
-
-    let explain_fn = instance
-        .get_typed_func::<(i32, i32), i32>(&mut store, "explain")
-        .map_err(|e| format!("Function 'explain' not found: {}", e))?;
-
-    let autofix_fn = instance
-        .get_typed_func::<(i32, i32), i32>(&mut store, "autofix")
-        .map_err(|e| format!("Function 'autofix' not found: {}", e))?;
-
-    let mapdeep_fn = instance
-        .get_typed_func::<(i32, i32), i32>(&mut store, "mapdeep")
-        .map_err(|e| format!("Function 'mapdeep' not found: {}", e))?;
-
-    let trend_fn = instance
-        .get_typed_func::<(i32, i32), i32>(&mut store, "trend")
-        .map_err(|e| format!("Function 'trend' not found: {}", e))?;
-
-    let enforce_fn = instance
-        .get_typed_func::<(i32, i32), i32>(&mut store, "enforce")
-        .map_err(|e| format!("Function 'enforce' not found: {}", e))?;
-
-    let slo_enforce_fn = instance
-        .get_typed_func::<(i32, i32), i32>(&mut store, "slo_enforce")
-        .map_err(|e| format!("Function 'slo_enforce' not found: {}", e))?;
-
-    // Get memory export for string passing
-    let memory = instance
-        .get_memory(&mut store, "memory")
-        .ok_or("WASM module must export 'memory'")?;
-
-    let wasm_executor = WasmExecutor {
-        store: Mutex::new(store),
-        memory,
-        predict_fn,
-        explain_fn,
-        autofix_fn,
-        mapdeep_fn,
-        trend_fn,
-        enforce_fn,
-        slo_enforce_fn,
-    };
-
-    Ok(ProEngineHandle::new(Box::new(wasm_executor)))
+/// Instantiate WASM module and report the ABI negotiated with it
+pub fn negotiate_abi(bytes: &[u8]) -> Result<NegotiatedAbi, String> {
+    WasmExecutor::new(bytes).map(|executor| executor.abi)
 }
 
 struct WasmExecutor {
     store: Mutex<wasmtime::Store<()>>,
     memory: wasmtime::Memory,
-    predict_fn: wasmtime::TypedFunc<(i32, i32), i32>,
-    explain_fn: wasmtime::TypedFunc<(i32, i32), i32>,
-    autofix_fn: wasmtime::TypedFunc<(i32, i32), i32>,
-    mapdeep_fn: wasmtime::TypedFunc<(i32, i32), i32>,
-    trend_fn: wasmtime::TypedFunc<(i32, i32), i32>,
-    enforce_fn: wasmtime::TypedFunc<(i32, i32), i32>,
-    slo_enforce_fn: wasmtime::TypedFunc<(i32, i32), i32>,
+    abi: NegotiatedAbi,
+    allocator: Option<GuestAllocator>,
+    functions: HashMap<String, wasmtime::TypedFunc<(i32, i32), i32>>,
+}
+
+/// Engine `alloc`/`dealloc` exports (ABI v2+)
+struct GuestAllocator {
+    alloc: wasmtime::TypedFunc<i32, i32>,
+    dealloc: wasmtime::TypedFunc<(i32, i32), ()>,
+}
+
+/// Memory and exports needed to move bytes across the boundary
+struct Boundary<'a> {
+    memory: wasmtime::Memory,
+    transport: &'a AbiTransport,
+    allocator: Option<&'a GuestAllocator>,
+}
+
+impl WasmExecutor {
+    fn new(bytes: &[u8]) -> Result<Self, String> {
+        // Verify bytes are valid WASM
+        if bytes.len() < 8 || &bytes[0..4] != b"\0asm" {
+            return Err("Invalid WASM magic number".to_string());
+        }
+
+        // Use wasmtime to instantiate the WASM module
+        let engine = wasmtime::Engine::default();
+        let module = wasmtime::Module::from_binary(&engine, bytes)
+            .map_err(|e| format!("WASM compilation failed: {}", e))?;
+
+        let mut store = wasmtime::Store::new(&engine, ());
+        let instance = wasmtime::Instance::new(&mut store, &module, &[])
+            .map_err(|e| format!("WASM instantiation failed: {}", e))?;
+
+        // Get memory export for string passing
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or("WASM module must export 'memory'")?;
+
+        let version = Self::abi_version(&instance, &mut store)?;
+        let allocator = if version >= ALLOCATOR_ABI_VERSION {
+            Some(GuestAllocator::lookup(&instance, &mut store, version)?)
+        } else {
+            None
+        };
+        let abi = Self::handshake(&instance, &mut store, memory, version, allocator.as_ref())?;
+
+        // Only functions for negotiated capabilities are required
+        let mut functions = HashMap::new();
+        for capability in &abi.capabilities {
+            let func = instance
+                .get_typed_func::<(i32, i32), i32>(&mut store, capability)
+                .map_err(|e| {
+                    format!(
+                        "ProEngine advertises '{}' but does not export it: {}",
+                        capability, e
+                    )
+                })?;
+            functions.insert(capability.clone(), func);
+        }
+
+        Ok(Self {
+            store: Mutex::new(store),
+            memory,
+            abi,
+            allocator,
+            functions,
+        })
+    }
+
+    /// Engine ABI version; engines built before the handshake existed are v1
+    fn abi_version(
+        instance: &wasmtime::Instance,
+        store: &mut wasmtime::Store<()>,
+    ) -> Result<u32, String> {
+        let version = match instance.get_typed_func::<(), i32>(&mut *store, ABI_VERSION_EXPORT) {
+            Ok(func) => {
+                let version = func
+                    .call(&mut *store, ())
+                    .map_err(|e| format!("ProEngine ABI version query failed: {}", e))?;
+                u32::try_from(version)
+                    .map_err(|_| format!("ProEngine reported invalid ABI version {}", version))?
+            }
+            Err(_) => 1,
+        };
+        check_abi_version(version)?;
+        Ok(version)
+    }
+
+    /// Negotiate capabilities, falling back to everything the host knows
+    fn handshake(
+        instance: &wasmtime::Instance,
+        store: &mut wasmtime::Store<()>,
+        memory: wasmtime::Memory,
+        version: u32,
+        allocator: Option<&GuestAllocator>,
+    ) -> Result<NegotiatedAbi, String> {
+        let negotiate =
+            match instance.get_typed_func::<(i32, i32), i32>(&mut *store, NEGOTIATE_EXPORT) {
+                Ok(func) => func,
+                Err(_) => return Ok(NegotiatedAbi::legacy(version)),
+            };
+
+        let offer = serde_json::to_string(&HandshakeOffer::host())
+            .map_err(|e| format!("Failed to serialize ABI handshake: {}", e))?;
+        let transport = NegotiatedAbi::default_transport(version);
+        let boundary = Boundary {
+            memory,
+            transport: &transport,
+            allocator,
+        };
+        let reply = boundary
+            .call(store, &negotiate, &offer)
+            .map_err(|e| format!("ProEngine ABI negotiation failed: {}", e))?;
+        let reply: HandshakeReply = serde_json::from_str(&reply)
+            .map_err(|e| format!("Invalid ProEngine handshake reply: {}", e))?;
+
+        NegotiatedAbi::from_reply(reply)
+    }
 }
 
 impl ProEngineExecutor for WasmExecutor {
@@ -88,30 +150,7 @@ impl ProEngineExecutor for WasmExecutor {
         let json_input = serde_json::to_string(&req)
             .map_err(|e| format!("Failed to serialize request: {}", e))?;
 
-        // Call appropriate WASM function based on request type
-        let result = match req {
-            ProEngineRequest::Predict { .. } => {
-                self.call_wasm_function(&json_input, &self.predict_fn)
-            }
-            ProEngineRequest::Explain { .. } => {
-                self.call_wasm_function(&json_input, &self.explain_fn)
-            }
-            ProEngineRequest::Autofix { .. } => {
-                self.call_wasm_function(&json_input, &self.autofix_fn)
-            }
-            ProEngineRequest::MapDeep { .. } => {
-                self.call_wasm_function(&json_input, &self.mapdeep_fn)
-            }
-            ProEngineRequest::TrendSnapshot { .. } => {
-                self.call_wasm_function(&json_input, &self.trend_fn)
-            }
-            ProEngineRequest::PolicyEnforce { .. } => {
-                self.call_wasm_function(&json_input, &self.enforce_fn)
-            }
-            ProEngineRequest::SloEnforce { .. } => {
-                self.call_wasm_function(&json_input, &self.slo_enforce_fn)
-            }
-        }?;
+        let result = self.call(capability_for(&req), &json_input)?;
 
         // Deserialize response from JSON
         let response: ProEngineResponse = serde_json::from_str(&result)
@@ -122,32 +161,119 @@ impl ProEngineExecutor for WasmExecutor {
 }
 
 impl WasmExecutor {
-    fn call_wasm_function(
-        &self,
-        input: &str,
-        func: &wasmtime::TypedFunc<(i32, i32), i32>,
-    ) -> Result<String, String> {
+    /// Call a negotiated engine function with a JSON payload
+    fn call(&self, capability: &str, input: &str) -> Result<String, String> {
+        let func = self.functions.get(capability).ok_or_else(|| {
+            format!(
+                "ProEngine (ABI v{}) does not support '{}'",
+                self.abi.version, capability
+            )
+        })?;
+
         let mut store = self
             .store
             .lock()
             .map_err(|e| format!("Mutex lock failed: {}", e))?;
+        let boundary = Boundary {
+            memory: self.memory,
+            transport: &self.abi.transport,
+            allocator: self.allocator.as_ref(),
+        };
+        boundary.call(&mut store, func, input)
+    }
+}
 
-        // Write input directly to WASM memory at fixed location (simulating INPUT_BUFFER)
-        let input_bytes = input.as_bytes();
-        let input_len = input_bytes.len() as i32;
+/// Engine export serving a request
+fn capability_for(req: &ProEngineRequest) -> &'static str {
+    match req {
+        ProEngineRequest::Predict { .. } => "predict",
+        ProEngineRequest::Explain { .. } => "explain",
+        ProEngineRequest::Autofix { .. } => "autofix",
+        ProEngineRequest::MapDeep { .. } => "mapdeep",
+        ProEngineRequest::TrendSnapshot { .. } => "trend",
+        ProEngineRequest::PolicyEnforce { .. } => "enforce",
+        ProEngineRequest::SloEnforce { .. } => "slo_enforce",
+    }
+}
+
+impl GuestAllocator {
+    fn lookup(
+        instance: &wasmtime::Instance,
+        store: &mut wasmtime::Store<()>,
+        version: u32,
+    ) -> Result<Self, String> {
+        let missing = |export: &str, e: wasmtime::Error| {
+            format!(
+                "ProEngine ABI v{} requires an '{}' export: {}",
+                version, export, e
+            )
+        };
+        Ok(Self {
+            alloc: instance
+                .get_typed_func::<i32, i32>(&mut *store, ALLOC_EXPORT)
+                .map_err(|e| missing(ALLOC_EXPORT, e))?,
+            dealloc: instance
+                .get_typed_func::<(i32, i32), ()>(&mut *store, DEALLOC_EXPORT)
+                .map_err(|e| missing(DEALLOC_EXPORT, e))?,
+        })
+    }
+}
+
+impl Boundary<'_> {
+    /// Call an engine function with a JSON input, returning its JSON output
+    fn call(
+        &self,
+        store: &mut wasmtime::Store<()>,
+        func: &wasmtime::TypedFunc<(i32, i32), i32>,
+        input: &str,
+    ) -> Result<String, String> {
+        match self.transport {
+            AbiTransport::FixedBuffers {
+                input_offset,
+                input_buffer_size,
+                output_offset,
+                output_buffer_size,
+            } => self.call_packed(
+                store,
+                func,
+                input,
+                (*input_offset, *input_buffer_size),
+                (*output_offset, *output_buffer_size),
+            ),
+            AbiTransport::Allocator => {
+                let allocator = self
+                    .allocator
+                    .ok_or("ProEngine allocator exports are not available")?;
+                self.call_region(store, func, input, allocator)
+            }
+        }
+    }
 
-        if input_bytes.len() > 4096 {
-            return Err("Input too large for WASM buffer".to_string());
+    /// ABI v1: input is written to a fixed buffer, and the result packs
+    /// (length << 16) | offset into the output buffer
+    fn call_packed(
+        &self,
+        store: &mut wasmtime::Store<()>,
+        func: &wasmtime::TypedFunc<(i32, i32), i32>,
+        input: &str,
+        (input_offset, input_buffer_size): (usize, usize),
+        (output_offset, output_buffer_size): (usize, usize),
+    ) -> Result<String, String> {
+        let input_bytes = input.as_bytes();
+        if input_bytes.len() > input_buffer_size {
+            return Err(format!(
+                "Input of {} bytes exceeds the {}-byte WASM input buffer",
+                input_bytes.len(),
+                input_buffer_size
+            ));
         }
 
-        // Write input to fixed location in WASM memory (offset 0 for input buffer)
         self.memory
-            .write(&mut *store, 0, input_bytes)
+            .write(&mut *store, input_offset, input_bytes)
             .map_err(|e| format!("Failed to write input to WASM memory: {}", e))?;
 
-        // Call the WASM function with buffer location
         let packed_result = func
-            .call(&mut *store, (0, input_len))
+            .call(&mut *store, (input_offset as i32, input_bytes.len() as i32))
             .map_err(|e| format!("WASM function call failed: {}", e))?;
 
         if packed_result < 0 {
@@ -157,21 +283,168 @@ impl WasmExecutor {
         // Unpack result: upper 16 bits = length, lower 16 bits = offset
         let result_len = (packed_result >> 16) as usize;
         let result_offset = (packed_result & 0xFFFF) as usize;
+        if result_offset + result_len > output_buffer_size {
+            return Err(format!(
+                "WASM result overruns the {}-byte output buffer",
+                output_buffer_size
+            ));
+        }
 
-        // Read result from WASM memory (from OUTPUT_BUFFER at offset 4096)
         let mut result_bytes = vec![0u8; result_len];
         self.memory
-            .read(&*store, 4096 + result_offset, &mut result_bytes)
+            .read(&*store, output_offset + result_offset, &mut result_bytes)
             .map_err(|e| format!("Failed to read result data: {}", e))?;
 
         String::from_utf8(result_bytes).map_err(|e| format!("Invalid UTF-8 in WASM result: {}", e))
     }
 
-    #[allow(dead_code)]
-    fn allocate_memory(&self, _size: i32) -> Result<i32, String> {
-        // For this simple implementation, we'll use a fixed memory location
-        // In a real implementation, the WASM module should export an allocate function
-        // For now, assume memory starts at offset 1024 and we have enough space
-        Ok(1024) // Fixed allocation for simplicity
+    /// ABI v2: input goes into memory from the engine's `alloc`, and the
+    /// result points at a region holding a u32 LE length then the payload.
+    /// The host frees both once the payload is copied out.
+    fn call_region(
+        &self,
+        store: &mut wasmtime::Store<()>,
+        func: &wasmtime::TypedFunc<(i32, i32), i32>,
+        input: &str,
+        allocator: &GuestAllocator,
+    ) -> Result<String, String> {
+        let input_bytes = input.as_bytes();
+        let input_len = i32::try_from(input_bytes.len())
+            .map_err(|_| format!("Input of {} bytes is too large", input_bytes.len()))?;
+
+        let input_ptr = allocator
+            .alloc
+            .call(&mut *store, input_len)
+            .map_err(|e| format!("WASM allocation failed: {}", e))?;
+        if input_ptr <= 0 {
+            return Err(format!(
+                "ProEngine could not allocate {} bytes for input",
+                input_len
+            ));
+        }
+
+        let result = self
+            .memory
+            .write(&mut *store, input_ptr as usize, input_bytes)
+            .map_err(|e| format!("Failed to write input to WASM memory: {}", e))
+            .and_then(|_| {
+                func.call(&mut *store, (input_ptr, input_len))
+                    .map_err(|e| format!("WASM function call failed: {}", e))
+            });
+        allocator
+            .dealloc
+            .call(&mut *store, (input_ptr, input_len))
+            .map_err(|e| format!("WASM deallocation failed: {}", e))?;
+
+        let region = result?;
+        if region <= 0 {
+            return Err("WASM function returned error".to_string());
+        }
+
+        let mut header = [0u8; REGION_HEADER_SIZE];
+        self.memory
+            .read(&*store, region as usize, &mut header)
+            .map_err(|e| format!("Failed to read result header: {}", e))?;
+        let result_len = u32::from_le_bytes(header) as usize;
+
+        let mut result_bytes = vec![0u8; result_len];
+        self.memory
+            .read(
+                &*store,
+                region as usize + REGION_HEADER_SIZE,
+                &mut result_bytes,
+            )
+            .map_err(|e| format!("Failed to read result data: {}", e))?;
+
+        allocator
+            .dealloc
+            .call(
+                &mut *store,
+                (region, (REGION_HEADER_SIZE + result_len) as i32),
+            )
+            .map_err(|e| format!("WASM deallocation failed: {}", e))?;
+
+        String::from_utf8(result_bytes).map_err(|e| format!("Invalid UTF-8 in WASM result: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Engine exporting the handshake and only `predict`
+    fn engine_wat(abi_version: i32) -> String {
+        let reply = r#"{"abi_version":1,"capabilities":["predict"],"output_buffer_size":4096}"#;
+        let escaped = reply.replace('"', "\\\"");
+        format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 4096) "{escaped}")
+                (func (export "costpilot_abi_version") (result i32) (i32.const {abi_version}))
+                (func (export "costpilot_negotiate") (param i32 i32) (result i32)
+                    (i32.const {packed}))
+                (func (export "predict") (param i32 i32) (result i32) (i32.const -1)))"#,
+            packed = (reply.len() as i32) << 16,
+        )
+    }
+
+    #[test]
+    fn test_handshake_negotiates_capabilities() {
+        let wasm = wat::parse_str(engine_wat(1)).unwrap();
+        let abi = negotiate_abi(&wasm).unwrap();
+
+        assert_eq!(abi.version, 1);
+        assert!(abi.supports("predict"));
+        assert!(!abi.supports("autofix"));
+    }
+
+    /// ABI v2 engine with a bump allocator whose `predict` echoes its input
+    fn allocator_engine_wat() -> String {
+        let reply = r#"{"abi_version":2,"capabilities":["predict"]}"#;
+        format!(
+            r#"(module
+                (memory (export "memory") 4)
+                (global $next (mut i32) (i32.const 1024))
+                (data (i32.const 16) "{escaped}")
+                (func $alloc (export "alloc") (param $size i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr (global.get $next))
+                    (global.set $next (i32.add (global.get $next) (local.get $size)))
+                    (local.get $ptr))
+                (func (export "dealloc") (param i32 i32))
+                (func $region (param $src i32) (param $len i32) (result i32)
+                    (local $ptr i32)
+                    (local.set $ptr (call $alloc (i32.add (local.get $len) (i32.const 4))))
+                    (i32.store (local.get $ptr) (local.get $len))
+                    (memory.copy (i32.add (local.get $ptr) (i32.const 4))
+                        (local.get $src) (local.get $len))
+                    (local.get $ptr))
+                (func (export "costpilot_abi_version") (result i32) (i32.const 2))
+                (func (export "costpilot_negotiate") (param i32 i32) (result i32)
+                    (call $region (i32.const 16) (i32.const {len})))
+                (func (export "predict") (param $ptr i32) (param $len i32) (result i32)
+                    (call $region (local.get $ptr) (local.get $len))))"#,
+            escaped = reply.replace('"', "\\\""),
+            len = reply.len(),
+        )
+    }
+
+    #[test]
+    fn test_allocator_transport_has_no_size_ceiling() {
+        let wasm = wat::parse_str(allocator_engine_wat()).unwrap();
+        let executor = WasmExecutor::new(&wasm).unwrap();
+        assert_eq!(executor.abi.transport, AbiTransport::Allocator);
+
+        // Well past the 4KB legacy buffers in both directions
+        let payload = format!("\"{}\"", "x".repeat(64 * 1024));
+        assert_eq!(executor.call("predict", &payload).unwrap(), payload);
+        assert!(executor.call("autofix", "{}").is_err());
+    }
+
+    #[test]
+    fn test_incompatible_abi_is_rejected() {
+        let wasm = wat::parse_str(engine_wat(99)).unwrap();
+        let err = negotiate_abi(&wasm).unwrap_err();
+        assert!(err.contains("Incompatible ProEngine ABI"), "{}", err);
     }
 }
@@ -93,7 +93,7 @@ impl RateLimitState {
                         return state;
                     }
                     // Tampered file detected - reset to clean state
-                    eprintln!("Warning: Rate limit file integrity check failed. Resetting.");
+                    tracing::warn!("Rate limit file integrity check failed, resetting");
                 }
             }
         }
@@ -2,6 +2,8 @@
 
 pub mod abi;
 pub mod api;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod bundle_installer;
 pub mod crypto;
 #[cfg(test)]
 mod crypto_tests;
@@ -13,19 +15,25 @@ pub mod host_bridge;
 pub mod instantiate;
 pub mod license;
 pub mod loader;
+pub mod native;
 pub mod pro_loader;
+pub mod reload;
 pub mod runtime;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod wasm_runtime;
 
 pub use abi::{ProEngineRequest as AbiRequest, ProEngineResponse as AbiResponse};
 pub use api::{ProEngineExecutor, ProEngineRequest, ProEngineResponse};
+#[cfg(not(target_arch = "wasm32"))]
+pub use bundle_installer::{BundleInstaller, BundleManifest, InstallError, InstallReport};
 pub use errors::ProEngineError;
 #[cfg(not(target_arch = "wasm32"))]
 pub use handle::ProEngineHandle as WasmProEngineHandle;
 pub use host_bridge::call_pro_engine;
 pub use license::License;
 pub use loader::{load_pro_engine_from_file, LicenseInfo, LoaderError};
+pub use native::NativeProEngine;
+pub use reload::{ProEngineReloader, ReloadOutcome, ReloadWatcher, ReloadableExecutor};
 #[cfg(not(target_arch = "wasm32"))]
 pub use wasm_runtime::{WasmError, WasmRuntime, WasmSandboxConfig};
 
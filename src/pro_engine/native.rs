@@ -0,0 +1,326 @@
+// Native ProEngine - runs the Premium engines in-process
+//
+// This is the implementation compiled into `pro_engine_wasm`: each export
+// deserializes a `ProEngineRequest`, runs it here and returns the
+// serialized `ProEngineResponse`. It also serves as an executor in tests.
+
+use crate::edition::{Capabilities, EditionContext, EditionMode, EditionPaths};
+use crate::engines::autofix::AutofixEngine;
+use crate::engines::explain::PredictionExplainer;
+use crate::engines::mapping::{GraphBuilder, GraphConfig};
+use crate::engines::policy::{PolicyConfig, PolicyEngine};
+use crate::engines::prediction::{PredictionEngine, UsageProfile};
+use crate::engines::shared::models::{CostEstimate, ResourceChange};
+use crate::engines::slo::{SloConfig, SloManager};
+use crate::engines::trend::{SnapshotMetadata, TrendEngine};
+use crate::pro_engine::{ProEngineExecutor, ProEngineRequest, ProEngineResponse};
+
+/// Executes ProEngine requests with the native engines
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NativeProEngine;
+
+impl NativeProEngine {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Premium context for engines that gate on edition. The engine itself
+    /// is the Premium implementation, so it holds no nested handle.
+    fn edition() -> EditionContext {
+        EditionContext {
+            mode: EditionMode::Premium,
+            license: None,
+            pro_engine: None,
+            capabilities: Capabilities {
+                allow_predict: true,
+                allow_explain_full: true,
+                allow_autofix: true,
+                allow_mapping_deep: true,
+                allow_trend: true,
+                allow_policy_enforce: true,
+                allow_slo_enforce: true,
+            },
+            pro: None,
+            paths: EditionPaths::default(),
+            org_policy: None,
+        }
+    }
+
+    /// Price `changes` from the usage profile and the plan resources in
+    /// `context` that size them
+    fn predict(
+        changes: &[ResourceChange],
+        usage: UsageProfile,
+        context: &[ResourceChange],
+    ) -> Result<Vec<CostEstimate>, String> {
+        PredictionEngine::new()
+            .map(|engine| {
+                engine
+                    .with_usage_profile(usage)
+                    .with_autoscaling_targets(context)
+                    .with_task_definitions(context)
+            })
+            .and_then(|mut engine| engine.predict(changes))
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl ProEngineExecutor for NativeProEngine {
+    fn execute(&self, req: ProEngineRequest) -> Result<ProEngineResponse, String> {
+        let edition = Self::edition();
+
+        match req {
+            ProEngineRequest::Predict {
+                changes,
+                usage,
+                context,
+            } => Self::predict(&changes, usage, &context).map(ProEngineResponse::Predict),
+            ProEngineRequest::Explain {
+                detections,
+                changes,
+                estimates,
+            } => {
+                let engine = PredictionEngine::new().map_err(|e| e.to_string())?;
+                let explainer = PredictionExplainer::from_engine(&engine);
+
+                // Explain flagged resources, or every estimate if none were flagged
+                let explanations = changes
+                    .iter()
+                    .filter(|c| {
+                        detections.is_empty()
+                            || detections.iter().any(|d| d.resource_id == c.resource_id)
+                    })
+                    .filter_map(|change| {
+                        let estimate = estimates
+                            .iter()
+                            .find(|e| e.resource_id == change.resource_id)?;
+                        Some(explainer.explain(change, estimate))
+                    })
+                    .collect();
+                Ok(ProEngineResponse::Explain(explanations))
+            }
+            ProEngineRequest::Autofix {
+                detections,
+                changes,
+                estimates,
+                mode,
+            } => AutofixEngine::generate_fixes(&detections, &changes, &estimates, mode, &edition)
+                .map(ProEngineResponse::Autofix)
+                .map_err(|e| e.to_string()),
+            ProEngineRequest::MapDeep { changes, max_depth } => {
+                let config = GraphConfig {
+                    max_depth: Some(max_depth as usize),
+                    ..GraphConfig::default()
+                };
+                GraphBuilder::with_config(config)
+                    .build_graph(&changes)
+                    .map(ProEngineResponse::MapDeep)
+                    .map_err(|e| e.to_string())
+            }
+            ProEngineRequest::TrendSnapshot { changes, metadata } => {
+                let estimates = Self::predict(&changes, UsageProfile::default(), &changes)?;
+                // Snapshots are returned, never written, so storage is unused
+                let engine = TrendEngine::new(edition.paths.config_dir.join("snapshots"), &edition)
+                    .map_err(|e| e.to_string())?;
+                let mut snapshot = engine
+                    .create_snapshot(estimates, None, None)
+                    .map_err(|e| e.to_string())?;
+                if let Some(triggered_by) = metadata {
+                    snapshot.metadata = Some(SnapshotMetadata {
+                        triggered_by: Some(triggered_by),
+                        ci_run_id: None,
+                        pr_number: None,
+                        environment: None,
+                    });
+                }
+                Ok(ProEngineResponse::TrendSnapshot(Box::new(snapshot)))
+            }
+            ProEngineRequest::PolicyEnforce { changes, estimates } => {
+                let total = CostEstimate::builder()
+                    .resource_id("total")
+                    .monthly_cost(estimates.iter().map(|e| e.monthly_cost).sum())
+                    .build();
+                let result =
+                    PolicyEngine::new(PolicyConfig::default(), &edition).evaluate(&changes, &total);
+                Ok(ProEngineResponse::PolicyEnforce(result))
+            }
+            ProEngineRequest::SloEnforce { snapshot } => {
+                let report =
+                    SloManager::new(SloConfig::default(), &edition).evaluate_snapshot(&snapshot);
+                Ok(ProEngineResponse::SloEnforce(report))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::{ChangeAction, Detection, RegressionType, Severity};
+    use std::collections::HashMap;
+
+    fn changes() -> Vec<ResourceChange> {
+        vec![ResourceChange {
+            resource_id: "module.web.aws_instance.app".to_string(),
+            resource_type: "aws_instance".to_string(),
+            action: ChangeAction::Create,
+            module_path: Some("module.web".to_string()),
+            old_config: None,
+            new_config: Some(serde_json::json!({ "instance_type": "m5.4xlarge" })),
+            tags: HashMap::new(),
+            monthly_cost: None,
+            config: None,
+            cost_impact: None,
+        }]
+    }
+
+    #[test]
+    fn test_predict_then_snapshot() {
+        let engine = NativeProEngine::new();
+
+        let estimates = match engine
+            .execute(ProEngineRequest::Predict {
+                changes: changes(),
+                usage: UsageProfile::default(),
+                context: Vec::new(),
+            })
+            .unwrap()
+        {
+            ProEngineResponse::Predict(estimates) => estimates,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(estimates.len(), 1);
+        assert!(estimates[0].monthly_cost > 0.0);
+
+        let snapshot = match engine
+            .execute(ProEngineRequest::TrendSnapshot {
+                changes: changes(),
+                metadata: Some("ci".to_string()),
+            })
+            .unwrap()
+        {
+            ProEngineResponse::TrendSnapshot(snapshot) => snapshot,
+            other => panic!("unexpected response: {:?}", other),
+        };
+        assert_eq!(snapshot.total_monthly_cost, estimates[0].monthly_cost);
+        assert_eq!(
+            snapshot.metadata.unwrap().triggered_by.as_deref(),
+            Some("ci")
+        );
+    }
+
+    #[test]
+    fn test_explain_covers_flagged_resources() {
+        let detection = Detection {
+            rule_id: "OVERPROVISIONED_EC2".to_string(),
+            severity: Severity::High,
+            resource_id: "module.web.aws_instance.app".to_string(),
+            regression_type: RegressionType::Configuration,
+            severity_score: 70,
+            message: "Large instance".to_string(),
+            fix_snippet: None,
+            estimated_cost: None,
+        };
+        let orphan = Detection {
+            resource_id: "aws_instance.missing".to_string(),
+            ..detection.clone()
+        };
+
+        let engine = NativeProEngine::new();
+        let estimates = match engine
+            .execute(ProEngineRequest::Predict {
+                changes: changes(),
+                usage: UsageProfile::default(),
+                context: Vec::new(),
+            })
+            .unwrap()
+        {
+            ProEngineResponse::Predict(estimates) => estimates,
+            other => panic!("unexpected response: {:?}", other),
+        };
+
+        let response = engine
+            .execute(ProEngineRequest::Explain {
+                detections: vec![detection, orphan],
+                changes: changes(),
+                estimates,
+            })
+            .unwrap();
+        match response {
+            ProEngineResponse::Explain(explanations) => assert_eq!(explanations.len(), 1),
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_predict_prices_tables_from_usage_and_plan_context() {
+        let table = |name: &str, config: serde_json::Value| ResourceChange {
+            resource_id: format!("aws_dynamodb_table.{}", name),
+            resource_type: "aws_dynamodb_table".to_string(),
+            action: ChangeAction::Create,
+            module_path: None,
+            old_config: None,
+            new_config: Some(config),
+            tags: HashMap::new(),
+            monthly_cost: None,
+            config: None,
+            cost_impact: None,
+        };
+        let events = table(
+            "events",
+            serde_json::json!({ "name": "events", "billing_mode": "PAY_PER_REQUEST" }),
+        );
+        let orders = table(
+            "orders",
+            serde_json::json!({ "name": "orders", "read_capacity": 10, "write_capacity": 5 }),
+        );
+        // The target is priced in another batch, so it only arrives as context
+        let target = ResourceChange {
+            resource_id: "aws_appautoscaling_target.orders_read".to_string(),
+            resource_type: "aws_appautoscaling_target".to_string(),
+            new_config: Some(serde_json::json!({
+                "resource_id": "table/orders",
+                "scalable_dimension": "dynamodb:table:ReadCapacityUnits",
+                "min_capacity": 5,
+                "max_capacity": 200
+            })),
+            ..orders.clone()
+        };
+
+        let estimates = match NativeProEngine::new()
+            .execute(ProEngineRequest::Predict {
+                changes: vec![events, orders],
+                usage: UsageProfile::default().with(
+                    "aws_dynamodb_table.events",
+                    "monthly_read_request_units",
+                    100_000_000.0,
+                ),
+                context: vec![target],
+            })
+            .unwrap()
+        {
+            ProEngineResponse::Predict(estimates) => estimates,
+            other => panic!("unexpected response: {:?}", other),
+        };
+
+        // 100M reads × $0.25/M + 1M default writes × $1.25/M + 1 GB × $0.25
+        assert!((estimates[0].monthly_cost - 26.5).abs() < 1e-6);
+        assert!(estimates[1].prediction_interval_high >= 200.0 * 0.00013 * 730.0);
+    }
+
+    #[test]
+    fn test_response_round_trips_through_json() {
+        let response = NativeProEngine::new()
+            .execute(ProEngineRequest::PolicyEnforce {
+                changes: changes(),
+                estimates: Vec::new(),
+            })
+            .unwrap();
+
+        let json = serde_json::to_string(&response).unwrap();
+        match serde_json::from_str::<ProEngineResponse>(&json).unwrap() {
+            ProEngineResponse::PolicyEnforce(result) => assert!(result.passed),
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+}
@@ -1,16 +1,65 @@
+use super::bundle_installer::{sha256_hex, BundleInstaller};
+use super::reload::ReloadableExecutor;
 use super::{crypto, instantiate, license::License};
 use crate::edition::{EditionContext, ProEngineHandle as EditionProEngineHandle};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Encrypted ProEngine file name under `~/.costpilot/`
+pub const ENGINE_FILE: &str = "pro-engine.wasm.enc";
+
+/// ProEngine WASM signature file name under `~/.costpilot/`
+pub const ENGINE_SIGNATURE_FILE: &str = "pro-engine.sig";
+
+/// Audit log under `~/.costpilot/` recording engine verification results
+pub const ENGINE_AUDIT_LOG: &str = "audit_log.json";
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn load_pro_engine(edition: &mut EditionContext) -> Result<(), String> {
     let home = dirs::home_dir().ok_or_else(|| "Could not determine home directory".to_string())?;
     let base = home.join(".costpilot");
-    let wasm_enc = base.join("pro-engine.wasm.enc");
+
+    let Some(engine) = load_verified_engine(&base)? else {
+        return Ok(());
+    };
+
+    edition.mode = crate::edition::EditionMode::for_license(&engine.license);
+    edition.license = Some(engine.license);
+
+    let executor = Arc::new(ReloadableExecutor::new(Box::new(WrapperExecutor {
+        inner: engine.executor,
+    })));
+    let engine_edition = EditionProEngineHandle::with_reloadable(
+        base.join(ENGINE_FILE),
+        Some(engine.plaintext),
+        executor,
+    );
+
+    edition.pro = Some(engine_edition);
+    edition.capabilities = crate::edition::Capabilities::from_edition(edition);
+
+    Ok(())
+}
+
+/// Engine that passed license, manifest, decryption and signature checks
+pub struct VerifiedEngine {
+    pub license: License,
+    pub plaintext: Vec<u8>,
+    pub executor: crate::pro_engine::ProEngineHandle,
+    /// SHA-256 of the encrypted engine file, as listed in the signed manifest
+    pub sha256: String,
+}
+
+/// Verify and instantiate the engine under `base`; `None` if any of the
+/// engine, license or signature files is missing
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_verified_engine(base: &Path) -> Result<Option<VerifiedEngine>, String> {
+    let wasm_enc = base.join(ENGINE_FILE);
     let license_file = base.join("license.json");
-    let sig_file = base.join("pro-engine.sig");
+    let sig_file = base.join(ENGINE_SIGNATURE_FILE);
 
     if !wasm_enc.exists() || !license_file.exists() || !sig_file.exists() {
-        return Ok(());
+        return Ok(None);
     }
 
     let lic = License::load_from_file(&license_file)?;
@@ -19,37 +68,95 @@ pub fn load_pro_engine(edition: &mut EditionContext) -> Result<(), String> {
 
     crypto::verify_license_signature(&lic)?;
 
-    edition.license = Some(lic.clone());
-
-    let key = crypto::derive_key(&lic.license_key);
-
     let ciphertext =
         std::fs::read(&wasm_enc).map_err(|e| format!("Failed to read encrypted WASM: {}", e))?;
-    let plaintext = crypto::decrypt_aes_gcm(&ciphertext, &key)?;
-
     let sig =
         std::fs::read(&sig_file).map_err(|e| format!("Failed to read WASM signature: {}", e))?;
+    let sha256 = sha256_hex(&ciphertext);
+
+    // The signed manifest must vouch for the exact bytes read above before
+    // anything is decrypted; the same bytes are used from here on
+    let manifest =
+        match BundleInstaller::new(base.to_path_buf()).verify_installed(&ciphertext, &sig) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                let reason = format!("ProEngine verification failed: {}", e);
+                record_verification(base, &lic, &sha256, Err(&reason));
+                return Err(reason);
+            }
+        };
+
+    let key = crypto::derive_key(&lic.license_key);
+    let plaintext = crypto::decrypt_aes_gcm(&ciphertext, &key)?;
     crypto::verify_wasm_signature(&plaintext, &sig)?;
 
-    let engine_internal = instantiate::instantiate_wasm(&plaintext)?;
+    let executor = instantiate::instantiate_wasm(&plaintext)?;
+    record_verification(base, &lic, &sha256, Ok(&manifest.engine_version));
 
-    let engine_edition = EditionProEngineHandle::with_executor(
-        wasm_enc.clone(),
-        Some(plaintext),
-        Box::new(WrapperExecutor {
-            inner: engine_internal,
-        }),
-    );
+    Ok(Some(VerifiedEngine {
+        license: lic,
+        plaintext,
+        executor,
+        sha256,
+    }))
+}
 
-    edition.pro = Some(engine_edition);
-    edition.mode = crate::edition::EditionMode::Premium;
-    edition.capabilities = crate::edition::Capabilities::from_edition(edition);
+/// Append the verification result to the audit log next to the engine
+///
+/// A verified digest is only recorded when it differs from the last one, so
+/// routine runs do not grow the log; failing to write the log is not fatal.
+#[cfg(not(target_arch = "wasm32"))]
+fn record_verification(base: &Path, license: &License, sha256: &str, outcome: Result<&str, &str>) {
+    use crate::cli::commands::audit::{load_audit_log, save_audit_log};
+    use crate::engines::policy::{AuditEvent, AuditEventType};
 
-    Ok(())
+    let path = base.join(ENGINE_AUDIT_LOG);
+    let result = load_audit_log(Some(path.clone())).and_then(|mut log| {
+        let event = match outcome {
+            Ok(version) => {
+                let last = log
+                    .get_by_event_type(AuditEventType::EngineVerified)
+                    .last()
+                    .and_then(|entry| entry.event.new_value.clone());
+                if last.as_deref() == Some(sha256) {
+                    return Ok(());
+                }
+                let mut event = AuditEvent::new(
+                    AuditEventType::EngineVerified,
+                    license.email.clone(),
+                    ENGINE_FILE.to_string(),
+                    "pro_engine".to_string(),
+                    format!("Verified ProEngine {} against its signed manifest", version),
+                )
+                .with_metadata("engine_version".to_string(), version.to_string());
+                event.old_value = last;
+                event.new_value = Some(sha256.to_string());
+                event
+            }
+            Err(reason) => AuditEvent::new(
+                AuditEventType::EngineRejected,
+                license.email.clone(),
+                ENGINE_FILE.to_string(),
+                "pro_engine".to_string(),
+                "Refused to load ProEngine".to_string(),
+            )
+            .with_metadata("sha256".to_string(), sha256.to_string())
+            .with_error(reason.to_string()),
+        };
+        log.append(event)?;
+        save_audit_log(&log, Some(path.clone()))
+    });
+    if let Err(e) = result {
+        tracing::warn!(
+            path = %path.display(),
+            "Failed to record ProEngine verification: {}",
+            e
+        );
+    }
 }
 
-struct WrapperExecutor {
-    inner: crate::pro_engine::ProEngineHandle,
+pub(crate) struct WrapperExecutor {
+    pub(crate) inner: crate::pro_engine::ProEngineHandle,
 }
 
 impl crate::pro_engine::ProEngineExecutor for WrapperExecutor {
@@ -60,3 +167,48 @@ impl crate::pro_engine::ProEngineExecutor for WrapperExecutor {
         self.inner.execute(req)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::commands::audit::load_audit_log;
+    use crate::engines::policy::AuditEventType;
+    use tempfile::TempDir;
+
+    fn license() -> License {
+        License {
+            email: "ops@example.com".to_string(),
+            license_key: "key".to_string(),
+            expires: "2099-01-01T00:00:00Z".to_string(),
+            signature: String::new(),
+            issuer: "test-costpilot".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verification_results_are_audited() {
+        let dir = TempDir::new().unwrap();
+        record_verification(dir.path(), &license(), "aaa", Ok("2.0.0"));
+        record_verification(dir.path(), &license(), "aaa", Ok("2.0.0"));
+        record_verification(dir.path(), &license(), "bbb", Err("checksum mismatch"));
+        record_verification(dir.path(), &license(), "ccc", Ok("2.1.0"));
+
+        let log = load_audit_log(Some(dir.path().join(ENGINE_AUDIT_LOG))).unwrap();
+        assert!(log.verify_chain().is_ok());
+        let verified = log.get_by_event_type(AuditEventType::EngineVerified);
+        let digests: Vec<_> = verified
+            .iter()
+            .map(|e| (e.event.old_value.as_deref(), e.event.new_value.as_deref()))
+            .collect();
+        assert_eq!(
+            digests,
+            vec![(None, Some("aaa")), (Some("aaa"), Some("ccc"))]
+        );
+
+        let rejected = log.get_by_event_type(AuditEventType::EngineRejected);
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].event.metadata["sha256"], "bbb");
+        assert!(!rejected[0].event.success);
+        assert_eq!(rejected[0].event.actor, "ops@example.com");
+    }
+}
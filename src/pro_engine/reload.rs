@@ -0,0 +1,326 @@
+// ProEngine hot-reload - re-verify and swap the engine when its files change
+//
+// The engine is verified once at startup. Long-running sessions hold a
+// `ReloadableExecutor` instead, and a `ProEngineReloader` polls the encrypted
+// engine, its signature and the license. When they change, the full
+// verification (license signature, decryption, WASM signature) runs again and
+// the new instance is swapped in. A bundle that fails verification is
+// rejected and the running engine is kept.
+
+use super::pro_loader::{ENGINE_FILE, ENGINE_SIGNATURE_FILE};
+use super::{ProEngineExecutor, ProEngineRequest, ProEngineResponse};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, RwLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Default interval between file checks
+pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+type SharedExecutor = Arc<dyn ProEngineExecutor + Send + Sync>;
+
+/// Verifies and instantiates the engine under a base directory
+pub type EngineLoader =
+    Box<dyn Fn(&Path) -> Result<Option<Box<dyn ProEngineExecutor + Send + Sync>>, String> + Send>;
+
+/// Executor whose engine instance can be replaced while in use
+///
+/// Requests in flight keep the instance they started with; the next request
+/// sees the swapped one.
+pub struct ReloadableExecutor {
+    current: RwLock<SharedExecutor>,
+    generation: AtomicU64,
+}
+
+impl ReloadableExecutor {
+    pub fn new(executor: Box<dyn ProEngineExecutor + Send + Sync>) -> Self {
+        Self {
+            current: RwLock::new(Arc::from(executor)),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Replace the engine instance, returning the new generation
+    pub fn swap(&self, executor: Box<dyn ProEngineExecutor + Send + Sync>) -> u64 {
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        *current = Arc::from(executor);
+        self.generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Number of successful swaps since startup
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    fn current(&self) -> SharedExecutor {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl ProEngineExecutor for ReloadableExecutor {
+    fn execute(&self, req: ProEngineRequest) -> Result<ProEngineResponse, String> {
+        // Release the lock before running so a swap never waits on a request
+        let executor = self.current();
+        executor.execute(req)
+    }
+}
+
+/// Result of a single reload check
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReloadOutcome {
+    /// Engine files are unchanged since the last check
+    Unchanged,
+
+    /// New engine verified and swapped in
+    Reloaded { generation: u64 },
+
+    /// Changed files failed verification; the running engine is kept
+    Rejected(String),
+}
+
+/// Watches the engine files and reloads on change
+pub struct ProEngineReloader {
+    base: PathBuf,
+    executor: Arc<ReloadableExecutor>,
+    loader: EngineLoader,
+    fingerprint: Option<String>,
+}
+
+impl ProEngineReloader {
+    /// Watch `base` (normally `~/.costpilot`), treating the files currently
+    /// on disk as the ones the running engine was loaded from
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(base: impl Into<PathBuf>, executor: Arc<ReloadableExecutor>) -> Self {
+        Self::with_loader(
+            base,
+            executor,
+            Box::new(|base: &Path| {
+                Ok(
+                    super::pro_loader::load_verified_engine(base)?.map(|engine| {
+                        Box::new(super::pro_loader::WrapperExecutor {
+                            inner: engine.executor,
+                        }) as Box<dyn ProEngineExecutor + Send + Sync>
+                    }),
+                )
+            }),
+        )
+    }
+
+    /// Watch with a custom loader
+    pub fn with_loader(
+        base: impl Into<PathBuf>,
+        executor: Arc<ReloadableExecutor>,
+        loader: EngineLoader,
+    ) -> Self {
+        let base = base.into();
+        let fingerprint = fingerprint(&base);
+        Self {
+            base,
+            executor,
+            loader,
+            fingerprint,
+        }
+    }
+
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// Check the engine files once, reloading if they changed
+    pub fn poll(&mut self) -> ReloadOutcome {
+        let current = fingerprint(&self.base);
+        if current == self.fingerprint {
+            return ReloadOutcome::Unchanged;
+        }
+        // Remember the files even on failure so a bad bundle is reported
+        // once, not on every poll
+        self.fingerprint = current;
+
+        if self.fingerprint.is_none() {
+            return ReloadOutcome::Rejected(format!(
+                "ProEngine files missing from {}",
+                self.base.display()
+            ));
+        }
+
+        match (self.loader)(&self.base) {
+            Ok(Some(executor)) => ReloadOutcome::Reloaded {
+                generation: self.executor.swap(executor),
+            },
+            Ok(None) => ReloadOutcome::Rejected(format!(
+                "ProEngine files missing from {}",
+                self.base.display()
+            )),
+            Err(e) => ReloadOutcome::Rejected(e),
+        }
+    }
+
+    /// Poll on a background thread, reporting every change to `on_event`
+    pub fn spawn<F>(mut self, interval: Duration, mut on_event: F) -> ReloadWatcher
+    where
+        F: FnMut(&ReloadOutcome) + Send + 'static,
+    {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let handle = std::thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                let outcome = self.poll();
+                if outcome != ReloadOutcome::Unchanged {
+                    on_event(&outcome);
+                }
+            }
+        });
+
+        ReloadWatcher {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+}
+
+/// Background reload thread; stopped when dropped
+pub struct ReloadWatcher {
+    stop: Option<mpsc::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ReloadWatcher {
+    /// Stop polling and wait for the thread to exit
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ReloadWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Hash of the engine, signature and license files; `None` if any is missing
+fn fingerprint(base: &Path) -> Option<String> {
+    let mut hasher = Sha256::new();
+    for name in [ENGINE_FILE, ENGINE_SIGNATURE_FILE, "license.json"] {
+        let bytes = std::fs::read(base.join(name)).ok()?;
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(&bytes);
+    }
+    Some(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::shared::models::CostEstimate;
+    use tempfile::TempDir;
+
+    /// Answers predictions with a fixed cost
+    struct FixedCost(f64);
+
+    impl ProEngineExecutor for FixedCost {
+        fn execute(&self, _req: ProEngineRequest) -> Result<ProEngineResponse, String> {
+            Ok(ProEngineResponse::Predict(vec![CostEstimate::builder()
+                .resource_id("r")
+                .monthly_cost(self.0)
+                .build()]))
+        }
+    }
+
+    /// Loads a "bundle" whose engine file holds the cost, rejecting non-numbers
+    fn loader() -> EngineLoader {
+        Box::new(|base: &Path| {
+            let text =
+                std::fs::read_to_string(base.join(ENGINE_FILE)).map_err(|e| e.to_string())?;
+            let cost: f64 = text
+                .trim()
+                .parse()
+                .map_err(|_| "WASM signature verification failed".to_string())?;
+            Ok(Some(Box::new(FixedCost(cost))))
+        })
+    }
+
+    fn write_bundle(dir: &Path, engine: &str) {
+        std::fs::write(dir.join(ENGINE_FILE), engine).unwrap();
+        std::fs::write(dir.join(ENGINE_SIGNATURE_FILE), "sig").unwrap();
+        std::fs::write(dir.join("license.json"), "{}").unwrap();
+    }
+
+    fn cost(executor: &ReloadableExecutor) -> f64 {
+        match executor
+            .execute(ProEngineRequest::Predict {
+                changes: vec![],
+                usage: Default::default(),
+                context: vec![],
+            })
+            .unwrap()
+        {
+            ProEngineResponse::Predict(estimates) => estimates[0].monthly_cost,
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_changed_bundle_is_swapped_in() {
+        let dir = TempDir::new().unwrap();
+        write_bundle(dir.path(), "10");
+        let executor = Arc::new(ReloadableExecutor::new(Box::new(FixedCost(10.0))));
+        let mut reloader = ProEngineReloader::with_loader(dir.path(), executor.clone(), loader());
+
+        assert_eq!(reloader.poll(), ReloadOutcome::Unchanged);
+
+        write_bundle(dir.path(), "20");
+        assert_eq!(reloader.poll(), ReloadOutcome::Reloaded { generation: 1 });
+        assert_eq!(cost(&executor), 20.0);
+        assert_eq!(reloader.poll(), ReloadOutcome::Unchanged);
+    }
+
+    #[test]
+    fn test_rejected_bundle_keeps_running_engine() {
+        let dir = TempDir::new().unwrap();
+        write_bundle(dir.path(), "10");
+        let executor = Arc::new(ReloadableExecutor::new(Box::new(FixedCost(10.0))));
+        let mut reloader = ProEngineReloader::with_loader(dir.path(), executor.clone(), loader());
+
+        write_bundle(dir.path(), "tampered");
+        assert!(matches!(reloader.poll(), ReloadOutcome::Rejected(_)));
+        assert_eq!(reloader.poll(), ReloadOutcome::Unchanged);
+        assert_eq!(cost(&executor), 10.0);
+        assert_eq!(executor.generation(), 0);
+
+        std::fs::remove_file(dir.path().join(ENGINE_SIGNATURE_FILE)).unwrap();
+        assert!(matches!(reloader.poll(), ReloadOutcome::Rejected(_)));
+        assert_eq!(cost(&executor), 10.0);
+    }
+
+    #[test]
+    fn test_background_watcher_reports_reload() {
+        let dir = TempDir::new().unwrap();
+        write_bundle(dir.path(), "10");
+        let executor = Arc::new(ReloadableExecutor::new(Box::new(FixedCost(10.0))));
+        let reloader = ProEngineReloader::with_loader(dir.path(), executor.clone(), loader());
+
+        let (tx, rx) = mpsc::channel();
+        let watcher = reloader.spawn(Duration::from_millis(10), move |outcome| {
+            let _ = tx.send(outcome.clone());
+        });
+
+        write_bundle(dir.path(), "30");
+        let outcome = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        watcher.stop();
+
+        assert_eq!(outcome, ReloadOutcome::Reloaded { generation: 1 });
+        assert_eq!(cost(&executor), 30.0);
+    }
+}
@@ -0,0 +1,412 @@
+// Encryption at rest for local stores (`storage` in costpilot.yaml)
+//
+// Trend snapshots and the usage log name resources, modules and the people
+// running CostPilot. With `storage.encrypt_at_rest` on, new writes are sealed
+// with AES-256-GCM under a key derived from the installed license. Reads
+// decrypt transparently and pass plaintext through, so a store can hold a mix
+// of both while `costpilot storage encrypt` migrates older files.
+
+use crate::engines::shared::error_model::{CostPilotError, ErrorCategory, Result};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::pro_engine::License;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::{engine::general_purpose, Engine as _};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Prefix marking an encrypted record; the version names the key derivation
+/// and record layout
+pub const ENCRYPTED_PREFIX: &str = "costpilot-enc:v1:";
+
+/// Environment variable turning `storage.encrypt_at_rest` on or off
+pub const ENCRYPT_ENV_VAR: &str = "COSTPILOT_ENCRYPT_AT_REST";
+
+/// Project config read for the `storage` section
+const PROJECT_CONFIG: &str = "costpilot.yaml";
+
+const NONCE_LEN: usize = 12;
+
+/// Storage settings (`storage` in costpilot.yaml)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StorageConfig {
+    /// Encrypt new trend snapshots and usage log entries with a key derived
+    /// from the installed license
+    #[serde(default)]
+    pub encrypt_at_rest: bool,
+}
+
+impl StorageConfig {
+    /// Load `storage` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `storage` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `storage` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        match root.get("storage").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid storage section: {}", e))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// How records are laid out in a store file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileLayout {
+    /// The whole file is one record (trend snapshots)
+    Whole,
+    /// Each line is a record (NDJSON logs)
+    Lines,
+}
+
+/// AES-256-GCM cipher keyed from a license
+#[derive(Clone)]
+pub struct AtRestCipher {
+    key: [u8; 32],
+}
+
+impl fmt::Debug for AtRestCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AtRestCipher").finish_non_exhaustive()
+    }
+}
+
+impl AtRestCipher {
+    /// Cipher using `key` as is
+    pub fn new(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    /// Cipher keyed from `license`; the pro engine key is expanded again so
+    /// store files never share a key with the engine bundle
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_license(license: &License) -> Self {
+        let engine_key = crate::pro_engine::crypto::derive_key(&license.license_key);
+        let hk = hkdf::SimpleHkdf::<sha2::Sha256>::new(Some(b"costpilot-at-rest-v1"), &engine_key);
+        let mut key = [0u8; 32];
+        hk.expand(b"store-key", &mut key)
+            .expect("HKDF expand failed (invalid length)");
+        Self { key }
+    }
+
+    /// Cipher keyed from the license in the user config directory, whether
+    /// or not it is still valid; expired licenses must still open old stores
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn installed() -> Option<Self> {
+        let path = crate::edition::EditionPaths::default().license_path();
+        License::load_from_file(&path)
+            .ok()
+            .map(|license| Self::from_license(&license))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn installed() -> Option<Self> {
+        None
+    }
+
+    /// Encrypt `plaintext` into a single-line record
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new((&self.key).into())
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| {
+                CostPilotError::new(
+                    "AT_REST_002",
+                    ErrorCategory::SecurityViolation,
+                    "Failed to encrypt store record",
+                )
+            })?;
+
+        let mut record = nonce.to_vec();
+        record.extend(ciphertext);
+        Ok(format!(
+            "{}{}",
+            ENCRYPTED_PREFIX,
+            general_purpose::STANDARD.encode(record)
+        ))
+    }
+
+    /// Decrypt a record written by `encrypt`
+    pub fn decrypt(&self, record: &str) -> Result<Vec<u8>> {
+        let failed = |reason: &str| {
+            CostPilotError::new(
+                "AT_REST_002",
+                ErrorCategory::SecurityViolation,
+                format!("Failed to decrypt store record: {}", reason),
+            )
+            .with_hint("The record was written under a different license or has been modified")
+        };
+        let encoded = record
+            .trim()
+            .strip_prefix(ENCRYPTED_PREFIX)
+            .ok_or_else(|| failed("not an encrypted record"))?;
+        let bytes = general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|_| failed("invalid encoding"))?;
+        if bytes.len() < NONCE_LEN {
+            return Err(failed("record too short"));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        Aes256Gcm::new((&self.key).into())
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| failed("authentication failed"))
+    }
+
+    /// Rewrite a plaintext store file encrypted; records already encrypted
+    /// are kept. Returns whether anything needed encrypting.
+    pub fn encrypt_file(&self, path: &Path, layout: FileLayout, dry_run: bool) -> Result<bool> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+
+        let (sealed, changed) = match layout {
+            FileLayout::Whole if is_encrypted(&content) || content.trim().is_empty() => {
+                (content, false)
+            }
+            FileLayout::Whole => (self.encrypt(content.as_bytes())?, true),
+            FileLayout::Lines => {
+                let mut changed = false;
+                let mut lines = Vec::new();
+                for line in content.lines() {
+                    if line.trim().is_empty() || is_encrypted(line) {
+                        lines.push(line.to_string());
+                    } else {
+                        lines.push(self.encrypt(line.as_bytes())?);
+                        changed = true;
+                    }
+                }
+                (format!("{}\n", lines.join("\n")), changed)
+            }
+        };
+
+        if changed && !dry_run {
+            // Write beside the original and rename, so an interrupted run
+            // never leaves a half-written store
+            let staged = path.with_extension("encrypting");
+            std::fs::write(&staged, sealed)
+                .and_then(|_| std::fs::rename(&staged, path))
+                .map_err(|e| {
+                    CostPilotError::io_error(format!("Failed to write {}: {}", path.display(), e))
+                })?;
+        }
+        Ok(changed)
+    }
+}
+
+/// Whether `content` starts with an encrypted record
+pub fn is_encrypted(content: &str) -> bool {
+    content.trim_start().starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Encryption settings a store reads and writes with
+#[derive(Debug, Clone, Default)]
+pub struct StoreEncryption {
+    cipher: Option<AtRestCipher>,
+    encrypt_writes: bool,
+}
+
+impl StoreEncryption {
+    /// Plaintext writes; encrypted records cannot be read
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn with_cipher(cipher: AtRestCipher, encrypt_writes: bool) -> Self {
+        Self {
+            cipher: Some(cipher),
+            encrypt_writes,
+        }
+    }
+
+    /// Settings from `storage` in ./costpilot.yaml and `COSTPILOT_ENCRYPT_AT_REST`,
+    /// keyed from the installed license. Reads of encrypted records work
+    /// whenever a license is installed, even with encryption turned off.
+    ///
+    /// Stores are shared by every profile, so `storage` is read from the base
+    /// config: a profile cannot leave one run's records in plaintext.
+    pub fn from_project() -> Self {
+        let mut config = if Path::new(PROJECT_CONFIG).exists() {
+            StorageConfig::load(Path::new(PROJECT_CONFIG), None).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring storage settings: {}", e);
+                StorageConfig::default()
+            })
+        } else {
+            StorageConfig::default()
+        };
+        if let Ok(value) = std::env::var(ENCRYPT_ENV_VAR) {
+            config.encrypt_at_rest = matches!(
+                value.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            );
+        }
+        Self {
+            cipher: AtRestCipher::installed(),
+            encrypt_writes: config.encrypt_at_rest,
+        }
+    }
+
+    pub fn encrypts_writes(&self) -> bool {
+        self.encrypt_writes
+    }
+
+    /// Content to write for `plaintext`: a single encrypted record, or the
+    /// plaintext itself when encryption is off
+    pub fn seal(&self, plaintext: &str) -> Result<String> {
+        if !self.encrypt_writes {
+            return Ok(plaintext.to_string());
+        }
+        self.cipher()?.encrypt(plaintext.as_bytes())
+    }
+
+    /// Plaintext of stored `content`, decrypting each encrypted line
+    pub fn open(&self, content: &str) -> Result<String> {
+        if !content.contains(ENCRYPTED_PREFIX) {
+            return Ok(content.to_string());
+        }
+        let cipher = self.cipher()?;
+        let mut lines = Vec::new();
+        for line in content.lines() {
+            if is_encrypted(line) {
+                let plaintext = cipher.decrypt(line)?;
+                lines.push(String::from_utf8(plaintext).map_err(|_| {
+                    CostPilotError::new(
+                        "AT_REST_002",
+                        ErrorCategory::SecurityViolation,
+                        "Decrypted store record is not UTF-8",
+                    )
+                })?);
+            } else {
+                lines.push(line.to_string());
+            }
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn cipher(&self) -> Result<&AtRestCipher> {
+        self.cipher.as_ref().ok_or_else(|| {
+            CostPilotError::new(
+                "AT_REST_001",
+                ErrorCategory::SecurityViolation,
+                "No license installed to derive the storage encryption key",
+            )
+            .with_hint(
+                "Install the license the store was encrypted with, or turn off storage.encrypt_at_rest",
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn cipher(seed: u8) -> AtRestCipher {
+        AtRestCipher::new([seed; 32])
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let encryption = StoreEncryption::with_cipher(cipher(1), true);
+        let sealed = encryption.seal("{\"id\": \"web\"}\n").unwrap();
+
+        assert!(is_encrypted(&sealed));
+        assert!(!sealed.contains("web"));
+        assert_eq!(
+            encryption.open(&sealed).unwrap().trim_end(),
+            "{\"id\": \"web\"}"
+        );
+    }
+
+    #[test]
+    fn test_plaintext_passes_through() {
+        let encryption = StoreEncryption::with_cipher(cipher(1), true);
+        assert_eq!(encryption.open("{\"a\": 1}").unwrap(), "{\"a\": 1}");
+        assert_eq!(
+            StoreEncryption::disabled().seal("{\"a\": 1}").unwrap(),
+            "{\"a\": 1}"
+        );
+    }
+
+    #[test]
+    fn test_mixed_lines() {
+        let encryption = StoreEncryption::with_cipher(cipher(2), true);
+        let content = format!("{}\n{{\"b\":2}}", encryption.seal("{\"a\":1}").unwrap());
+        assert_eq!(encryption.open(&content).unwrap(), "{\"a\":1}\n{\"b\":2}");
+    }
+
+    #[test]
+    fn test_wrong_key_fails() {
+        let sealed = cipher(1).encrypt(b"secret").unwrap();
+        let err = cipher(2).decrypt(&sealed).unwrap_err();
+        assert_eq!(err.id, "AT_REST_002");
+    }
+
+    #[test]
+    fn test_encrypted_content_needs_license() {
+        let sealed = cipher(1).encrypt(b"secret").unwrap();
+        let err = StoreEncryption::disabled().open(&sealed).unwrap_err();
+        assert_eq!(err.id, "AT_REST_001");
+
+        let writes = StoreEncryption {
+            cipher: None,
+            encrypt_writes: true,
+        };
+        assert_eq!(writes.seal("x").unwrap_err().id, "AT_REST_001");
+    }
+
+    #[test]
+    fn test_encrypt_file_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("usage_events.ndjson");
+        std::fs::write(&path, "{\"a\":1}\n{\"b\":2}\n").unwrap();
+        let cipher = cipher(3);
+
+        assert!(cipher.encrypt_file(&path, FileLayout::Lines, true).unwrap());
+        assert!(!is_encrypted(&std::fs::read_to_string(&path).unwrap()));
+
+        assert!(cipher
+            .encrypt_file(&path, FileLayout::Lines, false)
+            .unwrap());
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.lines().all(is_encrypted));
+        assert!(!cipher
+            .encrypt_file(&path, FileLayout::Lines, false)
+            .unwrap());
+
+        let encryption = StoreEncryption::with_cipher(cipher, false);
+        assert_eq!(encryption.open(&content).unwrap(), "{\"a\":1}\n{\"b\":2}");
+    }
+
+    #[test]
+    fn test_storage_config() {
+        let config = StorageConfig::from_yaml("storage:\n  encrypt_at_rest: true\n").unwrap();
+        assert!(config.encrypt_at_rest);
+        assert!(
+            !StorageConfig::from_yaml("version: \"1\"\n")
+                .unwrap()
+                .encrypt_at_rest
+        );
+        assert!(StorageConfig::from_yaml("storage:\n  encrypt: true\n").is_err());
+    }
+}
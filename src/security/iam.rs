@@ -0,0 +1,400 @@
+// IAM policy linting for planned resources
+//
+// Finds policy documents in resource configs: JSON strings in Terraform
+// attributes (`policy`, `assume_role_policy`, `inline_policy`) and objects in
+// CloudFormation/SAM properties (`PolicyDocument`, `Policies`,
+// `AssumeRolePolicyDocument`). Allow statements are checked for wildcards,
+// privilege escalation and public principals; findings are reported as
+// detections next to cost detections.
+
+use crate::engines::shared::models::{
+    ChangeAction, Detection, RegressionType, ResourceChange, Severity,
+};
+use serde_json::Value;
+
+/// `Action: "*"`
+pub const IAM_WILDCARD_ACTION: &str = "IAM_WILDCARD_ACTION";
+/// `Resource: "*"` with actions that change something
+pub const IAM_WILDCARD_RESOURCE: &str = "IAM_WILDCARD_RESOURCE";
+/// Actions that let a principal grant itself more permissions
+pub const IAM_PRIVILEGE_ESCALATION: &str = "IAM_PRIVILEGE_ESCALATION";
+/// Resource or trust policies allowing any principal without conditions
+pub const IAM_PUBLIC_POLICY: &str = "IAM_PUBLIC_POLICY";
+
+/// Actions that can be chained into administrator access
+const ESCALATION_ACTIONS: &[&str] = &[
+    "iam:AddUserToGroup",
+    "iam:AttachGroupPolicy",
+    "iam:AttachRolePolicy",
+    "iam:AttachUserPolicy",
+    "iam:CreateAccessKey",
+    "iam:CreateLoginProfile",
+    "iam:CreatePolicyVersion",
+    "iam:PassRole",
+    "iam:PutGroupPolicy",
+    "iam:PutRolePolicy",
+    "iam:PutUserPolicy",
+    "iam:SetDefaultPolicyVersion",
+    "iam:UpdateAssumeRolePolicy",
+    "iam:UpdateLoginProfile",
+];
+
+/// Nested attributes searched for policy documents
+const MAX_DEPTH: usize = 6;
+
+/// Lint policy documents of created, updated and replaced resources
+pub fn lint_iam_policies(changes: &[ResourceChange]) -> Vec<Detection> {
+    let mut detections: Vec<Detection> = Vec::new();
+    for change in changes {
+        if matches!(change.action, ChangeAction::Delete | ChangeAction::NoOp) {
+            continue;
+        }
+        let Some(config) = change.new_config.as_ref().or(change.config.as_ref()) else {
+            continue;
+        };
+
+        let mut documents = Vec::new();
+        collect_documents(config, false, 0, &mut documents);
+        for (document, trust) in documents {
+            for statement in statements(&document) {
+                for detection in lint_statement(change, statement, trust) {
+                    let duplicate = detections.iter().any(|d| {
+                        d.rule_id == detection.rule_id && d.resource_id == detection.resource_id
+                    });
+                    if !duplicate {
+                        detections.push(detection);
+                    }
+                }
+            }
+        }
+    }
+    detections
+}
+
+/// Policy documents under `value`, flagged when they are trust policies
+fn collect_documents(value: &Value, trust: bool, depth: usize, out: &mut Vec<(Value, bool)>) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    match value {
+        Value::Object(map) if map.contains_key("Statement") => out.push((value.clone(), trust)),
+        Value::Object(map) => {
+            for (key, nested) in map {
+                let trust = trust
+                    || key.to_ascii_lowercase().contains("assume_role")
+                    || key.contains("AssumeRole");
+                collect_documents(nested, trust, depth + 1, out);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_documents(item, trust, depth + 1, out);
+            }
+        }
+        Value::String(s) if s.trim_start().starts_with('{') && s.contains("Statement") => {
+            if let Ok(document) = serde_json::from_str::<Value>(s) {
+                collect_documents(&document, trust, depth + 1, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn statements(document: &Value) -> Vec<&Value> {
+    match &document["Statement"] {
+        Value::Array(statements) => statements.iter().collect(),
+        statement @ Value::Object(_) => vec![statement],
+        _ => Vec::new(),
+    }
+}
+
+/// String or list-of-strings element values
+fn strings(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(s) => vec![s.as_str()],
+        Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn lint_statement(change: &ResourceChange, statement: &Value, trust: bool) -> Vec<Detection> {
+    if statement["Effect"].as_str() != Some("Allow") {
+        return Vec::new();
+    }
+    let mut detections = Vec::new();
+
+    if let Some(principal) = statement.get("Principal") {
+        if is_public(principal) && statement.get("Condition").is_none() {
+            let message = if trust {
+                format!("{} can be assumed by any AWS principal", change.resource_id)
+            } else {
+                format!(
+                    "{} allows access from any principal",
+                    policy_subject(change)
+                )
+            };
+            detections.push(detection(
+                change,
+                IAM_PUBLIC_POLICY,
+                Severity::Critical,
+                message,
+                "Name the principals that need access, or add a Condition such as aws:SourceArn or aws:PrincipalOrgID",
+            ));
+        }
+        return detections;
+    }
+
+    let actions = strings(&statement["Action"]);
+    let all_resources = strings(&statement["Resource"]).contains(&"*");
+
+    if actions.contains(&"*") {
+        let (severity, message) = if all_resources {
+            (
+                Severity::Critical,
+                format!(
+                    "{} grants full administrator access (Action \"*\" on Resource \"*\")",
+                    change.resource_id
+                ),
+            )
+        } else {
+            (
+                Severity::High,
+                format!("{} allows every action (Action \"*\")", change.resource_id),
+            )
+        };
+        detections.push(detection(
+            change,
+            IAM_WILDCARD_ACTION,
+            severity,
+            message,
+            "List the specific actions the principal needs",
+        ));
+        return detections;
+    }
+
+    let escalation: Vec<&str> = ESCALATION_ACTIONS
+        .iter()
+        .copied()
+        .filter(|action| {
+            actions
+                .iter()
+                .any(|pattern| action_matches(pattern, action))
+        })
+        .collect();
+    if !escalation.is_empty() {
+        detections.push(detection(
+            change,
+            IAM_PRIVILEGE_ESCALATION,
+            Severity::High,
+            format!(
+                "{} allows privilege escalation through {}",
+                change.resource_id,
+                escalation.join(", ")
+            ),
+            "Remove the IAM write actions or scope them to specific resources with a permissions boundary",
+        ));
+    }
+
+    if all_resources && actions.iter().any(|action| !is_read_only(action)) {
+        detections.push(detection(
+            change,
+            IAM_WILDCARD_RESOURCE,
+            Severity::Medium,
+            format!(
+                "{} allows write actions on every resource (Resource \"*\")",
+                change.resource_id
+            ),
+            "Scope Resource to the ARNs the actions apply to",
+        ));
+    }
+    detections
+}
+
+/// `"*"`, `{"AWS": "*"}` or a list containing `"*"`
+fn is_public(principal: &Value) -> bool {
+    match principal {
+        Value::Object(map) => map.values().any(|v| strings(v).contains(&"*")),
+        other => strings(other).contains(&"*"),
+    }
+}
+
+fn policy_subject(change: &ResourceChange) -> String {
+    let kind = match change.resource_type.as_str() {
+        t if t.starts_with("aws_s3_bucket") => "S3 bucket policy",
+        t if t.starts_with("aws_sqs_queue") || t == "aws_queuepolicy" => "SQS queue policy",
+        t if t.starts_with("aws_sns_topic") || t == "aws_topicpolicy" => "SNS topic policy",
+        _ => "Resource policy",
+    };
+    format!("{} {}", kind, change.resource_id)
+}
+
+/// IAM action patterns are case-insensitive and support `*` and `?`
+fn action_matches(pattern: &str, action: &str) -> bool {
+    fn matches(pattern: &[u8], action: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => action.is_empty(),
+            Some((b'*', rest)) => (0..=action.len()).any(|i| matches(rest, &action[i..])),
+            Some((&p, rest)) => action
+                .split_first()
+                .is_some_and(|(&a, tail)| (p == b'?' || p == a) && matches(rest, tail)),
+        }
+    }
+    matches(
+        pattern.to_ascii_lowercase().as_bytes(),
+        action.to_ascii_lowercase().as_bytes(),
+    )
+}
+
+fn is_read_only(action: &str) -> bool {
+    let verb = action.split_once(':').map_or(action, |(_, verb)| verb);
+    ["get", "list", "describe"]
+        .iter()
+        .any(|prefix| verb.to_ascii_lowercase().starts_with(prefix))
+}
+
+fn detection(
+    change: &ResourceChange,
+    rule_id: &str,
+    severity: Severity,
+    message: String,
+    fix: &str,
+) -> Detection {
+    Detection {
+        rule_id: rule_id.to_string(),
+        severity_score: match severity {
+            Severity::Critical => 90,
+            Severity::High => 70,
+            Severity::Medium => 45,
+            Severity::Low => 20,
+        },
+        severity,
+        resource_id: change.resource_id.clone(),
+        regression_type: RegressionType::Configuration,
+        message,
+        fix_snippet: Some(fix.to_string()),
+        estimated_cost: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn change(id: &str, resource_type: &str, config: Value) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(id)
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .build()
+    }
+
+    fn rules(detections: &[Detection]) -> Vec<(&str, &str, &Severity)> {
+        detections
+            .iter()
+            .map(|d| (d.rule_id.as_str(), d.resource_id.as_str(), &d.severity))
+            .collect()
+    }
+
+    #[test]
+    fn test_terraform_policy_strings() {
+        let admin = json!({"Version": "2012-10-17", "Statement": [
+            {"Effect": "Allow", "Action": "*", "Resource": "*"}
+        ]});
+        let escalation = json!({"Statement": [
+            {"Effect": "Allow", "Action": ["iam:Put*", "iam:PassRole", "s3:GetObject"], "Resource": "*"},
+            {"Effect": "Deny", "Action": "*", "Resource": "*"}
+        ]});
+        let read_only =
+            json!({"Statement": {"Effect": "Allow", "Action": "ec2:Describe*", "Resource": "*"}});
+        let changes = vec![
+            change(
+                "aws_iam_policy.admin",
+                "aws_iam_policy",
+                json!({"policy": admin.to_string()}),
+            ),
+            change(
+                "aws_iam_role.ci",
+                "aws_iam_role",
+                json!({"inline_policy": [{"name": "deploy", "policy": escalation.to_string()}]}),
+            ),
+            change(
+                "aws_iam_role_policy.ro",
+                "aws_iam_role_policy",
+                json!({"policy": read_only.to_string()}),
+            ),
+        ];
+
+        let detections = lint_iam_policies(&changes);
+        assert_eq!(
+            rules(&detections),
+            vec![
+                (
+                    IAM_WILDCARD_ACTION,
+                    "aws_iam_policy.admin",
+                    &Severity::Critical
+                ),
+                (IAM_PRIVILEGE_ESCALATION, "aws_iam_role.ci", &Severity::High),
+                (IAM_WILDCARD_RESOURCE, "aws_iam_role.ci", &Severity::Medium),
+            ]
+        );
+        assert!(detections[1]
+            .message
+            .contains("iam:PassRole, iam:PutGroupPolicy"));
+    }
+
+    #[test]
+    fn test_public_resource_and_trust_policies() {
+        let public = json!({"Statement": [{"Effect": "Allow", "Principal": "*", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::site/*"}]});
+        let conditioned = json!({"Statement": [{"Effect": "Allow", "Principal": {"AWS": "*"}, "Action": "sqs:SendMessage",
+            "Resource": "*", "Condition": {"ArnEquals": {"aws:SourceArn": "arn:aws:sns:us-east-1:1:t"}}}]});
+        let changes = vec![
+            change(
+                "aws_s3_bucket_policy.site",
+                "aws_s3_bucket_policy",
+                json!({"policy": public.to_string()}),
+            ),
+            change(
+                "aws_sqs_queue_policy.q",
+                "aws_sqs_queue_policy",
+                json!({"policy": conditioned.to_string()}),
+            ),
+            // CloudFormation properties hold documents as objects
+            change(
+                "OpenRole",
+                "aws_iam_role",
+                json!({"AssumeRolePolicyDocument": {"Statement": [
+                    {"Effect": "Allow", "Principal": {"AWS": ["*"]}, "Action": "sts:AssumeRole"}
+                ]}}),
+            ),
+        ];
+
+        let detections = lint_iam_policies(&changes);
+        assert_eq!(
+            rules(&detections),
+            vec![
+                (
+                    IAM_PUBLIC_POLICY,
+                    "aws_s3_bucket_policy.site",
+                    &Severity::Critical
+                ),
+                (IAM_PUBLIC_POLICY, "OpenRole", &Severity::Critical),
+            ]
+        );
+        assert!(detections[0].message.starts_with("S3 bucket policy"));
+        assert!(detections[1]
+            .message
+            .contains("assumed by any AWS principal"));
+    }
+
+    #[test]
+    fn test_action_patterns() {
+        assert!(action_matches("iam:*", "iam:PassRole"));
+        assert!(action_matches("IAM:passrole", "iam:PassRole"));
+        assert!(action_matches("iam:Create?ccessKey", "iam:CreateAccessKey"));
+        assert!(!action_matches("iam:Get*", "iam:PassRole"));
+        assert!(is_read_only("s3:ListBucket"));
+        assert!(!is_read_only("s3:PutObject"));
+    }
+}
@@ -1,7 +1,13 @@
 // Zero-IAM security validation module
 
+pub mod at_rest;
+mod iam;
 mod sandbox;
 mod validator;
 
+pub use iam::{
+    lint_iam_policies, IAM_PRIVILEGE_ESCALATION, IAM_PUBLIC_POLICY, IAM_WILDCARD_ACTION,
+    IAM_WILDCARD_RESOURCE,
+};
 pub use sandbox::{SandboxLimits, SandboxViolation};
 pub use validator::SecurityValidator;
@@ -1,4 +1,6 @@
+use super::iam::lint_iam_policies;
 use super::sandbox::{SandboxLimits, SandboxViolation};
+use crate::engines::shared::models::{Detection, ResourceChange};
 use crate::errors::{CostPilotError, ErrorCategory};
 use regex::Regex;
 use std::path::Path;
@@ -152,6 +154,12 @@ impl SecurityValidator {
         })
     }
 
+    /// Lint IAM, bucket, queue and trust policies of planned resources for
+    /// wildcards, privilege escalation and public access
+    pub fn validate_iam_policies(&self, changes: &[ResourceChange]) -> Vec<Detection> {
+        lint_iam_policies(changes)
+    }
+
     /// Get sandbox limits
     pub fn limits(&self) -> &SandboxLimits {
         &self.limits
@@ -28,8 +28,18 @@ impl BaselinesValidator {
             }
         };
 
+        Self::validate_str(path, &content)
+    }
+
+    /// Validate baselines content, reporting problems against `path`
+    pub fn validate_str(
+        path: impl AsRef<Path>,
+        content: &str,
+    ) -> ValidationResult<ValidationReport> {
+        let mut report = ValidationReport::new(path, FileType::Baselines);
+
         // Parse JSON
-        let baselines: BaselinesConfig = match serde_json::from_str(&content) {
+        let baselines: BaselinesConfig = match serde_json::from_str(content) {
             Ok(b) => b,
             Err(e) => {
                 report.add_error(ValidationError::from(e));
@@ -45,7 +55,10 @@ impl BaselinesValidator {
 
     fn validate_baselines(baselines: &BaselinesConfig, report: &mut ValidationReport) {
         // Check if baselines are empty
-        if baselines.modules.is_empty() && baselines.services.is_empty() {
+        if baselines.global.is_none()
+            && baselines.modules.is_empty()
+            && baselines.services.is_empty()
+        {
             report.add_warning(
                 ValidationWarning::new("Baselines file is empty")
                     .with_warning_code("W300")
@@ -155,6 +168,17 @@ impl BaselinesValidator {
             }
         }
 
+        if let Some(tolerance) = &baselines.tolerance {
+            for problem in tolerance.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("tolerance")
+                        .with_error_code("E304")
+                        .with_hint("See the tolerance section in docs/BASELINES_SYSTEM.md"),
+                );
+            }
+        }
+
         // Check for duplicate module paths (shouldn't happen with HashMap, but validate anyway)
         let module_count = baselines.modules.len();
         if module_count > 0 {
@@ -0,0 +1,378 @@
+// Config bundle - costpilot.yaml, policies, baselines and SLOs in one file
+//
+// A `costpilot.bundle.yaml` carries a standard configuration to many
+// repositories as a single document. YAML anchors and `<<` merge keys share
+// settings between sections; top-level `x-*` keys hold anchor templates and
+// are otherwise ignored. Each section is validated by the validator of the
+// file it replaces, and written out as that file for the commands that read
+// it.
+
+use crate::engines::slo::slo_types::{Slo, SloConfig};
+use crate::validation::error::{ValidationError, ValidationResult, ValidationWarning};
+use crate::validation::{
+    BaselinesValidator, ConfigValidator, FileType, PolicyValidator, SloValidator, ValidationReport,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Bundle file looked up in the project root
+pub const BUNDLE_FILE: &str = "costpilot.bundle.yaml";
+
+/// Directory the bundle's sections are written to
+pub const BUNDLE_DIR: &str = ".costpilot/bundle";
+
+/// Policy used by `scan` when no `--policy` is given
+pub const DEFAULT_POLICY: &str = "default";
+
+const SECTIONS: [&str; 5] = ["version", "config", "policies", "baselines", "slos"];
+
+/// Sections of a config bundle, with anchors and merge keys resolved
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigBundle {
+    /// costpilot.yaml
+    pub config: Option<serde_yaml::Value>,
+
+    /// Policy files by name
+    pub policies: BTreeMap<String, serde_yaml::Value>,
+
+    /// baselines.json
+    pub baselines: Option<serde_yaml::Value>,
+
+    /// SLO definitions, in the `.costpilot/slo.json` format
+    pub slos: Option<serde_yaml::Value>,
+}
+
+/// Files written from a bundle
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BundlePaths {
+    pub config: Option<PathBuf>,
+    pub policies: BTreeMap<String, PathBuf>,
+    pub baselines: Option<PathBuf>,
+    pub slos: Option<PathBuf>,
+}
+
+impl BundlePaths {
+    /// Policy `scan` applies by default: the one named `default`, or the
+    /// only one in the bundle
+    pub fn default_policy(&self) -> Option<&Path> {
+        match self.policies.get(DEFAULT_POLICY) {
+            Some(path) => Some(path),
+            None if self.policies.len() == 1 => self.policies.values().next().map(PathBuf::as_path),
+            None => None,
+        }
+    }
+}
+
+impl ConfigBundle {
+    /// Parse bundle content, resolving anchors and merge keys
+    pub fn from_yaml(content: &str) -> ValidationResult<Self> {
+        let mut root: serde_yaml::Value =
+            serde_yaml::from_str(content).map_err(|e| Box::new(ValidationError::from(e)))?;
+        root.apply_merge()
+            .map_err(|e| Box::new(ValidationError::from(e)))?;
+        let serde_yaml::Value::Mapping(mut root) = root else {
+            return Err(Box::new(
+                ValidationError::new("Bundle must be a mapping of sections")
+                    .with_error_code("E600"),
+            ));
+        };
+
+        for key in root.keys() {
+            let key = key.as_str().unwrap_or_default();
+            if !SECTIONS.contains(&key) && !key.starts_with("x-") {
+                return Err(Box::new(
+                    ValidationError::new(format!("Unknown bundle section '{}'", key))
+                        .with_field(key)
+                        .with_error_code("E600")
+                        .with_hint(
+                            "Sections are config, policies, baselines and slos; \
+                             prefix anchor templates with x-",
+                        ),
+                ));
+            }
+        }
+
+        let mut policies = BTreeMap::new();
+        if let Some(section) = root.remove("policies") {
+            let serde_yaml::Value::Mapping(section) = section else {
+                return Err(Box::new(
+                    ValidationError::new("policies must map policy names to policies")
+                        .with_field("policies")
+                        .with_error_code("E601"),
+                ));
+            };
+            for (name, policy) in section {
+                let name = name.as_str().unwrap_or_default().to_string();
+                if name.is_empty()
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                {
+                    return Err(Box::new(
+                        ValidationError::new(format!("Invalid policy name '{}'", name))
+                            .with_field("policies")
+                            .with_error_code("E601")
+                            .with_hint("Policy names use letters, digits, '-' and '_'"),
+                    ));
+                }
+                policies.insert(name, policy);
+            }
+        }
+
+        Ok(Self {
+            config: root.remove("config"),
+            policies,
+            baselines: root.remove("baselines"),
+            slos: root.remove("slos"),
+        })
+    }
+
+    /// Read and parse a bundle file
+    pub fn load(path: &Path) -> ValidationResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            Box::new(
+                ValidationError::new(format!("Failed to read {}: {}", path.display(), e))
+                    .with_error_code("E600"),
+            )
+        })?;
+        Self::from_yaml(&content)
+    }
+
+    /// Write each section as the file it replaces; files already holding
+    /// the same content are left untouched
+    pub fn write_to(&self, dir: &Path) -> std::io::Result<BundlePaths> {
+        let mut paths = BundlePaths::default();
+        if let Some(config) = &self.config {
+            paths.config = Some(write_if_changed(dir, "costpilot.yaml", &to_yaml(config))?);
+        }
+        for (name, policy) in &self.policies {
+            let file = format!("policies/{}.yaml", name);
+            paths.policies.insert(
+                name.clone(),
+                write_if_changed(dir, &file, &to_yaml(policy))?,
+            );
+        }
+        if let Some(baselines) = &self.baselines {
+            paths.baselines = Some(write_if_changed(
+                dir,
+                "baselines.json",
+                &to_json(baselines),
+            )?);
+        }
+        if let Some(slos) = &self.slos {
+            paths.slos = Some(write_if_changed(dir, "slo.json", &to_json(slos))?);
+        }
+        Ok(paths)
+    }
+}
+
+fn to_yaml(value: &serde_yaml::Value) -> String {
+    serde_yaml::to_string(value).unwrap_or_default()
+}
+
+fn to_json(value: &serde_yaml::Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn write_if_changed(dir: &Path, file: &str, content: &str) -> std::io::Result<PathBuf> {
+    let path = dir.join(file);
+    if std::fs::read_to_string(&path).ok().as_deref() != Some(content) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content)?;
+    }
+    Ok(path)
+}
+
+pub struct BundleValidator;
+
+impl BundleValidator {
+    /// Validate a bundle file
+    pub fn validate_file(path: impl AsRef<Path>) -> ValidationResult<ValidationReport> {
+        let path = path.as_ref();
+        let mut report = ValidationReport::new(path, FileType::Bundle);
+
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) => {
+                report.add_error(
+                    ValidationError::new(format!("Failed to read file: {}", e))
+                        .with_error_code("E600")
+                        .with_hint("Ensure the file exists and is readable"),
+                );
+                return Ok(report);
+            }
+        };
+
+        Self::validate_str(path, &content)
+    }
+
+    /// Validate bundle content, running each section through the validator
+    /// of the file it replaces
+    pub fn validate_str(
+        path: impl AsRef<Path>,
+        content: &str,
+    ) -> ValidationResult<ValidationReport> {
+        let path = path.as_ref();
+        let mut report = ValidationReport::new(path, FileType::Bundle);
+
+        let bundle = match ConfigBundle::from_yaml(content) {
+            Ok(bundle) => bundle,
+            Err(e) => {
+                report.add_error(*e);
+                return Ok(report);
+            }
+        };
+
+        if let Some(config) = &bundle.config {
+            let section = ConfigValidator::validate_str(path, &to_yaml(config))?;
+            merge_section(&mut report, "config", section);
+        }
+        for (name, policy) in &bundle.policies {
+            let section = PolicyValidator::validate_str(path, &to_yaml(policy))?;
+            merge_section(&mut report, &format!("policies.{}", name), section);
+        }
+        if let Some(baselines) = &bundle.baselines {
+            let section = BaselinesValidator::validate_str(path, &to_json(baselines))?;
+            merge_section(&mut report, "baselines", section);
+        }
+        if let Some(slos) = &bundle.slos {
+            match serde_yaml::from_value::<SloConfig>(slos.clone()) {
+                Ok(config) => {
+                    let by_id: HashMap<String, Slo> = config
+                        .slos
+                        .into_iter()
+                        .map(|slo| (slo.id.clone(), slo))
+                        .collect();
+                    let content = serde_yaml::to_string(&by_id).unwrap_or_default();
+                    let section = SloValidator::validate_str(path, &content)?;
+                    merge_section(&mut report, "slos", section);
+                }
+                Err(e) => report.add_error(
+                    ValidationError::new(format!("Invalid slos section: {}", e))
+                        .with_field("slos")
+                        .with_error_code("E602")
+                        .with_hint(
+                            "slos uses the .costpilot/slo.json format: version and a list of slos",
+                        ),
+                ),
+            }
+        }
+
+        if bundle == ConfigBundle::default() {
+            report.add_warning(
+                ValidationWarning::new("Bundle has no sections")
+                    .with_suggestion("Add config, policies, baselines or slos"),
+            );
+        }
+
+        Ok(report)
+    }
+}
+
+/// Add a section's findings under the section's name. Positions refer to
+/// the section as rendered on its own, so they are dropped.
+fn merge_section(report: &mut ValidationReport, prefix: &str, section: ValidationReport) {
+    let qualify = |field: Option<String>| match field {
+        Some(field) => format!("{}.{}", prefix, field),
+        None => prefix.to_string(),
+    };
+    for mut error in section.errors {
+        error.field = Some(qualify(error.field.take()));
+        error.line = None;
+        error.column = None;
+        error.snippet = None;
+        report.add_error(error);
+    }
+    for mut warning in section.warnings {
+        warning.field = Some(qualify(warning.field.take()));
+        report.add_warning(warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLE: &str = r#"
+x-owner: &owner
+  owner: platform-team
+config:
+  version: "1.0.0"
+  free_tier:
+    enabled: false
+policies:
+  default:
+    metadata:
+      <<: *owner
+      version: "1.0.0"
+    rules: []
+baselines:
+  version: "1.0"
+  global:
+    name: global
+    expected_monthly_cost: 1000.0
+    acceptable_variance_percent: 10.0
+    last_updated: "2024-01-01T00:00:00Z"
+    justification: Budget
+    owner: platform-team
+  modules: {}
+"#;
+
+    #[test]
+    fn test_bundle_resolves_anchors_and_writes_sections() {
+        let bundle = ConfigBundle::from_yaml(BUNDLE).unwrap();
+        assert_eq!(
+            bundle.policies["default"]["metadata"]["owner"],
+            serde_yaml::Value::from("platform-team")
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let paths = bundle.write_to(dir.path()).unwrap();
+        assert_eq!(
+            paths.default_policy(),
+            Some(dir.path().join("policies/default.yaml").as_path())
+        );
+        let config = std::fs::read_to_string(paths.config.unwrap()).unwrap();
+        assert!(config.contains("enabled: false"));
+        let baselines: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(paths.baselines.unwrap()).unwrap())
+                .unwrap();
+        assert_eq!(baselines["global"]["expected_monthly_cost"], 1000.0);
+        assert!(paths.slos.is_none());
+    }
+
+    #[test]
+    fn test_bundle_rejects_unknown_sections_and_bad_policy_names() {
+        let err = ConfigBundle::from_yaml("polices: {}\n").unwrap_err();
+        assert!(err.message.contains("Unknown bundle section 'polices'"));
+
+        let err = ConfigBundle::from_yaml("policies:\n  ../x: {}\n").unwrap_err();
+        assert_eq!(err.error_code.as_deref(), Some("E601"));
+    }
+
+    #[test]
+    fn test_validator_delegates_and_qualifies_fields() {
+        let content =
+            "config:\n  version: \"1.0.0\"\n  free_tier:\n    enabled: maybe\nslos:\n  slos: 3\n";
+        let report = BundleValidator::validate_str(BUNDLE_FILE, content).unwrap();
+
+        assert!(!report.is_valid);
+        assert!(report
+            .errors
+            .iter()
+            .any(|e| e.field.as_deref().is_some_and(|f| f.starts_with("config"))));
+        assert!(report
+            .errors
+            .iter()
+            .any(|e| e.error_code.as_deref() == Some("E602")));
+
+        let report = BundleValidator::validate_str(BUNDLE_FILE, BUNDLE).unwrap();
+        assert!(report.errors.iter().all(|e| !e
+            .field
+            .as_deref()
+            .unwrap_or_default()
+            .starts_with("config")));
+    }
+}
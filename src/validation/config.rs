@@ -2,8 +2,18 @@
 //
 // Validates the main CostPilot configuration file against the expected schema.
 
+use crate::engines::attribution::tagging::TaggingConfig;
+use crate::engines::detection::DetectionConfig;
+use crate::engines::grouping::EnvironmentMapping;
+use crate::engines::performance::AdaptiveBudgetConfig;
+use crate::engines::policy::{PolicyApprovals, PolicySources};
+use crate::engines::prediction::{ClusterPricing, FreeTierConfig};
+use crate::engines::shared::redaction::RedactionConfig;
+use crate::feature_flags::FeatureFlagsConfig;
+use crate::security::at_rest::StorageConfig;
 use crate::validation::error::{ValidationError, ValidationResult, ValidationWarning};
-use crate::validation::{FileType, ValidationReport};
+use crate::validation::{FileType, SourceMap, ValidationReport};
+use crate::zero_cost_guard::ZeroCostConfig;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -33,6 +43,46 @@ pub struct CostPilotConfig {
 
     #[serde(default)]
     pub integrations: Option<IntegrationsConfig>,
+
+    #[serde(default)]
+    pub performance: Option<PerformanceConfig>,
+
+    #[serde(default)]
+    pub detection: Option<DetectionConfig>,
+
+    #[serde(default)]
+    pub kubernetes: Option<ClusterPricing>,
+
+    #[serde(default)]
+    pub zero_cost: Option<ZeroCostConfig>,
+
+    #[serde(default)]
+    pub tagging: Option<TaggingConfig>,
+
+    #[serde(default)]
+    pub environment_mapping: Option<EnvironmentMapping>,
+
+    #[serde(default)]
+    pub free_tier: Option<FreeTierConfig>,
+
+    #[serde(default)]
+    pub policy_sources: Option<PolicySources>,
+
+    #[serde(default)]
+    pub policy_approvals: Option<PolicyApprovals>,
+
+    #[serde(default)]
+    pub feature_flags: Option<FeatureFlagsConfig>,
+
+    #[serde(default)]
+    pub storage: Option<StorageConfig>,
+
+    #[serde(default)]
+    pub redaction: Option<RedactionConfig>,
+
+    /// Language of upgrade messages, validator output and report headings
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +95,10 @@ pub struct ScanConfig {
 
     #[serde(default)]
     pub explain: Option<bool>,
+
+    /// Parser used when `costpilot scan` is run without `--infra-format`
+    #[serde(default)]
+    pub infra_format: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +159,12 @@ pub struct GithubIntegration {
     pub comment_on_pr: Option<bool>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceConfig {
+    #[serde(default)]
+    pub adaptive_budgets: Option<AdaptiveBudgetConfig>,
+}
+
 pub struct ConfigValidator;
 
 impl ConfigValidator {
@@ -126,21 +186,96 @@ impl ConfigValidator {
             }
         };
 
-        // Parse YAML
-        let config: CostPilotConfig = match serde_yaml::from_str(&content) {
-            Ok(c) => c,
+        Self::validate_str(path, &content)
+    }
+
+    /// Validate configuration content, reporting problems against `path`
+    pub fn validate_str(
+        path: impl AsRef<Path>,
+        content: &str,
+    ) -> ValidationResult<ValidationReport> {
+        let mut report = ValidationReport::new(path, FileType::Config);
+
+        // Parse YAML, keeping field positions for diagnostics
+        let source_map = SourceMap::new(content);
+        let root: serde_yaml::Value = match serde_yaml::from_str(content) {
+            Ok(root) => root,
             Err(e) => {
-                report.add_error(ValidationError::from(e));
+                report.add_error(source_map.annotate(ValidationError::from(e)));
                 return Ok(report);
             }
         };
 
-        // Validate configuration
-        Self::validate_config(&config, &mut report);
+        // Validate the base configuration, then each profile overlaid on it
+        Self::validate_profile(&root, None, &mut report);
+        for profile in crate::config::profile_names(&root) {
+            Self::validate_profile(&root, Some(&profile), &mut report);
+        }
+        source_map.annotate_report(&mut report);
 
         Ok(report)
     }
 
+    /// Validate the configuration as seen with `profile` selected
+    ///
+    /// Problems already reported for the base configuration are not repeated;
+    /// new ones are reported under `profiles.<name>`.
+    fn validate_profile(
+        root: &serde_yaml::Value,
+        profile: Option<&str>,
+        report: &mut ValidationReport,
+    ) {
+        let mut scratch = ValidationReport::new(&report.file_path, FileType::Config);
+        match crate::config::apply_profile(root.clone(), profile) {
+            Ok(merged) => match serde_yaml::from_value::<CostPilotConfig>(merged) {
+                Ok(config) => Self::validate_config(&config, &mut scratch),
+                Err(e) => scratch.add_error(ValidationError::from(e)),
+            },
+            Err(e) => {
+                scratch.add_error(ValidationError::new(e.to_string()).with_error_code("E105"))
+            }
+        }
+
+        let Some(profile) = profile else {
+            for error in scratch.errors {
+                report.add_error(error);
+            }
+            for warning in scratch.warnings {
+                report.add_warning(warning);
+            }
+            return;
+        };
+
+        let prefixed = |field: Option<String>| {
+            Some(match field {
+                Some(field) => format!("profiles.{}.{}", profile, field),
+                None => format!("profiles.{}", profile),
+            })
+        };
+        for mut error in scratch.errors {
+            if report
+                .errors
+                .iter()
+                .any(|e| e.message == error.message && e.field == error.field)
+            {
+                continue;
+            }
+            error.field = prefixed(error.field);
+            report.add_error(error);
+        }
+        for mut warning in scratch.warnings {
+            if report
+                .warnings
+                .iter()
+                .any(|w| w.message == warning.message && w.field == warning.field)
+            {
+                continue;
+            }
+            warning.field = prefixed(warning.field);
+            report.add_warning(warning);
+        }
+    }
+
     /// Validate configuration structure and values
     fn validate_config(config: &CostPilotConfig, report: &mut ValidationReport) {
         // Version check
@@ -190,6 +325,23 @@ impl ConfigValidator {
             }
         }
 
+        // Default infra format validation
+        if let Some(infra_format) = config.scan.as_ref().and_then(|s| s.infra_format.as_ref()) {
+            if !matches!(
+                infra_format.as_str(),
+                "terraform" | "kubernetes" | "sam" | "serverless"
+            ) {
+                report.add_error(
+                    ValidationError::new(format!("Invalid infra format: {}", infra_format))
+                        .with_field("scan.infra_format")
+                        .with_error_code("E109")
+                        .with_hint(
+                            "Supported formats: 'terraform', 'kubernetes', 'sam', 'serverless'",
+                        ),
+                );
+            }
+        }
+
         // Heuristics cache TTL validation
         if let Some(heuristics) = &config.heuristics {
             if let Some(cache_ttl) = &heuristics.cache_ttl {
@@ -217,6 +369,145 @@ impl ConfigValidator {
                 }
             }
         }
+
+        // Adaptive budget floor/ceiling validation
+        if let Some(adaptive) = config
+            .performance
+            .as_ref()
+            .and_then(|p| p.adaptive_budgets.as_ref())
+        {
+            for problem in adaptive.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("performance.adaptive_budgets")
+                        .with_error_code("E104")
+                        .with_hint("Floors must not exceed ceilings; baselines must be positive"),
+                );
+            }
+        }
+
+        // Custom detection rule validation
+        if let Some(detection) = &config.detection {
+            for problem in detection.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("detection.custom_rules")
+                        .with_error_code("E107")
+                        .with_hint(
+                            "Rules need a unique id, resource_type and message; conditions need \
+                             exists, or operator and value",
+                        ),
+                );
+            }
+        }
+
+        // Kubernetes cluster pricing validation
+        if let Some(kubernetes) = &config.kubernetes {
+            for problem in kubernetes.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("kubernetes")
+                        .with_error_code("E108")
+                        .with_hint(
+                            "Node pools need a unique name and positive vcpu and memory_gib; \
+                             target_utilization must be in (0, 1]",
+                        ),
+                );
+            }
+        }
+
+        // Zero-cost guard environment rules validation
+        if let Some(zero_cost) = &config.zero_cost {
+            for problem in zero_cost.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("zero_cost.environments")
+                        .with_error_code("E110")
+                        .with_hint(
+                            "List entries need a resource type, a pattern cannot be both denied \
+                             and allowed, and max_monthly_cost must not be negative",
+                        ),
+                );
+            }
+        }
+
+        // Tagging rules validation
+        if let Some(tagging) = &config.tagging {
+            for problem in tagging.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("tagging.rules")
+                        .with_error_code("E111")
+                        .with_hint(
+                            "Rules need a resource_type and at least one of required, \
+                             allowed_values or formats; formats must be valid regular expressions",
+                        ),
+                );
+            }
+        }
+
+        // Environment mapping validation
+        if let Some(mapping) = &config.environment_mapping {
+            for problem in mapping.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("environment_mapping")
+                        .with_error_code("E112")
+                        .with_hint(
+                            "Precedence lists tag, workspace, directory and address at most once; \
+                             rules need a pattern and an environment",
+                        ),
+                );
+            }
+        }
+
+        // Policy sources validation
+        if let Some(sources) = &config.policy_sources {
+            for problem in sources.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("policy_sources")
+                        .with_error_code("E113")
+                        .with_hint("Each source needs a path; paths and pack names must be unique"),
+                );
+            }
+        }
+
+        // Policy approver keyring validation
+        if let Some(approvals) = &config.policy_approvals {
+            for problem in approvals.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("policy_approvals")
+                        .with_error_code("E116")
+                        .with_hint("Map each approver to a public key file; reviews need at least 1 approval"),
+                );
+            }
+        }
+
+        // Feature flags validation
+        if let Some(flags) = &config.feature_flags {
+            for problem in flags.validate() {
+                report.add_error(
+                    ValidationError::new(problem)
+                        .with_field("feature_flags")
+                        .with_error_code("E115")
+                        .with_hint("Rollouts are fractions between 0.0 and 1.0"),
+                );
+            }
+        }
+
+        // Locale validation
+        if let Some(locale) = &config.locale {
+            if crate::i18n::Locale::parse(locale).is_none() {
+                report.add_error(
+                    ValidationError::new(format!("Unsupported locale '{}'", locale))
+                        .with_field("locale")
+                        .with_error_code("E114")
+                        .with_hint(format!("Use one of: {}", crate::i18n::supported_codes())),
+                );
+            }
+        }
     }
 
     fn is_valid_semver(version: &str) -> bool {
@@ -303,4 +594,59 @@ output:
         assert_eq!(config.version, Some("1.0.0".to_string()));
         assert_eq!(config.default_region, Some("us-east-1".to_string()));
     }
+
+    #[test]
+    fn test_profiles_validated_against_merged_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("costpilot.yaml");
+        std::fs::write(
+            &path,
+            r#"
+version: "1.0.0"
+output:
+  format: text
+profiles:
+  prod:
+    output:
+      format: json
+  dev:
+    output:
+      format: html
+"#,
+        )
+        .unwrap();
+
+        let report = ConfigValidator::validate_file(&path).unwrap();
+        assert!(!report.is_valid);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(
+            report.errors[0].field.as_deref(),
+            Some("profiles.dev.output.format")
+        );
+        assert_eq!(report.errors[0].line, Some(11));
+        assert_eq!(report.errors[0].column, Some(7));
+        assert_eq!(
+            report.errors[0].snippet.as_deref(),
+            Some("      format: html")
+        );
+    }
+
+    #[test]
+    fn test_adaptive_budget_limits_validated() {
+        let yaml = r#"
+version: "1.0.0"
+performance:
+  adaptive_budgets:
+    engines:
+      mapping:
+        min_memory_mb: 512
+        max_memory_mb: 128
+"#;
+        let config: CostPilotConfig = serde_yaml::from_str(yaml).unwrap();
+        let mut report = ValidationReport::new(Path::new("costpilot.yaml"), FileType::Config);
+        ConfigValidator::validate_config(&config, &mut report);
+
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].error_code.as_deref(), Some("E104"));
+    }
 }
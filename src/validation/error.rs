@@ -1,5 +1,6 @@
 // Validation errors and result types
 
+use crate::i18n::{t, tf};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -13,6 +14,9 @@ pub struct ValidationError {
     pub field: Option<String>,
     pub line: Option<usize>,
     pub column: Option<usize>,
+    /// Source line the error points at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
     pub hint: Option<String>,
     pub error_code: Option<String>,
 }
@@ -24,6 +28,7 @@ impl ValidationError {
             field: None,
             line: None,
             column: None,
+            snippet: None,
             hint: None,
             error_code: None,
         }
@@ -73,22 +78,41 @@ impl ValidationError {
 
         // Location
         if let Some(field) = &self.field {
-            output.push_str(&format!("     Field: {}\n", field.yellow()));
+            output.push_str(&format!(
+                "     {}: {}\n",
+                t("validation.field"),
+                field.yellow()
+            ));
         }
         if let Some(line) = self.line {
-            if let Some(column) = self.column {
-                output.push_str(&format!(
-                    "     Location: line {}, column {}\n",
-                    line, column
-                ));
-            } else {
-                output.push_str(&format!("     Location: line {}\n", line));
+            let line_text = line.to_string();
+            let location = match self.column {
+                Some(column) => tf(
+                    "validation.location_column",
+                    &[("line", &line_text), ("column", &column.to_string())],
+                ),
+                None => tf("validation.location", &[("line", &line_text)]),
+            };
+            output.push_str(&format!("     {}\n", location));
+            if let Some(snippet) = &self.snippet {
+                output.push_str(&format!("     {:>4} | {}\n", line, snippet));
+                if let Some(column) = self.column {
+                    output.push_str(&format!(
+                        "          | {}{}\n",
+                        " ".repeat(column.saturating_sub(1)),
+                        "^".red().bold()
+                    ));
+                }
             }
         }
 
         // Hint
         if let Some(hint) = &self.hint {
-            output.push_str(&format!("     💡 Hint: {}\n", hint.cyan()));
+            output.push_str(&format!(
+                "     💡 {}: {}\n",
+                t("validation.hint"),
+                hint.cyan()
+            ));
         }
 
         output
@@ -188,12 +212,20 @@ impl ValidationWarning {
 
         // Field
         if let Some(field) = &self.field {
-            output.push_str(&format!("     Field: {}\n", field.yellow()));
+            output.push_str(&format!(
+                "     {}: {}\n",
+                t("validation.field"),
+                field.yellow()
+            ));
         }
 
         // Suggestion
         if let Some(suggestion) = &self.suggestion {
-            output.push_str(&format!("     💡 Suggestion: {}\n", suggestion.cyan()));
+            output.push_str(&format!(
+                "     💡 {}: {}\n",
+                t("validation.suggestion"),
+                suggestion.cyan()
+            ));
         }
 
         output
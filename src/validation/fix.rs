@@ -0,0 +1,386 @@
+// Safe auto-fixes for costpilot.yaml
+//
+// `costpilot validate --fix` rewrites a config file in canonical form: required
+// fields get their defaults, deprecated key spellings are renamed and sections
+// are ordered like the schema. Fixes never change the meaning of a valid
+// config, and the original file is kept next to it as `<file>.bak`.
+
+use crate::validation::error::{ValidationError, ValidationResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+/// Version written when the `version` field is missing
+pub const DEFAULT_CONFIG_VERSION: &str = "1.0.0";
+
+/// Deprecated key paths and their current names, relative to the config root
+const RENAMED_KEYS: &[(&str, &str)] = &[
+    ("region", "default_region"),
+    ("output.colour", "output.color"),
+    ("policies.default_policy", "policies.default"),
+    ("heuristics.ttl", "heuristics.cache_ttl"),
+];
+
+/// Canonical key order per section, following the schema field order
+const CANONICAL_ORDER: &[(&str, &[&str])] = &[
+    (
+        "",
+        &[
+            "version",
+            "default_region",
+            "scan",
+            "policies",
+            "output",
+            "heuristics",
+            "slo",
+            "integrations",
+            "performance",
+            "detection",
+            "kubernetes",
+            "zero_cost",
+            "tagging",
+            "environment_mapping",
+            "free_tier",
+            "policy_sources",
+            "policy_approvals",
+            "feature_flags",
+            "storage",
+            "redaction",
+            "locale",
+            "profiles",
+        ],
+    ),
+    (
+        "scan",
+        &[
+            "infra_format",
+            "fail_on_critical",
+            "show_autofix",
+            "explain",
+        ],
+    ),
+    ("policies", &["default", "exemptions", "directory"]),
+    ("output", &["format", "verbose", "color"]),
+    ("heuristics", &["auto_update", "cache_ttl", "file"]),
+    ("slo", &["config", "snapshots_dir"]),
+    ("integrations", &["github"]),
+    ("integrations.github", &["enabled", "comment_on_pr"]),
+    (
+        "kubernetes",
+        &[
+            "node_pools",
+            "daemonset_nodes",
+            "target_utilization",
+            "default_cpu_request",
+            "default_memory_request_gib",
+        ],
+    ),
+    ("zero_cost", &["default_environment", "environments"]),
+    ("tagging", &["enforcement", "rules"]),
+    (
+        "environment_mapping",
+        &[
+            "precedence",
+            "tag_keys",
+            "workspaces",
+            "directories",
+            "min_confidence",
+        ],
+    ),
+    ("free_tier", &["enabled"]),
+    ("storage", &["encrypt_at_rest"]),
+    (
+        "redaction",
+        &["sensitive_tags", "identifier_attributes", "salt"],
+    ),
+];
+
+/// A change made by the fixer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppliedFix {
+    pub field: String,
+    pub description: String,
+}
+
+/// Fixed file content and the changes that produced it
+#[derive(Debug, Clone)]
+pub struct FixOutcome {
+    pub content: String,
+    pub fixes: Vec<AppliedFix>,
+}
+
+impl FixOutcome {
+    pub fn is_changed(&self) -> bool {
+        !self.fixes.is_empty()
+    }
+}
+
+pub struct ConfigFixer;
+
+impl ConfigFixer {
+    /// Compute fixes for config `content` without touching the filesystem
+    pub fn fix_str(content: &str) -> ValidationResult<FixOutcome> {
+        let mut root: Value = serde_yaml::from_str(content).map_err(ValidationError::from)?;
+        let Value::Mapping(map) = &mut root else {
+            return Err(Box::new(
+                ValidationError::new("Config root must be a mapping")
+                    .with_error_code("E106")
+                    .with_hint("Automatic fixes need a YAML mapping at the top level"),
+            ));
+        };
+
+        let mut fixes = Vec::new();
+        Self::fix_section(map, "", &mut fixes);
+        if !map.contains_key("version") {
+            map.insert("version".into(), DEFAULT_CONFIG_VERSION.into());
+            fixes.push(AppliedFix {
+                field: "version".to_string(),
+                description: format!("Added missing version \"{}\"", DEFAULT_CONFIG_VERSION),
+            });
+        }
+
+        if let Some(Value::Mapping(profiles)) = map.get_mut(crate::config::PROFILES_KEY) {
+            for (name, profile) in profiles.iter_mut() {
+                if let (Some(name), Value::Mapping(profile)) = (name.as_str(), profile) {
+                    let prefix = format!("{}.{}", crate::config::PROFILES_KEY, name);
+                    Self::fix_section(profile, &prefix, &mut fixes);
+                }
+            }
+        }
+
+        // Reorder last so renamed and added keys land in canonical position
+        let before = serde_yaml::to_string(&root).unwrap_or_default();
+        let Value::Mapping(map) = &mut root else {
+            unreachable!("root checked above");
+        };
+        Self::reorder(map, "");
+        if let Some(Value::Mapping(profiles)) = map.get_mut(crate::config::PROFILES_KEY) {
+            for (_, profile) in profiles.iter_mut() {
+                if let Value::Mapping(profile) = profile {
+                    Self::reorder(profile, "");
+                }
+            }
+        }
+        let content = serde_yaml::to_string(&root).map_err(ValidationError::from)?;
+        if content != before {
+            fixes.push(AppliedFix {
+                field: String::new(),
+                description: "Reordered keys canonically".to_string(),
+            });
+        }
+
+        Ok(FixOutcome { content, fixes })
+    }
+
+    /// Fix `path` in place, writing the original to `<path>.bak` first
+    ///
+    /// The file is left untouched when there is nothing to fix.
+    pub fn fix_file(path: impl AsRef<Path>) -> ValidationResult<Vec<AppliedFix>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(ValidationError::from)?;
+        let outcome = Self::fix_str(&content)?;
+        if !outcome.is_changed() {
+            return Ok(Vec::new());
+        }
+
+        std::fs::write(backup_path(path), &content).map_err(ValidationError::from)?;
+        std::fs::write(path, &outcome.content).map_err(ValidationError::from)?;
+        Ok(outcome.fixes)
+    }
+
+    /// Rename deprecated keys and fill required defaults in one config layer
+    ///
+    /// `prefix` is only used for reporting; renames are looked up relative to
+    /// the layer so profiles get the same treatment as the base config.
+    fn fix_section(map: &mut Mapping, prefix: &str, fixes: &mut Vec<AppliedFix>) {
+        let report_path = |path: &str| {
+            if prefix.is_empty() {
+                path.to_string()
+            } else {
+                format!("{}.{}", prefix, path)
+            }
+        };
+
+        Self::rename_keys(map, "", &report_path, fixes);
+
+        if let Some(Value::Mapping(output)) = map.get_mut("output") {
+            if let Some(Value::String(format)) = output.get_mut("format") {
+                let normalized = match format.to_lowercase().as_str() {
+                    "md" => "markdown".to_string(),
+                    other => other.to_string(),
+                };
+                if *format != normalized
+                    && matches!(normalized.as_str(), "json" | "text" | "markdown")
+                {
+                    fixes.push(AppliedFix {
+                        field: report_path("output.format"),
+                        description: format!("Normalized \"{}\" to \"{}\"", format, normalized),
+                    });
+                    *format = normalized;
+                }
+            }
+        }
+
+        if let Some(Value::Mapping(integrations)) = map.get_mut("integrations") {
+            if let Some(Value::Mapping(github)) = integrations.get_mut("github") {
+                if !github.contains_key("enabled") {
+                    github.insert("enabled".into(), false.into());
+                    fixes.push(AppliedFix {
+                        field: report_path("integrations.github.enabled"),
+                        description: "Added missing required field (false)".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Rename kebab-case and deprecated keys, recursing into sub-mappings
+    fn rename_keys(
+        map: &mut Mapping,
+        path: &str,
+        report_path: &dyn Fn(&str) -> String,
+        fixes: &mut Vec<AppliedFix>,
+    ) {
+        let keys: Vec<String> = map
+            .keys()
+            .filter_map(|k| k.as_str().map(str::to_string))
+            .collect();
+
+        for key in keys {
+            let old_path = join(path, &key);
+            let snake = key.replace('-', "_");
+            let new_key = RENAMED_KEYS
+                .iter()
+                .find(|(old, _)| *old == join(path, &snake))
+                .map(|(_, new)| new.rsplit('.').next().unwrap_or(new).to_string())
+                .unwrap_or(snake);
+
+            // Never clobber a value that is already set under the new name
+            let key = if new_key != key && !map.contains_key(new_key.as_str()) {
+                let value = map.remove(key.as_str()).unwrap_or(Value::Null);
+                map.insert(new_key.clone().into(), value);
+                fixes.push(AppliedFix {
+                    field: report_path(&old_path),
+                    description: format!("Renamed to {}", report_path(&join(path, &new_key))),
+                });
+                new_key
+            } else {
+                key
+            };
+
+            // Profiles are fixed separately as their own layer
+            if path.is_empty() && key == crate::config::PROFILES_KEY {
+                continue;
+            }
+            if let Some(Value::Mapping(child)) = map.get_mut(key.as_str()) {
+                Self::rename_keys(child, &join(path, &key), report_path, fixes);
+            }
+        }
+    }
+
+    /// Order keys as in `CANONICAL_ORDER`; unknown keys keep their order at the end
+    fn reorder(map: &mut Mapping, path: &str) {
+        if let Some((_, order)) = CANONICAL_ORDER.iter().find(|(p, _)| *p == path) {
+            let mut entries: Vec<(Value, Value)> = std::mem::take(map).into_iter().collect();
+            let rank = |key: &Value| {
+                key.as_str()
+                    .and_then(|k| order.iter().position(|o| *o == k))
+                    .unwrap_or(order.len())
+            };
+            entries.sort_by_key(|(k, _)| rank(k));
+            map.extend(entries);
+        }
+
+        for (key, value) in map.iter_mut() {
+            if let (Some(key), Value::Mapping(child)) = (key.as_str(), value) {
+                if !(path.is_empty() && key == crate::config::PROFILES_KEY) {
+                    Self::reorder(child, &join(path, key));
+                }
+            }
+        }
+    }
+}
+
+/// Where `fix_file` keeps the original content
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+fn join(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixes_deprecated_keys_and_defaults() {
+        let outcome = ConfigFixer::fix_str(
+            r#"
+output:
+  format: JSON
+  colour: true
+region: us-east-1
+integrations:
+  github:
+    comment-on-pr: true
+"#,
+        )
+        .unwrap();
+
+        let fields: Vec<&str> = outcome.fixes.iter().map(|f| f.field.as_str()).collect();
+        assert!(fields.contains(&"region"));
+        assert!(fields.contains(&"output.colour"));
+        assert!(fields.contains(&"output.format"));
+        assert!(fields.contains(&"integrations.github.comment-on-pr"));
+        assert!(fields.contains(&"integrations.github.enabled"));
+        assert!(fields.contains(&"version"));
+
+        assert_eq!(
+            outcome.content,
+            "version: 1.0.0\ndefault_region: us-east-1\noutput:\n  format: json\n  color: true\n\
+             integrations:\n  github:\n    enabled: false\n    comment_on_pr: true\n"
+        );
+    }
+
+    #[test]
+    fn test_canonical_config_is_unchanged() {
+        let content = "version: 1.0.0\ndefault_region: us-east-1\noutput:\n  format: json\n";
+        let outcome = ConfigFixer::fix_str(content).unwrap();
+        assert!(!outcome.is_changed());
+        assert_eq!(outcome.content, content);
+    }
+
+    #[test]
+    fn test_fix_file_writes_backup_and_profiles() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("costpilot.yaml");
+        let original = "profiles:\n  dev:\n    region: eu-west-1\nversion: 1.0.0\n";
+        std::fs::write(&path, original).unwrap();
+
+        let fixes = ConfigFixer::fix_file(&path).unwrap();
+        assert_eq!(fixes[0].field, "profiles.dev.region");
+        assert_eq!(
+            fixes[0].description,
+            "Renamed to profiles.dev.default_region"
+        );
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path)).unwrap(),
+            original
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "version: 1.0.0\nprofiles:\n  dev:\n    default_region: eu-west-1\n"
+        );
+
+        // A second run has nothing left to do
+        assert!(ConfigFixer::fix_file(&path).unwrap().is_empty());
+    }
+}
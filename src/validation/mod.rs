@@ -5,29 +5,37 @@
 // - policy files (YAML/JSON)
 // - baselines.json (cost baselines)
 // - slo.yaml (SLO definitions)
+// - costpilot.bundle.yaml (all of the above in one document)
 //
 // Each validator provides:
 // - Schema validation
 // - Semantic validation
 // - Helpful error messages with remediation hints
-// - File path and line number tracking for errors
+// - File path, line/column and source snippet tracking for errors
 
 pub mod baselines;
+pub mod bundle;
 pub mod config;
 pub mod error;
+pub mod fix;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod output;
 pub mod policy;
 pub mod slo;
+pub mod source_map;
 
 pub use baselines::BaselinesValidator;
+pub use bundle::{BundlePaths, BundleValidator, ConfigBundle, BUNDLE_DIR, BUNDLE_FILE};
 pub use config::ConfigValidator;
 pub use error::{ValidationError, ValidationResult, ValidationWarning};
+pub use fix::{AppliedFix, ConfigFixer};
 #[cfg(not(target_arch = "wasm32"))]
 pub use output::OutputValidator;
 pub use policy::PolicyValidator;
 pub use slo::SloValidator;
+pub use source_map::{Location, SourceMap};
 
+use crate::i18n::{t, tf};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -47,6 +55,9 @@ pub struct ValidationReport {
     pub is_valid: bool,
     pub errors: Vec<ValidationError>,
     pub warnings: Vec<ValidationWarning>,
+    /// Changes made by `--fix` before validating
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<AppliedFix>,
 }
 
 impl ValidationReport {
@@ -57,6 +68,7 @@ impl ValidationReport {
             is_valid: true,
             errors: Vec::new(),
             warnings: Vec::new(),
+            fixes: Vec::new(),
         }
     }
 
@@ -89,24 +101,72 @@ impl ValidationReport {
 
         // Header
         output.push_str(&format!(
-            "📋 Validation Report: {}\n",
+            "📋 {}: {}\n",
+            t("validation.report_title"),
             self.file_path.bright_cyan()
         ));
-        output.push_str(&format!("   Type: {:?}\n\n", self.file_type));
+        output.push_str(&format!(
+            "   {}: {:?}\n\n",
+            t("validation.type"),
+            self.file_type
+        ));
+
+        // Fixes applied by --fix
+        if !self.fixes.is_empty() {
+            output.push_str(&format!(
+                "🔧 {}\n",
+                tf(
+                    "validation.fixes_applied",
+                    &[
+                        ("count", &self.fixes.len().to_string()),
+                        (
+                            "path",
+                            &fix::backup_path(Path::new(&self.file_path))
+                                .display()
+                                .to_string()
+                        ),
+                    ]
+                )
+                .bold()
+            ));
+            for applied in &self.fixes {
+                if applied.field.is_empty() {
+                    output.push_str(&format!("  • {}\n", applied.description));
+                } else {
+                    output.push_str(&format!(
+                        "  • {}: {}\n",
+                        applied.field.yellow(),
+                        applied.description
+                    ));
+                }
+            }
+            output.push('\n');
+        }
 
         // Status
         if self.is_valid {
-            output.push_str(&format!("✅ {} Valid\n\n", "Status:".bold()));
+            output.push_str(&format!(
+                "✅ {} {}\n\n",
+                t("validation.status").bold(),
+                t("validation.valid")
+            ));
         } else {
-            output.push_str(&format!("❌ {} Invalid\n\n", "Status:".bold()));
+            output.push_str(&format!(
+                "❌ {} {}\n\n",
+                t("validation.status").bold(),
+                t("validation.invalid")
+            ));
         }
 
         // Errors
         if !self.errors.is_empty() {
             output.push_str(&format!(
-                "🔴 {} Errors ({})\n",
-                "Validation".bold(),
-                self.errors.len()
+                "🔴 {}\n",
+                tf(
+                    "validation.errors",
+                    &[("count", &self.errors.len().to_string())]
+                )
+                .bold()
             ));
             for error in &self.errors {
                 output.push_str(&format!("\n{}\n", error.format()));
@@ -117,9 +177,12 @@ impl ValidationReport {
         // Warnings
         if !self.warnings.is_empty() {
             output.push_str(&format!(
-                "🟡 {} Warnings ({})\n",
-                "Validation".bold(),
-                self.warnings.len()
+                "🟡 {}\n",
+                tf(
+                    "validation.warnings",
+                    &[("count", &self.warnings.len().to_string())]
+                )
+                .bold()
             ));
             for warning in &self.warnings {
                 output.push_str(&format!("\n{}\n", warning.format()));
@@ -129,16 +192,22 @@ impl ValidationReport {
 
         // Summary
         if self.is_valid && self.warnings.is_empty() {
-            output.push_str("✨ No issues found!\n");
+            output.push_str(&format!("✨ {}\n", t("validation.no_issues")));
         } else if self.is_valid {
             output.push_str(&format!(
-                "⚠️  File is valid but has {} warning(s). Consider addressing them.\n",
-                self.warnings.len()
+                "⚠️  {}\n",
+                tf(
+                    "validation.valid_with_warnings",
+                    &[("count", &self.warnings.len().to_string())]
+                )
             ));
         } else {
             output.push_str(&format!(
-                "❌ File has {} error(s). Fix these before using the configuration.\n",
-                self.errors.len()
+                "❌ {}\n",
+                tf(
+                    "validation.has_errors",
+                    &[("count", &self.errors.len().to_string())]
+                )
             ));
         }
 
@@ -158,6 +227,7 @@ pub enum FileType {
     Policy,
     Baselines,
     Slo,
+    Bundle,
 }
 
 /// Validate any supported configuration file
@@ -180,17 +250,54 @@ pub fn validate_file(path: impl AsRef<Path>) -> ValidationResult<ValidationRepor
         FileType::Policy => PolicyValidator::validate_file(path),
         FileType::Baselines => BaselinesValidator::validate_file(path),
         FileType::Slo => SloValidator::validate_file(path),
+        FileType::Bundle => BundleValidator::validate_file(path),
     }
 }
 
+/// Validate in-memory content as if it were read from `path`
+///
+/// Used by editor integrations to check unsaved buffers.
+pub fn validate_content(
+    path: impl AsRef<Path>,
+    content: &str,
+) -> ValidationResult<ValidationReport> {
+    let path = path.as_ref();
+    match detect_file_type(path)? {
+        FileType::Config => ConfigValidator::validate_str(path, content),
+        FileType::Policy => PolicyValidator::validate_str(path, content),
+        FileType::Baselines => BaselinesValidator::validate_str(path, content),
+        FileType::Slo => SloValidator::validate_str(path, content),
+        FileType::Bundle => BundleValidator::validate_str(path, content),
+    }
+}
+
+/// Apply safe auto-fixes to a config file, then validate it
+///
+/// Only `costpilot.yaml` files are fixed; other file types are validated
+/// unchanged. Files that cannot be parsed are left for the validator to report.
+pub fn fix_and_validate_file(path: impl AsRef<Path>) -> ValidationResult<ValidationReport> {
+    let path = path.as_ref();
+    let fixes = if path.exists() && detect_file_type(path)? == FileType::Config {
+        ConfigFixer::fix_file(path).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let mut report = validate_file(path)?;
+    report.fixes = fixes;
+    Ok(report)
+}
+
 /// Detect file type from path
-fn detect_file_type(path: &Path) -> ValidationResult<FileType> {
+pub fn detect_file_type(path: &Path) -> ValidationResult<FileType> {
     let file_name = path
         .file_name()
         .and_then(|n| n.to_str())
         .ok_or_else(|| ValidationError::new("Invalid file path"))?;
 
-    if file_name == "costpilot.yaml"
+    if file_name == BUNDLE_FILE || file_name == "costpilot.bundle.yml" {
+        Ok(FileType::Bundle)
+    } else if file_name == "costpilot.yaml"
         || file_name == "costpilot.yml"
         || file_name == ".costpilot.yaml"
     {
@@ -206,7 +313,7 @@ fn detect_file_type(path: &Path) -> ValidationResult<FileType> {
         Ok(FileType::Policy)
     } else {
         Err(Box::new(ValidationError::new(format!(
-            "Could not detect file type for: {}. Supported: costpilot.yaml, baselines.json, slo.yaml, *.yaml (policies)",
+            "Could not detect file type for: {}. Supported: costpilot.yaml, costpilot.bundle.yaml, baselines.json, slo.yaml, *.yaml (policies)",
             file_name
         ))))
     }
@@ -239,6 +346,10 @@ mod tests {
             detect_file_type(&PathBuf::from("my-policy.yaml")).unwrap(),
             FileType::Policy
         );
+        assert_eq!(
+            detect_file_type(&PathBuf::from("costpilot.bundle.yaml")).unwrap(),
+            FileType::Bundle
+        );
     }
 
     #[test]
@@ -5,7 +5,7 @@
 use crate::engines::policy::parser::PolicyRule;
 use crate::engines::policy::PolicyExemption;
 use crate::validation::error::{ValidationError, ValidationResult, ValidationWarning};
-use crate::validation::{FileType, ValidationReport};
+use crate::validation::{FileType, SourceMap, ValidationReport};
 // use crate::engines::policy::Exemption; // TODO: Define Exemption type
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -44,17 +44,29 @@ impl PolicyValidator {
             }
         };
 
-        // Parse YAML
-        let policy: Policy = match serde_yaml::from_str(&content) {
+        Self::validate_str(path, &content)
+    }
+
+    /// Validate policy content, reporting problems against `path`
+    pub fn validate_str(
+        path: impl AsRef<Path>,
+        content: &str,
+    ) -> ValidationResult<ValidationReport> {
+        let mut report = ValidationReport::new(path, FileType::Policy);
+
+        // Parse YAML, keeping field positions for diagnostics
+        let source_map = SourceMap::new(content);
+        let policy: Policy = match serde_yaml::from_str(content) {
             Ok(p) => p,
             Err(e) => {
-                report.add_error(ValidationError::from(e));
+                report.add_error(source_map.annotate(ValidationError::from(e)));
                 return Ok(report);
             }
         };
 
         // Validate policy
         Self::validate_policy(&policy, &mut report);
+        source_map.annotate_report(&mut report);
 
         Ok(report)
     }
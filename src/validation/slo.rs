@@ -28,8 +28,18 @@ impl SloValidator {
             }
         };
 
+        Self::validate_str(path, &content)
+    }
+
+    /// Validate SLO content, reporting problems against `path`
+    pub fn validate_str(
+        path: impl AsRef<Path>,
+        content: &str,
+    ) -> ValidationResult<ValidationReport> {
+        let mut report = ValidationReport::new(path, FileType::Slo);
+
         // Parse YAML - expecting a map of SLO name -> Slo
-        let slos: std::collections::HashMap<String, Slo> = match serde_yaml::from_str(&content) {
+        let slos: std::collections::HashMap<String, Slo> = match serde_yaml::from_str(content) {
             Ok(s) => s,
             Err(e) => {
                 report.add_error(ValidationError::from(e));
@@ -0,0 +1,223 @@
+// Source positions for validated YAML files
+//
+// Validators work on deserialized structs and report problems by field path
+// (`rules[0].conditions[1].value`). The source map walks the YAML event stream
+// once, recording where every mapping key and sequence item starts, so those
+// paths can be turned back into line/column positions and a source snippet.
+
+use crate::validation::error::ValidationError;
+use crate::validation::ValidationReport;
+use std::collections::HashMap;
+use yaml_rust2::parser::{MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::Marker;
+use yaml_rust2::Event;
+
+/// 1-based position of a node in the source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Field paths of a YAML document mapped to their source positions
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    lines: Vec<String>,
+    locations: HashMap<String, Location>,
+}
+
+impl SourceMap {
+    /// Index `content`; positions are left empty if it does not parse as YAML
+    pub fn new(content: &str) -> Self {
+        let mut builder = PathRecorder::default();
+        let locations = match Parser::new_from_str(content).load(&mut builder, false) {
+            Ok(()) => builder.locations,
+            Err(_) => HashMap::new(),
+        };
+
+        Self {
+            lines: content.lines().map(str::to_string).collect(),
+            locations,
+        }
+    }
+
+    /// Position of `field`, falling back to its closest recorded ancestor
+    ///
+    /// Missing fields are reported at the mapping that should contain them.
+    pub fn locate(&self, field: &str) -> Option<Location> {
+        let mut path = field;
+        loop {
+            if path.is_empty() {
+                return None;
+            }
+            if let Some(location) = self.locations.get(path) {
+                return Some(*location);
+            }
+            path = match path.rfind(['.', '[']) {
+                Some(idx) => &path[..idx],
+                None => "",
+            };
+        }
+    }
+
+    /// Source text of a 1-based line
+    pub fn line(&self, line: usize) -> Option<&str> {
+        line.checked_sub(1)
+            .and_then(|idx| self.lines.get(idx))
+            .map(String::as_str)
+    }
+
+    /// Attach the position and snippet of the error's field, if not already set
+    pub fn annotate(&self, mut error: ValidationError) -> ValidationError {
+        if error.line.is_none() {
+            if let Some(location) = error.field.as_deref().and_then(|f| self.locate(f)) {
+                error.line = Some(location.line);
+                error.column = Some(location.column);
+            }
+        }
+        if error.snippet.is_none() {
+            error.snippet = error
+                .line
+                .and_then(|line| self.line(line))
+                .map(|text| text.trim_end().to_string());
+        }
+        error
+    }
+
+    /// Annotate every error in `report`
+    pub fn annotate_report(&self, report: &mut ValidationReport) {
+        let errors = std::mem::take(&mut report.errors);
+        report.errors = errors.into_iter().map(|e| self.annotate(e)).collect();
+    }
+}
+
+enum Frame {
+    Mapping { path: String, key: Option<String> },
+    Sequence { path: String, index: usize },
+}
+
+#[derive(Default)]
+struct PathRecorder {
+    stack: Vec<Frame>,
+    locations: HashMap<String, Location>,
+}
+
+impl PathRecorder {
+    /// Path of the node starting at `mark`, recording it when it names a field
+    ///
+    /// Mapping keys record the position of the key itself; their values take
+    /// the key's path without recording again.
+    fn enter(&mut self, mark: Marker, scalar: Option<&str>) -> String {
+        let location = Location {
+            line: mark.line(),
+            column: mark.col() + 1,
+        };
+
+        match self.stack.last_mut() {
+            None => String::new(),
+            Some(Frame::Sequence { path, index }) => {
+                let child = format!("{}[{}]", path, index);
+                *index += 1;
+                self.locations.entry(child.clone()).or_insert(location);
+                child
+            }
+            Some(Frame::Mapping { path, key }) => match key.take() {
+                Some(key) => join(path, &key),
+                None => {
+                    // Complex (non-scalar) keys are not addressable by field path
+                    let name = scalar.unwrap_or_default().to_string();
+                    let child = join(path, &name);
+                    *key = Some(name);
+                    self.locations.entry(child.clone()).or_insert(location);
+                    child
+                }
+            },
+        }
+    }
+}
+
+impl MarkedEventReceiver for PathRecorder {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        match ev {
+            Event::Scalar(value, ..) => {
+                self.enter(mark, Some(&value));
+            }
+            Event::Alias(_) => {
+                self.enter(mark, None);
+            }
+            Event::MappingStart(..) => {
+                let path = self.enter(mark, None);
+                self.stack.push(Frame::Mapping { path, key: None });
+            }
+            Event::SequenceStart(..) => {
+                let path = self.enter(mark, None);
+                self.stack.push(Frame::Sequence { path, index: 0 });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn join(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "\
+metadata:
+  name: budgets
+rules:
+  - name: nat
+    conditions:
+      - field: monthly_cost
+        operator: greater_than
+        value: high
+";
+
+    #[test]
+    fn test_locates_nested_fields() {
+        let map = SourceMap::new(POLICY);
+        assert_eq!(
+            map.locate("metadata.name"),
+            Some(Location { line: 2, column: 3 })
+        );
+        assert_eq!(
+            map.locate("rules[0].conditions[0].value"),
+            Some(Location { line: 8, column: 9 })
+        );
+        assert_eq!(map.locate("rules[0].conditions").unwrap().line, 5);
+    }
+
+    #[test]
+    fn test_missing_field_falls_back_to_parent() {
+        let map = SourceMap::new(POLICY);
+        assert_eq!(map.locate("rules[0].severity").unwrap().line, 4);
+        assert_eq!(map.locate("exemptions"), None);
+    }
+
+    #[test]
+    fn test_annotate_attaches_position_and_snippet() {
+        let map = SourceMap::new(POLICY);
+        let error = map.annotate(
+            ValidationError::new("numeric value required")
+                .with_field("rules[0].conditions[0].value"),
+        );
+        assert_eq!(error.line, Some(8));
+        assert_eq!(error.column, Some(9));
+        assert_eq!(error.snippet.as_deref(), Some("        value: high"));
+
+        // Positions reported by the parser are kept, only the snippet is added
+        let error = map.annotate(ValidationError::new("bad").with_line(1).with_column(1));
+        assert_eq!(error.snippet.as_deref(), Some("metadata:"));
+    }
+}
@@ -0,0 +1,137 @@
+// Browser entry point: analyze a pasted Terraform plan entirely client-side
+//
+// Runs the free-edition detection, prediction and single-level mapping
+// engines in memory. Nothing here touches the filesystem, the clock or the
+// network, so the same code runs under wasm32-unknown-unknown in a browser
+// tab. `std::time::Instant` panics there, which is why this drives the
+// engines directly instead of going through `ScanPipeline`.
+
+use super::runtime::{validate_input_size, validate_json_depth, SandboxLimits, ValidationResult};
+use crate::engines::detection::DetectionEngine;
+use crate::engines::mapping::{DependencyGraph, GraphBuilder, GraphConfig, MermaidGenerator};
+use crate::engines::pipeline::ResourceArena;
+use crate::engines::prediction::PredictionEngine;
+use crate::engines::shared::error_model::CostPilotError;
+use crate::engines::shared::models::{CostEstimate, Detection, ResourceChange};
+use serde::Serialize;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+/// Everything the demo page renders for one plan
+#[derive(Debug, Serialize)]
+pub struct BrowserAnalysis {
+    pub total_monthly: f64,
+    pub changes: Vec<ResourceChange>,
+    pub estimates: Vec<CostEstimate>,
+    pub detections: Vec<Detection>,
+    pub graph: DependencyGraph,
+    /// Mermaid source for `graph`
+    pub mermaid: String,
+}
+
+/// Analyze Terraform plan JSON (`terraform show -json`) within the
+/// default sandbox limits
+pub fn analyze_plan(plan_json: &str) -> Result<BrowserAnalysis, CostPilotError> {
+    let limits = SandboxLimits::default();
+    for check in [
+        validate_input_size(plan_json.as_bytes(), &limits),
+        validate_json_depth(plan_json, &limits),
+    ] {
+        match check {
+            ValidationResult::Ok => {}
+            ValidationResult::ExceedsFileSize { size, limit } => {
+                return Err(CostPilotError::validation_error(format!(
+                    "Plan is {} bytes; the browser limit is {} bytes",
+                    size, limit
+                )))
+            }
+            ValidationResult::ExceedsStackDepth { depth, limit } => {
+                return Err(CostPilotError::validation_error(format!(
+                    "Plan nests {} levels deep; the limit is {}",
+                    depth, limit
+                )))
+            }
+            ValidationResult::InvalidJson { error } => {
+                return Err(CostPilotError::parse_error(format!(
+                    "Plan is not valid JSON: {}",
+                    error
+                )))
+            }
+        }
+    }
+
+    let detection = DetectionEngine::new();
+    let changes = detection.detect_from_terraform_json(plan_json)?;
+    let estimates = PredictionEngine::new()?.predict(&changes)?;
+    let arena = ResourceArena::new(changes, estimates);
+    let detections = detection.analyze_changes(arena.changes(), &arena.cost_tuples())?;
+
+    // Free edition mapping: direct dependencies only
+    let graph = GraphBuilder::with_config(GraphConfig {
+        max_depth: Some(1),
+        ..GraphConfig::default()
+    })
+    .build_graph(arena.changes())?;
+    let mermaid = MermaidGenerator::new().generate(&graph)?;
+
+    Ok(BrowserAnalysis {
+        total_monthly: arena.total_monthly_cost(),
+        changes: arena.changes().to_vec(),
+        estimates: arena.estimates().to_vec(),
+        detections,
+        graph,
+        mermaid,
+    })
+}
+
+/// `analyzePlan(planJson)`: the analysis as a JSON string; throws an
+/// `Error` whose message leads with the CP code
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen(js_name = analyzePlan)]
+pub fn analyze_plan_js(plan_json: &str) -> Result<String, JsError> {
+    let analysis = analyze_plan(plan_json).map_err(|e| JsError::new(&e.to_string()))?;
+    serde_json::to_string(&analysis).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// `version()`: CostPilot version of this build
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn version() -> String {
+    crate::VERSION.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = r#"{
+        "format_version": "1.0",
+        "resource_changes": [
+            {"address": "aws_instance.web", "type": "aws_instance", "name": "web",
+             "change": {"actions": ["create"], "before": null,
+                        "after": {"instance_type": "m5.xlarge"}}},
+            {"address": "aws_nat_gateway.nat", "type": "aws_nat_gateway", "name": "nat",
+             "change": {"actions": ["create"], "before": null, "after": {}}}
+        ]
+    }"#;
+
+    #[test]
+    fn test_analyze_plan() {
+        let analysis = analyze_plan(PLAN).unwrap();
+        assert_eq!(analysis.changes.len(), 2);
+        assert_eq!(analysis.estimates.len(), 2);
+        assert!(analysis.total_monthly > 0.0);
+        assert!(analysis.mermaid.starts_with("flowchart"));
+    }
+
+    #[test]
+    fn test_analyze_plan_rejects_bad_input() {
+        let err = analyze_plan("{not json").unwrap_err();
+        assert_eq!(err.code(), "CP1020");
+
+        let deep = format!("{}1{}", "[".repeat(64), "]".repeat(64));
+        let err = analyze_plan(&deep).unwrap_err();
+        assert!(err.message.contains("levels deep"), "{}", err.message);
+    }
+}
@@ -1,7 +1,9 @@
 // WASM module exports
 
+pub mod browser;
 pub mod runtime;
 
+pub use browser::{analyze_plan, BrowserAnalysis};
 pub use runtime::{
     validate_input_size, validate_json_depth, EngineBudget, MemoryTracker, SandboxLimits,
     ValidationResult,
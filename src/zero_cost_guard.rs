@@ -1,6 +1,17 @@
 // Zero-cost guard module for enforcing zero_cost_policy invariants
+//
+// Besides keeping CostPilot itself from touching real infrastructure, the
+// guard gates planned resources per environment with the `zero_cost` section
+// of costpilot.yaml: deny lists, allow lists, resources assumed to stay in the
+// free tier, and an optional monthly cost cap for everything else.
 
+use crate::engines::grouping::{normalize_environment, EnvironmentResolver};
 use crate::engines::shared::error_model::{CostPilotError, ErrorCategory};
+use crate::engines::shared::models::{ChangeAction, CostEstimate, ResourceChange};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::process::Command;
 
 /// Zero-cost policy violations
@@ -15,13 +26,372 @@ pub enum ZeroCostViolation {
     ForbiddenCommand(String),
 }
 
+/// Resource rules per environment (`zero_cost` in costpilot.yaml)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ZeroCostConfig {
+    /// Environment of resources whose tags and address do not name one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_environment: Option<String>,
+
+    /// Rules keyed by environment; `dev` and `development` are the same
+    #[serde(default)]
+    pub environments: BTreeMap<String, EnvironmentRules>,
+}
+
+/// What an environment may create
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnvironmentRules {
+    /// When non-empty, only these (and `free_tier`) resources may be created
+    #[serde(default)]
+    pub allow: Vec<ResourcePattern>,
+
+    /// Resources that must not be created or changed
+    #[serde(default)]
+    pub deny: Vec<ResourcePattern>,
+
+    /// Resources assumed to stay within the free tier: always allowed and
+    /// excluded from `max_monthly_cost`
+    #[serde(default)]
+    pub free_tier: Vec<ResourcePattern>,
+
+    /// Cap on the estimated monthly cost of the environment's other resources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_monthly_cost: Option<f64>,
+
+    /// `block` fails the scan; `warn` only reports
+    #[serde(default)]
+    pub enforcement: GuardEnforcement,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GuardEnforcement {
+    #[default]
+    Block,
+    Warn,
+}
+
+/// A resource type (`aws_nat_gateway`, `aws_*`), optionally narrowed by
+/// attribute values: `{ type: aws_instance, instance_type: [t3.micro] }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ResourcePattern {
+    Type(String),
+    Match {
+        #[serde(rename = "type")]
+        resource_type: String,
+        /// Attribute name to an accepted value or list of values
+        #[serde(flatten)]
+        attributes: BTreeMap<String, Value>,
+    },
+}
+
+impl ResourcePattern {
+    fn resource_type(&self) -> &str {
+        match self {
+            ResourcePattern::Type(resource_type) => resource_type,
+            ResourcePattern::Match { resource_type, .. } => resource_type,
+        }
+    }
+
+    /// Whether `change` is of this type and has every listed attribute value
+    pub fn matches(&self, change: &ResourceChange) -> bool {
+        if !type_matches(self.resource_type(), &change.resource_type) {
+            return false;
+        }
+        let ResourcePattern::Match { attributes, .. } = self else {
+            return true;
+        };
+        let config = change.new_config.as_ref().or(change.config.as_ref());
+        attributes.iter().all(|(name, accepted)| {
+            let Some(actual) = config.and_then(|c| c.get(name)).map(value_text) else {
+                return false;
+            };
+            match accepted {
+                Value::Array(values) => values.iter().any(|v| value_text(v) == actual),
+                value => value_text(value) == actual,
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for ResourcePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourcePattern::Type(resource_type) => write!(f, "{}", resource_type),
+            ResourcePattern::Match {
+                resource_type,
+                attributes,
+            } => {
+                let attributes: Vec<String> = attributes
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value_text(value)))
+                    .collect();
+                write!(f, "{}[{}]", resource_type, attributes.join(","))
+            }
+        }
+    }
+}
+
+/// `*` matches any run of characters
+fn type_matches(pattern: &str, resource_type: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == resource_type,
+        Some((prefix, rest)) => {
+            let Some(remainder) = resource_type.strip_prefix(prefix) else {
+                return false;
+            };
+            if rest.is_empty() {
+                return true;
+            }
+            (0..=remainder.len())
+                .filter(|i| remainder.is_char_boundary(*i))
+                .any(|i| type_matches(rest, &remainder[i..]))
+        }
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Why a planned resource failed the environment's rules
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceRule {
+    Denied,
+    NotAllowed,
+    OverBudget,
+}
+
+impl ResourceRule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResourceRule::Denied => "denied",
+            ResourceRule::NotAllowed => "not_allowed",
+            ResourceRule::OverBudget => "over_budget",
+        }
+    }
+}
+
+/// A planned resource (or environment total) that breaks its environment's rules
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResourceGateViolation {
+    pub environment: String,
+    /// Resource address, or `environment:<name>` for budget violations
+    pub resource_id: String,
+    pub rule: ResourceRule,
+    pub message: String,
+    /// `false` when the environment's enforcement is `warn`
+    pub blocking: bool,
+}
+
+impl ZeroCostConfig {
+    /// Load `zero_cost` from a costpilot.yaml file, applying `profile`
+    pub fn load(path: &Path, profile: Option<&str>) -> Result<Self, CostPilotError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CostPilotError::io_error(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::from_yaml_with_profile(&content, profile)
+    }
+
+    /// Parse `zero_cost` from costpilot.yaml content
+    pub fn from_yaml(content: &str) -> Result<Self, CostPilotError> {
+        Self::from_yaml_with_profile(content, None)
+    }
+
+    /// Parse `zero_cost` with a profile overlaid on the base
+    pub fn from_yaml_with_profile(
+        content: &str,
+        profile: Option<&str>,
+    ) -> Result<Self, CostPilotError> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| CostPilotError::parse_error(format!("Invalid costpilot.yaml: {}", e)))?;
+        let root = crate::config::apply_profile(root, profile)
+            .map_err(|e| CostPilotError::config_error(e.to_string()))?;
+
+        let config: Self = match root.get("zero_cost").cloned() {
+            Some(value) => serde_yaml::from_value(value).map_err(|e| {
+                CostPilotError::parse_error(format!("Invalid zero_cost section: {}", e))
+            })?,
+            None => Self::default(),
+        };
+
+        if let Some(problem) = config.validate().first() {
+            return Err(CostPilotError::validation_error(problem.clone())
+                .with_hint("Check zero_cost.environments in costpilot.yaml"));
+        }
+
+        Ok(config)
+    }
+
+    /// Describe invalid rules; empty if every environment is usable
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (environment, rules) in &self.environments {
+            let lists = [
+                ("allow", &rules.allow),
+                ("deny", &rules.deny),
+                ("free_tier", &rules.free_tier),
+            ];
+            for (list, patterns) in lists {
+                if patterns.iter().any(|p| p.resource_type().is_empty()) {
+                    problems.push(format!(
+                        "environment '{}': {} entries need a resource type",
+                        environment, list
+                    ));
+                }
+            }
+            for pattern in &rules.deny {
+                if rules.allow.contains(pattern) || rules.free_tier.contains(pattern) {
+                    problems.push(format!(
+                        "environment '{}': {} is both denied and allowed",
+                        environment, pattern
+                    ));
+                }
+            }
+            if rules
+                .max_monthly_cost
+                .is_some_and(|max| !max.is_finite() || max < 0.0)
+            {
+                problems.push(format!(
+                    "environment '{}': max_monthly_cost must be zero or positive",
+                    environment
+                ));
+            }
+        }
+        problems
+    }
+
+    /// Rules for `environment`, matching configured names after normalization
+    pub fn rules_for(&self, environment: &str) -> Option<&EnvironmentRules> {
+        let environment = normalize_environment(environment);
+        self.environments
+            .iter()
+            .find(|(name, _)| normalize_environment(name) == environment)
+            .map(|(_, rules)| rules)
+    }
+}
+
 /// Zero-cost guard that enforces no real cloud costs or network calls
-pub struct ZeroCostGuard;
+#[derive(Debug, Clone, Default)]
+pub struct ZeroCostGuard {
+    config: ZeroCostConfig,
+    resolver: EnvironmentResolver,
+}
 
 impl ZeroCostGuard {
     /// Create a new zero-cost guard
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Gate planned resources with per-environment rules
+    pub fn with_config(mut self, config: ZeroCostConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Infer resource environments with the project's `environment_mapping`
+    pub fn with_environment_resolver(mut self, resolver: EnvironmentResolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Check planned resources against their environment's rules
+    ///
+    /// A resource's environment comes from the environment resolver (tags or
+    /// address by default), falling back to `default_environment`; resources
+    /// in environments without rules and deletions are not checked.
+    pub fn check_changes(
+        &self,
+        changes: &[ResourceChange],
+        estimates: &[CostEstimate],
+    ) -> Vec<ResourceGateViolation> {
+        let mut violations = Vec::new();
+        // environment -> (rules, billable monthly cost)
+        let mut totals: BTreeMap<String, (&EnvironmentRules, f64)> = BTreeMap::new();
+
+        for change in changes {
+            if matches!(change.action, ChangeAction::Delete | ChangeAction::NoOp) {
+                continue;
+            }
+            let mut environment = self
+                .resolver
+                .resolve(&change.resource_id, &change.tags)
+                .environment;
+            if environment == "unknown" {
+                match &self.config.default_environment {
+                    Some(default) => environment = normalize_environment(default),
+                    None => continue,
+                }
+            }
+            let Some(rules) = self.config.rules_for(&environment) else {
+                continue;
+            };
+            let blocking = rules.enforcement == GuardEnforcement::Block;
+            let violation = |rule, message| ResourceGateViolation {
+                environment: environment.clone(),
+                resource_id: change.resource_id.clone(),
+                rule,
+                message,
+                blocking,
+            };
+
+            if let Some(pattern) = rules.deny.iter().find(|p| p.matches(change)) {
+                violations.push(violation(
+                    ResourceRule::Denied,
+                    format!(
+                        "{} is denied in {} ({})",
+                        change.resource_type, environment, pattern
+                    ),
+                ));
+                continue;
+            }
+            if rules.free_tier.iter().any(|p| p.matches(change)) {
+                continue;
+            }
+            if !rules.allow.is_empty() && !rules.allow.iter().any(|p| p.matches(change)) {
+                violations.push(violation(
+                    ResourceRule::NotAllowed,
+                    format!(
+                        "{} is not on the {} allow list",
+                        change.resource_type, environment
+                    ),
+                ));
+                continue;
+            }
+
+            let cost = estimates
+                .iter()
+                .find(|e| e.resource_id == change.resource_id)
+                .map(|e| e.monthly_cost)
+                .or(change.monthly_cost)
+                .unwrap_or(0.0);
+            totals.entry(environment.clone()).or_insert((rules, 0.0)).1 += cost;
+        }
+
+        for (environment, (rules, cost)) in totals {
+            let Some(max) = rules.max_monthly_cost else {
+                continue;
+            };
+            if cost > max {
+                violations.push(ResourceGateViolation {
+                    resource_id: format!("environment:{}", environment),
+                    rule: ResourceRule::OverBudget,
+                    message: format!(
+                        "{} resources outside the free tier cost ${:.2}/month (limit ${:.2})",
+                        environment, cost, max
+                    ),
+                    blocking: rules.enforcement == GuardEnforcement::Block,
+                    environment,
+                });
+            }
+        }
+        violations
     }
 
     /// Enforce zero-cost policy before executing any command
@@ -253,12 +623,6 @@ impl ZeroCostGuard {
     }
 }
 
-impl Default for ZeroCostGuard {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,4 +653,159 @@ mod tests {
             ))
         );
     }
+
+    const RULES: &str = r#"
+zero_cost:
+  default_environment: dev
+  environments:
+    development:
+      deny: [aws_nat_gateway, "aws_eks_*"]
+      free_tier:
+        - type: aws_instance
+          instance_type: [t2.micro, t3.micro]
+        - aws_s3_bucket
+      max_monthly_cost: 20
+    staging:
+      allow: [aws_instance, aws_lb]
+      enforcement: warn
+"#;
+
+    fn change(
+        id: &str,
+        resource_type: &str,
+        config: serde_json::Value,
+        cost: f64,
+    ) -> ResourceChange {
+        ResourceChange::builder()
+            .resource_id(id)
+            .resource_type(resource_type)
+            .action(ChangeAction::Create)
+            .new_config(config)
+            .monthly_cost(cost)
+            .build()
+    }
+
+    #[test]
+    fn test_environment_allow_deny_and_free_tier() {
+        let guard = ZeroCostGuard::new().with_config(ZeroCostConfig::from_yaml(RULES).unwrap());
+        let changes = vec![
+            change(
+                "aws_instance.web",
+                "aws_instance",
+                serde_json::json!({"instance_type": "t3.micro"}),
+                7.5,
+            ),
+            change(
+                "aws_nat_gateway.main",
+                "aws_nat_gateway",
+                serde_json::json!({}),
+                32.85,
+            ),
+            change(
+                "aws_eks_cluster.dev",
+                "aws_eks_cluster",
+                serde_json::json!({}),
+                73.0,
+            ),
+            change(
+                "aws_db_instance.db",
+                "aws_db_instance",
+                serde_json::json!({}),
+                25.0,
+            ),
+            change(
+                "module.staging.aws_sqs_queue.q",
+                "aws_sqs_queue",
+                serde_json::json!({}),
+                1.0,
+            ),
+            change(
+                "module.prod.aws_nat_gateway.main",
+                "aws_nat_gateway",
+                serde_json::json!({}),
+                32.85,
+            ),
+        ];
+
+        let violations = guard.check_changes(&changes, &[]);
+        let summary: Vec<(&str, &str, &ResourceRule, bool)> = violations
+            .iter()
+            .map(|v| {
+                (
+                    v.environment.as_str(),
+                    v.resource_id.as_str(),
+                    &v.rule,
+                    v.blocking,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "development",
+                    "aws_nat_gateway.main",
+                    &ResourceRule::Denied,
+                    true
+                ),
+                (
+                    "development",
+                    "aws_eks_cluster.dev",
+                    &ResourceRule::Denied,
+                    true
+                ),
+                (
+                    "staging",
+                    "module.staging.aws_sqs_queue.q",
+                    &ResourceRule::NotAllowed,
+                    false
+                ),
+                (
+                    "development",
+                    "environment:development",
+                    &ResourceRule::OverBudget,
+                    true
+                ),
+            ]
+        );
+        // Only the db instance counts against the cap; the t3.micro is free tier
+        assert!(violations[3].message.contains("$25.00"));
+    }
+
+    #[test]
+    fn test_attribute_patterns_and_validation() {
+        let pattern: ResourcePattern =
+            serde_yaml::from_str("{type: aws_instance, instance_type: [t2.micro, t3.micro]}")
+                .unwrap();
+        let large = change(
+            "aws_instance.a",
+            "aws_instance",
+            serde_json::json!({"instance_type": "m5.large"}),
+            0.0,
+        );
+        let micro = change(
+            "aws_instance.b",
+            "aws_instance",
+            serde_json::json!({"instance_type": "t3.micro"}),
+            0.0,
+        );
+        assert!(!pattern.matches(&large));
+        assert!(pattern.matches(&micro));
+        assert_eq!(
+            pattern.to_string(),
+            "aws_instance[instance_type=[\"t2.micro\",\"t3.micro\"]]"
+        );
+        assert!(type_matches("aws_*_gateway", "aws_nat_gateway"));
+        assert!(!type_matches("aws_*_gateway", "aws_nat_gateway_route"));
+
+        let conflicting =
+            "zero_cost:\n  environments:\n    dev:\n      allow: [aws_lb]\n      deny: [aws_lb]\n";
+        assert!(ZeroCostConfig::from_yaml(conflicting).is_err());
+        let negative = "zero_cost:\n  environments:\n    dev:\n      max_monthly_cost: -1\n";
+        assert!(ZeroCostConfig::from_yaml(negative).is_err());
+        assert_eq!(
+            ZeroCostConfig::from_yaml("scan: {}\n").unwrap(),
+            ZeroCostConfig::default()
+        );
+    }
 }
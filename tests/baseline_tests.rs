@@ -75,6 +75,7 @@ fn test_baselines_manager_loads_config() {
         modules: HashMap::new(),
         services: HashMap::new(),
         metadata: None,
+        tolerance: None,
     };
 
     let _manager = BaselinesManager::from_config(config);
@@ -179,6 +180,7 @@ proptest! {
             modules,
             services: HashMap::new(),
             metadata: None,
+            tolerance: None,
         };
 
         let manager = BaselinesManager::from_config(config.clone());
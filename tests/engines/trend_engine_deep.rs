@@ -53,6 +53,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -78,6 +79,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             },
             CostEstimate {
                 resource_id: "module1.aws.rds.db1".to_string(),
@@ -89,6 +91,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             },
             CostEstimate {
                 resource_id: "module2.aws.s3.bucket1".to_string(),
@@ -100,6 +103,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "storage".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -128,6 +132,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -158,6 +163,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -202,6 +208,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -229,6 +236,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -243,6 +251,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -272,6 +281,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -286,6 +296,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             },
             CostEstimate {
                 resource_id: "module2.new.resource".to_string(),
@@ -297,6 +308,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -326,6 +338,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -340,6 +353,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             },
             CostEstimate {
                 resource_id: "module2.new.resource".to_string(),
@@ -351,6 +365,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -380,6 +395,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -417,6 +433,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -444,6 +461,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             });
         }
 
@@ -469,6 +487,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "free_tier".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -493,6 +512,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             },
             CostEstimate {
                 resource_id: "cheap.resource".to_string(),
@@ -504,6 +524,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -528,6 +549,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -811,6 +833,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -835,6 +858,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -862,6 +886,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -892,6 +917,7 @@ mod trend_engine_deep_tests {
                     pricing_model: "on_demand".to_string(),
                     confidence: 0.95,
                     breakdown: HashMap::new(),
+                    provenance: None,
                 }
             ];
 
@@ -928,6 +954,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -969,6 +996,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "free".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -983,6 +1011,7 @@ mod trend_engine_deep_tests {
                 pricing_model: "on_demand".to_string(),
                 confidence: 0.95,
                 breakdown: HashMap::new(),
+                provenance: None,
             }
         ];
 
@@ -1000,6 +1029,7 @@ mod trend_engine_deep_tests {
 
     fn create_test_snapshot(cost: f64) -> CostSnapshot {
         CostSnapshot {
+            schema_version: costpilot::engines::shared::json_schema::OUTPUT_SCHEMA_VERSION.to_string(),
             id: format!("test_{}", cost),
             timestamp: "2024-01-01T00:00:00Z".to_string(),
             commit_hash: None,
@@ -1010,6 +1040,7 @@ mod trend_engine_deep_tests {
             regressions: vec![],
             slo_violations: vec![],
             metadata: None,
+            actuals: None,
         }
     }
 
@@ -65,6 +65,9 @@ fn golden_simple_dependency_graph() {
             has_cycles: false,
             cycles: vec![],
             total_cost: Some(0.0),
+            collapsed_count: 0,
+            stacks: Vec::new(),
+            cross_stack_references: Vec::new(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
         },
@@ -146,6 +149,9 @@ fn golden_complex_web_app_graph() {
             has_cycles: false,
             cycles: vec![],
             total_cost: Some(119.13),
+            collapsed_count: 0,
+            stacks: Vec::new(),
+            cross_stack_references: Vec::new(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             timestamp: "2024-01-01T00:00:00Z".to_string(),
         },
@@ -209,6 +215,9 @@ fn golden_database_tier_graph() {
             has_cycles: false,
             cycles: vec![],
             total_cost: Some(87.60),
+            collapsed_count: 0,
+            stacks: Vec::new(),
+            cross_stack_references: Vec::new(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             timestamp: "2024-01-01T00:00:00Z".to_string(),
         },
@@ -230,6 +239,9 @@ fn golden_empty_graph() {
             has_cycles: false,
             cycles: vec![],
             total_cost: Some(0.0),
+            collapsed_count: 0,
+            stacks: Vec::new(),
+            cross_stack_references: Vec::new(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             timestamp: "2024-01-01T00:00:00Z".to_string(),
         },
@@ -270,6 +282,9 @@ fn golden_isolated_resources() {
             has_cycles: false,
             cycles: vec![],
             total_cost: Some(45.0),
+            collapsed_count: 0,
+            stacks: Vec::new(),
+            cross_stack_references: Vec::new(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             timestamp: "2024-01-01T00:00:00Z".to_string(),
         },
@@ -46,6 +46,7 @@ pub fn free() -> EditionContext {
         },
         pro: None,
         paths: costpilot::edition::EditionPaths::default(),
+        org_policy: None,
     }
 }
 
@@ -72,6 +73,7 @@ pub fn premium() -> EditionContext {
         },
         pro: Some(stub_handle),
         paths: costpilot::edition::EditionPaths::default(),
+        org_policy: None,
     }
 }
 
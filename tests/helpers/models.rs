@@ -17,6 +17,7 @@ pub fn make_test_cost_estimate(resource_id: &str, monthly_cost: f64) -> CostEsti
         confidence_score: 0.95,
         heuristic_reference: Some("test-default".to_string()),
         cold_start_inference: false,
+        provenance: None,
     }
 }
 
@@ -34,6 +35,7 @@ pub fn make_test_cost_estimate_with_confidence(
         confidence_score: confidence,
         heuristic_reference: Some("test-default".to_string()),
         cold_start_inference: false,
+        provenance: None,
     }
 }
 
@@ -170,6 +172,7 @@ pub fn make_cost_estimate(monthly_cost: f64) -> CostEstimate {
         confidence_score: 0.95,
         heuristic_reference: Some("test-default".to_string()),
         cold_start_inference: false,
+        provenance: None,
     }
 }
 
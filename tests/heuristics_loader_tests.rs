@@ -365,6 +365,7 @@ fn test_validate_missing_version() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -388,6 +389,7 @@ fn test_validate_invalid_version_major() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -411,6 +413,7 @@ fn test_validate_invalid_version_minor() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -434,6 +437,7 @@ fn test_validate_invalid_version_patch() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -457,6 +461,7 @@ fn test_validate_empty_ec2_instances() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -490,6 +495,7 @@ fn test_validate_invalid_ec2_hourly_cost() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -524,6 +530,7 @@ fn test_validate_invalid_lambda_price() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -558,6 +565,7 @@ fn test_validate_empty_rds_mysql() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -687,6 +695,7 @@ fn test_get_statistics() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let stats = loader.get_statistics(&heuristics);
@@ -1015,6 +1024,7 @@ fn test_validate_extremely_high_cost() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -1048,6 +1058,7 @@ fn test_validate_zero_cost() {
         networking: Default::default(),
         prediction_intervals: Default::default(),
         cold_start_defaults: Default::default(),
+        monitoring: Default::default(),
     };
 
     let result = loader.validate(&invalid_heuristics);
@@ -66,6 +66,7 @@ fn test_policy_engine_performance() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let policy_config = costpilot::engines::policy::PolicyConfig {
@@ -120,6 +121,7 @@ fn test_full_scan_pipeline_performance() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     // Policy evaluation
@@ -1,6 +1,6 @@
 use costpilot::edition::EditionContext;
 use costpilot::engines::performance::budgets::PerformanceBudgets;
-use costpilot::engines::prediction::PredictionEngine;
+use costpilot::engines::prediction::{PredictionEngine, UsageProfile};
 use costpilot::engines::shared::models::{ChangeAction, ResourceChange};
 #[cfg(test)]
 use proptest::prelude::*;
@@ -1251,6 +1251,11 @@ fn test_predict_eks() {
     assert!(result.is_ok());
     let cost = result.unwrap();
     assert!(cost.monthly_cost >= 0.0);
+
+    // $0.10/hour control plane × 730 hours
+    assert!((cost.monthly_cost - 73.0).abs() < 1e-6);
+    let steps = cost.provenance.unwrap().steps;
+    assert_eq!(steps[0].operation, "EKS Control Plane");
 }
 
 #[test]
@@ -1933,6 +1938,228 @@ fn test_predict_total_cost() {
     assert!(total_cost.monthly >= 0.0);
 }
 
+#[test]
+fn test_ec2_tenancy_and_license_adjust_static_rate() {
+    let engine = PredictionEngine::new().unwrap();
+    let change = ResourceChange::builder()
+        .resource_type("aws_instance".to_string())
+        .resource_id("aws_instance.sql".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({
+            "instance_type": "m5.xlarge",
+            "tenancy": "dedicated",
+            "platform_details": "Windows with SQL Server Standard"
+        }))
+        .build();
+
+    let estimate = engine.predict_resource_cost(&change).unwrap();
+    // $150 × 1.1 + 4 vCPU × ($0.046 + $0.125) × 730
+    let expected = 150.0 * 1.1 + 4.0 * (0.046 + 0.125) * 730.0;
+    assert!((estimate.monthly_cost - expected).abs() < 1e-6);
+
+    let steps = estimate.provenance.unwrap().steps;
+    let operations: Vec<&str> = steps.iter().map(|s| s.operation.as_str()).collect();
+    assert_eq!(
+        operations,
+        vec![
+            "Flat Monthly Rate",
+            "EC2 Instance Cost",
+            "Planned Action",
+            "Prediction Interval"
+        ]
+    );
+    assert!(steps[1]
+        .input
+        .contains("license=windows+sql_standard, vcpus=4"));
+}
+
+#[test]
+fn test_dynamodb_billing_modes_from_usage_and_autoscaling() {
+    let on_demand = ResourceChange::builder()
+        .resource_type("aws_dynamodb_table".to_string())
+        .resource_id("aws_dynamodb_table.events".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({ "name": "events", "billing_mode": "PAY_PER_REQUEST" }))
+        .build();
+    let provisioned = ResourceChange::builder()
+        .resource_type("aws_dynamodb_table".to_string())
+        .resource_id("aws_dynamodb_table.orders".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({ "name": "orders", "read_capacity": 10, "write_capacity": 5 }))
+        .build();
+    let target = ResourceChange::builder()
+        .resource_type("aws_appautoscaling_target".to_string())
+        .resource_id("aws_appautoscaling_target.orders_read".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({
+            "resource_id": "table/orders",
+            "scalable_dimension": "dynamodb:table:ReadCapacityUnits",
+            "min_capacity": 5,
+            "max_capacity": 200
+        }))
+        .build();
+
+    // Without a profile or autoscaling the static table rate holds
+    let engine = PredictionEngine::new().unwrap();
+    let estimate = engine.predict_resource_cost(&on_demand).unwrap();
+    assert_eq!(estimate.monthly_cost, 20.0);
+
+    let engine = PredictionEngine::new()
+        .unwrap()
+        .with_usage_profile(UsageProfile::default().with(
+            "aws_dynamodb_table.events",
+            "monthly_read_request_units",
+            100_000_000.0,
+        ))
+        .with_autoscaling_targets(&[provisioned.clone(), target]);
+
+    let estimate = engine.predict_resource_cost(&on_demand).unwrap();
+    // 100M reads × $0.25/M + 1M default writes × $1.25/M + 1 GB × $0.25
+    assert!((estimate.monthly_cost - 26.5).abs() < 1e-6);
+    let steps = estimate.provenance.unwrap().steps;
+    assert_eq!(steps[1].operation, "DynamoDB Cost");
+    assert!(steps[1].reasoning.starts_with("On-demand billing"));
+
+    let estimate = engine.predict_resource_cost(&provisioned).unwrap();
+    // The interval covers 5-200 RCU
+    let read_max = 200.0 * 0.00013 * 730.0;
+    assert!(estimate.prediction_interval_high >= read_max);
+    let steps = estimate.provenance.unwrap().steps;
+    assert!(steps[1].reasoning.contains("RCU autoscaling 5-200"));
+}
+
+#[test]
+fn test_rds_multi_az_and_serverless_v2_priced_as_ranges() {
+    let instance = ResourceChange::builder()
+        .resource_type("aws_db_instance".to_string())
+        .resource_id("aws_db_instance.orders".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({
+            "engine": "postgres",
+            "instance_class": "db.t3.small",
+            "multi_az": true,
+            "storage_type": "gp3",
+            "allocated_storage": 100,
+            "max_allocated_storage": 500,
+            "iops": 12000
+        }))
+        .build();
+    let cluster = ResourceChange::builder()
+        .resource_type("aws_rds_cluster".to_string())
+        .resource_id("aws_rds_cluster.reporting".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({
+            "engine": "aurora-postgresql",
+            "serverlessv2_scaling_configuration": [{ "min_capacity": 0.5, "max_capacity": 16 }]
+        }))
+        .build();
+
+    let engine = PredictionEngine::new().unwrap();
+
+    // (db.t3.small instance hours + 100 GB × $0.115 + 9000 IOPS above the
+    // gp3 baseline × $0.02) × 2 AZs, up to 500 GB as storage autoscales
+    let estimate = engine.predict_resource_cost(&instance).unwrap();
+    let instance_monthly = 26.28;
+    let expected = (instance_monthly + 100.0 * 0.115 + 9000.0 * 0.02) * 2.0;
+    assert!((estimate.monthly_cost - expected).abs() < 1e-6);
+    let high = (instance_monthly + 500.0 * 0.115 + 9000.0 * 0.02) * 2.0;
+    assert!(estimate.prediction_interval_high >= high - 1e-6);
+    let steps = estimate.provenance.unwrap().steps;
+    assert_eq!(steps[0].operation, "RDS Instance Cost");
+    assert!(steps[0].input.contains("instance_class=db.t3.small"));
+    assert!(steps[0].input.contains("max_allocated_storage=500"));
+
+    // 0.5-16 ACU × $0.12/ACU-hour
+    let estimate = engine.predict_resource_cost(&cluster).unwrap();
+    assert!((estimate.monthly_cost - 0.5 * 0.12 * 730.0).abs() < 1e-6);
+    assert!(estimate.prediction_interval_high >= 16.0 * 0.12 * 730.0 - 1e-6);
+    assert!(!estimate.cold_start_inference);
+    let steps = estimate.provenance.unwrap().steps;
+    assert_eq!(steps[0].operation, "RDS Instance Cost");
+    assert!(steps[0].input.contains("acu=0.5-16"));
+}
+
+#[test]
+fn test_node_groups_and_fargate_priced_from_plan() {
+    let node_group = ResourceChange::builder()
+        .resource_type("aws_eks_node_group".to_string())
+        .resource_id("aws_eks_node_group.general".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({
+            "instance_types": ["m5.large"],
+            "scaling_config": [{ "desired_size": 3, "min_size": 2, "max_size": 10 }]
+        }))
+        .build();
+    let task_definition = ResourceChange::builder()
+        .resource_type("aws_ecs_task_definition".to_string())
+        .resource_id("aws_ecs_task_definition.api".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({ "family": "api", "cpu": "512", "memory": "1024" }))
+        .build();
+    let service = ResourceChange::builder()
+        .resource_type("aws_ecs_service".to_string())
+        .resource_id("aws_ecs_service.api".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({ "launch_type": "FARGATE", "desired_count": 2 }))
+        .build();
+
+    let engine = PredictionEngine::new()
+        .unwrap()
+        .with_task_definitions(&[task_definition, service.clone()]);
+
+    // Three m5.large nodes at $70.08/month, ranging over 2-10 nodes
+    let estimate = engine.predict_resource_cost(&node_group).unwrap();
+    assert!((estimate.monthly_cost - 3.0 * 70.08).abs() < 1e-6);
+    assert!(estimate.prediction_interval_low <= 2.0 * 70.08 + 1e-6);
+    assert!(estimate.prediction_interval_high >= 10.0 * 70.08 - 1e-6);
+    let steps = estimate.provenance.unwrap().steps;
+    assert_eq!(steps[0].operation, "EC2 Instance Cost");
+    assert_eq!(steps[1].operation, "EKS Node Group Cost");
+
+    // 2 tasks × (0.5 vCPU + 1 GB) billed per second
+    let estimate = engine.predict_resource_cost(&service).unwrap();
+    let expected = 2.0 * (0.5 * 0.000011244 + 0.000001235) * 730.0 * 3600.0;
+    assert!((estimate.monthly_cost - expected).abs() < 1e-6);
+    assert!(!estimate.cold_start_inference);
+    let steps = estimate.provenance.unwrap().steps;
+    assert_eq!(steps[0].operation, "Fargate Cost");
+    assert_eq!(steps[0].input, "tasks=2, vcpu=0.5, memory_gb=1");
+}
+
+#[test]
+fn test_cloudwatch_priced_from_usage_and_retention() {
+    let log_group = ResourceChange::builder()
+        .resource_type("aws_cloudwatch_log_group".to_string())
+        .resource_id("aws_cloudwatch_log_group.app".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({ "name": "/app", "retention_in_days": 30 }))
+        .build();
+    let alarm = ResourceChange::builder()
+        .resource_type("aws_cloudwatch_metric_alarm".to_string())
+        .resource_id("aws_cloudwatch_metric_alarm.cpu".to_string())
+        .action(ChangeAction::Create)
+        .new_config(json!({ "metric_name": "CPUUtilization", "period": 300 }))
+        .build();
+
+    let engine = PredictionEngine::new()
+        .unwrap()
+        .with_usage_profile(UsageProfile::default().with(
+            "aws_cloudwatch_log_group.app",
+            "monthly_ingestion_gb",
+            100.0,
+        ));
+
+    // 100 GB × $0.50 ingested, 30 days of it stored at $0.03/GB-month
+    let estimate = engine.predict_resource_cost(&log_group).unwrap();
+    let expected = 50.0 + 100.0 * 30.0 / (365.0 / 12.0) * 0.03;
+    assert!((estimate.monthly_cost - expected).abs() < 1e-6);
+    let steps = estimate.provenance.unwrap().steps;
+    assert_eq!(steps[0].operation, "CloudWatch Cost");
+
+    let estimate = engine.predict_resource_cost(&alarm).unwrap();
+    assert!((estimate.monthly_cost - 0.10).abs() < 1e-9);
+}
+
 #[test]
 fn test_explain() {
     let engine = PredictionEngine::new().unwrap();
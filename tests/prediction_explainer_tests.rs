@@ -45,6 +45,7 @@ fn test_prediction_explainer_explain_ec2() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -81,6 +82,7 @@ fn test_prediction_explainer_explain_rds() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -117,6 +119,7 @@ fn test_prediction_explainer_explain_lambda() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -152,6 +155,7 @@ fn test_prediction_explainer_explain_dynamodb() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -186,6 +190,7 @@ fn test_prediction_explainer_explain_nat_gateway() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -221,6 +226,7 @@ fn test_prediction_explainer_explain_load_balancer() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -256,6 +262,7 @@ fn test_prediction_explainer_explain_s3() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -290,6 +297,7 @@ fn test_prediction_explainer_explain_generic() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -325,6 +333,7 @@ fn test_prediction_explainer_explain_with_cold_start() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -361,6 +370,7 @@ fn test_prediction_explainer_explain_with_high_confidence() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -398,6 +408,7 @@ fn test_prediction_explainer_explain_with_low_confidence() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -442,6 +453,7 @@ fn test_prediction_explainer_zero_cost_edge_case() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -475,6 +487,7 @@ fn test_prediction_explainer_negative_cost_edge_case() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -508,6 +521,7 @@ fn test_prediction_explainer_extremely_high_cost() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -541,6 +555,7 @@ fn test_prediction_explainer_empty_resource_id_edge_case() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -575,6 +590,7 @@ fn test_prediction_explainer_extremely_long_resource_names() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -608,6 +624,7 @@ fn test_prediction_explainer_zero_confidence_edge_case() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     };
 
     let reasoning = explainer.explain(&change, &estimate);
@@ -252,6 +252,7 @@ fn test_autofix_engine_edition_enforcement() {
         breakdown: None,
         hourly: None,
         daily: None,
+        provenance: None,
     }];
 
     // Free edition should reject patch mode
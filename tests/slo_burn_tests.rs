@@ -153,7 +153,7 @@ fn test_slo_burn_critical_risk() {
         .arg("--format")
         .arg("json");
 
-    let assert = cmd.assert().failure().code(1);
+    let assert = cmd.assert().failure().code(4);
 
     // Parse JSON output
     let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();